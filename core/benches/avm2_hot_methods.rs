@@ -0,0 +1,39 @@
+//! Rough timings for AVM2 code that calls the same small methods many times, which is
+//! where pre-decoding hot method bodies and caching their slot lookups pays off.
+//!
+//! Run with `cargo bench -p ruffle_core --bench avm2_hot_methods`, on this commit and on
+//! one before the change being measured, to compare them.
+
+use ruffle_core::tag_utils::SwfMovie;
+use ruffle_core::PlayerBuilder;
+use std::time::{Duration, Instant};
+
+const FRAMES: u32 = 10;
+
+fn main() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/benches/swfs/avm2_hot_methods/avm2_hot_methods.swf"
+    );
+    let movie = SwfMovie::from_path(path, None).expect("benchmark movie should load");
+    let player = PlayerBuilder::new()
+        .with_movie(movie)
+        .with_autoplay(true)
+        .with_max_execution_duration(Duration::from_secs(600))
+        .build();
+    let mut player = player.lock().expect("player isn't locked elsewhere");
+
+    // Warm up once, so that the movie's setup and the first calls of each method
+    // aren't included.
+    player.run_frame();
+
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        player.run_frame();
+    }
+    println!(
+        "{:<24} {:>10.2?} / frame",
+        "avm2_hot_methods",
+        start.elapsed() / FRAMES
+    );
+}
@@ -0,0 +1,95 @@
+//! Rough timings for the per-pixel kernels used by `BitmapData` operations on large bitmaps.
+//!
+//! Run with `cargo bench -p ruffle_core --features rayon` to compare against the
+//! single-threaded fallback (`cargo bench -p ruffle_core`).
+
+use ruffle_core::bitmap::bitmap_data::{Color, LehmerRng};
+use ruffle_core::bitmap::parallel;
+use ruffle_core::bitmap::turbulence::Turbulence;
+use std::time::{Duration, Instant};
+
+const SIZE: usize = 4096;
+const ITERATIONS: u32 = 5;
+
+fn bench(name: &str, pixels: &mut [Color], f: impl Fn(&mut [Color])) {
+    // Warm up once, so that thread pool startup isn't included.
+    f(pixels);
+
+    let mut total = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        f(pixels);
+        total += start.elapsed();
+    }
+    println!("{name:<24} {:>10.2?} / iteration", total / ITERATIONS);
+}
+
+fn main() {
+    let mut pixels = vec![Color::argb(255, 128, 64, 32); SIZE * SIZE];
+
+    bench("color_transform", &mut pixels, |pixels| {
+        parallel::for_each_row(pixels, SIZE, 0..SIZE, |_, row| {
+            for pixel in row {
+                let color = pixel.to_un_multiplied_alpha();
+                let color = Color::argb(
+                    color.alpha(),
+                    color.red().wrapping_mul(3),
+                    color.green() / 2,
+                    color.blue().saturating_add(10),
+                );
+                *pixel = color.to_premultiplied_alpha(true);
+            }
+        });
+    });
+
+    bench("threshold", &mut pixels, |pixels| {
+        let modified = parallel::sum_rows(pixels, SIZE, 0..SIZE, |_, row| {
+            let mut modified = 0;
+            for pixel in row {
+                if u32::from(*pixel) & 0x00FF0000 > 0x00800000 {
+                    *pixel = Color::from(0xFF00FF00);
+                    modified += 1;
+                }
+            }
+            modified
+        });
+        assert!(modified as usize <= SIZE * SIZE);
+    });
+
+    bench("noise", &mut pixels, |pixels| {
+        parallel::for_each_row(pixels, SIZE, 0..SIZE, |y, row| {
+            let mut rng = LehmerRng::with_seed(1234);
+            rng.skip(y as u64 * SIZE as u64 * 4);
+            for pixel in row {
+                *pixel = Color::argb(
+                    rng.gen_range(0..255),
+                    rng.gen_range(0..255),
+                    rng.gen_range(0..255),
+                    rng.gen_range(0..255),
+                );
+            }
+        });
+    });
+
+    let turbulence = Turbulence::from_seed(1234);
+    let offsets = vec![(0.0, 0.0); 2];
+    bench("perlin_noise", &mut pixels, |pixels| {
+        parallel::for_each_row(pixels, SIZE, 0..SIZE, |y, row| {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let noise = turbulence.turbulence(
+                    0,
+                    (x as f64, y as f64),
+                    (1.0 / 64.0, 1.0 / 64.0),
+                    2,
+                    true,
+                    false,
+                    (0.0, 0.0),
+                    (SIZE as f64, SIZE as f64),
+                    &offsets,
+                );
+                let gray = (((noise * 255.0 + 255.0) + 0.5) / 2.0) as u8;
+                *pixel = Color::argb(255, gray, gray, gray);
+            }
+        });
+    });
+}
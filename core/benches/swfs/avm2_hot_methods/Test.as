@@ -0,0 +1,47 @@
+package {
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	// Moves particles around on every frame, calling the same small methods many
+	// times, like the physics loop of a game.
+	public class Test extends Sprite {
+		private var particles:Vector.<Particle> = new Vector.<Particle>();
+
+		public function Test() {
+			for (var i:int = 0; i < 500; i++) {
+				particles.push(new Particle(i % 37, i % 53));
+			}
+			addEventListener(Event.ENTER_FRAME, onEnterFrame);
+		}
+
+		private function onEnterFrame(e:Event):void {
+			for (var step:int = 0; step < 100; step++) {
+				for each (var particle:Particle in particles) {
+					move(particle, 0.1);
+				}
+			}
+		}
+
+		private function move(particle:Particle, dt:Number):void {
+			particle.vy += 9.8 * dt;
+			particle.x += particle.vx * dt;
+			particle.y += particle.vy * dt;
+			if (particle.y > 400) {
+				particle.y = 400;
+				particle.vy = -particle.vy * 0.8;
+			}
+		}
+	}
+}
+
+class Particle {
+	public var x:Number;
+	public var y:Number;
+	public var vx:Number = 1;
+	public var vy:Number = 0;
+
+	public function Particle(x:Number, y:Number) {
+		this.x = x;
+		this.y = y;
+	}
+}
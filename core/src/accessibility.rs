@@ -0,0 +1,227 @@
+//! The tree of accessible objects that is exposed to screen readers.
+//!
+//! Objects are described by their `accessibilityProperties` in AVM2, or their `_accProps`
+//! in AVM1. Buttons and text fields are exposed even without them, as Flash Player does.
+
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier, TObject as _, Value as Avm1Value,
+};
+use crate::avm2::{Activation as Avm2Activation, TObject as _, Value as Avm2Value};
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use ruffle_render::matrix::Matrix;
+
+/// What an accessible object is, as reported to screen readers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AccessibilityRole {
+    /// The stage, which holds all of the other objects.
+    Window,
+
+    /// An object that only groups its children, or has a name of its own.
+    Group,
+
+    Button,
+
+    /// A text field that can't be edited.
+    StaticText,
+
+    /// A text field that can be edited.
+    TextInput,
+}
+
+/// An accessible object, along with its accessible children.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessibilityNode {
+    /// Identifies the object, as long as it's on the stage.
+    pub id: u64,
+
+    pub role: AccessibilityRole,
+
+    pub name: String,
+
+    pub description: String,
+
+    /// The keyboard shortcut that activates the object, such as "Control+A".
+    pub shortcut: String,
+
+    /// The value of a text field. Password fields don't expose their value.
+    pub value: Option<String>,
+
+    /// The bounds of the object, in window pixels, as `(x_min, y_min, x_max, y_max)`.
+    pub bounds: (f64, f64, f64, f64),
+
+    pub children: Vec<AccessibilityNode>,
+}
+
+/// The `accessibilityProperties` or `_accProps` of an object.
+#[derive(Default)]
+struct AccessibilityProperties {
+    name: String,
+    description: String,
+    shortcut: String,
+    silent: bool,
+    force_simple: bool,
+}
+
+/// Builds the tree of accessible objects on the stage.
+pub fn build_tree(context: &mut UpdateContext<'_, '_>) -> AccessibilityNode {
+    let stage = context.stage;
+    let view_matrix = stage.view_matrix();
+    let viewport = context.renderer.viewport_dimensions();
+    let mut children = vec![];
+    for child in stage.iter_render_list() {
+        add_nodes(context, child, view_matrix, &mut children);
+    }
+
+    AccessibilityNode {
+        id: stage.as_ptr() as u64,
+        role: AccessibilityRole::Window,
+        name: String::new(),
+        description: String::new(),
+        shortcut: String::new(),
+        value: None,
+        bounds: (0.0, 0.0, viewport.width as f64, viewport.height as f64),
+        children,
+    }
+}
+
+/// Adds the nodes of an object to its parent's children.
+///
+/// Objects that aren't accessible on their own add the nodes of their children instead.
+fn add_nodes<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: DisplayObject<'gc>,
+    view_matrix: Matrix,
+    nodes: &mut Vec<AccessibilityNode>,
+) {
+    if !object.visible() {
+        return;
+    }
+
+    let properties = properties(context, object);
+    if properties.silent {
+        return;
+    }
+
+    let mut role = AccessibilityRole::Group;
+    let mut name = properties.name;
+    let mut value = None;
+    if object.as_avm1_button().is_some() || object.as_avm2_button().is_some() {
+        role = AccessibilityRole::Button;
+    } else if let Some(text) = object.as_edit_text() {
+        if text.is_editable() {
+            role = AccessibilityRole::TextInput;
+            if !text.is_password() {
+                value = Some(text.text().to_string());
+            }
+        } else {
+            role = AccessibilityRole::StaticText;
+            if name.is_empty() {
+                name = text.text().to_string();
+            }
+        }
+    }
+
+    let mut children = vec![];
+    if !properties.force_simple {
+        if let Some(container) = object.as_container() {
+            for child in container.iter_render_list() {
+                add_nodes(context, child, view_matrix, &mut children);
+            }
+        }
+    }
+
+    if role == AccessibilityRole::Group && name.is_empty() && properties.description.is_empty() {
+        nodes.append(&mut children);
+        return;
+    }
+
+    let bounds = object.bounds_with_transform(&(view_matrix * object.local_to_global_matrix()));
+    nodes.push(AccessibilityNode {
+        id: object.as_ptr() as u64,
+        role,
+        name,
+        description: properties.description,
+        shortcut: properties.shortcut,
+        value,
+        bounds: (
+            bounds.x_min.to_pixels(),
+            bounds.y_min.to_pixels(),
+            bounds.x_max.to_pixels(),
+            bounds.y_max.to_pixels(),
+        ),
+        children,
+    });
+}
+
+fn properties<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: DisplayObject<'gc>,
+) -> AccessibilityProperties {
+    if let Avm2Value::Object(object) = object.object2() {
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let Ok(Avm2Value::Object(properties)) =
+            object.get_public_property("accessibilityProperties", &mut activation)
+        else {
+            return Default::default();
+        };
+
+        let mut get_string = |name| {
+            properties
+                .get_public_property(name, &mut activation)
+                .and_then(|value| value.coerce_to_string(&mut activation))
+                .map(|value| value.to_string())
+                .unwrap_or_default()
+        };
+        let name = get_string("name");
+        let description = get_string("description");
+        let shortcut = get_string("shortcut");
+        let mut get_bool = |name| {
+            properties
+                .get_public_property(name, &mut activation)
+                .map(|value| value.coerce_to_boolean())
+                .unwrap_or_default()
+        };
+        AccessibilityProperties {
+            name,
+            description,
+            shortcut,
+            silent: get_bool("silent"),
+            force_simple: get_bool("forceSimple"),
+        }
+    } else if let Avm1Value::Object(object) = object.object() {
+        let mut activation = Avm1Activation::from_stub(
+            context.reborrow(),
+            ActivationIdentifier::root("[Accessibility Properties]"),
+        );
+        let Ok(Avm1Value::Object(properties)) = object.get("_accProps", &mut activation) else {
+            return Default::default();
+        };
+
+        let mut get_string = |name| match properties.get(name, &mut activation) {
+            Ok(Avm1Value::Undefined) | Err(_) => String::new(),
+            Ok(value) => value
+                .coerce_to_string(&mut activation)
+                .map(|value| value.to_string())
+                .unwrap_or_default(),
+        };
+        let name = get_string("name");
+        let description = get_string("description");
+        let shortcut = get_string("shortcut");
+        let mut get_bool = |name| {
+            properties
+                .get(name, &mut activation)
+                .map(|value| value.as_bool(activation.swf_version()))
+                .unwrap_or_default()
+        };
+        AccessibilityProperties {
+            name,
+            description,
+            shortcut,
+            silent: get_bool("silent"),
+            force_simple: get_bool("forceSimple"),
+        }
+    } else {
+        Default::default()
+    }
+}
@@ -0,0 +1,37 @@
+//! Opt-in tallying of the built-in ActionScript APIs that a movie calls
+//!
+//! This is meant for surveying content, such as finding out which APIs a collection of movies
+//! relies on, rather than for debugging a single movie.
+
+use fnv::FnvHashMap;
+
+/// Counts calls to native (Rust) functions and methods while enabled.
+#[derive(Debug, Default)]
+pub struct ApiUsage {
+    enabled: bool,
+    calls: FnvHashMap<String, u32>,
+}
+
+impl ApiUsage {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Count a call to the API named by `name`, which is only built if tallying is enabled.
+    pub fn record(&mut self, name: impl FnOnce() -> String) {
+        if self.enabled {
+            *self.calls.entry(name()).or_default() += 1;
+        }
+    }
+
+    /// Each API that has been called, along with how many times.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u32)> {
+        self.calls
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+    }
+}
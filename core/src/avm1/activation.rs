@@ -8,6 +8,7 @@ use crate::avm1::scope::Scope;
 use crate::avm1::{fscommand, globals, scope, ArrayObject, ScriptObject, Value};
 use crate::backend::navigator::{NavigationMethod, Request};
 use crate::context::UpdateContext;
+use crate::debugger::{Scope as DebugScope, StopReason, Variable};
 use crate::display_object::{DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer};
 use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
 use crate::loader::MovieLoaderVMData;
@@ -170,6 +171,10 @@ impl<'a> ActivationIdentifier<'a> {
     pub fn depth(&self) -> u16 {
         self.depth
     }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
 }
 
 /// Represents a single activation of a given AVM1 function or keyframe.
@@ -219,6 +224,11 @@ pub struct Activation<'a, 'gc: 'a> {
     /// Whether the base clip was removed when we started this frame.
     base_clip_unloaded: bool,
 
+    /// Whether this activation is running code and has told the debugger so.
+    ///
+    /// Blocks such as `try` run in the same activation, so they don't count as new functions.
+    in_debugger: bool,
+
     pub context: UpdateContext<'a, 'gc>,
 
     /// An identifier to refer to this activation by, when debugging.
@@ -262,6 +272,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             base_clip,
             target_clip: Some(base_clip),
             base_clip_unloaded: base_clip.avm1_removed(),
+            in_debugger: false,
             this,
             callee,
             local_registers: None,
@@ -285,6 +296,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             base_clip: self.base_clip,
             target_clip: self.target_clip,
             base_clip_unloaded: self.base_clip_unloaded,
+            in_debugger: false,
             this: self.this,
             callee: self.callee,
             local_registers: self.local_registers,
@@ -313,6 +325,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             base_clip,
             target_clip: Some(base_clip),
             base_clip_unloaded: base_clip.avm1_removed(),
+            in_debugger: false,
             this: context.avm1.global_object().into(),
             callee: None,
             local_registers: None,
@@ -428,14 +441,72 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     pub fn run_actions(&mut self, code: SwfSlice) -> Result<ReturnType<'gc>, Error<'gc>> {
         let mut read = Reader::new(&code.movie.data()[code.start..], self.swf_version());
 
-        loop {
+        let entered = self.context.debugger.is_attached() && !self.in_debugger;
+        if entered {
+            self.in_debugger = true;
+            self.context
+                .debugger
+                .enter_function(self.id.name().to_string());
+        }
+
+        let result = loop {
             let result = self.do_action(&code, &mut read);
             match result {
                 Ok(FrameControl::Return(return_type)) => break Ok(return_type),
                 Ok(FrameControl::Continue) => {}
                 Err(e) => break Err(e),
             }
+        };
+
+        if entered {
+            self.in_debugger = false;
+            self.context.debugger.exit_function();
         }
+        result
+    }
+
+    /// Stop for the attached debugger, showing it the locals and registers of this activation.
+    fn debug_stop(&mut self, reason: StopReason) {
+        let locals = *self.scope().locals();
+        let locals = locals
+            .get_keys(self, true)
+            .into_iter()
+            .map(|name| {
+                let value = locals.get_local_stored(name, self, false);
+                Variable {
+                    name: name.to_string(),
+                    value: value.map_or_else(String::new, |value| describe_value(&value)),
+                }
+            })
+            .collect();
+        let registers = self.local_registers.map_or_else(Vec::new, |registers| {
+            let registers = registers.read();
+            (0..registers.len())
+                .map(|register| Variable {
+                    name: format!("r{register}"),
+                    value: registers
+                        .get(register)
+                        .map_or_else(String::new, describe_value),
+                })
+                .collect()
+        });
+
+        self.context.debugger.stop(
+            reason,
+            vec![
+                DebugScope {
+                    name: "Locals".to_string(),
+                    variables: locals,
+                },
+                DebugScope {
+                    name: "Registers".to_string(),
+                    variables: registers,
+                },
+            ],
+        );
+
+        // The time spent stopped doesn't count towards the script's time limit.
+        self.context.update_start = Instant::now();
     }
 
     /// Run a single action from a given action reader.
@@ -456,6 +527,13 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             //Executing beyond the end of a function constitutes an implicit return.
             Ok(FrameControl::Return(ReturnType::Implicit))
         } else {
+            // AVM1 has no line numbers, so every action is a statement to the debugger.
+            if self.context.debugger.is_attached() {
+                if let Some(reason) = self.context.debugger.statement(None) {
+                    self.debug_stop(reason);
+                }
+            }
+
             let action = reader.read_action()?;
             avm_debug!(
                 self.context.avm1,
@@ -832,7 +910,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             object.call("[Anonymous]".into(), self, Value::Undefined, &args)?
         } else {
             // Call `this[method_name]`.
-            object.call_method(method_name, &args, self, ExecutionReason::FunctionCall)?
+            object.call_method_or_resolve(method_name, &args, self)?
         };
         self.stack_push(result);
 
@@ -1176,9 +1254,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             *self.context.time_offset += 1;
         }
 
-        let time = Instant::now()
-            .duration_since(self.context.start_time)
-            .as_millis() as u32;
+        let time = self.context.elapsed_time().as_millis() as u32;
         let result = time.wrapping_add(*self.context.time_offset);
         self.context.avm1.push(result.into());
         Ok(FrameControl::Continue)
@@ -1233,11 +1309,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         if let Some(fscommand) = fscommand::parse(&url) {
             fscommand::handle(fscommand, &target, self)?;
         } else {
-            self.context.navigator.navigate_to_url(
-                &url.to_utf8_lossy(),
-                &target.to_utf8_lossy(),
-                None,
-            );
+            self.context
+                .navigate_to_url(&url.to_utf8_lossy(), &target.to_utf8_lossy(), None);
         }
 
         Ok(FrameControl::Continue)
@@ -1389,7 +1462,6 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         };
 
         self.context
-            .navigator
             .navigate_to_url(&url.to_utf8_lossy(), &target.to_utf8_lossy(), vars);
 
         Ok(FrameControl::Continue)
@@ -3145,3 +3217,20 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 }
+
+/// Describe a value for the debugger, without running any ActionScript.
+fn describe_value(value: &Value<'_>) -> String {
+    match value {
+        Value::Undefined => "undefined".to_string(),
+        Value::Null => "null".to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(value) => value.to_string(),
+        Value::String(value) => format!("{:?}", value.to_string()),
+        Value::Object(object) => match object.as_display_object() {
+            Some(display_object) => display_object.path().to_string(),
+            None if object.as_executable().is_some() => "[type Function]".to_string(),
+            None => "[object Object]".to_string(),
+        },
+        Value::MovieClip(reference) => reference.path().to_string(),
+    }
+}
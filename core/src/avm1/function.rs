@@ -8,6 +8,7 @@ use crate::avm1::scope::Scope;
 use crate::avm1::value::Value;
 use crate::avm1::{ArrayObject, Object, ObjectPtr, ScriptObject, TObject};
 use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::profiler::Category;
 use crate::string::{AvmString, SwfStrExt as _};
 use crate::tag_utils::SwfSlice;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
@@ -343,6 +344,10 @@ impl<'gc> Executable<'gc> {
             Executable::Native(nf) => {
                 // TODO: Change NativeFunction to accept `this: Value`.
                 let this = this.coerce_to_object(activation);
+                activation.context.api_usage.record(|| match name {
+                    ExecutionName::Static(name) => format!("AVM1 {name}"),
+                    ExecutionName::Dynamic(name) => format!("AVM1 {name}"),
+                });
                 return nf(activation, this, args);
             }
             Executable::Action(af) => af,
@@ -453,7 +458,13 @@ impl<'gc> Executable<'gc> {
             }
         }
 
-        Ok(frame.run_actions(af.data.clone())?.value())
+        let begin = frame.context.profiler.begin();
+        let result = frame.run_actions(af.data.clone());
+        frame.context.profiler.end_call(begin, Category::Avm1, || {
+            af.name()
+                .map_or_else(|| "[Anonymous]".to_string(), |name| name.to_string())
+        });
+        Ok(result?.value())
     }
 }
 
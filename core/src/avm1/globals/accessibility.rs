@@ -1,5 +1,6 @@
 //! Accessibility class
 
+use crate::accessibility;
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
@@ -18,8 +19,7 @@ pub fn is_active<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "Accessibility", "isActive");
-    Ok(Value::Bool(false))
+    Ok(activation.context.ui.is_accessibility_active().into())
 }
 
 pub fn send_event<'gc>(
@@ -36,7 +36,10 @@ pub fn update_properties<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm1_stub!(activation, "Accessibility", "updateProperties");
+    if activation.context.ui.is_accessibility_active() {
+        let tree = accessibility::build_tree(&mut activation.context);
+        activation.context.ui.update_accessibility_tree(tree);
+    }
     Ok(Value::Undefined)
 }
 
@@ -18,6 +18,7 @@ use crate::swf::BlendMode;
 use crate::{avm1_stub, avm_error};
 use gc_arena::{GcCell, MutationContext};
 use ruffle_render::transform::Transform;
+use swf::Rectangle;
 
 const PROTO_DECLS: &[Declaration] = declare_properties! {
     "height" => property(height);
@@ -103,7 +104,13 @@ fn constructor<'gc>(
         return Ok(Value::Undefined);
     }
 
-    let bitmap_data = BitmapData::new(width, height, transparency, fill_color);
+    let bitmap_data = BitmapData::new(
+        width,
+        height,
+        transparency,
+        fill_color,
+        activation.context.stage.bitmap_modifications(),
+    );
     this.set_native(
         activation.context.gc_context,
         NativeObject::BitmapData(BitmapDataWrapper::new(GcCell::new(
@@ -598,10 +605,10 @@ fn apply_filter<'gc>(
 
             let src_min_x = source_rect
                 .get("x", activation)?
-                .coerce_to_f64(activation)? as u32;
+                .coerce_to_f64(activation)? as i32;
             let src_min_y = source_rect
                 .get("y", activation)?
-                .coerce_to_f64(activation)? as u32;
+                .coerce_to_f64(activation)? as i32;
             let src_width = source_rect
                 .get("width", activation)?
                 .coerce_to_f64(activation)? as u32;
@@ -614,8 +621,8 @@ fn apply_filter<'gc>(
                 .unwrap_or(&Value::Undefined)
                 .coerce_to_object(activation);
 
-            let dest_x = dest_point.get("x", activation)?.coerce_to_f64(activation)? as u32;
-            let dest_y = dest_point.get("y", activation)?.coerce_to_f64(activation)? as u32;
+            let dest_x = dest_point.get("x", activation)?.coerce_to_f64(activation)? as i32;
+            let dest_y = dest_point.get("y", activation)?.coerce_to_f64(activation)? as i32;
 
             let filter_object = args
                 .get(3)
@@ -644,12 +651,58 @@ fn apply_filter<'gc>(
 fn generate_filter_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let NativeObject::BitmapData(bitmap_data) = this.native() {
         if !bitmap_data.disposed() {
-            avm1_stub!(activation, "BitmapData", "generateFilterRect");
-            return Ok(Value::Undefined);
+            let source_rect = args
+                .get(0)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let x = source_rect
+                .get("x", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let y = source_rect
+                .get("y", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let width = source_rect
+                .get("width", activation)?
+                .coerce_to_f64(activation)? as i32;
+            let height = source_rect
+                .get("height", activation)?
+                .coerce_to_f64(activation)? as i32;
+
+            let filter_object = args
+                .get(1)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let Some(filter) =
+                bitmap_filter::avm1_to_filter(filter_object, &mut activation.context)
+            else {
+                return Ok(Value::Undefined);
+            };
+
+            let dest_rect = operations::generate_filter_rect(
+                &mut activation.context,
+                bitmap_data,
+                Rectangle {
+                    x_min: x,
+                    y_min: y,
+                    x_max: x + width,
+                    y_max: y + height,
+                },
+                &filter,
+            );
+            let proto = activation.context.avm1.prototypes().rectangle_constructor;
+            return proto.construct(
+                activation,
+                &[
+                    dest_rect.x_min.into(),
+                    dest_rect.y_min.into(),
+                    dest_rect.width().into(),
+                    dest_rect.height().into(),
+                ],
+            );
         }
     }
 
@@ -776,7 +829,9 @@ fn perlin_noise<'gc>(
                 .unwrap_or(&Value::Undefined)
                 .coerce_to_object(activation);
 
-            let octave_offsets: Result<Vec<_>, Error<'gc>> = (0..num_octaves)
+            // Only the octaves that have an offset need one - the rest default to (0, 0).
+            let num_offsets = num_octaves.min(offsets.length(activation)?.max(0) as usize);
+            let octave_offsets: Result<Vec<_>, Error<'gc>> = (0..num_offsets)
                 .map(|i| {
                     if let Value::Object(e) = offsets.get_element(activation, i as i32) {
                         let x = e.get("x", activation)?.coerce_to_f64(activation)?;
@@ -1466,6 +1521,7 @@ fn load_bitmap<'gc>(
         bitmap.height().into(),
         transparency,
         bitmap.bitmap_data().read().pixels().to_vec(),
+        activation.context.stage.bitmap_modifications(),
     );
     Ok(new_bitmap_data(
         activation.context.gc_context,
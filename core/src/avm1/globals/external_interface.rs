@@ -75,8 +75,10 @@ pub fn call<'gc>(
                 external_args.push(ExternalValue::from_avm1(activation, arg.to_owned())?);
             }
         }
+        // AVM1 has no way to opt into exceptions being marshalled.
         Ok(method
             .call(&mut activation.context, &external_args)
+            .unwrap_or(ExternalValue::Null)
             .into_avm1(activation))
     } else {
         Ok(Value::Null)
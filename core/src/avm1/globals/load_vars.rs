@@ -186,7 +186,7 @@ fn send<'gc>(
         );
     }
 
-    activation.context.navigator.navigate_to_url(
+    activation.context.navigate_to_url(
         &url.to_utf8_lossy(),
         &window.to_utf8_lossy(),
         Some((method, form_values)),
@@ -89,6 +89,15 @@ pub fn add_property<'gc>(
                 return Ok(false.into());
             }
 
+            // Adding a property calls its watcher, and the result is stored underneath the
+            // getter and setter. Later watcher calls receive it as the old value.
+            let object = this.raw_script_object();
+            if object.has_watcher(activation, name) {
+                let mut value = Value::Undefined;
+                this.call_watcher(activation, name, &mut value, this)?;
+                object.set_data(name, value, activation)?;
+            }
+
             Ok(true.into())
         }
         _ => Ok(false.into()),
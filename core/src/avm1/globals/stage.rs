@@ -6,16 +6,18 @@ use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 use crate::avm1::property_decl::{define_properties_on, Declaration};
-use crate::avm1::{Object, ScriptObject, Value};
+use crate::avm1::{Object, ScriptObject, TObject, Value};
 use crate::context::GcContext;
 use crate::display_object::StageDisplayState;
 use crate::string::{AvmString, WStr, WString};
+use swf::{Rectangle, Twips};
 
 const OBJECT_DECLS: &[Declaration] = declare_properties! {
     "align" => property(align, set_align);
     "height" => property(height);
     "scaleMode" => property(scale_mode, set_scale_mode);
     "displayState" => property(display_state, set_display_state);
+    "fullScreenSourceRect" => property(full_screen_source_rect, set_full_screen_source_rect);
     "showMenu" => property(show_menu, set_show_menu);
     "width" => property(width);
 };
@@ -154,6 +156,53 @@ fn set_display_state<'gc>(
     Ok(Value::Undefined)
 }
 
+fn full_screen_source_rect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(rect) = activation.context.stage.full_screen_source_rect() else {
+        return Ok(Value::Undefined);
+    };
+    let constructor = activation.context.avm1.prototypes().rectangle_constructor;
+    constructor.construct(
+        activation,
+        &[
+            rect.x_min.to_pixels().into(),
+            rect.y_min.to_pixels().into(),
+            rect.width().to_pixels().into(),
+            rect.height().to_pixels().into(),
+        ],
+    )
+}
+
+fn set_full_screen_source_rect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rect = match args.get(0) {
+        Some(Value::Object(object)) => {
+            let mut get = |name: &'static str| -> Result<f64, Error<'gc>> {
+                object.get(name, activation)?.coerce_to_f64(activation)
+            };
+            let (x, y, width, height) = (get("x")?, get("y")?, get("width")?, get("height")?);
+            Some(Rectangle {
+                x_min: Twips::from_pixels(x),
+                y_min: Twips::from_pixels(y),
+                x_max: Twips::from_pixels(x + width),
+                y_max: Twips::from_pixels(y + height),
+            })
+        }
+        _ => None,
+    };
+    activation
+        .context
+        .stage
+        .set_full_screen_source_rect(&mut activation.context, rect);
+    Ok(Value::Undefined)
+}
+
 fn show_menu<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
@@ -73,6 +73,7 @@ const PROTO_DECLS: &[Declaration] = declare_properties! {
     "length" => property(tf_getter!(length));
     "maxhscroll" => property(tf_getter!(maxhscroll));
     "maxscroll" => property(tf_getter!(maxscroll));
+    "mouseWheelEnabled" => property(tf_getter!(mouse_wheel_enabled), tf_setter!(set_mouse_wheel_enabled));
     "multiline" => property(tf_getter!(multiline), tf_setter!(set_multiline));
     "password" => property(tf_getter!(password), tf_setter!(set_password));
     "scroll" => property(tf_getter!(scroll), tf_setter!(set_scroll));
@@ -491,6 +492,23 @@ pub fn text_height<'gc>(
     Ok(round_down_to_pixel(metrics.1).to_pixels().into())
 }
 
+pub fn mouse_wheel_enabled<'gc>(
+    this: EditText<'gc>,
+    _activation: &mut Activation<'_, 'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.is_mouse_wheel_enabled().into())
+}
+
+pub fn set_mouse_wheel_enabled<'gc>(
+    this: EditText<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let is_mouse_wheel_enabled = value.as_bool(activation.swf_version());
+    this.set_mouse_wheel_enabled(is_mouse_wheel_enabled, &mut activation.context);
+    Ok(())
+}
+
 pub fn multiline<'gc>(
     this: EditText<'gc>,
     _activation: &mut Activation<'_, 'gc>,
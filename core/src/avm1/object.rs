@@ -95,7 +95,11 @@ pub trait TObject<'gc>: 'gc + Collect + Into<Object<'gc>> + Clone + Copy {
             .get_local_stored(name, activation, is_slash_path)
     }
 
-    /// Retrieve a named property from the object, or its prototype.
+    /// Retrieve a named property from the object, or its prototype, on behalf of ActionScript.
+    ///
+    /// A property that doesn't exist is provided by the `__resolve` method of the object, if
+    /// it has one. Lookups made by the player itself must use `get` instead, since they never
+    /// call `__resolve` in Flash Player.
     fn get_non_slash_path(
         &self,
         name: impl Into<AvmString<'gc>>,
@@ -107,9 +111,10 @@ pub trait TObject<'gc>: 'gc + Collect + Into<Object<'gc>> + Clone + Copy {
         } else {
             ((*self).into(), Value::Object((*self).into()))
         };
-        match search_prototype(proto, name.into(), activation, this, false)? {
+        let name = name.into();
+        match search_prototype(proto, name, activation, this, false)? {
             Some((value, _depth)) => Ok(value),
-            None => Ok(Value::Undefined),
+            None => resolve_missing(proto, name, activation, this),
         }
     }
 
@@ -267,38 +272,27 @@ pub trait TObject<'gc>: 'gc + Collect + Into<Object<'gc>> + Clone + Copy {
         activation: &mut Activation<'_, 'gc>,
         reason: ExecutionReason,
     ) -> Result<Value<'gc>, Error<'gc>> {
-        let this = (*self).into();
-
-        if let Some(s) = this.as_stage_object() {
-            let d_o = s.as_display_object().unwrap();
-
-            if d_o.avm1_removed() {
-                return Ok(Value::Undefined);
-            }
-        }
+        call_method((*self).into(), name, args, activation, reason, false)
+    }
 
-        let (method, depth) =
-            match search_prototype(Value::Object(this), name, activation, this, false)? {
-                Some((Value::Object(method), depth)) => (method, depth),
-                _ => return Ok(Value::Undefined),
-            };
-
-        // If the method was found on the object itself, change `depth` as-if
-        // the method was found on the object's prototype.
-        let depth = depth.max(1);
-
-        match method.as_executable() {
-            Some(exec) => exec.exec(
-                ExecutionName::Dynamic(name),
-                activation,
-                this.into(),
-                depth,
-                args,
-                reason,
-                method,
-            ),
-            None => method.call(name, activation, this.into(), args),
-        }
+    /// Call a method on the object on behalf of an `ActionCallMethod` opcode.
+    ///
+    /// This is the same as `call_method`, except that a method that doesn't exist is
+    /// provided by the `__resolve` method of the object, if it has one.
+    fn call_method_or_resolve(
+        &self,
+        name: AvmString<'gc>,
+        args: &[Value<'gc>],
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        call_method(
+            (*self).into(),
+            name,
+            args,
+            activation,
+            ExecutionReason::FunctionCall,
+            true,
+        )
     }
 
     /// Retrive a getter defined on this object.
@@ -729,3 +723,68 @@ pub fn search_prototype<'gc>(
 
     Ok(None)
 }
+
+/// Call a method on an object, finding it on the prototype chain.
+///
+/// If `resolve` is set, a method that doesn't exist is provided by `__resolve`.
+fn call_method<'gc>(
+    this: Object<'gc>,
+    name: AvmString<'gc>,
+    args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc>,
+    reason: ExecutionReason,
+    resolve: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(s) = this.as_stage_object() {
+        let d_o = s.as_display_object().unwrap();
+
+        if d_o.avm1_removed() {
+            return Ok(Value::Undefined);
+        }
+    }
+
+    let (method, depth) =
+        match search_prototype(Value::Object(this), name, activation, this, false)? {
+            Some((Value::Object(method), depth)) => (method, depth),
+            None if resolve => {
+                match resolve_missing(Value::Object(this), name, activation, this)? {
+                    Value::Object(method) => (method, 0),
+                    _ => return Ok(Value::Undefined),
+                }
+            }
+            _ => return Ok(Value::Undefined),
+        };
+
+    // If the method was found on the object itself, change `depth` as-if
+    // the method was found on the object's prototype.
+    let depth = depth.max(1);
+
+    match method.as_executable() {
+        Some(exec) => exec.exec(
+            ExecutionName::Dynamic(name),
+            activation,
+            this.into(),
+            depth,
+            args,
+            reason,
+            method,
+        ),
+        None => method.call(name, activation, this.into(), args),
+    }
+}
+
+/// Provides the value of a property that doesn't exist, by calling the `__resolve` method found
+/// on the prototype chain with the name of the property.
+fn resolve_missing<'gc>(
+    proto: Value<'gc>,
+    name: AvmString<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    match search_prototype(proto, "__resolve".into(), activation, this, false)? {
+        Some((Value::Object(resolve), _depth)) => {
+            resolve.call("__resolve".into(), activation, this.into(), &[name.into()])
+        }
+        _ => Ok(Value::Undefined),
+    }
+}
@@ -128,6 +128,14 @@ impl<'gc> ScriptObject<'gc> {
         Ok(())
     }
 
+    /// Whether a property has a watcher, set with `Object.watch`.
+    pub fn has_watcher(&self, activation: &mut Activation<'_, 'gc>, name: AvmString<'gc>) -> bool {
+        self.0
+            .read()
+            .watchers
+            .contains_key(name, activation.is_case_sensitive())
+    }
+
     // TODO: Make an iterator?
     pub fn own_properties(&self) -> Vec<(AvmString<'gc>, Value<'gc>)> {
         self.0
@@ -353,6 +361,18 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
     }
 
     fn unwatch(&self, activation: &mut Activation<'_, 'gc>, name: AvmString<'gc>) -> bool {
+        // Flash Player can't remove the watchers of getter/setter properties, which keep
+        // being called.
+        let is_virtual = self
+            .0
+            .read()
+            .properties
+            .get(name, activation.is_case_sensitive())
+            .map_or(false, |property| property.is_virtual());
+        if is_virtual {
+            return false;
+        }
+
         self.0
             .write(activation.context.gc_context)
             .watchers
@@ -8,7 +8,7 @@ use crate::avm1::{Object, ObjectPtr, ScriptObject, TObject, Value};
 use crate::avm_warn;
 use crate::context::UpdateContext;
 use crate::display_object::{
-    DisplayObject, EditText, MovieClip, TDisplayObject, TDisplayObjectContainer,
+    DisplayObject, EditText, MovieClip, TDisplayObject, TDisplayObjectContainer, TInteractiveObject,
 };
 use crate::string::{AvmString, WStr};
 use crate::types::Percent;
@@ -744,20 +744,45 @@ fn set_high_quality<'gc>(
     Ok(())
 }
 
-fn focus_rect<'gc>(activation: &mut Activation<'_, 'gc>, _this: DisplayObject<'gc>) -> Value<'gc> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
-    Value::Null
+fn focus_rect<'gc>(activation: &mut Activation<'_, 'gc>, this: DisplayObject<'gc>) -> Value<'gc> {
+    let focus_rect = this.as_interactive().and_then(|this| this.focus_rect());
+    match focus_rect {
+        Some(focus_rect) => focus_rect.into(),
+        // The root's `_focusrect` is the default for every other object.
+        None if is_root(activation, this) => activation.context.stage.stage_focus_rect().into(),
+        None => Value::Null,
+    }
 }
 
 fn set_focus_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    this: DisplayObject<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
+    let focus_rect = match val {
+        Value::Undefined | Value::Null => None,
+        val => Some(val.as_bool(activation.swf_version())),
+    };
+    if let Some(this) = this.as_interactive() {
+        this.set_focus_rect(activation.context.gc_context, focus_rect);
+    }
+    if is_root(activation, this) {
+        activation
+            .context
+            .stage
+            .set_stage_focus_rect(activation.context.gc_context, focus_rect.unwrap_or(true));
+    }
     Ok(())
 }
 
+fn is_root<'gc>(activation: &Activation<'_, 'gc>, this: DisplayObject<'gc>) -> bool {
+    activation
+        .context
+        .stage
+        .root_clip()
+        .map_or(false, |root| DisplayObject::ptr_eq(root, this))
+}
+
 fn sound_buf_time<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: DisplayObject<'gc>,
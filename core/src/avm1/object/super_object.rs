@@ -144,6 +144,15 @@ impl<'gc> TObject<'gc> for SuperObject<'gc> {
         }
     }
 
+    fn call_method_or_resolve(
+        &self,
+        name: AvmString<'gc>,
+        args: &[Value<'gc>],
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        self.call_method(name, args, activation, ExecutionReason::FunctionCall)
+    }
+
     fn create_bare_object(
         &self,
         activation: &mut Activation<'_, 'gc>,
@@ -4,7 +4,7 @@
 //! the insertion order of properties, which is necessary for accurate
 //! enumeration order.
 
-use crate::string::{utils as string_utils, AvmString, WStr};
+use crate::string::AvmString;
 use fnv::FnvBuildHasher;
 use gc_arena::Collect;
 use indexmap::{Equivalent, IndexMap};
@@ -21,56 +21,36 @@ impl<'gc, V> PropertyMap<'gc, V> {
         Self(FnvIndexMap::default())
     }
 
-    pub fn contains_key<T: AsRef<WStr>>(&self, key: T, case_sensitive: bool) -> bool {
-        if case_sensitive {
-            self.0.contains_key(&CaseSensitive(key.as_ref()))
-        } else {
-            self.0.contains_key(&CaseInsentitive(key.as_ref()))
-        }
+    pub fn contains_key(&self, key: AvmString<'gc>, case_sensitive: bool) -> bool {
+        self.get_index_of(key, case_sensitive).is_some()
     }
 
     pub fn entry<'a>(&'a mut self, key: AvmString<'gc>, case_sensitive: bool) -> Entry<'gc, 'a, V> {
-        if case_sensitive {
-            match self.0.get_index_of(&CaseSensitive(key.as_ref())) {
-                Some(index) => Entry::Occupied(OccupiedEntry {
-                    map: &mut self.0,
-                    index,
-                }),
-                None => Entry::Vacant(VacantEntry {
-                    map: &mut self.0,
-                    key,
-                }),
-            }
-        } else {
-            match self.0.get_index_of(&CaseInsentitive(key.as_ref())) {
-                Some(index) => Entry::Occupied(OccupiedEntry {
-                    map: &mut self.0,
-                    index,
-                }),
-                None => Entry::Vacant(VacantEntry {
-                    map: &mut self.0,
-                    key,
-                }),
-            }
+        match self.get_index_of(key, case_sensitive) {
+            Some(index) => Entry::Occupied(OccupiedEntry {
+                map: &mut self.0,
+                index,
+            }),
+            None => Entry::Vacant(VacantEntry {
+                map: &mut self.0,
+                key: PropertyName {
+                    name: key,
+                    hash: key.hash_ignore_case(),
+                },
+            }),
         }
     }
 
     /// Gets the value for the specified property.
-    pub fn get<T: AsRef<WStr>>(&self, key: T, case_sensitive: bool) -> Option<&V> {
-        if case_sensitive {
-            self.0.get(&CaseSensitive(key.as_ref()))
-        } else {
-            self.0.get(&CaseInsentitive(key.as_ref()))
-        }
+    pub fn get(&self, key: AvmString<'gc>, case_sensitive: bool) -> Option<&V> {
+        let index = self.get_index_of(key, case_sensitive)?;
+        self.get_index(index)
     }
 
     /// Gets a mutable reference to the value for the specified property.
-    pub fn get_mut<T: AsRef<WStr>>(&mut self, key: T, case_sensitive: bool) -> Option<&mut V> {
-        if case_sensitive {
-            self.0.get_mut(&CaseSensitive(key.as_ref()))
-        } else {
-            self.0.get_mut(&CaseInsentitive(key.as_ref()))
-        }
+    pub fn get_mut(&mut self, key: AvmString<'gc>, case_sensitive: bool) -> Option<&mut V> {
+        let index = self.get_index_of(key, case_sensitive)?;
+        self.0.get_index_mut(index).map(|(_, v)| v)
     }
 
     /// Gets a value by index, based on insertion order.
@@ -90,20 +70,30 @@ impl<'gc, V> PropertyMap<'gc, V> {
 
     /// Returns the value tuples in Flash's iteration order (most recently added first).
     pub fn iter(&self) -> impl Iterator<Item = (AvmString<'gc>, &V)> {
-        self.0.iter().rev().map(|(k, v)| (k.0, v))
+        self.0.iter().rev().map(|(k, v)| (k.name, v))
     }
 
     /// Returns the key-value tuples in Flash's iteration order (most recently added first).
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (AvmString<'gc>, &mut V)> {
-        self.0.iter_mut().rev().map(|(k, v)| (k.0, v))
+        self.0.iter_mut().rev().map(|(k, v)| (k.name, v))
     }
 
-    pub fn remove<T: AsRef<WStr>>(&mut self, key: T, case_sensitive: bool) -> Option<V> {
+    pub fn remove(&mut self, key: AvmString<'gc>, case_sensitive: bool) -> Option<V> {
+        let index = self.get_index_of(key, case_sensitive)?;
         // Note that we must use shift_remove to maintain order in case this object is enumerated.
+        self.0.shift_remove_index(index).map(|(_, v)| v)
+    }
+
+    /// Finds the insertion index of the specified property.
+    ///
+    /// The case-insensitive hash of the name is cached by the string itself, so looking up
+    /// an interned name, such as one from the constant pool, doesn't hash it again.
+    fn get_index_of(&self, key: AvmString<'gc>, case_sensitive: bool) -> Option<usize> {
+        let hash = key.hash_ignore_case();
         if case_sensitive {
-            self.0.shift_remove(&CaseSensitive(key.as_ref()))
+            self.0.get_index_of(&CaseSensitive { key, hash })
         } else {
-            self.0.shift_remove(&CaseInsentitive(key.as_ref()))
+            self.0.get_index_of(&CaseInsentitive { key, hash })
         }
     }
 }
@@ -111,7 +101,7 @@ impl<'gc, V> PropertyMap<'gc, V> {
 unsafe impl<'gc, V: Collect> Collect for PropertyMap<'gc, V> {
     fn trace(&self, cc: &gc_arena::Collection) {
         for (key, value) in &self.0 {
-            key.0.trace(cc);
+            key.name.trace(cc);
             value.trace(cc);
         }
     }
@@ -130,7 +120,7 @@ pub struct OccupiedEntry<'gc, 'a, V> {
 impl<'gc, 'a, V> OccupiedEntry<'gc, 'a, V> {
     pub fn remove_entry(&mut self) -> (AvmString<'gc>, V) {
         let (k, v) = self.map.shift_remove_index(self.index).unwrap();
-        (k.0, v)
+        (k.name, v)
     }
 
     pub fn get(&self) -> &V {
@@ -148,43 +138,51 @@ impl<'gc, 'a, V> OccupiedEntry<'gc, 'a, V> {
 
 pub struct VacantEntry<'gc, 'a, V> {
     map: &'a mut FnvIndexMap<PropertyName<'gc>, V>,
-    key: AvmString<'gc>,
+    key: PropertyName<'gc>,
 }
 
 impl<'gc, 'a, V> VacantEntry<'gc, 'a, V> {
     pub fn insert(self, value: V) {
-        self.map.insert(PropertyName(self.key), value);
+        self.map.insert(self.key, value);
     }
 }
 
-/// Wraps a str-like type, causing the hash map to use a case insensitive hash and equality.
-struct CaseInsentitive<T>(T);
+/// Wraps a property name, causing the hash map to use a case insensitive hash and equality.
+struct CaseInsentitive<'gc> {
+    key: AvmString<'gc>,
+    hash: u64,
+}
 
-impl<'a> Hash for CaseInsentitive<&'a WStr> {
+impl<'gc> Hash for CaseInsentitive<'gc> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        swf_hash_string_ignore_case(self.0, state);
+        state.write_u64(self.hash);
     }
 }
 
-impl<'gc, 'a> Equivalent<PropertyName<'gc>> for CaseInsentitive<&'a WStr> {
+impl<'gc> Equivalent<PropertyName<'gc>> for CaseInsentitive<'gc> {
     fn equivalent(&self, key: &PropertyName<'gc>) -> bool {
-        key.0.eq_ignore_case(self.0)
+        // Names with different hashes can't match, so only compare the ones that might.
+        key.hash == self.hash && (key.name == self.key || key.name.eq_ignore_case(&self.key))
     }
 }
 
-/// Wraps an str-like type, causing the property map to use a case insensitive hash lookup,
+/// Wraps a property name, causing the property map to use a case insensitive hash lookup,
 /// but case sensitive equality.
-struct CaseSensitive<T>(T);
+struct CaseSensitive<'gc> {
+    key: AvmString<'gc>,
+    hash: u64,
+}
 
-impl<'a> Hash for CaseSensitive<&'a WStr> {
+impl<'gc> Hash for CaseSensitive<'gc> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        swf_hash_string_ignore_case(self.0, state);
+        state.write_u64(self.hash);
     }
 }
 
-impl<'gc, 'a> Equivalent<PropertyName<'gc>> for CaseSensitive<&'a WStr> {
+impl<'gc> Equivalent<PropertyName<'gc>> for CaseSensitive<'gc> {
     fn equivalent(&self, key: &PropertyName<'gc>) -> bool {
-        key.0 == self.0
+        // Comparing two interned names only compares their pointers.
+        key.hash == self.hash && key.name == self.key
     }
 }
 
@@ -193,19 +191,19 @@ impl<'gc, 'a> Equivalent<PropertyName<'gc>> for CaseSensitive<&'a WStr> {
 /// SWFv6, which is case insensitive. The equality check is handled by the `Equivalent`
 /// impls above, which allow it to be either case-sensitive or insensitive.
 /// Note that the property of if key1 == key2 -> hash(key1) == hash(key2) still holds.
+///
+/// The hash is computed once when the property is added, so that neither growing the map
+/// nor comparing names during a lookup has to case-fold the name again.
 #[derive(Debug, Clone, PartialEq, Eq, Collect)]
 #[collect(require_static)]
-struct PropertyName<'gc>(AvmString<'gc>);
+struct PropertyName<'gc> {
+    name: AvmString<'gc>,
+    hash: u64,
+}
 
 #[allow(clippy::derive_hash_xor_eq)]
 impl<'gc> Hash for PropertyName<'gc> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        swf_hash_string_ignore_case(self.0.as_ref(), state);
+        state.write_u64(self.hash);
     }
 }
-
-fn swf_hash_string_ignore_case<H: Hasher>(s: &WStr, state: &mut H) {
-    s.iter()
-        .for_each(|c| string_utils::swf_to_lowercase(c).hash(state));
-    state.write_u8(0xff);
-}
@@ -137,7 +137,12 @@ impl<'gc> Scope<'gc> {
         activation: &mut Activation<'_, 'gc>,
         top_level: bool,
     ) -> Result<CallableValue<'gc>, Error<'gc>> {
-        if self.locals().has_property(activation, name) {
+        // Objects of `with` statements that have a `__resolve` method provide every variable
+        // that isn't found on them, hiding the rest of the scope chain.
+        if self.locals().has_property(activation, name)
+            || (self.class == ScopeClass::With
+                && self.locals().has_property(activation, "__resolve".into()))
+        {
             return self
                 .locals()
                 .get_non_slash_path(name, activation)
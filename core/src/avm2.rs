@@ -26,7 +26,7 @@ macro_rules! avm_debug {
 }
 
 pub mod activation;
-mod amf;
+pub mod amf;
 mod array;
 pub mod bytearray;
 mod call_stack;
@@ -45,10 +45,12 @@ mod multiname;
 mod namespace;
 pub mod object;
 mod parameters;
+mod predecode;
 pub mod property;
 mod property_map;
 mod qname;
 mod regexp;
+mod sampler;
 mod scope;
 mod script;
 mod string;
@@ -56,6 +58,7 @@ mod stubs;
 mod traits;
 mod value;
 pub mod vector;
+mod verify;
 mod vtable;
 
 pub use crate::avm2::activation::Activation;
@@ -72,9 +75,11 @@ pub use crate::avm2::object::{
     SoundChannelObject, StageObject, TObject,
 };
 pub use crate::avm2::qname::QName;
+pub use crate::avm2::sampler::{ClassProfile, MemoryProfile};
 pub use crate::avm2::value::Value;
 
 use self::object::WeakObject;
+use self::sampler::Sampler;
 use self::scope::Scope;
 
 const BROADCAST_WHITELIST: [&str; 4] = ["enterFrame", "exitFrame", "frameConstructed", "render"];
@@ -121,6 +126,10 @@ pub struct Avm2<'gc> {
     pub flash_utils_internal: Namespace<'gc>,
     pub flash_geom_internal: Namespace<'gc>,
     pub flash_events_internal: Namespace<'gc>,
+    pub flash_system_internal: Namespace<'gc>,
+    pub flash_net_internal: Namespace<'gc>,
+    pub flash_desktop_internal: Namespace<'gc>,
+    pub flash_ui_internal: Namespace<'gc>,
 
     #[collect(require_static)]
     native_method_table: &'static [Option<(&'static str, NativeMethodImpl)>],
@@ -152,6 +161,18 @@ pub struct Avm2<'gc> {
     /// strong references around (this matches Flash's behavior).
     orphan_objects: Rc<Vec<DisplayObjectWeak<'gc>>>,
 
+    /// The classes registered with `registerClassAlias`, by alias.
+    ///
+    /// AMF serialization uses these to write the class of typed objects,
+    /// and to construct instances of them when they're read back.
+    alias_to_class_map: FnvHashMap<AvmString<'gc>, ClassObject<'gc>>,
+
+    /// The aliases registered with `registerClassAlias`, by class.
+    class_to_alias_map: FnvHashMap<ClassObject<'gc>, AvmString<'gc>>,
+
+    /// Object allocations recorded for `flash.sampler` and memory profiles.
+    sampler: Sampler<'gc>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -187,6 +208,10 @@ impl<'gc> Avm2<'gc> {
             flash_utils_internal: Namespace::internal("flash.utils", context),
             flash_geom_internal: Namespace::internal("flash.geom", context),
             flash_events_internal: Namespace::internal("flash.events", context),
+            flash_system_internal: Namespace::internal("flash.system", context),
+            flash_net_internal: Namespace::internal("flash.net", context),
+            flash_desktop_internal: Namespace::internal("flash.desktop", context),
+            flash_ui_internal: Namespace::internal("flash.ui", context),
 
             native_method_table: Default::default(),
             native_instance_allocator_table: Default::default(),
@@ -196,6 +221,11 @@ impl<'gc> Avm2<'gc> {
 
             orphan_objects: Default::default(),
 
+            alias_to_class_map: Default::default(),
+            class_to_alias_map: Default::default(),
+
+            sampler: Default::default(),
+
             #[cfg(feature = "avm_debug")]
             debug_output: false,
         }
@@ -513,6 +543,38 @@ impl<'gc> Avm2<'gc> {
         self.stage_domain
     }
 
+    /// Registers an alias for a class, as done by `registerClassAlias`.
+    pub fn register_class_alias(&mut self, name: AvmString<'gc>, class: ClassObject<'gc>) {
+        self.alias_to_class_map.insert(name, class);
+        self.class_to_alias_map.insert(class, name);
+    }
+
+    pub fn get_class_by_alias(&self, name: AvmString<'gc>) -> Option<ClassObject<'gc>> {
+        self.alias_to_class_map.get(&name).copied()
+    }
+
+    pub fn get_alias_by_class(&self, class: ClassObject<'gc>) -> Option<AvmString<'gc>> {
+        self.class_to_alias_map.get(&class).copied()
+    }
+
+    pub fn sampler(&self) -> &Sampler<'gc> {
+        &self.sampler
+    }
+
+    pub fn sampler_mut(&mut self) -> &mut Sampler<'gc> {
+        &mut self.sampler
+    }
+
+    /// Start or stop tracking object allocations for memory profiles.
+    pub fn set_memory_profiling(&mut self, profiling: bool) {
+        self.sampler.set_profiling(profiling);
+    }
+
+    /// Count the objects allocated since memory profiling started that are still alive.
+    pub fn memory_profile(&mut self, mc: MutationContext<'gc, '_>) -> MemoryProfile {
+        self.sampler.memory_profile(mc)
+    }
+
     /// Pushes an executable on the call stack
     pub fn push_call(&self, mc: MutationContext<'gc, '_>, calling: &Executable<'gc>) {
         self.call_stack.write(mc).push(calling)
@@ -14,17 +14,22 @@ use crate::avm2::object::{
     ScriptObject, XmlListObject,
 };
 use crate::avm2::object::{Object, TObject};
+use crate::avm2::predecode::DecodedBody;
+use crate::avm2::property::Property;
 use crate::avm2::scope::{search_scope_stack, Scope, ScopeChain};
 use crate::avm2::script::Script;
 use crate::avm2::value::Value;
+use crate::avm2::verify::verify_method;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::avm2::QName;
 use crate::avm2::{value, Avm2, Error};
 use crate::context::{GcContext, UpdateContext};
+use crate::debugger::{Scope as DebugScope, StopReason, Variable};
 use crate::string::{AvmAtom, AvmString};
 use crate::swf::extensions::ReadSwfExt;
-use gc_arena::{Gc, GcCell};
+use gc_arena::{Gc, GcCell, MutationContext};
+use instant::Instant;
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::cmp::{min, Ordering};
@@ -835,10 +840,26 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             .body()
             .ok_or_else(|| "Cannot execute non-native method without body".into());
         let body = body?;
+
+        if !method.verified.get() {
+            let instructions = verify_method(self, &method)?;
+            *method.instructions.borrow_mut() = Some(instructions);
+            method.verified.set(true);
+        }
+
+        let decoded = BytecodeMethod::hot_body(method, self.context.gc_context);
         let mut reader = Reader::new(&body.code);
 
+        let debugging = self.context.debugger.is_attached();
+        if debugging {
+            let name = self.avm2().call_stack().read().current_function_name();
+            self.context
+                .debugger
+                .enter_function(name.unwrap_or_else(|| "<unknown>".to_string()));
+        }
+
         let val = loop {
-            let result = self.do_next_opcode(method, &mut reader, &body.code);
+            let result = self.do_next_opcode(method, decoded, &mut reader, &body.code);
             match result {
                 Ok(FrameControl::Return(value)) => break Ok(value),
                 Ok(FrameControl::Continue) => {}
@@ -846,6 +867,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
             }
         };
 
+        if debugging {
+            self.context.debugger.exit_function();
+        }
+
         self.clear_stack();
         self.clear_scope();
         val
@@ -901,9 +926,13 @@ impl<'a, 'gc> Activation<'a, 'gc> {
     }
 
     /// Run a single action from a given action reader.
+    ///
+    /// If the method has a pre-decoded body, the action is taken from it instead of being
+    /// read, and the reader is moved past it.
     fn do_next_opcode<'b>(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
+        decoded: Option<Gc<'gc, DecodedBody<'gc>>>,
         reader: &mut Reader<'b>,
         full_data: &'b [u8],
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
@@ -919,7 +948,17 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         }
 
         let instruction_start = reader.pos(full_data);
-        let op = reader.read_op();
+        let instruction = decoded.and_then(|decoded| {
+            let index = decoded.instruction_at(instruction_start)?;
+            Some((decoded, index))
+        });
+        let op = if let Some((decoded, index)) = instruction {
+            let (op, next) = decoded.op(index);
+            reader.seek_absolute(full_data, next);
+            Ok(op.clone())
+        } else {
+            reader.read_op()
+        };
         if let Ok(op) = op {
             avm_debug!(self.avm2(), "Opcode: {op:?}");
 
@@ -959,8 +998,8 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                 }
                 Op::ReturnValue => self.op_return_value(method),
                 Op::ReturnVoid => self.op_return_void(),
-                Op::GetProperty { index } => self.op_get_property(method, index),
-                Op::SetProperty { index } => self.op_set_property(method, index),
+                Op::GetProperty { index } => self.op_get_property(method, index, instruction),
+                Op::SetProperty { index } => self.op_set_property(method, index, instruction),
                 Op::InitProperty { index } => self.op_init_property(method, index),
                 Op::DeleteProperty { index } => self.op_delete_property(method, index),
                 Op::GetSuper { index } => self.op_get_super(method, index),
@@ -1420,10 +1459,37 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Return(Value::Undefined))
     }
 
+    /// Look up which slot a property with a static name is in, using the inline cache of
+    /// the current instruction when it has one.
+    ///
+    /// Returns `None` if the property isn't a slot, which also leaves the cache as it was.
+    fn cached_slot(
+        &mut self,
+        instruction: Option<(Gc<'gc, DecodedBody<'gc>>, usize)>,
+        object: Object<'gc>,
+        multiname: &Multiname<'gc>,
+        is_write: bool,
+    ) -> Option<u32> {
+        let (decoded, index) = instruction?;
+        if let Some(slot_id) = decoded.cached_slot(index, object) {
+            return Some(slot_id);
+        }
+
+        let vtable = object.vtable()?;
+        let slot_id = match vtable.get_trait(multiname)? {
+            Property::Slot { slot_id } => slot_id,
+            Property::ConstSlot { slot_id } if !is_write => slot_id,
+            _ => return None,
+        };
+        DecodedBody::cache_slot(decoded, self.context.gc_context, index, vtable, slot_id);
+        Some(slot_id)
+    }
+
     fn op_get_property(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
+        instruction: Option<(Gc<'gc, DecodedBody<'gc>>, usize)>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let multiname = self.pool_maybe_uninitialized_multiname(method, index)?;
 
@@ -1431,6 +1497,14 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         if !multiname.has_lazy_component() {
             let object = self.pop_stack();
             let object = object.coerce_to_object_or_typeerror(self, Some(&multiname))?;
+
+            // fast path for slots found by the last run of this instruction
+            if let Some(slot_id) = self.cached_slot(instruction, object, &multiname, false) {
+                let value = object.get_slot(slot_id)?;
+                self.push_stack(value);
+                return Ok(FrameControl::Continue);
+            }
+
             let value = object.get_property(&multiname, self)?;
             self.push_stack(value);
             return Ok(FrameControl::Continue);
@@ -1471,6 +1545,7 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         index: Index<AbcMultiname>,
+        instruction: Option<(Gc<'gc, DecodedBody<'gc>>, usize)>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         let value = self.pop_stack();
         let multiname = self.pool_maybe_uninitialized_multiname(method, index)?;
@@ -1479,6 +1554,17 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         if !multiname.has_lazy_component() {
             let object = self.pop_stack();
             let mut object = object.coerce_to_object_or_typeerror(self, Some(&multiname))?;
+
+            // fast path for slots found by the last run of this instruction
+            if let Some(slot_id) = self.cached_slot(instruction, object, &multiname, true) {
+                let vtable = object
+                    .vtable()
+                    .expect("Cached slots are only used with vtables");
+                let value = vtable.coerce_trait_value(slot_id, value, self)?;
+                object.set_slot(slot_id, value, self.context.gc_context)?;
+                return Ok(FrameControl::Continue);
+            }
+
             object.set_property(&multiname, value, self)?;
             return Ok(FrameControl::Continue);
         }
@@ -3198,7 +3284,6 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
-    #[cfg(feature = "avm_debug")]
     fn op_debug(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
@@ -3206,6 +3291,11 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         register_name: Index<String>,
         register: u8,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        // Looking up the name is only worth it if something is going to use it.
+        if !cfg!(feature = "avm_debug") && !self.context.debugger.is_attached() {
+            return Ok(FrameControl::Continue);
+        }
+
         if is_local_register {
             let register_name = self.pool_string(&method, register_name)?;
             if (register as usize) < self.local_registers.0.len() {
@@ -3218,6 +3308,10 @@ impl<'a, 'gc> Activation<'a, 'gc> {
                     "Debug: {register_name} = <out-of-bounds register #{register}>",
                 );
             }
+
+            self.context
+                .debugger
+                .set_register_name(register as u32, register_name.to_string());
         } else {
             avm_debug!(self.avm2(), "Unknown debugging mode!");
         }
@@ -3225,55 +3319,102 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Ok(FrameControl::Continue)
     }
 
-    #[cfg(not(feature = "avm_debug"))]
-    fn op_debug(
-        &mut self,
-        _method: Gc<'gc, BytecodeMethod<'gc>>,
-        _is_local_register: bool,
-        _register_name: Index<String>,
-        _register: u8,
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        Ok(FrameControl::Continue)
-    }
-
-    #[cfg(feature = "avm_debug")]
     fn op_debug_file(
         &mut self,
         method: Gc<'gc, BytecodeMethod<'gc>>,
         file_name: Index<String>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        if !cfg!(feature = "avm_debug") && !self.context.debugger.is_attached() {
+            return Ok(FrameControl::Continue);
+        }
+
         let file_name = self.pool_string(&method, file_name)?;
 
         avm_debug!(self.avm2(), "File: {file_name}");
 
-        Ok(FrameControl::Continue)
-    }
+        self.context.debugger.set_file(file_name.to_string());
 
-    #[cfg(not(feature = "avm_debug"))]
-    fn op_debug_file(
-        &mut self,
-        _method: Gc<'gc, BytecodeMethod<'gc>>,
-        _file_name: Index<String>,
-    ) -> Result<FrameControl<'gc>, Error<'gc>> {
         Ok(FrameControl::Continue)
     }
 
     fn op_debug_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         avm_debug!(self.avm2(), "Line: {line_num}");
 
+        if self.context.debugger.is_attached() {
+            if let Some(reason) = self.context.debugger.statement(Some(line_num)) {
+                self.debug_stop(reason);
+            }
+        }
+
         Ok(FrameControl::Continue)
     }
 
     fn op_bkpt(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         // while a debugger is not attached, this is a no-op
+        if self.context.debugger.is_attached() {
+            self.debug_stop(StopReason::Breakpoint);
+        }
         Ok(FrameControl::Continue)
     }
 
-    fn op_bkpt_line(&mut self, _line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
+    fn op_bkpt_line(&mut self, line_num: u32) -> Result<FrameControl<'gc>, Error<'gc>> {
         // while a debugger is not attached, this is a no-op
+        if self.context.debugger.is_attached() {
+            self.context.debugger.statement(Some(line_num));
+            self.debug_stop(StopReason::Breakpoint);
+        }
         Ok(FrameControl::Continue)
     }
 
+    /// Stop for the attached debugger, showing it the registers and scopes of this activation.
+    fn debug_stop(&mut self, reason: StopReason) {
+        let mc = self.context.gc_context;
+        let registers = self
+            .local_registers
+            .0
+            .iter()
+            .enumerate()
+            .map(|(register, value)| {
+                let name = match self.context.debugger.register_name(register as u32) {
+                    Some(name) => name.to_string(),
+                    None if register == 0 => "this".to_string(),
+                    None => format!("r{register}"),
+                };
+                Variable {
+                    name,
+                    value: describe_value(mc, value),
+                }
+            })
+            .collect();
+        let scopes = self
+            .scope_frame()
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(index, scope)| Variable {
+                name: index.to_string(),
+                value: describe_value(mc, &scope.values().into()),
+            })
+            .collect();
+
+        self.context.debugger.stop(
+            reason,
+            vec![
+                DebugScope {
+                    name: "Registers".to_string(),
+                    variables: registers,
+                },
+                DebugScope {
+                    name: "Scope stack".to_string(),
+                    variables: scopes,
+                },
+            ],
+        );
+
+        // The time spent stopped doesn't count towards the script's time limit.
+        self.context.update_start = Instant::now();
+    }
+
     fn op_timestamp(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         // while a debugger is not attached, this is a no-op
         Ok(FrameControl::Continue)
@@ -3284,3 +3425,16 @@ impl<'a, 'gc> Activation<'a, 'gc> {
         Err(Error::AvmError(error_val))
     }
 }
+
+/// Describe a value for the debugger, without running any ActionScript.
+fn describe_value<'gc>(mc: MutationContext<'gc, '_>, value: &Value<'gc>) -> String {
+    match value {
+        Value::Undefined => "undefined".to_string(),
+        Value::Null => "null".to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(value) => value.to_string(),
+        Value::Integer(value) => value.to_string(),
+        Value::String(value) => format!("{:?}", value.to_string()),
+        Value::Object(object) => format!("[object {}]", object.instance_of_class_name(mc)),
+    }
+}
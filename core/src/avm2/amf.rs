@@ -1,36 +1,31 @@
-use crate::avm2::bytearray::ByteArrayStorage;
-use crate::avm2::object::{ByteArrayObject, TObject, VectorObject};
+use crate::avm2::bytearray::{ByteArrayError, ByteArrayStorage};
+use crate::avm2::error::{argument_error, range_error, reference_error};
+use crate::avm2::object::{ByteArrayObject, ObjectPtr, TObject, VectorObject};
+use crate::avm2::property::Property;
 use crate::avm2::vector::VectorStorage;
 use crate::avm2::ArrayObject;
 use crate::avm2::ArrayStorage;
 use crate::avm2::{Activation, Error, Object, Value};
 use crate::string::AvmString;
 use enumset::EnumSet;
-use flash_lso::types::{AMFVersion, Element, Lso};
 use flash_lso::types::{Attribute, ClassDefinition, Value as AmfValue};
+use flash_lso::types::{Element, Lso};
+use std::collections::HashMap;
 
-/// Serialize a Value to an AmfValue
+/// Serialize a Value to an AMF0 AmfValue
+///
+/// AMF3 data is written with [`Amf3Writer`] instead.
 pub fn serialize_value<'gc>(
     activation: &mut Activation<'_, 'gc>,
     elem: Value<'gc>,
-    amf_version: AMFVersion,
 ) -> Option<AmfValue> {
     match elem {
         Value::Undefined => Some(AmfValue::Undefined),
         Value::Null => Some(AmfValue::Null),
         Value::Bool(b) => Some(AmfValue::Bool(b)),
         Value::Number(f) => Some(AmfValue::Number(f)),
-        Value::Integer(num) => {
-            // NOTE - we should really be converting `Value::Integer` to `Value::Number`
-            // whenever it's outside this range, instead of performing this during AMF serialization.
-            // Integers are unsupported in AMF0, and must be converted to Number regardless of whether
-            // it can be represented as an integer.
-            if amf_version == AMFVersion::AMF0 || num >= (1 << 28) || num < -(1 << 28) {
-                Some(AmfValue::Number(num as f64))
-            } else {
-                Some(AmfValue::Integer(num))
-            }
-        }
+        // Integers are unsupported in AMF0.
+        Value::Integer(num) => Some(AmfValue::Number(num as f64)),
         Value::String(s) => Some(AmfValue::String(s.to_string())),
         Value::Object(o) => {
             // TODO: Find a more general rule for which object types should be skipped,
@@ -41,7 +36,7 @@ pub fn serialize_value<'gc>(
                 Some(AmfValue::Undefined)
             } else if o.as_array_storage().is_some() {
                 let mut values = Vec::new();
-                recursive_serialize(activation, o, &mut values, amf_version).unwrap();
+                recursive_serialize(activation, o, &mut values).unwrap();
 
                 let mut dense = vec![];
                 let mut sparse = vec![];
@@ -95,7 +90,7 @@ pub fn serialize_value<'gc>(
                     let obj_vec: Vec<_> = vec
                         .iter()
                         .map(|v| {
-                            serialize_value(activation, v, amf_version)
+                            serialize_value(activation, v)
                                 .expect("Unexpected non-object value in object vector")
                         })
                         .collect();
@@ -126,7 +121,7 @@ pub fn serialize_value<'gc>(
                     .map_or(false, |c| c == activation.avm2().classes().object);
                 if is_object {
                     let mut object_body = Vec::new();
-                    recursive_serialize(activation, o, &mut object_body, amf_version).unwrap();
+                    recursive_serialize(activation, o, &mut object_body).unwrap();
                     Some(AmfValue::Object(
                         object_body,
                         Some(ClassDefinition {
@@ -152,7 +147,6 @@ pub fn recursive_serialize<'gc>(
     activation: &mut Activation<'_, 'gc>,
     obj: Object<'gc>,
     elements: &mut Vec<Element>,
-    amf_version: AMFVersion,
 ) -> Result<(), Error<'gc>> {
     let mut last_index = obj.get_next_enumerant(0, activation)?;
     while let Some(index) = last_index {
@@ -161,7 +155,7 @@ pub fn recursive_serialize<'gc>(
             .coerce_to_string(activation)?;
         let value = obj.get_public_property(name, activation)?;
 
-        if let Some(value) = serialize_value(activation, value, amf_version) {
+        if let Some(value) = serialize_value(activation, value) {
             elements.push(Element::new(name.to_utf8_lossy(), value));
         }
         last_index = obj.get_next_enumerant(index, activation)?;
@@ -322,3 +316,1025 @@ pub fn deserialize_lso<'gc>(
 
     Ok(obj)
 }
+
+const AMF3_UNDEFINED: u8 = 0x00;
+const AMF3_NULL: u8 = 0x01;
+const AMF3_FALSE: u8 = 0x02;
+const AMF3_TRUE: u8 = 0x03;
+const AMF3_INTEGER: u8 = 0x04;
+const AMF3_DOUBLE: u8 = 0x05;
+const AMF3_STRING: u8 = 0x06;
+const AMF3_XML_DOC: u8 = 0x07;
+const AMF3_DATE: u8 = 0x08;
+const AMF3_ARRAY: u8 = 0x09;
+const AMF3_OBJECT: u8 = 0x0A;
+const AMF3_XML: u8 = 0x0B;
+const AMF3_BYTE_ARRAY: u8 = 0x0C;
+const AMF3_VECTOR_INT: u8 = 0x0D;
+const AMF3_VECTOR_UINT: u8 = 0x0E;
+const AMF3_VECTOR_DOUBLE: u8 = 0x0F;
+const AMF3_VECTOR_OBJECT: u8 = 0x10;
+const AMF3_DICTIONARY: u8 = 0x11;
+
+/// The smallest and largest integers that fit in an AMF3 integer (a signed 29-bit value).
+const AMF3_INTEGER_MIN: i32 = -(1 << 28);
+const AMF3_INTEGER_MAX: i32 = (1 << 28) - 1;
+
+/// The traits of an AMF3 object: its class alias, and the names of its sealed properties.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct Amf3Traits<'gc> {
+    class_name: AvmString<'gc>,
+    sealed: Vec<AvmString<'gc>>,
+    is_dynamic: bool,
+    is_externalizable: bool,
+}
+
+/// Whether a value is left out when it's the value of a dynamic property.
+fn is_skipped_property(value: Value<'_>) -> bool {
+    matches!(value, Value::Object(o) if o.as_executable().is_some())
+}
+
+/// Returns the names and values of the enumerable properties of an object.
+pub(crate) fn enumerable_properties<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    obj: Object<'gc>,
+) -> Result<Vec<(Value<'gc>, Value<'gc>)>, Error<'gc>> {
+    let mut properties = Vec::new();
+    let mut last_index = obj.get_next_enumerant(0, activation)?;
+    while let Some(index) = last_index {
+        let name = obj.get_enumerant_name(index, activation)?;
+        let value = obj.get_enumerant_value(index, activation)?;
+        properties.push((name, value));
+        last_index = obj.get_next_enumerant(index, activation)?;
+    }
+    Ok(properties)
+}
+
+/// Whether an object implements `flash.utils.IExternalizable`.
+fn is_externalizable<'gc>(activation: &mut Activation<'_, 'gc>, obj: Object<'gc>) -> bool {
+    let iexternalizable = activation.avm2().classes().iexternalizable;
+    obj.is_of_type(
+        iexternalizable.inner_class_definition(),
+        &mut activation.context,
+    )
+}
+
+/// Writes values to a `ByteArray` or a `Socket` in the AMF3 format.
+///
+/// Strings, objects and traits that were already written are written as references
+/// to their first occurrence, so that shared and cyclic references survive a roundtrip.
+pub struct Amf3Writer<'gc> {
+    /// The `ByteArray` or `Socket` being written to. `IExternalizable` objects write their
+    /// own data to it.
+    target: Object<'gc>,
+
+    /// Data that hasn't been written to the target yet.
+    buffer: Vec<u8>,
+
+    strings: HashMap<AvmString<'gc>, u32>,
+    objects: HashMap<*const ObjectPtr, u32>,
+    traits: HashMap<Amf3Traits<'gc>, u32>,
+}
+
+impl<'gc> Amf3Writer<'gc> {
+    pub fn new(target: Object<'gc>) -> Self {
+        Self {
+            target,
+            buffer: Vec::new(),
+            strings: HashMap::new(),
+            objects: HashMap::new(),
+            traits: HashMap::new(),
+        }
+    }
+
+    /// Writes any buffered data to the target.
+    pub fn flush(&mut self, activation: &mut Activation<'_, 'gc>) -> Result<(), Error<'gc>> {
+        if !self.buffer.is_empty() {
+            if let Some(mut bytearray) = self.target.as_bytearray_mut(activation.context.gc_context)
+            {
+                bytearray
+                    .write_bytes(&self.buffer)
+                    .map_err(|e| e.to_avm(activation))?;
+            } else if let Some(socket) = self.target.as_socket() {
+                socket.write_bytes(&self.buffer);
+            }
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+
+    fn write_u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    fn write_u29(&mut self, value: u32) {
+        let value = value & 0x1FFF_FFFF;
+        if value < 0x80 {
+            self.buffer.push(value as u8);
+        } else if value < 0x4000 {
+            self.buffer
+                .extend_from_slice(&[(value >> 7) as u8 | 0x80, (value & 0x7F) as u8]);
+        } else if value < 0x20_0000 {
+            self.buffer.extend_from_slice(&[
+                (value >> 14) as u8 | 0x80,
+                ((value >> 7) & 0x7F) as u8 | 0x80,
+                (value & 0x7F) as u8,
+            ]);
+        } else {
+            self.buffer.extend_from_slice(&[
+                (value >> 22) as u8 | 0x80,
+                ((value >> 15) & 0x7F) as u8 | 0x80,
+                ((value >> 8) & 0x7F) as u8 | 0x80,
+                (value & 0xFF) as u8,
+            ]);
+        }
+    }
+
+    fn write_f64(&mut self, value: f64) {
+        self.buffer.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn write_utf8(&mut self, value: AvmString<'gc>) {
+        let value = value.to_utf8_lossy();
+        self.write_u29(((value.len() as u32) << 1) | 1);
+        self.buffer.extend_from_slice(value.as_bytes());
+    }
+
+    /// Writes a string, or a reference to it if it was written before.
+    fn write_string(&mut self, value: AvmString<'gc>) {
+        // The empty string is never sent by reference.
+        if value.is_empty() {
+            self.write_u29(1);
+        } else if let Some(index) = self.strings.get(&value) {
+            self.write_u29(index << 1);
+        } else {
+            self.strings.insert(value, self.strings.len() as u32);
+            self.write_utf8(value);
+        }
+    }
+
+    /// Writes a reference to an object if it was written before, and returns `true`.
+    /// Otherwise, the object is added to the reference table and `false` is returned.
+    fn write_object_reference(&mut self, obj: Object<'gc>) -> bool {
+        if let Some(index) = self.objects.get(&obj.as_ptr()) {
+            self.write_u29(index << 1);
+            true
+        } else {
+            self.objects.insert(obj.as_ptr(), self.objects.len() as u32);
+            false
+        }
+    }
+
+    fn write_traits(&mut self, traits: Amf3Traits<'gc>) {
+        if let Some(index) = self.traits.get(&traits) {
+            self.write_u29((index << 2) | 0b01);
+            return;
+        }
+
+        if traits.is_externalizable {
+            self.write_u29(0b111);
+            self.write_string(traits.class_name);
+        } else {
+            let dynamic_flag = if traits.is_dynamic { 0b1000 } else { 0 };
+            self.write_u29(((traits.sealed.len() as u32) << 4) | dynamic_flag | 0b011);
+            self.write_string(traits.class_name);
+            for name in &traits.sealed {
+                self.write_string(*name);
+            }
+        }
+        self.traits.insert(traits, self.traits.len() as u32);
+    }
+
+    /// Writes the dynamic properties of an object, followed by the empty string that ends them.
+    fn write_dynamic_properties(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        properties: Vec<(Value<'gc>, Value<'gc>)>,
+    ) -> Result<(), Error<'gc>> {
+        for (name, value) in properties {
+            if !is_skipped_property(value) {
+                let name = name.coerce_to_string(activation)?;
+                self.write_string(name);
+                self.write_value(activation, value)?;
+            }
+        }
+        self.write_string("".into());
+        Ok(())
+    }
+
+    pub fn write_value(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        value: Value<'gc>,
+    ) -> Result<(), Error<'gc>> {
+        match value {
+            Value::Undefined => self.write_u8(AMF3_UNDEFINED),
+            Value::Null => self.write_u8(AMF3_NULL),
+            Value::Bool(false) => self.write_u8(AMF3_FALSE),
+            Value::Bool(true) => self.write_u8(AMF3_TRUE),
+            Value::Integer(i) if (AMF3_INTEGER_MIN..=AMF3_INTEGER_MAX).contains(&i) => {
+                self.write_u8(AMF3_INTEGER);
+                self.write_u29(i as u32);
+            }
+            Value::Integer(i) => {
+                self.write_u8(AMF3_DOUBLE);
+                self.write_f64(i as f64);
+            }
+            Value::Number(n) => {
+                self.write_u8(AMF3_DOUBLE);
+                self.write_f64(n);
+            }
+            Value::String(s) => {
+                self.write_u8(AMF3_STRING);
+                self.write_string(s);
+            }
+            Value::Object(o) => self.write_object(activation, o)?,
+        }
+        Ok(())
+    }
+
+    fn write_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        obj: Object<'gc>,
+    ) -> Result<(), Error<'gc>> {
+        if obj.as_executable().is_some() || obj.as_display_object().is_some() {
+            self.write_u8(AMF3_UNDEFINED);
+        } else if let Some(date) = obj.as_date_object() {
+            self.write_u8(AMF3_DATE);
+            if !self.write_object_reference(obj) {
+                self.write_u29(1);
+                let time = date
+                    .date_time()
+                    .map_or(f64::NAN, |date_time| date_time.timestamp_millis() as f64);
+                self.write_f64(time);
+            }
+        } else if let Some(xml) = obj.as_xml_object() {
+            self.write_u8(AMF3_XML);
+            if !self.write_object_reference(obj) {
+                let string = xml.node().xml_to_xml_string(activation)?;
+                self.write_utf8(string);
+            }
+        } else if let Some(bytearray) = obj.as_bytearray() {
+            let bytes = bytearray.bytes().to_vec();
+            drop(bytearray);
+            self.write_u8(AMF3_BYTE_ARRAY);
+            if !self.write_object_reference(obj) {
+                self.write_u29(((bytes.len() as u32) << 1) | 1);
+                self.buffer.extend_from_slice(&bytes);
+            }
+        } else if obj.as_array_storage().is_some() {
+            self.write_u8(AMF3_ARRAY);
+            if !self.write_object_reference(obj) {
+                self.write_array(activation, obj)?;
+            }
+        } else if obj.as_vector_storage().is_some() {
+            self.write_vector(activation, obj)?;
+        } else if obj.as_dictionary_object().is_some() {
+            self.write_u8(AMF3_DICTIONARY);
+            if !self.write_object_reference(obj) {
+                let entries = enumerable_properties(activation, obj)?;
+                self.write_u29(((entries.len() as u32) << 1) | 1);
+                // Weak keys aren't supported, so dictionaries are always written with strong keys.
+                self.write_u8(0);
+                for (key, value) in entries {
+                    self.write_value(activation, key)?;
+                    self.write_value(activation, value)?;
+                }
+            }
+        } else {
+            self.write_u8(AMF3_OBJECT);
+            if !self.write_object_reference(obj) {
+                self.write_typed_object(activation, obj)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_array(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        array: Object<'gc>,
+    ) -> Result<(), Error<'gc>> {
+        // Arrays with holes are written entirely as associative arrays.
+        let dense: Vec<Value<'gc>> = match array.as_array_storage() {
+            Some(storage) if storage.iter().all(|value| value.is_some()) => {
+                storage.iter().flatten().collect()
+            }
+            _ => Vec::new(),
+        };
+
+        self.write_u29(((dense.len() as u32) << 1) | 1);
+        let properties = enumerable_properties(activation, array)?
+            .into_iter()
+            .filter(|(name, _)| match name {
+                Value::Integer(i) => *i < 0 || *i as usize >= dense.len(),
+                _ => true,
+            })
+            .collect();
+        self.write_dynamic_properties(activation, properties)?;
+        for value in dense {
+            self.write_value(activation, value)?;
+        }
+        Ok(())
+    }
+
+    fn write_vector(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        vector: Object<'gc>,
+    ) -> Result<(), Error<'gc>> {
+        let (values, is_fixed, value_type) = match vector.as_vector_storage() {
+            Some(storage) => (
+                storage.iter().collect::<Vec<_>>(),
+                storage.is_fixed(),
+                storage.value_type(),
+            ),
+            None => return Ok(()),
+        };
+
+        let classes = activation.avm2().classes();
+        let marker = if value_type == classes.int {
+            AMF3_VECTOR_INT
+        } else if value_type == classes.uint {
+            AMF3_VECTOR_UINT
+        } else if value_type == classes.number {
+            AMF3_VECTOR_DOUBLE
+        } else {
+            AMF3_VECTOR_OBJECT
+        };
+
+        self.write_u8(marker);
+        if self.write_object_reference(vector) {
+            return Ok(());
+        }
+        self.write_u29(((values.len() as u32) << 1) | 1);
+        self.write_u8(is_fixed as u8);
+
+        match marker {
+            AMF3_VECTOR_INT => {
+                for value in values {
+                    let value = value.coerce_to_i32(activation)?;
+                    self.buffer.extend_from_slice(&value.to_be_bytes());
+                }
+            }
+            AMF3_VECTOR_UINT => {
+                for value in values {
+                    let value = value.coerce_to_u32(activation)?;
+                    self.buffer.extend_from_slice(&value.to_be_bytes());
+                }
+            }
+            AMF3_VECTOR_DOUBLE => {
+                for value in values {
+                    let value = value.coerce_to_number(activation)?;
+                    self.write_f64(value);
+                }
+            }
+            _ => {
+                let type_name = activation
+                    .avm2()
+                    .get_alias_by_class(value_type)
+                    .unwrap_or_default();
+                self.write_string(type_name);
+                for value in values {
+                    self.write_value(activation, value)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_typed_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        obj: Object<'gc>,
+    ) -> Result<(), Error<'gc>> {
+        let class = obj.instance_of();
+        let class_name = class
+            .and_then(|class| activation.avm2().get_alias_by_class(class))
+            .unwrap_or_default();
+
+        if is_externalizable(activation, obj) {
+            self.write_traits(Amf3Traits {
+                class_name,
+                sealed: Vec::new(),
+                is_dynamic: false,
+                is_externalizable: true,
+            });
+
+            // The object writes its own data, after everything that was written so far.
+            self.flush(activation)?;
+            obj.call_public_property("writeExternal", &[self.target.into()], activation)?;
+            return Ok(());
+        }
+
+        let is_object = class.map_or(true, |class| class == activation.avm2().classes().object);
+        let is_dynamic = class.map_or(true, |class| {
+            !class.inner_class_definition().read().is_sealed()
+        });
+
+        // The sealed properties are the public variables and read-write accessors of the class.
+        let mut sealed: Vec<AvmString<'gc>> = match obj.vtable() {
+            Some(vtable) if !is_object => vtable
+                .public_properties()
+                .into_iter()
+                .filter(|(_, property)| {
+                    matches!(
+                        property,
+                        Property::Slot { .. }
+                            | Property::Virtual {
+                                get: Some(_),
+                                set: Some(_)
+                            }
+                    )
+                })
+                .map(|(name, _)| name)
+                .collect(),
+            _ => Vec::new(),
+        };
+        sealed.sort_unstable();
+
+        self.write_traits(Amf3Traits {
+            class_name,
+            sealed: sealed.clone(),
+            is_dynamic,
+            is_externalizable: false,
+        });
+
+        for name in sealed {
+            let value = obj.get_public_property(name, activation)?;
+            self.write_value(activation, value)?;
+        }
+
+        if is_dynamic {
+            let properties = enumerable_properties(activation, obj)?;
+            self.write_dynamic_properties(activation, properties)?;
+        }
+        Ok(())
+    }
+}
+
+/// Reads values in the AMF3 format from a `ByteArray` or a `Socket`.
+///
+/// Nothing is consumed from the source until [`Amf3Reader::commit`] is called, so that
+/// a value that fails to be read can be read again once more data is available.
+pub struct Amf3Reader<'gc> {
+    /// The `ByteArray` or `Socket` being read. `IExternalizable` objects read their own data
+    /// from it.
+    source: Object<'gc>,
+
+    /// The position of the next byte to read, relative to the start of the source's data.
+    position: usize,
+
+    strings: Vec<AvmString<'gc>>,
+    objects: Vec<Value<'gc>>,
+    traits: Vec<Amf3Traits<'gc>>,
+
+    /// How many values are being read inside each other.
+    depth: usize,
+}
+
+/// How deeply values can be nested before reading them fails, so that malformed data
+/// can't overflow the stack.
+const MAX_READ_DEPTH: usize = 256;
+
+impl<'gc> Amf3Reader<'gc> {
+    /// Creates a reader that starts at the current position of the given `ByteArray`,
+    /// or at the start of the received data of the given `Socket`.
+    pub fn new(source: Object<'gc>) -> Self {
+        Self {
+            source,
+            position: Self::source_position(source),
+            strings: Vec::new(),
+            objects: Vec::new(),
+            traits: Vec::new(),
+            depth: 0,
+        }
+    }
+
+    /// The position the source will be read from next.
+    fn source_position(source: Object<'gc>) -> usize {
+        // Sockets discard their data as it's read, so they're always read from the start.
+        source.as_bytearray().map_or(0, |b| b.position())
+    }
+
+    /// The position of the next byte to read.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Consumes everything that was read so far from the source.
+    pub fn commit(&mut self) {
+        if let Some(bytearray) = self.source.as_bytearray() {
+            bytearray.set_position(self.position);
+        } else if let Some(socket) = self.source.as_socket() {
+            let mut buffer = socket.read_buffer();
+            let len = self.position.min(buffer.len());
+            buffer.drain(..len);
+            self.position = 0;
+        }
+    }
+
+    /// The number of bytes left to read.
+    fn remaining(&self) -> usize {
+        if let Some(bytearray) = self.source.as_bytearray() {
+            bytearray.len().saturating_sub(self.position)
+        } else if let Some(socket) = self.source.as_socket() {
+            socket.read_buffer().len().saturating_sub(self.position)
+        } else {
+            0
+        }
+    }
+
+    fn read_bytes(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        len: usize,
+    ) -> Result<Vec<u8>, Error<'gc>> {
+        let bytes = if let Some(bytearray) = self.source.as_bytearray() {
+            bytearray
+                .read_at(len, self.position)
+                .ok()
+                .map(<[u8]>::to_vec)
+        } else if let Some(socket) = self.source.as_socket() {
+            let end = self.position.saturating_add(len);
+            socket
+                .read_buffer()
+                .get(self.position..end)
+                .map(<[u8]>::to_vec)
+        } else {
+            None
+        };
+        match bytes {
+            Some(bytes) => {
+                self.position += len;
+                Ok(bytes)
+            }
+            None => Err(ByteArrayError::EndOfFile.to_avm(activation)),
+        }
+    }
+
+    fn read_u8(&mut self, activation: &mut Activation<'_, 'gc>) -> Result<u8, Error<'gc>> {
+        Ok(self.read_bytes(activation, 1)?[0])
+    }
+
+    fn read_u29(&mut self, activation: &mut Activation<'_, 'gc>) -> Result<u32, Error<'gc>> {
+        let mut value = 0;
+        for _ in 0..3 {
+            let byte = self.read_u8(activation)?;
+            value = (value << 7) | u32::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        let byte = self.read_u8(activation)?;
+        Ok((value << 8) | u32::from(byte))
+    }
+
+    fn read_f64(&mut self, activation: &mut Activation<'_, 'gc>) -> Result<f64, Error<'gc>> {
+        let bytes = self.read_bytes(activation, 8)?;
+        Ok(f64::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
+    fn read_utf8(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        len: usize,
+    ) -> Result<AvmString<'gc>, Error<'gc>> {
+        let bytes = self.read_bytes(activation, len)?;
+        Ok(AvmString::new_utf8_bytes(
+            activation.context.gc_context,
+            &bytes,
+        ))
+    }
+
+    /// Reads a string, or a reference to a string that was read before.
+    fn read_string(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<AvmString<'gc>, Error<'gc>> {
+        let header = self.read_u29(activation)?;
+        if header & 1 == 0 {
+            return self
+                .strings
+                .get((header >> 1) as usize)
+                .copied()
+                .ok_or_else(|| "Error: Invalid AMF3 string reference".into());
+        }
+
+        let len = (header >> 1) as usize;
+        if len == 0 {
+            return Ok("".into());
+        }
+        let string = self.read_utf8(activation, len)?;
+        self.strings.push(string);
+        Ok(string)
+    }
+
+    /// Reads the header of an object. Returns `Ok(Err(value))` if it's a reference
+    /// to an object that was read before, or `Ok(Ok(header))` with the remaining
+    /// bits of the header otherwise.
+    fn read_object_header(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Result<u32, Value<'gc>>, Error<'gc>> {
+        let header = self.read_u29(activation)?;
+        if header & 1 == 0 {
+            return match self.objects.get((header >> 1) as usize) {
+                Some(value) => Ok(Err(*value)),
+                None => Err("Error: Invalid AMF3 object reference".into()),
+            };
+        }
+        Ok(Ok(header >> 1))
+    }
+
+    fn read_traits(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        header: u32,
+    ) -> Result<Amf3Traits<'gc>, Error<'gc>> {
+        if header & 1 == 0 {
+            return self
+                .traits
+                .get((header >> 1) as usize)
+                .cloned()
+                .ok_or_else(|| "Error: Invalid AMF3 traits reference".into());
+        }
+
+        let is_externalizable = header & 0b10 != 0;
+        let is_dynamic = header & 0b100 != 0;
+        let class_name = self.read_string(activation)?;
+        let mut sealed = Vec::new();
+        if !is_externalizable {
+            for _ in 0..(header >> 3) {
+                sealed.push(self.read_string(activation)?);
+            }
+        }
+
+        let traits = Amf3Traits {
+            class_name,
+            sealed,
+            is_dynamic,
+            is_externalizable,
+        };
+        self.traits.push(traits.clone());
+        Ok(traits)
+    }
+
+    pub fn read_value(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        if self.depth >= MAX_READ_DEPTH {
+            return Err(Error::AvmError(range_error(
+                activation,
+                "Error #1023: Stack overflow occurred.",
+                1023,
+            )?));
+        }
+
+        self.depth += 1;
+        let value = self.read_value_inner(activation);
+        self.depth -= 1;
+        value
+    }
+
+    fn read_value_inner(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let marker = self.read_u8(activation)?;
+        Ok(match marker {
+            AMF3_UNDEFINED => Value::Undefined,
+            AMF3_NULL => Value::Null,
+            AMF3_FALSE => false.into(),
+            AMF3_TRUE => true.into(),
+            AMF3_INTEGER => {
+                // Sign-extend the 29-bit value.
+                let value = self.read_u29(activation)?;
+                (((value << 3) as i32) >> 3).into()
+            }
+            AMF3_DOUBLE => self.read_f64(activation)?.into(),
+            AMF3_STRING => self.read_string(activation)?.into(),
+            AMF3_XML_DOC | AMF3_XML => {
+                let len = match self.read_object_header(activation)? {
+                    Ok(len) => len as usize,
+                    Err(value) => return Ok(value),
+                };
+                let string = self.read_utf8(activation, len)?;
+                let xml = activation
+                    .avm2()
+                    .classes()
+                    .xml
+                    .construct(activation, &[string.into()])?;
+                self.objects.push(xml.into());
+                xml.into()
+            }
+            AMF3_DATE => {
+                if let Err(value) = self.read_object_header(activation)? {
+                    return Ok(value);
+                }
+                let time = self.read_f64(activation)?;
+                let date = activation
+                    .avm2()
+                    .classes()
+                    .date
+                    .construct(activation, &[time.into()])?;
+                self.objects.push(date.into());
+                date.into()
+            }
+            AMF3_ARRAY => {
+                let dense_len = match self.read_object_header(activation)? {
+                    Ok(len) => len as usize,
+                    Err(value) => return Ok(value),
+                };
+                let mut array = ArrayObject::empty(activation)?;
+                self.objects.push(array.into());
+                self.read_dynamic_properties(activation, array)?;
+                for i in 0..dense_len {
+                    let value = self.read_value(activation)?;
+                    if let Some(mut storage) =
+                        array.as_array_storage_mut(activation.context.gc_context)
+                    {
+                        storage.set(i, value);
+                    }
+                }
+                array.into()
+            }
+            AMF3_OBJECT => {
+                let header = match self.read_object_header(activation)? {
+                    Ok(header) => header,
+                    Err(value) => return Ok(value),
+                };
+                self.read_typed_object(activation, header)?
+            }
+            AMF3_BYTE_ARRAY => {
+                let len = match self.read_object_header(activation)? {
+                    Ok(len) => len as usize,
+                    Err(value) => return Ok(value),
+                };
+                let bytes = self.read_bytes(activation, len)?;
+                let storage = ByteArrayStorage::from_vec(bytes);
+                let bytearray = ByteArrayObject::from_storage(activation, storage)?;
+                self.objects.push(bytearray.into());
+                bytearray.into()
+            }
+            AMF3_VECTOR_INT | AMF3_VECTOR_UINT | AMF3_VECTOR_DOUBLE | AMF3_VECTOR_OBJECT => {
+                let len = match self.read_object_header(activation)? {
+                    Ok(len) => len as usize,
+                    Err(value) => return Ok(value),
+                };
+                self.read_vector(activation, marker, len)?
+            }
+            AMF3_DICTIONARY => {
+                let len = match self.read_object_header(activation)? {
+                    Ok(len) => len as usize,
+                    Err(value) => return Ok(value),
+                };
+                // Whether the keys are weak. Weak keys aren't supported.
+                self.read_u8(activation)?;
+
+                let mut dictionary = activation
+                    .avm2()
+                    .classes()
+                    .dictionary
+                    .construct(activation, &[])?;
+                self.objects.push(dictionary.into());
+                for _ in 0..len {
+                    let key = self.read_value(activation)?;
+                    let value = self.read_value(activation)?;
+                    match (key, dictionary.as_dictionary_object()) {
+                        (Value::Object(key), Some(dictionary)) => {
+                            dictionary.set_property_by_object(
+                                key,
+                                value,
+                                activation.context.gc_context,
+                            );
+                        }
+                        _ => {
+                            let key = key.coerce_to_string(activation)?;
+                            dictionary.set_public_property(key, value, activation)?;
+                        }
+                    }
+                }
+                dictionary.into()
+            }
+            _ => return Err(format!("Error: Invalid AMF3 marker {marker}").into()),
+        })
+    }
+
+    /// Reads dynamic properties into an object, up to the empty string that ends them.
+    fn read_dynamic_properties(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        mut obj: Object<'gc>,
+    ) -> Result<(), Error<'gc>> {
+        loop {
+            let name = self.read_string(activation)?;
+            if name.is_empty() {
+                return Ok(());
+            }
+            let value = self.read_value(activation)?;
+            obj.set_public_property(name, value, activation)?;
+        }
+    }
+
+    fn read_vector(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        marker: u8,
+        len: usize,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let is_fixed = self.read_u8(activation)? != 0;
+
+        let value_type = match marker {
+            AMF3_VECTOR_INT => activation.avm2().classes().int,
+            AMF3_VECTOR_UINT => activation.avm2().classes().uint,
+            AMF3_VECTOR_DOUBLE => activation.avm2().classes().number,
+            _ => {
+                let type_name = self.read_string(activation)?;
+                activation
+                    .avm2()
+                    .get_class_by_alias(type_name)
+                    .unwrap_or_else(|| activation.avm2().classes().object)
+            }
+        };
+
+        // The vector is created before its values are read, as they may refer to it.
+        let storage = VectorStorage::new(0, false, value_type, activation);
+        let vector = VectorObject::from_vector(storage, activation)?;
+        self.objects.push(vector.into());
+
+        // Every value takes at least a byte, so a length beyond the end of the data can't be
+        // trusted when reserving space.
+        let mut values = Vec::with_capacity(len.min(self.remaining()));
+        for _ in 0..len {
+            let value = match marker {
+                AMF3_VECTOR_INT => {
+                    let bytes = self.read_bytes(activation, 4)?;
+                    i32::from_be_bytes(bytes.try_into().unwrap()).into()
+                }
+                AMF3_VECTOR_UINT => {
+                    let bytes = self.read_bytes(activation, 4)?;
+                    u32::from_be_bytes(bytes.try_into().unwrap()).into()
+                }
+                AMF3_VECTOR_DOUBLE => self.read_f64(activation)?.into(),
+                _ => self.read_value(activation)?,
+            };
+            values.push(value);
+        }
+
+        let values = values
+            .into_iter()
+            .map(|value| value.coerce_to_type(activation, value_type.inner_class_definition()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(mut storage) = vector.as_vector_storage_mut(activation.context.gc_context) {
+            storage.replace_storage(values);
+            storage.set_is_fixed(is_fixed);
+        }
+        Ok(vector.into())
+    }
+
+    fn read_typed_object(
+        &mut self,
+        activation: &mut Activation<'_, 'gc>,
+        header: u32,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        let traits = self.read_traits(activation, header)?;
+        let class = if traits.class_name.is_empty() {
+            None
+        } else {
+            activation.avm2().get_class_by_alias(traits.class_name)
+        };
+
+        if traits.is_externalizable {
+            let Some(class) = class else {
+                return Err(Error::AvmError(reference_error(
+                    activation,
+                    &format!(
+                        "Error #1014: Class {} could not be found.",
+                        traits.class_name
+                    ),
+                    1014,
+                )?));
+            };
+
+            let obj = class.construct(activation, &[])?;
+            if !is_externalizable(activation, obj) {
+                return Err(Error::AvmError(argument_error(
+                    activation,
+                    &format!(
+                        "Error #2173: Unable to read object in stream. The class {} does not implement flash.utils.IExternalizable but is aliased to an externalizable class.",
+                        traits.class_name
+                    ),
+                    2173,
+                )?));
+            }
+            self.objects.push(obj.into());
+
+            // The object reads its own data, starting from the current position.
+            self.commit();
+            obj.call_public_property("readExternal", &[self.source.into()], activation)?;
+            self.position = Self::source_position(self.source);
+            return Ok(obj.into());
+        }
+
+        // Objects of unknown classes are read as plain objects.
+        let class = class.unwrap_or_else(|| activation.avm2().classes().object);
+        let mut obj = class.construct(activation, &[])?;
+        self.objects.push(obj.into());
+
+        for name in traits.sealed {
+            let value = self.read_value(activation)?;
+            obj.set_public_property(name, value, activation)?;
+        }
+        if traits.is_dynamic {
+            self.read_dynamic_properties(activation, obj)?;
+        }
+        Ok(obj.into())
+    }
+}
+
+/// The AMF version of the shared object files that are written.
+const LSO_AMF3_VERSION: u32 = 3;
+
+/// Serializes the properties of a shared object's `data` to a shared object file,
+/// with its body in the AMF3 format.
+///
+/// Returns `None` if there are no properties to write.
+pub fn serialize_lso<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    name: &str,
+    data: Object<'gc>,
+) -> Result<Option<Vec<u8>>, Error<'gc>> {
+    let body = ByteArrayObject::from_storage(activation, ByteArrayStorage::new())?;
+    let mut writer = Amf3Writer::new(body);
+    let mut is_empty = true;
+    for (name, value) in enumerable_properties(activation, data)? {
+        if is_skipped_property(value) {
+            continue;
+        }
+        let name = name.coerce_to_string(activation)?;
+        writer.write_string(name);
+        writer.write_value(activation, value)?;
+        writer.write_u8(0);
+        is_empty = false;
+    }
+    writer.flush(activation)?;
+    if is_empty {
+        return Ok(None);
+    }
+
+    let name = name.split('/').last().unwrap_or("<unknown>");
+    let mut bytes = vec![0x00, 0xBF, 0, 0, 0, 0];
+    bytes.extend_from_slice(b"TCSO");
+    bytes.extend_from_slice(&[0x00, 0x04, 0x00, 0x00, 0x00, 0x00]);
+    bytes.extend_from_slice(&(name.len() as u16).to_be_bytes());
+    bytes.extend_from_slice(name.as_bytes());
+    bytes.extend_from_slice(&LSO_AMF3_VERSION.to_be_bytes());
+    if let Some(body) = body.as_bytearray() {
+        bytes.extend_from_slice(body.bytes());
+    }
+
+    // The length doesn't include the 6 bytes before the "TCSO" signature.
+    let len = (bytes.len() - 6) as u32;
+    bytes[2..6].copy_from_slice(&len.to_be_bytes());
+    Ok(Some(bytes))
+}
+
+/// Deserializes a shared object file with its body in the AMF3 format into an object
+/// containing the properties stored.
+///
+/// Returns `None` if the file isn't an AMF3 shared object file.
+pub fn deserialize_amf3_lso<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    bytes: &[u8],
+) -> Result<Option<Object<'gc>>, Error<'gc>> {
+    if bytes.len() < 18 || bytes[0..2] != [0x00, 0xBF] || &bytes[6..10] != b"TCSO" {
+        return Ok(None);
+    }
+    let name_len = u16::from_be_bytes([bytes[16], bytes[17]]) as usize;
+    let body_start = 18 + name_len + 4;
+    let Some(version) = bytes.get(body_start - 4..body_start) else {
+        return Ok(None);
+    };
+    if version != LSO_AMF3_VERSION.to_be_bytes() {
+        return Ok(None);
+    }
+
+    let body = ByteArrayStorage::from_vec(bytes[body_start..].to_vec());
+    let body = ByteArrayObject::from_storage(activation, body)?;
+    let mut reader = Amf3Reader::new(body);
+    let mut obj = activation
+        .avm2()
+        .classes()
+        .object
+        .construct(activation, &[])?;
+    while reader.position() < bytes.len() - body_start {
+        let name = reader.read_string(activation)?;
+        let value = reader.read_value(activation)?;
+        reader.read_u8(activation)?;
+        obj.set_public_property(name, value, activation)?;
+    }
+    Ok(Some(obj))
+}
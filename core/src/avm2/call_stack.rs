@@ -45,35 +45,44 @@ impl<'gc> CallStack<'gc> {
     pub fn display(&self, output: &mut WString) {
         for call in self.stack.iter().rev() {
             output.push_utf8("\n\tat ");
-            match call {
-                CallNode::GlobalInit(script) => {
-                    let name = if let Some(tuint) = script.translation_unit() {
-                        if let Some(name) = tuint.name() {
-                            name.to_utf8_lossy().to_string()
-                        } else {
-                            "<No name>".to_string()
-                        }
-                    } else {
-                        "<No translation unit>".to_string()
-                    };
-
-                    // NOTE: We intentionally diverge from Flash Player's output
-                    // here - everything with the [] brackets is extra information
-                    // added by Ruffle
-                    output.push_utf8(&format!("global$init() [TU={}]", name));
-                }
-                CallNode::Method { method, superclass } => {
-                    display_function(output, method, *superclass)
-                }
-            }
+            display_call(output, call);
         }
     }
 
+    /// The name of the innermost function, as it's shown in stack traces.
+    pub fn current_function_name(&self) -> Option<String> {
+        let mut output = WString::new();
+        display_call(&mut output, self.stack.last()?);
+        Some(output.to_string())
+    }
+
     pub fn is_empty(&self) -> bool {
         self.stack.is_empty()
     }
 }
 
+fn display_call(output: &mut WString, call: &CallNode<'_>) {
+    match call {
+        CallNode::GlobalInit(script) => {
+            let name = if let Some(tuint) = script.translation_unit() {
+                if let Some(name) = tuint.name() {
+                    name.to_utf8_lossy().to_string()
+                } else {
+                    "<No name>".to_string()
+                }
+            } else {
+                "<No translation unit>".to_string()
+            };
+
+            // NOTE: We intentionally diverge from Flash Player's output
+            // here - everything with the [] brackets is extra information
+            // added by Ruffle
+            output.push_utf8(&format!("global$init() [TU={}]", name));
+        }
+        CallNode::Method { method, superclass } => display_function(output, method, *superclass),
+    }
+}
+
 impl<'gc> Default for CallStack<'gc> {
     fn default() -> Self {
         Self::new()
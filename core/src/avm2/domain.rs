@@ -106,34 +106,34 @@ impl<'gc> Domain<'gc> {
     pub fn has_definition(self, name: QName<'gc>) -> bool {
         let read = self.0.read();
 
-        if read.defs.contains_key(name) {
-            return true;
-        }
-
         if let Some(parent) = read.parent {
-            return parent.has_definition(name);
+            if parent.has_definition(name) {
+                return true;
+            }
         }
 
-        false
+        read.defs.contains_key(name)
     }
 
     /// Determine if a class has been defined within the current domain (including parents)
     pub fn has_class(self, name: QName<'gc>) -> bool {
         let read = self.0.read();
 
-        if read.classes.contains_key(name) {
-            return true;
-        }
-
         if let Some(parent) = read.parent {
-            return parent.has_class(name);
+            if parent.has_class(name) {
+                return true;
+            }
         }
 
-        false
+        read.classes.contains_key(name)
     }
 
     /// Resolve a Multiname and return the script that provided it.
     ///
+    /// Parent domains are searched first, so that a definition loaded into a
+    /// parent domain (such as a runtime shared library) takes precedence over
+    /// one with the same name in a child domain.
+    ///
     /// If a name does not exist or cannot be resolved, no script or name will
     /// be returned.
     pub fn get_defining_script(
@@ -142,6 +142,12 @@ impl<'gc> Domain<'gc> {
     ) -> Result<Option<(QName<'gc>, Script<'gc>)>, Error<'gc>> {
         let read = self.0.read();
 
+        if let Some(parent) = read.parent {
+            if let Some(found) = parent.get_defining_script(multiname)? {
+                return Ok(Some(found));
+            }
+        }
+
         if let Some(name) = multiname.local_name() {
             if let Some((ns, script)) = read.defs.get_with_ns_for_multiname(multiname) {
                 let qname = QName::new(ns, name);
@@ -149,10 +155,6 @@ impl<'gc> Domain<'gc> {
             }
         }
 
-        if let Some(parent) = read.parent {
-            return parent.get_defining_script(multiname);
-        }
-
         Ok(None)
     }
 
@@ -161,15 +163,14 @@ impl<'gc> Domain<'gc> {
         multiname: &Multiname<'gc>,
     ) -> Result<Option<GcCell<'gc, Class<'gc>>>, Error<'gc>> {
         let read = self.0.read();
-        if let Some(class) = read.classes.get_for_multiname(multiname).copied() {
-            return Ok(Some(class));
-        }
 
         if let Some(parent) = read.parent {
-            return parent.get_class_inner(multiname);
+            if let Some(class) = parent.get_class_inner(multiname)? {
+                return Ok(Some(class));
+            }
         }
 
-        Ok(None)
+        Ok(read.classes.get_for_multiname(multiname).copied())
     }
 
     pub fn get_class(
@@ -254,8 +255,12 @@ impl<'gc> Domain<'gc> {
         let res = self.get_defined_value(activation, name);
 
         if let Some(type_name) = type_name {
-            let type_qname = QName::from_qualified_name(type_name, activation);
-            let type_class = self.get_defined_value(activation, type_qname)?;
+            // The type may itself be a `Vector.<SomeType>`, or `*` for a vector of any type.
+            let type_class = if &*type_name == b"*" {
+                Value::Null
+            } else {
+                self.get_defined_value_handling_vector(activation, type_name)?
+            };
             if let Ok(res) = res {
                 let class = res.as_object().ok_or_else(|| {
                     Error::RustError(format!("Vector type {:?} was not an object", res).into())
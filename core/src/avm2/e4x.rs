@@ -26,17 +26,43 @@ pub struct E4XNode<'gc>(GcCell<'gc, E4XNodeData<'gc>>);
 #[collect(no_drop)]
 pub struct E4XNodeData<'gc> {
     parent: Option<E4XNode<'gc>>,
+    namespace: Option<E4XNamespace<'gc>>,
     local_name: Option<AvmString<'gc>>,
     kind: E4XNodeKind<'gc>,
+
+    /// The namespaces declared on this node with `xmlns` attributes.
+    /// Only elements declare namespaces.
+    namespace_declarations: Vec<E4XNamespace<'gc>>,
 }
 
+/// A namespace of an XML name, along with the prefix it was declared with.
+#[derive(Copy, Clone, Collect, Debug, PartialEq)]
+#[collect(no_drop)]
+pub struct E4XNamespace<'gc> {
+    /// The prefix of this namespace: empty for the default namespace, or
+    /// `None` if it hasn't been given one.
+    pub prefix: Option<AvmString<'gc>>,
+    pub uri: AvmString<'gc>,
+}
+
+impl<'gc> E4XNamespace<'gc> {
+    pub fn new(prefix: Option<AvmString<'gc>>, uri: AvmString<'gc>) -> Self {
+        Self { prefix, uri }
+    }
+}
+
+/// The namespace that the `xml` prefix is always bound to.
+const XML_NAMESPACE_URI: &str = "http://www.w3.org/XML/1998/namespace";
+
 impl<'gc> Debug for E4XNodeData<'gc> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("E4XNodeData")
             // Don't print the actual parent, to avoid infinite recursion
             .field("parent", &self.parent.is_some())
+            .field("namespace", &self.namespace)
             .field("local_name", &self.local_name)
             .field("kind", &self.kind)
+            .field("namespace_declarations", &self.namespace_declarations)
             .finish()
     }
 }
@@ -52,6 +78,17 @@ fn malformed_element<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
     )
 }
 
+fn illegal_cyclical_loop<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match type_error(
+        activation,
+        "Error #1118: Illegal cyclical loop between nodes.",
+        1118,
+    ) {
+        Ok(err) => Error::AvmError(err),
+        Err(err) => err,
+    }
+}
+
 #[derive(Collect, Debug)]
 #[collect(no_drop)]
 pub enum E4XNodeKind<'gc> {
@@ -72,11 +109,13 @@ impl<'gc> E4XNode<'gc> {
             mc,
             E4XNodeData {
                 parent: None,
+                namespace: None,
                 local_name: None,
                 kind: E4XNodeKind::Element {
                     attributes: vec![],
                     children: vec![],
                 },
+                namespace_declarations: vec![],
             },
         ))
     }
@@ -86,8 +125,10 @@ impl<'gc> E4XNode<'gc> {
             mc,
             E4XNodeData {
                 parent,
+                namespace: None,
                 local_name: None,
                 kind: E4XNodeKind::Text(text),
+                namespace_declarations: vec![],
             },
         ))
     }
@@ -97,11 +138,13 @@ impl<'gc> E4XNode<'gc> {
             mc,
             E4XNodeData {
                 parent: Some(parent),
+                namespace: None,
                 local_name: Some(name),
                 kind: E4XNodeKind::Element {
                     attributes: vec![],
                     children: vec![],
                 },
+                namespace_declarations: vec![],
             },
         ))
     }
@@ -116,14 +159,17 @@ impl<'gc> E4XNode<'gc> {
             mc,
             E4XNodeData {
                 parent: Some(parent),
+                namespace: None,
                 local_name: Some(name),
                 kind: E4XNodeKind::Attribute(value),
+                namespace_declarations: vec![],
             },
         ))
     }
 
     pub fn equals(&self, other: &Self) -> bool {
-        if self.local_name() != other.local_name() {
+        if self.local_name() != other.local_name() || self.namespace_uri() != other.namespace_uri()
+        {
             return false;
         }
 
@@ -195,8 +241,10 @@ impl<'gc> E4XNode<'gc> {
             mc,
             E4XNodeData {
                 parent: None,
+                namespace: this.namespace,
                 local_name: this.local_name,
                 kind,
+                namespace_declarations: this.namespace_declarations.clone(),
             },
         ));
 
@@ -280,6 +328,97 @@ impl<'gc> E4XNode<'gc> {
         Ok(())
     }
 
+    /// Inserts nodes into the children of this element, starting at `index`.
+    ///
+    /// Throws an error if one of the nodes is this element or one of its ancestors.
+    /// Nothing is inserted into nodes that aren't elements.
+    pub fn insert_children(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        index: usize,
+        nodes: &[Self],
+    ) -> Result<(), Error<'gc>> {
+        if !matches!(&*self.kind(), E4XNodeKind::Element { .. }) {
+            return Ok(());
+        }
+
+        if nodes
+            .iter()
+            .any(|node| self.is_self_or_descendant_of(*node))
+        {
+            return Err(illegal_cyclical_loop(activation));
+        }
+
+        let mc = activation.context.gc_context;
+        for node in nodes {
+            node.set_parent(Some(*self), mc);
+        }
+        if let E4XNodeKind::Element { children, .. } = &mut *self.kind_mut(mc) {
+            let index = index.min(children.len());
+            children.splice(index..index, nodes.iter().copied());
+        }
+        Ok(())
+    }
+
+    /// Replaces the child at `index` with the given nodes.
+    ///
+    /// Implementation of `[[Replace]]` from ECMA-357 (9.1.1.12)
+    pub fn replace_child_at(
+        &self,
+        activation: &mut Activation<'_, 'gc>,
+        index: usize,
+        nodes: &[Self],
+    ) -> Result<(), Error<'gc>> {
+        if nodes
+            .iter()
+            .any(|node| self.is_self_or_descendant_of(*node))
+        {
+            return Err(illegal_cyclical_loop(activation));
+        }
+
+        let mc = activation.context.gc_context;
+        let removed = match &mut *self.kind_mut(mc) {
+            E4XNodeKind::Element { children, .. } if index < children.len() => {
+                Some(children.remove(index))
+            }
+            _ => None,
+        };
+        if let Some(removed) = removed {
+            removed.set_parent(None, mc);
+        }
+        self.insert_children(activation, index, nodes)
+    }
+
+    /// Whether this node is `other`, or one of its descendants.
+    pub fn is_self_or_descendant_of(&self, other: Self) -> bool {
+        let mut node = Some(*self);
+        while let Some(current) = node {
+            if E4XNode::ptr_eq(current, other) {
+                return true;
+            }
+            node = current.parent();
+        }
+        false
+    }
+
+    /// Converts a value given to an XML mutation method (such as `appendChild`) into
+    /// the nodes to insert. XML and XMLList objects give the nodes they hold, and any
+    /// other value is parsed as XML.
+    pub fn nodes_from_value(
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Vec<Self>, Error<'gc>> {
+        if let Some(obj) = value.as_object() {
+            if let Some(xml) = obj.as_xml_object() {
+                return Ok(vec![*xml.node()]);
+            }
+            if let Some(list) = obj.as_xml_list_object() {
+                return Ok(list.children().iter().map(|child| *child.node()).collect());
+            }
+        }
+        Self::parse(value, activation)
+    }
+
     /// Parses a value provided to `XML`/`XMLList` into a list of nodes.
     /// The caller is responsible for validating that the number of top-level nodes
     /// is correct (for XML, there should be exactly one.)
@@ -375,7 +514,9 @@ impl<'gc> E4XNode<'gc> {
                     activation.context.gc_context,
                     E4XNodeData {
                         parent: None,
+                        namespace: None,
                         local_name: None,
+                        namespace_declarations: vec![],
                         kind: if is_text {
                             E4XNodeKind::Text(text)
                         } else {
@@ -395,8 +536,13 @@ impl<'gc> E4XNode<'gc> {
 
             match &event {
                 Event::Start(bs) => {
-                    let child = E4XNode::from_start_event(activation, bs, parser.decoder())
-                        .map_err(|_| malformed_element(activation))?;
+                    let child = E4XNode::from_start_event(
+                        activation,
+                        bs,
+                        parser.decoder(),
+                        open_tags.last().copied(),
+                    )
+                    .map_err(|_| malformed_element(activation))?;
 
                     if let Some(current_tag) = open_tags.last_mut() {
                         current_tag.append_child(activation.context.gc_context, child)?;
@@ -404,8 +550,13 @@ impl<'gc> E4XNode<'gc> {
                     open_tags.push(child);
                 }
                 Event::Empty(bs) => {
-                    let node = E4XNode::from_start_event(activation, bs, parser.decoder())
-                        .map_err(|_| malformed_element(activation))?;
+                    let node = E4XNode::from_start_event(
+                        activation,
+                        bs,
+                        parser.decoder(),
+                        open_tags.last().copied(),
+                    )
+                    .map_err(|_| malformed_element(activation))?;
                     push_childless_node(node, &mut open_tags, &mut top_level, activation)?;
                 }
                 Event::End(_) => {
@@ -456,8 +607,10 @@ impl<'gc> E4XNode<'gc> {
                         activation.context.gc_context,
                         E4XNodeData {
                             parent: None,
+                            namespace: None,
                             local_name: None,
                             kind,
+                            namespace_declarations: vec![],
                         },
                     ));
 
@@ -473,53 +626,102 @@ impl<'gc> E4XNode<'gc> {
 
     /// Construct an XML Element node from a `quick_xml` `BytesStart` event.
     ///
+    /// Namespace prefixes are resolved against the declarations of the element itself,
+    /// and then against the namespaces in scope of `parent`. Prefixes that aren't
+    /// declared anywhere are ignored.
+    ///
     /// The returned node will always be an `Element`, and it must only contain
     /// valid encoded UTF-8 data. (Other encoding support is planned later.)
     pub fn from_start_event(
         activation: &mut Activation<'_, 'gc>,
         bs: &BytesStart<'_>,
         decoder: quick_xml::Decoder,
+        parent: Option<E4XNode<'gc>>,
     ) -> Result<Self, quick_xml::Error> {
-        // FIXME - handle namespace
-        let name =
-            AvmString::new_utf8_bytes(activation.context.gc_context, bs.local_name().into_inner());
-
-        let mut attribute_nodes = Vec::new();
+        let mc = activation.context.gc_context;
 
         let attributes: Result<Vec<_>, _> = bs.attributes().collect();
-        for attribute in attributes? {
-            let key = AvmString::new_utf8_bytes(
-                activation.context.gc_context,
-                attribute.key.into_inner(),
-            );
+        let attributes = attributes?;
+
+        let mut namespace_declarations = Vec::new();
+        let mut plain_attributes = Vec::new();
+        for attribute in attributes {
             let value_str = custom_unescape(&attribute.value, decoder)?;
-            let value =
-                AvmString::new_utf8_bytes(activation.context.gc_context, value_str.as_bytes());
+            let value = AvmString::new_utf8_bytes(mc, value_str.as_bytes());
+
+            let key = attribute.key.into_inner();
+            if key == b"xmlns" {
+                namespace_declarations.push(E4XNamespace::new(Some("".into()), value));
+            } else if let Some(prefix) = key.strip_prefix(b"xmlns:") {
+                let prefix = AvmString::new_utf8_bytes(mc, prefix);
+                namespace_declarations.push(E4XNamespace::new(Some(prefix), value));
+            } else {
+                plain_attributes.push((key.to_vec(), value));
+            }
+        }
+
+        let resolve = |prefix: &[u8]| -> Option<E4XNamespace<'gc>> {
+            if prefix == b"xml" {
+                return Some(E4XNamespace::new(
+                    Some("xml".into()),
+                    XML_NAMESPACE_URI.into(),
+                ));
+            }
+            let prefix = ruffle_wstr::from_utf8_bytes(prefix);
+            let is_prefix =
+                |ns: &&E4XNamespace<'gc>| ns.prefix.map_or(false, |p| p.as_wstr() == &*prefix);
+            namespace_declarations
+                .iter()
+                .find(is_prefix)
+                .copied()
+                .or_else(|| {
+                    parent.and_then(|parent| {
+                        parent.in_scope_namespaces().iter().find(is_prefix).copied()
+                    })
+                })
+                // The default namespace can be undeclared with `xmlns=""`.
+                .filter(|ns| !ns.uri.is_empty())
+        };
+
+        let (name, namespace) = split_qualified_name(bs.name().into_inner());
+        let namespace = resolve(namespace.unwrap_or_default());
+        let name = AvmString::new_utf8_bytes(mc, name);
+
+        let mut attribute_nodes = Vec::new();
+        for (key, value) in plain_attributes {
+            let (key, prefix) = split_qualified_name(&key);
+            // Unprefixed attributes are never in the default namespace.
+            let namespace = prefix.and_then(resolve);
+            let key = AvmString::new_utf8_bytes(mc, key);
 
             let attribute_data = E4XNodeData {
                 parent: None,
+                namespace,
                 local_name: Some(key),
                 kind: E4XNodeKind::Attribute(value),
+                namespace_declarations: vec![],
             };
-            let attribute = E4XNode(GcCell::new(activation.context.gc_context, attribute_data));
+            let attribute = E4XNode(GcCell::new(mc, attribute_data));
             attribute_nodes.push(attribute);
         }
 
         let data = E4XNodeData {
             parent: None,
+            namespace,
             local_name: Some(name),
             kind: E4XNodeKind::Element {
                 attributes: attribute_nodes,
                 children: Vec::new(),
             },
+            namespace_declarations,
         };
 
-        let result = E4XNode(GcCell::new(activation.context.gc_context, data));
+        let result = E4XNode(GcCell::new(mc, data));
 
-        let mut result_kind = result.kind_mut(activation.context.gc_context);
+        let mut result_kind = result.kind_mut(mc);
         if let E4XNodeKind::Element { attributes, .. } = &mut *result_kind {
             for attribute in attributes {
-                attribute.set_parent(Some(result), activation.context.gc_context);
+                attribute.set_parent(Some(result), mc);
             }
         }
 
@@ -530,6 +732,119 @@ impl<'gc> E4XNode<'gc> {
         self.0.read().local_name
     }
 
+    pub fn set_local_name(&self, name: AvmString<'gc>, mc: MutationContext<'gc, '_>) {
+        self.0.write(mc).local_name = Some(name);
+    }
+
+    pub fn namespace(&self) -> Option<E4XNamespace<'gc>> {
+        self.0.read().namespace
+    }
+
+    /// The URI of the namespace of this node, which is empty if it has none.
+    pub fn namespace_uri(&self) -> AvmString<'gc> {
+        self.namespace()
+            .map_or_else(AvmString::default, |ns| ns.uri)
+    }
+
+    pub fn set_namespace(
+        &self,
+        namespace: Option<E4XNamespace<'gc>>,
+        mc: MutationContext<'gc, '_>,
+    ) {
+        self.0.write(mc).namespace = namespace.filter(|ns| !ns.uri.is_empty());
+    }
+
+    pub fn namespace_declarations(&self) -> Vec<E4XNamespace<'gc>> {
+        self.0.read().namespace_declarations.clone()
+    }
+
+    /// Declares a namespace on this element, replacing any declaration with the same prefix.
+    ///
+    /// Implementation of `AddInScopeNamespace` from ECMA-357 (9.1.1.13)
+    pub fn add_namespace_declaration(
+        &self,
+        namespace: E4XNamespace<'gc>,
+        mc: MutationContext<'gc, '_>,
+    ) {
+        let mut this = self.0.write(mc);
+        if !matches!(this.kind, E4XNodeKind::Element { .. }) {
+            return;
+        }
+        let Some(prefix) = namespace.prefix else {
+            return;
+        };
+        if prefix.is_empty() && this.namespace.is_none() {
+            return;
+        }
+
+        if let Some(existing) = this
+            .namespace_declarations
+            .iter_mut()
+            .find(|ns| ns.prefix == Some(prefix))
+        {
+            *existing = namespace;
+        } else {
+            this.namespace_declarations.push(namespace);
+        }
+    }
+
+    /// Removes the declarations of a namespace from this element and its descendants,
+    /// unless the element or its attributes are in that namespace.
+    ///
+    /// Implementation of `XML.prototype.removeNamespace` from ECMA-357 (13.4.4.31)
+    pub fn remove_namespace_declaration(
+        &self,
+        namespace: E4XNamespace<'gc>,
+        mc: MutationContext<'gc, '_>,
+    ) {
+        let mut this = self.0.write(mc);
+        let E4XNodeKind::Element {
+            attributes,
+            children,
+        } = &this.kind
+        else {
+            return;
+        };
+
+        let is_used = |ns: Option<E4XNamespace<'gc>>| {
+            ns.map_or(false, |ns| {
+                ns.uri == namespace.uri
+                    && (namespace.prefix.is_none() || ns.prefix == namespace.prefix)
+            })
+        };
+        if is_used(this.namespace) || attributes.iter().any(|attr| is_used(attr.namespace())) {
+            return;
+        }
+
+        let children = children.clone();
+        this.namespace_declarations.retain(|ns| {
+            ns.uri != namespace.uri || (namespace.prefix.is_some() && ns.prefix != namespace.prefix)
+        });
+        drop(this);
+
+        for child in children {
+            child.remove_namespace_declaration(namespace, mc);
+        }
+    }
+
+    /// The namespaces declared on this node and all of its ancestors.
+    /// Declarations on inner elements hide the declarations of outer elements
+    /// with the same prefix.
+    pub fn in_scope_namespaces(&self) -> Vec<E4XNamespace<'gc>> {
+        let mut namespaces: Vec<E4XNamespace<'gc>> = Vec::new();
+        let mut node = Some(*self);
+        while let Some(current) = node {
+            let data = current.0.read();
+            for ns in &data.namespace_declarations {
+                if !namespaces.iter().any(|n| n.prefix == ns.prefix) {
+                    namespaces.push(*ns);
+                }
+            }
+            node = data.parent;
+        }
+        namespaces
+    }
+
     pub fn set_parent(&self, parent: Option<E4XNode<'gc>>, mc: MutationContext<'gc, '_>) {
         self.0.write(mc).parent = parent;
     }
@@ -544,16 +859,25 @@ impl<'gc> E4XNode<'gc> {
             return false;
         }
 
-        // FIXME - we need to handle namespaces here
+        // A wildcard name (`*`) matches names in any namespace.
         if name.is_any_name() {
             return true;
         }
 
-        if let Some(local_name) = self.local_name() {
-            Some(local_name) == name.local_name()
-        } else {
-            false
+        match self.local_name() {
+            Some(local_name) if Some(local_name) == name.local_name() => {}
+            _ => return false,
+        }
+
+        if name.is_any_namespace() {
+            return true;
         }
+
+        // Only the URI of the namespace is compared, the prefix doesn't matter.
+        let uri = self.namespace_uri();
+        name.namespace_set()
+            .iter()
+            .any(|ns| ns.is_namespace() && ns.as_uri() == uri)
     }
 
     pub fn descendants(&self, name: &Multiname<'gc>, out: &mut Vec<E4XOrXml<'gc>>) {
@@ -644,6 +968,14 @@ impl<'gc> E4XNode<'gc> {
     }
 }
 
+/// Splits a qualified XML name into its local name and its prefix, if it has one.
+fn split_qualified_name(name: &[u8]) -> (&[u8], Option<&[u8]>) {
+    match name.iter().position(|&c| c == b':') {
+        Some(colon) => (&name[colon + 1..], Some(&name[..colon])),
+        None => (name, None),
+    }
+}
+
 pub fn simple_content_to_string<'gc>(
     children: impl Iterator<Item = E4XOrXml<'gc>>,
     activation: &mut Activation<'_, 'gc>,
@@ -703,8 +1035,54 @@ pub fn escape_element_value(s: AvmString) -> WString {
     r
 }
 
-fn to_xml_string_inner<'gc>(xml: E4XOrXml<'gc>, buf: &mut WString) -> Result<(), Error<'gc>> {
-    // FIXME: Implement pretty printing and namespace support.
+/// Finds the prefix to write a name in `namespace` with. If the namespace isn't in scope,
+/// it's declared on the element being written.
+fn namespace_prefix<'gc>(
+    namespace: E4XNamespace<'gc>,
+    is_attribute: bool,
+    in_scope: &mut Vec<E4XNamespace<'gc>>,
+    declarations: &mut Vec<E4XNamespace<'gc>>,
+    mc: MutationContext<'gc, '_>,
+) -> AvmString<'gc> {
+    // Attributes can't use the default namespace.
+    let is_usable = |ns: &&E4XNamespace<'gc>| {
+        ns.uri == namespace.uri && !(is_attribute && ns.prefix.map_or(true, |p| p.is_empty()))
+    };
+    let found = in_scope
+        .iter()
+        .filter(is_usable)
+        .find(|ns| ns.prefix == namespace.prefix)
+        .or_else(|| in_scope.iter().find(is_usable));
+    if let Some(prefix) = found.and_then(|ns| ns.prefix) {
+        return prefix;
+    }
+
+    let prefix = match namespace.prefix {
+        Some(prefix) if !(is_attribute && prefix.is_empty()) => prefix,
+        _ => AvmString::new_utf8(mc, format!("ns{}", in_scope.len())),
+    };
+    let declaration = E4XNamespace::new(Some(prefix), namespace.uri);
+    in_scope.retain(|ns| ns.prefix != Some(prefix));
+    in_scope.push(declaration);
+    declarations.push(declaration);
+    prefix
+}
+
+fn push_qualified_name(buf: &mut WString, prefix: Option<AvmString<'_>>, local_name: &WStr) {
+    if let Some(prefix) = prefix.filter(|prefix| !prefix.is_empty()) {
+        buf.push_str(&prefix);
+        buf.push_char(':');
+    }
+    buf.push_str(local_name);
+}
+
+fn to_xml_string_inner<'gc>(
+    xml: E4XOrXml<'gc>,
+    buf: &mut WString,
+    ancestor_namespaces: &[E4XNamespace<'gc>],
+    mc: MutationContext<'gc, '_>,
+) -> Result<(), Error<'gc>> {
+    // FIXME: Implement pretty printing.
 
     let node = xml.node();
     let node_kind = node.kind();
@@ -738,13 +1116,66 @@ fn to_xml_string_inner<'gc>(xml: E4XOrXml<'gc>, buf: &mut WString) -> Result<(),
         } => (children, attributes),
     };
 
+    let mut in_scope = ancestor_namespaces.to_vec();
+    let mut declarations = Vec::new();
+    for declaration in node.namespace_declarations() {
+        if !in_scope.contains(&declaration) {
+            in_scope.retain(|ns| ns.prefix != declaration.prefix);
+            in_scope.push(declaration);
+            declarations.push(declaration);
+        }
+    }
+
+    let prefix = match node.namespace() {
+        Some(namespace) => Some(namespace_prefix(
+            namespace,
+            false,
+            &mut in_scope,
+            &mut declarations,
+            mc,
+        )),
+        None => {
+            // Elements without a namespace have to undeclare an inherited default namespace.
+            let default = E4XNamespace::new(Some("".into()), "".into());
+            if in_scope
+                .iter()
+                .any(|ns| ns.prefix == default.prefix && !ns.uri.is_empty())
+            {
+                in_scope.retain(|ns| ns.prefix != default.prefix);
+                in_scope.push(default);
+                declarations.push(default);
+            }
+            None
+        }
+    };
+
+    let attribute_prefixes: Vec<_> = attributes
+        .iter()
+        .map(|attribute| {
+            attribute.namespace().map(|namespace| {
+                namespace_prefix(namespace, true, &mut in_scope, &mut declarations, mc)
+            })
+        })
+        .collect();
+
     buf.push_char('<');
-    buf.push_str(&node.local_name().unwrap());
+    push_qualified_name(buf, prefix, &node.local_name().unwrap());
+
+    for declaration in &declarations {
+        buf.push_utf8(" xmlns");
+        if let Some(prefix) = declaration.prefix.filter(|prefix| !prefix.is_empty()) {
+            buf.push_char(':');
+            buf.push_str(&prefix);
+        }
+        buf.push_utf8("=\"");
+        buf.push_str(&escape_attribute_value(declaration.uri));
+        buf.push_char('"');
+    }
 
-    for attribute in attributes {
+    for (attribute, attribute_prefix) in attributes.iter().zip(attribute_prefixes) {
         if let E4XNodeKind::Attribute(value) = &*attribute.kind() {
             buf.push_char(' ');
-            buf.push_str(&attribute.local_name().unwrap());
+            push_qualified_name(buf, attribute_prefix, &attribute.local_name().unwrap());
             buf.push_char('=');
             buf.push_char('"');
             buf.push_str(&escape_attribute_value(*value));
@@ -760,11 +1191,11 @@ fn to_xml_string_inner<'gc>(xml: E4XOrXml<'gc>, buf: &mut WString) -> Result<(),
     buf.push_char('>');
 
     for child in children {
-        to_xml_string_inner(E4XOrXml::E4X(*child), buf)?;
+        to_xml_string_inner(E4XOrXml::E4X(*child), buf, &in_scope, mc)?;
     }
 
     buf.push_utf8("</");
-    buf.push_str(&node.local_name().unwrap());
+    push_qualified_name(buf, prefix, &node.local_name().unwrap());
     buf.push_char('>');
 
     Ok(())
@@ -776,7 +1207,7 @@ pub fn to_xml_string<'gc>(
     activation: &mut Activation<'_, 'gc>,
 ) -> Result<AvmString<'gc>, Error<'gc>> {
     let mut buf = WString::new();
-    to_xml_string_inner(xml, &mut buf)?;
+    to_xml_string_inner(xml, &mut buf, &[], activation.context.gc_context)?;
     Ok(AvmString::new(activation.context.gc_context, buf))
 }
 
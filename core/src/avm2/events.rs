@@ -296,6 +296,23 @@ impl<'gc> DispatchList<'gc> {
             .filter(move |eh| eh.use_capture == use_capture)
             .map(|eh| eh.handler)
     }
+
+    /// Yield every event handler in this list, along with the event type it handles, its
+    /// priority, and whether it's for the capture phase.
+    pub fn iter_all_handlers(
+        &self,
+    ) -> impl '_ + Iterator<Item = (AvmString<'gc>, i32, Object<'gc>, bool)> {
+        self.0.iter().flat_map(|(event, priorities)| {
+            priorities
+                .iter()
+                .rev()
+                .flat_map(move |(priority, handlers)| {
+                    handlers
+                        .iter()
+                        .map(move |eh| (*event, *priority, eh.handler, eh.use_capture))
+                })
+        })
+    }
 }
 
 impl<'gc> Default for DispatchList<'gc> {
@@ -7,6 +7,7 @@ use crate::avm2::scope::ScopeChain;
 use crate::avm2::traits::TraitKind;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::profiler::Category;
 use crate::string::WString;
 use gc_arena::{Collect, Gc};
 use std::fmt;
@@ -109,6 +110,7 @@ impl<'gc> Executable<'gc> {
         activation: &mut Activation<'_, 'gc>,
         callee: Object<'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
+        let begin = activation.context.profiler.begin();
         let ret = match self {
             Executable::Native(bm) => {
                 let method = bm.method.method;
@@ -148,6 +150,11 @@ impl<'gc> Executable<'gc> {
                     arguments,
                     &bm.method.signature,
                 )?;
+                activation.context.api_usage.record(|| {
+                    let mut name = WString::new();
+                    self.write_full_name(&mut name);
+                    format!("AVM2 {name}")
+                });
                 activation
                     .context
                     .avm2
@@ -195,6 +202,14 @@ impl<'gc> Executable<'gc> {
             .context
             .avm2
             .pop_call(activation.context.gc_context);
+        activation
+            .context
+            .profiler
+            .end_call(begin, Category::Avm2, || {
+                let mut name = WString::new();
+                self.write_full_name(&mut name);
+                name.to_string()
+            });
         ret
     }
 
@@ -58,6 +58,7 @@ pub struct SystemClasses<'gc> {
     pub framelabel: ClassObject<'gc>,
     pub scene: ClassObject<'gc>,
     pub application_domain: ClassObject<'gc>,
+    pub system: ClassObject<'gc>,
     pub event: ClassObject<'gc>,
     pub fullscreenevent: ClassObject<'gc>,
     pub video: ClassObject<'gc>,
@@ -79,6 +80,8 @@ pub struct SystemClasses<'gc> {
     pub loader: ClassObject<'gc>,
     pub loaderinfo: ClassObject<'gc>,
     pub bytearray: ClassObject<'gc>,
+    pub dictionary: ClassObject<'gc>,
+    pub iexternalizable: ClassObject<'gc>,
     pub stage: ClassObject<'gc>,
     pub sprite: ClassObject<'gc>,
     pub simplebutton: ClassObject<'gc>,
@@ -94,16 +97,21 @@ pub struct SystemClasses<'gc> {
     pub progressevent: ClassObject<'gc>,
     pub httpstatusevent: ClassObject<'gc>,
     pub textevent: ClassObject<'gc>,
+    pub imeevent: ClassObject<'gc>,
     pub errorevent: ClassObject<'gc>,
     pub ioerrorevent: ClassObject<'gc>,
     pub securityerrorevent: ClassObject<'gc>,
     pub transform: ClassObject<'gc>,
     pub colortransform: ClassObject<'gc>,
+    pub perspectiveprojection: ClassObject<'gc>,
     pub matrix: ClassObject<'gc>,
     pub illegaloperationerror: ClassObject<'gc>,
     pub eventdispatcher: ClassObject<'gc>,
     pub rectangle: ClassObject<'gc>,
     pub keyboardevent: ClassObject<'gc>,
+    pub focusevent: ClassObject<'gc>,
+    pub stagevideo: ClassObject<'gc>,
+    pub stagevideoevent: ClassObject<'gc>,
     pub point: ClassObject<'gc>,
     pub rangeerror: ClassObject<'gc>,
     pub referenceerror: ClassObject<'gc>,
@@ -125,6 +133,7 @@ pub struct SystemClasses<'gc> {
     pub vertexbuffer3d: ClassObject<'gc>,
     pub program3d: ClassObject<'gc>,
     pub urlvariables: ClassObject<'gc>,
+    pub urlrequestheader: ClassObject<'gc>,
     pub bevelfilter: ClassObject<'gc>,
     pub bitmapfilter: ClassObject<'gc>,
     pub blurfilter: ClassObject<'gc>,
@@ -138,13 +147,34 @@ pub struct SystemClasses<'gc> {
     pub texture: ClassObject<'gc>,
     pub cubetexture: ClassObject<'gc>,
     pub rectangletexture: ClassObject<'gc>,
+    pub videotexture: ClassObject<'gc>,
     pub morphshape: ClassObject<'gc>,
     pub shader: ClassObject<'gc>,
     pub shaderinput: ClassObject<'gc>,
     pub shaderparameter: ClassObject<'gc>,
     pub netstatusevent: ClassObject<'gc>,
+    pub shaderevent: ClassObject<'gc>,
+    pub sampledataevent: ClassObject<'gc>,
+    pub activityevent: ClassObject<'gc>,
     pub shaderfilter: ClassObject<'gc>,
     pub statusevent: ClassObject<'gc>,
+    pub syncevent: ClassObject<'gc>,
+    pub dataevent: ClassObject<'gc>,
+    pub filereference: ClassObject<'gc>,
+    pub socket: ClassObject<'gc>,
+    pub serversocketconnectevent: ClassObject<'gc>,
+    pub datagramsocketdataevent: ClassObject<'gc>,
+    pub nativedragevent: ClassObject<'gc>,
+    pub clipboard: ClassObject<'gc>,
+    pub nativedragmanager: ClassObject<'gc>,
+    pub file: ClassObject<'gc>,
+    pub contextmenuevent: ClassObject<'gc>,
+    pub gameinput: ClassObject<'gc>,
+    pub touchevent: ClassObject<'gc>,
+    pub gestureevent: ClassObject<'gc>,
+    pub transformgestureevent: ClassObject<'gc>,
+    pub multitouch: ClassObject<'gc>,
+    pub newobjectsample: ClassObject<'gc>,
 }
 
 impl<'gc> SystemClasses<'gc> {
@@ -178,6 +208,7 @@ impl<'gc> SystemClasses<'gc> {
             framelabel: object,
             scene: object,
             application_domain: object,
+            system: object,
             event: object,
             fullscreenevent: object,
             video: object,
@@ -199,6 +230,8 @@ impl<'gc> SystemClasses<'gc> {
             loader: object,
             loaderinfo: object,
             bytearray: object,
+            dictionary: object,
+            iexternalizable: object,
             stage: object,
             sprite: object,
             simplebutton: object,
@@ -214,16 +247,21 @@ impl<'gc> SystemClasses<'gc> {
             progressevent: object,
             httpstatusevent: object,
             textevent: object,
+            imeevent: object,
             errorevent: object,
             ioerrorevent: object,
             securityerrorevent: object,
             transform: object,
             colortransform: object,
+            perspectiveprojection: object,
             matrix: object,
             illegaloperationerror: object,
             eventdispatcher: object,
             rectangle: object,
             keyboardevent: object,
+            focusevent: object,
+            stagevideo: object,
+            stagevideoevent: object,
             point: object,
             rangeerror: object,
             referenceerror: object,
@@ -245,6 +283,7 @@ impl<'gc> SystemClasses<'gc> {
             vertexbuffer3d: object,
             program3d: object,
             urlvariables: object,
+            urlrequestheader: object,
             bevelfilter: object,
             bitmapfilter: object,
             blurfilter: object,
@@ -258,13 +297,34 @@ impl<'gc> SystemClasses<'gc> {
             texture: object,
             cubetexture: object,
             rectangletexture: object,
+            videotexture: object,
             morphshape: object,
             shader: object,
             shaderinput: object,
             shaderparameter: object,
             netstatusevent: object,
+            shaderevent: object,
+            sampledataevent: object,
+            activityevent: object,
             shaderfilter: object,
             statusevent: object,
+            syncevent: object,
+            dataevent: object,
+            filereference: object,
+            socket: object,
+            serversocketconnectevent: object,
+            datagramsocketdataevent: object,
+            nativedragevent: object,
+            clipboard: object,
+            nativedragmanager: object,
+            file: object,
+            contextmenuevent: object,
+            gameinput: object,
+            touchevent: object,
+            gestureevent: object,
+            transformgestureevent: object,
+            multitouch: object,
+            newobjectsample: object,
         }
     }
 }
@@ -694,6 +754,7 @@ fn load_playerglobal<'gc>(
             ("flash.display", "Sprite", sprite),
             ("flash.display", "Stage", stage),
             ("flash.display", "Stage3D", stage3d),
+            ("flash.media", "StageVideo", stagevideo),
             ("flash.display3D", "Context3D", context3d),
             ("flash.display3D", "IndexBuffer3D", indexbuffer3d),
             ("flash.display3D", "Program3D", program3d),
@@ -704,6 +765,7 @@ fn load_playerglobal<'gc>(
                 "RectangleTexture",
                 rectangletexture
             ),
+            ("flash.display3D.textures", "VideoTexture", videotexture),
             ("flash.display3D", "VertexBuffer3D", vertexbuffer3d),
             (
                 "flash.errors",
@@ -715,8 +777,11 @@ fn load_playerglobal<'gc>(
             ("flash.events", "Event", event),
             ("flash.events", "EventDispatcher", eventdispatcher),
             ("flash.events", "TextEvent", textevent),
+            ("flash.events", "IMEEvent", imeevent),
             ("flash.events", "ErrorEvent", errorevent),
             ("flash.events", "KeyboardEvent", keyboardevent),
+            ("flash.events", "FocusEvent", focusevent),
+            ("flash.events", "StageVideoEvent", stagevideoevent),
             ("flash.events", "ProgressEvent", progressevent),
             ("flash.events", "HTTPStatusEvent", httpstatusevent),
             ("flash.events", "SecurityErrorEvent", securityerrorevent),
@@ -725,18 +790,55 @@ fn load_playerglobal<'gc>(
             ("flash.events", "FullScreenEvent", fullscreenevent),
             ("flash.events", "UncaughtErrorEvents", uncaughterrorevents),
             ("flash.events", "NetStatusEvent", netstatusevent),
+            ("flash.events", "ShaderEvent", shaderevent),
+            ("flash.events", "SampleDataEvent", sampledataevent),
+            ("flash.events", "ActivityEvent", activityevent),
             ("flash.events", "StatusEvent", statusevent),
+            ("flash.events", "SyncEvent", syncevent),
+            ("flash.events", "DataEvent", dataevent),
+            ("flash.net", "FileReference", filereference),
+            (
+                "flash.events",
+                "ServerSocketConnectEvent",
+                serversocketconnectevent
+            ),
+            (
+                "flash.events",
+                "DatagramSocketDataEvent",
+                datagramsocketdataevent
+            ),
+            ("flash.events", "NativeDragEvent", nativedragevent),
+            ("flash.desktop", "Clipboard", clipboard),
+            ("flash.desktop", "NativeDragManager", nativedragmanager),
+            ("flash.filesystem", "File", file),
+            ("flash.events", "ContextMenuEvent", contextmenuevent),
+            ("flash.ui", "GameInput", gameinput),
+            ("flash.events", "TouchEvent", touchevent),
+            ("flash.events", "GestureEvent", gestureevent),
+            (
+                "flash.events",
+                "TransformGestureEvent",
+                transformgestureevent
+            ),
+            ("flash.ui", "Multitouch", multitouch),
+            ("flash.sampler", "NewObjectSample", newobjectsample),
             ("flash.geom", "Matrix", matrix),
             ("flash.geom", "Point", point),
             ("flash.geom", "Rectangle", rectangle),
             ("flash.geom", "Transform", transform),
             ("flash.geom", "ColorTransform", colortransform),
+            ("flash.geom", "PerspectiveProjection", perspectiveprojection),
             ("flash.media", "SoundChannel", soundchannel),
             ("flash.media", "SoundTransform", soundtransform),
             ("flash.media", "Video", video),
+            ("flash.net", "Socket", socket),
             ("flash.net", "URLVariables", urlvariables),
+            ("flash.net", "URLRequestHeader", urlrequestheader),
             ("flash.utils", "ByteArray", bytearray),
+            ("flash.utils", "Dictionary", dictionary),
+            ("flash.utils", "IExternalizable", iexternalizable),
             ("flash.system", "ApplicationDomain", application_domain),
+            ("flash.system", "System", system),
             ("flash.text", "StaticText", statictext),
             ("flash.text", "TextFormat", textformat),
             ("flash.text", "TextField", textfield),
@@ -47,6 +47,18 @@ package {
         AS3 native function attribute(name:*):XMLList;
         AS3 native function nodeKind():String;
         AS3 native function appendChild(child:Object):XML;
+        AS3 native function prependChild(value:Object):XML;
+        AS3 native function insertChildAfter(child1:Object, child2:Object):*;
+        AS3 native function insertChildBefore(child1:Object, child2:Object):*;
+        AS3 native function setChildren(value:Object):XML;
+        AS3 native function replace(propertyName:Object, value:*):XML;
+        AS3 native function addNamespace(ns:*):XML;
+        AS3 native function removeNamespace(ns:*):XML;
+        AS3 native function setNamespace(ns:*):void;
+        AS3 native function inScopeNamespaces():Array;
+        AS3 native function namespaceDeclarations():Array;
+        AS3 native function setName(name:*):void;
+        AS3 native function setLocalName(name:*):void;
         AS3 native function descendants(name:Object = "*"):XMLList;
         AS3 native function text():XMLList;
         AS3 native function toString():String;
@@ -144,6 +156,66 @@ package {
             return self.AS3::appendChild(child);
         };
 
+        prototype.prependChild = function(value:Object):XML {
+            var self:XML = this;
+            return self.AS3::prependChild(value);
+        };
+
+        prototype.insertChildAfter = function(child1:Object, child2:Object):* {
+            var self:XML = this;
+            return self.AS3::insertChildAfter(child1, child2);
+        };
+
+        prototype.insertChildBefore = function(child1:Object, child2:Object):* {
+            var self:XML = this;
+            return self.AS3::insertChildBefore(child1, child2);
+        };
+
+        prototype.setChildren = function(value:Object):XML {
+            var self:XML = this;
+            return self.AS3::setChildren(value);
+        };
+
+        prototype.replace = function(propertyName:Object, value:*):XML {
+            var self:XML = this;
+            return self.AS3::replace(propertyName, value);
+        };
+
+        prototype.addNamespace = function(ns:*):XML {
+            var self:XML = this;
+            return self.AS3::addNamespace(ns);
+        };
+
+        prototype.removeNamespace = function(ns:*):XML {
+            var self:XML = this;
+            return self.AS3::removeNamespace(ns);
+        };
+
+        prototype.setNamespace = function(ns:*):void {
+            var self:XML = this;
+            self.AS3::setNamespace(ns);
+        };
+
+        prototype.inScopeNamespaces = function():Array {
+            var self:XML = this;
+            return self.AS3::inScopeNamespaces();
+        };
+
+        prototype.namespaceDeclarations = function():Array {
+            var self:XML = this;
+            return self.AS3::namespaceDeclarations();
+        };
+
+        prototype.setName = function(name:*):void {
+            var self:XML = this;
+            self.AS3::setName(name);
+        };
+
+        prototype.setLocalName = function(name:*):void {
+            var self:XML = this;
+            self.AS3::setLocalName(name);
+        };
+
         prototype.descendants = function(name:Object):XMLList {
             var self:XML = this;
             return self.AS3::descendants(name);
@@ -0,0 +1,3 @@
+//! `flash.accessibility` namespace
+
+pub mod accessibility;
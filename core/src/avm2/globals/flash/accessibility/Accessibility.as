@@ -10,9 +10,6 @@ package flash.accessibility
 
     public final class Accessibility
     {
-        // Indicates whether a screen reader is active and the application is communicating with it.
-        private static var _active: Boolean;
-
         // Sends an event to the Microsoft Active Accessibility API.
         public static function sendEvent(source:DisplayObject, childID:uint, eventType:uint, nonHTML:Boolean = false):void 
         {
@@ -20,15 +17,10 @@ package flash.accessibility
         }
 
         // Tells Flash Player to apply any accessibility changes made by using the DisplayObject.accessibilityProperties property.
-        public static function updateProperties():void
-        {
-            stub_method("flash.accessibility.Accessibility", "updateProperties");
-        }
+        public static native function updateProperties():void;
 
-        public static function get active() : Boolean
-        {
-            return _active;
-        }
+        // Indicates whether a screen reader is active and the application is communicating with it.
+        public static native function get active() : Boolean;
 
     }
 }
@@ -0,0 +1,29 @@
+//! `flash.accessibility.Accessibility` builtin
+
+use crate::accessibility;
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+/// Implements `flash.accessibility.Accessibility.active`'s getter
+pub fn get_active<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.is_accessibility_active().into())
+}
+
+/// Implements `flash.accessibility.Accessibility.updateProperties`
+pub fn update_properties<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if activation.context.ui.is_accessibility_active() {
+        let tree = accessibility::build_tree(&mut activation.context);
+        activation.context.ui.update_accessibility_tree(tree);
+    }
+    Ok(Value::Undefined)
+}
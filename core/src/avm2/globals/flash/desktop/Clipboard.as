@@ -0,0 +1,130 @@
+package flash.desktop
+{
+    import flash.display.BitmapData;
+
+    public class Clipboard
+    {
+        private static var _generalClipboard:Clipboard = null;
+
+        // Whether this is the system clipboard, rather than one created by the movie.
+        private var _isGeneral:Boolean = false;
+
+        // The data of a clipboard created by the movie, keyed by format.
+        private var _data:Object = {};
+
+        public function Clipboard()
+        {
+        }
+
+        public static function get generalClipboard():Clipboard
+        {
+            if (_generalClipboard == null) {
+                _generalClipboard = new Clipboard();
+                _generalClipboard._isGeneral = true;
+            }
+            return _generalClipboard;
+        }
+
+        public function get formats():Array
+        {
+            var formats:Array = [];
+            if (this._isGeneral) {
+                if (Clipboard.getText() != "") {
+                    formats.push(ClipboardFormats.TEXT_FORMAT);
+                }
+                if (Clipboard.hasBitmap()) {
+                    formats.push(ClipboardFormats.BITMAP_FORMAT);
+                }
+            } else {
+                for (var format:String in this._data) {
+                    formats.push(format);
+                }
+            }
+            return formats;
+        }
+
+        public function clear():void
+        {
+            if (this._isGeneral) {
+                Clipboard.setText("");
+            } else {
+                this._data = {};
+            }
+        }
+
+        public function clearData(format:String):void
+        {
+            if (this._isGeneral) {
+                if (format == ClipboardFormats.TEXT_FORMAT) {
+                    Clipboard.setText("");
+                }
+            } else {
+                delete this._data[format];
+            }
+        }
+
+        public function getData(format:String, transferMode:String = "originalPreferred"):Object
+        {
+            if (this._isGeneral) {
+                if (format == ClipboardFormats.TEXT_FORMAT) {
+                    return Clipboard.getText();
+                }
+                if (format == ClipboardFormats.BITMAP_FORMAT) {
+                    return Clipboard.getBitmap();
+                }
+                return null;
+            }
+
+            var data:* = this._data[format];
+            if (data is Function) {
+                data = data();
+                this._data[format] = data;
+            }
+            return data === undefined ? null : data;
+        }
+
+        public function hasFormat(format:String):Boolean
+        {
+            return this.formats.indexOf(format) != -1;
+        }
+
+        public function setData(format:String, data:Object, serializable:Boolean = true):Boolean
+        {
+            if (this._isGeneral) {
+                if (format == ClipboardFormats.TEXT_FORMAT) {
+                    Clipboard.setText(String(data));
+                    return true;
+                }
+                if (format == ClipboardFormats.BITMAP_FORMAT && data is BitmapData) {
+                    Clipboard.setBitmap(data as BitmapData);
+                    return true;
+                }
+                return false;
+            }
+
+            this._data[format] = data;
+            return true;
+        }
+
+        public function setDataHandler(format:String, handler:Function, serializable:Boolean = true):Boolean
+        {
+            if (this._isGeneral) {
+                // The system clipboard can't call back into the movie, so the data is taken right away.
+                return this.setData(format, handler(), serializable);
+            }
+
+            this._data[format] = handler;
+            return true;
+        }
+
+        private static native function getText():String;
+
+        private static native function setText(text:String):void;
+
+        private static native function hasBitmap():Boolean;
+
+        private static native function getBitmap():BitmapData;
+
+        private static native function setBitmap(bitmap:BitmapData):void;
+    }
+}
@@ -8,6 +8,12 @@ package flash.desktop
 
     public class ClipboardFormats
     {
+        // Image data.
+        public static const BITMAP_FORMAT:String = "air:bitmap";
+
+        // An array of files.
+        public static const FILE_LIST_FORMAT:String = "air:file list";
+
         // HTML data.
         public static const HTML_FORMAT:String = "air:html";
 
@@ -17,5 +23,8 @@ package flash.desktop
         // String data.
         public static const TEXT_FORMAT:String = "air:text";
 
+        // A URL string.
+        public static const URL_FORMAT:String = "air:url";
+
     }
 }
@@ -0,0 +1,40 @@
+package flash.desktop
+{
+    import flash.display.BitmapData;
+    import flash.display.InteractiveObject;
+    import flash.geom.Point;
+    import __ruffle__.stub_method;
+
+    public final class NativeDragManager
+    {
+        // Set by the player while files are being dropped onto it.
+        internal static var _isDragging:Boolean = false;
+
+        // The object that accepted the drop during `nativeDragEnter`, which receives `nativeDragDrop`.
+        internal static var _dropTarget:InteractiveObject = null;
+
+        public static var dropAction:String = NativeDragActions.COPY;
+
+        public static function get isSupported():Boolean
+        {
+            return true;
+        }
+
+        public static function get isDragging():Boolean
+        {
+            return _isDragging;
+        }
+
+        public static function acceptDragDrop(target:InteractiveObject):void
+        {
+            if (_isDragging) {
+                _dropTarget = target;
+            }
+        }
+
+        public static function doDrag(dragInitiator:InteractiveObject, clipboard:Clipboard, dragImage:BitmapData = null, offset:Point = null, allowedActions:NativeDragOptions = null):void
+        {
+            stub_method("flash.desktop.NativeDragManager", "doDrag");
+        }
+    }
+}
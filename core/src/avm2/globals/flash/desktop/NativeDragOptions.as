@@ -0,0 +1,18 @@
+package flash.desktop
+{
+    public class NativeDragOptions
+    {
+        public var allowCopy:Boolean = true;
+        public var allowLink:Boolean = true;
+        public var allowMove:Boolean = true;
+
+        public function NativeDragOptions()
+        {
+        }
+
+        public function toString():String
+        {
+            return "[NativeDragOptions allowCopy=" + this.allowCopy + " allowLink=" + this.allowLink + " allowMove=" + this.allowMove + "]";
+        }
+    }
+}
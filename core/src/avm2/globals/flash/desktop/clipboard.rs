@@ -0,0 +1,103 @@
+//! `flash.desktop.Clipboard` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::{argument_error, security_error};
+use crate::avm2::object::{BitmapDataObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper, Color};
+use crate::string::AvmString;
+use gc_arena::GcCell;
+
+/// Movies can only read the system clipboard while they handle a `paste` event.
+fn check_paste_handler<'gc>(activation: &mut Activation<'_, 'gc>) -> Result<(), Error<'gc>> {
+    if activation.context.in_paste_handler {
+        return Ok(());
+    }
+    Err(Error::AvmError(security_error(
+        activation,
+        "Error #2179: The Clipboard.generalClipboard object may only be read while processing a flash.events.Event.PASTE event.",
+        2179,
+    )?))
+}
+
+/// Implements `flash.desktop.Clipboard.getText`
+pub fn get_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_paste_handler(activation)?;
+    let text = activation.context.ui.clipboard_content();
+    Ok(AvmString::new_utf8(activation.context.gc_context, text).into())
+}
+
+/// Implements `flash.desktop.Clipboard.setText`
+pub fn set_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let text = args.get_string(activation, 0)?.to_string();
+    activation.context.ui.set_clipboard_content(text);
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.desktop.Clipboard.hasBitmap`
+pub fn has_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_paste_handler(activation)?;
+    Ok(activation.context.ui.clipboard_image().is_some().into())
+}
+
+/// Implements `flash.desktop.Clipboard.getBitmap`
+pub fn get_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    check_paste_handler(activation)?;
+    let image = match activation.context.ui.clipboard_image() {
+        Some(image) => image.to_rgba(),
+        None => return Ok(Value::Null),
+    };
+
+    let pixels = image
+        .data()
+        .chunks_exact(4)
+        .map(|p| Color::argb(p[3], p[0], p[1], p[2]).to_premultiplied_alpha(true))
+        .collect();
+    let bitmap_data = BitmapData::new_with_pixels(
+        image.width(),
+        image.height(),
+        true,
+        pixels,
+        activation.context.stage.bitmap_modifications(),
+    );
+    let bitmap_data =
+        BitmapDataWrapper::new(GcCell::new(activation.context.gc_context, bitmap_data));
+    let class = activation.avm2().classes().bitmapdata;
+    Ok(BitmapDataObject::from_bitmap_data_internal(activation, bitmap_data, class)?.into())
+}
+
+/// Implements `flash.desktop.Clipboard.setBitmap`
+pub fn set_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(bitmap_data) = args.get_object(activation, 0, "bitmap")?.as_bitmap_data() else {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Parameter 0 is of the incorrect type. Should be type BitmapData.",
+            2005,
+        )?));
+    };
+    let image = bitmap_data.sync().read().to_unmultiplied_bitmap();
+    activation.context.ui.set_clipboard_image(image);
+    Ok(Value::Undefined)
+}
@@ -4,9 +4,7 @@ package flash.display {
     import flash.geom.Point;
     import flash.geom.Matrix;
     import flash.filters.BitmapFilter;
-    import flash.filters.ShaderFilter;
     import flash.utils.ByteArray;
-    import __ruffle__.stub_method;
 
     [Ruffle(InstanceAllocator)]
     public class BitmapData implements IBitmapDrawable {
@@ -67,14 +65,6 @@ package flash.display {
             sourceBitmapData:BitmapData, sourceRect:Rectangle, destPoint:Point, randomSeed:int = 0, numPixels:int = 0,
             fillColor:uint = 0
         ):int;
-
-        public function generateFilterRect(sourceRect:Rectangle, filter:BitmapFilter):Rectangle {
-            // Flash always reports that a ShaderFilter affects the entire BitampData, ignoring SourceRect.
-            if (filter is ShaderFilter) {
-                return this.rect.clone();
-            }
-            stub_method("flash.display.BitmapData", "generateFilterRect");
-            return sourceRect.clone();
-        }
+        public native function generateFilterRect(sourceRect:Rectangle, filter:BitmapFilter):Rectangle;
     }
 }
@@ -16,9 +16,7 @@ package flash.display
         public native function beginGradientFill(
             type:String, colors:Array, alphas:Array, ratios:Array, matrix:Matrix = null, spreadMethod:String = "pad", interpolationMethod:String = "rgb", focalPointRatio:Number = 0
         ): void;
-        public function beginShaderFill(shader:Shader, matrix:Matrix = null):void {
-            stub_method("flash.display.Graphics", "beginShaderFill");
-        }
+        public native function beginShaderFill(shader:Shader, matrix:Matrix = null):void;
         public native function clear(): void;
         public native function curveTo(controlX:Number, controlY:Number, anchorX:Number, anchorY:Number): void;
         public native function drawCircle(x:Number, y:Number, radius:Number): void;
@@ -31,7 +29,6 @@ package flash.display
         ): void;
         public native function lineTo(x:Number, y:Number): void;
         public native function moveTo(x:Number, y:Number): void;
-        //public native function beginShaderFill(shader:Shader, matrix:Matrix = null):void;
         public native function lineGradientStyle(
             type:String, colors:Array, alphas:Array, ratios:Array, matrix:Matrix = null, spreadMethod:String = "pad", interpolationMethod:String = "rgb", focalPointRatio:Number = 0
         ):void;
@@ -42,7 +39,67 @@ package flash.display
             x:Number, y:Number, width:Number, height:Number, topLeftRadius:Number, topRightRadius:Number, bottomLeftRadius:Number, bottomRightRadius:Number
         ):void;
         public native function drawTriangles(vertices:Vector.<Number>, indices:Vector.<int> = null, uvtData:Vector.<Number> = null, culling:String = "none"):void;
-        public native function drawGraphicsData(graphicsData:Vector.<IGraphicsData>):void;
+        public function drawGraphicsData(graphicsData:Vector.<IGraphicsData>):void {
+            for each (var item:IGraphicsData in graphicsData) {
+                if (item is GraphicsSolidFill) {
+                    var solidFill:GraphicsSolidFill = item as GraphicsSolidFill;
+                    beginFill(solidFill.color, solidFill.alpha);
+                } else if (item is GraphicsGradientFill) {
+                    var gradientFill:GraphicsGradientFill = item as GraphicsGradientFill;
+                    beginGradientFill(
+                        gradientFill.type, gradientFill.colors, gradientFill.alphas, gradientFill.ratios, gradientFill.matrix,
+                        gradientFill.spreadMethod, gradientFill.interpolationMethod, gradientFill.focalPointRatio
+                    );
+                } else if (item is GraphicsBitmapFill) {
+                    var bitmapFill:GraphicsBitmapFill = item as GraphicsBitmapFill;
+                    beginBitmapFill(bitmapFill.bitmapData, bitmapFill.matrix, bitmapFill.repeat, bitmapFill.smooth);
+                } else if (item is GraphicsShaderFill) {
+                    var shaderFill:GraphicsShaderFill = item as GraphicsShaderFill;
+                    beginShaderFill(shaderFill.shader, shaderFill.matrix);
+                } else if (item is GraphicsEndFill) {
+                    endFill();
+                } else if (item is GraphicsStroke) {
+                    applyStroke(item as GraphicsStroke);
+                } else if (item is GraphicsPath) {
+                    var path:GraphicsPath = item as GraphicsPath;
+                    if (path.commands != null && path.data != null) {
+                        drawPath(path.commands, path.data, path.winding);
+                    }
+                } else if (item is GraphicsTrianglePath) {
+                    var trianglePath:GraphicsTrianglePath = item as GraphicsTrianglePath;
+                    if (trianglePath.vertices != null) {
+                        drawTriangles(trianglePath.vertices, trianglePath.indices, trianglePath.uvtData, trianglePath.culling);
+                    }
+                }
+            }
+        }
+
+        private function applyStroke(stroke:GraphicsStroke):void {
+            if (isNaN(stroke.thickness)) {
+                lineStyle();
+                return;
+            }
+
+            var solidFill:GraphicsSolidFill = stroke.fill as GraphicsSolidFill;
+            if (solidFill != null) {
+                lineStyle(stroke.thickness, solidFill.color, solidFill.alpha, stroke.pixelHinting, stroke.scaleMode, stroke.caps, stroke.joints, stroke.miterLimit);
+                return;
+            }
+
+            lineStyle(stroke.thickness, 0, 1.0, stroke.pixelHinting, stroke.scaleMode, stroke.caps, stroke.joints, stroke.miterLimit);
+            if (stroke.fill is GraphicsGradientFill) {
+                var gradientFill:GraphicsGradientFill = stroke.fill as GraphicsGradientFill;
+                lineGradientStyle(
+                    gradientFill.type, gradientFill.colors, gradientFill.alphas, gradientFill.ratios, gradientFill.matrix,
+                    gradientFill.spreadMethod, gradientFill.interpolationMethod, gradientFill.focalPointRatio
+                );
+            } else if (stroke.fill is GraphicsBitmapFill) {
+                var bitmapFill:GraphicsBitmapFill = stroke.fill as GraphicsBitmapFill;
+                lineBitmapStyle(bitmapFill.bitmapData, bitmapFill.matrix, bitmapFill.repeat, bitmapFill.smooth);
+            } else if (stroke.fill is GraphicsShaderFill) {
+                stub_method("flash.display.Graphics", "drawGraphicsData", "with shader stroke");
+            }
+        }
         //public native function lineShaderStyle(shader:Shader, matrix:Matrix = null):void;
         public native function lineBitmapStyle(bitmap:BitmapData, matrix:Matrix = null, repeat:Boolean = true, smooth:Boolean = false):void;
         public native function readGraphicsData(recurse:Boolean = true):Vector.<IGraphicsData>;
@@ -1,34 +1,48 @@
 package flash.display {
     import __ruffle__.stub_method;
     import __ruffle__.stub_getter;
-    import __ruffle__.stub_setter;
-    import __ruffle__.stub_constructor;
     import flash.events.EventDispatcher;
+    import flash.events.ShaderEvent;
+    import flash.utils.ByteArray;
+    import flash.utils.setTimeout;
 
     public class ShaderJob extends EventDispatcher {
 
         private var _shader:Shader;
         private var _target:Object;
+        private var _width:int;
+        private var _height:int;
         
         public function ShaderJob(shader:Shader = null, target:Object = null, width:int = 0, height:int = 0) {
             this._shader = shader;
             this._target = target;
-            stub_constructor("flash.display.ShaderJob");
+            this._width = width;
+            this._height = height;
         }
 
         public function cancel():void {
             stub_method("flash.display.ShaderJob", "cancel")
         }
 
-        public native function start(waitForCompletion:Boolean = false):void;
+        public function start(waitForCompletion:Boolean = false):void {
+            this.runJob();
+            if (!waitForCompletion) {
+                // The job runs synchronously, but `complete` is still only dispatched
+                // after `start` has returned.
+                var event:ShaderEvent = new ShaderEvent(ShaderEvent.COMPLETE, false, false,
+                    this._target as BitmapData, this._target as ByteArray, this._target as Vector.<Number>);
+                setTimeout(this.dispatchEvent, 0, event);
+            }
+        }
+
+        private native function runJob():void;
 
         public function get height():int {
-            stub_getter("flash.display.ShaderJob", "height");
-            return 0;
+            return this._height;
         }
 
         public function set height(value:int):void {
-            stub_setter("flash.display.ShaderJob", "height");
+            this._height = value;
         }
 
         public function get progress():Number {
@@ -51,5 +65,13 @@ package flash.display {
         public function set target(value:Object):void {
             this._target = value;
         }
+
+        public function get width():int {
+            return this._width;
+        }
+
+        public function set width(value:int):void {
+            this._width = value;
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,10 +1,14 @@
 package flash.display {
     import flash.accessibility.AccessibilityProperties;
     import flash.errors.IllegalOperationError;
+    import flash.events.StageVideoAvailabilityEvent;
     import flash.filters.BitmapFilter;
     import flash.geom.Rectangle;
     import flash.geom.Transform;
+    import flash.media.StageVideo;
+    import flash.media.StageVideoAvailability;
     import flash.ui.ContextMenu;
+    import flash.utils.setTimeout;
 
     [Ruffle(NativeInstanceInit)]
     public class Stage extends DisplayObjectContainer {
@@ -161,6 +165,19 @@ package flash.display {
 
         public native function get stage3Ds():Vector.<Stage3D>;
 
+        public native function get stageVideos():Vector.<StageVideo>;
+
+        override public function addEventListener(type:String, listener:Function, useCapture:Boolean = false, priority:int = 0, useWeakReference:Boolean = false):void {
+            super.addEventListener(type, listener, useCapture, priority, useWeakReference);
+            if (type == StageVideoAvailabilityEvent.STAGE_VIDEO_AVAILABILITY) {
+                // Flash Player reports the availability asynchronously after a listener is added.
+                var stage:Stage = this;
+                setTimeout(function() {
+                    stage.dispatchEvent(new StageVideoAvailabilityEvent(type, false, false, StageVideoAvailability.AVAILABLE));
+                }, 0);
+            }
+        }
+
         public native function invalidate():void;
     }
 }
@@ -3,7 +3,6 @@ package flash.display {
     import flash.events.EventDispatcher;
     import flash.display3D.Context3D;
     import flash.utils.setTimeout;
-    import __ruffle__.stub_method;
 
     [Ruffle(InstanceAllocator)]
     public class Stage3D extends EventDispatcher {
@@ -22,9 +21,24 @@ package flash.display {
             }, 0);
         }
 
+        private static native function isProfileSupported(profile:String):Boolean;
+
         public function requestContext3DMatchingProfiles(profiles:Vector.<String>):void {
-            stub_method("flash.display.Stage3D", "requestContext3DMatchingProfiles");
-            requestContext3D();
+            // Pick the most capable of the requested profiles that we support.
+            var knownProfiles:Array = ["baselineConstrained", "baseline", "baselineExtended", "standardConstrained", "standard", "standardExtended"];
+            var best:int = -1;
+            for each (var profile:String in profiles) {
+                if (isProfileSupported(profile)) {
+                    best = Math.max(best, knownProfiles.indexOf(profile));
+                }
+            }
+            if (best == -1) {
+                // None are supported, so fall back to the closest supported profile.
+                for each (profile in profiles) {
+                    best = Math.max(best, knownProfiles.indexOf(profile));
+                }
+            }
+            requestContext3D("auto", best == -1 ? "baseline" : knownProfiles[best]);
         }
 
         // FIXME - actually implement this
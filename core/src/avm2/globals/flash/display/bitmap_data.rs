@@ -31,7 +31,7 @@ use swf::{Rectangle, Twips};
 // and `y + height` as floating point operations before
 // `round_to_even`, which is needed to match Flash Player's
 // rounding behavior.
-fn get_rectangle_x_y_width_height<'gc>(
+pub fn get_rectangle_x_y_width_height<'gc>(
     activation: &mut Activation<'_, 'gc>,
     rectangle: Object<'gc>,
 ) -> Result<(i32, i32, i32, i32), Error<'gc>> {
@@ -75,6 +75,7 @@ pub fn fill_bitmap_data_from_symbol<'gc>(
             Bitmap::height(*bd).into(),
             true,
             bd.bitmap_data().read().pixels().to_vec(),
+            activation.context.stage.bitmap_modifications(),
         ),
     );
     BitmapDataWrapper::new(new_bitmap_data)
@@ -133,7 +134,13 @@ pub fn init<'gc>(
             )?));
         }
 
-        let new_bitmap_data = BitmapData::new(width, height, transparency, fill_color);
+        let new_bitmap_data = BitmapData::new(
+            width,
+            height,
+            transparency,
+            fill_color,
+            activation.context.stage.bitmap_modifications(),
+        );
         BitmapDataWrapper::new(GcCell::new(activation.context.gc_context, new_bitmap_data))
     };
 
@@ -1086,8 +1093,8 @@ pub fn apply_filter<'gc>(
         }
 
         let source_point = (
-            source_rect.x_min.to_pixels().floor() as u32,
-            source_rect.y_min.to_pixels().floor() as u32,
+            source_rect.x_min.to_pixels().floor() as i32,
+            source_rect.y_min.to_pixels().floor() as i32,
         );
         let source_size = (
             source_rect.width().to_pixels().ceil() as u32,
@@ -1097,10 +1104,10 @@ pub fn apply_filter<'gc>(
         let dest_point = (
             dest_point
                 .get_public_property("x", activation)?
-                .coerce_to_u32(activation)?,
+                .coerce_to_i32(activation)?,
             dest_point
                 .get_public_property("y", activation)?
-                .coerce_to_u32(activation)?,
+                .coerce_to_i32(activation)?,
         );
 
         operations::apply_filter(
@@ -1116,6 +1123,48 @@ pub fn apply_filter<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implement `BitmapData.generateFilterRect`
+pub fn generate_filter_rect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.as_bitmap_data() {
+        bitmap_data.check_valid(activation)?;
+        let source_rect = args.get_object(activation, 0, "sourceRect")?;
+        let (x, y, width, height) = get_rectangle_x_y_width_height(activation, source_rect)?;
+        let filter = args.get_object(activation, 1, "filter")?;
+        let filter = Filter::from_avm2_object(activation, filter)?;
+
+        let dest_rect = operations::generate_filter_rect(
+            &mut activation.context,
+            bitmap_data,
+            Rectangle {
+                x_min: x,
+                y_min: y,
+                x_max: x + width,
+                y_max: y + height,
+            },
+            &filter,
+        );
+        return Ok(activation
+            .avm2()
+            .classes()
+            .rectangle
+            .construct(
+                activation,
+                &[
+                    dest_rect.x_min.into(),
+                    dest_rect.y_min.into(),
+                    dest_rect.width().into(),
+                    dest_rect.height().into(),
+                ],
+            )?
+            .into());
+    }
+    Ok(Value::Undefined)
+}
+
 /// Implement `BitmapData.clone`
 pub fn clone<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -1168,7 +1217,7 @@ pub fn palette_map<'gc>(
                 .get_public_property("x", activation)?
                 .coerce_to_i32(activation)?,
             dest_point
-                .get_public_property("x", activation)?
+                .get_public_property("y", activation)?
                 .coerce_to_i32(activation)?,
         );
 
@@ -1225,11 +1274,17 @@ pub fn perlin_noise<'gc>(
             let grayscale = args.get_bool(7);
             let offsets = args.try_get_object(activation, 8);
 
-            let octave_offsets: Result<Vec<_>, Error<'gc>> = (0..num_octaves)
-                .map(|i| {
-                    if let Some(offsets) = offsets {
-                        if let Some(offsets) = offsets.as_array_storage() {
-                            if let Some(Value::Object(e)) = offsets.get(i) {
+            // Only the octaves that have an offset need one - the rest default to (0, 0).
+            let octave_offsets =
+                if let Some(offsets) = offsets.as_ref().and_then(|o| o.as_array_storage()) {
+                    let values: Vec<_> = (0..num_octaves.min(offsets.length()))
+                        .map(|i| offsets.get(i))
+                        .collect();
+                    drop(offsets);
+                    values
+                        .into_iter()
+                        .map(|value| {
+                            if let Some(Value::Object(e)) = value {
                                 let x = e
                                     .get_public_property("x", activation)?
                                     .coerce_to_number(activation)?;
@@ -1240,14 +1295,11 @@ pub fn perlin_noise<'gc>(
                             } else {
                                 Ok((0.0, 0.0))
                             }
-                        } else {
-                            Ok((0.0, 0.0))
-                        }
-                    } else {
-                        Ok((0.0, 0.0))
-                    }
-                })
-                .collect();
+                        })
+                        .collect::<Result<Vec<_>, Error<'gc>>>()
+                } else {
+                    Ok(Vec::new())
+                };
             let octave_offsets = octave_offsets?;
 
             operations::perlin_noise(
@@ -2,17 +2,19 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error::make_error_2008;
-use crate::avm2::globals::flash::geom::transform::object_to_matrix;
-use crate::avm2::object::{Object, TObject, VectorObject};
+use crate::avm2::globals::flash::display::shader_job::get_shader_args;
+use crate::avm2::globals::flash::geom::transform::{matrix_to_object, object_to_matrix};
+use crate::avm2::object::{ArrayObject, Object, TObject, VectorObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::vector::VectorStorage;
 use crate::avm2::{ArrayStorage, Error};
 use crate::avm2_stub_method;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
 use crate::drawing::Drawing;
 use crate::string::{AvmString, WStr};
-use ruffle_render::shape_utils::{DrawCommand, GradientType};
+use ruffle_render::bitmap::BitmapSource;
+use ruffle_render::shape_utils::{DrawCommand, DrawPath, FillRule, GradientType};
 use std::f64::consts::FRAC_1_SQRT_2;
 use swf::{
     Color, FillStyle, Fixed16, Fixed8, Gradient, GradientInterpolation, GradientRecord,
@@ -90,6 +92,34 @@ pub fn begin_bitmap_fill<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Graphics.beginShaderFill`.
+pub fn begin_shader_fill<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_display_object() {
+        let shader = args.get_object(activation, 0, "shader")?;
+        let (shader_handle, arguments) = get_shader_args(shader, activation)?;
+        let matrix = if let Some(matrix) = args.try_get_object(activation, 1) {
+            Matrix::from(object_to_matrix(matrix, activation)?)
+        } else {
+            // Users can explicitly pass in `null` to mean identity matrix
+            Matrix::IDENTITY
+        };
+        let scale_matrix = Matrix::scale(
+            (Twips::TWIPS_PER_PIXEL as i16).into(),
+            (Twips::TWIPS_PER_PIXEL as i16).into(),
+        );
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_shader_fill_style(shader_handle, arguments, matrix * scale_matrix);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `Graphics.beginGradientFill`.
 pub fn begin_gradient_fill<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -956,23 +986,200 @@ pub fn draw_round_rect_complex<'gc>(
     Ok(Value::Undefined)
 }
 
-/// Implements `Graphics.drawTriangles`
-pub fn draw_triangles<'gc>(
+/// How `Graphics.drawTriangles` culls triangles, depending on which way they face.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TriangleCulling {
+    None,
+    Positive,
+    Negative,
+}
+
+impl TriangleCulling {
+    fn is_culled(self, [a, b, c]: [(f64, f64); 3]) -> bool {
+        // With the Y axis pointing down, triangles that are wound counterclockwise on screen
+        // face towards the viewer.
+        let cross = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+        match self {
+            TriangleCulling::None => false,
+            TriangleCulling::Positive => cross < 0.0,
+            TriangleCulling::Negative => cross > 0.0,
+        }
+    }
+}
+
+fn parse_triangle_culling<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "drawTriangles");
-    Ok(Value::Undefined)
+    culling: AvmString<'gc>,
+) -> Result<TriangleCulling, Error<'gc>> {
+    if &culling == b"none" {
+        Ok(TriangleCulling::None)
+    } else if &culling == b"positive" {
+        Ok(TriangleCulling::Positive)
+    } else if &culling == b"negative" {
+        Ok(TriangleCulling::Negative)
+    } else {
+        Err(make_error_2008(activation, "culling"))
+    }
 }
 
-/// Implements `Graphics.drawGraphicsData`
-pub fn draw_graphics_data<'gc>(
+fn vector_to_numbers<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    vector: Object<'gc>,
+) -> Result<Vec<f64>, Error<'gc>> {
+    let values: Vec<_> = vector
+        .as_vector_storage()
+        .expect("Guaranteed by AS")
+        .iter()
+        .collect();
+    values
+        .into_iter()
+        .map(|value| value.coerce_to_number(activation))
+        .collect()
+}
+
+/// Builds the matrix of a bitmap fill that maps the bitmap onto a triangle, so that each
+/// vertex shows the bitmap at its normalized `uv` coordinates.
+///
+/// The `t` component of `uvtData` is ignored, so the bitmap is mapped affinely rather than
+/// with perspective correction.
+fn triangle_bitmap_matrix(
+    points: [(f64, f64); 3],
+    uvs: [(f64, f64); 3],
+    width: f64,
+    height: f64,
+) -> Option<Matrix> {
+    let [s0, s1, s2] = points;
+    let [t0, t1, t2] = uvs.map(|(u, v)| (u * width, v * height));
+
+    let det = (t1.0 - t0.0) * (t2.1 - t0.1) - (t2.0 - t0.0) * (t1.1 - t0.1);
+    if det == 0.0 || !det.is_finite() {
+        return None;
+    }
+
+    // Solve for the linear part that maps the triangle's edges in bitmap space to its edges
+    // on screen, then for the translation that maps the first vertex.
+    let a = ((s1.0 - s0.0) * (t2.1 - t0.1) - (s2.0 - s0.0) * (t1.1 - t0.1)) / det;
+    let c = ((s2.0 - s0.0) * (t1.0 - t0.0) - (s1.0 - s0.0) * (t2.0 - t0.0)) / det;
+    let b = ((s1.1 - s0.1) * (t2.1 - t0.1) - (s2.1 - s0.1) * (t1.1 - t0.1)) / det;
+    let d = ((s2.1 - s0.1) * (t1.0 - t0.0) - (s1.1 - s0.1) * (t2.0 - t0.0)) / det;
+    let tx = s0.0 - (a * t0.0 + c * t0.1);
+    let ty = s0.1 - (b * t0.0 + d * t0.1);
+
+    // Bitmap fill matrices map from bitmap pixels to twips.
+    let twips_per_pixel = Twips::TWIPS_PER_PIXEL as f64;
+    Some(Matrix {
+        a: Fixed16::from_f64(a * twips_per_pixel),
+        b: Fixed16::from_f64(b * twips_per_pixel),
+        c: Fixed16::from_f64(c * twips_per_pixel),
+        d: Fixed16::from_f64(d * twips_per_pixel),
+        tx: Twips::from_pixels(tx),
+        ty: Twips::from_pixels(ty),
+    })
+}
+
+/// Implements `Graphics.drawTriangles`
+pub fn draw_triangles<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "drawGraphicsData");
+    if let Some(this) = this.as_display_object() {
+        let vertices = args.get_object(activation, 0, "vertices")?;
+        let vertices = vector_to_numbers(activation, vertices)?;
+        let indices = match args.try_get_object(activation, 1) {
+            Some(indices) => Some(vector_to_numbers(activation, indices)?),
+            None => None,
+        };
+        let uvt_data = match args.try_get_object(activation, 2) {
+            Some(uvt_data) => Some(vector_to_numbers(activation, uvt_data)?),
+            None => None,
+        };
+        let culling = args.get_string(activation, 3);
+        let culling = parse_triangle_culling(activation, culling?)?;
+
+        let vertex_count = vertices.len() / 2;
+        let vertex = |i: usize| (vertices[i * 2], vertices[i * 2 + 1]);
+
+        let triangles: Vec<[usize; 3]> = if let Some(indices) = &indices {
+            indices
+                .chunks_exact(3)
+                .map(|triangle| {
+                    // Negative indices are out of range, like indices past the last vertex.
+                    [0, 1, 2].map(|i| usize::try_from(triangle[i] as i64).unwrap_or(usize::MAX))
+                })
+                .collect()
+        } else {
+            (0..vertex_count / 3)
+                .map(|i| [i * 3, i * 3 + 1, i * 3 + 2])
+                .collect()
+        };
+
+        // `uvtData` has either two (`u`, `v`) or three (`u`, `v`, `t`) values per vertex.
+        let uv_stride = match &uvt_data {
+            Some(uvt_data) if uvt_data.len() == vertex_count * 3 => 3,
+            _ => 2,
+        };
+        let uv = |i: usize| {
+            uvt_data.as_ref().and_then(|uvt_data| {
+                Some((
+                    *uvt_data.get(i * uv_stride)?,
+                    *uvt_data.get(i * uv_stride + 1)?,
+                ))
+            })
+        };
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            let fill_style = draw.fill_style().cloned();
+            let bitmap_size = match &fill_style {
+                Some(FillStyle::Bitmap { id, .. }) => draw.bitmap_size(*id),
+                _ => None,
+            };
+
+            for triangle in triangles {
+                if triangle.iter().any(|&i| i >= vertex_count) {
+                    continue;
+                }
+
+                let points = triangle.map(vertex);
+                if culling.is_culled(points) {
+                    continue;
+                }
+
+                // Each triangle is filled on its own, so that overlapping triangles
+                // don't cancel each other out.
+                if let Some(fill_style) = &fill_style {
+                    let mut style = fill_style.clone();
+                    if let (
+                        FillStyle::Bitmap { matrix, .. },
+                        Some(size),
+                        [Some(uv0), Some(uv1), Some(uv2)],
+                    ) = (&mut style, &bitmap_size, triangle.map(uv))
+                    {
+                        if let Some(uv_matrix) = triangle_bitmap_matrix(
+                            points,
+                            [uv0, uv1, uv2],
+                            size.width.into(),
+                            size.height.into(),
+                        ) {
+                            *matrix = uv_matrix;
+                        }
+                    }
+                    draw.set_fill_style(Some(style));
+                }
+
+                let [a, b, c] = points.map(|(x, y)| Point::from_pixels(x, y));
+                draw.draw_command(DrawCommand::MoveTo(a));
+                draw.draw_command(DrawCommand::LineTo(b));
+                draw.draw_command(DrawCommand::LineTo(c));
+                draw.draw_command(DrawCommand::LineTo(a));
+            }
+
+            if fill_style.is_some() {
+                draw.set_fill_style(fill_style);
+            }
+        }
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -1005,8 +1212,8 @@ pub fn line_bitmap_style<'gc>(
             height: bitmap.height() as u16,
         };
         let scale_matrix = Matrix::scale(
-            Fixed16::from_f64(bitmap.width as f64),
-            Fixed16::from_f64(bitmap.height as f64),
+            (Twips::TWIPS_PER_PIXEL as i16).into(),
+            (Twips::TWIPS_PER_PIXEL as i16).into(),
         );
 
         if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
@@ -1023,14 +1230,288 @@ pub fn line_bitmap_style<'gc>(
     Ok(Value::Undefined)
 }
 
+/// A fill or stroke read from a drawing, to be converted into `IGraphicsData` objects.
+enum ReadPath {
+    Fill {
+        style: FillStyle,
+        commands: Vec<DrawCommand>,
+        winding_rule: FillRule,
+    },
+    Stroke {
+        style: LineStyle,
+        commands: Vec<DrawCommand>,
+    },
+}
+
 /// Implements `Graphics.readGraphicsData`
 pub fn read_graphics_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.display.Graphics", "readGraphicsData");
+    let recurse = args.get_bool(0);
+
+    let mut graphics_data = Vec::new();
+    if let Some(this) = this.as_display_object() {
+        read_graphics_data_internal(
+            activation,
+            this,
+            Matrix::IDENTITY,
+            recurse,
+            &mut graphics_data,
+        )?;
+    }
+
     let value_type = activation.avm2().classes().igraphicsdata;
-    let new_storage = VectorStorage::new(0, false, value_type, activation);
+    let new_storage = VectorStorage::from_values(graphics_data, false, value_type);
     Ok(VectorObject::from_vector(new_storage, activation)?.into())
 }
+
+fn read_graphics_data_internal<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: DisplayObject<'gc>,
+    matrix: Matrix,
+    recurse: bool,
+    graphics_data: &mut Vec<Value<'gc>>,
+) -> Result<(), Error<'gc>> {
+    let paths: Vec<_> = object
+        .as_drawing(activation.context.gc_context)
+        .map(|drawing| {
+            drawing
+                .paths()
+                .into_iter()
+                .map(|path| match path {
+                    DrawPath::Fill {
+                        style,
+                        commands,
+                        winding_rule,
+                    } => ReadPath::Fill {
+                        style: style.clone(),
+                        commands,
+                        winding_rule,
+                    },
+                    DrawPath::Stroke {
+                        style, commands, ..
+                    } => ReadPath::Stroke {
+                        style: style.clone(),
+                        commands,
+                    },
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for path in paths {
+        match path {
+            ReadPath::Fill {
+                style,
+                commands,
+                winding_rule,
+            } => {
+                let Some(fill) = fill_style_to_object(activation, &style, matrix)? else {
+                    continue;
+                };
+                let winding = match winding_rule {
+                    FillRule::EvenOdd => "evenOdd",
+                    FillRule::NonZero => "nonZero",
+                };
+                let path = commands_to_object(activation, &commands, matrix, winding)?;
+                let end_fill = activation
+                    .avm2()
+                    .classes()
+                    .graphicsendfill
+                    .construct(activation, &[])?;
+                graphics_data.extend([fill, path, end_fill.into()]);
+            }
+            ReadPath::Stroke { style, commands } => {
+                let Some(fill) = fill_style_to_object(activation, style.fill_style(), matrix)?
+                else {
+                    continue;
+                };
+                let scale_mode = match (style.allow_scale_x(), style.allow_scale_y()) {
+                    (false, false) => "none",
+                    (true, false) => "horizontal",
+                    (false, true) => "vertical",
+                    (true, true) => "normal",
+                };
+                let caps = match style.start_cap() {
+                    LineCapStyle::None => "none",
+                    LineCapStyle::Round => "round",
+                    LineCapStyle::Square => "square",
+                };
+                let (joints, miter_limit) = match style.join_style() {
+                    LineJoinStyle::Round => ("round", 3.0),
+                    LineJoinStyle::Bevel => ("bevel", 3.0),
+                    LineJoinStyle::Miter(miter_limit) => ("miter", miter_limit.to_f64()),
+                };
+                let args = [
+                    style.width().to_pixels().into(),
+                    style.is_pixel_hinted().into(),
+                    scale_mode.into(),
+                    caps.into(),
+                    joints.into(),
+                    miter_limit.into(),
+                    fill,
+                ];
+                let stroke = activation
+                    .avm2()
+                    .classes()
+                    .graphicsstroke
+                    .construct(activation, &args)?;
+                let path = commands_to_object(activation, &commands, matrix, "evenOdd")?;
+                // A stroke without a thickness ends the line, so that it isn't applied to
+                // any of the following paths.
+                let end_stroke = activation
+                    .avm2()
+                    .classes()
+                    .graphicsstroke
+                    .construct(activation, &[])?;
+                graphics_data.extend([stroke.into(), path, end_stroke.into()]);
+            }
+        }
+    }
+
+    if recurse {
+        if let Some(container) = object.as_container() {
+            for child in container.iter_render_list() {
+                let child_matrix = matrix * Matrix::from(*child.base().matrix());
+                read_graphics_data_internal(
+                    activation,
+                    child,
+                    child_matrix,
+                    recurse,
+                    graphics_data,
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts a fill style into a `GraphicsSolidFill` or `GraphicsGradientFill`.
+///
+/// Returns `None` for fills that can't be represented.
+fn fill_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: &FillStyle,
+    matrix: Matrix,
+) -> Result<Option<Value<'gc>>, Error<'gc>> {
+    let (gradient_type, gradient, focal_point) = match style {
+        FillStyle::Color(color) => {
+            let args = [color.to_rgb().into(), (color.a as f64 / 255.0).into()];
+            let fill = activation
+                .avm2()
+                .classes()
+                .graphicssolidfill
+                .construct(activation, &args)?;
+            return Ok(Some(fill.into()));
+        }
+        FillStyle::LinearGradient(gradient) => ("linear", gradient, 0.0),
+        FillStyle::RadialGradient(gradient) => ("radial", gradient, 0.0),
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => ("radial", gradient, focal_point.to_f64()),
+        FillStyle::Bitmap { .. } => {
+            avm2_stub_method!(
+                activation,
+                "flash.display.Graphics",
+                "readGraphicsData",
+                "bitmap fills"
+            );
+            return Ok(None);
+        }
+    };
+
+    let colors: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| record.color.to_rgb().into())
+        .collect();
+    let alphas: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| (record.color.a as f64 / 255.0).into())
+        .collect();
+    let ratios: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| record.ratio.into())
+        .collect();
+    let spread = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation = match gradient.interpolation {
+        GradientInterpolation::Rgb => "rgb",
+        GradientInterpolation::LinearRgb => "linearRGB",
+    };
+
+    let args = [
+        gradient_type.into(),
+        ArrayObject::from_storage(activation, ArrayStorage::from_args(&colors))?.into(),
+        ArrayObject::from_storage(activation, ArrayStorage::from_args(&alphas))?.into(),
+        ArrayObject::from_storage(activation, ArrayStorage::from_args(&ratios))?.into(),
+        matrix_to_object((matrix * gradient.matrix).into(), activation)?,
+        spread.into(),
+        interpolation.into(),
+        focal_point.into(),
+    ];
+    let fill = activation
+        .avm2()
+        .classes()
+        .graphicsgradientfill
+        .construct(activation, &args)?;
+    Ok(Some(fill.into()))
+}
+
+/// Converts drawing commands into a `GraphicsPath`.
+fn commands_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    commands: &[DrawCommand],
+    matrix: Matrix,
+    winding: &'static str,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut path_commands = Vec::with_capacity(commands.len());
+    let mut data = Vec::with_capacity(commands.len() * 2);
+    let mut push_point = |point: Point<Twips>| {
+        let point = matrix * point;
+        data.push(point.x.to_pixels().into());
+        data.push(point.y.to_pixels().into());
+    };
+
+    for command in commands {
+        match command {
+            DrawCommand::MoveTo(point) => {
+                path_commands.push(Value::Integer(1));
+                push_point(*point);
+            }
+            DrawCommand::LineTo(point) => {
+                path_commands.push(Value::Integer(2));
+                push_point(*point);
+            }
+            DrawCommand::CurveTo { control, anchor } => {
+                path_commands.push(Value::Integer(3));
+                push_point(*control);
+                push_point(*anchor);
+            }
+        }
+    }
+
+    let int_class = activation.avm2().classes().int;
+    let path_commands = VectorStorage::from_values(path_commands, false, int_class);
+    let path_commands = VectorObject::from_vector(path_commands, activation)?;
+    let number_class = activation.avm2().classes().number;
+    let data = VectorStorage::from_values(data, false, number_class);
+    let data = VectorObject::from_vector(data, activation)?;
+
+    let args = [path_commands.into(), data.into(), winding.into()];
+    let path = activation
+        .avm2()
+        .classes()
+        .graphicspath
+        .construct(activation, &args)?;
+    Ok(path.into())
+}
@@ -13,7 +13,7 @@ use crate::avm2::ClassObject;
 use crate::avm2::Multiname;
 use crate::avm2::{Error, Object};
 use crate::avm2_stub_method;
-use crate::backend::navigator::{NavigationMethod, Request};
+use crate::backend::navigator::{is_allowed_request_header, NavigationMethod, Request};
 use crate::display_object::LoaderDisplay;
 use crate::display_object::MovieClip;
 use crate::loader::MovieLoaderVMData;
@@ -117,9 +117,6 @@ pub fn request_from_url_request<'gc>(
     activation: &mut Activation<'_, 'gc>,
     url_request: Object<'gc>,
 ) -> Result<Request, Error<'gc>> {
-    // FIXME: set `followRedirects`  and `userAgent`
-    // from the `URLRequest`
-
     let mut url = url_request
         .get_public_property("url", activation)?
         .coerce_to_string(activation)?
@@ -151,12 +148,31 @@ pub fn request_from_url_request<'gc>(
             .coerce_to_string(activation)?
             .to_string();
 
+        if !is_allowed_request_header(&name) {
+            tracing::warn!("Ignoring disallowed request header {name}");
+            continue;
+        }
+
         // Note - testing with Flash Player shows that later entries in the array
         // overwrite earlier ones with the same name. Flash Player never sends an HTTP
         // request with duplicate headers
         string_headers.insert(name, value);
     }
 
+    // The user agent can't be set through `requestHeaders`, only through this property.
+    let user_agent = url_request.get_public_property("userAgent", activation)?;
+    if !matches!(user_agent, Value::Null | Value::Undefined) {
+        let user_agent = user_agent.coerce_to_string(activation)?.to_string();
+        string_headers.insert("User-Agent".to_string(), user_agent);
+    }
+
+    let follow_redirects = url_request
+        .get_public_property("followRedirects", activation)?
+        .coerce_to_boolean();
+    let manage_cookies = url_request
+        .get_public_property("manageCookies", activation)?
+        .coerce_to_boolean();
+
     // TODO: URLRequest.method should not be able to have invalid types.
     // We should throw an error there on set.
     let method = NavigationMethod::from_method_str(&method).unwrap_or(NavigationMethod::Get);
@@ -197,6 +213,8 @@ pub fn request_from_url_request<'gc>(
 
     let mut request = Request::request(method, url.to_string(), body);
     request.set_headers(string_headers);
+    request.set_follow_redirects(follow_redirects);
+    request.set_manage_cookies(manage_cookies);
 
     Ok(request)
 }
@@ -2,12 +2,17 @@ use ruffle_render::{
     bitmap::PixelRegion,
     pixel_bender::{
         PixelBenderParam, PixelBenderParamQualifier, PixelBenderShaderArgument,
-        PixelBenderShaderHandle, PixelBenderType, OUT_COORD_NAME,
+        PixelBenderShaderHandle, PixelBenderType, PixelBenderTypeOpcode, OUT_COORD_NAME,
     },
 };
 
 use crate::{
-    avm2::{string::AvmString, Activation, Error, Object, TObject, Value},
+    avm2::{
+        bytearray::Endian,
+        error::{argument_error, error},
+        string::AvmString,
+        Activation, Error, Object, TObject, Value,
+    },
     avm2_stub_method,
     pixel_bender::PixelBenderTypeExt,
 };
@@ -130,8 +135,9 @@ pub fn get_shader_args<'gc>(
     Ok((shader_handle.clone(), args))
 }
 
-/// Implements `ShaderJob.start`.
-pub fn start<'gc>(
+/// Implements `ShaderJob.runJob`, which `ShaderJob.start` calls before it dispatches
+/// the `complete` event.
+pub fn run_job<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
@@ -140,44 +146,144 @@ pub fn start<'gc>(
         activation,
         "flash.display.ShaderJob",
         "start",
-        "async execution and non-BitmapData inputs"
+        "non-BitmapData inputs"
     );
 
-    let shader = this
-        .get_public_property("shader", activation)?
-        .as_object()
-        .expect("Missing Shader object");
+    let Some(shader) = this.get_public_property("shader", activation)?.as_object() else {
+        return Err(null_parameter_error(activation, "shader"));
+    };
 
     let (shader_handle, arguments) = get_shader_args(shader, activation)?;
 
-    let target = this
-        .get_public_property("target", activation)?
-        .as_object()
-        .expect("ShaderJob.target is not an object");
+    let Some(target) = this.get_public_property("target", activation)?.as_object() else {
+        return Err(null_parameter_error(activation, "target"));
+    };
+
+    if let Some(target_bitmap) = target.as_bitmap_data() {
+        let target_bitmap = target_bitmap.sync();
+
+        // Perform both a GPU->CPU and CPU->GPU sync before writing to it.
+        // FIXME - are both necessary?
+        let mut target_bitmap_data = target_bitmap.write(activation.context.gc_context);
+        target_bitmap_data.update_dirty_texture(activation.context.renderer);
 
-    let target_bitmap = target
-        .as_bitmap_data()
-        .expect("ShaderJob.target is not a BitmapData (FIXME - support other types)")
-        .sync();
+        let target_handle = target_bitmap_data
+            .bitmap_handle(activation.context.renderer)
+            .expect("Missing handle");
 
-    // Perform both a GPU->CPU and CPU->GPU sync before writing to it.
-    // FIXME - are both necessary?
-    let mut target_bitmap_data = target_bitmap.write(activation.context.gc_context);
-    target_bitmap_data.update_dirty_texture(activation.context.renderer);
+        let sync_handle = activation.context.renderer.run_pixelbender_shader(
+            shader_handle,
+            &arguments,
+            target_handle,
+        );
+        let sync_handle = match sync_handle {
+            Ok(sync_handle) => sync_handle,
+            Err(e) => {
+                drop(target_bitmap_data);
+                return Err(shader_failed_error(activation, e));
+            }
+        };
+
+        let width = target_bitmap_data.width();
+        let height = target_bitmap_data.height();
+        target_bitmap_data.set_gpu_dirty(sync_handle, PixelRegion::for_whole_size(width, height));
+        return Ok(Value::Undefined);
+    }
+
+    // `ByteArray` and `Vector.<Number>` targets receive the raw float output of the shader,
+    // so the size of the job has to be specified explicitly.
+    let is_byte_array = target.as_bytearray().is_some();
+    let is_number_vector = target.as_vector_storage().map_or(false, |vector| {
+        vector.value_type() == activation.avm2().classes().number
+    });
+    if !is_byte_array && !is_number_vector {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2004: One of the parameters is invalid.",
+            2004,
+        )?));
+    }
 
-    let target_handle = target_bitmap_data
-        .bitmap_handle(activation.context.renderer)
-        .expect("Missing handle");
+    let width = this
+        .get_public_property("width", activation)?
+        .coerce_to_i32(activation)?
+        .max(0) as u32;
+    let height = this
+        .get_public_property("height", activation)?
+        .coerce_to_i32(activation)?
+        .max(0) as u32;
 
-    let sync_handle = activation
+    let channels = output_channels(&shader_handle);
+    let pixels = match activation
         .context
         .renderer
-        .run_pixelbender_shader(shader_handle, &arguments, target_handle)
-        .expect("Failed to run shader");
+        .run_pixelbender_shader_to_floats(shader_handle, &arguments, width, height)
+    {
+        Ok(pixels) => pixels,
+        Err(e) => return Err(shader_failed_error(activation, e)),
+    };
 
-    let width = target_bitmap_data.width();
-    let height = target_bitmap_data.height();
-    target_bitmap_data.set_gpu_dirty(sync_handle, PixelRegion::for_whole_size(width, height));
+    let output = pixels.chunks_exact(4).flat_map(|pixel| &pixel[..channels]);
+
+    if let Some(mut byte_array) = target.as_bytearray_mut(activation.context.gc_context) {
+        let endian = byte_array.endian();
+        let bytes: Vec<u8> = output
+            .flat_map(|val| match endian {
+                Endian::Big => val.to_be_bytes(),
+                Endian::Little => val.to_le_bytes(),
+            })
+            .collect();
+        byte_array
+            .write_at(&bytes, 0)
+            .map_err(|e| e.to_avm(activation))?;
+    } else if let Some(mut vector) = target.as_vector_storage_mut(activation.context.gc_context) {
+        vector.replace_storage(output.map(|val| Value::Number(*val as f64)).collect());
+    }
 
     Ok(Value::Undefined)
 }
+
+fn null_parameter_error<'gc>(activation: &mut Activation<'_, 'gc>, name: &str) -> Error<'gc> {
+    match argument_error(
+        activation,
+        &format!("Error #2007: Parameter {name} must be non-null."),
+        2007,
+    ) {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
+
+fn shader_failed_error<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    e: ruffle_render::error::Error,
+) -> Error<'gc> {
+    tracing::error!("Failed to run shader: {e:?}");
+    match error(activation, "Error: The shader job could not be run.", 0) {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
+
+/// Returns the number of channels written by the shader's output parameter.
+fn output_channels(shader: &PixelBenderShaderHandle) -> usize {
+    shader
+        .0
+        .parsed_shader()
+        .params
+        .iter()
+        .find_map(|param| match param {
+            PixelBenderParam::Normal {
+                qualifier: PixelBenderParamQualifier::Output,
+                param_type,
+                ..
+            } => Some(match param_type {
+                PixelBenderTypeOpcode::TFloat => 1,
+                PixelBenderTypeOpcode::TFloat2 => 2,
+                PixelBenderTypeOpcode::TFloat3 => 3,
+                _ => 4,
+            }),
+            _ => None,
+        })
+        .unwrap_or(4)
+}
@@ -2,14 +2,14 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::error::make_error_2008;
+use crate::avm2::globals::flash::display::display_object::object_to_rectangle;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::{ArrayObject, ArrayStorage};
-use crate::display_object::{StageDisplayState, TDisplayObject};
+use crate::display_object::TDisplayObject;
 use crate::string::{AvmString, WString};
-use crate::{avm2_stub_getter, avm2_stub_setter};
 use swf::Color;
 
 /// Implements `flash.display.Stage`'s native instance constructor.
@@ -157,12 +157,7 @@ pub fn set_display_state<'gc>(
     _this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Ok(mut display_state) = args.get_string(activation, 0)?.parse() {
-        // It's not entirely clear why when setting to FullScreen, desktop flash player at least will
-        // set its value to FullScreenInteractive. Overriding until flash logic is clearer.
-        if display_state == StageDisplayState::FullScreen {
-            display_state = StageDisplayState::FullScreenInteractive;
-        }
+    if let Ok(display_state) = args.get_string(activation, 0)?.parse() {
         activation
             .context
             .stage
@@ -225,8 +220,11 @@ pub fn set_frame_rate<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if !activation.context.forced_frame_rate {
+        // Flash Player clamps the frame rate, rather than throwing for values out of range.
         let new_frame_rate = args.get_f64(activation, 0)?;
-        *activation.context.frame_rate = new_frame_rate;
+        if !new_frame_rate.is_nan() {
+            *activation.context.frame_rate = new_frame_rate.clamp(0.01, 1000.0);
+        }
     }
 
     Ok(Value::Undefined)
@@ -284,8 +282,6 @@ pub fn set_scale_mode<'gc>(
 }
 
 /// Implement `stageFocusRect`'s getter
-///
-/// This setting is currently ignored in Ruffle.
 pub fn get_stage_focus_rect<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -299,8 +295,6 @@ pub fn get_stage_focus_rect<'gc>(
 }
 
 /// Implement `stageFocusRect`'s setter
-///
-/// This setting is currently ignored in Ruffle.
 pub fn set_stage_focus_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -362,26 +356,20 @@ pub fn set_stage_height<'gc>(
 
 /// Implement `allowsFullScreen`'s getter
 pub fn get_allows_full_screen<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.Stage", "allowsFullScreen");
     Ok(true.into())
 }
 
 /// Implement `allowsFullScreenInteractive`'s getter
 pub fn get_allows_full_screen_interactive<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(
-        activation,
-        "flash.display.Stage",
-        "allowsFullScreenInteractive"
-    );
-    Ok(false.into())
+    Ok(true.into())
 }
 
 /// Implement `quality`'s getter
@@ -430,6 +418,26 @@ pub fn get_stage3ds<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implement `stageVideos`'s getter
+pub fn get_stage_videos<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(stage) = this.as_display_object().and_then(|this| this.as_stage()) {
+        let storage = ArrayStorage::from_storage(
+            stage
+                .stage_videos()
+                .iter()
+                .map(|obj| Some(Value::Object(*obj)))
+                .collect(),
+        );
+        let stage_videos_array = ArrayObject::from_storage(activation, storage)?;
+        return Ok(stage_videos_array.into());
+    }
+    Ok(Value::Undefined)
+}
+
 /// Implement `invalidate`
 pub fn invalidate<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -438,6 +446,7 @@ pub fn invalidate<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(stage) = this.as_display_object().and_then(|this| this.as_stage()) {
         stage.set_invalidated(activation.context.gc_context, true);
+        *activation.context.needs_render = true;
     }
     Ok(Value::Undefined)
 }
@@ -448,28 +457,53 @@ pub fn get_full_screen_source_rect<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.Stage", "fullScreenSourceRect");
-    Ok(Value::Undefined)
+    let Some(rect) = activation.context.stage.full_screen_source_rect() else {
+        return Ok(Value::Null);
+    };
+    let rect = activation.avm2().classes().rectangle.construct(
+        activation,
+        &[
+            rect.x_min.to_pixels().into(),
+            rect.y_min.to_pixels().into(),
+            rect.width().to_pixels().into(),
+            rect.height().to_pixels().into(),
+        ],
+    )?;
+    Ok(rect.into())
 }
 
 /// Stage.fullScreenSourceRect's setter
 pub fn set_full_screen_source_rect<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.display.Stage", "fullScreenSourceRect");
+    let rect = match args.try_get_object(activation, 0) {
+        Some(rect) => Some(object_to_rectangle(activation, rect)?),
+        None => None,
+    };
+    activation
+        .context
+        .stage
+        .set_full_screen_source_rect(&mut activation.context, rect);
     Ok(Value::Undefined)
 }
 
+/// The size of the screen that fullscreen mode fills, in pixels.
+fn full_screen_size(activation: &mut Activation<'_, '_>) -> (u32, u32) {
+    activation.context.ui.screen_size().unwrap_or_else(|| {
+        let viewport = activation.context.renderer.viewport_dimensions();
+        (viewport.width, viewport.height)
+    })
+}
+
 /// Stage.fullScreenHeight's getter
 pub fn get_full_screen_height<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.Stage", "fullScreenHeight");
-    Ok(768.into())
+    Ok(full_screen_size(activation).1.into())
 }
 
 /// Stage.fullScreenWidth's getter
@@ -478,6 +512,5 @@ pub fn get_full_screen_width<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.display.Stage", "fullScreenWidth");
-    Ok(1024.into())
+    Ok(full_screen_size(activation).0.into())
 }
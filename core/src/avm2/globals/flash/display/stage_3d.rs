@@ -1,20 +1,48 @@
-use crate::avm2::object::Context3DObject;
+use crate::avm2::error::make_error_2008;
 use crate::avm2::object::TObject;
+use crate::avm2::object::{Context3DObject, Context3DProfile};
 
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, Value};
+use crate::avm2_stub_method;
 
 pub use crate::avm2::object::stage_3d_allocator;
 
+pub fn is_profile_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let profile = args.get_string(activation, 0)?;
+    Ok(Context3DProfile::from_wstr(&profile)
+        .map_or(false, Context3DProfile::is_supported)
+        .into())
+}
+
 pub fn request_context3d_internal<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this_stage3d = this.as_stage_3d().unwrap();
     if this_stage3d.context3d().is_none() {
+        let profile = args.get_string(activation, 1)?;
+        let Some(profile) = Context3DProfile::from_wstr(&profile) else {
+            return Err(make_error_2008(activation, "profile"));
+        };
+        if !profile.is_supported() {
+            avm2_stub_method!(
+                activation,
+                "flash.display.Stage3D",
+                "requestContext3D",
+                "standard profiles"
+            );
+        }
+        // Like a GPU without support for the requested profile, fall back to the closest one
+        // we support, so that `Context3D.profile` reports what the context can actually do.
+        let profile = profile.closest_supported();
         let context = activation.context.renderer.create_context3d()?;
-        let context3d_obj = Context3DObject::from_context(activation, context)?;
+        let context3d_obj = Context3DObject::from_context(activation, context, profile)?;
         this_stage3d.set_context3d(context3d_obj, activation.context.gc_context);
 
         let event = activation
@@ -1,11 +1,14 @@
 package flash.display3D {
+    import flash.display.BitmapData;
     import flash.events.EventDispatcher;
     import flash.geom.Matrix3D;
+    import flash.geom.Point;
     import flash.geom.Rectangle;
     import flash.display3D.textures.CubeTexture;
     import flash.display3D.textures.TextureBase;
     import flash.display3D.textures.RectangleTexture;
     import flash.display3D.textures.Texture;
+    import flash.display3D.textures.VideoTexture;
     import __ruffle__.stub_method;
     import __ruffle__.stub_getter;
 
@@ -14,6 +17,7 @@ package flash.display3D {
 
         public native function createIndexBuffer(numIndices:int, bufferUsage:String = "staticDraw"):IndexBuffer3D;
         public native function createVertexBuffer(numVertices:int, data32PerVertex:int, bufferUsage:String = "staticDraw"):VertexBuffer3D;
+        public native function createVertexBufferForInstances(numVertices:int, data32PerVertex:int, instancesPerElement:int, bufferUsage:String = "staticDraw"):VertexBuffer3D;
         public native function configureBackBuffer(
             width:int, height:int, antiAlias:int, enableDepthAndStencil:Boolean = true, wantsBestResolution:Boolean = false, wantsBestResolutionOnBrowserZoom:Boolean = false
             ):void;
@@ -21,11 +25,18 @@ package flash.display3D {
         public native function createProgram():Program3D;
         public native function setProgram(program:Program3D):void;
         public native function drawTriangles(indexBuffer:IndexBuffer3D, firstIndex:int = 0, numTriangles:int = -1):void;
+        public native function drawTrianglesInstanced(indexBuffer:IndexBuffer3D, numInstances:int, firstIndex:int = 0, numTriangles:int = -1):void;
         public native function present():void;
+        public native function drawToBitmapData(destination:BitmapData, srcRect:Rectangle = null, destPoint:Point = null):void;
         public native function setCulling(triangleFaceToCull:String):void;
         public native function createTexture(width:int, height:int, format:String, optimizeForRenderToTexture:Boolean, streamingLevels:int = 0):Texture;
         public native function createCubeTexture(size:int, format:String, optimizeForRenderToTexture:Boolean, streamingLevels:int = 0):CubeTexture;
         public native function createRectangleTexture(width:int, height:int, format:String, optimizeForRenderToTexture:Boolean):RectangleTexture;
+        public native function createVideoTexture():VideoTexture;
+
+        public static function get supportsVideoTexture():Boolean {
+            return true;
+        }
 
         public function get driverInfo():String {
             stub_getter("flash.display3D.Context3D", "driverInfo");
@@ -47,10 +58,7 @@ package flash.display3D {
 
         public native function setTextureAt(sampler:int, texture:TextureBase):void;
 
-        public function get profile():String {
-            stub_getter("flash.display3D.Context3D", "profile");
-            return "baseline";
-        }
+        public native function get profile():String;
 
         public function get maxBackBufferWidth():int {
             stub_getter("flash.display3D.Context3D", "maxBackBufferWidth");
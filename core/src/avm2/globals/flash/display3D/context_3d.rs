@@ -1,10 +1,14 @@
 use crate::avm2::error::{argument_error, error, make_error_2008};
+use crate::avm2::globals::flash::display::bitmap_data::get_rectangle_x_y_width_height;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Activation;
 use crate::avm2::TObject;
 use crate::avm2::Value;
 use crate::avm2::{Error, Object};
 use crate::avm2_stub_method;
+use crate::bitmap::bitmap_data::BitmapDataDrawError;
+use crate::bitmap::operations;
+use ruffle_render::backend::Context3DMipFilter;
 use ruffle_render::backend::Context3DTextureFilter;
 use ruffle_render::backend::Context3DWrapMode;
 use ruffle_render::backend::{
@@ -43,6 +47,36 @@ pub fn create_vertex_buffer<'gc>(
         return context.create_vertex_buffer(
             num_vertices,
             data_32_per_vertex as u8,
+            0,
+            BufferUsage::DynamicDraw,
+            activation,
+        );
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn create_vertex_buffer_for_instances<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(context) = this.as_context_3d() {
+        // FIXME - get bufferUsage and pass it through
+        let num_vertices = args.get_u32(activation, 0)?;
+        let data_32_per_vertex = args.get_u32(activation, 1)?;
+        let instances_per_element = args.get_u32(activation, 2)?;
+
+        if data_32_per_vertex > 64 {
+            return Err("data_32_per_vertex is greater than 64".into());
+        }
+        if instances_per_element == 0 {
+            return Err(make_error_2008(activation, "instancesPerElement"));
+        }
+
+        return context.create_vertex_buffer(
+            num_vertices,
+            data_32_per_vertex as u8,
+            instances_per_element,
             BufferUsage::DynamicDraw,
             activation,
         );
@@ -50,6 +84,17 @@ pub fn create_vertex_buffer<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_profile<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(context) = this.as_context_3d() {
+        return Ok(context.profile().as_str().into());
+    }
+    Ok(Value::Undefined)
+}
+
 pub fn configure_back_buffer<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -213,7 +258,29 @@ pub fn draw_triangles<'gc>(
         let first_index = args.get_u32(activation, 1)?;
         let num_triangles = args.get_u32(activation, 2)? as i32;
 
-        context.draw_triangles(index_buffer, first_index, num_triangles);
+        context.draw_triangles(index_buffer, first_index, num_triangles, 1);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn draw_triangles_instanced<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(context) = this.as_context_3d() {
+        let index_buffer = args
+            .get(0)
+            .unwrap_or(&Value::Undefined)
+            .coerce_to_object(activation)?
+            .as_index_buffer()
+            .unwrap();
+
+        let num_instances = args.get_u32(activation, 1)?;
+        let first_index = args.get_u32(activation, 2)?;
+        let num_triangles = args.get_u32(activation, 3)? as i32;
+
+        context.draw_triangles(index_buffer, first_index, num_triangles, num_instances);
     }
     Ok(Value::Undefined)
 }
@@ -229,6 +296,54 @@ pub fn present<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn draw_to_bitmap_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(context) = this.as_context_3d() {
+        let destination = args.get_object(activation, 0, "destination")?;
+        if let Some(destination) = destination.as_bitmap_data() {
+            destination.check_valid(activation)?;
+
+            let Some(back_buffer) = context.back_buffer() else {
+                // Nothing has been drawn yet, as the back buffer hasn't been configured.
+                return Ok(Value::Undefined);
+            };
+
+            let src_rect = match args.try_get_object(activation, 1) {
+                Some(rect) => get_rectangle_x_y_width_height(activation, rect)?,
+                None => (0, 0, back_buffer.width.into(), back_buffer.height.into()),
+            };
+            let dest_point = match args.try_get_object(activation, 2) {
+                Some(point) => (
+                    point
+                        .get_public_property("x", activation)?
+                        .coerce_to_i32(activation)?,
+                    point
+                        .get_public_property("y", activation)?
+                        .coerce_to_i32(activation)?,
+                ),
+                None => (0, 0),
+            };
+
+            match operations::draw_stage3d_back_buffer(
+                &mut activation.context,
+                destination,
+                back_buffer,
+                src_rect,
+                dest_point,
+            ) {
+                Ok(()) => {}
+                Err(BitmapDataDrawError::Unimplemented) => {
+                    return Err("Render backend does not support Context3D.drawToBitmapData".into());
+                }
+            }
+        }
+    }
+    Ok(Value::Undefined)
+}
+
 pub fn set_culling<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -447,6 +562,28 @@ pub fn create_rectangle_texture<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn create_video_texture<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(context) = this.as_context_3d() {
+        let class = activation.avm2().classes().videotexture;
+
+        // The real texture is created once we know the size of the attached video.
+        return context.create_texture(
+            1,
+            1,
+            Context3DTextureFormat::Bgra,
+            false,
+            0,
+            class,
+            activation,
+        );
+    }
+    Ok(Value::Undefined)
+}
+
 pub fn create_cube_texture<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -488,21 +625,23 @@ pub fn set_texture_at<'gc>(
         // This is a native method, so all of the arguments have been checked and coerced for us
         let sampler = args[0].as_integer(activation.context.gc_context)? as u32;
         let mut cube = false;
+        let mut clamp = false;
         let texture = if matches!(args[1], Value::Null) {
             None
         } else {
             let obj = args[1].coerce_to_object(activation)?;
-            cube = obj.is_of_type(
-                activation
-                    .avm2()
-                    .classes()
-                    .cubetexture
-                    .inner_class_definition(),
-                &mut activation.context,
-            );
+            let classes = activation.avm2().classes();
+            let cube_class = classes.cubetexture.inner_class_definition();
+            let rectangle_class = classes.rectangletexture.inner_class_definition();
+            let video_class = classes.videotexture.inner_class_definition();
+
+            cube = obj.is_of_type(cube_class, &mut activation.context);
+            // Rectangle and video textures have no mipmaps, and can't be sampled with wrapping.
+            clamp = obj.is_of_type(rectangle_class, &mut activation.context)
+                || obj.is_of_type(video_class, &mut activation.context);
             Some(obj.as_texture().unwrap().handle())
         };
-        context.set_texture_at(sampler, texture, cube);
+        context.set_texture_at(sampler, texture, cube, clamp);
     }
     Ok(Value::Undefined)
 }
@@ -585,15 +724,6 @@ pub fn set_render_to_texture<'gc>(
     let surface_selector = args.get_u32(activation, 3)?;
     let color_output_index = args.get_u32(activation, 4)?;
 
-    if anti_alias != 0 {
-        avm2_stub_method!(
-            activation,
-            "flash.display3D.Context3D",
-            "setRenderToTexture",
-            "antiAlias != 0"
-        );
-    }
-
     if surface_selector != 0 {
         avm2_stub_method!(
             activation,
@@ -664,16 +794,12 @@ pub fn set_sampler_state_at<'gc>(
             );
         }
 
-        if &*mip_filter != b"mipnone" {
-            avm2_stub_method!(
-                activation,
-                "flash.display3D.Context3D",
-                "setSamplerStateAt",
-                "mipFilter != 'none'"
-            );
-        }
+        // Mip levels other than the first can't be uploaded yet, which is stubbed
+        // in the texture upload methods instead.
+        let mip_filter = Context3DMipFilter::from_wstr(&mip_filter)
+            .ok_or_else(|| make_error_2008(activation, "mipfilter"))?;
 
-        context.set_sampler_state_at(sampler, wrap, filter);
+        context.set_sampler_state_at(sampler, wrap, filter, mip_filter);
     }
     Ok(Value::Undefined)
 }
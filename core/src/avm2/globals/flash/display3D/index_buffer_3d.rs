@@ -1,4 +1,5 @@
-use crate::avm2::object::{ClassObject, TObject};
+use crate::avm2::error::error;
+use crate::avm2::object::{ClassObject, IndexBuffer3DObject, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Activation;
 use crate::avm2::Value;
@@ -11,6 +12,23 @@ pub fn index_buffer_3d_allocator<'gc>(
     panic!("This allocator should not be called!")
 }
 
+/// Checks that an upload of `count` indices starting at `start_offset` fits within the buffer.
+fn check_upload_range<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    index_buffer: IndexBuffer3DObject<'gc>,
+    start_offset: u32,
+    count: u32,
+) -> Result<(), Error<'gc>> {
+    if start_offset as u64 + count as u64 > index_buffer.num_indices() as u64 {
+        return Err(Error::AvmError(error(
+            activation,
+            "Error #3669: Bad input size.",
+            3669,
+        )?));
+    }
+    Ok(())
+}
+
 pub fn upload_from_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -25,6 +43,7 @@ pub fn upload_from_byte_array<'gc>(
         let byte_offset = args.get_u32(activation, 1)?;
         let start_offset = args.get_u32(activation, 2)?;
         let count = args.get_u32(activation, 3)?;
+        check_upload_range(activation, index_buffer, start_offset, count)?;
 
         let data = byte_array
             // Each index is always 16 bits (2 bytes)
@@ -58,8 +77,7 @@ pub fn upload_from_vector<'gc>(
 
         let start_offset = args.get_u32(activation, 1)?;
         let count = args.get_u32(activation, 2)?;
-
-        index_buffer.set_count(count as usize);
+        check_upload_range(activation, index_buffer, start_offset, count)?;
 
         let data: Result<Vec<u16>, _> = vector
             .iter()
@@ -1,8 +1,10 @@
+use crate::avm2::error::error;
 use crate::avm2::Activation;
 
 use crate::avm2::TObject;
 use crate::avm2::Value;
 use crate::avm2::{Error, Object};
+use naga_agal::{validate_agal, ShaderType};
 
 pub fn upload<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -28,6 +30,17 @@ pub fn upload<'gc>(
             .ok_or_else(|| Error::from("ArgumentError: Parameter must be a ByteArray"))?;
         let fragment_agal = fragment_agal.bytes().to_vec();
 
+        let validation = validate_agal(&vertex_agal, ShaderType::Vertex)
+            .and_then(|()| validate_agal(&fragment_agal, ShaderType::Fragment));
+        if let Err(e) = validation {
+            let code = e.code();
+            return Err(Error::AvmError(error(
+                activation,
+                &format!("Error #{code}: {e}"),
+                code,
+            )?));
+        }
+
         this.context3d()
             .upload_shaders(this, vertex_agal, fragment_agal);
     }
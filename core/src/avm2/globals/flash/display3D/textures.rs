@@ -3,3 +3,4 @@
 pub mod cube_texture;
 pub mod rectangle_texture;
 pub mod texture;
+pub mod video_texture;
@@ -1,6 +1,8 @@
 package flash.display3D.textures {
     import flash.display.BitmapData;
+    import flash.utils.ByteArray;
     public final class CubeTexture extends TextureBase {
         public native function uploadFromBitmapData(source:BitmapData, side:uint, miplevel:uint = 0):void
+        public native function uploadCompressedTextureFromByteArray(data:ByteArray, byteArrayOffset:uint, async:Boolean = false):void;
     }
 }
\ No newline at end of file
@@ -4,6 +4,8 @@ use crate::avm2::Value;
 use crate::avm2::{Error, Object};
 use crate::avm2_stub_method;
 
+pub use super::texture::upload_compressed_texture_from_byte_array;
+
 pub fn upload_from_bitmap_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -1,8 +1,12 @@
+use crate::avm2::error::error;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Activation;
+use crate::avm2::EventObject;
 use crate::avm2::TObject;
 use crate::avm2::Value;
-use crate::avm2::{Error, Object};
+use crate::avm2::{Avm2, Error, Object};
 use crate::avm2_stub_method;
+use ruffle_render::atf::{AtfError, AtfTexture};
 
 pub fn upload_from_bitmap_data<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -30,3 +34,100 @@ pub fn upload_from_bitmap_data<'gc>(
     }
     Ok(Value::Undefined)
 }
+
+/// Implements `uploadCompressedTextureFromByteArray` for both `Texture` and `CubeTexture`.
+pub fn upload_compressed_texture_from_byte_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(texture) = this.as_texture() {
+        let byte_array = args.get_object(activation, 0, "data")?;
+        let byte_array = byte_array
+            .as_bytearray()
+            .ok_or_else(|| Error::from("ArgumentError: Parameter must be a ByteArray"))?;
+        let offset = args.get_u32(activation, 1)? as usize;
+        let is_async = args.get_bool(2);
+
+        let bytes = byte_array.bytes().get(offset..).unwrap_or_default();
+        let texture_data = match AtfTexture::parse(bytes) {
+            Ok(texture_data) => texture_data,
+            Err(e) => {
+                drop(byte_array);
+                return Err(decoding_failed(activation, e));
+            }
+        };
+
+        // A texture can be uploaded with all of its mip levels, or only the first.
+        let (width, height) = texture.size();
+        let full_mip_levels = 32 - width.max(height).leading_zeros();
+        if (texture_data.width, texture_data.height) != (width, height) {
+            drop(byte_array);
+            return Err(Error::AvmError(error(
+                activation,
+                "Error #3679: Texture size does not match.",
+                3679,
+            )?));
+        }
+        if texture_data.num_mip_levels != 1 && texture_data.num_mip_levels != full_mip_levels {
+            drop(byte_array);
+            return Err(Error::AvmError(error(
+                activation,
+                "Error #3678: Texture mip level count does not match.",
+                3678,
+            )?));
+        }
+
+        if !texture_data.format.is_raw() {
+            avm2_stub_method!(
+                activation,
+                "flash.display3D.textures.Texture",
+                "uploadCompressedTextureFromByteArray",
+                "with JPEG-XR or LZMA compressed ATF data"
+            );
+        }
+        let faces = texture_data.decode();
+        drop(byte_array);
+
+        let faces = match faces {
+            Ok(faces) => faces,
+            Err(e) => return Err(decoding_failed(activation, e)),
+        };
+
+        let cube_class = activation.avm2().classes().cubetexture;
+        let is_cube = this.is_of_type(cube_class.inner_class_definition(), &mut activation.context);
+        if is_cube != (faces.len() == 6) {
+            return Err(Error::AvmError(error(
+                activation,
+                "Error #3676: Texture format mismatch.",
+                3676,
+            )?));
+        }
+
+        for (side, face) in faces.into_iter().enumerate() {
+            texture
+                .context3d()
+                .copy_pixels_to_texture(face, texture.handle(), side as u32);
+        }
+
+        if is_async {
+            // We decode synchronously, so the texture is ready straight away.
+            let texture_ready =
+                EventObject::bare_default_event(&mut activation.context, "textureReady");
+            Avm2::dispatch_event(&mut activation.context, texture_ready, this);
+        }
+    }
+    Ok(Value::Undefined)
+}
+
+fn decoding_failed<'gc>(activation: &mut Activation<'_, 'gc>, e: AtfError) -> Error<'gc> {
+    tracing::warn!("Failed to decode ATF texture: {e}");
+    match error(
+        activation,
+        "Error #3677: Texture decoding failed. Internal error.",
+        3677,
+    ) {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
@@ -0,0 +1,68 @@
+use crate::avm2::Activation;
+use crate::avm2::TObject;
+use crate::avm2::Value;
+use crate::avm2::{Error, Object};
+use crate::avm2_stub_method;
+
+pub fn attach_net_stream<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(texture) = this.as_texture() {
+        if let Some(old_stream) = texture.video_source() {
+            old_stream.detach_video_texture(activation.context.gc_context, texture);
+        }
+
+        let stream = match args[0] {
+            Value::Null => None,
+            _ => args[0].coerce_to_object(activation)?.as_netstream(),
+        };
+
+        if let Some(stream) = stream {
+            stream.attach_video_texture(activation.context.gc_context, texture);
+
+            // Show the current frame straight away, rather than waiting for the next one.
+            if let Some(frame) = stream.last_decoded_bitmap() {
+                texture.copy_video_frame(&frame)?;
+            }
+        }
+        texture.set_video_source(activation.context.gc_context, stream);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn attach_camera<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    avm2_stub_method!(
+        activation,
+        "flash.display3D.textures.VideoTexture",
+        "attachCamera"
+    );
+    Ok(Value::Undefined)
+}
+
+pub fn get_video_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(texture) = this.as_texture() {
+        return Ok(texture.video_size().0.into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_video_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(texture) = this.as_texture() {
+        return Ok(texture.video_size().1.into());
+    }
+    Ok(Value::Undefined)
+}
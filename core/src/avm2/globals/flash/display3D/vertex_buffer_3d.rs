@@ -1,3 +1,5 @@
+use crate::avm2::error::error;
+use crate::avm2::object::VertexBuffer3DObject;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Activation;
 use crate::avm2::ClassObject;
@@ -12,6 +14,23 @@ pub fn vertex_buffer_3d_allocator<'gc>(
     panic!("This allocator should not be called!")
 }
 
+/// Checks that an upload of `num_vertices` vertices starting at `start_vertex` fits within the buffer.
+fn check_upload_range<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    vertex_buffer: VertexBuffer3DObject<'gc>,
+    start_vertex: u32,
+    num_vertices: u32,
+) -> Result<(), Error<'gc>> {
+    if start_vertex as u64 + num_vertices as u64 > vertex_buffer.num_vertices() as u64 {
+        return Err(Error::AvmError(error(
+            activation,
+            "Error #3669: Bad input size.",
+            3669,
+        )?));
+    }
+    Ok(())
+}
+
 pub fn upload_from_byte_array<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -30,6 +49,7 @@ pub fn upload_from_byte_array<'gc>(
         let byte_offset = args.get_u32(activation, 1)?;
         let start_vertex = args.get_u32(activation, 2)?;
         let num_vertices = args.get_u32(activation, 3)?;
+        check_upload_range(activation, vertex_buffer, start_vertex, num_vertices)?;
 
         let data = byte_array
             .read_at(
@@ -66,6 +86,7 @@ pub fn upload_from_vector<'gc>(
 
         let start_vertex = args.get_u32(activation, 1)?;
         let num_vertices = args.get_u32(activation, 2)?;
+        check_upload_range(activation, vertex_buffer, start_vertex, num_vertices)?;
 
         let data: Result<Vec<f32>, _> = vector
             .iter()
@@ -21,7 +21,7 @@ package flash.events
         //  Creates a copy of the DataEvent object and sets the value of each property to match that of the original.
         override public function clone():Event
         {
-            return new DataEvent(this.type, this.bubbles, this.cancelable, this.text, this.data);
+            return new DataEvent(this.type, this.bubbles, this.cancelable, this.data);
         }
 
         //  Returns a string that contains all the properties of the DataEvent object.
@@ -0,0 +1,74 @@
+package flash.events {
+    import flash.utils.ByteArray;
+
+    public class DatagramSocketDataEvent extends Event {
+        public static const DATA:String = "data";
+
+        private var _data:ByteArray;
+        private var _srcAddress:String;
+        private var _srcPort:int;
+        private var _dstAddress:String;
+        private var _dstPort:int;
+
+        public function DatagramSocketDataEvent(type:String, bubbles:Boolean = false, cancelable:Boolean = false,
+                                                srcAddress:String = "", srcPort:int = 0,
+                                                dstAddress:String = "", dstPort:int = 0, data:ByteArray = null) {
+            super(type, bubbles, cancelable);
+            this._srcAddress = srcAddress;
+            this._srcPort = srcPort;
+            this._dstAddress = dstAddress;
+            this._dstPort = dstPort;
+            this._data = data;
+        }
+
+        public function get data():ByteArray {
+            return this._data;
+        }
+
+        public function set data(value:ByteArray):void {
+            this._data = value;
+        }
+
+        public function get srcAddress():String {
+            return this._srcAddress;
+        }
+
+        public function set srcAddress(value:String):void {
+            this._srcAddress = value;
+        }
+
+        public function get srcPort():int {
+            return this._srcPort;
+        }
+
+        public function set srcPort(value:int):void {
+            this._srcPort = value;
+        }
+
+        public function get dstAddress():String {
+            return this._dstAddress;
+        }
+
+        public function set dstAddress(value:String):void {
+            this._dstAddress = value;
+        }
+
+        public function get dstPort():int {
+            return this._dstPort;
+        }
+
+        public function set dstPort(value:int):void {
+            this._dstPort = value;
+        }
+
+        override public function clone():Event {
+            return new DatagramSocketDataEvent(this.type, this.bubbles, this.cancelable, this.srcAddress, this.srcPort,
+                                               this.dstAddress, this.dstPort, this.data);
+        }
+
+        override public function toString():String {
+            return this.formatToString("DatagramSocketDataEvent", "type", "bubbles", "cancelable", "eventPhase",
+                                       "srcAddress", "srcPort", "dstAddress", "dstPort");
+        }
+    }
+}
@@ -27,11 +27,14 @@ package flash.events
         // Indicates whether the Shift key is active (true) or inactive (false).
         public var shiftKey: Boolean;
 
+        // Indicates whether the command key is activated (Mac only).
+        public var commandKey: Boolean;
+
         // Indicates whether the Control key is activated on Mac and whether the Ctrl key is activated on Windows or Linux.
         public var controlKey: Boolean;
 
         public function GestureEvent(type:String, bubbles:Boolean = true, cancelable:Boolean = false, phase:String = null, localX:Number = 0,
-            localY:Number = 0, ctrlKey:Boolean = false, altKey:Boolean = false, shiftKey:Boolean = false, controlKey:Boolean = false)
+            localY:Number = 0, ctrlKey:Boolean = false, altKey:Boolean = false, shiftKey:Boolean = false, commandKey:Boolean = false, controlKey:Boolean = false)
         {
             super(type,bubbles,cancelable);
             this.phase = phase;
@@ -40,6 +43,7 @@ package flash.events
             this.ctrlKey = ctrlKey;
             this.altKey = altKey;
             this.shiftKey = shiftKey;
+            this.commandKey = commandKey;
             this.controlKey = controlKey;
         }
         
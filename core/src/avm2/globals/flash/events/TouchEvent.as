@@ -37,8 +37,6 @@ public class TouchEvent extends Event {
     public var altKey: Boolean; // Indicates whether the Alt key is active (true) or inactive (false).
     public var shiftKey: Boolean; // Indicates whether the Shift key is active (true) or inactive (false).
     public var isRelatedObjectInaccessible: Boolean; // If true, the relatedObject property is set to null for reasons related to security sandboxes.
-    private var _stageX: Number; // [read-only] The horizontal coordinate at which the event occurred in global Stage coordinates.
-    private var _stageY: Number; // [read-only] The vertical coordinate at which the event occurred in global Stage coordinates.
 
     public function TouchEvent(type: String, bubbles: Boolean = true, cancelable: Boolean = false, touchPointID: int = 0,
                                isPrimaryTouchPoint: Boolean = false, localX: Number = NaN, localY: Number = NaN,
@@ -89,12 +87,10 @@ public class TouchEvent extends Event {
     // Instructs Flash Player or Adobe AIR to render after processing of this event completes, if the display list has been modified.
     public native function updateAfterEvent(): void;
 
-    public function get stageX(): Number {
-        return this._stageX;
-    }
+    // [read-only] The horizontal coordinate at which the event occurred in global Stage coordinates.
+    public native function get stageX(): Number;
 
-    public function get stageY(): Number {
-        return this._stageY;
-    }
+    // [read-only] The vertical coordinate at which the event occurred in global Stage coordinates.
+    public native function get stageY(): Number;
 }
 }
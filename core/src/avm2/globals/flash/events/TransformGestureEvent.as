@@ -0,0 +1,56 @@
+package flash.events
+{
+    public class TransformGestureEvent extends GestureEvent
+    {
+        public static const GESTURE_DIRECTIONAL_TAP:String = "gestureDirectionalTap";
+        public static const GESTURE_PAN:String = "gesturePan";
+        public static const GESTURE_ROTATE:String = "gestureRotate";
+        public static const GESTURE_SWIPE:String = "gestureSwipe";
+        public static const GESTURE_ZOOM:String = "gestureZoom";
+
+        // The horizontal scale of the display object, since the previous gesture event.
+        public var scaleX: Number;
+
+        // The vertical scale of the display object, since the previous gesture event.
+        public var scaleY: Number;
+
+        // The current rotation angle, in degrees, of the display object along the z-axis, since the previous gesture event.
+        public var rotation: Number;
+
+        // The horizontal translation of the display object, since the previous gesture event.
+        public var offsetX: Number;
+
+        // The vertical translation of the display object, since the previous gesture event.
+        public var offsetY: Number;
+
+        // The velocity of the gesture, in pixels per millisecond.
+        public var velocity: Number;
+
+        public function TransformGestureEvent(type:String, bubbles:Boolean = true, cancelable:Boolean = false, phase:String = null,
+            localX:Number = 0, localY:Number = 0, scaleX:Number = 1.0, scaleY:Number = 1.0, rotation:Number = 0, offsetX:Number = 0,
+            offsetY:Number = 0, ctrlKey:Boolean = false, altKey:Boolean = false, shiftKey:Boolean = false, commandKey:Boolean = false,
+            controlKey:Boolean = false, velocity:Number = 0)
+        {
+            super(type, bubbles, cancelable, phase, localX, localY, ctrlKey, altKey, shiftKey, commandKey, controlKey);
+            this.scaleX = scaleX;
+            this.scaleY = scaleY;
+            this.rotation = rotation;
+            this.offsetX = offsetX;
+            this.offsetY = offsetY;
+            this.velocity = velocity;
+        }
+
+        override public function clone():Event
+        {
+            return new TransformGestureEvent(this.type, this.bubbles, this.cancelable, this.phase, this.localX, this.localY,
+                this.scaleX, this.scaleY, this.rotation, this.offsetX, this.offsetY, this.ctrlKey, this.altKey, this.shiftKey,
+                this.commandKey, this.controlKey, this.velocity);
+        }
+
+        override public function toString():String
+        {
+            return this.formatToString("TransformGestureEvent","type","bubbles","cancelable","eventPhase","phase","localX","localY",
+                "stageX","stageY","scaleX","scaleY","rotation","offsetX","offsetY","ctrlKey","altKey","shiftKey","commandKey","controlKey");
+        }
+    }
+}
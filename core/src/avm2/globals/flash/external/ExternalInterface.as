@@ -5,6 +5,10 @@ package flash.external
    public final class ExternalInterface
    {
       public static native function get available(): Boolean;
+
+      public static native function get marshallExceptions(): Boolean;
+
+      public static native function set marshallExceptions(value: Boolean): void;
       
       public static native function addCallback(functionName: String, closure: Function) : void;
       
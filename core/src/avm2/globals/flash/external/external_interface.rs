@@ -18,11 +18,18 @@ pub fn call<'gc>(
     {
         let mut external_args = Vec::with_capacity(args.len() - 1);
         for arg in &args[1..] {
-            external_args.push(ExternalValue::from_avm2(arg.to_owned()));
+            external_args.push(ExternalValue::from_avm2(activation, arg.to_owned())?);
+        }
+        match method.call(&mut activation.context, &external_args) {
+            Ok(result) => result.into_avm2(activation),
+            Err(message) if activation.context.external_interface.marshall_exceptions() => {
+                Err(Error::AvmError(error(activation, &message, 0)?))
+            }
+            Err(message) => {
+                tracing::warn!("Exception in ExternalInterface call to {name}: {message}");
+                Ok(Value::Null)
+            }
         }
-        Ok(method
-            .call(&mut activation.context, &external_args)
-            .into_avm2(activation))
     } else {
         Ok(Value::Null)
     }
@@ -47,6 +54,31 @@ pub fn get_available<'gc>(
     Ok(activation.context.external_interface.available().into())
 }
 
+pub fn get_marshall_exceptions<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .external_interface
+        .marshall_exceptions()
+        .into())
+}
+
+pub fn set_marshall_exceptions<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let marshall_exceptions = args.get_bool(0);
+    activation
+        .context
+        .external_interface
+        .set_marshall_exceptions(marshall_exceptions);
+    Ok(Value::Undefined)
+}
+
 pub fn add_callback<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
@@ -0,0 +1,237 @@
+package flash.filesystem {
+    import flash.net.FileReference;
+
+    // AIR-only class for accessing files, which only works in players that allow it.
+    public class File extends FileReference {
+        private var _nativePath:String;
+
+        public function File(path:String = null) {
+            if (path != null) {
+                this.nativePath = File.pathFromURL(path);
+            }
+        }
+
+        private static function pathFromURL(path:String):String {
+            var schemes:Array = [["app:/", "application"], ["app-storage:/", "applicationStorage"]];
+            for each (var scheme:Array in schemes) {
+                if (path.indexOf(scheme[0]) == 0) {
+                    return File.resolve(File.specialDirectory(scheme[1]), decodeURI(path.substr(scheme[0].length)));
+                }
+            }
+            if (path.indexOf("file://") == 0) {
+                return decodeURI(path.substr("file://".length));
+            }
+            return path;
+        }
+
+        public static function get applicationDirectory():File {
+            return File.special("application");
+        }
+
+        public static function get applicationStorageDirectory():File {
+            return File.special("applicationStorage");
+        }
+
+        public static function get cacheDirectory():File {
+            return File.special("cache");
+        }
+
+        public static function get desktopDirectory():File {
+            return File.special("desktop");
+        }
+
+        public static function get documentsDirectory():File {
+            return File.special("documents");
+        }
+
+        public static function get userDirectory():File {
+            return File.special("user");
+        }
+
+        private static function special(directory:String):File {
+            var file:File = new File();
+            file._nativePath = File.specialDirectory(directory);
+            return file;
+        }
+
+        public static native function get separator():String;
+
+        public static function get lineEnding():String {
+            return File.separator == "\\" ? "\r\n" : "\n";
+        }
+
+        public static function get systemCharset():String {
+            return "utf-8";
+        }
+
+        public static function createTempFile():File {
+            var file:File = File.cacheDirectory.resolvePath("tmp" + Math.floor(Math.random() * 0x7fffffff));
+            var stream:FileStream = new FileStream();
+            stream.open(file, FileMode.WRITE);
+            stream.close();
+            return file;
+        }
+
+        public static function createTempDirectory():File {
+            var directory:File = File.cacheDirectory.resolvePath("tmp" + Math.floor(Math.random() * 0x7fffffff));
+            directory.createDirectory();
+            return directory;
+        }
+
+        public function get nativePath():String {
+            return this._nativePath;
+        }
+
+        public function set nativePath(value:String):void {
+            this._nativePath = File.resolve(value, ".");
+        }
+
+        public function get url():String {
+            return this._nativePath == null ? null : "file://" + encodeURI(this._nativePath);
+        }
+
+        public function set url(value:String):void {
+            this.nativePath = File.pathFromURL(value);
+        }
+
+        override public function get name():String {
+            if (this._nativePath == null) {
+                return null;
+            }
+            var path:String = this._nativePath;
+            var index:int = Math.max(path.lastIndexOf("/"), path.lastIndexOf(File.separator));
+            return path.substr(index + 1);
+        }
+
+        override public function get extension():String {
+            var name:String = this.name;
+            if (name == null) {
+                return null;
+            }
+            var dot:int = name.lastIndexOf(".");
+            return dot == -1 ? null : name.substr(dot + 1);
+        }
+
+        override public function get type():String {
+            var extension:String = this.extension;
+            return extension == null ? null : "." + extension;
+        }
+
+        override public function get size():Number {
+            var info:Array = this.info(true);
+            return info[1];
+        }
+
+        override public function get creationDate():Date {
+            var info:Array = this.info(true);
+            return info[2];
+        }
+
+        override public function get modificationDate():Date {
+            var info:Array = this.info(true);
+            return info[3];
+        }
+
+        public function get exists():Boolean {
+            return this.info(false) != null;
+        }
+
+        public function get isDirectory():Boolean {
+            var info:Array = this.info(false);
+            return info != null && info[0];
+        }
+
+        public function get isHidden():Boolean {
+            var name:String = this.name;
+            return name != null && name.charAt(0) == ".";
+        }
+
+        public function get isPackage():Boolean {
+            return false;
+        }
+
+        public function get isSymbolicLink():Boolean {
+            return false;
+        }
+
+        public function get parent():File {
+            if (this._nativePath == null) {
+                return null;
+            }
+            var parentPath:String = File.resolve(this._nativePath, "..");
+            if (parentPath == this._nativePath || parentPath == "") {
+                return null;
+            }
+            var file:File = new File();
+            file._nativePath = parentPath;
+            return file;
+        }
+
+        // Returns whether this is a directory, its size, creation date and modification date,
+        // or null if it doesn't exist (throwing instead if `mustExist` is set).
+        private native function info(mustExist:Boolean):Array;
+
+        private static native function specialDirectory(directory:String):String;
+
+        private static native function resolve(base:String, path:String):String;
+
+        public function resolvePath(path:String):File {
+            var file:File = new File();
+            file._nativePath = File.resolve(this._nativePath, path);
+            return file;
+        }
+
+        public function getRelativePath(ref:FileReference, useDotDot:Boolean = false):String {
+            var other:String = (ref as File).nativePath;
+            var prefix:String = this._nativePath + "/";
+            if (other.indexOf(prefix) == 0) {
+                return other.substr(prefix.length);
+            }
+            return null;
+        }
+
+        public function clone():File {
+            var file:File = new File();
+            file._nativePath = this._nativePath;
+            return file;
+        }
+
+        public native function createDirectory():void;
+
+        public native function deleteFile():void;
+
+        public native function deleteDirectory(deleteDirectoryContents:Boolean = false):void;
+
+        public function moveToTrash():void {
+            if (this.isDirectory) {
+                this.deleteDirectory(true);
+            } else {
+                this.deleteFile();
+            }
+        }
+
+        public function getDirectoryListing():Array {
+            var listing:Array = [];
+            for each (var path:String in this.listDirectory()) {
+                var file:File = new File();
+                file._nativePath = path;
+                listing.push(file);
+            }
+            return listing;
+        }
+
+        private native function listDirectory():Array;
+
+        public function copyTo(newLocation:FileReference, overwrite:Boolean = false):void {
+            this.copyPath((newLocation as File).nativePath, overwrite);
+        }
+
+        public function moveTo(newLocation:FileReference, overwrite:Boolean = false):void {
+            this.movePath((newLocation as File).nativePath, overwrite);
+        }
+
+        private native function copyPath(to:String, overwrite:Boolean):void;
+
+        private native function movePath(to:String, overwrite:Boolean):void;
+    }
+}
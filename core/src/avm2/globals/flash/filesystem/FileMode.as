@@ -0,0 +1,8 @@
+package flash.filesystem {
+    public class FileMode {
+        public static const APPEND:String = "append";
+        public static const READ:String = "read";
+        public static const UPDATE:String = "update";
+        public static const WRITE:String = "write";
+    }
+}
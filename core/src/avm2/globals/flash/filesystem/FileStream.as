@@ -0,0 +1,257 @@
+package flash.filesystem {
+    import flash.errors.IOError;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.utils.ByteArray;
+    import flash.utils.IDataInput;
+    import flash.utils.IDataOutput;
+
+    // AIR-only class for reading and writing files.
+    //
+    // The contents of the file are read when it's opened, and written back
+    // when it's closed, if they were changed.
+    public class FileStream extends EventDispatcher implements IDataInput, IDataOutput {
+        private var _file:File;
+        private var _mode:String;
+        private var _buffer:ByteArray;
+        private var _dirty:Boolean;
+
+        public function FileStream() {}
+
+        public function open(file:File, fileMode:String):void {
+            if (this._file != null) {
+                this.close();
+            }
+
+            var buffer:ByteArray;
+            if (fileMode == FileMode.WRITE) {
+                buffer = new ByteArray();
+            } else if (fileMode == FileMode.READ) {
+                buffer = FileStream.readFile(file.nativePath);
+            } else {
+                buffer = file.exists ? FileStream.readFile(file.nativePath) : new ByteArray();
+            }
+            if (fileMode == FileMode.APPEND) {
+                buffer.position = buffer.length;
+            }
+
+            // The endianness and object encoding can be set before a file is opened.
+            buffer.endian = this.buffer.endian;
+            buffer.objectEncoding = this.buffer.objectEncoding;
+
+            this._file = file;
+            this._mode = fileMode;
+            this._buffer = buffer;
+            // Opening a file for writing creates it, or empties it if it exists.
+            this._dirty = fileMode != FileMode.READ;
+        }
+
+        public function openAsync(file:File, fileMode:String):void {
+            this.open(file, fileMode);
+            this.dispatchEvent(new Event(Event.OPEN));
+            this.dispatchEvent(new Event(Event.COMPLETE));
+        }
+
+        public function close():void {
+            if (this._file == null) {
+                return;
+            }
+            if (this._dirty) {
+                FileStream.writeFile(this._file.nativePath, this._buffer);
+            }
+            this._file = null;
+            this._dirty = false;
+            this.dispatchEvent(new Event(Event.CLOSE));
+        }
+
+        public function truncate():void {
+            this.checkWritable();
+            this._buffer.length = this._buffer.position;
+            this._dirty = true;
+        }
+
+        private static native function readFile(path:String):ByteArray;
+
+        private static native function writeFile(path:String, data:ByteArray):void;
+
+        private function checkReadable():void {
+            if (this._file == null || this._mode == FileMode.WRITE || this._mode == FileMode.APPEND) {
+                throw new IOError("Error #2029: This URLStream object does not have a stream opened.", 2029);
+            }
+        }
+
+        private function checkWritable():void {
+            if (this._file == null || this._mode == FileMode.READ) {
+                throw new IOError("Error #2029: This URLStream object does not have a stream opened.", 2029);
+            }
+            this._dirty = true;
+        }
+
+        public function get bytesAvailable():uint {
+            return this._file == null ? 0 : this._buffer.bytesAvailable;
+        }
+
+        public function get position():Number {
+            return this._file == null ? 0 : this._buffer.position;
+        }
+
+        public function set position(value:Number):void {
+            if (this._file != null) {
+                this._buffer.position = value;
+            }
+        }
+
+        public function get endian():String {
+            return this.buffer.endian;
+        }
+
+        public function set endian(value:String):void {
+            this.buffer.endian = value;
+        }
+
+        public function get objectEncoding():uint {
+            return this.buffer.objectEncoding;
+        }
+
+        public function set objectEncoding(value:uint):void {
+            this.buffer.objectEncoding = value;
+        }
+
+        private function get buffer():ByteArray {
+            if (this._buffer == null) {
+                this._buffer = new ByteArray();
+            }
+            return this._buffer;
+        }
+
+        public function readBoolean():Boolean {
+            this.checkReadable();
+            return this._buffer.readBoolean();
+        }
+
+        public function readByte():int {
+            this.checkReadable();
+            return this._buffer.readByte();
+        }
+
+        public function readBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkReadable();
+            this._buffer.readBytes(bytes, offset, length);
+        }
+
+        public function readDouble():Number {
+            this.checkReadable();
+            return this._buffer.readDouble();
+        }
+
+        public function readFloat():Number {
+            this.checkReadable();
+            return this._buffer.readFloat();
+        }
+
+        public function readInt():int {
+            this.checkReadable();
+            return this._buffer.readInt();
+        }
+
+        public function readMultiByte(length:uint, charSet:String):String {
+            this.checkReadable();
+            return this._buffer.readMultiByte(length, charSet);
+        }
+
+        public function readObject():* {
+            this.checkReadable();
+            return this._buffer.readObject();
+        }
+
+        public function readShort():int {
+            this.checkReadable();
+            return this._buffer.readShort();
+        }
+
+        public function readUnsignedByte():uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedByte();
+        }
+
+        public function readUnsignedInt():uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedInt();
+        }
+
+        public function readUnsignedShort():uint {
+            this.checkReadable();
+            return this._buffer.readUnsignedShort();
+        }
+
+        public function readUTF():String {
+            this.checkReadable();
+            return this._buffer.readUTF();
+        }
+
+        public function readUTFBytes(length:uint):String {
+            this.checkReadable();
+            return this._buffer.readUTFBytes(length);
+        }
+
+        public function writeBoolean(value:Boolean):void {
+            this.checkWritable();
+            this._buffer.writeBoolean(value);
+        }
+
+        public function writeByte(value:int):void {
+            this.checkWritable();
+            this._buffer.writeByte(value);
+        }
+
+        public function writeBytes(bytes:ByteArray, offset:uint = 0, length:uint = 0):void {
+            this.checkWritable();
+            this._buffer.writeBytes(bytes, offset, length);
+        }
+
+        public function writeDouble(value:Number):void {
+            this.checkWritable();
+            this._buffer.writeDouble(value);
+        }
+
+        public function writeFloat(value:Number):void {
+            this.checkWritable();
+            this._buffer.writeFloat(value);
+        }
+
+        public function writeInt(value:int):void {
+            this.checkWritable();
+            this._buffer.writeInt(value);
+        }
+
+        public function writeMultiByte(value:String, charSet:String):void {
+            this.checkWritable();
+            this._buffer.writeMultiByte(value, charSet);
+        }
+
+        public function writeObject(object:*):void {
+            this.checkWritable();
+            this._buffer.writeObject(object);
+        }
+
+        public function writeShort(value:int):void {
+            this.checkWritable();
+            this._buffer.writeShort(value);
+        }
+
+        public function writeUnsignedInt(value:uint):void {
+            this.checkWritable();
+            this._buffer.writeUnsignedInt(value);
+        }
+
+        public function writeUTF(value:String):void {
+            this.checkWritable();
+            this._buffer.writeUTF(value);
+        }
+
+        public function writeUTFBytes(value:String):void {
+            this.checkWritable();
+            this._buffer.writeUTFBytes(value);
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! `flash.filesystem.File` native function definitions
+
+use crate::avm2::error::{io_error, security_error};
+use crate::avm2::globals::flash::net::file_reference::date_value;
+use crate::avm2::object::{ArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::backend::filesystem::{resolve_path, SpecialDirectory};
+use crate::string::AvmString;
+use std::io;
+
+/// Converts an error from the filesystem backend into the error that AIR throws.
+pub fn filesystem_error<'gc>(activation: &mut Activation<'_, 'gc>, error: io::Error) -> Error<'gc> {
+    let error = match error.kind() {
+        io::ErrorKind::NotFound => io_error(
+            activation,
+            "Error #3003: File or directory does not exist.",
+            3003,
+        ),
+        io::ErrorKind::AlreadyExists => {
+            io_error(activation, "Error #3002: File or directory exists.", 3002)
+        }
+        io::ErrorKind::PermissionDenied => security_error(
+            activation,
+            "Error #3001: File or directory access denied.",
+            3001,
+        ),
+        _ => {
+            tracing::warn!("File I/O error: {}", error);
+            io_error(activation, "Error #2038: File I/O Error.", 2038)
+        }
+    };
+    match error {
+        Ok(error) => Error::AvmError(error),
+        Err(error) => error,
+    }
+}
+
+/// Returns the `nativePath` of a `File`, throwing if it hasn't been set.
+fn native_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<String, Error<'gc>> {
+    match this.get_public_property("nativePath", activation)? {
+        Value::Null | Value::Undefined => Err(Error::AvmError(io_error(
+            activation,
+            "Error #3003: File or directory does not exist.",
+            3003,
+        )?)),
+        path => Ok(path.coerce_to_string(activation)?.to_string()),
+    }
+}
+
+/// Implements `File.separator`
+pub fn get_separator<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let separator = activation.context.filesystem.separator().to_string();
+    Ok(AvmString::new_utf8(activation.context.gc_context, separator).into())
+}
+
+/// Implements `File.specialDirectory`
+pub fn special_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let directory = args.get_string(activation, 0)?;
+    let directory = match directory.to_utf8_lossy().as_ref() {
+        "application" => SpecialDirectory::Application,
+        "applicationStorage" => SpecialDirectory::ApplicationStorage,
+        "cache" => SpecialDirectory::Cache,
+        "desktop" => SpecialDirectory::Desktop,
+        "documents" => SpecialDirectory::Documents,
+        _ => SpecialDirectory::User,
+    };
+
+    match activation.context.filesystem.special_directory(directory) {
+        Some(path) => Ok(AvmString::new_utf8(activation.context.gc_context, path).into()),
+        None => Err(filesystem_error(
+            activation,
+            io::ErrorKind::PermissionDenied.into(),
+        )),
+    }
+}
+
+/// Implements `File.resolve`
+pub fn resolve<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let base = args.get_string(activation, 0)?;
+    let path = args.get_string(activation, 1)?;
+    let resolved = resolve_path(&base.to_utf8_lossy(), &path.to_utf8_lossy());
+    Ok(AvmString::new_utf8(activation.context.gc_context, resolved).into())
+}
+
+/// Implements `File.info`
+pub fn info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let must_exist = args.get_bool(0);
+    let path = native_path(activation, this)?;
+
+    let info = match activation.context.filesystem.info(&path) {
+        Ok(info) => info,
+        Err(e) if e.kind() == io::ErrorKind::NotFound && !must_exist => return Ok(Value::Null),
+        Err(e) => return Err(filesystem_error(activation, e)),
+    };
+    let creation_date = date_value(activation, info.creation_date)?;
+    let modification_date = date_value(activation, info.modification_date)?;
+    let values = [
+        info.is_directory.into(),
+        (info.size as f64).into(),
+        creation_date,
+        modification_date,
+    ];
+    Ok(ArrayObject::from_storage(activation, values.into_iter().collect())?.into())
+}
+
+/// Implements `File.createDirectory`
+pub fn create_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = native_path(activation, this)?;
+    activation
+        .context
+        .filesystem
+        .create_directory(&path)
+        .map_err(|e| filesystem_error(activation, e))?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `File.deleteFile`
+pub fn delete_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = native_path(activation, this)?;
+    activation
+        .context
+        .filesystem
+        .delete(&path, false)
+        .map_err(|e| filesystem_error(activation, e))?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `File.deleteDirectory`
+pub fn delete_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let recursive = args.get_bool(0);
+    let path = native_path(activation, this)?;
+    activation
+        .context
+        .filesystem
+        .delete(&path, recursive)
+        .map_err(|e| filesystem_error(activation, e))?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `File.listDirectory`
+pub fn list_directory<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = native_path(activation, this)?;
+    let entries = activation
+        .context
+        .filesystem
+        .list_directory(&path)
+        .map_err(|e| filesystem_error(activation, e))?;
+    let entries = entries
+        .into_iter()
+        .map(|entry| AvmString::new_utf8(activation.context.gc_context, entry))
+        .collect();
+    Ok(ArrayObject::from_storage(activation, entries)?.into())
+}
+
+/// Copies or moves a file to another path, as `File.copyTo` and `File.moveTo` do.
+fn transfer<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+    is_move: bool,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let from = native_path(activation, this)?;
+    let to = args.get_string(activation, 0)?.to_string();
+    let overwrite = args.get_bool(1);
+
+    let filesystem = &mut activation.context.filesystem;
+    let result = match filesystem.info(&to) {
+        Ok(_) if !overwrite => Err(io::ErrorKind::AlreadyExists.into()),
+        Ok(info) => filesystem.delete(&to, info.is_directory),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+    .and_then(|_| {
+        if is_move {
+            filesystem.rename(&from, &to)
+        } else {
+            filesystem.copy(&from, &to)
+        }
+    });
+    result.map_err(|e| filesystem_error(activation, e))?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `File.copyPath`
+pub fn copy_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    transfer(activation, this, args, false)
+}
+
+/// Implements `File.movePath`
+pub fn move_path<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    transfer(activation, this, args, true)
+}
@@ -0,0 +1,42 @@
+//! `flash.filesystem.FileStream` native function definitions
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::globals::flash::filesystem::file::filesystem_error;
+use crate::avm2::object::ByteArrayObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Implements `FileStream.readFile`
+pub fn read_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?;
+    let data = activation
+        .context
+        .filesystem
+        .read(&path.to_utf8_lossy())
+        .map_err(|e| filesystem_error(activation, e))?;
+    Ok(ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(data))?.into())
+}
+
+/// Implements `FileStream.writeFile`
+pub fn write_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let path = args.get_string(activation, 0)?;
+    let data = args.get_object(activation, 1, "data")?;
+    let data = match data.as_bytearray() {
+        Some(data) => data.bytes().to_vec(),
+        None => return Ok(Value::Undefined),
+    };
+    activation
+        .context
+        .filesystem
+        .write(&path.to_utf8_lossy(), &data)
+        .map_err(|e| filesystem_error(activation, e))?;
+    Ok(Value::Undefined)
+}
@@ -2,9 +2,8 @@ package flash.geom {
 	import flash.display.DisplayObject;
 	import flash.geom.Matrix3D;
 	import flash.geom.PerspectiveProjection;
-	import __ruffle__.stub_getter;
+	import flash.geom.Vector3D;
 	import __ruffle__.stub_method;
-	import __ruffle__.stub_setter;
 
 	public class Transform {
 		internal var _displayObject:DisplayObject;
@@ -24,22 +23,47 @@ package flash.geom {
 		public native function get pixelBounds():Rectangle;
 
 		public function get matrix3D():Matrix3D {
-			stub_getter("flash.geom.Transform", "matrix3D");
-			return new Matrix3D();
+			var d:DisplayObject = this._displayObject;
+			if (d.z == 0 && d.rotationX == 0 && d.rotationY == 0 && d.scaleZ == 1) {
+				return null;
+			}
+
+			var m:Matrix3D = new Matrix3D();
+			m.appendScale(d.scaleX, d.scaleY, d.scaleZ);
+			m.appendRotation(d.rotationX, Vector3D.X_AXIS);
+			m.appendRotation(d.rotationY, Vector3D.Y_AXIS);
+			m.appendRotation(d.rotationZ, Vector3D.Z_AXIS);
+			m.appendTranslation(d.x, d.y, d.z);
+			return m;
 		}
 
 		public function set matrix3D(m:Matrix3D):void {
-			stub_setter("flash.geom.Transform", "matrix3D");
-		}
+			var d:DisplayObject = this._displayObject;
+			if (m == null) {
+				d.z = 0;
+				d.rotationX = 0;
+				d.rotationY = 0;
+				d.scaleZ = 1;
+				return;
+			}
 
-		public function get perspectiveProjection():PerspectiveProjection {
-			stub_getter("flash.geom.Transform", "perspectiveProjection");
-			return new PerspectiveProjection();
+			var components:Vector.<Vector3D> = m.decompose();
+			var position:Vector3D = components[0];
+			var rotation:Vector3D = components[1];
+			var scale:Vector3D = components[2];
+			d.x = position.x;
+			d.y = position.y;
+			d.z = position.z;
+			d.scaleX = scale.x;
+			d.scaleY = scale.y;
+			d.scaleZ = scale.z;
+			d.rotationX = rotation.x * 180 / Math.PI;
+			d.rotationY = rotation.y * 180 / Math.PI;
+			d.rotationZ = rotation.z * 180 / Math.PI;
 		}
 
-		public function set perspectiveProjection(val: PerspectiveProjection):void {
-			stub_setter("flash.geom.Transform", "perspectiveProjection");
-		}
+		public native function get perspectiveProjection():PerspectiveProjection;
+		public native function set perspectiveProjection(val:PerspectiveProjection):void;
 
 		public function getRelativeMatrix3D(relativeTo:DisplayObject):Matrix3D {
 			stub_method("flash.geom.Transform", "getRelativeMatrix3D");
@@ -2,7 +2,7 @@ use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
 use crate::avm2_stub_getter;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{PerspectiveProjection, TDisplayObject};
 use crate::prelude::{DisplayObject, Matrix, Twips};
 use ruffle_render::quality::StageQuality;
 use swf::{ColorTransform, Fixed8, Rectangle};
@@ -84,6 +84,71 @@ pub fn set_matrix<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let projection = dobj.perspective_projection().or_else(|| {
+        // The root of the display list always has the stage's projection.
+        dobj.parent()
+            .filter(|parent| parent.as_stage().is_some())
+            .map(|_| {
+                let (width, height) = activation.context.stage.stage_size();
+                PerspectiveProjection::for_stage(width as f64, height as f64)
+            })
+    });
+
+    let Some(projection) = projection else {
+        return Ok(Value::Null);
+    };
+
+    let mut object = activation
+        .avm2()
+        .classes()
+        .perspectiveprojection
+        .construct(activation, &[])?;
+    let center = activation.avm2().classes().point.construct(
+        activation,
+        &[projection.center.0.into(), projection.center.1.into()],
+    )?;
+    object.set_public_property("fieldOfView", projection.field_of_view.into(), activation)?;
+    object.set_public_property("projectionCenter", center.into(), activation)?;
+    Ok(object.into())
+}
+
+pub fn set_perspective_projection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let dobj = get_display_object(this, activation)?;
+    let projection = match args.try_get_object(activation, 0) {
+        Some(object) => {
+            let field_of_view = object
+                .get_public_property("fieldOfView", activation)?
+                .coerce_to_number(activation)?;
+            let center = object
+                .get_public_property("projectionCenter", activation)?
+                .coerce_to_object(activation)?;
+            let x = center
+                .get_public_property("x", activation)?
+                .coerce_to_number(activation)?;
+            let y = center
+                .get_public_property("y", activation)?
+                .coerce_to_number(activation)?;
+            Some(PerspectiveProjection {
+                field_of_view,
+                center: (x, y),
+            })
+        }
+        None => None,
+    };
+    dobj.set_perspective_projection(activation.context.gc_context, projection);
+    Ok(Value::Undefined)
+}
+
 pub fn get_concatenated_matrix<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -1,7 +1,10 @@
 //! `flash.media` namespace
 
+pub mod camera;
+pub mod microphone;
 pub mod sound;
 pub mod sound_channel;
 pub mod sound_mixer;
 pub mod sound_transform;
+pub mod stage_video;
 pub mod video;
@@ -5,122 +5,131 @@ package flash.media {
     import flash.display.BitmapData;
 
     public final class Camera extends EventDispatcher {
-        public function copyToByteArray(rect:Rectangle, destination:ByteArray) {
-            __ruffle__.stub_method("flash.media.Camera", "copyToByteArray");
-        }
-
-        public function copyToVector(rect:Rectangle, destination:Vector.<uint>) {
-            __ruffle__.stub_method("flash.media.Camera", "copyToVector");
-        }
-
-        public function drawToBitmapData(destination:BitmapData) {
-            __ruffle__.stub_method("flash.media.Camera", "drawToBitmapData");
-        }
-
-        public static function getCamera(name: String = null):Camera {
-            __ruffle__.stub_method("flash.media.Camera", "getCamera");
-            return null;
-        }
+        private static var _cameras:Array = [];
+
+        private var _index:int;
+        private var _name:String;
+        private var _width:int = 160;
+        private var _height:int = 120;
+        private var _fps:Number = 15;
+        private var _bandwidth:int = 16384;
+        private var _quality:int = 0;
+        private var _keyFrameInterval:int = 15;
+        private var _loopback:Boolean = false;
+        private var _motionLevel:int = 50;
+        private var _motionTimeout:int = 2000;
+
+        public native function copyToByteArray(rect:Rectangle, destination:ByteArray):void;
+
+        public native function copyToVector(rect:Rectangle, destination:Vector.<uint>):void;
+
+        public native function drawToBitmapData(destination:BitmapData):void;
+
+        // The name is the index of the camera in `Camera.names`, as a string.
+        public static function getCamera(name:String = null):Camera {
+            var names:Array = Camera.names;
+            var index:int = name == null ? 0 : int(name);
+            if (index < 0 || index >= names.length) {
+                return null;
+            }
+
+            // Each camera is only ever represented by one object.
+            var camera:Camera = _cameras[index];
+            if (camera == null) {
+                camera = new Camera();
+                camera._index = index;
+                camera._name = names[index];
+                camera.init(index);
+                _cameras[index] = camera;
+            }
+            return camera;
+        }
+
+        private native function init(index:int):void;
+        private native function applyMode(width:int, height:int, fps:Number):void;
+        private native function applyMotionLevel(motionLevel:int, timeout:int):void;
 
         public function setKeyFrameInterval(keyFrameInterval:int) {
-            __ruffle__.stub_method("flash.media.Camera", "setKeyFrameInterval");
+            this._keyFrameInterval = Math.max(1, Math.min(300, keyFrameInterval));
         }
 
         public function setLoopback(compress:Boolean = false) {
-            __ruffle__.stub_method("flash.media.Camera", "setLoopback");
+            // Captured video is shown uncompressed, so the loopback only matters when publishing.
+            this._loopback = compress;
         }
 
         public function setMode(width:int, height:int, fps:Number, favorArea:Boolean = true) {
-            __ruffle__.stub_method("flash.media.Camera", "setMode");
+            this._width = width;
+            this._height = height;
+            this._fps = fps;
+            this.applyMode(width, height, fps);
         }
 
         public function setMotionLevel(motionLevel:int, timeout:int = 2000) {
-            __ruffle__.stub_method("flash.media.Camera", "setMotionLevel");
+            this._motionLevel = Math.max(0, Math.min(100, motionLevel));
+            this._motionTimeout = Math.max(0, timeout);
+            this.applyMotionLevel(this._motionLevel, this._motionTimeout);
         }
 
         public function setQuality(bandwidth:int, quality:int) {
-            __ruffle__.stub_method("flash.media.Camera", "setQuality");
+            this._bandwidth = bandwidth;
+            this._quality = Math.max(0, Math.min(100, quality));
         }
 
-        public function get activityLevel(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "activityLevel");
-            return 0;
-        }
+        public native function get activityLevel():Number;
 
-        public function get bandwidth(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "bandwidth");
-            return 0;
+        public function get bandwidth():int {
+            return this._bandwidth;
         }
 
-        public function get currentFPS(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "currentFPS");
-            return 0;
-        }
+        public native function get currentFPS():Number;
 
-        public function get fps(): Number {
-            __ruffle__.stub_getter("flash.media.Camera", "fps");
-            return 0;
+        public function get fps():Number {
+            return this._fps;
         }
 
-        public function get height(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "height");
-            return 0;
+        public function get height():int {
+            return this._height;
         }
 
-        public function get index(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "index");
-            return 0;
+        public function get index():int {
+            return this._index;
         }
 
-        public static function get isSupported(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "isSupported");
-            return false;
+        public static function get isSupported():Boolean {
+            return Camera.names.length > 0;
         }
 
-        public function get keyFrameInterval(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "keyFrameInterval");
-            return 0;
+        public function get keyFrameInterval():int {
+            return this._keyFrameInterval;
         }
 
-        public function get loopback(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "loopback");
-            return false;
+        public function get loopback():Boolean {
+            return this._loopback;
         }
 
-        public function get motionLevel(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "motionLevel");
-            return 0;
+        public function get motionLevel():int {
+            return this._motionLevel;
         }
 
-        public function get motionTimeout(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "motionTimeout");
-            return 0;
+        public function get motionTimeout():int {
+            return this._motionTimeout;
         }
 
-        public function get muted(): Boolean {
-            __ruffle__.stub_getter("flash.media.Camera", "muted");
-            return true;
-        }
+        public native function get muted():Boolean;
 
-        public function get name(): String {
-            __ruffle__.stub_getter("flash.media.Camera", "name");
-            return "";
+        public function get name():String {
+            return this._name;
         }
 
-        public static function get names(): Array {
-            __ruffle__.stub_getter("flash.media.Camera", "names");
-            return [];
-        }
-        
-        public function get quality(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "quality");
-            return 0;
+        public static native function get names():Array;
+
+        public function get quality():int {
+            return this._quality;
         }
-        
-        public function get width(): int {
-            __ruffle__.stub_getter("flash.media.Camera", "width");
-            return 0;
+
+        public function get width():int {
+            return this._width;
         }
     }
-
-}
\ No newline at end of file
+}
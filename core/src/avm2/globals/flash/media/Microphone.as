@@ -2,152 +2,208 @@ package flash.media {
     import flash.events.EventDispatcher;
 
     public final class Microphone extends EventDispatcher {
+        private static var _microphones:Array = [];
+
+        private var _index:int;
+        private var _name:String;
+        private var _gain:Number = 50;
+        private var _rate:int = 8;
+        private var _silenceLevel:Number = 10;
+        private var _silenceTimeout:int = 2000;
+        private var _codec:String = SoundCodec.NELLYMOSER;
+        private var _encodeQuality:int = 6;
+        private var _framesPerPacket:int = 2;
+        private var _noiseSuppressionLevel:int = -30;
+        private var _useEchoSuppression:Boolean = false;
+        private var _enableVAD:Boolean = true;
+        private var _enhancedOptions:MicrophoneEnhancedOptions = new MicrophoneEnhancedOptions();
+        private var _soundTransform:SoundTransform = new SoundTransform();
+        private var _loopBack:Boolean = false;
+
         public static function getEnhancedMicrophone(index:int = -1):Microphone {
-            __ruffle__.stub_method("flash.media.Microphone", "getEnhancedMicrophone");
-            return new Microphone();
+            __ruffle__.stub_method("flash.media.Microphone", "getEnhancedMicrophone", "acoustic echo cancellation");
+            return getMicrophone(index);
         }
 
         public static function getMicrophone(index:int = -1):Microphone {
-            __ruffle__.stub_method("flash.media.Microphone", "getMicrophone");
-            return new Microphone();
+            var names:Array = Microphone.names;
+            if (index == -1) {
+                index = 0;
+            }
+            if (index < 0 || index >= names.length) {
+                return null;
+            }
+
+            // Each microphone is only ever represented by one object.
+            var microphone:Microphone = _microphones[index];
+            if (microphone == null) {
+                microphone = new Microphone();
+                microphone._index = index;
+                microphone._name = names[index];
+                _microphones[index] = microphone;
+            }
+            return microphone;
+        }
+
+        private native function startCapture(index:int, rate:int, gain:Number, silenceLevel:Number, loopBack:Boolean):void;
+        private native function stopCapture():void;
+
+        // Audio is captured while it's being listened to, either through
+        // `sampleData` events or by looping it back to the speakers.
+        private function updateCapture():void {
+            if (this._loopBack || this.hasEventListener("sampleData")) {
+                this.startCapture(this._index, this._rate, this._gain, this._silenceLevel, this._loopBack);
+            } else {
+                this.stopCapture();
+            }
+        }
+
+        override public function addEventListener(type:String, listener:Function, useCapture:Boolean = false, priority:int = 0, useWeakReference:Boolean = false):void {
+            super.addEventListener(type, listener, useCapture, priority, useWeakReference);
+            if (type == "sampleData") {
+                this.updateCapture();
+            }
+        }
+
+        override public function removeEventListener(type:String, listener:Function, useCapture:Boolean = false):void {
+            super.removeEventListener(type, listener, useCapture);
+            if (type == "sampleData") {
+                this.updateCapture();
+            }
         }
 
         public function setLoopBack(isLooped:Boolean=true) {
-            __ruffle__.stub_method("flash.media.Microphone", "setLoopBack");
+            this._loopBack = isLooped;
+            this.updateCapture();
         }
 
         public function setSilenceLevel(silenceLevel:Number, timeout:int = -1) {
-            __ruffle__.stub_method("flash.media.Microphone", "setSilenceLevel");
+            this._silenceLevel = Math.max(0, Math.min(100, silenceLevel));
+            if (timeout >= 0) {
+                this._silenceTimeout = timeout;
+            }
+            this.updateCapture();
         }
 
         public function setUseEchoSuppression(isEchoSuppressed:Boolean) {
             __ruffle__.stub_method("flash.media.Microphone", "setUseEchoSuppression");
+            this._useEchoSuppression = isEchoSuppressed;
         }
 
-        public function get activityLevel():Number {
-            __ruffle__.stub_getter("flash.media.Microphone", "activityLevel");
-            return 0.0;
-        }
+        public native function get activityLevel():Number;
 
         public function get codec():String {
-            __ruffle__.stub_getter("flash.media.Microphone", "codec");
-            return "";
+            return this._codec;
         }
 
         public function set codec(codec:String) {
-            __ruffle__.stub_setter("flash.media.Microphone", "codec");
+            // Captured audio is delivered uncompressed, so the codec only matters when publishing.
+            this._codec = codec;
         }
 
         public function get enableVAD():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "enableVAD");
-            return false;
+            return this._enableVAD;
         }
 
         public function set enableVAD(isEnabled:Boolean) {
-            __ruffle__.stub_setter("flash.media.Microphone", "enableVAD");
+            this._enableVAD = isEnabled;
         }
 
         public function get encodeQuality():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "encodeQuality");
-            return 0;
+            return this._encodeQuality;
         }
 
         public function set encodeQuality(quality:int) {
-            __ruffle__.stub_setter("flash.media.Microphone", "encodeQuality");
+            this._encodeQuality = quality;
         }
 
         public function get enhancedOptions():MicrophoneEnhancedOptions {
-            __ruffle__.stub_getter("flash.media.Microphone", "enhancedOptions");
-            return new MicrophoneEnhancedOptions();
+            return this._enhancedOptions;
         }
 
         public function set enhancedOptions(params:MicrophoneEnhancedOptions) {
             __ruffle__.stub_setter("flash.media.Microphone", "enhancedOptions");
+            this._enhancedOptions = params;
         }
 
         public function get framesPerPacket():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "framesPerPacket");
-            return 0;
+            return this._framesPerPacket;
         }
 
         public function set framesPerPacket(fpp:int) {
-            __ruffle__.stub_setter("flash.media.Microphone", "framesPerPacket");
+            this._framesPerPacket = fpp;
         }
 
         public function get gain():Number {
-            __ruffle__.stub_getter("flash.media.Microphone", "gain");
-            return 0.0;
+            return this._gain;
         }
 
         public function set gain(gain:Number) {
-            __ruffle__.stub_setter("flash.media.Microphone", "gain");
+            this._gain = Math.max(0, Math.min(100, gain));
+            this.updateCapture();
         }
 
         public function get index():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "index");
-            return 0;
+            return this._index;
         }
 
         public static function get isSupported():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "isSupported");
-            return false;
+            return Microphone.names.length > 0;
         }
 
-        public function get muted():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "muted");
-            return true;
-        }
+        public native function get muted():Boolean;
 
-        public function get name():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "name");
-            return "";
+        public function get name():String {
+            return this._name;
         }
 
-        public static function get names():Array {
-            __ruffle__.stub_getter("flash.media.Microphone", "names");
-            return [];
-        }
+        public static native function get names():Array;
 
         public function get noiseSuppressionLevel():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "noiseSuppressionLevel");
-            return 0;
+            return this._noiseSuppressionLevel;
         }
 
         public function set noiseSuppressionLevel(level:int) {
             __ruffle__.stub_setter("flash.media.Microphone", "noiseSuppressionLevel");
+            this._noiseSuppressionLevel = level;
         }
 
         public function get rate():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "rate");
-            return 0;
+            return this._rate;
         }
 
         public function set rate(level:int) {
-            __ruffle__.stub_setter("flash.media.Microphone", "rate");
+            // Unsupported rates are rounded to the closest one that is supported.
+            var rates:Array = [5, 8, 11, 16, 22, 44];
+            var closest:int = rates[0];
+            for each (var rate:int in rates) {
+                if (Math.abs(rate - level) < Math.abs(closest - level)) {
+                    closest = rate;
+                }
+            }
+            this._rate = closest;
+            this.updateCapture();
         }
 
-        public function get silenceLevel():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "silenceLevel");
-            return 0;
+        public function get silenceLevel():Number {
+            return this._silenceLevel;
         }
 
         public function get silenceTimeout():int {
-            __ruffle__.stub_getter("flash.media.Microphone", "silenceTimeout");
-            return 0;
+            return this._silenceTimeout;
         }
 
         public function get soundTransform():flash.media.SoundTransform {
-            __ruffle__.stub_getter("flash.media.Microphone", "soundTransform");
-            return new SoundTransform();
+            return this._soundTransform;
         }
 
         public function set soundTransform(tf:flash.media.SoundTransform) {
             __ruffle__.stub_setter("flash.media.Microphone", "soundTransform");
+            this._soundTransform = tf;
         }
 
         public function get useEchoSuppression():Boolean {
-            __ruffle__.stub_getter("flash.media.Microphone", "useEchoSuppression");
-            return false;
+            return this._useEchoSuppression;
         }
     }
-}
\ No newline at end of file
+}
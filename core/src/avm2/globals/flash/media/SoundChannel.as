@@ -9,5 +9,10 @@ package flash.media {
         public native function get soundTransform():SoundTransform;
         public native function set soundTransform(value:SoundTransform):void;
         public native function stop():void;
+
+        // Ruffle extension: how fast the sound plays, relative to its normal speed.
+        // This also changes its pitch. It isn't available in Flash Player.
+        public native function get playbackRate():Number;
+        public native function set playbackRate(value:Number):void;
     }
 }
\ No newline at end of file
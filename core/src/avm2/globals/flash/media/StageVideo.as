@@ -0,0 +1,35 @@
+package flash.media {
+    import flash.events.EventDispatcher;
+    import flash.geom.Point;
+    import flash.geom.Rectangle;
+    import flash.net.NetStream;
+    import __ruffle__.stub_method;
+
+    [Ruffle(InstanceAllocator)]
+    public final class StageVideo extends EventDispatcher {
+        public native function get viewPort():Rectangle;
+        public native function set viewPort(value:Rectangle):void;
+
+        public native function get depth():int;
+        public native function set depth(value:int):void;
+
+        public native function get pan():Point;
+        public native function set pan(value:Point):void;
+
+        public native function get zoom():Point;
+        public native function set zoom(value:Point):void;
+
+        public native function get videoWidth():int;
+        public native function get videoHeight():int;
+
+        public function get colorSpaces():Vector.<String> {
+            return Vector.<String>(["BT.601", "BT.709"]);
+        }
+
+        public native function attachNetStream(netStream:NetStream):void;
+
+        public function attachCamera(camera:Camera):void {
+            stub_method("flash.media.StageVideo", "attachCamera");
+        }
+    }
+}
@@ -2,6 +2,7 @@ package flash.media
 {
     import flash.display.DisplayObject
     import flash.net.NetStream
+    import flash.media.Camera
     
     [Ruffle(InstanceAllocator)]
     public class Video extends DisplayObject
@@ -44,5 +45,7 @@ package flash.media
         }
 
         public native function attachNetStream(netStream: NetStream);
+
+        public native function attachCamera(camera: Camera);
     }
 }
@@ -0,0 +1,245 @@
+//! `flash.media.Camera` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::error::argument_error;
+use crate::avm2::globals::flash::display::bitmap_data::get_rectangle_x_y_width_height;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::bitmap::bitmap_data::Color;
+use crate::camera::{Camera, CameraMode, Cameras, MotionSettings};
+use crate::string::AvmString;
+use ruffle_render::bitmap::PixelRegion;
+use std::time::Duration;
+
+/// Finds the native side of a `Camera`, which `Camera.getCamera` always creates.
+fn camera<'gc>(activation: &mut Activation<'_, 'gc>, this: Object<'gc>) -> Option<Camera<'gc>> {
+    activation.context.cameras.get(this)
+}
+
+/// Returns the pixels of the last captured frame within a rectangle, clamped to the
+/// size of the capture mode.
+fn pixels_in_rect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    camera: Camera<'gc>,
+    rect: Object<'gc>,
+) -> Result<Vec<u32>, Error<'gc>> {
+    let (x, y, width, height) = get_rectangle_x_y_width_height(activation, rect)?;
+    let mode = camera.mode();
+    let mut region = PixelRegion::for_region_i32(x, y, width, height);
+    region.clamp(mode.width, mode.height);
+
+    let mut pixels = Vec::with_capacity((region.width() * region.height()) as usize);
+    for y in region.y_min..region.y_max {
+        for x in region.x_min..region.x_max {
+            pixels.push(camera.pixel(x, y).unwrap_or(0));
+        }
+    }
+    Ok(pixels)
+}
+
+/// Implements `Camera.names`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = activation
+        .context
+        .ui
+        .camera_names()
+        .into_iter()
+        .map(|name| Value::from(AvmString::new_utf8(activation.context.gc_context, name)))
+        .collect();
+
+    Ok(ArrayObject::from_storage(activation, names)?.into())
+}
+
+/// Implements `Camera.init`, which is called from `Camera.getCamera`
+pub fn init<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+
+    activation
+        .context
+        .cameras
+        .create(activation.context.gc_context, this, index.max(0) as usize);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.applyMode`
+pub fn apply_mode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let width = args.get_i32(activation, 0)?;
+    let height = args.get_i32(activation, 1)?;
+    let fps = args.get_f64(activation, 2)?;
+
+    if let Some(camera) = camera(activation, this) {
+        let mode = CameraMode {
+            width: width.max(1) as u32,
+            height: height.max(1) as u32,
+            fps: fps.max(1.0),
+        };
+        Cameras::set_mode(&mut activation.context, camera, mode);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.applyMotionLevel`
+pub fn apply_motion_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let level = args.get_i32(activation, 0)?;
+    let timeout = args.get_i32(activation, 1)?;
+
+    if let Some(camera) = camera(activation, this) {
+        let motion = MotionSettings {
+            level: level.into(),
+            timeout: Duration::from_millis(timeout.max(0) as u64),
+        };
+        Cameras::set_motion(activation.context.gc_context, camera, motion);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.activityLevel`
+pub fn get_activity_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera(activation, this)
+        .map_or(-1.0, |camera| camera.activity_level())
+        .into())
+}
+
+/// Implements `Camera.currentFPS`
+pub fn get_current_fps<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera(activation, this)
+        .map_or(0.0, |camera| camera.current_fps())
+        .into())
+}
+
+/// Implements `Camera.muted`
+pub fn get_muted<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(camera(activation, this)
+        .map_or(true, |camera| camera.is_muted())
+        .into())
+}
+
+/// Implements `Camera.drawToBitmapData`
+pub fn draw_to_bitmap_data<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let destination = args.get_object(activation, 0, "destination")?;
+    let Some(bitmap_data) = destination.as_bitmap_data() else {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2005: Parameter 0 is of the incorrect type. Should be type BitmapData.",
+            2005,
+        )?));
+    };
+    bitmap_data.check_valid(activation)?;
+
+    let Some(camera) = camera(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    if camera.last_frame().is_none() {
+        return Ok(Value::Undefined);
+    }
+
+    let mode = camera.mode();
+    let width = bitmap_data.width().min(mode.width);
+    let height = bitmap_data.height().min(mode.height);
+    let target = if width == bitmap_data.width() && height == bitmap_data.height() {
+        bitmap_data
+            .overwrite_cpu_pixels_from_gpu(activation.context.gc_context)
+            .0
+    } else {
+        bitmap_data.sync()
+    };
+    let mut write = target.write(activation.context.gc_context);
+    let transparency = write.transparency();
+    for y in 0..height {
+        for x in 0..width {
+            if let Some(color) = camera.pixel(x, y) {
+                let color = Color::from(color).to_premultiplied_alpha(transparency);
+                write.set_pixel32_raw(x, y, color);
+            }
+        }
+    }
+    write.set_cpu_dirty(PixelRegion::for_whole_size(width, height));
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.copyToByteArray`
+pub fn copy_to_byte_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rect = args.get_object(activation, 0, "rect")?;
+    let destination = args.get_object(activation, 1, "destination")?;
+
+    let Some(camera) = camera(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let pixels = pixels_in_rect(activation, camera, rect)?;
+    if let Some(mut bytearray) = destination.as_bytearray_mut(activation.context.gc_context) {
+        for pixel in pixels {
+            bytearray
+                .write_unsigned_int(pixel)
+                .map_err(|e| e.to_avm(activation))?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Camera.copyToVector`
+pub fn copy_to_vector<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let rect = args.get_object(activation, 0, "rect")?;
+    let destination = args.get_object(activation, 1, "destination")?;
+
+    let Some(camera) = camera(activation, this) else {
+        return Ok(Value::Undefined);
+    };
+    let pixels = pixels_in_rect(activation, camera, rect)?;
+    if let Some(mut vector) = destination.as_vector_storage_mut(activation.context.gc_context) {
+        if !vector.is_fixed() {
+            vector.resize(pixels.len(), activation)?;
+        }
+        for (i, pixel) in pixels.into_iter().enumerate() {
+            vector.set(i, pixel.into(), activation)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,99 @@
+//! `flash.media.Microphone` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{ArrayObject, Object};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::audio::MicrophoneSettings;
+use crate::string::AvmString;
+
+/// Implements `Microphone.names`
+pub fn get_names<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let names = activation
+        .context
+        .audio
+        .microphone_names()
+        .into_iter()
+        .map(|name| Value::from(AvmString::new_utf8(activation.context.gc_context, name)))
+        .collect();
+
+    Ok(ArrayObject::from_storage(activation, names)?.into())
+}
+
+/// Implements `Microphone.startCapture`
+pub fn start_capture<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let index = args.get_i32(activation, 0)?;
+    let rate = match args.get_i32(activation, 1)? {
+        5 => 5512,
+        11 => 11025,
+        22 => 22050,
+        44 => 44100,
+        rate => rate as u32 * 1000,
+    };
+    let gain = args.get_f64(activation, 2)?;
+    let silence_level = args.get_f64(activation, 3)?;
+    let loop_back = args.get_bool(4);
+
+    activation.context.audio_manager.start_microphone(
+        activation.context.audio,
+        this,
+        MicrophoneSettings {
+            index: index.max(0) as usize,
+            rate,
+            gain,
+            silence_level,
+            loop_back,
+        },
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.stopCapture`
+pub fn stop_capture<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation
+        .context
+        .audio_manager
+        .stop_microphone(activation.context.audio, this);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.activityLevel`
+pub fn get_activity_level<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .audio_manager
+        .microphone_activity_level(this)
+        .into())
+}
+
+/// Implements `Microphone.muted`
+pub fn get_muted<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .audio_manager
+        .is_microphone_muted(this)
+        .into())
+}
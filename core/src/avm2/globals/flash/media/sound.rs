@@ -2,6 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::object::{Object, QueuedPlay, SoundChannelObject, TObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::backend::navigator::Request;
@@ -173,6 +174,31 @@ pub fn play<'gc>(
 
         let sound_channel = SoundChannelObject::empty(activation)?;
 
+        // A sound without any data instead plays what its `sampleData` handlers supply.
+        if sound_object.sound_handle().is_none()
+            && this
+                .call_public_property("hasEventListener", &["sampleData".into()], activation)?
+                .coerce_to_boolean()
+        {
+            if let Some(instance) = activation.context.start_dynamic_sound(this) {
+                if let Some(sound_transform) = sound_transform {
+                    activation
+                        .context
+                        .set_local_sound_transform(instance, sound_transform);
+                }
+
+                sound_channel
+                    .as_sound_channel()
+                    .unwrap()
+                    .set_sound_instance(activation, instance);
+
+                activation
+                    .context
+                    .attach_avm2_sound_channel(instance, sound_channel);
+            }
+            return Ok(sound_channel.into());
+        }
+
         let queued_play = QueuedPlay {
             position,
             sound_info,
@@ -193,27 +219,40 @@ pub fn play<'gc>(
 /// `Sound.extract`
 pub fn extract<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.media.Sound", "extract");
+    let bytearray = args.get_object(activation, 0, "target")?;
+    let length = args.get_f64(activation, 1)?;
+    let start_position = args.get_f64(activation, 2)?;
+
+    let Some(sound_handle) = this
+        .as_sound_object()
+        .and_then(|sound| sound.sound_handle())
+    else {
+        return Ok(0.into());
+    };
 
-    let bytearray = args
-        .get(0)
-        .unwrap_or(&Value::Undefined)
-        .coerce_to_object(activation)?;
-    let length = args
-        .get(1)
-        .unwrap_or(&Value::Number(0.0))
-        .coerce_to_number(activation)?;
+    // A negative start position continues from where the last extraction stopped.
+    let start_position = (start_position >= 0.0).then_some(start_position as u32);
+    let samples = activation
+        .context
+        .audio
+        .extract_sound(sound_handle, start_position, length.max(0.0) as u32)
+        .unwrap_or_default();
 
     if let Some(mut bytearray) = bytearray.as_bytearray_mut(activation.context.gc_context) {
-        bytearray
-            .write_bytes(vec![0u8; length.ceil() as usize].as_slice())
-            .map_err(|e| e.to_avm(activation))?;
+        for [left, right] in &samples {
+            bytearray
+                .write_float(*left)
+                .map_err(|e| e.to_avm(activation))?;
+            bytearray
+                .write_float(*right)
+                .map_err(|e| e.to_avm(activation))?;
+        }
     }
 
-    Ok(Value::Undefined)
+    Ok(samples.len().into())
 }
 
 /// `Sound.close`
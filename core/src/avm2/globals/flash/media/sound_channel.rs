@@ -2,12 +2,19 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::display_object::SoundTransform;
 
 pub use crate::avm2::object::sound_channel_allocator;
 
+/// The slowest a sound can be played with `playbackRate`.
+const MIN_PLAYBACK_RATE: f64 = 1.0 / 16.0;
+
+/// The fastest a sound can be played with `playbackRate`.
+const MAX_PLAYBACK_RATE: f64 = 16.0;
+
 /// Implements `SoundChannel.leftPeak`
 pub fn get_left_peak<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -103,3 +110,36 @@ pub fn stop<'gc>(
 
     Ok(Value::Undefined)
 }
+
+/// Implements `playbackRate`'s getter
+pub fn get_playback_rate<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(sound_channel) = this.as_sound_channel() {
+        return Ok(sound_channel.playback_rate().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `playbackRate`'s setter
+pub fn set_playback_rate<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(sound_channel) = this.as_sound_channel() {
+        let playback_rate = args.get_f64(activation, 0)?;
+        let playback_rate = if playback_rate.is_nan() {
+            1.0
+        } else {
+            playback_rate.clamp(MIN_PLAYBACK_RATE, MAX_PLAYBACK_RATE)
+        };
+
+        sound_channel.set_playback_rate(&mut activation.context, playback_rate);
+    }
+
+    Ok(Value::Undefined)
+}
@@ -3,6 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::object::Object;
 use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2_stub_getter;
@@ -104,18 +105,14 @@ pub fn compute_spectrum<'gc>(
     _this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let arg0 = args[0].as_object().unwrap();
-    let mut bytearray = arg0
-        .as_bytearray_mut(activation.context.gc_context)
-        .unwrap();
-    let mut hist = activation.context.audio.get_sample_history();
+    let output_array = args.get_object(activation, 0, "outputArray")?;
+    let fft = args.get_bool(1);
+    let stretch = args.get_i32(activation, 2)?;
 
-    let fft = args.len() > 1 && args[1].coerce_to_boolean();
-    let stretch = if args.len() > 2 {
-        args[2].coerce_to_i32(activation)?
-    } else {
-        0
-    };
+    let mut hist = activation.context.audio.get_sample_history();
+    let mut bytearray = output_array
+        .as_bytearray_mut(activation.context.gc_context)
+        .expect("outputArray is a ByteArray");
 
     if fft {
         // TODO: Use `std::sync::LazyLock` once it's stabilized?
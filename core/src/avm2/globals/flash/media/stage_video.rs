@@ -0,0 +1,211 @@
+//! `flash.media.StageVideo` native methods
+
+use crate::avm2::error::range_error;
+use crate::avm2::globals::flash::display::display_object::object_to_rectangle;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Avm2, Error, Object, Value};
+
+pub use crate::avm2::object::stage_video_allocator;
+
+pub fn get_view_port<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_stage_video() {
+        let view_port = this.view_port();
+        let rectangle = activation.avm2().classes().rectangle.construct(
+            activation,
+            &[
+                view_port.x_min.to_pixels().into(),
+                view_port.y_min.to_pixels().into(),
+                view_port.width().to_pixels().into(),
+                view_port.height().to_pixels().into(),
+            ],
+        )?;
+        return Ok(rectangle.into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_view_port<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_stage_video() {
+        let view_port = args.get_object(activation, 0, "value")?;
+        this.set_view_port(object_to_rectangle(activation, view_port)?);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_depth<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_stage_video() {
+        return Ok(this.depth().into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_depth<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_stage_video() {
+        this.set_depth(args.get_i32(activation, 0)?);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_pan<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_stage_video() {
+        return new_point(activation, this.pan());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_pan<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_stage_video() {
+        let value = args.get_object(activation, 0, "value")?;
+        let (x, y) = object_to_point(activation, value)?;
+        if !(-1.0..=1.0).contains(&x) || !(-1.0..=1.0).contains(&y) {
+            return Err(Error::AvmError(range_error(
+                activation,
+                "Error #2006: The supplied index is out of bounds.",
+                2006,
+            )?));
+        }
+        this.set_pan((x, y));
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_zoom<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_stage_video() {
+        return new_point(activation, this.zoom());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_zoom<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_stage_video() {
+        let value = args.get_object(activation, 0, "value")?;
+        let (x, y) = object_to_point(activation, value)?;
+        if x < 1.0 || y < 1.0 {
+            return Err(Error::AvmError(range_error(
+                activation,
+                "Error #2006: The supplied index is out of bounds.",
+                2006,
+            )?));
+        }
+        this.set_zoom((x, y));
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_video_width<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_stage_video() {
+        let frame = this
+            .net_stream()
+            .and_then(|stream| stream.last_decoded_bitmap());
+        return Ok(frame.map_or(0, |frame| frame.width).into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn get_video_height<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this.as_stage_video() {
+        let frame = this
+            .net_stream()
+            .and_then(|stream| stream.last_decoded_bitmap());
+        return Ok(frame.map_or(0, |frame| frame.height).into());
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn attach_net_stream<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(stage_video) = this.as_stage_video() {
+        let stream = args
+            .try_get_object(activation, 0)
+            .and_then(|stream| stream.as_netstream());
+        stage_video.set_net_stream(stream, activation.context.gc_context);
+
+        // Videos are decoded and drawn in software, rather than by a hardware video decoder.
+        let status = if stream.is_some() {
+            "software"
+        } else {
+            "unavailable"
+        };
+        let event = activation.avm2().classes().stagevideoevent.construct(
+            activation,
+            &[
+                "renderState".into(), /* type */
+                false.into(),         /* bubbles */
+                false.into(),         /* cancelable */
+                status.into(),        /* status */
+                "BT.601".into(),      /* colorSpace */
+            ],
+        )?;
+        Avm2::dispatch_event(&mut activation.context, event, this);
+    }
+    Ok(Value::Undefined)
+}
+
+fn new_point<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    (x, y): (f64, f64),
+) -> Result<Value<'gc>, Error<'gc>> {
+    let point = activation
+        .avm2()
+        .classes()
+        .point
+        .construct(activation, &[x.into(), y.into()])?;
+    Ok(point.into())
+}
+
+fn object_to_point<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    object: Object<'gc>,
+) -> Result<(f64, f64), Error<'gc>> {
+    let x = object
+        .get_public_property("x", activation)?
+        .coerce_to_number(activation)?;
+    let y = object
+        .get_public_property("y", activation)?
+        .coerce_to_number(activation)?;
+    Ok((x, y))
+}
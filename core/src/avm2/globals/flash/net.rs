@@ -1,15 +1,23 @@
 //! `flash.net` namespace
 
+use crate::avm2::error::{reference_error, type_error};
 use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, Value};
 
+pub mod datagram_socket;
+pub mod file_reference;
+pub mod file_reference_list;
 pub mod local_connection;
 pub mod net_connection;
 pub mod net_stream;
+pub mod network_info;
 pub mod object_encoding;
+pub mod server_socket;
 pub mod shared_object;
 pub mod socket;
 pub mod url_loader;
+pub mod url_stream;
 
 /// Implements `flash.net.navigateToURL`
 pub fn navigate_to_url<'gc>(
@@ -31,11 +39,65 @@ pub fn navigate_to_url<'gc>(
         .get_public_property("url", activation)?
         .coerce_to_string(activation)?;
 
-    activation.context.navigator.navigate_to_url(
-        &url.to_utf8_lossy(),
-        &target.to_utf8_lossy(),
-        None,
-    );
+    activation
+        .context
+        .navigate_to_url(&url.to_utf8_lossy(), &target.to_utf8_lossy(), None);
 
     Ok(Value::Undefined)
 }
+
+/// Implements `flash.net.registerClassAlias`
+pub fn register_class_alias<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if matches!(args.get_value(0), Value::Null) {
+        return Err(Error::AvmError(type_error(
+            activation,
+            "Error #2007: Parameter aliasName must be non-null.",
+            2007,
+        )?));
+    }
+    let name = args.get_string(activation, 0)?;
+
+    let Some(class) = args
+        .try_get_object(activation, 1)
+        .and_then(|class| class.as_class_object())
+    else {
+        return Err(Error::AvmError(type_error(
+            activation,
+            "Error #2007: Parameter classObject must be non-null.",
+            2007,
+        )?));
+    };
+
+    activation.context.avm2.register_class_alias(name, class);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.getClassByAlias`
+pub fn get_class_by_alias<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if matches!(args.get_value(0), Value::Null) {
+        return Err(Error::AvmError(type_error(
+            activation,
+            "Error #2007: Parameter aliasName must be non-null.",
+            2007,
+        )?));
+    }
+    let name = args.get_string(activation, 0)?;
+
+    match activation.context.avm2.get_class_by_alias(name) {
+        Some(class) => Ok(class.into()),
+        None => Err(Error::AvmError(reference_error(
+            activation,
+            &format!("Error #1014: Class {name} could not be found."),
+            1014,
+        )?)),
+    }
+}
@@ -6,86 +6,80 @@ package flash.net
 
     public class FileReference extends EventDispatcher
     {
-        private var _creationDate: Date;
-        private var _creator: String;
-        private var _data: ByteArray;
-        private var _extension: String;
-        private var _modificationDate: Date;
-        private var _name: String;
-        private static var _permissionStatus: String;
-        private var _size: Number;
-        private var _type: String;
+        // Set by the player once a file has been picked or saved.
+        internal var _creationDate: Date;
+        internal var _creator: String;
+        internal var _data: ByteArray;
+        internal var _modificationDate: Date;
+        internal var _name: String;
+        internal var _size: Number;
+        internal var _type: String;
+
+        // The contents of the picked file, which become `data` once it's loaded.
+        internal var _contents: ByteArray;
 
         public function FileReference() {
-            
+
         }
 
         public function get creationDate(): Date {
             return this._creationDate;
-        }   
+        }
 
         public function get creator(): String {
-            retunr this._creator;
-        }   
+            return this._creator;
+        }
 
         public function get data(): ByteArray {
             return this._data;
-        }   
+        }
 
         public function get extension(): String {
-            return this._extension;
-        }   
+            if (this._name == null) {
+                return null;
+            }
+            var dot: int = this._name.lastIndexOf(".");
+            return dot == -1 ? null : this._name.substr(dot + 1);
+        }
 
         public function get modificationDate(): Date {
             return this._modificationDate;
-        }   
+        }
 
         public function get name(): String {
-            retunr this._name;
-        }   
+            return this._name;
+        }
 
         public static function get permissionStatus(): String {
-            return FileReference._permissionStatus;
-        }   
+            return "granted";
+        }
 
         public function get size(): Number {
             return this._size;
-        }   
+        }
 
         public function get type(): String {
             return this._type;
-        }   
+        }
 
-        public function browse(typeFilter:Array = null):Boolean {
-            return false;
-        }   
+        public native function browse(typeFilter:Array = null):Boolean;
 
-        public function cancel():void {
-            stub_method("flash.net.FileReference", "cancel");
-        }   
+        public native function cancel():void;
 
-        public function download(request:URLRequest, defaultFileName:String = null):void {
-            stub_method("flash.net.FileReference", "download");
-        }   
+        public native function download(request:URLRequest, defaultFileName:String = null):void;
 
-        public function load():void {
-            stub_method("flash.net.FileReference", "load");
-        }   
+        public native function load():void;
 
         public function requestPermission():void {
             stub_method("flash.net.FileReference", "requestPermission");
-        }   
+        }
 
-        public function save(data:*, defaultFileName:String = null):void {
-            stub_method("flash.net.FileReference", "save");
-        }   
+        public native function save(data:*, defaultFileName:String = null):void;
 
-        public function upload(request:URLRequest, uploadDataFieldName:String = "Filedata", testUpload:Boolean = false):void {
-            stub_method("flash.net.FileReference", "upload");
-        }   
+        public native function upload(request:URLRequest, uploadDataFieldName:String = "Filedata", testUpload:Boolean = false):void;
 
         public function uploadUnencoded(request:URLRequest):void {
             stub_method("flash.net.FileReference", "uploadUnencoded");
         }
     }
-}
\ No newline at end of file
+}
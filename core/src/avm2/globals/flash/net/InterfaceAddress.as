@@ -0,0 +1,10 @@
+package flash.net {
+    public class InterfaceAddress {
+        public var address:String;
+        public var broadcast:String;
+        public var ipVersion:String;
+        public var prefixLength:int;
+
+        public function InterfaceAddress() {}
+    }
+}
@@ -9,19 +9,61 @@ package flash.net {
 
         public var objectEncoding:uint = NetConnection.defaultObjectEncoding;
 
+        // The object that the server's calls are made on.
+        internal var _client:Object;
 
-        public native function connect(command:String, ... arguments):void;
+        // Whether `connect(null)` was called, which is used to play local files and HTTP streams.
+        private var _isLocal:Boolean = false;
+
+        public function NetConnection() {
+            this._client = this;
+        }
+
+        public function get client():Object {
+            return this._client;
+        }
+
+        public function set client(value:Object):void {
+            if (value == null) {
+                throw new TypeError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+            this._client = value;
+        }
+
+        public function get connected():Boolean {
+            return this._isLocal || this.isRtmpConnected();
+        }
+
+        public function get uri():String {
+            return this._isLocal ? "null" : this.rtmpUri();
+        }
+
+        public native function get usingTLS():Boolean;
+
+        public native function get protocol():String;
+
+        public function connect(command:String, ... arguments):void {
+            this._isLocal = (command == null);
+            this.innerConnect(command, arguments);
+        }
 
         public function addHeader(operation:String, mustUnderstand:Boolean = false, param:Object = null):void {
             stub_method("flash.net.NetConnection", "addHeader");
         }
 
-        public function call(command:String, responder:Responder, ... arguments):void {
-            stub_method("flash.net.NetConnection", "call");
-        }
-        
+        public native function call(command:String, responder:Responder, ... arguments):void;
+
         public function close():void {
-            stub_method("flash.net.NetConnection", "close");
+            this._isLocal = false;
+            this.innerClose();
         }
+
+        private native function innerConnect(command:String, args:Array):void;
+
+        private native function innerClose():void;
+
+        private native function isRtmpConnected():Boolean;
+
+        private native function rtmpUri():String;
     }
 }
@@ -18,16 +18,12 @@ package flash.net {
         public static const DIRECT_CONNECTIONS: String = "directConnections";
 
         public function NetStream(connection:NetConnection, peer:String = CONNECT_TO_FMS) {
-
+            this.setConnection(connection);
         }
 
-        public function appendBytes(bytes:ByteArray) {
-            stub_method("flash.net.NetStream", "appendBytes");
-        }
+        public native function appendBytes(bytes:ByteArray):void;
 
-        public function appendBytesAction(action:String) {
-            stub_method("flash.net.NetStream", "appendBytesAction");
-        }
+        public native function appendBytesAction(action:String):void;
 
         public function attach(connection:NetConnection) {
             stub_method("flash.net.NetStream", "attach");
@@ -41,9 +37,7 @@ package flash.net {
             stub_method("flash.net.NetStream", "attachCamera");
         }
 
-        public function close() {
-            stub_method("flash.net.NetStream", "close");
-        }
+        public native function close();
 
         public function dispose() {
             stub_method("flash.net.NetStream", "dispose");
@@ -61,9 +55,7 @@ package flash.net {
             stub_method("flash.net.NetStream", "preloadEmbeddedData");
         }
 
-        public function publish(name:String=null, type:String=null) {
-            stub_method("flash.net.NetStream", "publish");
-        }
+        public native function publish(name:String=null, type:String=null);
 
         public function receiveAudio(flag:Boolean) {
             stub_method("flash.net.NetStream", "receiveAudio");
@@ -87,9 +79,7 @@ package flash.net {
             stub_method("flash.net.NetStream", "seek");
         }
 
-        public function send(handlerName:String, ...args) {
-            stub_method("flash.net.NetStream", "send");
-        }
+        public native function send(handlerName:String, ...args);
 
         public function setDRMAuthenticationCredentials(userName:String, password:String, type:String) {
             stub_method("flash.net.NetStream", "setDRMAuthenticationCredentials");
@@ -360,5 +350,7 @@ package flash.net {
         public function set videoStreamSettings(settings: VideoStreamSettings) {
             stub_setter("flash.net.NetStream", "videoStreamSettings");
         };
+
+        private native function setConnection(connection:NetConnection):void;
     }
 }
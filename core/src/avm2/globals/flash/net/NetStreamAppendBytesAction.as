@@ -0,0 +1,12 @@
+package flash.net {
+    public final class NetStreamAppendBytesAction {
+        // Indicates that the media stream data is complete.
+        public static const END_SEQUENCE:String = "endSequence";
+
+        // Indicates a timescale discontinuity. The next bytes appended start with a new FLV header.
+        public static const RESET_BEGIN:String = "resetBegin";
+
+        // Indicates a timescale discontinuity. The next bytes appended start with a new FLV tag.
+        public static const RESET_SEEK:String = "resetSeek";
+    }
+}
@@ -0,0 +1,52 @@
+package flash.net {
+    import flash.events.EventDispatcher;
+
+    // AIR-only class for listing the network interfaces of the machine.
+    public class NetworkInfo extends EventDispatcher {
+        private static var _networkInfo:NetworkInfo;
+        private static var _constructing:Boolean = false;
+
+        public function NetworkInfo() {
+            if (!_constructing) {
+                throw new ArgumentError("Error #2012: NetworkInfo$ class cannot be instantiated.", 2012);
+            }
+        }
+
+        public static function get isSupported():Boolean {
+            return ServerSocket.isSupported;
+        }
+
+        public static function get networkInfo():NetworkInfo {
+            if (_networkInfo == null) {
+                _constructing = true;
+                _networkInfo = new NetworkInfo();
+                _constructing = false;
+            }
+            return _networkInfo;
+        }
+
+        public function findInterfaces():Vector.<NetworkInterface> {
+            var interfaces:Vector.<NetworkInterface> = new Vector.<NetworkInterface>();
+            for each (var info:Object in innerFindInterfaces()) {
+                var networkInterface:NetworkInterface = new NetworkInterface();
+                networkInterface.active = info.active;
+                networkInterface.displayName = info.displayName;
+                networkInterface.hardwareAddress = info.hardwareAddress;
+                networkInterface.mtu = info.mtu;
+                networkInterface.name = info.name;
+                for each (var addressInfo:Object in info.addresses) {
+                    var address:InterfaceAddress = new InterfaceAddress();
+                    address.address = addressInfo.address;
+                    address.broadcast = addressInfo.broadcast;
+                    address.ipVersion = addressInfo.ipVersion;
+                    address.prefixLength = addressInfo.prefixLength;
+                    networkInterface.addresses.push(address);
+                }
+                interfaces.push(networkInterface);
+            }
+            return interfaces;
+        }
+
+        private native function innerFindInterfaces():Array;
+    }
+}
@@ -0,0 +1,14 @@
+package flash.net {
+    public class NetworkInterface {
+        public var active:Boolean;
+        public var addresses:Vector.<InterfaceAddress> = new Vector.<InterfaceAddress>();
+        public var displayName:String;
+        public var hardwareAddress:String;
+        public var mtu:int;
+        public var name:String;
+        public var parent:NetworkInterface;
+        public var subInterfaces:Vector.<NetworkInterface>;
+
+        public function NetworkInterface() {}
+    }
+}
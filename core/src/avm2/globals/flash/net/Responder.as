@@ -1,9 +1,14 @@
 package flash.net {
-    import __ruffle__.stub_constructor;
-    
     public class Responder {
+        // Called with the server's reply to `NetConnection.call`.
+        internal var _result:Function;
+
+        // Called with the status object if the server reports an error instead.
+        internal var _status:Function;
+
         public function Responder(result:Function, status:Function = null) {
-            stub_constructor("flash.net.Responder");
+            this._result = result;
+            this._status = status;
         }
     }
 }
@@ -0,0 +1,19 @@
+package flash.net {
+    import flash.events.EventDispatcher;
+
+    // AIR-only class for listening for TCP connections.
+    public class ServerSocket extends EventDispatcher {
+        public function ServerSocket() {}
+
+        public static native function get isSupported():Boolean;
+
+        public native function get bound():Boolean;
+        public native function get listening():Boolean;
+        public native function get localAddress():String;
+        public native function get localPort():int;
+
+        public native function bind(localPort:int = 0, localAddress:String = "0.0.0.0"):void;
+        public native function listen(backlog:int = 0):void;
+        public native function close():void;
+    }
+}
@@ -1,15 +1,26 @@
 package flash.net
 {
    import flash.events.EventDispatcher;
-   import __ruffle__.stub_method;
 
    namespace ruffle = "__ruffle__";
    
    public class SharedObject extends EventDispatcher
    {
+      // Remote shared objects, by path and name.
+      private static var _remoteObjects:Object = {};
+
+      // The object that the server's `send` calls are made on.
+      internal var _client:Object;
+
+      // The name of a remote shared object on the server, which is null for local shared objects.
+      private var _remoteName:String;
+      private var _persistent:Boolean = false;
+      private var _fps:Number = Infinity;
+
       public function SharedObject()
       {
          this.data = {};
+         this._client = this;
       }
 
       // NOTE: We currently always use AMF3 serialization.
@@ -19,6 +30,29 @@ package flash.net
 
       native public static function getLocal(name:String, localPath:String = null, secure:Boolean = false): SharedObject;
 
+      public static function getRemote(name:String, remotePath:String = null, persistence:Object = false, secure:Boolean = false): SharedObject {
+         // Each remote shared object is only ever represented by one object.
+         var key:String = remotePath + "/" + name;
+         var sharedObject:SharedObject = _remoteObjects[key];
+         if (sharedObject == null) {
+            sharedObject = new SharedObject();
+            sharedObject._remoteName = name;
+            // A path to store a copy of the data locally also makes it persistent on the server.
+            sharedObject._persistent = persistence is String || persistence == true;
+            _remoteObjects[key] = sharedObject;
+         }
+         return sharedObject;
+      }
+
+      public function connect(myConnection:NetConnection, params:String = null):void {
+         if (this._remoteName == null) {
+            throw new Error("Error #2139: SharedObject could not connect.", 2139);
+         }
+         this.connectRemote(myConnection, this._remoteName, this._persistent);
+      }
+
+      private native function connectRemote(connection:NetConnection, name:String, persistent:Boolean):void;
+
       native public function get size() : uint;
 
       native public function flush(minDiskSpace:int = 0) : String;
@@ -26,7 +60,43 @@ package flash.net
       native public function clear() : void;
 
       public function setProperty(propertyName:String, value:Object = null):void {
-         stub_method("flash.net.SharedObject", "setProperty");
+         if (value == null) {
+            delete this.data[propertyName];
+         } else {
+            this.data[propertyName] = value;
+         }
+         this.setDirty(propertyName);
+      }
+
+      public function setDirty(propertyName:String):void {
+         // Local shared objects are written in full when they're flushed.
+         if (this._remoteName != null) {
+            this.sendChange(propertyName);
+         }
+      }
+
+      private native function sendChange(propertyName:String):void;
+
+      public native function send(... arguments):void;
+
+      public function get client():Object {
+         return this._client;
+      }
+
+      public function set client(value:Object):void {
+         if (value == null) {
+            throw new TypeError("Error #2004: One of the parameters is invalid.", 2004);
+         }
+         this._client = value;
+      }
+
+      public function get fps():Number {
+         return this._fps;
+      }
+
+      public function set fps(updatesPerSecond:Number):void {
+         // Changes are sent to the server as soon as they're made.
+         this._fps = updatesPerSecond;
       }
 
       // note: this is supposed to be a read-only property
@@ -16,6 +16,9 @@ package flash.net {
 		public var digest:String;
 		private var _method:String = URLRequestMethod.GET;
 		private var _data:Object;
+		private var _followRedirects:Boolean = URLRequestDefaults.followRedirects;
+		private var _manageCookies:Boolean = URLRequestDefaults.manageCookies;
+		private var _userAgent:String = URLRequestDefaults.userAgent;
 
 		public function URLRequest(url:String = null) {
 			this.url = url;
@@ -54,5 +57,29 @@ package flash.net {
 			_requestHeaders = headers;
 		}
 
+		public function get followRedirects():Boolean {
+			return this._followRedirects;
+		}
+
+		public function set followRedirects(value:Boolean):void {
+			this._followRedirects = value;
+		}
+
+		public function get manageCookies():Boolean {
+			return this._manageCookies;
+		}
+
+		public function set manageCookies(value:Boolean):void {
+			this._manageCookies = value;
+		}
+
+		public function get userAgent():String {
+			return this._userAgent;
+		}
+
+		public function set userAgent(value:String):void {
+			this._userAgent = value;
+		}
+
 	}
 }
@@ -100,13 +100,11 @@ package flash.net
 
         public static function get userAgent():String
         {
-            stub_getter("flash.net.URLRequestDefaults", "userAgent");
             return _userAgent;
         }
 
         public static function set userAgent(value:String):void
         {
-            stub_setter("flash.net.URLRequestDefaults", "userAgent");
             _userAgent = value;
         }
     }
@@ -1,23 +1,86 @@
 package flash.net {
+    import flash.events.DataEvent;
+    import flash.events.Event;
     import flash.events.EventDispatcher;
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_method;
+    import flash.events.IOErrorEvent;
+    import flash.events.ProgressEvent;
+    import flash.events.SecurityErrorEvent;
+    import flash.utils.ByteArray;
 
+    // An XMLSocket is a Socket that sends and receives strings terminated by null bytes.
     public class XMLSocket extends EventDispatcher {
-        public function XMLSocket(host: String = null, port: int = 0) {
-            stub_constructor("flash.net.XMLSocket");
+        private var _socket:Socket = new Socket();
+
+        // The received bytes of a message that hasn't been terminated yet.
+        private var _pending:ByteArray = new ByteArray();
+
+        public function XMLSocket(host:String = null, port:int = 0) {
+            this._socket.addEventListener(Event.CONNECT, this.redispatch);
+            this._socket.addEventListener(Event.CLOSE, this.redispatch);
+            this._socket.addEventListener(IOErrorEvent.IO_ERROR, this.redispatch);
+            this._socket.addEventListener(SecurityErrorEvent.SECURITY_ERROR, this.redispatch);
+            this._socket.addEventListener(ProgressEvent.SOCKET_DATA, this.onSocketData);
+
+            if (host != null) {
+                this.connect(host, port);
+            }
+        }
+
+        public function get connected():Boolean {
+            return this._socket.connected;
+        }
+
+        public function get timeout():int {
+            return this._socket.timeout;
         }
 
-        public function close(): void {
-            stub_method("flash.net.XMLSocket", "close");
+        public function set timeout(value:int):void {
+            this._socket.timeout = value;
         }
 
-        public function connect(host: String, port: int): void {
-            stub_method("flash.net.XMLSocket", "connect");
+        public function close():void {
+            if (this._socket.connected) {
+                this._socket.close();
+            }
+            this._pending.clear();
         }
 
-        public function send(object: *): void {
-            stub_method("flash.net.XMLSocket", "send");
+        public function connect(host:String, port:int):void {
+            this._pending.clear();
+            this._socket.connect(host, port);
+        }
+
+        public function send(object:*):void {
+            this._socket.writeUTFBytes(String(object));
+            this._socket.writeByte(0);
+            this._socket.flush();
+        }
+
+        private function redispatch(event:Event):void {
+            this.dispatchEvent(event.clone());
+        }
+
+        private function onSocketData(event:ProgressEvent):void {
+            this._socket.readBytes(this._pending, this._pending.length);
+
+            // Dispatch every message that has been terminated.
+            var start:uint = 0;
+            for (var i:uint = 0; i < this._pending.length; i++) {
+                if (this._pending[i] == 0) {
+                    this._pending.position = start;
+                    var data:String = this._pending.readUTFBytes(i - start);
+                    start = i + 1;
+                    this.dispatchEvent(new DataEvent(DataEvent.DATA, false, false, data));
+                }
+            }
+
+            if (start > 0) {
+                var rest:ByteArray = new ByteArray();
+                if (start < this._pending.length) {
+                    rest.writeBytes(this._pending, start);
+                }
+                this._pending = rest;
+            }
         }
     }
 }
@@ -0,0 +1,303 @@
+//! `flash.net.DatagramSocket` native methods
+
+use crate::avm2::error::{io_error, range_error};
+use crate::avm2::globals::flash::net::socket::invalid_socket_error;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::context::UpdateContext;
+use crate::socket::Datagram;
+use crate::string::AvmString;
+
+fn invalid_port_number<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+    match range_error(
+        activation,
+        "Error #2003: Invalid socket port number specified.",
+        2003,
+    ) {
+        Ok(err) => Error::AvmError(err),
+        Err(e) => e,
+    }
+}
+
+/// Implements `DatagramSocket.isSupported`
+pub fn get_is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((!cfg!(target_family = "wasm")).into())
+}
+
+/// Implements `DatagramSocket.bound`
+pub fn get_bound<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .sockets
+        .datagram_handle(this)
+        .is_some()
+        .into())
+}
+
+/// Implements `DatagramSocket.connected`
+pub fn get_connected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sockets = &activation.context.sockets;
+    let connected = sockets
+        .datagram_handle(this)
+        .and_then(|handle| sockets.datagram_remote(handle))
+        .is_some();
+    Ok(connected.into())
+}
+
+/// Implements `DatagramSocket.localAddress`
+pub fn get_local_address<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sockets = &activation.context.sockets;
+    let address = sockets
+        .datagram_handle(this)
+        .and_then(|handle| sockets.datagram_address(handle))
+        .map(|(address, _)| address.to_owned());
+
+    Ok(match address {
+        Some(address) => AvmString::new_utf8(activation.context.gc_context, address).into(),
+        None => "".into(),
+    })
+}
+
+/// Implements `DatagramSocket.localPort`
+pub fn get_local_port<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sockets = &activation.context.sockets;
+    let port = sockets
+        .datagram_handle(this)
+        .and_then(|handle| sockets.datagram_address(handle))
+        .map_or(0, |(_, port)| port);
+    Ok(port.into())
+}
+
+/// Implements `DatagramSocket.remoteAddress`
+pub fn get_remote_address<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sockets = &activation.context.sockets;
+    let address = sockets
+        .datagram_handle(this)
+        .and_then(|handle| sockets.datagram_remote(handle))
+        .map(|(address, _)| address.to_owned());
+
+    Ok(match address {
+        Some(address) => AvmString::new_utf8(activation.context.gc_context, address).into(),
+        None => "".into(),
+    })
+}
+
+/// Implements `DatagramSocket.remotePort`
+pub fn get_remote_port<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sockets = &activation.context.sockets;
+    let port = sockets
+        .datagram_handle(this)
+        .and_then(|handle| sockets.datagram_remote(handle))
+        .map_or(0, |(_, port)| port);
+    Ok(port.into())
+}
+
+/// Implements `DatagramSocket.bind`
+pub fn bind<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let port = args.get_i32(activation, 0)?;
+    let address = args.get_string(activation, 1)?;
+
+    let Ok(port) = u16::try_from(port) else {
+        return Err(invalid_port_number(activation));
+    };
+
+    if activation.context.sockets.datagram_handle(this).is_some() {
+        return Err(invalid_socket_error(activation));
+    }
+
+    let UpdateContext {
+        sockets, navigator, ..
+    } = &mut activation.context;
+
+    if let Err(e) =
+        sockets.bind_datagram(*navigator, this, address.to_utf8_lossy().into_owned(), port)
+    {
+        tracing::warn!("DatagramSocket.bind: {e}");
+        return Err(Error::AvmError(io_error(
+            activation,
+            "Error #2031: Socket Error.",
+            2031,
+        )?));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DatagramSocket.connect`
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let address = args.get_string(activation, 0)?;
+    let port = args.get_i32(activation, 1)?;
+
+    let Ok(port) = u16::try_from(port) else {
+        return Err(invalid_port_number(activation));
+    };
+
+    // Connecting an unbound socket binds it to any local port first.
+    let handle = match activation.context.sockets.datagram_handle(this) {
+        Some(handle) => handle,
+        None => {
+            bind(activation, this, &[0.into(), "0.0.0.0".into()])?;
+            activation
+                .context
+                .sockets
+                .datagram_handle(this)
+                .ok_or_else(|| invalid_socket_error(activation))?
+        }
+    };
+
+    activation
+        .context
+        .sockets
+        .connect_datagram(handle, address.to_utf8_lossy().into_owned(), port);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DatagramSocket.send`
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bytes = args.get_object(activation, 0, "bytes")?;
+    let offset = args.get_u32(activation, 1)? as usize;
+    let length = args.get_u32(activation, 2)? as usize;
+    let address = args.try_get_string(activation, 3)?;
+    let port = args.get_i32(activation, 4)?;
+
+    let data = {
+        let bytearray = bytes
+            .as_bytearray()
+            .ok_or("DatagramSocket.send: bytes must be a ByteArray")?;
+        let bytes = bytearray.bytes();
+        let end = if length == 0 {
+            bytes.len()
+        } else {
+            offset.saturating_add(length)
+        };
+        match bytes.get(offset..end) {
+            Some(data) => data.to_vec(),
+            None => {
+                return Err(Error::AvmError(range_error(
+                    activation,
+                    "Error #2006: The supplied index is out of bounds.",
+                    2006,
+                )?))
+            }
+        }
+    };
+
+    // Unbound sockets are bound to any local port when they first send.
+    let handle = match activation.context.sockets.datagram_handle(this) {
+        Some(handle) => handle,
+        None => {
+            bind(activation, this, &[0.into(), "0.0.0.0".into()])?;
+            activation
+                .context
+                .sockets
+                .datagram_handle(this)
+                .ok_or_else(|| invalid_socket_error(activation))?
+        }
+    };
+
+    let remote = activation
+        .context
+        .sockets
+        .datagram_remote(handle)
+        .map(|(address, port)| (address.to_owned(), port));
+    let (address, port) = match (remote, address) {
+        (Some(remote), None) => remote,
+        // Connected sockets can't send packets anywhere else.
+        (Some(_), Some(_)) => {
+            return Err(Error::AvmError(io_error(
+                activation,
+                "Error #2031: Socket Error.",
+                2031,
+            )?))
+        }
+        (None, Some(address)) => {
+            let Ok(port) = u16::try_from(port) else {
+                return Err(invalid_port_number(activation));
+            };
+            (address.to_utf8_lossy().into_owned(), port)
+        }
+        (None, None) => return Err(invalid_socket_error(activation)),
+    };
+
+    activation.context.sockets.send_datagram(
+        handle,
+        Datagram {
+            data,
+            address,
+            port,
+        },
+    );
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DatagramSocket.receive`
+pub fn receive<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(handle) = activation.context.sockets.datagram_handle(this) else {
+        return Err(invalid_socket_error(activation));
+    };
+
+    activation.context.sockets.receive_datagrams(handle);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DatagramSocket.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(handle) = activation.context.sockets.datagram_handle(this) else {
+        return Err(invalid_socket_error(activation));
+    };
+
+    activation.context.sockets.close_datagram(handle);
+
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,368 @@
+//! `flash.net.FileReference` native function definitions
+
+use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::error::{argument_error, illegal_operation_error};
+use crate::avm2::globals::flash::display::loader::request_from_url_request;
+use crate::avm2::object::{ByteArrayObject, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Multiname, Object, Value};
+use crate::backend::navigator::Request;
+use crate::backend::ui::{FileFilter, SelectedFile};
+use crate::string::AvmString;
+use chrono::{DateTime, Utc};
+use url::form_urlencoded;
+
+/// The boundary between the parts of an upload, which is the same one that Flash Player uses.
+const MULTIPART_BOUNDARY: &str = "----------Ij5GI3ei4cH2ae0Ef1KM7cH2ei4GI3";
+
+/// Reads the `FileFilter`s passed to `browse`.
+pub fn file_filters<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    filters: Option<Object<'gc>>,
+) -> Result<Vec<FileFilter>, Error<'gc>> {
+    let Some(filters) = filters else {
+        return Ok(Vec::new());
+    };
+    let filters: Vec<_> = match filters.as_array_storage() {
+        Some(filters) => filters.iter().collect(),
+        None => return Ok(Vec::new()),
+    };
+
+    let mut file_filters = Vec::with_capacity(filters.len());
+    for filter in filters.into_iter().flatten() {
+        let filter = filter.coerce_to_object(activation)?;
+        // The `description` getter includes the extensions, as dialogs show them.
+        let description = filter
+            .get_public_property("description", activation)?
+            .coerce_to_string(activation)?
+            .to_string();
+        let extensions = filter
+            .get_public_property("extension", activation)?
+            .coerce_to_string(activation)?
+            .to_string();
+        let mac_type = match filter.get_public_property("macType", activation)? {
+            Value::Null | Value::Undefined => None,
+            mac_type => Some(mac_type.coerce_to_string(activation)?.to_string()),
+        };
+
+        file_filters.push(FileFilter {
+            description,
+            extensions: extensions
+                .split(';')
+                .map(|extension| extension.trim().trim_start_matches("*.").to_string())
+                .filter(|extension| !extension.is_empty())
+                .collect(),
+            mac_type,
+        });
+    }
+    Ok(file_filters)
+}
+
+/// Shows a dialog for picking files for a `FileReference` or `FileReferenceList`.
+///
+/// Returns `false` if no dialog could be shown.
+pub fn browse_for_files<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    filters: Option<Object<'gc>>,
+    multiple: bool,
+) -> Result<bool, Error<'gc>> {
+    let filters = file_filters(activation, filters)?;
+    let Some(dialog) = activation
+        .context
+        .ui
+        .display_file_open_dialog(filters, multiple)
+    else {
+        return Ok(false);
+    };
+
+    let future = activation.context.load_manager.select_file_dialog(
+        activation.context.player.clone(),
+        this,
+        dialog,
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(true)
+}
+
+/// Creates a `Date` for a time that a file was created or modified at, or `null` if unknown.
+pub fn date_value<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    date_time: Option<DateTime<Utc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(date_time) = date_time else {
+        return Ok(Value::Null);
+    };
+    let millis = date_time.timestamp_millis() as f64;
+    Ok(activation
+        .avm2()
+        .classes()
+        .date
+        .construct(activation, &[millis.into()])?
+        .into())
+}
+
+/// Sets the properties of a `FileReference` to describe a file that the user picked.
+pub fn set_selected_file<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    mut this: Object<'gc>,
+    file: SelectedFile,
+) -> Result<(), Error<'gc>> {
+    let ns = activation.avm2().flash_net_internal;
+    let extension = file.name.rfind('.').map(|dot| file.name[dot..].to_string());
+    let creation_date = date_value(activation, file.creation_date)?;
+    let modification_date = date_value(activation, file.modification_date)?;
+    let name = AvmString::new_utf8(activation.context.gc_context, file.name);
+    let file_type = match extension {
+        Some(extension) => AvmString::new_utf8(activation.context.gc_context, extension).into(),
+        None => Value::Null,
+    };
+    let size = file.contents.len();
+    let contents =
+        ByteArrayObject::from_storage(activation, ByteArrayStorage::from_vec(file.contents))?;
+
+    this.set_property(&Multiname::new(ns, "_name"), name.into(), activation)?;
+    this.set_property(&Multiname::new(ns, "_size"), size.into(), activation)?;
+    this.set_property(&Multiname::new(ns, "_type"), file_type, activation)?;
+    this.set_property(&Multiname::new(ns, "_creator"), Value::Null, activation)?;
+    this.set_property(
+        &Multiname::new(ns, "_creationDate"),
+        creation_date,
+        activation,
+    )?;
+    this.set_property(
+        &Multiname::new(ns, "_modificationDate"),
+        modification_date,
+        activation,
+    )?;
+    this.set_property(
+        &Multiname::new(ns, "_contents"),
+        contents.into(),
+        activation,
+    )?;
+    // The data of a newly picked file isn't available until it's loaded again.
+    this.set_property(&Multiname::new(ns, "_data"), Value::Null, activation)?;
+    Ok(())
+}
+
+/// Returns the contents of the file that the user picked, or throws if there's none.
+fn selected_contents<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let ns = activation.avm2().flash_net_internal;
+    match this.get_property(&Multiname::new(ns, "_contents"), activation)? {
+        Value::Object(contents) => Ok(contents),
+        _ => Err(Error::AvmError(illegal_operation_error(
+            activation,
+            "Error #2037: Functions called in incorrect sequence, or earlier call was unsuccessful.",
+            2037,
+        )?)),
+    }
+}
+
+/// Builds the body of a multipart upload of a file, along with its content type.
+fn multipart_body(
+    fields: &[(String, String)],
+    field_name: &str,
+    file_name: &str,
+    contents: &[u8],
+) -> (Vec<u8>, String) {
+    let mut body = Vec::with_capacity(contents.len() + 1024);
+    let write_field = |body: &mut Vec<u8>, name: &str, value: &str| {
+        body.extend_from_slice(format!("--{MULTIPART_BOUNDARY}\r\n").as_bytes());
+        body.extend_from_slice(
+            format!("Content-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n")
+                .as_bytes(),
+        );
+    };
+
+    write_field(&mut body, "Filename", file_name);
+    for (name, value) in fields {
+        write_field(&mut body, name, value);
+    }
+
+    body.extend_from_slice(format!("--{MULTIPART_BOUNDARY}\r\n").as_bytes());
+    body.extend_from_slice(
+        format!(
+            "Content-Disposition: form-data; name=\"{field_name}\"; filename=\"{file_name}\"\r\n\
+             Content-Type: application/octet-stream\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(contents);
+    body.extend_from_slice(b"\r\n");
+
+    write_field(&mut body, "Upload", "Submit Query");
+    body.extend_from_slice(format!("--{MULTIPART_BOUNDARY}--\r\n").as_bytes());
+
+    (
+        body,
+        format!("multipart/form-data; boundary={MULTIPART_BOUNDARY}"),
+    )
+}
+
+/// Implements `FileReference.browse`
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let filters = args.try_get_object(activation, 0);
+    Ok(browse_for_files(activation, this, filters, false)?.into())
+}
+
+/// Implements `FileReference.cancel`
+pub fn cancel<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.context.load_manager.cancel_file_reference(this);
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.download`
+pub fn download<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url_request = args.get_object(activation, 0, "request")?;
+    let request = request_from_url_request(activation, url_request)?;
+    let file_name = match args.try_get_string(activation, 1)? {
+        Some(file_name) => file_name.to_string(),
+        None => url::Url::parse(request.url())
+            .ok()
+            .and_then(|url| {
+                url.path_segments()
+                    .and_then(|mut segments| segments.next_back().map(str::to_string))
+            })
+            .unwrap_or_default(),
+    };
+
+    let future = activation.context.load_manager.download_file(
+        activation.context.player.clone(),
+        this,
+        request,
+        file_name,
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.load`
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    selected_contents(activation, this)?;
+
+    let future = activation
+        .context
+        .load_manager
+        .load_file_reference(activation.context.player.clone(), this);
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.save`
+pub fn save<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let data = match args.get_value(0) {
+        Value::Null | Value::Undefined => {
+            return Err(Error::AvmError(argument_error(
+                activation,
+                "Error #2007: Parameter data must be non-null.",
+                2007,
+            )?));
+        }
+        Value::Object(object) if object.as_bytearray().is_some() => {
+            object.as_bytearray().unwrap().bytes().to_vec()
+        }
+        data => data
+            .coerce_to_string(activation)?
+            .to_utf8_lossy()
+            .into_owned()
+            .into_bytes(),
+    };
+    let file_name = args
+        .try_get_string(activation, 1)?
+        .map(|file_name| file_name.to_string())
+        .unwrap_or_default();
+
+    let size = data.len();
+    let Some(dialog) = activation
+        .context
+        .ui
+        .display_file_save_dialog(file_name, data)
+    else {
+        return Err(Error::AvmError(illegal_operation_error(
+            activation,
+            "Error #2174: Only one download, upload, load or save operation can be active at a time on each FileReference.",
+            2174,
+        )?));
+    };
+
+    let future = activation.context.load_manager.save_file_dialog(
+        activation.context.player.clone(),
+        this,
+        dialog,
+        size,
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.upload`
+pub fn upload<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url_request = args.get_object(activation, 0, "request")?;
+    let field_name = args.get_string(activation, 1)?.to_string();
+    let contents = selected_contents(activation, this)?;
+
+    let url = url_request
+        .get_public_property("url", activation)?
+        .coerce_to_string(activation)?
+        .to_string();
+    // Uploads are always posted, with the variables of the request as fields of the form.
+    let fields = match url_request.get_public_property("data", activation)? {
+        Value::Null | Value::Undefined => Vec::new(),
+        data => {
+            let data = data.coerce_to_string(activation)?.to_string();
+            form_urlencoded::parse(data.as_bytes())
+                .into_owned()
+                .collect()
+        }
+    };
+    let headers = request_from_url_request(activation, url_request)?
+        .headers()
+        .clone();
+
+    let ns = activation.avm2().flash_net_internal;
+    let file_name = this
+        .get_property(&Multiname::new(ns, "_name"), activation)?
+        .coerce_to_string(activation)?
+        .to_string();
+    let body = {
+        let contents = contents.as_bytearray().unwrap();
+        multipart_body(&fields, &field_name, &file_name, contents.bytes())
+    };
+    let mut request = Request::post(url, Some(body));
+    request.set_headers(headers);
+
+    let future = activation.context.load_manager.upload_file(
+        activation.context.player.clone(),
+        this,
+        request,
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
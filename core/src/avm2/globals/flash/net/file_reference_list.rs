@@ -0,0 +1,15 @@
+//! `flash.net.FileReferenceList` native function definitions
+
+use crate::avm2::globals::flash::net::file_reference::browse_for_files;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+
+/// Implements `FileReferenceList.browse`
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let filters = args.try_get_object(activation, 0);
+    Ok(browse_for_files(activation, this, filters, true)?.into())
+}
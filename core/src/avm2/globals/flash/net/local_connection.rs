@@ -1,10 +1,10 @@
-use crate::avm2::error::type_error;
-use crate::avm2::object::TObject;
+use crate::avm2::amf::serialize_value;
+use crate::avm2::error::{argument_error, type_error};
 use crate::avm2::parameters::ParametersExt;
-use crate::avm2::{Activation, Avm2, Error, Object, Value};
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::local_connection::{movie_domain, LocalConnections};
 use crate::string::AvmString;
-
-use crate::avm2_stub_method;
+use flash_lso::types::Value as AmfValue;
 
 /// Implements `domain` getter
 pub fn get_domain<'gc>(
@@ -12,23 +12,57 @@ pub fn get_domain<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let movie = activation.context.swf;
-
-    let domain = if let Ok(url) = url::Url::parse(movie.url()) {
-        if url.scheme() == "file" {
-            "localhost".into()
-        } else if let Some(domain) = url.domain() {
-            AvmString::new_utf8(activation.context.gc_context, domain)
-        } else {
-            // no domain?
-            "localhost".into()
-        }
-    } else {
+    let Some(domain) = movie_domain(activation.context.swf.url()) else {
         tracing::error!("LocalConnection::domain: Unable to parse movie URL");
         return Ok(Value::Null);
     };
 
-    Ok(Value::String(domain))
+    Ok(AvmString::new_utf8(activation.context.gc_context, domain).into())
+}
+
+/// Implements `LocalConnection.connect`
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if matches!(args.get_value(0), Value::Null) {
+        return Err(Error::AvmError(type_error(
+            activation,
+            "Error #2007: Parameter connectionName must be non-null.",
+            2007,
+        )?));
+    }
+    let name = args.get_string(activation, 0)?;
+
+    if activation.context.local_connections.is_connected(this)
+        || !LocalConnections::connect(&mut activation.context, this, &name.to_utf8_lossy())
+    {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2082: Connect failed because the object is already connected.",
+            2082,
+        )?));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if !LocalConnections::close(&mut activation.context, this) {
+        return Err(Error::AvmError(argument_error(
+            activation,
+            "Error #2083: Close failed because the object is not connected.",
+            2083,
+        )?));
+    }
+
+    Ok(Value::Undefined)
 }
 
 /// Implements `LocalConnection.send`
@@ -53,21 +87,23 @@ pub fn send<'gc>(
         )?));
     }
 
-    avm2_stub_method!(activation, "flash.net.LocalConnection", "send");
-
-    let event = activation.avm2().classes().statusevent.construct(
-        activation,
-        &[
-            "status".into(),
-            false.into(),
-            false.into(),
-            Value::Null,
-            "error".into(),
-        ],
-    )?;
+    let name = args.get_string(activation, 0)?;
+    let method = args.get_string(activation, 1)?;
+    // Arguments are always sent with AMF0, which movies of both AVMs can read.
+    let arguments: Vec<_> = args[2..]
+        .iter()
+        .map(|argument| serialize_value(activation, *argument).unwrap_or(AmfValue::Undefined))
+        .collect();
 
-    // FIXME: Adding the event listener after calling `send` works in FP.
-    Avm2::dispatch_event(&mut activation.context, event, this);
+    // The sender is told whether the message was received on the next tick,
+    // so listeners can be added after calling `send`.
+    LocalConnections::send(
+        &mut activation.context,
+        this,
+        &name.to_utf8_lossy(),
+        &method.to_utf8_lossy(),
+        &arguments,
+    );
 
     Ok(Value::Undefined)
 }
@@ -1,13 +1,34 @@
 use crate::{
-    avm2::{Activation, Avm2, Error, EventObject, Object, Value},
+    avm2::{
+        amf::serialize_value, parameters::ParametersExt, Activation, Avm2, Error, EventObject,
+        Object, TObject, Value,
+    },
     avm2_stub_method,
+    net_connection::NetConnections,
+    string::AvmString,
 };
+use flash_lso::types::Value as AmfValue;
 
-pub fn connect<'gc>(
+/// Serializes the arguments of a command, which are always sent with AMF0.
+fn serialize_arguments<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    arguments: &[Value<'gc>],
+) -> Vec<AmfValue> {
+    arguments
+        .iter()
+        .map(|argument| serialize_value(activation, *argument).unwrap_or(AmfValue::Undefined))
+        .collect()
+}
+
+pub fn inner_connect<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(handle) = activation.context.net_connections.handle(this) {
+        activation.context.net_connections.close(handle);
+    }
+
     if let Value::Null = args[0] {
         let event = EventObject::net_status_event(
             activation,
@@ -20,11 +41,161 @@ pub fn connect<'gc>(
         Avm2::dispatch_event(&mut activation.context, event, this);
         return Ok(Value::Undefined);
     }
-    avm2_stub_method!(
-        activation,
-        "flash.net.NetConnection",
-        "connect",
-        "with non-null command"
-    );
+
+    let command = args.get_string(activation, 0)?;
+    let Ok(url) = url::Url::parse(&command.to_utf8_lossy()) else {
+        avm2_stub_method!(
+            activation,
+            "flash.net.NetConnection",
+            "connect",
+            "with non-RTMP command"
+        );
+        return Ok(Value::Undefined);
+    };
+    if !matches!(url.scheme(), "rtmp" | "rtmps") {
+        avm2_stub_method!(
+            activation,
+            "flash.net.NetConnection",
+            "connect",
+            "with non-RTMP command"
+        );
+        return Ok(Value::Undefined);
+    }
+
+    let arguments: Vec<Value<'gc>> = args
+        .try_get_object(activation, 1)
+        .and_then(|arguments| {
+            arguments.as_array_storage().map(|arguments| {
+                arguments
+                    .iter()
+                    .map(|v| v.unwrap_or(Value::Undefined))
+                    .collect()
+            })
+        })
+        .unwrap_or_default();
+    let arguments = serialize_arguments(activation, &arguments);
+
+    if let Err(e) = NetConnections::connect(&mut activation.context, this, &url, &arguments) {
+        tracing::warn!("Invalid RTMP URL {}: {}", url, e);
+        let event = EventObject::net_status_event(
+            activation,
+            "netStatus",
+            &[("code", "NetConnection.Connect.Failed"), ("level", "error")],
+        );
+        Avm2::dispatch_event(&mut activation.context, event, this);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn call<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(handle) = activation.context.net_connections.handle(this) else {
+        avm2_stub_method!(
+            activation,
+            "flash.net.NetConnection",
+            "call",
+            "without RTMP connection"
+        );
+        return Ok(Value::Undefined);
+    };
+
+    let command = args.get_string(activation, 0)?.to_utf8_lossy().into_owned();
+    let responder = args.try_get_object(activation, 1);
+    let arguments = serialize_arguments(activation, &args[2..]);
+
+    activation
+        .context
+        .net_connections
+        .call(handle, &command, responder, &arguments);
+
+    Ok(Value::Undefined)
+}
+
+pub fn inner_close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(handle) = activation.context.net_connections.handle(this) {
+        let was_connected = activation.context.net_connections.is_connected(handle);
+        activation.context.net_connections.close(handle);
+
+        if was_connected {
+            let event = EventObject::net_status_event(
+                activation,
+                "netStatus",
+                &[
+                    ("code", "NetConnection.Connect.Closed"),
+                    ("level", "status"),
+                ],
+            );
+            Avm2::dispatch_event(&mut activation.context, event, this);
+        }
+    }
+
     Ok(Value::Undefined)
 }
+
+pub fn is_rtmp_connected<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let net_connections = &activation.context.net_connections;
+    let is_connected = net_connections
+        .handle(this)
+        .map_or(false, |handle| net_connections.is_connected(handle));
+
+    Ok(is_connected.into())
+}
+
+pub fn rtmp_uri<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let net_connections = &activation.context.net_connections;
+    let uri = net_connections
+        .handle(this)
+        .and_then(|handle| net_connections.uri(handle))
+        .map(|uri| uri.to_string());
+
+    Ok(match uri {
+        Some(uri) => AvmString::new_utf8(activation.context.gc_context, uri).into(),
+        None => Value::Null,
+    })
+}
+
+pub fn get_using_tls<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let net_connections = &activation.context.net_connections;
+    let using_tls = net_connections
+        .handle(this)
+        .map_or(false, |handle| net_connections.is_secure(handle));
+
+    Ok(using_tls.into())
+}
+
+pub fn get_protocol<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let net_connections = &activation.context.net_connections;
+    let Some(handle) = net_connections.handle(this) else {
+        return Ok(Value::Null);
+    };
+
+    Ok(if net_connections.is_secure(handle) {
+        "rtmps".into()
+    } else {
+        "rtmp".into()
+    })
+}
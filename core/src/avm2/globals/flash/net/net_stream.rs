@@ -1,6 +1,12 @@
-use crate::avm2::error::type_error;
+use crate::avm2::amf::serialize_value;
+use crate::avm2::error::{make_error_2008, type_error};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::{Activation, Error, Object, TObject, Value};
+use crate::avm2_stub_method;
+use crate::net_connection::NetConnectionHandle;
+use crate::streams::{AppendBytesAction, NetStream};
+use crate::string::AvmString;
+use flash_lso::types::Value as AmfValue;
 
 pub use crate::avm2::object::netstream_allocator as net_stream_allocator;
 
@@ -28,6 +34,45 @@ pub fn get_bytes_total<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn append_bytes<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bytes = args.get_object(activation, 0, "bytes")?;
+
+    if let Some(ns) = this.as_netstream() {
+        if let Some(bytearray) = bytes.as_bytearray() {
+            ns.append_data(bytearray.bytes());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn append_bytes_action<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let action = args.get_string(activation, 0)?;
+    let action = if &action == b"endSequence" {
+        AppendBytesAction::EndSequence
+    } else if &action == b"resetBegin" {
+        AppendBytesAction::ResetBegin
+    } else if &action == b"resetSeek" {
+        AppendBytesAction::ResetSeek
+    } else {
+        return Err(make_error_2008(activation, "action"));
+    };
+
+    if let Some(ns) = this.as_netstream() {
+        ns.append_bytes_action(activation.context.gc_context, action);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn play<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -116,3 +161,108 @@ pub fn set_client<'gc>(
 
     Ok(Value::Undefined)
 }
+
+/// Returns the stream of a `NetStream`, and the RTMP connection it's played or published
+/// on, if any.
+fn rtmp_stream<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Option<(NetStream<'gc>, NetConnectionHandle)> {
+    let ns = this.as_netstream()?;
+    let handle = activation
+        .context
+        .net_connections
+        .handle(ns.net_connection()?)?;
+    Some((ns, handle))
+}
+
+pub fn set_connection<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(ns) = this.as_netstream() {
+        let connection = args.try_get_object(activation, 0);
+        ns.set_net_connection(activation.context.gc_context, connection);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn publish<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some((ns, handle)) = rtmp_stream(activation, this) else {
+        avm2_stub_method!(
+            activation,
+            "flash.net.NetStream",
+            "publish",
+            "without RTMP connection"
+        );
+        return Ok(Value::Undefined);
+    };
+
+    // Publishing with no name stops publishing.
+    let Some(name) = args.try_get_string(activation, 0)? else {
+        activation.context.net_connections.close_stream(handle, ns);
+        return Ok(Value::Undefined);
+    };
+    let publish_type = args
+        .try_get_string(activation, 1)?
+        .unwrap_or_else(|| AvmString::from("live"));
+
+    activation.context.net_connections.publish(
+        handle,
+        ns,
+        name.to_string(),
+        publish_type.to_string(),
+    );
+
+    Ok(Value::Undefined)
+}
+
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some((ns, handle)) = rtmp_stream(activation, this) else {
+        avm2_stub_method!(
+            activation,
+            "flash.net.NetStream",
+            "send",
+            "without RTMP connection"
+        );
+        return Ok(Value::Undefined);
+    };
+
+    let handler_name = args.get_string(activation, 0)?;
+    let mut values = vec![AmfValue::String(handler_name.to_string())];
+    for value in &args[1..] {
+        values.push(serialize_value(activation, *value).unwrap_or(AmfValue::Undefined));
+    }
+
+    activation
+        .context
+        .net_connections
+        .send_stream_data(handle, ns, &values);
+
+    Ok(Value::Undefined)
+}
+
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some((ns, handle)) = rtmp_stream(activation, this) {
+        activation.context.net_connections.close_stream(handle, ns);
+    }
+    if let Some(ns) = this.as_netstream() {
+        ns.pause(&mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
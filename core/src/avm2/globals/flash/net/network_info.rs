@@ -0,0 +1,68 @@
+//! `flash.net.NetworkInfo` native methods
+
+use crate::avm2::object::{ArrayObject, TObject};
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::string::AvmString;
+
+/// Lists the network interfaces of the machine as an array of plain objects, which
+/// `NetworkInfo.findInterfaces` turns into `NetworkInterface` objects.
+pub fn inner_find_interfaces<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let interfaces = activation.context.navigator.network_interfaces();
+
+    let mut interface_objects = Vec::with_capacity(interfaces.len());
+    for interface in interfaces {
+        let mut address_objects = Vec::with_capacity(interface.addresses.len());
+        for address in interface.addresses {
+            let mut address_object = activation
+                .avm2()
+                .classes()
+                .object
+                .construct(activation, &[])?;
+            let ip = AvmString::new_utf8(activation.context.gc_context, address.address);
+            let broadcast = AvmString::new_utf8(activation.context.gc_context, address.broadcast);
+            address_object.set_public_property("address", ip.into(), activation)?;
+            address_object.set_public_property("broadcast", broadcast.into(), activation)?;
+            address_object.set_public_property(
+                "ipVersion",
+                address.ip_version.into(),
+                activation,
+            )?;
+            address_object.set_public_property(
+                "prefixLength",
+                address.prefix_length.into(),
+                activation,
+            )?;
+            address_objects.push(address_object);
+        }
+
+        let mut interface_object = activation
+            .avm2()
+            .classes()
+            .object
+            .construct(activation, &[])?;
+        let name = AvmString::new_utf8(activation.context.gc_context, interface.name);
+        let display_name =
+            AvmString::new_utf8(activation.context.gc_context, interface.display_name);
+        let hardware_address =
+            AvmString::new_utf8(activation.context.gc_context, interface.hardware_address);
+        let addresses =
+            ArrayObject::from_storage(activation, address_objects.into_iter().collect())?;
+        interface_object.set_public_property("name", name.into(), activation)?;
+        interface_object.set_public_property("displayName", display_name.into(), activation)?;
+        interface_object.set_public_property("active", interface.active.into(), activation)?;
+        interface_object.set_public_property(
+            "hardwareAddress",
+            hardware_address.into(),
+            activation,
+        )?;
+        interface_object.set_public_property("mtu", interface.mtu.into(), activation)?;
+        interface_object.set_public_property("addresses", addresses.into(), activation)?;
+        interface_objects.push(interface_object);
+    }
+
+    Ok(ArrayObject::from_storage(activation, interface_objects.into_iter().collect())?.into())
+}
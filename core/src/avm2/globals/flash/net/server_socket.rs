@@ -0,0 +1,153 @@
+//! `flash.net.ServerSocket` native methods
+
+use crate::avm2::error::{io_error, range_error};
+use crate::avm2::globals::flash::net::socket::invalid_socket_error;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::{Activation, Error, Object, Value};
+use crate::context::UpdateContext;
+use crate::string::AvmString;
+
+/// Implements `ServerSocket.isSupported`
+pub fn get_is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((!cfg!(target_family = "wasm")).into())
+}
+
+/// Implements `ServerSocket.bound`
+pub fn get_bound<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation
+        .context
+        .sockets
+        .server_handle(this)
+        .is_some()
+        .into())
+}
+
+/// Implements `ServerSocket.listening`
+pub fn get_listening<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sockets = &activation.context.sockets;
+    let listening = sockets
+        .server_handle(this)
+        .map_or(false, |handle| sockets.is_listening(handle));
+    Ok(listening.into())
+}
+
+/// Implements `ServerSocket.localAddress`
+pub fn get_local_address<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sockets = &activation.context.sockets;
+    let address = sockets
+        .server_handle(this)
+        .and_then(|handle| sockets.server_address(handle))
+        .map(|(address, _)| address.to_owned());
+
+    Ok(match address {
+        Some(address) => AvmString::new_utf8(activation.context.gc_context, address).into(),
+        None => "".into(),
+    })
+}
+
+/// Implements `ServerSocket.localPort`
+pub fn get_local_port<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let sockets = &activation.context.sockets;
+    let port = sockets
+        .server_handle(this)
+        .and_then(|handle| sockets.server_address(handle))
+        .map_or(0, |(_, port)| port);
+    Ok(port.into())
+}
+
+/// Implements `ServerSocket.bind`
+pub fn bind<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let port = args.get_i32(activation, 0)?;
+    let address = args.get_string(activation, 1)?;
+
+    let Ok(port) = u16::try_from(port) else {
+        return Err(Error::AvmError(range_error(
+            activation,
+            "Error #2003: Invalid socket port number specified.",
+            2003,
+        )?));
+    };
+
+    if activation.context.sockets.server_handle(this).is_some() {
+        return Err(invalid_socket_error(activation));
+    }
+
+    let UpdateContext {
+        sockets, navigator, ..
+    } = &mut activation.context;
+
+    if let Err(e) =
+        sockets.bind_server(*navigator, this, address.to_utf8_lossy().into_owned(), port)
+    {
+        tracing::warn!("ServerSocket.bind: {e}");
+        return Err(Error::AvmError(io_error(
+            activation,
+            "Error #2031: Socket Error.",
+            2031,
+        )?));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ServerSocket.listen`
+pub fn listen<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if args.get_i32(activation, 0)? < 0 {
+        return Err(Error::AvmError(range_error(
+            activation,
+            "Error #2006: The supplied index is out of bounds.",
+            2006,
+        )?));
+    }
+
+    let Some(handle) = activation.context.sockets.server_handle(this) else {
+        return Err(invalid_socket_error(activation));
+    };
+
+    activation.context.sockets.listen(handle);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ServerSocket.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(handle) = activation.context.sockets.server_handle(this) else {
+        return Err(invalid_socket_error(activation));
+    };
+
+    activation.context.sockets.close_server(handle);
+
+    Ok(Value::Undefined)
+}
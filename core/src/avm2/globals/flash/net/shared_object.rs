@@ -1,32 +1,17 @@
 //! `flash.net.SharedObject` builtin/prototype
 
+use crate::avm2::amf::serialize_value;
 use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::Multiname;
 use crate::avm2::{Activation, Error, Namespace, Object, Value};
 use crate::avm2_stub_method;
 use crate::display_object::DisplayObject;
 use crate::display_object::TDisplayObject;
 use crate::string::AvmString;
-use flash_lso::types::{AMFVersion, Lso};
+use flash_lso::types::Value as AmfValue;
 use std::borrow::Cow;
 
-fn new_lso<'gc>(
-    activation: &mut Activation<'_, 'gc>,
-    name: &str,
-    data: Object<'gc>,
-) -> Result<Lso, Error<'gc>> {
-    let mut elements = Vec::new();
-    crate::avm2::amf::recursive_serialize(activation, data, &mut elements, AMFVersion::AMF3)?;
-    Ok(Lso::new(
-        elements,
-        name.split('/')
-            .last()
-            .map(|e| e.to_string())
-            .unwrap_or_else(|| "<unknown>".to_string()),
-        AMFVersion::AMF3,
-    ))
-}
-
 pub fn get_local<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -168,7 +153,10 @@ pub fn get_local<'gc>(
 
     // Load the data object from storage if it existed prior
     if let Some(saved) = activation.context.storage.get(&full_name) {
-        if let Ok(lso) = flash_lso::read::Reader::default().parse(&saved) {
+        if let Some(obj) = crate::avm2::amf::deserialize_amf3_lso(activation, &saved)? {
+            data = obj.into();
+        } else if let Ok(lso) = flash_lso::read::Reader::default().parse(&saved) {
+            // Every AMF3 file is read above, so this only handles AMF0 files.
             data = crate::avm2::amf::deserialize_lso(activation, &lso)?.into();
         }
     }
@@ -210,13 +198,10 @@ pub fn flush<'gc>(
         .coerce_to_string(activation)?;
     let name = name.to_utf8_lossy();
 
-    let mut lso = new_lso(activation, &name, data)?;
     // Flash does not write empty LSOs to disk
-    if lso.body.is_empty() {
-        Ok(true.into())
-    } else {
-        let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
-        Ok(activation.context.storage.put(&name, &bytes).into())
+    match crate::avm2::amf::serialize_lso(activation, &name, data)? {
+        Some(bytes) => Ok(activation.context.storage.put(&name, &bytes).into()),
+        None => Ok(true.into()),
     }
 }
 
@@ -238,22 +223,100 @@ pub fn get_size<'gc>(
         .coerce_to_string(activation)?;
     let name = name.to_utf8_lossy();
 
-    let mut lso = new_lso(activation, &name, data)?;
     // Flash returns 0 for empty LSOs, but the actual number of bytes (including the header) otherwise
-    if lso.body.is_empty() {
-        Ok(0.into())
-    } else {
-        let bytes = flash_lso::write::write_to_bytes(&mut lso).unwrap_or_default();
-        Ok(bytes.len().into())
+    match crate::avm2::amf::serialize_lso(activation, &name, data)? {
+        Some(bytes) => Ok(bytes.len().into()),
+        None => Ok(0.into()),
     }
 }
 
 pub fn close<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.net.SharedObject", "close");
+    let net_connections = &mut activation.context.net_connections;
+    if net_connections.is_shared_object_connected(this) {
+        net_connections.close_shared_object(this);
+    } else {
+        avm2_stub_method!(activation, "flash.net.SharedObject", "close");
+    }
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.connectRemote`, which is called from `SharedObject.connect`
+pub fn connect_remote<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let connection = args.get_object(activation, 0, "myConnection")?;
+    let name = args.get_string(activation, 1)?.to_utf8_lossy().into_owned();
+    let persistent = args.get_bool(2);
+
+    let net_connections = &mut activation.context.net_connections;
+    match net_connections.handle(connection) {
+        Some(handle) => net_connections.connect_shared_object(handle, this, name, persistent),
+        None => avm2_stub_method!(
+            activation,
+            "flash.net.SharedObject",
+            "connect",
+            "without RTMP connection"
+        ),
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.sendChange`, which is called from `SharedObject.setDirty`
+pub fn send_change<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let property = args.get_string(activation, 0)?;
+    let data = this
+        .get_public_property("data", activation)?
+        .coerce_to_object(activation)?;
+
+    // Properties that were deleted are removed from the server too.
+    let value = if data.has_public_property(property, activation) {
+        let value = data.get_public_property(property, activation)?;
+        Some(serialize_value(activation, value).unwrap_or(AmfValue::Undefined))
+    } else {
+        None
+    };
+    activation
+        .context
+        .net_connections
+        .set_shared_object_property(this, property.to_utf8_lossy().into_owned(), value);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SharedObject.send`
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some((handler, arguments)) = args.split_first() else {
+        return Ok(Value::Undefined);
+    };
+    let handler = handler
+        .coerce_to_string(activation)?
+        .to_utf8_lossy()
+        .into_owned();
+    let arguments = arguments
+        .iter()
+        .map(|argument| serialize_value(activation, *argument).unwrap_or(AmfValue::Undefined))
+        .collect();
+
+    activation
+        .context
+        .net_connections
+        .send_shared_object_message(this, handler, arguments);
+
     Ok(Value::Undefined)
 }
 
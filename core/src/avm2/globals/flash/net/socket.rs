@@ -1,3 +1,4 @@
+use crate::avm2::amf::{Amf3Reader, Amf3Writer};
 use crate::avm2::bytearray::{Endian, ObjectEncoding};
 use crate::avm2::error::{io_error, make_error_2008, security_error};
 pub use crate::avm2::object::socket_allocator;
@@ -8,7 +9,6 @@ use crate::context::UpdateContext;
 use encoding_rs::Encoding;
 use encoding_rs::UTF_8;
 use flash_lso::amf0::read::AMF0Decoder;
-use flash_lso::amf3::read::AMF3Decoder;
 use flash_lso::types::{AMFVersion, Element};
 
 macro_rules! assert_socket_open {
@@ -108,6 +108,18 @@ pub fn get_bytes_available<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn get_bytes_pending<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(socket) = this.as_socket() {
+        return Ok(socket.write_buffer().len().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn get_endian<'gc>(
     _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
@@ -364,30 +376,20 @@ pub fn read_object<'gc>(
     if let Some(socket) = this.as_socket() {
         assert_socket_open!(activation, socket);
 
-        let mut bytes = socket.read_buffer();
+        if socket.object_encoding() == ObjectEncoding::Amf3 {
+            let mut reader = Amf3Reader::new(this);
+            let value = reader.read_value(activation)?;
+            reader.commit();
+            return Ok(value);
+        }
 
-        let (bytes_left, value) = match socket.object_encoding() {
-            ObjectEncoding::Amf0 => {
-                let mut decoder = AMF0Decoder::default();
-                let (extra, amf) = decoder
-                    .parse_single_element(&bytes)
-                    .map_err(|_| "Error: Invalid object")?;
-                (
-                    extra.len(),
-                    crate::avm2::amf::deserialize_value(activation, &amf)?,
-                )
-            }
-            ObjectEncoding::Amf3 => {
-                let mut decoder = AMF3Decoder::default();
-                let (extra, amf) = decoder
-                    .parse_single_element(&bytes)
-                    .map_err(|_| "Error: Invalid object")?;
-                (
-                    extra.len(),
-                    crate::avm2::amf::deserialize_value(activation, &amf)?,
-                )
-            }
-        };
+        let mut bytes = socket.read_buffer();
+        let mut decoder = AMF0Decoder::default();
+        let (extra, amf) = decoder
+            .parse_single_element(&bytes)
+            .map_err(|_| "Error: Invalid object")?;
+        let bytes_left = extra.len();
+        let value = crate::avm2::amf::deserialize_value(activation, &amf)?;
 
         let len = bytes.len();
         let _ = bytes.drain(..(len - bytes_left));
@@ -644,21 +646,18 @@ pub fn write_object<'gc>(
         assert_socket_open!(activation, socket);
 
         let obj = args.get_value(0);
-        let amf_version = match socket.object_encoding() {
-            ObjectEncoding::Amf0 => AMFVersion::AMF0,
-            ObjectEncoding::Amf3 => AMFVersion::AMF3,
-        };
-        if let Some(amf) = crate::avm2::amf::serialize_value(activation, obj, amf_version) {
+        if socket.object_encoding() == ObjectEncoding::Amf3 {
+            let mut writer = Amf3Writer::new(this);
+            writer.write_value(activation, obj)?;
+            writer.flush(activation)?;
+        } else if let Some(amf) = crate::avm2::amf::serialize_value(activation, obj) {
             let element = Element::new("", amf);
-            let mut lso = flash_lso::types::Lso::new(vec![element], "", amf_version);
+            let mut lso = flash_lso::types::Lso::new(vec![element], "", AMFVersion::AMF0);
             let bytes = flash_lso::write::write_to_bytes(&mut lso)
                 .map_err(|_| "Failed to serialize object")?;
             // This is kind of hacky: We need to strip out the header and any padding so that we only write
             // the value. In the future, there should be a method to do this in the flash_lso crate.
-            let element_padding = match amf_version {
-                AMFVersion::AMF0 => 8,
-                AMFVersion::AMF3 => 7,
-            };
+            let element_padding = 8;
             socket.write_bytes(
                 &bytes[flash_lso::write::header_length(&lso.header) + element_padding
                     ..bytes.len() - 1],
@@ -731,7 +730,7 @@ pub fn write_utf_bytes<'gc>(
     Ok(Value::Undefined)
 }
 
-fn invalid_socket_error<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
+pub fn invalid_socket_error<'gc>(activation: &mut Activation<'_, 'gc>) -> Error<'gc> {
     match io_error(
         activation,
         "Error #2002: Operation attempted on invalid socket.",
@@ -0,0 +1,33 @@
+//! `flash.net.URLStream` native function definitions
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::display::loader::request_from_url_request;
+use crate::avm2::object::TObject;
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Multiname, Object};
+
+/// Native function definition for `URLStream.loadNative`
+pub fn load_native<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url_request = args.get_object(activation, 0, "request")?;
+    let request = request_from_url_request(activation, url_request)?;
+
+    // `load` has just given the stream a fresh buffer, which this load appends to.
+    let ns = activation.avm2().flash_net_internal;
+    let buffer = this
+        .get_property(&Multiname::new(ns, "_buffer"), activation)?
+        .coerce_to_object(activation)?;
+
+    let future = activation.context.load_manager.load_data_into_url_stream(
+        activation.context.player.clone(),
+        this,
+        buffer,
+        request,
+    );
+    activation.context.navigator.spawn_future(future);
+    Ok(Value::Undefined)
+}
@@ -1,6 +1,103 @@
 package flash.printing {
+    import flash.display.BitmapData;
+    import flash.display.Sprite;
     import flash.events.EventDispatcher;
+    import flash.geom.Matrix;
+    import flash.geom.Rectangle;
+    import __ruffle__.stub_method;
 
     public class PrintJob extends EventDispatcher {
+        // Pages are rasterized at this resolution, in pixels per inch.
+        private static const RESOLUTION:Number = 300;
+
+        private static const POINTS_PER_INCH:Number = 72;
+
+        // Without a print dialog, pages are US Letter with quarter inch margins.
+        private static const PAPER_WIDTH:int = 612;
+        private static const PAPER_HEIGHT:int = 792;
+        private static const MARGIN:int = 18;
+
+        private var _started:Boolean = false;
+        private var _pages:Array = [];
+
+        public function PrintJob() {
+        }
+
+        public static native function get isSupported():Boolean;
+
+        public function get paperWidth():int {
+            return PAPER_WIDTH;
+        }
+
+        public function get paperHeight():int {
+            return PAPER_HEIGHT;
+        }
+
+        public function get pageWidth():int {
+            return this._started ? PAPER_WIDTH - MARGIN * 2 : 0;
+        }
+
+        public function get pageHeight():int {
+            return this._started ? PAPER_HEIGHT - MARGIN * 2 : 0;
+        }
+
+        public function get orientation():String {
+            return PrintJobOrientation.PORTRAIT;
+        }
+
+        public function get maxPixelsPerInch():Number {
+            return RESOLUTION;
+        }
+
+        public function start():Boolean {
+            this._started = PrintJob.isSupported;
+            this._pages = [];
+            return this._started;
+        }
+
+        public function addPage(sprite:Sprite, printArea:Rectangle = null, options:PrintJobOptions = null, frameNum:int = 0):void {
+            if (!this._started) {
+                throw new Error("Error #2057: The page could not be added to the print job.", 2057);
+            }
+            if (frameNum != 0) {
+                stub_method("flash.printing.PrintJob", "addPage", "with frameNum");
+            }
+
+            var area:Rectangle = printArea ? printArea : sprite.getBounds(sprite);
+            var scale:Number = RESOLUTION / POINTS_PER_INCH;
+
+            // The area is printed at one point per pixel from the top left corner of the page,
+            // and anything that doesn't fit is cut off.
+            var matrix:Matrix = new Matrix();
+            matrix.translate(MARGIN - area.x, MARGIN - area.y);
+            matrix.scale(scale, scale);
+            var clip:Rectangle = new Rectangle(
+                MARGIN * scale,
+                MARGIN * scale,
+                Math.min(area.width, this.pageWidth) * scale,
+                Math.min(area.height, this.pageHeight) * scale
+            );
+
+            var page:BitmapData = new BitmapData(Math.ceil(PAPER_WIDTH * scale), Math.ceil(PAPER_HEIGHT * scale), false, 0xFFFFFF);
+            page.draw(sprite, matrix, null, null, clip, true);
+            this._pages.push(page);
+        }
+
+        public function send():void {
+            if (this._started && this._pages.length > 0) {
+                this.sendPages(this._pages, PAPER_WIDTH, PAPER_HEIGHT);
+            }
+            this.terminate();
+        }
+
+        public function terminate():void {
+            for each (var page:BitmapData in this._pages) {
+                page.dispose();
+            }
+            this._pages = [];
+            this._started = false;
+        }
+
+        private native function sendPages(pages:Array, paperWidth:Number, paperHeight:Number):void;
     }
-}
\ No newline at end of file
+}
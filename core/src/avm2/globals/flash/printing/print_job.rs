@@ -0,0 +1,48 @@
+//! `flash.printing.PrintJob` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::printing::PrintPage;
+
+/// Implements `PrintJob.isSupported`
+pub fn get_is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.can_print().into())
+}
+
+/// Implements `PrintJob.sendPages`, which prints the pages rasterized by `addPage`
+pub fn send_pages<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let pages = args.get_object(activation, 0, "pages")?;
+    let width = args.get_f64(activation, 1)?;
+    let height = args.get_f64(activation, 2)?;
+
+    let bitmap_datas: Vec<_> = match pages.as_array_storage() {
+        Some(pages) => pages
+            .iter()
+            .flatten()
+            .filter_map(|page| page.as_object()?.as_bitmap_data())
+            .collect(),
+        None => return Ok(Value::Undefined),
+    };
+    let pages = bitmap_datas
+        .into_iter()
+        .map(|bitmap_data| PrintPage {
+            image: bitmap_data.sync().read().to_unmultiplied_bitmap(),
+            width,
+            height,
+        })
+        .collect();
+    activation.context.ui.print(pages);
+
+    Ok(Value::Undefined)
+}
@@ -1,26 +1,15 @@
 package flash.sampler {
-    import __ruffle__.stub_method;
+    public native function getSize(o:*):Number;
 
-    public function getSize(param1:*):Number {
-        stub_method("flash.sampler", "getSize");
-        return 0;
-    }
+    public native function clearSamples():void;
 
-    public function clearSamples():void {
-        stub_method("flash.sampler", "clearSamples");
-    }
+    public native function startSampling():void;
 
-    public function startSampling():void {
-        stub_method("flash.sampler", "startSampling");
-    }
+    public native function stopSampling():void;
 
-    public function stopSampling():void {
-        stub_method("flash.sampler", "stopSampling");
-    }
-    
-    public function getSamples():Object {
-        stub_method("flash.sampler", "getSamples");
-        return {};
-    }
-}
+    public native function pauseSampling():void;
+
+    public native function getSampleCount():Number;
 
+    public native function getSamples():Object;
+}
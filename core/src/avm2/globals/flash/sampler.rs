@@ -0,0 +1,105 @@
+//! `flash.sampler` namespace
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::sampler::value_size;
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Multiname};
+
+pub mod new_object_sample;
+
+/// Implements `flash.sampler.getSize`
+pub fn get_size<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let value = args.get(0).copied().unwrap_or(Value::Undefined);
+    Ok((value_size(value) as f64).into())
+}
+
+/// Implements `flash.sampler.startSampling`
+pub fn start_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler_mut().start_sampling();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.stopSampling`
+pub fn stop_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler_mut().stop_sampling();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.pauseSampling`
+pub fn pause_sampling<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler_mut().pause_sampling();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.clearSamples`
+pub fn clear_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation.avm2().sampler_mut().clear_samples();
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.getSampleCount`
+pub fn get_sample_count<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok((activation.avm2().sampler().sample_count() as f64).into())
+}
+
+/// Implements `flash.sampler.getSamples`
+///
+/// Only object allocations are sampled, so every sample is a `NewObjectSample`.
+pub fn get_samples<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let allocations = activation.avm2().sampler_mut().take_samples();
+    let sample_class = activation.avm2().classes().newobjectsample;
+    let public = activation.avm2().public_namespace;
+
+    let mut samples = Vec::with_capacity(allocations.len());
+    for allocation in allocations {
+        let mut sample = sample_class.construct(activation, &[])?;
+        sample.init_property(
+            &Multiname::new(public, "time"),
+            allocation.time.into(),
+            activation,
+        )?;
+        sample.init_property(
+            &Multiname::new(public, "id"),
+            allocation.id.into(),
+            activation,
+        )?;
+        sample.init_property(
+            &Multiname::new(public, "type"),
+            allocation.class.into(),
+            activation,
+        )?;
+        samples.push(sample.into());
+    }
+
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_args(&samples))?.into())
+}
@@ -0,0 +1,43 @@
+//! `flash.sampler.NewObjectSample` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Multiname};
+
+/// Look up the sample ID of a `NewObjectSample`.
+fn sample_id<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+) -> Result<f64, Error<'gc>> {
+    let name = Multiname::new(activation.avm2().public_namespace, "id");
+    this.get_property(&name, activation)?
+        .coerce_to_number(activation)
+}
+
+/// Implements `NewObjectSample.object`
+///
+/// This is `undefined` once the object has been garbage collected.
+pub fn get_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = sample_id(activation, this)?;
+    let mc = activation.context.gc_context;
+    Ok(activation
+        .avm2()
+        .sampler()
+        .object(mc, id)
+        .map_or(Value::Undefined, Value::Object))
+}
+
+/// Implements `NewObjectSample.size`
+pub fn get_size<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let id = sample_id(activation, this)?;
+    Ok(activation.avm2().sampler().size(id).unwrap_or(0.0).into())
+}
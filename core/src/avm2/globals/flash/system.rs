@@ -5,6 +5,7 @@ pub mod application_domain;
 pub mod capabilities;
 pub mod security;
 pub mod system;
+pub mod worker;
 
 use crate::avm2::activation::Activation;
 use crate::avm2::object::Object;
@@ -16,10 +16,7 @@ package flash.system
         public static var _conversionMode: String = "ALPHANUMERIC_HALF";
 
         // Indicates whether the system IME is enabled (true) or disabled (false).
-        public static var _enabled: Boolean;
-
-        // The isSupported property is set to true if the IME class is available on the current platform, otherwise it is set to false.
-        private static var _isSupported: Boolean;
+        public static var _enabled: Boolean = true;
 
         // Causes the runtime to abandon any composition that is in progress.
         public static function compositionAbandoned():void
@@ -45,20 +42,18 @@ package flash.system
             stub_method("flash.system.IME", "setCompositionString");
         }
 
-        public function get isSupported() : Boolean
+        public static function get isSupported() : Boolean
         {
-            return _isSupported;
+            return true;
         }
 
         public static function get enabled():Boolean
         {
-            stub_getter("flash.system.IME", "enabled");
             return _enabled;
         }
 
         public static function set enabled(value:Boolean):void
         {
-            stub_setter("flash.system.IME", "enabled");
             _enabled = value;
         }
 
@@ -0,0 +1,96 @@
+package flash.system {
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.utils.ByteArray;
+    import flash.utils.setTimeout;
+
+    // Workers run cooperatively, so a receiver can't wait for a message to be sent:
+    // `receive(true)` and sending to a full queue don't block.
+    public final class MessageChannel extends EventDispatcher {
+        private static var _constructing:Boolean = false;
+
+        private var _messages:Array = [];
+        private var _state:String = MessageChannelState.OPEN;
+
+        public function MessageChannel() {
+            if (!_constructing) {
+                throw new ArgumentError("Error #2012: MessageChannel$ class cannot be instantiated.", 2012);
+            }
+        }
+
+        internal static function create():MessageChannel {
+            _constructing = true;
+            var channel:MessageChannel = new MessageChannel();
+            _constructing = false;
+            return channel;
+        }
+
+        // Copies a value passed between workers. Shareable byte arrays, channels and workers
+        // are shared, and everything else is copied with AMF serialization.
+        internal static function copyMessage(value:*):* {
+            if (value is ByteArray && ByteArray(value).shareable) {
+                return value;
+            }
+            if (value is MessageChannel || value is Worker) {
+                return value;
+            }
+            if (value == null || typeof value != "object") {
+                return value;
+            }
+
+            var bytes:ByteArray = new ByteArray();
+            bytes.writeObject(value);
+            bytes.position = 0;
+            return bytes.readObject();
+        }
+
+        public function get messageAvailable():Boolean {
+            return this._messages.length > 0;
+        }
+
+        public function get state():String {
+            return this._state;
+        }
+
+        public function send(arg:*, queueLimit:int = -1):void {
+            if (this._state != MessageChannelState.OPEN) {
+                return;
+            }
+            if (queueLimit >= 0 && this._messages.length >= queueLimit) {
+                return;
+            }
+
+            this._messages.push(copyMessage(arg));
+            setTimeout(this.dispatchEvent, 0, new Event(Event.CHANNEL_MESSAGE));
+        }
+
+        public function receive(blockUntilReceived:Boolean = false):* {
+            if (this._messages.length == 0) {
+                return null;
+            }
+
+            var message:* = this._messages.shift();
+            if (this._state == MessageChannelState.CLOSING && this._messages.length == 0) {
+                this.setState(MessageChannelState.CLOSED);
+            }
+            return message;
+        }
+
+        public function close():void {
+            if (this._state != MessageChannelState.OPEN) {
+                return;
+            }
+
+            if (this._messages.length > 0) {
+                this.setState(MessageChannelState.CLOSING);
+            } else {
+                this.setState(MessageChannelState.CLOSED);
+            }
+        }
+
+        private function setState(state:String):void {
+            this._state = state;
+            this.dispatchEvent(new Event(Event.CHANNEL_STATE));
+        }
+    }
+}
@@ -12,6 +12,15 @@ package flash.system {
 
         public static native function setClipboard(string:String): void;
 
+        private static var _ime:IME = null;
+
+        public static function get ime():IME {
+            if (_ime == null) {
+                _ime = new IME();
+            }
+            return _ime;
+        }
+
         public static function disposeXML(node:XML):void {
             stub_method("flash.system.System", "disposeXML");
         }
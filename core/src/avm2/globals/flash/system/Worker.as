@@ -1,9 +1,109 @@
 package flash.system {
-    public final class Worker {
-        public function Worker() {}
-        
+    import flash.display.Loader;
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+    import flash.utils.ByteArray;
+
+    // Background workers run cooperatively in the same player as the primordial worker,
+    // with the code of each one loaded into its own application domain.
+    public final class Worker extends EventDispatcher {
+        private static var _primordial:Worker;
+        private static var _constructing:Boolean = false;
+
+        // All background workers created with `WorkerDomain.createWorker`.
+        internal static var _workers:Array = [];
+
+        // The SWF that this worker runs, or `null` for the primordial worker.
+        private var _swf:ByteArray;
+
+        // The application domain that the code of this worker is loaded into.
+        internal var _domain:ApplicationDomain;
+
+        private var _loader:Loader;
+        private var _state:String = WorkerState.NEW;
+        private var _sharedProperties:Object = {};
+
+        public function Worker() {
+            if (!_constructing) {
+                throw new ArgumentError("Error #2012: Worker$ class cannot be instantiated.", 2012);
+            }
+        }
+
+        internal static function create(swf:ByteArray):Worker {
+            _constructing = true;
+            var worker:Worker = new Worker();
+            _constructing = false;
+            worker._swf = swf;
+            return worker;
+        }
+
+        internal static function get primordial():Worker {
+            if (_primordial == null) {
+                _primordial = create(null);
+                _primordial._state = WorkerState.RUNNING;
+            }
+            return _primordial;
+        }
+
         public static function get isSupported():Boolean {
-            return false;
+            return true;
+        }
+
+        public static native function get current():Worker;
+
+        public function get isPrimordial():Boolean {
+            return this._swf == null;
+        }
+
+        public function get state():String {
+            return this._state;
+        }
+
+        public function createMessageChannel(receiver:Worker):MessageChannel {
+            if (receiver == null) {
+                throw new TypeError("Error #2007: Parameter receiver must be non-null.", 2007);
+            }
+            return MessageChannel.create();
+        }
+
+        public function getSharedProperty(key:String):* {
+            return this._sharedProperties[key];
+        }
+
+        public function setSharedProperty(key:String, value:*):void {
+            this._sharedProperties[key] = MessageChannel.copyMessage(value);
+        }
+
+        public function start():void {
+            if (this._state != WorkerState.NEW) {
+                return;
+            }
+
+            this._domain = new ApplicationDomain(null);
+            _workers.push(this);
+
+            var context:LoaderContext = new LoaderContext(false, this._domain);
+            context.allowCodeImport = true;
+            this._loader = new Loader();
+            this._loader.loadBytes(this._swf, context);
+
+            this.setState(WorkerState.RUNNING);
+        }
+
+        public function terminate():Boolean {
+            if (this.isPrimordial || this._state != WorkerState.RUNNING) {
+                return false;
+            }
+
+            this._loader.unload();
+            this._loader = null;
+            this.setState(WorkerState.TERMINATED);
+            return true;
+        }
+
+        private function setState(state:String):void {
+            this._state = state;
+            this.dispatchEvent(new Event(Event.WORKER_STATE));
         }
     }
 }
@@ -0,0 +1,56 @@
+//! `flash.system.Worker` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::{Error, Multiname};
+
+/// Implements `Worker.current`.
+///
+/// Background workers run in the same player as the primordial worker, with the code of
+/// each one loaded into its own application domain. The current worker is the one whose
+/// domain the calling code belongs to.
+pub fn get_current<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let caller_domain = activation
+        .caller_domain()
+        .expect("Missing caller domain in Worker.current");
+    let ns = activation.avm2().flash_system_internal;
+
+    let workers: Vec<Value<'gc>> = this
+        .get_property(&Multiname::new(ns, "_workers"), activation)?
+        .as_object()
+        .and_then(|workers| {
+            workers
+                .as_array_storage()
+                .map(|workers| workers.iter().flatten().collect())
+        })
+        .unwrap_or_default();
+
+    for worker in workers {
+        let Some(worker) = worker.as_object() else {
+            continue;
+        };
+        let Some(worker_domain) = worker
+            .get_property(&Multiname::new(ns, "_domain"), activation)?
+            .as_object()
+            .and_then(|domain| domain.as_application_domain())
+        else {
+            continue;
+        };
+
+        // Code loaded by the worker itself may be in a child domain.
+        let mut domain = Some(caller_domain);
+        while let Some(current) = domain {
+            if current == worker_domain {
+                return Ok(worker.into());
+            }
+            domain = current.parent_domain();
+        }
+    }
+
+    this.get_property(&Multiname::new(ns, "primordial"), activation)
+}
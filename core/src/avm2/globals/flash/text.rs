@@ -1,6 +1,8 @@
 //! `flash.text` namespace
 
+pub mod engine;
 pub mod font;
 pub mod static_text;
+pub mod style_sheet;
 pub mod text_field;
 pub mod text_format;
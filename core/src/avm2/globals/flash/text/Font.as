@@ -1,12 +1,68 @@
 package flash.text {
     public class Font {
-        public static native function enumerateFonts(enumerateDeviceFonts:Boolean = false):Array;
-        public static native function registerFont(font:Class):void;
+        // The font classes registered with `registerFont`, in the order they were registered.
+        private static var _registeredFonts:Array = [];
 
-        public native function get fontName():String;
-        public native function get fontStyle():String;
-        public native function get fontType():String;
+        public static function enumerateFonts(enumerateDeviceFonts:Boolean = false):Array {
+            var fonts:Array = [];
+            for each (var fontClass:Class in _registeredFonts) {
+                fonts.push(new fontClass());
+            }
+            if (enumerateDeviceFonts) {
+                for each (var name:String in getDeviceFontNames()) {
+                    var font:Font = new Font();
+                    font._deviceFontName = name;
+                    fonts.push(font);
+                }
+            }
+            return fonts;
+        }
 
-        public native function hasGlyphs(str:String):Boolean;
+        public static function registerFont(font:Class):void {
+            registerEmbeddedFont(font);
+            if (_registeredFonts.indexOf(font) == -1) {
+                _registeredFonts.push(font);
+            }
+        }
+
+        private static native function registerEmbeddedFont(font:Class):void;
+        private static native function getDeviceFontNames():Array;
+        private static native function deviceFontHasGlyphs(name:String, str:String):Boolean;
+
+        // The name of this font, if it's a device font returned by `enumerateFonts(true)`.
+        private var _deviceFontName:String = null;
+
+        public function get fontName():String {
+            if (this._deviceFontName != null) {
+                return this._deviceFontName;
+            }
+            return this.getEmbeddedFontName();
+        }
+
+        public function get fontStyle():String {
+            if (this._deviceFontName != null) {
+                return FontStyle.REGULAR;
+            }
+            return this.getEmbeddedFontStyle();
+        }
+
+        public function get fontType():String {
+            if (this._deviceFontName != null) {
+                return FontType.DEVICE;
+            }
+            return this.getEmbeddedFontType();
+        }
+
+        public function hasGlyphs(str:String):Boolean {
+            if (this._deviceFontName != null) {
+                return deviceFontHasGlyphs(this._deviceFontName, str);
+            }
+            return this.hasEmbeddedGlyphs(str);
+        }
+
+        private native function getEmbeddedFontName():String;
+        private native function getEmbeddedFontStyle():String;
+        private native function getEmbeddedFontType():String;
+        private native function hasEmbeddedGlyphs(str:String):Boolean;
     }
-}
\ No newline at end of file
+}
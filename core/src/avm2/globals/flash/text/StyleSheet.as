@@ -1,37 +1,64 @@
 package flash.text {
-    
-    import __ruffle__.stub_constructor;
-    import __ruffle__.stub_method;
-    import __ruffle__.stub_getter;
-    
-    public dynamic class StyleSheet {
+    import flash.events.EventDispatcher;
+
+    public dynamic class StyleSheet extends EventDispatcher {
+        // Style objects, by lowercased selector.
+        private var _css:Object = {};
+
         public function StyleSheet() {}
-        
+
         public function get styleNames():Array {
-            stub_getter("flash.text.StyleSheet", "styleNames");
-            return [];
+            var names:Array = [];
+            for (var name:String in this._css) {
+                names.push(name);
+            }
+            return names;
         }
-        
+
         public function clear():void {
-            stub_method("flash.text.StyleSheet", "clear");
+            this._css = {};
         }
-        
+
         public function getStyle(styleName:String):Object {
-            stub_method("flash.text.StyleSheet", "getStyle");
-            return null;
+            return copyStyle(this._css[styleName.toLowerCase()]);
         }
-        
+
         public function parseCSS(CSSText:String):void {
-            stub_method("flash.text.StyleSheet", "parseCSS");
+            var rules:Array = innerParseCSS(CSSText);
+            if (rules == null) {
+                return;
+            }
+            for each (var rule:Array in rules) {
+                var selector:String = rule[0];
+                var style:Object = this._css[selector] || {};
+                for (var property:String in rule[1]) {
+                    style[property] = rule[1][property];
+                }
+                this._css[selector] = style;
+            }
         }
-        
+
         public function setStyle(styleName:String, styleObject:Object):void {
-            stub_method("flash.text.StyleSheet", "setStyle");
+            if (styleObject == null) {
+                delete this._css[styleName.toLowerCase()];
+            } else {
+                this._css[styleName.toLowerCase()] = copyStyle(styleObject);
+            }
         }
-        
-        public function transform(formatObject:Object):TextFormat {
-            stub_method("flash.text.StyleSheet", "transform");
-            return null;
+
+        public native function transform(formatObject:Object):TextFormat;
+
+        private static function copyStyle(style:Object):Object {
+            if (style == null) {
+                return null;
+            }
+            var copy:Object = {};
+            for (var property:String in style) {
+                copy[property] = style[property];
+            }
+            return copy;
         }
+
+        private native function innerParseCSS(css:String):Array;
     }
 }
@@ -1,5 +1,6 @@
 package flash.text {
     import flash.display.InteractiveObject;
+    import flash.geom.Rectangle;
     import __ruffle__.stub_setter;
     import __ruffle__.stub_method;
 
@@ -75,8 +76,19 @@ package flash.text {
         }
         public function set styleSheet(value:StyleSheet):void {
             this._styleSheet = value;
-            stub_setter("flash.text.TextField", "styleSheet");
+            if (value) {
+                var names:Array = value.styleNames;
+                var styles:Array = [];
+                for each (var name:String in names) {
+                    styles.push(value.getStyle(name));
+                }
+                this.setStyleSheet(names, styles);
+            } else {
+                this.setStyleSheet(null, null);
+            }
         }
+
+        private native function setStyleSheet(names:Array, styles:Array):void;
         
         public native function get text():String;
         public native function set text(value:String):void;
@@ -123,7 +135,16 @@ package flash.text {
         public native function get selectionEndIndex(): int;
 
         public native function appendText(text:String):void;
+        public native function getCharBoundaries(charIndex:int):Rectangle;
+        public native function getCharIndexAtPoint(x:Number, y:Number):int;
+        public native function getFirstCharInParagraph(charIndex:int):int;
+        public native function getLineIndexAtPoint(x:Number, y:Number):int;
+        public native function getLineIndexOfChar(charIndex:int):int;
+        public native function getLineLength(lineIndex:int):int;
         public native function getLineMetrics(lineIndex:int):TextLineMetrics;
+        public native function getLineOffset(lineIndex:int):int;
+        public native function getLineText(lineIndex:int):String;
+        public native function getParagraphLength(charIndex:int):int;
         public native function getTextFormat(beginIndex:int = -1, endIndex:int = -1):TextFormat;
         public native function setTextFormat(format:TextFormat, beginIndex:int = -1, endIndex:int = -1):void;
         public native function replaceSelectedText(value:String):void;
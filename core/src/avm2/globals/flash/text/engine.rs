@@ -0,0 +1,3 @@
+//! `flash.text.engine` namespace
+
+pub mod text_line;
@@ -1,8 +1,82 @@
 package flash.text.engine {
     import flash.events.EventDispatcher;
+
     public class ContentElement {
         public static const GRAPHIC_ELEMENT:uint = 65007;
-        
-        public function ContentElement(elementFormat:ElementFormat = null, eventMirror:EventDispatcher = null, textRotation:String = "rotate0") {}
+
+        public var userData:*;
+
+        internal var _elementFormat:ElementFormat;
+        internal var _eventMirror:EventDispatcher;
+        internal var _textRotation:String;
+        internal var _text:String = null;
+        internal var _textBlock:TextBlock = null;
+        internal var _textBlockBeginIndex:int = 0;
+        internal var _groupElement:GroupElement = null;
+
+        public function ContentElement(elementFormat:ElementFormat = null, eventMirror:EventDispatcher = null, textRotation:String = "rotate0") {
+            this._elementFormat = elementFormat;
+            this._eventMirror = eventMirror;
+            this._textRotation = textRotation;
+        }
+
+        public function get elementFormat():ElementFormat {
+            return this._elementFormat;
+        }
+
+        public function set elementFormat(value:ElementFormat):void {
+            this._elementFormat = value;
+        }
+
+        public function get eventMirror():EventDispatcher {
+            return this._eventMirror;
+        }
+
+        public function set eventMirror(value:EventDispatcher):void {
+            this._eventMirror = value;
+        }
+
+        public function get textRotation():String {
+            return this._textRotation;
+        }
+
+        public function set textRotation(value:String):void {
+            this._textRotation = value;
+        }
+
+        public function get text():String {
+            return this._text;
+        }
+
+        public function get rawText():String {
+            return this._text;
+        }
+
+        public function get textBlock():TextBlock {
+            return this._textBlock;
+        }
+
+        public function get textBlockBeginIndex():int {
+            return this._textBlockBeginIndex;
+        }
+
+        public function get groupElement():GroupElement {
+            return this._groupElement;
+        }
+
+        // Attaches this element to a text block, starting at the given index of its text.
+        // Returns the index after the end of this element.
+        internal function attach(textBlock:TextBlock, beginIndex:int):int {
+            this._textBlock = textBlock;
+            this._textBlockBeginIndex = beginIndex;
+            var text:String = this.rawText;
+            return beginIndex + (text != null ? text.length : 0);
+        }
+
+        // Appends the elements that contain text of their own (that is, everything
+        // except groups) to `leaves`.
+        internal function collectLeaves(leaves:Array):void {
+            leaves.push(this);
+        }
     }
 }
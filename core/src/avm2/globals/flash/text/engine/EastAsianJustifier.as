@@ -0,0 +1,18 @@
+package flash.text.engine {
+    public final class EastAsianJustifier extends TextJustifier {
+        public var justificationStyle:String;
+        public var composeTrailingIdeographicSpaces:Boolean = false;
+
+        public function EastAsianJustifier(locale:String = "ja", lineJustification:String = "allButLast",
+                                           justificationStyle:String = "pushInKinsoku") {
+            super(locale, lineJustification);
+            this.justificationStyle = justificationStyle;
+        }
+
+        override public function clone():TextJustifier {
+            var justifier:EastAsianJustifier = new EastAsianJustifier(this.locale, this.lineJustification, this.justificationStyle);
+            justifier.composeTrailingIdeographicSpaces = this.composeTrailingIdeographicSpaces;
+            return justifier;
+        }
+    }
+}
@@ -0,0 +1,102 @@
+package flash.text.engine {
+    import flash.events.EventDispatcher;
+
+    public final class GroupElement extends ContentElement {
+        private var _elements:Vector.<ContentElement>;
+
+        public function GroupElement(elements:Vector.<ContentElement> = null, elementFormat:ElementFormat = null,
+                                     eventMirror:EventDispatcher = null, textRotation:String = "rotate0") {
+            super(elementFormat, eventMirror, textRotation);
+            this.setElements(elements);
+        }
+
+        override public function get text():String {
+            return this.rawText;
+        }
+
+        override public function get rawText():String {
+            var text:String = "";
+            for each (var element:ContentElement in this._elements) {
+                var elementText:String = element.rawText;
+                if (elementText != null) {
+                    text += elementText;
+                }
+            }
+            return text;
+        }
+
+        public function get elementCount():int {
+            return this._elements.length;
+        }
+
+        public function getElementAt(index:int):ContentElement {
+            if (index < 0 || index >= this._elements.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            return this._elements[index];
+        }
+
+        public function getElementIndex(element:ContentElement):int {
+            return this._elements.indexOf(element);
+        }
+
+        public function getElementAtCharIndex(charIndex:int):ContentElement {
+            var index:int = 0;
+            for each (var element:ContentElement in this._elements) {
+                var text:String = element.rawText;
+                var length:int = text != null ? text.length : 0;
+                if (charIndex >= index && charIndex < index + length) {
+                    return element;
+                }
+                index += length;
+            }
+            return null;
+        }
+
+        public function setElements(value:Vector.<ContentElement>):void {
+            for each (var oldElement:ContentElement in this._elements) {
+                oldElement._groupElement = null;
+            }
+            this._elements = value != null ? value.concat() : new Vector.<ContentElement>();
+            for each (var element:ContentElement in this._elements) {
+                if (element._groupElement != null && element._groupElement != this) {
+                    throw new ArgumentError("Error #2175: One or more elements of the content of the GroupElement already has a group element.", 2175);
+                }
+                element._groupElement = this;
+            }
+        }
+
+        public function replaceElements(beginIndex:int, endIndex:int, newElements:Vector.<ContentElement>):Vector.<ContentElement> {
+            if (beginIndex < 0 || endIndex > this._elements.length || beginIndex > endIndex) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            var removed:Vector.<ContentElement> = this._elements.splice(beginIndex, endIndex - beginIndex);
+            for each (var oldElement:ContentElement in removed) {
+                oldElement._groupElement = null;
+            }
+            if (newElements != null) {
+                for (var i:int = 0; i < newElements.length; i++) {
+                    newElements[i]._groupElement = this;
+                    this._elements.splice(beginIndex + i, 0, newElements[i]);
+                }
+            }
+            return removed;
+        }
+
+        override internal function attach(textBlock:TextBlock, beginIndex:int):int {
+            this._textBlock = textBlock;
+            this._textBlockBeginIndex = beginIndex;
+            var index:int = beginIndex;
+            for each (var element:ContentElement in this._elements) {
+                index = element.attach(textBlock, index);
+            }
+            return index;
+        }
+
+        override internal function collectLeaves(leaves:Array):void {
+            for each (var element:ContentElement in this._elements) {
+                element.collectLeaves(leaves);
+            }
+        }
+    }
+}
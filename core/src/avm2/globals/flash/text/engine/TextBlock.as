@@ -0,0 +1,249 @@
+package flash.text.engine {
+    import __ruffle__.stub_method;
+
+    public final class TextBlock {
+        public var userData:*;
+        public var applyNonLinearFontScaling:Boolean;
+        public var baselineFontDescription:FontDescription;
+        public var baselineFontSize:Number;
+        public var baselineZero:String;
+        public var bidiLevel:int;
+        public var lineRotation:String;
+
+        private var _content:ContentElement;
+        private var _tabStops:Vector.<TabStop>;
+        private var _textJustifier:TextJustifier;
+        private var _firstLine:TextLine = null;
+        private var _lastLine:TextLine = null;
+        private var _textLineCreationResult:String = null;
+
+        public function TextBlock(content:ContentElement = null, tabStops:Vector.<TabStop> = null, textJustifier:TextJustifier = null,
+                                  lineRotation:String = "rotate0", baselineZero:String = "roman", bidiLevel:int = 0,
+                                  applyNonLinearFontScaling:Boolean = true, baselineFontDescription:FontDescription = null,
+                                  baselineFontSize:Number = 12.0) {
+            this.content = content;
+            this.tabStops = tabStops;
+            this.textJustifier = textJustifier != null ? textJustifier : new SpaceJustifier();
+            this.lineRotation = lineRotation;
+            this.baselineZero = baselineZero;
+            this.bidiLevel = bidiLevel;
+            this.applyNonLinearFontScaling = applyNonLinearFontScaling;
+            this.baselineFontDescription = baselineFontDescription;
+            this.baselineFontSize = baselineFontSize;
+        }
+
+        public function get content():ContentElement {
+            return this._content;
+        }
+
+        public function set content(value:ContentElement):void {
+            if (this._content != null && this._content != value) {
+                this._content.attach(null, 0);
+            }
+            this._content = value;
+            this.invalidateLines();
+        }
+
+        public function get tabStops():Vector.<TabStop> {
+            return this._tabStops != null ? this._tabStops.concat() : null;
+        }
+
+        public function set tabStops(value:Vector.<TabStop>):void {
+            this._tabStops = value != null ? value.concat() : null;
+        }
+
+        public function get textJustifier():TextJustifier {
+            return this._textJustifier.clone();
+        }
+
+        public function set textJustifier(value:TextJustifier):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter textJustifier must be non-null.", 2007);
+            }
+            this._textJustifier = value.clone();
+        }
+
+        public function get firstLine():TextLine {
+            return this._firstLine;
+        }
+
+        public function get lastLine():TextLine {
+            return this._lastLine;
+        }
+
+        public function get firstInvalidLine():TextLine {
+            for (var line:TextLine = this._firstLine; line != null; line = line._nextLine) {
+                if (line._validity != TextLineValidity.VALID) {
+                    return line;
+                }
+            }
+            return null;
+        }
+
+        public function get textLineCreationResult():String {
+            return this._textLineCreationResult;
+        }
+
+        public function createTextLine(previousLine:TextLine = null, width:Number = 1000000, lineOffset:Number = 0.0,
+                                       fitSomething:Boolean = false):TextLine {
+            return this.layoutLine(null, previousLine, width, fitSomething);
+        }
+
+        public function recreateTextLine(textLine:TextLine, previousLine:TextLine = null, width:Number = 1000000,
+                                         lineOffset:Number = 0.0, fitSomething:Boolean = false):TextLine {
+            if (textLine == null) {
+                throw new TypeError("Error #2007: Parameter textLine must be non-null.", 2007);
+            }
+            if (textLine._textBlock != null && textLine._textBlock != this) {
+                textLine._textBlock.releaseLines(textLine, textLine);
+            }
+            return this.layoutLine(textLine, previousLine, width, fitSomething);
+        }
+
+        public function releaseLines(firstLine:TextLine, lastLine:TextLine):void {
+            if (firstLine == null || lastLine == null) {
+                throw new TypeError("Error #2007: Parameter firstLine must be non-null.", 2007);
+            }
+            if (firstLine._textBlock != this || lastLine._textBlock != this) {
+                throw new ArgumentError("Error #2005: Parameter 0 is of the incorrect type.", 2005);
+            }
+
+            var before:TextLine = firstLine._previousLine;
+            var after:TextLine = lastLine._nextLine;
+            var line:TextLine = firstLine;
+            while (line != null) {
+                var next:TextLine = line == lastLine ? null : line._nextLine;
+                line._textBlock = null;
+                line._previousLine = null;
+                line._nextLine = null;
+                line._validity = TextLineValidity.INVALID;
+                line = next;
+            }
+
+            if (before != null) {
+                before._nextLine = after;
+            } else {
+                this._firstLine = after;
+            }
+            if (after != null) {
+                after._previousLine = before;
+            } else {
+                this._lastLine = before;
+            }
+        }
+
+        public function releaseLineCreationData():void {
+            // No data is kept between calls to `createTextLine`.
+        }
+
+        public function getTextLineAtCharIndex(charIndex:int):TextLine {
+            for (var line:TextLine = this._firstLine; line != null; line = line._nextLine) {
+                if (charIndex >= line._textBlockBeginIndex && charIndex < line._textBlockBeginIndex + line._rawTextLength) {
+                    return line;
+                }
+            }
+            return null;
+        }
+
+        public function findNextAtomBoundary(afterCharIndex:int):int {
+            return afterCharIndex + 1;
+        }
+
+        public function findPreviousAtomBoundary(beforeCharIndex:int):int {
+            return beforeCharIndex - 1;
+        }
+
+        public function findNextWordBoundary(afterCharIndex:int):int {
+            var text:String = this.rawText;
+            var index:int = afterCharIndex + 1;
+            while (index < text.length && !(isSpace(text.charAt(index - 1)) && !isSpace(text.charAt(index)))) {
+                index++;
+            }
+            return Math.min(index, text.length);
+        }
+
+        public function findPreviousWordBoundary(beforeCharIndex:int):int {
+            var text:String = this.rawText;
+            var index:int = beforeCharIndex - 1;
+            while (index > 0 && !(isSpace(text.charAt(index - 1)) && !isSpace(text.charAt(index)))) {
+                index--;
+            }
+            return Math.max(index, 0);
+        }
+
+        public function dump():String {
+            stub_method("flash.text.engine.TextBlock", "dump");
+            return "";
+        }
+
+        private function get rawText():String {
+            var text:String = this._content != null ? this._content.rawText : null;
+            return text != null ? text : "";
+        }
+
+        private static function isSpace(char:String):Boolean {
+            return char == " " || char == "\t" || char == "\n" || char == "\r" || char == "\u2029" || char == "\u3000";
+        }
+
+        private function invalidateLines():void {
+            for (var line:TextLine = this._firstLine; line != null; line = line._nextLine) {
+                line._validity = TextLineValidity.INVALID;
+            }
+        }
+
+        // Lays out the line of text after `previousLine` into `textLine`, or into a new
+        // line if `textLine` is null.
+        private function layoutLine(textLine:TextLine, previousLine:TextLine, width:Number, fitSomething:Boolean):TextLine {
+            if (width < 0 || width > TextLine.MAX_LINE_WIDTH || isNaN(width)) {
+                throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+            }
+
+            var beginIndex:int = 0;
+            if (previousLine != null) {
+                if (previousLine._textBlock != this) {
+                    throw new ArgumentError("Error #2004: One of the parameters is invalid.", 2004);
+                }
+                beginIndex = previousLine._textBlockBeginIndex + previousLine._rawTextLength;
+            }
+
+            var text:String = this.rawText;
+            if (beginIndex >= text.length) {
+                this._textLineCreationResult = TextLineCreationResult.COMPLETE;
+                return null;
+            }
+
+            // Lines after `previousLine` are no longer valid, as the text may now be split differently.
+            var oldNext:TextLine = previousLine != null ? previousLine._nextLine : this._firstLine;
+            if (oldNext != null) {
+                this.releaseLines(oldNext, this._lastLine);
+            }
+
+            this._content.attach(this, 0);
+            var leaves:Array = [];
+            this._content.collectLeaves(leaves);
+
+            if (textLine == null) {
+                textLine = new TextLine();
+            }
+            if (!textLine.layout(text, leaves, beginIndex, width, fitSomething)) {
+                this._textLineCreationResult = TextLineCreationResult.INSUFFICIENT_WIDTH;
+                return null;
+            }
+            textLine._textBlock = this;
+            textLine._textBlockBeginIndex = beginIndex;
+            textLine._specifiedWidth = width;
+            textLine._validity = TextLineValidity.VALID;
+
+            textLine._previousLine = previousLine;
+            if (previousLine != null) {
+                previousLine._nextLine = textLine;
+            } else {
+                this._firstLine = textLine;
+            }
+            this._lastLine = textLine;
+
+            this._textLineCreationResult = TextLineCreationResult.SUCCESS;
+            return textLine;
+        }
+    }
+}
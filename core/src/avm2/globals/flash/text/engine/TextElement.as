@@ -0,0 +1,23 @@
+package flash.text.engine {
+    import flash.events.EventDispatcher;
+
+    public final class TextElement extends ContentElement {
+        public function TextElement(text:String = null, elementFormat:ElementFormat = null, eventMirror:EventDispatcher = null,
+                                    textRotation:String = "rotate0") {
+            super(elementFormat, eventMirror, textRotation);
+            this._text = text;
+        }
+
+        public function set text(value:String):void {
+            this._text = value;
+        }
+
+        public function replaceText(beginIndex:int, endIndex:int, newText:String):void {
+            var text:String = this._text != null ? this._text : "";
+            if (beginIndex < 0 || endIndex > text.length || beginIndex > endIndex) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            this._text = text.substring(0, beginIndex) + (newText != null ? newText : "") + text.substring(endIndex);
+        }
+    }
+}
@@ -0,0 +1,27 @@
+package flash.text.engine {
+    public class TextJustifier {
+        private var _locale:String;
+        public var lineJustification:String;
+
+        public function TextJustifier(locale:String, lineJustification:String) {
+            this._locale = locale;
+            this.lineJustification = lineJustification;
+        }
+
+        public static function getJustifierForLocale(locale:String):TextJustifier {
+            var language:String = locale != null ? locale.substr(0, 2).toLowerCase() : "";
+            if (language == "ja" || language == "zh") {
+                return new EastAsianJustifier(locale);
+            }
+            return new SpaceJustifier(locale);
+        }
+
+        public function get locale():String {
+            return this._locale;
+        }
+
+        public function clone():TextJustifier {
+            return new TextJustifier(this._locale, this.lineJustification);
+        }
+    }
+}
@@ -0,0 +1,325 @@
+package flash.text.engine {
+    import flash.display.DisplayObject;
+    import flash.display.DisplayObjectContainer;
+    import flash.events.EventDispatcher;
+    import flash.geom.Point;
+    import flash.geom.Rectangle;
+    import flash.text.TextField;
+    import flash.text.TextFieldAutoSize;
+    import flash.text.TextLineMetrics;
+    import __ruffle__.stub_method;
+
+    // A line of text created by a `TextBlock`.
+    //
+    // The text is drawn by a `TextField` child, positioned so that the line's origin
+    // is on the baseline of the text.
+    [Ruffle(InstanceAllocator)]
+    public final class TextLine extends DisplayObjectContainer {
+        public static const MAX_LINE_WIDTH:int = 1000000;
+
+        // The size of the gutter that `TextField` leaves around its text.
+        private static const GUTTER:Number = 2;
+
+        public var userData:*;
+
+        internal var _textBlock:TextBlock = null;
+        internal var _textBlockBeginIndex:int = 0;
+        internal var _rawTextLength:int = 0;
+        internal var _specifiedWidth:Number = 0;
+        internal var _validity:String = TextLineValidity.VALID;
+        internal var _previousLine:TextLine = null;
+        internal var _nextLine:TextLine = null;
+
+        private var _field:TextField = null;
+        private var _ascent:Number = 0;
+        private var _descent:Number = 0;
+        private var _textWidth:Number = 0;
+
+        public function get textBlock():TextBlock {
+            return this._textBlock;
+        }
+
+        public function get textBlockBeginIndex():int {
+            return this._textBlockBeginIndex;
+        }
+
+        public function get rawTextLength():int {
+            return this._rawTextLength;
+        }
+
+        public function get atomCount():int {
+            return this._rawTextLength;
+        }
+
+        public function get specifiedWidth():Number {
+            return this._specifiedWidth;
+        }
+
+        public function get previousLine():TextLine {
+            return this._previousLine;
+        }
+
+        public function get nextLine():TextLine {
+            return this._nextLine;
+        }
+
+        public function get validity():String {
+            return this._validity;
+        }
+
+        public function set validity(value:String):void {
+            if (value != TextLineValidity.INVALID && value != TextLineValidity.POSSIBLY_INVALID &&
+                value != TextLineValidity.STATIC && value != TextLineValidity.VALID) {
+                throw new ArgumentError("Error #2008: Parameter validity must be one of the accepted values.", 2008);
+            }
+            this._validity = value;
+        }
+
+        public function get ascent():Number {
+            return this._ascent;
+        }
+
+        public function get descent():Number {
+            return this._descent;
+        }
+
+        public function get totalAscent():Number {
+            return this._ascent;
+        }
+
+        public function get totalDescent():Number {
+            return this._descent;
+        }
+
+        public function get totalHeight():Number {
+            return this._ascent + this._descent;
+        }
+
+        public function get textHeight():Number {
+            return this._ascent + this._descent;
+        }
+
+        public function get textWidth():Number {
+            return this._textWidth;
+        }
+
+        public function get unjustifiedTextWidth():Number {
+            return this._textWidth;
+        }
+
+        public function get hasGraphicElement():Boolean {
+            return false;
+        }
+
+        public function get hasTabs():Boolean {
+            return false;
+        }
+
+        public function get mirrorRegions():Vector.<TextLineMirrorRegion> {
+            return null;
+        }
+
+        public function getMirrorRegion(mirror:EventDispatcher):TextLineMirrorRegion {
+            return null;
+        }
+
+        public function getBaselinePosition(baseline:String):Number {
+            switch (baseline) {
+                case TextBaseline.ROMAN:
+                    return 0;
+                case TextBaseline.ASCENT:
+                case TextBaseline.IDEOGRAPHIC_TOP:
+                    return -this._ascent;
+                case TextBaseline.DESCENT:
+                case TextBaseline.IDEOGRAPHIC_BOTTOM:
+                    return this._descent;
+                case TextBaseline.IDEOGRAPHIC_CENTER:
+                    return (this._descent - this._ascent) / 2;
+                default:
+                    throw new ArgumentError("Error #2008: Parameter baseline must be one of the accepted values.", 2008);
+            }
+        }
+
+        public function getAtomIndexAtCharIndex(charIndex:int):int {
+            var index:int = charIndex - this._textBlockBeginIndex;
+            return index >= 0 && index < this._rawTextLength ? index : -1;
+        }
+
+        public function getAtomTextBlockBeginIndex(atomIndex:int):int {
+            checkAtomIndex(atomIndex);
+            return this._textBlockBeginIndex + atomIndex;
+        }
+
+        public function getAtomTextBlockEndIndex(atomIndex:int):int {
+            checkAtomIndex(atomIndex);
+            return this._textBlockBeginIndex + atomIndex + 1;
+        }
+
+        public function getAtomBounds(atomIndex:int):Rectangle {
+            checkAtomIndex(atomIndex);
+            stub_method("flash.text.engine.TextLine", "getAtomBounds");
+            // Without the positions of individual glyphs, assume that every atom is the same width.
+            var atomWidth:Number = this._rawTextLength > 0 ? this._textWidth / this._rawTextLength : 0;
+            return new Rectangle(atomIndex * atomWidth, -this._ascent, atomWidth, this._ascent + this._descent);
+        }
+
+        public function getAtomCenter(atomIndex:int):Number {
+            var bounds:Rectangle = this.getAtomBounds(atomIndex);
+            return bounds.x + bounds.width / 2;
+        }
+
+        public function getAtomIndexAtPoint(stageX:Number, stageY:Number):int {
+            var point:Point = this.globalToLocal(new Point(stageX, stageY));
+            if (this._rawTextLength == 0 || point.y < -this._ascent || point.y > this._descent ||
+                point.x < 0 || point.x >= this._textWidth) {
+                return -1;
+            }
+            stub_method("flash.text.engine.TextLine", "getAtomIndexAtPoint");
+            return int(point.x / (this._textWidth / this._rawTextLength));
+        }
+
+        public function getAtomBidiLevel(atomIndex:int):int {
+            checkAtomIndex(atomIndex);
+            return 0;
+        }
+
+        public function getAtomTextRotation(atomIndex:int):String {
+            checkAtomIndex(atomIndex);
+            return TextRotation.ROTATE_0;
+        }
+
+        public function getAtomGraphic(atomIndex:int):DisplayObject {
+            checkAtomIndex(atomIndex);
+            return null;
+        }
+
+        public function getAtomWordBoundaryOnLeft(atomIndex:int):Boolean {
+            checkAtomIndex(atomIndex);
+            if (this._textBlock == null || this._textBlock.content == null) {
+                return atomIndex == 0;
+            }
+            var text:String = this._textBlock.content.rawText;
+            var index:int = this._textBlockBeginIndex + atomIndex;
+            return index == 0 || text.charAt(index - 1) == " ";
+        }
+
+        public function flushAtomData():void {
+            // Atom data is computed on demand, so there's nothing to release.
+        }
+
+        public function dump():String {
+            stub_method("flash.text.engine.TextLine", "dump");
+            return "";
+        }
+
+        private function checkAtomIndex(atomIndex:int):void {
+            if (atomIndex < 0 || atomIndex >= this._rawTextLength) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+        }
+
+        private static function isLineBreak(char:String):Boolean {
+            return char == "\n" || char == "\r" || char == "\u2028" || char == "\u2029";
+        }
+
+        // Fills this line with as much of `text` (starting at `beginIndex`) as fits in `width`.
+        // `leaves` are the elements of the text block that provide the formatting of the text.
+        //
+        // Returns false if not even one character could fit, and `fitSomething` is false.
+        internal function layout(text:String, leaves:Array, beginIndex:int, width:Number, fitSomething:Boolean):Boolean {
+            // The line can't go past the next mandatory break.
+            var endIndex:int = text.length;
+            var breakLength:int = 0;
+            for (var i:int = beginIndex; i < text.length; i++) {
+                var char:String = text.charAt(i);
+                if (isLineBreak(char)) {
+                    endIndex = i;
+                    breakLength = char == "\r" && text.charAt(i + 1) == "\n" ? 2 : 1;
+                    break;
+                }
+            }
+
+            if (this._field == null) {
+                this._field = new TextField();
+                this._field.selectable = false;
+                this.addChild(this._field);
+            }
+            var field:TextField = this._field;
+
+            // Let the text field find where the text wraps.
+            field.autoSize = TextFieldAutoSize.NONE;
+            field.multiline = false;
+            field.wordWrap = true;
+            field.width = width + GUTTER * 2;
+            setText(field, text, leaves, beginIndex, endIndex);
+
+            var visibleEnd:int = endIndex;
+            var rawEnd:int = endIndex + breakLength;
+            if (field.numLines > 1) {
+                try {
+                    visibleEnd = rawEnd = beginIndex + field.getLineOffset(1);
+                } catch (e:RangeError) {
+                    // The second line has no text on it.
+                }
+            }
+
+            // Then lay out just the text on this line.
+            field.wordWrap = false;
+            field.autoSize = TextFieldAutoSize.LEFT;
+            setText(field, text, leaves, beginIndex, visibleEnd);
+            if (field.textWidth > width && !fitSomething) {
+                return false;
+            }
+
+            var metrics:TextLineMetrics;
+            if (visibleEnd > beginIndex) {
+                metrics = field.getLineMetrics(0);
+            } else {
+                // Measure the height of an empty line with a space.
+                field.text = " ";
+                metrics = field.getLineMetrics(0);
+                field.text = "";
+            }
+
+            this._rawTextLength = rawEnd - beginIndex;
+            this._ascent = metrics.ascent;
+            this._descent = metrics.descent;
+            this._textWidth = visibleEnd > beginIndex ? field.textWidth : 0;
+            field.x = -GUTTER;
+            field.y = -GUTTER - metrics.ascent;
+            return true;
+        }
+
+        private static function setText(field:TextField, text:String, leaves:Array, beginIndex:int, endIndex:int):void {
+            // Graphic elements aren't drawn, so leave a space in their place.
+            var graphicChar:String = String.fromCharCode(ContentElement.GRAPHIC_ELEMENT);
+            field.text = text.substring(beginIndex, endIndex).split(graphicChar).join(" ");
+
+            var embedFonts:Boolean = false;
+            var alpha:Number = NaN;
+            for each (var leaf:ContentElement in leaves) {
+                var leafText:String = leaf.rawText;
+                if (leafText == null) {
+                    continue;
+                }
+                var start:int = Math.max(leaf._textBlockBeginIndex, beginIndex);
+                var end:int = Math.min(leaf._textBlockBeginIndex + leafText.length, endIndex);
+                if (start >= end) {
+                    continue;
+                }
+
+                var format:ElementFormat = leaf._elementFormat != null ? leaf._elementFormat : new ElementFormat();
+                field.setTextFormat(format.toTextFormat(), start - beginIndex, end - beginIndex);
+                if (format.fontDescription.fontLookup == FontLookup.EMBEDDED_CFF) {
+                    embedFonts = true;
+                }
+                // A text field can only have one alpha, so use that of the first element.
+                if (isNaN(alpha)) {
+                    alpha = format.alpha;
+                }
+            }
+            field.embedFonts = embedFonts;
+            field.alpha = isNaN(alpha) ? 1 : alpha;
+        }
+    }
+}
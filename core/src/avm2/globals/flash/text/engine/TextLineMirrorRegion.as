@@ -0,0 +1,36 @@
+package flash.text.engine {
+    import flash.events.EventDispatcher;
+    import flash.geom.Rectangle;
+
+    public final class TextLineMirrorRegion {
+        internal var _textLine:TextLine;
+        internal var _element:ContentElement;
+        internal var _bounds:Rectangle;
+        internal var _nextRegion:TextLineMirrorRegion;
+        internal var _previousRegion:TextLineMirrorRegion;
+
+        public function get textLine():TextLine {
+            return this._textLine;
+        }
+
+        public function get element():ContentElement {
+            return this._element;
+        }
+
+        public function get mirror():EventDispatcher {
+            return this._element != null ? this._element.eventMirror : null;
+        }
+
+        public function get bounds():Rectangle {
+            return this._bounds != null ? this._bounds.clone() : null;
+        }
+
+        public function get nextRegion():TextLineMirrorRegion {
+            return this._nextRegion;
+        }
+
+        public function get previousRegion():TextLineMirrorRegion {
+            return this._previousRegion;
+        }
+    }
+}
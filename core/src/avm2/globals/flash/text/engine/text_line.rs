@@ -0,0 +1,17 @@
+//! `flash.text.engine.TextLine` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
+use crate::avm2::object::{ClassObject, Object};
+use crate::avm2::Error;
+use crate::display_object::MovieClip;
+
+/// Allocates a `TextLine`, which is a plain container of the text fields that draw its text.
+pub fn text_line_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let movie = activation.context.swf.clone();
+    let display_object = MovieClip::new(movie, activation.context.gc_context).into();
+    initialize_for_allocator(activation, display_object, class)
+}
@@ -1,16 +1,16 @@
 //! `flash.text.Font` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::argument_error;
 use crate::avm2::object::{Object, TObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::{ArrayObject, ArrayStorage, Error};
-use crate::avm2_stub_method;
 use crate::character::Character;
 use crate::string::AvmString;
 
-/// Implements `Font.fontName`
-pub fn get_font_name<'gc>(
+/// Implements `Font.fontName` for embedded fonts
+pub fn get_embedded_font_name<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
@@ -39,8 +39,8 @@ pub fn get_font_name<'gc>(
     Ok(Value::Undefined)
 }
 
-/// Implements `Font.fontStyle`
-pub fn get_font_style<'gc>(
+/// Implements `Font.fontStyle` for embedded fonts
+pub fn get_embedded_font_style<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
@@ -70,8 +70,8 @@ pub fn get_font_style<'gc>(
     Ok(Value::Undefined)
 }
 
-/// Implements `Font.fontType`
-pub fn get_font_type<'gc>(
+/// Implements `Font.fontType` for embedded fonts
+pub fn get_embedded_font_type<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
@@ -83,13 +83,16 @@ pub fn get_font_type<'gc>(
             .avm2_class_registry()
             .class_symbol(this)
     }) {
-        if let Some(Character::Font(_)) = activation
+        if let Some(Character::Font(font)) = activation
             .context
             .library
             .library_for_movie_mut(movie)
             .character_by_id(character_id)
         {
-            //TODO: How do we distinguish between CFF and non-CFF embedded fonts?
+            // Only `DefineFont4` fonts embed a font file; these are always CFF fonts.
+            if font.has_font_face() {
+                return Ok("embeddedCFF".into());
+            }
             return Ok("embedded".into());
         }
     }
@@ -97,8 +100,8 @@ pub fn get_font_type<'gc>(
     Ok(Value::Undefined)
 }
 
-/// Implements `Font.hasGlyphs`
-pub fn has_glyphs<'gc>(
+/// Implements `Font.hasGlyphs` for embedded fonts
+pub fn has_embedded_glyphs<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
@@ -125,22 +128,76 @@ pub fn has_glyphs<'gc>(
     Ok(Value::Undefined)
 }
 
-/// `Font.enumerateFonts`
-pub fn enumerate_fonts<'gc>(
+/// `Font.enumerateFonts` for device fonts: the names of the fonts installed on the system
+pub fn get_device_font_names<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.text.Font", "enumerateFonts");
-    Ok(ArrayObject::from_storage(activation, ArrayStorage::new(0))?.into())
+    let names: Vec<Option<Value<'gc>>> = activation
+        .context
+        .ui
+        .device_font_names()
+        .iter()
+        .map(|name| Some(AvmString::new_utf8(activation.context.gc_context, name).into()))
+        .collect();
+    Ok(ArrayObject::from_storage(activation, ArrayStorage::from_storage(names))?.into())
 }
 
-/// `Font.registerFont`
-pub fn register_font<'gc>(
+/// `Font.hasGlyphs` for device fonts
+pub fn device_font_has_glyphs<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_method!(activation, "flash.text.Font", "registerFont");
-    Ok(Value::Undefined)
+    let name = args.get_string(activation, 0)?;
+    let text = args.get_string(activation, 1)?;
+
+    let context = &mut activation.context;
+    let font = context.library.get_device_font(
+        &name.to_utf8_lossy(),
+        false,
+        false,
+        context.ui,
+        context.gc_context,
+    );
+    Ok(font
+        .map_or(false, |font| font.has_glyphs_for_str(&text))
+        .into())
+}
+
+/// `Font.registerFont`: makes an embedded font available to text in every movie
+pub fn register_embedded_font<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let font_class = args.get_object(activation, 0, "font")?;
+    if let Some((movie, character_id)) = font_class.as_class_object().and_then(|class| {
+        activation
+            .context
+            .library
+            .avm2_class_registry()
+            .class_symbol(class)
+    }) {
+        let font = match activation
+            .context
+            .library
+            .library_for_movie_mut(movie)
+            .character_by_id(character_id)
+        {
+            Some(Character::Font(font)) => Some(*font),
+            _ => None,
+        };
+        if let Some(font) = font {
+            activation.context.library.register_font(font);
+            return Ok(Value::Undefined);
+        }
+    }
+
+    Err(Error::AvmError(argument_error(
+        activation,
+        "Error #1508: The value specified for argument font is invalid.",
+        1508,
+    )?))
 }
@@ -0,0 +1,72 @@
+//! `flash.text.StyleSheet` native methods
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::object::{ArrayObject, Object, TObject, TextFormatObject};
+use crate::avm2::parameters::ParametersExt;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::html::{parse_css, TextFormat, STYLE_PROPERTIES};
+use crate::string::AvmString;
+
+/// Converts a style object into the `TextFormat` it describes.
+pub fn style_to_text_format<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    style: Object<'gc>,
+) -> Result<TextFormat, Error<'gc>> {
+    let mut text_format = TextFormat::default();
+    for &property in STYLE_PROPERTIES {
+        let value = style.get_public_property(property, activation)?;
+        if !matches!(value, Value::Undefined | Value::Null) {
+            let value = value.coerce_to_string(activation)?;
+            text_format.apply_style_property(property, &value.to_utf8_lossy());
+        }
+    }
+    Ok(text_format)
+}
+
+/// Implements `StyleSheet.transform`
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let Some(style) = args.try_get_object(activation, 0) else {
+        return Ok(Value::Null);
+    };
+
+    let text_format = style_to_text_format(activation, style)?;
+    Ok(TextFormatObject::from_text_format(activation, text_format)?.into())
+}
+
+/// Parses CSS text into an array of `[selector, style object]` pairs, or `null` if it's invalid.
+pub fn inner_parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let css = args.get_string(activation, 0)?;
+    let Some(rules) = parse_css(&css.to_utf8_lossy()) else {
+        return Ok(Value::Null);
+    };
+
+    let mut rule_objects = Vec::with_capacity(rules.len());
+    for (selector, properties) in rules {
+        let mut style = activation
+            .avm2()
+            .classes()
+            .object
+            .construct(activation, &[])?;
+        for (name, value) in properties {
+            let name = AvmString::new_utf8(activation.context.gc_context, name);
+            let value = AvmString::new_utf8(activation.context.gc_context, value);
+            style.set_public_property(name, value.into(), activation)?;
+        }
+
+        let selector = AvmString::new_utf8(activation.context.gc_context, selector);
+        let rule = ArrayStorage::from_args(&[selector.into(), style.into()]);
+        rule_objects.push(ArrayObject::from_storage(activation, rule)?);
+    }
+
+    Ok(ArrayObject::from_storage(activation, rule_objects.into_iter().collect())?.into())
+}
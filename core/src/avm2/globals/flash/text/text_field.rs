@@ -1,16 +1,18 @@
 //! `flash.text.TextField` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::error::range_error;
 use crate::avm2::globals::flash::display::display_object::initialize_for_allocator;
+use crate::avm2::globals::flash::text::style_sheet::style_to_text_format;
 use crate::avm2::object::{ClassObject, Object, TObject, TextFormatObject};
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::display_object::{AutoSizeMode, EditText, TDisplayObject, TextSelection};
-use crate::html::TextFormat;
-use crate::string::AvmString;
+use crate::html::{StyleSheet, TextFormat};
+use crate::string::{AvmString, WStr};
 use crate::{avm2_stub_getter, avm2_stub_setter};
-use swf::Color;
+use swf::{Color, Point};
 
 pub fn text_field_allocator<'gc>(
     class: ClassObject<'gc>,
@@ -1302,21 +1304,73 @@ pub fn set_max_chars<'gc>(
     Ok(Value::Undefined)
 }
 
-pub fn get_mouse_wheel_enabled<'gc>(
+pub fn set_style_sheet<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let (Some(names), Some(styles)) = (
+            args.try_get_object(activation, 0),
+            args.try_get_object(activation, 1),
+        ) else {
+            this.set_style_sheet(None, &mut activation.context);
+            return Ok(Value::Undefined);
+        };
+
+        let length = names.as_array_storage().map_or(0, |names| names.length());
+        let mut style_sheet = StyleSheet::new();
+        for i in 0..length {
+            let index = AvmString::new_utf8(activation.context.gc_context, i.to_string());
+            let name = names
+                .get_public_property(index, activation)?
+                .coerce_to_string(activation)?;
+            if let Some(style) = styles.get_public_property(index, activation)?.as_object() {
+                let text_format = style_to_text_format(activation, style)?;
+                style_sheet.set_style(&name, text_format);
+            }
+        }
+        this.set_style_sheet(Some(style_sheet), &mut activation.context);
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn get_mouse_wheel_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.text.TextField", "mouseWheelEnabled");
-    Ok(true.into())
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.is_mouse_wheel_enabled().into());
+    }
+
+    Ok(Value::Undefined)
 }
 
 pub fn set_mouse_wheel_enabled<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_setter!(activation, "flash.text.TextField", "mouseWheelEnabled");
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let is_mouse_wheel_enabled = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+        this.set_mouse_wheel_enabled(is_mouse_wheel_enabled, &mut activation.context);
+    }
+
     Ok(Value::Undefined)
 }
 
@@ -1337,3 +1391,249 @@ pub fn set_restrict<'gc>(
     avm2_stub_setter!(activation, "flash.text.TextField", "restrict");
     Ok(Value::Undefined)
 }
+
+/// Implements `TextField.getLineOffset`
+pub fn get_line_offset<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let line_num = args.get_i32(activation, 0)?;
+        let offset = usize::try_from(line_num)
+            .ok()
+            .and_then(|line| this.line_offset(line));
+        return match offset {
+            Some(offset) => Ok(offset.into()),
+            None => Err(Error::AvmError(range_error(
+                activation,
+                "Error #2006: The supplied index is out of bounds.",
+                2006,
+            )?)),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.getLineLength`
+pub fn get_line_length<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let line_num = args.get_i32(activation, 0)?;
+        let Some(line) = usize::try_from(line_num).ok() else {
+            return Err(Error::AvmError(range_error(
+                activation,
+                "Error #2006: The supplied index is out of bounds.",
+                2006,
+            )?));
+        };
+        let Some(offset) = this.line_offset(line) else {
+            return Err(Error::AvmError(range_error(
+                activation,
+                "Error #2006: The supplied index is out of bounds.",
+                2006,
+            )?));
+        };
+        // A line runs until the next one starts, including any trailing whitespace.
+        let end = this
+            .line_offset(line + 1)
+            .unwrap_or_else(|| this.text_length());
+        return Ok(end.saturating_sub(offset).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.getLineText`
+pub fn get_line_text<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let line_num = args.get_i32(activation, 0)?;
+        let Some(line) = usize::try_from(line_num).ok() else {
+            return Err(Error::AvmError(range_error(
+                activation,
+                "Error #2006: The supplied index is out of bounds.",
+                2006,
+            )?));
+        };
+        let Some(offset) = this.line_offset(line) else {
+            return Err(Error::AvmError(range_error(
+                activation,
+                "Error #2006: The supplied index is out of bounds.",
+                2006,
+            )?));
+        };
+        let text = this.text();
+        let end = this.line_offset(line + 1).unwrap_or(text.len());
+        let line_text = text.slice(offset..end).unwrap_or_else(WStr::empty);
+        return Ok(AvmString::new(activation.context.gc_context, line_text).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.getLineIndexOfChar`
+pub fn get_line_index_of_char<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let char_index = args.get_i32(activation, 0)?;
+        let line = usize::try_from(char_index)
+            .ok()
+            .and_then(|char_index| this.line_index_of_char(char_index));
+        return Ok(line.map_or(-1, |line| line as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.getLineIndexAtPoint`
+pub fn get_line_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let x = args.get_f64(activation, 0)?;
+        let y = args.get_f64(activation, 1)?;
+        let line = this.line_index_at_point(Point::from_pixels(x, y));
+        return Ok(line.map_or(-1, |line| line as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.getCharIndexAtPoint`
+pub fn get_char_index_at_point<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let x = args.get_f64(activation, 0)?;
+        let y = args.get_f64(activation, 1)?;
+        let index = this.char_index_at_point(Point::from_pixels(x, y));
+        return Ok(index.map_or(-1, |index| index as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.getCharBoundaries`
+pub fn get_char_boundaries<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let char_index = args.get_i32(activation, 0)?;
+        let Some(bounds) = usize::try_from(char_index)
+            .ok()
+            .and_then(|char_index| this.char_bounds(char_index))
+        else {
+            return Ok(Value::Null);
+        };
+
+        let rectangle = activation.avm2().classes().rectangle.construct(
+            activation,
+            &[
+                bounds.x_min.to_pixels().into(),
+                bounds.y_min.to_pixels().into(),
+                bounds.width().to_pixels().into(),
+                bounds.height().to_pixels().into(),
+            ],
+        )?;
+        return Ok(rectangle.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Returns the range of the paragraph containing a position in the text, including the
+/// line break at its end, or None if the position is outside of the text.
+fn paragraph_range(text: &WStr, position: usize) -> Option<std::ops::Range<usize>> {
+    if position >= text.len() {
+        return None;
+    }
+    let is_break = |c: u16| c == u16::from(b'\r') || c == u16::from(b'\n');
+    let start = (0..position)
+        .rev()
+        .find(|&i| is_break(text.at(i)))
+        .map_or(0, |i| i + 1);
+    let end = (position..text.len())
+        .find(|&i| is_break(text.at(i)))
+        .map_or(text.len(), |i| i + 1);
+    Some(start..end)
+}
+
+/// Implements `TextField.getFirstCharInParagraph`
+pub fn get_first_char_in_paragraph<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let char_index = args.get_i32(activation, 0)?;
+        let text = this.text();
+        let range = usize::try_from(char_index)
+            .ok()
+            .and_then(|char_index| paragraph_range(&text, char_index));
+        return Ok(range.map_or(-1, |range| range.start as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `TextField.getParagraphLength`
+pub fn get_paragraph_length<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(this) = this
+        .as_display_object()
+        .and_then(|this| this.as_edit_text())
+    {
+        let char_index = args.get_i32(activation, 0)?;
+        let text = this.text();
+        let range = usize::try_from(char_index)
+            .ok()
+            .and_then(|char_index| paragraph_range(&text, char_index));
+        return Ok(range.map_or(-1, |range| range.len() as i32).into());
+    }
+
+    Ok(Value::Undefined)
+}
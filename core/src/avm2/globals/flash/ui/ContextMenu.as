@@ -1,7 +1,6 @@
 package flash.ui
 {
     import flash.display.NativeMenu;
-    import __ruffle__.stub_setter;
 
     public final class ContextMenu extends NativeMenu
     {
@@ -23,13 +22,30 @@ package flash.ui
 
         public function set builtInItems(value:ContextMenuBuiltInItems):void {
             this._builtInItems = value;
-            stub_setter("flash.ui.ContextMenu", "builtInItems");
+        }
+
+        public function clone(): ContextMenu
+        {
+            var menu: ContextMenu = new ContextMenu();
+            for each (var item: ContextMenuItem in this.customItems) {
+                menu.customItems.push(item.clone());
+            }
+
+            var builtInItems: ContextMenuBuiltInItems = menu.builtInItems;
+            builtInItems.forwardAndBack = this._builtInItems.forwardAndBack;
+            builtInItems.loop = this._builtInItems.loop;
+            builtInItems.play = this._builtInItems.play;
+            builtInItems.print = this._builtInItems.print;
+            builtInItems.quality = this._builtInItems.quality;
+            builtInItems.rewind = this._builtInItems.rewind;
+            builtInItems.save = this._builtInItems.save;
+            builtInItems.zoom = this._builtInItems.zoom;
+            return menu;
         }
 
         public static function get isSupported() : Boolean
         {
-            // TODO: return true when implementation actually affects the context menu
-            return false;
+            return true;
         }
     }
 }
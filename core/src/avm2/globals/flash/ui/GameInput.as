@@ -1,13 +1,79 @@
 package flash.ui {
     import flash.events.EventDispatcher;
+    import flash.events.GameInputEvent;
 
     public final class GameInput extends EventDispatcher {
+        // Every `GameInput` that was created, which are told about devices being added and removed.
+        internal static var _instances:Array = [];
+
+        // The connected devices, in the order that they were connected.
+        internal static var _devices:Array = [];
+
+        public function GameInput() {
+            _instances.push(this);
+        }
+
         public static function get isSupported():Boolean {
-            return false;
+            return true;
         }
 
         public static function get numDevices():int {
-            return 0;
+            return _devices.length;
+        }
+
+        public static function getDeviceAt(index:int):GameInputDevice {
+            if (index < 0 || index >= _devices.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            return _devices[index];
+        }
+
+        // Called by the player when a gamepad is connected.
+        internal static function _addDevice(id:uint, name:String, numButtons:int, numAxes:int):void {
+            var device:GameInputDevice = new GameInputDevice();
+            device._gamepadId = id;
+            device._id = "GAMEPAD_" + id;
+            device._name = name;
+            for (var i:int = 0; i < numButtons; i++) {
+                device._controls.push(new GameInputControl(device, "BUTTON_" + i, 0, 1));
+            }
+            for (i = 0; i < numAxes; i++) {
+                device._controls.push(new GameInputControl(device, "AXIS_" + i, -1, 1));
+            }
+            _devices.push(device);
+            dispatchToInstances(GameInputEvent.DEVICE_ADDED, device);
+        }
+
+        // Called by the player when a gamepad is disconnected.
+        internal static function _removeDevice(id:uint):void {
+            var device:GameInputDevice = findDevice(id);
+            if (device != null) {
+                _devices.splice(_devices.indexOf(device), 1);
+                dispatchToInstances(GameInputEvent.DEVICE_REMOVED, device);
+            }
+        }
+
+        // Called by the player when a button or stick of a gamepad moves.
+        internal static function _setControlValue(id:uint, index:int, value:Number):void {
+            var device:GameInputDevice = findDevice(id);
+            if (device != null && device.enabled) {
+                device._controls[index]._setValue(value);
+            }
+        }
+
+        private static function findDevice(id:uint):GameInputDevice {
+            for each (var device:GameInputDevice in _devices) {
+                if (device._gamepadId == id) {
+                    return device;
+                }
+            }
+            return null;
+        }
+
+        private static function dispatchToInstances(type:String, device:GameInputDevice):void {
+            for each (var instance:GameInput in _instances) {
+                instance.dispatchEvent(new GameInputEvent(type, false, false, device));
+            }
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,46 @@
+package flash.ui {
+    import flash.events.Event;
+    import flash.events.EventDispatcher;
+
+    public class GameInputControl extends EventDispatcher {
+        private var _device:GameInputDevice;
+        private var _id:String;
+        private var _minValue:Number;
+        private var _maxValue:Number;
+        private var _value:Number = 0;
+
+        public function GameInputControl(device:GameInputDevice, id:String, minValue:Number, maxValue:Number) {
+            this._device = device;
+            this._id = id;
+            this._minValue = minValue;
+            this._maxValue = maxValue;
+        }
+
+        public function get device():GameInputDevice {
+            return this._device;
+        }
+
+        public function get id():String {
+            return this._id;
+        }
+
+        public function get minValue():Number {
+            return this._minValue;
+        }
+
+        public function get maxValue():Number {
+            return this._maxValue;
+        }
+
+        public function get value():Number {
+            return this._value;
+        }
+
+        internal function _setValue(value:Number):void {
+            if (value != this._value) {
+                this._value = value;
+                this.dispatchEvent(new Event(Event.CHANGE));
+            }
+        }
+    }
+}
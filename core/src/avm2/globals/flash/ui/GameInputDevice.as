@@ -1,4 +1,52 @@
 package flash.ui {
+    import flash.utils.ByteArray;
+    import __ruffle__.stub_method;
+
     public final class GameInputDevice {
+        public static const MAX_BUFFER_SIZE:int = 32000;
+
+        // The player's identifier for the gamepad.
+        internal var _gamepadId:uint;
+
+        internal var _id:String;
+        internal var _name:String;
+        internal var _controls:Array = [];
+
+        // Controls only report their values once the device is enabled.
+        public var enabled:Boolean = false;
+
+        public var sampleInterval:int = 0;
+
+        public function get id():String {
+            return this._id;
+        }
+
+        public function get name():String {
+            return this._name;
+        }
+
+        public function get numControls():int {
+            return this._controls.length;
+        }
+
+        public function getControlAt(i:int):GameInputControl {
+            if (i < 0 || i >= this._controls.length) {
+                throw new RangeError("Error #2006: The supplied index is out of bounds.", 2006);
+            }
+            return this._controls[i];
+        }
+
+        public function startCachingSamples(numSamples:int, controls:Vector.<String>):void {
+            stub_method("flash.ui.GameInputDevice", "startCachingSamples");
+        }
+
+        public function stopCachingSamples():void {
+            stub_method("flash.ui.GameInputDevice", "stopCachingSamples");
+        }
+
+        public function getCachedSamples(data:ByteArray, append:Boolean = false):int {
+            stub_method("flash.ui.GameInputDevice", "getCachedSamples");
+            return 0;
+        }
     }
 }
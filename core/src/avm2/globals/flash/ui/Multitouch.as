@@ -1,6 +1,53 @@
 package flash.ui {
+    import flash.events.GestureEvent;
+    import flash.events.TransformGestureEvent;
+
     public final class Multitouch {
-        // FIXME - implement a setter and gett
-        public static var inputMode:String = MultitouchInputMode.GESTURE;
+        private static var _inputMode:String = MultitouchInputMode.GESTURE;
+
+        public static function get inputMode():String {
+            return _inputMode;
+        }
+
+        public static function set inputMode(value:String):void {
+            if (value == null) {
+                throw new TypeError("Error #2007: Parameter inputMode must be non-null.", 2007);
+            }
+            if (value != MultitouchInputMode.GESTURE && value != MultitouchInputMode.NONE && value != MultitouchInputMode.TOUCH_POINT) {
+                throw new ArgumentError("Error #2008: Parameter inputMode must be one of the accepted values.", 2008);
+            }
+            _inputMode = value;
+        }
+
+        public static native function get maxTouchPoints():int;
+
+        public static function get mapTouchToMouse():Boolean {
+            return true;
+        }
+
+        public static function set mapTouchToMouse(value:Boolean):void {
+            // The frontends always report the primary touch point as the mouse.
+        }
+
+        public static function get supportsTouchEvents():Boolean {
+            return maxTouchPoints > 0;
+        }
+
+        public static function get supportsGestureEvents():Boolean {
+            return maxTouchPoints > 0;
+        }
+
+        public static function get supportedGestures():Vector.<String> {
+            if (!supportsGestureEvents) {
+                return null;
+            }
+            return Vector.<String>([
+                TransformGestureEvent.GESTURE_PAN,
+                TransformGestureEvent.GESTURE_ROTATE,
+                TransformGestureEvent.GESTURE_SWIPE,
+                TransformGestureEvent.GESTURE_ZOOM,
+                GestureEvent.GESTURE_TWO_FINGER_TAP
+            ]);
+        }
     }
-}
\ No newline at end of file
+}
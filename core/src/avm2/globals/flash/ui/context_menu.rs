@@ -66,12 +66,6 @@ pub fn make_context_menu_state<'gc>(
         }
     }
 
-    result.build_builtin_items(
-        builtin_items,
-        activation.context.stage,
-        activation.context.ui.language(),
-    );
-
     if let Some(menu) = menu {
         if let Ok(Value::Object(custom_items)) = menu.get_public_property("customItems", activation)
         {
@@ -111,5 +105,13 @@ pub fn make_context_menu_state<'gc>(
             }
         }
     }
+
+    // Custom items are shown above the built-in ones.
+    result.build_builtin_items(
+        builtin_items,
+        activation.context.stage,
+        activation.context.ui.language(),
+    );
+
     result
 }
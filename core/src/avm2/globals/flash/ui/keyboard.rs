@@ -4,6 +4,7 @@ use crate::avm2::activation::Activation;
 use crate::avm2::value::Value;
 use crate::avm2::{Error, Object};
 use crate::avm2_stub_getter;
+use crate::events::KeyCode;
 use crate::string::AvmString;
 
 pub fn get_caps_lock<'gc>(
@@ -11,8 +12,11 @@ pub fn get_caps_lock<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.ui.Keyboard", "capsLock");
-    Ok(false.into())
+    Ok(activation
+        .context
+        .input
+        .is_key_toggled(KeyCode::CapsLock)
+        .into())
 }
 
 pub fn get_has_virtual_keyboard<'gc>(
@@ -29,8 +33,11 @@ pub fn get_num_lock<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm2_stub_getter!(activation, "flash.ui.Keyboard", "numLock");
-    Ok(false.into())
+    Ok(activation
+        .context
+        .input
+        .is_key_toggled(KeyCode::NumLock)
+        .into())
 }
 
 pub fn get_physical_keyboard_type<'gc>(
@@ -1,9 +1,13 @@
 //! `flash.ui.Mouse` builtin
 
 use crate::avm2::activation::Activation;
-use crate::avm2::object::Object;
+use crate::avm2::error::make_error_2008;
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::backend::ui::CustomMouseCursor;
+use crate::string::AvmString;
 
 pub fn hide<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -22,3 +26,95 @@ pub fn show<'gc>(
     activation.context.ui.set_mouse_visible(true);
     Ok(Value::Undefined)
 }
+
+/// Implements `flash.ui.Mouse.cursor`'s getter
+pub fn get_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let cursor = activation.context.mouse_cursors.cursor();
+    Ok(AvmString::new_utf8(activation.context.gc_context, cursor).into())
+}
+
+/// Implements `flash.ui.Mouse.cursor`'s setter
+pub fn set_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_string();
+    if !activation.context.mouse_cursors.is_valid(&name) {
+        return Err(make_error_2008(activation, "cursor"));
+    }
+
+    let context = &mut activation.context;
+    context.mouse_cursors.set_cursor(&name, context.ui);
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.Mouse.registerCursor`
+pub fn register_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_string();
+    let cursor = args.get_object(activation, 1, "cursor")?;
+
+    let hot_spot = cursor
+        .get_public_property("hotSpot", activation)?
+        .as_object();
+    let (hotspot_x, hotspot_y) = match hot_spot {
+        Some(hot_spot) => (
+            hot_spot
+                .get_public_property("x", activation)?
+                .coerce_to_u32(activation)?,
+            hot_spot
+                .get_public_property("y", activation)?
+                .coerce_to_u32(activation)?,
+        ),
+        None => (0, 0),
+    };
+    let frame_rate = cursor
+        .get_public_property("frameRate", activation)?
+        .coerce_to_number(activation)?;
+
+    let mut frames = Vec::new();
+    let data = cursor.get_public_property("data", activation)?.as_object();
+    if let Some(vector) = data.as_ref().and_then(|data| data.as_vector_storage()) {
+        for bitmap_data in vector.iter() {
+            if let Some(bitmap_data) = bitmap_data
+                .as_object()
+                .and_then(|bitmap_data| bitmap_data.as_bitmap_data())
+            {
+                frames.push(CustomMouseCursor {
+                    image: bitmap_data.sync().read().to_unmultiplied_bitmap(),
+                    hotspot_x,
+                    hotspot_y,
+                });
+            }
+        }
+    }
+    if frames.is_empty() {
+        return Err(make_error_2008(activation, "cursor"));
+    }
+
+    let context = &mut activation.context;
+    context
+        .mouse_cursors
+        .register(name, frames, frame_rate, context.ui);
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.Mouse.unregisterCursor`
+pub fn unregister_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args.get_string(activation, 0)?.to_string();
+    let context = &mut activation.context;
+    context.mouse_cursors.unregister(&name, context.ui);
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,15 @@
+//! `flash.ui.Multitouch` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+
+/// Implements `flash.ui.Multitouch.maxTouchPoints`'s getter
+pub fn get_max_touch_points<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.max_touch_points().into())
+}
@@ -4,11 +4,11 @@ use crate::avm2::metadata::Metadata;
 use crate::avm2::method::Method;
 use crate::avm2::object::TObject;
 use crate::avm2::property::Property;
+use crate::avm2::traits::TraitKind;
 use crate::avm2::ClassObject;
 use crate::avm2::{Activation, Error, Object, Value};
 use crate::string::AvmString;
 use crate::string::WString;
-use instant::Instant;
 use std::fmt::Write;
 
 pub mod byte_array;
@@ -22,10 +22,7 @@ pub fn get_timer<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    Ok((Instant::now()
-        .duration_since(activation.context.start_time)
-        .as_millis() as u32)
-        .into())
+    Ok((activation.context.elapsed_time().as_millis() as u32).into())
 }
 
 /// Implements `flash.utils.setInterval`
@@ -509,7 +506,7 @@ fn describe_internal_body<'gc>(
 
                 write!(
                     xml_string,
-                    "<{elem_name} name=\"{prop_name}\" type=\"{prop_class_name}\">"
+                    "<{elem_name} name=\"{prop_name}\" type=\"{prop_class_name}\" {uri}>"
                 )
                 .unwrap();
                 if let Some(metadata) = trait_metadata {
@@ -616,6 +613,13 @@ fn describe_internal_body<'gc>(
         xml_string += "</constructor>";
     }
 
+    // Metadata on the class itself is shown alongside the instance traits.
+    if !is_static {
+        if let Some(metadata) = class_metadata(class_obj)? {
+            write_metadata(&mut xml_string, &metadata);
+        }
+    }
+
     // If we're describing a Class object, add a <factory> element describing the instance.
     if is_static {
         write!(xml_string, "<factory type=\"{qualified_name}\">").unwrap();
@@ -625,6 +629,26 @@ fn describe_internal_body<'gc>(
     Ok(xml_string)
 }
 
+/// Looks up the metadata attached to the trait that declared a class in its script.
+fn class_metadata<'gc>(
+    class_obj: ClassObject<'gc>,
+) -> Result<Option<Box<[Metadata<'gc>]>>, Error<'gc>> {
+    let name = class_obj.inner_class_definition().read().name();
+    let Some((_, script)) = class_obj
+        .class_scope()
+        .domain()
+        .get_defining_script(&name.into())?
+    else {
+        return Ok(None);
+    };
+
+    let traits = script.traits()?;
+    Ok(traits
+        .iter()
+        .find(|t| t.name() == name && matches!(t.kind(), TraitKind::Class { .. }))
+        .and_then(|t| t.metadata()))
+}
+
 fn write_params<'gc>(
     xml_string: &mut String,
     method: &Method<'gc>,
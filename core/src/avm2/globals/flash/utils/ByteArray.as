@@ -24,10 +24,46 @@ package flash.utils {
 		public native function get position():uint;
 		public native function set position(value:uint):void;
 
+		// Whether this byte array is shared, rather than copied, when it's passed to another worker.
+		private var _shareable:Boolean = false;
+
+		public function get shareable():Boolean {
+			return this._shareable;
+		}
+
+		public function set shareable(value:Boolean):void {
+			this._shareable = value;
+		}
+
 		public function ByteArray() {
 			this.objectEncoding = _defaultObjectEncoding;
 		}
 
+		// Workers run on the same thread, so these operations are always atomic.
+		public function atomicCompareAndSwapIntAt(byteIndex:int, expectedValue:int, newValue:int):int {
+			if (byteIndex < 0 || byteIndex % 4 != 0 || byteIndex + 4 > this.length) {
+				throw new RangeError("Error #1506: The specified range is invalid.", 1506);
+			}
+
+			var oldPosition:uint = this.position;
+			this.position = byteIndex;
+			var value:int = this.readInt();
+			if (value == expectedValue) {
+				this.position = byteIndex;
+				this.writeInt(newValue);
+			}
+			this.position = oldPosition;
+			return value;
+		}
+
+		public function atomicCompareAndSwapLength(expectedLength:int, newLength:int):int {
+			var length:int = this.length;
+			if (length == expectedLength) {
+				this.length = newLength;
+			}
+			return length;
+		}
+
 		public native function clear():void;
 
 		public function deflate(): void {
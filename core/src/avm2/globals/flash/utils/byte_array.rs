@@ -1,4 +1,5 @@
 use crate::avm2::activation::Activation;
+use crate::avm2::amf::{Amf3Reader, Amf3Writer};
 use crate::avm2::bytearray::{Endian, ObjectEncoding};
 use crate::avm2::error::make_error_2008;
 pub use crate::avm2::object::byte_array_allocator;
@@ -9,7 +10,6 @@ use crate::string::AvmString;
 use encoding_rs::Encoding;
 use encoding_rs::UTF_8;
 use flash_lso::amf0::read::AMF0Decoder;
-use flash_lso::amf3::read::AMF3Decoder;
 use flash_lso::types::{AMFVersion, Element};
 
 /// Writes a single byte to the bytearray
@@ -729,32 +729,28 @@ pub fn read_object<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    let object_encoding = match this.as_bytearray() {
+        Some(bytearray) => bytearray.object_encoding(),
+        None => return Ok(Value::Undefined),
+    };
+
+    if object_encoding == ObjectEncoding::Amf3 {
+        let mut reader = Amf3Reader::new(this);
+        let value = reader.read_value(activation)?;
+        reader.commit();
+        return Ok(value);
+    }
+
     if let Some(bytearray) = this.as_bytearray() {
         let bytes = bytearray
             .read_at(bytearray.bytes_available(), bytearray.position())
             .map_err(|e| e.to_avm(activation))?;
-        let (bytes_left, value) = match bytearray.object_encoding() {
-            ObjectEncoding::Amf0 => {
-                let mut decoder = AMF0Decoder::default();
-                let (extra, amf) = decoder
-                    .parse_single_element(bytes)
-                    .map_err(|_| "Error: Invalid object")?;
-                (
-                    extra.len(),
-                    crate::avm2::amf::deserialize_value(activation, &amf)?,
-                )
-            }
-            ObjectEncoding::Amf3 => {
-                let mut decoder = AMF3Decoder::default();
-                let (extra, amf) = decoder
-                    .parse_single_element(bytes)
-                    .map_err(|_| "Error: Invalid object")?;
-                (
-                    extra.len(),
-                    crate::avm2::amf::deserialize_value(activation, &amf)?,
-                )
-            }
-        };
+        let mut decoder = AMF0Decoder::default();
+        let (extra, amf) = decoder
+            .parse_single_element(bytes)
+            .map_err(|_| "Error: Invalid object")?;
+        let bytes_left = extra.len();
+        let value = crate::avm2::amf::deserialize_value(activation, &amf)?;
 
         bytearray.set_position(bytearray.len() - bytes_left);
         return Ok(value);
@@ -768,23 +764,28 @@ pub fn write_object<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
-        let obj = args.get(0).cloned().unwrap_or(Value::Undefined);
-        let amf_version = match bytearray.object_encoding() {
-            ObjectEncoding::Amf0 => AMFVersion::AMF0,
-            ObjectEncoding::Amf3 => AMFVersion::AMF3,
-        };
-        if let Some(amf) = crate::avm2::amf::serialize_value(activation, obj, amf_version) {
-            let element = Element::new("", amf);
-            let mut lso = flash_lso::types::Lso::new(vec![element], "", amf_version);
-            let bytes = flash_lso::write::write_to_bytes(&mut lso)
-                .map_err(|_| "Failed to serialize object")?;
-            // This is kind of hacky: We need to strip out the header and any padding so that we only write
-            // the value. In the future, there should be a method to do this in the flash_lso crate.
-            let element_padding = match amf_version {
-                AMFVersion::AMF0 => 8,
-                AMFVersion::AMF3 => 7,
-            };
+    let obj = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let object_encoding = match this.as_bytearray() {
+        Some(bytearray) => bytearray.object_encoding(),
+        None => return Ok(Value::Undefined),
+    };
+
+    if object_encoding == ObjectEncoding::Amf3 {
+        let mut writer = Amf3Writer::new(this);
+        writer.write_value(activation, obj)?;
+        writer.flush(activation)?;
+        return Ok(Value::Undefined);
+    }
+
+    if let Some(amf) = crate::avm2::amf::serialize_value(activation, obj) {
+        let element = Element::new("", amf);
+        let mut lso = flash_lso::types::Lso::new(vec![element], "", AMFVersion::AMF0);
+        let bytes =
+            flash_lso::write::write_to_bytes(&mut lso).map_err(|_| "Failed to serialize object")?;
+        // This is kind of hacky: We need to strip out the header and any padding so that we only write
+        // the value. In the future, there should be a method to do this in the flash_lso crate.
+        let element_padding = 8;
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
             bytearray
                 .write_bytes(
                     &bytes[flash_lso::write::header_length(&lso.header) + element_padding
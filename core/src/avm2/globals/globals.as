@@ -32,8 +32,12 @@ include "flash/utils/IDataOutput.as"
 include "flash/utils/IExternalizable.as"
 include "flash/utils/ByteArray.as"
 include "flash/utils/Dictionary.as"
+include "flash/desktop/Clipboard.as"
 include "flash/desktop/ClipboardFormats.as"
 include "flash/desktop/ClipboardTransferMode.as"
+include "flash/desktop/NativeDragActions.as"
+include "flash/desktop/NativeDragManager.as"
+include "flash/desktop/NativeDragOptions.as"
 
 include "flash/events/IEventDispatcher.as"
 include "flash/events/EventDispatcher.as"
@@ -126,6 +130,7 @@ include "flash/display3D/textures/TextureBase.as"
 include "flash/display3D/textures/CubeTexture.as"
 include "flash/display3D/textures/Texture.as"
 include "flash/display3D/textures/RectangleTexture.as"
+include "flash/display3D/textures/VideoTexture.as"
 include "flash/display3D/VertexBuffer3D.as"
 
 include "flash/display/MovieClip.as"
@@ -133,10 +138,12 @@ include "flash/display/MovieClip.as"
 // Event needs to come before its subclasses
 include "flash/events/Event.as"
 include "flash/events/TextEvent.as"
+include "flash/events/IMEEvent.as"
 include "flash/events/ActivityEvent.as"
 include "flash/events/ErrorEvent.as"
 include "flash/events/GestureEvent.as"
 include "flash/events/MouseEvent.as"
+include "flash/events/NativeDragEvent.as"
 include "flash/events/AccelerometerEvent.as"
 include "flash/events/AsyncErrorEvent.as"
 include "flash/events/AudioOutputChangeEvent.as"
@@ -145,6 +152,7 @@ include "flash/events/AVHTTPStatusEvent.as"
 include "flash/events/AVPauseAtPeriodEndEvent.as"
 include "flash/events/ContextMenuEvent.as"
 include "flash/events/DataEvent.as"
+include "flash/events/DatagramSocketDataEvent.as"
 include "flash/events/DRMAuthenticationCompleteEvent.as"
 include "flash/events/DRMAuthenticationErrorEvent.as"
 include "flash/events/DRMLicenseRequestEvent.as"
@@ -163,6 +171,7 @@ include "flash/events/NetStatusEvent.as"
 include "flash/events/ProgressEvent.as"
 include "flash/events/SampleDataEvent.as"
 include "flash/events/SecurityErrorEvent.as"
+include "flash/events/ServerSocketConnectEvent.as"
 include "flash/events/ShaderEvent.as"
 include "flash/events/SoftKeyboardEvent.as"
 include "flash/events/SoftKeyboardTrigger.as"
@@ -174,6 +183,7 @@ include "flash/events/ThrottleEvent.as"
 include "flash/events/ThrottleType.as"
 include "flash/events/TimerEvent.as"
 include "flash/events/TouchEvent.as"
+include "flash/events/TransformGestureEvent.as"
 include "flash/events/UncaughtErrorEvent.as"
 include "flash/events/UncaughtErrorEvents.as"
 include "flash/events/VideoEvent.as"
@@ -240,6 +250,7 @@ include "flash/media/SoundChannel.as"
 include "flash/media/SoundLoaderContext.as"
 include "flash/media/SoundMixer.as"
 include "flash/media/SoundTransform.as"
+include "flash/media/StageVideo.as"
 include "flash/media/StageVideoAvailability.as"
 include "flash/media/StageVideoAvailabilityReason.as"
 include "flash/media/Video.as"
@@ -250,24 +261,30 @@ include "flash/media/VideoStreamSettings.as"
 include "flash/external/ExternalInterface.as"
 
 include "flash/net.as"
+include "flash/net/DatagramSocket.as"
 include "flash/net/FileFilter.as"
 include "flash/net/FileReference.as"
 include "flash/net/FileReferenceList.as"
 include "flash/net/IDynamicPropertyOutput.as"
 include "flash/net/IDynamicPropertyWriter.as"
+include "flash/net/InterfaceAddress.as"
 include "flash/net/LocalConnection.as"
 include "flash/net/NetConnection.as"
+include "flash/net/NetworkInfo.as"
+include "flash/net/NetworkInterface.as"
 include "flash/net/NetGroupReceiveMode.as"
 include "flash/net/NetGroupReplicationStrategy.as"
 include "flash/net/NetGroupSendMode.as"
 include "flash/net/NetGroupSendResult.as"
 include "flash/net/NetStream.as"
+include "flash/net/NetStreamAppendBytesAction.as"
 include "flash/net/NetStreamInfo.as"
 include "flash/net/NetStreamMulticastInfo.as"
 include "flash/net/NetStreamPlayOptions.as"
 include "flash/net/URLRequestDefaults.as"
 include "flash/net/ObjectEncoding.as"
 include "flash/net/Responder.as"
+include "flash/net/ServerSocket.as"
 include "flash/net/SharedObject.as"
 include "flash/net/SharedObjectFlushStatus.as"
 include "flash/net/Socket.as"
@@ -280,6 +297,11 @@ include "flash/net/URLStream.as"
 include "flash/net/URLVariables.as"
 include "flash/net/XMLSocket.as"
 
+// File needs to come after FileReference, which it extends
+include "flash/filesystem/File.as"
+include "flash/filesystem/FileMode.as"
+include "flash/filesystem/FileStream.as"
+
 include "flash/printing/PrintJob.as"
 include "flash/printing/PrintJobOptions.as"
 include "flash/printing/PrintJobOrientation.as"
@@ -304,6 +326,7 @@ include "flash/system/IME.as"
 include "flash/system/IMEConversionMode.as"
 include "flash/system/LoaderContext.as"
 include "flash/system/JPEGLoaderContext.as"
+include "flash/system/MessageChannel.as"
 include "flash/system/MessageChannelState.as"
 include "flash/system/Security.as"
 include "flash/system/SecurityDomain.as"
@@ -338,22 +361,33 @@ include "flash/text/TextRenderer.as"
 
 include "flash/text/engine/BreakOpportunity.as"
 include "flash/text/engine/CFFHinting.as"
-include "flash/text/engine/ContentElement.as"
+include "flash/text/engine/ContentElement.as" // ContentElement is the superclass of all elements
 include "flash/text/engine/DigitCase.as"
 include "flash/text/engine/DigitWidth.as"
 include "flash/text/engine/ElementFormat.as"
 include "flash/text/engine/FontDescription.as"
 include "flash/text/engine/FontLookup.as"
+include "flash/text/engine/FontMetrics.as"
 include "flash/text/engine/FontPosture.as"
 include "flash/text/engine/FontWeight.as"
+include "flash/text/engine/GraphicElement.as"
+include "flash/text/engine/GroupElement.as"
 include "flash/text/engine/JustificationStyle.as"
 include "flash/text/engine/Kerning.as"
 include "flash/text/engine/LigatureLevel.as"
 include "flash/text/engine/LineJustification.as"
 include "flash/text/engine/RenderingMode.as"
 include "flash/text/engine/TabAlignment.as"
+include "flash/text/engine/TabStop.as"
 include "flash/text/engine/TextBaseline.as"
+include "flash/text/engine/TextBlock.as"
+include "flash/text/engine/TextElement.as"
+include "flash/text/engine/TextJustifier.as" // TextJustifier is the superclass of SpaceJustifier and EastAsianJustifier
+include "flash/text/engine/EastAsianJustifier.as"
+include "flash/text/engine/SpaceJustifier.as"
+include "flash/text/engine/TextLine.as"
 include "flash/text/engine/TextLineCreationResult.as"
+include "flash/text/engine/TextLineMirrorRegion.as"
 include "flash/text/engine/TextLineValidity.as"
 include "flash/text/engine/TextRotation.as"
 include "flash/text/engine/TypographicCase.as"
@@ -362,6 +396,7 @@ include "flash/ui/ContextMenu.as"
 include "flash/ui/ContextMenuBuiltInItems.as"
 include "flash/ui/ContextMenuItem.as"
 include "flash/ui/GameInput.as"
+include "flash/ui/GameInputControl.as"
 include "flash/ui/GameInputDevice.as"
 include "flash/ui/Keyboard.as"
 include "flash/ui/KeyboardType.as"
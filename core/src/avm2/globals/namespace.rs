@@ -9,7 +9,7 @@ use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::avm2::Namespace;
 use crate::avm2::QName;
-use crate::{avm2_stub_constructor, avm2_stub_getter};
+use crate::avm2_stub_constructor;
 use gc_arena::GcCell;
 
 /// Implements `Namespace`'s instance initializer.
@@ -19,13 +19,10 @@ pub fn instance_init<'gc>(
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(this) = this.as_namespace_object() {
-        let uri_value = match args {
-            [_prefix, uri] => {
-                avm2_stub_constructor!(activation, "Namespace", "Namespace prefix not supported");
-                Some(*uri)
-            }
-            [uri] => Some(*uri),
-            _ => None,
+        let (prefix_value, uri_value) = match args {
+            [prefix, uri, ..] => (Some(*prefix), Some(*uri)),
+            [uri] => (None, Some(*uri)),
+            _ => (None, None),
         };
 
         let namespace = match uri_value {
@@ -40,6 +37,12 @@ pub fn instance_init<'gc>(
             None => activation.avm2().public_namespace,
         };
 
+        let prefix = match prefix_value {
+            Some(Value::Undefined) | None => None,
+            Some(prefix) => Some(prefix.coerce_to_string(activation)?),
+        };
+        this.set_prefix(activation.context.gc_context, prefix);
+
         this.init_namespace(activation.context.gc_context, namespace);
     }
     Ok(Value::Undefined)
@@ -76,13 +79,17 @@ pub fn class_init<'gc>(
 
 /// Implements `Namespace.prefix`'s getter
 pub fn prefix<'gc>(
-    activation: &mut Activation<'_, 'gc>,
+    _activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if this.as_namespace_object().is_some() {
-        avm2_stub_getter!(activation, "Namespace", "prefix");
-        return Ok("".into());
+    if let Some(o) = this.as_namespace_object() {
+        // Namespaces without a prefix have an empty one if they're the public namespace.
+        return Ok(match o.prefix() {
+            Some(prefix) => prefix.into(),
+            None if o.namespace().as_uri().is_empty() => "".into(),
+            None => Value::Undefined,
+        });
     }
 
     Ok(Value::Undefined)
@@ -1,15 +1,14 @@
 //! XML builtin and prototype
 
-use crate::avm2::e4x::{name_to_multiname, E4XNode, E4XNodeKind};
+use crate::avm2::e4x::{name_to_multiname, E4XNamespace, E4XNode, E4XNodeKind};
 use crate::avm2::error::type_error;
 pub use crate::avm2::object::xml_allocator;
 use crate::avm2::object::{
-    E4XOrXml, NamespaceObject, QNameObject, TObject, XmlListObject, XmlObject,
+    ArrayObject, E4XOrXml, NamespaceObject, QNameObject, TObject, XmlListObject, XmlObject,
 };
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::string::AvmString;
-use crate::avm2::{Activation, Error, Multiname, Object, Value};
-use crate::avm2_stub_method;
+use crate::avm2::{Activation, Error, Multiname, Namespace, Object, Value};
 
 fn ill_formed_markup_err<'gc>(
     activation: &mut Activation<'_, 'gc>,
@@ -56,16 +55,60 @@ pub fn init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Boxes the namespace of an XML name into a `Namespace` object.
+fn namespace_object<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    namespace: E4XNamespace<'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    let avm_namespace = Namespace::package(namespace.uri, &mut activation.borrow_gc());
+    let object = NamespaceObject::from_namespace(activation, avm_namespace)?;
+    if let Some(namespace_object) = object.as_namespace_object() {
+        namespace_object.set_prefix(activation.context.gc_context, namespace.prefix);
+    }
+    Ok(object)
+}
+
+/// Converts a `Namespace` object, a `QName` object or a URI into the namespace of an XML name.
+fn value_to_namespace<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    value: Value<'gc>,
+) -> Result<E4XNamespace<'gc>, Error<'gc>> {
+    if let Some(obj) = value.as_object() {
+        if let Some(namespace) = obj.as_namespace_object() {
+            return Ok(E4XNamespace::new(
+                namespace.prefix(),
+                namespace.namespace().as_uri(),
+            ));
+        }
+        if let Some(qname) = obj.as_qname_object() {
+            return Ok(E4XNamespace::new(None, qname.uri().unwrap_or_default()));
+        }
+    }
+
+    let uri = value.coerce_to_string(activation)?;
+    let prefix = uri.is_empty().then(AvmString::default);
+    Ok(E4XNamespace::new(prefix, uri))
+}
+
+fn namespaces_to_array<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    namespaces: Vec<E4XNamespace<'gc>>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let mut values = Vec::with_capacity(namespaces.len());
+    for namespace in namespaces {
+        values.push(namespace_object(activation, namespace)?.into());
+    }
+    Ok(ArrayObject::from_storage(activation, values.into_iter().collect())?.into())
+}
+
 pub fn name<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let node = this.as_xml_object().unwrap();
+    let node = *this.as_xml_object().unwrap().node();
     if let Some(local_name) = node.local_name() {
-        avm2_stub_method!(activation, "XML", "name", "namespaces");
-        // FIXME - use namespace
-        let namespace = activation.avm2().public_namespace;
+        let namespace = Namespace::package(node.namespace_uri(), &mut activation.borrow_gc());
         Ok(QNameObject::from_name(activation, Multiname::new(namespace, local_name))?.into())
     } else {
         Ok(Value::Null)
@@ -74,13 +117,166 @@ pub fn name<'gc>(
 
 pub fn namespace<'gc>(
     activation: &mut Activation<'_, 'gc>,
-    _this: Object<'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = *this.as_xml_object().unwrap().node();
+    if !matches!(
+        &*node.kind(),
+        E4XNodeKind::Element { .. } | E4XNodeKind::Attribute(_)
+    ) {
+        return Ok(Value::Null);
+    }
+
+    let in_scope = node.in_scope_namespaces();
+    match args.get(0).copied().unwrap_or(Value::Null) {
+        Value::Null | Value::Undefined => {
+            let namespace = match node.namespace() {
+                // Use the prefix that the namespace is declared with, if it's in scope.
+                Some(namespace) => in_scope
+                    .iter()
+                    .find(|ns| ns.uri == namespace.uri)
+                    .copied()
+                    .unwrap_or(namespace),
+                None => E4XNamespace::new(Some(AvmString::default()), AvmString::default()),
+            };
+            Ok(namespace_object(activation, namespace)?.into())
+        }
+        prefix => {
+            let prefix = prefix.coerce_to_string(activation)?;
+            match in_scope.into_iter().find(|ns| ns.prefix == Some(prefix)) {
+                Some(namespace) => Ok(namespace_object(activation, namespace)?.into()),
+                None => Ok(Value::Undefined),
+            }
+        }
+    }
+}
+
+pub fn in_scope_namespaces<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // FIXME: Implement namespace support (including prefix)
-    avm2_stub_method!(activation, "XML", "namespace");
-    let namespace = activation.avm2().public_namespace;
-    Ok(NamespaceObject::from_namespace(activation, namespace)?.into())
+    let namespaces = this.as_xml_object().unwrap().node().in_scope_namespaces();
+    namespaces_to_array(activation, namespaces)
+}
+
+pub fn namespace_declarations<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = *this.as_xml_object().unwrap().node();
+    // Declarations that an ancestor already makes are left out.
+    let ancestor_namespaces = node
+        .parent()
+        .map(|parent| parent.in_scope_namespaces())
+        .unwrap_or_default();
+    let declarations = node
+        .namespace_declarations()
+        .into_iter()
+        .filter(|ns| !ancestor_namespaces.contains(ns))
+        .collect();
+    namespaces_to_array(activation, declarations)
+}
+
+pub fn add_namespace<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let namespace = value_to_namespace(activation, args[0])?;
+    let node = *this.as_xml_object().unwrap().node();
+    node.add_namespace_declaration(namespace, activation.context.gc_context);
+    Ok(this.into())
+}
+
+pub fn remove_namespace<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let namespace = value_to_namespace(activation, args[0])?;
+    let node = *this.as_xml_object().unwrap().node();
+    node.remove_namespace_declaration(namespace, activation.context.gc_context);
+    Ok(this.into())
+}
+
+pub fn set_namespace<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = *this.as_xml_object().unwrap().node();
+    let declaring_node = match &*node.kind() {
+        E4XNodeKind::Element { .. } => Some(node),
+        E4XNodeKind::Attribute(_) => node.parent(),
+        _ => return Ok(Value::Undefined),
+    };
+
+    let namespace = value_to_namespace(activation, args[0])?;
+    let mc = activation.context.gc_context;
+    node.set_namespace(Some(namespace), mc);
+    if let Some(declaring_node) = declaring_node {
+        declaring_node.add_namespace_declaration(namespace, mc);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = *this.as_xml_object().unwrap().node();
+    if !matches!(
+        &*node.kind(),
+        E4XNodeKind::Element { .. } | E4XNodeKind::Attribute(_)
+    ) {
+        return Ok(Value::Undefined);
+    }
+
+    let name = args[0];
+    let mc = activation.context.gc_context;
+    if let Some(qname) = name.as_object().and_then(|obj| obj.as_qname_object()) {
+        let local_name = qname.local_name();
+        node.set_local_name(local_name, mc);
+        let uri = qname.uri().unwrap_or_default();
+        let namespace = node
+            .in_scope_namespaces()
+            .into_iter()
+            .find(|ns| ns.uri == uri)
+            .unwrap_or_else(|| E4XNamespace::new(None, uri));
+        node.set_namespace(Some(namespace), mc);
+    } else {
+        // A plain name is in the default namespace, so the node leaves its old one.
+        let local_name = name.coerce_to_string(activation)?;
+        node.set_local_name(local_name, mc);
+        node.set_namespace(None, mc);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn set_local_name<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = *this.as_xml_object().unwrap().node();
+    if !matches!(
+        &*node.kind(),
+        E4XNodeKind::Element { .. } | E4XNodeKind::Attribute(_)
+    ) {
+        return Ok(Value::Undefined);
+    }
+
+    let name = args[0];
+    let local_name = match name.as_object().and_then(|obj| obj.as_qname_object()) {
+        Some(qname) => qname.local_name(),
+        None => name.coerce_to_string(activation)?,
+    };
+    node.set_local_name(local_name, activation.context.gc_context);
+    Ok(Value::Undefined)
 }
 
 pub fn local_name<'gc>(
@@ -307,29 +503,171 @@ pub fn node_kind<'gc>(
     Ok(name.into())
 }
 
+/// Finds the index of a child given to `insertChildBefore` or `insertChildAfter`.
+fn child_position<'gc>(node: E4XNode<'gc>, child: Value<'gc>) -> Option<usize> {
+    let child = child.as_object()?;
+    let child_node = if let Some(xml) = child.as_xml_object() {
+        *xml.node()
+    } else if let Some(list) = child.as_xml_list_object() {
+        match &**list.children() {
+            [child] => *child.node(),
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
+
+    let kind = node.kind();
+    let E4XNodeKind::Element { children, .. } = &*kind else {
+        return None;
+    };
+    children
+        .iter()
+        .position(|child| E4XNode::ptr_eq(*child, child_node))
+}
+
+fn child_count(node: E4XNode<'_>) -> usize {
+    match &*node.kind() {
+        E4XNodeKind::Element { children, .. } => children.len(),
+        _ => 0,
+    }
+}
+
 pub fn append_child<'gc>(
     activation: &mut Activation<'_, 'gc>,
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let xml = this.as_xml_object().unwrap();
+    let node = *this.as_xml_object().unwrap().node();
+    let children = E4XNode::nodes_from_value(args[0], activation)?;
+    node.insert_children(activation, child_count(node), &children)?;
+    Ok(this.into())
+}
 
-    let child = args.get_object(activation, 0, "child")?;
-    if let Some(child) = child.as_xml_object() {
-        xml.node()
-            .append_child(activation.context.gc_context, *child.node())?;
-    } else if let Some(list) = child.as_xml_list_object() {
-        if list.target().is_some() {
-            return Err("Cannot append XMLList with target".into());
-        }
-        for child in &*list.children() {
-            xml.node()
-                .append_child(activation.context.gc_context, *child.node())?;
-        }
+pub fn prepend_child<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = *this.as_xml_object().unwrap().node();
+    let children = E4XNode::nodes_from_value(args[0], activation)?;
+    node.insert_children(activation, 0, &children)?;
+    Ok(this.into())
+}
+
+pub fn insert_child_after<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = *this.as_xml_object().unwrap().node();
+    if !matches!(&*node.kind(), E4XNodeKind::Element { .. }) {
+        return Ok(Value::Undefined);
+    }
+
+    // A null reference child inserts at the start.
+    let index = match args[0] {
+        Value::Null | Value::Undefined => 0,
+        child => match child_position(node, child) {
+            Some(position) => position + 1,
+            None => return Ok(Value::Undefined),
+        },
+    };
+    let children = E4XNode::nodes_from_value(args[1], activation)?;
+    node.insert_children(activation, index, &children)?;
+    Ok(this.into())
+}
+
+pub fn insert_child_before<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = *this.as_xml_object().unwrap().node();
+    if !matches!(&*node.kind(), E4XNodeKind::Element { .. }) {
+        return Ok(Value::Undefined);
+    }
+
+    // A null reference child inserts at the end.
+    let index = match args[0] {
+        Value::Null | Value::Undefined => child_count(node),
+        child => match child_position(node, child) {
+            Some(position) => position,
+            None => return Ok(Value::Undefined),
+        },
+    };
+    let children = E4XNode::nodes_from_value(args[1], activation)?;
+    node.insert_children(activation, index, &children)?;
+    Ok(this.into())
+}
+
+pub fn set_children<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = *this.as_xml_object().unwrap().node();
+    let children = E4XNode::nodes_from_value(args[0], activation)?;
+    node.remove_all_children(activation.context.gc_context);
+    node.insert_children(activation, 0, &children)?;
+    Ok(this.into())
+}
+
+pub fn replace<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let node = *this.as_xml_object().unwrap().node();
+    if !matches!(&*node.kind(), E4XNodeKind::Element { .. }) {
+        return Ok(this.into());
+    }
+
+    // Values other than XML become text, rather than being parsed.
+    let value = args[1];
+    let is_xml = value.as_object().map_or(false, |obj| {
+        obj.as_xml_object().is_some() || obj.as_xml_list_object().is_some()
+    });
+    let replacement = if is_xml {
+        E4XNode::nodes_from_value(value, activation)?
     } else {
-        return Err(format!("Cannot append non-XML value {child:?}").into());
+        let text = value.coerce_to_string(activation)?;
+        vec![E4XNode::text(activation.context.gc_context, text, None)]
     };
-    Ok(Value::Undefined)
+
+    let property_name = args[0];
+    let is_index = matches!(property_name, Value::Integer(_) | Value::Number(_))
+        || matches!(property_name, Value::String(s) if s.parse::<u32>().is_ok());
+    if is_index {
+        let index = property_name.coerce_to_u32(activation)? as usize;
+        node.replace_child_at(activation, index, &replacement)?;
+        return Ok(this.into());
+    }
+
+    let multiname = name_to_multiname(activation, &property_name, false)?;
+    let matching: Vec<usize> = match &*node.kind() {
+        E4XNodeKind::Element { children, .. } => children
+            .iter()
+            .enumerate()
+            .filter(|(_, child)| child.matches_name(&multiname))
+            .map(|(index, _)| index)
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    // Every matching child is removed, and the first is replaced with the value.
+    if let Some((&first, rest)) = matching.split_first() {
+        let mc = activation.context.gc_context;
+        for &index in rest.iter().rev() {
+            let removed = match &mut *node.kind_mut(mc) {
+                E4XNodeKind::Element { children, .. } => children.remove(index),
+                _ => unreachable!("node must be an element"),
+            };
+            removed.set_parent(None, mc);
+        }
+        node.replace_child_at(activation, first, &replacement)?;
+    }
+    Ok(this.into())
 }
 
 pub fn descendants<'gc>(
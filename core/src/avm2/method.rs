@@ -2,12 +2,17 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::object::{ClassObject, Object};
+use crate::avm2::predecode::{DecodedBody, HOT_METHOD_THRESHOLD};
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::value::{abc_default_value, Value};
+use crate::avm2::verify::Instruction;
 use crate::avm2::Error;
 use crate::avm2::Multiname;
 use crate::string::AvmString;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::Lock;
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use std::cell::{Cell, RefCell};
 use std::fmt;
 use std::ops::Deref;
 use std::rc::Rc;
@@ -128,6 +133,22 @@ pub struct BytecodeMethod<'gc> {
     /// A free-standing function corresponds to the `Function` trait type, and
     /// is instantiated with the `newfunction` opcode.
     pub is_function: bool,
+
+    /// Whether this method's body has already passed verification.
+    #[collect(require_static)]
+    pub verified: Cell<bool>,
+
+    /// The instructions that were decoded while verifying this method's body, kept until
+    /// the body is pre-decoded.
+    #[collect(require_static)]
+    pub instructions: RefCell<Option<Vec<Instruction>>>,
+
+    /// How many times this method has been called, up to `HOT_METHOD_THRESHOLD`.
+    #[collect(require_static)]
+    pub invocations: Cell<u32>,
+
+    /// The pre-decoded body of this method, once it has been called often enough.
+    pub decoded: Lock<Option<Gc<'gc, DecodedBody<'gc>>>>,
 }
 
 impl<'gc> BytecodeMethod<'gc> {
@@ -169,6 +190,10 @@ impl<'gc> BytecodeMethod<'gc> {
                         return_type,
                         is_function,
                         activation_class,
+                        verified: Cell::new(false),
+                        instructions: RefCell::new(None),
+                        invocations: Cell::new(0),
+                        decoded: Lock::new(None),
                     });
                 }
             }
@@ -183,6 +208,10 @@ impl<'gc> BytecodeMethod<'gc> {
             return_type: Multiname::any(activation.context.gc_context),
             is_function,
             activation_class: None,
+            verified: Cell::new(false),
+            instructions: RefCell::new(None),
+            invocations: Cell::new(0),
+            decoded: Lock::new(None),
         })
     }
 
@@ -217,6 +246,32 @@ impl<'gc> BytecodeMethod<'gc> {
         &self.signature
     }
 
+    /// Count a call to this method, and get its pre-decoded body if it has been called
+    /// often enough to be worth decoding.
+    ///
+    /// The body is built from the instructions that were decoded during verification, so it
+    /// must have been verified first.
+    pub fn hot_body(
+        this: Gc<'gc, Self>,
+        mc: MutationContext<'gc, '_>,
+    ) -> Option<Gc<'gc, DecodedBody<'gc>>> {
+        if let Some(decoded) = this.decoded.get() {
+            return Some(decoded);
+        }
+
+        let invocations = this.invocations.get() + 1;
+        this.invocations.set(invocations);
+        if invocations < HOT_METHOD_THRESHOLD {
+            return None;
+        }
+
+        let body = this.body()?;
+        let instructions = this.instructions.take()?;
+        let decoded = DecodedBody::new(mc, body, instructions);
+        unlock!(Gc::write(mc, this), BytecodeMethod, decoded).set(Some(decoded));
+        Some(decoded)
+    }
+
     /// Get the name of this method.
     pub fn method_name(&self) -> &str {
         let name_index = self.method().name.0 as usize;
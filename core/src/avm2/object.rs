@@ -57,6 +57,7 @@ mod sound_object;
 mod soundchannel_object;
 mod stage3d_object;
 mod stage_object;
+mod stage_video_object;
 mod textformat_object;
 mod texture_object;
 mod vector_object;
@@ -72,7 +73,9 @@ pub use crate::avm2::object::bytearray_object::{
     byte_array_allocator, ByteArrayObject, ByteArrayObjectWeak,
 };
 pub use crate::avm2::object::class_object::{ClassObject, ClassObjectWeak};
-pub use crate::avm2::object::context3d_object::{Context3DObject, Context3DObjectWeak};
+pub use crate::avm2::object::context3d_object::{
+    Context3DObject, Context3DObjectWeak, Context3DProfile,
+};
 pub use crate::avm2::object::date_object::{date_allocator, DateObject, DateObjectWeak};
 pub use crate::avm2::object::dictionary_object::{
     dictionary_allocator, DictionaryObject, DictionaryObjectWeak,
@@ -120,6 +123,9 @@ pub use crate::avm2::object::stage3d_object::{
     stage_3d_allocator, Stage3DObject, Stage3DObjectWeak,
 };
 pub use crate::avm2::object::stage_object::{StageObject, StageObjectWeak};
+pub use crate::avm2::object::stage_video_object::{
+    stage_video_allocator, StageVideoObject, StageVideoObjectWeak,
+};
 pub use crate::avm2::object::textformat_object::{
     textformat_allocator, TextFormatObject, TextFormatObjectWeak,
 };
@@ -173,7 +179,8 @@ pub use crate::avm2::object::xml_object::{xml_allocator, XmlObject, XmlObjectWea
         Program3DObject(Program3DObject<'gc>),
         NetStreamObject(NetStreamObject<'gc>),
         ShaderDataObject(ShaderDataObject<'gc>),
-        SocketObject(SocketObject<'gc>)
+        SocketObject(SocketObject<'gc>),
+        StageVideoObject(StageVideoObject<'gc>)
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -1365,6 +1372,10 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         None
     }
 
+    fn as_stage_video(&self) -> Option<StageVideoObject<'gc>> {
+        None
+    }
+
     fn as_texture(&self) -> Option<TextureObject<'gc>> {
         None
     }
@@ -1421,7 +1432,8 @@ impl<'gc> Object<'gc> {
             Self::Program3DObject(o) => WeakObject::Program3DObject(Program3DObjectWeak(Gc::downgrade(o.0))),
             Self::NetStreamObject(o) => WeakObject::NetStreamObject(NetStreamObjectWeak(GcCell::downgrade(o.0))),
             Self::ShaderDataObject(o) => WeakObject::ShaderDataObject(ShaderDataObjectWeak(Gc::downgrade(o.0))),
-            Self::SocketObject(o) => WeakObject::SocketObject(SocketObjectWeak(Gc::downgrade(o.0)))
+            Self::SocketObject(o) => WeakObject::SocketObject(SocketObjectWeak(Gc::downgrade(o.0))),
+            Self::StageVideoObject(o) => WeakObject::StageVideoObject(StageVideoObjectWeak(Gc::downgrade(o.0)))
         }
     }
 }
@@ -1478,6 +1490,7 @@ pub enum WeakObject<'gc> {
     NetStreamObject(NetStreamObjectWeak<'gc>),
     ShaderDataObject(ShaderDataObjectWeak<'gc>),
     SocketObject(SocketObjectWeak<'gc>),
+    StageVideoObject(StageVideoObjectWeak<'gc>),
 }
 
 impl<'gc> WeakObject<'gc> {
@@ -1517,6 +1530,7 @@ impl<'gc> WeakObject<'gc> {
             Self::NetStreamObject(o) => NetStreamObject(o.0.upgrade(mc)?).into(),
             Self::ShaderDataObject(o) => ShaderDataObject(o.0.upgrade(mc)?).into(),
             Self::SocketObject(o) => SocketObject(o.0.upgrade(mc)?).into(),
+            Self::StageVideoObject(o) => StageVideoObject(o.0.upgrade(mc)?).into(),
         })
     }
 }
@@ -872,6 +872,14 @@ impl<'gc> TObject<'gc> for ClassObject<'gc> {
 
         self.call_init(instance.into(), arguments, activation)?;
 
+        if activation.avm2().sampler().is_recording() {
+            let time = activation.context.elapsed_time().as_micros() as f64;
+            let mc = activation.context.gc_context;
+            let sampler = activation.avm2().sampler_mut();
+            sampler.record(self, instance, time);
+            sampler.prune(mc);
+        }
+
         Ok(instance)
     }
 
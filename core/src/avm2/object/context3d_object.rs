@@ -8,6 +8,7 @@ use crate::avm2::Error;
 use crate::avm2_stub_method;
 use crate::bitmap::bitmap_data::BitmapData;
 use crate::context::RenderContext;
+use crate::string::WStr;
 use gc_arena::barrier::unlock;
 use gc_arena::lock::RefLock;
 use gc_arena::{Collect, Gc, GcCell, GcWeak, Mutation};
@@ -16,7 +17,7 @@ use ruffle_render::backend::{
     Context3DTextureFormat, Context3DTriangleFace, Context3DVertexBufferFormat, ProgramType,
     Texture,
 };
-use ruffle_render::bitmap::{Bitmap, BitmapFormat};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle, BitmapInfo};
 use ruffle_render::commands::CommandHandler;
 use std::cell::{Cell, Ref, RefMut};
 use std::rc::Rc;
@@ -38,6 +39,7 @@ impl<'gc> Context3DObject<'gc> {
     pub fn from_context(
         activation: &mut Activation<'_, 'gc>,
         context: Box<dyn Context3D>,
+        profile: Context3DProfile,
     ) -> Result<Object<'gc>, Error<'gc>> {
         let class = activation.avm2().classes().context3d;
 
@@ -46,6 +48,7 @@ impl<'gc> Context3DObject<'gc> {
             Context3DData {
                 base: RefLock::new(ScriptObjectData::new(class)),
                 render_context: Cell::new(Some(context)),
+                profile,
             },
         ))
         .into();
@@ -124,19 +127,43 @@ impl<'gc> Context3DObject<'gc> {
         })?;
 
         Ok(Value::Object(TextureObject::from_handle(
-            activation, *self, texture, class,
+            activation,
+            *self,
+            texture,
+            (width, height),
+            class,
         )?))
     }
 
+    /// Creates a backend texture without an associated AVM2 object.
+    pub(crate) fn create_texture_handle(
+        &self,
+        width: u32,
+        height: u32,
+        format: Context3DTextureFormat,
+    ) -> Result<Rc<dyn Texture>, ruffle_render::error::Error> {
+        self.with_context_3d(|ctx| ctx.create_texture(width, height, format, false, 0))
+    }
+
+    pub fn profile(&self) -> Context3DProfile {
+        self.0.profile
+    }
+
     pub fn create_vertex_buffer(
         &self,
         num_vertices: u32,
         data_32_per_vertex: u8,
+        instances_per_element: u32,
         usage: BufferUsage,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<Value<'gc>, Error<'gc>> {
         let handle = self.with_context_3d(|ctx| {
-            ctx.create_vertex_buffer(usage, num_vertices, data_32_per_vertex)
+            ctx.create_vertex_buffer(
+                usage,
+                num_vertices,
+                data_32_per_vertex,
+                instances_per_element,
+            )
         });
         Ok(Value::Object(VertexBuffer3DObject::from_handle(
             activation,
@@ -229,10 +256,11 @@ impl<'gc> Context3DObject<'gc> {
         index_buffer: IndexBuffer3DObject<'gc>,
         first_index: u32,
         mut num_triangles: i32,
+        num_instances: u32,
     ) {
         if num_triangles == -1 {
             // FIXME - should we error if the number of indices isn't a multiple of 3?
-            num_triangles = (index_buffer.count() / 3) as i32;
+            num_triangles = (index_buffer.num_indices().saturating_sub(first_index) / 3) as i32;
         }
         let handle = index_buffer.handle();
 
@@ -241,6 +269,7 @@ impl<'gc> Context3DObject<'gc> {
                 index_buffer: &*handle,
                 first_index: first_index as usize,
                 num_triangles: num_triangles as isize,
+                num_instances,
             })
         });
     }
@@ -302,6 +331,11 @@ impl<'gc> Context3DObject<'gc> {
         Ok(self.with_context_3d(|ctx| activation.context.renderer.context3d_present(ctx))?)
     }
 
+    /// Submits any pending work, and returns the back buffer, if it has been configured.
+    pub(crate) fn back_buffer(&self) -> Option<BitmapInfo> {
+        self.with_context_3d(|ctx| ctx.back_buffer())
+    }
+
     // Renders our finalized frame to the screen, as part of the Ruffle rendering process.
     pub fn render(&self, context: &mut RenderContext<'_, 'gc>) {
         self.with_context_3d(|context3d| {
@@ -348,31 +382,51 @@ impl<'gc> Context3DObject<'gc> {
         layer: u32,
     ) {
         let source = source.read();
+        self.copy_pixels_to_texture(
+            Bitmap::new(
+                source.width(),
+                source.height(),
+                BitmapFormat::Rgba,
+                source.pixels_rgba(),
+            ),
+            dest,
+            layer,
+        );
+    }
+
+    pub(crate) fn copy_pixels_to_texture(&self, source: Bitmap, dest: Rc<dyn Texture>, layer: u32) {
         self.with_context_3d(|ctx| {
             ctx.process_command(Context3DCommand::CopyBitmapToTexture {
-                source: Bitmap::new(
-                    source.width(),
-                    source.height(),
-                    BitmapFormat::Rgba,
-                    source.pixels_rgba(),
-                ),
+                source,
                 dest,
                 layer,
             })
         });
     }
 
+    pub(crate) fn copy_bitmap_handle_to_texture(
+        &self,
+        source: BitmapHandle,
+        dest: Rc<dyn Texture>,
+    ) {
+        self.with_context_3d(|ctx| {
+            ctx.process_command(Context3DCommand::CopyBitmapHandleToTexture { source, dest })
+        });
+    }
+
     pub(crate) fn set_texture_at(
         &self,
         sampler: u32,
         texture: Option<Rc<dyn Texture>>,
         cube: bool,
+        clamp: bool,
     ) {
         self.with_context_3d(|ctx| {
             ctx.process_command(Context3DCommand::SetTextureAt {
                 sampler,
                 texture,
                 cube,
+                clamp,
             })
         });
     }
@@ -418,7 +472,11 @@ impl<'gc> Context3DObject<'gc> {
         let class = activation.avm2().classes().cubetexture;
 
         Ok(Value::Object(TextureObject::from_handle(
-            activation, *self, texture, class,
+            activation,
+            *self,
+            texture,
+            (size, size),
+            class,
         )?))
     }
 
@@ -427,12 +485,14 @@ impl<'gc> Context3DObject<'gc> {
         sampler: u32,
         wrap: ruffle_render::backend::Context3DWrapMode,
         filter: ruffle_render::backend::Context3DTextureFilter,
+        mip_filter: ruffle_render::backend::Context3DMipFilter,
     ) {
         self.with_context_3d(|ctx| {
             ctx.process_command(Context3DCommand::SetSamplerStateAt {
                 sampler,
                 wrap,
                 filter,
+                mip_filter,
             })
         });
     }
@@ -452,6 +512,65 @@ pub struct Context3DData<'gc> {
 
     #[collect(require_static)]
     render_context: Cell<Option<Box<dyn Context3D>>>,
+
+    /// The profile of this context, which is the closest supported one to what was requested.
+    #[collect(require_static)]
+    profile: Context3DProfile,
+}
+
+/// A set of Stage3D features, as requested by `Stage3D.requestContext3D`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Context3DProfile {
+    BaselineConstrained,
+    Baseline,
+    BaselineExtended,
+    StandardConstrained,
+    Standard,
+    StandardExtended,
+}
+
+impl Context3DProfile {
+    pub fn from_wstr(profile: &WStr) -> Option<Self> {
+        if profile == b"baselineConstrained" {
+            Some(Self::BaselineConstrained)
+        } else if profile == b"baseline" {
+            Some(Self::Baseline)
+        } else if profile == b"baselineExtended" {
+            Some(Self::BaselineExtended)
+        } else if profile == b"standardConstrained" {
+            Some(Self::StandardConstrained)
+        } else if profile == b"standard" {
+            Some(Self::Standard)
+        } else if profile == b"standardExtended" {
+            Some(Self::StandardExtended)
+        } else {
+            None
+        }
+    }
+
+    /// Whether we implement every feature of this profile.
+    ///
+    /// The standard profiles require multiple render targets and the fragment depth register,
+    /// which aren't implemented yet.
+    pub fn is_supported(self) -> bool {
+        self <= Self::BaselineExtended
+    }
+
+    /// The most capable supported profile that doesn't exceed this one.
+    pub fn closest_supported(self) -> Self {
+        self.min(Self::BaselineExtended)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::BaselineConstrained => "baselineConstrained",
+            Self::Baseline => "baseline",
+            Self::BaselineExtended => "baselineExtended",
+            Self::StandardConstrained => "standardConstrained",
+            Self::Standard => "standard",
+            Self::StandardExtended => "standardExtended",
+        }
+    }
 }
 
 impl<'gc> TObject<'gc> for Context3DObject<'gc> {
@@ -485,23 +604,12 @@ impl std::fmt::Debug for Context3DObject<'_> {
 // This would ideally be placed closer to the actual usage, but
 // we don't have stub support in 'render' crates
 fn check_texture_stub(activation: &mut Activation<'_, '_>, format: Context3DTextureFormat) {
-    match format {
-        Context3DTextureFormat::BgrPacked => {
-            avm2_stub_method!(
-                activation,
-                "flash.display3D.Context3D",
-                "createTexture",
-                "with BgrPacked"
-            );
-        }
-        Context3DTextureFormat::Compressed => {
-            avm2_stub_method!(
-                activation,
-                "flash.display3D.Context3D",
-                "createTexture",
-                "with Compressed"
-            );
-        }
-        _ => {}
+    if matches!(format, Context3DTextureFormat::BgrPacked) {
+        avm2_stub_method!(
+            activation,
+            "flash.display3D.Context3D",
+            "createTexture",
+            "with BgrPacked"
+        );
     }
 }
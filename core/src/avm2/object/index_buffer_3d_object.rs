@@ -9,7 +9,7 @@ use gc_arena::barrier::unlock;
 use gc_arena::lock::RefLock;
 use gc_arena::{Collect, Gc, GcWeak, Mutation};
 use ruffle_render::backend::IndexBuffer;
-use std::cell::{Cell, Ref, RefCell, RefMut};
+use std::cell::{Ref, RefCell, RefMut};
 
 use super::Context3DObject;
 
@@ -35,7 +35,6 @@ impl<'gc> IndexBuffer3DObject<'gc> {
                 base: RefLock::new(ScriptObjectData::new(class)),
                 context3d,
                 handle: RefCell::new(handle),
-                count: Cell::new(0),
             },
         ))
         .into();
@@ -46,12 +45,9 @@ impl<'gc> IndexBuffer3DObject<'gc> {
         Ok(this)
     }
 
-    pub fn count(&self) -> usize {
-        self.0.count.get()
-    }
-
-    pub fn set_count(&self, val: usize) {
-        self.0.count.set(val);
+    /// The number of indices this buffer was created with.
+    pub fn num_indices(&self) -> u32 {
+        self.0.handle.borrow().num_indices()
     }
 
     pub fn handle(&self) -> RefMut<'_, dyn IndexBuffer> {
@@ -71,8 +67,6 @@ pub struct IndexBuffer3DObjectData<'gc> {
 
     handle: RefCell<Box<dyn IndexBuffer>>,
 
-    count: Cell<usize>,
-
     context3d: Context3DObject<'gc>,
 }
 
@@ -3,6 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use crate::avm2::Namespace;
@@ -22,6 +23,7 @@ pub fn namespace_allocator<'gc>(
         NamespaceObjectData {
             base,
             namespace: activation.context.avm2.public_namespace,
+            prefix: None,
         },
     ))
     .into())
@@ -52,6 +54,9 @@ pub struct NamespaceObjectData<'gc> {
 
     /// The namespace name this object is associated with.
     namespace: Namespace<'gc>,
+
+    /// The prefix this namespace is declared with in XML, if it has one.
+    prefix: Option<AvmString<'gc>>,
 }
 
 impl<'gc> NamespaceObject<'gc> {
@@ -65,7 +70,11 @@ impl<'gc> NamespaceObject<'gc> {
 
         let mut this: Object<'gc> = NamespaceObject(GcCell::new(
             activation.context.gc_context,
-            NamespaceObjectData { base, namespace },
+            NamespaceObjectData {
+                base,
+                namespace,
+                prefix: None,
+            },
         ))
         .into();
         this.install_instance_slots(activation.context.gc_context);
@@ -82,6 +91,14 @@ impl<'gc> NamespaceObject<'gc> {
     pub fn namespace(self) -> Namespace<'gc> {
         return self.0.read().namespace;
     }
+
+    pub fn prefix(self) -> Option<AvmString<'gc>> {
+        self.0.read().prefix
+    }
+
+    pub fn set_prefix(&self, mc: MutationContext<'gc, '_>, prefix: Option<AvmString<'gc>>) {
+        self.0.write(mc).prefix = prefix;
+    }
 }
 
 impl<'gc> TObject<'gc> for NamespaceObject<'gc> {
@@ -398,6 +398,16 @@ impl<'gc> ScriptObjectData<'gc> {
     }
 
     /// Gets the number of (standard) enumerants.
+    /// The number of slots on this object.
+    pub fn num_slots(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// The number of dynamic properties on this object.
+    pub fn num_values(&self) -> usize {
+        self.values.len()
+    }
+
     pub fn num_enumerants(&self) -> u32 {
         self.enumerants.len() as u32
     }
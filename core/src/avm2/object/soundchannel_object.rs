@@ -28,6 +28,7 @@ pub fn sound_channel_allocator<'gc>(
                 should_stop: false,
             },
             position: 0.0,
+            playback_rate: 1.0,
         },
     ))
     .into())
@@ -61,6 +62,9 @@ pub struct SoundChannelObjectData<'gc> {
 
     /// Position of the last playing sound in milliseconds.
     position: f64,
+
+    /// How fast the sound plays, relative to its normal speed.
+    playback_rate: f64,
 }
 
 #[derive(Collect)]
@@ -90,6 +94,7 @@ impl<'gc> SoundChannelObject<'gc> {
                     should_stop: false,
                 },
                 position: 0.0,
+                playback_rate: 1.0,
             },
         ));
         sound_object.install_instance_slots(activation.context.gc_context);
@@ -126,6 +131,7 @@ impl<'gc> SoundChannelObject<'gc> {
         instance: SoundInstanceHandle,
     ) {
         let mut this = self.0.write(activation.context.gc_context);
+        let playback_rate = this.playback_rate;
         match &mut this.sound_channel_data {
             SoundChannelData::NotLoaded {
                 sound_transform,
@@ -137,6 +143,13 @@ impl<'gc> SoundChannelObject<'gc> {
                         .set_local_sound_transform(instance, sound_transform.clone());
                 }
 
+                if playback_rate != 1.0 {
+                    activation
+                        .context
+                        .audio
+                        .set_sound_playback_rate(instance, playback_rate);
+                }
+
                 if *should_stop {
                     activation.context.stop_sound(instance);
                 }
@@ -185,6 +198,20 @@ impl<'gc> SoundChannelObject<'gc> {
         }
     }
 
+    pub fn playback_rate(self) -> f64 {
+        self.0.read().playback_rate
+    }
+
+    pub fn set_playback_rate(self, context: &mut UpdateContext<'_, 'gc>, playback_rate: f64) {
+        let mut this = self.0.write(context.gc_context);
+        this.playback_rate = playback_rate;
+        if let SoundChannelData::Loaded { sound_instance } = this.sound_channel_data {
+            context
+                .audio
+                .set_sound_playback_rate(sound_instance, playback_rate);
+        }
+    }
+
     pub fn stop(self, activation: &mut Activation<'_, 'gc>) {
         let mut this = self.0.write(activation.context.gc_context);
         match &mut this.sound_channel_data {
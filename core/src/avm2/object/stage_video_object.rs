@@ -0,0 +1,137 @@
+//! Object representation for StageVideo objects
+
+use crate::avm2::activation::Activation;
+use crate::avm2::object::script_object::ScriptObjectData;
+use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::streams::NetStream;
+use core::fmt;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::{Lock, RefLock};
+use gc_arena::{Collect, Gc, GcWeak, Mutation};
+use std::cell::{Cell, Ref, RefCell, RefMut};
+use swf::{Rectangle, Twips};
+
+/// A class instance allocator that allocates StageVideo objects.
+pub fn stage_video_allocator<'gc>(
+    class: ClassObject<'gc>,
+    activation: &mut Activation<'_, 'gc>,
+) -> Result<Object<'gc>, Error<'gc>> {
+    Ok(StageVideoObject(Gc::new(
+        activation.gc(),
+        StageVideoObjectData {
+            base: RefLock::new(ScriptObjectData::new(class)),
+            net_stream: Lock::new(None),
+            view_port: RefCell::new(Default::default()),
+            depth: Cell::new(0),
+            pan: Cell::new((0.0, 0.0)),
+            zoom: Cell::new((1.0, 1.0)),
+        },
+    ))
+    .into())
+}
+
+#[derive(Clone, Collect, Copy)]
+#[collect(no_drop)]
+pub struct StageVideoObject<'gc>(pub Gc<'gc, StageVideoObjectData<'gc>>);
+
+#[derive(Clone, Collect, Copy, Debug)]
+#[collect(no_drop)]
+pub struct StageVideoObjectWeak<'gc>(pub GcWeak<'gc, StageVideoObjectData<'gc>>);
+
+impl fmt::Debug for StageVideoObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StageVideoObject")
+            .field("ptr", &Gc::as_ptr(self.0))
+            .finish()
+    }
+}
+
+impl<'gc> StageVideoObject<'gc> {
+    pub fn net_stream(self) -> Option<NetStream<'gc>> {
+        self.0.net_stream.get()
+    }
+
+    pub fn set_net_stream(self, net_stream: Option<NetStream<'gc>>, mc: &Mutation<'gc>) {
+        unlock!(Gc::write(mc, self.0), StageVideoObjectData, net_stream).set(net_stream)
+    }
+
+    pub fn view_port(self) -> Rectangle<Twips> {
+        self.0.view_port.borrow().clone()
+    }
+
+    pub fn set_view_port(self, view_port: Rectangle<Twips>) {
+        *self.0.view_port.borrow_mut() = view_port;
+    }
+
+    pub fn depth(self) -> i32 {
+        self.0.depth.get()
+    }
+
+    pub fn set_depth(self, depth: i32) {
+        self.0.depth.set(depth);
+    }
+
+    pub fn pan(self) -> (f64, f64) {
+        self.0.pan.get()
+    }
+
+    pub fn set_pan(self, pan: (f64, f64)) {
+        self.0.pan.set(pan);
+    }
+
+    pub fn zoom(self) -> (f64, f64) {
+        self.0.zoom.get()
+    }
+
+    pub fn set_zoom(self, zoom: (f64, f64)) {
+        self.0.zoom.set(zoom);
+    }
+}
+
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct StageVideoObjectData<'gc> {
+    /// Base script object
+    base: RefLock<ScriptObjectData<'gc>>,
+
+    /// The stream whose video is shown, if one was attached with `attachNetStream`.
+    net_stream: Lock<Option<NetStream<'gc>>>,
+
+    /// The area of the stage that the video fills.
+    #[collect(require_static)]
+    view_port: RefCell<Rectangle<Twips>>,
+
+    /// The order of this video relative to other stage videos, which are drawn from the
+    /// lowest depth up.
+    depth: Cell<i32>,
+
+    /// Which part of a zoomed video is shown, from -1 to 1 in each direction.
+    pan: Cell<(f64, f64)>,
+
+    /// How much the video is magnified, which is at least 1 in each direction.
+    zoom: Cell<(f64, f64)>,
+}
+
+impl<'gc> TObject<'gc> for StageVideoObject<'gc> {
+    fn base(&self) -> Ref<ScriptObjectData<'gc>> {
+        self.0.base.borrow()
+    }
+
+    fn base_mut(&self, mc: &Mutation<'gc>) -> RefMut<ScriptObjectData<'gc>> {
+        unlock!(Gc::write(mc, self.0), StageVideoObjectData, base).borrow_mut()
+    }
+
+    fn as_ptr(&self) -> *const ObjectPtr {
+        Gc::as_ptr(self.0) as *const ObjectPtr
+    }
+
+    fn value_of(&self, _mc: &Mutation<'gc>) -> Result<Value<'gc>, Error<'gc>> {
+        Ok(Value::Object(Object::from(*self)))
+    }
+
+    fn as_stage_video(&self) -> Option<StageVideoObject<'gc>> {
+        Some(*self)
+    }
+}
@@ -5,11 +5,13 @@ use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::streams::NetStream;
 use gc_arena::barrier::unlock;
-use gc_arena::lock::RefLock;
+use gc_arena::lock::{Lock, RefLock};
 use gc_arena::{Collect, Gc, GcWeak, Mutation};
-use ruffle_render::backend::Texture;
-use std::cell::{Ref, RefMut};
+use ruffle_render::backend::{Context3DTextureFormat, Texture};
+use ruffle_render::bitmap::BitmapInfo;
+use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::rc::Rc;
 
 use super::{ClassObject, Context3DObject};
@@ -27,6 +29,7 @@ impl<'gc> TextureObject<'gc> {
         activation: &mut Activation<'_, 'gc>,
         context3d: Context3DObject<'gc>,
         handle: Rc<dyn Texture>,
+        size: (u32, u32),
         class: ClassObject<'gc>,
     ) -> Result<Object<'gc>, Error<'gc>> {
         let mut this: Object<'gc> = TextureObject(Gc::new(
@@ -34,7 +37,10 @@ impl<'gc> TextureObject<'gc> {
             TextureObjectData {
                 base: RefLock::new(ScriptObjectData::new(class)),
                 context3d,
-                handle,
+                handle: RefCell::new(handle),
+                size,
+                video_source: Lock::new(None),
+                video_size: Cell::new((0, 0)),
             },
         ))
         .into();
@@ -46,7 +52,47 @@ impl<'gc> TextureObject<'gc> {
     }
 
     pub fn handle(&self) -> Rc<dyn Texture> {
-        self.0.handle.clone()
+        self.0.handle.borrow().clone()
+    }
+
+    /// The width and height that the texture was created with.
+    pub fn size(&self) -> (u32, u32) {
+        self.0.size
+    }
+
+    /// The `NetStream` feeding this `VideoTexture`, if any.
+    pub fn video_source(&self) -> Option<NetStream<'gc>> {
+        self.0.video_source.get()
+    }
+
+    pub fn set_video_source(&self, mc: &Mutation<'gc>, source: Option<NetStream<'gc>>) {
+        unlock!(Gc::write(mc, self.0), TextureObjectData, video_source).set(source);
+    }
+
+    /// The size of the last video frame copied into this `VideoTexture`.
+    pub fn video_size(&self) -> (u32, u32) {
+        self.0.video_size.get()
+    }
+
+    /// Copies a decoded video frame into this `VideoTexture`.
+    ///
+    /// If the frame size has changed, the underlying texture is recreated to match.
+    pub fn copy_video_frame(&self, frame: &BitmapInfo) -> Result<(), ruffle_render::error::Error> {
+        let size = (frame.width as u32, frame.height as u32);
+        if size != self.video_size() {
+            let handle = self.0.context3d.create_texture_handle(
+                size.0,
+                size.1,
+                Context3DTextureFormat::Bgra,
+            )?;
+            *self.0.handle.borrow_mut() = handle;
+            self.0.video_size.set(size);
+        }
+
+        self.0
+            .context3d
+            .copy_bitmap_handle_to_texture(frame.handle.clone(), self.handle());
+        Ok(())
     }
 
     pub fn context3d(&self) -> Context3DObject<'gc> {
@@ -62,8 +108,18 @@ pub struct TextureObjectData<'gc> {
 
     context3d: Context3DObject<'gc>,
 
+    /// The backend texture. This is only ever replaced for `VideoTexture`s,
+    /// whose size follows the attached video.
+    #[collect(require_static)]
+    handle: RefCell<Rc<dyn Texture>>,
+
+    #[collect(require_static)]
+    size: (u32, u32),
+
+    video_source: Lock<Option<NetStream<'gc>>>,
+
     #[collect(require_static)]
-    handle: Rc<dyn Texture>,
+    video_size: Cell<(u32, u32)>,
 }
 
 impl<'gc> TObject<'gc> for TextureObject<'gc> {
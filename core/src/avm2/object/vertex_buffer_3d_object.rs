@@ -59,6 +59,11 @@ impl<'gc> VertexBuffer3DObject<'gc> {
     pub fn data32_per_vertex(&self) -> u8 {
         self.0.data32_per_vertex
     }
+
+    /// The number of vertices this buffer was created with.
+    pub fn num_vertices(&self) -> u32 {
+        self.0.handle.num_vertices()
+    }
 }
 
 #[derive(Collect)]
@@ -351,6 +351,48 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
         if !name.is_any_name() && !name.is_attribute() {
             if let Some(local_name) = name.local_name() {
                 if let Ok(index) = local_name.parse::<usize>() {
+                    let value_xml = value.as_object().and_then(|obj| obj.as_xml_object());
+
+                    if let Some(child) = write.children.get(index) {
+                        let node = *child.node();
+                        match value_xml {
+                            // Replacing a node also replaces it in its parent, so that the
+                            // change is reflected in the original XML.
+                            Some(value_xml) => {
+                                if let Some(parent) = node.parent() {
+                                    let position = match &*parent.kind() {
+                                        E4XNodeKind::Element { children, .. } => {
+                                            children.iter().position(|c| E4XNode::ptr_eq(*c, node))
+                                        }
+                                        _ => None,
+                                    };
+                                    if let Some(position) = position {
+                                        parent.replace_child_at(
+                                            activation,
+                                            position,
+                                            &[*value_xml.node()],
+                                        )?;
+                                    }
+                                }
+                                write.children[index] = E4XOrXml::Xml(value_xml);
+                            }
+                            // Anything else replaces the content of the node.
+                            None => {
+                                let mc = activation.context.gc_context;
+                                let text = value.coerce_to_string(activation)?;
+                                let is_attribute =
+                                    matches!(&*node.kind(), E4XNodeKind::Attribute(_));
+                                if is_attribute {
+                                    *node.kind_mut(mc) = E4XNodeKind::Attribute(text);
+                                } else {
+                                    node.remove_all_children(mc);
+                                    node.append_child(mc, E4XNode::text(mc, text, None))?;
+                                }
+                            }
+                        }
+                        return Ok(());
+                    }
+
                     if let Some(target) = write.target {
                         return Err(format!(
                             "Modifying an XMLList object is not yet implemented: target {:?}",
@@ -359,13 +401,11 @@ impl<'gc> TObject<'gc> for XmlListObject<'gc> {
                         .into());
                     }
 
-                    if index >= write.children.len() {
-                        if let Some(value_xml) =
-                            value.as_object().and_then(|obj| obj.as_xml_object())
-                        {
-                            write.children.push(E4XOrXml::Xml(value_xml));
-                            return Ok(());
-                        }
+                    // Assigning past the end appends to the list. Filter expressions
+                    // (`list.(@id == 1)`) build their results this way.
+                    if let Some(value_xml) = value_xml {
+                        write.children.push(E4XOrXml::Xml(value_xml));
+                        return Ok(());
                     }
                 }
             }
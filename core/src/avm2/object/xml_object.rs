@@ -1,7 +1,7 @@
 //! Object representation for XML objects
 
 use crate::avm2::activation::Activation;
-use crate::avm2::e4x::{name_to_multiname, E4XNode, E4XNodeKind};
+use crate::avm2::e4x::{name_to_multiname, E4XNamespace, E4XNode, E4XNodeKind};
 use crate::avm2::object::script_object::ScriptObjectData;
 use crate::avm2::object::{ClassObject, Object, ObjectPtr, TObject, XmlListObject};
 use crate::avm2::string::AvmString;
@@ -182,36 +182,35 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
                     }
                 }
             }
+        }
 
-            let matched_children = if let E4XNodeKind::Element {
-                children,
-                attributes,
-            } = &*read.node.kind()
-            {
-                let search_children = if name.is_attribute() {
-                    attributes
-                } else {
-                    children
-                };
-
-                search_children
-                    .iter()
-                    .filter_map(|child| {
-                        if child.matches_name(name) {
-                            Some(E4XOrXml::E4X(*child))
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<_>>()
+        // Names with an explicit namespace (`xml.ns::child`) match the children in that namespace.
+        let matched_children = if let E4XNodeKind::Element {
+            children,
+            attributes,
+        } = &*read.node.kind()
+        {
+            let search_children = if name.is_attribute() {
+                attributes
             } else {
-                Vec::new()
+                children
             };
 
-            return Ok(XmlListObject::new(activation, matched_children, Some(self.into())).into());
-        }
+            search_children
+                .iter()
+                .filter_map(|child| {
+                    if child.matches_name(name) {
+                        Some(E4XOrXml::E4X(*child))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        } else {
+            Vec::new()
+        };
 
-        read.base.get_property_local(name, activation)
+        Ok(XmlListObject::new(activation, matched_children, Some(self.into())).into())
     }
 
     fn call_property_local(
@@ -258,26 +257,26 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
 
         // FIXME - see if we can deduplicate this with get_property_local in
         // an efficient way
-        if !name.has_explicit_namespace() {
-            if let Some(local_name) = name.local_name() {
-                // The only supported numerical index is 0
+        if let Some(local_name) = name.local_name() {
+            // The only supported numerical index is 0
+            if !name.has_explicit_namespace() {
                 if let Ok(index) = local_name.parse::<usize>() {
                     return index == 0;
                 }
+            }
 
-                if let E4XNodeKind::Element {
-                    children,
-                    attributes,
-                } = &*read.node.kind()
-                {
-                    let search_children = if name.is_attribute() {
-                        attributes
-                    } else {
-                        children
-                    };
+            if let E4XNodeKind::Element {
+                children,
+                attributes,
+            } = &*read.node.kind()
+            {
+                let search_children = if name.is_attribute() {
+                    attributes
+                } else {
+                    children
+                };
 
-                    return search_children.iter().any(|child| child.matches_name(name));
-                }
+                return search_children.iter().any(|child| child.matches_name(name));
             }
         }
         read.base.has_own_dynamic_property(name)
@@ -298,15 +297,17 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc>,
     ) -> Result<(), Error<'gc>> {
-        if name.has_explicit_namespace() {
-            return Err(format!(
-                "Can not set property {:?} with an explicit namespace yet",
-                name
-            )
-            .into());
-        }
-
         let mc = activation.context.gc_context;
+        let self_node = *self.node();
+
+        // New elements and attributes are created in the namespace of the name, if it has one.
+        let namespace = if name.has_explicit_namespace() {
+            name.namespace_set()
+                .first()
+                .map(|ns| E4XNamespace::new(None, ns.as_uri()))
+        } else {
+            None
+        };
 
         if name.is_attribute() {
             self.delete_property_local(activation, name)?;
@@ -323,10 +324,10 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
                 return Err(format!("Cannot set attribute {:?} without a local name", name).into());
             };
             let value = value.coerce_to_string(activation)?;
-            let new_attr = E4XNode::attribute(mc, local_name, value, *self.node());
+            let new_attr = E4XNode::attribute(mc, local_name, value, self_node);
+            new_attr.set_namespace(namespace, mc);
 
-            let write = self.0.write(mc);
-            let mut kind = write.node.kind_mut(mc);
+            let mut kind = self_node.kind_mut(mc);
             let E4XNodeKind::Element { attributes, .. } = &mut *kind else {
                 return Ok(());
             };
@@ -335,45 +336,62 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
             return Ok(());
         }
 
-        let self_node = *self.node();
-        let write = self.0.write(mc);
-        let mut kind = write.node.kind_mut(mc);
-        let E4XNodeKind::Element { children, .. } = &mut *kind else {
+        if !matches!(&*self_node.kind(), E4XNodeKind::Element { .. }) {
             return Ok(());
-        };
-
-        if value.as_object().map_or(false, |obj| {
-            obj.as_xml_object().is_some() || obj.as_xml_list_object().is_some()
-        }) {
-            return Err(
-                format!("Cannot set an XML/XMLList object {value:?} as an element yet").into(),
-            );
         }
 
         if name.is_any_name() {
             return Err("Any name (*) not yet implemented for set".into());
         }
 
-        let text = value.coerce_to_string(activation)?;
-
-        let matches: Vec<_> = children
-            .iter()
-            .filter(|child| child.matches_name(name))
-            .collect();
-        match matches.as_slice() {
-            [] => {
-                let element_with_text =
-                    E4XNode::element(mc, name.local_name().unwrap(), write.node);
-                element_with_text.append_child(mc, E4XNode::text(mc, text, Some(self_node)))?;
-                children.push(element_with_text);
-                Ok(())
+        // XML values replace the matching child, and anything else replaces its content.
+        let is_xml = value.as_object().map_or(false, |obj| {
+            obj.as_xml_object().is_some() || obj.as_xml_list_object().is_some()
+        });
+        let new_nodes = if is_xml {
+            Some(E4XNode::nodes_from_value(value, activation)?)
+        } else {
+            None
+        };
+
+        let (matching, child_count) = match &*self_node.kind() {
+            E4XNodeKind::Element { children, .. } => (
+                children
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, child)| child.matches_name(name))
+                    .map(|(index, child)| (index, *child))
+                    .collect::<Vec<_>>(),
+                children.len(),
+            ),
+            _ => (Vec::new(), 0),
+        };
+
+        // Only the first matching child is kept.
+        for &(index, child) in matching.iter().skip(1).rev() {
+            if let E4XNodeKind::Element { children, .. } = &mut *self_node.kind_mut(mc) {
+                children.remove(index);
+            }
+            child.set_parent(None, mc);
+        }
+
+        match (matching.first(), new_nodes) {
+            (Some(&(index, _)), Some(nodes)) => {
+                self_node.replace_child_at(activation, index, &nodes)
             }
-            [child] => {
+            (None, Some(nodes)) => self_node.insert_children(activation, child_count, &nodes),
+            (Some(&(_, child)), None) => {
+                let text = value.coerce_to_string(activation)?;
                 child.remove_all_children(mc);
-                child.append_child(mc, E4XNode::text(mc, text, Some(self_node)))?;
-                Ok(())
+                child.append_child(mc, E4XNode::text(mc, text, Some(child)))
+            }
+            (None, None) => {
+                let text = value.coerce_to_string(activation)?;
+                let element_with_text = E4XNode::element(mc, name.local_name().unwrap(), self_node);
+                element_with_text.set_namespace(namespace, mc);
+                element_with_text.append_child(mc, E4XNode::text(mc, text, None))?;
+                self_node.append_child(mc, element_with_text)
             }
-            _ => Err(format!("Can not replace multiple elements yet: {name:?} = {value:?}").into()),
         }
     }
 
@@ -382,14 +400,6 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
         activation: &mut Activation<'_, 'gc>,
         name: &Multiname<'gc>,
     ) -> Result<bool, Error<'gc>> {
-        if name.has_explicit_namespace() {
-            return Err(format!(
-                "Can not set property {:?} with an explicit namespace yet",
-                name
-            )
-            .into());
-        }
-
         let mc = activation.context.gc_context;
         let write = self.0.write(mc);
         let mut kind = write.node.kind_mut(mc);
@@ -418,4 +428,39 @@ impl<'gc> TObject<'gc> for XmlObject<'gc> {
         }
         Ok(true)
     }
+
+    fn get_next_enumerant(
+        self,
+        last_index: u32,
+        _activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Option<u32>, Error<'gc>> {
+        // An XML object enumerates like an XMLList holding only itself, which is
+        // what lets filter expressions (`xml.(@id == 1)`) work on XML objects.
+        // As with XMLList, the prototype chain is never enumerated.
+        Ok(Some(if last_index == 0 { 1 } else { 0 }))
+    }
+
+    fn get_enumerant_value(
+        self,
+        index: u32,
+        _activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        if index == 1 {
+            Ok(self.into())
+        } else {
+            Ok(Value::Undefined)
+        }
+    }
+
+    fn get_enumerant_name(
+        self,
+        index: u32,
+        _activation: &mut Activation<'_, 'gc>,
+    ) -> Result<Value<'gc>, Error<'gc>> {
+        if index == 1 {
+            Ok(0.into())
+        } else {
+            Ok(Value::Undefined)
+        }
+    }
 }
@@ -0,0 +1,101 @@
+//! Pre-decoded method bodies for frequently called methods
+
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::verify::Instruction;
+use crate::avm2::vtable::VTable;
+use gc_arena::barrier::unlock;
+use gc_arena::lock::RefLock;
+use gc_arena::{Collect, Gc, Mutation};
+use swf::avm2::types::{MethodBody, Op};
+
+/// How many times a method has to be called before its body is decoded ahead of time.
+///
+/// Most methods only run a handful of times (such as script and class initializers), so
+/// they're interpreted straight from bytecode rather than spending memory on slot caches
+/// and offset tables for them.
+pub const HOT_METHOD_THRESHOLD: u32 = 10;
+
+/// Marks offsets in the method body that aren't the start of an instruction.
+const NO_INSTRUCTION: u32 = u32::MAX;
+
+/// A method body that has been decoded into a list of ops, so that running it doesn't
+/// require reading them from bytecode each time.
+///
+/// This also holds an inline cache for each property access, which remembers where the
+/// property was found on the last object it was used on.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct DecodedBody<'gc> {
+    /// Each instruction, along with the offset of the one after it.
+    #[collect(require_static)]
+    ops: Vec<(Op, usize)>,
+
+    /// The index of the instruction at each offset in the method body.
+    #[collect(require_static)]
+    instruction_at: Vec<u32>,
+
+    /// The vtable of the last object each instruction accessed a slot on, along with the
+    /// slot that the property was in.
+    slot_caches: RefLock<Vec<Option<(VTable<'gc>, u32)>>>,
+}
+
+impl<'gc> DecodedBody<'gc> {
+    /// Build a decoded body from the instructions that were decoded while verifying it.
+    pub fn new(
+        mc: &Mutation<'gc>,
+        body: &MethodBody,
+        instructions: Vec<Instruction>,
+    ) -> Gc<'gc, Self> {
+        let mut instruction_at = vec![NO_INSTRUCTION; body.code.len()];
+        for (index, instruction) in instructions.iter().enumerate() {
+            instruction_at[instruction.start] = index as u32;
+        }
+        let ops: Vec<_> = instructions
+            .into_iter()
+            .map(|instruction| (instruction.op, instruction.end))
+            .collect();
+
+        let slot_caches = RefLock::new(vec![None; ops.len()]);
+        Gc::new(
+            mc,
+            Self {
+                ops,
+                instruction_at,
+                slot_caches,
+            },
+        )
+    }
+
+    /// Get the instruction at an offset in the method body, if one starts there.
+    pub fn instruction_at(&self, offset: usize) -> Option<usize> {
+        self.instruction_at
+            .get(offset)
+            .filter(|index| **index != NO_INSTRUCTION)
+            .map(|index| *index as usize)
+    }
+
+    /// Get the op of an instruction, and the offset of the instruction after it.
+    pub fn op(&self, instruction: usize) -> (&Op, usize) {
+        let (op, next) = &self.ops[instruction];
+        (op, *next)
+    }
+
+    /// Get the slot that an instruction last accessed, if the object has the same vtable.
+    pub fn cached_slot(&self, instruction: usize, object: Object<'gc>) -> Option<u32> {
+        let (vtable, slot_id) = self.slot_caches.borrow()[instruction]?;
+        let object_vtable = object.vtable()?;
+        VTable::ptr_eq(vtable, object_vtable).then_some(slot_id)
+    }
+
+    /// Remember which slot an instruction accessed on an object with the given vtable.
+    pub fn cache_slot(
+        this: Gc<'gc, Self>,
+        mc: &Mutation<'gc>,
+        instruction: usize,
+        vtable: VTable<'gc>,
+        slot_id: u32,
+    ) {
+        unlock!(Gc::write(mc, this), DecodedBody, slot_caches).borrow_mut()[instruction] =
+            Some((vtable, slot_id));
+    }
+}
@@ -0,0 +1,239 @@
+//! Allocation tracking for `flash.sampler` and memory profiling
+
+use crate::avm2::object::{ClassObject, Object, TObject, WeakObject};
+use crate::avm2::Value;
+use fnv::FnvHashMap;
+use gc_arena::{Collect, MutationContext};
+use std::collections::BTreeMap;
+
+/// The size that every object is assumed to take up, before counting its properties.
+const OBJECT_SIZE: usize = 40;
+
+/// The size of a single property value.
+const VALUE_SIZE: usize = 8;
+
+/// An object allocation that was recorded by the sampler.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct Allocation<'gc> {
+    /// The sample ID of this allocation, as seen by `NewObjectSample.id`.
+    pub id: f64,
+
+    /// The time this object was allocated, in microseconds since the player started.
+    pub time: f64,
+
+    /// The class that this object was constructed from.
+    pub class: ClassObject<'gc>,
+
+    /// The size of the object when it was constructed.
+    pub size: f64,
+
+    /// The object itself, which isn't kept alive by the sampler.
+    pub object: WeakObject<'gc>,
+}
+
+/// Records object allocations, for use by `flash.sampler` and memory profiles.
+///
+/// Allocations are only recorded while a movie has started sampling, or while a
+/// memory profile has been requested, so that the cost is only paid when it's needed.
+#[derive(Collect, Default)]
+#[collect(no_drop)]
+pub struct Sampler<'gc> {
+    /// Whether the movie has called `startSampling`.
+    sampling: bool,
+
+    /// Whether the sampler has been paused with `pauseSampling`.
+    paused: bool,
+
+    /// Whether allocations are being tracked for a memory profile.
+    profiling: bool,
+
+    /// The ID of the next allocation.
+    next_id: f64,
+
+    /// Allocations that haven't been returned by `getSamples` yet.
+    samples: Vec<Allocation<'gc>>,
+
+    /// Every tracked allocation that may still be alive, by ID.
+    live: FnvHashMap<u64, Allocation<'gc>>,
+
+    /// The number of live allocations after they were last pruned.
+    live_after_prune: usize,
+}
+
+impl<'gc> Sampler<'gc> {
+    /// Whether allocations are currently being recorded.
+    pub fn is_recording(&self) -> bool {
+        (self.sampling && !self.paused) || self.profiling
+    }
+
+    pub fn start_sampling(&mut self) {
+        self.sampling = true;
+        self.paused = false;
+    }
+
+    pub fn stop_sampling(&mut self) {
+        self.sampling = false;
+        self.paused = false;
+        self.samples.clear();
+    }
+
+    pub fn pause_sampling(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn clear_samples(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Take every sample recorded since the last call.
+    pub fn take_samples(&mut self) -> Vec<Allocation<'gc>> {
+        std::mem::take(&mut self.samples)
+    }
+
+    /// The number of samples that haven't been taken yet.
+    pub fn sample_count(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Start or stop tracking allocations for memory profiles.
+    pub fn set_profiling(&mut self, profiling: bool) {
+        self.profiling = profiling;
+    }
+
+    /// Record an object that was just constructed.
+    pub fn record(&mut self, class: ClassObject<'gc>, object: Object<'gc>, time: f64) {
+        let id = self.next_id;
+        self.next_id += 1.0;
+
+        let allocation = Allocation {
+            id,
+            time,
+            class,
+            size: object_size(object) as f64,
+            object: object.downgrade(),
+        };
+
+        if self.sampling && !self.paused {
+            self.samples.push(allocation);
+        }
+        self.live.insert(id as u64, allocation);
+    }
+
+    /// Look up a tracked object by its sample ID, if it's still alive.
+    pub fn object(&self, mc: MutationContext<'gc, '_>, id: f64) -> Option<Object<'gc>> {
+        self.live.get(&(id as u64))?.object.upgrade(mc)
+    }
+
+    /// Look up the size of a tracked object when it was allocated, if it's still alive.
+    pub fn size(&self, id: f64) -> Option<f64> {
+        Some(self.live.get(&(id as u64))?.size)
+    }
+
+    /// Forget about tracked objects that have been garbage collected.
+    ///
+    /// This is done whenever the number of tracked objects doubles, so that the list of
+    /// live objects doesn't grow forever.
+    pub fn prune(&mut self, mc: MutationContext<'gc, '_>) {
+        if self.live.len() < self.live_after_prune.max(1024) * 2 {
+            return;
+        }
+        self.live
+            .retain(|_, allocation| allocation.object.upgrade(mc).is_some());
+        self.live_after_prune = self.live.len();
+    }
+
+    /// Count the tracked objects that are still alive, by the name of their class.
+    pub fn memory_profile(&mut self, mc: MutationContext<'gc, '_>) -> MemoryProfile {
+        let mut classes: BTreeMap<String, ClassProfile> = BTreeMap::new();
+        self.live.retain(|_, allocation| {
+            let Some(object) = allocation.object.upgrade(mc) else {
+                return false;
+            };
+            let name = allocation
+                .class
+                .inner_class_definition()
+                .read()
+                .name()
+                .to_qualified_name_err_message(mc)
+                .to_string();
+            let class = classes.entry(name).or_default();
+            class.count += 1;
+            class.size += object_size(object);
+            true
+        });
+        self.live_after_prune = self.live.len();
+
+        let mut classes: Vec<_> = classes.into_iter().collect();
+        classes.sort_by(|(_, a), (_, b)| b.count.cmp(&a.count));
+        MemoryProfile { classes }
+    }
+}
+
+/// The number of live objects of each class, as tracked by the sampler.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryProfile {
+    /// Each class name with its live objects, sorted from most to fewest objects.
+    pub classes: Vec<(String, ClassProfile)>,
+}
+
+impl MemoryProfile {
+    /// Write this profile out as CSV, with one row for each class.
+    pub fn to_csv(&self) -> String {
+        let mut csv = "class,count,size\n".to_string();
+        for (name, class) in &self.classes {
+            csv.push_str(&format!(
+                "\"{}\",{},{}\n",
+                name.replace('"', "\"\""),
+                class.count,
+                class.size
+            ));
+        }
+        csv
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ClassProfile {
+    /// The number of objects of this class that are still alive.
+    pub count: usize,
+
+    /// The estimated size of those objects, in bytes.
+    pub size: usize,
+}
+
+/// Estimate how much memory a value takes up, in bytes.
+///
+/// This is used for `flash.sampler.getSize`. Sizes won't match Flash Player exactly, but
+/// they grow with the number of properties and elements in the same way.
+pub fn value_size(value: Value<'_>) -> usize {
+    match value {
+        Value::Undefined | Value::Null => 0,
+        Value::Bool(_) => 4,
+        Value::Number(_) => 8,
+        Value::Integer(_) => 4,
+        Value::String(s) => 24 + s.len() * if s.is_wide() { 2 } else { 1 },
+        Value::Object(o) => object_size(o),
+    }
+}
+
+/// Estimate how much memory an object takes up, in bytes.
+pub fn object_size(object: Object<'_>) -> usize {
+    let mut size = OBJECT_SIZE;
+    {
+        let base = object.base();
+        size += (base.num_slots() + base.num_values()) * VALUE_SIZE;
+    }
+
+    if let Some(array) = object.as_array_storage() {
+        size += array.length() * VALUE_SIZE;
+    } else if let Some(vector) = object.as_vector_storage() {
+        size += vector.length() * VALUE_SIZE;
+    } else if let Some(bytes) = object.as_bytearray() {
+        size += bytes.len();
+    } else if let Some(bitmap_data) = object.as_bitmap_data() {
+        size += (bitmap_data.width() * bitmap_data.height() * 4) as usize;
+    }
+
+    size
+}
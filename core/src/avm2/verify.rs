@@ -0,0 +1,302 @@
+//! AVM2 bytecode verification
+
+use crate::avm2::error::verify_error;
+use crate::avm2::method::BytecodeMethod;
+use crate::avm2::{Activation, Error};
+use crate::swf::extensions::ReadSwfExt;
+use swf::avm2::read::Reader;
+use swf::avm2::types::{AbcFile, MethodBody, Op};
+use swf::error::Error as SwfError;
+
+/// A single decoded instruction, along with where it starts and ends in the method body.
+#[derive(Clone)]
+pub struct Instruction {
+    pub op: Op,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Instruction {
+    /// The offsets that this instruction may branch to.
+    fn branch_targets(&self) -> Vec<i64> {
+        match &self.op {
+            Op::IfEq { offset }
+            | Op::IfFalse { offset }
+            | Op::IfGe { offset }
+            | Op::IfGt { offset }
+            | Op::IfLe { offset }
+            | Op::IfLt { offset }
+            | Op::IfNe { offset }
+            | Op::IfNge { offset }
+            | Op::IfNgt { offset }
+            | Op::IfNle { offset }
+            | Op::IfNlt { offset }
+            | Op::IfStrictEq { offset }
+            | Op::IfStrictNe { offset }
+            | Op::IfTrue { offset }
+            | Op::Jump { offset } => vec![self.end as i64 + *offset as i64],
+            // Unlike other branches, `lookupswitch` offsets are relative to the start of
+            // the instruction.
+            Op::LookupSwitch {
+                default_offset,
+                case_offsets,
+            } => std::iter::once(default_offset)
+                .chain(case_offsets.iter())
+                .map(|offset| self.start as i64 + *offset as i64)
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// Whether execution can continue on to the next instruction after this one.
+    fn falls_through(&self) -> bool {
+        !matches!(
+            self.op,
+            Op::Jump { .. }
+                | Op::LookupSwitch { .. }
+                | Op::ReturnValue
+                | Op::ReturnVoid
+                | Op::Throw
+        )
+    }
+}
+
+/// Check a method body for malformed bytecode before it is run for the first time.
+///
+/// This rejects code that would otherwise misbehave in the interpreter with the same
+/// `VerifyError`s that Flash Player throws for it. The instructions that were decoded along
+/// the way are returned, so that the method body doesn't have to be decoded again.
+pub fn verify_method<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: &BytecodeMethod<'gc>,
+) -> Result<Vec<Instruction>, Error<'gc>> {
+    let Some(body) = method.body() else {
+        return Ok(Vec::new());
+    };
+
+    if body.code.is_empty() {
+        return Err(Error::AvmError(verify_error(
+            activation,
+            "Error #1043: Invalid code_length=0.",
+            1043,
+        )?));
+    }
+
+    let instructions = decode(activation, method, body)?;
+
+    // Maps each byte offset in the method body to the instruction that starts there.
+    let mut instruction_at = vec![None; body.code.len()];
+    for (index, instruction) in instructions.iter().enumerate() {
+        instruction_at[instruction.start] = Some(index);
+    }
+    let instruction_index = |offset: i64| {
+        usize::try_from(offset)
+            .ok()
+            .and_then(|offset| instruction_at.get(offset).copied().flatten())
+    };
+
+    for instruction in &instructions {
+        check_operands(activation, method.abc().as_ref(), body, instruction)?;
+
+        for target in instruction.branch_targets() {
+            if instruction_index(target).is_none() {
+                return Err(Error::AvmError(verify_error(
+                    activation,
+                    "Error #1021: At least one branch target was not on a valid instruction in the method.",
+                    1021,
+                )?));
+            }
+        }
+    }
+
+    let mut handlers = Vec::with_capacity(body.exceptions.len());
+    for exception in &body.exceptions {
+        let target = instruction_index(exception.target_offset as i64);
+        if exception.from_offset > exception.to_offset
+            || exception.to_offset as usize > body.code.len()
+            || target.is_none()
+        {
+            return Err(Error::AvmError(verify_error(
+                activation,
+                "Error #1054: Illegal range or target offsets in exception handler.",
+                1054,
+            )?));
+        }
+        handlers.extend(target);
+    }
+
+    // Walk every instruction that can be reached, to make sure none of them can run past
+    // the end of the method. Unreachable code is never run, so it isn't checked.
+    let mut reachable = vec![false; instructions.len()];
+    let mut pending = vec![0];
+    pending.extend(handlers);
+    while let Some(index) = pending.pop() {
+        if reachable[index] {
+            continue;
+        }
+        reachable[index] = true;
+
+        let instruction = &instructions[index];
+        pending.extend(
+            instruction
+                .branch_targets()
+                .into_iter()
+                .filter_map(instruction_index),
+        );
+
+        if instruction.falls_through() {
+            if index + 1 >= instructions.len() {
+                return Err(Error::AvmError(verify_error(
+                    activation,
+                    "Error #1020: Code cannot fall off the end of a method.",
+                    1020,
+                )?));
+            }
+            pending.push(index + 1);
+        }
+    }
+
+    Ok(instructions)
+}
+
+/// Decode every instruction in a method body.
+fn decode<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    method: &BytecodeMethod<'gc>,
+    body: &MethodBody,
+) -> Result<Vec<Instruction>, Error<'gc>> {
+    let mut instructions = vec![];
+    let mut reader = Reader::new(&body.code);
+
+    loop {
+        let start = reader.pos(&body.code);
+        if start >= body.code.len() {
+            break;
+        }
+
+        match reader.read_op() {
+            // `callmethod` is never generated by compilers, and Flash Player refuses to run it.
+            Ok(Op::CallMethod { .. }) | Err(SwfError::InvalidData(_)) => {
+                let message = format!(
+                    "Error #1011: Method {} contained illegal opcode {} at offset {}.",
+                    method.method_name(),
+                    body.code[start],
+                    start
+                );
+                return Err(Error::AvmError(verify_error(activation, &message, 1011)?));
+            }
+            Ok(op) => instructions.push(Instruction {
+                op,
+                start,
+                end: reader.pos(&body.code),
+            }),
+            // The operands of the last instruction run past the end of the method.
+            Err(_) => {
+                return Err(Error::AvmError(verify_error(
+                    activation,
+                    "Error #1020: Code cannot fall off the end of a method.",
+                    1020,
+                )?));
+            }
+        }
+    }
+
+    Ok(instructions)
+}
+
+/// Check that the registers, scopes and constant pool entries used by an instruction exist.
+fn check_operands<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    abc: &AbcFile,
+    body: &MethodBody,
+    instruction: &Instruction,
+) -> Result<(), Error<'gc>> {
+    let pool = &abc.constant_pool;
+
+    // Entry 0 of each constant pool is implicit, so it isn't included in the pool's length.
+    let (index, count) = match &instruction.op {
+        Op::GetLocal { index }
+        | Op::SetLocal { index }
+        | Op::Kill { index }
+        | Op::IncLocal { index }
+        | Op::IncLocalI { index }
+        | Op::DecLocal { index }
+        | Op::DecLocalI { index } => return check_register(activation, body, *index),
+        Op::HasNext2 {
+            object_register,
+            index_register,
+        } => {
+            check_register(activation, body, *object_register)?;
+            return check_register(activation, body, *index_register);
+        }
+        Op::GetScopeObject { index } => {
+            let max_scope = body.max_scope_depth.saturating_sub(body.init_scope_depth);
+            if *index as u32 >= max_scope {
+                let message = format!("Error #1019: Getscopeobject {index} is out of bounds.");
+                return Err(Error::AvmError(verify_error(activation, &message, 1019)?));
+            }
+            return Ok(());
+        }
+        Op::NewFunction { index } | Op::CallStatic { index, .. } => {
+            if index.0 as usize >= abc.methods.len() {
+                let message = format!(
+                    "Error #1027: Method_info {} exceeds method_count={}.",
+                    index.0,
+                    abc.methods.len()
+                );
+                return Err(Error::AvmError(verify_error(activation, &message, 1027)?));
+            }
+            return Ok(());
+        }
+        Op::PushInt { value } => (value.0, pool.ints.len()),
+        Op::PushUint { value } => (value.0, pool.uints.len()),
+        Op::PushDouble { value } => (value.0, pool.doubles.len()),
+        Op::PushString { value } => (value.0, pool.strings.len()),
+        Op::Dxns { index } => (index.0, pool.strings.len()),
+        Op::PushNamespace { value } => (value.0, pool.namespaces.len()),
+        Op::GetProperty { index }
+        | Op::SetProperty { index }
+        | Op::InitProperty { index }
+        | Op::DeleteProperty { index }
+        | Op::GetSuper { index }
+        | Op::SetSuper { index }
+        | Op::FindDef { index }
+        | Op::FindProperty { index }
+        | Op::FindPropStrict { index }
+        | Op::GetLex { index }
+        | Op::GetDescendants { index }
+        | Op::Coerce { index }
+        | Op::AsType { type_name: index }
+        | Op::IsType { index }
+        | Op::CallProperty { index, .. }
+        | Op::CallPropLex { index, .. }
+        | Op::CallPropVoid { index, .. }
+        | Op::CallSuper { index, .. }
+        | Op::CallSuperVoid { index, .. }
+        | Op::ConstructProp { index, .. } => (index.0, pool.multinames.len()),
+        _ => return Ok(()),
+    };
+
+    if index as usize > count {
+        let message = format!(
+            "Error #1032: Cpool index {} is out of range {}.",
+            index,
+            count + 1
+        );
+        return Err(Error::AvmError(verify_error(activation, &message, 1032)?));
+    }
+
+    Ok(())
+}
+
+fn check_register<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    body: &MethodBody,
+    register: u32,
+) -> Result<(), Error<'gc>> {
+    if register >= body.num_locals {
+        let message = format!("Error #1025: An invalid register {register} was accessed.");
+        return Err(Error::AvmError(verify_error(activation, &message, 1025)?));
+    }
+    Ok(())
+}
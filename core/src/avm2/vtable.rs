@@ -135,6 +135,10 @@ impl<'gc> VTable<'gc> {
             .map(|c| c.get_name(mc))
     }
 
+    pub fn ptr_eq(a: VTable<'gc>, b: VTable<'gc>) -> bool {
+        GcCell::ptr_eq(a.0, b.0)
+    }
+
     pub fn get_trait(self, name: &Multiname<'gc>) -> Option<Property> {
         if name.is_attribute() {
             return None;
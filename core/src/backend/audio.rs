@@ -1,6 +1,9 @@
 use crate::{
     avm1::SoundObject,
-    avm2::{Avm2, EventObject as Avm2EventObject, SoundChannelObject},
+    avm2::{
+        Activation as Avm2Activation, Avm2, EventObject as Avm2EventObject, Object as Avm2Object,
+        SoundChannelObject, TObject as _,
+    },
     context::UpdateContext,
     display_object::{self, DisplayObject, MovieClip, TDisplayObject},
 };
@@ -23,9 +26,20 @@ mod mixer;
 pub use mixer::*;
 
 #[cfg(not(feature = "audio"))]
-mod decoders {
+pub mod decoders {
     #[derive(Debug, thiserror::Error)]
     pub enum Error {}
+
+    /// Stands in for the decoder of `NetStream` audio, which is dropped without the
+    /// `audio` feature.
+    #[derive(Debug, Default)]
+    pub struct FlvAudioDecoder;
+
+    impl FlvAudioDecoder {
+        pub fn decode(&mut self, _audio: flv_rs::AudioData<'_>) -> Vec<[f32; 2]> {
+            Vec::new()
+        }
+    }
 }
 
 use instant::Duration;
@@ -96,6 +110,10 @@ pub trait AudioBackend: Downcast {
     /// Set the volume transform for a sound instance.
     fn set_sound_transform(&mut self, instance: SoundInstanceHandle, transform: SoundTransform);
 
+    /// Set how fast a sound instance plays, relative to its normal speed.
+    /// This also changes its pitch.
+    fn set_sound_playback_rate(&mut self, instance: SoundInstanceHandle, rate: f64);
+
     fn get_sound_peak(&mut self, instance: SoundInstanceHandle) -> Option<[f32; 2]>;
 
     // TODO: Eventually remove this/move it to library.
@@ -123,6 +141,32 @@ pub trait AudioBackend: Downcast {
         None
     }
 
+    /// Returns the names of the microphones that audio can be captured from.
+    fn microphone_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Starts capturing mono audio from the microphone at `index` in `microphone_names`,
+    /// replacing any previous capture.
+    ///
+    /// The user may first be asked for permission, so capture may only begin once
+    /// `microphone_state` returns `MicrophoneState::Allowed`.
+    fn start_microphone(&mut self, _index: usize) {}
+
+    /// Stops capturing audio from the microphone.
+    fn stop_microphone(&mut self) {}
+
+    /// Returns the state of the current microphone capture.
+    fn microphone_state(&self) -> MicrophoneState {
+        MicrophoneState::Unavailable
+    }
+
+    /// Returns the audio captured from the microphone since the last call,
+    /// along with its sample rate in Hz.
+    fn take_microphone_samples(&mut self) -> (u32, Vec<f32>) {
+        (44100, Vec::new())
+    }
+
     /// Returns the master volume of the audio backend.
     fn volume(&self) -> f32;
 
@@ -131,10 +175,59 @@ pub trait AudioBackend: Downcast {
 
     /// Returns the last whole window of output samples.
     fn get_sample_history(&self) -> [[f32; 2]; 1024];
+
+    /// Decodes up to `num_sample_frames` sample frames of a sound as 44.1kHz stereo, for
+    /// `Sound.extract`.
+    ///
+    /// Starts at `start_sample_frame`, or where the previous extraction of this sound
+    /// stopped if `None`. Returns `None` if the sound can't be decoded.
+    fn extract_sound(
+        &mut self,
+        sound: SoundHandle,
+        start_sample_frame: Option<u32>,
+        num_sample_frames: u32,
+    ) -> Option<Vec<[f32; 2]>>;
+
+    /// Starts playing a dynamic sound, whose 44.1kHz stereo samples are supplied over time by
+    /// `SampleDataEvent` handlers. At most `capacity` sample frames can be queued at once.
+    ///
+    /// Returns `None` if this backend doesn't support dynamic sounds.
+    fn start_dynamic_sound(&mut self, capacity: usize) -> Option<SoundInstanceHandle>;
+
+    /// Queues sample frames for a dynamic sound, returning how many fit in its queue.
+    fn append_dynamic_sound(
+        &mut self,
+        instance: SoundInstanceHandle,
+        samples: &[[f32; 2]],
+    ) -> usize;
+
+    /// Returns the number of sample frames queued for a dynamic sound that haven't played yet,
+    /// or `None` if the sound is no longer playing.
+    fn dynamic_sound_buffered(&mut self, instance: SoundInstanceHandle) -> Option<usize>;
+
+    /// Ends a dynamic sound once its queued sample frames have played.
+    fn end_dynamic_sound(&mut self, instance: SoundInstanceHandle);
 }
 
 impl_downcast!(AudioBackend);
 
+/// The state of a microphone capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub enum MicrophoneState {
+    /// No microphone is being captured, or it can't be captured from.
+    Unavailable,
+
+    /// The user hasn't yet decided whether to allow access to the microphone.
+    Pending,
+
+    /// Audio is being captured from the microphone.
+    Allowed,
+
+    /// The user denied access to the microphone.
+    Denied,
+}
+
 /// Information about a sound provided to `NullAudioBackend`.
 struct NullSound {
     /// The duration of the sound in milliseconds.
@@ -243,6 +336,8 @@ impl AudioBackend for NullAudioBackend {
 
     fn set_sound_transform(&mut self, _instance: SoundInstanceHandle, _transform: SoundTransform) {}
 
+    fn set_sound_playback_rate(&mut self, _instance: SoundInstanceHandle, _rate: f64) {}
+
     fn get_sound_peak(&mut self, _instance: SoundInstanceHandle) -> Option<[f32; 2]> {
         None
     }
@@ -258,6 +353,33 @@ impl AudioBackend for NullAudioBackend {
     fn get_sample_history(&self) -> [[f32; 2]; 1024] {
         [[0.0f32; 2]; 1024]
     }
+
+    fn extract_sound(
+        &mut self,
+        _sound: SoundHandle,
+        _start_sample_frame: Option<u32>,
+        _num_sample_frames: u32,
+    ) -> Option<Vec<[f32; 2]>> {
+        None
+    }
+
+    fn start_dynamic_sound(&mut self, _capacity: usize) -> Option<SoundInstanceHandle> {
+        None
+    }
+
+    fn append_dynamic_sound(
+        &mut self,
+        _instance: SoundInstanceHandle,
+        _samples: &[[f32; 2]],
+    ) -> usize {
+        0
+    }
+
+    fn dynamic_sound_buffered(&mut self, _instance: SoundInstanceHandle) -> Option<usize> {
+        None
+    }
+
+    fn end_dynamic_sound(&mut self, _instance: SoundInstanceHandle) {}
 }
 
 impl Default for NullAudioBackend {
@@ -284,6 +406,9 @@ pub struct AudioManager<'gc> {
 
     /// Whether a sound transform has been changed.
     transforms_dirty: bool,
+
+    /// The AVM2 `Microphone` that audio is being captured for, if any.
+    microphone: Option<MicrophoneCapture<'gc>>,
 }
 
 impl<'gc> AudioManager<'gc> {
@@ -301,12 +426,25 @@ impl<'gc> AudioManager<'gc> {
     /// The player will adjust animation speed to stay within this many seconds of the audio track.
     pub const STREAM_DEFAULT_SYNC_THRESHOLD: f64 = 0.2;
 
+    /// The fewest sample frames a `SampleDataEvent` handler can supply before its sound ends.
+    pub const MIN_SAMPLE_DATA_FRAMES: usize = 2048;
+
+    /// The most sample frames a `SampleDataEvent` handler can supply at once.
+    pub const MAX_SAMPLE_DATA_FRAMES: usize = 8192;
+
+    /// The number of sample frames that can be queued for a dynamic sound.
+    const DYNAMIC_SOUND_CAPACITY: usize = Self::MAX_SAMPLE_DATA_FRAMES * 4;
+
+    /// The maximum number of `SampleDataEvent`s dispatched to a sound in a single tick.
+    const MAX_SAMPLE_DATA_REQUESTS: usize = 4;
+
     pub fn new() -> Self {
         Self {
             sounds: Vec::with_capacity(Self::MAX_SOUNDS),
             global_sound_transform: Default::default(),
             stream_buffer_time: Self::DEFAULT_STREAM_BUFFER_TIME,
             transforms_dirty: false,
+            microphone: None,
         }
     }
 
@@ -384,6 +522,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object,
                 avm2_object: None,
                 stream_start_frame: None,
+                sample_data_source: None,
+                sample_data_position: 0,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -393,6 +533,367 @@ impl<'gc> AudioManager<'gc> {
         }
     }
 
+    /// Starts playing a dynamic sound, whose samples are requested from `source` by
+    /// dispatching `SampleDataEvent`s to it.
+    pub fn start_dynamic_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        source: Avm2Object<'gc>,
+    ) -> Option<SoundInstanceHandle> {
+        if self.sounds.len() < Self::MAX_SOUNDS {
+            let handle = audio.start_dynamic_sound(Self::DYNAMIC_SOUND_CAPACITY)?;
+            let instance = SoundInstance {
+                sound: None,
+                instance: handle,
+                display_object: None,
+                transform: display_object::SoundTransform::default(),
+                avm1_object: None,
+                avm2_object: None,
+                stream_start_frame: None,
+                sample_data_source: Some(source),
+                sample_data_position: 0,
+            };
+            audio.set_sound_transform(handle, self.transform_for_sound(&instance));
+            self.sounds.push(instance);
+            Some(handle)
+        } else {
+            None
+        }
+    }
+
+    /// Requests samples from the sources of dynamic sounds until each has at least
+    /// `target_latency` sample frames queued.
+    pub fn update_dynamic_sounds(context: &mut UpdateContext<'_, 'gc>, target_latency: usize) {
+        // Leave room for a full request on top of the target latency.
+        let target_latency =
+            target_latency.min(Self::DYNAMIC_SOUND_CAPACITY - Self::MAX_SAMPLE_DATA_FRAMES);
+        let sources: Vec<_> = context
+            .audio_manager
+            .sounds
+            .iter()
+            .filter_map(|sound| Some((sound.instance, sound.sample_data_source?)))
+            .collect();
+
+        for (instance, source) in sources {
+            // Cap the requests per tick, so that a source that supplies tiny amounts
+            // of samples can't stall the player.
+            for _ in 0..Self::MAX_SAMPLE_DATA_REQUESTS {
+                match context.audio.dynamic_sound_buffered(instance) {
+                    Some(buffered) if buffered < target_latency => {}
+                    _ => break,
+                }
+                if !Self::request_sample_data(context, instance, source) {
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Dispatches a `SampleDataEvent` to `source` and queues the samples it writes.
+    ///
+    /// Returns `false` if the source supplied too few samples, which ends the sound.
+    fn request_sample_data(
+        context: &mut UpdateContext<'_, 'gc>,
+        instance: SoundInstanceHandle,
+        source: Avm2Object<'gc>,
+    ) -> bool {
+        let Some(i) = context
+            .audio_manager
+            .sounds
+            .iter()
+            .position(|sound| sound.instance == instance)
+        else {
+            return false;
+        };
+        let position = context.audio_manager.sounds[i].sample_data_position;
+
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let data = match activation
+            .avm2()
+            .classes()
+            .bytearray
+            .construct(&mut activation, &[])
+        {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::error!(
+                    "Encountered AVM2 error when constructing `ByteArray`: {}",
+                    e
+                );
+                return false;
+            }
+        };
+        let event = match activation.avm2().classes().sampledataevent.construct(
+            &mut activation,
+            &[
+                "sampleData".into(),
+                false.into(),
+                false.into(),
+                (position as f64).into(),
+                data.into(),
+            ],
+        ) {
+            Ok(event) => event,
+            Err(e) => {
+                tracing::error!(
+                    "Encountered AVM2 error when constructing `SampleDataEvent`: {}",
+                    e
+                );
+                return false;
+            }
+        };
+        Avm2::dispatch_event(&mut activation.context, event, source);
+
+        // The source writes pairs of 32-bit floats, one for each channel.
+        let samples: Vec<[f32; 2]> = data
+            .as_bytearray()
+            .map(|data| {
+                (0..data.len() / 8)
+                    .map(|i| {
+                        [
+                            data.read_float_at(i * 8).unwrap_or_default(),
+                            data.read_float_at(i * 8 + 4).unwrap_or_default(),
+                        ]
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let samples = &samples[..samples.len().min(Self::MAX_SAMPLE_DATA_FRAMES)];
+        context.audio.append_dynamic_sound(instance, samples);
+
+        let Some(sound) = context
+            .audio_manager
+            .sounds
+            .iter_mut()
+            .find(|sound| sound.instance == instance)
+        else {
+            return false;
+        };
+        sound.sample_data_position += samples.len() as u64;
+        if samples.len() < Self::MIN_SAMPLE_DATA_FRAMES {
+            // Like Flash, play out the remaining samples and then end the sound.
+            sound.sample_data_source = None;
+            context.audio.end_dynamic_sound(instance);
+            return false;
+        }
+        true
+    }
+
+    /// Starts capturing audio for an AVM2 `Microphone`, or updates the settings of the
+    /// capture if it's already running.
+    pub fn start_microphone(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        object: Avm2Object<'gc>,
+        settings: MicrophoneSettings,
+    ) {
+        let previous = match self.microphone.take() {
+            Some(capture) if Avm2Object::ptr_eq(capture.object, object) => Some(capture),
+            Some(capture) => {
+                // Only one microphone can be captured at once.
+                if let Some(instance) = capture.loop_back_instance {
+                    audio.stop_sound(instance);
+                }
+                None
+            }
+            None => None,
+        };
+
+        if previous.as_ref().map(|capture| capture.settings.index) != Some(settings.index) {
+            audio.start_microphone(settings.index);
+        }
+
+        let mut loop_back_instance = previous.as_ref().and_then(|c| c.loop_back_instance);
+        match (settings.loop_back, loop_back_instance) {
+            (true, None) => {
+                loop_back_instance = audio.start_dynamic_sound(Self::DYNAMIC_SOUND_CAPACITY);
+            }
+            (false, Some(instance)) => {
+                audio.stop_sound(instance);
+                loop_back_instance = None;
+            }
+            _ => {}
+        }
+
+        self.microphone = Some(MicrophoneCapture {
+            object,
+            settings,
+            state: previous
+                .as_ref()
+                .map_or(MicrophoneState::Unavailable, |c| c.state),
+            activity_level: previous.as_ref().map_or(0.0, |c| c.activity_level),
+            is_active: previous.as_ref().map_or(false, |c| c.is_active),
+            position: previous.as_ref().map_or(0, |c| c.position),
+            loop_back_instance,
+        });
+    }
+
+    /// Stops capturing audio for an AVM2 `Microphone`.
+    pub fn stop_microphone(&mut self, audio: &mut dyn AudioBackend, object: Avm2Object<'gc>) {
+        if let Some(capture) = &self.microphone {
+            if Avm2Object::ptr_eq(capture.object, object) {
+                if let Some(instance) = capture.loop_back_instance {
+                    audio.stop_sound(instance);
+                }
+                audio.stop_microphone();
+                self.microphone = None;
+            }
+        }
+    }
+
+    /// Returns the activity level of an AVM2 `Microphone` from 0 to 100,
+    /// or -1 if it isn't capturing audio.
+    pub fn microphone_activity_level(&self, object: Avm2Object<'gc>) -> f64 {
+        match &self.microphone {
+            Some(capture) if Avm2Object::ptr_eq(capture.object, object) => capture.activity_level,
+            _ => -1.0,
+        }
+    }
+
+    /// Returns whether the user has not allowed an AVM2 `Microphone` to capture audio.
+    pub fn is_microphone_muted(&self, object: Avm2Object<'gc>) -> bool {
+        !matches!(
+            &self.microphone,
+            Some(capture) if Avm2Object::ptr_eq(capture.object, object)
+                && capture.state == MicrophoneState::Allowed
+        )
+    }
+
+    /// Delivers the audio captured from the microphone to its AVM2 `Microphone`.
+    ///
+    /// This dispatches `status` events when the user allows or denies access, `activity`
+    /// events when the audio crosses the silence level, and `sampleData` events with the
+    /// captured samples.
+    pub fn update_microphone(context: &mut UpdateContext<'_, 'gc>) {
+        let Some(capture) = &mut context.audio_manager.microphone else {
+            return;
+        };
+        let object = capture.object;
+        let settings = capture.settings;
+
+        let state = context.audio.microphone_state();
+        let status_code = match state {
+            _ if state == capture.state => None,
+            MicrophoneState::Allowed => Some("Microphone.Unmuted"),
+            MicrophoneState::Denied => Some("Microphone.Muted"),
+            MicrophoneState::Unavailable | MicrophoneState::Pending => None,
+        };
+        capture.state = state;
+
+        let (sample_rate, samples) = context.audio.take_microphone_samples();
+        let gain = (settings.gain / 50.0) as f32;
+        let samples: Vec<f32> = samples
+            .into_iter()
+            .map(|sample| (sample * gain).clamp(-1.0, 1.0))
+            .collect();
+
+        let mut activity_changed = None;
+        if !samples.is_empty() {
+            let peak = samples.iter().fold(0.0f32, |peak, s| peak.max(s.abs()));
+            capture.activity_level = (f64::from(peak) * 100.0).round();
+            let is_active = capture.activity_level >= settings.silence_level;
+            if is_active != capture.is_active {
+                capture.is_active = is_active;
+                activity_changed = Some(is_active);
+            }
+        }
+
+        if let Some(instance) = capture.loop_back_instance {
+            let loop_back: Vec<[f32; 2]> = resample(&samples, sample_rate, 44100)
+                .into_iter()
+                .map(|sample| [sample, sample])
+                .collect();
+            context.audio.append_dynamic_sound(instance, &loop_back);
+        }
+
+        let samples = resample(&samples, sample_rate, settings.rate);
+        let position = capture.position;
+        capture.position += samples.len() as u64;
+
+        if let Some(code) = status_code {
+            Self::dispatch_event(
+                context,
+                object,
+                |classes| classes.statusevent,
+                &[
+                    "status".into(),
+                    false.into(),
+                    false.into(),
+                    code.into(),
+                    "status".into(),
+                ],
+            );
+        }
+
+        if let Some(activating) = activity_changed {
+            Self::dispatch_event(
+                context,
+                object,
+                |classes| classes.activityevent,
+                &[
+                    "activity".into(),
+                    false.into(),
+                    false.into(),
+                    activating.into(),
+                ],
+            );
+        }
+
+        if samples.is_empty() {
+            return;
+        }
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let data = match activation
+            .avm2()
+            .classes()
+            .bytearray
+            .construct(&mut activation, &[])
+        {
+            Ok(data) => data,
+            Err(e) => {
+                tracing::error!(
+                    "Encountered AVM2 error when constructing `ByteArray`: {}",
+                    e
+                );
+                return;
+            }
+        };
+        if let Some(mut bytearray) = data.as_bytearray_mut(activation.context.gc_context) {
+            for sample in samples {
+                let _ = bytearray.write_float(sample);
+            }
+            bytearray.set_position(0);
+        }
+        Self::dispatch_event(
+            context,
+            object,
+            |classes| classes.sampledataevent,
+            &[
+                "sampleData".into(),
+                false.into(),
+                false.into(),
+                (position as f64).into(),
+                data.into(),
+            ],
+        );
+    }
+
+    /// Constructs an AVM2 event of the given class and dispatches it to `target`.
+    fn dispatch_event(
+        context: &mut UpdateContext<'_, 'gc>,
+        target: Avm2Object<'gc>,
+        class: impl FnOnce(&SystemClasses<'gc>) -> ClassObject<'gc>,
+        args: &[Avm2Value<'gc>],
+    ) {
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let class = class(activation.avm2().classes());
+        match class.construct(&mut activation, args) {
+            Ok(event) => Avm2::dispatch_event(context, event, target),
+            Err(e) => tracing::error!("Encountered AVM2 error when constructing event: {}", e),
+        }
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
@@ -481,6 +982,8 @@ impl<'gc> AudioManager<'gc> {
                 avm1_object: None,
                 avm2_object: None,
                 stream_start_frame: Some(clip_frame),
+                sample_data_source: None,
+                sample_data_position: 0,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -663,6 +1166,79 @@ pub struct SoundInstance<'gc> {
     avm2_object: Option<SoundChannelObject<'gc>>,
 
     stream_start_frame: Option<u16>,
+
+    /// The AVM2 `Sound` object that supplies the samples of a dynamic sound.
+    ///
+    /// This is cleared once the sound has stopped requesting samples.
+    sample_data_source: Option<Avm2Object<'gc>>,
+
+    /// The number of sample frames supplied to a dynamic sound so far.
+    sample_data_position: u64,
+}
+
+/// The settings of an AVM2 `Microphone` that affect how its audio is captured.
+#[derive(Debug, Clone, Copy, Collect)]
+#[collect(require_static)]
+pub struct MicrophoneSettings {
+    /// The index of the microphone in `AudioBackend::microphone_names`.
+    pub index: usize,
+
+    /// The sample rate to deliver captured audio at, in Hz.
+    pub rate: u32,
+
+    /// The amplification of the captured audio from 0 to 100, where 50 leaves it unchanged.
+    pub gain: f64,
+
+    /// The activity level from 0 to 100 above which the microphone is considered active.
+    pub silence_level: f64,
+
+    /// Whether captured audio is also played through the speakers.
+    pub loop_back: bool,
+}
+
+/// An AVM2 `Microphone` that audio is being captured for.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct MicrophoneCapture<'gc> {
+    /// The AVM2 `Microphone` object.
+    object: Avm2Object<'gc>,
+
+    settings: MicrophoneSettings,
+
+    /// The state of the capture when it was last updated.
+    state: MicrophoneState,
+
+    /// The loudness of the last captured audio from 0 to 100.
+    activity_level: f64,
+
+    /// Whether the activity level was last above the silence level.
+    is_active: bool,
+
+    /// The number of samples delivered so far.
+    position: u64,
+
+    /// The dynamic sound that plays the captured audio, if looping back.
+    #[collect(require_static)]
+    loop_back_instance: Option<SoundInstanceHandle>,
+}
+
+/// Linearly resamples mono audio from one sample rate to another.
+pub(crate) fn resample(samples: &[f32], from: u32, to: u32) -> Vec<f32> {
+    if from == to || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let step = f64::from(from) / f64::from(to);
+    let len = (samples.len() as f64 / step) as usize;
+    (0..len)
+        .map(|i| {
+            let position = i as f64 * step;
+            let index = position as usize;
+            let fraction = (position - index as f64) as f32;
+            let current = samples[index];
+            let next = samples.get(index + 1).copied().unwrap_or(current);
+            current + (next - current) * fraction
+        })
+        .collect()
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
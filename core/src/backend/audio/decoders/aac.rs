@@ -0,0 +1,72 @@
+use std::fmt;
+use symphonia::core::{
+    audio,
+    codecs::{self, Decoder as _},
+    errors,
+    formats::Packet,
+};
+use symphonia::default::codecs::AacDecoder as SymphoniaAacDecoder;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Couldn't decode AAC frame")]
+    FrameDecode(#[from] errors::Error),
+}
+
+/// Decoder for the raw AAC frames of an FLV or MP4 stream.
+///
+/// Only AAC-LC is supported. Unlike the other decoders, this is fed one frame at a
+/// time, since the frames are spread across tags that arrive while the stream plays.
+pub struct AacDecoder {
+    decoder: SymphoniaAacDecoder,
+    sample_buf: Option<audio::SampleBuffer<i16>>,
+}
+
+impl AacDecoder {
+    /// Creates a decoder for the stream described by an `AudioSpecificConfig`.
+    pub fn new(config: &[u8]) -> Result<Self, Error> {
+        let mut codec_params = codecs::CodecParameters::new();
+        codec_params
+            .for_codec(codecs::CODEC_TYPE_AAC)
+            .with_extra_data(config.into());
+        let decoder = SymphoniaAacDecoder::try_new(&codec_params, &Default::default())?;
+        Ok(Self {
+            decoder,
+            sample_buf: None,
+        })
+    }
+
+    /// Decodes a frame, returning its sample rate and stereo sample frames.
+    /// If the stream is mono, the sample is duplicated across both channels.
+    pub fn decode(&mut self, frame: &[u8]) -> Result<(u32, Vec<[i16; 2]>), Error> {
+        let packet = Packet::new_from_slice(0, 0, 0, frame);
+        let decoded = self.decoder.decode(&packet)?;
+        let spec = *decoded.spec();
+        let capacity = decoded.capacity() * spec.channels.count();
+        if self
+            .sample_buf
+            .as_ref()
+            .map_or(false, |sample_buf| sample_buf.capacity() < capacity)
+        {
+            self.sample_buf = None;
+        }
+        let sample_buf = self
+            .sample_buf
+            .get_or_insert_with(|| audio::SampleBuffer::new(decoded.capacity() as u64, spec));
+        sample_buf.copy_interleaved_ref(decoded);
+
+        let samples = sample_buf
+            .samples()
+            .chunks_exact(spec.channels.count().max(1))
+            .map(|frame| [frame[0], *frame.get(1).unwrap_or(&frame[0])])
+            .collect();
+        Ok((spec.rate, samples))
+    }
+}
+
+impl fmt::Debug for AacDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AacDecoder").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(feature = "aac")]
+use super::AacDecoder;
+use super::{make_decoder, Decoder};
+use crate::backend::audio::resample;
+use flv_rs::{
+    AudioData, AudioDataType, SoundFormat as FlvSoundFormat, SoundRate, SoundSize, SoundType,
+};
+use std::io::Cursor;
+use swf::{AudioCompression, SoundFormat};
+
+/// Decoder for the audio tags of an FLV stream, as played by `NetStream`.
+///
+/// Each tag is decoded on its own, except for AAC, whose decoder is set up by the
+/// sequence header at the start of the stream.
+#[derive(Debug, Default)]
+pub struct FlvAudioDecoder {
+    #[cfg(feature = "aac")]
+    aac_decoder: Option<AacDecoder>,
+}
+
+impl FlvAudioDecoder {
+    /// Decodes the audio of a tag as 44.1kHz stereo.
+    pub fn decode(&mut self, audio: AudioData<'_>) -> Vec<[f32; 2]> {
+        let (sample_rate, samples) = match audio.data {
+            #[cfg(feature = "aac")]
+            AudioDataType::AacSequenceHeader(config) => {
+                match AacDecoder::new(config) {
+                    Ok(decoder) => self.aac_decoder = Some(decoder),
+                    Err(e) => tracing::error!("Couldn't create AAC decoder: {}", e),
+                }
+                return Vec::new();
+            }
+            #[cfg(feature = "aac")]
+            AudioDataType::AacRaw(frame) => {
+                let Some(decoder) = &mut self.aac_decoder else {
+                    return Vec::new();
+                };
+                match decoder.decode(frame) {
+                    Ok(decoded) => decoded,
+                    Err(e) => {
+                        tracing::warn!("Couldn't decode stream audio: {}", e);
+                        return Vec::new();
+                    }
+                }
+            }
+            #[cfg(not(feature = "aac"))]
+            AudioDataType::AacSequenceHeader(_) | AudioDataType::AacRaw(_) => {
+                tracing::warn!("Stub: AAC stream audio");
+                return Vec::new();
+            }
+            AudioDataType::Raw(data) => {
+                let Some(format) = sound_format(&audio) else {
+                    tracing::warn!("Unsupported stream audio format {:?}", audio.format);
+                    return Vec::new();
+                };
+                match make_decoder(&format, Cursor::new(data.to_vec())) {
+                    Ok(decoder) => (u32::from(decoder.sample_rate()), decoder.collect()),
+                    Err(e) => {
+                        tracing::warn!("Couldn't decode stream audio: {}", e);
+                        return Vec::new();
+                    }
+                }
+            }
+        };
+
+        let channel = |index: usize| {
+            let channel: Vec<f32> = samples
+                .iter()
+                .map(|frame: &[i16; 2]| f32::from(frame[index]) / 32768.0)
+                .collect();
+            resample(&channel, sample_rate, 44100)
+        };
+        channel(0)
+            .into_iter()
+            .zip(channel(1))
+            .map(|(left, right)| [left, right])
+            .collect()
+    }
+}
+
+/// The SWF sound format that's equivalent to the format of an FLV audio tag.
+fn sound_format(audio: &AudioData<'_>) -> Option<SoundFormat> {
+    let (compression, sample_rate) = match audio.format {
+        FlvSoundFormat::LinearPCMPlatformEndian => {
+            (AudioCompression::UncompressedUnknownEndian, None)
+        }
+        FlvSoundFormat::Adpcm => (AudioCompression::Adpcm, None),
+        FlvSoundFormat::MP3 | FlvSoundFormat::MP38kHz => (AudioCompression::Mp3, None),
+        FlvSoundFormat::LinearPCMLittleEndian => (AudioCompression::Uncompressed, None),
+        FlvSoundFormat::Nellymoser => (AudioCompression::Nellymoser, None),
+        FlvSoundFormat::Nellymoser16kHz => (AudioCompression::Nellymoser, Some(16000)),
+        FlvSoundFormat::Nellymoser8kHz => (AudioCompression::Nellymoser, Some(8000)),
+        _ => return None,
+    };
+    let sample_rate = sample_rate.unwrap_or(match audio.rate {
+        SoundRate::R5_500 => 5512,
+        SoundRate::R11_000 => 11025,
+        SoundRate::R22_000 => 22050,
+        SoundRate::R44_000 => 44100,
+    });
+    Some(SoundFormat {
+        compression,
+        sample_rate,
+        is_stereo: audio.sound_type == SoundType::Stereo,
+        is_16_bit: audio.size == SoundSize::Bits16,
+    })
+}
@@ -3,7 +3,9 @@ use super::{SoundHandle, SoundInstanceHandle, SoundTransform};
 use crate::backend::audio::{DecodeError, RegisterError};
 use crate::tag_utils::SwfSlice;
 use generational_arena::Arena;
+use std::collections::HashMap;
 use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
 use swf::AudioCompression;
 
@@ -69,6 +71,25 @@ pub struct AudioMixer {
 
     /// The last two windows of output samples.
     output_memory: Arc<RwLock<CircBuf>>,
+
+    /// The stream used by the last call to `extract_sound`, so that consecutive calls
+    /// can continue decoding where the previous one stopped.
+    extractor: Option<SoundExtractor>,
+
+    /// The sample queues of playing dynamic sounds, which are filled by `SampleDataEvent`
+    /// handlers on this thread and drained by the sound thread without locking.
+    dynamic_sounds: HashMap<SoundInstanceHandle, Arc<DynamicSoundBuffer>>,
+}
+
+/// A decoder stream for `Sound.extract`, resampled to 44.1kHz.
+struct SoundExtractor {
+    /// The sound being extracted.
+    sound: SoundHandle,
+
+    /// The index of the next sample frame that `stream` will output.
+    position: u32,
+
+    stream: Box<dyn Stream>,
 }
 
 /// An audio stream.
@@ -194,6 +215,17 @@ struct SoundInstance {
     /// (respectively) of this sound over the buffer currently being
     /// mixed. Used to compute `peak`, and is reset after every time.
     range: ([f32; 2], [f32; 2]),
+
+    /// How fast this sound plays, relative to its normal speed.
+    /// This also changes its pitch.
+    playback_rate: f64,
+
+    /// The two sample frames that output is currently being interpolated between,
+    /// once this sound has played at a rate other than 1.
+    rate_frames: Option<([i16; 2], [i16; 2])>,
+
+    /// How far between `rate_frames` the next output sample frame is, from 0 to 1.
+    rate_phase: f64,
 }
 
 impl SoundInstance {
@@ -207,6 +239,9 @@ impl SoundInstance {
             right_transform: [0.0, 1.0],
             peak: [0.0, 0.0],
             range: ([std::f32::INFINITY; 2], [std::f32::NEG_INFINITY; 2]),
+            playback_rate: 1.0,
+            rate_frames: None,
+            rate_phase: 0.0,
         }
     }
 
@@ -220,7 +255,37 @@ impl SoundInstance {
             right_transform: [0.0, 1.0],
             peak: [0.0, 0.0],
             range: ([std::f32::INFINITY; 2], [std::f32::NEG_INFINITY; 2]),
+            playback_rate: 1.0,
+            rate_frames: None,
+            rate_phase: 0.0,
+        }
+    }
+
+    /// Yields the next sample frame of this sound, resampled according to `playback_rate`.
+    fn next_frame(&mut self) -> [i16; 2] {
+        use dasp::Signal;
+
+        if self.rate_frames.is_none() && self.playback_rate == 1.0 {
+            return self.stream.next();
         }
+
+        let stream = &mut self.stream;
+        let (mut from, mut to) = *self
+            .rate_frames
+            .get_or_insert_with(|| (stream.next(), stream.next()));
+        while self.rate_phase >= 1.0 {
+            from = to;
+            to = stream.next();
+            self.rate_phase -= 1.0;
+        }
+        self.rate_frames = Some((from, to));
+
+        let phase = self.rate_phase;
+        self.rate_phase += self.playback_rate;
+        let lerp = |from: i16, to: i16| {
+            (f64::from(from) + (f64::from(to) - f64::from(from)) * phase).round() as i16
+        };
+        [lerp(from[0], to[0]), lerp(from[1], to[1])]
     }
 
     /// Updates `peak` from `range`, and resets the latter to default.
@@ -242,6 +307,8 @@ impl AudioMixer {
             num_output_channels,
             output_sample_rate,
             output_memory: Arc::new(RwLock::new(CircBuf::new())),
+            extractor: None,
+            dynamic_sounds: HashMap::new(),
         }
     }
 
@@ -329,7 +396,12 @@ impl AudioMixer {
     }
 
     /// Transforms a `Stream` into a new `Stream` that matches the output sample rate.
-    fn make_resampler(&self, mut stream: impl Stream) -> impl Stream {
+    fn make_resampler(&self, stream: impl Stream) -> impl Stream {
+        Self::resample(stream, self.output_sample_rate)
+    }
+
+    /// Transforms a `Stream` into a new `Stream` with the given sample rate.
+    fn resample(mut stream: impl Stream, output_sample_rate: u32) -> impl Stream {
         // TODO: Allow interpolator to be user-configurable?
         let left = stream.next();
         let right = stream.next();
@@ -339,7 +411,7 @@ impl AudioMixer {
             stream,
             interpolator,
             sample_rate,
-            self.output_sample_rate.into(),
+            output_sample_rate.into(),
         ))
     }
 
@@ -439,7 +511,7 @@ impl AudioMixer {
             let mut output_frame = Stereo::<T::Signed>::EQUILIBRIUM;
             for (_, sound) in sound_instances.iter_mut() {
                 if sound.active && !sound.stream.is_exhausted() {
-                    let sound_frame = sound.stream.next();
+                    let sound_frame = sound.next_frame();
                     let [left_0, left_1] = sound_frame.mul_amp(sound.left_transform);
                     let [right_0, right_1] = sound_frame.mul_amp(sound.right_transform);
                     let mut sound_frame: Stereo<T> = [
@@ -486,6 +558,74 @@ impl AudioMixer {
         *output_memory.get()
     }
 
+    /// Decodes `num_sample_frames` sample frames of a registered sound at 44.1kHz,
+    /// as used by `Sound.extract`.
+    ///
+    /// Extraction begins at `start_sample_frame`, or where the previous call stopped if `None`.
+    /// Fewer sample frames are returned if the end of the sound is reached.
+    /// Returns `None` if the sound is not registered or can't be decoded.
+    pub fn extract_sound(
+        &mut self,
+        sound_handle: SoundHandle,
+        start_sample_frame: Option<u32>,
+        num_sample_frames: u32,
+    ) -> Option<Vec<[f32; 2]>> {
+        let sound = self.sounds.get(sound_handle)?;
+
+        let continuing = self.extractor.as_ref().filter(|e| e.sound == sound_handle);
+        let start_sample_frame = start_sample_frame
+            .or_else(|| continuing.map(|e| e.position))
+            .unwrap_or(0);
+
+        // Decoding is only possible forwards, so restart the stream unless we can resume it.
+        if continuing.map(|e| e.position) != Some(start_sample_frame) {
+            let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
+            let decoder = match Self::make_seekable_decoder(&sound.format, data) {
+                Ok(decoder) => decoder,
+                Err(e) => {
+                    tracing::error!("Couldn't extract sound: {}", e);
+                    return None;
+                }
+            };
+            let stream = EventSoundStream::new_with_settings(
+                decoder,
+                &swf::SoundInfo {
+                    event: swf::SoundEvent::Start,
+                    in_sample: None,
+                    out_sample: None,
+                    num_loops: 1,
+                    envelope: None,
+                },
+                sound.num_sample_frames,
+                sound.skip_sample_frames,
+            );
+            let mut stream: Box<dyn Stream> = Box::new(Self::resample(stream, 44100));
+            for _ in 0..start_sample_frame {
+                if stream.is_exhausted() {
+                    break;
+                }
+                stream.next();
+            }
+            self.extractor = Some(SoundExtractor {
+                sound: sound_handle,
+                position: start_sample_frame,
+                stream,
+            });
+        }
+
+        let extractor = self.extractor.as_mut()?;
+        let mut samples = Vec::with_capacity(num_sample_frames as usize);
+        for _ in 0..num_sample_frames {
+            if extractor.stream.is_exhausted() {
+                break;
+            }
+            let [left, right] = extractor.stream.next();
+            samples.push([f32::from(left) / 32768.0, f32::from(right) / 32768.0]);
+            extractor.position += 1;
+        }
+        Some(samples)
+    }
+
     /// Registers an embedded SWF sound with the audio mixer.
     pub fn register_sound(&mut self, swf_sound: &swf::Sound) -> Result<SoundHandle, RegisterError> {
         // Slice off latency seek for MP3 data.
@@ -587,6 +727,65 @@ impl AudioMixer {
         Ok(handle)
     }
 
+    /// Starts playing a dynamic sound, whose 44.1kHz samples are supplied over time with
+    /// `append_dynamic_sound`.
+    ///
+    /// At most `capacity` sample frames can be queued at once.
+    pub fn start_dynamic_sound(&mut self, capacity: usize) -> SoundInstanceHandle {
+        let buffer = Arc::new(DynamicSoundBuffer::new(capacity));
+        let stream = self.make_resampler(DynamicSoundStream {
+            buffer: Arc::clone(&buffer),
+            position: 0,
+        });
+
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        let handle = sound_instances.insert(SoundInstance::new_stream(Box::new(stream)));
+        self.dynamic_sounds.insert(handle, buffer);
+        handle
+    }
+
+    /// Queues sample frames to be played by a dynamic sound.
+    ///
+    /// Returns the number of sample frames that fit in the queue.
+    pub fn append_dynamic_sound(
+        &mut self,
+        instance: SoundInstanceHandle,
+        samples: &[[f32; 2]],
+    ) -> usize {
+        self.dynamic_sound_buffer(instance)
+            .map(|buffer| buffer.push(samples))
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of sample frames queued by a dynamic sound that haven't been played yet.
+    ///
+    /// Returns `None` if the sound is no longer playing.
+    pub fn dynamic_sound_buffered(&mut self, instance: SoundInstanceHandle) -> Option<usize> {
+        self.dynamic_sound_buffer(instance)
+            .map(|buffer| buffer.len())
+    }
+
+    /// Marks that no more samples will be queued for a dynamic sound, so that it ends once its
+    /// queue runs out instead of waiting for more.
+    pub fn end_dynamic_sound(&mut self, instance: SoundInstanceHandle) {
+        if let Some(buffer) = self.dynamic_sound_buffer(instance) {
+            buffer.ended.store(true, Ordering::Release);
+        }
+    }
+
+    fn dynamic_sound_buffer(
+        &mut self,
+        instance: SoundInstanceHandle,
+    ) -> Option<&DynamicSoundBuffer> {
+        // The sound thread drops its stream once the sound has finished.
+        self.dynamic_sounds
+            .retain(|_, buffer| Arc::strong_count(buffer) > 1);
+        self.dynamic_sounds.get(&instance).map(|buffer| &**buffer)
+    }
+
     /// Stops a playing sound instance.
     pub fn stop_sound(&mut self, sound: SoundInstanceHandle) {
         let mut sound_instances = self
@@ -594,6 +793,7 @@ impl AudioMixer {
             .lock()
             .expect("Cannot be called reentrant");
         sound_instances.remove(sound);
+        self.dynamic_sounds.remove(&sound);
     }
 
     pub fn stop_all_sounds(&mut self) {
@@ -674,6 +874,17 @@ impl AudioMixer {
         }
     }
 
+    /// Sets how fast the given playing sound plays, relative to its normal speed.
+    pub fn set_sound_playback_rate(&mut self, instance: SoundInstanceHandle, rate: f64) {
+        let mut sound_instances = self
+            .sound_instances
+            .lock()
+            .expect("Cannot be called reentrant");
+        if let Some(instance) = sound_instances.get_mut(instance) {
+            instance.playback_rate = rate;
+        }
+    }
+
     pub fn volume(&self) -> f32 {
         *self.volume.read().expect("Cannot be called reentrant")
     }
@@ -754,7 +965,8 @@ struct EventSoundStream {
     decoder: Box<dyn SeekableDecoder>,
     num_loops: u16,
     start_sample_frame: u32,
-    end_sample_frame: Option<u32>,
+    /// The sample frame after the last one to play in each loop.
+    end_sample_frame: u32,
     cur_sample_frame: u32,
     skip_sample_frames: u32,
     is_exhausted: bool,
@@ -772,17 +984,22 @@ impl EventSoundStream {
         let start_sample_frame = (f64::from(settings.in_sample.unwrap_or(0)) / sample_divisor)
             as u32
             + skip_sample_frames;
-        let end_sample_frame = settings
-            .out_sample
-            .map(|n| (f64::from(n) / sample_divisor) as u32)
-            .unwrap_or(num_sample_frames)
-            + skip_sample_frames;
+        // The out point is the last sample to play. Without one, we stop at the stated length
+        // of the sound, so that padding at the end of MP3 data doesn't leave gaps between loops.
+        let end_sample_frame = match settings.out_sample {
+            Some(out_sample) => {
+                (f64::from(out_sample) / sample_divisor) as u32 + 1 + skip_sample_frames
+            }
+            None if num_sample_frames > 0 => num_sample_frames + skip_sample_frames,
+            None => u32::MAX,
+        };
 
         let mut stream = Self {
             decoder,
-            num_loops: settings.num_loops,
+            // Flash plays sounds with 0 loops once.
+            num_loops: settings.num_loops.max(1),
             start_sample_frame,
-            end_sample_frame: Some(end_sample_frame),
+            end_sample_frame,
             cur_sample_frame: start_sample_frame,
             skip_sample_frames,
             is_exhausted: false,
@@ -793,7 +1010,7 @@ impl EventSoundStream {
 
     /// Resets the decoder to the start point of the loop.
     fn next_loop(&mut self) {
-        if self.num_loops > 0 {
+        if self.num_loops > 0 && self.start_sample_frame < self.end_sample_frame {
             self.num_loops -= 1;
             self.decoder.seek_to_sample_frame(self.start_sample_frame);
             self.cur_sample_frame = self.start_sample_frame;
@@ -809,22 +1026,28 @@ impl dasp::signal::Signal for EventSoundStream {
     #[inline]
     fn next(&mut self) -> Self::Frame {
         // Loop the sound if necessary, and get the next frame.
-        if !self.is_exhausted {
-            if let Some(frame) = self.decoder.next() {
-                self.cur_sample_frame += 1;
-                if let Some(end) = self.end_sample_frame {
-                    if self.cur_sample_frame > end {
+        while !self.is_exhausted {
+            if self.cur_sample_frame >= self.end_sample_frame {
+                self.next_loop();
+                continue;
+            }
+            match self.decoder.next() {
+                Some(frame) => {
+                    self.cur_sample_frame += 1;
+                    return frame;
+                }
+                None => {
+                    // The data ended before the out point. If it did so straight
+                    // after seeking, looping again won't help.
+                    if self.cur_sample_frame == self.start_sample_frame {
+                        self.is_exhausted = true;
+                    } else {
                         self.next_loop();
                     }
                 }
-                frame
-            } else {
-                self.next_loop();
-                self.next()
             }
-        } else {
-            [0, 0]
         }
+        [0, 0]
     }
 
     #[inline]
@@ -846,6 +1069,110 @@ impl Stream for EventSoundStream {
     }
 }
 
+/// A single-producer, single-consumer queue of 44.1kHz sample frames for a dynamic sound.
+///
+/// The main thread pushes samples supplied by `SampleDataEvent` handlers while the sound thread
+/// pops them, so this is lock-free to keep either side from stalling the other.
+struct DynamicSoundBuffer {
+    /// The ring of sample frames, with both 16-bit channels packed into each element.
+    frames: Box<[AtomicU32]>,
+
+    /// The total number of sample frames popped so far.
+    read: AtomicUsize,
+
+    /// The total number of sample frames pushed so far.
+    write: AtomicUsize,
+
+    /// Whether no more sample frames will be pushed.
+    ended: AtomicBool,
+}
+
+impl DynamicSoundBuffer {
+    fn new(capacity: usize) -> Self {
+        Self {
+            frames: (0..capacity.max(1)).map(|_| AtomicU32::new(0)).collect(),
+            read: AtomicUsize::new(0),
+            write: AtomicUsize::new(0),
+            ended: AtomicBool::new(false),
+        }
+    }
+
+    /// The number of sample frames waiting to be popped.
+    fn len(&self) -> usize {
+        let read = self.read.load(Ordering::Acquire);
+        self.write.load(Ordering::Acquire).wrapping_sub(read)
+    }
+
+    /// Pushes as many of `samples` as fit, returning how many were pushed.
+    fn push(&self, samples: &[[f32; 2]]) -> usize {
+        let write = self.write.load(Ordering::Relaxed);
+        let free = self.frames.len() - self.len();
+        let count = samples.len().min(free);
+        for (i, [left, right]) in samples[..count].iter().enumerate() {
+            let left = (left.clamp(-1.0, 1.0) * 32767.0) as i16 as u16;
+            let right = (right.clamp(-1.0, 1.0) * 32767.0) as i16 as u16;
+            self.frames[write.wrapping_add(i) % self.frames.len()].store(
+                u32::from(left) | (u32::from(right) << 16),
+                Ordering::Relaxed,
+            );
+        }
+        self.write
+            .store(write.wrapping_add(count), Ordering::Release);
+        count
+    }
+
+    /// Pops the next sample frame, or returns `None` if the queue is empty.
+    fn pop(&self) -> Option<[i16; 2]> {
+        let read = self.read.load(Ordering::Relaxed);
+        if self.write.load(Ordering::Acquire) == read {
+            return None;
+        }
+        let frame = self.frames[read % self.frames.len()].load(Ordering::Relaxed);
+        self.read.store(read.wrapping_add(1), Ordering::Release);
+        Some([frame as u16 as i16, (frame >> 16) as u16 as i16])
+    }
+}
+
+/// A stream that plays the samples queued in a `DynamicSoundBuffer`.
+struct DynamicSoundStream {
+    buffer: Arc<DynamicSoundBuffer>,
+
+    /// The number of sample frames played so far.
+    position: u32,
+}
+
+impl dasp::signal::Signal for DynamicSoundStream {
+    type Frame = [i16; 2];
+
+    #[inline]
+    fn next(&mut self) -> [i16; 2] {
+        // If the queue runs dry, play silence until more samples arrive.
+        if let Some(frame) = self.buffer.pop() {
+            self.position += 1;
+            frame
+        } else {
+            [0, 0]
+        }
+    }
+
+    #[inline]
+    fn is_exhausted(&self) -> bool {
+        self.buffer.ended.load(Ordering::Acquire) && self.buffer.len() == 0
+    }
+}
+
+impl Stream for DynamicSoundStream {
+    #[inline]
+    fn source_position(&self) -> u32 {
+        self.position
+    }
+
+    #[inline]
+    fn source_sample_rate(&self) -> u16 {
+        44100
+    }
+}
+
 /// A stream that converts a source stream to a different sample rate.
 struct ConverterStream<S, I>(dasp::signal::interpolate::Converter<S, I>)
 where
@@ -966,7 +1293,7 @@ impl EnvelopeSignal {
         let mut envelope = envelope
             .iter()
             .map(|pt| swf::SoundEnvelopePoint {
-                sample: (f64::from(pt.sample) * scale) as u32,
+                sample: (f64::from(pt.sample) * scale).round() as u32,
                 ..*pt
             })
             .collect::<swf::SoundEnvelope>()
@@ -1110,6 +1437,11 @@ macro_rules! impl_audio_mixer_backend {
             self.$mixer.set_sound_transform(instance, transform)
         }
 
+        #[inline]
+        fn set_sound_playback_rate(&mut self, instance: SoundInstanceHandle, rate: f64) {
+            self.$mixer.set_sound_playback_rate(instance, rate)
+        }
+
         #[inline]
         fn get_sound_peak(&mut self, instance: SoundInstanceHandle) -> Option<[f32; 2]> {
             self.$mixer.get_sound_peak(instance)
@@ -1128,5 +1460,40 @@ macro_rules! impl_audio_mixer_backend {
         fn get_sample_history(&self) -> [[f32; 2]; 1024] {
             self.$mixer.get_sample_history()
         }
+
+        #[inline]
+        fn extract_sound(
+            &mut self,
+            sound: SoundHandle,
+            start_sample_frame: Option<u32>,
+            num_sample_frames: u32,
+        ) -> Option<Vec<[f32; 2]>> {
+            self.$mixer
+                .extract_sound(sound, start_sample_frame, num_sample_frames)
+        }
+
+        #[inline]
+        fn start_dynamic_sound(&mut self, capacity: usize) -> Option<SoundInstanceHandle> {
+            Some(self.$mixer.start_dynamic_sound(capacity))
+        }
+
+        #[inline]
+        fn append_dynamic_sound(
+            &mut self,
+            instance: SoundInstanceHandle,
+            samples: &[[f32; 2]],
+        ) -> usize {
+            self.$mixer.append_dynamic_sound(instance, samples)
+        }
+
+        #[inline]
+        fn dynamic_sound_buffered(&mut self, instance: SoundInstanceHandle) -> Option<usize> {
+            self.$mixer.dynamic_sound_buffered(instance)
+        }
+
+        #[inline]
+        fn end_dynamic_sound(&mut self, instance: SoundInstanceHandle) {
+            self.$mixer.end_dynamic_sound(instance)
+        }
     };
 }
@@ -0,0 +1,164 @@
+//! Access to files for AIR's `flash.filesystem` API.
+
+use chrono::{DateTime, Utc};
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// The directories that AIR's `File` class provides as static properties.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpecialDirectory {
+    /// `File.applicationDirectory`, which holds the files the application was installed with.
+    Application,
+
+    /// `File.applicationStorageDirectory`, where the application stores its own data.
+    ApplicationStorage,
+
+    /// `File.cacheDirectory`.
+    Cache,
+
+    /// `File.desktopDirectory`.
+    Desktop,
+
+    /// `File.documentsDirectory`.
+    Documents,
+
+    /// `File.userDirectory`.
+    User,
+}
+
+/// A file or directory, as reported by `FilesystemBackend::info`.
+#[derive(Clone, Debug)]
+pub struct FileInfo {
+    pub is_directory: bool,
+
+    pub size: u64,
+
+    pub creation_date: Option<DateTime<Utc>>,
+
+    pub modification_date: Option<DateTime<Utc>>,
+}
+
+/// Gives AIR movies access to files.
+///
+/// Paths are absolute native paths, as used by `File.nativePath`. Implementations must
+/// refuse access to anything outside of the directories that they expose, by returning
+/// an error of the kind `io::ErrorKind::PermissionDenied`.
+pub trait FilesystemBackend {
+    /// Returns the path of one of the directories that `File` provides,
+    /// or `None` if files can't be accessed.
+    fn special_directory(&self, directory: SpecialDirectory) -> Option<String>;
+
+    /// The separator between the components of a path.
+    fn separator(&self) -> &str {
+        "/"
+    }
+
+    fn info(&self, path: &str) -> io::Result<FileInfo>;
+
+    fn read(&self, path: &str) -> io::Result<Vec<u8>>;
+
+    /// Writes data to a file, creating it if it doesn't exist yet.
+    fn write(&mut self, path: &str, data: &[u8]) -> io::Result<()>;
+
+    /// Creates a directory, along with any of its parents that don't exist yet.
+    fn create_directory(&mut self, path: &str) -> io::Result<()>;
+
+    /// Deletes a file, or a directory along with its contents if `recursive` is set.
+    fn delete(&mut self, path: &str, recursive: bool) -> io::Result<()>;
+
+    /// Returns the paths of the entries of a directory.
+    fn list_directory(&self, path: &str) -> io::Result<Vec<String>>;
+
+    fn copy(&mut self, from: &str, to: &str) -> io::Result<()>;
+
+    fn rename(&mut self, from: &str, to: &str) -> io::Result<()>;
+
+    /// Allows reading a file or directory that the user chose to give to the movie,
+    /// such as by dropping it onto the player, even if it's outside of the exposed directories.
+    fn grant_read_access(&mut self, _path: &str) {}
+}
+
+/// Resolves a path relative to another, as `File.resolvePath` does.
+///
+/// `.` and `..` components are resolved without accessing any files.
+pub fn resolve_path(base: &str, path: &str) -> String {
+    let mut resolved = PathBuf::new();
+    for component in Path::new(base).join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            component => resolved.push(component),
+        }
+    }
+    resolved.to_string_lossy().into_owned()
+}
+
+/// A filesystem backend that refuses all access to files.
+#[derive(Default)]
+pub struct NullFilesystemBackend;
+
+impl NullFilesystemBackend {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn denied<T>() -> io::Result<T> {
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "Files can't be accessed",
+        ))
+    }
+}
+
+impl FilesystemBackend for NullFilesystemBackend {
+    fn special_directory(&self, _directory: SpecialDirectory) -> Option<String> {
+        None
+    }
+
+    fn info(&self, _path: &str) -> io::Result<FileInfo> {
+        Self::denied()
+    }
+
+    fn read(&self, _path: &str) -> io::Result<Vec<u8>> {
+        Self::denied()
+    }
+
+    fn write(&mut self, _path: &str, _data: &[u8]) -> io::Result<()> {
+        Self::denied()
+    }
+
+    fn create_directory(&mut self, _path: &str) -> io::Result<()> {
+        Self::denied()
+    }
+
+    fn delete(&mut self, _path: &str, _recursive: bool) -> io::Result<()> {
+        Self::denied()
+    }
+
+    fn list_directory(&self, _path: &str) -> io::Result<Vec<String>> {
+        Self::denied()
+    }
+
+    fn copy(&mut self, _from: &str, _to: &str) -> io::Result<()> {
+        Self::denied()
+    }
+
+    fn rename(&mut self, _from: &str, _to: &str) -> io::Result<()> {
+        Self::denied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_relative_paths() {
+        assert_eq!(resolve_path("/a/b", "c/d.txt"), "/a/b/c/d.txt");
+        assert_eq!(resolve_path("/a/b", "../c"), "/a/c");
+        assert_eq!(resolve_path("/a/b", "./c/./d"), "/a/b/c/d");
+        assert_eq!(resolve_path("/a/b", "/c"), "/c");
+    }
+}
@@ -1,7 +1,12 @@
 //! Browser-related platform functions
 
+use crate::external::Value as ExternalValue;
 use crate::loader::Error;
-use crate::socket::{ConnectionState, SocketAction, SocketHandle};
+use crate::local_connection::LocalConnectionMessage;
+use crate::socket::{
+    ConnectionState, Datagram, DatagramSocketHandle, ServerSocketHandle, SocketAction,
+    SocketHandle, SocketKind,
+};
 use crate::string::WStr;
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
@@ -38,6 +43,30 @@ pub enum SocketMode {
     Ask,
 }
 
+/// A network interface of the machine, as listed by AIR's `NetworkInfo.findInterfaces`.
+#[derive(Clone, Debug, Default)]
+pub struct NetworkInterface {
+    pub name: String,
+    pub display_name: String,
+    pub active: bool,
+    /// The MAC address, as hexadecimal bytes separated by hyphens, or empty if unknown.
+    pub hardware_address: String,
+    /// The maximum transmission unit, or -1 if unknown.
+    pub mtu: i32,
+    pub addresses: Vec<InterfaceAddress>,
+}
+
+/// An IP address of a network interface.
+#[derive(Clone, Debug, Default)]
+pub struct InterfaceAddress {
+    pub address: String,
+    /// The broadcast address of the subnet, or empty for IPv6 addresses.
+    pub broadcast: String,
+    /// `"IPv4"` or `"IPv6"`.
+    pub ip_version: &'static str,
+    pub prefix_length: i32,
+}
+
 /// The handling mode of links opening a new website.
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
@@ -55,6 +84,169 @@ pub enum OpenURLMode {
     Deny,
 }
 
+/// The handling mode of `javascript:` URLs that a movie tries to open.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum JavascriptUrlMode {
+    /// Open the URL like any other, which runs the script on the page if there is one.
+    #[default]
+    #[serde(rename = "navigate")]
+    Navigate,
+
+    /// Ignore all `javascript:` URLs.
+    #[serde(rename = "deny")]
+    Deny,
+
+    /// Turn URLs such as `javascript:showScore(10)` into `ExternalInterface` calls,
+    /// so that hosts without a page can handle them too.
+    #[serde(rename = "externalInterface")]
+    ExternalInterface,
+}
+
+/// Decides which URLs a movie may open through `getURL` and `navigateToURL`.
+///
+/// Rules are either URL schemes, written with a trailing colon such as `mailto:`, or
+/// domains such as `example.com`, which also cover their subdomains.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NavigationPolicy {
+    /// What to do with URLs that aren't covered by either list.
+    pub open_url_mode: OpenURLMode,
+
+    /// URLs that can always be opened.
+    pub allow: Vec<String>,
+
+    /// URLs that can never be opened, even if they're also allowed.
+    pub deny: Vec<String>,
+
+    pub javascript: JavascriptUrlMode,
+}
+
+impl Default for NavigationPolicy {
+    fn default() -> Self {
+        Self::new(OpenURLMode::Allow)
+    }
+}
+
+impl NavigationPolicy {
+    pub fn new(open_url_mode: OpenURLMode) -> Self {
+        Self {
+            open_url_mode,
+            allow: Vec::new(),
+            deny: Vec::new(),
+            javascript: JavascriptUrlMode::default(),
+        }
+    }
+
+    /// Whether `url` should be opened, opened after the user confirms it, or not opened at all.
+    pub fn check(&self, url: &Url) -> OpenURLMode {
+        if self.deny.iter().any(|rule| rule_matches(rule, url)) {
+            OpenURLMode::Deny
+        } else if self.allow.iter().any(|rule| rule_matches(rule, url)) {
+            OpenURLMode::Allow
+        } else {
+            self.open_url_mode
+        }
+    }
+}
+
+fn rule_matches(rule: &str, url: &Url) -> bool {
+    if let Some(scheme) = rule.strip_suffix(':') {
+        return url.scheme().eq_ignore_ascii_case(scheme);
+    }
+    let Some(host) = url.host_str() else {
+        return false;
+    };
+    let rule = rule.trim_start_matches('.');
+    host.eq_ignore_ascii_case(rule)
+        || (host.len() > rule.len()
+            && host.as_bytes()[host.len() - rule.len() - 1] == b'.'
+            && host[host.len() - rule.len()..].eq_ignore_ascii_case(rule))
+}
+
+/// The script of a `javascript:` URL, if `url` is one.
+pub fn javascript_url_script(url: &str) -> Option<&str> {
+    let scheme = url.get(.."javascript:".len())?;
+    scheme
+        .eq_ignore_ascii_case("javascript:")
+        .then(|| &url["javascript:".len()..])
+}
+
+/// Read a script such as `showScore(10, "easy");` as a call of a function with
+/// literal arguments, which is how most movies use `javascript:` URLs.
+///
+/// Returns `None` for any other script.
+pub fn parse_javascript_call(script: &str) -> Option<(String, Vec<ExternalValue>)> {
+    let script = percent_encoding::percent_decode_str(script).decode_utf8_lossy();
+    let script = script.trim().trim_end_matches(';').trim_end();
+    let (name, rest) = script.split_once('(')?;
+    let name = name.trim();
+    let is_identifier = |part: &str| {
+        part.chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+            && part
+                .chars()
+                .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    };
+    if !name.split('.').all(is_identifier) {
+        return None;
+    }
+
+    let mut args = Vec::new();
+    let mut rest = rest.trim_start();
+    if let Some(end) = rest.strip_prefix(')') {
+        return end.trim().is_empty().then(|| (name.to_string(), args));
+    }
+    loop {
+        let (arg, after) = parse_javascript_literal(rest)?;
+        args.push(arg);
+        let after = after.trim_start();
+        if let Some(after) = after.strip_prefix(',') {
+            rest = after.trim_start();
+        } else if let Some(end) = after.strip_prefix(')') {
+            return end.trim().is_empty().then(|| (name.to_string(), args));
+        } else {
+            return None;
+        }
+    }
+}
+
+/// Read a string, number, boolean, `null` or `undefined` from the start of `input`.
+fn parse_javascript_literal(input: &str) -> Option<(ExternalValue, &str)> {
+    let mut chars = input.char_indices();
+    let (_, first) = chars.next()?;
+    if first == '"' || first == '\'' {
+        let mut value = String::new();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\\' => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    'r' => value.push('\r'),
+                    't' => value.push('\t'),
+                    c => value.push(c),
+                },
+                c if c == first => return Some((ExternalValue::String(value), &input[i + 1..])),
+                c => value.push(c),
+            }
+        }
+        return None;
+    }
+
+    let end = input
+        .find(|c: char| c == ',' || c == ')' || c.is_whitespace())
+        .unwrap_or(input.len());
+    let (token, rest) = input.split_at(end);
+    let value = match token {
+        "true" => ExternalValue::Bool(true),
+        "false" => ExternalValue::Bool(false),
+        "null" => ExternalValue::Null,
+        "undefined" => ExternalValue::Undefined,
+        _ => ExternalValue::Number(token.parse().ok()?),
+    };
+    Some((value, rest))
+}
+
 impl NavigationMethod {
     /// Convert an SWF method enum into a NavigationMethod.
     pub fn from_send_vars_method(s: SendVarsMethod) -> Option<Self> {
@@ -108,6 +300,12 @@ pub struct Request {
     /// to give us a consistent order - hopefully, no servers depend on
     /// the order of headers.
     headers: IndexMap<String, String>,
+
+    /// Whether redirects should be followed, rather than returned as the response.
+    follow_redirects: bool,
+
+    /// Whether cookies should be sent with the request and stored from its response.
+    manage_cookies: bool,
 }
 
 impl Request {
@@ -118,6 +316,8 @@ impl Request {
             method: NavigationMethod::Get,
             body: None,
             headers: Default::default(),
+            follow_redirects: true,
+            manage_cookies: true,
         }
     }
 
@@ -128,6 +328,8 @@ impl Request {
             method: NavigationMethod::Post,
             body,
             headers: Default::default(),
+            follow_redirects: true,
+            manage_cookies: true,
         }
     }
 
@@ -139,6 +341,8 @@ impl Request {
             method,
             body,
             headers: Default::default(),
+            follow_redirects: true,
+            manage_cookies: true,
         }
     }
 
@@ -168,6 +372,88 @@ impl Request {
     pub fn set_headers(&mut self, headers: IndexMap<String, String>) {
         self.headers = headers;
     }
+
+    pub fn follow_redirects(&self) -> bool {
+        self.follow_redirects
+    }
+
+    pub fn set_follow_redirects(&mut self, follow_redirects: bool) {
+        self.follow_redirects = follow_redirects;
+    }
+
+    pub fn manage_cookies(&self) -> bool {
+        self.manage_cookies
+    }
+
+    pub fn set_manage_cookies(&mut self, manage_cookies: bool) {
+        self.manage_cookies = manage_cookies;
+    }
+}
+
+/// The request headers that Flash Player refuses to let content set.
+///
+/// These are either controlled by the player or browser itself, or could be
+/// used to forge requests on behalf of the user.
+const DISALLOWED_REQUEST_HEADERS: &[&str] = &[
+    "accept-charset",
+    "accept-encoding",
+    "accept-ranges",
+    "age",
+    "allow",
+    "allowed",
+    "authorization",
+    "charge-to",
+    "connect",
+    "connection",
+    "content-length",
+    "content-location",
+    "content-range",
+    "cookie",
+    "date",
+    "delete",
+    "etag",
+    "expect",
+    "get",
+    "head",
+    "host",
+    "if-modified-since",
+    "keep-alive",
+    "last-modified",
+    "location",
+    "max-forwards",
+    "options",
+    "origin",
+    "post",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "proxy-connection",
+    "public",
+    "put",
+    "range",
+    "referer",
+    "request-range",
+    "retry-after",
+    "server",
+    "te",
+    "trace",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+    "uri",
+    "user-agent",
+    "vary",
+    "via",
+    "warning",
+    "www-authenticate",
+    "x-flash-version",
+];
+
+/// Whether content is allowed to send a request header with the given name.
+pub fn is_allowed_request_header(name: &str) -> bool {
+    !name.is_empty()
+        && !DISALLOWED_REQUEST_HEADERS
+            .iter()
+            .any(|disallowed| disallowed.eq_ignore_ascii_case(name))
 }
 
 /// A response to a successful fetch request.
@@ -183,6 +469,59 @@ pub struct SuccessResponse {
 
     /// The field to indicate if the request has been redirected.
     pub redirected: bool,
+
+    /// The headers of the response, as (header_name, header_value) pairs.
+    pub headers: Vec<(String, String)>,
+}
+
+/// The body of a response that is still being received.
+pub trait ResponseBody {
+    /// Wait for the next chunk of the body, or `None` once all of it was received.
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>>;
+}
+
+/// A response body that was already received as a whole.
+struct BufferedBody(Option<Vec<u8>>);
+
+impl ResponseBody for BufferedBody {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>> {
+        let chunk = self.0.take();
+        Box::pin(async move { Ok(chunk) })
+    }
+}
+
+/// A response to a successful fetch request, whose body is received in chunks.
+pub struct StreamingResponse {
+    /// The final URL obtained after any redirects.
+    pub url: String,
+
+    /// The status code of the response.
+    pub status: u16,
+
+    /// The field to indicate if the request has been redirected.
+    pub redirected: bool,
+
+    /// The headers of the response, as (header_name, header_value) pairs.
+    pub headers: Vec<(String, String)>,
+
+    /// The length of the whole body, if the server announced it.
+    pub expected_length: Option<u64>,
+
+    /// The body of the response, as it is being received.
+    pub body: Box<dyn ResponseBody>,
+}
+
+impl From<SuccessResponse> for StreamingResponse {
+    fn from(response: SuccessResponse) -> Self {
+        Self {
+            url: response.url,
+            status: response.status,
+            redirected: response.redirected,
+            headers: response.headers,
+            expected_length: Some(response.body.len() as u64),
+            body: Box::new(BufferedBody(Some(response.body))),
+        }
+    }
 }
 
 /// A response to a non-successful fetch request.
@@ -232,6 +571,14 @@ pub trait NavigatorBackend {
     /// Fetch data and return it some time in the future.
     fn fetch(&self, request: Request) -> OwnedFuture<SuccessResponse, ErrorResponse>;
 
+    /// Fetch data, and return the response as soon as its body starts to arrive.
+    ///
+    /// The default implementation waits for the whole body to be fetched.
+    fn fetch_stream(&self, request: Request) -> OwnedFuture<StreamingResponse, ErrorResponse> {
+        let fetch = self.fetch(request);
+        Box::pin(async move { Ok(fetch.await?.into()) })
+    }
+
     /// Take a URL string and resolve it to the actual URL from which a file
     /// can be fetched. This includes handling of relative links and pre-processing.
     ///
@@ -255,6 +602,14 @@ pub trait NavigatorBackend {
     /// URL (generally only if configured to do so by the user).
     fn pre_process_url(&self, url: Url) -> Url;
 
+    /// How `javascript:` URLs that the movie opens should be handled.
+    ///
+    /// The player makes `ExternalInterface` calls out of them itself, so in that mode they
+    /// never reach `navigate_to_url`.
+    fn javascript_url_mode(&self) -> JavascriptUrlMode {
+        JavascriptUrlMode::Navigate
+    }
+
     /// Handle any Socket connection request
     ///
     /// Use [SocketAction::Connect] to notify AVM that the connection failed or succeeded.
@@ -264,6 +619,10 @@ pub trait NavigatorBackend {
     /// Use [SocketAction::Data] to send data to AVM side.
     ///
     /// When the Sender of the Receiver is dropped then this task should end.
+    ///
+    /// Only [SocketKind::Socket] connections are subject to socket policy files, and
+    /// [SocketKind::Rtmps] connections should be wrapped in TLS.
+    #[allow(clippy::too_many_arguments)]
     fn connect_socket(
         &mut self,
         host: String,
@@ -272,7 +631,67 @@ pub trait NavigatorBackend {
         handle: SocketHandle,
         receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
+        kind: SocketKind,
     );
+
+    /// Handle a request to listen for TCP connections, made by AIR's `ServerSocket`.
+    ///
+    /// Returns the local address and port that were bound, or a message describing why
+    /// the address couldn't be bound.
+    ///
+    /// Use [SocketAction::Accept] to pass accepted connections to AVM side.
+    ///
+    /// When the Sender of the Receiver is dropped then the server socket should stop listening.
+    fn bind_server_socket(
+        &mut self,
+        address: String,
+        port: u16,
+        handle: ServerSocketHandle,
+        receiver: Receiver<()>,
+        sender: Sender<SocketAction>,
+    ) -> Result<(String, u16), String>;
+
+    /// Handle a request to bind a UDP socket, made by AIR's `DatagramSocket`.
+    ///
+    /// Returns the local address and port that were bound, or a message describing why
+    /// the address couldn't be bound.
+    ///
+    /// Packets to send are received from the Receiver. Use [SocketAction::Datagram] to pass
+    /// received packets to AVM side.
+    ///
+    /// When the Sender of the Receiver is dropped then the socket should be closed.
+    fn bind_datagram_socket(
+        &mut self,
+        address: String,
+        port: u16,
+        handle: DatagramSocketHandle,
+        receiver: Receiver<Datagram>,
+        sender: Sender<SocketAction>,
+    ) -> Result<(String, u16), String>;
+
+    /// Lists the network interfaces of the machine, for AIR's `NetworkInfo`.
+    fn network_interfaces(&self) -> Vec<NetworkInterface>;
+
+    /// Handle a request to listen for messages that other players send to a `LocalConnection`,
+    /// made by `LocalConnection.connect`.
+    ///
+    /// Returns `false` if another player is already listening with that connection name.
+    ///
+    /// Messages sent to the connection should be passed to the Sender until
+    /// [NavigatorBackend::close_local_connection] is called with the same name.
+    fn listen_local_connection(
+        &mut self,
+        name: &str,
+        sender: Sender<LocalConnectionMessage>,
+    ) -> bool;
+
+    /// Stops listening for messages sent to a `LocalConnection` by other players.
+    fn close_local_connection(&mut self, name: &str);
+
+    /// Sends a message to a `LocalConnection` that's listening in another player.
+    ///
+    /// Returns `false` if no other player is listening with that connection name.
+    fn send_local_connection(&mut self, name: &str, data: Vec<u8>) -> bool;
 }
 
 #[cfg(not(target_family = "wasm"))]
@@ -411,11 +830,51 @@ impl NavigatorBackend for NullNavigatorBackend {
         handle: SocketHandle,
         _receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
+        _kind: SocketKind,
     ) {
         sender
             .send(SocketAction::Connect(handle, ConnectionState::Failed))
             .expect("working channel send");
     }
+    fn bind_server_socket(
+        &mut self,
+        _address: String,
+        _port: u16,
+        _handle: ServerSocketHandle,
+        _receiver: Receiver<()>,
+        _sender: Sender<SocketAction>,
+    ) -> Result<(String, u16), String> {
+        Err("Server sockets are not supported".to_string())
+    }
+
+    fn bind_datagram_socket(
+        &mut self,
+        _address: String,
+        _port: u16,
+        _handle: DatagramSocketHandle,
+        _receiver: Receiver<Datagram>,
+        _sender: Sender<SocketAction>,
+    ) -> Result<(String, u16), String> {
+        Err("Datagram sockets are not supported".to_string())
+    }
+
+    fn network_interfaces(&self) -> Vec<NetworkInterface> {
+        vec![]
+    }
+
+    fn listen_local_connection(
+        &mut self,
+        _name: &str,
+        _sender: Sender<LocalConnectionMessage>,
+    ) -> bool {
+        true
+    }
+
+    fn close_local_connection(&mut self, _name: &str) {}
+
+    fn send_local_connection(&mut self, _name: &str, _data: Vec<u8>) -> bool {
+        false
+    }
 }
 
 // The following functions are helper functions used in different
@@ -568,6 +1027,94 @@ pub fn fetch_path<NavigatorType: NavigatorBackend>(
             body,
             status: 0,
             redirected: false,
+            headers: Vec::new(),
         })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(allow: &[&str], deny: &[&str]) -> NavigationPolicy {
+        NavigationPolicy {
+            allow: allow.iter().map(|rule| rule.to_string()).collect(),
+            deny: deny.iter().map(|rule| rule.to_string()).collect(),
+            ..NavigationPolicy::new(OpenURLMode::Confirm)
+        }
+    }
+
+    fn check(policy: &NavigationPolicy, url: &str) -> OpenURLMode {
+        policy.check(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn domains_cover_subdomains() {
+        let policy = policy(&["example.com"], &["ads.example.com"]);
+        assert_eq!(check(&policy, "https://example.com/"), OpenURLMode::Allow);
+        assert_eq!(
+            check(&policy, "https://www.EXAMPLE.com/game"),
+            OpenURLMode::Allow
+        );
+        assert_eq!(
+            check(&policy, "https://ads.example.com/"),
+            OpenURLMode::Deny
+        );
+        assert_eq!(
+            check(&policy, "https://notexample.com/"),
+            OpenURLMode::Confirm
+        );
+    }
+
+    #[test]
+    fn schemes() {
+        let policy = policy(&["https:"], &["mailto:"]);
+        assert_eq!(check(&policy, "https://example.com/"), OpenURLMode::Allow);
+        assert_eq!(check(&policy, "http://example.com/"), OpenURLMode::Confirm);
+        assert_eq!(
+            check(&policy, "mailto:someone@example.com"),
+            OpenURLMode::Deny
+        );
+    }
+
+    #[test]
+    fn javascript_calls() {
+        assert_eq!(javascript_url_script("JavaScript:go()"), Some("go()"));
+        assert_eq!(javascript_url_script("https://example.com/"), None);
+
+        assert_eq!(
+            parse_javascript_call("game.showScore(10, 'it\\'s easy', true);"),
+            Some((
+                "game.showScore".to_string(),
+                vec![
+                    ExternalValue::Number(10.0),
+                    ExternalValue::String("it's easy".to_string()),
+                    ExternalValue::Bool(true),
+                ]
+            ))
+        );
+        assert_eq!(
+            parse_javascript_call("void%20reset( )"),
+            None,
+            "Only plain calls are supported"
+        );
+        assert_eq!(
+            parse_javascript_call("reset(%22a%20b%22)"),
+            Some((
+                "reset".to_string(),
+                vec![ExternalValue::String("a b".to_string())]
+            ))
+        );
+        assert_eq!(parse_javascript_call("alert(document.cookie)"), None);
+        assert_eq!(parse_javascript_call("a(1); b(2)"), None);
+    }
+
+    #[test]
+    fn request_headers_are_filtered() {
+        assert!(is_allowed_request_header("X-Session-Token"));
+        assert!(is_allowed_request_header("Content-Type"));
+        assert!(!is_allowed_request_header("Cookie"));
+        assert!(!is_allowed_request_header("user-agent"));
+        assert!(!is_allowed_request_header(""));
+    }
+}
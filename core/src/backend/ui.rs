@@ -1,11 +1,20 @@
+use crate::accessibility::AccessibilityNode;
 use crate::events::{KeyCode, PlayerEvent, TextControlCode};
+use crate::printing::PrintPage;
+use chrono::{DateTime, Utc};
 use downcast_rs::Downcast;
 use fluent_templates::loader::langid;
 pub use fluent_templates::LanguageIdentifier;
+use ruffle_render::bitmap::Bitmap;
 use std::borrow::Cow;
 use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
 
 pub type FullscreenError = Cow<'static, str>;
+
+/// The result of a file dialog, which resolves once the user closes it.
+pub type DialogResultFuture<T> = Pin<Box<dyn Future<Output = T> + 'static>>;
 pub static US_ENGLISH: LanguageIdentifier = langid!("en-US");
 
 pub trait UiBackend: Downcast {
@@ -16,14 +25,33 @@ pub trait UiBackend: Downcast {
     /// Changes the mouse cursor image.
     fn set_mouse_cursor(&mut self, cursor: MouseCursor);
 
+    /// Shows a custom image as the mouse cursor instead of the one given to `set_mouse_cursor`,
+    /// or goes back to that one if `None`.
+    ///
+    /// Animated cursors are shown by calling this again with each of their frames.
+    fn set_custom_mouse_cursor(&mut self, _cursor: Option<&CustomMouseCursor>) {}
+
     /// Get the clipboard content
     fn clipboard_content(&mut self) -> String;
 
     /// Sets the clipboard to the given content.
     fn set_clipboard_content(&mut self, content: String);
 
+    /// Returns the image on the clipboard, in RGBA format, if there is one.
+    fn clipboard_image(&mut self) -> Option<Bitmap> {
+        None
+    }
+
+    /// Puts an image in RGBA format on the clipboard.
+    fn set_clipboard_image(&mut self, _image: Bitmap) {}
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError>;
 
+    /// The size of the screen that fullscreen mode fills, in pixels, if it's known.
+    fn screen_size(&self) -> Option<(u32, u32)> {
+        None
+    }
+
     /// Displays a message about an error during root movie download.
     /// In particular, on web this can be a CORS error, which we can sidestep
     /// by providing a direct .swf link instead.
@@ -36,9 +64,154 @@ pub trait UiBackend: Downcast {
     fn open_virtual_keyboard(&self);
 
     fn language(&self) -> &LanguageIdentifier;
+
+    /// Looks up a font installed on the system, for text rendered with device fonts.
+    ///
+    /// If the exact style isn't available, the closest match for the family may be returned.
+    fn load_device_font(
+        &self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<FontDefinition>;
+
+    /// The family names of all fonts installed on the system, sorted alphabetically.
+    /// Used by `Font.enumerateFonts(true)`.
+    fn device_font_names(&self) -> Vec<String>;
+
+    /// The number of points that the touchscreen can track at once, or 0 if there's no touchscreen.
+    fn max_touch_points(&self) -> u32 {
+        0
+    }
+
+    /// Whether a screen reader or other assistive technology is reading the content.
+    ///
+    /// The player only builds the accessibility tree while this is true.
+    fn is_accessibility_active(&self) -> bool {
+        false
+    }
+
+    /// Exposes a new accessibility tree to assistive technology.
+    fn update_accessibility_tree(&mut self, _tree: AccessibilityNode) {}
+
+    /// Returns the names of the cameras that video can be captured from.
+    fn camera_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Starts capturing video from the camera at `index` in `camera_names`,
+    /// replacing any camera that was already being captured.
+    ///
+    /// The requested mode is a hint; frames may arrive in any size.
+    /// Frames are only delivered once `camera_state` returns `CameraState::Allowed`.
+    fn start_camera(&mut self, _index: usize, _width: u32, _height: u32, _fps: f64) {}
+
+    /// Stops capturing video from the camera.
+    fn stop_camera(&mut self) {}
+
+    /// Returns the state of the current camera capture.
+    fn camera_state(&self) -> CameraState {
+        CameraState::Unavailable
+    }
+
+    /// Returns the newest frame captured from the camera since the last call, in RGBA format.
+    fn take_camera_frame(&mut self) -> Option<Bitmap> {
+        None
+    }
+
+    /// Whether pages can be sent to a printer with `print`, as reported by `PrintJob.isSupported`.
+    fn can_print(&self) -> bool {
+        false
+    }
+
+    /// Sends rasterized pages to be printed, as a single document.
+    fn print(&mut self, _pages: Vec<PrintPage>) {}
+
+    /// Shows a dialog for the user to pick files to open, as used by `FileReference.browse`.
+    ///
+    /// The dialog resolves to the picked files, which is empty if the user cancelled it.
+    /// Returns `None` if no dialog can be shown, such as when one is already open.
+    fn display_file_open_dialog(
+        &mut self,
+        _filters: Vec<FileFilter>,
+        _multiple: bool,
+    ) -> Option<DialogResultFuture<Vec<SelectedFile>>> {
+        None
+    }
+
+    /// Shows a dialog for the user to pick where to save a file, as used by
+    /// `FileReference.save` and `FileReference.download`, then writes `data` there.
+    ///
+    /// The dialog resolves to the name of the saved file, or `None` if the user cancelled it.
+    /// Returns `None` if no dialog can be shown, such as when one is already open.
+    fn display_file_save_dialog(
+        &mut self,
+        _file_name: String,
+        _data: Vec<u8>,
+    ) -> Option<DialogResultFuture<Option<String>>> {
+        None
+    }
 }
 impl_downcast!(UiBackend);
 
+/// The state of a camera capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CameraState {
+    /// No camera is being captured, or it can't be captured from.
+    Unavailable,
+
+    /// The user hasn't yet decided whether to allow access to the camera.
+    Pending,
+
+    /// Video is being captured from the camera.
+    Allowed,
+
+    /// The user denied access to the camera.
+    Denied,
+}
+
+/// The kinds of files that can be picked in a file dialog, as given by an AS3 `FileFilter`.
+#[derive(Clone, Debug)]
+pub struct FileFilter {
+    pub description: String,
+
+    /// The file extensions, without their leading `*.`.
+    pub extensions: Vec<String>,
+
+    /// The Mac file types, which are used instead of the extensions on macOS if given.
+    pub mac_type: Option<String>,
+}
+
+/// A file that the user picked in a dialog shown by `UiBackend::display_file_open_dialog`.
+#[derive(Clone, Debug)]
+pub struct SelectedFile {
+    /// The name of the file, without its directory.
+    pub name: String,
+
+    pub creation_date: Option<DateTime<Utc>>,
+
+    pub modification_date: Option<DateTime<Utc>>,
+
+    pub contents: Vec<u8>,
+}
+
+/// A font file installed on the system.
+/// Communicated from the UI backend to the core via `UiBackend::load_device_font`.
+pub struct FontDefinition {
+    /// The family name of the font.
+    pub name: String,
+
+    pub is_bold: bool,
+
+    pub is_italic: bool,
+
+    /// The contents of a TrueType or OpenType font file, or a font collection.
+    pub data: Vec<u8>,
+
+    /// The index of the face to use within `data`, if it is a font collection.
+    pub index: u32,
+}
+
 /// A mouse cursor icon displayed by the Flash Player.
 /// Communicated from the core to the UI backend via `UiBackend::set_mouse_cursor`.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -60,11 +233,26 @@ pub enum MouseCursor {
     Grab,
 }
 
+/// A cursor image registered with AS3 `Mouse.registerCursor`.
+#[derive(Clone, Debug)]
+pub struct CustomMouseCursor {
+    /// The image of the cursor, in RGBA format.
+    pub image: Bitmap,
+
+    /// The position of the point that the cursor clicks at, relative to the top-left of the image.
+    pub hotspot_x: u32,
+    pub hotspot_y: u32,
+}
+
 pub struct InputManager {
     keys_down: HashSet<KeyCode>,
     last_key: KeyCode,
     last_char: Option<char>,
     last_text_control: Option<TextControlCode>,
+
+    /// Whether caps lock and num lock are toggled on.
+    caps_lock: bool,
+    num_lock: bool,
 }
 
 impl InputManager {
@@ -74,6 +262,8 @@ impl InputManager {
             last_key: KeyCode::Unknown,
             last_char: None,
             last_text_control: None,
+            caps_lock: false,
+            num_lock: false,
         }
     }
 
@@ -93,11 +283,22 @@ impl InputManager {
 
     pub fn handle_event(&mut self, event: &PlayerEvent) {
         match *event {
-            PlayerEvent::KeyDown { key_code, key_char } => {
+            PlayerEvent::KeyDown {
+                key_code, key_char, ..
+            } => {
                 self.last_char = key_char;
+                if !self.is_key_down(key_code) {
+                    match key_code {
+                        KeyCode::CapsLock => self.caps_lock = !self.caps_lock,
+                        KeyCode::NumLock => self.num_lock = !self.num_lock,
+                        _ => {}
+                    }
+                }
                 self.add_key(key_code);
             }
-            PlayerEvent::KeyUp { key_code, key_char } => {
+            PlayerEvent::KeyUp {
+                key_code, key_char, ..
+            } => {
                 self.last_char = key_char;
                 self.remove_key(key_code);
                 self.last_text_control = None;
@@ -115,6 +316,24 @@ impl InputManager {
         self.keys_down.contains(&key)
     }
 
+    /// Returns whether caps lock or num lock is toggled on.
+    ///
+    /// Without `set_lock_state`, this is only known from the presses of the keys since
+    /// the player started, so it's assumed to start off.
+    pub fn is_key_toggled(&self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::CapsLock => self.caps_lock,
+            KeyCode::NumLock => self.num_lock,
+            _ => false,
+        }
+    }
+
+    /// Sets whether caps lock and num lock are toggled on, for frontends that can tell.
+    pub fn set_lock_state(&mut self, caps_lock: bool, num_lock: bool) {
+        self.caps_lock = caps_lock;
+        self.num_lock = num_lock;
+    }
+
     pub fn last_key_code(&self) -> KeyCode {
         self.last_key
     }
@@ -175,6 +394,19 @@ impl UiBackend for NullUiBackend {
     fn language(&self) -> &LanguageIdentifier {
         &US_ENGLISH
     }
+
+    fn load_device_font(
+        &self,
+        _name: &str,
+        _is_bold: bool,
+        _is_italic: bool,
+    ) -> Option<FontDefinition> {
+        None
+    }
+
+    fn device_font_names(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 impl Default for NullUiBackend {
@@ -1,5 +1,6 @@
 pub mod bitmap_data;
 pub mod operations;
+pub mod parallel;
 pub mod turbulence;
 
 /// Determine if a particular bitmap data size is valid.
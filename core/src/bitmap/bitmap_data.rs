@@ -5,8 +5,10 @@ use gc_arena::Collect;
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::bitmap::{Bitmap, BitmapFormat, BitmapHandle, PixelRegion, SyncHandle};
 use ruffle_wstr::WStr;
+use std::cell::Cell;
 use std::fmt::Debug;
 use std::ops::Range;
+use std::rc::Rc;
 use swf::{Rectangle, Twips};
 use tracing::instrument;
 
@@ -31,6 +33,32 @@ impl LehmerRng {
     pub fn gen_range(&mut self, rng: Range<u8>) -> u8 {
         rng.start + (self.gen() % ((rng.end - rng.start) as u32 + 1)) as u8
     }
+
+    /// Advances the sequence by `n` values, as if `gen` had been called `n` times.
+    ///
+    /// Since `X_(k+n) = a^n * X_k mod m`, this only takes `O(log n)` steps, which allows
+    /// separate parts of a bitmap to be filled with noise independently.
+    pub fn skip(&mut self, n: u64) {
+        const A: u64 = 16_807;
+        const M: u64 = 2_147_483_647;
+
+        if n == 0 {
+            return;
+        }
+
+        let mut multiplier = 1;
+        let mut base = A;
+        let mut exp = n;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                multiplier = multiplier * base % M;
+            }
+            base = base * base % M;
+            exp >>= 1;
+        }
+
+        self.x = ((self.x as u64 % M) * multiplier % M) as u32;
+    }
 }
 
 /// This can represent both a premultiplied and an unmultiplied ARGB color value.
@@ -183,6 +211,25 @@ impl From<Color> for swf::Color {
     }
 }
 
+/// Counts the modifications of the `BitmapData`s of a player.
+///
+/// Bitmaps can be drawn without belonging to the display list (such as through bitmap
+/// fills), so the stage checks this to know whether it might look different when it's
+/// redrawn. Every `BitmapData` shares the counter of the player it was created by.
+#[derive(Clone, Debug, Default)]
+pub struct BitmapModifications(Rc<Cell<u64>>);
+
+impl BitmapModifications {
+    /// The number of times that a `BitmapData` has been modified so far.
+    pub fn count(&self) -> u64 {
+        self.0.get()
+    }
+
+    fn record(&self) {
+        self.0.set(self.0.get().wrapping_add(1));
+    }
+}
+
 bitflags! {
     pub struct ChannelOptions: u8 {
         const RED = 1 << 0;
@@ -221,6 +268,10 @@ pub struct BitmapData<'gc> {
     avm2_object: Option<Avm2Object<'gc>>,
 
     dirty_state: DirtyState,
+
+    /// The modification counter of the player that this `BitmapData` belongs to.
+    #[collect(require_static)]
+    modifications: BitmapModifications,
 }
 
 #[derive(Clone, Collect, Debug)]
@@ -245,7 +296,7 @@ mod wrapper {
     use ruffle_render::commands::CommandHandler;
     use std::cell::Ref;
 
-    use super::{copy_pixels_to_bitmapdata, BitmapData, DirtyState};
+    use super::{copy_pixels_to_bitmapdata, BitmapData, BitmapModifications, DirtyState};
 
     /// A wrapper type that ensures that we always wait for a pending
     /// GPU -> CPU sync to complete (using `sync_handle`) before accessing
@@ -300,6 +351,7 @@ mod wrapper {
                     bitmap_handle: None,
                     avm2_object: None,
                     dirty_state: DirtyState::Clean,
+                    modifications: BitmapModifications::default(),
                 },
             ))
         }
@@ -377,6 +429,12 @@ mod wrapper {
             self.0.read()
         }
 
+        /// Whether this bitmap has been modified on the GPU, and those changes
+        /// haven't been copied back to the CPU-side pixels yet.
+        pub fn has_pending_gpu_changes(&self) -> bool {
+            matches!(self.0.read().dirty_state, DirtyState::GpuModified(_, _))
+        }
+
         // These methods do not require a sync to complete, as they do not depend on the
         // CPU-side pixels. They are implemented directly on `BitmapDataWrapper`, allowing
         // callers to avoid calling sync()
@@ -500,7 +558,13 @@ impl std::fmt::Debug for BitmapData<'_> {
 }
 
 impl<'gc> BitmapData<'gc> {
-    pub fn new(width: u32, height: u32, transparency: bool, fill_color: u32) -> Self {
+    pub fn new(
+        width: u32,
+        height: u32,
+        transparency: bool,
+        fill_color: u32,
+        modifications: BitmapModifications,
+    ) -> Self {
         Self {
             pixels: vec![
                 Color(fill_color).to_premultiplied_alpha(transparency);
@@ -513,6 +577,7 @@ impl<'gc> BitmapData<'gc> {
             bitmap_handle: None,
             avm2_object: None,
             dirty_state: DirtyState::Clean,
+            modifications,
         }
     }
 
@@ -521,6 +586,7 @@ impl<'gc> BitmapData<'gc> {
         height: u32,
         transparency: bool,
         pixels: Vec<Color>,
+        modifications: BitmapModifications,
     ) -> Self {
         Self {
             pixels,
@@ -531,9 +597,15 @@ impl<'gc> BitmapData<'gc> {
             avm2_object: None,
             disposed: false,
             dirty_state: DirtyState::Clean,
+            modifications,
         }
     }
 
+    /// The modification counter of the player that this `BitmapData` belongs to.
+    pub fn modifications(&self) -> &BitmapModifications {
+        &self.modifications
+    }
+
     pub fn disposed(&self) -> bool {
         self.disposed
     }
@@ -546,6 +618,7 @@ impl<'gc> BitmapData<'gc> {
         // There's no longer a handle to update
         self.dirty_state = DirtyState::Clean;
         self.disposed = true;
+        self.modifications.record();
     }
 
     pub fn bitmap_handle(&mut self, renderer: &mut dyn RenderBackend) -> Option<BitmapHandle> {
@@ -572,11 +645,13 @@ impl<'gc> BitmapData<'gc> {
 
     pub fn set_gpu_dirty(&mut self, sync_handle: Box<dyn SyncHandle>, region: PixelRegion) {
         self.dirty_state = DirtyState::GpuModified(sync_handle, region);
+        self.modifications.record();
     }
 
     pub fn set_cpu_dirty(&mut self, region: PixelRegion) {
         debug_assert!(region.x_max <= self.width);
         debug_assert!(region.y_max <= self.height);
+        self.modifications.record();
         match &mut self.dirty_state {
             DirtyState::CpuModified(old_region) => old_region.union(region),
             DirtyState::Clean => self.dirty_state = DirtyState::CpuModified(region),
@@ -608,6 +683,17 @@ impl<'gc> BitmapData<'gc> {
         output
     }
 
+    /// Copies the pixels into an RGBA bitmap without premultiplied alpha,
+    /// as used when handing images to the system.
+    pub fn to_unmultiplied_bitmap(&self) -> Bitmap {
+        let mut data = Vec::with_capacity(self.pixels.len() * 4);
+        for p in &self.pixels {
+            let p = p.to_un_multiplied_alpha();
+            data.extend_from_slice(&[p.red(), p.green(), p.blue(), p.alpha()]);
+        }
+        Bitmap::new(self.width, self.height, BitmapFormat::Rgba, data)
+    }
+
     pub fn width(&self) -> u32 {
         self.width
     }
@@ -766,3 +852,24 @@ impl ThresholdOperation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lehmer_rng_skip_matches_gen() {
+        for seed in [1, 2, 12345, 2_147_483_646, 2_147_483_648, u32::MAX] {
+            for n in [0, 1, 2, 7, 1000] {
+                let mut expected = LehmerRng::with_seed(seed);
+                for _ in 0..n {
+                    expected.gen();
+                }
+
+                let mut skipped = LehmerRng::with_seed(seed);
+                skipped.skip(n);
+                assert_eq!(skipped.gen(), expected.gen(), "seed {seed}, n {n}");
+            }
+        }
+    }
+}
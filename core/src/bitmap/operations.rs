@@ -6,12 +6,15 @@ use crate::bitmap::bitmap_data::{
     BitmapData, BitmapDataDrawError, BitmapDataWrapper, ChannelOptions, Color, IBitmapDrawable,
     LehmerRng, ThresholdOperation,
 };
+use crate::bitmap::parallel;
 use crate::bitmap::turbulence::Turbulence;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::TDisplayObject;
+use crate::profiler::Category;
 use gc_arena::MutationContext;
-use ruffle_render::bitmap::{PixelRegion, PixelSnapping};
+use ruffle_render::bitmap::{BitmapInfo, PixelRegion, PixelSnapping};
 use ruffle_render::commands::{CommandHandler, CommandList, RenderBlendMode};
+use ruffle_render::filters::software::{self, FilterImage};
 use ruffle_render::filters::Filter;
 use ruffle_render::matrix::Matrix;
 use ruffle_render::quality::StageQuality;
@@ -195,11 +198,30 @@ pub fn noise<'gc>(
         seed as u32
     };
 
-    let mut rng = LehmerRng::with_seed(true_seed);
+    // Every pixel consumes the same number of random values, so each row can
+    // start its own generator at the right point in the sequence.
+    let values_per_pixel = if gray_scale {
+        1 + channel_options.contains(ChannelOptions::ALPHA) as u64
+    } else {
+        [
+            ChannelOptions::RED,
+            ChannelOptions::GREEN,
+            ChannelOptions::BLUE,
+            ChannelOptions::ALPHA,
+        ]
+        .into_iter()
+        .filter(|c| channel_options.contains(*c))
+        .count() as u64
+    };
 
-    for y in 0..write.height() {
-        for x in 0..write.width() {
-            let pixel_color = if gray_scale {
+    let width = write.width() as usize;
+    let height = write.height() as usize;
+    parallel::for_each_row(write.raw_pixels_mut(), width, 0..height, |y, row| {
+        let mut rng = LehmerRng::with_seed(true_seed);
+        rng.skip(y as u64 * width as u64 * values_per_pixel);
+
+        for pixel in row {
+            *pixel = if gray_scale {
                 let gray = rng.gen_range(low..high);
                 let alpha = if channel_options.contains(ChannelOptions::ALPHA) {
                     rng.gen_range(low..high)
@@ -235,10 +257,8 @@ pub fn noise<'gc>(
 
                 Color::argb(a, r, g, b)
             };
-
-            write.set_pixel32_raw(x, y, pixel_color);
         }
-    }
+    });
     let region = PixelRegion::for_whole_size(write.width(), write.height());
     write.set_cpu_dirty(region);
 }
@@ -254,104 +274,116 @@ pub fn perlin_noise<'gc>(
     fractal_noise: bool,
     channel_options: ChannelOptions,
     grayscale: bool,
-    offsets: Vec<(f64, f64)>, // must contain `num_octaves` values
+    offsets: Vec<(f64, f64)>, // missing values are treated as (0, 0)
 ) {
     let (target, _) = target.overwrite_cpu_pixels_from_gpu(mc);
     let mut write = target.write(mc);
 
     let turb = Turbulence::from_seed(random_seed);
 
-    for y in 0..write.height() {
-        for x in 0..write.width() {
-            let px = x as f64;
-            let py = y as f64;
-
-            let mut noise = [0.0; 4];
-
-            // grayscale mode is different enough to warrant its own branch
-            if grayscale {
-                noise[0] = turb.turbulence(
-                    0,
-                    (px, py),
-                    (1.0 / base.0, 1.0 / base.1),
-                    num_octaves,
-                    fractal_noise,
-                    stitch,
-                    (0.0, 0.0),
-                    (write.width() as f64, write.height() as f64),
-                    &offsets,
-                );
-
-                noise[1] = noise[0];
-                noise[2] = noise[0];
-
-                noise[3] = if channel_options.contains(ChannelOptions::ALPHA) {
-                    turb.turbulence(
-                        1,
+    let width = write.width();
+    let height = write.height();
+    let transparency = write.transparency();
+    let stitch_size = (width as f64, height as f64);
+
+    parallel::for_each_row(
+        write.raw_pixels_mut(),
+        width as usize,
+        0..height as usize,
+        |y, row| {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let px = x as f64;
+                let py = y as f64;
+
+                let mut noise = [0.0; 4];
+
+                // grayscale mode is different enough to warrant its own branch
+                if grayscale {
+                    noise[0] = turb.turbulence(
+                        0,
                         (px, py),
                         (1.0 / base.0, 1.0 / base.1),
                         num_octaves,
                         fractal_noise,
                         stitch,
                         (0.0, 0.0),
-                        (write.width() as f64, write.height() as f64),
+                        stitch_size,
                         &offsets,
-                    )
-                } else {
-                    1.0
-                };
-            } else {
-                // Flash seems to pass the `color_channel` parameter to `turbulence`
-                // somewhat strangely. It's not always r=0, g=1, b=2, a=3; instead,
-                // it skips incrementing the parameter after channels that are
-                // not included in `channel_options`.
-                let mut channel = 0;
-
-                for (c, noise_c) in noise.iter_mut().enumerate() {
-                    // this will work both in fractal_sum and turbulence "modes",
-                    // because of the saturating conversion to u8
-                    *noise_c = if c == 3 { 1.0 } else { -1.0 };
-
-                    // `c` is always in 0..4, so `1 << c` is never actually truncated here
-                    let c = ChannelOptions::from_bits_truncate(1 << c);
-                    if channel_options.contains(c) {
-                        *noise_c = turb.turbulence(
-                            channel,
+                    );
+
+                    noise[1] = noise[0];
+                    noise[2] = noise[0];
+
+                    noise[3] = if channel_options.contains(ChannelOptions::ALPHA) {
+                        turb.turbulence(
+                            1,
                             (px, py),
                             (1.0 / base.0, 1.0 / base.1),
                             num_octaves,
                             fractal_noise,
                             stitch,
                             (0.0, 0.0),
-                            (write.width() as f64, write.height() as f64),
+                            stitch_size,
                             &offsets,
-                        );
-                        channel += 1;
+                        )
+                    } else {
+                        1.0
+                    };
+                } else {
+                    // Flash seems to pass the `color_channel` parameter to `turbulence`
+                    // somewhat strangely. It's not always r=0, g=1, b=2, a=3; instead,
+                    // it skips incrementing the parameter after channels that are
+                    // not included in `channel_options`.
+                    let mut channel = 0;
+
+                    for (c, noise_c) in noise.iter_mut().enumerate() {
+                        // this will work both in fractal_sum and turbulence "modes",
+                        // because of the saturating conversion to u8
+                        *noise_c = if c == 3 { 1.0 } else { -1.0 };
+
+                        // `c` is always in 0..4, so `1 << c` is never actually truncated here
+                        let c = ChannelOptions::from_bits_truncate(1 << c);
+                        if channel_options.contains(c) {
+                            *noise_c = turb.turbulence(
+                                channel,
+                                (px, py),
+                                (1.0 / base.0, 1.0 / base.1),
+                                num_octaves,
+                                fractal_noise,
+                                stitch,
+                                (0.0, 0.0),
+                                stitch_size,
+                                &offsets,
+                            );
+                            channel += 1;
+                        }
                     }
                 }
-            }
 
-            let mut color = [0_u8; 4];
-            for chan in 0..4 {
-                // This is precisely how Adobe Flash converts the -1..1 or 0..1 floats to u8.
-                // Please don't touch, it was difficult to figure out the exact method. :)
-                color[chan] = (if fractal_noise {
-                    // Yes, the + 0.5 for correct (nearest) rounding is done before the division by 2.0,
-                    // making it technically less correct (I think), but this is how it is!
-                    ((noise[chan] * 255.0 + 255.0) + 0.5) / 2.0
-                } else {
-                    (noise[chan] * 255.0) + 0.5
-                }) as u8;
-            }
+                let mut color = [0_u8; 4];
+                for chan in 0..4 {
+                    // This is precisely how Adobe Flash converts the -1..1 or 0..1 floats to u8.
+                    // Please don't touch, it was difficult to figure out the exact method. :)
+                    color[chan] = (if fractal_noise {
+                        // Yes, the + 0.5 for correct (nearest) rounding is done before the division by 2.0,
+                        // making it technically less correct (I think), but this is how it is!
+                        ((noise[chan] * 255.0 + 255.0) + 0.5) / 2.0
+                    } else {
+                        (noise[chan] * 255.0) + 0.5
+                    }) as u8;
+                }
 
-            if !write.transparency() {
-                color[3] = 255;
-            }
+                if !transparency {
+                    color[3] = 255;
+                }
 
-            write.set_pixel32_raw(x, y, Color::argb(color[3], color[0], color[1], color[2]));
-        }
-    }
-    let region = PixelRegion::for_whole_size(write.width(), write.height());
+                // The noise is generated with straight alpha, but we store premultiplied colors.
+                *pixel = Color::argb(color[3], color[0], color[1], color[2])
+                    .to_premultiplied_alpha(transparency);
+            }
+        },
+    );
+    let region = PixelRegion::for_whole_size(width, height);
     write.set_cpu_dirty(region);
 }
 
@@ -469,7 +501,7 @@ pub fn color_transform<'gc>(
     let x_max = x_max.min(target.width());
     let y_max = y_max.min(target.height());
 
-    if x_max == 0 || y_max == 0 || x_min == x_max || y_min == y_max {
+    if x_max == 0 || y_max == 0 || x_min >= x_max || y_min >= y_max {
         return;
     }
 
@@ -477,19 +509,21 @@ pub fn color_transform<'gc>(
     let mut write = target.write(mc);
     let transparency = write.transparency();
 
-    for y in y_min..y_max {
-        for x in x_min..x_max {
-            let color = write.get_pixel32_raw(x, y).to_un_multiplied_alpha();
+    let width = write.width() as usize;
+    parallel::for_each_row(
+        write.raw_pixels_mut(),
+        width,
+        y_min as usize..y_max as usize,
+        |_, row| {
+            for pixel in &mut row[x_min as usize..x_max as usize] {
+                let color = pixel.to_un_multiplied_alpha();
 
-            let color = color_transform * swf::Color::from(color);
+                let color = color_transform * swf::Color::from(color);
 
-            write.set_pixel32_raw(
-                x,
-                y,
-                Color::from(color).to_premultiplied_alpha(transparency),
-            )
-        }
-    }
+                *pixel = Color::from(color).to_premultiplied_alpha(transparency);
+            }
+        },
+    );
     write.set_cpu_dirty(PixelRegion::encompassing_pixels(
         (x_min, y_min),
         (x_max - 1, y_max - 1),
@@ -516,11 +550,6 @@ pub fn threshold<'gc>(
     let (src_min_x, src_min_y, src_width, src_height) = src_rect;
     let (dest_min_x, dest_min_y) = dest_point;
 
-    // The number of modified pixels
-    // This doesn't seem to include pixels changed due to copy_source
-    let mut modified_count = 0;
-    let mut dirty_area: Option<PixelRegion> = None;
-
     let mut source_region =
         PixelRegion::for_whole_size(source_bitmap.width(), source_bitmap.height());
     let mut dest_region = PixelRegion::for_whole_size(target.width(), target.height());
@@ -535,6 +564,7 @@ pub fn threshold<'gc>(
         return 0;
     }
 
+    let source_width = source_bitmap.width() as usize;
     let source = if source_bitmap.ptr_eq(target) {
         None
     } else {
@@ -544,54 +574,59 @@ pub fn threshold<'gc>(
     let target = target.sync();
     let mut write = target.write(mc);
 
-    // Check each pixel
-    for y in 0..dest_region.height() {
-        for x in 0..dest_region.width() {
-            let dest_x = dest_region.x_min + x;
-            let dest_y = dest_region.y_min + y;
-            let src_x = source_region.x_min + x;
-            let src_y = source_region.y_min + y;
+    // When thresholding a bitmap onto itself, read from a snapshot of the original pixels,
+    // so that rows can be processed independently of each other.
+    let snapshot;
+    let source_pixels = if let Some(source) = &source {
+        source.raw_pixels()
+    } else {
+        snapshot = write.raw_pixels().to_vec();
+        &snapshot[..]
+    };
 
-            // Extract source colour
-            let source_color = if let Some(source) = &source {
-                source
-                    .get_pixel32_raw(src_x, src_y)
-                    .to_un_multiplied_alpha()
-            } else {
-                write.get_pixel32_raw(src_x, src_y).to_un_multiplied_alpha()
-            };
+    let width = write.width() as usize;
+    let transparency = write.transparency();
+    let x_min = dest_region.x_min as usize;
+    let x_max = dest_region.x_max as usize;
+    let src_x_offset = source_region.x_min as i64 - dest_region.x_min as i64;
+    let src_y_offset = source_region.y_min as i64 - dest_region.y_min as i64;
 
-            // If the test, as defined by the operation pass then set to input colour
-            if operation.matches(u32::from(source_color) & mask, masked_threshold) {
-                modified_count += 1;
-                write.set_pixel32_raw(dest_x, dest_y, Color::from(colour));
-            } else {
-                // If the test fails, but copy_source is true then take the colour from the source
-                if copy_source {
-                    let new_color = if let Some(source) = &source {
-                        source
-                            .get_pixel32_raw(dest_x, dest_y)
-                            .to_un_multiplied_alpha()
-                    } else {
-                        write
-                            .get_pixel32_raw(dest_x, dest_y)
-                            .to_un_multiplied_alpha()
-                    };
+    // `colour` is given with straight alpha, but we store premultiplied colors.
+    let colour = Color::from(colour).to_premultiplied_alpha(transparency);
 
-                    write.set_pixel32_raw(dest_x, dest_y, new_color);
+    // The number of modified pixels
+    // This doesn't seem to include pixels changed due to copy_source
+    let modified_count = parallel::sum_rows(
+        write.raw_pixels_mut(),
+        width,
+        dest_region.y_min as usize..dest_region.y_max as usize,
+        |dest_y, row| {
+            let mut modified_count = 0;
+            let src_y = (dest_y as i64 + src_y_offset) as usize;
+
+            for (dest_x, pixel) in row.iter_mut().enumerate().take(x_max).skip(x_min) {
+                let src_x = (dest_x as i64 + src_x_offset) as usize;
+
+                // Extract source colour
+                let source_color =
+                    source_pixels[src_x + src_y * source_width].to_un_multiplied_alpha();
+
+                // If the test, as defined by the operation pass then set to input colour
+                if operation.matches(u32::from(source_color) & mask, masked_threshold) {
+                    modified_count += 1;
+                    *pixel = colour;
+                } else if copy_source {
+                    // If the test fails, but copy_source is true then take the colour from the
+                    // corresponding source pixel
+                    *pixel = source_color.to_premultiplied_alpha(transparency);
                 }
             }
-            if let Some(dirty_area) = &mut dirty_area {
-                dirty_area.encompass(dest_x, dest_y);
-            } else {
-                dirty_area = Some(PixelRegion::for_pixel(dest_x, dest_y));
-            }
-        }
-    }
 
-    if let Some(dirty_area) = dirty_area {
-        write.set_cpu_dirty(dirty_area);
-    }
+            modified_count
+        },
+    );
+
+    write.set_cpu_dirty(dest_region);
 
     modified_count
 }
@@ -669,6 +704,7 @@ pub fn palette_map<'gc>(
         return;
     }
 
+    let source_width = source_bitmap.width() as usize;
     let source = if source_bitmap.ptr_eq(target) {
         None
     } else {
@@ -678,32 +714,48 @@ pub fn palette_map<'gc>(
     let target = target.sync();
     let mut write = target.write(mc);
 
-    for y in 0..dest_region.height() {
-        for x in 0..dest_region.width() {
-            let dest_x = dest_region.x_min + x;
-            let dest_y = dest_region.y_min + y;
-            let src_x = source_region.x_min + x;
-            let src_y = source_region.y_min + y;
-
-            let source_color = if let Some(source) = &source {
-                source
-                    .get_pixel32_raw(src_x, src_y)
-                    .to_un_multiplied_alpha()
-            } else {
-                write.get_pixel32_raw(src_x, src_y).to_un_multiplied_alpha()
-            };
-
-            let r = channel_arrays.0[source_color.red() as usize];
-            let g = channel_arrays.1[source_color.green() as usize];
-            let b = channel_arrays.2[source_color.blue() as usize];
-            let a = channel_arrays.3[source_color.alpha() as usize];
-
-            let sum = u32::wrapping_add(u32::wrapping_add(r, g), u32::wrapping_add(b, a));
-            let mix_color = Color::from(sum).to_premultiplied_alpha(true);
+    // When mapping a bitmap onto itself, read from a snapshot of the original pixels,
+    // so that rows can be processed independently of each other.
+    let snapshot;
+    let source_pixels = if let Some(source) = &source {
+        source.raw_pixels()
+    } else {
+        snapshot = write.raw_pixels().to_vec();
+        &snapshot[..]
+    };
 
-            write.set_pixel32_raw(dest_x, dest_y, mix_color);
-        }
-    }
+    let width = write.width() as usize;
+    let transparency = write.transparency();
+    let x_min = dest_region.x_min as usize;
+    let x_max = dest_region.x_max as usize;
+    let src_x_offset = source_region.x_min as i64 - dest_region.x_min as i64;
+    let src_y_offset = source_region.y_min as i64 - dest_region.y_min as i64;
+
+    parallel::for_each_row(
+        write.raw_pixels_mut(),
+        width,
+        dest_region.y_min as usize..dest_region.y_max as usize,
+        |dest_y, row| {
+            let src_y = (dest_y as i64 + src_y_offset) as usize;
+
+            for (dest_x, pixel) in row.iter_mut().enumerate().take(x_max).skip(x_min) {
+                let src_x = (dest_x as i64 + src_x_offset) as usize;
+
+                let source_color =
+                    source_pixels[src_x + src_y * source_width].to_un_multiplied_alpha();
+
+                let r = channel_arrays.0[source_color.red() as usize];
+                let g = channel_arrays.1[source_color.green() as usize];
+                let b = channel_arrays.2[source_color.blue() as usize];
+                let a = channel_arrays.3[source_color.alpha() as usize];
+
+                // The channel results are combined by adding them together, so that a
+                // single array can affect multiple channels.
+                let sum = u32::wrapping_add(u32::wrapping_add(r, g), u32::wrapping_add(b, a));
+                *pixel = Color::from(sum).to_premultiplied_alpha(transparency);
+            }
+        },
+    );
 
     write.set_cpu_dirty(dest_region);
 }
@@ -756,6 +808,7 @@ pub fn compare<'gc>(
             left.height(),
             true,
             pixels,
+            left.modifications().clone(),
         ))
     } else {
         None
@@ -1163,22 +1216,66 @@ pub fn apply_filter<'gc>(
     context: &mut UpdateContext<'_, 'gc>,
     target: BitmapDataWrapper<'gc>,
     source: BitmapDataWrapper<'gc>,
-    source_point: (u32, u32),
+    source_point: (i32, i32),
     source_size: (u32, u32),
-    dest_point: (u32, u32),
+    dest_point: (i32, i32),
     filter: Filter,
 ) {
-    if !context.renderer.is_filter_supported(&filter) {
-        let mut source_region = PixelRegion::for_whole_size(source.width(), source.height());
-        let mut dest_region = PixelRegion::for_whole_size(target.width(), target.height());
-        dest_region.clamp_with_intersection(
-            (dest_point.0 as i32, dest_point.1 as i32),
-            (source_point.0 as i32, source_point.1 as i32),
-            (source_size.0 as i32, source_size.1 as i32),
-            &mut source_region,
-        );
+    let begin = context.profiler.begin();
+    apply_filter_inner(
+        context,
+        target,
+        source,
+        source_point,
+        source_size,
+        dest_point,
+        filter,
+    );
+    context
+        .profiler
+        .end(begin, Category::Filters, "BitmapData.applyFilter");
+}
+
+fn apply_filter_inner<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    target: BitmapDataWrapper<'gc>,
+    source: BitmapDataWrapper<'gc>,
+    source_point: (i32, i32),
+    source_size: (u32, u32),
+    dest_point: (i32, i32),
+    filter: Filter,
+) {
+    // Only the part of `sourceRect` that exists in the source, and fits in the destination, is used
+    let mut source_region = PixelRegion::for_whole_size(source.width(), source.height());
+    let mut dest_region = PixelRegion::for_whole_size(target.width(), target.height());
+    dest_region.clamp_with_intersection(
+        dest_point,
+        source_point,
+        (source_size.0 as i32, source_size.1 as i32),
+        &mut source_region,
+    );
+
+    if dest_region.width() == 0 || dest_region.height() == 0 {
+        return;
+    }
 
-        if dest_region.width() == 0 || dest_region.height() == 0 {
+    if !context.renderer.is_filter_supported(&filter) {
+        if let Some(filtered) = apply_filter_on_cpu(source, source_region, &filter) {
+            let target = target.sync();
+            let mut write = target.write(context.gc_context);
+            let transparency = write.transparency();
+            for y in 0..dest_region.height() {
+                for x in 0..dest_region.width() {
+                    let [r, g, b, a] = filtered.get(x, y);
+                    let color = if transparency {
+                        Color::argb(a, r, g, b)
+                    } else {
+                        Color::argb(255, r, g, b)
+                    };
+                    write.set_pixel32_raw(dest_region.x_min + x, dest_region.y_min + y, color);
+                }
+            }
+            write.set_cpu_dirty(dest_region);
             return;
         }
 
@@ -1201,10 +1298,10 @@ pub fn apply_filter<'gc>(
 
     let sync_handle = context.renderer.apply_filter(
         source_handle,
-        source_point,
-        source_size,
+        (source_region.x_min, source_region.y_min),
+        (source_region.width(), source_region.height()),
         dest,
-        dest_point,
+        (dest_region.x_min, dest_region.y_min),
         filter,
     );
     let region = PixelRegion::for_whole_size(write.width(), write.height());
@@ -1216,6 +1313,69 @@ pub fn apply_filter<'gc>(
     }
 }
 
+/// Calculates the area affected by applying `filter` to `source_rect` of `target`,
+/// as used by `BitmapData.generateFilterRect`.
+pub fn generate_filter_rect(
+    context: &mut UpdateContext<'_, '_>,
+    target: BitmapDataWrapper<'_>,
+    source_rect: Rectangle<i32>,
+    filter: &Filter,
+) -> Rectangle<i32> {
+    if matches!(filter, Filter::ShaderFilter(_)) {
+        // Flash always reports that a ShaderFilter affects the entire BitmapData, ignoring sourceRect.
+        return Rectangle {
+            x_min: 0,
+            y_min: 0,
+            x_max: target.width() as i32,
+            y_max: target.height() as i32,
+        };
+    }
+    context.renderer.calculate_dest_rect(filter, source_rect)
+}
+
+/// Applies a filter to a region of `source` using the software implementations in `ruffle_render`.
+/// Returns `None` if there's no software implementation of the filter.
+fn apply_filter_on_cpu(
+    source: BitmapDataWrapper<'_>,
+    source_region: PixelRegion,
+    filter: &Filter,
+) -> Option<FilterImage> {
+    // A displacement map's bitmap is only available to us as a render handle, so it needs the renderer.
+    if matches!(
+        filter,
+        Filter::DisplacementMapFilter(_) | Filter::ShaderFilter(_)
+    ) {
+        return None;
+    }
+
+    let read = source.read_area(source_region);
+    let mut source = FilterImage::new(source_region.width(), source_region.height());
+    for y in 0..source_region.height() {
+        for x in 0..source_region.width() {
+            let color = read.get_pixel32_raw(source_region.x_min + x, source_region.y_min + y);
+            source.set(
+                x,
+                y,
+                [color.red(), color.green(), color.blue(), color.alpha()],
+            );
+        }
+    }
+
+    match filter {
+        Filter::BevelFilter(filter) => Some(software::apply_bevel(filter, &source)),
+        Filter::BlurFilter(filter) => Some(software::apply_blur(filter, &source)),
+        Filter::ColorMatrixFilter(filter) => Some(software::apply_color_matrix(filter, &source)),
+        Filter::ConvolutionFilter(filter) => Some(software::apply_convolution(filter, &source)),
+        Filter::DropShadowFilter(filter) => Some(software::apply_drop_shadow(filter, &source)),
+        Filter::GlowFilter(filter) => Some(software::apply_glow(filter, &source)),
+        Filter::GradientBevelFilter(filter) => {
+            Some(software::apply_gradient_bevel(filter, &source))
+        }
+        Filter::GradientGlowFilter(filter) => Some(software::apply_gradient_glow(filter, &source)),
+        Filter::DisplacementMapFilter(_) | Filter::ShaderFilter(_) => None,
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn copy_on_cpu<'gc>(
     context: MutationContext<'gc, '_>,
@@ -1373,7 +1533,7 @@ pub fn draw<'gc>(
     context: &mut UpdateContext<'_, 'gc>,
     target: BitmapDataWrapper<'gc>,
     mut source: IBitmapDrawable<'gc>,
-    transform: Transform,
+    mut transform: Transform,
     smoothing: bool,
     blend_mode: BlendMode,
     clip_rect: Option<Rectangle<Twips>>,
@@ -1391,6 +1551,7 @@ pub fn draw<'gc>(
     // - We are using a bitmapdata as a source
     // - We aren't using impactful blend modes
     // - We don't have a scale component of the transform matrix
+    let mut can_blit_on_cpu = false;
     if let IBitmapDrawable::BitmapData(source) = &source {
         // This seems to do nothing in Flash, regardless of the pixel contents
         // of the source BitmapData. Note - this is different from drawing a 'Bitmap'
@@ -1399,56 +1560,25 @@ pub fn draw<'gc>(
             return Ok(());
         }
 
-        if (blend_mode == BlendMode::Normal || blend_mode == BlendMode::Layer)
+        can_blit_on_cpu = (blend_mode == BlendMode::Normal || blend_mode == BlendMode::Layer)
             && transform.matrix.a == 1.0
             && transform.matrix.b == 0.0
             && transform.matrix.c == 0.0
-            && transform.matrix.d == 1.0
-        {
-            let mut source_region = PixelRegion::for_whole_size(source.width(), source.height());
-            let mut dest_region = PixelRegion::for_whole_size(target.width(), target.height());
-            let tx = transform.matrix.tx.to_pixels().floor() as i32;
-            let ty = transform.matrix.ty.to_pixels().floor() as i32;
-
-            let (cx, cy, cw, ch) = if let Some(clip_rect) = clip_rect {
-                (
-                    clip_rect.x_min.to_pixels().floor() as i32,
-                    clip_rect.y_min.to_pixels().floor() as i32,
-                    clip_rect.width().to_pixels().ceil() as i32,
-                    clip_rect.height().to_pixels().ceil() as i32,
-                )
-            } else {
-                (0, 0, target.width() as i32, target.height() as i32)
-            };
-
-            dest_region.clamp_with_intersection(
-                (cx, cy),
-                (cx - tx, cy - ty),
-                (cw, ch),
-                &mut source_region,
-            );
+            && transform.matrix.d == 1.0;
 
-            if transform.color_transform != ColorTransform::default() {
-                blend_and_transform(
-                    context,
-                    *source,
-                    target,
-                    source_region,
-                    dest_region,
-                    &transform.color_transform,
-                );
-            } else {
-                copy_on_cpu(
-                    context.gc_context,
-                    *source,
-                    target,
-                    source_region,
-                    dest_region,
-                    source.transparency(), // If transparent source, blend the pixels. Otherwise they'll be 0xFF alpha and nothing to blend.
-                );
-            }
+        // If either bitmap has been modified on the GPU, blitting on the CPU would first
+        // require waiting for those changes to be copied back. Render on the GPU instead.
+        if can_blit_on_cpu && !target.has_pending_gpu_changes() && !source.has_pending_gpu_changes()
+        {
+            blit_on_cpu(context, *source, target, &transform, clip_rect);
             return Ok(());
         }
+
+        if can_blit_on_cpu {
+            // Match the CPU blit, which only ever copies whole pixels.
+            transform.matrix.tx = Twips::from_pixels(transform.matrix.tx.to_pixels().floor());
+            transform.matrix.ty = Twips::from_pixels(transform.matrix.ty.to_pixels().floor());
+        }
     }
 
     let mut transform_stack = ruffle_render::transform::TransformStack::new();
@@ -1525,8 +1655,9 @@ pub fn draw<'gc>(
         commands
     };
 
-    let (target, include_dirty_area) = target.overwrite_cpu_pixels_from_gpu(context.gc_context);
-    let mut write = target.write(context.gc_context);
+    let (target_data, include_dirty_area) =
+        target.overwrite_cpu_pixels_from_gpu(context.gc_context);
+    let mut write = target_data.write(context.gc_context);
     // If we have another dirty area to preserve, expand this to include it
     if let Some(old) = include_dirty_area {
         dirty_region.union(old);
@@ -1545,6 +1676,145 @@ pub fn draw<'gc>(
             write.set_gpu_dirty(sync_handle, dirty_region);
             Ok(())
         }
+        None => {
+            drop(write);
+            // This backend can't render offscreen, but we can still handle simple blits ourselves.
+            match source {
+                IBitmapDrawable::BitmapData(source) if can_blit_on_cpu => {
+                    blit_on_cpu(context, source, target, &transform, clip_rect);
+                    Ok(())
+                }
+                _ => Err(BitmapDataDrawError::Unimplemented),
+            }
+        }
+    }
+}
+
+/// Draws `source` onto `target` on the CPU, for draws that are just a translated copy.
+fn blit_on_cpu<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    source: BitmapDataWrapper<'gc>,
+    target: BitmapDataWrapper<'gc>,
+    transform: &Transform,
+    clip_rect: Option<Rectangle<Twips>>,
+) {
+    let mut source_region = PixelRegion::for_whole_size(source.width(), source.height());
+    let mut dest_region = PixelRegion::for_whole_size(target.width(), target.height());
+    let tx = transform.matrix.tx.to_pixels().floor() as i32;
+    let ty = transform.matrix.ty.to_pixels().floor() as i32;
+
+    let (cx, cy, cw, ch) = if let Some(clip_rect) = clip_rect {
+        (
+            clip_rect.x_min.to_pixels().floor() as i32,
+            clip_rect.y_min.to_pixels().floor() as i32,
+            clip_rect.width().to_pixels().ceil() as i32,
+            clip_rect.height().to_pixels().ceil() as i32,
+        )
+    } else {
+        (0, 0, target.width() as i32, target.height() as i32)
+    };
+
+    dest_region.clamp_with_intersection((cx, cy), (cx - tx, cy - ty), (cw, ch), &mut source_region);
+
+    if transform.color_transform != ColorTransform::default() {
+        blend_and_transform(
+            context,
+            source,
+            target,
+            source_region,
+            dest_region,
+            &transform.color_transform,
+        );
+    } else {
+        copy_on_cpu(
+            context.gc_context,
+            source,
+            target,
+            source_region,
+            dest_region,
+            source.transparency(), // If transparent source, blend the pixels. Otherwise they'll be 0xFF alpha and nothing to blend.
+        );
+    }
+}
+
+/// Copies a region of the Stage3D back buffer into `target`, for `Context3D.drawToBitmapData`.
+pub fn draw_stage3d_back_buffer<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    target: BitmapDataWrapper<'gc>,
+    back_buffer: BitmapInfo,
+    src_rect: (i32, i32, i32, i32),
+    dest_point: (i32, i32),
+) -> Result<(), BitmapDataDrawError> {
+    let (src_min_x, src_min_y, src_width, src_height) = src_rect;
+    let (dest_min_x, dest_min_y) = dest_point;
+
+    let mut source_region =
+        PixelRegion::for_whole_size(back_buffer.width.into(), back_buffer.height.into());
+    let mut dest_region = PixelRegion::for_whole_size(target.width(), target.height());
+    dest_region.clamp_with_intersection(
+        (dest_min_x, dest_min_y),
+        (src_min_x, src_min_y),
+        (src_width, src_height),
+        &mut source_region,
+    );
+
+    if dest_region.width() == 0 || dest_region.height() == 0 {
+        return Ok(());
+    }
+
+    // The back buffer replaces the destination pixels, rather than being blended over them.
+    fill_rect(
+        context.gc_context,
+        target,
+        dest_region.x_min as i32,
+        dest_region.y_min as i32,
+        dest_region.width() as i32,
+        dest_region.height() as i32,
+        0,
+    );
+
+    let clip_mat = Matrix {
+        a: dest_region.width() as f32,
+        b: 0.0,
+        c: 0.0,
+        d: dest_region.height() as f32,
+        tx: Twips::from_pixels(dest_region.x_min.into()),
+        ty: Twips::from_pixels(dest_region.y_min.into()),
+    };
+    let transform = Transform {
+        matrix: Matrix::translate(
+            Twips::from_pixels(dest_region.x_min as f64 - source_region.x_min as f64),
+            Twips::from_pixels(dest_region.y_min as f64 - source_region.y_min as f64),
+        ),
+        color_transform: ColorTransform::default(),
+    };
+
+    let mut commands = CommandList::new();
+    commands.push_mask();
+    commands.draw_rect(swf::Color::BLACK, clip_mat);
+    commands.activate_mask();
+    commands.render_bitmap(back_buffer.handle, transform, false, PixelSnapping::Never);
+    commands.deactivate_mask();
+    commands.draw_rect(swf::Color::BLACK, clip_mat);
+    commands.pop_mask();
+
+    let handle = target.bitmap_handle(context.gc_context, context.renderer);
+    let (target, include_dirty_area) = target.overwrite_cpu_pixels_from_gpu(context.gc_context);
+    let mut write = target.write(context.gc_context);
+    if let Some(old) = include_dirty_area {
+        dest_region.union(old);
+    }
+
+    let quality = context.stage.quality();
+    let image = context
+        .renderer
+        .render_offscreen(handle, commands, quality, dest_region);
+
+    match image {
+        Some(sync_handle) => {
+            write.set_gpu_dirty(sync_handle, dest_region);
+            Ok(())
+        }
         None => Err(BitmapDataDrawError::Unimplemented),
     }
 }
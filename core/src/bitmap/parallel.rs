@@ -0,0 +1,88 @@
+//! Helpers for running per-pixel operations over horizontal bands of a bitmap.
+//!
+//! When the `rayon` feature is enabled, rows are processed in parallel. Otherwise
+//! (for example, on web), they are processed sequentially on the current thread.
+
+use crate::bitmap::bitmap_data::Color;
+use std::ops::Range;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// The minimum number of rows that are handed to a single worker at once.
+/// Small bitmaps aren't worth splitting up at all.
+#[cfg(feature = "rayon")]
+const MIN_ROWS_PER_TASK: usize = 16;
+
+/// Calls `f` with the index and pixels of every row in `rows`.
+///
+/// `pixels` is the entire bitmap, laid out row by row with `width` pixels per row.
+pub fn for_each_row<F>(pixels: &mut [Color], width: usize, rows: Range<usize>, f: F)
+where
+    F: Fn(usize, &mut [Color]) + Send + Sync,
+{
+    sum_rows(pixels, width, rows, |y, row| {
+        f(y, row);
+        0
+    });
+}
+
+/// Calls `f` with the index and pixels of every row in `rows`, returning the sum of
+/// the values returned for each row.
+///
+/// `pixels` is the entire bitmap, laid out row by row with `width` pixels per row.
+pub fn sum_rows<F>(pixels: &mut [Color], width: usize, rows: Range<usize>, f: F) -> u32
+where
+    F: Fn(usize, &mut [Color]) -> u32 + Send + Sync,
+{
+    if width == 0 || rows.is_empty() {
+        return 0;
+    }
+
+    let start = rows.start;
+    let pixels = &mut pixels[rows.start * width..rows.end * width];
+
+    #[cfg(feature = "rayon")]
+    {
+        pixels
+            .par_chunks_mut(width)
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .enumerate()
+            .map(|(i, row)| f(start + i, row))
+            .sum()
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    {
+        pixels
+            .chunks_mut(width)
+            .enumerate()
+            .map(|(i, row)| f(start + i, row))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visits_only_requested_rows() {
+        let mut pixels = vec![Color::from(0); 4 * 5];
+        for_each_row(&mut pixels, 4, 1..3, |y, row| {
+            for pixel in row {
+                *pixel = Color::from(y as u32 + 1);
+            }
+        });
+
+        let rows: Vec<u32> = pixels.chunks(4).map(|row| u32::from(row[0])).collect();
+        assert_eq!(rows, vec![0, 2, 3, 0, 0]);
+    }
+
+    #[test]
+    fn sums_row_results() {
+        let mut pixels = vec![Color::from(0); 3 * 100];
+        let total = sum_rows(&mut pixels, 3, 0..100, |_, row| row.len() as u32);
+        assert_eq!(total, 300);
+    }
+}
@@ -2,6 +2,7 @@
 /// feTurbulence element in the SVG specification. It's the usual Perlin noise.
 /// See: https://www.w3.org/TR/SVG11/filters.html#feTurbulenceElement
 /// The `octave_offsets` parameter of `turbulence` was added after porting.
+/// It may contain fewer entries than `num_octaves`; the remaining octaves use no offset.
 
 // Copyright © 2015 W3C® (MIT, ERCIM, Keio, Beihang).
 // This software or document includes material copied from or derived
@@ -200,7 +201,7 @@ impl Turbulence {
                 };
             }
             if base_freq.1 != 0.0 {
-                let lo_freq = (tile_size.1 * base_freq.0).floor() / tile_size.1;
+                let lo_freq = (tile_size.1 * base_freq.1).floor() / tile_size.1;
                 let hi_freq = (tile_size.1 * base_freq.1).ceil() / tile_size.1;
                 base_freq.1 = if base_freq.1 / lo_freq < hi_freq / base_freq.1 {
                     lo_freq
@@ -221,7 +222,8 @@ impl Turbulence {
         let mut sum = 0.0;
         let mut ratio = 1.0;
         for octave in 0..num_octaves {
-            let offset = octave_offsets.get(octave).unwrap();
+            // Octaves without an explicit offset aren't offset at all.
+            let offset = octave_offsets.get(octave).unwrap_or(&(0.0, 0.0));
             let vec = (
                 (point.0 + offset.0) * base_freq.0 * ratio,
                 (point.1 + offset.1) * base_freq.1 * ratio,
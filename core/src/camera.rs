@@ -0,0 +1,425 @@
+//! Video captured from cameras by `Camera`, and the `Video`s that display it.
+
+use crate::avm2::{Activation, Avm2, Error, EventObject, Object};
+use crate::backend::ui::CameraState;
+use crate::context::UpdateContext;
+use gc_arena::{Collect, GcCell, MutationContext};
+use instant::Instant;
+use ruffle_render::bitmap::{Bitmap, BitmapInfo, PixelRegion};
+use std::fmt;
+use std::time::Duration;
+
+/// How many pixels apart motion is sampled at, in each direction.
+const MOTION_SAMPLE_STEP: usize = 4;
+
+/// How much the brightness of a pixel has to change to count as motion, out of 255.
+const MOTION_THRESHOLD: i32 = 20;
+
+/// The capture mode requested with `Camera.setMode`.
+#[derive(Clone, Copy, Debug, Collect)]
+#[collect(require_static)]
+pub struct CameraMode {
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+}
+
+/// The motion detection settings from `Camera.setMotionLevel`.
+#[derive(Clone, Copy, Debug, Collect)]
+#[collect(require_static)]
+pub struct MotionSettings {
+    /// The activity level above which the camera is considered active.
+    pub level: f64,
+
+    /// How long the activity level has to stay below `level` before the camera becomes inactive.
+    pub timeout: Duration,
+}
+
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub struct Camera<'gc>(GcCell<'gc, CameraData<'gc>>);
+
+impl fmt::Debug for Camera<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Camera")
+            .field("ptr", &self.0.as_ptr())
+            .finish()
+    }
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct CameraData<'gc> {
+    /// The AVM2 `Camera` this is the native side of.
+    object: Object<'gc>,
+
+    /// The index of the camera in `UiBackend::camera_names`.
+    index: usize,
+
+    mode: CameraMode,
+
+    motion: MotionSettings,
+
+    /// How many `Video`s this camera is attached to. It's captured from while this is non-zero.
+    attachments: u32,
+
+    #[collect(require_static)]
+    state: CameraState,
+
+    /// The last captured frame, as uploaded to the renderer.
+    #[collect(require_static)]
+    frame: Option<BitmapInfo>,
+
+    /// The pixels of the last captured frame, in RGBA format.
+    #[collect(require_static)]
+    pixels: Option<Bitmap>,
+
+    activity_level: f64,
+
+    is_active: bool,
+
+    /// When the activity level was last above the motion level.
+    #[collect(require_static)]
+    last_motion: Instant,
+
+    current_fps: f64,
+
+    /// The number of frames captured since `fps_start`.
+    fps_frames: u32,
+
+    #[collect(require_static)]
+    fps_start: Instant,
+}
+
+impl<'gc> Camera<'gc> {
+    pub fn object(self) -> Object<'gc> {
+        self.0.read().object
+    }
+
+    pub fn last_frame(self) -> Option<BitmapInfo> {
+        self.0.read().frame.clone()
+    }
+
+    pub fn activity_level(self) -> f64 {
+        self.0.read().activity_level
+    }
+
+    pub fn current_fps(self) -> f64 {
+        self.0.read().current_fps
+    }
+
+    /// Whether the user has denied access to the camera, or hasn't allowed it yet.
+    pub fn is_muted(self) -> bool {
+        self.0.read().state != CameraState::Allowed
+    }
+
+    pub fn mode(self) -> CameraMode {
+        self.0.read().mode
+    }
+
+    /// Returns the ARGB color of a pixel of the last captured frame, scaled to the
+    /// size of the capture mode.
+    pub fn pixel(self, x: u32, y: u32) -> Option<u32> {
+        let read = self.0.read();
+        let pixels = read.pixels.as_ref()?;
+        if x >= read.mode.width || y >= read.mode.height {
+            return None;
+        }
+        let source_x = (x as u64 * pixels.width() as u64 / read.mode.width as u64) as usize;
+        let source_y = (y as u64 * pixels.height() as u64 / read.mode.height as u64) as usize;
+        let i = (source_y * pixels.width() as usize + source_x) * 4;
+        let [r, g, b, _]: [u8; 4] = pixels.data().get(i..i + 4)?.try_into().ok()?;
+        Some(0xFF00_0000 | (r as u32) << 16 | (g as u32) << 8 | b as u32)
+    }
+
+    /// Stores a newly captured frame and uploads it to the renderer.
+    fn set_frame(self, context: &mut UpdateContext<'_, 'gc>, bitmap: Bitmap) {
+        let mut write = self.0.write(context.gc_context);
+
+        let (width, height) = (bitmap.width(), bitmap.height());
+        let frame = match write.frame.take() {
+            Some(frame) if u32::from(frame.width) == width && u32::from(frame.height) == height => {
+                let region = PixelRegion::for_whole_size(width, height);
+                context
+                    .renderer
+                    .update_texture(&frame.handle, bitmap.clone(), region)
+                    .map(|_| frame)
+            }
+            _ => context
+                .renderer
+                .register_bitmap(bitmap.clone())
+                .map(|handle| BitmapInfo {
+                    handle,
+                    width: width as u16,
+                    height: height as u16,
+                }),
+        };
+        match frame {
+            Ok(frame) => write.frame = Some(frame),
+            Err(e) => tracing::error!("Couldn't upload camera frame: {}", e),
+        }
+
+        if let Some(activity_level) = write
+            .pixels
+            .as_ref()
+            .map(|previous| motion_level(previous, &bitmap))
+        {
+            write.activity_level = activity_level;
+        }
+        write.pixels = Some(bitmap);
+
+        write.fps_frames += 1;
+        let elapsed = write.fps_start.elapsed();
+        if elapsed >= Duration::from_secs(1) {
+            write.current_fps = f64::from(write.fps_frames) / elapsed.as_secs_f64();
+            write.fps_frames = 0;
+            write.fps_start = Instant::now();
+        }
+    }
+
+    /// Updates whether there's motion in front of the camera, returning whether that changed.
+    fn update_activity(self, mc: MutationContext<'gc, '_>) -> Option<bool> {
+        let mut write = self.0.write(mc);
+        if write.activity_level >= write.motion.level {
+            write.last_motion = Instant::now();
+        }
+        let is_active = write.activity_level >= write.motion.level
+            || (write.is_active && write.last_motion.elapsed() < write.motion.timeout);
+        if is_active == write.is_active {
+            return None;
+        }
+        write.is_active = is_active;
+        Some(is_active)
+    }
+}
+
+/// Returns the percentage of the picture that changed brightness between two frames.
+fn motion_level(previous: &Bitmap, current: &Bitmap) -> f64 {
+    if previous.width() != current.width() || previous.height() != current.height() {
+        return 0.0;
+    }
+
+    let luma = |data: &[u8], i: usize| {
+        (i32::from(data[i]) * 299 + i32::from(data[i + 1]) * 587 + i32::from(data[i + 2]) * 114)
+            / 1000
+    };
+    let width = current.width() as usize;
+    let mut sampled = 0;
+    let mut moved = 0;
+    for y in (0..current.height() as usize).step_by(MOTION_SAMPLE_STEP) {
+        for x in (0..width).step_by(MOTION_SAMPLE_STEP) {
+            let i = (y * width + x) * 4;
+            sampled += 1;
+            if (luma(previous.data(), i) - luma(current.data(), i)).abs() > MOTION_THRESHOLD {
+                moved += 1;
+            }
+        }
+    }
+
+    if sampled == 0 {
+        0.0
+    } else {
+        (f64::from(moved) * 100.0 / f64::from(sampled)).round()
+    }
+}
+
+/// All `Camera`s that have been created, and the one that's being captured from.
+#[derive(Collect)]
+#[collect(no_drop)]
+pub struct Cameras<'gc> {
+    cameras: Vec<Camera<'gc>>,
+
+    /// The camera being captured from. Only one camera can be captured at once.
+    capturing: Option<Camera<'gc>>,
+}
+
+impl<'gc> Cameras<'gc> {
+    pub fn empty() -> Self {
+        Self {
+            cameras: Vec::new(),
+            capturing: None,
+        }
+    }
+
+    /// Creates the native side of an AVM2 `Camera`.
+    pub fn create(
+        &mut self,
+        mc: MutationContext<'gc, '_>,
+        object: Object<'gc>,
+        index: usize,
+    ) -> Camera<'gc> {
+        let camera = Camera(GcCell::new(
+            mc,
+            CameraData {
+                object,
+                index,
+                mode: CameraMode {
+                    width: 160,
+                    height: 120,
+                    fps: 15.0,
+                },
+                motion: MotionSettings {
+                    level: 50.0,
+                    timeout: Duration::from_millis(2000),
+                },
+                attachments: 0,
+                state: CameraState::Unavailable,
+                frame: None,
+                pixels: None,
+                activity_level: -1.0,
+                is_active: false,
+                last_motion: Instant::now(),
+                current_fps: 0.0,
+                fps_frames: 0,
+                fps_start: Instant::now(),
+            },
+        ));
+        self.cameras.push(camera);
+        camera
+    }
+
+    /// Finds the native side of an AVM2 `Camera`.
+    pub fn get(&self, object: Object<'gc>) -> Option<Camera<'gc>> {
+        self.cameras
+            .iter()
+            .copied()
+            .find(|camera| Object::ptr_eq(camera.object(), object))
+    }
+
+    /// Changes the capture mode of a camera, restarting the capture if it's running.
+    pub fn set_mode(context: &mut UpdateContext<'_, 'gc>, camera: Camera<'gc>, mode: CameraMode) {
+        camera.0.write(context.gc_context).mode = mode;
+        if context
+            .cameras
+            .capturing
+            .map_or(false, |c| GcCell::ptr_eq(c.0, camera.0))
+        {
+            let index = camera.0.read().index;
+            context
+                .ui
+                .start_camera(index, mode.width, mode.height, mode.fps);
+        }
+    }
+
+    pub fn set_motion(mc: MutationContext<'gc, '_>, camera: Camera<'gc>, motion: MotionSettings) {
+        camera.0.write(mc).motion = motion;
+    }
+
+    /// Starts capturing from a camera because a `Video` was attached to it.
+    ///
+    /// This replaces the capture of any other camera.
+    pub fn attach(context: &mut UpdateContext<'_, 'gc>, camera: Camera<'gc>) {
+        camera.0.write(context.gc_context).attachments += 1;
+        if context
+            .cameras
+            .capturing
+            .map_or(false, |c| GcCell::ptr_eq(c.0, camera.0))
+        {
+            return;
+        }
+
+        if let Some(previous) = context.cameras.capturing.take() {
+            previous.0.write(context.gc_context).state = CameraState::Unavailable;
+        }
+        let (index, mode) = {
+            let read = camera.0.read();
+            (read.index, read.mode)
+        };
+        context
+            .ui
+            .start_camera(index, mode.width, mode.height, mode.fps);
+        context.cameras.capturing = Some(camera);
+    }
+
+    /// Stops capturing from a camera once no `Video`s are attached to it.
+    pub fn detach(context: &mut UpdateContext<'_, 'gc>, camera: Camera<'gc>) {
+        let mut write = camera.0.write(context.gc_context);
+        write.attachments = write.attachments.saturating_sub(1);
+        if write.attachments > 0 {
+            return;
+        }
+
+        let Some(capturing) = context.cameras.capturing else {
+            return;
+        };
+        if GcCell::ptr_eq(capturing.0, camera.0) {
+            write.state = CameraState::Unavailable;
+            context.cameras.capturing = None;
+            context.ui.stop_camera();
+        }
+    }
+
+    /// Delivers newly captured frames, and the events of the camera being captured from.
+    pub fn update_cameras(context: &mut UpdateContext<'_, 'gc>) {
+        let Some(camera) = context.cameras.capturing else {
+            return;
+        };
+        let object = camera.object();
+
+        let state = context.ui.camera_state();
+        let status_code = {
+            let mut write = camera.0.write(context.gc_context);
+            let code = match state {
+                _ if state == write.state => None,
+                CameraState::Allowed => Some("Camera.Unmuted"),
+                CameraState::Denied => Some("Camera.Muted"),
+                CameraState::Unavailable | CameraState::Pending => None,
+            };
+            write.state = state;
+            code
+        };
+        if let Some(code) = status_code {
+            Self::dispatch_event(context, object, |activation| {
+                activation.avm2().classes().statusevent.construct(
+                    activation,
+                    &[
+                        "status".into(),
+                        false.into(),
+                        false.into(),
+                        code.into(),
+                        "status".into(),
+                    ],
+                )
+            });
+        }
+
+        let Some(bitmap) = context.ui.take_camera_frame() else {
+            return;
+        };
+        camera.set_frame(context, bitmap);
+        *context.needs_render = true;
+
+        if let Some(activating) = camera.update_activity(context.gc_context) {
+            Self::dispatch_event(context, object, |activation| {
+                activation.avm2().classes().activityevent.construct(
+                    activation,
+                    &[
+                        "activity".into(),
+                        false.into(),
+                        false.into(),
+                        activating.into(),
+                    ],
+                )
+            });
+        }
+
+        Self::dispatch_event(context, object, |activation| {
+            Ok(EventObject::bare_default_event(
+                &mut activation.context,
+                "videoFrame",
+            ))
+        });
+    }
+
+    fn dispatch_event(
+        context: &mut UpdateContext<'_, 'gc>,
+        target: Object<'gc>,
+        event: impl FnOnce(&mut Activation<'_, 'gc>) -> Result<Object<'gc>, Error<'gc>>,
+    ) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+        match event(&mut activation) {
+            Ok(event) => Avm2::dispatch_event(context, event, target),
+            Err(e) => tracing::error!("Encountered AVM2 error when constructing event: {}", e),
+        }
+    }
+}
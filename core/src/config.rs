@@ -24,6 +24,34 @@ pub enum Letterbox {
     On,
 }
 
+/// Controls how often the player runs frames of the movie, relative to
+/// how often the host calls [`crate::Player::tick`].
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename = "frameRateMode")]
+pub enum FrameRateMode {
+    /// Frames run at the movie's own frame rate, regardless of the display.
+    ///
+    /// If the host falls behind, several frames are run in a single tick to catch up,
+    /// so that the movie stays in time with its music.
+    #[default]
+    #[serde(rename = "native")]
+    Native,
+
+    /// A frame runs once the previous one has been presented, so that frames are paced
+    /// by the refresh rate of the display.
+    ///
+    /// While nothing is being presented, such as when the window is minimized, frames run
+    /// at the movie's own frame rate instead.
+    #[serde(rename = "vsync")]
+    Vsync,
+
+    /// Frames run as quickly as the host is able to tick the player, with a short sleep
+    /// between them so that the host doesn't spin.
+    #[serde(rename = "unlimited")]
+    Unlimited,
+}
+
 /// The networking API access mode of the Ruffle player.
 /// This setting is only used on web.
 #[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,689 @@
+//! An ActionScript console, for evaluating expressions while a movie runs
+//!
+//! Ruffle can't compile ActionScript, so the console understands a small language of its own
+//! that covers what's usually needed to poke at a running movie: literals, names, member
+//! access (`a.b` and `a[0]`), calls (`a.b(1, "c")`) and assignments (`a.b = 5`).
+//!
+//! Expressions are evaluated in the context of a display object, which is `this` and whose
+//! members can be used by name. They run on AVM1 or AVM2, depending on the movie that the
+//! display object belongs to.
+
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier, ExecutionReason, Object as Avm1Object,
+    TObject as _, Value as Avm1Value,
+};
+use crate::avm2::{
+    Activation as Avm2Activation, Object as Avm2Object, TObject as _, Value as Avm2Value,
+};
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::string::AvmString;
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+/// A parsed console expression.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    Number(f64),
+    String(String),
+    Bool(bool),
+    Null,
+    Undefined,
+    This,
+    Name(String),
+
+    /// `object.name` or `object[key]`, where `name` becomes a string key.
+    Member(Box<Expression>, Box<Expression>),
+
+    Call(Box<Expression>, Vec<Expression>),
+    Assign(Box<Expression>, Box<Expression>),
+}
+
+impl Expression {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut parser = Parser {
+            input,
+            chars: input.char_indices().peekable(),
+        };
+        let expression = parser.assignment()?;
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok(expression),
+            Some((_, c)) => Err(format!("Unexpected '{c}'")),
+        }
+    }
+
+    /// Whether evaluating this could run code, rather than just read values.
+    fn has_effects(&self) -> bool {
+        match self {
+            Expression::Call(..) | Expression::Assign(..) => true,
+            Expression::Member(object, key) => object.has_effects() || key.has_effects(),
+            _ => false,
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    /// Skip whitespace, then take the next character if it's `expected`.
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        self.chars.next_if(|&(_, c)| c == expected).is_some()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(format!("Expected '{expected}'"))
+        }
+    }
+
+    fn assignment(&mut self) -> Result<Expression, String> {
+        let target = self.postfix()?;
+        if !self.eat('=') {
+            return Ok(target);
+        }
+        if !matches!(target, Expression::Name(_) | Expression::Member(..)) {
+            return Err("Only names and members can be assigned to".to_string());
+        }
+        let value = self.assignment()?;
+        Ok(Expression::Assign(Box::new(target), Box::new(value)))
+    }
+
+    fn postfix(&mut self) -> Result<Expression, String> {
+        let mut expression = self.primary()?;
+        loop {
+            if self.eat('.') {
+                self.skip_whitespace();
+                let name = self.identifier().ok_or("Expected a name after '.'")?;
+                expression = Expression::Member(
+                    Box::new(expression),
+                    Box::new(Expression::String(name.to_string())),
+                );
+            } else if self.eat('[') {
+                let key = self.assignment()?;
+                self.expect(']')?;
+                expression = Expression::Member(Box::new(expression), Box::new(key));
+            } else if self.eat('(') {
+                let mut arguments = Vec::new();
+                if !self.eat(')') {
+                    loop {
+                        arguments.push(self.assignment()?);
+                        if self.eat(')') {
+                            break;
+                        }
+                        self.expect(',')?;
+                    }
+                }
+                expression = Expression::Call(Box::new(expression), arguments);
+            } else {
+                return Ok(expression);
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expression, String> {
+        self.skip_whitespace();
+        let Some(&(start, c)) = self.chars.peek() else {
+            return Err("Unexpected end of input".to_string());
+        };
+        match c {
+            '(' => {
+                self.chars.next();
+                let expression = self.assignment()?;
+                self.expect(')')?;
+                Ok(expression)
+            }
+            '"' | '\'' => self.string(c),
+            '-' => {
+                self.chars.next();
+                match self.primary()? {
+                    Expression::Number(n) => Ok(Expression::Number(-n)),
+                    _ => Err("Only numbers can be negated".to_string()),
+                }
+            }
+            c if c.is_ascii_digit() || c == '.' => {
+                while self
+                    .chars
+                    .next_if(|(_, c)| c.is_ascii_alphanumeric() || *c == '.')
+                    .is_some()
+                {}
+                let end = self.position();
+                let number = &self.input[start..end];
+                let value = match number.strip_prefix("0x") {
+                    Some(hex) => i64::from_str_radix(hex, 16).map(|n| n as f64).ok(),
+                    None => number.parse().ok(),
+                };
+                value
+                    .map(Expression::Number)
+                    .ok_or_else(|| format!("Invalid number '{number}'"))
+            }
+            _ => match self.identifier() {
+                Some("true") => Ok(Expression::Bool(true)),
+                Some("false") => Ok(Expression::Bool(false)),
+                Some("null") => Ok(Expression::Null),
+                Some("undefined") => Ok(Expression::Undefined),
+                Some("this") => Ok(Expression::This),
+                Some(name) => Ok(Expression::Name(name.to_string())),
+                None => Err(format!("Unexpected '{c}'")),
+            },
+        }
+    }
+
+    fn string(&mut self, quote: char) -> Result<Expression, String> {
+        self.chars.next();
+        let mut string = String::new();
+        loop {
+            match self.chars.next() {
+                None => return Err("Unterminated string".to_string()),
+                Some((_, c)) if c == quote => return Ok(Expression::String(string)),
+                Some((_, '\\')) => match self.chars.next() {
+                    Some((_, 'n')) => string.push('\n'),
+                    Some((_, 'r')) => string.push('\r'),
+                    Some((_, 't')) => string.push('\t'),
+                    Some((_, c)) => string.push(c),
+                    None => return Err("Unterminated string".to_string()),
+                },
+                Some((_, c)) => string.push(c),
+            }
+        }
+    }
+
+    fn identifier(&mut self) -> Option<&'a str> {
+        let start = self.position();
+        while self
+            .chars
+            .next_if(|&(_, c)| is_identifier_char(c))
+            .is_some()
+        {}
+        let end = self.position();
+        (end > start).then(|| &self.input[start..end])
+    }
+
+    fn position(&mut self) -> usize {
+        self.chars.peek().map_or(self.input.len(), |&(i, _)| i)
+    }
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '$'
+}
+
+/// Evaluate an expression, returning the result as it should be shown in the console.
+pub fn evaluate<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    target: DisplayObject<'gc>,
+    input: &str,
+) -> Result<String, String> {
+    let expression = Expression::parse(input)?;
+    if target.movie().is_action_script_3() {
+        let mut console = Avm2Console::new(context, target);
+        let value = console.evaluate(&expression)?;
+        Ok(console.describe(value))
+    } else {
+        Avm1Console::run(context, target, |console| {
+            let value = console.evaluate(&expression)?;
+            Ok(console.describe(value))
+        })
+        .unwrap_or_else(|| Err("There's no movie to run in".to_string()))
+    }
+}
+
+/// The ways that the input could continue, when it ends with a partially typed name.
+///
+/// Each completion is the whole input with that name finished.
+pub fn complete<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    target: DisplayObject<'gc>,
+    input: &str,
+) -> Vec<String> {
+    let partial_start = input
+        .char_indices()
+        .rev()
+        .take_while(|&(_, c)| is_identifier_char(c))
+        .last()
+        .map_or(input.len(), |(i, _)| i);
+    let (before, partial) = input.split_at(partial_start);
+
+    let object = match before.strip_suffix('.') {
+        Some(before) => {
+            let object = &before[member_chain_start(before)..];
+            match Expression::parse(object) {
+                // Completing shouldn't change anything, so only look at plain values.
+                Ok(expression) if !expression.has_effects() => Some(expression),
+                _ => return Vec::new(),
+            }
+        }
+        None => None,
+    };
+
+    let mut names = if target.movie().is_action_script_3() {
+        let mut console = Avm2Console::new(context, target);
+        console.members(object.as_ref())
+    } else {
+        Avm1Console::run(context, target, |console| console.members(object.as_ref()))
+            .unwrap_or_default()
+    };
+    names.retain(|name| name.starts_with(partial) && name != partial);
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .map(|name| format!("{before}{name}"))
+        .collect()
+}
+
+/// Where the chain of members that ends `input` starts, such as `b.c[0]` in `a = b.c[0]`.
+fn member_chain_start(input: &str) -> usize {
+    let mut depth = 0;
+    for (i, c) in input.char_indices().rev() {
+        match c {
+            ')' | ']' => depth += 1,
+            '(' | '[' if depth == 0 => return i + 1,
+            '(' | '[' => depth -= 1,
+            c if depth == 0 && !is_identifier_char(c) && c != '.' => return i + c.len_utf8(),
+            _ => {}
+        }
+    }
+    0
+}
+
+struct Avm1Console<'a, 'b, 'gc> {
+    activation: &'b mut Avm1Activation<'a, 'gc>,
+    this: Avm1Object<'gc>,
+}
+
+impl<'a, 'b, 'gc> Avm1Console<'a, 'b, 'gc> {
+    /// Run with the target's timeline as the scope, or the root movie's if it has none.
+    fn run<R>(
+        context: &mut UpdateContext<'_, 'gc>,
+        target: DisplayObject<'gc>,
+        f: impl for<'c, 'd> FnOnce(&mut Avm1Console<'c, 'd, 'gc>) -> R,
+    ) -> Option<R> {
+        let target = [Some(target), context.stage.root_clip()]
+            .into_iter()
+            .flatten()
+            .find(|target| matches!(target.object(), Avm1Value::Object(_)))?;
+        let mut activation = Avm1Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[Console]"),
+            target,
+        );
+        let swf_version = target.swf_version();
+        Some(activation.run_with_child_frame_for_display_object(
+            "[Console]",
+            target,
+            swf_version,
+            |activation| {
+                let this = target.object().coerce_to_object(activation);
+                f(&mut Avm1Console { activation, this })
+            },
+        ))
+    }
+
+    fn evaluate(&mut self, expression: &Expression) -> Result<Avm1Value<'gc>, String> {
+        let activation = &mut *self.activation;
+        Ok(match expression {
+            Expression::Number(n) => (*n).into(),
+            Expression::String(s) => AvmString::new_utf8(activation.context.gc_context, s).into(),
+            Expression::Bool(b) => (*b).into(),
+            Expression::Null => Avm1Value::Null,
+            Expression::Undefined => Avm1Value::Undefined,
+            Expression::This => self.this.into(),
+            Expression::Name(name) => {
+                let name = AvmString::new_utf8(activation.context.gc_context, name);
+                activation.get_variable(name).map_err(error)?.into()
+            }
+            Expression::Member(object, key) => {
+                let object = self.object(object)?;
+                let key = self.key(key)?;
+                object.get(key, self.activation).map_err(error)?
+            }
+            Expression::Call(function, arguments) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.evaluate(argument))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match &**function {
+                    Expression::Member(object, key) => {
+                        let object = self.object(object)?;
+                        let key = self.key(key)?;
+                        object
+                            .call_method(key, &arguments, self.activation, ExecutionReason::Special)
+                            .map_err(error)?
+                    }
+                    Expression::Name(name) => {
+                        let activation = &mut *self.activation;
+                        let name = AvmString::new_utf8(activation.context.gc_context, name);
+                        activation
+                            .get_variable(name)
+                            .map_err(error)?
+                            .call_with_default_this(self.this, name, activation, &arguments)
+                            .map_err(error)?
+                    }
+                    function => {
+                        let function = self.object(function)?;
+                        function
+                            .call(
+                                "[Console]".into(),
+                                self.activation,
+                                self.this.into(),
+                                &arguments,
+                            )
+                            .map_err(error)?
+                    }
+                }
+            }
+            Expression::Assign(target, value) => {
+                let value = self.evaluate(value)?;
+                match &**target {
+                    Expression::Member(object, key) => {
+                        let object = self.object(object)?;
+                        let key = self.key(key)?;
+                        object.set(key, value, self.activation).map_err(error)?;
+                    }
+                    Expression::Name(name) => {
+                        let activation = &mut *self.activation;
+                        let name = AvmString::new_utf8(activation.context.gc_context, name);
+                        activation.set_variable(name, value).map_err(error)?;
+                    }
+                    _ => unreachable!("The parser only allows assigning to names and members"),
+                }
+                value
+            }
+        })
+    }
+
+    fn object(&mut self, expression: &Expression) -> Result<Avm1Object<'gc>, String> {
+        match self.evaluate(expression)? {
+            value @ (Avm1Value::Object(_) | Avm1Value::MovieClip(_)) => {
+                Ok(value.coerce_to_object(self.activation))
+            }
+            value => Err(format!("{} has no properties", self.describe(value))),
+        }
+    }
+
+    fn key(&mut self, expression: &Expression) -> Result<AvmString<'gc>, String> {
+        let key = self.evaluate(expression)?;
+        key.coerce_to_string(self.activation).map_err(error)
+    }
+
+    /// The names of the members of an object, or of `this` and the globals.
+    fn members(&mut self, object: Option<&Expression>) -> Vec<String> {
+        let mut names = Vec::new();
+        let objects = match object {
+            Some(object) => match self.object(object) {
+                Ok(object) => vec![object],
+                Err(_) => return names,
+            },
+            None => {
+                names.extend(["this", "_root", "_global", "_parent"].map(str::to_string));
+                let global = self.activation.context.avm1.global_object();
+                vec![self.this, global]
+            }
+        };
+        for object in objects {
+            names.extend(
+                object
+                    .get_keys(self.activation, true)
+                    .iter()
+                    .map(|name| name.to_string()),
+            );
+        }
+        names
+    }
+
+    fn describe(&mut self, value: Avm1Value<'gc>) -> String {
+        match value {
+            Avm1Value::String(s) => format!("{:?}", s.to_string()),
+            Avm1Value::Undefined => "undefined".to_string(),
+            value => match value.coerce_to_string(self.activation) {
+                Ok(s) => s.to_string(),
+                Err(e) => e.to_string(),
+            },
+        }
+    }
+}
+
+struct Avm2Console<'a, 'gc> {
+    activation: Avm2Activation<'a, 'gc>,
+    this: Option<Avm2Object<'gc>>,
+}
+
+impl<'a, 'gc> Avm2Console<'a, 'gc> {
+    /// Run in the application domain of the target's movie.
+    fn new(context: &'a mut UpdateContext<'_, 'gc>, target: DisplayObject<'gc>) -> Self {
+        let domain = context
+            .library
+            .library_for_movie(target.movie())
+            .map_or_else(
+                || context.avm2.stage_domain(),
+                |library| library.avm2_domain(),
+            );
+        Self {
+            activation: Avm2Activation::from_domain(context.reborrow(), domain),
+            this: target.object2().as_object(),
+        }
+    }
+
+    fn evaluate(&mut self, expression: &Expression) -> Result<Avm2Value<'gc>, String> {
+        Ok(match expression {
+            Expression::Number(n) => (*n).into(),
+            Expression::String(s) => {
+                AvmString::new_utf8(self.activation.context.gc_context, s).into()
+            }
+            Expression::Bool(b) => (*b).into(),
+            Expression::Null => Avm2Value::Null,
+            Expression::Undefined => Avm2Value::Undefined,
+            Expression::This => self.this.map_or(Avm2Value::Null, Into::into),
+            Expression::Name(name) => self.name(name)?,
+            Expression::Member(object, key) => match self.object(object) {
+                Ok(object) => {
+                    let key = self.key(key)?;
+                    object
+                        .get_public_property(key, &mut self.activation)
+                        .map_err(error)?
+                }
+                Err(e) => self.definition(expression).ok_or(e)?,
+            },
+            Expression::Call(function, arguments) => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| self.evaluate(argument))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match &**function {
+                    Expression::Member(object, key) => match self.object(object) {
+                        Ok(object) => {
+                            let key = self.key(key)?;
+                            object
+                                .call_public_property(key, &arguments, &mut self.activation)
+                                .map_err(error)?
+                        }
+                        Err(e) => {
+                            let function = self.definition(function).ok_or(e)?;
+                            let function = function
+                                .coerce_to_object(&mut self.activation)
+                                .map_err(error)?;
+                            function
+                                .call(Avm2Value::Null, &arguments, &mut self.activation)
+                                .map_err(error)?
+                        }
+                    },
+                    function => {
+                        let function = self.object(function)?;
+                        let receiver = self.this.map_or(Avm2Value::Null, Into::into);
+                        function
+                            .call(receiver, &arguments, &mut self.activation)
+                            .map_err(error)?
+                    }
+                }
+            }
+            Expression::Assign(target, value) => {
+                let value = self.evaluate(value)?;
+                let (mut object, key) = match &**target {
+                    Expression::Member(object, key) => (self.object(object)?, self.key(key)?),
+                    Expression::Name(name) => (
+                        self.this.ok_or("There's no object to assign to")?,
+                        AvmString::new_utf8(self.activation.context.gc_context, name),
+                    ),
+                    _ => unreachable!("The parser only allows assigning to names and members"),
+                };
+                object
+                    .set_public_property(key, value, &mut self.activation)
+                    .map_err(error)?;
+                value
+            }
+        })
+    }
+
+    /// Look up a name on `this`, and then as a definition in the application domain.
+    fn name(&mut self, name: &str) -> Result<Avm2Value<'gc>, String> {
+        let name = AvmString::new_utf8(self.activation.context.gc_context, name);
+        if let Some(this) = self.this {
+            if this.has_public_property(name, &mut self.activation) {
+                return this
+                    .get_public_property(name, &mut self.activation)
+                    .map_err(error);
+            }
+        }
+        let domain = self.activation.domain();
+        domain
+            .get_defined_value_handling_vector(&mut self.activation, name)
+            .map_err(|_| format!("{name} is not defined"))
+    }
+
+    /// Packages aren't values, so definitions such as `flash.utils.getTimer` are looked up
+    /// by the qualified name that the expression spells out.
+    fn definition(&mut self, expression: &Expression) -> Option<Avm2Value<'gc>> {
+        let name = qualified_name(expression)?;
+        let name = AvmString::new_utf8(self.activation.context.gc_context, name);
+        let domain = self.activation.domain();
+        domain
+            .get_defined_value_handling_vector(&mut self.activation, name)
+            .ok()
+    }
+
+    fn object(&mut self, expression: &Expression) -> Result<Avm2Object<'gc>, String> {
+        let value = self.evaluate(expression)?;
+        value
+            .coerce_to_object(&mut self.activation)
+            .map_err(|_| format!("{} has no properties", self.describe(value)))
+    }
+
+    fn key(&mut self, expression: &Expression) -> Result<AvmString<'gc>, String> {
+        let key = self.evaluate(expression)?;
+        key.coerce_to_string(&mut self.activation).map_err(error)
+    }
+
+    /// The names of the public members of an object, or of `this`.
+    fn members(&mut self, object: Option<&Expression>) -> Vec<String> {
+        let mut names = Vec::new();
+        let object = match object {
+            Some(object) => self.object(object).ok(),
+            None => {
+                names.push("this".to_string());
+                self.this
+            }
+        };
+        let Some(object) = object else {
+            return names;
+        };
+
+        if let Some(vtable) = object.vtable() {
+            names.extend(
+                vtable
+                    .resolved_traits()
+                    .iter()
+                    .filter(|(_, ns, _)| ns.is_public())
+                    .map(|(name, _, _)| name.to_string()),
+            );
+        }
+        let mut index = 0;
+        while let Ok(Some(next)) = object.get_next_enumerant(index, &mut self.activation) {
+            if let Ok(name) = object.get_enumerant_name(next, &mut self.activation) {
+                if let Ok(name) = name.coerce_to_string(&mut self.activation) {
+                    names.push(name.to_string());
+                }
+            }
+            index = next;
+        }
+        names
+    }
+
+    fn describe(&mut self, value: Avm2Value<'gc>) -> String {
+        match value {
+            Avm2Value::String(s) => format!("{:?}", s.to_string()),
+            value => match value.coerce_to_string(&mut self.activation) {
+                Ok(s) => s.to_string(),
+                Err(e) => e.to_string(),
+            },
+        }
+    }
+}
+
+/// The dotted name that an expression such as `flash.utils.getTimer` spells out, if any.
+fn qualified_name(expression: &Expression) -> Option<String> {
+    match expression {
+        Expression::Name(name) => Some(name.clone()),
+        Expression::Member(object, key) => match &**key {
+            Expression::String(key) => Some(format!("{}.{key}", qualified_name(object)?)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn error(error: impl ToString) -> String {
+    error.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_expressions() {
+        assert_eq!(
+            Expression::parse("a.b[0]('c', -1.5) = true"),
+            Err("Only names and members can be assigned to".to_string())
+        );
+        assert_eq!(
+            Expression::parse("this.a[\"b\"] = 0x10"),
+            Ok(Expression::Assign(
+                Box::new(Expression::Member(
+                    Box::new(Expression::Member(
+                        Box::new(Expression::This),
+                        Box::new(Expression::String("a".to_string()))
+                    )),
+                    Box::new(Expression::String("b".to_string()))
+                )),
+                Box::new(Expression::Number(16.0))
+            ))
+        );
+        assert_eq!(
+            Expression::parse("trace(x, null)"),
+            Ok(Expression::Call(
+                Box::new(Expression::Name("trace".to_string())),
+                vec![Expression::Name("x".to_string()), Expression::Null]
+            ))
+        );
+        assert!(Expression::parse("a.").is_err());
+        assert!(Expression::parse("'a").is_err());
+    }
+
+    #[test]
+    fn finds_member_chains() {
+        assert_eq!(member_chain_start("a = b.c[d(1)]"), 4);
+        assert_eq!(member_chain_start("f(a.b"), 2);
+        assert_eq!(member_chain_start("a.b"), 0);
+    }
+}
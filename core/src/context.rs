@@ -1,25 +1,37 @@
 //! Contexts and helper types passed between functions.
 
+use crate::api_usage::ApiUsage;
 use crate::avm1::Avm1;
 use crate::avm1::SystemProperties;
 use crate::avm1::{Object as Avm1Object, Value as Avm1Value};
 use crate::avm2::{Avm2, Object as Avm2Object, SoundChannelObject};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    filesystem::FilesystemBackend,
     log::LogBackend,
-    navigator::NavigatorBackend,
+    navigator::{
+        javascript_url_script, parse_javascript_call, JavascriptUrlMode, NavigationMethod,
+        NavigatorBackend,
+    },
     storage::StorageBackend,
     ui::{InputManager, UiBackend},
 };
+use crate::camera::Cameras;
 use crate::context_menu::ContextMenuState;
+use crate::debugger::Debugger;
 use crate::display_object::{EditText, InteractiveObject, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
 use crate::frame_lifecycle::FramePhase;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnections;
+use crate::mouse_cursor::MouseCursors;
+use crate::net_connection::NetConnections;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::profiler::Profiler;
+use crate::save_state::Journal;
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
 use crate::string::AvmStringInterner;
@@ -28,6 +40,7 @@ use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::timer::Timers;
 use core::fmt;
 use gc_arena::{Collect, Mutation};
+use indexmap::IndexMap;
 use instant::Instant;
 use rand::rngs::SmallRng;
 use ruffle_render::backend::{BitmapCacheEntry, RenderBackend};
@@ -84,6 +97,18 @@ pub struct UpdateContext<'a, 'gc> {
     /// A collection of stubs encountered during this movie.
     pub stub_tracker: &'a mut StubCollection,
 
+    /// Records timings of the player while profiling is enabled.
+    pub profiler: &'a mut Profiler,
+
+    /// Counts calls to built-in APIs while enabled.
+    pub api_usage: &'a mut ApiUsage,
+
+    /// Records everything that happens to the player while save states are enabled.
+    pub journal: &'a mut Journal,
+
+    /// Decides when scripts stop while a debugger is attached.
+    pub debugger: &'a mut Debugger,
+
     /// The library containing character definitions for this SWF.
     /// Used to instantiate a `DisplayObject` of a given ID.
     pub library: &'a mut Library<'gc>,
@@ -119,6 +144,9 @@ pub struct UpdateContext<'a, 'gc> {
     /// The storage backend, used for storing persistent state
     pub storage: &'a mut dyn StorageBackend,
 
+    /// The filesystem backend, used by AIR's `flash.filesystem` API
+    pub filesystem: &'a mut dyn FilesystemBackend,
+
     /// The logging backend, used for trace output capturing.
     ///
     /// **DO NOT** use this field directly, use the `avm_trace` method instead.
@@ -192,6 +220,10 @@ pub struct UpdateContext<'a, 'gc> {
     /// The instant at which the SWF was launched.
     pub start_time: Instant,
 
+    /// The time that scripts see as having passed since the SWF was launched, if the
+    /// player is running on a fixed clock instead of in real time.
+    pub fixed_time: Option<Duration>,
+
     /// The instant at which the current update started.
     pub update_start: Instant,
 
@@ -214,6 +246,10 @@ pub struct UpdateContext<'a, 'gc> {
     /// Whether movies are prevented from changing the stage frame rate.
     pub forced_frame_rate: bool,
 
+    /// Whether a `paste` event is being dispatched, which is the only time that
+    /// `Clipboard.generalClipboard` can be read.
+    pub in_paste_handler: bool,
+
     /// Amount of actions performed since the last timeout check
     pub actions_since_timeout_check: &'a mut u16,
 
@@ -227,6 +263,18 @@ pub struct UpdateContext<'a, 'gc> {
 
     pub sockets: &'a mut Sockets<'gc>,
 
+    /// RTMP connections made by `NetConnection`.
+    pub net_connections: &'a mut NetConnections<'gc>,
+
+    /// `LocalConnection`s that are listening for messages.
+    pub local_connections: &'a mut LocalConnections<'gc>,
+
+    /// Cameras that video can be captured from.
+    pub cameras: &'a mut Cameras<'gc>,
+
+    /// The cursor chosen with `Mouse.cursor`, and the custom cursors that can be chosen.
+    pub mouse_cursors: &'a mut MouseCursors,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     pub dynamic_root: gc_arena::DynamicRootSet<'gc>,
 }
@@ -268,6 +316,10 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             .start_sound(self.audio, sound, settings, owner, avm1_object)
     }
 
+    pub fn start_dynamic_sound(&mut self, source: Avm2Object<'gc>) -> Option<SoundInstanceHandle> {
+        self.audio_manager.start_dynamic_sound(self.audio, source)
+    }
+
     pub fn attach_avm2_sound_channel(
         &mut self,
         instance: SoundInstanceHandle,
@@ -334,6 +386,14 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
         self.gc_context
     }
 
+    /// The time that scripts see as having passed since the SWF was launched.
+    pub fn elapsed_time(&mut self) -> Duration {
+        let time = self
+            .fixed_time
+            .unwrap_or_else(|| Instant::now().duration_since(self.start_time));
+        self.journal.time(time)
+    }
+
     /// Transform a borrowed update context into an owned update context with
     /// a shorter internal lifetime.
     ///
@@ -351,6 +411,10 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             gc_context: self.gc_context,
             interner: self.interner,
             stub_tracker: self.stub_tracker,
+            profiler: self.profiler,
+            api_usage: self.api_usage,
+            journal: self.journal,
+            debugger: self.debugger,
             library: self.library,
             player_version: self.player_version,
             needs_render: self.needs_render,
@@ -363,6 +427,7 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             ui: self.ui,
             video: self.video,
             storage: self.storage,
+            filesystem: self.filesystem,
             rng: self.rng,
             stage: self.stage,
             mouse_over_object: self.mouse_over_object,
@@ -383,6 +448,7 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             avm2: self.avm2,
             external_interface: self.external_interface,
             start_time: self.start_time,
+            fixed_time: self.fixed_time,
             update_start: self.update_start,
             max_execution_duration: self.max_execution_duration,
             focus_tracker: self.focus_tracker,
@@ -390,10 +456,15 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
             time_offset: self.time_offset,
             frame_rate: self.frame_rate,
             forced_frame_rate: self.forced_frame_rate,
+            in_paste_handler: self.in_paste_handler,
             actions_since_timeout_check: self.actions_since_timeout_check,
             frame_phase: self.frame_phase,
             stream_manager: self.stream_manager,
             sockets: self.sockets,
+            net_connections: self.net_connections,
+            local_connections: self.local_connections,
+            cameras: self.cameras,
+            mouse_cursors: self.mouse_cursors,
             dynamic_root: self.dynamic_root,
         }
     }
@@ -416,6 +487,39 @@ impl<'a, 'gc> UpdateContext<'a, 'gc> {
     pub fn avm_trace(&self, message: &str) {
         self.log.avm_trace(&message.replace('\r', "\n"));
     }
+
+    /// Open a URL for `getURL` or `navigateToURL`.
+    ///
+    /// `javascript:` URLs become `ExternalInterface` calls if the navigator asks for that,
+    /// and everything else is left to the navigator.
+    pub fn navigate_to_url(
+        &mut self,
+        url: &str,
+        target: &str,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        if self.navigator.javascript_url_mode() == JavascriptUrlMode::ExternalInterface {
+            if let Some(script) = javascript_url_script(url) {
+                let Some((name, args)) = parse_javascript_call(script) else {
+                    tracing::warn!(
+                        "Can't turn the script \"{script}\" into an ExternalInterface call"
+                    );
+                    return;
+                };
+                let Some(method) = self.external_interface.get_method_for(&name) else {
+                    tracing::warn!("No ExternalInterface method {name} for a javascript: URL");
+                    return;
+                };
+                if let Err(e) = method.call(self, &args) {
+                    tracing::warn!(
+                        "ExternalInterface call {name} for a javascript: URL failed: {e}"
+                    );
+                }
+                return;
+            }
+        }
+        self.navigator.navigate_to_url(url, target, vars_method);
+    }
 }
 
 /// A queued ActionScript call.
@@ -6,8 +6,7 @@
 
 use crate::avm1;
 use crate::avm2;
-use crate::display_object::Stage;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, Stage, TDisplayObject};
 use crate::i18n::core_text;
 use fluent_templates::LanguageIdentifier;
 use gc_arena::Collect;
@@ -19,6 +18,9 @@ use serde::Serialize;
 pub struct ContextMenuState<'gc> {
     info: Vec<ContextMenuItem>,
     callbacks: Vec<ContextMenuCallback<'gc>>,
+
+    /// The object that was right-clicked, and the object whose menu is shown.
+    target: Option<(DisplayObject<'gc>, DisplayObject<'gc>)>,
 }
 
 impl<'gc> ContextMenuState<'gc> {
@@ -35,6 +37,12 @@ impl<'gc> ContextMenuState<'gc> {
     pub fn callback(&self, index: usize) -> &ContextMenuCallback<'gc> {
         &self.callbacks[index]
     }
+    pub fn target(&self) -> Option<(DisplayObject<'gc>, DisplayObject<'gc>)> {
+        self.target
+    }
+    pub fn set_target(&mut self, mouse_target: DisplayObject<'gc>, owner: DisplayObject<'gc>) {
+        self.target = Some((mouse_target, owner));
+    }
     pub fn build_builtin_items(
         &mut self,
         item_flags: BuiltInItemFlags,
@@ -1,24 +1,28 @@
 mod avm1;
 mod avm2;
+mod console;
 mod display_object;
 mod handle;
+mod memory;
 mod movie;
+mod profiler;
 
 use crate::context::{RenderContext, UpdateContext};
 use crate::debug_ui::avm1::Avm1ObjectWindow;
 use crate::debug_ui::avm2::Avm2ObjectWindow;
+use crate::debug_ui::console::ConsoleWindow;
 use crate::debug_ui::display_object::{DisplayObjectSearchWindow, DisplayObjectWindow};
 use crate::debug_ui::handle::{AVM1ObjectHandle, AVM2ObjectHandle, DisplayObjectHandle};
+use crate::debug_ui::memory::MemoryProfileWindow;
 use crate::debug_ui::movie::{MovieListWindow, MovieWindow};
+use crate::debug_ui::profiler::ProfilerWindow;
 use crate::display_object::TDisplayObject;
+use crate::inspector::draw_debug_rect;
 use crate::tag_utils::SwfMovie;
 use gc_arena::DynamicRootSet;
 use hashbrown::HashMap;
-use ruffle_render::commands::CommandHandler;
-use ruffle_render::matrix::Matrix;
 use std::fmt::{Debug, Formatter};
 use std::sync::{Arc, Weak};
-use swf::{Color, Rectangle, Twips};
 use weak_table::PtrWeakKeyHashMap;
 
 #[derive(Default)]
@@ -31,6 +35,9 @@ pub struct DebugUi {
     items_to_save: Vec<ItemToSave>,
     movie_list: Option<MovieListWindow>,
     display_object_search: Option<DisplayObjectSearchWindow>,
+    memory_profile: Option<MemoryProfileWindow>,
+    profiler: Option<ProfilerWindow>,
+    console: Option<ConsoleWindow>,
 }
 
 #[derive(Debug)]
@@ -44,6 +51,9 @@ pub enum Message {
     ShowKnownMovies,
     SaveFile(ItemToSave),
     SearchForDisplayObject,
+    ShowMemoryProfile,
+    ShowProfiler,
+    ShowConsole(Option<DisplayObjectHandle>),
 }
 
 impl DebugUi {
@@ -85,6 +95,24 @@ impl DebugUi {
             }
         }
 
+        if let Some(mut memory_profile) = self.memory_profile.take() {
+            if memory_profile.show(egui_ctx, context, &mut messages) {
+                self.memory_profile = Some(memory_profile);
+            }
+        }
+
+        if let Some(mut profiler) = self.profiler.take() {
+            if profiler.show(egui_ctx, context, &mut messages) {
+                self.profiler = Some(profiler);
+            }
+        }
+
+        if let Some(mut console) = self.console.take() {
+            if console.show(egui_ctx, context, &mut messages) {
+                self.console = Some(console);
+            }
+        }
+
         for message in messages {
             match message {
                 Message::TrackDisplayObject(object) => {
@@ -114,6 +142,15 @@ impl DebugUi {
                 Message::SearchForDisplayObject => {
                     self.display_object_search = Some(Default::default());
                 }
+                Message::ShowMemoryProfile => {
+                    self.memory_profile = Some(Default::default());
+                }
+                Message::ShowProfiler => {
+                    self.profiler = Some(Default::default());
+                }
+                Message::ShowConsole(target) => {
+                    self.console = Some(ConsoleWindow::new(target));
+                }
             }
         }
     }
@@ -183,6 +220,13 @@ impl DebugUi {
                 draw_debug_rect(context, swf::Color::RED, bounds, 5.0);
             }
         }
+
+        if let Some(object) = self.console.as_ref().and_then(|w| w.hovered_debug_rect()) {
+            let object = object.fetch(dynamic_root_set);
+            let bounds = world_matrix * object.world_bounds();
+
+            draw_debug_rect(context, swf::Color::RED, bounds, 5.0);
+        }
     }
 }
 
@@ -199,47 +243,3 @@ impl Debug for ItemToSave {
             .finish()
     }
 }
-
-fn draw_debug_rect(
-    context: &mut RenderContext,
-    color: Color,
-    bounds: Rectangle<Twips>,
-    thickness: f32,
-) {
-    let width = bounds.width().to_pixels() as f32;
-    let height = bounds.height().to_pixels() as f32;
-    let thickness_twips = Twips::from_pixels(thickness as f64);
-
-    // Top
-    context.commands.draw_rect(
-        color,
-        Matrix::create_box(
-            width,
-            thickness,
-            0.0,
-            bounds.x_min,
-            bounds.y_min - thickness_twips,
-        ),
-    );
-    // Bottom
-    context.commands.draw_rect(
-        color,
-        Matrix::create_box(width, thickness, 0.0, bounds.x_min, bounds.y_max),
-    );
-    // Left
-    context.commands.draw_rect(
-        color,
-        Matrix::create_box(
-            thickness,
-            height,
-            0.0,
-            bounds.x_min - thickness_twips,
-            bounds.y_min,
-        ),
-    );
-    // Right
-    context.commands.draw_rect(
-        color,
-        Matrix::create_box(thickness, height, 0.0, bounds.x_max, bounds.y_min),
-    );
-}
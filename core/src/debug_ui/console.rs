@@ -0,0 +1,128 @@
+use crate::console;
+use crate::context::UpdateContext;
+use crate::debug_ui::display_object::open_display_object_button;
+use crate::debug_ui::handle::DisplayObjectHandle;
+use crate::debug_ui::Message;
+use egui::{Key, ScrollArea, TextEdit, TextStyle, Window};
+
+#[derive(Debug, Default)]
+pub struct ConsoleWindow {
+    /// The display object that expressions are evaluated on, or the root movie if none.
+    target: Option<DisplayObjectHandle>,
+    input: String,
+    history: Vec<(String, Result<String, String>)>,
+    completions: Vec<String>,
+    hovered_debug_rect: Option<DisplayObjectHandle>,
+}
+
+impl ConsoleWindow {
+    pub fn new(target: Option<DisplayObjectHandle>) -> Self {
+        Self {
+            target,
+            ..Default::default()
+        }
+    }
+
+    pub fn hovered_debug_rect(&self) -> Option<DisplayObjectHandle> {
+        self.hovered_debug_rect.clone()
+    }
+
+    pub fn show(
+        &mut self,
+        egui_ctx: &egui::Context,
+        context: &mut UpdateContext,
+        messages: &mut Vec<Message>,
+    ) -> bool {
+        let mut keep_open = true;
+        self.hovered_debug_rect = None;
+
+        Window::new("Console")
+            .open(&mut keep_open)
+            .default_width(500.0)
+            .show(egui_ctx, |ui| {
+                let target = match &self.target {
+                    Some(handle) => Some(handle.fetch(context.dynamic_root)),
+                    None => context.stage.root_clip(),
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label("Target:");
+                    if let Some(target) = target {
+                        open_display_object_button(
+                            ui,
+                            context,
+                            messages,
+                            target,
+                            &mut self.hovered_debug_rect,
+                        );
+                    } else {
+                        ui.label("None");
+                    }
+                    if self.target.is_some() && ui.button("Use Root").clicked() {
+                        self.target = None;
+                    }
+                });
+                ui.separator();
+
+                ScrollArea::vertical()
+                    .max_height(300.0)
+                    .auto_shrink([false, true])
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for (input, result) in &self.history {
+                            ui.monospace(format!("> {input}"));
+                            match result {
+                                Ok(value) => ui.monospace(value),
+                                Err(error) => {
+                                    ui.colored_label(ui.style().visuals.error_fg_color, error)
+                                }
+                            };
+                        }
+                    });
+                ui.separator();
+
+                let response = ui.add(
+                    TextEdit::singleline(&mut self.input)
+                        .font(TextStyle::Monospace)
+                        .hint_text("Expression, such as this.x = 10")
+                        .desired_width(f32::INFINITY)
+                        .lock_focus(true),
+                );
+                let Some(target) = target else {
+                    return;
+                };
+
+                if response.has_focus() && ui.input(|input| input.key_pressed(Key::Tab)) {
+                    self.completions = console::complete(context, target, &self.input);
+                    if let [completion] = self.completions.as_slice() {
+                        self.input = completion.clone();
+                        self.completions.clear();
+                    }
+                } else if response.changed() {
+                    self.completions.clear();
+                }
+
+                if response.lost_focus() && ui.input(|input| input.key_pressed(Key::Enter)) {
+                    let input = std::mem::take(&mut self.input);
+                    if !input.trim().is_empty() {
+                        let result = console::evaluate(context, target, &input);
+                        self.history.push((input, result));
+                    }
+                    self.completions.clear();
+                    response.request_focus();
+                }
+
+                if !self.completions.is_empty() {
+                    ui.horizontal_wrapped(|ui| {
+                        for completion in &self.completions {
+                            if ui.button(completion).clicked() {
+                                self.input = completion.clone();
+                            }
+                        }
+                    });
+                }
+            });
+
+        keep_open
+    }
+}
@@ -5,16 +5,26 @@ pub use search::DisplayObjectSearchWindow;
 
 use crate::avm1::TObject as _;
 use crate::avm2::object::TObject as _;
+use crate::avm2::{
+    Activation as Avm2Activation, Multiname, Object as Avm2Object, Value as Avm2Value,
+};
 use crate::context::UpdateContext;
 use crate::debug_ui::handle::{AVM1ObjectHandle, AVM2ObjectHandle, DisplayObjectHandle};
 use crate::debug_ui::movie::open_movie_button;
 use crate::debug_ui::Message;
-use crate::display_object::{DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer};
+use crate::display_object::{
+    Bitmap, DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer,
+};
+use crate::inspector::{display_object_type, filter_name};
+use crate::string::AvmString;
 use egui::collapsing_header::CollapsingState;
-use egui::{Button, Checkbox, CollapsingHeader, ComboBox, Grid, Id, TextEdit, Ui, Widget, Window};
+use egui::{
+    Button, Checkbox, CollapsingHeader, ColorImage, ComboBox, DragValue, Grid, Id, Slider,
+    TextEdit, TextureHandle, TextureOptions, Ui, Widget, Window,
+};
 use ruffle_wstr::{WStr, WString};
 use std::borrow::Cow;
-use swf::{ColorTransform, Fixed8};
+use swf::{ColorTransform, Fixed8, Twips};
 
 const DEFAULT_DEBUG_COLORS: [[f32; 3]; 10] = [
     [0.00, 0.39, 0.00], // "darkgreen" / #006400
@@ -53,9 +63,19 @@ enum Panel {
     Position,
     Display,
     Children,
+    Events,
     TypeSpecific,
 }
 
+/// A texture showing the contents of a bitmap.
+struct BitmapPreview(TextureHandle);
+
+impl std::fmt::Debug for BitmapPreview {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BitmapPreview").field(&self.0.id()).finish()
+    }
+}
+
 #[derive(Debug)]
 pub struct DisplayObjectWindow {
     open_panel: Panel,
@@ -63,6 +83,7 @@ pub struct DisplayObjectWindow {
     debug_rect_visible: bool,
     hovered_debug_rect: Option<DisplayObjectHandle>,
     search: String,
+    bitmap_preview: Option<BitmapPreview>,
 }
 
 impl Default for DisplayObjectWindow {
@@ -78,6 +99,7 @@ impl Default for DisplayObjectWindow {
             debug_rect_visible: false,
             hovered_debug_rect: None,
             search: Default::default(),
+            bitmap_preview: None,
         }
     }
 }
@@ -134,6 +156,14 @@ impl DisplayObjectWindow {
                             );
                         }
                     }
+                    if let crate::avm2::Value::Object(_) = object.object2() {
+                        ui.selectable_value(&mut self.open_panel, Panel::Events, "Events");
+                    }
+                    if ui.button("Console").clicked() {
+                        messages.push(Message::ShowConsole(Some(DisplayObjectHandle::new(
+                            context, object,
+                        ))));
+                    }
                 });
                 ui.separator();
 
@@ -141,11 +171,14 @@ impl DisplayObjectWindow {
                     Panel::Position => self.show_position(ui, context, object, messages),
                     Panel::Display => self.show_display(ui, context, object, messages),
                     Panel::Children => self.show_children(ui, context, object, messages),
-                    Panel::TypeSpecific => {
-                        if let DisplayObject::MovieClip(object) = object {
+                    Panel::Events => self.show_events(ui, context, object, messages),
+                    Panel::TypeSpecific => match object {
+                        DisplayObject::MovieClip(object) => {
                             self.show_movieclip(ui, context, object)
                         }
-                    }
+                        DisplayObject::Bitmap(object) => self.show_bitmap(ui, object),
+                        _ => {}
+                    },
                 }
             });
         keep_open
@@ -264,6 +297,66 @@ impl DisplayObjectWindow {
             });
     }
 
+    pub fn show_bitmap(&mut self, ui: &mut Ui, object: Bitmap<'_>) {
+        let bitmap_data = object.bitmap_data_wrapper();
+        ui.horizontal(|ui| {
+            ui.label(format!("{}x{}", bitmap_data.width(), bitmap_data.height()));
+            if ui.button("Refresh").clicked() {
+                self.bitmap_preview = None;
+            }
+        });
+
+        let preview = self.bitmap_preview.get_or_insert_with(|| {
+            let bitmap = bitmap_data.sync().read().to_unmultiplied_bitmap();
+            let image = ColorImage::from_rgba_unmultiplied(
+                [bitmap.width() as usize, bitmap.height() as usize],
+                bitmap.data(),
+            );
+            BitmapPreview(
+                ui.ctx()
+                    .load_texture("bitmap_preview", image, TextureOptions::NEAREST),
+            )
+        });
+        ui.image(&preview.0, preview.0.size_vec2());
+    }
+
+    pub fn show_events<'gc>(
+        &mut self,
+        ui: &mut Ui,
+        context: &mut UpdateContext<'_, 'gc>,
+        object: DisplayObject<'gc>,
+        messages: &mut Vec<Message>,
+    ) {
+        let handlers = event_handlers(context, object);
+        if handlers.is_empty() {
+            ui.weak("No event listeners");
+            return;
+        }
+
+        Grid::new(ui.id().with("events"))
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Event");
+                ui.label("Priority");
+                ui.label("Phase");
+                ui.label("Listener");
+                ui.end_row();
+
+                for (event, priority, handler, use_capture) in handlers {
+                    ui.label(event.to_string());
+                    ui.label(priority.to_string());
+                    ui.label(if use_capture { "Capture" } else { "Bubble" });
+                    if ui.button(format!("{:p}", handler.as_ptr())).clicked() {
+                        messages.push(Message::TrackAVM2Object(AVM2ObjectHandle::new(
+                            context, handler,
+                        )));
+                    }
+                    ui.end_row();
+                }
+            });
+    }
+
     pub fn show_display<'gc>(
         &mut self,
         ui: &mut Ui,
@@ -392,6 +485,13 @@ impl DisplayObjectWindow {
                     object.set_blend_mode(context.gc_context, new_blend);
                 }
 
+                let mut alpha = object.alpha();
+                ui.label("Alpha");
+                if Slider::new(&mut alpha, 0.0..=1.0).ui(ui).changed() {
+                    object.set_alpha(context.gc_context, alpha);
+                }
+                ui.end_row();
+
                 let color_transform = *object.base().color_transform();
                 ui.label("Color Transform");
                 ui.label(summary_color_transform(color_transform));
@@ -400,13 +500,26 @@ impl DisplayObjectWindow {
 
         let filters = object.filters();
         if !filters.is_empty() {
+            let mut removed = None;
             CollapsingHeader::new(format!("Filters ({})", filters.len()))
                 .id_source(ui.id().with("filters"))
                 .show(ui, |ui| {
-                    for filter in filters {
-                        ui.label(format!("{:?}", filter));
+                    for (index, filter) in filters.iter().enumerate() {
+                        CollapsingHeader::new(filter_name(filter))
+                            .id_source(ui.id().with(("filter", index)))
+                            .show(ui, |ui| {
+                                if ui.button("Remove").clicked() {
+                                    removed = Some(index);
+                                }
+                                ui.monospace(format!("{filter:#?}"));
+                            });
                     }
                 });
+            if let Some(index) = removed {
+                let mut filters = filters;
+                filters.remove(index);
+                object.set_filters(context.gc_context, filters);
+            }
         }
     }
 
@@ -520,17 +633,49 @@ impl DisplayObjectWindow {
                 }
                 ui.end_row();
 
-                let matrix = *object.base().matrix();
+                let gc_context = context.gc_context;
+
                 ui.label("Local Position");
-                ui.label(format!("{:.2}, {:.2}", matrix.tx, matrix.ty));
+                ui.horizontal(|ui| {
+                    let mut x = object.x().to_pixels();
+                    let mut y = object.y().to_pixels();
+                    if DragValue::new(&mut x).prefix("x: ").ui(ui).changed() {
+                        object.set_x(gc_context, Twips::from_pixels(x));
+                        object.set_transformed_by_script(gc_context, true);
+                    }
+                    if DragValue::new(&mut y).prefix("y: ").ui(ui).changed() {
+                        object.set_y(gc_context, Twips::from_pixels(y));
+                        object.set_transformed_by_script(gc_context, true);
+                    }
+                });
                 ui.end_row();
 
                 ui.label("Local Rotation");
-                ui.label(format!("{}, {}", matrix.b, matrix.c));
+                let mut rotation: f64 = object.rotation(gc_context).into();
+                if DragValue::new(&mut rotation)
+                    .suffix("°")
+                    .clamp_range(-180.0..=180.0)
+                    .ui(ui)
+                    .changed()
+                {
+                    object.set_rotation(gc_context, rotation.into());
+                    object.set_transformed_by_script(gc_context, true);
+                }
                 ui.end_row();
 
                 ui.label("Local Scale");
-                ui.label(format!("{}, {}", matrix.a, matrix.d));
+                ui.horizontal(|ui| {
+                    let mut scale_x = object.scale_x(gc_context).percent();
+                    let mut scale_y = object.scale_y(gc_context).percent();
+                    if DragValue::new(&mut scale_x).suffix("%").ui(ui).changed() {
+                        object.set_scale_x(gc_context, scale_x.into());
+                        object.set_transformed_by_script(gc_context, true);
+                    }
+                    if DragValue::new(&mut scale_y).suffix("%").ui(ui).changed() {
+                        object.set_scale_y(gc_context, scale_y.into());
+                        object.set_transformed_by_script(gc_context, true);
+                    }
+                });
                 ui.end_row();
             });
     }
@@ -652,7 +797,30 @@ fn summary_color_transform_entry(name: &str, mult: Fixed8, add: i16) -> Option<S
 }
 
 fn has_type_specific_tab(object: DisplayObject) -> bool {
-    matches!(object, DisplayObject::MovieClip(_))
+    matches!(
+        object,
+        DisplayObject::MovieClip(_) | DisplayObject::Bitmap(_)
+    )
+}
+
+/// All of the event listeners registered on the AVM2 side of a display object.
+fn event_handlers<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: DisplayObject<'gc>,
+) -> Vec<(AvmString<'gc>, i32, Avm2Object<'gc>, bool)> {
+    let Avm2Value::Object(object) = object.object2() else {
+        return Vec::new();
+    };
+
+    let mut activation = Avm2Activation::from_nothing(context.reborrow());
+    let name = Multiname::new(activation.avm2().flash_events_internal, "_dispatchList");
+    match object.get_property(&name, &mut activation) {
+        Ok(Avm2Value::Object(list)) => list
+            .as_dispatch()
+            .map(|list| list.iter_all_handlers().collect())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
 }
 
 fn summary_name(object: DisplayObject) -> Cow<'static, str> {
@@ -666,22 +834,6 @@ fn summary_name(object: DisplayObject) -> Cow<'static, str> {
     }
 }
 
-fn display_object_type(object: DisplayObject) -> &'static str {
-    match object {
-        DisplayObject::Stage(_) => "Stage",
-        DisplayObject::Bitmap(_) => "Bitmap",
-        DisplayObject::Avm1Button(_) => "Avm1Button",
-        DisplayObject::Avm2Button(_) => "Avm2Button",
-        DisplayObject::EditText(_) => "EditText",
-        DisplayObject::Graphic(_) => "Graphic",
-        DisplayObject::MorphShape(_) => "MorphShape",
-        DisplayObject::MovieClip(_) => "MovieClip",
-        DisplayObject::Text(_) => "Text",
-        DisplayObject::Video(_) => "Video",
-        DisplayObject::LoaderDisplay(_) => "LoaderDisplay",
-    }
-}
-
 fn blend_mode_name(mode: ExtendedBlendMode) -> &'static str {
     match mode {
         ExtendedBlendMode::Normal => "Normal",
@@ -0,0 +1,70 @@
+use crate::avm2::MemoryProfile;
+use crate::context::UpdateContext;
+use crate::debug_ui::{ItemToSave, Message};
+use egui::{Grid, Window};
+
+#[derive(Debug, Default)]
+pub struct MemoryProfileWindow {
+    profile: Option<MemoryProfile>,
+}
+
+impl MemoryProfileWindow {
+    pub fn show(
+        &mut self,
+        egui_ctx: &egui::Context,
+        context: &mut UpdateContext,
+        messages: &mut Vec<Message>,
+    ) -> bool {
+        let mut keep_open = true;
+
+        // Objects are only counted once they've been allocated while this window is open.
+        context.avm2.set_memory_profiling(true);
+
+        Window::new("Memory Profile")
+            .open(&mut keep_open)
+            .scroll2([true, true])
+            .show(egui_ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Refresh").clicked() || self.profile.is_none() {
+                        self.profile = Some(context.avm2.memory_profile(context.gc_context));
+                    }
+                    if let Some(profile) = &self.profile {
+                        if ui.button("Save CSV...").clicked() {
+                            messages.push(Message::SaveFile(ItemToSave {
+                                suggested_name: "memory_profile.csv".to_string(),
+                                data: profile.to_csv().into_bytes(),
+                            }));
+                        }
+                    }
+                });
+                ui.weak("Only AVM2 objects constructed since this window was opened are counted.");
+                ui.separator();
+
+                let Some(profile) = &self.profile else {
+                    return;
+                };
+
+                Grid::new("memory_profile")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Class");
+                        ui.strong("Objects");
+                        ui.strong("Size");
+                        ui.end_row();
+
+                        for (name, class) in &profile.classes {
+                            ui.label(name);
+                            ui.label(class.count.to_string());
+                            ui.label(format!("{} bytes", class.size));
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if !keep_open {
+            context.avm2.set_memory_profiling(false);
+        }
+        keep_open
+    }
+}
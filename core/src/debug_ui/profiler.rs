@@ -0,0 +1,104 @@
+use crate::context::UpdateContext;
+use crate::debug_ui::{ItemToSave, Message};
+use crate::profiler::Category;
+use egui::{Grid, Window};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default)]
+pub struct ProfilerWindow {}
+
+impl ProfilerWindow {
+    pub fn show(
+        &mut self,
+        egui_ctx: &egui::Context,
+        context: &mut UpdateContext,
+        messages: &mut Vec<Message>,
+    ) -> bool {
+        let mut keep_open = true;
+
+        Window::new("Profiler")
+            .open(&mut keep_open)
+            .scroll2([true, true])
+            .show(egui_ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if context.profiler.is_enabled() {
+                        if ui.button("Stop").clicked() {
+                            context.profiler.stop_profiling();
+                        }
+                    } else if ui.button("Start").clicked() {
+                        context.profiler.start_profiling();
+                    }
+
+                    if ui
+                        .add_enabled(
+                            !context.profiler.events().is_empty(),
+                            egui::Button::new("Save Chrome Trace..."),
+                        )
+                        .clicked()
+                    {
+                        messages.push(Message::SaveFile(ItemToSave {
+                            suggested_name: "ruffle_profile.json".to_string(),
+                            data: context.profiler.to_chrome_trace().into_bytes(),
+                        }));
+                    }
+                });
+                ui.separator();
+
+                let gc_stats = context.profiler.gc_stats();
+                Grid::new("profiler_gc").num_columns(2).show(ui, |ui| {
+                    ui.label("GC slices");
+                    ui.label(gc_stats.slices.to_string());
+                    ui.end_row();
+
+                    ui.label("GC total");
+                    ui.label(format!(
+                        "{:.2} ms",
+                        gc_stats.total_time.as_secs_f64() * 1000.0
+                    ));
+                    ui.end_row();
+
+                    ui.label("Longest GC slice");
+                    ui.label(format!(
+                        "{:.2} ms",
+                        gc_stats.longest_slice.as_secs_f64() * 1000.0
+                    ));
+                    ui.end_row();
+                });
+                ui.separator();
+
+                // Totals are summed up from the recorded spans, each of which covers
+                // everything nested inside of it.
+                let mut totals: BTreeMap<&str, (Category, usize, u64)> = BTreeMap::new();
+                for event in context.profiler.events() {
+                    let total = totals
+                        .entry(event.name.as_ref())
+                        .or_insert((event.category, 0, 0));
+                    total.1 += 1;
+                    total.2 += event.duration;
+                }
+                let mut totals: Vec<_> = totals.into_iter().collect();
+                totals.sort_by(|(_, a), (_, b)| b.2.cmp(&a.2));
+
+                Grid::new("profiler_totals")
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.strong("Name");
+                        ui.strong("Category");
+                        ui.strong("Count");
+                        ui.strong("Total");
+                        ui.end_row();
+
+                        for (name, (category, count, duration)) in totals.iter().take(100) {
+                            ui.label(*name);
+                            ui.label(format!("{category:?}"));
+                            ui.label(count.to_string());
+                            ui.label(format!("{:.2} ms", *duration as f64 / 1000.0));
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        keep_open
+    }
+}
@@ -0,0 +1,354 @@
+//! Debugging of ActionScript, for both AVM1 and AVM2
+//!
+//! A debugger front-end talks to the player through a [`DebuggerConnection`], which sends
+//! [`DebugCommand`]s and receives [`DebugEvent`]s. Both AVMs report to the [`Debugger`] as
+//! they enter and leave functions and reach new statements, and it decides when to stop.
+//!
+//! Stopping blocks the thread that runs the movie until the front-end resumes it, with the
+//! stopped activation still on the stack. Everything the front-end can look at is sent along
+//! with the [`DebugEvent::Stopped`] event, so no ActionScript runs while the movie is stopped.
+//!
+//! AVM2 statements are the lines that movies compiled with debugging information mark with
+//! `debugline` instructions, so line breakpoints and stepping need such a movie. AVM1 has no
+//! line information, so every action is treated as a statement.
+
+use std::collections::{HashMap, HashSet};
+
+/// A request from a debugger front-end.
+#[derive(Clone, Debug)]
+pub enum DebugCommand {
+    /// Replace the line breakpoints in the given source file.
+    ///
+    /// Files are matched by their name alone, as movies hold the paths that they had on the
+    /// machine that compiled them.
+    SetBreakpoints { file: String, lines: Vec<u32> },
+
+    /// Replace the breakpoints on functions, which are matched against the names shown in
+    /// stack traces, such as `Main/init()` or `onEnterFrame`.
+    SetFunctionBreakpoints(Vec<String>),
+
+    /// Stop at the next statement.
+    Pause,
+
+    /// Resume running until the next breakpoint.
+    Continue,
+
+    /// Stop at the next statement, including in functions called by this one.
+    StepIn,
+
+    /// Stop at the next statement of this function or the ones that called it.
+    StepOver,
+
+    /// Stop once this function returns.
+    StepOut,
+
+    /// The front-end went away, so forget its breakpoints and resume.
+    Disconnect,
+}
+
+/// Why the movie stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StopReason {
+    Breakpoint,
+    Step,
+    Pause,
+}
+
+/// A function on the stack of a stopped movie.
+#[derive(Clone, Debug)]
+pub struct StackFrame {
+    pub function: String,
+
+    /// The source file that the function was compiled from, if known.
+    pub file: Option<String>,
+
+    /// The line that's running in the source file, if known.
+    pub line: Option<u32>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Variable {
+    pub name: String,
+    pub value: String,
+}
+
+/// A group of variables in the innermost stack frame, such as its locals or registers.
+#[derive(Clone, Debug)]
+pub struct Scope {
+    pub name: String,
+    pub variables: Vec<Variable>,
+}
+
+/// A notification for a debugger front-end.
+#[derive(Clone, Debug)]
+pub enum DebugEvent {
+    /// The movie stopped, with the stack from innermost to outermost function.
+    Stopped {
+        reason: StopReason,
+        stack: Vec<StackFrame>,
+        scopes: Vec<Scope>,
+    },
+
+    /// The movie resumed after being stopped.
+    Continued,
+}
+
+/// The link between the player and a debugger front-end.
+pub trait DebuggerConnection {
+    /// Take the next command, if one has arrived.
+    fn poll(&mut self) -> Option<DebugCommand>;
+
+    /// Wait for the next command, or return `None` if the front-end can never send another.
+    fn wait(&mut self) -> Option<DebugCommand>;
+
+    fn send(&mut self, event: DebugEvent);
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Step {
+    In,
+
+    /// Stop at a statement running at or above this depth.
+    Over(usize),
+
+    /// Stop at a statement running above this depth.
+    Out(usize),
+}
+
+#[derive(Default)]
+struct Frame {
+    function: String,
+    file: Option<String>,
+    line: Option<u32>,
+
+    /// The names of local registers, as given by `debug` instructions.
+    register_names: HashMap<u32, String>,
+}
+
+/// Keeps track of where scripts are running and decides when they should stop.
+#[derive(Default)]
+pub struct Debugger {
+    connection: Option<Box<dyn DebuggerConnection>>,
+
+    /// The functions that are running, from outermost to innermost.
+    frames: Vec<Frame>,
+
+    line_breakpoints: HashMap<String, HashSet<u32>>,
+
+    function_breakpoints: HashSet<String>,
+
+    step: Option<Step>,
+
+    /// A stop that will happen at the next statement, for a pause or a function breakpoint.
+    pending_stop: Option<StopReason>,
+}
+
+impl Debugger {
+    pub fn new(connection: Option<Box<dyn DebuggerConnection>>) -> Self {
+        Self {
+            connection,
+            ..Default::default()
+        }
+    }
+
+    /// Whether a front-end is attached, without which nothing else needs to be called.
+    pub fn is_attached(&self) -> bool {
+        self.connection.is_some()
+    }
+
+    /// Handle the commands that arrived while scripts weren't running.
+    pub fn poll(&mut self) {
+        while let Some(command) = self.connection.as_mut().and_then(|c| c.poll()) {
+            self.handle_command(command);
+        }
+    }
+
+    /// Handle a command, returning whether it resumes a stopped movie.
+    fn handle_command(&mut self, command: DebugCommand) -> bool {
+        match command {
+            DebugCommand::SetBreakpoints { file, lines } => {
+                self.line_breakpoints
+                    .insert(file_name(&file).to_string(), lines.into_iter().collect());
+                false
+            }
+            DebugCommand::SetFunctionBreakpoints(functions) => {
+                self.function_breakpoints = functions.into_iter().collect();
+                false
+            }
+            DebugCommand::Pause => {
+                self.pending_stop = Some(StopReason::Pause);
+                false
+            }
+            DebugCommand::Continue => {
+                self.step = None;
+                true
+            }
+            DebugCommand::StepIn => {
+                self.step = Some(Step::In);
+                true
+            }
+            DebugCommand::StepOver => {
+                self.step = Some(Step::Over(self.frames.len()));
+                true
+            }
+            DebugCommand::StepOut => {
+                self.step = Some(Step::Out(self.frames.len()));
+                true
+            }
+            DebugCommand::Disconnect => {
+                self.line_breakpoints.clear();
+                self.function_breakpoints.clear();
+                self.step = None;
+                self.pending_stop = None;
+                true
+            }
+        }
+    }
+
+    /// A function started running.
+    pub fn enter_function(&mut self, function: String) {
+        if self.function_breakpoints.contains(&function) {
+            self.pending_stop = Some(StopReason::Breakpoint);
+        }
+        self.frames.push(Frame {
+            function,
+            ..Default::default()
+        });
+    }
+
+    /// The innermost function stopped running.
+    pub fn exit_function(&mut self) {
+        self.frames.pop();
+    }
+
+    /// The innermost function is running code from the given source file.
+    pub fn set_file(&mut self, file: String) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.file = Some(file);
+        }
+    }
+
+    /// A local register of the innermost function holds the variable of the given name.
+    pub fn set_register_name(&mut self, register: u32, name: String) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.register_names.insert(register, name);
+        }
+    }
+
+    /// The name of a local register of the innermost function, if it's known.
+    pub fn register_name(&self, register: u32) -> Option<&str> {
+        let frame = self.frames.last()?;
+        frame.register_names.get(&register).map(String::as_str)
+    }
+
+    /// The innermost function reached a new statement, which is on the given line if known.
+    ///
+    /// Returns the reason to stop here, in which case the caller should call `stop`.
+    pub fn statement(&mut self, line: Option<u32>) -> Option<StopReason> {
+        let depth = self.frames.len();
+        let frame = self.frames.last_mut()?;
+        if line.is_some() {
+            frame.line = line;
+        }
+
+        if let Some(reason) = self.pending_stop.take() {
+            return Some(reason);
+        }
+
+        if let (Some(file), Some(line)) = (&frame.file, line) {
+            if self
+                .line_breakpoints
+                .get(file_name(file))
+                .map_or(false, |lines| lines.contains(&line))
+            {
+                return Some(StopReason::Breakpoint);
+            }
+        }
+
+        let stepped = match self.step? {
+            Step::In => true,
+            Step::Over(step_depth) => depth <= step_depth,
+            Step::Out(step_depth) => depth < step_depth,
+        };
+        stepped.then_some(StopReason::Step)
+    }
+
+    /// Stop the movie until the front-end resumes it.
+    ///
+    /// `scopes` holds the variables of the innermost function.
+    pub fn stop(&mut self, reason: StopReason, scopes: Vec<Scope>) {
+        let Some(connection) = &mut self.connection else {
+            return;
+        };
+
+        self.step = None;
+        let stack = self
+            .frames
+            .iter()
+            .rev()
+            .map(|frame| StackFrame {
+                function: frame.function.clone(),
+                file: frame.file.clone(),
+                line: frame.line,
+            })
+            .collect();
+        connection.send(DebugEvent::Stopped {
+            reason,
+            stack,
+            scopes,
+        });
+
+        loop {
+            let Some(command) = self.connection.as_mut().and_then(|c| c.wait()) else {
+                // The front-end is gone for good, so the movie can run freely from now on.
+                *self = Self::default();
+                return;
+            };
+            if self.handle_command(command) {
+                break;
+            }
+        }
+
+        if let Some(connection) = &mut self.connection {
+            connection.send(DebugEvent::Continued);
+        }
+    }
+}
+
+/// The name of a source file, without the directories or packages it's in.
+///
+/// Flex records source files as `directory;package;File.as`.
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\', ';']).next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steps_over_calls() {
+        let mut debugger = Debugger::default();
+        debugger.enter_function("outer".to_string());
+        debugger.set_file("C:\\project\\src;;Main.as".to_string());
+        debugger.handle_command(DebugCommand::SetBreakpoints {
+            file: "/home/user/project/src/Main.as".to_string(),
+            lines: vec![3],
+        });
+        assert_eq!(debugger.statement(Some(2)), None);
+        assert_eq!(debugger.statement(Some(3)), Some(StopReason::Breakpoint));
+
+        assert!(debugger.handle_command(DebugCommand::StepOver));
+        debugger.enter_function("inner".to_string());
+        assert_eq!(debugger.statement(Some(10)), None);
+        debugger.exit_function();
+        assert_eq!(debugger.statement(Some(4)), Some(StopReason::Step));
+
+        debugger.handle_command(DebugCommand::SetFunctionBreakpoints(vec![
+            "inner".to_string()
+        ]));
+        assert!(debugger.handle_command(DebugCommand::Continue));
+        debugger.enter_function("inner".to_string());
+        assert_eq!(debugger.statement(None), Some(StopReason::Breakpoint));
+    }
+}
@@ -31,6 +31,7 @@ mod interactive;
 mod loader_display;
 mod morph_shape;
 mod movie_clip;
+mod perspective;
 mod stage;
 mod text;
 mod video;
@@ -49,6 +50,7 @@ pub use interactive::{Avm2MousePick, InteractiveObject, TInteractiveObject};
 pub use loader_display::LoaderDisplay;
 pub use morph_shape::{MorphShape, MorphShapeStatic};
 pub use movie_clip::{MovieClip, MovieClipWeak, Scene};
+pub use perspective::{PerspectiveProjection, Transform3D};
 use ruffle_render::backend::{BitmapCacheEntry, RenderBackend};
 use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, PixelSnapping};
 use ruffle_render::blend::ExtendedBlendMode;
@@ -206,6 +208,16 @@ pub struct DisplayObjectBase<'gc> {
 
     skew: f64,
 
+    /// The 3D components of this display object's transform.
+    /// `None` until any of `z`, `rotationX`, `rotationY` or `scaleZ` is set.
+    #[collect(require_static)]
+    transform_3d: Option<Transform3D>,
+
+    /// The perspective projection applied to 3D-transformed descendants of this object.
+    /// `None` means the projection of the parent (or the stage's default) is used.
+    #[collect(require_static)]
+    perspective_projection: Option<PerspectiveProjection>,
+
     /// The next display object in order of execution.
     ///
     /// `None` in an AVM2 movie.
@@ -249,6 +261,11 @@ pub struct DisplayObjectBase<'gc> {
     #[collect(require_static)]
     next_scroll_rect: Rectangle<Twips>,
 
+    /// The 9-slice scaling grid of this display object, in local space.
+    /// Set by `DefineScalingGrid` tags and the ActionScript `scale9Grid` property.
+    #[collect(require_static)]
+    scaling_grid: Option<Rectangle<Twips>>,
+
     /// If this Display Object should cacheAsBitmap - and if so, the cache itself.
     /// None means not cached, Some means cached.
     #[collect(require_static)]
@@ -269,6 +286,8 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             scale_x: Percent::from_unit(1.0),
             scale_y: Percent::from_unit(1.0),
             skew: 0.0,
+            transform_3d: None,
+            perspective_projection: None,
             next_avm1_clip: None,
             masker: None,
             maskee: None,
@@ -279,6 +298,7 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             flags: DisplayObjectFlags::VISIBLE,
             scroll_rect: None,
             next_scroll_rect: Default::default(),
+            scaling_grid: None,
             cache: None,
         }
     }
@@ -431,6 +451,17 @@ impl<'gc> DisplayObjectBase<'gc> {
         changed
     }
 
+    fn transform_3d(&self) -> Transform3D {
+        self.transform_3d.unwrap_or_default()
+    }
+
+    fn set_transform_3d(&mut self, transform_3d: Option<Transform3D>) -> bool {
+        let changed = self.transform_3d != transform_3d;
+        self.set_transformed_by_script(true);
+        self.transform_3d = transform_3d;
+        changed
+    }
+
     fn scale_x(&mut self) -> Percent {
         self.cache_scale_rotation();
         self.scale_x
@@ -497,9 +528,16 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.filters.clone()
     }
 
-    fn set_filters(&mut self, filters: Vec<Filter>) {
+    /// Sets the filters of this object, returning `true` if they're different to the old ones.
+    fn set_filters(&mut self, filters: Vec<Filter>) -> bool {
+        if self.filters == filters {
+            // Content commonly reassigns the same filters every frame (e.g. tweening libraries).
+            // Keep the cached (already filtered) bitmap around in that case.
+            return false;
+        }
         self.filters = filters;
         self.recheck_cache_as_bitmap();
+        true
     }
 
     fn alpha(&self) -> f64 {
@@ -689,6 +727,10 @@ impl<'gc> DisplayObjectBase<'gc> {
         true
     }
 
+    fn is_cache_invalidated(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::CACHE_INVALIDATED)
+    }
+
     fn clear_invalidate_flag(&mut self) {
         self.flags.remove(DisplayObjectFlags::CACHE_INVALIDATED);
     }
@@ -758,7 +800,16 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
-    context.transform_stack.push(this.base().transform());
+    render_base_transformed(this, context);
+}
+
+/// Renders this display object with its own transform, blend mode and bitmap cache,
+/// even if it is being used as a mask.
+fn render_base_transformed<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) {
+    let Some(transform) = render_transform(this, context) else {
+        return;
+    };
+    context.transform_stack.push(&transform);
     let blend_mode = this.blend_mode();
     let original_commands = if blend_mode != ExtendedBlendMode::Normal {
         Some(std::mem::take(&mut context.commands))
@@ -931,6 +982,39 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     context.transform_stack.pop();
 }
 
+/// The transform to render this display object with, including the projection of its
+/// 3D transform, if any.
+///
+/// Returns `None` if the object is entirely behind the viewer.
+fn render_transform<'gc>(
+    this: DisplayObject<'gc>,
+    context: &RenderContext<'_, 'gc>,
+) -> Option<Transform> {
+    let base = this.base();
+    let transform_3d = match base.transform_3d {
+        Some(transform_3d) if !transform_3d.is_flat() => transform_3d,
+        _ => return Some(base.transform().clone()),
+    };
+    drop(base);
+
+    let (stage_width, stage_height) = context.stage.stage_size();
+    let (stage_width, stage_height) = (stage_width as f64, stage_height as f64);
+    // Flash uses the projection of the nearest ancestor that has one, in that ancestor's
+    // coordinate space. We approximate it in the coordinate space of the parent.
+    let projection = std::iter::successors(this.parent(), |parent| parent.parent())
+        .find_map(|ancestor| ancestor.perspective_projection())
+        .unwrap_or_else(|| PerspectiveProjection::for_stage(stage_width, stage_height));
+    let focal_length = projection.focal_length(stage_width);
+
+    let rotation_z = this.rotation(context.gc_context);
+    let base = this.base();
+    let matrix = transform_3d.project(base.matrix(), rotation_z, &projection, focal_length)?;
+    Some(Transform {
+        matrix,
+        color_transform: *base.color_transform(),
+    })
+}
+
 pub fn render_base_inner<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) {
     let scroll_rect_matrix = if let Some(rect) = this.scroll_rect() {
         let cur_transform = context.transform_stack.transform();
@@ -960,6 +1044,14 @@ pub fn render_base_inner<'gc>(this: DisplayObject<'gc>, context: &mut RenderCont
     if let Some(m) = mask {
         mask_transform.matrix = this.global_to_local_matrix().unwrap_or_default();
         mask_transform.matrix *= m.local_to_global_matrix();
+    }
+
+    // When both the mask and the maskee are cached as bitmaps (which includes having filters),
+    // the mask is applied through its alpha channel instead of its shape.
+    let alpha_mask = mask.filter(|m| this.is_bitmap_cached() && m.is_bitmap_cached());
+    let original_commands = alpha_mask.map(|_| std::mem::take(&mut context.commands));
+
+    if let Some(m) = mask.filter(|_| alpha_mask.is_none()) {
         context.commands.push_mask();
         context.transform_stack.push(&mask_transform);
         m.render_self(context);
@@ -985,7 +1077,11 @@ pub fn render_base_inner<'gc>(this: DisplayObject<'gc>, context: &mut RenderCont
         context.commands.activate_mask();
     }
 
-    this.render_self(context);
+    if let Some(grid) = this.scaling_grid() {
+        render_scaling_grid(this, context, grid);
+    } else {
+        this.render_self(context);
+    }
 
     if let Some(rect_mat) = scroll_rect_matrix {
         // Draw the rectangle again after deactivating the mask,
@@ -995,7 +1091,32 @@ pub fn render_base_inner<'gc>(this: DisplayObject<'gc>, context: &mut RenderCont
         context.commands.pop_mask();
     }
 
-    if let Some(m) = mask {
+    if let (Some(m), Some(original_commands)) = (alpha_mask, original_commands) {
+        // Render the mask with its own transform and filters, into the parent's space.
+        let mask_parent_matrix = m
+            .base()
+            .matrix()
+            .inverse()
+            .map(|inverse| mask_transform.matrix * inverse)
+            .unwrap_or(mask_transform.matrix);
+        let maskee_commands = std::mem::take(&mut context.commands);
+        context.transform_stack.push(&Transform {
+            matrix: mask_parent_matrix,
+            color_transform: Default::default(),
+        });
+        render_base_transformed(m, context);
+        context.transform_stack.pop();
+        let mask_commands = std::mem::replace(&mut context.commands, maskee_commands);
+        context
+            .commands
+            .blend(mask_commands, RenderBlendMode::AlphaMask);
+
+        let masked_commands = std::mem::replace(&mut context.commands, original_commands);
+        context.commands.blend(
+            masked_commands,
+            RenderBlendMode::Builtin(swf::BlendMode::Layer),
+        );
+    } else if let Some(m) = mask {
         context.commands.deactivate_mask();
         context.transform_stack.push(&mask_transform);
         m.render_self(context);
@@ -1009,6 +1130,110 @@ pub fn render_base_inner<'gc>(this: DisplayObject<'gc>, context: &mut RenderCont
     }
 }
 
+/// Renders this display object with 9-slice scaling.
+///
+/// The object's bounds are split into 9 regions by the grid. Each region is drawn separately,
+/// clipped to its destination, with a transform that keeps the corners unscaled, stretches the
+/// edges along a single axis, and stretches the center along both.
+fn render_scaling_grid<'gc>(
+    this: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+    grid: Rectangle<Twips>,
+) {
+    let bounds = this.bounds_with_transform(&Matrix::IDENTITY);
+    let matrix = *this.base().matrix();
+    let scale_x = f64::sqrt(f64::from(matrix.a).powi(2) + f64::from(matrix.b).powi(2));
+    let scale_y = f64::sqrt(f64::from(matrix.c).powi(2) + f64::from(matrix.d).powi(2));
+
+    let (Some(columns), Some(rows)) = (
+        scaling_grid_slices(bounds.x_min, grid.x_min, grid.x_max, bounds.x_max, scale_x),
+        scaling_grid_slices(bounds.y_min, grid.y_min, grid.y_max, bounds.y_max, scale_y),
+    ) else {
+        this.render_self(context);
+        return;
+    };
+
+    let base_matrix = context.transform_stack.transform().matrix;
+    for (x_source, x_dest) in columns {
+        for (y_source, y_dest) in rows {
+            if x_dest.1 <= x_dest.0 || y_dest.1 <= y_dest.0 {
+                continue;
+            }
+
+            let scale_x = (x_dest.1 - x_dest.0) / (x_source.1 - x_source.0);
+            let scale_y = (y_dest.1 - y_dest.0) / (y_source.1 - y_source.0);
+            let slice_matrix = Matrix {
+                a: scale_x as f32,
+                d: scale_y as f32,
+                tx: Twips::new((x_dest.0 - x_source.0 * scale_x) as i32),
+                ty: Twips::new((y_dest.0 - y_source.0 * scale_y) as i32),
+                ..Default::default()
+            };
+            let clip_matrix = base_matrix
+                * Matrix::create_box(
+                    Twips::new((x_dest.1 - x_dest.0) as i32).to_pixels() as f32,
+                    Twips::new((y_dest.1 - y_dest.0) as i32).to_pixels() as f32,
+                    0.0,
+                    Twips::new(x_dest.0 as i32),
+                    Twips::new(y_dest.0 as i32),
+                );
+
+            context.commands.push_mask();
+            // The color doesn't matter, as this is a mask.
+            context.commands.draw_rect(Color::WHITE, clip_matrix);
+            context.commands.activate_mask();
+
+            context.transform_stack.push(&Transform {
+                matrix: slice_matrix,
+                color_transform: Default::default(),
+            });
+            this.render_self(context);
+            context.transform_stack.pop();
+
+            context.commands.deactivate_mask();
+            context.commands.draw_rect(Color::WHITE, clip_matrix);
+            context.commands.pop_mask();
+        }
+    }
+}
+
+/// Splits `min..max` at `grid_min` and `grid_max` into three slices, returning the source
+/// and destination range (in twips) of each slice so that the outer slices appear unscaled
+/// once the object is scaled by `scale`.
+///
+/// Returns `None` if the grid doesn't split the range, or the object isn't scaled.
+#[allow(clippy::type_complexity)]
+fn scaling_grid_slices(
+    min: Twips,
+    grid_min: Twips,
+    grid_max: Twips,
+    max: Twips,
+    scale: f64,
+) -> Option<[((f64, f64), (f64, f64)); 3]> {
+    if !(min < grid_min && grid_min < grid_max && grid_max < max) || !scale.is_normal() {
+        return None;
+    }
+
+    let (min, grid_min, grid_max, max) = (
+        min.get() as f64,
+        grid_min.get() as f64,
+        grid_max.get() as f64,
+        max.get() as f64,
+    );
+
+    // If the object is too small to fit the unscaled edges, they shrink proportionally.
+    let edges = (grid_min - min) + (max - grid_max);
+    let edge_scale = f64::min(1.0 / scale, (max - min) / edges);
+    let dest_min = min + (grid_min - min) * edge_scale;
+    let dest_max = max - (max - grid_max) * edge_scale;
+
+    Some([
+        ((min, grid_min), (min, dest_min)),
+        ((grid_min, grid_max), (dest_min, dest_max)),
+        ((grid_max, max), (dest_max, max)),
+    ])
+}
+
 #[enum_trait_object(
     #[derive(Clone, Collect, Debug, Copy)]
     #[collect(no_drop)]
@@ -1288,6 +1513,39 @@ pub trait TDisplayObject<'gc>:
         }
     }
 
+    /// The 3D components of this display object's transform.
+    /// Returned by the `z`/`rotationX`/`rotationY`/`scaleZ` ActionScript properties.
+    fn transform_3d(&self) -> Transform3D {
+        self.base().transform_3d()
+    }
+
+    /// Sets the 3D components of this display object's transform.
+    /// Set by the `z`/`rotationX`/`rotationY`/`scaleZ` ActionScript properties.
+    /// This invalidates any ancestors cacheAsBitmap automatically.
+    fn set_transform_3d(&self, gc_context: MutationContext<'gc, '_>, value: Transform3D) {
+        if self.base_mut(gc_context).set_transform_3d(Some(value)) {
+            if let Some(parent) = self.parent() {
+                parent.invalidate_cached_bitmap(gc_context);
+            }
+        }
+    }
+
+    /// The perspective projection set on this display object, if any.
+    fn perspective_projection(&self) -> Option<PerspectiveProjection> {
+        self.base().perspective_projection
+    }
+
+    /// Sets the perspective projection used by the 3D-transformed descendants of this object.
+    /// Set by the `transform.perspectiveProjection` ActionScript property.
+    fn set_perspective_projection(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        value: Option<PerspectiveProjection>,
+    ) {
+        self.base_mut(gc_context).perspective_projection = value;
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
     /// Gets the pixel width of the AABB containing this display object in local space.
     /// Returned by the ActionScript `_width`/`width` properties.
     fn width(&self) -> f64 {
@@ -1415,8 +1673,9 @@ pub trait TDisplayObject<'gc>:
     }
 
     fn set_filters(&self, gc_context: MutationContext<'gc, '_>, filters: Vec<Filter>) {
-        self.base_mut(gc_context).set_filters(filters);
-        self.invalidate_cached_bitmap(gc_context);
+        if self.base_mut(gc_context).set_filters(filters) {
+            self.invalidate_cached_bitmap(gc_context);
+        }
     }
 
     /// Returns the dot-syntax path to this display object, e.g. `_level0.foo.clip`
@@ -1479,6 +1738,8 @@ pub trait TDisplayObject<'gc>:
     fn set_parent(&self, context: &mut UpdateContext<'_, 'gc>, parent: Option<DisplayObject<'gc>>) {
         self.base_mut(context.gc_context)
             .set_parent_ignoring_orphan_list(parent);
+        // Sounds played inside this object inherit the sound transforms of its new ancestors.
+        context.set_sound_transforms_dirty();
     }
 
     /// Retrieve the parent of this display object.
@@ -1713,7 +1974,12 @@ pub trait TDisplayObject<'gc>:
 
     /// Sets whether this display object has a scroll rectangle applied.
     fn set_has_scroll_rect(&self, gc_context: MutationContext<'gc, '_>, value: bool) {
-        self.base_mut(gc_context).set_has_scroll_rect(value)
+        if self.has_scroll_rect() != value {
+            self.base_mut(gc_context).set_has_scroll_rect(value);
+            // Adding or removing the scroll rect changes both our cached appearance
+            // and our bounds.
+            self.invalidate_cached_bitmap(gc_context);
+        }
     }
 
     /// Called whenever the focus tracker has deemed this display object worthy, or no longer worthy,
@@ -2095,6 +2361,46 @@ pub trait TDisplayObject<'gc>:
         self.world_bounds().contains(point)
     }
 
+    /// The 9-slice scaling grid of this display object, in local space.
+    /// Returned by the `scale9Grid` ActionScript property.
+    fn scaling_grid(&self) -> Option<Rectangle<Twips>> {
+        self.base().scaling_grid.clone()
+    }
+
+    /// Sets the 9-slice scaling grid of this display object.
+    /// Set by `DefineScalingGrid` tags and the `scale9Grid` ActionScript property.
+    fn set_scaling_grid(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        scaling_grid: Option<Rectangle<Twips>>,
+    ) {
+        self.base_mut(gc_context).scaling_grid = scaling_grid;
+        self.invalidate_cached_bitmap(gc_context);
+    }
+
+    /// Tests if a given stage position point lies within the scroll rect of this object.
+    /// Content outside of the scroll rect is clipped, so it can't be hit either.
+    /// Always returns `true` if this object has no scroll rect.
+    fn hit_test_scroll_rect(&self, point: Point<Twips>) -> bool {
+        let Some(scroll_rect) = self.scroll_rect() else {
+            return true;
+        };
+        let Some(matrix) = self
+            .local_to_global_matrix_without_own_scroll_rect()
+            .inverse()
+        else {
+            return false;
+        };
+        let local = matrix * point;
+        Rectangle {
+            x_min: Twips::ZERO,
+            y_min: Twips::ZERO,
+            x_max: scroll_rect.width(),
+            y_max: scroll_rect.height(),
+        }
+        .contains(local)
+    }
+
     /// Tests if a given object's world bounds intersects with the world bounds
     /// of this object.
     fn hit_test_object(&self, other: DisplayObject<'gc>) -> bool {
@@ -447,6 +447,10 @@ impl<'gc> TInteractiveObject<'gc> for Avm1Button<'gc> {
         self.into()
     }
 
+    fn tab_enabled_default(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        true
+    }
+
     fn filter_clip_event(
         self,
         _context: &mut UpdateContext<'_, 'gc>,
@@ -534,7 +538,7 @@ impl<'gc> TInteractiveObject<'gc> for Avm1Button<'gc> {
 
         let (update_state, new_state) = if is_enabled {
             write.run_actions(context, condition, None);
-            write.play_sound(context, sound);
+            write.play_sound(context, sound, self_display_object);
 
             // Queue ActionScript-defined event handlers after the SWF defined ones.
             // (e.g., clip.onRelease = foo).
@@ -610,14 +614,21 @@ impl<'gc> TInteractiveObject<'gc> for Avm1Button<'gc> {
 }
 
 impl<'gc> Avm1ButtonData<'gc> {
-    fn play_sound(&self, context: &mut UpdateContext<'_, 'gc>, sound: Option<&swf::ButtonSound>) {
+    /// Plays a button sound, which is affected by the sound transforms of the button
+    /// and its ancestors.
+    fn play_sound(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+        sound: Option<&swf::ButtonSound>,
+        display_object: DisplayObject<'gc>,
+    ) {
         if let Some((id, sound_info)) = sound {
             if let Some(sound_handle) = context
                 .library
                 .library_for_movie_mut(self.movie())
                 .get_sound(*id)
             {
-                let _ = context.start_sound(sound_handle, sound_info, None, None);
+                let _ = context.start_sound(sound_handle, sound_info, Some(display_object), None);
             }
         }
     }
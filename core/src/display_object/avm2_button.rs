@@ -719,6 +719,10 @@ impl<'gc> TInteractiveObject<'gc> for Avm2Button<'gc> {
         self.into()
     }
 
+    fn tab_enabled_default(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        true
+    }
+
     fn filter_clip_event(
         self,
         _context: &mut UpdateContext<'_, 'gc>,
@@ -778,7 +782,7 @@ impl<'gc> TInteractiveObject<'gc> for Avm2Button<'gc> {
             _ => return ClipEventResult::NotHandled,
         };
 
-        write.play_sound(context, sound);
+        write.play_sound(context, sound, self.into());
         let old_state = write.state;
         drop(write);
 
@@ -841,14 +845,21 @@ impl<'gc> TInteractiveObject<'gc> for Avm2Button<'gc> {
 }
 
 impl<'gc> Avm2ButtonData<'gc> {
-    fn play_sound(&self, context: &mut UpdateContext<'_, 'gc>, sound: Option<&swf::ButtonSound>) {
+    /// Plays a button sound, which is affected by the sound transforms of the button
+    /// and its ancestors.
+    fn play_sound(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+        sound: Option<&swf::ButtonSound>,
+        display_object: DisplayObject<'gc>,
+    ) {
         if let Some((id, sound_info)) = sound {
             if let Some(sound_handle) = context
                 .library
                 .library_for_movie_mut(self.movie())
                 .get_sound(*id)
             {
-                let _ = context.start_sound(sound_handle, sound_info, None, None);
+                let _ = context.start_sound(sound_handle, sound_info, Some(display_object), None);
             }
         }
     }
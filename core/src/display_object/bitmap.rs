@@ -152,7 +152,13 @@ impl<'gc> Bitmap<'gc> {
             .as_colors()
             .map(crate::bitmap::bitmap_data::Color::from)
             .collect();
-        let bitmap_data = BitmapData::new_with_pixels(width, height, transparency, pixels);
+        let bitmap_data = BitmapData::new_with_pixels(
+            width,
+            height,
+            transparency,
+            pixels,
+            context.stage.bitmap_modifications(),
+        );
 
         let smoothing = true;
         Ok(Self::new_with_bitmap_data(
@@ -579,6 +579,9 @@ pub struct ChildContainer<'gc> {
     has_pending_removals: bool,
 
     mouse_children: bool,
+
+    /// Whether pressing Tab can focus the children of this container.
+    tab_children: bool,
 }
 
 impl<'gc> Default for ChildContainer<'gc> {
@@ -594,6 +597,7 @@ impl<'gc> ChildContainer<'gc> {
             depth_list: BTreeMap::new(),
             has_pending_removals: false,
             mouse_children: true,
+            tab_children: true,
         }
     }
 
@@ -843,6 +847,14 @@ impl<'gc> ChildContainer<'gc> {
         self.mouse_children = mouse_children;
     }
 
+    pub fn tab_children(&self) -> bool {
+        self.tab_children
+    }
+
+    pub fn set_tab_children(&mut self, tab_children: bool) {
+        self.tab_children = tab_children;
+    }
+
     /// Insert a child at a given render list position.
     ///
     /// If the child is already a child of another container, you must remove
@@ -21,7 +21,9 @@ use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject}
 use crate::drawing::Drawing;
 use crate::events::{ClipEvent, ClipEventResult, TextControlCode};
 use crate::font::{round_down_to_pixel, Glyph, TextRenderSettings};
-use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, TextFormat};
+use crate::html::{
+    BoxBounds, FormatSpans, LayoutBox, LayoutContent, LayoutMetrics, StyleSheet, TextFormat,
+};
 use crate::prelude::*;
 use crate::string::{utils as string_utils, AvmString, SwfStrExt as _, WStr, WString};
 use crate::tag_utils::SwfMovie;
@@ -32,7 +34,7 @@ use gc_arena::{Collect, Gc, GcCell, MutationContext};
 use ruffle_render::commands::CommandHandler;
 use ruffle_render::shape_utils::DrawCommand;
 use ruffle_render::transform::Transform;
-use std::{cell::Ref, cell::RefMut, sync::Arc};
+use std::{cell::Ref, cell::RefMut, ops::Range, sync::Arc};
 use swf::{Color, ColorTransform, Twips};
 
 use super::interactive::Avm2MousePick;
@@ -147,6 +149,21 @@ pub struct EditTextData<'gc> {
     /// Doesn't affect script-triggered modifications.
     max_chars: i32,
 
+    /// The CSS styles applied to HTML text.
+    #[collect(require_static)]
+    style_sheet: Option<StyleSheet>,
+
+    /// The HTML text that was last set, if a style sheet is applied.
+    ///
+    /// Styled text can't be converted back to equivalent HTML, so the original is kept instead.
+    #[collect(require_static)]
+    original_html_text: Option<WString>,
+
+    /// The range of the text that's being composed with an input method editor (IME).
+    /// This text is shown in the text field, but hasn't been input yet.
+    #[collect(require_static)]
+    ime_composition: Option<Range<usize>>,
+
     /// Flags indicating the text field's settings.
     flags: EditTextFlag,
 }
@@ -211,7 +228,7 @@ impl<'gc> EditText<'gc> {
         let text = swf_tag.initial_text().unwrap_or_default().decode(encoding);
 
         let mut text_spans = if swf_tag.is_html() {
-            FormatSpans::from_html(&text, default_format, swf_tag.is_multiline())
+            FormatSpans::from_html(&text, default_format, None, swf_tag.is_multiline())
         } else {
             FormatSpans::from_text(text.into_owned(), default_format)
         };
@@ -255,6 +272,7 @@ impl<'gc> EditText<'gc> {
             EditTextFlag::HAS_BACKGROUND,
             flags.contains(EditTextFlag::BORDER),
         );
+        flags.insert(EditTextFlag::MOUSE_WHEEL_ENABLED);
 
         // Selections are mandatory in AS3.
         let selection = if swf_movie.is_action_script_3() {
@@ -297,6 +315,9 @@ impl<'gc> EditText<'gc> {
                 line_data,
                 scroll: 1,
                 max_chars: swf_tag.max_length().unwrap_or_default() as i32,
+                style_sheet: None,
+                original_html_text: None,
+                ime_composition: None,
             },
         ));
 
@@ -351,6 +372,7 @@ impl<'gc> EditText<'gc> {
         let mut edit_text = self.0.write(context.gc_context);
         let default_format = edit_text.text_spans.default_format().clone();
         edit_text.text_spans = FormatSpans::from_text(text.into(), default_format);
+        edit_text.original_html_text = None;
         drop(edit_text);
 
         self.relayout(context);
@@ -358,7 +380,11 @@ impl<'gc> EditText<'gc> {
 
     pub fn html_text(self) -> WString {
         if self.is_html() {
-            self.0.read().text_spans.to_html()
+            let read = self.0.read();
+            if let Some(html) = &read.original_html_text {
+                return html.clone();
+            }
+            read.text_spans.to_html()
         } else {
             // Non-HTML text fields always return plain text.
             self.text()
@@ -372,8 +398,10 @@ impl<'gc> EditText<'gc> {
             write.text_spans = FormatSpans::from_html(
                 text,
                 default_format,
+                write.style_sheet.as_ref(),
                 write.flags.contains(EditTextFlag::MULTILINE),
             );
+            write.original_html_text = write.style_sheet.is_some().then(|| text.into());
             drop(write);
 
             self.relayout(context);
@@ -382,6 +410,23 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    pub fn style_sheet(self) -> Option<StyleSheet> {
+        self.0.read().style_sheet.clone()
+    }
+
+    /// Sets the CSS styles applied to HTML text, restyling the current text.
+    pub fn set_style_sheet(
+        self,
+        style_sheet: Option<StyleSheet>,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) {
+        let html_text = self.html_text();
+        self.0.write(context.gc_context).style_sheet = style_sheet;
+        if self.is_html() {
+            self.set_html_text(&html_text, context);
+        }
+    }
+
     pub fn text_length(self) -> usize {
         self.0.read().text_spans.text().len()
     }
@@ -456,6 +501,24 @@ impl<'gc> EditText<'gc> {
         self.relayout(context);
     }
 
+    pub fn is_mouse_wheel_enabled(self) -> bool {
+        self.0
+            .read()
+            .flags
+            .contains(EditTextFlag::MOUSE_WHEEL_ENABLED)
+    }
+
+    pub fn set_mouse_wheel_enabled(
+        self,
+        is_mouse_wheel_enabled: bool,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) {
+        self.0
+            .write(context.gc_context)
+            .flags
+            .set(EditTextFlag::MOUSE_WHEEL_ENABLED, is_mouse_wheel_enabled);
+    }
+
     pub fn is_selectable(self) -> bool {
         !self.0.read().flags.contains(EditTextFlag::NO_SELECT)
     }
@@ -564,10 +627,10 @@ impl<'gc> EditText<'gc> {
         text: &WStr,
         context: &mut UpdateContext<'_, 'gc>,
     ) {
-        self.0
-            .write(context.gc_context)
-            .text_spans
-            .replace_text(from, to, text, None);
+        let mut write = self.0.write(context.gc_context);
+        write.text_spans.replace_text(from, to, text, None);
+        write.original_html_text = None;
+        drop(write);
         self.relayout(context);
     }
 
@@ -883,6 +946,8 @@ impl<'gc> EditText<'gc> {
             0
         };
 
+        let ime_composition = edit_text.ime_composition.clone();
+
         // If the font can't be found or has no glyph information, use the "device font" instead.
         // We're cheating a bit and not actually rendering text using the OS/web.
         // Instead, we embed an SWF version of Noto Sans to use as the "device font", and render
@@ -929,6 +994,21 @@ impl<'gc> EditText<'gc> {
                         .render_shape(glyph_shape_handle, context.transform_stack.transform());
                     context.transform_stack.pop();
 
+                    // Underline the text being composed with an IME.
+                    if let Some(composition) = &ime_composition {
+                        if composition.contains(&(start + pos)) {
+                            let underline = context.transform_stack.transform().matrix
+                                * Matrix::create_box(
+                                    advance.to_pixels() as f32,
+                                    1.0,
+                                    0.0,
+                                    x,
+                                    params.height() + Twips::from_pixels(1.0),
+                                );
+                            context.commands.draw_rect(color, underline);
+                        }
+                    }
+
                     if let Some((caret_pos, length)) = caret {
                         if caret_pos == pos {
                             let caret = context.transform_stack.transform().matrix
@@ -1127,7 +1207,10 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn set_hscroll(self, hscroll: f64, context: &mut UpdateContext<'_, 'gc>) {
-        self.0.write(context.gc_context).hscroll = hscroll;
+        let old_hscroll = std::mem::replace(&mut self.0.write(context.gc_context).hscroll, hscroll);
+        if old_hscroll != hscroll {
+            self.on_scroller(context);
+        }
     }
 
     pub fn scroll(self) -> usize {
@@ -1144,7 +1227,10 @@ impl<'gc> EditText<'gc> {
             scroll as usize
         };
         let clamped = scroll_lines.clamp(1, self.maxscroll());
-        self.0.write(context.gc_context).scroll = clamped;
+        let old_scroll = std::mem::replace(&mut self.0.write(context.gc_context).scroll, clamped);
+        if old_scroll != clamped {
+            self.on_scroller(context);
+        }
     }
 
     pub fn max_chars(self) -> i32 {
@@ -1156,46 +1242,28 @@ impl<'gc> EditText<'gc> {
     }
 
     pub fn screen_position_to_index(self, position: Point<Twips>) -> Option<usize> {
-        let text = self.0.read();
-        let Some(mut position) = self.global_to_local(position) else {
-            return None;
-        };
-        position.x += Twips::from_pixels(Self::INTERNAL_PADDING);
-        position.y += Twips::from_pixels(Self::INTERNAL_PADDING);
-
-        for layout_box in text.layout.iter() {
-            let origin = layout_box.bounds().origin();
-            let mut matrix = Matrix::translate(origin.x(), origin.y());
-            matrix = matrix.inverse().expect("Invertible layout matrix");
-            let local_position = matrix * position;
-
-            if let Some((text, _tf, font, params, color)) =
-                layout_box.as_renderable_text(text.text_spans.text())
-            {
-                let mut result = None;
-                let baseline_adjustment =
-                    font.get_baseline_for_height(params.height()) - params.height();
-                font.evaluate(
-                    text,
-                    self.text_transform(color, baseline_adjustment),
-                    params,
-                    |pos, _transform, _glyph: &Glyph, advance, x| {
-                        if local_position.x >= x
-                            && local_position.x <= x + advance
-                            && local_position.y >= Twips::ZERO
-                            && local_position.y <= params.height()
-                        {
-                            if local_position.x >= x + (advance / 2) {
-                                result = Some(string_utils::next_char_boundary(text, pos));
-                            } else {
-                                result = Some(pos);
-                            }
-                        }
-                    },
-                );
-                if result.is_some() {
-                    return result;
+        let position = self.local_to_layout(self.global_to_local(position)?);
+        let read = self.0.read();
+        let text = read.text_spans.text();
+
+        for layout_box in read.layout.iter() {
+            let bounds = layout_box.bounds();
+            if position.y < bounds.offset_y() || position.y > bounds.extent_y() {
+                continue;
+            }
+
+            let mut result = None;
+            Self::for_each_glyph(layout_box, text, |pos, x, advance| {
+                if position.x >= x && position.x <= x + advance {
+                    if position.x >= x + (advance / 2) {
+                        result = Some(string_utils::next_char_boundary(text, pos));
+                    } else {
+                        result = Some(pos);
+                    }
                 }
+            });
+            if result.is_some() {
+                return result;
             }
         }
 
@@ -1425,6 +1493,84 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// Starts composing text with an input method editor (IME), in place of the selection.
+    pub fn ime_composition_start(self, context: &mut UpdateContext<'_, 'gc>) {
+        if self.0.read().flags.contains(EditTextFlag::READ_ONLY)
+            || self.0.read().ime_composition.is_some()
+        {
+            return;
+        }
+
+        if let Some(selection) = self.selection() {
+            if !selection.is_caret() {
+                self.replace_text(selection.start(), selection.end(), WStr::empty(), context);
+            }
+            self.set_selection(
+                Some(TextSelection::for_position(selection.start())),
+                context.gc_context,
+            );
+            self.0.write(context.gc_context).ime_composition =
+                Some(selection.start()..selection.start());
+        }
+    }
+
+    /// Replaces the text being composed with an IME.
+    ///
+    /// The composed text is shown underlined, but isn't input (and doesn't trigger any events)
+    /// until the composition ends. `cursor` is the range of `text` selected by the IME,
+    /// in UTF-8 bytes.
+    pub fn ime_composition_update(
+        self,
+        text: &str,
+        cursor: Option<(usize, usize)>,
+        context: &mut UpdateContext<'_, 'gc>,
+    ) {
+        self.ime_composition_start(context);
+        let Some(composition) = self.0.read().ime_composition.clone() else {
+            return;
+        };
+
+        let composed = WString::from_utf8(text);
+        self.replace_text(composition.start, composition.end, &composed, context);
+        let end = composition.start + composed.len();
+        self.0.write(context.gc_context).ime_composition = Some(composition.start..end);
+
+        let caret = cursor
+            .and_then(|(_, cursor_end)| text.get(..cursor_end))
+            .map_or(end, |prefix| {
+                composition.start + prefix.encode_utf16().count()
+            });
+        self.set_selection(Some(TextSelection::for_position(caret)), context.gc_context);
+    }
+
+    /// Finishes composing text with an IME, inputting the composed `text` as if it was typed.
+    pub fn ime_composition_end(self, text: &str, context: &mut UpdateContext<'_, 'gc>) {
+        let composition = self.0.write(context.gc_context).ime_composition.take();
+        if let Some(composition) = composition {
+            self.replace_text(composition.start, composition.end, WStr::empty(), context);
+            self.set_selection(
+                Some(TextSelection::for_position(composition.start)),
+                context.gc_context,
+            );
+        }
+
+        for character in text.chars() {
+            self.text_input(character, context);
+        }
+    }
+
+    /// Returns the position of the caret in the local coordinates of this text field, at the
+    /// bottom of the line it's on.
+    pub fn caret_position(self) -> Option<Point<Twips>> {
+        let position = self.selection()?.to();
+        if let Some(bounds) = self.char_bounds(position) {
+            Some(Point::new(bounds.x_min, bounds.y_max))
+        } else {
+            let bounds = self.char_bounds(position.checked_sub(1)?)?;
+            Some(Point::new(bounds.x_max, bounds.y_max))
+        }
+    }
+
     fn initialize_as_broadcaster(&self, activation: &mut Avm1Activation<'_, 'gc>) {
         if let Avm1Value::Object(object) = self.object() {
             activation.context.avm1.broadcaster_functions().initialize(
@@ -1465,6 +1611,26 @@ impl<'gc> EditText<'gc> {
         }
     }
 
+    /// Notifies listeners that the text field has been scrolled, either by the user or by a script.
+    fn on_scroller(self, context: &mut UpdateContext<'_, 'gc>) {
+        if let Avm1Value::Object(object) = self.object() {
+            let mut activation = Avm1Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[Scroller]"),
+                self.into(),
+            );
+            let _ = object.call_method(
+                "broadcastMessage".into(),
+                &["onScroller".into(), object.into()],
+                &mut activation,
+                ExecutionReason::Special,
+            );
+        } else if let Avm2Value::Object(object) = self.object2() {
+            let scroll_evt = Avm2EventObject::bare_event(context, "scroll", false, false);
+            Avm2::dispatch_event(context, scroll_evt, object);
+        }
+    }
+
     /// Construct the text field's AVM1 representation.
     fn construct_as_avm1_object(&self, context: &mut UpdateContext<'_, 'gc>, run_frame: bool) {
         let mut text = self.0.write(context.gc_context);
@@ -1526,6 +1692,25 @@ impl<'gc> EditText<'gc> {
         self.0.read().line_data.len()
     }
 
+    /// Returns the position in the text of the first character on a given line.
+    ///
+    /// Returns None if the line does not exist or has no text on it.
+    pub fn line_offset(self, line: usize) -> Option<usize> {
+        let read = self.0.read();
+        let line = read.line_data.get(line)?;
+        read.layout
+            .iter()
+            .filter(|layout_box| {
+                layout_box.bounds().offset_y() >= line.offset
+                    && layout_box.bounds().extent_y() <= line.extent
+            })
+            .filter_map(|layout_box| match layout_box.content() {
+                LayoutContent::Text { start, .. } => Some(*start),
+                _ => None,
+            })
+            .min()
+    }
+
     /// Calculate the layout metrics for a given line.
     ///
     /// Returns None if the line does not exist or there is not enough data
@@ -1533,8 +1718,7 @@ impl<'gc> EditText<'gc> {
     pub fn layout_metrics(self, line: Option<usize>) -> Option<LayoutMetrics> {
         let line = line.and_then(|line| self.0.read().line_data.get(line).copied());
         let mut union_bounds = None;
-        let mut font = None;
-        let mut text_format = None;
+        let mut line_metrics: Option<(Twips, Twips, Twips)> = None;
 
         let read = self.0.read();
 
@@ -1553,45 +1737,178 @@ impl<'gc> EditText<'gc> {
                 union_bounds = Some(layout_box.bounds());
             }
 
-            if font.is_none() {
-                match layout_box.content() {
-                    LayoutContent::Text {
-                        font: box_font,
-                        text_format: box_text_format,
-                        ..
-                    } => {
-                        font = Some(box_font);
-                        text_format = Some(box_text_format);
-                    }
-                    LayoutContent::Bullet {
-                        font: box_font,
-                        text_format: box_text_format,
-                        ..
-                    } => {
-                        font = Some(box_font);
-                        text_format = Some(box_text_format);
-                    }
-                    LayoutContent::Drawing { .. } => {}
+            let (font, text_format) = match layout_box.content() {
+                LayoutContent::Text {
+                    font, text_format, ..
                 }
-            }
+                | LayoutContent::Bullet {
+                    font, text_format, ..
+                } => (font, text_format),
+                LayoutContent::Drawing { .. } => continue,
+            };
+            let (Some(size), Some(leading)) = (text_format.size, text_format.leading) else {
+                continue;
+            };
+
+            // A line with several formats is as tall as its tallest text.
+            let size = Twips::from_pixels(size);
+            let ascent = font.get_baseline_for_height(size);
+            let descent = font.get_descent_for_height(size);
+            let leading = Twips::from_pixels(leading);
+            line_metrics = Some(match line_metrics {
+                Some((max_ascent, max_descent, max_leading)) => (
+                    max_ascent.max(ascent),
+                    max_descent.max(descent),
+                    max_leading.max(leading),
+                ),
+                None => (ascent, descent, leading),
+            });
         }
 
         let union_bounds = union_bounds?;
-        let font = font?;
-        let size = Twips::from_pixels(text_format?.size?);
-        let ascent = font.get_baseline_for_height(size);
-        let descent = font.get_descent_for_height(size);
-        let leading = Twips::from_pixels(text_format?.leading?);
+        let (ascent, descent, leading) = line_metrics?;
 
         Some(LayoutMetrics {
             ascent,
             descent,
             leading,
             width: union_bounds.width(),
-            height: union_bounds.height() + descent + leading,
+            height: ascent + descent + leading,
             x: union_bounds.offset_x() + Twips::from_pixels(EditText::INTERNAL_PADDING),
         })
     }
+
+    /// The vertical distance that the text has been scrolled by.
+    fn scroll_offset(self) -> Twips {
+        let read = self.0.read();
+        if read.scroll > 1 {
+            read.line_data
+                .get(read.scroll - 1)
+                .map_or(Twips::ZERO, |line| line.offset)
+        } else {
+            Twips::ZERO
+        }
+    }
+
+    /// Converts a point in the local coordinates of this text field to the coordinates
+    /// of its layout, which start inside the gutter and don't move when scrolled.
+    fn local_to_layout(self, position: Point<Twips>) -> Point<Twips> {
+        let padding = Twips::from_pixels(Self::INTERNAL_PADDING);
+        Point::new(
+            position.x - padding + Twips::from_pixels(self.hscroll()),
+            position.y - padding + self.scroll_offset(),
+        )
+    }
+
+    /// Converts a point in the coordinates of this text field's layout to its local
+    /// coordinates.
+    fn layout_to_local(self, position: Point<Twips>) -> Point<Twips> {
+        let padding = Twips::from_pixels(Self::INTERNAL_PADDING);
+        Point::new(
+            position.x + padding - Twips::from_pixels(self.hscroll()),
+            position.y + padding - self.scroll_offset(),
+        )
+    }
+
+    /// Calls `f` with the position in the text, the horizontal position and the advance of
+    /// every glyph in a text layout box, in layout coordinates.
+    fn for_each_glyph(
+        layout_box: &LayoutBox<'gc>,
+        text: &WStr,
+        mut f: impl FnMut(usize, Twips, Twips),
+    ) {
+        let LayoutContent::Text { start, .. } = *layout_box.content() else {
+            return;
+        };
+        if let Some((box_text, _tf, font, params, _color)) = layout_box.as_renderable_text(text) {
+            let origin_x = layout_box.bounds().offset_x();
+            font.evaluate(
+                box_text,
+                Transform::default(),
+                params,
+                |pos, _transform, _glyph: &Glyph, advance, x| f(start + pos, origin_x + x, advance),
+            );
+        }
+    }
+
+    /// Returns the bounds of the character at a position in the text, in the local
+    /// coordinates of this text field.
+    ///
+    /// Returns None if the character isn't drawn, such as a line break.
+    pub fn char_bounds(self, position: usize) -> Option<Rectangle<Twips>> {
+        let read = self.0.read();
+        let text = read.text_spans.text();
+        let layout_box = read.layout.iter().find(|layout_box| {
+            matches!(*layout_box.content(), LayoutContent::Text { start, end, .. } if (start..end).contains(&position))
+        })?;
+
+        // Characters that were combined into one glyph share its bounds.
+        let mut glyph: Option<(usize, Twips, Twips)> = None;
+        Self::for_each_glyph(layout_box, text, |pos, x, advance| {
+            if pos <= position && glyph.map_or(true, |(glyph_pos, _, _)| pos > glyph_pos) {
+                glyph = Some((pos, x, advance));
+            }
+        });
+        let (_, x, advance) = glyph?;
+
+        let bounds = layout_box.bounds();
+        drop(read);
+        let top_left = self.layout_to_local(Point::new(x, bounds.offset_y()));
+        Some(Rectangle {
+            x_min: top_left.x,
+            x_max: top_left.x + advance,
+            y_min: top_left.y,
+            y_max: top_left.y + bounds.height(),
+        })
+    }
+
+    /// Returns the position in the text of the character under a point in the local
+    /// coordinates of this text field.
+    pub fn char_index_at_point(self, position: Point<Twips>) -> Option<usize> {
+        let position = self.local_to_layout(position);
+        let read = self.0.read();
+        let text = read.text_spans.text();
+        let mut result = None;
+        for layout_box in read.layout.iter() {
+            let bounds = layout_box.bounds();
+            if position.y < bounds.offset_y() || position.y >= bounds.extent_y() {
+                continue;
+            }
+            Self::for_each_glyph(layout_box, text, |pos, x, advance| {
+                if position.x >= x && position.x < x + advance {
+                    result = Some(pos);
+                }
+            });
+            if result.is_some() {
+                break;
+            }
+        }
+        result
+    }
+
+    /// Returns the index of the line under a point in the local coordinates of this
+    /// text field.
+    pub fn line_index_at_point(self, position: Point<Twips>) -> Option<usize> {
+        let position = self.local_to_layout(position);
+        self.0
+            .read()
+            .line_data
+            .iter()
+            .position(|line| position.y >= line.offset && position.y < line.extent)
+    }
+
+    /// Returns the index of the line that a position in the text is on.
+    pub fn line_index_of_char(self, position: usize) -> Option<usize> {
+        if position >= self.text_length() {
+            return None;
+        }
+        (0..self.layout_lines())
+            .take_while(|&line| {
+                self.line_offset(line)
+                    .map_or(true, |offset| offset <= position)
+            })
+            .last()
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
@@ -1781,17 +2098,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         );
         context.commands.activate_mask();
 
-        let scroll_offset = if edit_text.scroll > 1 {
-            let line_data = &edit_text.line_data;
-
-            if let Some(line_data) = line_data.get(edit_text.scroll - 1) {
-                line_data.offset
-            } else {
-                Twips::ZERO
-            }
-        } else {
-            Twips::ZERO
-        };
+        let scroll_offset = self.scroll_offset();
         // TODO: Where does this come from? How is this different than INTERNAL_PADDING? Does this apply to y as well?
         // If this is actually right, offset the border in `redraw_border` instead of doing an extra push.
         context.transform_stack.push(&Transform {
@@ -1914,12 +2221,16 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         self.into()
     }
 
+    fn tab_enabled_default(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        self.is_editable()
+    }
+
     fn filter_clip_event(
         self,
         _context: &mut UpdateContext<'_, 'gc>,
         event: ClipEvent,
     ) -> ClipEventResult {
-        if event != ClipEvent::Press {
+        if !matches!(event, ClipEvent::Press | ClipEvent::MouseWheel { .. }) {
             return ClipEventResult::NotHandled;
         }
 
@@ -1929,8 +2240,19 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
     fn event_dispatch(
         self,
         context: &mut UpdateContext<'_, 'gc>,
-        _event: ClipEvent<'gc>,
+        event: ClipEvent<'gc>,
     ) -> ClipEventResult {
+        if let ClipEvent::MouseWheel { delta } = event {
+            if self.is_mouse_wheel_enabled() {
+                let new_scroll = self.scroll() as f64 - delta.lines().trunc();
+                self.set_scroll(new_scroll, context);
+            }
+            if context.is_action_script_3() {
+                self.event_dispatch_to_avm2(context, event);
+            }
+            return ClipEventResult::Handled;
+        }
+
         if self.is_editable() || self.is_selectable() {
             let tracker = context.focus_tracker;
             tracker.set(Some(self.into()), context);
@@ -2004,6 +2326,7 @@ bitflags::bitflags! {
         const FIRING_VARIABLE_BINDING = 1 << 0;
         const HAS_BACKGROUND = 1 << 1;
         const HAS_FOCUS = 1 << 2;
+        const MOUSE_WHEEL_ENABLED = 1 << 13;
 
         // The following bits need to match `swf::EditTextFlag`.
         const READ_ONLY = 1 << 3;
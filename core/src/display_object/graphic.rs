@@ -13,7 +13,8 @@ use core::fmt;
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_render::backend::ShapeHandle;
 use ruffle_render::commands::CommandHandler;
-use std::cell::{Ref, RefMut};
+use ruffle_render::shape_utils::{DistilledShape, LineScales};
+use std::cell::{Ref, RefCell, RefMut};
 use std::sync::Arc;
 
 #[derive(Clone, Collect, Copy)]
@@ -35,6 +36,10 @@ pub struct GraphicData<'gc> {
     static_data: gc_arena::Gc<'gc, GraphicStatic>,
     avm2_object: Option<Avm2Object<'gc>>,
     drawing: Option<Drawing>,
+    /// The shape registered for this graphic when it has strokes that don't scale with it,
+    /// along with the `LineScales::cache_key` of the matrix it was registered for.
+    #[collect(require_static)]
+    scaled_handle: RefCell<Option<([i32; 4], ShapeHandle)>>,
 }
 
 impl<'gc> Graphic<'gc> {
@@ -45,16 +50,20 @@ impl<'gc> Graphic<'gc> {
         movie: Arc<SwfMovie>,
     ) -> Self {
         let library = context.library.library_for_movie(movie.clone()).unwrap();
+        let distilled_shape: DistilledShape = (&swf_shape).into();
+        let has_non_scaling_strokes = distilled_shape.has_non_scaling_strokes();
+        let render_handle = context.renderer.register_shape(
+            distilled_shape,
+            &MovieLibrarySource {
+                library,
+                gc_context: context.gc_context,
+            },
+        );
         let static_data = GraphicStatic {
             id: swf_shape.id,
             bounds: swf_shape.shape_bounds.clone(),
-            render_handle: Some(context.renderer.register_shape(
-                (&swf_shape).into(),
-                &MovieLibrarySource {
-                    library,
-                    gc_context: context.gc_context,
-                },
-            )),
+            render_handle: Some(render_handle),
+            has_non_scaling_strokes,
             shape: swf_shape,
             movie,
         };
@@ -66,6 +75,7 @@ impl<'gc> Graphic<'gc> {
                 static_data: gc_arena::Gc::new(context.gc_context, static_data),
                 avm2_object: None,
                 drawing: None,
+                scaled_handle: RefCell::new(None),
             },
         ))
     }
@@ -76,6 +86,7 @@ impl<'gc> Graphic<'gc> {
             id: 0,
             bounds: Default::default(),
             render_handle: None,
+            has_non_scaling_strokes: false,
             shape: swf::Shape {
                 version: 32,
                 id: 0,
@@ -99,10 +110,45 @@ impl<'gc> Graphic<'gc> {
                 static_data: gc_arena::Gc::new(context.gc_context, static_data),
                 avm2_object: None,
                 drawing: Some(drawing),
+                scaled_handle: RefCell::new(None),
             },
         ))
     }
 
+    /// Renders a shape with strokes that don't scale with it, which has to be registered
+    /// again whenever it's drawn with a different scale.
+    fn render_with_scaled_strokes(&self, context: &mut RenderContext<'_, 'gc>) {
+        let transform = context.transform_stack.transform();
+        let key = LineScales::cache_key(&transform.matrix);
+
+        let read = self.0.read();
+        let mut scaled_handle = read.scaled_handle.borrow_mut();
+        let handle = match &*scaled_handle {
+            Some((handle_key, handle)) if *handle_key == key => handle.clone(),
+            _ => {
+                let static_data = read.static_data;
+                let library = context
+                    .library
+                    .library_for_movie(static_data.movie.clone())
+                    .unwrap();
+                let shape: DistilledShape = (&static_data.shape).into();
+                let handle = shape.with_scaled_strokes(&transform.matrix, |shape| {
+                    context.renderer.register_shape(
+                        shape,
+                        &MovieLibrarySource {
+                            library,
+                            gc_context: context.gc_context,
+                        },
+                    )
+                });
+                *scaled_handle = Some((key, handle.clone()));
+                handle
+            }
+        };
+
+        context.commands.render_shape(handle, transform);
+    }
+
     pub fn drawing(&self, gc_context: MutationContext<'gc, '_>) -> RefMut<'_, Drawing> {
         RefMut::map(self.0.write(gc_context), |w| {
             w.drawing.get_or_insert_with(Drawing::new)
@@ -169,7 +215,9 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             .library_for_movie_mut(self.movie())
             .get_graphic(id)
         {
-            self.0.write(context.gc_context).static_data = new_graphic.0.read().static_data;
+            let mut write = self.0.write(context.gc_context);
+            write.static_data = new_graphic.0.read().static_data;
+            write.scaled_handle = RefCell::new(None);
         } else {
             tracing::warn!("PlaceObject: expected Graphic at character ID {}", id);
         }
@@ -188,6 +236,8 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
 
         if let Some(drawing) = &self.0.read().drawing {
             drawing.render(context);
+        } else if self.0.read().static_data.has_non_scaling_strokes {
+            self.render_with_scaled_strokes(context);
         } else if let Some(render_handle) = self.0.read().static_data.render_handle.clone() {
             context
                 .commands
@@ -271,6 +321,7 @@ struct GraphicStatic {
     id: CharacterId,
     shape: swf::Shape,
     render_handle: Option<ShapeHandle>,
+    has_non_scaling_strokes: bool,
     bounds: Rectangle<Twips>,
     movie: Arc<SwfMovie>,
 }
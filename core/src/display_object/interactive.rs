@@ -1,5 +1,8 @@
 //! Interactive object enumtrait
 
+use crate::avm1::{
+    Activation as Avm1Activation, ActivationIdentifier, TObject as _, Value as Avm1Value,
+};
 use crate::avm2::activation::Activation as Avm2Activation;
 use crate::avm2::{Avm2, EventObject as Avm2EventObject, Value as Avm2Value};
 use crate::backend::ui::MouseCursor;
@@ -89,6 +92,15 @@ pub struct InteractiveObjectBase<'gc> {
     /// display object.
     #[collect(require_static)]
     last_click: Option<Instant>,
+
+    /// The AVM2 `tabEnabled` of this object, or `None` to use the default.
+    tab_enabled: Option<bool>,
+
+    /// The AVM2 `tabIndex` of this object, or `None` if it's not set.
+    tab_index: Option<i32>,
+
+    /// Whether a focus rectangle is drawn around this object, or `None` to use the stage's setting.
+    focus_rect: Option<bool>,
 }
 
 impl<'gc> Default for InteractiveObjectBase<'gc> {
@@ -98,6 +110,9 @@ impl<'gc> Default for InteractiveObjectBase<'gc> {
             flags: InteractiveObjectFlags::MOUSE_ENABLED,
             context_menu: Avm2Value::Null,
             last_click: None,
+            tab_enabled: None,
+            tab_index: None,
+            focus_rect: None,
         }
     }
 }
@@ -162,6 +177,72 @@ pub trait TInteractiveObject<'gc>:
         self.raw_interactive_mut(mc).context_menu = value;
     }
 
+    /// Whether pressing Tab can focus this object.
+    ///
+    /// AVM1 objects use their `tabEnabled` property. Objects that don't set it are tab
+    /// enabled if they act as buttons or can be typed into.
+    fn tab_enabled(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        if let Avm1Value::Object(object) = self.as_displayobject().object() {
+            let mut activation = Avm1Activation::from_stub(
+                context.reborrow(),
+                ActivationIdentifier::root("[Tab Enabled]"),
+            );
+            match object.get("tabEnabled", &mut activation) {
+                Ok(Avm1Value::Undefined) | Err(_) => {}
+                Ok(value) => return value.as_bool(activation.swf_version()),
+            }
+        } else if let Some(tab_enabled) = self.raw_interactive().tab_enabled {
+            return tab_enabled;
+        }
+        self.tab_enabled_default(context)
+    }
+
+    /// Whether pressing Tab can focus this object, if it doesn't say otherwise.
+    fn tab_enabled_default(self, _context: &mut UpdateContext<'_, 'gc>) -> bool {
+        false
+    }
+
+    /// Sets the AVM2 `tabEnabled` of this object.
+    fn set_tab_enabled(self, mc: MutationContext<'gc, '_>, value: bool) {
+        self.raw_interactive_mut(mc).tab_enabled = Some(value);
+    }
+
+    /// The position of this object in the tab order, if it was given one.
+    ///
+    /// AVM1 objects use their `tabIndex` property.
+    fn tab_index(self, context: &mut UpdateContext<'_, 'gc>) -> Option<i32> {
+        if let Avm1Value::Object(object) = self.as_displayobject().object() {
+            let mut activation = Avm1Activation::from_stub(
+                context.reborrow(),
+                ActivationIdentifier::root("[Tab Index]"),
+            );
+            match object.get("tabIndex", &mut activation) {
+                Ok(Avm1Value::Undefined | Avm1Value::Null) | Err(_) => None,
+                Ok(value) => value
+                    .coerce_to_i32(&mut activation)
+                    .ok()
+                    .filter(|index| *index >= 0),
+            }
+        } else {
+            self.raw_interactive().tab_index
+        }
+    }
+
+    /// Sets the AVM2 `tabIndex` of this object. Negative indices unset it.
+    fn set_tab_index(self, mc: MutationContext<'gc, '_>, value: i32) {
+        self.raw_interactive_mut(mc).tab_index = Some(value).filter(|index| *index >= 0);
+    }
+
+    /// Whether a focus rectangle is drawn around this object when it's focused with the
+    /// keyboard, or `None` if the stage's setting applies.
+    fn focus_rect(self) -> Option<bool> {
+        self.raw_interactive().focus_rect
+    }
+
+    fn set_focus_rect(self, mc: MutationContext<'gc, '_>, value: Option<bool>) {
+        self.raw_interactive_mut(mc).focus_rect = value;
+    }
+
     /// Filter the incoming clip event.
     ///
     /// If this returns `Handled`, then the rest of the event handling
@@ -141,6 +141,7 @@ impl<'gc> TDisplayObject<'gc> for LoaderDisplay<'gc> {
         self.base_mut(context.gc_context)
             .set_parent_ignoring_orphan_list(parent);
         let has_parent = self.parent().is_some();
+        context.set_sound_transforms_dirty();
 
         if context.is_action_script_3() && had_parent && !has_parent {
             context.avm2.add_orphan_obj((*self).into())
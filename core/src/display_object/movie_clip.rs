@@ -8,7 +8,8 @@ use crate::avm2::{
     QName as Avm2QName, StageObject as Avm2StageObject, TObject as Avm2TObject, Value as Avm2Value,
 };
 use crate::backend::audio::{SoundHandle, SoundInstanceHandle};
-use crate::backend::ui::MouseCursor;
+use crate::backend::navigator::Request;
+use crate::backend::ui::{FontDefinition, MouseCursor};
 use crate::frame_lifecycle::run_inner_goto_frame;
 use bitflags::bitflags;
 
@@ -29,7 +30,7 @@ use crate::display_object::{
 };
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult};
-use crate::font::Font;
+use crate::font::{Font, FontFace};
 use crate::limits::ExecutionLimit;
 use crate::loader;
 use crate::loader::Loader;
@@ -43,9 +44,11 @@ use smallvec::SmallVec;
 use std::cell::{Ref, RefMut};
 use std::cmp::max;
 use std::collections::HashMap;
+use std::rc::Rc;
 use std::sync::Arc;
 use swf::extensions::ReadSwfExt;
 use swf::{ClipEventFlag, FontFlag, FrameLabelData, SwfStr};
+use url::Url;
 
 use super::interactive::Avm2MousePick;
 
@@ -451,6 +454,15 @@ impl<'gc> MovieClip<'gc> {
             }
         }
 
+        // Nothing after an `ImportAssets` tag loads until the imported assets are available.
+        if context
+            .library
+            .library_for_movie(self.movie())
+            .map_or(false, |library| library.has_pending_imports())
+        {
+            return false;
+        }
+
         // TODO: Re-creating static data because preload step occurs after construction.
         // Should be able to hoist this up somewhere, or use MaybeUninit.
         let mut static_data = (*self.0.read().static_data).clone();
@@ -520,6 +532,10 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .csm_text_settings(context, reader),
+                TagCode::DefineScalingGrid => self
+                    .0
+                    .write(context.gc_context)
+                    .define_scaling_grid(context, reader),
                 TagCode::DefineBits => self
                     .0
                     .write(context.gc_context)
@@ -639,6 +655,12 @@ impl<'gc> MovieClip<'gc> {
                     .0
                     .write(context.gc_context)
                     .export_assets(context, reader),
+                TagCode::ImportAssets | TagCode::ImportAssets2 => {
+                    self.0
+                        .write(context.gc_context)
+                        .import_assets(context, reader, tag_code)?;
+                    return Ok(ControlFlow::Exit);
+                }
                 TagCode::FrameLabel => self.0.write(context.gc_context).frame_label(
                     reader,
                     cur_frame,
@@ -2752,6 +2774,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                 }
             }
 
+            if !self.hit_test_scroll_rect(point) {
+                return false;
+            }
+
             let mut clip_depth = 0;
 
             for child in self.iter_render_list() {
@@ -2845,6 +2871,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         self.base_mut(context.gc_context)
             .set_parent_ignoring_orphan_list(parent);
         let has_parent = self.parent().is_some();
+        context.set_sound_transforms_dirty();
 
         if context.is_action_script_3() && had_parent && !has_parent {
             context.avm2.add_orphan_obj((*self).into())
@@ -2942,6 +2969,10 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
         self.into()
     }
 
+    fn tab_enabled_default(self, context: &mut UpdateContext<'_, 'gc>) -> bool {
+        self.is_button_mode(context)
+    }
+
     fn filter_clip_event(
         self,
         context: &mut UpdateContext<'_, 'gc>,
@@ -3058,6 +3089,10 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
                 }
             }
 
+            if !self.hit_test_scroll_rect(point) {
+                return None;
+            }
+
             // In AVM2, mouse_enabled should only impact the ability to select the current clip
             // but it should still be possible to select any children where child.mouse_enabled() is
             // true.
@@ -3145,6 +3180,10 @@ impl<'gc> TInteractiveObject<'gc> for MovieClip<'gc> {
                 }
             }
 
+            if !self.hit_test_scroll_rect(point) {
+                return Avm2MousePick::Miss;
+            }
+
             if self.maskee().is_some() {
                 // If we're masking another object, we can't be hit.
                 return Avm2MousePick::Miss;
@@ -3510,6 +3549,41 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_scaling_grid(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc>,
+        reader: &mut SwfStream<'a>,
+    ) -> Result<(), Error> {
+        let id = reader.read_u16()?;
+        let splitter_rect = reader.read_rectangle()?;
+        let library = context.library.library_for_movie_mut(self.movie());
+        match library.character_by_id(id) {
+            Some(Character::MovieClip(clip)) => {
+                clip.set_scaling_grid(context.gc_context, Some(splitter_rect));
+            }
+            Some(Character::Avm1Button(button)) => {
+                button.set_scaling_grid(context.gc_context, Some(splitter_rect));
+            }
+            Some(Character::Avm2Button(button)) => {
+                button.set_scaling_grid(context.gc_context, Some(splitter_rect));
+            }
+            Some(_) => {
+                tracing::warn!(
+                    "Tried to apply DefineScalingGrid to non-sprite character ID {}",
+                    id
+                );
+            }
+            None => {
+                tracing::warn!(
+                    "Tried to apply DefineScalingGrid to unregistered character ID {}",
+                    id
+                );
+            }
+        }
+        Ok(())
+    }
+
     #[inline]
     fn preload_video_frame(
         &mut self,
@@ -3809,23 +3883,43 @@ impl<'gc, 'a> MovieClipData<'gc> {
         context: &mut UpdateContext<'_, 'gc>,
         reader: &mut SwfStream<'a>,
     ) -> Result<(), Error> {
-        tracing::warn!("DefineFont4 tag (TLF text) is not implemented");
         let font = reader.read_define_font_4()?;
         let font_id = font.id;
-        let font_object = Font::from_swf_tag(
-            context.gc_context,
-            context.renderer,
-            swf::Font {
-                version: 4,
-                id: font.id,
-                name: SwfStr::from_bytes(b"<Unknown Font4>"),
-                language: swf::Language::Unknown,
-                layout: None,
-                glyphs: Vec::new(),
-                flags: FontFlag::empty(),
-            },
-            reader.encoding(),
-        );
+        let name = font.name.to_string_lossy(reader.encoding());
+        let face = font.data.and_then(|data| {
+            let definition = FontDefinition {
+                name: name.clone(),
+                is_bold: font.is_bold,
+                is_italic: font.is_italic,
+                data: data.to_vec(),
+                index: 0,
+            };
+            FontFace::new(definition)
+                .map_err(|e| tracing::error!("Failed to parse DefineFont4 font {}: {}", name, e))
+                .ok()
+        });
+        let font_object = if let Some(face) = face {
+            Font::from_font_face(context.gc_context, Rc::new(face), Vec::new())
+        } else {
+            // Fonts without data only provide a name for device text.
+            let mut flags = FontFlag::empty();
+            flags.set(FontFlag::IS_BOLD, font.is_bold);
+            flags.set(FontFlag::IS_ITALIC, font.is_italic);
+            Font::from_swf_tag(
+                context.gc_context,
+                context.renderer,
+                swf::Font {
+                    version: 4,
+                    id: font.id,
+                    name: font.name,
+                    language: swf::Language::Unknown,
+                    layout: None,
+                    glyphs: Vec::new(),
+                    flags,
+                },
+                reader.encoding(),
+            )
+        };
         context
             .library
             .library_for_movie_mut(self.movie())
@@ -3998,6 +4092,48 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn import_assets(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc>,
+        reader: &mut SwfStream<'a>,
+        tag_code: swf::TagCode,
+    ) -> Result<(), Error> {
+        let (url, imports) = if tag_code == swf::TagCode::ImportAssets2 {
+            reader.read_import_assets_2()?
+        } else {
+            reader.read_import_assets()?
+        };
+        let url = url.to_str_lossy(reader.encoding());
+        let imports = imports
+            .into_iter()
+            .map(|import| {
+                let name = import.name.decode(reader.encoding());
+                (import.id, AvmString::new(context.gc_context, name))
+            })
+            .collect();
+
+        // The URL of a library is relative to the movie importing from it.
+        let movie = self.movie();
+        let url = match Url::parse(movie.url()).and_then(|base| base.join(&url)) {
+            Ok(url) => url.to_string(),
+            Err(_) => url.into_owned(),
+        };
+
+        context
+            .library
+            .library_for_movie_mut(movie.clone())
+            .begin_import();
+        let future = context.load_manager.load_asset_import(
+            context.player.clone(),
+            movie,
+            Request::get(url),
+            imports,
+        );
+        context.navigator.spawn_future(future);
+        Ok(())
+    }
+
     #[inline]
     fn frame_label(
         &mut self,
@@ -0,0 +1,201 @@
+//! 3D transform properties of display objects (`z`, `rotationX`, `rotationY`, `scaleZ`)
+//! and the perspective projection used to display them.
+//!
+//! Render backends only know about 2D affine matrices, so a 3D-transformed display object
+//! is drawn with the affine transform that best approximates its perspective projection
+//! around its registration point.
+
+use crate::prelude::*;
+use crate::types::Degrees;
+
+/// The `fieldOfView` used when no `PerspectiveProjection` has been set.
+pub const DEFAULT_FIELD_OF_VIEW: f64 = 55.0;
+
+/// A `flash.geom.PerspectiveProjection`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PerspectiveProjection {
+    /// The field of view in degrees, between 0 and 180 (exclusive).
+    pub field_of_view: f64,
+
+    /// The vanishing point, in pixels.
+    pub center: (f64, f64),
+}
+
+impl PerspectiveProjection {
+    /// The default projection of a stage of the given size in pixels.
+    pub fn for_stage(width: f64, height: f64) -> Self {
+        Self {
+            field_of_view: DEFAULT_FIELD_OF_VIEW,
+            center: (width / 2.0, height / 2.0),
+        }
+    }
+
+    /// The distance in pixels between the viewer and the `z = 0` plane, given the
+    /// width in pixels of the viewport.
+    pub fn focal_length(&self, width: f64) -> f64 {
+        (width / 2.0) / (self.field_of_view.to_radians() / 2.0).tan()
+    }
+}
+
+/// The 3D components of a display object's transform.
+///
+/// The 2D components (`x`, `y`, `rotationZ`, `scaleX`, `scaleY`) are stored in the display
+/// object's regular transform matrix.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform3D {
+    pub z: f64,
+    pub rotation_x: Degrees,
+    pub rotation_y: Degrees,
+    pub scale_z: f64,
+}
+
+impl Default for Transform3D {
+    fn default() -> Self {
+        Self {
+            z: 0.0,
+            rotation_x: Degrees::from(0.0),
+            rotation_y: Degrees::from(0.0),
+            scale_z: 1.0,
+        }
+    }
+}
+
+impl Transform3D {
+    /// Whether this transform leaves the object on the `z = 0` plane, unrotated.
+    pub fn is_flat(&self) -> bool {
+        self.z == 0.0 && f64::from(self.rotation_x) == 0.0 && f64::from(self.rotation_y) == 0.0
+    }
+
+    /// Projects a display object with this 3D transform onto its parent's plane.
+    ///
+    /// `matrix` is the object's 2D matrix, and `rotation_z` the rotation cached from it.
+    /// The rotations around the X and Y axes are applied between the object's scale and
+    /// its rotation around the Z axis, like `Matrix3D.appendRotation` in Flash.
+    ///
+    /// Returns `None` if the object is behind the viewer and shouldn't be drawn.
+    pub fn project(
+        &self,
+        matrix: &Matrix,
+        rotation_z: Degrees,
+        projection: &PerspectiveProjection,
+        focal_length: f64,
+    ) -> Option<Matrix> {
+        // Work in twips, like the 2D matrix.
+        let focal_length = focal_length * 20.0;
+        let z = self.z * 20.0;
+        let depth = focal_length + z;
+        if depth <= 0.0 || !depth.is_finite() {
+            return None;
+        }
+
+        let (a, b, c, d) = (
+            f64::from(matrix.a),
+            f64::from(matrix.b),
+            f64::from(matrix.c),
+            f64::from(matrix.d),
+        );
+
+        // Strip the Z rotation from the 2D matrix, leaving only the scale and skew.
+        let (sin_z, cos_z) = rotation_z.into_radians().sin_cos();
+        let (sa, sb) = (cos_z * a + sin_z * b, -sin_z * a + cos_z * b);
+        let (sc, sd) = (cos_z * c + sin_z * d, -sin_z * c + cos_z * d);
+
+        // The local X and Y axes after rotating around X, then Y.
+        let (sin_x, cos_x) = self.rotation_x.into_radians().sin_cos();
+        let (sin_y, cos_y) = self.rotation_y.into_radians().sin_cos();
+        let x_axis = [cos_y, 0.0, -sin_y];
+        let y_axis = [sin_x * sin_y, cos_x, sin_x * cos_y];
+
+        // Apply the scale and skew, then the rotation around Z.
+        let column = |x: f64, y: f64| {
+            let v = [
+                x_axis[0] * x + y_axis[0] * y,
+                x_axis[1] * x + y_axis[1] * y,
+                x_axis[2] * x + y_axis[2] * y,
+            ];
+            [
+                cos_z * v[0] - sin_z * v[1],
+                sin_z * v[0] + cos_z * v[1],
+                v[2],
+            ]
+        };
+        let col_a = column(sa, sb);
+        let col_b = column(sc, sd);
+
+        // Linearize the perspective divide around the registration point.
+        let scale = focal_length / depth;
+        let center_x = projection.center.0 * 20.0;
+        let center_y = projection.center.1 * 20.0;
+        let offset_x = matrix.tx.get() as f64 - center_x;
+        let offset_y = matrix.ty.get() as f64 - center_y;
+        let jacobian = |col: [f64; 3]| {
+            (
+                scale * (col[0] - offset_x * col[2] / depth),
+                scale * (col[1] - offset_y * col[2] / depth),
+            )
+        };
+        let (a, b) = jacobian(col_a);
+        let (c, d) = jacobian(col_b);
+
+        Some(Matrix {
+            a: a as f32,
+            b: b as f32,
+            c: c as f32,
+            d: d as f32,
+            tx: Twips::new((center_x + offset_x * scale) as i32),
+            ty: Twips::new((center_y + offset_y * scale) as i32),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROJECTION: PerspectiveProjection = PerspectiveProjection {
+        field_of_view: DEFAULT_FIELD_OF_VIEW,
+        center: (250.0, 250.0),
+    };
+
+    #[test]
+    fn flat_transform_is_unchanged() {
+        let matrix = Matrix {
+            a: 2.0,
+            d: 0.5,
+            tx: Twips::from_pixels(100.0),
+            ty: Twips::from_pixels(40.0),
+            ..Default::default()
+        };
+        let projected = Transform3D::default()
+            .project(&matrix, Degrees::from(0.0), &PROJECTION, 480.0)
+            .unwrap();
+        assert_eq!(projected, matrix);
+    }
+
+    #[test]
+    fn distant_objects_shrink_towards_center() {
+        let transform = Transform3D {
+            z: 480.0,
+            ..Default::default()
+        };
+        let matrix = Matrix::translate(Twips::from_pixels(350.0), Twips::from_pixels(250.0));
+        let projected = transform
+            .project(&matrix, Degrees::from(0.0), &PROJECTION, 480.0)
+            .unwrap();
+        assert_eq!(projected.a, 0.5);
+        assert_eq!(projected.d, 0.5);
+        assert_eq!(projected.tx, Twips::from_pixels(300.0));
+        assert_eq!(projected.ty, Twips::from_pixels(250.0));
+    }
+
+    #[test]
+    fn objects_behind_viewer_are_hidden() {
+        let transform = Transform3D {
+            z: -500.0,
+            ..Default::default()
+        };
+        assert!(transform
+            .project(&Matrix::IDENTITY, Degrees::from(0.0), &PROJECTION, 480.0)
+            .is_none());
+    }
+}
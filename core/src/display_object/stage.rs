@@ -7,6 +7,7 @@ use crate::avm2::{
     ScriptObject as Avm2ScriptObject, StageObject as Avm2StageObject, Value as Avm2Value,
 };
 use crate::backend::ui::MouseCursor;
+use crate::bitmap::bitmap_data::BitmapModifications;
 use crate::config::Letterbox;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::container::{
@@ -26,6 +27,7 @@ use crate::vminterface::Instantiator;
 use bitflags::bitflags;
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_render::backend::ViewportDimensions;
+use ruffle_render::bitmap::PixelSnapping;
 use ruffle_render::commands::CommandHandler;
 use ruffle_render::quality::StageQuality;
 use ruffle_render::transform::Transform;
@@ -102,6 +104,13 @@ pub struct StageData<'gc> {
     /// Whether or not a RENDER event should be dispatched on the next render
     invalidated: bool,
 
+    /// The modification counter shared by every `BitmapData` of this player.
+    #[collect(require_static)]
+    bitmap_modifications: BitmapModifications,
+
+    /// The number of `BitmapData` modifications when the stage was last rendered.
+    rendered_bitmap_modifications: u64,
+
     /// Whether to use high quality downsampling for bitmaps.
     ///
     /// This is usally implied by `quality` being `Best` or higher, but the AVM1
@@ -121,6 +130,13 @@ pub struct StageData<'gc> {
     /// Whether or not objects display a glowing border when they have focus.
     stage_focus_rect: bool,
 
+    /// The area of the stage that is scaled to fill the screen in fullscreen mode, if set.
+    ///
+    /// Flash Player scales this area with the GPU, which is commonly used to upscale
+    /// low-resolution games.
+    #[collect(require_static)]
+    full_screen_source_rect: Option<Rectangle<Twips>>,
+
     /// Whether to show default context menu items
     show_menu: bool,
 
@@ -133,6 +149,9 @@ pub struct StageData<'gc> {
     /// An array of AVM2 'Stage3D' instances
     stage3ds: Vec<Avm2Object<'gc>>,
 
+    /// An array of AVM2 'StageVideo' instances
+    stage_videos: Vec<Avm2Object<'gc>>,
+
     /// The swf that registered this stage
     movie: Arc<SwfMovie>,
 
@@ -166,11 +185,13 @@ impl<'gc> Stage<'gc> {
                 scale_mode: Default::default(),
                 forced_scale_mode: false,
                 display_state: if fullscreen {
-                    StageDisplayState::FullScreen
+                    StageDisplayState::FullScreenInteractive
                 } else {
                     StageDisplayState::Normal
                 },
                 invalidated: false,
+                bitmap_modifications: BitmapModifications::default(),
+                rendered_bitmap_modifications: 0,
                 align: Default::default(),
                 forced_align: false,
                 use_bitmap_downsampling: false,
@@ -178,9 +199,11 @@ impl<'gc> Stage<'gc> {
                 window_mode: Default::default(),
                 show_menu: true,
                 stage_focus_rect: true,
+                full_screen_source_rect: None,
                 avm2_object: Avm2ScriptObject::custom_object(gc_context, None, None),
                 loader_info: Avm2ScriptObject::custom_object(gc_context, None, None),
                 stage3ds: vec![],
+                stage_videos: vec![],
                 movie,
                 viewport_matrix: Matrix::IDENTITY,
             },
@@ -195,6 +218,7 @@ impl<'gc> Stage<'gc> {
 
     pub fn set_background_color(self, gc_context: MutationContext<'gc, '_>, color: Option<Color>) {
         self.0.write(gc_context).background_color = color;
+        self.invalidate_cached_bitmap(gc_context);
     }
 
     pub fn inverse_view_matrix(self) -> Matrix {
@@ -250,6 +274,24 @@ impl<'gc> Stage<'gc> {
         self.0.write(gc_context).invalidated = value;
     }
 
+    /// The modification counter to create this player's `BitmapData`s with.
+    pub fn bitmap_modifications(self) -> BitmapModifications {
+        self.0.read().bitmap_modifications.clone()
+    }
+
+    /// Whether anything may look different since the stage was last rendered.
+    ///
+    /// Display objects invalidate their ancestors whenever they change, all the way up to
+    /// the stage, so this only needs to check the stage itself. Stage3D and stage videos
+    /// are drawn separately, and are assumed to always change.
+    pub fn needs_redraw(self) -> bool {
+        let this = self.0.read();
+        self.base().is_cache_invalidated()
+            || this.rendered_bitmap_modifications != this.bitmap_modifications.count()
+            || !this.stage3ds.is_empty()
+            || !this.stage_videos.is_empty()
+    }
+
     /// Returns the quality setting of the stage.
     ///
     /// In the Flash Player, the quality setting affects anti-aliasing and smoothing of bitmaps.
@@ -275,13 +317,19 @@ impl<'gc> Stage<'gc> {
                 | StageQuality::High16x16
                 | StageQuality::High16x16Linear
         );
+        drop(this);
         context.renderer.set_quality(quality);
+        self.invalidate_cached_bitmap(context.gc_context);
     }
 
     pub fn stage3ds(&self) -> Ref<Vec<Avm2Object<'gc>>> {
         Ref::map(self.0.read(), |this| &this.stage3ds)
     }
 
+    pub fn stage_videos(&self) -> Ref<Vec<Avm2Object<'gc>>> {
+        Ref::map(self.0.read(), |this| &this.stage_videos)
+    }
+
     /// Get the boolean flag which determines whether or not objects display a glowing border
     /// when they have focus.
     ///
@@ -348,12 +396,21 @@ impl<'gc> Stage<'gc> {
         self.0.read().display_state
     }
 
+    /// Gets whether keyboard input is restricted, as in the `FullScreen` display state.
+    ///
+    /// Only keys that don't enter text reach the movie in this state.
+    pub fn is_keyboard_restricted(self) -> bool {
+        self.display_state() == StageDisplayState::FullScreen
+    }
+
     /// Toggles display state between fullscreen and normal
+    ///
+    /// As the user asked for this, the keyboard isn't restricted in fullscreen.
     pub fn toggle_display_state(self, context: &mut UpdateContext<'_, 'gc>) {
         if self.is_fullscreen() {
             self.set_display_state(context, StageDisplayState::Normal);
         } else {
-            self.set_display_state(context, StageDisplayState::FullScreen);
+            self.set_display_state(context, StageDisplayState::FullScreenInteractive);
         }
     }
 
@@ -379,10 +436,34 @@ impl<'gc> Stage<'gc> {
 
         if result.is_ok() {
             self.0.write(context.gc_context).display_state = display_state;
+            self.build_matrices(context);
             self.fire_fullscreen_event(context);
         }
     }
 
+    /// Get the area of the stage that is scaled to fill the screen in fullscreen mode.
+    pub fn full_screen_source_rect(self) -> Option<Rectangle<Twips>> {
+        self.0.read().full_screen_source_rect.clone()
+    }
+
+    /// Set the area of the stage that is scaled to fill the screen in fullscreen mode.
+    /// Empty areas are ignored.
+    pub fn set_full_screen_source_rect(
+        self,
+        context: &mut UpdateContext<'_, 'gc>,
+        rect: Option<Rectangle<Twips>>,
+    ) {
+        self.0.write(context.gc_context).full_screen_source_rect =
+            rect.filter(|rect| rect.width() > Twips::ZERO && rect.height() > Twips::ZERO);
+        self.build_matrices(context);
+    }
+
+    /// The area of the stage that fills the screen, if it's fullscreen and has a source rect.
+    fn active_full_screen_source_rect(self) -> Option<Rectangle<Twips>> {
+        self.full_screen_source_rect()
+            .filter(|_| self.is_fullscreen())
+    }
+
     /// Get the stage alignment.
     pub fn align(self) -> StageAlign {
         self.0.read().align
@@ -446,6 +527,11 @@ impl<'gc> Stage<'gc> {
 
     /// Determine if we should letterbox the stage content.
     fn should_letterbox(self) -> bool {
+        // The area outside of the fullscreen source rect is always hidden.
+        if self.active_full_screen_source_rect().is_some() {
+            return true;
+        }
+
         // Only enable letterbox in the default `ShowAll` scale mode.
         // If content changes the scale mode or alignment, it signals that it is size-aware.
         // For example, `NoScale` is used to make responsive layouts; don't letterbox over it.
@@ -545,8 +631,35 @@ impl<'gc> Stage<'gc> {
         };
 
         drop(stage);
+        self.invalidate_cached_bitmap(context.gc_context);
+
+        let source_rect = self.active_full_screen_source_rect();
+        if let Some(source_rect) = &source_rect {
+            // Scale the source rect to fit the viewport, ignoring the scale mode and alignment.
+            let source_width = source_rect.width().to_pixels();
+            let source_height = source_rect.height().to_pixels();
+            let scale = f64::min(
+                viewport_width / source_width,
+                viewport_height / source_height,
+            );
+            let tx = (viewport_width - source_width * scale) / 2.0
+                - source_rect.x_min.to_pixels() * scale;
+            let ty = (viewport_height - source_height * scale) / 2.0
+                - source_rect.y_min.to_pixels() * scale;
+            self.0.write(context.gc_context).viewport_matrix = Matrix {
+                a: scale as f32,
+                b: 0.0,
+                c: 0.0,
+                d: scale as f32,
+                tx: Twips::from_pixels(tx),
+                ty: Twips::from_pixels(ty),
+            };
+        }
 
-        self.0.write(context.gc_context).view_bounds = if self.should_letterbox() {
+        self.0.write(context.gc_context).view_bounds = if let Some(source_rect) = source_rect {
+            // Fullscreen source rect: only that area is visible
+            source_rect
+        } else if self.should_letterbox() {
             // Letterbox: movie area
             Rectangle {
                 x_min: Twips::ZERO,
@@ -574,6 +687,65 @@ impl<'gc> Stage<'gc> {
         }
     }
 
+    /// Draw the frames of the stage videos into their view ports, from the lowest depth up.
+    fn render_stage_videos(&self, context: &mut RenderContext<'_, 'gc>) {
+        let mut stage_videos: Vec<_> = self
+            .stage_videos()
+            .iter()
+            .filter_map(|stage_video| stage_video.as_stage_video())
+            .collect();
+        stage_videos.sort_by_key(|stage_video| stage_video.depth());
+
+        for stage_video in stage_videos {
+            let Some(frame) = stage_video
+                .net_stream()
+                .and_then(|stream| stream.last_decoded_bitmap())
+            else {
+                continue;
+            };
+            let view_port = stage_video.view_port();
+            if view_port.width() <= Twips::ZERO || view_port.height() <= Twips::ZERO {
+                continue;
+            }
+
+            // Zooming magnifies the frame around its center, and panning moves the visible
+            // part of it towards an edge.
+            let (zoom_x, zoom_y) = stage_video.zoom();
+            let (pan_x, pan_y) = stage_video.pan();
+            let width = view_port.width().to_pixels();
+            let height = view_port.height().to_pixels();
+            let x = view_port.x_min.to_pixels() - (zoom_x - 1.0) * width * (pan_x + 1.0) / 2.0;
+            let y = view_port.y_min.to_pixels() - (zoom_y - 1.0) * height * (pan_y + 1.0) / 2.0;
+            let mut transform = context.transform_stack.transform();
+            transform.matrix *= Matrix {
+                a: (width * zoom_x / frame.width as f64) as f32,
+                b: 0.0,
+                c: 0.0,
+                d: (height * zoom_y / frame.height as f64) as f32,
+                tx: Twips::from_pixels(x),
+                ty: Twips::from_pixels(y),
+            };
+
+            let view_port_matrix = context.transform_stack.transform().matrix
+                * Matrix::create_box(
+                    width as f32,
+                    height as f32,
+                    0.0,
+                    view_port.x_min,
+                    view_port.y_min,
+                );
+            context.commands.push_mask();
+            context.commands.draw_rect(Color::WHITE, view_port_matrix);
+            context.commands.activate_mask();
+            context
+                .commands
+                .render_bitmap(frame.handle, transform, true, PixelSnapping::Never);
+            context.commands.deactivate_mask();
+            context.commands.draw_rect(Color::WHITE, view_port_matrix);
+            context.commands.pop_mask();
+        }
+    }
+
     /// Draw the stage's letterbox.
     fn draw_letterbox(&self, context: &mut RenderContext<'_, 'gc>) {
         let ViewportDimensions {
@@ -584,70 +756,64 @@ impl<'gc> Stage<'gc> {
         let viewport_width = viewport_width as f32;
         let viewport_height = viewport_height as f32;
 
+        // The visible area of the stage, in viewport pixels.
         let view_matrix = self.0.read().viewport_matrix;
-
-        let (movie_width, movie_height) = self.0.read().movie_size;
-        let movie_width = movie_width as f32 * view_matrix.a;
-        let movie_height = movie_height as f32 * view_matrix.d;
-
-        let margin_left = view_matrix.tx.to_pixels() as f32;
-        let margin_right = viewport_width - movie_width - margin_left;
-        let margin_top = view_matrix.ty.to_pixels() as f32;
-        let margin_bottom = viewport_height - movie_height - margin_top;
-
-        // Letterboxing only occurs in `StageScaleMode::ShowAll`, and they would only appear on the top+bottom or left+right.
-        if margin_top + margin_bottom > margin_left + margin_right {
-            // Top + bottom
-            if margin_top > 0.0 {
-                context.commands.draw_rect(
-                    Color::BLACK,
-                    Matrix::create_box(
-                        viewport_width,
-                        margin_top,
-                        0.0,
-                        Twips::default(),
-                        Twips::default(),
-                    ),
-                );
-            }
-            if margin_bottom > 0.0 {
-                context.commands.draw_rect(
-                    Color::BLACK,
-                    Matrix::create_box(
-                        viewport_width,
-                        margin_bottom,
-                        0.0,
-                        Twips::default(),
-                        Twips::from_pixels((viewport_height - margin_bottom) as f64),
-                    ),
-                );
-            }
-        } else {
-            // Left + right
-            if margin_left > 0.0 {
-                context.commands.draw_rect(
-                    Color::BLACK,
-                    Matrix::create_box(
-                        margin_left,
-                        viewport_height,
-                        0.0,
-                        Twips::default(),
-                        Twips::default(),
-                    ),
-                );
-            }
-            if margin_right > 0.0 {
-                context.commands.draw_rect(
-                    Color::BLACK,
-                    Matrix::create_box(
-                        margin_right,
-                        viewport_height,
-                        0.0,
-                        Twips::from_pixels((viewport_width - margin_right) as f64),
-                        Twips::default(),
-                    ),
-                );
-            }
+        let view_area = view_matrix * self.view_bounds();
+
+        let margin_left = view_area.x_min.to_pixels() as f32;
+        let margin_right = viewport_width - view_area.x_max.to_pixels() as f32;
+        let margin_top = view_area.y_min.to_pixels() as f32;
+        let margin_bottom = viewport_height - view_area.y_max.to_pixels() as f32;
+
+        // In `StageScaleMode::ShowAll`, margins only appear on the top+bottom or left+right,
+        // but the fullscreen source rect can leave margins on every side.
+        if margin_top > 0.0 {
+            context.commands.draw_rect(
+                Color::BLACK,
+                Matrix::create_box(
+                    viewport_width,
+                    margin_top,
+                    0.0,
+                    Twips::default(),
+                    Twips::default(),
+                ),
+            );
+        }
+        if margin_bottom > 0.0 {
+            context.commands.draw_rect(
+                Color::BLACK,
+                Matrix::create_box(
+                    viewport_width,
+                    margin_bottom,
+                    0.0,
+                    Twips::default(),
+                    Twips::from_pixels((viewport_height - margin_bottom) as f64),
+                ),
+            );
+        }
+        if margin_left > 0.0 {
+            context.commands.draw_rect(
+                Color::BLACK,
+                Matrix::create_box(
+                    margin_left,
+                    viewport_height,
+                    0.0,
+                    Twips::default(),
+                    Twips::default(),
+                ),
+            );
+        }
+        if margin_right > 0.0 {
+            context.commands.draw_rect(
+                Color::BLACK,
+                Matrix::create_box(
+                    margin_right,
+                    viewport_height,
+                    0.0,
+                    Twips::from_pixels((viewport_width - margin_right) as f64),
+                    Twips::default(),
+                ),
+            );
         }
     }
 
@@ -683,11 +849,13 @@ impl<'gc> Stage<'gc> {
     /// TODO: Need additional check as Flash Player does not
     /// broadcast the 'render' event on the first render
     pub fn broadcast_render(&self, context: &mut UpdateContext<'_, 'gc>) {
+        // Calling `invalidate` from a `render` listener requests another `render` event
+        // before the next frame is drawn, not during this one.
+        self.set_invalidated(context.gc_context, false);
+
         let render_evt = Avm2EventObject::bare_default_event(context, "render");
         let dobject_constr = context.avm2.classes().display_object;
         Avm2::broadcast_event(context, render_evt, dobject_constr);
-
-        self.set_invalidated(context.gc_context, false);
     }
 
     /// Fires `Stage.onFullScreen` in AVM1 or `Event.FULLSCREEN` in AVM2.
@@ -705,20 +873,39 @@ impl<'gc> Stage<'gc> {
         } else if let Avm2Value::Object(stage) = self.object2() {
             let full_screen_event_cls = context.avm2.classes().fullscreenevent;
             let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let interactive = self.display_state() == StageDisplayState::FullScreenInteractive;
             let full_screen_event = full_screen_event_cls
                 .construct(
                     &mut activation,
                     &[
-                        "fullScreen".into(),
-                        false.into(),
-                        false.into(),
-                        self.is_fullscreen().into(),
-                        true.into(),
+                        "fullScreen".into(),         /* type */
+                        false.into(),                /* bubbles */
+                        false.into(),                /* cancelable */
+                        self.is_fullscreen().into(), /* fullScreen */
+                        interactive.into(),          /* interactive */
                     ],
                 )
                 .unwrap(); // we don't expect to break here
 
-            Avm2::dispatch_event(context, full_screen_event, stage);
+            Avm2::dispatch_event(&mut activation.context, full_screen_event, stage);
+
+            // Ruffle doesn't ask the user to allow keyboard input, so it's accepted right away.
+            if interactive {
+                let accepted_event = full_screen_event_cls
+                    .construct(
+                        &mut activation,
+                        &[
+                            "fullScreenInteractiveAccepted".into(), /* type */
+                            false.into(),                           /* bubbles */
+                            false.into(),                           /* cancelable */
+                            true.into(),                            /* fullScreen */
+                            true.into(),                            /* interactive */
+                        ],
+                    )
+                    .unwrap();
+
+                Avm2::dispatch_event(&mut activation.context, accepted_event, stage);
+            }
         }
     }
 }
@@ -773,11 +960,20 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
             .construct(&mut activation, &[])
             .expect("Failed to construct Stage3D");
 
+        // Flash Player usually offers a single StageVideo too
+        let stage_video = activation
+            .avm2()
+            .classes()
+            .stagevideo
+            .construct(&mut activation, &[])
+            .expect("Failed to construct StageVideo");
+
         match avm2_stage {
             Ok(avm2_stage) => {
                 let mut write = self.0.write(activation.context.gc_context);
                 write.avm2_object = avm2_stage.into();
                 write.stage3ds = vec![stage3d];
+                write.stage_videos = vec![stage_video];
             }
             Err(e) => tracing::error!("Unable to construct AVM2 Stage: {}", e),
         }
@@ -808,11 +1004,19 @@ impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
     }
 
     fn render(&self, context: &mut RenderContext<'_, 'gc>) {
+        self.base_mut(context.gc_context).clear_invalidate_flag();
+        let mut write = self.0.write(context.gc_context);
+        write.rendered_bitmap_modifications = write.bitmap_modifications.count();
+        drop(write);
+
         context.transform_stack.push(&Transform {
             matrix: self.0.read().viewport_matrix,
             color_transform: Default::default(),
         });
 
+        // Stage videos are drawn on a plane below everything else, including Stage3D.
+        self.render_stage_videos(context);
+
         // All of our Stage3D instances get rendered *underneath* the main stage.
         // Note that the stage background color is actually the lowest possible layer,
         // and get applied when we start the frame (before `render` is called).
@@ -5,6 +5,7 @@ use crate::avm2::{
     Activation as Avm2Activation, Object as Avm2Object, StageObject as Avm2StageObject,
     Value as Avm2Value,
 };
+use crate::camera::{Camera, Cameras};
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, DisplayObjectPtr, TDisplayObject};
 use crate::prelude::*;
@@ -101,7 +102,7 @@ pub enum VideoSource<'gc> {
     /// particular character. If you need to mutate the video source, consider
     /// reallocating a new source for your specific video instead.
     ///
-    /// This warning does not apply to `NetStream` or `Unconnected` videos,
+    /// This warning does not apply to `NetStream`, `Camera` or `Unconnected` videos,
     /// which are never aliased.
     Swf {
         /// The video stream definition.
@@ -119,6 +120,11 @@ pub enum VideoSource<'gc> {
         /// The stream the video is downloaded from.
         stream: NetStream<'gc>,
     },
+    /// An attached Camera.
+    Camera {
+        /// The camera the video is captured from.
+        camera: Camera<'gc>,
+    },
     Unconnected,
 }
 
@@ -185,6 +191,8 @@ impl<'gc> Video<'gc> {
     ///
     /// Existing video state related to the old video stream will be dropped.
     pub fn attach_netstream(self, context: &mut UpdateContext<'_, 'gc>, stream: NetStream<'gc>) {
+        self.detach_source(context);
+        stream.attach_video(context.gc_context, self);
         let mut video = self.0.write(context.gc_context);
 
         video.source = GcCell::new(context.gc_context, VideoSource::NetStream { stream });
@@ -192,6 +200,36 @@ impl<'gc> Video<'gc> {
         video.keyframes = BTreeSet::new();
     }
 
+    /// Convert this Video into a Camera sourced video, or disconnect it if `camera` is `None`.
+    ///
+    /// Existing video state related to the old video stream will be dropped.
+    pub fn attach_camera(self, context: &mut UpdateContext<'_, 'gc>, camera: Option<Camera<'gc>>) {
+        self.detach_source(context);
+        if let Some(camera) = camera {
+            Cameras::attach(context, camera);
+        }
+
+        let mut video = self.0.write(context.gc_context);
+        let source = match camera {
+            Some(camera) => VideoSource::Camera { camera },
+            None => VideoSource::Unconnected,
+        };
+        video.source = GcCell::new(context.gc_context, source);
+        video.stream = VideoStream::Uninstantiated(0);
+        video.keyframes = BTreeSet::new();
+    }
+
+    /// Stops capturing from the camera or displaying the stream this Video is
+    /// attached to, if any.
+    fn detach_source(self, context: &mut UpdateContext<'_, 'gc>) {
+        let source = self.0.read().source;
+        match &*source.read() {
+            VideoSource::Camera { camera } => Cameras::detach(context, *camera),
+            VideoSource::NetStream { stream } => stream.detach_video(context.gc_context, self),
+            _ => {}
+        }
+    }
+
     /// Preload frame data from an SWF.
     ///
     /// This function yields an error if this video player is not playing an
@@ -216,6 +254,7 @@ impl<'gc> Video<'gc> {
                 frames.insert(tag.frame_num.into(), (subslice.start, subslice.end));
             }
             VideoSource::NetStream { .. } => {}
+            VideoSource::Camera { .. } => {}
             VideoSource::Unconnected { .. } => {}
         }
     }
@@ -248,6 +287,7 @@ impl<'gc> Video<'gc> {
         let num_frames = match &*read.source.read() {
             VideoSource::Swf { streamdef, .. } => streamdef.num_frames as usize,
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -336,6 +376,7 @@ impl<'gc> Video<'gc> {
                 }
             },
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -435,6 +476,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 }
             }
             VideoSource::NetStream { .. } => return,
+            VideoSource::Camera { .. } => return,
             VideoSource::Unconnected { .. } => return,
         };
 
@@ -496,6 +538,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         match &*self.0.read().source.read() {
             VideoSource::Swf { streamdef, .. } => streamdef.id,
             VideoSource::NetStream { .. } => 0,
+            VideoSource::Camera { .. } => 0,
             VideoSource::Unconnected { .. } => 0,
         }
     }
@@ -541,6 +584,9 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 stream.last_decoded_bitmap(),
                 None,
             ),
+            VideoSource::Camera { camera } => {
+                (false, None, read.movie.version(), camera.last_frame(), None)
+            }
             VideoSource::Unconnected { .. } => return context.transform_stack.pop(),
         };
 
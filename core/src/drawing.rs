@@ -1,21 +1,48 @@
 use crate::context::RenderContext;
 use gc_arena::Collect;
 use ruffle_render::backend::{RenderBackend, ShapeHandle};
-use ruffle_render::bitmap::{BitmapHandle, BitmapInfo, BitmapSize, BitmapSource};
+use ruffle_render::bitmap::{
+    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, BitmapSize, BitmapSource,
+};
 use ruffle_render::commands::CommandHandler;
-use ruffle_render::shape_utils::{DistilledShape, DrawCommand, DrawPath, FillRule};
+use ruffle_render::matrix::Matrix;
+use ruffle_render::pixel_bender::{PixelBenderShaderArgument, PixelBenderShaderHandle};
+use ruffle_render::shape_utils::{
+    DistilledShape, DrawCommand, DrawPath, FillRule, LineScaleMode, LineScales,
+};
 use std::cell::{Cell, RefCell};
 use swf::{FillStyle, LineStyle, Point, Rectangle, Twips};
 
+/// How many separately registered shapes a drawing may be made of, before they're
+/// registered again as a single shape.
+const MAX_COMMITTED_HANDLES: usize = 16;
+
+/// Bitmap fills with an id of at least this refer to a shader fill, rather than a bitmap.
+const SHADER_FILL_ID: u16 = 0x8000;
+
+/// The largest width or height, in pixels, of the bitmap that a shader fill is rendered into.
+const MAX_SHADER_FILL_SIZE: i32 = 4096;
+
 #[derive(Clone, Debug, Collect)]
 #[collect(require_static)]
 pub struct Drawing {
-    render_handle: RefCell<Option<ShapeHandle>>,
+    /// Shapes registered for the first `committed_paths` entries of `paths`.
+    /// As paths are only ever appended until the drawing is cleared, these don't
+    /// need to be registered again when more paths are added.
+    committed_handles: RefCell<Vec<ShapeHandle>>,
+    committed_paths: Cell<usize>,
+    /// The shape registered for the fill and lines that are still being drawn.
+    pending_handle: RefCell<Option<ShapeHandle>>,
+    /// The shape registered for the whole drawing when it has strokes that don't scale with it,
+    /// along with the `LineScales::cache_key` of the matrix it was registered for.
+    scaled_handle: RefCell<Option<([i32; 4], ShapeHandle)>>,
+    has_non_scaling_strokes: bool,
     shape_bounds: Rectangle<Twips>,
     edge_bounds: Rectangle<Twips>,
     dirty: Cell<bool>,
     paths: Vec<DrawingPath>,
     bitmaps: Vec<BitmapInfo>,
+    shader_fills: Vec<DrawingShaderFill>,
     current_fill: Option<DrawingFill>,
     current_line: Option<DrawingLine>,
     pending_lines: Vec<DrawingLine>,
@@ -33,34 +60,44 @@ impl Default for Drawing {
 impl Drawing {
     pub fn new() -> Self {
         Self {
-            render_handle: RefCell::new(None),
+            committed_handles: RefCell::new(Vec::new()),
+            committed_paths: Cell::new(0),
+            pending_handle: RefCell::new(None),
+            scaled_handle: RefCell::new(None),
             shape_bounds: Default::default(),
             edge_bounds: Default::default(),
             dirty: Cell::new(false),
             paths: Vec::new(),
             bitmaps: Vec::new(),
+            shader_fills: Vec::new(),
             current_fill: None,
             current_line: None,
             pending_lines: Vec::new(),
             cursor: Point::ZERO,
             fill_start: Point::ZERO,
+            has_non_scaling_strokes: false,
             winding_rule: FillRule::EvenOdd,
         }
     }
 
     pub fn from_swf_shape(shape: &swf::Shape) -> Self {
         let mut this = Self {
-            render_handle: RefCell::new(None),
+            committed_handles: RefCell::new(Vec::new()),
+            committed_paths: Cell::new(0),
+            pending_handle: RefCell::new(None),
+            scaled_handle: RefCell::new(None),
             shape_bounds: shape.shape_bounds.clone(),
             edge_bounds: shape.edge_bounds.clone(),
             dirty: Cell::new(true),
             paths: Vec::new(),
             bitmaps: Vec::new(),
+            shader_fills: Vec::new(),
             current_fill: None,
             current_line: None,
             pending_lines: Vec::new(),
             cursor: Point::ZERO,
             fill_start: Point::ZERO,
+            has_non_scaling_strokes: false,
             winding_rule: if shape.flags.contains(swf::ShapeFlag::NON_ZERO_WINDING_RULE) {
                 FillRule::NonZero
             } else {
@@ -106,17 +143,22 @@ impl Drawing {
 
     pub fn copy_from(&mut self, other: &Drawing) {
         *self = Drawing {
-            render_handle: RefCell::new(None),
+            committed_handles: RefCell::new(Vec::new()),
+            committed_paths: Cell::new(0),
+            pending_handle: RefCell::new(None),
+            scaled_handle: RefCell::new(None),
             dirty: Cell::new(true),
             shape_bounds: other.shape_bounds.clone(),
             edge_bounds: other.edge_bounds.clone(),
             paths: other.paths.clone(),
             bitmaps: other.bitmaps.clone(),
+            shader_fills: other.shader_fills.clone(),
             current_fill: other.current_fill.clone(),
             current_line: other.current_line.clone(),
             pending_lines: other.pending_lines.clone(),
             cursor: other.cursor,
             fill_start: other.fill_start,
+            has_non_scaling_strokes: other.has_non_scaling_strokes,
             winding_rule: other.winding_rule,
         }
     }
@@ -157,7 +199,11 @@ impl Drawing {
         self.current_line = None;
         self.pending_lines.clear();
         self.paths.clear();
+        self.committed_handles.get_mut().clear();
+        self.committed_paths.set(0);
         self.bitmaps.clear();
+        self.shader_fills.clear();
+        self.has_non_scaling_strokes = false;
         self.edge_bounds = Default::default();
         self.shape_bounds = Default::default();
         self.dirty.set(true);
@@ -175,6 +221,9 @@ impl Drawing {
             }
         }
         if let Some(style) = style {
+            if LineScaleMode::for_style(&style) != LineScaleMode::Both {
+                self.has_non_scaling_strokes = true;
+            }
             self.current_line = Some(DrawingLine {
                 style,
                 commands: vec![DrawCommand::MoveTo(self.cursor)],
@@ -185,6 +234,11 @@ impl Drawing {
         self.dirty.set(true);
     }
 
+    /// The style of the fill that's currently being drawn, if any.
+    pub fn fill_style(&self) -> Option<&FillStyle> {
+        self.current_fill.as_ref().map(|fill| &fill.style)
+    }
+
     pub fn set_line_fill_style(&mut self, fill_style: FillStyle) {
         if let Some(style) = self.current_line.as_ref().map(|l| l.style.clone()) {
             self.set_line_style(Some(style.with_fill_style(fill_style)));
@@ -239,82 +293,248 @@ impl Drawing {
         id
     }
 
-    pub fn render(&self, context: &mut RenderContext) {
-        if self.dirty.get() {
-            self.dirty.set(false);
-            let mut paths = Vec::with_capacity(self.paths.len());
-
-            for path in &self.paths {
-                match path {
-                    DrawingPath::Fill(fill) => {
-                        paths.push(DrawPath::Fill {
-                            style: &fill.style,
-                            commands: fill.commands.to_owned(),
-                            winding_rule: FillRule::EvenOdd,
-                        });
-                    }
-                    DrawingPath::Line(line) => {
-                        paths.push(DrawPath::Stroke {
-                            style: &line.style,
-                            commands: line.commands.to_owned(),
-                            is_closed: line.is_closed,
-                        });
-                    }
+    /// Starts a fill that's drawn by a Pixel Bender shader.
+    ///
+    /// `matrix` maps from the shader's output coordinates to twips, like the matrix of a
+    /// bitmap fill.
+    pub fn set_shader_fill_style(
+        &mut self,
+        shader: PixelBenderShaderHandle,
+        arguments: Vec<PixelBenderShaderArgument<'static>>,
+        matrix: swf::Matrix,
+    ) {
+        let id = SHADER_FILL_ID + self.shader_fills.len() as u16;
+        self.shader_fills.push(DrawingShaderFill {
+            shader,
+            arguments,
+            bitmap: RefCell::new(None),
+        });
+        self.set_fill_style(Some(FillStyle::Bitmap {
+            id,
+            matrix,
+            is_smoothed: false,
+            is_repeating: false,
+        }));
+    }
+
+    /// Runs the shaders of any shader fills that have grown, into bitmaps that are large
+    /// enough to cover their paths.
+    fn update_shader_fills(&self, renderer: &mut dyn RenderBackend) {
+        for (index, shader_fill) in self.shader_fills.iter().enumerate() {
+            let (width, height) = self.shader_fill_size(SHADER_FILL_ID + index as u16);
+            let mut bitmap = shader_fill.bitmap.borrow_mut();
+            let is_covered = bitmap.as_ref().map_or(width == 0 || height == 0, |bitmap| {
+                bitmap.width >= width && bitmap.height >= height
+            });
+            if is_covered {
+                continue;
+            }
+
+            let target = Bitmap::new(
+                width.into(),
+                height.into(),
+                BitmapFormat::Rgba,
+                vec![0; width as usize * height as usize * 4],
+            );
+            let handle = match renderer.register_bitmap(target) {
+                Ok(handle) => handle,
+                Err(e) => {
+                    tracing::error!("Failed to create bitmap for shader fill: {e}");
+                    continue;
                 }
+            };
+            if let Err(e) = renderer.run_pixelbender_shader(
+                shader_fill.shader.clone(),
+                &shader_fill.arguments,
+                handle.clone(),
+            ) {
+                tracing::error!("Failed to run shader fill: {e}");
+                continue;
             }
+            *bitmap = Some(BitmapInfo {
+                handle,
+                width,
+                height,
+            });
+        }
+    }
 
-            if let Some(fill) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style: &fill.style,
-                    commands: fill.commands.to_owned(),
-                    winding_rule: FillRule::EvenOdd,
-                })
+    /// The size of the area in a shader fill's output coordinates that's covered by the
+    /// paths using it, starting from the origin.
+    fn shader_fill_size(&self, id: u16) -> (u16, u16) {
+        let fills = self
+            .paths
+            .iter()
+            .filter_map(|path| match path {
+                DrawingPath::Fill(fill) => Some(fill),
+                DrawingPath::Line(_) => None,
+            })
+            .chain(self.current_fill.iter());
+
+        let (mut width, mut height) = (0, 0);
+        for fill in fills {
+            let FillStyle::Bitmap {
+                id: fill_id,
+                matrix,
+                ..
+            } = &fill.style
+            else {
+                continue;
+            };
+            if *fill_id != id {
+                continue;
             }
+            let Some(inverse) = Matrix::from(*matrix).inverse() else {
+                continue;
+            };
 
-            for line in &self.pending_lines {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo(self.fill_start));
-                    true
-                } else {
-                    self.cursor == self.fill_start
+            for command in &fill.commands {
+                let points = match command {
+                    DrawCommand::MoveTo(point) | DrawCommand::LineTo(point) => [*point, *point],
+                    DrawCommand::CurveTo { control, anchor } => [*control, *anchor],
                 };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
+                for point in points {
+                    // The inverse of a bitmap fill matrix maps from twips to pixels.
+                    let point = inverse * point;
+                    width = width.max(point.x.get().clamp(0, MAX_SHADER_FILL_SIZE) + 1);
+                    height = height.max(point.y.get().clamp(0, MAX_SHADER_FILL_SIZE) + 1);
+                }
             }
+        }
+        (width as u16, height as u16)
+    }
 
-            if let Some(line) = &self.current_line {
-                let mut commands = line.commands.to_owned();
-                let is_closed = if self.current_fill.is_some() {
-                    commands.push(DrawCommand::LineTo(self.fill_start));
-                    true
-                } else {
-                    self.cursor == self.fill_start
-                };
-                paths.push(DrawPath::Stroke {
-                    style: &line.style,
-                    commands,
-                    is_closed,
-                })
+    pub fn render(&self, context: &mut RenderContext) {
+        if self.has_non_scaling_strokes {
+            self.render_with_scaled_strokes(context);
+            return;
+        }
+
+        if self.dirty.get() {
+            self.dirty.set(false);
+            self.update_shader_fills(context.renderer);
+
+            let mut committed_handles = self.committed_handles.borrow_mut();
+            if committed_handles.len() >= MAX_COMMITTED_HANDLES {
+                committed_handles.clear();
+                self.committed_paths.set(0);
             }
 
-            let shape = DistilledShape {
-                paths,
-                shape_bounds: self.shape_bounds.clone(),
-                edge_bounds: self.edge_bounds.clone(),
-                id: 0,
-            };
-            self.render_handle
-                .replace(Some(context.renderer.register_shape(shape, self)));
+            // Only the paths that were added since the last render need to be registered.
+            if self.committed_paths.get() < self.paths.len() {
+                let paths = self.paths[self.committed_paths.get()..]
+                    .iter()
+                    .map(DrawingPath::to_draw_path)
+                    .collect();
+                committed_handles.push(
+                    context
+                        .renderer
+                        .register_shape(self.distilled_shape(paths), self),
+                );
+                self.committed_paths.set(self.paths.len());
+            }
+
+            let paths = self.pending_paths();
+            let pending_handle = (!paths.is_empty()).then(|| {
+                context
+                    .renderer
+                    .register_shape(self.distilled_shape(paths), self)
+            });
+            self.pending_handle.replace(pending_handle);
         }
 
-        if let Some(handle) = self.render_handle.borrow().to_owned() {
+        let transform = context.transform_stack.transform();
+        let committed_handles = self.committed_handles.borrow();
+        let pending_handle = self.pending_handle.borrow();
+        for handle in committed_handles.iter().chain(pending_handle.iter()) {
             context
                 .commands
-                .render_shape(handle, context.transform_stack.transform());
+                .render_shape(handle.to_owned(), transform.clone());
+        }
+    }
+
+    /// Renders a drawing with strokes that don't scale with it, which has to be registered
+    /// again whenever it's drawn with a different scale.
+    fn render_with_scaled_strokes(&self, context: &mut RenderContext) {
+        let transform = context.transform_stack.transform();
+        let key = LineScales::cache_key(&transform.matrix);
+
+        let mut scaled_handle = self.scaled_handle.borrow_mut();
+        let handle = match &*scaled_handle {
+            Some((handle_key, handle)) if *handle_key == key && !self.dirty.get() => handle.clone(),
+            _ => {
+                self.dirty.set(false);
+                self.update_shader_fills(context.renderer);
+                let shape = self.distilled_shape(self.paths());
+                let handle = shape.with_scaled_strokes(&transform.matrix, |shape| {
+                    context.renderer.register_shape(shape, self)
+                });
+                *scaled_handle = Some((key, handle.clone()));
+                handle
+            }
+        };
+
+        context.commands.render_shape(handle, transform);
+    }
+
+    /// The fill and lines that are still being drawn.
+    fn pending_paths(&self) -> Vec<DrawPath<'_>> {
+        let mut paths = Vec::new();
+
+        if let Some(fill) = &self.current_fill {
+            paths.push(DrawPath::Fill {
+                style: &fill.style,
+                commands: fill.commands.to_owned(),
+                winding_rule: FillRule::EvenOdd,
+            })
+        }
+
+        for line in &self.pending_lines {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo(self.fill_start));
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
+
+        if let Some(line) = &self.current_line {
+            let mut commands = line.commands.to_owned();
+            let is_closed = if self.current_fill.is_some() {
+                commands.push(DrawCommand::LineTo(self.fill_start));
+                true
+            } else {
+                self.cursor == self.fill_start
+            };
+            paths.push(DrawPath::Stroke {
+                style: &line.style,
+                commands,
+                is_closed,
+            })
+        }
+
+        paths
+    }
+
+    /// All of the fills and strokes of this drawing, in the order they're rendered.
+    pub fn paths(&self) -> Vec<DrawPath<'_>> {
+        let mut paths: Vec<_> = self.paths.iter().map(DrawingPath::to_draw_path).collect();
+        paths.extend(self.pending_paths());
+        paths
+    }
+
+    fn distilled_shape<'a>(&self, paths: Vec<DrawPath<'a>>) -> DistilledShape<'a> {
+        DistilledShape {
+            paths,
+            shape_bounds: self.shape_bounds.clone(),
+            edge_bounds: self.edge_bounds.clone(),
+            id: 0,
         }
     }
 
@@ -322,11 +542,7 @@ impl Drawing {
         &self.shape_bounds
     }
 
-    pub fn hit_test(
-        &self,
-        point: Point<Twips>,
-        local_matrix: &ruffle_render::matrix::Matrix,
-    ) -> bool {
+    pub fn hit_test(&self, point: Point<Twips>, local_matrix: &Matrix) -> bool {
         use ruffle_render::shape_utils;
         for path in &self.paths {
             match path {
@@ -410,15 +626,26 @@ impl Drawing {
     }
 }
 
+impl Drawing {
+    fn bitmap(&self, id: u16) -> Option<BitmapInfo> {
+        if id >= SHADER_FILL_ID {
+            let shader_fill = self.shader_fills.get((id - SHADER_FILL_ID) as usize)?;
+            shader_fill.bitmap.borrow().clone()
+        } else {
+            self.bitmaps.get(id as usize).cloned()
+        }
+    }
+}
+
 impl BitmapSource for Drawing {
     fn bitmap_size(&self, id: u16) -> Option<BitmapSize> {
-        self.bitmaps.get(id as usize).map(|bm| BitmapSize {
+        self.bitmap(id).map(|bm| BitmapSize {
             width: bm.width,
             height: bm.height,
         })
     }
     fn bitmap_handle(&self, id: u16, _backend: &mut dyn RenderBackend) -> Option<BitmapHandle> {
-        self.bitmaps.get(id as usize).map(|bm| bm.handle.clone())
+        self.bitmap(id).map(|bm| bm.handle)
     }
 }
 
@@ -428,6 +655,15 @@ struct DrawingFill {
     commands: Vec<DrawCommand>,
 }
 
+/// A fill drawn by a Pixel Bender shader, which is rendered into a bitmap when the
+/// drawing is rendered.
+#[derive(Debug, Clone)]
+struct DrawingShaderFill {
+    shader: PixelBenderShaderHandle,
+    arguments: Vec<PixelBenderShaderArgument<'static>>,
+    bitmap: RefCell<Option<BitmapInfo>>,
+}
+
 #[derive(Debug, Clone)]
 struct DrawingLine {
     style: LineStyle,
@@ -441,6 +677,23 @@ enum DrawingPath {
     Line(DrawingLine),
 }
 
+impl DrawingPath {
+    fn to_draw_path(&self) -> DrawPath<'_> {
+        match self {
+            DrawingPath::Fill(fill) => DrawPath::Fill {
+                style: &fill.style,
+                commands: fill.commands.to_owned(),
+                winding_rule: FillRule::EvenOdd,
+            },
+            DrawingPath::Line(line) => DrawPath::Stroke {
+                style: &line.style,
+                commands: line.commands.to_owned(),
+                is_closed: line.is_closed,
+            },
+        }
+    }
+}
+
 fn stretch_bounds(
     bounds: &Rectangle<Twips>,
     command: &DrawCommand,
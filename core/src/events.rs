@@ -2,15 +2,17 @@ use crate::display_object::InteractiveObject;
 use serde::Deserialize;
 use swf::ClipEventFlag;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum PlayerEvent {
     KeyDown {
         key_code: KeyCode,
         key_char: Option<char>,
+        key_location: KeyLocation,
     },
     KeyUp {
         key_code: KeyCode,
         key_char: Option<char>,
+        key_location: KeyLocation,
     },
     MouseMove {
         x: f64,
@@ -36,6 +38,123 @@ pub enum PlayerEvent {
     TextControl {
         code: TextControlCode,
     },
+
+    /// The user started composing text with an input method editor (IME).
+    ImeCompositionStart,
+
+    /// The text being composed with an IME changed.
+    ImeCompositionUpdate {
+        /// The text being composed, which isn't final yet.
+        text: String,
+
+        /// The range of `text` that the IME's cursor selects, in UTF-8 bytes.
+        cursor: Option<(usize, usize)>,
+    },
+
+    /// The user finished composing text with an IME.
+    ImeCompositionEnd {
+        /// The text that was composed, which is empty if the composition was cancelled.
+        text: String,
+    },
+
+    /// A finger or stylus touched, moved on, or left a touchscreen.
+    ///
+    /// The frontend also reports the primary touch point as mouse events.
+    Touch {
+        phase: TouchPhase,
+        point: TouchPoint,
+    },
+
+    /// A gamepad was connected.
+    ///
+    /// `id` identifies the gamepad in the other gamepad events, and isn't reused while
+    /// the gamepad is connected.
+    GamepadConnected {
+        id: u32,
+        name: String,
+    },
+    GamepadDisconnected {
+        id: u32,
+    },
+    GamepadButtonDown {
+        id: u32,
+        button: GamepadButton,
+    },
+    GamepadButtonUp {
+        id: u32,
+        button: GamepadButton,
+    },
+
+    /// A stick of a gamepad moved, to a position between -1.0 and 1.0.
+    GamepadAxisChanged {
+        id: u32,
+        axis: GamepadAxis,
+        value: f64,
+    },
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TouchPhase {
+    Begin,
+    Move,
+    End,
+}
+
+/// A point of contact with a touchscreen.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TouchPoint {
+    /// Identifies the point while it's touching.
+    pub id: i32,
+
+    /// The position of the point, in the same coordinates as mouse events.
+    pub x: f64,
+    pub y: f64,
+
+    /// How hard the point is pressed, from 0.0 to 1.0, if the touchscreen can tell.
+    pub pressure: Option<f64>,
+
+    /// The width and height of the area of contact, if the touchscreen can tell.
+    pub size: Option<(f64, f64)>,
+}
+
+/// A button of a gamepad, named after its position in the standard layout.
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum GamepadButton {
+    South,
+    East,
+    West,
+    North,
+    LeftShoulder,
+    RightShoulder,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+}
+
+impl GamepadButton {
+    pub const COUNT: u32 = 16;
+}
+
+/// An axis of a gamepad's sticks. Positive values are right and down.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+}
+
+impl GamepadAxis {
+    pub const COUNT: u32 = 4;
 }
 
 /// The distance scrolled by the mouse wheel.
@@ -457,6 +576,7 @@ pub enum KeyCode {
     F13 = 124,
     F14 = 125,
     F15 = 126,
+    NumLock = 144,
     ScrollLock = 145,
     Semicolon = 186,
     Equals = 187,
@@ -475,6 +595,31 @@ impl KeyCode {
     pub fn from_u8(n: u8) -> Option<Self> {
         num_traits::FromPrimitive::from_u8(n)
     }
+
+    /// The character code that Flash reports for keys that don't type a printable character,
+    /// regardless of the keyboard layout.
+    pub fn control_char(self) -> Option<char> {
+        match self {
+            KeyCode::Backspace => Some('\u{8}'),
+            KeyCode::Tab => Some('\t'),
+            KeyCode::Return | KeyCode::NumpadEnter => Some('\r'),
+            KeyCode::Escape => Some('\u{1b}'),
+            KeyCode::Delete => Some('\u{7f}'),
+            _ => None,
+        }
+    }
+}
+
+/// Where a key is on the keyboard, for keys that appear more than once.
+///
+/// The values match AS3's `flash.ui.KeyLocation`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum KeyLocation {
+    #[default]
+    Standard = 0,
+    Left = 1,
+    Right = 2,
+    NumPad = 3,
 }
 
 /// Subset of `KeyCode` that contains only mouse buttons.
@@ -6,14 +6,19 @@ use crate::avm1::{
     ScriptObject as Avm1ScriptObject,
 };
 use crate::avm2::activation::Activation as Avm2Activation;
+use crate::avm2::amf::enumerable_properties;
 use crate::avm2::object::TObject as _;
 use crate::avm2::Value as Avm2Value;
-use crate::avm2::{ArrayObject as Avm2ArrayObject, Object as Avm2Object};
+use crate::avm2::{ArrayObject as Avm2ArrayObject, Error as Avm2Error, Object as Avm2Object};
 use crate::context::UpdateContext;
 use crate::string::AvmString;
 use gc_arena::Collect;
 use std::collections::BTreeMap;
 
+/// How deeply objects can be nested when they're passed from AVM2, which stops objects
+/// that refer to themselves from being marshalled forever.
+const MAX_AVM2_DEPTH: usize = 256;
+
 /// An intermediate format of representing shared data between ActionScript and elsewhere.
 /// Regardless of the capabilities of both sides, all data will be translated to this potentially
 /// lossy format. Any recursion or additional metadata in ActionScript will not be translated.
@@ -189,35 +194,63 @@ impl Value {
         }
     }
 
-    pub fn from_avm2(value: Avm2Value) -> Value {
-        match value {
+    pub fn from_avm2<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        value: Avm2Value<'gc>,
+    ) -> Result<Value, Avm2Error<'gc>> {
+        Self::from_avm2_nested(activation, value, 0)
+    }
+
+    fn from_avm2_nested<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        value: Avm2Value<'gc>,
+        depth: usize,
+    ) -> Result<Value, Avm2Error<'gc>> {
+        Ok(match value {
             Avm2Value::Undefined => Value::Undefined,
             Avm2Value::Null => Value::Null,
             Avm2Value::Bool(value) => value.into(),
             Avm2Value::Number(value) => value.into(),
             Avm2Value::Integer(value) => value.into(),
             Avm2Value::String(value) => Value::String(value.to_string()),
+            Avm2Value::Object(_) if depth >= MAX_AVM2_DEPTH => {
+                tracing::warn!("ExternalInterface value is nested too deeply");
+                Value::Null
+            }
+            Avm2Value::Object(object) if object.as_executable().is_some() => Value::Null,
             Avm2Value::Object(object) => {
-                if let Some(array) = object.as_array_storage() {
-                    let length = array.length();
-                    let values = (0..length)
-                        .map(|i| {
-                            // FIXME - is this right?
-                            let element = array.get(i).unwrap_or(Avm2Value::Null);
-                            Value::from_avm2(element)
-                        })
-                        .collect();
+                let elements = object.as_array_storage().map(|array| {
+                    (0..array.length())
+                        .map(|i| array.get(i).unwrap_or(Avm2Value::Undefined))
+                        .collect::<Vec<_>>()
+                });
+                if let Some(elements) = elements {
+                    let mut values = Vec::with_capacity(elements.len());
+                    for element in elements {
+                        values.push(Value::from_avm2_nested(activation, element, depth + 1)?);
+                    }
                     Value::List(values)
                 } else {
-                    tracing::warn!("from_avm2 needs to be implemented for Avm2Value::Object");
-                    Value::Null
+                    let mut values = BTreeMap::new();
+                    for (name, value) in enumerable_properties(activation, object)? {
+                        // Methods can't be called from outside, so they're left out.
+                        if matches!(value, Avm2Value::Object(o) if o.as_executable().is_some()) {
+                            continue;
+                        }
+                        let name = name.coerce_to_string(activation)?.to_string();
+                        values.insert(name, Value::from_avm2_nested(activation, value, depth + 1)?);
+                    }
+                    Value::Object(values)
                 }
             }
-        }
+        })
     }
 
-    pub fn into_avm2<'gc>(self, activation: &mut Avm2Activation<'_, 'gc>) -> Avm2Value<'gc> {
-        match self {
+    pub fn into_avm2<'gc>(
+        self,
+        activation: &mut Avm2Activation<'_, 'gc>,
+    ) -> Result<Avm2Value<'gc>, Avm2Error<'gc>> {
+        Ok(match self {
             Value::Undefined => Avm2Value::Undefined,
             Value::Null => Avm2Value::Null,
             Value::Bool(value) => Avm2Value::Bool(value),
@@ -225,19 +258,28 @@ impl Value {
             Value::String(value) => {
                 Avm2Value::String(AvmString::new_utf8(activation.context.gc_context, value))
             }
-            Value::Object(_values) => {
-                tracing::warn!("into_avm2 needs to be implemented for Value::Object");
-                Avm2Value::Undefined
+            Value::Object(values) => {
+                let mut object = activation
+                    .avm2()
+                    .classes()
+                    .object
+                    .construct(activation, &[])?;
+                for (key, value) in values {
+                    let key = AvmString::new_utf8(activation.context.gc_context, key);
+                    let value = value.into_avm2(activation)?;
+                    object.set_public_property(key, value, activation)?;
+                }
+                object.into()
             }
             Value::List(values) => {
                 let storage = values
-                    .iter()
-                    .map(|value| value.to_owned().into_avm2(activation))
-                    .collect();
+                    .into_iter()
+                    .map(|value| value.into_avm2(activation))
+                    .collect::<Result<_, _>>()?;
 
-                Avm2Value::Object(Avm2ArrayObject::from_storage(activation, storage).unwrap())
+                Avm2ArrayObject::from_storage(activation, storage)?.into()
             }
-        }
+        })
     }
 }
 
@@ -260,6 +302,17 @@ impl<'gc> Callback<'gc> {
         name: &str,
         args: impl IntoIterator<Item = Value>,
     ) -> Value {
+        self.try_call(context, name, args).unwrap_or(Value::Null)
+    }
+
+    /// Calls the callback, returning the message of the error that it threw if
+    /// `ExternalInterface.marshallExceptions` is enabled.
+    pub fn try_call(
+        &self,
+        context: &mut UpdateContext<'_, 'gc>,
+        name: &str,
+        args: impl IntoIterator<Item = Value>,
+    ) -> Result<Value, String> {
         match self {
             Callback::Avm1 { this, method } => {
                 if let Some(base_clip) = context.stage.root_clip() {
@@ -278,10 +331,10 @@ impl<'gc> Callback<'gc> {
                         .call(name, &mut activation, this.into(), &args)
                         .and_then(|value| Value::from_avm1(&mut activation, value))
                     {
-                        return result;
+                        return Ok(result);
                     }
                 }
-                Value::Null
+                Ok(Value::Null)
             }
             Callback::Avm2 { method } => {
                 let domain = context
@@ -290,18 +343,23 @@ impl<'gc> Callback<'gc> {
                     .unwrap()
                     .avm2_domain();
                 let mut activation = Avm2Activation::from_domain(context.reborrow(), domain);
-                let args: Vec<Avm2Value> = args
+                let result = args
                     .into_iter()
                     .map(|v| v.into_avm2(&mut activation))
-                    .collect();
-                match method.call(Avm2Value::Null, &args, &mut activation) {
-                    Ok(result) => Value::from_avm2(result),
+                    .collect::<Result<Vec<_>, _>>()
+                    .and_then(|args| method.call(Avm2Value::Null, &args, &mut activation))
+                    .and_then(|result| Value::from_avm2(&mut activation, result));
+                match result {
+                    Ok(result) => Ok(result),
                     Err(e) => {
+                        if activation.context.external_interface.marshall_exceptions() {
+                            return Err(avm2_error_message(&mut activation, e));
+                        }
                         tracing::error!(
                             "Unhandled error in External Interface callback {name}: {:?}",
                             e
                         );
-                        Value::Null
+                        Ok(Value::Null)
                     }
                 }
             }
@@ -309,6 +367,20 @@ impl<'gc> Callback<'gc> {
     }
 }
 
+/// Describes an error thrown by AVM2 code, for when it's passed to the host as an exception.
+fn avm2_error_message<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    error: Avm2Error<'gc>,
+) -> String {
+    match error {
+        Avm2Error::AvmError(value) => value
+            .coerce_to_string(activation)
+            .map(|message| message.to_string())
+            .unwrap_or_else(|_| "Error".to_string()),
+        error => format!("{error:?}"),
+    }
+}
+
 pub trait FsCommandProvider {
     fn on_fs_command(&self, command: &str, args: &str) -> bool;
 }
@@ -328,15 +400,16 @@ pub trait ExternalInterfaceProvider {
 }
 
 pub trait ExternalInterfaceMethod {
-    fn call(&self, context: &mut UpdateContext<'_, '_>, args: &[Value]) -> Value;
+    /// Calls the method, returning the message of the exception that it threw, if any.
+    fn call(&self, context: &mut UpdateContext<'_, '_>, args: &[Value]) -> Result<Value, String>;
 }
 
 impl<F> ExternalInterfaceMethod for F
 where
     F: Fn(&mut UpdateContext<'_, '_>, &[Value]) -> Value,
 {
-    fn call(&self, context: &mut UpdateContext<'_, '_>, args: &[Value]) -> Value {
-        self(context, args)
+    fn call(&self, context: &mut UpdateContext<'_, '_>, args: &[Value]) -> Result<Value, String> {
+        Ok(self(context, args))
     }
 }
 
@@ -348,6 +421,7 @@ pub struct ExternalInterface<'gc> {
     callbacks: BTreeMap<String, Callback<'gc>>,
     #[collect(require_static)]
     fs_commands: Box<dyn FsCommandProvider>,
+    marshall_exceptions: bool,
 }
 
 impl<'gc> ExternalInterface<'gc> {
@@ -359,6 +433,7 @@ impl<'gc> ExternalInterface<'gc> {
             providers,
             callbacks: Default::default(),
             fs_commands,
+            marshall_exceptions: false,
         }
     }
 
@@ -390,6 +465,16 @@ impl<'gc> ExternalInterface<'gc> {
         !self.providers.is_empty()
     }
 
+    /// Whether exceptions are passed between ActionScript and the host,
+    /// as set by `ExternalInterface.marshallExceptions`.
+    pub fn marshall_exceptions(&self) -> bool {
+        self.marshall_exceptions
+    }
+
+    pub fn set_marshall_exceptions(&mut self, marshall_exceptions: bool) {
+        self.marshall_exceptions = marshall_exceptions;
+    }
+
     pub fn invoke_fs_command(&self, command: &str, args: &str) -> bool {
         self.fs_commands.on_fs_command(command, args)
     }
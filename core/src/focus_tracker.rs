@@ -1,22 +1,48 @@
 use crate::avm1::Avm1;
-use crate::avm1::Value;
-use crate::context::UpdateContext;
+use crate::avm1::{Activation as Avm1Activation, ActivationIdentifier, TObject as _, Value};
+use crate::avm2::{Activation as Avm2Activation, Avm2, TObject as _, Value as Avm2Value};
+use crate::context::{RenderContext, UpdateContext};
+use crate::display_object::TInteractiveObject;
 pub use crate::display_object::{
     DisplayObject, TDisplayObject, TDisplayObjectContainer, TextSelection,
 };
 use gc_arena::{Collect, GcCell, MutationContext};
+use ruffle_render::commands::CommandHandler;
+use ruffle_render::matrix::Matrix;
+use swf::{Color, Twips};
+
+/// The color of the rectangle drawn around objects that are focused with the keyboard.
+const HIGHLIGHT_COLOR: Color = Color::from_rgb(0xFFFF00, 255);
+
+/// The width of the highlight, in pixels.
+const HIGHLIGHT_THICKNESS: f64 = 2.0;
 
 #[derive(Clone, Copy, Collect)]
 #[collect(no_drop)]
-pub struct FocusTracker<'gc>(GcCell<'gc, Option<DisplayObject<'gc>>>);
+pub struct FocusTracker<'gc>(GcCell<'gc, FocusTrackerData<'gc>>);
+
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+struct FocusTrackerData<'gc> {
+    focus: Option<DisplayObject<'gc>>,
+
+    /// Whether the focus was moved with the keyboard, which draws a rectangle around it.
+    highlight: bool,
+}
 
 impl<'gc> FocusTracker<'gc> {
     pub fn new(gc_context: MutationContext<'gc, '_>) -> Self {
-        Self(GcCell::new(gc_context, None))
+        Self(GcCell::new(
+            gc_context,
+            FocusTrackerData {
+                focus: None,
+                highlight: false,
+            },
+        ))
     }
 
     pub fn get(&self) -> Option<DisplayObject<'gc>> {
-        *self.0.read()
+        self.0.read().focus
     }
 
     pub fn set(
@@ -24,7 +50,14 @@ impl<'gc> FocusTracker<'gc> {
         focused_element: Option<DisplayObject<'gc>>,
         context: &mut UpdateContext<'_, 'gc>,
     ) {
-        let old = std::mem::replace(&mut *self.0.write(context.gc_context), focused_element);
+        let old = {
+            let mut data = self.0.write(context.gc_context);
+            data.highlight = false;
+            std::mem::replace(&mut data.focus, focused_element)
+        };
+
+        // The focus highlight and text caret are drawn on top of the stage.
+        *context.needs_render = true;
 
         if old.is_none() && focused_element.is_none() {
             // We didn't have anything, we still don't, no change.
@@ -42,6 +75,15 @@ impl<'gc> FocusTracker<'gc> {
 
             tracing::info!("Focus is now on {:?}", focused_element);
 
+            if context.is_action_script_3() {
+                if let Some(old) = old {
+                    Self::dispatch_focus_event(context, "focusOut", old, focused_element);
+                }
+                if let Some(new) = focused_element {
+                    Self::dispatch_focus_event(context, "focusIn", new, old);
+                }
+            }
+
             if let Some(level0) = context.stage.root_clip() {
                 Avm1::notify_system_listeners(
                     level0,
@@ -70,4 +112,201 @@ impl<'gc> FocusTracker<'gc> {
             }
         }
     }
+
+    /// Moves the focus to the next object in the tab order, or the previous one if `reverse`
+    /// is set, as pressing Tab does.
+    ///
+    /// In AVM2, this dispatches a `keyFocusChange` event first, which can cancel the change.
+    pub fn cycle(&self, context: &mut UpdateContext<'_, 'gc>, reverse: bool) {
+        let order = tab_order(context);
+        if order.is_empty() {
+            return;
+        }
+
+        let current = self
+            .get()
+            .and_then(|focus| order.iter().position(|o| DisplayObject::ptr_eq(*o, focus)));
+        let next = match (current, reverse) {
+            (Some(index), false) => order[(index + 1) % order.len()],
+            (Some(index), true) => order[(index + order.len() - 1) % order.len()],
+            (None, false) => order[0],
+            (None, true) => order[order.len() - 1],
+        };
+
+        if context.is_action_script_3() {
+            let target = self.get().unwrap_or_else(|| context.stage.into());
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let focus_event = activation
+                .avm2()
+                .classes()
+                .focusevent
+                .construct(
+                    &mut activation,
+                    &[
+                        "keyFocusChange".into(), /* type */
+                        true.into(),             /* bubbles */
+                        true.into(),             /* cancelable */
+                        next.object2(),          /* relatedObject */
+                        reverse.into(),          /* shiftKey */
+                        9.into(),                /* keyCode */
+                    ],
+                )
+                .expect("Failed to construct FocusEvent");
+            if let Avm2Value::Object(target) = target.object2() {
+                Avm2::dispatch_event(&mut activation.context, focus_event, target);
+                if focus_event.as_event().unwrap().is_cancelled() {
+                    return;
+                }
+            }
+        }
+
+        self.set(Some(next), context);
+        self.0.write(context.gc_context).highlight = true;
+    }
+
+    /// Draws a rectangle around the focused object, if it was focused with the keyboard.
+    pub fn render_highlight(&self, context: &mut RenderContext<'_, 'gc>) {
+        let data = self.0.read();
+        let Some(focus) = data.focus.filter(|_| data.highlight) else {
+            return;
+        };
+        let show_rect = focus
+            .as_interactive()
+            .and_then(|int| int.focus_rect())
+            .unwrap_or_else(|| context.stage.stage_focus_rect());
+        if !show_rect {
+            return;
+        }
+
+        let bounds = context.stage.view_matrix() * focus.world_bounds();
+        let width = bounds.width().to_pixels() as f32;
+        let height = bounds.height().to_pixels() as f32;
+        let thickness = Twips::from_pixels(HIGHLIGHT_THICKNESS);
+        let size = HIGHLIGHT_THICKNESS as f32;
+        let edges = [
+            (
+                width + size * 2.0,
+                size,
+                bounds.x_min - thickness,
+                bounds.y_min - thickness,
+            ),
+            (
+                width + size * 2.0,
+                size,
+                bounds.x_min - thickness,
+                bounds.y_max,
+            ),
+            (size, height, bounds.x_min - thickness, bounds.y_min),
+            (size, height, bounds.x_max, bounds.y_min),
+        ];
+        for (width, height, x, y) in edges {
+            context.commands.draw_rect(
+                HIGHLIGHT_COLOR,
+                Matrix::create_box(width, height, 0.0, x, y),
+            );
+        }
+    }
+
+    fn dispatch_focus_event(
+        context: &mut UpdateContext<'_, 'gc>,
+        event_type: &'static str,
+        target: DisplayObject<'gc>,
+        related_object: Option<DisplayObject<'gc>>,
+    ) {
+        let Avm2Value::Object(target) = target.object2() else {
+            return;
+        };
+        let related_object = related_object.map_or(Avm2Value::Null, |o| o.object2());
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let focus_event = activation
+            .avm2()
+            .classes()
+            .focusevent
+            .construct(
+                &mut activation,
+                &[
+                    event_type.into(), /* type */
+                    true.into(),       /* bubbles */
+                    false.into(),      /* cancelable */
+                    related_object,    /* relatedObject */
+                ],
+            )
+            .expect("Failed to construct FocusEvent");
+        Avm2::dispatch_event(&mut activation.context, focus_event, target);
+    }
+}
+
+/// The objects that pressing Tab moves the focus between, in order.
+///
+/// If any object has a `tabIndex`, only objects that have one are in the order, sorted by it.
+/// Otherwise, objects are ordered by their position on the stage, from top to bottom and then
+/// from left to right.
+fn tab_order<'gc>(context: &mut UpdateContext<'_, 'gc>) -> Vec<DisplayObject<'gc>> {
+    let mut objects = vec![];
+    let stage = context.stage;
+    for child in stage.iter_render_list() {
+        add_tab_objects(context, child, &mut objects);
+    }
+
+    if objects.iter().any(|(_, index)| index.is_some()) {
+        let mut objects: Vec<_> = objects
+            .into_iter()
+            .filter_map(|(object, index)| Some((object, index?)))
+            .collect();
+        objects.sort_by_key(|(_, index)| *index);
+        objects.into_iter().map(|(object, _)| object).collect()
+    } else {
+        let mut objects: Vec<_> = objects.into_iter().map(|(object, _)| object).collect();
+        objects.sort_by_key(|object| {
+            let bounds = object.world_bounds();
+            (bounds.y_min, bounds.x_min)
+        });
+        objects
+    }
+}
+
+/// Adds an object and its descendants that can be focused with Tab, along with their
+/// `tabIndex`.
+fn add_tab_objects<'gc>(
+    context: &mut UpdateContext<'_, 'gc>,
+    object: DisplayObject<'gc>,
+    objects: &mut Vec<(DisplayObject<'gc>, Option<i32>)>,
+) {
+    if !object.visible() {
+        return;
+    }
+
+    if let Some(int) = object.as_interactive() {
+        if int.tab_enabled(context) {
+            objects.push((object, int.tab_index(context)));
+        }
+    }
+
+    if let Some(container) = object.as_container() {
+        if tab_children(context, object) {
+            for child in container.iter_render_list() {
+                add_tab_objects(context, child, objects);
+            }
+        }
+    }
+}
+
+/// Whether pressing Tab can focus the children of a container.
+///
+/// AVM1 containers use their `tabChildren` property.
+fn tab_children<'gc>(context: &mut UpdateContext<'_, 'gc>, object: DisplayObject<'gc>) -> bool {
+    if let Value::Object(avm1_object) = object.object() {
+        let mut activation = Avm1Activation::from_stub(
+            context.reborrow(),
+            ActivationIdentifier::root("[Tab Children]"),
+        );
+        match avm1_object.get("tabChildren", &mut activation) {
+            Ok(Value::Undefined) | Err(_) => true,
+            Ok(value) => value.as_bool(activation.swf_version()),
+        }
+    } else {
+        object
+            .as_container()
+            .map_or(true, |container| container.raw_container().tab_children())
+    }
 }
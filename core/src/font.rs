@@ -7,7 +7,12 @@ use ruffle_render::backend::{RenderBackend, ShapeHandle};
 use ruffle_render::transform::Transform;
 use std::cell::{Ref, RefCell};
 use std::cmp::max;
+use std::rc::Rc;
 
+mod device;
+mod shaping;
+
+pub use device::{DeviceFonts, FontFace};
 pub use swf::TextGridFit;
 
 /// Certain Flash routines measure text by rounding down to the nearest whole pixel.
@@ -68,13 +73,12 @@ pub struct Font<'gc>(Gc<'gc, FontData>);
 #[derive(Debug, Clone, Collect)]
 #[collect(require_static)]
 struct FontData {
-    /// The list of glyphs defined in the font.
-    /// Used directly by `DefineText` tags.
-    glyphs: Vec<Glyph>,
+    /// The glyphs of the font.
+    glyphs: GlyphSource,
 
-    /// A map from a Unicode code point to glyph in the `glyphs` array.
-    /// Used by `DefineEditText` tags.
-    code_point_to_glyph: fnv::FnvHashMap<u16, usize>,
+    /// Fonts installed on the system that are used for characters missing from this font,
+    /// in order of preference. Only used by device fonts.
+    fallbacks: Vec<Rc<FontFace>>,
 
     /// The scaling applied to the font height to render at the proper size.
     /// This depends on the DefineFont tag version.
@@ -100,6 +104,23 @@ struct FontData {
     descriptor: FontDescriptor,
 }
 
+#[derive(Debug, Clone)]
+enum GlyphSource {
+    /// Glyphs defined by a SWF font tag.
+    Memory {
+        /// The list of glyphs defined in the font.
+        /// Used directly by `DefineText` tags.
+        glyphs: Vec<Glyph>,
+
+        /// A map from a Unicode code point to glyph in the `glyphs` array.
+        /// Used by `DefineEditText` tags.
+        code_point_to_glyph: fnv::FnvHashMap<u16, usize>,
+    },
+
+    /// Glyphs loaded from a font installed on the system.
+    FontFace(Rc<FontFace>),
+}
+
 impl<'gc> Font<'gc> {
     pub fn from_swf_tag(
         gc_context: MutationContext<'gc, '_>,
@@ -152,8 +173,11 @@ impl<'gc> Font<'gc> {
         Font(Gc::new(
             gc_context,
             FontData {
-                glyphs,
-                code_point_to_glyph,
+                glyphs: GlyphSource::Memory {
+                    glyphs,
+                    code_point_to_glyph,
+                },
+                fallbacks: Vec::new(),
 
                 /// DefineFont3 stores coordinates at 20x the scale of DefineFont1/2.
                 /// (SWF19 p.164)
@@ -167,27 +191,96 @@ impl<'gc> Font<'gc> {
         ))
     }
 
+    /// Creates a font from an OpenType font file, such as a font installed on the system
+    /// or a font embedded with a `DefineFont4` tag.
+    pub fn from_font_face(
+        gc_context: MutationContext<'gc, '_>,
+        face: Rc<FontFace>,
+        fallbacks: Vec<Rc<FontFace>>,
+    ) -> Font<'gc> {
+        Font(Gc::new(
+            gc_context,
+            FontData {
+                scale: device::EM_SQUARE,
+                kerning_pairs: fnv::FnvHashMap::default(),
+                ascent: face.ascent(),
+                descent: face.descent(),
+                leading: face.leading(),
+                descriptor: face.descriptor().clone(),
+                glyphs: GlyphSource::FontFace(face),
+                fallbacks,
+            },
+        ))
+    }
+
+    /// Returns a copy of this font that draws characters it doesn't have with the given fonts.
+    pub fn with_fallbacks(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        fallbacks: Vec<Rc<FontFace>>,
+    ) -> Font<'gc> {
+        Font(Gc::new(
+            gc_context,
+            FontData {
+                fallbacks,
+                ..(*self.0).clone()
+            },
+        ))
+    }
+
     /// Returns whether this font contains glyph shapes.
     /// If not, this font should be rendered as a device font.
     pub fn has_glyphs(&self) -> bool {
-        !self.0.glyphs.is_empty()
+        match &self.0.glyphs {
+            GlyphSource::Memory { glyphs, .. } => !glyphs.is_empty(),
+            GlyphSource::FontFace(_) => true,
+        }
+    }
+
+    /// Returns whether this font's glyphs are read from an OpenType font file,
+    /// rather than from glyph shapes in a SWF font tag.
+    pub fn has_font_face(&self) -> bool {
+        matches!(self.0.glyphs, GlyphSource::FontFace(_))
     }
 
     /// Returns a glyph entry by index.
     /// Used by `Text` display objects.
     pub fn get_glyph(&self, i: usize) -> Option<&Glyph> {
-        self.0.glyphs.get(i)
+        match &self.0.glyphs {
+            GlyphSource::Memory { glyphs, .. } => glyphs.get(i),
+            // Fonts from the system are never referenced by `DefineText` tags.
+            GlyphSource::FontFace(_) => None,
+        }
     }
 
-    /// Returns a glyph entry by character.
+    /// Returns a glyph entry by character, falling back to other fonts if this font
+    /// doesn't have it.
     /// Used by `EditText` display objects.
     pub fn get_glyph_for_char(&self, c: char) -> Option<&Glyph> {
-        // TODO: Properly handle UTF-16/out-of-bounds code points.
-        let code_point = c as u16;
-        if let Some(index) = self.0.code_point_to_glyph.get(&code_point) {
-            self.get_glyph(*index)
-        } else {
-            None
+        self.get_own_glyph_for_char(c).or_else(|| {
+            self.0
+                .fallbacks
+                .iter()
+                .find_map(|fallback| fallback.get_glyph(c))
+        })
+    }
+
+    /// Returns a glyph entry by character, without considering fallback fonts.
+    fn get_own_glyph_for_char(&self, c: char) -> Option<&Glyph> {
+        match &self.0.glyphs {
+            GlyphSource::Memory {
+                code_point_to_glyph,
+                ..
+            } => {
+                // TODO: Properly handle UTF-16/out-of-bounds code points.
+                let code_point = c as u16;
+                if let Some(index) = code_point_to_glyph.get(&code_point) {
+                    self.get_glyph(*index)
+                } else {
+                    None
+                }
+            }
+            GlyphSource::FontFace(face) => face.get_glyph(c),
         }
     }
 
@@ -195,7 +288,7 @@ impl<'gc> Font<'gc> {
     pub fn has_glyphs_for_str(&self, target_str: &WStr) -> bool {
         for character in target_str.chars() {
             let c = character.unwrap_or(char::REPLACEMENT_CHARACTER);
-            if self.get_glyph_for_char(c).is_none() {
+            if self.get_own_glyph_for_char(c).is_none() {
                 return false;
             }
         }
@@ -207,6 +300,10 @@ impl<'gc> Font<'gc> {
     /// to the advance value between these two characters.
     /// Returns 0 twips if no kerning offset exists between these two characters.
     pub fn get_kerning_offset(&self, left: char, right: char) -> Twips {
+        if let GlyphSource::FontFace(face) = &self.0.glyphs {
+            return face.get_kerning_offset(left, right);
+        }
+
         // TODO: Properly handle UTF-16/out-of-bounds code points.
         let left_code_point = left as u16;
         let right_code_point = right as u16;
@@ -240,7 +337,10 @@ impl<'gc> Font<'gc> {
 
     /// Returns whether this font contains kerning information.
     pub fn has_kerning_info(&self) -> bool {
-        !self.0.kerning_pairs.is_empty()
+        match &self.0.glyphs {
+            GlyphSource::Memory { .. } => !self.0.kerning_pairs.is_empty(),
+            GlyphSource::FontFace(face) => face.has_kerning_info(),
+        }
     }
 
     pub fn scale(&self) -> f32 {
@@ -269,27 +369,26 @@ impl<'gc> Font<'gc> {
 
         transform.matrix.a = scale;
         transform.matrix.d = scale;
-        let mut char_indices = text.char_indices().peekable();
-        let has_kerning_info = self.has_kerning_info();
+        let to_twips = |value: Twips| Twips::new((value.get() as f32 * scale) as i32);
         let mut x = Twips::ZERO;
-        while let Some((pos, c)) = char_indices.next() {
-            let c = c.unwrap_or(char::REPLACEMENT_CHARACTER);
-            if let Some(glyph) = self.get_glyph_for_char(c) {
-                let mut advance = Twips::new(glyph.swf_glyph.advance.into());
-                if has_kerning_info && params.kerning {
-                    let next_char = char_indices.peek().cloned().unwrap_or((0, Ok('\0'))).1;
-                    let next_char = next_char.unwrap_or(char::REPLACEMENT_CHARACTER);
-                    advance += self.get_kerning_offset(c, next_char);
-                }
-                let twips_advance =
-                    Twips::new((advance.get() as f32 * scale) as i32) + params.letter_spacing;
-
-                glyph_func(pos, &transform, glyph, twips_advance, x);
+        for shaped in self.shape(text, params.kerning) {
+            let mut twips_advance = to_twips(shaped.advance);
+            if shaped.is_spacing {
+                twips_advance += params.letter_spacing;
+            }
 
-                // Step horizontally.
-                transform.matrix.tx += twips_advance;
-                x += twips_advance;
+            if shaped.offset == Point::ZERO {
+                glyph_func(shaped.pos, &transform, shaped.glyph, twips_advance, x);
+            } else {
+                let mut glyph_transform = transform.clone();
+                glyph_transform.matrix.tx += to_twips(shaped.offset.x);
+                glyph_transform.matrix.ty += to_twips(shaped.offset.y);
+                glyph_func(shaped.pos, &glyph_transform, shaped.glyph, twips_advance, x);
             }
+
+            // Step horizontally.
+            transform.matrix.tx += twips_advance;
+            x += twips_advance;
         }
     }
 
@@ -0,0 +1,610 @@
+//! Fonts installed on the system, used to render text with device fonts.
+//!
+//! A device font is looked up by name through the `UiBackend`. Characters that it doesn't
+//! have are drawn with a chain of fallback fonts covering other scripts, such as CJK, Arabic
+//! and emoji. If no installed font matches the name, Ruffle's built-in Noto Sans is used,
+//! with the same fallbacks.
+
+use crate::backend::ui::{FontDefinition, LanguageIdentifier, UiBackend};
+use crate::font::shaping::ShapedGlyph;
+use crate::font::{FontDescriptor, Glyph};
+use crate::prelude::*;
+use fnv::FnvHashMap;
+use ouroboros::self_referencing;
+use std::cell::OnceCell;
+use std::fmt;
+use std::rc::Rc;
+use swf::{Point, ShapeRecord, StyleChangeData};
+
+/// The size of the EM square of fonts loaded from the system, in glyph units.
+///
+/// Glyphs are converted to the same units as DefineFont3 glyphs, so that fonts can fall back
+/// to one another (and to the built-in device font) within a single line of text.
+pub const EM_SQUARE: f32 = 20480.0;
+
+/// The generic font names that Flash maps to a font installed on the system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DefaultFont {
+    /// `_sans`, or `_ゴシック` in Japanese.
+    Sans,
+
+    /// `_serif`, or `_明朝` in Japanese.
+    Serif,
+
+    /// `_typewriter`, or `_等幅` in Japanese.
+    Typewriter,
+}
+
+impl DefaultFont {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "_sans" | "_ゴシック" => Some(Self::Sans),
+            "_serif" | "_明朝" => Some(Self::Serif),
+            "_typewriter" | "_等幅" => Some(Self::Typewriter),
+            _ => None,
+        }
+    }
+
+    /// The fonts used for this generic name, in order of preference.
+    /// Most platforms have at least one of them installed.
+    fn candidates(self) -> &'static [&'static str] {
+        match self {
+            Self::Sans => &[
+                "Arial",
+                "Helvetica",
+                "Liberation Sans",
+                "Arimo",
+                "DejaVu Sans",
+                "Noto Sans",
+            ],
+            Self::Serif => &[
+                "Times New Roman",
+                "Times",
+                "Liberation Serif",
+                "Tinos",
+                "DejaVu Serif",
+                "Noto Serif",
+            ],
+            Self::Typewriter => &[
+                "Courier New",
+                "Courier",
+                "Liberation Mono",
+                "Cousine",
+                "DejaVu Sans Mono",
+                "Noto Sans Mono",
+            ],
+        }
+    }
+}
+
+const JAPANESE_FONTS: &[&str] = &[
+    "Yu Gothic",
+    "MS PGothic",
+    "Meiryo",
+    "Hiragino Sans",
+    "Hiragino Kaku Gothic ProN",
+    "Noto Sans CJK JP",
+    "Noto Sans JP",
+    "IPAGothic",
+];
+
+const SIMPLIFIED_CHINESE_FONTS: &[&str] = &[
+    "Microsoft YaHei",
+    "SimSun",
+    "PingFang SC",
+    "Hiragino Sans GB",
+    "Noto Sans CJK SC",
+    "Noto Sans SC",
+    "WenQuanYi Micro Hei",
+];
+
+const TRADITIONAL_CHINESE_FONTS: &[&str] = &[
+    "Microsoft JhengHei",
+    "PMingLiU",
+    "PingFang TC",
+    "Noto Sans CJK TC",
+    "Noto Sans TC",
+];
+
+const KOREAN_FONTS: &[&str] = &[
+    "Malgun Gothic",
+    "Gulim",
+    "Apple SD Gothic Neo",
+    "Noto Sans CJK KR",
+    "Noto Sans KR",
+    "NanumGothic",
+];
+
+/// Fonts covering scripts that are commonly missing from Latin fonts, after the CJK fonts.
+/// Each list holds alternatives for different platforms; the first one installed is used.
+const SCRIPT_FALLBACK_FONTS: &[&[&str]] = &[
+    &["Segoe UI", "Tahoma", "Geeza Pro", "Noto Sans Arabic"],
+    &["Arial Hebrew", "Noto Sans Hebrew"],
+    &["Leelawadee UI", "Thonburi", "Noto Sans Thai"],
+    &[
+        "Nirmala UI",
+        "Mangal",
+        "Kohinoor Devanagari",
+        "Noto Sans Devanagari",
+    ],
+    &[
+        "Segoe UI Emoji",
+        "Segoe UI Symbol",
+        "Apple Symbols",
+        "Noto Emoji",
+        "Symbola",
+        "Noto Color Emoji",
+    ],
+    &["Arial Unicode MS", "DejaVu Sans", "Noto Sans"],
+];
+
+/// The fallback fonts to try for characters missing from a device font, in order.
+///
+/// The same Han characters are drawn differently in Chinese, Japanese and Korean, so the CJK
+/// fonts for the user's language are preferred.
+fn fallback_font_names(language: &LanguageIdentifier) -> Vec<&'static [&'static str]> {
+    let cjk = match language.language.as_str() {
+        "ko" => [
+            KOREAN_FONTS,
+            JAPANESE_FONTS,
+            SIMPLIFIED_CHINESE_FONTS,
+            TRADITIONAL_CHINESE_FONTS,
+        ],
+        "zh" => match language.to_string().as_str() {
+            "zh-TW" | "zh-HK" | "zh-MO" | "zh-Hant" => [
+                TRADITIONAL_CHINESE_FONTS,
+                SIMPLIFIED_CHINESE_FONTS,
+                JAPANESE_FONTS,
+                KOREAN_FONTS,
+            ],
+            _ => [
+                SIMPLIFIED_CHINESE_FONTS,
+                TRADITIONAL_CHINESE_FONTS,
+                JAPANESE_FONTS,
+                KOREAN_FONTS,
+            ],
+        },
+        _ => [
+            JAPANESE_FONTS,
+            SIMPLIFIED_CHINESE_FONTS,
+            TRADITIONAL_CHINESE_FONTS,
+            KOREAN_FONTS,
+        ],
+    };
+    cjk.into_iter()
+        .chain(SCRIPT_FALLBACK_FONTS.iter().copied())
+        .collect()
+}
+
+#[self_referencing]
+struct FaceData {
+    data: Vec<u8>,
+
+    #[borrows(data)]
+    #[covariant]
+    face: rustybuzz::Face<'this>,
+}
+
+/// An OpenType font file, whose glyphs are converted into shapes on first use.
+///
+/// This is either a font installed on the system, or the CFF font data of a `DefineFont4` tag.
+/// Both TrueType and CFF outlines are supported.
+pub struct FontFace {
+    face: FaceData,
+
+    /// The glyphs converted so far, indexed by glyph ID.
+    glyphs: Vec<OnceCell<Glyph>>,
+
+    /// The scale from font units to `EM_SQUARE` units.
+    scale: f32,
+
+    descriptor: FontDescriptor,
+}
+
+impl FontFace {
+    pub fn new(definition: FontDefinition) -> Result<Self, ttf_parser::FaceParsingError> {
+        let index = definition.index;
+        let face = FaceData::try_new(definition.data, |data| {
+            ttf_parser::Face::parse(data, index).map(rustybuzz::Face::from_face)
+        })?;
+
+        let (num_glyphs, units_per_em) = {
+            let face = face.borrow_face();
+            (face.number_of_glyphs(), face.units_per_em())
+        };
+
+        Ok(Self {
+            face,
+            glyphs: (0..num_glyphs).map(|_| OnceCell::new()).collect(),
+            scale: EM_SQUARE / f32::from(units_per_em),
+            descriptor: FontDescriptor::from_parts(
+                &definition.name,
+                definition.is_bold,
+                definition.is_italic,
+            ),
+        })
+    }
+
+    pub fn descriptor(&self) -> &FontDescriptor {
+        &self.descriptor
+    }
+
+    /// The distance from the top of each glyph to the baseline, in `EM_SQUARE` units.
+    pub fn ascent(&self) -> u16 {
+        (f32::from(self.face.borrow_face().ascender()) * self.scale) as u16
+    }
+
+    /// The distance from the baseline to the bottom of each glyph, in `EM_SQUARE` units.
+    pub fn descent(&self) -> u16 {
+        (-f32::from(self.face.borrow_face().descender()) * self.scale) as u16
+    }
+
+    /// The distance between the bottom of one line and the top of the next, in `EM_SQUARE`
+    /// units.
+    pub fn leading(&self) -> i16 {
+        (f32::from(self.face.borrow_face().line_gap()) * self.scale) as i16
+    }
+
+    /// Returns the glyph for a character, converting it into a shape if this is its first use.
+    pub fn get_glyph(&self, c: char) -> Option<&Glyph> {
+        let glyph_id = self.face.borrow_face().glyph_index(c)?;
+        self.get_glyph_by_id(glyph_id, c)
+    }
+
+    /// Returns the glyph with the given ID, which was made from the given character.
+    fn get_glyph_by_id(&self, glyph_id: ttf_parser::GlyphId, c: char) -> Option<&Glyph> {
+        let face = self.face.borrow_face();
+        let glyph = self.glyphs.get(usize::from(glyph_id.0))?;
+        Some(glyph.get_or_init(|| {
+            let mut builder = ShapeRecordBuilder::new(self.scale);
+            face.outline_glyph(glyph_id, &mut builder);
+            let advance = face.glyph_hor_advance(glyph_id).unwrap_or_default();
+            Glyph {
+                shape_handle: None.into(),
+                shape: None.into(),
+                swf_glyph: swf::Glyph {
+                    shape_records: builder.records,
+                    code: c as u16,
+                    // Like DefineFont3, advances are limited to 16 bits.
+                    advance: (f32::from(advance) * self.scale) as i16,
+                    bounds: None,
+                },
+            }
+        }))
+    }
+
+    /// Returns the kerning adjustment between two characters, from the font's `kern` table.
+    pub fn get_kerning_offset(&self, left: char, right: char) -> Twips {
+        let face = self.face.borrow_face();
+        let (Some(left), Some(right)) = (face.glyph_index(left), face.glyph_index(right)) else {
+            return Twips::ZERO;
+        };
+        let adjustment = face.tables().kern.and_then(|kern| {
+            kern.subtables
+                .into_iter()
+                .filter(|subtable| subtable.horizontal && !subtable.variable)
+                .find_map(|subtable| subtable.glyphs_kerning(left, right))
+        });
+        Twips::new((f32::from(adjustment.unwrap_or_default()) * self.scale) as i32)
+    }
+
+    pub fn has_kerning_info(&self) -> bool {
+        self.face.borrow_face().tables().kern.is_some()
+    }
+
+    /// Shapes a run of characters with this font, appending its glyphs to `glyphs` in visual
+    /// order.
+    ///
+    /// Each character is given with its position in the text being evaluated. The whole run
+    /// must have the same direction.
+    pub fn shape<'a>(
+        &'a self,
+        chars: &[(usize, char)],
+        is_rtl: bool,
+        kerning: bool,
+        glyphs: &mut Vec<ShapedGlyph<'a>>,
+    ) {
+        let text: String = chars.iter().map(|(_, c)| c).collect();
+        let mut buffer = rustybuzz::UnicodeBuffer::new();
+        buffer.push_str(&text);
+        buffer.set_direction(if is_rtl {
+            rustybuzz::Direction::RightToLeft
+        } else {
+            rustybuzz::Direction::LeftToRight
+        });
+        buffer.guess_segment_properties();
+
+        let features = if kerning {
+            vec![]
+        } else {
+            vec![rustybuzz::Feature::new(
+                ttf_parser::Tag::from_bytes(b"kern"),
+                0,
+                ..,
+            )]
+        };
+        let output = rustybuzz::shape(self.face.borrow_face(), &features, buffer);
+
+        // Clusters are byte offsets into `text`.
+        let char_offsets: Vec<usize> = text.char_indices().map(|(offset, _)| offset).collect();
+        let to_units = |value: i32| Twips::new((value as f32 * self.scale) as i32);
+        for (info, position) in output.glyph_infos().iter().zip(output.glyph_positions()) {
+            // Characters that this font doesn't have are shaped into glyph 0.
+            if info.glyph_id == 0 {
+                continue;
+            }
+            let index = match char_offsets.binary_search(&(info.cluster as usize)) {
+                Ok(index) => index,
+                Err(index) => index.saturating_sub(1),
+            };
+            let (pos, c) = chars[index];
+            if let Some(glyph) = self.get_glyph_by_id(ttf_parser::GlyphId(info.glyph_id as u16), c)
+            {
+                glyphs.push(ShapedGlyph {
+                    pos,
+                    glyph,
+                    advance: to_units(position.x_advance),
+                    // Font units point Y upwards, while glyph shapes point it downwards.
+                    offset: Point::new(to_units(position.x_offset), -to_units(position.y_offset)),
+                    is_spacing: position.x_advance != 0,
+                });
+            }
+        }
+    }
+}
+
+impl fmt::Debug for FontFace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FontFace")
+            .field("descriptor", &self.descriptor)
+            .finish()
+    }
+}
+
+/// Converts glyph outlines from a font file into SWF shape records.
+struct ShapeRecordBuilder {
+    records: Vec<ShapeRecord>,
+
+    /// The scale from font units to shape coordinates.
+    scale: f32,
+
+    /// The current point, in font units.
+    cursor: (f32, f32),
+
+    /// The current point, in shape coordinates.
+    position: Point<Twips>,
+
+    /// The start of the current contour, in shape coordinates.
+    contour_start: Point<Twips>,
+}
+
+impl ShapeRecordBuilder {
+    fn new(scale: f32) -> Self {
+        Self {
+            records: Vec::new(),
+            scale,
+            cursor: (0.0, 0.0),
+            position: Point::ZERO,
+            contour_start: Point::ZERO,
+        }
+    }
+
+    fn shape_point(&self, x: f32, y: f32) -> Point<Twips> {
+        // Font outlines point Y upwards, while shapes point it downwards.
+        Point::new(
+            Twips::new((x * self.scale).round() as i32),
+            Twips::new((-y * self.scale).round() as i32),
+        )
+    }
+}
+
+impl ttf_parser::OutlineBuilder for ShapeRecordBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let point = self.shape_point(x, y);
+        self.records
+            .push(ShapeRecord::StyleChange(Box::new(StyleChangeData {
+                move_to: Some(point),
+                fill_style_0: None,
+                fill_style_1: self.records.is_empty().then_some(1),
+                line_style: None,
+                new_styles: None,
+            })));
+        self.cursor = (x, y);
+        self.position = point;
+        self.contour_start = point;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let point = self.shape_point(x, y);
+        self.records.push(ShapeRecord::StraightEdge {
+            delta: point - self.position,
+        });
+        self.cursor = (x, y);
+        self.position = point;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let control = self.shape_point(x1, y1);
+        let anchor = self.shape_point(x, y);
+        self.records.push(ShapeRecord::CurvedEdge {
+            control_delta: control - self.position,
+            anchor_delta: anchor - control,
+        });
+        self.cursor = (x, y);
+        self.position = anchor;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        // Shapes only have quadratic curves, so split the cubic curve in half and approximate
+        // each half with a quadratic curve.
+        let (x0, y0) = self.cursor;
+        let mid = |a: f32, b: f32| (a + b) / 2.0;
+        let (ax, ay) = (mid(x0, x1), mid(y0, y1));
+        let (bx, by) = (mid(x1, x2), mid(y1, y2));
+        let (cx, cy) = (mid(x2, x), mid(y2, y));
+        let (dx, dy) = (mid(ax, bx), mid(ay, by));
+        let (ex, ey) = (mid(bx, cx), mid(by, cy));
+        let (mx, my) = (mid(dx, ex), mid(dy, ey));
+        let control = |p0: f32, p1: f32, p2: f32, p3: f32| (3.0 * (p1 + p2) - p0 - p3) / 4.0;
+        self.quad_to(control(x0, ax, dx, mx), control(y0, ay, dy, my), mx, my);
+        self.quad_to(control(mx, ex, cx, x), control(my, ey, cy, y), x, y);
+    }
+
+    fn close(&mut self) {
+        if self.position != self.contour_start {
+            self.records.push(ShapeRecord::StraightEdge {
+                delta: self.contour_start - self.position,
+            });
+            self.position = self.contour_start;
+        }
+    }
+}
+
+/// The fonts that have been loaded from the system.
+#[derive(Default)]
+pub struct DeviceFonts {
+    /// Every font that has been loaded, by the name and style it was requested with.
+    /// Fonts that couldn't be parsed are remembered as `None`, so that they aren't loaded again.
+    faces: FnvHashMap<FontDescriptor, Option<Rc<FontFace>>>,
+}
+
+impl DeviceFonts {
+    fn face(
+        &mut self,
+        ui: &dyn UiBackend,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Rc<FontFace>> {
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+        if let Some(face) = self.faces.get(&descriptor) {
+            return face.clone();
+        }
+
+        // Fonts that aren't installed aren't remembered, as the backend may still be
+        // loading them.
+        let definition = ui.load_device_font(name, is_bold, is_italic)?;
+        let face = match FontFace::new(definition) {
+            Ok(face) => Some(Rc::new(face)),
+            Err(e) => {
+                tracing::warn!("Failed to load device font {name}: {e}");
+                None
+            }
+        };
+        self.faces.insert(descriptor, face.clone());
+        face
+    }
+
+    /// Finds the installed font to render text with the given font name and style.
+    ///
+    /// The name may be a comma-separated list of fonts in order of preference, and may include
+    /// Flash's generic font names such as `_sans`.
+    pub fn primary_face(
+        &mut self,
+        ui: &dyn UiBackend,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Rc<FontFace>> {
+        for name in name
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            let face = match DefaultFont::from_name(name) {
+                Some(default_font) => default_font
+                    .candidates()
+                    .iter()
+                    .find_map(|candidate| self.face(ui, candidate, is_bold, is_italic)),
+                None => self.face(ui, name, is_bold, is_italic),
+            };
+            if face.is_some() {
+                return face;
+            }
+        }
+
+        None
+    }
+
+    /// The installed fonts to render characters that are missing from a device font with,
+    /// in order of preference.
+    pub fn fallback_faces(&mut self, ui: &dyn UiBackend) -> Vec<Rc<FontFace>> {
+        let mut faces: Vec<Rc<FontFace>> = Vec::new();
+        for candidates in fallback_font_names(ui.language()) {
+            if let Some(face) = candidates
+                .iter()
+                .find_map(|name| self.face(ui, name, false, false))
+            {
+                if !faces.iter().any(|existing| Rc::ptr_eq(existing, &face)) {
+                    faces.push(face);
+                }
+            }
+        }
+        faces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::ui::US_ENGLISH;
+    use swf::PointDelta;
+    use ttf_parser::OutlineBuilder;
+
+    #[test]
+    fn outlines_are_flipped_and_closed() {
+        let mut builder = ShapeRecordBuilder::new(2.0);
+        builder.move_to(0.0, 0.0);
+        builder.line_to(10.0, 0.0);
+        builder.quad_to(10.0, 10.0, 0.0, 10.0);
+        builder.close();
+
+        let twips = |x, y| PointDelta::new(Twips::new(x), Twips::new(y));
+        assert_eq!(builder.records.len(), 4);
+        assert!(matches!(
+            &builder.records[0],
+            ShapeRecord::StyleChange(style) if style.fill_style_1 == Some(1)
+        ));
+        assert_eq!(
+            builder.records[1],
+            ShapeRecord::StraightEdge {
+                delta: twips(20, 0)
+            }
+        );
+        assert_eq!(
+            builder.records[2],
+            ShapeRecord::CurvedEdge {
+                control_delta: twips(0, -20),
+                anchor_delta: twips(-20, 0),
+            }
+        );
+        assert_eq!(
+            builder.records[3],
+            ShapeRecord::StraightEdge {
+                delta: twips(0, 20)
+            }
+        );
+    }
+
+    #[test]
+    fn cubic_curves_are_split() {
+        let mut builder = ShapeRecordBuilder::new(1.0);
+        builder.move_to(0.0, 0.0);
+        builder.curve_to(0.0, 100.0, 100.0, 100.0, 100.0, 0.0);
+
+        assert_eq!(builder.records.len(), 3);
+        assert_eq!(builder.position, Point::new(Twips::new(100), Twips::ZERO));
+    }
+
+    #[test]
+    fn cjk_fallbacks_follow_language() {
+        let korean: LanguageIdentifier = "ko-KR".parse().unwrap();
+        let traditional_chinese: LanguageIdentifier = "zh-TW".parse().unwrap();
+        assert_eq!(fallback_font_names(&korean)[0], KOREAN_FONTS);
+        assert_eq!(
+            fallback_font_names(&traditional_chinese)[0],
+            TRADITIONAL_CHINESE_FONTS
+        );
+        assert_eq!(fallback_font_names(&US_ENGLISH)[0], JAPANESE_FONTS);
+    }
+}
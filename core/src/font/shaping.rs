@@ -0,0 +1,233 @@
+//! Bidirectional reordering and complex script shaping of text.
+//!
+//! Text is split into runs of the same direction with the Unicode Bidirectional Algorithm,
+//! and each run is split further by the font that draws it. Runs drawn with fonts installed
+//! on the system are shaped with HarfBuzz's algorithms, which join Arabic letters, form
+//! ligatures and position combining marks. Runs drawn with SWF fonts are laid out one
+//! character at a time, as those fonts have no shaping information.
+
+use crate::font::{Font, FontFace, Glyph, GlyphSource};
+use crate::prelude::*;
+use crate::string::WStr;
+use std::ops::Range;
+use unicode_bidi::{bidi_class, BidiClass, BidiInfo};
+
+/// A glyph positioned on a line of text.
+pub struct ShapedGlyph<'a> {
+    /// The position in the text of the character that this glyph was made from.
+    /// When several characters combine into one glyph, this is the first of them.
+    pub pos: usize,
+
+    pub glyph: &'a Glyph,
+
+    /// The distance to move after drawing this glyph, in EM-square coordinates.
+    pub advance: Twips,
+
+    /// The offset of this glyph from the current position, in EM-square coordinates.
+    /// This is non-zero for combining marks drawn above or below another glyph.
+    pub offset: Point<Twips>,
+
+    /// Whether letter spacing is added after this glyph.
+    /// Combining marks don't take up space of their own, so they aren't spaced.
+    pub is_spacing: bool,
+}
+
+/// The font that draws a run of characters.
+#[derive(Clone, Copy)]
+enum RunFont<'a> {
+    /// The glyphs of a SWF font, laid out one character at a time.
+    Swf,
+
+    /// A font installed on the system, which can shape text.
+    Face(&'a FontFace),
+}
+
+impl<'a> RunFont<'a> {
+    fn is_same(self, other: Self) -> bool {
+        match (self, other) {
+            (Self::Swf, Self::Swf) => true,
+            (Self::Face(a), Self::Face(b)) => std::ptr::eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// Whether a character belongs to the character before it, and should be drawn with the
+/// same font if possible (such as combining marks and zero-width joiners).
+fn is_attached(c: char) -> bool {
+    matches!(bidi_class(c), BidiClass::NSM | BidiClass::BN)
+}
+
+/// Whether a character may cause text to be laid out right-to-left.
+fn is_rtl(c: char) -> bool {
+    matches!(
+        bidi_class(c),
+        BidiClass::R
+            | BidiClass::AL
+            | BidiClass::AN
+            | BidiClass::RLE
+            | BidiClass::RLO
+            | BidiClass::RLI
+    )
+}
+
+impl<'gc> Font<'gc> {
+    /// Returns the font that draws a character, if any.
+    fn run_font(&self, c: char) -> Option<RunFont<'_>> {
+        match &self.0.glyphs {
+            GlyphSource::Memory { .. } => self.get_own_glyph_for_char(c).map(|_| RunFont::Swf),
+            GlyphSource::FontFace(face) => face.get_glyph(c).map(|_| RunFont::Face(face.as_ref())),
+        }
+        .or_else(|| {
+            self.0
+                .fallbacks
+                .iter()
+                .find(|fallback| fallback.get_glyph(c).is_some())
+                .map(|fallback| RunFont::Face(fallback.as_ref()))
+        })
+    }
+
+    fn has_char(&self, font: RunFont<'_>, c: char) -> bool {
+        match font {
+            RunFont::Swf => self.get_own_glyph_for_char(c).is_some(),
+            RunFont::Face(face) => face.get_glyph(c).is_some(),
+        }
+    }
+
+    /// Lays out a line of text, returning its glyphs in visual order.
+    ///
+    /// Characters that no font can draw are skipped.
+    pub fn shape(&self, text: &WStr, kerning: bool) -> Vec<ShapedGlyph<'_>> {
+        let chars: Vec<(usize, char)> = text
+            .char_indices()
+            .map(|(pos, c)| (pos, c.unwrap_or(char::REPLACEMENT_CHARACTER)))
+            .collect();
+
+        let mut glyphs = Vec::with_capacity(chars.len());
+        if chars.iter().any(|(_, c)| is_rtl(*c)) {
+            for (range, is_rtl) in visual_runs(&chars) {
+                self.shape_run(&chars, range, is_rtl, kerning, &mut glyphs);
+            }
+        } else {
+            self.shape_run(&chars, 0..chars.len(), false, kerning, &mut glyphs);
+        }
+        glyphs
+    }
+
+    /// Lays out a run of characters of the same direction, splitting it by the font that
+    /// draws each character.
+    fn shape_run<'a>(
+        &'a self,
+        chars: &[(usize, char)],
+        range: Range<usize>,
+        is_rtl: bool,
+        kerning: bool,
+        glyphs: &mut Vec<ShapedGlyph<'a>>,
+    ) {
+        let mut runs: Vec<(RunFont<'a>, Range<usize>)> = Vec::new();
+        for i in range {
+            let c = chars[i].1;
+            // Keep marks and joiners with the font of the character they're attached to.
+            let font = match runs.last() {
+                Some((font, run)) if run.end == i && is_attached(c) && self.has_char(*font, c) => {
+                    Some(*font)
+                }
+                _ => self.run_font(c),
+            };
+            let Some(font) = font else {
+                continue;
+            };
+            match runs.last_mut() {
+                Some((last, run)) if run.end == i && last.is_same(font) => run.end = i + 1,
+                _ => runs.push((font, i..i + 1)),
+            }
+        }
+
+        if is_rtl {
+            runs.reverse();
+        }
+        for (font, run) in runs {
+            match font {
+                RunFont::Swf => self.layout_swf_run(chars, run, is_rtl, kerning, glyphs),
+                RunFont::Face(face) => face.shape(&chars[run], is_rtl, kerning, glyphs),
+            }
+        }
+    }
+
+    /// Lays out a run of characters drawn with this SWF font, one character at a time.
+    fn layout_swf_run<'a>(
+        &'a self,
+        chars: &[(usize, char)],
+        run: Range<usize>,
+        is_rtl: bool,
+        kerning: bool,
+        glyphs: &mut Vec<ShapedGlyph<'a>>,
+    ) {
+        let has_kerning = kerning && !is_rtl && self.has_kerning_info();
+        let run_glyphs = run.filter_map(|i| {
+            let (pos, c) = chars[i];
+            let glyph = self.get_own_glyph_for_char(c)?;
+            let mut advance = Twips::new(glyph.swf_glyph.advance.into());
+            if has_kerning {
+                let next_char = chars.get(i + 1).map_or('\0', |(_, c)| *c);
+                advance += self.get_kerning_offset(c, next_char);
+            }
+            Some(ShapedGlyph {
+                pos,
+                glyph,
+                advance,
+                offset: Point::ZERO,
+                is_spacing: true,
+            })
+        });
+        if is_rtl {
+            glyphs.extend(run_glyphs.rev());
+        } else {
+            glyphs.extend(run_glyphs);
+        }
+    }
+}
+
+/// Splits text into runs of the same direction with the Unicode Bidirectional Algorithm,
+/// returning them in visual order as ranges of `chars`, along with whether they're
+/// right-to-left.
+fn visual_runs(chars: &[(usize, char)]) -> Vec<(Range<usize>, bool)> {
+    let text: String = chars.iter().map(|(_, c)| c).collect();
+    let char_offsets: Vec<usize> = text.char_indices().map(|(offset, _)| offset).collect();
+    let to_char_index = |offset: usize| char_offsets.partition_point(|&o| o < offset);
+
+    let bidi_info = BidiInfo::new(&text, None);
+    let mut runs = Vec::new();
+    for paragraph in &bidi_info.paragraphs {
+        let (levels, level_runs) = bidi_info.visual_runs(paragraph, paragraph.range.clone());
+        for run in level_runs {
+            let is_rtl = levels[run.start].is_rtl();
+            runs.push((to_char_index(run.start)..to_char_index(run.end), is_rtl));
+        }
+    }
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn runs(text: &str) -> Vec<(Range<usize>, bool)> {
+        let chars: Vec<(usize, char)> = text.chars().enumerate().collect();
+        visual_runs(&chars)
+    }
+
+    #[test]
+    fn left_to_right_text_is_one_run() {
+        assert_eq!(runs("Hello world"), vec![(0..11, false)]);
+    }
+
+    #[test]
+    fn right_to_left_runs_are_reordered() {
+        // "abc " followed by two Hebrew letters and " def".
+        assert_eq!(
+            runs("abc \u{5d0}\u{5d1} def"),
+            vec![(0..4, false), (4..6, true), (6..10, false)]
+        );
+    }
+}
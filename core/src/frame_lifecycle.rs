@@ -14,6 +14,7 @@
 use crate::avm2::Avm2;
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::profiler::Category;
 use tracing::instrument;
 
 /// Which phase of the frame we're currently in.
@@ -72,29 +73,46 @@ pub fn run_all_phases_avm2(context: &mut UpdateContext<'_, '_>) {
     let stage = context.stage;
 
     *context.frame_phase = FramePhase::Enter;
+    let begin = context.profiler.begin();
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.enter_frame(context);
     });
     stage.enter_frame(context);
+    context.profiler.end(begin, Category::Events, "enterFrame");
 
     *context.frame_phase = FramePhase::Construct;
+    let begin = context.profiler.begin();
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.construct_frame(context);
     });
     stage.construct_frame(context);
+    context
+        .profiler
+        .end(begin, Category::Script, "Construct frame");
+
+    let begin = context.profiler.begin();
     stage.frame_constructed(context);
+    context
+        .profiler
+        .end(begin, Category::Events, "frameConstructed");
 
     *context.frame_phase = FramePhase::FrameScripts;
+    let begin = context.profiler.begin();
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.run_frame_scripts(context);
     });
     stage.run_frame_scripts(context);
+    context
+        .profiler
+        .end(begin, Category::Script, "Frame scripts");
 
     *context.frame_phase = FramePhase::Exit;
+    let begin = context.profiler.begin();
     Avm2::each_orphan_obj(context, |orphan, context| {
         orphan.on_exit_frame(context);
     });
     stage.exit_frame(context);
+    context.profiler.end(begin, Category::Events, "exitFrame");
 
     // We cannot easily remove dead `GcWeak` instances from the orphan list
     // inside `each_orphan_movie`, since the callback may modify the orphan list.
@@ -0,0 +1,446 @@
+//! HTTP Dynamic Streaming (HDS) support.
+//!
+//! HDS streams are described by an F4M manifest, which points to one or more
+//! renditions of a stream and the bootstrap info (`abst` box) used to locate
+//! their fragments. Each fragment is an F4F file whose `mdat` box contains a
+//! run of FLV tags, which are fed into a `NetStream` as if they were appended
+//! with `appendBytes`.
+
+use base64::Engine;
+use byteorder::{BigEndian, ReadBytesExt};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::io::Read;
+use thiserror::Error;
+use url::Url;
+
+#[derive(Debug, Error)]
+pub enum HdsError {
+    #[error("Invalid manifest: {0}")]
+    InvalidManifest(#[from] quick_xml::Error),
+
+    #[error("Manifest does not contain any media")]
+    NoMedia,
+
+    #[error("Media has no bootstrap info")]
+    NoBootstrapInfo,
+
+    #[error("Invalid base64 data: {0}")]
+    InvalidBase64(#[from] base64::DecodeError),
+
+    #[error("Invalid URL: {0}")]
+    InvalidUrl(#[from] url::ParseError),
+
+    #[error("Truncated box")]
+    TruncatedBox(#[from] std::io::Error),
+
+    #[error("Expected a {0} box")]
+    UnexpectedBox(&'static str),
+}
+
+/// Bootstrap info as declared in a manifest, either inline or by reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BootstrapSource {
+    Inline(Vec<u8>),
+    Url(String),
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Media {
+    pub url: String,
+    pub bitrate: Option<u32>,
+    pub bootstrap_info_id: Option<String>,
+
+    /// The AMF0-encoded `onMetaData` arguments for this rendition, if given.
+    pub metadata: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Manifest {
+    pub base_url: Option<String>,
+    pub media: Vec<Media>,
+    bootstrap_infos: Vec<(Option<String>, BootstrapSource)>,
+}
+
+impl Manifest {
+    pub fn parse(data: &[u8]) -> Result<Self, HdsError> {
+        let mut manifest = Self::default();
+        let mut reader = Reader::from_reader(data);
+        reader.expand_empty_elements(true);
+        reader.trim_text(true);
+
+        let mut path: Vec<Vec<u8>> = Vec::new();
+        let mut bootstrap: Option<(Option<String>, Option<String>)> = None;
+        let mut text = String::new();
+        loop {
+            match reader.read_event()? {
+                Event::Start(e) => {
+                    let name = e.local_name().as_ref().to_vec();
+                    let attribute = |key: &[u8]| -> Result<Option<String>, HdsError> {
+                        for attribute in e.attributes().with_checks(false) {
+                            let attribute = attribute.map_err(quick_xml::Error::from)?;
+                            if attribute.key.local_name().as_ref() == key {
+                                return Ok(Some(attribute.unescape_value()?.into_owned()));
+                            }
+                        }
+                        Ok(None)
+                    };
+                    match &name[..] {
+                        b"media" => manifest.media.push(Media {
+                            url: attribute(b"url")?.unwrap_or_default(),
+                            bitrate: attribute(b"bitrate")?.and_then(|b| b.parse().ok()),
+                            bootstrap_info_id: attribute(b"bootstrapInfoId")?,
+                            metadata: None,
+                        }),
+                        b"bootstrapInfo" => {
+                            bootstrap = Some((attribute(b"id")?, attribute(b"url")?));
+                        }
+                        _ => {}
+                    }
+                    text.clear();
+                    path.push(name);
+                }
+                Event::Text(e) => text.push_str(&e.unescape()?),
+                Event::CData(e) => text.push_str(&String::from_utf8_lossy(&e)),
+                Event::End(_) => {
+                    let name = path.pop().unwrap_or_default();
+                    let parent = path.last().map(|p| &p[..]);
+                    match (&name[..], parent) {
+                        (b"baseURL", _) => manifest.base_url = Some(text.trim().to_string()),
+                        (b"metadata", Some(b"media")) => {
+                            if let Some(media) = manifest.media.last_mut() {
+                                media.metadata = Some(decode_base64(&text)?);
+                            }
+                        }
+                        (b"bootstrapInfo", _) => {
+                            if let Some((id, url)) = bootstrap.take() {
+                                let source = match url {
+                                    Some(url) => BootstrapSource::Url(url),
+                                    None => BootstrapSource::Inline(decode_base64(&text)?),
+                                };
+                                manifest.bootstrap_infos.push((id, source));
+                            }
+                        }
+                        _ => {}
+                    }
+                    text.clear();
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Picks the rendition to play.
+    ///
+    /// We don't measure bandwidth, so this is always the first one listed.
+    pub fn default_media(&self) -> Result<&Media, HdsError> {
+        self.media.first().ok_or(HdsError::NoMedia)
+    }
+
+    /// Finds the bootstrap info for the given media.
+    pub fn bootstrap_for(&self, media: &Media) -> Result<&BootstrapSource, HdsError> {
+        self.bootstrap_infos
+            .iter()
+            .find(|(id, _)| media.bootstrap_info_id.is_none() || *id == media.bootstrap_info_id)
+            .map(|(_, source)| source)
+            .ok_or(HdsError::NoBootstrapInfo)
+    }
+
+    /// Resolves the URL that the media's fragments and bootstrap are relative to.
+    pub fn base_url(&self, manifest_url: &Url) -> Result<Url, HdsError> {
+        match &self.base_url {
+            Some(base_url) if base_url.ends_with('/') => Ok(manifest_url.join(base_url)?),
+            Some(base_url) => Ok(manifest_url.join(&format!("{base_url}/"))?),
+            None => Ok(manifest_url.clone()),
+        }
+    }
+
+    /// Builds the URL of a single fragment of the given media.
+    pub fn fragment_url(
+        &self,
+        manifest_url: &Url,
+        media: &Media,
+        segment: u32,
+        fragment: u32,
+    ) -> Result<Url, HdsError> {
+        let base_url = self.base_url(manifest_url)?;
+        Ok(base_url.join(&format!("{}Seg{segment}-Frag{fragment}", media.url))?)
+    }
+}
+
+fn decode_base64(text: &str) -> Result<Vec<u8>, HdsError> {
+    let text: String = text.chars().filter(|c| !c.is_ascii_whitespace()).collect();
+    Ok(base64::engine::general_purpose::STANDARD.decode(text)?)
+}
+
+/// Returns whether the given response looks like an F4M manifest.
+pub fn is_manifest(data: &[u8]) -> bool {
+    let start = &data[..data.len().min(512)];
+    let start = String::from_utf8_lossy(start);
+    start.contains("<manifest") && start.contains("ns.adobe.com/f4m")
+}
+
+/// The parts of an `abst` box needed to enumerate fragments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BootstrapInfo {
+    pub is_live: bool,
+    pub time_scale: u32,
+
+    /// `(first segment, fragments per segment)` runs from the first `asrt` box.
+    pub segment_runs: Vec<(u32, u32)>,
+
+    /// The first fragment number listed in the first `afrt` box.
+    pub first_fragment: u32,
+}
+
+impl BootstrapInfo {
+    pub fn parse(data: &[u8]) -> Result<Self, HdsError> {
+        let (box_type, mut data) = read_box(&mut &data[..])?;
+        if box_type != *b"abst" {
+            return Err(HdsError::UnexpectedBox("abst"));
+        }
+
+        let _version_and_flags = data.read_u32::<BigEndian>()?;
+        let _bootstrap_info_version = data.read_u32::<BigEndian>()?;
+        let profile_flags = data.read_u8()?;
+        let time_scale = data.read_u32::<BigEndian>()?;
+        let _current_media_time = data.read_u64::<BigEndian>()?;
+        let _smpte_time_code_offset = data.read_u64::<BigEndian>()?;
+        let _movie_identifier = read_string(&mut data)?;
+        for _ in 0..data.read_u8()? {
+            let _server = read_string(&mut data)?;
+        }
+        for _ in 0..data.read_u8()? {
+            let _quality = read_string(&mut data)?;
+        }
+        let _drm_data = read_string(&mut data)?;
+        let _metadata = read_string(&mut data)?;
+
+        let mut segment_runs = None;
+        for _ in 0..data.read_u8()? {
+            let (box_type, table) = read_box(&mut data)?;
+            if box_type != *b"asrt" {
+                return Err(HdsError::UnexpectedBox("asrt"));
+            }
+            let runs = parse_segment_runs(table)?;
+            segment_runs.get_or_insert(runs);
+        }
+
+        let mut first_fragment = None;
+        for _ in 0..data.read_u8()? {
+            let (box_type, table) = read_box(&mut data)?;
+            if box_type != *b"afrt" {
+                return Err(HdsError::UnexpectedBox("afrt"));
+            }
+            let first = parse_first_fragment(table)?;
+            first_fragment = first_fragment.or(first);
+        }
+
+        Ok(Self {
+            is_live: profile_flags & 0b0010_0000 != 0,
+            time_scale,
+            segment_runs: segment_runs.unwrap_or_default(),
+            first_fragment: first_fragment.unwrap_or(1),
+        })
+    }
+
+    /// Lists every `(segment, fragment)` pair available in this bootstrap.
+    ///
+    /// Live streams only keep the last couple of fragments, as older ones may
+    /// already have been removed from the server.
+    pub fn fragments(&self) -> Vec<(u32, u32)> {
+        let mut fragments = Vec::new();
+        let mut fragment = self.first_fragment;
+        for &(segment, mut count) in &self.segment_runs {
+            if count == u32::MAX && self.is_live {
+                // Some live servers advertise an open-ended run.
+                count = 2;
+            }
+            for _ in 0..count {
+                fragments.push((segment, fragment));
+                fragment = fragment.wrapping_add(1);
+            }
+        }
+
+        if self.is_live && fragments.len() > 2 {
+            fragments.drain(..fragments.len() - 2);
+        }
+        fragments
+    }
+}
+
+fn parse_segment_runs(mut data: &[u8]) -> Result<Vec<(u32, u32)>, HdsError> {
+    let _version_and_flags = data.read_u32::<BigEndian>()?;
+    for _ in 0..data.read_u8()? {
+        let _quality = read_string(&mut data)?;
+    }
+    let count = data.read_u32::<BigEndian>()?;
+    let mut runs = Vec::with_capacity(count.min(1024) as usize);
+    for _ in 0..count {
+        let first_segment = data.read_u32::<BigEndian>()?;
+        let fragments_per_segment = data.read_u32::<BigEndian>()?;
+        runs.push((first_segment, fragments_per_segment));
+    }
+    Ok(runs)
+}
+
+fn parse_first_fragment(mut data: &[u8]) -> Result<Option<u32>, HdsError> {
+    let _version_and_flags = data.read_u32::<BigEndian>()?;
+    let _time_scale = data.read_u32::<BigEndian>()?;
+    for _ in 0..data.read_u8()? {
+        let _quality = read_string(&mut data)?;
+    }
+    if data.read_u32::<BigEndian>()? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(data.read_u32::<BigEndian>()?))
+}
+
+/// Reads an F4F box header, returning its type and contents.
+fn read_box<'a>(data: &mut &'a [u8]) -> Result<([u8; 4], &'a [u8]), HdsError> {
+    let size = data.read_u32::<BigEndian>()? as u64;
+    let mut box_type = [0; 4];
+    data.read_exact(&mut box_type)?;
+    let (header_size, size) = match size {
+        0 => (8, data.len() as u64 + 8),
+        1 => (16, data.read_u64::<BigEndian>()?),
+        size => (8, size),
+    };
+    let length = size
+        .checked_sub(header_size)
+        .filter(|length| *length <= data.len() as u64)
+        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?
+        as usize;
+    let (contents, rest) = data.split_at(length);
+    *data = rest;
+    Ok((box_type, contents))
+}
+
+fn read_string(data: &mut &[u8]) -> Result<String, HdsError> {
+    let end = data
+        .iter()
+        .position(|b| *b == 0)
+        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?;
+    let string = String::from_utf8_lossy(&data[..end]).into_owned();
+    *data = &data[end + 1..];
+    Ok(string)
+}
+
+/// Extracts the FLV tags from an F4F fragment.
+pub fn fragment_tags(mut data: &[u8]) -> Result<Vec<u8>, HdsError> {
+    let mut tags = Vec::new();
+    while !data.is_empty() {
+        let (box_type, contents) = read_box(&mut data)?;
+        if box_type == *b"mdat" {
+            tags.extend_from_slice(contents);
+        }
+    }
+    Ok(tags)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_box(box_type: &[u8; 4], contents: &[u8]) -> Vec<u8> {
+        let mut data = ((contents.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(box_type);
+        data.extend_from_slice(contents);
+        data
+    }
+
+    fn make_bootstrap(is_live: bool, segment_runs: &[(u32, u32)], first_fragment: u32) -> Vec<u8> {
+        let mut asrt = vec![0, 0, 0, 0, 0];
+        asrt.extend_from_slice(&(segment_runs.len() as u32).to_be_bytes());
+        for (segment, count) in segment_runs {
+            asrt.extend_from_slice(&segment.to_be_bytes());
+            asrt.extend_from_slice(&count.to_be_bytes());
+        }
+
+        let mut afrt = vec![0, 0, 0, 0];
+        afrt.extend_from_slice(&1000u32.to_be_bytes());
+        afrt.push(0);
+        afrt.extend_from_slice(&1u32.to_be_bytes());
+        afrt.extend_from_slice(&first_fragment.to_be_bytes());
+        afrt.extend_from_slice(&0u64.to_be_bytes());
+        afrt.extend_from_slice(&4000u32.to_be_bytes());
+
+        let mut abst = vec![0, 0, 0, 0];
+        abst.extend_from_slice(&1u32.to_be_bytes());
+        abst.push(if is_live { 0b0010_0000 } else { 0 });
+        abst.extend_from_slice(&1000u32.to_be_bytes());
+        abst.extend_from_slice(&0u64.to_be_bytes());
+        abst.extend_from_slice(&0u64.to_be_bytes());
+        abst.extend_from_slice(b"\0\0\0\0\0");
+        abst.push(1);
+        abst.extend(make_box(b"asrt", &asrt));
+        abst.push(1);
+        abst.extend(make_box(b"afrt", &afrt));
+        make_box(b"abst", &abst)
+    }
+
+    #[test]
+    fn manifest_is_parsed() {
+        let manifest = br#"<?xml version="1.0" encoding="UTF-8"?>
+            <manifest xmlns="http://ns.adobe.com/f4m/1.0">
+                <baseURL>http://example.com/videos</baseURL>
+                <bootstrapInfo profile="named" id="boot1" url="video.bootstrap"/>
+                <bootstrapInfo profile="named" id="boot2">AAAA</bootstrapInfo>
+                <media url="video_low" bitrate="300" bootstrapInfoId="boot2">
+                    <metadata>AgAK
+                        b25NZXRhRGF0YQ==</metadata>
+                </media>
+            </manifest>"#;
+        assert!(is_manifest(manifest));
+
+        let manifest = Manifest::parse(manifest).unwrap();
+        let media = manifest.default_media().unwrap();
+        assert_eq!(media.url, "video_low");
+        assert_eq!(media.bitrate, Some(300));
+        assert_eq!(
+            media.metadata.as_deref(),
+            Some(&b"\x02\x00\x0aonMetaData"[..])
+        );
+        assert_eq!(
+            manifest.bootstrap_for(media).unwrap(),
+            &BootstrapSource::Inline(vec![0, 0, 0])
+        );
+
+        let manifest_url = Url::parse("http://example.com/manifest.f4m").unwrap();
+        assert_eq!(
+            manifest
+                .fragment_url(&manifest_url, media, 1, 3)
+                .unwrap()
+                .as_str(),
+            "http://example.com/videos/video_lowSeg1-Frag3"
+        );
+    }
+
+    #[test]
+    fn bootstrap_lists_fragments() {
+        let bootstrap = BootstrapInfo::parse(&make_bootstrap(false, &[(1, 3), (2, 2)], 5)).unwrap();
+        assert_eq!(bootstrap.time_scale, 1000);
+        assert_eq!(
+            bootstrap.fragments(),
+            vec![(1, 5), (1, 6), (1, 7), (2, 8), (2, 9)]
+        );
+    }
+
+    #[test]
+    fn live_bootstrap_keeps_latest_fragments() {
+        let bootstrap = BootstrapInfo::parse(&make_bootstrap(true, &[(1, 10)], 1)).unwrap();
+        assert!(bootstrap.is_live);
+        assert_eq!(bootstrap.fragments(), vec![(1, 9), (1, 10)]);
+    }
+
+    #[test]
+    fn fragment_tags_are_extracted() {
+        let mut fragment = make_box(b"afra", &[0; 9]);
+        fragment.extend(make_box(b"moof", &[1, 2, 3]));
+        fragment.extend(make_box(b"mdat", &[4, 5, 6, 7]));
+        assert_eq!(fragment_tags(&fragment).unwrap(), vec![4, 5, 6, 7]);
+    }
+}
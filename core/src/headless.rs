@@ -0,0 +1,403 @@
+//! Running movies headlessly, as fast as possible, with scripted input
+//!
+//! Scripted input is written as a plain text file, with one event per line. Each line
+//! starts with the number of the frame the event is sent before, counting from 1:
+//!
+//! ```text
+//! # Press the start button, then hold the right arrow key for a second.
+//! 10 mouse_move 320 240
+//! 10 mouse_down 320 240 left
+//! 11 mouse_up 320 240 left
+//! 30 key_down 39
+//! 60 key_up 39
+//! 75 text hello
+//! ```
+//!
+//! Scripts recorded by the player also start with a `seed` line, which holds the seed of
+//! the random number generator the movie used.
+//!
+//! While a movie runs headlessly, scripts only see time pass as frames are run, so the
+//! same movie with the same input always runs the same way, no matter how fast it runs.
+
+use crate::events::{
+    KeyCode, KeyLocation, MouseButton, MouseWheelDelta, PlayerEvent, TextControlCode,
+};
+use crate::limits::ExecutionLimit;
+use crate::Player;
+use std::fmt;
+use std::time::Duration;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[error("Line {line}: {message}")]
+pub struct InputScriptError {
+    /// The line of the script that couldn't be parsed, counting from 1.
+    pub line: usize,
+    pub message: String,
+}
+
+/// An event that is sent to the player before a given frame runs.
+#[derive(Debug, Clone)]
+pub struct ScriptedEvent {
+    pub frame: u32,
+    pub event: PlayerEvent,
+}
+
+/// A list of events to send to a player that runs headlessly.
+#[derive(Debug, Clone, Default)]
+pub struct InputScript {
+    /// The seed of the random number generator, if the script was recorded.
+    pub seed: Option<u64>,
+
+    /// Every event in the script, ordered by the frame they're sent on.
+    events: Vec<ScriptedEvent>,
+}
+
+impl InputScript {
+    pub fn parse(source: &str) -> Result<Self, InputScriptError> {
+        let mut seed = None;
+        let mut events = Vec::new();
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let result = match line.strip_prefix("seed ") {
+                Some(value) => value
+                    .trim()
+                    .parse()
+                    .map(|value| seed = Some(value))
+                    .map_err(|_| format!("Invalid seed \"{value}\"")),
+                None => parse_line(line, &mut events),
+            };
+            result.map_err(|message| InputScriptError {
+                line: index + 1,
+                message,
+            })?;
+        }
+
+        // Events on the same frame are kept in the order they were written.
+        events.sort_by_key(|event| event.frame);
+        Ok(Self { seed, events })
+    }
+
+    pub fn events(&self) -> &[ScriptedEvent] {
+        &self.events
+    }
+
+    /// Add an event to the end of the script, if it's one that scripts can hold.
+    ///
+    /// Returns `false` for events that can't be written to a script, such as gamepad input.
+    pub fn push(&mut self, frame: u32, event: PlayerEvent) -> bool {
+        if !is_scriptable(&event) {
+            return false;
+        }
+        self.events.push(ScriptedEvent { frame, event });
+        true
+    }
+}
+
+/// The names of text control codes in scripts.
+const TEXT_CONTROL_CODES: [(&str, TextControlCode); 11] = [
+    ("move_left", TextControlCode::MoveLeft),
+    ("move_right", TextControlCode::MoveRight),
+    ("select_left", TextControlCode::SelectLeft),
+    ("select_right", TextControlCode::SelectRight),
+    ("select_all", TextControlCode::SelectAll),
+    ("copy", TextControlCode::Copy),
+    ("paste", TextControlCode::Paste),
+    ("cut", TextControlCode::Cut),
+    ("backspace", TextControlCode::Backspace),
+    ("enter", TextControlCode::Enter),
+    ("delete", TextControlCode::Delete),
+];
+
+fn is_scriptable(event: &PlayerEvent) -> bool {
+    matches!(
+        event,
+        PlayerEvent::MouseMove { .. }
+            | PlayerEvent::MouseDown { .. }
+            | PlayerEvent::MouseUp { .. }
+            | PlayerEvent::MouseWheel { .. }
+            | PlayerEvent::MouseLeave
+            | PlayerEvent::KeyDown { .. }
+            | PlayerEvent::KeyUp { .. }
+            | PlayerEvent::TextInput { .. }
+            | PlayerEvent::TextControl { .. }
+    )
+}
+
+fn button_name(button: MouseButton) -> &'static str {
+    match button {
+        MouseButton::Middle => "middle",
+        MouseButton::Right => "right",
+        MouseButton::Left | MouseButton::Unknown => "left",
+    }
+}
+
+impl fmt::Display for InputScript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(seed) = self.seed {
+            writeln!(f, "seed {seed}")?;
+        }
+        for ScriptedEvent { frame, event } in &self.events {
+            write!(f, "{frame} ")?;
+            match event {
+                PlayerEvent::MouseMove { x, y } => writeln!(f, "mouse_move {x} {y}")?,
+                PlayerEvent::MouseDown { x, y, button } => {
+                    writeln!(f, "mouse_down {x} {y} {}", button_name(*button))?
+                }
+                PlayerEvent::MouseUp { x, y, button } => {
+                    writeln!(f, "mouse_up {x} {y} {}", button_name(*button))?
+                }
+                PlayerEvent::MouseWheel { delta } => writeln!(f, "mouse_wheel {}", delta.lines())?,
+                PlayerEvent::MouseLeave => writeln!(f, "mouse_leave")?,
+                PlayerEvent::KeyDown {
+                    key_code, key_char, ..
+                }
+                | PlayerEvent::KeyUp {
+                    key_code, key_char, ..
+                } => {
+                    let name = if matches!(event, PlayerEvent::KeyDown { .. }) {
+                        "key_down"
+                    } else {
+                        "key_up"
+                    };
+                    write!(f, "{name} {}", *key_code as u8)?;
+                    if let Some(key_char) = key_char {
+                        write!(f, " {}", *key_char as u32)?;
+                    }
+                    writeln!(f)?
+                }
+                PlayerEvent::TextInput { codepoint } if !codepoint.is_whitespace() => {
+                    writeln!(f, "text {codepoint}")?
+                }
+                PlayerEvent::TextInput { codepoint } => writeln!(f, "char {}", *codepoint as u32)?,
+                PlayerEvent::TextControl { code } => {
+                    let name = TEXT_CONTROL_CODES
+                        .iter()
+                        .find(|(_, c)| c == code)
+                        .map_or("", |(name, _)| name);
+                    writeln!(f, "text_control {name}")?
+                }
+                _ => writeln!(f, "# {event:?}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_line(line: &str, events: &mut Vec<ScriptedEvent>) -> Result<(), String> {
+    let (frame, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let frame: u32 = frame
+        .parse()
+        .map_err(|_| format!("Invalid frame number \"{frame}\""))?;
+    let (name, args) = rest
+        .trim_start()
+        .split_once(char::is_whitespace)
+        .unwrap_or((rest.trim_start(), ""));
+    let args = args.trim();
+
+    let mut push = |event| events.push(ScriptedEvent { frame, event });
+    match name {
+        "mouse_move" => {
+            let (x, y) = parse_position(args)?;
+            push(PlayerEvent::MouseMove { x, y });
+        }
+        "mouse_down" | "mouse_up" => {
+            let mut parts = args.split_whitespace();
+            let x = parse_number(parts.next())?;
+            let y = parse_number(parts.next())?;
+            let button = match parts.next().unwrap_or("left") {
+                "left" => MouseButton::Left,
+                "middle" => MouseButton::Middle,
+                "right" => MouseButton::Right,
+                button => return Err(format!("Unknown mouse button \"{button}\"")),
+            };
+            push(if name == "mouse_down" {
+                PlayerEvent::MouseDown { x, y, button }
+            } else {
+                PlayerEvent::MouseUp { x, y, button }
+            });
+        }
+        "mouse_wheel" => {
+            let lines = parse_number(Some(args))?;
+            push(PlayerEvent::MouseWheel {
+                delta: MouseWheelDelta::Lines(lines),
+            });
+        }
+        "mouse_leave" => push(PlayerEvent::MouseLeave),
+        "key_down" | "key_up" => {
+            let mut parts = args.split_whitespace();
+            let key_code = parts.next().unwrap_or_default();
+            let key_code = key_code
+                .parse()
+                .ok()
+                .and_then(KeyCode::from_u8)
+                .ok_or_else(|| format!("Invalid key code \"{key_code}\""))?;
+            let key_char = parts.next().map(parse_char).transpose()?;
+            push(if name == "key_down" {
+                PlayerEvent::KeyDown {
+                    key_code,
+                    key_char,
+                    key_location: KeyLocation::Standard,
+                }
+            } else {
+                PlayerEvent::KeyUp {
+                    key_code,
+                    key_char,
+                    key_location: KeyLocation::Standard,
+                }
+            });
+        }
+        "text" => {
+            for codepoint in args.chars() {
+                push(PlayerEvent::TextInput { codepoint });
+            }
+        }
+        "char" => {
+            let codepoint = parse_char(args)?;
+            push(PlayerEvent::TextInput { codepoint });
+        }
+        "text_control" => {
+            let code = TEXT_CONTROL_CODES
+                .iter()
+                .find(|(name, _)| *name == args)
+                .map(|(_, code)| *code)
+                .ok_or_else(|| format!("Unknown text control code \"{args}\""))?;
+            push(PlayerEvent::TextControl { code });
+        }
+        _ => return Err(format!("Unknown event \"{name}\"")),
+    }
+    Ok(())
+}
+
+fn parse_position(args: &str) -> Result<(f64, f64), String> {
+    let mut parts = args.split_whitespace();
+    Ok((parse_number(parts.next())?, parse_number(parts.next())?))
+}
+
+fn parse_char(arg: &str) -> Result<char, String> {
+    arg.parse()
+        .ok()
+        .and_then(char::from_u32)
+        .ok_or_else(|| format!("Invalid character code \"{arg}\""))
+}
+
+fn parse_number(arg: Option<&str>) -> Result<f64, String> {
+    let arg = arg.ok_or("Missing argument")?;
+    arg.parse().map_err(|_| format!("Invalid number \"{arg}\""))
+}
+
+/// Runs a player one frame at a time, as fast as possible, sending scripted input
+/// along the way.
+pub struct HeadlessRunner {
+    script: InputScript,
+
+    /// The index of the next event in the script to send.
+    next_event: usize,
+
+    /// The number of frames run so far.
+    frame: u32,
+
+    /// The time that scripts see as having passed, in milliseconds.
+    time: f64,
+}
+
+impl HeadlessRunner {
+    /// Start running a player headlessly.
+    ///
+    /// From this point on, scripts will only see time pass as frames are run.
+    pub fn new(player: &mut Player, script: InputScript) -> Self {
+        player.set_fixed_time(Some(Duration::ZERO));
+        Self {
+            script,
+            next_event: 0,
+            frame: 0,
+            time: 0.0,
+        }
+    }
+
+    /// The number of frames that have been run so far.
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Send any events scripted for the next frame, then run and render it.
+    pub fn run_frame(&mut self, player: &mut Player) {
+        self.frame += 1;
+        player.preload(&mut ExecutionLimit::none());
+
+        while let Some(event) = self
+            .script
+            .events
+            .get(self.next_event)
+            .filter(|event| event.frame <= self.frame)
+        {
+            player.handle_event(event.event.clone());
+            self.next_event += 1;
+        }
+
+        let frame_time = 1000.0 / player.frame_rate();
+        player.run_frame();
+        self.time += frame_time;
+        player.set_fixed_time(Some(Duration::from_secs_f64(self.time / 1000.0)));
+        player.update_timers(frame_time);
+        player.audio_mut().tick();
+        player.render();
+    }
+}
+
+/// Hash the pixels of a captured frame, so that runs can be compared without keeping
+/// every frame around.
+///
+/// This uses 64-bit FNV-1a, which gives the same result on every platform and version.
+pub fn hash_pixels(pixels: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    pixels.iter().fold(OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_input_script() {
+        let script = InputScript::parse(
+            "# Comment\n\
+             5 key_down 39\n\
+             2 mouse_down 10 20\n\
+             \n\
+             2 text hi\n",
+        )
+        .unwrap();
+        let events = script.events();
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[0].frame, 2);
+        assert!(matches!(
+            events[0].event,
+            PlayerEvent::MouseDown {
+                button: MouseButton::Left,
+                ..
+            }
+        ));
+        assert!(matches!(
+            events[2].event,
+            PlayerEvent::TextInput { codepoint: 'i' }
+        ));
+        assert_eq!(events[3].frame, 5);
+
+        let error = InputScript::parse("1 mouse_move 10\n2 jump").unwrap_err();
+        assert_eq!(error.line, 1);
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!(hash_pixels(b""), 0xcbf29ce484222325);
+        assert_eq!(hash_pixels(b"a"), 0xaf63dc4c8601ec8c);
+    }
+}
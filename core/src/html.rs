@@ -3,12 +3,14 @@
 mod dimensions;
 mod iterators;
 mod layout;
+mod stylesheet;
 mod text_format;
 
 pub use dimensions::BoxBounds;
 pub use dimensions::Position;
 pub use dimensions::Size;
 pub use layout::{LayoutBox, LayoutContent, LayoutMetrics};
+pub use stylesheet::{parse_css, StyleSheet, STYLE_PROPERTIES};
 pub use text_format::{FormatSpans, TextFormat, TextSpan};
 
 #[cfg(test)]
@@ -462,14 +462,28 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         is_device_font: bool,
     ) -> Option<Font<'gc>> {
         let library = context.library.library_for_movie_mut(self.movie.clone());
+        let font_name = span.font.to_utf8_lossy();
 
-        // If this text field is set to use device fonts, fallback to using our embedded Noto Sans.
+        // If this text field is set to use device fonts, look for a matching font on the system,
+        // falling back to using our embedded Noto Sans.
         // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
-        // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
         if let Some(font) = library
-            .get_font_by_name(&span.font.to_utf8_lossy(), span.bold, span.italic)
+            .get_font_by_name(&font_name, span.bold, span.italic)
+            .or_else(|| {
+                context
+                    .library
+                    .get_registered_font_by_name(&font_name, span.bold, span.italic)
+            })
             .filter(|f| !is_device_font && f.has_glyphs())
-            .or_else(|| context.library.device_font())
+            .or_else(|| {
+                context.library.get_device_font(
+                    &font_name,
+                    span.bold,
+                    span.italic,
+                    context.ui,
+                    context.gc_context,
+                )
+            })
         {
             self.font = Some(font);
             return self.font;
@@ -528,11 +542,25 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         is_device_font: bool,
     ) {
         let library = context.library.library_for_movie_mut(self.movie.clone());
+        let font_name = span.font.to_utf8_lossy();
 
         if let Some(bullet_font) = library
-            .get_font_by_name(&span.font.to_utf8_lossy(), span.bold, span.italic)
+            .get_font_by_name(&font_name, span.bold, span.italic)
+            .or_else(|| {
+                context
+                    .library
+                    .get_registered_font_by_name(&font_name, span.bold, span.italic)
+            })
             .filter(|f| !is_device_font && f.has_glyphs())
-            .or_else(|| context.library.device_font())
+            .or_else(|| {
+                context.library.get_device_font(
+                    &font_name,
+                    span.bold,
+                    span.italic,
+                    context.ui,
+                    context.gc_context,
+                )
+            })
             .or(self.font)
         {
             let mut bullet_cursor = self.cursor;
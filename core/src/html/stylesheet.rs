@@ -0,0 +1,215 @@
+//! CSS style sheets, as used by `TextField.styleSheet`
+
+use crate::html::text_format::TextFormat;
+use crate::string::{WStr, WString};
+use std::collections::HashMap;
+
+/// The CSS properties that Flash understands, by the name they're given in style objects.
+pub const STYLE_PROPERTIES: &[&str] = &[
+    "color",
+    "display",
+    "fontFamily",
+    "fontSize",
+    "fontStyle",
+    "fontWeight",
+    "kerning",
+    "leading",
+    "letterSpacing",
+    "marginLeft",
+    "marginRight",
+    "textAlign",
+    "textDecoration",
+    "textIndent",
+];
+
+/// A set of styles that are applied to the elements of HTML text.
+///
+/// Styles are looked up by selector, which is either a tag name (`p`) or a class name
+/// preceded by a period (`.title`). Selectors are case-insensitive.
+#[derive(Clone, Debug, Default)]
+pub struct StyleSheet {
+    styles: HashMap<WString, TextFormat>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn set_style(&mut self, selector: &WStr, format: TextFormat) {
+        self.styles.insert(selector.to_ascii_lowercase(), format);
+    }
+
+    pub fn style(&self, selector: &WStr) -> Option<&TextFormat> {
+        self.styles.get(&selector.to_ascii_lowercase())
+    }
+
+    /// Applies the styles of an element to the format it inherits from its parent.
+    ///
+    /// The style of the element's tag is applied first, then the style of its class.
+    pub fn apply(&self, format: TextFormat, tag: &WStr, class: Option<&WStr>) -> TextFormat {
+        let mut format = match self.style(tag) {
+            Some(style) => style.clone().mix_with(format),
+            None => format,
+        };
+        if let Some(class) = class {
+            let mut selector = WString::from_utf8(".");
+            selector.push_str(class);
+            if let Some(style) = self.style(&selector) {
+                format = style.clone().mix_with(format);
+            }
+        }
+        format
+    }
+}
+
+/// A rule of a style sheet: a selector, and the properties it sets.
+pub type StyleRule = (String, Vec<(String, String)>);
+
+/// Parses CSS text into rules.
+///
+/// Property names are converted to the camel case names used by style objects
+/// (`font-size` becomes `fontSize`), and selectors are lowercased. Rules with several
+/// selectors separated by commas are split into one rule per selector.
+///
+/// Returns `None` if the text isn't valid CSS.
+pub fn parse_css(css: &str) -> Option<Vec<StyleRule>> {
+    let css = strip_comments(css);
+    let mut rules = Vec::new();
+    let mut rest = css.trim_start();
+    while !rest.is_empty() {
+        let open = rest.find('{')?;
+        let close = open + rest[open..].find('}')?;
+
+        let properties: Vec<(String, String)> = rest[open + 1..close]
+            .split(';')
+            .filter_map(|declaration| {
+                let (name, value) = declaration.split_once(':')?;
+                let name = name.trim();
+                (!name.is_empty()).then(|| (css_property_name(name), value.trim().to_string()))
+            })
+            .collect();
+
+        for selector in rest[..open].split(',') {
+            let selector = selector.trim();
+            if selector.is_empty() {
+                return None;
+            }
+            rules.push((selector.to_ascii_lowercase(), properties.clone()));
+        }
+
+        rest = rest[close + 1..].trim_start();
+    }
+    Some(rules)
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Converts a hyphenated CSS property name to camel case.
+fn css_property_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut capitalize = false;
+    for c in name.chars() {
+        if c == '-' {
+            capitalize = true;
+        } else if capitalize {
+            result.extend(c.to_uppercase());
+            capitalize = false;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Parses the number at the start of a CSS value, ignoring any unit after it.
+fn parse_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let end = value
+        .char_indices()
+        .find(|&(i, c)| !(c.is_ascii_digit() || c == '.' || (i == 0 && (c == '-' || c == '+'))))
+        .map_or(value.len(), |(i, _)| i);
+    value[..end].parse().ok()
+}
+
+impl TextFormat {
+    /// Applies a property of a style object to this format.
+    ///
+    /// Unknown properties and invalid values are ignored.
+    pub fn apply_style_property(&mut self, name: &str, value: &str) {
+        let value = value.trim();
+        match name {
+            "color" => {
+                if let Some(hex) = value.strip_prefix('#') {
+                    if let Ok(rgb) = u32::from_str_radix(hex, 16) {
+                        self.color = Some(swf::Color::from_rgb(rgb, 0));
+                    }
+                }
+            }
+            "fontFamily" => {
+                let font = value
+                    .split(',')
+                    .map(|family| family.trim().trim_matches(|c| c == '"' || c == '\''))
+                    .map(|family| match family {
+                        "mono" => "_typewriter",
+                        "sans-serif" => "_sans",
+                        "serif" => "_serif",
+                        family => family,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",");
+                self.font = Some(WString::from_utf8(&font));
+            }
+            "fontSize" => {
+                if let Some(size) = parse_number(value) {
+                    self.size = Some(size.max(0.0));
+                }
+            }
+            "fontStyle" => match value {
+                "italic" => self.italic = Some(true),
+                "normal" => self.italic = Some(false),
+                _ => {}
+            },
+            "fontWeight" => match value {
+                "bold" => self.bold = Some(true),
+                "normal" => self.bold = Some(false),
+                _ => {}
+            },
+            "kerning" => match value {
+                "true" => self.kerning = Some(true),
+                "false" => self.kerning = Some(false),
+                _ => {}
+            },
+            "leading" => self.leading = parse_number(value).or(self.leading),
+            "letterSpacing" => self.letter_spacing = parse_number(value).or(self.letter_spacing),
+            "marginLeft" => self.left_margin = parse_number(value).or(self.left_margin),
+            "marginRight" => self.right_margin = parse_number(value).or(self.right_margin),
+            "textAlign" => match value {
+                "left" => self.align = Some(swf::TextAlign::Left),
+                "center" => self.align = Some(swf::TextAlign::Center),
+                "right" => self.align = Some(swf::TextAlign::Right),
+                "justify" => self.align = Some(swf::TextAlign::Justify),
+                _ => {}
+            },
+            "textDecoration" => match value {
+                "underline" => self.underline = Some(true),
+                "none" => self.underline = Some(false),
+                _ => {}
+            },
+            "textIndent" => self.indent = parse_number(value).or(self.indent),
+            _ => {}
+        }
+    }
+}
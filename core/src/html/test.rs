@@ -1,6 +1,7 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::stylesheet::{parse_css, StyleSheet};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
 use crate::string::{WStr, WString};
 use swf::{Rectangle, Twips};
@@ -864,3 +865,62 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn parse_css_rules() {
+    let rules = parse_css(
+        "/* headings */ H1, .Title { font-size: 24px; font-weight: bold }\np { color: #FF0000; }",
+    )
+    .unwrap();
+
+    let heading = vec![
+        ("fontSize".to_string(), "24px".to_string()),
+        ("fontWeight".to_string(), "bold".to_string()),
+    ];
+    assert_eq!(
+        rules,
+        vec![
+            ("h1".to_string(), heading.clone()),
+            (".title".to_string(), heading),
+            (
+                "p".to_string(),
+                vec![("color".to_string(), "#FF0000".to_string())]
+            ),
+        ]
+    );
+
+    assert_eq!(parse_css("p { color: #FF0000;"), None);
+}
+
+#[test]
+fn formatspans_from_html_with_style_sheet() {
+    let mut style_sheet = StyleSheet::new();
+    let mut title = TextFormat::default();
+    title.apply_style_property("fontSize", "24px");
+    title.apply_style_property("fontWeight", "bold");
+    style_sheet.set_style(WStr::from_units(b".Title"), title);
+    let mut link = TextFormat::default();
+    link.apply_style_property("textDecoration", "underline");
+    style_sheet.set_style(WStr::from_units(b"a"), link);
+
+    let default_format = TextFormat {
+        font: Some(WString::from_utf8("Arial")),
+        size: Some(12.0),
+        ..Default::default()
+    };
+    let fs = FormatSpans::from_html(
+        WStr::from_units(b"<span class='title'>abc</span><a href='x'>def</a>ghi"),
+        default_format,
+        Some(&style_sheet),
+        false,
+    );
+
+    let title = fs.span(0).unwrap();
+    assert_eq!(title.size, 24.0);
+    assert!(title.bold);
+    let link = fs.span(1).unwrap();
+    assert_eq!(link.size, 12.0);
+    assert!(link.underline);
+    let plain = fs.span(2).unwrap();
+    assert!(!plain.bold && !plain.underline);
+}
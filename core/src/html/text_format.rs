@@ -2,6 +2,7 @@
 
 use crate::context::UpdateContext;
 use crate::html::iterators::TextSpanIter;
+use crate::html::stylesheet::StyleSheet;
 use crate::string::{Integer, SwfStrExt as _, Units, WStr, WString};
 use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
@@ -564,11 +565,15 @@ impl FormatSpans {
 
     /// Lower an HTML tree into text-span representation.
     ///
-    /// This is the "legacy" implementation of this process: it only looks for
-    /// a handful of presentational attributes in the HTML tree to generate
-    /// styling. There's also a `lower_from_css` that respects both
-    /// presentational markup and CSS stylesheets.
-    pub fn from_html(html: &WStr, default_format: TextFormat, is_multiline: bool) -> Self {
+    /// Styling is generated from a handful of presentational tags and attributes in
+    /// the HTML tree. If a style sheet is given, the styles of each element's tag and
+    /// `class` attribute are applied on top of the format it inherits.
+    pub fn from_html(
+        html: &WStr,
+        default_format: TextFormat,
+        style_sheet: Option<&StyleSheet>,
+        is_multiline: bool,
+    ) -> Self {
         let mut format_stack = vec![default_format.clone()];
         let mut text = WString::new();
         let mut spans: Vec<TextSpan> = Vec::new();
@@ -630,6 +635,10 @@ impl FormatSpans {
                         })
                     };
                     let mut format = format_stack.last().unwrap().clone();
+                    if let Some(style_sheet) = style_sheet {
+                        let tag = decode_to_wstr(e.name().into_inner());
+                        format = style_sheet.apply(format, &tag, attribute(b"class").as_deref());
+                    }
                     match &e.name().into_inner().to_ascii_lowercase()[..] {
                         b"br" => {
                             if is_multiline {
@@ -0,0 +1,137 @@
+//! Recording the input a movie receives, and replaying it later
+//!
+//! While recording, every event the player receives is written to an [`InputScript`] along
+//! with the frame it arrived before, and the seed of the random number generator. Replaying
+//! that script sends each event back on the same frame of a freshly loaded movie.
+//!
+//! For the replay to go the same way, the player runs on a fixed clock while recording or
+//! replaying: scripts and timers see exactly one frame's worth of time pass per frame,
+//! however long the frame actually took. Anything that comes from outside of the player,
+//! such as network responses and the current date, is not recorded.
+
+use crate::events::PlayerEvent;
+use crate::headless::InputScript;
+use std::time::Duration;
+
+pub struct InputLog {
+    script: InputScript,
+
+    /// Whether the script is being replayed, instead of recorded.
+    replaying: bool,
+
+    /// The index of the next event in the script to replay.
+    next_event: usize,
+
+    /// The number of frames run so far.
+    frame: u32,
+
+    /// The time that scripts see as having passed, in milliseconds.
+    time: f64,
+}
+
+impl InputLog {
+    /// Start recording a new script.
+    pub fn record() -> Self {
+        Self::new(InputScript::default(), false)
+    }
+
+    /// Replay a script that was recorded earlier.
+    pub fn replay(script: InputScript) -> Self {
+        Self::new(script, true)
+    }
+
+    fn new(script: InputScript, replaying: bool) -> Self {
+        Self {
+            script,
+            replaying,
+            next_event: 0,
+            frame: 0,
+            time: 0.0,
+        }
+    }
+
+    /// Start from the beginning, now that the root movie has loaded.
+    ///
+    /// Returns the seed that the player's random number generator should use, which is
+    /// taken from the script if it's being replayed.
+    pub fn start(&mut self, new_seed: u64) -> u64 {
+        self.frame = 0;
+        self.time = 0.0;
+        if self.replaying {
+            self.next_event = 0;
+            self.script.seed.unwrap_or(new_seed)
+        } else {
+            *self.script.seed.get_or_insert(new_seed)
+        }
+    }
+
+    /// Whether there are still events to replay, in which case live input is ignored.
+    pub fn is_replaying(&self) -> bool {
+        self.replaying && self.next_event < self.script.events().len()
+    }
+
+    /// Record an event, which will be replayed before the next frame.
+    pub fn record(&mut self, event: &PlayerEvent) {
+        if !self.replaying {
+            self.script.push(self.frame + 1, event.clone());
+        }
+    }
+
+    /// The replayed events to send before the next frame runs.
+    pub fn begin_frame(&mut self) -> Vec<PlayerEvent> {
+        self.frame += 1;
+        if !self.replaying {
+            return Vec::new();
+        }
+
+        let events: Vec<_> = self.script.events()[self.next_event..]
+            .iter()
+            .take_while(|event| event.frame <= self.frame)
+            .map(|event| event.event.clone())
+            .collect();
+        self.next_event += events.len();
+        events
+    }
+
+    /// Finish a frame that took `frame_time` milliseconds, returning the time that scripts
+    /// should now see as having passed.
+    pub fn end_frame(&mut self, frame_time: f64) -> Duration {
+        self.time += frame_time;
+        Duration::from_secs_f64(self.time / 1000.0)
+    }
+
+    /// The script that has been recorded so far, if recording.
+    pub fn recording(&self) -> Option<&InputScript> {
+        (!self.replaying).then_some(&self.script)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replays_recorded_events() {
+        let mut log = InputLog::record();
+        assert_eq!(log.start(7), 7);
+        log.begin_frame();
+        log.end_frame(10.0);
+        log.record(&PlayerEvent::MouseLeave);
+        log.begin_frame();
+
+        let script = InputScript::parse(&log.recording().unwrap().to_string()).unwrap();
+        assert_eq!(script.seed, Some(7));
+
+        let mut log = InputLog::replay(script);
+        assert_eq!(log.start(8), 7);
+        assert!(log.is_replaying());
+        assert!(log.begin_frame().is_empty());
+        assert_eq!(log.end_frame(10.0), Duration::from_millis(10));
+        assert!(matches!(
+            log.begin_frame().as_slice(),
+            [PlayerEvent::MouseLeave]
+        ));
+        assert!(!log.is_replaying());
+        assert!(log.recording().is_none());
+    }
+}
@@ -0,0 +1,189 @@
+//! Inspecting and editing the display list from outside of the player
+//!
+//! This is what the web extension's developer tools panel uses. Display objects are found by
+//! their path from the stage: the index of each one in its parent's render list.
+
+use crate::context::RenderContext;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use gc_arena::MutationContext;
+use ruffle_render::commands::CommandHandler;
+use ruffle_render::filters::Filter;
+use ruffle_render::matrix::Matrix;
+use serde::{Deserialize, Serialize};
+use swf::{Color, Rectangle, Twips};
+
+/// A snapshot of a display object and everything below it.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayNode {
+    pub kind: &'static str,
+    pub name: String,
+    pub depth: i32,
+    pub x: f64,
+    pub y: f64,
+    pub rotation: f64,
+    pub scale_x: f64,
+    pub scale_y: f64,
+    pub alpha: f64,
+    pub visible: bool,
+
+    /// The bounds on the stage, in pixels, as `[x_min, y_min, x_max, y_max]`.
+    pub bounds: [f64; 4],
+
+    pub filters: Vec<&'static str>,
+    pub children: Vec<DisplayNode>,
+}
+
+/// A property of a display object that can be edited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DisplayProperty {
+    X,
+    Y,
+    Rotation,
+    ScaleX,
+    ScaleY,
+    Alpha,
+    Visible,
+}
+
+pub(crate) fn snapshot<'gc>(
+    object: DisplayObject<'gc>,
+    gc_context: MutationContext<'gc, '_>,
+) -> DisplayNode {
+    let bounds = object.world_bounds();
+    let children = object
+        .as_container()
+        .map(|container| {
+            container
+                .iter_render_list()
+                .map(|child| snapshot(child, gc_context))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    DisplayNode {
+        kind: display_object_type(object),
+        name: object.name().to_string(),
+        depth: object.depth(),
+        x: object.x().to_pixels(),
+        y: object.y().to_pixels(),
+        rotation: object.rotation(gc_context).into(),
+        scale_x: object.scale_x(gc_context).percent(),
+        scale_y: object.scale_y(gc_context).percent(),
+        alpha: object.alpha(),
+        visible: object.visible(),
+        bounds: [
+            bounds.x_min.to_pixels(),
+            bounds.y_min.to_pixels(),
+            bounds.x_max.to_pixels(),
+            bounds.y_max.to_pixels(),
+        ],
+        filters: object.filters().iter().map(filter_name).collect(),
+        children,
+    }
+}
+
+/// Find a display object by its path from `root`.
+pub(crate) fn find<'gc>(root: DisplayObject<'gc>, path: &[usize]) -> Option<DisplayObject<'gc>> {
+    path.iter().try_fold(root, |object, &index| {
+        object.as_container()?.iter_render_list().nth(index)
+    })
+}
+
+pub(crate) fn set_property<'gc>(
+    object: DisplayObject<'gc>,
+    gc_context: MutationContext<'gc, '_>,
+    property: DisplayProperty,
+    value: f64,
+) {
+    match property {
+        DisplayProperty::X => object.set_x(gc_context, Twips::from_pixels(value)),
+        DisplayProperty::Y => object.set_y(gc_context, Twips::from_pixels(value)),
+        DisplayProperty::Rotation => object.set_rotation(gc_context, value.into()),
+        DisplayProperty::ScaleX => object.set_scale_x(gc_context, value.into()),
+        DisplayProperty::ScaleY => object.set_scale_y(gc_context, value.into()),
+        DisplayProperty::Alpha => object.set_alpha(gc_context, value),
+        DisplayProperty::Visible => object.set_visible(gc_context, value != 0.0),
+    }
+    if property != DisplayProperty::Visible {
+        // Keep the timeline from moving it back.
+        object.set_transformed_by_script(gc_context, true);
+    }
+}
+
+pub(crate) fn display_object_type(object: DisplayObject) -> &'static str {
+    match object {
+        DisplayObject::Stage(_) => "Stage",
+        DisplayObject::Bitmap(_) => "Bitmap",
+        DisplayObject::Avm1Button(_) => "Avm1Button",
+        DisplayObject::Avm2Button(_) => "Avm2Button",
+        DisplayObject::EditText(_) => "EditText",
+        DisplayObject::Graphic(_) => "Graphic",
+        DisplayObject::MorphShape(_) => "MorphShape",
+        DisplayObject::MovieClip(_) => "MovieClip",
+        DisplayObject::Text(_) => "Text",
+        DisplayObject::Video(_) => "Video",
+        DisplayObject::LoaderDisplay(_) => "LoaderDisplay",
+    }
+}
+
+pub(crate) fn filter_name(filter: &Filter) -> &'static str {
+    match filter {
+        Filter::BevelFilter(_) => "BevelFilter",
+        Filter::BlurFilter(_) => "BlurFilter",
+        Filter::ColorMatrixFilter(_) => "ColorMatrixFilter",
+        Filter::ConvolutionFilter(_) => "ConvolutionFilter",
+        Filter::DisplacementMapFilter(_) => "DisplacementMapFilter",
+        Filter::DropShadowFilter(_) => "DropShadowFilter",
+        Filter::GlowFilter(_) => "GlowFilter",
+        Filter::GradientBevelFilter(_) => "GradientBevelFilter",
+        Filter::GradientGlowFilter(_) => "GradientGlowFilter",
+        Filter::ShaderFilter(_) => "ShaderFilter",
+    }
+}
+
+/// Draw the outline of a display object's bounds on top of the stage.
+pub(crate) fn draw_debug_rect(
+    context: &mut RenderContext,
+    color: Color,
+    bounds: Rectangle<Twips>,
+    thickness: f32,
+) {
+    let width = bounds.width().to_pixels() as f32;
+    let height = bounds.height().to_pixels() as f32;
+    let thickness_twips = Twips::from_pixels(thickness as f64);
+
+    // Top
+    context.commands.draw_rect(
+        color,
+        Matrix::create_box(
+            width,
+            thickness,
+            0.0,
+            bounds.x_min,
+            bounds.y_min - thickness_twips,
+        ),
+    );
+    // Bottom
+    context.commands.draw_rect(
+        color,
+        Matrix::create_box(width, thickness, 0.0, bounds.x_min, bounds.y_max),
+    );
+    // Left
+    context.commands.draw_rect(
+        color,
+        Matrix::create_box(
+            thickness,
+            height,
+            0.0,
+            bounds.x_min - thickness_twips,
+            bounds.y_min,
+        ),
+    );
+    // Right
+    context.commands.draw_rect(
+        color,
+        Matrix::create_box(thickness, height, 0.0, bounds.x_max, bounds.y_min),
+    );
+}
@@ -14,14 +14,18 @@ extern crate downcast_rs;
 #[macro_use]
 extern crate num_derive;
 
+pub mod accessibility;
+pub mod api_usage;
 #[macro_use]
 mod avm1;
 mod avm2;
 mod binary_data;
 pub mod bitmap;
+mod camera;
 mod character;
 pub mod context;
 pub mod context_menu;
+pub mod debugger;
 mod drawing;
 mod ecma_conversions;
 pub(crate) mod either;
@@ -29,19 +33,32 @@ pub mod events;
 pub mod focus_tracker;
 mod font;
 mod frame_lifecycle;
+mod hds;
+pub mod headless;
 mod html;
+pub mod input_recording;
+pub mod inspector;
 mod library;
 pub mod limits;
 pub mod loader;
+pub mod local_connection;
 mod locale;
+mod mouse_cursor;
+mod mp4;
+mod net_connection;
 pub mod pixel_bender;
 mod player;
 mod prelude;
+pub mod printing;
+pub mod profiler;
+mod rtmp;
+pub mod save_state;
 pub mod socket;
 mod streams;
 pub mod string;
 pub mod tag_utils;
 pub mod timer;
+mod touch;
 mod types;
 mod vminterface;
 mod xml;
@@ -49,6 +66,7 @@ mod xml;
 pub mod backend;
 pub mod compatibility_rules;
 pub mod config;
+pub mod console;
 #[cfg(feature = "egui")]
 pub mod debug_ui;
 pub mod external;
@@ -1,10 +1,11 @@
 use crate::avm1::{PropertyMap as Avm1PropertyMap, PropertyMap};
 use crate::avm2::{ClassObject as Avm2ClassObject, Domain as Avm2Domain};
 use crate::backend::audio::SoundHandle;
+use crate::backend::ui::UiBackend;
 use crate::character::Character;
 
 use crate::display_object::{Bitmap, Graphic, MorphShape, TDisplayObject, Text};
-use crate::font::{Font, FontDescriptor};
+use crate::font::{DeviceFonts, Font, FontDescriptor};
 use crate::prelude::*;
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
@@ -14,6 +15,7 @@ use ruffle_render::bitmap::BitmapHandle;
 use ruffle_render::utils::remove_invalid_jpeg_data;
 
 use std::collections::HashMap;
+use std::rc::Rc;
 use std::sync::{Arc, Weak};
 use swf::CharacterId;
 use weak_table::{traits::WeakElement, PtrWeakKeyHashMap, WeakValueHashMap};
@@ -125,6 +127,10 @@ pub struct MovieLibrary<'gc> {
     jpeg_tables: Option<Vec<u8>>,
     fonts: HashMap<FontDescriptor, Font<'gc>>,
     avm2_domain: Option<Avm2Domain<'gc>>,
+
+    /// The number of runtime shared libraries that this movie is still importing assets from.
+    /// The movie stops preloading until they have all finished loading.
+    pending_imports: usize,
 }
 
 impl<'gc> MovieLibrary<'gc> {
@@ -135,6 +141,7 @@ impl<'gc> MovieLibrary<'gc> {
             jpeg_tables: None,
             fonts: HashMap::new(),
             avm2_domain: None,
+            pending_imports: 0,
         }
     }
 
@@ -160,6 +167,30 @@ impl<'gc> MovieLibrary<'gc> {
         self.export_characters.insert(export_name, id, false);
     }
 
+    /// Registers a character that was imported from the library of another movie, under the
+    /// ID and export name that this movie knows it by.
+    pub fn register_import(
+        &mut self,
+        id: CharacterId,
+        export_name: AvmString<'gc>,
+        character: Character<'gc>,
+    ) {
+        self.register_character(id, character);
+        self.register_export(id, export_name);
+    }
+
+    pub fn begin_import(&mut self) {
+        self.pending_imports += 1;
+    }
+
+    pub fn finish_import(&mut self) {
+        self.pending_imports = self.pending_imports.saturating_sub(1);
+    }
+
+    pub fn has_pending_imports(&self) -> bool {
+        self.pending_imports > 0
+    }
+
     #[allow(dead_code)]
     pub fn characters(&self) -> &HashMap<CharacterId, Character<'gc>> {
         &self.characters
@@ -387,6 +418,20 @@ pub struct Library<'gc> {
     /// The embedded device font.
     device_font: Option<Font<'gc>>,
 
+    /// Fonts installed on the system, for text rendered with device fonts.
+    system_fonts: DeviceFonts,
+
+    /// Device fonts that were found on the system, by the name and style they were requested with.
+    device_fonts: HashMap<FontDescriptor, Font<'gc>>,
+
+    /// The embedded device font with fallbacks for the characters it doesn't have, and the
+    /// number of fallbacks it was created with.
+    default_device_font: Option<(usize, Font<'gc>)>,
+
+    /// Embedded fonts that were registered with `Font.registerFont`, which can be used
+    /// by text in any movie.
+    registered_fonts: HashMap<FontDescriptor, Font<'gc>>,
+
     /// A list of the symbols associated with specific AVM2 constructor
     /// prototypes.
     avm2_class_registry: Avm2ClassRegistry<'gc>,
@@ -399,6 +444,15 @@ unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
             val.trace(cc);
         }
         self.device_font.trace(cc);
+        for (_, font) in self.device_fonts.iter() {
+            font.trace(cc);
+        }
+        if let Some((_, font)) = &self.default_device_font {
+            font.trace(cc);
+        }
+        for (_, font) in self.registered_fonts.iter() {
+            font.trace(cc);
+        }
         self.avm2_class_registry.trace(cc);
     }
 }
@@ -408,6 +462,10 @@ impl<'gc> Library<'gc> {
         Self {
             movie_libraries: PtrWeakKeyHashMap::new(),
             device_font: None,
+            system_fonts: DeviceFonts::default(),
+            device_fonts: HashMap::new(),
+            default_device_font: None,
+            registered_fonts: HashMap::new(),
             avm2_class_registry: Default::default(),
         }
     }
@@ -426,6 +484,31 @@ impl<'gc> Library<'gc> {
         self.movie_libraries.keys().collect()
     }
 
+    /// Registers an embedded font so that text in any movie can use it.
+    pub fn register_font(&mut self, font: Font<'gc>) {
+        self.registered_fonts
+            .entry(font.descriptor().clone())
+            .or_insert(font);
+    }
+
+    /// Find a registered font by its name and parameters.
+    pub fn get_registered_font_by_name(
+        &self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<Font<'gc>> {
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+        if let Some(font) = self.registered_fonts.get(&descriptor) {
+            return Some(*font);
+        }
+        self.registered_fonts
+            .iter()
+            .find(|(d, _)| d.class() == name)
+            .map(|(_, f)| f)
+            .copied()
+    }
+
     /// Returns the device font for use when a font is unavailable.
     pub fn device_font(&self) -> Option<Font<'gc>> {
         self.device_font
@@ -434,6 +517,48 @@ impl<'gc> Library<'gc> {
     /// Sets the device font.
     pub fn set_device_font(&mut self, font: Font<'gc>) {
         self.device_font = Some(font);
+        self.default_device_font = None;
+    }
+
+    /// Returns the font to render text with the given device font name and style.
+    ///
+    /// This is a font installed on the system if one matches the name, or the embedded
+    /// device font otherwise. Either way, characters that the font doesn't have are drawn
+    /// with fallback fonts installed on the system.
+    pub fn get_device_font(
+        &mut self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+        ui: &dyn UiBackend,
+        gc_context: MutationContext<'gc, '_>,
+    ) -> Option<Font<'gc>> {
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+        if let Some(font) = self.device_fonts.get(&descriptor) {
+            return Some(*font);
+        }
+
+        let face = self.system_fonts.primary_face(ui, name, is_bold, is_italic);
+        let mut fallbacks = self.system_fonts.fallback_faces(ui);
+        if let Some(face) = face {
+            fallbacks.retain(|fallback| !Rc::ptr_eq(fallback, &face));
+            let font = Font::from_font_face(gc_context, face, fallbacks);
+            self.device_fonts.insert(descriptor, font);
+            return Some(font);
+        }
+
+        // Fonts may still become available later (e.g. while they're being loaded on web),
+        // so the embedded font is only reused while the fallbacks are unchanged.
+        match self.default_device_font {
+            Some((num_fallbacks, font)) if num_fallbacks == fallbacks.len() => Some(font),
+            _ => {
+                let font = self.device_font?;
+                let num_fallbacks = fallbacks.len();
+                let font = font.with_fallbacks(gc_context, fallbacks);
+                self.default_device_font = Some((num_fallbacks, font));
+                Some(font)
+            }
+        }
     }
 
     /// Get the AVM2 class registry.
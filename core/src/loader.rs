@@ -5,14 +5,19 @@ use crate::avm1::ExecutionReason;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{Object, SoundObject, TObject, Value};
 use crate::avm2::bytearray::ByteArrayStorage;
+use crate::avm2::globals::flash::net::file_reference::set_selected_file;
 use crate::avm2::object::{
-    BitmapDataObject, ByteArrayObject, EventObject as Avm2EventObject, LoaderStream, TObject as _,
+    ArrayObject as Avm2ArrayObject, BitmapDataObject, ByteArrayObject,
+    EventObject as Avm2EventObject, LoaderStream, TObject as _,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Object as Avm2Object,
-    Value as Avm2Value,
+    Activation as Avm2Activation, Avm2, Domain as Avm2Domain, Error as Avm2Error,
+    Multiname as Avm2Multiname, Object as Avm2Object, Value as Avm2Value,
 };
-use crate::backend::navigator::{OwnedFuture, Request};
+use crate::backend::navigator::{
+    ErrorResponse, OwnedFuture, Request, StreamingResponse, SuccessResponse,
+};
+use crate::backend::ui::{DialogResultFuture, SelectedFile};
 use crate::bitmap::bitmap_data::Color;
 use crate::bitmap::bitmap_data::{BitmapData, BitmapDataWrapper};
 use crate::context::{ActionQueue, ActionType, UpdateContext};
@@ -21,9 +26,10 @@ use crate::display_object::{
 };
 use crate::events::ClipEvent;
 use crate::frame_lifecycle::catchup_display_object_to_frame;
+use crate::hds::{self, BootstrapInfo, BootstrapSource, Manifest};
 use crate::limits::ExecutionLimit;
 use crate::player::Player;
-use crate::streams::NetStream;
+use crate::streams::{flv_tag, AppendBytesAction, NetStream};
 use crate::string::AvmString;
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
@@ -35,6 +41,7 @@ use std::fmt;
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 use swf::read::{extract_swz, read_compression_type};
+use swf::CharacterId;
 use thiserror::Error;
 use url::{form_urlencoded, ParseError, Url};
 
@@ -147,9 +154,18 @@ pub enum Error {
     #[error("Non-data loader spawned as data loader")]
     NotLoadDataLoader,
 
+    #[error("Non-stream loader spawned as stream loader")]
+    NotLoadStreamLoader,
+
     #[error("Non-sound loader spawned as sound loader")]
     NotSoundLoader,
 
+    #[error("Non-FileReference loader spawned as FileReference loader")]
+    NotFileReferenceLoader,
+
+    #[error("Non-asset import loader spawned as asset import loader")]
+    NotAssetImportLoader,
+
     #[error("Non-NetStream loader spawned as NetStream loader")]
     NotNetStreamLoader,
 
@@ -171,6 +187,9 @@ pub enum Error {
     #[error("Invalid sound: {0}")]
     InvalidSound(#[from] crate::backend::audio::DecodeError),
 
+    #[error("Invalid HDS stream: {0}")]
+    InvalidHds(#[from] crate::hds::HdsError),
+
     #[error("Unexpected content of type {1}, expected {0}")]
     UnexpectedData(ContentType, ContentType),
 
@@ -221,9 +240,12 @@ impl<'gc> LoadManager<'gc> {
             | Loader::Form { self_handle, .. }
             | Loader::LoadVars { self_handle, .. }
             | Loader::LoadURLLoader { self_handle, .. }
+            | Loader::LoadURLStream { self_handle, .. }
             | Loader::SoundAvm1 { self_handle, .. }
             | Loader::SoundAvm2 { self_handle, .. }
             | Loader::NetStream { self_handle, .. }
+            | Loader::FileReference { self_handle, .. }
+            | Loader::AssetImport { self_handle, .. }
             | Loader::MovieUnloader { self_handle, .. } => *self_handle = Some(handle),
         }
         handle
@@ -320,6 +342,29 @@ impl<'gc> LoadManager<'gc> {
         }
     }
 
+    /// Kick off a load of the runtime shared library that a movie imports assets from.
+    ///
+    /// `imports` are the IDs that the importing movie uses for the assets, along with the
+    /// names that the library exports them under.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_asset_import(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        importer: Arc<SwfMovie>,
+        request: Request,
+        imports: Vec<(CharacterId, AvmString<'gc>)>,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::AssetImport {
+            self_handle: None,
+            importer,
+            imports,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.asset_import_loader(player, request)
+    }
+
     /// Kick off a form data load into an AVM1 object.
     ///
     /// Returns the loader's async process, which you will need to spawn.
@@ -376,6 +421,26 @@ impl<'gc> LoadManager<'gc> {
         loader.load_url_loader(player, request, data_format)
     }
 
+    /// Kick off a data load into a `URLStream`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_data_into_url_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        buffer: Avm2Object<'gc>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::LoadURLStream {
+            self_handle: None,
+            target_object,
+            buffer,
+        };
+        let handle = self.add_loader(loader);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.load_url_stream(player, request)
+    }
+
     /// Kick off an AVM1 audio load.
     ///
     /// Returns the loader's async process, which you will need to spawn.
@@ -428,6 +493,93 @@ impl<'gc> LoadManager<'gc> {
         loader.stream_loader(player, request)
     }
 
+    /// Wait for the user to pick files for a `FileReference` or `FileReferenceList`,
+    /// firing `select` once they have, or `cancel` if they didn't.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn select_file_dialog(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        dialog: DialogResultFuture<Vec<SelectedFile>>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = self.add_file_reference_loader(target_object);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.select_file_loader(player, dialog)
+    }
+
+    /// Wait for the user to save a file for a `FileReference`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn save_file_dialog(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        dialog: DialogResultFuture<Option<String>>,
+        size: usize,
+    ) -> OwnedFuture<(), Error> {
+        let handle = self.add_file_reference_loader(target_object);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.save_file_loader(player, dialog, size)
+    }
+
+    /// Make the contents of the file picked for a `FileReference` available as its `data`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_file_reference(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = self.add_file_reference_loader(target_object);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.file_reference_loader(player)
+    }
+
+    /// Kick off an upload of the file picked for a `FileReference`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn upload_file(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = self.add_file_reference_loader(target_object);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.upload_loader(player, request)
+    }
+
+    /// Kick off a download into a file that the user picks for a `FileReference`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn download_file(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        request: Request,
+        file_name: String,
+    ) -> OwnedFuture<(), Error> {
+        let handle = self.add_file_reference_loader(target_object);
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.download_loader(player, request, file_name)
+    }
+
+    /// Cancel every operation of a `FileReference`, so that it fires no further events.
+    pub fn cancel_file_reference(&mut self, target: Avm2Object<'gc>) {
+        self.0.retain(|_, loader| {
+            !matches!(loader, Loader::FileReference { target_object, .. }
+                if Avm2Object::ptr_eq(*target_object, target))
+        });
+    }
+
+    fn add_file_reference_loader(&mut self, target_object: Avm2Object<'gc>) -> Handle {
+        self.add_loader(Loader::FileReference {
+            self_handle: None,
+            target_object,
+        })
+    }
+
     /// Process tags on all loaders in the Parsing phase.
     ///
     /// Returns true if *all* loaders finished preloading.
@@ -562,6 +714,21 @@ pub enum Loader<'gc> {
         target_object: Avm2Object<'gc>,
     },
 
+    /// Loader that is streaming data into a `URLStream`, appending it to its buffer
+    /// as it arrives.
+    LoadURLStream {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The target `URLStream` to stream data into.
+        target_object: Avm2Object<'gc>,
+
+        /// The buffer of the `URLStream` when the load started. The load stops once
+        /// the stream starts another one.
+        buffer: Avm2Object<'gc>,
+    },
+
     /// Loader that is loading an MP3 into an AVM1 Sound object.
     SoundAvm1 {
         /// The handle to refer to this loader instance.
@@ -592,6 +759,32 @@ pub enum Loader<'gc> {
         target_stream: NetStream<'gc>,
     },
 
+    /// Loader that is picking, saving or transferring a file for a `FileReference`
+    /// or `FileReferenceList`.
+    FileReference {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The `FileReference` or `FileReferenceList` to fire events on.
+        target_object: Avm2Object<'gc>,
+    },
+
+    /// Loader that is loading a runtime shared library to import assets from.
+    AssetImport {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The movie that imports the assets.
+        #[collect(require_static)]
+        importer: Arc<SwfMovie>,
+
+        /// The IDs that the importing movie uses for the assets, and the names that the
+        /// library exports them under.
+        imports: Vec<(CharacterId, AvmString<'gc>)>,
+    },
+
     /// Loader that is unloading a MovieClip.
     MovieUnloader {
         /// The handle to refer to this loader instance.
@@ -929,6 +1122,87 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Creates a future for loading a runtime shared library and importing assets from it.
+    fn asset_import_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::AssetImport { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotAssetImportLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let response = fetch.await.map_err(|e| e.error);
+
+            player.lock().unwrap().update(|uc| {
+                let (importer, imports) = match uc.load_manager.get_loader(handle) {
+                    Some(Loader::AssetImport {
+                        importer, imports, ..
+                    }) => (importer.clone(), imports.clone()),
+                    None => return Err(Error::Cancelled),
+                    _ => return Err(Error::NotAssetImportLoader),
+                };
+
+                // The importing movie continues loading even if the library couldn't be loaded,
+                // just without the assets.
+                let result = Self::import_assets(uc, importer.clone(), response, imports);
+                uc.library.library_for_movie_mut(importer).finish_import();
+                result
+            })
+        })
+    }
+
+    /// Loads the movie of a runtime shared library, and registers the assets it exports in the
+    /// library of the importing movie.
+    fn import_assets(
+        uc: &mut UpdateContext<'_, 'gc>,
+        importer: Arc<SwfMovie>,
+        response: Result<SuccessResponse, Error>,
+        imports: Vec<(CharacterId, AvmString<'gc>)>,
+    ) -> Result<(), Error> {
+        let response = response?;
+        ContentType::sniff(&response.body).expect(ContentType::Swf)?;
+        let movie = Arc::new(SwfMovie::from_data(
+            &response.body,
+            response.url,
+            Some(importer.url().to_string()),
+        )?);
+
+        // The library's own frames never play, but its definitions need to be loaded.
+        let library_clip = MovieClip::new(movie.clone(), uc.gc_context);
+        library_clip.preload(uc, &mut ExecutionLimit::none());
+
+        for (id, name) in imports {
+            let character = uc
+                .library
+                .library_for_movie(movie.clone())
+                .and_then(|library| library.character_by_export_name(name))
+                .cloned();
+            match character {
+                Some(character) => uc
+                    .library
+                    .library_for_movie_mut(importer.clone())
+                    .register_import(id, name, character),
+                None => tracing::warn!(
+                    "Can't import {} from {}: it isn't exported",
+                    name,
+                    movie.url()
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
     /// Creates a future for a LoadVars load call.
     fn load_vars_loader(
         &mut self,
@@ -1145,6 +1419,13 @@ impl<'gc> Loader<'gc> {
                                 ],
                             )
                             .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                        set_response_info(
+                            &mut activation,
+                            http_status_evt,
+                            &response.url,
+                            &response.headers,
+                        )
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
 
                         Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
 
@@ -1181,6 +1462,8 @@ impl<'gc> Loader<'gc> {
                                 ],
                             )
                             .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                        set_response_info(&mut activation, http_status_evt, &response.url, &[])
+                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
 
                         Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
 
@@ -1209,6 +1492,26 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    /// Creates a future for a LoadURLStream load call.
+    fn load_url_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::LoadURLStream { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotLoadStreamLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(url_stream_loader(player, handle, request))
+    }
+
     /// Creates a future for a Sound load call.
     fn sound_loader_avm1(
         &mut self,
@@ -1371,13 +1674,14 @@ impl<'gc> Loader<'gc> {
             let fetch = player.lock().unwrap().navigator().fetch(request);
             let response = fetch.await;
 
+            if let Ok(response) = &response {
+                if hds::is_manifest(&response.body) {
+                    return hds_stream_loader(player, handle, &response.url, &response.body).await;
+                }
+            }
+
             player.lock().unwrap().update(|uc| {
-                let loader = uc.load_manager.get_loader(handle);
-                let stream = match loader {
-                    Some(&Loader::NetStream { target_stream, .. }) => target_stream,
-                    None => return Err(Error::Cancelled),
-                    _ => return Err(Error::NotNetStreamLoader),
-                };
+                let stream = Loader::target_stream(uc, handle)?;
 
                 match response {
                     Ok(mut response) => {
@@ -1393,6 +1697,390 @@ impl<'gc> Loader<'gc> {
         })
     }
 
+    fn file_reference_handle(&self) -> Result<Handle, Error> {
+        match self {
+            Loader::FileReference { self_handle, .. } => {
+                Ok(self_handle.expect("Loader not self-introduced"))
+            }
+            _ => Err(Error::NotFileReferenceLoader),
+        }
+    }
+
+    /// Find the `FileReference` or `FileReferenceList` of a file loader, removing the loader
+    /// as it's done once the events it's about to fire are fired.
+    fn take_file_reference(
+        uc: &mut UpdateContext<'_, 'gc>,
+        handle: Index,
+    ) -> Result<Avm2Object<'gc>, Error> {
+        match uc.load_manager.get_loader(handle) {
+            Some(&Loader::FileReference { target_object, .. }) => {
+                uc.load_manager.0.remove(handle);
+                Ok(target_object)
+            }
+            None => Err(Error::Cancelled),
+            _ => Err(Error::NotFileReferenceLoader),
+        }
+    }
+
+    /// Creates a future for picking files for a `FileReference` or `FileReferenceList`.
+    fn select_file_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        dialog: DialogResultFuture<Vec<SelectedFile>>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self.file_reference_handle() {
+            Ok(handle) => handle,
+            Err(e) => return Box::pin(async { Err(e) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let files = dialog.await;
+
+            player.lock().unwrap().update(|uc| {
+                let mut target = Loader::take_file_reference(uc, handle)?;
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+                if files.is_empty() {
+                    let cancel_evt =
+                        Avm2EventObject::bare_default_event(&mut activation.context, "cancel");
+                    Avm2::dispatch_event(&mut activation.context, cancel_evt, target);
+                    return Ok(());
+                }
+
+                let filereference = activation.avm2().classes().filereference;
+                if target.is_of_type(
+                    filereference.inner_class_definition(),
+                    &mut activation.context,
+                ) {
+                    if let Some(file) = files.into_iter().next() {
+                        set_selected_file(&mut activation, target, file)
+                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                    }
+                } else {
+                    // A `FileReferenceList` gets a `FileReference` for each file.
+                    let mut file_list = Vec::with_capacity(files.len());
+                    for file in files {
+                        let file_reference = filereference
+                            .construct(&mut activation, &[])
+                            .and_then(|file_reference| {
+                                set_selected_file(&mut activation, file_reference, file)?;
+                                Ok(file_reference)
+                            })
+                            .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                        file_list.push(file_reference);
+                    }
+                    let file_list = Avm2ArrayObject::from_storage(
+                        &mut activation,
+                        file_list.into_iter().collect(),
+                    )
+                    .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                    let ns = activation.avm2().flash_net_internal;
+                    target
+                        .set_property(
+                            &Avm2Multiname::new(ns, "_fileList"),
+                            file_list.into(),
+                            &mut activation,
+                        )
+                        .map_err(|e| Error::Avm2Error(e.to_string()))?;
+                }
+
+                let select_evt =
+                    Avm2EventObject::bare_default_event(&mut activation.context, "select");
+                Avm2::dispatch_event(&mut activation.context, select_evt, target);
+                Ok(())
+            })
+        })
+    }
+
+    /// Creates a future for saving a file for a `FileReference`.
+    fn save_file_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        dialog: DialogResultFuture<Option<String>>,
+        size: usize,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self.file_reference_handle() {
+            Ok(handle) => handle,
+            Err(e) => return Box::pin(async { Err(e) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let file_name = dialog.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::take_file_reference(uc, handle)?;
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                Loader::file_saved(&mut activation, target, file_name, size)
+                    .map_err(|e| Error::Avm2Error(e.to_string()))
+            })
+        })
+    }
+
+    /// Fires the events of a `FileReference` that saved a file, or `cancel` if it wasn't saved.
+    fn file_saved(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        mut target: Avm2Object<'gc>,
+        file_name: Option<String>,
+        size: usize,
+    ) -> Result<(), Avm2Error<'gc>> {
+        let Some(file_name) = file_name else {
+            let cancel_evt = Avm2EventObject::bare_default_event(&mut activation.context, "cancel");
+            Avm2::dispatch_event(&mut activation.context, cancel_evt, target);
+            return Ok(());
+        };
+
+        let ns = activation.avm2().flash_net_internal;
+        let file_name = AvmString::new_utf8(activation.context.gc_context, file_name);
+        target.set_property(
+            &Avm2Multiname::new(ns, "_name"),
+            file_name.into(),
+            activation,
+        )?;
+        target.set_property(&Avm2Multiname::new(ns, "_size"), size.into(), activation)?;
+
+        let select_evt = Avm2EventObject::bare_default_event(&mut activation.context, "select");
+        Avm2::dispatch_event(&mut activation.context, select_evt, target);
+        Loader::dispatch_file_transfer_events(activation, target, size)?;
+        let complete_evt = Avm2EventObject::bare_default_event(&mut activation.context, "complete");
+        Avm2::dispatch_event(&mut activation.context, complete_evt, target);
+        Ok(())
+    }
+
+    /// Fires the `open` and `progress` events of a `FileReference` that transferred a file.
+    fn dispatch_file_transfer_events(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        target: Avm2Object<'gc>,
+        size: usize,
+    ) -> Result<(), Avm2Error<'gc>> {
+        let open_evt = Avm2EventObject::bare_default_event(&mut activation.context, "open");
+        Avm2::dispatch_event(&mut activation.context, open_evt, target);
+
+        // FIXME - we should fire "progress" events as the file is transferred, not just at the end
+        let progress_evt = activation.avm2().classes().progressevent.construct(
+            activation,
+            &[
+                "progress".into(),
+                false.into(),
+                false.into(),
+                size.into(),
+                size.into(),
+            ],
+        )?;
+        Avm2::dispatch_event(&mut activation.context, progress_evt, target);
+        Ok(())
+    }
+
+    /// Fires an `ioError` event on a `FileReference` whose file couldn't be transferred.
+    fn dispatch_file_io_error(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        target: Avm2Object<'gc>,
+    ) -> Result<(), Avm2Error<'gc>> {
+        let io_error_evt = activation.avm2().classes().ioerrorevent.construct(
+            activation,
+            &[
+                "ioError".into(),
+                false.into(),
+                false.into(),
+                "Error #2038: File I/O Error.".into(),
+                2038.into(),
+            ],
+        )?;
+        Avm2::dispatch_event(&mut activation.context, io_error_evt, target);
+        Ok(())
+    }
+
+    /// Makes the contents of the file picked for a `FileReference` available as its `data`.
+    fn file_loaded(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        mut target: Avm2Object<'gc>,
+    ) -> Result<(), Avm2Error<'gc>> {
+        let ns = activation.avm2().flash_net_internal;
+        let contents = target.get_property(&Avm2Multiname::new(ns, "_contents"), activation)?;
+        let size = contents
+            .as_object()
+            .and_then(|contents| contents.as_bytearray().map(|bytes| bytes.len()))
+            .unwrap_or_default();
+
+        Loader::dispatch_file_transfer_events(activation, target, size)?;
+        target.set_property(&Avm2Multiname::new(ns, "_data"), contents, activation)?;
+        let complete_evt = Avm2EventObject::bare_default_event(&mut activation.context, "complete");
+        Avm2::dispatch_event(&mut activation.context, complete_evt, target);
+        Ok(())
+    }
+
+    /// Fires the events of a `FileReference` whose upload finished.
+    fn file_uploaded(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        target: Avm2Object<'gc>,
+        response: Result<SuccessResponse, ErrorResponse>,
+        size: usize,
+    ) -> Result<(), Avm2Error<'gc>> {
+        let (status, redirected) = match &response {
+            Ok(response) => (response.status, response.redirected),
+            Err(response) => match response.error {
+                Error::HttpNotOk(_, status, redirected) => (status, redirected),
+                _ => (0, false),
+            },
+        };
+
+        if response.is_ok() {
+            Loader::dispatch_file_transfer_events(activation, target, size)?;
+        }
+
+        let http_status_evt = activation.avm2().classes().httpstatusevent.construct(
+            activation,
+            &[
+                "httpStatus".into(),
+                false.into(),
+                false.into(),
+                status.into(),
+                redirected.into(),
+            ],
+        )?;
+        Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
+
+        let Ok(response) = response else {
+            return Loader::dispatch_file_io_error(activation, target);
+        };
+
+        let complete_evt = Avm2EventObject::bare_default_event(&mut activation.context, "complete");
+        Avm2::dispatch_event(&mut activation.context, complete_evt, target);
+
+        let data = AvmString::new_utf8_bytes(activation.context.gc_context, &response.body);
+        let upload_complete_evt = activation.avm2().classes().dataevent.construct(
+            activation,
+            &[
+                "uploadCompleteData".into(),
+                false.into(),
+                false.into(),
+                data.into(),
+            ],
+        )?;
+        Avm2::dispatch_event(&mut activation.context, upload_complete_evt, target);
+        Ok(())
+    }
+
+    /// Creates a future for loading the contents of the file picked for a `FileReference`.
+    fn file_reference_loader(&mut self, player: Weak<Mutex<Player>>) -> OwnedFuture<(), Error> {
+        let handle = match self.file_reference_handle() {
+            Ok(handle) => handle,
+            Err(e) => return Box::pin(async { Err(e) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::take_file_reference(uc, handle)?;
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                Loader::file_loaded(&mut activation, target)
+                    .map_err(|e| Error::Avm2Error(e.to_string()))
+            })
+        })
+    }
+
+    /// Creates a future for uploading the file picked for a `FileReference`.
+    fn upload_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self.file_reference_handle() {
+            Ok(handle) => handle,
+            Err(e) => return Box::pin(async { Err(e) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let size = request.body().as_ref().map_or(0, |(body, _)| body.len());
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let response = fetch.await;
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::take_file_reference(uc, handle)?;
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+                Loader::file_uploaded(&mut activation, target, response, size)
+                    .map_err(|e| Error::Avm2Error(e.to_string()))
+            })
+        })
+    }
+
+    /// Creates a future for downloading a file for a `FileReference`, and saving it
+    /// where the user picks.
+    fn download_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        request: Request,
+        file_name: String,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self.file_reference_handle() {
+            Ok(handle) => handle,
+            Err(e) => return Box::pin(async { Err(e) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let fetch = player.lock().unwrap().navigator().fetch(request);
+            let response = fetch.await;
+
+            let dialog = match response {
+                Ok(response) => {
+                    let size = response.body.len();
+                    player
+                        .lock()
+                        .unwrap()
+                        .ui_mut()
+                        .display_file_save_dialog(file_name, response.body)
+                        .map(|dialog| (dialog, size))
+                }
+                Err(_) => None,
+            };
+            let saved = match dialog {
+                Some((dialog, size)) => Some((dialog.await, size)),
+                None => None,
+            };
+
+            player.lock().unwrap().update(|uc| {
+                let target = Loader::take_file_reference(uc, handle)?;
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+
+                let result = match saved {
+                    Some((file_name, size)) => {
+                        Loader::file_saved(&mut activation, target, file_name, size)
+                    }
+                    None => Loader::dispatch_file_io_error(&mut activation, target),
+                };
+                result.map_err(|e| Error::Avm2Error(e.to_string()))
+            })
+        })
+    }
+
+    /// Find the `NetStream` that a stream loader is loading into.
+    fn target_stream(uc: &UpdateContext<'_, 'gc>, handle: Index) -> Result<NetStream<'gc>, Error> {
+        match uc.load_manager.get_loader(handle) {
+            Some(&Loader::NetStream { target_stream, .. }) => Ok(target_stream),
+            None => Err(Error::Cancelled),
+            _ => Err(Error::NotNetStreamLoader),
+        }
+    }
+
     /// Report a movie loader start event to script code.
     fn movie_loader_start(handle: Index, uc: &mut UpdateContext<'_, 'gc>) -> Result<(), Error> {
         let me = uc.load_manager.get_loader_mut(handle);
@@ -1596,6 +2284,7 @@ impl<'gc> Loader<'gc> {
                         bitmap.height(),
                         transparency,
                         bitmap.as_colors().map(Color::from).collect(),
+                        activation.context.stage.bitmap_modifications(),
                     );
                     let bitmapdata_wrapper = BitmapDataWrapper::new(GcCell::new(
                         activation.context.gc_context,
@@ -2073,3 +2762,323 @@ impl<'gc> Loader<'gc> {
         }
     }
 }
+
+/// Play an HDS stream by fetching each fragment listed in its manifest, and
+/// appending them to the stream in order.
+///
+/// Live streams are not refreshed, so only the fragments available when the
+/// stream was started are played.
+async fn hds_stream_loader(
+    player: Arc<Mutex<Player>>,
+    handle: Index,
+    manifest_url: &str,
+    manifest: &[u8],
+) -> Result<(), Error> {
+    let manifest_url = Url::parse(manifest_url).map_err(hds::HdsError::from)?;
+    let manifest = Manifest::parse(manifest)?;
+    let media = manifest.default_media()?;
+
+    let bootstrap = match manifest.bootstrap_for(media)? {
+        BootstrapSource::Inline(bootstrap) => bootstrap.clone(),
+        BootstrapSource::Url(url) => {
+            let url = manifest
+                .base_url(&manifest_url)?
+                .join(url)
+                .map_err(hds::HdsError::from)?;
+            let fetch = player
+                .lock()
+                .unwrap()
+                .navigator()
+                .fetch(Request::get(url.to_string()));
+            fetch.await.map_err(|response| response.error)?.body
+        }
+    };
+    let bootstrap = BootstrapInfo::parse(&bootstrap)?;
+    if bootstrap.is_live {
+        tracing::warn!("HDS live stream {} will not be refreshed", manifest_url);
+    }
+
+    player.lock().unwrap().update(|uc| {
+        let stream = Loader::target_stream(uc, handle)?;
+        stream.start_live(uc.gc_context);
+        if let Some(metadata) = &media.metadata {
+            stream.append_data(&flv_tag(18, 0, metadata));
+        }
+        Ok(())
+    })?;
+
+    for (i, (segment, fragment)) in bootstrap.fragments().into_iter().enumerate() {
+        let url = manifest.fragment_url(&manifest_url, media, segment, fragment)?;
+        let fetch = player
+            .lock()
+            .unwrap()
+            .navigator()
+            .fetch(Request::get(url.to_string()));
+        let response = fetch.await;
+
+        player.lock().unwrap().update(|uc| {
+            let stream = Loader::target_stream(uc, handle)?;
+            match response.map_err(|response| response.error) {
+                Ok(response) => {
+                    stream.append_data(&hds::fragment_tags(&response.body)?);
+                    if i == 0 {
+                        stream.trigger_status_event(
+                            uc,
+                            &[("code", "NetStream.Buffer.Full"), ("level", "status")],
+                        );
+                    }
+                    Ok(())
+                }
+                Err(error) => {
+                    stream.append_bytes_action(uc.gc_context, AppendBytesAction::EndSequence);
+                    Err(error)
+                }
+            }
+        })?;
+    }
+
+    player.lock().unwrap().update(|uc| {
+        let stream = Loader::target_stream(uc, handle)?;
+        stream.append_bytes_action(uc.gc_context, AppendBytesAction::EndSequence);
+        Ok(())
+    })
+}
+
+/// Set the response headers and URL of an `HTTPStatusEvent`.
+fn set_response_info<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    mut http_status_evt: Avm2Object<'gc>,
+    url: &str,
+    headers: &[(String, String)],
+) -> Result<(), Avm2Error<'gc>> {
+    let urlrequestheader = activation.avm2().classes().urlrequestheader;
+    let mut response_headers = Vec::with_capacity(headers.len());
+    for (name, value) in headers {
+        let name = AvmString::new_utf8(activation.context.gc_context, name);
+        let value = AvmString::new_utf8(activation.context.gc_context, value);
+        let header = urlrequestheader.construct(activation, &[name.into(), value.into()])?;
+        response_headers.push(header);
+    }
+    let response_headers =
+        Avm2ArrayObject::from_storage(activation, response_headers.into_iter().collect())?;
+    http_status_evt.set_public_property("responseHeaders", response_headers.into(), activation)?;
+    let url = AvmString::new_utf8(activation.context.gc_context, url);
+    http_status_evt.set_public_property("responseURL", url.into(), activation)?;
+    Ok(())
+}
+
+/// The `URLStream` of a `LoadURLStream` loader, if it is still connected and hasn't
+/// started another load since.
+fn active_url_stream<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    handle: Index,
+) -> Result<Option<Avm2Object<'gc>>, Avm2Error<'gc>> {
+    let Some(&Loader::LoadURLStream {
+        target_object,
+        buffer,
+        ..
+    }) = activation.context.load_manager.get_loader(handle)
+    else {
+        return Ok(None);
+    };
+
+    let ns = activation.avm2().flash_net_internal;
+    let connected = target_object
+        .get_property(&Avm2Multiname::new(ns, "_connected"), activation)?
+        .coerce_to_boolean();
+    let current_buffer = target_object
+        .get_property(&Avm2Multiname::new(ns, "_buffer"), activation)?
+        .as_object();
+    let is_same_load = current_buffer.map_or(false, |current| Avm2Object::ptr_eq(current, buffer));
+    Ok((connected && is_same_load).then_some(target_object))
+}
+
+/// Disconnect a `URLStream` after its load failed, and dispatch the events for the failure.
+fn fail_url_stream<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    mut target: Avm2Object<'gc>,
+    url: &str,
+    error: Error,
+) -> Result<(), Avm2Error<'gc>> {
+    let ns = activation.avm2().flash_net_internal;
+    target.set_property(
+        &Avm2Multiname::new(ns, "_connected"),
+        false.into(),
+        activation,
+    )?;
+
+    if let Error::HttpNotOk(_, status, redirected) = error {
+        let http_status_evt = activation.avm2().classes().httpstatusevent.construct(
+            activation,
+            &[
+                "httpStatus".into(),
+                false.into(),
+                false.into(),
+                status.into(),
+                redirected.into(),
+            ],
+        )?;
+        set_response_info(activation, http_status_evt, url, &[])?;
+        Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
+    }
+
+    // FIXME - Match the exact error message generated by Flash
+    let io_error_evt = activation.avm2().classes().ioerrorevent.construct(
+        activation,
+        &[
+            "ioError".into(),
+            false.into(),
+            false.into(),
+            "Error #2032: Stream Error".into(),
+            2032.into(),
+        ],
+    )?;
+    Avm2::dispatch_event(&mut activation.context, io_error_evt, target);
+    Ok(())
+}
+
+/// Dispatch the events for a `URLStream` whose response started to arrive.
+fn url_stream_opened<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    handle: Index,
+    response: &StreamingResponse,
+) -> Result<(), Avm2Error<'gc>> {
+    let Some(target) = active_url_stream(activation, handle)? else {
+        return Ok(());
+    };
+
+    let open_evt = Avm2EventObject::bare_default_event(&mut activation.context, "open");
+    Avm2::dispatch_event(&mut activation.context, open_evt, target);
+
+    let http_status_evt = activation.avm2().classes().httpstatusevent.construct(
+        activation,
+        &[
+            "httpStatus".into(),
+            false.into(),
+            false.into(),
+            response.status.into(),
+            response.redirected.into(),
+        ],
+    )?;
+    set_response_info(
+        activation,
+        http_status_evt,
+        &response.url,
+        &response.headers,
+    )?;
+    Avm2::dispatch_event(&mut activation.context, http_status_evt, target);
+    Ok(())
+}
+
+/// Append a chunk of a response to the buffer of a `URLStream`, or finish its load.
+///
+/// Returns whether the load is finished.
+fn url_stream_received<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc>,
+    handle: Index,
+    url: &str,
+    chunk: Result<Option<Vec<u8>>, Error>,
+    bytes_loaded: &mut usize,
+    bytes_total: f64,
+) -> Result<bool, Avm2Error<'gc>> {
+    let Some(target) = active_url_stream(activation, handle)? else {
+        return Ok(true);
+    };
+
+    match chunk {
+        Ok(Some(chunk)) => {
+            let ns = activation.avm2().flash_net_internal;
+            let buffer = target
+                .get_property(&Avm2Multiname::new(ns, "_buffer"), activation)?
+                .coerce_to_object(activation)?;
+            if let Some(mut storage) = buffer.as_bytearray_mut(activation.context.gc_context) {
+                let length = storage.len();
+                if let Err(e) = storage.write_at(&chunk, length) {
+                    drop(storage);
+                    return Err(e.to_avm(activation));
+                }
+            }
+            *bytes_loaded += chunk.len();
+
+            let progress_evt = activation.avm2().classes().progressevent.construct(
+                activation,
+                &[
+                    "progress".into(),
+                    false.into(),
+                    false.into(),
+                    (*bytes_loaded).into(),
+                    bytes_total.into(),
+                ],
+            )?;
+            Avm2::dispatch_event(&mut activation.context, progress_evt, target);
+            Ok(false)
+        }
+        Ok(None) => {
+            let complete_evt =
+                Avm2EventObject::bare_default_event(&mut activation.context, "complete");
+            Avm2::dispatch_event(&mut activation.context, complete_evt, target);
+            Ok(true)
+        }
+        Err(error) => {
+            fail_url_stream(activation, target, url, error)?;
+            Ok(true)
+        }
+    }
+}
+
+/// Stream the body of a response into a `URLStream`, appending each chunk to the
+/// stream's buffer and dispatching a progress event as soon as it arrives.
+///
+/// The load is abandoned once the stream is closed or starts another load.
+async fn url_stream_loader(
+    player: Arc<Mutex<Player>>,
+    handle: Index,
+    request: Request,
+) -> Result<(), Error> {
+    let fetch = player.lock().unwrap().navigator().fetch_stream(request);
+    let mut response = match fetch.await {
+        Ok(response) => response,
+        Err(response) => {
+            return player.lock().unwrap().update(|uc| {
+                let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                match active_url_stream(&mut activation, handle) {
+                    Ok(Some(target)) => {
+                        fail_url_stream(&mut activation, target, &response.url, response.error)
+                    }
+                    Ok(None) => Ok(()),
+                    Err(e) => Err(e),
+                }
+                .map_err(|e| Error::Avm2Error(e.to_string()))
+            });
+        }
+    };
+
+    player.lock().unwrap().update(|uc| {
+        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+        url_stream_opened(&mut activation, handle, &response)
+            .map_err(|e| Error::Avm2Error(e.to_string()))
+    })?;
+
+    let bytes_total = response.expected_length.unwrap_or(0) as f64;
+    let mut bytes_loaded = 0;
+    loop {
+        let chunk = response.body.next_chunk().await;
+
+        let finished = player.lock().unwrap().update(|uc| {
+            let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+            url_stream_received(
+                &mut activation,
+                handle,
+                &response.url,
+                chunk,
+                &mut bytes_loaded,
+                bytes_total,
+            )
+            .map_err(|e| Error::Avm2Error(e.to_string()))
+        })?;
+
+        if finished {
+            return Ok(());
+        }
+    }
+}
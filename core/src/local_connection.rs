@@ -0,0 +1,259 @@
+//! Connections made by `LocalConnection`, which let movies call methods on each other,
+//! whether they're playing in this player or in another one.
+
+use crate::avm2::amf::deserialize_value;
+use crate::avm2::{Activation, Avm2, Error, Multiname, Object, TObject, Value};
+use crate::context::UpdateContext;
+use crate::rtmp::{read_amf0_values, write_amf0};
+use crate::string::AvmString;
+use flash_lso::types::Value as AmfValue;
+use gc_arena::Collect;
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+/// A message sent to a `LocalConnection`.
+#[derive(Clone, Debug)]
+pub struct LocalConnectionMessage {
+    /// The qualified name of the connection that the message was sent to.
+    pub name: String,
+
+    /// The domain of the sender, the name of the method to call and its arguments,
+    /// encoded with AMF0.
+    pub data: Vec<u8>,
+}
+
+/// Returns the domain of a movie, as reported by `LocalConnection.domain`.
+pub fn movie_domain(url: &str) -> Option<String> {
+    let url = url::Url::parse(url).ok()?;
+    Some(match url.domain() {
+        Some(domain) if url.scheme() != "file" => domain.to_string(),
+        _ => "localhost".to_string(),
+    })
+}
+
+/// Qualifies a connection name with a domain, unless it starts with an underscore
+/// or is already qualified.
+///
+/// Connection names aren't case sensitive.
+fn qualify_name(name: &str, domain: &str) -> String {
+    let name = name.to_lowercase();
+    if name.starts_with('_') || name.contains(':') {
+        name
+    } else {
+        format!("{}:{name}", domain.to_lowercase())
+    }
+}
+
+/// Manages the `LocalConnection`s that are listening for messages.
+pub struct LocalConnections<'gc> {
+    /// The `LocalConnection`s that are listening, by qualified connection name.
+    connections: HashMap<String, Object<'gc>>,
+
+    /// Whether each message that was sent reached a listening connection,
+    /// which is reported to the sender on the next tick.
+    statuses: Vec<(Object<'gc>, bool)>,
+
+    receiver: Receiver<LocalConnectionMessage>,
+    sender: Sender<LocalConnectionMessage>,
+}
+
+unsafe impl<'gc> Collect for LocalConnections<'gc> {
+    fn trace(&self, cc: &gc_arena::Collection) {
+        for connection in self.connections.values() {
+            connection.trace(cc);
+        }
+        for (connection, _) in &self.statuses {
+            connection.trace(cc);
+        }
+    }
+}
+
+impl<'gc> LocalConnections<'gc> {
+    pub fn empty() -> Self {
+        let (sender, receiver) = channel();
+
+        Self {
+            connections: HashMap::new(),
+            statuses: Vec::new(),
+            receiver,
+            sender,
+        }
+    }
+
+    pub fn is_connected(&self, object: Object<'gc>) -> bool {
+        self.connections
+            .values()
+            .any(|connection| Object::ptr_eq(*connection, object))
+    }
+
+    /// Starts listening for messages sent to a connection name.
+    ///
+    /// Returns `false` if a `LocalConnection` in this or another player is already
+    /// listening with that name.
+    pub fn connect(context: &mut UpdateContext<'_, 'gc>, object: Object<'gc>, name: &str) -> bool {
+        let domain = movie_domain(context.swf.url()).unwrap_or_default();
+        let name = qualify_name(name, &domain);
+        if context.local_connections.connections.contains_key(&name)
+            || !context
+                .navigator
+                .listen_local_connection(&name, context.local_connections.sender.clone())
+        {
+            return false;
+        }
+
+        context.local_connections.connections.insert(name, object);
+        true
+    }
+
+    /// Stops listening for messages.
+    ///
+    /// Returns `false` if the `LocalConnection` wasn't listening.
+    pub fn close(context: &mut UpdateContext<'_, 'gc>, object: Object<'gc>) -> bool {
+        let connections = &mut context.local_connections.connections;
+        let Some(name) = connections
+            .iter()
+            .find(|(_, connection)| Object::ptr_eq(**connection, object))
+            .map(|(name, _)| name.clone())
+        else {
+            return false;
+        };
+
+        connections.remove(&name);
+        context.navigator.close_local_connection(&name);
+        true
+    }
+
+    /// Sends a call to a method to the `LocalConnection` listening with a connection name.
+    ///
+    /// The call is made, and the sender is told whether anything was listening, on the next tick.
+    pub fn send(
+        context: &mut UpdateContext<'_, 'gc>,
+        object: Object<'gc>,
+        name: &str,
+        method: &str,
+        arguments: &[AmfValue],
+    ) {
+        let domain = movie_domain(context.swf.url()).unwrap_or_default();
+        let name = qualify_name(name, &domain);
+
+        let mut data = Vec::new();
+        write_amf0(&mut data, &AmfValue::String(domain));
+        write_amf0(&mut data, &AmfValue::String(method.to_string()));
+        for argument in arguments {
+            write_amf0(&mut data, argument);
+        }
+
+        let local_connections = &mut context.local_connections;
+        let delivered = if local_connections.connections.contains_key(&name) {
+            let _ = local_connections
+                .sender
+                .send(LocalConnectionMessage { name, data });
+            true
+        } else {
+            context.navigator.send_local_connection(&name, data)
+        };
+        context.local_connections.statuses.push((object, delivered));
+    }
+
+    pub fn update_local_connections(context: &mut UpdateContext<'_, 'gc>) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        for (object, delivered) in
+            std::mem::take(&mut activation.context.local_connections.statuses)
+        {
+            let level = if delivered { "status" } else { "error" };
+            let event = activation.avm2().classes().statusevent.construct(
+                &mut activation,
+                &[
+                    "status".into(),
+                    false.into(),
+                    false.into(),
+                    Value::Null,
+                    level.into(),
+                ],
+            );
+            match event {
+                Ok(event) => Avm2::dispatch_event(&mut activation.context, event, object),
+                Err(e) => tracing::error!("Unable to construct LocalConnection status: {:?}", e),
+            }
+        }
+
+        let messages: Vec<_> = activation
+            .context
+            .local_connections
+            .receiver
+            .try_iter()
+            .collect();
+        for message in messages {
+            let Some(object) = activation
+                .context
+                .local_connections
+                .connections
+                .get(&message.name)
+                .copied()
+            else {
+                // The connection was closed before the message arrived.
+                continue;
+            };
+
+            if let Err(e) = Self::receive(&mut activation, object, &message.data) {
+                tracing::error!("Unhandled error in LocalConnection call: {:?}", e);
+            }
+        }
+    }
+
+    /// Calls the method of a message on the client of a `LocalConnection`, if it accepts
+    /// messages from the domain of the sender.
+    fn receive(
+        activation: &mut Activation<'_, 'gc>,
+        object: Object<'gc>,
+        data: &[u8],
+    ) -> Result<(), Error<'gc>> {
+        let mut values = read_amf0_values(data).into_iter();
+        let (Some(AmfValue::String(sender_domain)), Some(AmfValue::String(method))) =
+            (values.next(), values.next())
+        else {
+            tracing::warn!("Ignoring malformed LocalConnection message");
+            return Ok(());
+        };
+
+        let domain = movie_domain(activation.context.swf.url()).unwrap_or_default();
+        if !sender_domain.eq_ignore_ascii_case(&domain) {
+            let ns = activation.avm2().flash_net_internal;
+            let allowed_domains = object
+                .get_property(&Multiname::new(ns, "_allowedDomains"), activation)?
+                .as_object();
+            let is_allowed = allowed_domains
+                .and_then(|domains| {
+                    let domains = domains.as_array_storage()?;
+                    let is_allowed = domains.iter().flatten().any(|allowed| match allowed {
+                        Value::String(allowed) => {
+                            let allowed = allowed.to_utf8_lossy();
+                            allowed == "*" || allowed.eq_ignore_ascii_case(&sender_domain)
+                        }
+                        _ => false,
+                    });
+                    Some(is_allowed)
+                })
+                .unwrap_or(false);
+            if !is_allowed {
+                tracing::warn!(
+                    "LocalConnection ignored call to {} from domain {}",
+                    method,
+                    sender_domain
+                );
+                return Ok(());
+            }
+        }
+
+        let arguments = values
+            .map(|argument| deserialize_value(activation, &argument))
+            .collect::<Result<Vec<_>, _>>()?;
+        let client = object.get_public_property("client", activation)?;
+        if let Some(client) = client.as_object() {
+            let method = AvmString::new_utf8(activation.context.gc_context, method);
+            client.call_public_property(method, &arguments, activation)?;
+        }
+        Ok(())
+    }
+}
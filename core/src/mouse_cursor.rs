@@ -0,0 +1,147 @@
+//! The cursor chosen with AS3 `Mouse.cursor`, including custom cursors registered
+//! with `Mouse.registerCursor`.
+
+use crate::backend::ui::{CustomMouseCursor, MouseCursor, UiBackend};
+use std::collections::HashMap;
+
+/// A cursor registered with `Mouse.registerCursor`.
+struct RegisteredCursor {
+    frames: Vec<CustomMouseCursor>,
+
+    /// How many frames of the cursor are shown per second.
+    frame_rate: f64,
+}
+
+pub struct MouseCursors {
+    registered: HashMap<String, RegisteredCursor>,
+
+    /// The name of the cursor set with `Mouse.cursor`.
+    cursor: String,
+
+    /// The cursor chosen for the object under the mouse, shown when `Mouse.cursor` is `auto`.
+    auto_cursor: MouseCursor,
+
+    /// The frame of the custom cursor that's being shown, if there is one.
+    shown_frame: Option<usize>,
+
+    /// How long the shown frame has been shown for, in milliseconds.
+    frame_time: f64,
+}
+
+impl MouseCursors {
+    pub fn new() -> Self {
+        Self {
+            registered: HashMap::new(),
+            cursor: "auto".to_string(),
+            auto_cursor: MouseCursor::Arrow,
+            shown_frame: None,
+            frame_time: 0.0,
+        }
+    }
+
+    /// The name of the cursor set with `Mouse.cursor`.
+    pub fn cursor(&self) -> &str {
+        &self.cursor
+    }
+
+    /// Returns whether a cursor can be set with `Mouse.cursor`.
+    pub fn is_valid(&self, name: &str) -> bool {
+        name == "auto"
+            || Self::native_cursor_for(name).is_some()
+            || self.registered.contains_key(name)
+    }
+
+    /// Sets the cursor chosen for the object under the mouse.
+    pub fn set_auto_cursor(&mut self, cursor: MouseCursor, ui: &mut dyn UiBackend) {
+        self.auto_cursor = cursor;
+        self.update_native_cursor(ui);
+    }
+
+    /// Shows the native cursor that was set with `Mouse.cursor`, or the one chosen for the
+    /// object under the mouse otherwise.
+    fn update_native_cursor(&self, ui: &mut dyn UiBackend) {
+        ui.set_mouse_cursor(Self::native_cursor_for(&self.cursor).unwrap_or(self.auto_cursor));
+    }
+
+    fn native_cursor_for(name: &str) -> Option<MouseCursor> {
+        match name {
+            "arrow" => Some(MouseCursor::Arrow),
+            "button" => Some(MouseCursor::Hand),
+            "hand" => Some(MouseCursor::Grab),
+            "ibeam" => Some(MouseCursor::IBeam),
+            _ => None,
+        }
+    }
+
+    pub fn set_cursor(&mut self, name: &str, ui: &mut dyn UiBackend) {
+        self.cursor = name.to_string();
+        self.update_native_cursor(ui);
+        self.show_frame(0, ui);
+    }
+
+    pub fn register(
+        &mut self,
+        name: String,
+        frames: Vec<CustomMouseCursor>,
+        frame_rate: f64,
+        ui: &mut dyn UiBackend,
+    ) {
+        let is_shown = name == self.cursor;
+        self.registered
+            .insert(name, RegisteredCursor { frames, frame_rate });
+        if is_shown {
+            self.show_frame(0, ui);
+        }
+    }
+
+    pub fn unregister(&mut self, name: &str, ui: &mut dyn UiBackend) {
+        if self.registered.remove(name).is_some() && name == self.cursor {
+            self.set_cursor("auto", ui);
+        }
+    }
+
+    /// Advances the animation of the custom cursor that's shown.
+    pub fn tick(&mut self, dt: f64, ui: &mut dyn UiBackend) {
+        let (Some(frame), Some(cursor)) = (self.shown_frame, self.registered.get(&self.cursor))
+        else {
+            return;
+        };
+        if cursor.frames.len() < 2 || cursor.frame_rate <= 0.0 {
+            return;
+        }
+
+        let frame_duration = 1000.0 / cursor.frame_rate;
+        self.frame_time += dt;
+        if self.frame_time >= frame_duration {
+            let elapsed_frames = (self.frame_time / frame_duration) as usize;
+            let frame_time = self.frame_time % frame_duration;
+            self.show_frame((frame + elapsed_frames) % cursor.frames.len(), ui);
+            self.frame_time = frame_time;
+        }
+    }
+
+    fn show_frame(&mut self, frame: usize, ui: &mut dyn UiBackend) {
+        self.frame_time = 0.0;
+        match self
+            .registered
+            .get(&self.cursor)
+            .and_then(|cursor| cursor.frames.get(frame))
+        {
+            Some(image) => {
+                ui.set_custom_mouse_cursor(Some(image));
+                self.shown_frame = Some(frame);
+            }
+            None => {
+                if self.shown_frame.take().is_some() {
+                    ui.set_custom_mouse_cursor(None);
+                }
+            }
+        }
+    }
+}
+
+impl Default for MouseCursors {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,875 @@
+//! MP4/F4V support for `NetStream`.
+//!
+//! Instead of being demuxed while playing, MP4 files are remuxed into an FLV
+//! up front, which is then played like any other FLV. This only needs the
+//! sample tables in the `moov` box, and works for files where it comes after
+//! the media data too.
+//!
+//! Like Flash Player, the XMP metadata of an F4V is passed to `onXMPData`.
+//! The cue points that it lists are passed to `onCuePoint` as they're reached,
+//! the same as cue points embedded in an FLV.
+
+use crate::rtmp::write_amf0;
+use crate::streams::flv_tag;
+use byteorder::{BigEndian, ReadBytesExt};
+use flash_lso::types::{Element, Value as AmfValue};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::io::Read;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Mp4Error {
+    #[error("Truncated box")]
+    TruncatedBox(#[from] std::io::Error),
+
+    #[error("Missing {0} box")]
+    MissingBox(&'static str),
+
+    #[error("Sample lies outside of the file")]
+    InvalidSample,
+
+    #[error("No playable tracks")]
+    NoTracks,
+
+    #[error("Invalid XMP metadata")]
+    InvalidXmp(#[from] quick_xml::Error),
+}
+
+const FLV_TAG_AUDIO: u8 = 8;
+const FLV_TAG_VIDEO: u8 = 9;
+const FLV_TAG_SCRIPT: u8 = 18;
+
+const FLV_CODEC_AVC: u8 = 7;
+const FLV_SOUND_MP3: u8 = 2;
+const FLV_SOUND_AAC: u8 = 10;
+
+/// The `uuid` box that XMP metadata can be stored in.
+const XMP_UUID: [u8; 16] = [
+    0xBE, 0x7A, 0xCF, 0xCB, 0x97, 0xA9, 0x42, 0xE8, 0x9C, 0x71, 0x99, 0x94, 0x91, 0xE3, 0xAF, 0xAC,
+];
+
+/// Returns whether the given data looks like an MP4 or F4V file.
+pub fn is_mp4(data: &[u8]) -> bool {
+    data.get(4..8) == Some(b"ftyp")
+}
+
+#[derive(Debug)]
+enum Media {
+    Video {
+        width: u32,
+        height: u32,
+        /// The `AVCDecoderConfigurationRecord`.
+        config: Vec<u8>,
+    },
+    Audio {
+        /// The FLV sound format the track is stored as.
+        format: u8,
+        sample_rate: u32,
+        channels: u16,
+        /// The `AudioSpecificConfig` of AAC tracks.
+        config: Option<Vec<u8>>,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    offset: u64,
+    size: u32,
+    /// Decoding time, in the track's timescale.
+    dts: u64,
+    /// Offset of the presentation time from the decoding time.
+    cts_offset: i64,
+    is_sync: bool,
+}
+
+#[derive(Debug)]
+struct Track {
+    media: Media,
+    timescale: u32,
+    samples: Vec<Sample>,
+}
+
+impl Track {
+    fn to_millis(&self, time: i64) -> i64 {
+        time * 1000 / i64::from(self.timescale.max(1))
+    }
+
+    fn duration(&self) -> f64 {
+        self.samples
+            .last()
+            .map_or(0.0, |s| s.dts as f64 / f64::from(self.timescale.max(1)))
+    }
+}
+
+/// Reads a box header, returning its type and contents.
+fn read_box<'a>(data: &mut &'a [u8]) -> Result<([u8; 4], &'a [u8]), Mp4Error> {
+    let size = data.read_u32::<BigEndian>()? as u64;
+    let mut box_type = [0; 4];
+    data.read_exact(&mut box_type)?;
+    let (header_size, size) = match size {
+        0 => (8, data.len() as u64 + 8),
+        1 => (16, data.read_u64::<BigEndian>()?),
+        size => (8, size),
+    };
+    let length = size
+        .checked_sub(header_size)
+        .filter(|length| *length <= data.len() as u64)
+        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?
+        as usize;
+    let (contents, rest) = data.split_at(length);
+    *data = rest;
+    Ok((box_type, contents))
+}
+
+/// Finds the first child box of the given type.
+fn find_box<'a>(mut data: &'a [u8], box_type: &[u8; 4]) -> Result<Option<&'a [u8]>, Mp4Error> {
+    while !data.is_empty() {
+        let (child_type, contents) = read_box(&mut data)?;
+        if child_type == *box_type {
+            return Ok(Some(contents));
+        }
+    }
+    Ok(None)
+}
+
+fn expect_box<'a>(
+    data: &'a [u8],
+    box_type: &'static [u8; 4],
+    name: &'static str,
+) -> Result<&'a [u8], Mp4Error> {
+    find_box(data, box_type)?.ok_or(Mp4Error::MissingBox(name))
+}
+
+fn skip(data: &mut &[u8], length: usize) -> Result<(), Mp4Error> {
+    *data = data
+        .get(length..)
+        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?;
+    Ok(())
+}
+
+/// Reads an MPEG-4 descriptor from an `esds` box, returning its tag and contents.
+fn read_descriptor<'a>(data: &mut &'a [u8]) -> Result<(u8, &'a [u8]), Mp4Error> {
+    let tag = data.read_u8()?;
+    let mut length = 0usize;
+    for _ in 0..4 {
+        let byte = data.read_u8()?;
+        length = (length << 7) | (byte & 0x7F) as usize;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+    let contents = data
+        .get(..length)
+        .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?;
+    *data = &data[length..];
+    Ok((tag, contents))
+}
+
+/// Reads the object type and decoder specific info from an `esds` box.
+fn parse_esds(mut data: &[u8]) -> Result<(u8, Option<Vec<u8>>), Mp4Error> {
+    skip(&mut data, 4)?;
+    let (_, mut es) = read_descriptor(&mut data)?;
+    let _es_id = es.read_u16::<BigEndian>()?;
+    let flags = es.read_u8()?;
+    if flags & 0x80 != 0 {
+        skip(&mut es, 2)?;
+    }
+    if flags & 0x40 != 0 {
+        let url_length = es.read_u8()? as usize;
+        skip(&mut es, url_length)?;
+    }
+    if flags & 0x20 != 0 {
+        skip(&mut es, 2)?;
+    }
+
+    let (_, mut decoder_config) = read_descriptor(&mut es)?;
+    let object_type = decoder_config.read_u8()?;
+    skip(&mut decoder_config, 12)?;
+    let specific_info = if decoder_config.is_empty() {
+        None
+    } else {
+        Some(read_descriptor(&mut decoder_config)?.1.to_vec())
+    };
+    Ok((object_type, specific_info))
+}
+
+/// Reads the media type of a track from its sample description.
+fn parse_sample_description(
+    mut stsd: &[u8],
+    width: u32,
+    height: u32,
+) -> Result<Option<Media>, Mp4Error> {
+    skip(&mut stsd, 8)?;
+    let (entry_type, mut entry) = read_box(&mut stsd)?;
+    match &entry_type {
+        b"avc1" | b"avc3" => {
+            skip(&mut entry, 78)?;
+            let config = expect_box(entry, b"avcC", "avcC")?.to_vec();
+            Ok(Some(Media::Video {
+                width,
+                height,
+                config,
+            }))
+        }
+        b"mp4a" => {
+            let mut header = entry;
+            skip(&mut header, 8)?;
+            let version = header.read_u16::<BigEndian>()?;
+            skip(&mut header, 6)?;
+            let channels = header.read_u16::<BigEndian>()?;
+            skip(&mut header, 6)?;
+            let sample_rate = header.read_u32::<BigEndian>()? >> 16;
+            let header_size = match version {
+                1 => 44,
+                2 => 64,
+                _ => 28,
+            };
+            skip(&mut entry, header_size)?;
+
+            let (object_type, config) = parse_esds(expect_box(entry, b"esds", "esds")?)?;
+            let format = match object_type {
+                0x40 | 0x66 | 0x67 | 0x68 => FLV_SOUND_AAC,
+                0x69 | 0x6B => FLV_SOUND_MP3,
+                _ => {
+                    tracing::warn!("Unsupported MP4 audio object type {:#x}", object_type);
+                    return Ok(None);
+                }
+            };
+            Ok(Some(Media::Audio {
+                format,
+                sample_rate,
+                channels,
+                config,
+            }))
+        }
+        _ => {
+            tracing::warn!(
+                "Unsupported MP4 sample entry {}",
+                String::from_utf8_lossy(&entry_type)
+            );
+            Ok(None)
+        }
+    }
+}
+
+/// Reads a table of `count` entries that follows a full box header.
+fn read_table<T>(
+    data: Option<&[u8]>,
+    mut read_entry: impl FnMut(&mut &[u8]) -> Result<T, Mp4Error>,
+) -> Result<Vec<T>, Mp4Error> {
+    let Some(mut data) = data else {
+        return Ok(Vec::new());
+    };
+    skip(&mut data, 4)?;
+    let count = data.read_u32::<BigEndian>()?;
+    let mut entries = Vec::with_capacity(count.min(65536) as usize);
+    for _ in 0..count {
+        entries.push(read_entry(&mut data)?);
+    }
+    Ok(entries)
+}
+
+/// Lists every sample of a track from its sample table.
+fn parse_samples(stbl: &[u8]) -> Result<Vec<Sample>, Mp4Error> {
+    let time_to_sample = read_table(find_box(stbl, b"stts")?, |data| {
+        Ok((data.read_u32::<BigEndian>()?, data.read_u32::<BigEndian>()?))
+    })?;
+    let composition_offsets = read_table(find_box(stbl, b"ctts")?, |data| {
+        Ok((data.read_u32::<BigEndian>()?, data.read_i32::<BigEndian>()?))
+    })?;
+    let sync_samples = find_box(stbl, b"stss")?
+        .map(|stss| read_table(Some(stss), |data| Ok(data.read_u32::<BigEndian>()?)))
+        .transpose()?;
+    let sample_to_chunk = read_table(find_box(stbl, b"stsc")?, |data| {
+        let first_chunk = data.read_u32::<BigEndian>()?;
+        let samples_per_chunk = data.read_u32::<BigEndian>()?;
+        let _description_index = data.read_u32::<BigEndian>()?;
+        Ok((first_chunk, samples_per_chunk))
+    })?;
+    let chunk_offsets = match find_box(stbl, b"stco")? {
+        Some(stco) => read_table(Some(stco), |data| {
+            Ok(u64::from(data.read_u32::<BigEndian>()?))
+        })?,
+        None => read_table(find_box(stbl, b"co64")?, |data| {
+            Ok(data.read_u64::<BigEndian>()?)
+        })?,
+    };
+
+    let mut stsz = expect_box(stbl, b"stsz", "stsz")?;
+    skip(&mut stsz, 4)?;
+    let uniform_size = stsz.read_u32::<BigEndian>()?;
+    let sample_count = stsz.read_u32::<BigEndian>()?;
+    let mut sizes = Vec::with_capacity(sample_count.min(65536) as usize);
+    for _ in 0..sample_count {
+        sizes.push(if uniform_size == 0 {
+            stsz.read_u32::<BigEndian>()?
+        } else {
+            uniform_size
+        });
+    }
+
+    let mut offsets = Vec::with_capacity(sizes.len());
+    for (i, &chunk_offset) in chunk_offsets.iter().enumerate() {
+        let chunk = i as u32 + 1;
+        let samples_per_chunk = sample_to_chunk
+            .iter()
+            .rev()
+            .find(|(first_chunk, _)| *first_chunk <= chunk)
+            .map_or(0, |(_, samples)| *samples);
+        let mut offset = chunk_offset;
+        for _ in 0..samples_per_chunk {
+            let Some(size) = sizes.get(offsets.len()) else {
+                break;
+            };
+            offsets.push(offset);
+            offset += u64::from(*size);
+        }
+    }
+
+    let mut decode_times = time_to_sample
+        .iter()
+        .flat_map(|&(count, delta)| std::iter::repeat(delta).take(count as usize))
+        .scan(0u64, |time, delta| {
+            let dts = *time;
+            *time += u64::from(delta);
+            Some(dts)
+        });
+    let mut composition_offsets = composition_offsets
+        .iter()
+        .flat_map(|&(count, offset)| std::iter::repeat(offset).take(count as usize));
+
+    Ok(sizes
+        .iter()
+        .zip(offsets)
+        .enumerate()
+        .map(|(i, (&size, offset))| Sample {
+            offset,
+            size,
+            dts: decode_times.next().unwrap_or_default(),
+            cts_offset: composition_offsets.next().unwrap_or_default().into(),
+            is_sync: sync_samples
+                .as_ref()
+                .map_or(true, |sync| sync.binary_search(&(i as u32 + 1)).is_ok()),
+        })
+        .collect())
+}
+
+fn parse_track(trak: &[u8]) -> Result<Option<Track>, Mp4Error> {
+    let tkhd = expect_box(trak, b"tkhd", "tkhd")?;
+    let dimensions = tkhd
+        .get(tkhd.len().saturating_sub(8)..)
+        .filter(|d| d.len() == 8)
+        .ok_or(Mp4Error::MissingBox("tkhd"))?;
+    let width = u32::from_be_bytes([dimensions[0], dimensions[1], dimensions[2], dimensions[3]]);
+    let height = u32::from_be_bytes([dimensions[4], dimensions[5], dimensions[6], dimensions[7]]);
+
+    let mdia = expect_box(trak, b"mdia", "mdia")?;
+    let mut mdhd = expect_box(mdia, b"mdhd", "mdhd")?;
+    let version = mdhd.read_u8()?;
+    skip(&mut mdhd, if version == 1 { 3 + 16 } else { 3 + 8 })?;
+    let timescale = mdhd.read_u32::<BigEndian>()?;
+
+    let stbl = expect_box(expect_box(mdia, b"minf", "minf")?, b"stbl", "stbl")?;
+    let Some(media) = parse_sample_description(
+        expect_box(stbl, b"stsd", "stsd")?,
+        width >> 16,
+        height >> 16,
+    )?
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(Track {
+        media,
+        timescale,
+        samples: parse_samples(stbl)?,
+    }))
+}
+
+/// Builds the `onMetaData` script data describing the file.
+fn metadata(tracks: &[Track]) -> Vec<u8> {
+    let duration = tracks.iter().map(Track::duration).fold(0.0, f64::max);
+    let mut properties = vec![("duration", AmfValue::Number(duration))];
+    for track in tracks {
+        match &track.media {
+            Media::Video { width, height, .. } => {
+                let frame_rate = if duration > 0.0 {
+                    track.samples.len() as f64 / duration
+                } else {
+                    0.0
+                };
+                properties.extend([
+                    ("width", AmfValue::Number(f64::from(*width))),
+                    ("height", AmfValue::Number(f64::from(*height))),
+                    ("framerate", AmfValue::Number(frame_rate)),
+                    ("videocodecid", AmfValue::Number(f64::from(FLV_CODEC_AVC))),
+                ]);
+            }
+            Media::Audio {
+                format,
+                sample_rate,
+                channels,
+                ..
+            } => {
+                properties.extend([
+                    ("audiocodecid", AmfValue::Number(f64::from(*format))),
+                    ("audiosamplerate", AmfValue::Number(f64::from(*sample_rate))),
+                    ("audiochannels", AmfValue::Number(f64::from(*channels))),
+                ]);
+            }
+        }
+    }
+
+    let elements: Vec<Element> = properties
+        .into_iter()
+        .map(|(name, value)| Element::new(name, value))
+        .collect();
+    let length = elements.len() as u32;
+
+    let mut data = Vec::new();
+    write_amf0(&mut data, &AmfValue::String("onMetaData".to_string()));
+    write_amf0(&mut data, &AmfValue::ECMAArray(vec![], elements, length));
+    data
+}
+
+/// Finds the XMP metadata of the file, which F4V files keep in `moov/udta/XMP_`
+/// and other MP4 files in a top-level `uuid` box.
+fn find_xmp<'a>(data: &'a [u8], moov: &'a [u8]) -> Result<Option<&'a [u8]>, Mp4Error> {
+    if let Some(udta) = find_box(moov, b"udta")? {
+        if let Some(xmp) = find_box(udta, b"XMP_")? {
+            return Ok(Some(xmp));
+        }
+    }
+    let mut boxes = data;
+    while !boxes.is_empty() {
+        let (box_type, contents) = read_box(&mut boxes)?;
+        if box_type == *b"uuid" && contents.get(..16) == Some(&XMP_UUID) {
+            return Ok(Some(&contents[16..]));
+        }
+    }
+    Ok(None)
+}
+
+/// A cue point listed in XMP metadata.
+#[derive(Debug, Default, PartialEq)]
+struct CuePoint {
+    name: String,
+    /// The time of the cue point, in seconds.
+    time: f64,
+    /// Either "event" or "navigation".
+    cue_type: String,
+    parameters: Vec<(String, String)>,
+}
+
+/// Parses an XMP frame rate, such as `f1000` or `f30000s1001`, as frames per second.
+fn parse_frame_rate(rate: &str) -> Option<f64> {
+    let rate = rate.strip_prefix('f')?;
+    let (frames, seconds) = rate.split_once('s').unwrap_or((rate, "1"));
+    let frames: f64 = frames.parse().ok()?;
+    let seconds: f64 = seconds.parse().ok()?;
+    (frames > 0.0 && seconds > 0.0).then_some(frames / seconds)
+}
+
+/// Reads the cue points from the `xmpDM:markers` of XMP metadata.
+///
+/// Marker properties can be written either as attributes or as child elements.
+fn parse_cue_points(xmp: &[u8]) -> Result<Vec<CuePoint>, Mp4Error> {
+    let mut reader = Reader::from_reader(xmp);
+    reader.expand_empty_elements(true);
+    reader.trim_text(true);
+
+    let mut cue_points = Vec::new();
+    let mut path: Vec<Vec<u8>> = Vec::new();
+    // Marker times are counted in frames of their track, which default to milliseconds.
+    let mut frame_rate = 1000.0;
+    // The marker being read, the depth of its element, and its start time in frames.
+    let mut marker: Option<(CuePoint, usize, f64)> = None;
+    let mut parameter_key = None;
+    let mut text = String::new();
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                let mut attributes = Vec::new();
+                for attribute in e.attributes().with_checks(false) {
+                    let attribute = attribute.map_err(quick_xml::Error::from)?;
+                    attributes.push((
+                        attribute.key.local_name().as_ref().to_vec(),
+                        attribute.unescape_value()?.into_owned(),
+                    ));
+                }
+                let attribute = |key: &[u8]| {
+                    attributes
+                        .iter()
+                        .find(|(name, _)| name == key)
+                        .map(|(_, value)| value.clone())
+                };
+
+                if let Some(rate) = attribute(b"frameRate")
+                    .as_deref()
+                    .and_then(parse_frame_rate)
+                {
+                    frame_rate = rate;
+                }
+                let in_markers = path.iter().any(|name| name == b"markers");
+                if marker.is_none()
+                    && in_markers
+                    && (e.local_name().as_ref() == b"li"
+                        || attribute(b"startTime").is_some()
+                        || attribute(b"cuePointType").is_some())
+                {
+                    marker = Some((CuePoint::default(), path.len(), 0.0));
+                }
+                if let Some((cue_point, _, start)) = &mut marker {
+                    if let (Some(key), Some(value)) = (attribute(b"key"), attribute(b"value")) {
+                        cue_point.parameters.push((key, value));
+                    } else {
+                        if let Some(name) = attribute(b"name") {
+                            cue_point.name = name;
+                        }
+                        if let Some(cue_type) = attribute(b"cuePointType") {
+                            cue_point.cue_type = cue_type;
+                        }
+                        if let Some(time) = attribute(b"startTime").and_then(|t| t.parse().ok()) {
+                            *start = time;
+                        }
+                    }
+                }
+                text.clear();
+                path.push(e.local_name().as_ref().to_vec());
+            }
+            Event::Text(e) => text.push_str(&e.unescape()?),
+            Event::End(_) => {
+                let name = path.pop().unwrap_or_default();
+                let mut is_marker_end = false;
+                if let Some((cue_point, depth, start)) = &mut marker {
+                    let text = text.trim().to_string();
+                    match &name[..] {
+                        b"name" => cue_point.name = text,
+                        b"cuePointType" => cue_point.cue_type = text,
+                        b"startTime" => *start = text.parse().unwrap_or_default(),
+                        b"key" => parameter_key = Some(text),
+                        b"value" => {
+                            if let Some(key) = parameter_key.take() {
+                                cue_point.parameters.push((key, text));
+                            }
+                        }
+                        _ => {}
+                    }
+                    is_marker_end = path.len() == *depth;
+                }
+                if is_marker_end {
+                    if let Some((mut cue_point, _, start)) = marker.take() {
+                        // Other markers, such as chapters, aren't cue points.
+                        if !cue_point.cue_type.is_empty() {
+                            cue_point.time = start / frame_rate;
+                            cue_point.cue_type = cue_point.cue_type.to_ascii_lowercase();
+                            cue_points.push(cue_point);
+                        }
+                    }
+                }
+                text.clear();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(cue_points)
+}
+
+/// Builds the `onXMPData` script data that passes on the XMP metadata of the file.
+fn xmp_data(xmp: &[u8]) -> Vec<u8> {
+    let data = Element::new(
+        "data",
+        AmfValue::String(String::from_utf8_lossy(xmp).into()),
+    );
+    let mut script = Vec::new();
+    write_amf0(&mut script, &AmfValue::String("onXMPData".to_string()));
+    write_amf0(&mut script, &AmfValue::Object(vec![data], None));
+    script
+}
+
+/// Builds the `onCuePoint` script data for a cue point.
+fn cue_point_data(cue_point: &CuePoint) -> Vec<u8> {
+    let parameters = cue_point
+        .parameters
+        .iter()
+        .map(|(key, value)| Element::new(key.as_str(), AmfValue::String(value.clone())))
+        .collect();
+    let properties = vec![
+        Element::new("name", AmfValue::String(cue_point.name.clone())),
+        Element::new("time", AmfValue::Number(cue_point.time)),
+        Element::new("type", AmfValue::String(cue_point.cue_type.clone())),
+        Element::new("parameters", AmfValue::Object(parameters, None)),
+    ];
+    let mut script = Vec::new();
+    write_amf0(&mut script, &AmfValue::String("onCuePoint".to_string()));
+    write_amf0(&mut script, &AmfValue::Object(properties, None));
+    script
+}
+
+/// The first byte of an FLV audio tag for the given track.
+fn sound_header(format: u8, sample_rate: u32, channels: u16) -> u8 {
+    let rate = match sample_rate {
+        0..=8000 => 0,
+        8001..=16000 => 1,
+        16001..=32000 => 2,
+        _ => 3,
+    };
+    // AAC is always marked as 44kHz stereo; the real values are in its config.
+    let (rate, is_stereo) = if format == FLV_SOUND_AAC {
+        (3, true)
+    } else {
+        (rate, channels > 1)
+    };
+    (format << 4) | (rate << 2) | 0b10 | u8::from(is_stereo)
+}
+
+/// Remuxes an MP4 file into an FLV file with the same media.
+pub fn remux_to_flv(data: &[u8]) -> Result<Vec<u8>, Mp4Error> {
+    let moov = expect_box(data, b"moov", "moov")?;
+
+    let mut tracks = Vec::new();
+    let mut boxes = moov;
+    while !boxes.is_empty() {
+        let (box_type, contents) = read_box(&mut boxes)?;
+        if box_type == *b"trak" {
+            if let Some(track) = parse_track(contents)? {
+                tracks.push(track);
+            }
+        }
+    }
+    if tracks.is_empty() {
+        return Err(Mp4Error::NoTracks);
+    }
+
+    let has_audio = tracks
+        .iter()
+        .any(|t| matches!(t.media, Media::Audio { .. }));
+    let has_video = tracks
+        .iter()
+        .any(|t| matches!(t.media, Media::Video { .. }));
+    let mut flv = vec![
+        b'F',
+        b'L',
+        b'V',
+        1,
+        (u8::from(has_audio) << 2) | u8::from(has_video),
+        0,
+        0,
+        0,
+        9,
+        0,
+        0,
+        0,
+        0,
+    ];
+    flv.extend(flv_tag(FLV_TAG_SCRIPT, 0, &metadata(&tracks)));
+
+    let mut cue_points = Vec::new();
+    if let Some(xmp) = find_xmp(data, moov)? {
+        flv.extend(flv_tag(FLV_TAG_SCRIPT, 0, &xmp_data(xmp)));
+        match parse_cue_points(xmp) {
+            Ok(points) => cue_points = points,
+            Err(e) => tracing::warn!("Couldn't read MP4 cue points: {}", e),
+        }
+    }
+    cue_points.sort_by(|a, b| a.time.total_cmp(&b.time));
+    let mut cue_points = cue_points.into_iter().peekable();
+
+    for track in &tracks {
+        match &track.media {
+            Media::Video { config, .. } => {
+                let mut body = vec![0x10 | FLV_CODEC_AVC, 0, 0, 0, 0];
+                body.extend_from_slice(config);
+                flv.extend(flv_tag(FLV_TAG_VIDEO, 0, &body));
+            }
+            Media::Audio {
+                format,
+                sample_rate,
+                channels,
+                config: Some(config),
+            } if *format == FLV_SOUND_AAC => {
+                let mut body = vec![sound_header(*format, *sample_rate, *channels), 0];
+                body.extend_from_slice(config);
+                flv.extend(flv_tag(FLV_TAG_AUDIO, 0, &body));
+            }
+            Media::Audio { .. } => {}
+        }
+    }
+
+    // Samples of all tracks are interleaved in decoding order.
+    let mut samples: Vec<(i64, usize, Sample)> = tracks
+        .iter()
+        .enumerate()
+        .flat_map(|(i, track)| {
+            track
+                .samples
+                .iter()
+                .map(move |sample| (track.to_millis(sample.dts as i64), i, *sample))
+        })
+        .collect();
+    samples.sort_by_key(|(time, track, _)| (*time, *track));
+
+    for (time, track, sample) in samples {
+        while let Some(cue_point) = cue_points.next_if(|c| (c.time * 1000.0) as i64 <= time) {
+            let cue_time = (cue_point.time * 1000.0) as u32;
+            flv.extend(flv_tag(
+                FLV_TAG_SCRIPT,
+                cue_time,
+                &cue_point_data(&cue_point),
+            ));
+        }
+
+        let start = sample.offset as usize;
+        let sample_data = data
+            .get(start..start + sample.size as usize)
+            .ok_or(Mp4Error::InvalidSample)?;
+        let track = &tracks[track];
+        let (tag_type, mut body) = match &track.media {
+            Media::Video { .. } => {
+                let frame_type = if sample.is_sync { 1 } else { 2 };
+                let cts = track.to_millis(sample.cts_offset) as i32;
+                let mut body = vec![(frame_type << 4) | FLV_CODEC_AVC, 1];
+                body.extend_from_slice(&cts.to_be_bytes()[1..]);
+                (FLV_TAG_VIDEO, body)
+            }
+            Media::Audio {
+                format,
+                sample_rate,
+                channels,
+                ..
+            } => {
+                let mut body = vec![sound_header(*format, *sample_rate, *channels)];
+                if *format == FLV_SOUND_AAC {
+                    body.push(1);
+                }
+                (FLV_TAG_AUDIO, body)
+            }
+        };
+        body.extend_from_slice(sample_data);
+        flv.extend(flv_tag(tag_type, time as u32, &body));
+    }
+    for cue_point in cue_points {
+        let cue_time = (cue_point.time * 1000.0) as u32;
+        flv.extend(flv_tag(
+            FLV_TAG_SCRIPT,
+            cue_time,
+            &cue_point_data(&cue_point),
+        ));
+    }
+
+    Ok(flv)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_box(box_type: &[u8; 4], contents: &[u8]) -> Vec<u8> {
+        let mut data = ((contents.len() + 8) as u32).to_be_bytes().to_vec();
+        data.extend_from_slice(box_type);
+        data.extend_from_slice(contents);
+        data
+    }
+
+    fn make_table(entries: &[&[u32]]) -> Vec<u8> {
+        let mut data = vec![0, 0, 0, 0];
+        data.extend_from_slice(&(entries.len() as u32).to_be_bytes());
+        for entry in entries {
+            for value in *entry {
+                data.extend_from_slice(&value.to_be_bytes());
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn samples_are_located_in_chunks() {
+        let mut stbl = make_box(b"stts", &make_table(&[&[3, 100]]));
+        stbl.extend(make_box(b"stss", &make_table(&[&[1]])));
+        stbl.extend(make_box(b"stsc", &make_table(&[&[1, 2, 1], &[2, 1, 1]])));
+        stbl.extend(make_box(b"stco", &make_table(&[&[1000], &[2000]])));
+        let mut stsz = vec![0; 8];
+        stsz.extend_from_slice(&3u32.to_be_bytes());
+        for size in [10u32, 20, 30] {
+            stsz.extend_from_slice(&size.to_be_bytes());
+        }
+        stbl.extend(make_box(b"stsz", &stsz));
+
+        let samples = parse_samples(&stbl).unwrap();
+        let summary: Vec<_> = samples
+            .iter()
+            .map(|s| (s.offset, s.size, s.dts, s.is_sync))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1000, 10, 0, true),
+                (1010, 20, 100, false),
+                (2000, 30, 200, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn cue_points_are_read_from_xmp() {
+        let xmp = br#"<x:xmpmeta xmlns:x="adobe:ns:meta/">
+            <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+                xmlns:xmpDM="http://ns.adobe.com/xmp/1.0/DynamicMedia/">
+            <rdf:Description>
+                <xmpDM:Tracks><rdf:Bag><rdf:li>
+                    <rdf:Description xmpDM:trackName="CuePoint Markers" xmpDM:frameRate="f1000">
+                    <xmpDM:markers><rdf:Seq>
+                        <rdf:li><rdf:Description xmpDM:startTime="2500" xmpDM:name="intro"
+                            xmpDM:cuePointType="Navigation">
+                            <xmpDM:cuePointParams><rdf:Seq>
+                                <rdf:li xmpDM:key="title" xmpDM:value="Intro"/>
+                            </rdf:Seq></xmpDM:cuePointParams>
+                        </rdf:Description></rdf:li>
+                        <rdf:li>
+                            <xmpDM:startTime>500</xmpDM:startTime>
+                            <xmpDM:name>start</xmpDM:name>
+                            <xmpDM:cuePointType>Event</xmpDM:cuePointType>
+                        </rdf:li>
+                        <rdf:li xmpDM:startTime="4000" xmpDM:name="chapter"/>
+                    </rdf:Seq></xmpDM:markers>
+                    </rdf:Description>
+                </rdf:li></rdf:Bag></xmpDM:Tracks>
+            </rdf:Description>
+            </rdf:RDF></x:xmpmeta>"#;
+
+        assert_eq!(
+            parse_cue_points(xmp).unwrap(),
+            vec![
+                CuePoint {
+                    name: "intro".to_string(),
+                    time: 2.5,
+                    cue_type: "navigation".to_string(),
+                    parameters: vec![("title".to_string(), "Intro".to_string())],
+                },
+                CuePoint {
+                    name: "start".to_string(),
+                    time: 0.5,
+                    cue_type: "event".to_string(),
+                    parameters: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn frame_rates_are_parsed() {
+        assert_eq!(parse_frame_rate("f1000"), Some(1000.0));
+        assert_eq!(parse_frame_rate("f30000s1001"), Some(30000.0 / 1001.0));
+        assert_eq!(parse_frame_rate("f0"), None);
+        assert_eq!(parse_frame_rate("1000"), None);
+    }
+
+    #[test]
+    fn mp4_is_detected() {
+        assert!(is_mp4(&make_box(b"ftyp", b"f4v \0\0\0\0")));
+        assert!(!is_mp4(b"FLV\x01\x05\0\0\0\x09"));
+    }
+}
@@ -0,0 +1,995 @@
+//! RTMP connections made by `NetConnection`, and the streams played or published on them,
+//! as well as the remote `SharedObject`s connected through them.
+
+use crate::{
+    avm2::{
+        amf::{deserialize_value, serialize_value},
+        Activation, ArrayObject, Avm2, Error, EventObject, Multiname, Object, TObject, Value,
+    },
+    context::UpdateContext,
+    rtmp::{self, RtmpEvent, RtmpSession, SharedObjectEvent},
+    socket::{ConnectionState, SocketAction, SocketKind},
+    streams::{flv_tag, NetStream, StreamManager},
+    string::AvmString,
+};
+use flash_lso::types::{Element, Value as AmfValue};
+use gc_arena::Collect;
+use generational_arena::{Arena, Index};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::mpsc::{channel, Receiver, Sender},
+    time::Duration,
+};
+use url::Url;
+
+pub type NetConnectionHandle = Index;
+
+/// How long to wait for the server to accept the TCP connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// The transaction ID of the `connect` command.
+const CONNECT_TRANSACTION_ID: u32 = 1;
+
+/// What to do with a stream once the server has created it.
+#[derive(Clone, Debug, Collect)]
+#[collect(require_static)]
+enum StreamRequest {
+    Play(String),
+    Publish(String, String),
+}
+
+/// A `NetStream` that's waiting for the server to reply to `createStream`.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct PendingStream<'gc> {
+    stream: NetStream<'gc>,
+    request: StreamRequest,
+}
+
+/// A `NetStream` that has a stream ID on the server.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct RtmpStream<'gc> {
+    stream: NetStream<'gc>,
+    /// The timestamp of the first media message, which media timestamps are relative to.
+    first_timestamp: Option<u32>,
+}
+
+/// A `SharedObject` that's connected to a shared object on the server.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct RemoteSharedObject<'gc> {
+    object: Object<'gc>,
+    persistent: bool,
+    /// The version of the shared object that the server last sent us.
+    version: u32,
+    /// Properties that we've asked the server to change, which it hasn't accepted yet.
+    pending_changes: HashSet<String>,
+}
+
+#[derive(Collect)]
+#[collect(no_drop)]
+struct NetConnection<'gc> {
+    target: Object<'gc>,
+    sender: Sender<Vec<u8>>,
+    #[collect(require_static)]
+    session: RtmpSession,
+    uri: String,
+    secure: bool,
+    /// Whether the server has accepted the `connect` command.
+    connected: bool,
+    /// The `Responder`s of calls that are waiting for a reply, by transaction ID.
+    responders: HashMap<u32, Object<'gc>>,
+    pending_streams: HashMap<u32, PendingStream<'gc>>,
+    streams: HashMap<u32, RtmpStream<'gc>>,
+    /// The remote shared objects that are connected through this connection, by name.
+    shared_objects: HashMap<String, RemoteSharedObject<'gc>>,
+}
+
+impl<'gc> NetConnection<'gc> {
+    /// Sends anything that the session has queued to the server.
+    fn flush(&mut self) {
+        let output = self.session.take_output();
+        if !output.is_empty() {
+            let _ = self.sender.send(output);
+        }
+    }
+
+    fn stream_id(&self, stream: NetStream<'gc>) -> Option<u32> {
+        self.streams
+            .iter()
+            .find(|(_, rtmp_stream)| rtmp_stream.stream == stream)
+            .map(|(id, _)| *id)
+    }
+}
+
+/// Manages the RTMP connections of `NetConnection`s.
+pub struct NetConnections<'gc> {
+    connections: Arena<NetConnection<'gc>>,
+
+    receiver: Receiver<SocketAction>,
+    sender: Sender<SocketAction>,
+}
+
+unsafe impl<'gc> Collect for NetConnections<'gc> {
+    fn trace(&self, cc: &gc_arena::Collection) {
+        for (_, connection) in self.connections.iter() {
+            connection.trace(cc)
+        }
+    }
+}
+
+impl<'gc> NetConnections<'gc> {
+    pub fn empty() -> Self {
+        let (sender, receiver) = channel();
+
+        Self {
+            connections: Arena::new(),
+            receiver,
+            sender,
+        }
+    }
+
+    /// Connects a `NetConnection` to an `rtmp://` or `rtmps://` URL, closing any
+    /// connection that it already had.
+    ///
+    /// `arguments` are sent to the server after the `connect` command object.
+    pub fn connect(
+        context: &mut UpdateContext<'_, 'gc>,
+        target: Object<'gc>,
+        url: &Url,
+        arguments: &[AmfValue],
+    ) -> Result<(), &'static str> {
+        let (kind, default_port) = match url.scheme() {
+            "rtmp" => (SocketKind::Rtmp, rtmp::DEFAULT_PORT),
+            "rtmps" => (SocketKind::Rtmps, rtmp::DEFAULT_SECURE_PORT),
+            _ => return Err("unsupported protocol"),
+        };
+        let Some(host) = url.host_str() else {
+            return Err("missing host");
+        };
+        let port = url.port().unwrap_or(default_port);
+
+        if let Some(existing_handle) = context.net_connections.handle(target) {
+            context.net_connections.connections.remove(existing_handle);
+        }
+
+        // The application is the path, along with any query, which some servers use for authentication.
+        let mut app = url.path().trim_start_matches('/').to_string();
+        if let Some(query) = url.query() {
+            app.push('?');
+            app.push_str(query);
+        }
+        let properties = [
+            ("app", AmfValue::String(app)),
+            (
+                "flashVer",
+                AmfValue::String(format!("LNX {},0,0,0", context.player_version)),
+            ),
+            ("swfUrl", AmfValue::String(context.swf.url().to_string())),
+            ("tcUrl", AmfValue::String(url.to_string())),
+            ("fpad", AmfValue::Bool(false)),
+            ("capabilities", AmfValue::Number(239.0)),
+            ("audioCodecs", AmfValue::Number(3575.0)),
+            ("videoCodecs", AmfValue::Number(252.0)),
+            ("videoFunction", AmfValue::Number(1.0)),
+            ("objectEncoding", AmfValue::Number(0.0)),
+        ];
+        let command_object = AmfValue::Object(
+            properties
+                .into_iter()
+                .map(|(name, value)| Element::new(name, value))
+                .collect(),
+            None,
+        );
+
+        let (sender, receiver) = channel();
+        let mut session = RtmpSession::new();
+        session.connect(command_object, arguments);
+        let mut connection = NetConnection {
+            target,
+            sender,
+            session,
+            uri: url.to_string(),
+            secure: kind == SocketKind::Rtmps,
+            connected: false,
+            responders: HashMap::new(),
+            pending_streams: HashMap::new(),
+            streams: HashMap::new(),
+            shared_objects: HashMap::new(),
+        };
+        connection.flush();
+        let handle = context.net_connections.connections.insert(connection);
+
+        // NOTE: This call will send SocketAction::Connect to sender with connection status.
+        context.navigator.connect_socket(
+            host.to_string(),
+            port,
+            CONNECT_TIMEOUT,
+            handle,
+            receiver,
+            context.net_connections.sender.clone(),
+            kind,
+        );
+        Ok(())
+    }
+
+    pub fn handle(&self, target: Object<'gc>) -> Option<NetConnectionHandle> {
+        self.connections
+            .iter()
+            .find(|(_, connection)| Object::ptr_eq(connection.target, target))
+            .map(|(handle, _)| handle)
+    }
+
+    pub fn is_connected(&self, handle: NetConnectionHandle) -> bool {
+        matches!(
+            self.connections.get(handle),
+            Some(NetConnection {
+                connected: true,
+                ..
+            })
+        )
+    }
+
+    pub fn uri(&self, handle: NetConnectionHandle) -> Option<&str> {
+        self.connections
+            .get(handle)
+            .map(|connection| connection.uri.as_str())
+    }
+
+    pub fn is_secure(&self, handle: NetConnectionHandle) -> bool {
+        matches!(
+            self.connections.get(handle),
+            Some(NetConnection { secure: true, .. })
+        )
+    }
+
+    /// Calls a command on the server, which replies to `responder` if there is one.
+    pub fn call(
+        &mut self,
+        handle: NetConnectionHandle,
+        command: &str,
+        responder: Option<Object<'gc>>,
+        arguments: &[AmfValue],
+    ) {
+        let Some(connection) = self.connections.get_mut(handle) else {
+            return;
+        };
+
+        let transaction_id = match responder {
+            Some(responder) => {
+                let transaction_id = connection.session.next_transaction_id();
+                connection
+                    .responders
+                    .insert(transaction_id as u32, responder);
+                transaction_id
+            }
+            None => 0.0,
+        };
+        connection
+            .session
+            .send_command(0, command, transaction_id, &AmfValue::Null, arguments);
+        connection.flush();
+    }
+
+    /// Closes a connection. Its `NetConnection` receives no more events.
+    pub fn close(&mut self, handle: NetConnectionHandle) {
+        if let Some(NetConnection { sender, .. }) = self.connections.remove(handle) {
+            drop(sender); // NOTE: By dropping the sender, the reading task will close automatically.
+        }
+    }
+
+    /// Starts playing a stream from the server on a `NetStream`.
+    pub fn play(&mut self, handle: NetConnectionHandle, stream: NetStream<'gc>, name: String) {
+        self.request_stream(handle, stream, StreamRequest::Play(name));
+    }
+
+    /// Starts publishing a `NetStream` to the server.
+    pub fn publish(
+        &mut self,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        name: String,
+        publish_type: String,
+    ) {
+        self.request_stream(handle, stream, StreamRequest::Publish(name, publish_type));
+    }
+
+    fn request_stream(
+        &mut self,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        request: StreamRequest,
+    ) {
+        let Some(connection) = self.connections.get_mut(handle) else {
+            return;
+        };
+
+        // A stream that's already been created can be reused.
+        if let Some(stream_id) = connection.stream_id(stream) {
+            Self::start_stream(connection, stream_id, request);
+        } else {
+            let transaction_id = connection.session.next_transaction_id();
+            connection.session.send_command(
+                0,
+                "createStream",
+                transaction_id,
+                &AmfValue::Null,
+                &[],
+            );
+            connection
+                .pending_streams
+                .insert(transaction_id as u32, PendingStream { stream, request });
+        }
+        connection.flush();
+    }
+
+    fn start_stream(connection: &mut NetConnection<'gc>, stream_id: u32, request: StreamRequest) {
+        let session = &mut connection.session;
+        match request {
+            StreamRequest::Play(name) => {
+                // Play live streams if there are any, and recorded streams otherwise.
+                session.send_command(
+                    stream_id,
+                    "play",
+                    0.0,
+                    &AmfValue::Null,
+                    &[AmfValue::String(name), AmfValue::Number(-2000.0)],
+                );
+                session.set_buffer_length(stream_id, 100);
+            }
+            StreamRequest::Publish(name, publish_type) => {
+                session.send_command(
+                    stream_id,
+                    "publish",
+                    0.0,
+                    &AmfValue::Null,
+                    &[AmfValue::String(name), AmfValue::String(publish_type)],
+                );
+            }
+        }
+    }
+
+    /// Sends a data message on a stream, as `NetStream.send` does.
+    pub fn send_stream_data(
+        &mut self,
+        handle: NetConnectionHandle,
+        stream: NetStream<'gc>,
+        values: &[AmfValue],
+    ) {
+        let Some(connection) = self.connections.get_mut(handle) else {
+            return;
+        };
+        if let Some(stream_id) = connection.stream_id(stream) {
+            connection.session.send_data(stream_id, values);
+            connection.flush();
+        }
+    }
+
+    /// Stops playing or publishing a stream, and deletes it from the server.
+    pub fn close_stream(&mut self, handle: NetConnectionHandle, stream: NetStream<'gc>) {
+        let Some(connection) = self.connections.get_mut(handle) else {
+            return;
+        };
+        connection
+            .pending_streams
+            .retain(|_, pending| pending.stream != stream);
+        if let Some(stream_id) = connection.stream_id(stream) {
+            connection.streams.remove(&stream_id);
+            let stream_id_value = [AmfValue::Number(stream_id.into())];
+            let session = &mut connection.session;
+            session.send_command(stream_id, "closeStream", 0.0, &AmfValue::Null, &[]);
+            session.send_command(0, "deleteStream", 0.0, &AmfValue::Null, &stream_id_value);
+            connection.flush();
+        }
+    }
+
+    /// Connects a `SharedObject` to the shared object with the given name on the server,
+    /// disconnecting it from any other connection.
+    ///
+    /// The server replies by sending the properties of the shared object.
+    pub fn connect_shared_object(
+        &mut self,
+        handle: NetConnectionHandle,
+        object: Object<'gc>,
+        name: String,
+        persistent: bool,
+    ) {
+        self.close_shared_object(object);
+        let Some(connection) = self.connections.get_mut(handle) else {
+            return;
+        };
+
+        connection
+            .session
+            .send_shared_object(&name, 0, persistent, &[SharedObjectEvent::Use]);
+        connection.flush();
+        connection.shared_objects.insert(
+            name,
+            RemoteSharedObject {
+                object,
+                persistent,
+                version: 0,
+                pending_changes: HashSet::new(),
+            },
+        );
+    }
+
+    /// Finds the connection of a remote `SharedObject`, along with its name on the server.
+    fn shared_object_connection(
+        &mut self,
+        object: Object<'gc>,
+    ) -> Option<(&mut NetConnection<'gc>, String)> {
+        self.connections.iter_mut().find_map(|(_, connection)| {
+            let name = connection
+                .shared_objects
+                .iter()
+                .find(|(_, shared_object)| Object::ptr_eq(shared_object.object, object))
+                .map(|(name, _)| name.clone())?;
+            Some((connection, name))
+        })
+    }
+
+    /// Whether a `SharedObject` is connected to a shared object on a server.
+    pub fn is_shared_object_connected(&mut self, object: Object<'gc>) -> bool {
+        self.shared_object_connection(object).is_some()
+    }
+
+    /// Asks the server to change a property of a remote shared object, or to remove it
+    /// if `value` is `None`.
+    pub fn set_shared_object_property(
+        &mut self,
+        object: Object<'gc>,
+        property: String,
+        value: Option<AmfValue>,
+    ) {
+        let Some((connection, name)) = self.shared_object_connection(object) else {
+            return;
+        };
+        let shared_object = connection
+            .shared_objects
+            .get_mut(&name)
+            .expect("Shared object was just found");
+
+        let event = match value {
+            Some(value) => SharedObjectEvent::RequestChange(property.clone(), value),
+            None => SharedObjectEvent::RequestRemove(property.clone()),
+        };
+        shared_object.pending_changes.insert(property);
+        connection.session.send_shared_object(
+            &name,
+            shared_object.version,
+            shared_object.persistent,
+            &[event],
+        );
+        connection.flush();
+    }
+
+    /// Calls a handler on every client of a remote shared object, including this one,
+    /// as `SharedObject.send` does.
+    pub fn send_shared_object_message(
+        &mut self,
+        object: Object<'gc>,
+        handler: String,
+        arguments: Vec<AmfValue>,
+    ) {
+        let Some((connection, name)) = self.shared_object_connection(object) else {
+            return;
+        };
+        let shared_object = &connection.shared_objects[&name];
+
+        connection.session.send_shared_object(
+            &name,
+            shared_object.version,
+            shared_object.persistent,
+            &[SharedObjectEvent::SendMessage(handler, arguments)],
+        );
+        connection.flush();
+    }
+
+    /// Disconnects a remote `SharedObject`, which receives no more events.
+    pub fn close_shared_object(&mut self, object: Object<'gc>) {
+        let Some((connection, name)) = self.shared_object_connection(object) else {
+            return;
+        };
+        let Some(shared_object) = connection.shared_objects.remove(&name) else {
+            return;
+        };
+
+        connection.session.send_shared_object(
+            &name,
+            shared_object.version,
+            shared_object.persistent,
+            &[SharedObjectEvent::Release],
+        );
+        connection.flush();
+    }
+
+    pub fn update_net_connections(context: &mut UpdateContext<'_, 'gc>) {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        let mut actions = vec![];
+
+        while let Ok(action) = activation.context.net_connections.receiver.try_recv() {
+            actions.push(action)
+        }
+
+        for action in actions {
+            match action {
+                SocketAction::Connect(_, ConnectionState::Connected) => {
+                    // The handshake was sent when the connection was made.
+                }
+                SocketAction::Connect(handle, _) => {
+                    let target = match activation
+                        .context
+                        .net_connections
+                        .connections
+                        .remove(handle)
+                    {
+                        Some(connection) => connection.target,
+                        // Connection must have been closed before we could send event.
+                        None => continue,
+                    };
+                    dispatch_status(
+                        &mut activation,
+                        target,
+                        "NetConnection.Connect.Failed",
+                        "error",
+                    );
+                }
+                SocketAction::Data(handle, data) => {
+                    let Some(connection) = activation
+                        .context
+                        .net_connections
+                        .connections
+                        .get_mut(handle)
+                    else {
+                        continue;
+                    };
+
+                    let result = connection.session.receive(&data);
+                    connection.flush();
+                    match result {
+                        Ok(events) => {
+                            for event in events {
+                                Self::handle_event(&mut activation, handle, event);
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("RTMP connection failed: {}", e);
+                            Self::handle_close(&mut activation, handle);
+                        }
+                    }
+                }
+                SocketAction::Close(handle) => Self::handle_close(&mut activation, handle),
+                SocketAction::Accept(..) | SocketAction::Datagram(..) => {
+                    unreachable!("NetConnections only connect sockets")
+                }
+            }
+        }
+    }
+
+    fn handle_close(activation: &mut Activation<'_, 'gc>, handle: NetConnectionHandle) {
+        let (target, connected) = match activation
+            .context
+            .net_connections
+            .connections
+            .remove(handle)
+        {
+            Some(connection) => (connection.target, connection.connected),
+            // Connection must have been closed before we could send event.
+            None => return,
+        };
+
+        let code = if connected {
+            "NetConnection.Connect.Closed"
+        } else {
+            "NetConnection.Connect.Failed"
+        };
+        let level = if connected { "status" } else { "error" };
+        dispatch_status(activation, target, code, level);
+    }
+
+    fn handle_event(
+        activation: &mut Activation<'_, 'gc>,
+        handle: NetConnectionHandle,
+        event: RtmpEvent,
+    ) {
+        let Some(connection) = activation
+            .context
+            .net_connections
+            .connections
+            .get_mut(handle)
+        else {
+            // An earlier event closed the connection.
+            return;
+        };
+
+        match event {
+            RtmpEvent::Command {
+                stream_id,
+                name,
+                transaction_id,
+                command_object,
+                arguments,
+            } => {
+                let is_result = name == "_result";
+                if is_result || name == "_error" {
+                    let info = arguments.first().unwrap_or(&command_object).clone();
+                    Self::handle_reply(activation, handle, transaction_id, is_result, &info);
+                } else if name == "onStatus" {
+                    let info = arguments.first().unwrap_or(&AmfValue::Null);
+                    let target = if stream_id == 0 {
+                        Some(connection.target)
+                    } else {
+                        connection
+                            .streams
+                            .get(&stream_id)
+                            .and_then(|stream| stream.stream.avm2_object())
+                    };
+                    if let Some(target) = target {
+                        dispatch_status_info(activation, target, info);
+                    }
+                } else if name == "close" {
+                    Self::handle_close(activation, handle);
+                } else {
+                    Self::call_client(
+                        activation,
+                        handle,
+                        stream_id,
+                        &name,
+                        transaction_id,
+                        &arguments,
+                    );
+                }
+            }
+            RtmpEvent::Media {
+                stream_id,
+                kind,
+                timestamp,
+                data,
+            } => {
+                let Some(rtmp_stream) = connection.streams.get_mut(&stream_id) else {
+                    return;
+                };
+
+                let first_timestamp = *rtmp_stream.first_timestamp.get_or_insert(timestamp);
+                let timestamp = timestamp.wrapping_sub(first_timestamp);
+
+                // Media is played by the `NetStream` as if it were an FLV file.
+                let tag = flv_tag(kind.flv_tag_type(), timestamp, &data);
+                rtmp_stream.stream.append_data(&tag);
+            }
+            RtmpEvent::StreamBegin(stream_id) => {
+                tracing::debug!("RTMP stream {} began", stream_id);
+            }
+            RtmpEvent::SharedObject {
+                name,
+                version,
+                events,
+            } => {
+                let Some(shared_object) = connection.shared_objects.get_mut(&name) else {
+                    return;
+                };
+                shared_object.version = version;
+                let object = shared_object.object;
+
+                // A change to a property that we asked to change means that the server rejected ours.
+                let codes: Vec<_> = events
+                    .iter()
+                    .map(|event| match event {
+                        SharedObjectEvent::Change(property, _)
+                            if shared_object.pending_changes.remove(property) =>
+                        {
+                            "reject"
+                        }
+                        SharedObjectEvent::Success(property) => {
+                            shared_object.pending_changes.remove(property);
+                            "success"
+                        }
+                        _ => "change",
+                    })
+                    .collect();
+
+                if let Err(e) = Self::sync_shared_object(activation, object, events, &codes) {
+                    tracing::error!("Unhandled error syncing remote shared object: {:?}", e);
+                }
+            }
+        }
+    }
+
+    /// Applies the events that the server sent for a remote `SharedObject` to its data,
+    /// and tells it what changed with a `sync` event.
+    ///
+    /// `codes` are the change list codes of `change` and `success` events.
+    fn sync_shared_object(
+        activation: &mut Activation<'_, 'gc>,
+        mut object: Object<'gc>,
+        events: Vec<SharedObjectEvent>,
+        codes: &[&'static str],
+    ) -> Result<(), Error<'gc>> {
+        let mut change_list = Vec::new();
+        for (event, code) in events.into_iter().zip(codes) {
+            let mut data = object.get_public_property("data", activation)?.as_object();
+            let change = match event {
+                SharedObjectEvent::Change(property, value) => {
+                    let property = AvmString::new_utf8(activation.context.gc_context, property);
+                    let value = deserialize_value(activation, &value)?;
+                    let old_value = match &mut data {
+                        Some(data) => {
+                            let old_value = data.get_public_property(property, activation)?;
+                            data.set_public_property(property, value, activation)?;
+                            old_value
+                        }
+                        None => Value::Undefined,
+                    };
+                    Some((*code, Some(property), old_value))
+                }
+                SharedObjectEvent::Success(property) => {
+                    let property = AvmString::new_utf8(activation.context.gc_context, property);
+                    Some((*code, Some(property), Value::Undefined))
+                }
+                SharedObjectEvent::Remove(property) => {
+                    let property = AvmString::new_utf8(activation.context.gc_context, property);
+                    let old_value = match data {
+                        Some(data) => {
+                            let old_value = data.get_public_property(property, activation)?;
+                            data.delete_public_property(activation, property)?;
+                            old_value
+                        }
+                        None => Value::Undefined,
+                    };
+                    Some(("delete", Some(property), old_value))
+                }
+                SharedObjectEvent::Clear => {
+                    let data = activation
+                        .avm2()
+                        .classes()
+                        .object
+                        .construct(activation, &[])?;
+                    object.set_public_property("data", data.into(), activation)?;
+                    Some(("clear", None, Value::Undefined))
+                }
+                SharedObjectEvent::SendMessage(handler, arguments) => {
+                    let ns = activation.avm2().flash_net_internal;
+                    let client = object
+                        .get_property(&Multiname::new(ns, "_client"), activation)?
+                        .as_object();
+                    if let Some(client) = client {
+                        let arguments = arguments
+                            .iter()
+                            .map(|argument| deserialize_value(activation, argument))
+                            .collect::<Result<Vec<_>, _>>()?;
+                        let handler = AvmString::new_utf8(activation.context.gc_context, handler);
+                        client.call_public_property(handler, &arguments, activation)?;
+                    }
+                    None
+                }
+                SharedObjectEvent::Status(code, level) => {
+                    let info = AmfValue::Object(
+                        vec![
+                            Element::new("code", AmfValue::String(code)),
+                            Element::new("level", AmfValue::String(level)),
+                        ],
+                        None,
+                    );
+                    dispatch_status_info(activation, object, &info);
+                    None
+                }
+                // The other events are only sent by clients.
+                _ => None,
+            };
+
+            if let Some((code, name, old_value)) = change {
+                let mut info = activation
+                    .avm2()
+                    .classes()
+                    .object
+                    .construct(activation, &[])?;
+                info.set_public_property("code", code.into(), activation)?;
+                if let Some(name) = name {
+                    info.set_public_property("name", name.into(), activation)?;
+                }
+                if old_value != Value::Undefined {
+                    info.set_public_property("oldValue", old_value, activation)?;
+                }
+                change_list.push(Value::from(info));
+            }
+        }
+
+        if !change_list.is_empty() {
+            let change_list =
+                ArrayObject::from_storage(activation, change_list.into_iter().collect())?;
+            let sync_event = activation.avm2().classes().syncevent.construct(
+                activation,
+                &[
+                    "sync".into(),
+                    false.into(),
+                    false.into(),
+                    change_list.into(),
+                ],
+            )?;
+            Avm2::dispatch_event(&mut activation.context, sync_event, object);
+        }
+        Ok(())
+    }
+
+    /// Handles a `_result` or `_error` reply to one of our commands.
+    fn handle_reply(
+        activation: &mut Activation<'_, 'gc>,
+        handle: NetConnectionHandle,
+        transaction_id: f64,
+        is_result: bool,
+        info: &AmfValue,
+    ) {
+        let Some(connection) = activation
+            .context
+            .net_connections
+            .connections
+            .get_mut(handle)
+        else {
+            return;
+        };
+        let transaction_id = transaction_id as u32;
+
+        if transaction_id == CONNECT_TRANSACTION_ID && !connection.connected {
+            let target = connection.target;
+            if is_result {
+                connection.connected = true;
+                dispatch_status_info(activation, target, info);
+            } else {
+                // The server rejected us, and will close the connection.
+                activation.context.net_connections.close(handle);
+                dispatch_status_info(activation, target, info);
+                dispatch_status(activation, target, "NetConnection.Connect.Closed", "status");
+            }
+        } else if let Some(PendingStream { stream, request }) =
+            connection.pending_streams.remove(&transaction_id)
+        {
+            match info {
+                AmfValue::Number(stream_id) if is_result => {
+                    let stream_id = *stream_id as u32;
+                    connection.streams.insert(
+                        stream_id,
+                        RtmpStream {
+                            stream,
+                            first_timestamp: None,
+                        },
+                    );
+                    Self::start_stream(connection, stream_id, request.clone());
+                    connection.flush();
+
+                    if let StreamRequest::Play(_) = request {
+                        stream.start_live(activation.context.gc_context);
+                        StreamManager::ensure_playing(&mut activation.context, stream);
+                    }
+                }
+                _ => {
+                    if let Some(target) = stream.avm2_object() {
+                        dispatch_status(activation, target, "NetStream.Failed", "error");
+                    }
+                }
+            }
+        } else if let Some(responder) = connection.responders.remove(&transaction_id) {
+            let ns = activation.avm2().flash_net_internal;
+            let name = if is_result { "_result" } else { "_status" };
+            let result = deserialize_value(activation, info).and_then(|info| {
+                let function = responder.get_property(&Multiname::new(ns, name), activation)?;
+                if let Some(function) = function.as_object() {
+                    function.call(responder.into(), &[info], activation)?;
+                }
+                Ok(())
+            });
+            if let Err(e) = result {
+                tracing::error!("Unhandled error in Responder: {:?}", e);
+            }
+        }
+    }
+
+    /// Calls a method that the server called on the client of a `NetConnection` or `NetStream`,
+    /// and sends its return value back if the server asked for one.
+    fn call_client(
+        activation: &mut Activation<'_, 'gc>,
+        handle: NetConnectionHandle,
+        stream_id: u32,
+        name: &str,
+        transaction_id: f64,
+        arguments: &[AmfValue],
+    ) {
+        let Some(connection) = activation.context.net_connections.connections.get(handle) else {
+            return;
+        };
+        let client = if stream_id == 0 {
+            let ns = activation.avm2().flash_net_internal;
+            connection
+                .target
+                .get_property(&Multiname::new(ns, "_client"), activation)
+                .ok()
+                .and_then(|client| client.as_object())
+        } else {
+            connection
+                .streams
+                .get(&stream_id)
+                .and_then(|stream| stream.stream.client())
+        };
+        let Some(client) = client else {
+            return;
+        };
+
+        let result = arguments
+            .iter()
+            .map(|argument| deserialize_value(activation, argument))
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(|arguments| {
+                let name = AvmString::new_utf8(activation.context.gc_context, name);
+                client.call_public_property(name, &arguments, activation)
+            });
+        let result = match result {
+            Ok(result) => result,
+            Err(e) => {
+                tracing::error!(
+                    "Unhandled error calling RTMP client method {}: {:?}",
+                    name,
+                    e
+                );
+                Value::Undefined
+            }
+        };
+
+        if transaction_id != 0.0 {
+            let result = serialize_value(activation, result).unwrap_or(AmfValue::Undefined);
+            if let Some(connection) = activation
+                .context
+                .net_connections
+                .connections
+                .get_mut(handle)
+            {
+                connection.session.send_command(
+                    stream_id,
+                    "_result",
+                    transaction_id,
+                    &AmfValue::Null,
+                    &[result],
+                );
+                connection.flush();
+            }
+        }
+    }
+}
+
+/// Dispatches a `netStatus` event with an info object sent by the server.
+fn dispatch_status_info<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: Object<'gc>,
+    info: &AmfValue,
+) {
+    let info = match deserialize_value(activation, info) {
+        Ok(info) => info,
+        Err(e) => {
+            tracing::error!("Failed to read RTMP status info: {:?}", e);
+            return;
+        }
+    };
+
+    let net_status_event = activation
+        .avm2()
+        .classes()
+        .netstatusevent
+        .construct(
+            activation,
+            &["netStatus".into(), false.into(), false.into(), info],
+        )
+        .expect("NetStatusEvent should be constructed");
+    Avm2::dispatch_event(&mut activation.context, net_status_event, target);
+}
+
+fn dispatch_status<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    target: Object<'gc>,
+    code: &'static str,
+    level: &'static str,
+) {
+    let net_status_event =
+        EventObject::net_status_event(activation, "netStatus", &[("code", code), ("level", level)]);
+    Avm2::dispatch_event(&mut activation.context, net_status_event, target);
+}
@@ -1,3 +1,5 @@
+use crate::accessibility::{self, AccessibilityNode};
+use crate::api_usage::ApiUsage;
 use crate::avm1::globals::system::SandboxType;
 use crate::avm1::Attribute;
 use crate::avm1::Avm1;
@@ -7,45 +9,62 @@ use crate::avm1::VariableDumper;
 use crate::avm1::{Activation, ActivationIdentifier};
 use crate::avm1::{ScriptObject, TObject, Value};
 use crate::avm2::{
+    object::ArrayObject as Avm2ArrayObject, object::EventObject as Avm2EventObject,
     object::LoaderInfoObject, object::TObject as _, Activation as Avm2Activation, Avm2, CallStack,
-    Object as Avm2Object,
+    MemoryProfile, Multiname, Object as Avm2Object,
 };
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    filesystem::FilesystemBackend,
     log::LogBackend,
     navigator::{NavigatorBackend, Request},
     storage::StorageBackend,
     ui::{InputManager, MouseCursor, UiBackend},
 };
+use crate::camera::Cameras;
 use crate::compatibility_rules::CompatibilityRules;
-use crate::config::Letterbox;
+use crate::config::{FrameRateMode, Letterbox};
+use crate::console;
 use crate::context::GcContext;
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{
     BuiltInItemFlags, ContextMenuCallback, ContextMenuItem, ContextMenuState,
 };
+use crate::debugger::{Debugger, DebuggerConnection};
 use crate::display_object::Avm2MousePick;
 use crate::display_object::{
     EditText, InteractiveObject, MovieClip, Stage, StageAlign, StageDisplayState, StageScaleMode,
     TInteractiveObject, WindowMode,
 };
-use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, MouseButton, PlayerEvent};
+use crate::events::{
+    ButtonKeyCode, ClipEvent, ClipEventResult, GamepadAxis, GamepadButton, KeyCode, KeyLocation,
+    MouseButton, PlayerEvent, TextControlCode, TouchPhase, TouchPoint,
+};
 use crate::external::{ExternalInterface, ExternalInterfaceProvider, NullFsCommandProvider};
 use crate::external::{FsCommandProvider, Value as ExternalValue};
 use crate::focus_tracker::FocusTracker;
 use crate::font::Font;
 use crate::frame_lifecycle::{run_all_phases_avm2, FramePhase};
+use crate::headless::InputScript;
+use crate::input_recording::InputLog;
+use crate::inspector::{self, DisplayNode, DisplayProperty};
 use crate::library::Library;
 use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
+use crate::local_connection::LocalConnections;
 use crate::locale::get_current_date_time;
+use crate::mouse_cursor::MouseCursors;
+use crate::net_connection::NetConnections;
 use crate::prelude::*;
+use crate::profiler::{Category, Profiler};
+use crate::save_state::{Journal, JournalEntry, SaveState};
 use crate::socket::Sockets;
 use crate::streams::StreamManager;
-use crate::string::{AvmString, AvmStringInterner};
-use crate::stub::StubCollection;
+use crate::string::{AvmString, AvmStringInterner, WString};
+use crate::stub::{CompatibilityReport, StubCollection};
 use crate::tag_utils::SwfMovie;
 use crate::timer::Timers;
+use crate::touch::{Gesture, GesturePhase, TouchResult, TouchTracker};
 use crate::vminterface::Instantiator;
 use gc_arena::{ArenaParameters, Collect, DynamicRootSet, GcCell, Rootable};
 use instant::Instant;
@@ -68,6 +87,17 @@ use tracing::{info, instrument};
 /// `player_version`.
 pub const NEWEST_PLAYER_VERSION: u8 = 32;
 
+/// How often the accessibility tree is rebuilt while assistive technology is active, in milliseconds.
+const ACCESSIBILITY_UPDATE_INTERVAL: f64 = 500.0;
+
+/// How many frames the player may fall behind the movie's frame rate before it gives up
+/// on catching up and drops them.
+const MAX_FRAMES_BEHIND: f64 = 10.0;
+
+/// The shortest time the host waits between frames in [`FrameRateMode::Unlimited`], so that
+/// it yields to the rest of the system instead of spinning.
+const MIN_UNLIMITED_FRAME_INTERVAL: Duration = Duration::from_millis(1);
+
 #[derive(Collect)]
 #[collect(no_drop)]
 struct GcRoot<'gc> {
@@ -163,6 +193,15 @@ struct GcRootData<'gc> {
 
     sockets: Sockets<'gc>,
 
+    /// RTMP connections made by `NetConnection`.
+    net_connections: NetConnections<'gc>,
+
+    /// `LocalConnection`s that are listening for messages.
+    local_connections: LocalConnections<'gc>,
+
+    /// Cameras that video can be captured from.
+    cameras: Cameras<'gc>,
+
     /// Dynamic root for allowing handles to GC objects to exist outside of the GC.
     dynamic_root: DynamicRootSet<'gc>,
 }
@@ -191,6 +230,9 @@ impl<'gc> GcRootData<'gc> {
         &mut AudioManager<'gc>,
         &mut StreamManager<'gc>,
         &mut Sockets<'gc>,
+        &mut NetConnections<'gc>,
+        &mut LocalConnections<'gc>,
+        &mut Cameras<'gc>,
         DynamicRootSet<'gc>,
     ) {
         (
@@ -211,6 +253,9 @@ impl<'gc> GcRootData<'gc> {
             &mut self.audio_manager,
             &mut self.stream_manager,
             &mut self.sockets,
+            &mut self.net_connections,
+            &mut self.local_connections,
+            &mut self.cameras,
             self.dynamic_root,
         )
     }
@@ -219,6 +264,7 @@ impl<'gc> GcRootData<'gc> {
 type GcArena = gc_arena::Arena<Rootable![GcRoot<'_>]>;
 
 type Audio = Box<dyn AudioBackend>;
+type Filesystem = Box<dyn FilesystemBackend>;
 type Navigator = Box<dyn NavigatorBackend>;
 type Renderer = Box<dyn RenderBackend>;
 type Storage = Box<dyn StorageBackend>;
@@ -244,10 +290,15 @@ pub struct Player {
     is_playing: bool,
     needs_render: bool,
 
+    /// The display object that an inspector wants outlined on the stage, by its path from the
+    /// stage. See [`crate::inspector`].
+    inspector_highlight: Option<Vec<usize>>,
+
     renderer: Renderer,
     audio: Audio,
     navigator: Navigator,
     storage: Storage,
+    filesystem: Filesystem,
     log: Log,
     ui: Ui,
     video: Video,
@@ -260,16 +311,38 @@ pub struct Player {
 
     frame_rate: f64,
     forced_frame_rate: bool,
+
+    /// How frames are scheduled relative to ticks of the host.
+    frame_rate_mode: FrameRateMode,
+
+    /// Whether a frame has run in [`FrameRateMode::Vsync`] that hasn't been rendered yet.
+    frame_awaiting_present: bool,
+
     actions_since_timeout_check: u16,
 
     frame_phase: FramePhase,
 
     stub_tracker: StubCollection,
 
+    /// Timings of the player, recorded while profiling is enabled.
+    profiler: Profiler,
+
+    /// Calls to built-in APIs, counted while enabled.
+    api_usage: ApiUsage,
+
+    /// Everything that happened to the player, recorded while save states are enabled.
+    journal: Journal,
+
+    /// Decides when scripts stop while a debugger is attached.
+    debugger: Debugger,
+
+    /// The input being recorded or replayed, if any.
+    input_log: Option<InputLog>,
+
     /// A time budget for executing frames.
     /// Gained by passage of time between host frames, spent by executing SWF frames.
     /// This is how we support custom SWF framerates
-    /// and compensate for small lags by "catching up" (up to MAX_FRAMES_PER_TICK).
+    /// and compensate for small lags by "catching up" (up to MAX_FRAMES_BEHIND).
     frame_accumulator: f64,
     recent_run_frame_timings: VecDeque<f64>,
 
@@ -285,6 +358,19 @@ pub struct Player {
     mouse_cursor: MouseCursor,
     mouse_cursor_needs_check: bool,
 
+    /// The cursor chosen with `Mouse.cursor`, which overrides `mouse_cursor`.
+    mouse_cursors: MouseCursors,
+
+    /// The keys that gamepad buttons press, for movies that don't use `GameInput`.
+    gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
+
+    /// The points touching the touchscreen, from which gestures are recognized.
+    touches: TouchTracker,
+
+    /// The accessibility tree that was last exposed, and how long until it's rebuilt, in milliseconds.
+    accessibility_tree: Option<AccessibilityNode>,
+    time_until_accessibility_update: f64,
+
     system: SystemProperties,
 
     /// The current instance ID. Used to generate default `instanceN` names.
@@ -296,10 +382,16 @@ pub struct Player {
     /// The instant at which the SWF was launched.
     start_time: Instant,
 
+    /// The time that scripts see as having passed, if the player runs on a fixed clock.
+    fixed_time: Option<Duration>,
+
     /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
     /// is raised. This defaults to 15 seconds but can be changed.
     max_execution_duration: Duration,
 
+    /// How far ahead of playback dynamic sounds request samples through `SampleDataEvent`.
+    sample_data_latency: Duration,
+
     /// Self-reference to ourselves.
     ///
     /// This is a weak reference that is upgraded and handed out in various
@@ -372,6 +464,15 @@ impl Player {
         self.swf = Arc::new(movie);
         self.instance_counter = 0;
 
+        let mut seed = get_current_date_time().timestamp_millis() as u64;
+        if let Some(input_log) = &mut self.input_log {
+            seed = input_log.start(seed);
+            self.rng = SmallRng::seed_from_u64(seed);
+        }
+        if let Some(seed) = self.journal.start(seed) {
+            self.rng = SmallRng::seed_from_u64(seed);
+        }
+
         self.mutate_with_update_context(|context| {
             context.stage.set_movie_size(
                 context.gc_context,
@@ -493,6 +594,139 @@ impl Player {
         }
     }
 
+    /// Run as many frames as are due at the movie's frame rate.
+    ///
+    /// Frames that are missed because the host fell behind are caught up on, a few at a
+    /// time, so that the movie keeps in time with its audio. If the player falls too far
+    /// behind, the missed frames are dropped instead of running the movie at turbo speed.
+    fn run_native_frames(&mut self, dt: f64) {
+        self.frame_accumulator += dt;
+
+        let max_frames_per_tick = self.max_frames_per_tick();
+        let mut frame = 0;
+
+        // The frame rate is looked up again for every frame, as `Stage.frameRate` may have
+        // been changed by the frame that just ran.
+        while frame < max_frames_per_tick && self.frame_accumulator >= self.frame_time() {
+            let frame_time = self.frame_time();
+            let timer = Instant::now();
+            self.run_ticked_frame();
+            let elapsed = timer.elapsed().as_millis() as f64;
+
+            self.add_frame_timing(elapsed);
+
+            self.frame_accumulator -= frame_time;
+            frame += 1;
+            // The script probably tried implementing an FPS limiter with a busy loop.
+            // We fooled the busy loop by pretending that more time has passed that actually did.
+            // Then we need to actually pass this time, by decreasing frame_accumulator
+            // to delay the future frame.
+            if self.time_offset > 0 {
+                self.frame_accumulator -= self.time_offset as f64;
+            }
+        }
+
+        // Now that we're done running code,
+        // we can stop pretending that more time passed than actually did.
+        // Note: update_timers(dt) doesn't need to see this either.
+        // Timers will run at correct times and see correct time.
+        // Also note that in Flash, a blocking busy loop would delay setTimeout
+        // and cancel some setInterval callbacks, but here busy loops don't block
+        // so timer callbacks won't get cancelled/delayed.
+        self.time_offset = 0;
+
+        // Frames we haven't been able to run this tick are caught up on during the next
+        // ones, but only up to a point. Past that, drop whole frames and keep the remainder
+        // so that the next frame still runs on its original schedule.
+        let frame_time = self.frame_time();
+        if self.frame_accumulator >= frame_time * MAX_FRAMES_BEHIND {
+            self.frame_accumulator %= frame_time;
+        }
+
+        // Adjust playback speed for next frame to stay in sync with timeline audio tracks ("stream" sounds).
+        let cur_frame_offset = self.frame_accumulator;
+        self.frame_accumulator += self.mutate_with_update_context(|context| {
+            context
+                .audio_manager
+                .audio_skew_time(context.audio, cur_frame_offset)
+                * 1000.0
+        });
+    }
+
+    /// Run a frame on behalf of `tick`, sending it any replayed input first.
+    ///
+    /// While input is recorded or replayed, timers run in lockstep with frames instead of
+    /// with the time that actually passed.
+    /// Run a frame once the previous one has been presented, so that frames are paced by
+    /// the display. If the host isn't rendering at all, such as while it's minimized, frames
+    /// run at the movie's own frame rate instead.
+    fn run_vsync_frame(&mut self, dt: f64) {
+        if self.frame_awaiting_present {
+            self.frame_accumulator += dt;
+            if self.frame_accumulator < self.frame_time() {
+                return;
+            }
+        }
+        self.run_host_paced_frame();
+        self.frame_awaiting_present = true;
+    }
+
+    /// Run a single frame at a time chosen by the host, so there's nothing to catch up on.
+    fn run_host_paced_frame(&mut self) {
+        let timer = Instant::now();
+        self.run_ticked_frame();
+        self.add_frame_timing(timer.elapsed().as_millis() as f64);
+        self.frame_accumulator = 0.0;
+        self.time_offset = 0;
+    }
+
+    fn run_ticked_frame(&mut self) {
+        let Some(events) = self.input_log.as_mut().map(InputLog::begin_frame) else {
+            self.run_frame();
+            return;
+        };
+
+        for event in events {
+            self.journal.record(JournalEntry::Event(event.clone()));
+            self.dispatch_event(event);
+        }
+        self.run_frame();
+
+        let frame_time = self.frame_time();
+        if let Some(input_log) = &mut self.input_log {
+            self.fixed_time = Some(input_log.end_frame(frame_time));
+        }
+        self.update_timers(frame_time);
+    }
+
+    /// The time between frames at the current frame rate, in milliseconds.
+    fn frame_time(&self) -> f64 {
+        1000.0 / self.frame_rate
+    }
+
+    /// Update everything other than the movie's frames for `dt` milliseconds passing.
+    fn tick_subsystems(&mut self, dt: f64) {
+        self.journal.record(JournalEntry::Tick(dt));
+
+        self.update_sockets();
+        self.update_net_connections();
+        self.update_local_connections();
+        self.update_cameras();
+        self.mouse_cursors.tick(dt, self.ui.deref_mut());
+        self.update_accessibility(dt);
+        if self.input_log.is_none() {
+            self.update_timers(dt);
+        }
+        self.update(|context| {
+            StreamManager::tick(context, dt);
+        });
+        self.update_dynamic_sounds();
+        self.update(|context| {
+            AudioManager::update_microphone(context);
+        });
+        self.audio.tick();
+    }
+
     pub fn tick(&mut self, dt: f64) {
         // Don't run until preloading is complete.
         // TODO: Eventually we want to stream content similar to the Flash player.
@@ -500,64 +734,70 @@ impl Player {
             return;
         }
 
+        if self.journal.is_replaying() {
+            self.replay_state();
+            return;
+        }
+
+        self.debugger.poll();
+
         if self.is_playing() {
-            self.frame_accumulator += dt;
-            let frame_rate = self.frame_rate;
-            let frame_time = 1000.0 / frame_rate;
-
-            let max_frames_per_tick = self.max_frames_per_tick();
-            let mut frame = 0;
-
-            while frame < max_frames_per_tick && self.frame_accumulator >= frame_time {
-                let timer = Instant::now();
-                self.run_frame();
-                let elapsed = timer.elapsed().as_millis() as f64;
-
-                self.add_frame_timing(elapsed);
-
-                self.frame_accumulator -= frame_time;
-                frame += 1;
-                // The script probably tried implementing an FPS limiter with a busy loop.
-                // We fooled the busy loop by pretending that more time has passed that actually did.
-                // Then we need to actually pass this time, by decreasing frame_accumulator
-                // to delay the future frame.
-                if self.time_offset > 0 {
-                    self.frame_accumulator -= self.time_offset as f64;
-                }
+            match self.frame_rate_mode {
+                FrameRateMode::Native => self.run_native_frames(dt),
+                FrameRateMode::Vsync => self.run_vsync_frame(dt),
+                FrameRateMode::Unlimited => self.run_host_paced_frame(),
             }
 
-            // Now that we're done running code,
-            // we can stop pretending that more time passed than actually did.
-            // Note: update_timers(dt) doesn't need to see this either.
-            // Timers will run at correct times and see correct time.
-            // Also note that in Flash, a blocking busy loop would delay setTimeout
-            // and cancel some setInterval callbacks, but here busy loops don't block
-            // so timer callbacks won't get cancelled/delayed.
-            self.time_offset = 0;
-
-            // Sanity: If we had too many frames to tick, just reset the accumulator
-            // to prevent running at turbo speed.
-            if self.frame_accumulator >= frame_time {
-                self.frame_accumulator = 0.0;
-            }
-
-            // Adjust playback speed for next frame to stay in sync with timeline audio tracks ("stream" sounds).
-            let cur_frame_offset = self.frame_accumulator;
-            self.frame_accumulator += self.mutate_with_update_context(|context| {
-                context
-                    .audio_manager
-                    .audio_skew_time(context.audio, cur_frame_offset)
-                    * 1000.0
-            });
+            self.tick_subsystems(dt);
+        }
+    }
 
-            self.update_sockets();
-            self.update_timers(dt);
-            self.update(|context| {
-                StreamManager::tick(context, dt);
-            });
-            self.audio.tick();
+    /// Replay the state that's being restored, all at once.
+    fn replay_state(&mut self) {
+        let viewport = self.viewport_dimensions();
+        let volume = self.audio.volume();
+        self.audio.set_volume(0.0);
+
+        while let Some(entry) = self.journal.next_replayed() {
+            match entry {
+                JournalEntry::Frame => self.run_frame(),
+                JournalEntry::Tick(dt) => self.tick_subsystems(dt),
+                JournalEntry::Render => self.render(),
+                JournalEntry::Event(event) => self.dispatch_event(event),
+                JournalEntry::Viewport(dimensions) => self.set_viewport_dimensions(dimensions),
+                JournalEntry::Time(_) => {}
+            }
         }
+
+        self.set_viewport_dimensions(viewport);
+        self.audio.set_volume(volume);
+        self.frame_accumulator = 0.0;
+        self.needs_render = true;
     }
+
+    /// Whether this player was created with save states enabled.
+    pub fn save_states_enabled(&self) -> bool {
+        self.journal.is_enabled()
+    }
+
+    /// Save the current state of the movie, if save states are enabled and it has loaded.
+    pub fn save_state(&self) -> Option<SaveState> {
+        self.journal.save()
+    }
+
+    /// Restore a state that was saved from another player running the same movie.
+    ///
+    /// This must be called before the root movie has loaded, as the state is restored by
+    /// replaying it from the start of the movie.
+    pub fn restore_state(&mut self, state: SaveState) {
+        self.journal.set_pending(state);
+    }
+
+    /// The input recorded so far, if this player was created to record it.
+    pub fn input_recording(&self) -> Option<&InputScript> {
+        self.input_log.as_ref()?.recording()
+    }
+
     pub fn time_til_next_timer(&self) -> Option<f64> {
         self.time_til_next_timer
     }
@@ -565,7 +805,17 @@ impl Player {
     /// Returns the approximate duration of time until the next frame is due to run.
     /// This is only an approximation to be used for sleep durations.
     pub fn time_til_next_frame(&self) -> std::time::Duration {
-        let frame_time = 1000.0 / self.frame_rate;
+        if self.is_playing {
+            match self.frame_rate_mode {
+                FrameRateMode::Native => {}
+                // Until the last frame is rendered, wait as long as a native frame would.
+                FrameRateMode::Vsync if self.frame_awaiting_present => {}
+                FrameRateMode::Vsync => return std::time::Duration::ZERO,
+                FrameRateMode::Unlimited => return MIN_UNLIMITED_FRAME_INTERVAL,
+            }
+        }
+
+        let frame_time = self.frame_time();
         let mut dt = if self.frame_accumulator <= 0.0 {
             frame_time
         } else if self.frame_accumulator >= frame_time {
@@ -580,7 +830,28 @@ impl Player {
 
         dt = dt.max(0.0);
 
-        std::time::Duration::from_micros(dt as u64 * 1000)
+        std::time::Duration::from_micros((dt * 1000.0) as u64)
+    }
+
+    /// The time that scripts see as having passed, if the player runs on a fixed clock.
+    pub fn fixed_time(&self) -> Option<Duration> {
+        self.fixed_time
+    }
+
+    /// Run the player on a fixed clock, so that scripts see the given amount of time as
+    /// having passed since the movie started, or go back to real time with `None`.
+    pub fn set_fixed_time(&mut self, fixed_time: Option<Duration>) {
+        self.fixed_time = fixed_time;
+    }
+
+    pub fn frame_rate_mode(&self) -> FrameRateMode {
+        self.frame_rate_mode
+    }
+
+    pub fn set_frame_rate_mode(&mut self, frame_rate_mode: FrameRateMode) {
+        self.frame_rate_mode = frame_rate_mode;
+        self.frame_accumulator = 0.0;
+        self.frame_awaiting_present = false;
     }
 
     pub fn is_playing(&self) -> bool {
@@ -595,6 +866,319 @@ impl Player {
         self.mouse_in_stage = is_in;
     }
 
+    /// Passes a gamepad event to `GameInput`, and presses the key that the button is mapped to.
+    fn handle_gamepad_event(&mut self, event: PlayerEvent) {
+        let key_event = match event {
+            PlayerEvent::GamepadButtonDown { button, .. } => self
+                .gamepad_button_mapping
+                .get(&button)
+                .map(|&key_code| PlayerEvent::KeyDown {
+                    key_code,
+                    key_char: None,
+                    key_location: KeyLocation::Standard,
+                }),
+            PlayerEvent::GamepadButtonUp { button, .. } => self
+                .gamepad_button_mapping
+                .get(&button)
+                .map(|&key_code| PlayerEvent::KeyUp {
+                    key_code,
+                    key_char: None,
+                    key_location: KeyLocation::Standard,
+                }),
+            _ => None,
+        };
+
+        // `GameInput` keeps track of the devices even before an AS3 movie is loaded,
+        // so that the gamepads that are already connected are known to it.
+        self.mutate_with_update_context(|context| {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            if let Err(e) = Self::dispatch_game_input_event(&mut activation, event) {
+                tracing::error!("Error dispatching GameInput event: {}", e);
+            }
+        });
+
+        // The button itself is all that needs to be journaled, but it can't be written to an
+        // input recording, so the key is recorded instead.
+        if let Some(key_event) = key_event {
+            if let Some(input_log) = &mut self.input_log {
+                input_log.record(&key_event);
+            }
+            self.dispatch_event(key_event);
+        }
+    }
+
+    /// Tells AS3's `GameInput` about a gamepad event, which updates its devices and controls.
+    fn dispatch_game_input_event<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        event: PlayerEvent,
+    ) -> Result<(), crate::avm2::Error<'gc>> {
+        let (method, args): (_, Vec<Avm2Value<'gc>>) = match event {
+            PlayerEvent::GamepadConnected { id, name } => {
+                let name = AvmString::new_utf8(activation.context.gc_context, name);
+                (
+                    "_addDevice",
+                    vec![
+                        id.into(),
+                        name.into(),
+                        GamepadButton::COUNT.into(),
+                        GamepadAxis::COUNT.into(),
+                    ],
+                )
+            }
+            PlayerEvent::GamepadDisconnected { id } => ("_removeDevice", vec![id.into()]),
+            PlayerEvent::GamepadButtonDown { id, button } => (
+                "_setControlValue",
+                vec![id.into(), (button as u32).into(), 1.0.into()],
+            ),
+            PlayerEvent::GamepadButtonUp { id, button } => (
+                "_setControlValue",
+                vec![id.into(), (button as u32).into(), 0.0.into()],
+            ),
+            PlayerEvent::GamepadAxisChanged { id, axis, value } => (
+                "_setControlValue",
+                vec![
+                    id.into(),
+                    (GamepadButton::COUNT + axis as u32).into(),
+                    value.into(),
+                ],
+            ),
+            _ => return Ok(()),
+        };
+
+        let game_input = activation.avm2().classes().gameinput;
+        let method = Multiname::new(activation.avm2().flash_ui_internal, method);
+        game_input.call_property(&method, &args, activation)?;
+        Ok(())
+    }
+
+    /// Dispatches the touch and gesture events for a touch point, depending on `Multitouch.inputMode`.
+    ///
+    /// The frontend reports the primary touch point as the mouse on its own.
+    fn handle_touch_event(&mut self, phase: TouchPhase, point: TouchPoint) {
+        let inverse_view_matrix =
+            self.mutate_with_update_context(|context| context.stage.inverse_view_matrix());
+        let position = inverse_view_matrix * Point::from_pixels(point.x, point.y);
+        let result = self.touches.handle(
+            phase,
+            point.id,
+            position.x.to_pixels(),
+            position.y.to_pixels(),
+        );
+
+        self.mutate_with_update_context(|context| {
+            if !context.is_action_script_3() {
+                return;
+            }
+
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            if let Err(e) =
+                Self::dispatch_touch_events(&mut activation, phase, point, position, &result)
+            {
+                tracing::error!("Error dispatching touch event: {}", e);
+            }
+        });
+    }
+
+    fn dispatch_touch_events<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        phase: TouchPhase,
+        point: TouchPoint,
+        position: Point<Twips>,
+        result: &TouchResult,
+    ) -> Result<(), crate::avm2::Error<'gc>> {
+        let multitouch = activation.avm2().classes().multitouch;
+        let input_mode = multitouch
+            .get_public_property("inputMode", activation)?
+            .coerce_to_string(activation)?;
+
+        if &input_mode == b"touchPoint" {
+            let event_type = match phase {
+                TouchPhase::Begin => "touchBegin",
+                TouchPhase::Move => "touchMove",
+                TouchPhase::End => "touchEnd",
+            };
+            Self::dispatch_touch_event(activation, event_type, point, position, result)?;
+            if phase == TouchPhase::End && result.is_tap {
+                Self::dispatch_touch_event(activation, "touchTap", point, position, result)?;
+            }
+        } else if &input_mode == b"gesture" {
+            for gesture in &result.gestures {
+                Self::dispatch_gesture_event(activation, *gesture)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the object that touch events at a point on the stage are dispatched to.
+    fn touch_target<'gc>(
+        context: &mut UpdateContext<'_, 'gc>,
+        position: Point<Twips>,
+    ) -> DisplayObject<'gc> {
+        run_mouse_pick(context, position, false)
+            .map(|object| object.as_displayobject())
+            .unwrap_or_else(|| context.stage.into())
+    }
+
+    fn dispatch_touch_event<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        event_type: &'static str,
+        point: TouchPoint,
+        position: Point<Twips>,
+        result: &TouchResult,
+    ) -> Result<(), crate::avm2::Error<'gc>> {
+        let target = Self::touch_target(&mut activation.context, position);
+        let Avm2Value::Object(target_object) = target.object2() else {
+            return Ok(());
+        };
+
+        let local = target.mouse_to_local(position);
+        let (size_x, size_y) = point.size.unwrap_or((f64::NAN, f64::NAN));
+        let input = &activation.context.input;
+        let modifiers = [
+            input.is_key_down(KeyCode::Control),
+            input.is_key_down(KeyCode::Alt),
+            input.is_key_down(KeyCode::Shift),
+        ];
+        let event = activation.avm2().classes().touchevent.construct(
+            activation,
+            &[
+                event_type.into(),                         /* type */
+                true.into(),                               /* bubbles */
+                false.into(),                              /* cancelable */
+                point.id.into(),                           /* touchPointID */
+                result.is_primary.into(),                  /* isPrimaryTouchPoint */
+                local.x.to_pixels().into(),                /* localX */
+                local.y.to_pixels().into(),                /* localY */
+                size_x.into(),                             /* sizeX */
+                size_y.into(),                             /* sizeY */
+                point.pressure.unwrap_or(f64::NAN).into(), /* pressure */
+                Avm2Value::Null,                           /* relatedObject */
+                modifiers[0].into(),                       /* ctrlKey */
+                modifiers[1].into(),                       /* altKey */
+                modifiers[2].into(),                       /* shiftKey */
+            ],
+        )?;
+        Avm2::dispatch_event(&mut activation.context, event, target_object);
+        Ok(())
+    }
+
+    fn dispatch_gesture_event<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        gesture: Gesture,
+    ) -> Result<(), crate::avm2::Error<'gc>> {
+        let (x, y) = match gesture {
+            Gesture::Transform { x, y, .. }
+            | Gesture::Swipe { x, y, .. }
+            | Gesture::TwoFingerTap { x, y } => (x, y),
+        };
+        let position = Point::from_pixels(x, y);
+        let target = Self::touch_target(&mut activation.context, position);
+        let Avm2Value::Object(target_object) = target.object2() else {
+            return Ok(());
+        };
+
+        let local = target.mouse_to_local(position);
+        let input = &activation.context.input;
+        let modifiers = [
+            input.is_key_down(KeyCode::Control),
+            input.is_key_down(KeyCode::Alt),
+            input.is_key_down(KeyCode::Shift),
+        ];
+
+        // The events of a transform, with its phase, scale, rotation and offset.
+        let events: Vec<(&'static str, GesturePhase, f64, f64, f64, f64)> = match gesture {
+            Gesture::Transform {
+                phase,
+                scale,
+                rotation,
+                offset_x,
+                offset_y,
+                ..
+            } => {
+                // Every kind of transform begins and ends together,
+                // but only the ones that changed are updated.
+                let all = phase != GesturePhase::Update;
+                let mut events = vec![];
+                if all || scale != 1.0 {
+                    events.push(("gestureZoom", phase, scale, 0.0, 0.0, 0.0));
+                }
+                if all || rotation != 0.0 {
+                    events.push(("gestureRotate", phase, 1.0, rotation, 0.0, 0.0));
+                }
+                if all || offset_x != 0.0 || offset_y != 0.0 {
+                    events.push(("gesturePan", phase, 1.0, 0.0, offset_x, offset_y));
+                }
+                events
+            }
+            Gesture::Swipe {
+                offset_x, offset_y, ..
+            } => vec![(
+                "gestureSwipe",
+                GesturePhase::All,
+                1.0,
+                0.0,
+                offset_x,
+                offset_y,
+            )],
+            Gesture::TwoFingerTap { .. } => {
+                let event = activation.avm2().classes().gestureevent.construct(
+                    activation,
+                    &[
+                        "gestureTwoFingerTap".into(),      /* type */
+                        true.into(),                       /* bubbles */
+                        false.into(),                      /* cancelable */
+                        GesturePhase::All.as_str().into(), /* phase */
+                        local.x.to_pixels().into(),        /* localX */
+                        local.y.to_pixels().into(),        /* localY */
+                        modifiers[0].into(),               /* ctrlKey */
+                        modifiers[1].into(),               /* altKey */
+                        modifiers[2].into(),               /* shiftKey */
+                        false.into(),                      /* commandKey */
+                        modifiers[0].into(),               /* controlKey */
+                    ],
+                )?;
+                Avm2::dispatch_event(&mut activation.context, event, target_object);
+                return Ok(());
+            }
+        };
+
+        for (event_type, phase, scale, rotation, offset_x, offset_y) in events {
+            let event = activation
+                .avm2()
+                .classes()
+                .transformgestureevent
+                .construct(
+                    activation,
+                    &[
+                        event_type.into(),          /* type */
+                        true.into(),                /* bubbles */
+                        false.into(),               /* cancelable */
+                        phase.as_str().into(),      /* phase */
+                        local.x.to_pixels().into(), /* localX */
+                        local.y.to_pixels().into(), /* localY */
+                        scale.into(),               /* scaleX */
+                        scale.into(),               /* scaleY */
+                        rotation.into(),            /* rotation */
+                        offset_x.into(),            /* offsetX */
+                        offset_y.into(),            /* offsetY */
+                        modifiers[0].into(),        /* ctrlKey */
+                        modifiers[1].into(),        /* altKey */
+                        modifiers[2].into(),        /* shiftKey */
+                        false.into(),               /* commandKey */
+                        modifiers[0].into(),        /* controlKey */
+                    ],
+                )?;
+            Avm2::dispatch_event(&mut activation.context, event, target_object);
+        }
+        Ok(())
+    }
+
+    /// Tells the player whether caps lock and num lock are toggled on,
+    /// for frontends that can tell.
+    pub fn set_lock_state(&mut self, caps_lock: bool, num_lock: bool) {
+        self.input.set_lock_state(caps_lock, num_lock);
+    }
+
     /// Returns the master volume of the player. 1.0 is 100% volume.
     pub fn volume(&self) -> f32 {
         self.audio.volume()
@@ -611,18 +1195,34 @@ impl Player {
                 return vec![];
             }
 
-            // TODO: This should use a pointed display object with `.menu`
             let root_dobj = context.stage.root_clip();
+            let mouse_target = context
+                .mouse_over_object
+                .map(|object| object.as_displayobject())
+                .or(root_dobj);
 
-            let menu = if let Some(Value::Object(obj)) = root_dobj.map(|root| root.object()) {
+            let menu = if let Some(Value::Object(_)) = root_dobj.map(|root| root.object()) {
                 let mut activation = Activation::from_stub(
                     context.reborrow(),
                     ActivationIdentifier::root("[ContextMenu]"),
                 );
-                let menu_object = if let Ok(Value::Object(menu)) = obj.get("menu", &mut activation)
-                {
+
+                // The menu of the nearest object that has one is shown.
+                let owner = mouse_target.and_then(|target| {
+                    std::iter::successors(Some(target), |object| object.parent()).find_map(
+                        |object| match object.object() {
+                            Value::Object(obj) => match obj.get("menu", &mut activation) {
+                                Ok(Value::Object(menu)) => Some((object, menu)),
+                                _ => None,
+                            },
+                            _ => None,
+                        },
+                    )
+                });
+                let menu_object = if let Some((owner_object, menu)) = owner {
                     if let Ok(Value::Object(on_select)) = menu.get("onSelect", &mut activation) {
                         Self::run_context_menu_custom_callback(
+                            owner_object,
                             menu,
                             on_select,
                             &mut activation.context,
@@ -632,20 +1232,41 @@ impl Player {
                 } else {
                     None
                 };
-                crate::avm1::make_context_menu_state(menu_object, &mut activation)
-            } else if let Some(Avm2Value::Object(_obj)) = root_dobj.map(|root| root.object2()) {
-                // TODO: send "menuSelect" event
-                tracing::warn!("AVM2 Context menu callbacks are not implemented");
-
+                let mut menu = crate::avm1::make_context_menu_state(menu_object, &mut activation);
+                if let (Some(target), Some((owner, _))) = (mouse_target, owner) {
+                    menu.set_target(target, owner);
+                }
+                menu
+            } else if let Some(Avm2Value::Object(_)) = root_dobj.map(|root| root.object2()) {
                 let mut activation = Avm2Activation::from_nothing(context.reborrow());
 
-                let menu_object = root_dobj
-                    .expect("Root is confirmed to exist here")
-                    .as_interactive()
-                    .map(|iobj| iobj.context_menu())
-                    .and_then(|v| v.as_object());
+                // The menu of the nearest interactive object that has one is shown.
+                let owner = mouse_target.and_then(|target| {
+                    std::iter::successors(Some(target), |object| object.parent()).find_map(
+                        |object| {
+                            let menu = object.as_interactive()?.context_menu().as_object()?;
+                            Some((object, menu))
+                        },
+                    )
+                });
 
-                crate::avm2::make_context_menu_state(menu_object, &mut activation)
+                if let (Some(target), Some((owner, menu_object))) = (mouse_target, owner) {
+                    Self::dispatch_context_menu_event(
+                        &mut activation,
+                        "menuSelect",
+                        menu_object,
+                        target,
+                        owner,
+                    );
+                }
+                let mut menu = crate::avm2::make_context_menu_state(
+                    owner.map(|(_, menu)| menu),
+                    &mut activation,
+                );
+                if let (Some(target), Some((owner, _))) = (mouse_target, owner) {
+                    menu.set_target(target, owner);
+                }
+                menu
             } else {
                 // no AVM1 or AVM2 object - so just prepare the builtin items
                 let mut menu = ContextMenuState::new();
@@ -671,16 +1292,32 @@ impl Player {
         self.mutate_with_update_context(|context| {
             let menu = &context.current_context_menu;
             if let Some(ref menu) = menu {
+                let target = menu.target();
                 match menu.callback(index) {
                     ContextMenuCallback::Avm1 { item, callback } => {
-                        Self::run_context_menu_custom_callback(*item, *callback, context)
+                        let owner = target
+                            .map(|(_, owner)| owner)
+                            .or_else(|| context.stage.root_clip());
+                        if let Some(owner) = owner {
+                            Self::run_context_menu_custom_callback(owner, *item, *callback, context)
+                        }
                     }
                     ContextMenuCallback::Play => Self::toggle_play_root_movie(context),
                     ContextMenuCallback::Forward => Self::forward_root_movie(context),
                     ContextMenuCallback::Back => Self::back_root_movie(context),
                     ContextMenuCallback::Rewind => Self::rewind_root_movie(context),
-                    ContextMenuCallback::Avm2 { .. } => {
-                        // TODO: Send menuItemSelect event
+                    ContextMenuCallback::Avm2 { item } => {
+                        if let Some((mouse_target, owner)) = target {
+                            let item = *item;
+                            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                            Self::dispatch_context_menu_event(
+                                &mut activation,
+                                "menuItemSelect",
+                                item,
+                                mouse_target,
+                                owner,
+                            );
+                        }
                     }
                     ContextMenuCallback::QualityLow => {
                         context.stage.set_quality(context, StageQuality::Low)
@@ -698,35 +1335,67 @@ impl Player {
         });
     }
 
+    /// Calls the `onSelect` handler of an AVM1 `ContextMenu` or `ContextMenuItem`
+    /// with the object that the menu belongs to.
     fn run_context_menu_custom_callback<'gc>(
+        owner: DisplayObject<'gc>,
         item: Object<'gc>,
         callback: Object<'gc>,
         context: &mut UpdateContext<'_, 'gc>,
     ) {
-        if let Some(root_clip) = context.stage.root_clip() {
-            let mut activation = Activation::from_nothing(
-                context.reborrow(),
-                ActivationIdentifier::root("[Context Menu Callback]"),
-                root_clip,
-            );
+        let mut activation = Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[Context Menu Callback]"),
+            owner,
+        );
 
-            // TODO: Remember to also change the first arg
-            // when we support contextmenu on non-root-movie
-            let params = vec![root_clip.object(), Value::Object(item)];
+        let params = vec![owner.object(), Value::Object(item)];
 
-            let _ = callback.call(
-                "[Context Menu Callback]".into(),
-                &mut activation,
-                Value::Undefined,
-                &params,
-            );
+        let _ = callback.call(
+            "[Context Menu Callback]".into(),
+            &mut activation,
+            Value::Undefined,
+            &params,
+        );
+    }
+
+    /// Dispatches a `ContextMenuEvent` to an AVM2 `ContextMenu` or `ContextMenuItem`.
+    fn dispatch_context_menu_event<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        event_type: &'static str,
+        target: Avm2Object<'gc>,
+        mouse_target: DisplayObject<'gc>,
+        owner: DisplayObject<'gc>,
+    ) {
+        let event = activation.avm2().classes().contextmenuevent.construct(
+            activation,
+            &[
+                event_type.into(),      /* type */
+                false.into(),           /* bubbles */
+                false.into(),           /* cancelable */
+                mouse_target.object2(), /* mouseTarget */
+                owner.object2(),        /* contextMenuOwner */
+            ],
+        );
+        match event {
+            Ok(event) => Avm2::dispatch_event(&mut activation.context, event, target),
+            Err(e) => tracing::error!("Failed to construct ContextMenuEvent: {:?}", e),
         }
     }
 
+    /// Whether keys that type text are hidden from the movie, as in the `FullScreen` display
+    /// state.
+    pub fn is_keyboard_restricted(&self) -> bool {
+        self.gc_arena
+            .borrow()
+            .mutate(|_, gc_root| gc_root.data.read().stage.is_keyboard_restricted())
+    }
+
     pub fn set_fullscreen(&mut self, is_fullscreen: bool) {
         self.mutate_with_update_context(|context| {
+            // The user asked for fullscreen, so the keyboard isn't restricted.
             let display_state = if is_fullscreen {
-                StageDisplayState::FullScreen
+                StageDisplayState::FullScreenInteractive
             } else {
                 StageDisplayState::Normal
             };
@@ -789,6 +1458,74 @@ impl Player {
         self.needs_render
     }
 
+    /// A snapshot of the whole display list, starting from the stage.
+    pub fn inspect_display_tree(&mut self) -> DisplayNode {
+        self.mutate_with_update_context(|context| {
+            inspector::snapshot(context.stage.into(), context.gc_context)
+        })
+    }
+
+    /// Change a property of the display object at the given path from the stage.
+    ///
+    /// Returns `false` if there's no display object there.
+    pub fn set_display_property(
+        &mut self,
+        path: &[usize],
+        property: DisplayProperty,
+        value: f64,
+    ) -> bool {
+        let found = self.mutate_with_update_context(|context| {
+            let object = inspector::find(context.stage.into(), path);
+            if let Some(object) = object {
+                inspector::set_property(object, context.gc_context, property, value);
+            }
+            object.is_some()
+        });
+        self.needs_render |= found;
+        found
+    }
+
+    /// Outline the display object at the given path from the stage, or nothing.
+    pub fn set_inspector_highlight(&mut self, path: Option<Vec<usize>>) {
+        self.inspector_highlight = path;
+        self.needs_render = true;
+    }
+
+    /// Evaluate a console expression in the context of the display object at the given path
+    /// from the stage, or of the root movie. See [`crate::console`].
+    pub fn console_evaluate(
+        &mut self,
+        path: Option<&[usize]>,
+        input: &str,
+    ) -> Result<String, String> {
+        let result = self.update(|context| {
+            let target = Self::console_target(context, path)
+                .ok_or_else(|| "There's no display object at that path".to_string())?;
+            console::evaluate(context, target, input)
+        });
+        self.needs_render = true;
+        result
+    }
+
+    /// The ways that a partially typed console expression could continue.
+    pub fn console_complete(&mut self, path: Option<&[usize]>, input: &str) -> Vec<String> {
+        self.mutate_with_update_context(|context| {
+            Self::console_target(context, path)
+                .map(|target| console::complete(context, target, input))
+                .unwrap_or_default()
+        })
+    }
+
+    fn console_target<'gc>(
+        context: &UpdateContext<'_, 'gc>,
+        path: Option<&[usize]>,
+    ) -> Option<DisplayObject<'gc>> {
+        match path {
+            Some(path) => inspector::find(context.stage.into(), path),
+            None => context.stage.root_clip(),
+        }
+    }
+
     pub fn background_color(&mut self) -> Option<Color> {
         self.mutate_with_update_context(|context| context.stage.background_color())
     }
@@ -824,6 +1561,7 @@ impl Player {
     }
 
     pub fn set_viewport_dimensions(&mut self, dimensions: ViewportDimensions) {
+        self.journal.record(JournalEntry::Viewport(dimensions));
         self.mutate_with_update_context(|context| {
             context.renderer.set_viewport_dimensions(dimensions);
             context.stage.build_matrices(context);
@@ -877,6 +1615,71 @@ impl Player {
     /// 8. Mouse state is updated. This triggers button rollovers, which are a
     ///    second wave of event processing.
     pub fn handle_event(&mut self, event: PlayerEvent) {
+        if let Some(input_log) = &mut self.input_log {
+            // Live input would throw a replay off course.
+            if input_log.is_replaying() {
+                return;
+            }
+            input_log.record(&event);
+        }
+
+        if self.journal.is_recording() {
+            self.journal.record(JournalEntry::Event(event.clone()));
+        }
+
+        self.dispatch_event(event);
+    }
+
+    /// Handle an event without recording it.
+    fn dispatch_event(&mut self, mut event: PlayerEvent) {
+        // Keys that don't type a printable character have the same character code on all layouts.
+        if let PlayerEvent::KeyDown {
+            key_code, key_char, ..
+        }
+        | PlayerEvent::KeyUp {
+            key_code, key_char, ..
+        } = &mut event
+        {
+            if key_char.is_none() {
+                *key_char = key_code.control_char();
+            }
+        }
+
+        // In the `FullScreen` display state, movies can't see keys that type text.
+        if self.is_keyboard_restricted() {
+            let types_text = match &event {
+                PlayerEvent::KeyDown { key_char, .. } | PlayerEvent::KeyUp { key_char, .. } => {
+                    key_char.map_or(false, |c| !c.is_control() && c != ' ')
+                }
+                PlayerEvent::TextInput { codepoint } => *codepoint != ' ',
+                PlayerEvent::TextControl { .. }
+                | PlayerEvent::ImeCompositionStart
+                | PlayerEvent::ImeCompositionUpdate { .. }
+                | PlayerEvent::ImeCompositionEnd { .. } => true,
+                _ => false,
+            };
+            if types_text {
+                return;
+            }
+        }
+
+        if let PlayerEvent::Touch { phase, point } = event {
+            self.handle_touch_event(phase, point);
+            return;
+        }
+
+        if matches!(
+            event,
+            PlayerEvent::GamepadConnected { .. }
+                | PlayerEvent::GamepadDisconnected { .. }
+                | PlayerEvent::GamepadButtonDown { .. }
+                | PlayerEvent::GamepadButtonUp { .. }
+                | PlayerEvent::GamepadAxisChanged { .. }
+        ) {
+            self.handle_gamepad_event(event);
+            return;
+        }
+
         let prev_is_mouse_down = self.input.is_mouse_down();
         self.input.handle_event(&event);
         let is_mouse_button_changed = self.input.is_mouse_down() != prev_is_mouse_down;
@@ -993,8 +1796,16 @@ impl Player {
             }
 
             if context.is_action_script_3() {
-                if let PlayerEvent::KeyDown { key_code, key_char }
-                | PlayerEvent::KeyUp { key_code, key_char } = event
+                if let PlayerEvent::KeyDown {
+                    key_code,
+                    key_char,
+                    key_location,
+                }
+                | PlayerEvent::KeyUp {
+                    key_code,
+                    key_char,
+                    key_location,
+                } = event
                 {
                     let ctrl_key = context.input.is_key_down(KeyCode::Control);
                     let alt_key = context.input.is_key_down(KeyCode::Alt);
@@ -1012,7 +1823,6 @@ impl Player {
                     let event_name_val: Avm2Value<'_> =
                         AvmString::new_utf8(activation.context.gc_context, event_name).into();
 
-                    // TODO: keyLocation should not be a dummy value.
                     // ctrlKey and controlKey can be different from each other on Mac.
                     // commandKey should be supported.
                     let keyboard_event = keyboardevent_class
@@ -1024,7 +1834,7 @@ impl Player {
                                 false.into(),                            /* cancelable */
                                 key_char.map_or(0, |c| c as u32).into(), /* charCode */
                                 (key_code as u32).into(),                /* keyCode */
-                                0.into(),                                /* keyLocation */
+                                (key_location as u32).into(),            /* keyLocation */
                                 ctrl_key.into(),                         /* ctrlKey */
                                 alt_key.into(),                          /* altKey */
                                 shift_key.into(),                        /* shiftKey */
@@ -1046,6 +1856,18 @@ impl Player {
                 }
             }
 
+            // Tab moves the focus, backwards when Shift is held.
+            if let PlayerEvent::KeyDown {
+                key_code: KeyCode::Tab,
+                ..
+            } = event
+            {
+                let reverse = context.input.is_key_down(KeyCode::Shift);
+                let tracker = context.focus_tracker;
+                tracker.cycle(context, reverse);
+                *context.needs_render = true;
+            }
+
             // keyPress events take precedence over text input.
             if !key_press_handled {
                 if let PlayerEvent::TextInput { codepoint } = event {
@@ -1054,12 +1876,39 @@ impl Player {
                     }
                 }
                 if let PlayerEvent::TextControl { code } = event {
+                    if code == TextControlCode::Paste && context.is_action_script_3() {
+                        Self::dispatch_paste_event(context);
+                    }
                     if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
                         text.text_control_input(code, context);
                     }
                 }
             }
 
+            // Text composed with an IME is shown in the focused text field until it's committed.
+            let focused_text = context.focus_tracker.get().and_then(|o| o.as_edit_text());
+            match &event {
+                PlayerEvent::ImeCompositionStart => {
+                    if let Some(text) = focused_text {
+                        text.ime_composition_start(context);
+                    }
+                }
+                PlayerEvent::ImeCompositionUpdate { text, cursor } => {
+                    if let Some(edit_text) = focused_text {
+                        edit_text.ime_composition_update(text, *cursor, context);
+                    }
+                }
+                PlayerEvent::ImeCompositionEnd { text } => {
+                    if let Some(edit_text) = focused_text {
+                        edit_text.ime_composition_end(text, context);
+                    }
+                    if context.is_action_script_3() && !text.is_empty() {
+                        Self::dispatch_ime_composition_event(context, text);
+                    }
+                }
+                _ => {}
+            }
+
             // Propagate clip events.
             let (clip_event, listener) = match event {
                 PlayerEvent::KeyDown { .. } => {
@@ -1174,6 +2023,201 @@ impl Player {
         }
     }
 
+    /// Dispatches an `IMEEvent.IME_COMPOSITION` event with the text composed with an IME
+    /// to `System.ime`.
+    fn dispatch_ime_composition_event(context: &mut UpdateContext<'_, '_>, text: &str) {
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let system_class = activation.avm2().classes().system;
+        let ime = match system_class
+            .get_public_property("ime", &mut activation)
+            .and_then(|ime| ime.coerce_to_object(&mut activation))
+        {
+            Ok(ime) => ime,
+            Err(e) => {
+                tracing::error!("Failed to get System.ime: {:?}", e);
+                return;
+            }
+        };
+
+        let imeevent_class = activation.avm2().classes().imeevent;
+        let event_name = AvmString::new_utf8(activation.context.gc_context, "imeComposition");
+        let text = AvmString::new_utf8(activation.context.gc_context, text);
+        let ime_event = imeevent_class
+            .construct(
+                &mut activation,
+                &[
+                    event_name.into(), /* type */
+                    false.into(),      /* bubbles */
+                    false.into(),      /* cancelable */
+                    text.into(),       /* text */
+                ],
+            )
+            .expect("Failed to construct IMEEvent");
+
+        Avm2::dispatch_event(&mut activation.context, ime_event, ime);
+    }
+
+    /// Sends a `paste` event to the focused object, or the stage if nothing has focus.
+    ///
+    /// The system clipboard can be read with `Clipboard.generalClipboard` while the event is
+    /// handled.
+    fn dispatch_paste_event(context: &mut UpdateContext<'_, '_>) {
+        let target = context
+            .focus_tracker
+            .get()
+            .unwrap_or_else(|| context.stage.into());
+        let Avm2Value::Object(target) = target.object2() else {
+            return;
+        };
+
+        // `paste` events bubble, but can't be cancelled.
+        let paste_event = Avm2EventObject::bare_event(context, "paste", true, false);
+        context.in_paste_handler = true;
+        Avm2::dispatch_event(context, paste_event, target);
+        context.in_paste_handler = false;
+    }
+
+    /// Drops files onto the player, as when the user drags them from the system's file manager.
+    ///
+    /// AVM2 content is sent `NativeDragEvent.NATIVE_DRAG_ENTER` on the object under the mouse,
+    /// and if it accepts the files with `NativeDragManager.acceptDragDrop`, `NATIVE_DRAG_DROP`
+    /// with the files in its clipboard. Returns whether the content accepted the files.
+    pub fn drop_files(&mut self, paths: Vec<String>) -> bool {
+        self.mutate_with_update_context(|context| {
+            if !context.is_action_script_3() {
+                return false;
+            }
+
+            for path in &paths {
+                context.filesystem.grant_read_access(path);
+            }
+            let target = context
+                .mouse_over_object
+                .map(|object| object.as_displayobject())
+                .unwrap_or_else(|| context.stage.into());
+
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            match Self::dispatch_native_drag(&mut activation, target, &paths) {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    tracing::error!("Failed to drop files: {:?}", e);
+                    false
+                }
+            }
+        })
+    }
+
+    fn dispatch_native_drag<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        target: DisplayObject<'gc>,
+        paths: &[String],
+    ) -> Result<bool, crate::avm2::Error<'gc>> {
+        let target_object = match target.object2() {
+            Avm2Value::Object(object) => object,
+            _ => return Ok(false),
+        };
+
+        let mut files = Vec::with_capacity(paths.len());
+        for path in paths {
+            let path = AvmString::new_utf8(activation.context.gc_context, path);
+            let file_class = activation.avm2().classes().file;
+            files.push(file_class.construct(activation, &[path.into()])?);
+        }
+        let files = Avm2ArrayObject::from_storage(activation, files.into_iter().collect())?;
+        let clipboard = activation
+            .avm2()
+            .classes()
+            .clipboard
+            .construct(activation, &[])?;
+        clipboard.call_public_property(
+            "setData",
+            &["air:file list".into(), files.into()],
+            activation,
+        )?;
+
+        let mut manager = activation.avm2().classes().nativedragmanager;
+        let namespace = activation.avm2().flash_desktop_internal;
+        let is_dragging = Multiname::new(namespace, "_isDragging");
+        let drop_target = Multiname::new(namespace, "_dropTarget");
+        manager.set_property(&is_dragging, true.into(), activation)?;
+        manager.set_property(&drop_target, Avm2Value::Null, activation)?;
+
+        let enter_event =
+            Self::native_drag_event(activation, "nativeDragEnter", target, clipboard)?;
+        Avm2::dispatch_event(&mut activation.context, enter_event, target_object);
+
+        let accepted = manager
+            .get_property(&drop_target, activation)?
+            .as_object()
+            .and_then(|object| Some((object, object.as_display_object()?)));
+        let result = match accepted {
+            Some((object, display_object)) => {
+                let drop_event = Self::native_drag_event(
+                    activation,
+                    "nativeDragDrop",
+                    display_object,
+                    clipboard,
+                )?;
+                Avm2::dispatch_event(&mut activation.context, drop_event, object);
+                true
+            }
+            None => {
+                let exit_event =
+                    Self::native_drag_event(activation, "nativeDragExit", target, clipboard)?;
+                Avm2::dispatch_event(&mut activation.context, exit_event, target_object);
+                false
+            }
+        };
+        manager.set_property(&is_dragging, false.into(), activation)?;
+        manager.set_property(&drop_target, Avm2Value::Null, activation)?;
+        Ok(result)
+    }
+
+    fn native_drag_event<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc>,
+        event_type: &'static str,
+        target: DisplayObject<'gc>,
+        clipboard: Avm2Object<'gc>,
+    ) -> Result<Avm2Object<'gc>, crate::avm2::Error<'gc>> {
+        let local = target.mouse_to_local(*activation.context.mouse_position);
+        let input = &activation.context.input;
+        let modifiers = [
+            input.is_key_down(KeyCode::Control),
+            input.is_key_down(KeyCode::Alt),
+            input.is_key_down(KeyCode::Shift),
+        ];
+        activation.avm2().classes().nativedragevent.construct(
+            activation,
+            &[
+                event_type.into(),          /* type */
+                true.into(),                /* bubbles */
+                true.into(),                /* cancelable */
+                local.x.to_pixels().into(), /* localX */
+                local.y.to_pixels().into(), /* localY */
+                Avm2Value::Null,            /* relatedObject */
+                clipboard.into(),           /* clipboard */
+                Avm2Value::Null,            /* allowedActions */
+                "copy".into(),              /* dropAction */
+                modifiers[0].into(),        /* controlKey */
+                modifiers[1].into(),        /* altKey */
+                modifiers[2].into(),        /* shiftKey */
+            ],
+        )
+    }
+
+    /// Returns the position of the caret in the focused text field, in window pixels.
+    ///
+    /// Frontends use this to show the candidate window of an input method editor (IME)
+    /// next to the text being composed.
+    pub fn ime_cursor_position(&mut self) -> Option<(f64, f64)> {
+        self.mutate_with_update_context(|context| {
+            let text = context.focus_tracker.get()?.as_edit_text()?;
+            let local_position = text.caret_position()?;
+            let position = context.stage.view_matrix() * text.local_to_global(local_position);
+            Some((position.x.to_pixels(), position.y.to_pixels()))
+        })
+    }
+
     /// Update dragged object, if any.
     pub fn update_drag(context: &mut UpdateContext<'_, '_>) {
         let mouse_position = *context.mouse_position;
@@ -1219,7 +2263,7 @@ impl Player {
                 let was_visible = display_object.visible();
                 display_object.set_visible(context.gc_context, false);
                 // Set `_droptarget` to the object the mouse is hovering over.
-                let drop_target_object = run_mouse_pick(context, false);
+                let drop_target_object = run_mouse_pick(context, *context.mouse_position, false);
                 movie_clip.set_drop_target(
                     context.gc_context,
                     drop_target_object.map(|d| d.as_displayobject()),
@@ -1239,7 +2283,7 @@ impl Player {
         // Search through levels from top-to-bottom, returning the first display object that is under the mouse.
         let needs_render = self.mutate_with_update_context(|context| {
             let new_over_object = if mouse_in_stage {
-                run_mouse_pick(context, true)
+                run_mouse_pick(context, *context.mouse_position, true)
             } else {
                 None
             };
@@ -1475,7 +2519,8 @@ impl Player {
         // Update mouse cursor if it has changed.
         if new_cursor != self.mouse_cursor {
             self.mouse_cursor = new_cursor;
-            self.ui.set_mouse_cursor(new_cursor)
+            self.mouse_cursors
+                .set_auto_cursor(new_cursor, self.ui.deref_mut())
         }
         self.mouse_cursor_needs_check = mouse_cursor_needs_check;
 
@@ -1570,6 +2615,7 @@ impl Player {
 
     #[instrument(level = "debug", skip_all)]
     pub fn run_frame(&mut self) {
+        self.journal.record(JournalEntry::Frame);
         let frame_time = Duration::from_nanos((750_000_000.0 / self.frame_rate) as u64);
         let (mut execution_limit, may_execute_while_streaming) = match self.load_behavior {
             LoadBehavior::Streaming => (
@@ -1588,28 +2634,60 @@ impl Player {
             return;
         }
 
+        let frame_begin = self.profiler.begin();
         self.update(|context| {
+            let begin = context.profiler.begin();
             if context.is_action_script_3() {
                 run_all_phases_avm2(context);
             } else {
                 Avm1::run_frame(context);
             }
+            context.profiler.end(begin, Category::Script, "Run frame");
+
+            // `render` is dispatched once all of the frame's scripts have run, right before
+            // the frame is drawn.
+            if context.stage.invalidated() {
+                let begin = context.profiler.begin();
+                let stage = context.stage;
+                stage.broadcast_render(context);
+                context.profiler.end(begin, Category::Events, "render");
+            }
+
+            let begin = context.profiler.begin();
             AudioManager::update_sounds(context);
+            context
+                .profiler
+                .end(begin, Category::Audio, "Update sounds");
         });
 
-        self.needs_render = true;
+        // Also collect once per frame, so that movies which rarely receive events don't
+        // build up a large debt that then has to be paid off in one long pause.
+        self.collect_garbage();
+
+        self.profiler.end(frame_begin, Category::Frame, "Frame");
+
+        // Nothing needs to be drawn again if nothing on the stage changed this frame.
+        let needs_redraw = self
+            .gc_arena
+            .borrow()
+            .mutate(|_, gc_root| gc_root.data.read().stage.needs_redraw());
+        self.needs_render |= needs_redraw;
     }
 
     #[instrument(level = "debug", skip_all)]
     pub fn render(&mut self) {
+        self.journal.record(JournalEntry::Render);
+        let render_begin = self.profiler.begin();
         let invalidated = self
             .gc_arena
             .borrow()
             .mutate(|_, gc_root| gc_root.data.read().stage.invalidated());
         if invalidated {
             self.update(|context| {
+                let begin = context.profiler.begin();
                 let stage = context.stage;
                 stage.broadcast_render(context);
+                context.profiler.end(begin, Category::Events, "render");
             });
         }
 
@@ -1633,6 +2711,24 @@ impl Player {
             };
 
             stage.render(&mut render_context);
+            root_data
+                .focus_tracker
+                .render_highlight(&mut render_context);
+
+            if let Some(object) = self
+                .inspector_highlight
+                .as_deref()
+                .and_then(|path| inspector::find(stage.into(), path))
+            {
+                let world_matrix = stage.view_matrix() * *stage.base().matrix();
+                let bounds = world_matrix * object.world_bounds();
+                inspector::draw_debug_rect(
+                    &mut render_context,
+                    Color::from_rgb(0xFF00FF, 255),
+                    bounds,
+                    3.0,
+                );
+            }
 
             #[cfg(feature = "egui")]
             {
@@ -1653,10 +2749,41 @@ impl Player {
             (cache_draws, commands)
         });
 
+        // Filters are applied to cached bitmaps when the frame is submitted, so frames that
+        // redraw filtered objects are recorded separately.
+        let filtered_draws = cache_draws
+            .iter()
+            .filter(|entry| !entry.filters.is_empty())
+            .count();
+        let begin = self.profiler.begin();
         self.renderer
             .submit_frame(background_color, commands, cache_draws);
+        if filtered_draws > 0 {
+            self.profiler.end(
+                begin,
+                Category::Filters,
+                format!("Submit frame ({filtered_draws} filtered)"),
+            );
+        } else {
+            self.profiler.end(begin, Category::Render, "Submit frame");
+        }
 
+        self.profiler.end(render_begin, Category::Render, "Render");
         self.needs_render = false;
+        self.frame_awaiting_present = false;
+    }
+
+    /// The root movie.
+    pub fn swf(&self) -> &Arc<SwfMovie> {
+        &self.swf
+    }
+
+    /// The frame of the main timeline that has the given label, once that frame has loaded.
+    pub fn frame_label_number(&mut self, label: &str) -> Option<u16> {
+        self.mutate_with_update_context(|context| {
+            let root = context.stage.root_clip()?.as_movie_clip()?;
+            root.frame_label_to_number(&WString::from_utf8(label), context)
+        })
     }
 
     /// The current frame of the main timeline, if available.
@@ -1698,6 +2825,14 @@ impl Player {
         &mut self.storage
     }
 
+    pub fn filesystem(&self) -> &Filesystem {
+        &self.filesystem
+    }
+
+    pub fn filesystem_mut(&mut self) -> &mut Filesystem {
+        &mut self.filesystem
+    }
+
     pub fn destroy(self) -> Renderer {
         self.renderer
     }
@@ -1711,8 +2846,12 @@ impl Player {
     }
 
     pub fn run_actions(context: &mut UpdateContext<'_, '_>) {
+        let begin = context.profiler.begin();
+        let mut ran_actions = false;
+
         // Note that actions can queue further actions, so a while loop is necessary here.
         while let Some(action) = context.action_queue.pop_action() {
+            ran_actions = true;
             // We don't run frame actions if the clip was removed (or scheduled to be removed) after it queued the action.
             if !action.is_unload
                 && (!context.is_action_script_3()
@@ -1803,6 +2942,12 @@ impl Player {
             // across multiple executions and/or frames.
             context.avm1.clear_stack();
         }
+
+        if ran_actions {
+            context
+                .profiler
+                .end(begin, Category::Script, "Queued actions");
+        }
     }
 
     /// Runs the closure `f` with an `UpdateContext`.
@@ -1836,6 +2981,9 @@ impl Player {
                 audio_manager,
                 stream_manager,
                 sockets,
+                net_connections,
+                local_connections,
+                cameras,
                 dynamic_root,
             ) = root_data.update_context_params();
 
@@ -1862,6 +3010,7 @@ impl Player {
                 system: &mut self.system,
                 instance_counter: &mut self.instance_counter,
                 storage: self.storage.deref_mut(),
+                filesystem: self.filesystem.deref_mut(),
                 log: self.log.deref_mut(),
                 video: self.video.deref_mut(),
                 avm1_shared_objects,
@@ -1874,6 +3023,7 @@ impl Player {
                 avm2,
                 external_interface,
                 start_time: self.start_time,
+                fixed_time: self.fixed_time,
                 update_start: Instant::now(),
                 max_execution_duration: self.max_execution_duration,
                 focus_tracker,
@@ -1882,11 +3032,20 @@ impl Player {
                 audio_manager,
                 frame_rate: &mut self.frame_rate,
                 forced_frame_rate: self.forced_frame_rate,
+                in_paste_handler: false,
                 actions_since_timeout_check: &mut self.actions_since_timeout_check,
                 frame_phase: &mut self.frame_phase,
                 stub_tracker: &mut self.stub_tracker,
+                profiler: &mut self.profiler,
+                api_usage: &mut self.api_usage,
+                journal: &mut self.journal,
+                debugger: &mut self.debugger,
                 stream_manager,
                 sockets,
+                net_connections,
+                local_connections,
+                cameras,
+                mouse_cursors: &mut self.mouse_cursors,
                 dynamic_root,
             };
 
@@ -1977,12 +3136,73 @@ impl Player {
         });
         self.update_mouse_state(false, false);
 
-        // GC
-        self.gc_arena.borrow_mut().collect_debt();
+        self.collect_garbage();
 
         rval
     }
 
+    /// Run a slice of incremental garbage collection.
+    ///
+    /// Each slice only does as much work as has been allocated since the last one.
+    fn collect_garbage(&mut self) {
+        let begin = self.profiler.begin();
+        let start = Instant::now();
+        self.gc_arena.borrow_mut().collect_debt();
+        let elapsed = start.elapsed();
+        self.profiler.end(begin, Category::Gc, "Collect garbage");
+        self.profiler.record_gc_slice(elapsed);
+    }
+
+    pub fn profiler(&self) -> &Profiler {
+        &self.profiler
+    }
+
+    /// Start or stop recording frame timings and slow ActionScript calls.
+    ///
+    /// Starting a new profile discards the previous one.
+    pub fn set_profiling(&mut self, profiling: bool) {
+        if profiling {
+            self.profiler.start_profiling();
+        } else {
+            self.profiler.stop_profiling();
+        }
+    }
+
+    /// Start or stop counting calls to built-in ActionScript APIs.
+    pub fn set_api_usage_tracking(&mut self, enabled: bool) {
+        self.api_usage.set_enabled(enabled);
+    }
+
+    /// The built-in APIs that have been called while tracking was enabled.
+    pub fn api_usage(&self) -> &ApiUsage {
+        &self.api_usage
+    }
+
+    /// The stubs that the movie has run into, along with how many times.
+    pub fn encountered_stubs(&self) -> &StubCollection {
+        &self.stub_tracker
+    }
+
+    /// Describe which unsupported parts of Ruffle the movie has used so far.
+    pub fn compatibility_report(&self) -> CompatibilityReport {
+        CompatibilityReport::new(
+            self.swf.version(),
+            self.swf.is_action_script_3(),
+            &self.stub_tracker,
+        )
+    }
+
+    /// Start or stop tracking AVM2 object allocations for memory profiles.
+    pub fn set_memory_profiling(&mut self, profiling: bool) {
+        self.update(|context| context.avm2.set_memory_profiling(profiling));
+    }
+
+    /// Count the AVM2 objects that are still alive by class, out of the ones allocated
+    /// since memory profiling was started.
+    pub fn memory_profile(&mut self) -> MemoryProfile {
+        self.update(|context| context.avm2.memory_profile(context.gc_context))
+    }
+
     pub fn flush_shared_objects(&mut self) {
         self.update(|context| {
             if let Some(mut avm1_activation) =
@@ -2024,6 +3244,58 @@ impl Player {
         })
     }
 
+    /// Update RTMP connections made by `NetConnection`.
+    pub fn update_net_connections(&mut self) {
+        self.mutate_with_update_context(|context| {
+            NetConnections::update_net_connections(context);
+        })
+    }
+
+    /// Delivers messages sent to `LocalConnection`s.
+    pub fn update_local_connections(&mut self) {
+        self.mutate_with_update_context(|context| {
+            LocalConnections::update_local_connections(context);
+        })
+    }
+
+    pub fn update_cameras(&mut self) {
+        self.update(|context| {
+            Cameras::update_cameras(context);
+        })
+    }
+
+    /// Rebuilds the accessibility tree every so often while assistive technology is active,
+    /// and exposes it if it changed.
+    pub fn update_accessibility(&mut self, dt: f64) {
+        if !self.ui.is_accessibility_active() {
+            self.accessibility_tree = None;
+            return;
+        }
+
+        self.time_until_accessibility_update -= dt;
+        if self.accessibility_tree.is_some() && self.time_until_accessibility_update > 0.0 {
+            return;
+        }
+        self.time_until_accessibility_update = ACCESSIBILITY_UPDATE_INTERVAL;
+
+        let tree = self.mutate_with_update_context(accessibility::build_tree);
+        if self.accessibility_tree.as_ref() != Some(&tree) {
+            self.ui.update_accessibility_tree(tree.clone());
+            self.accessibility_tree = Some(tree);
+        }
+    }
+
+    /// Requests samples for dynamic sounds that are running low.
+    ///
+    /// This runs every tick rather than every frame, so that dynamic sounds
+    /// stay fed at low frame rates.
+    pub fn update_dynamic_sounds(&mut self) {
+        let target_latency = (self.sample_data_latency.as_secs_f64() * 44100.0) as usize;
+        self.update(|context| {
+            AudioManager::update_dynamic_sounds(context, target_latency);
+        })
+    }
+
     /// Returns whether this player consumes mouse wheel events.
     /// Used by web to prevent scrolling.
     pub fn should_prevent_scrolling(&mut self) -> bool {
@@ -2041,11 +3313,22 @@ impl Player {
         name: &str,
         args: impl IntoIterator<Item = ExternalValue>,
     ) -> ExternalValue {
+        self.try_call_internal_interface(name, args)
+            .unwrap_or(ExternalValue::Null)
+    }
+
+    /// Calls a callback added with `ExternalInterface.addCallback`, returning the message
+    /// of the error that it threw if `ExternalInterface.marshallExceptions` is enabled.
+    pub fn try_call_internal_interface(
+        &mut self,
+        name: &str,
+        args: impl IntoIterator<Item = ExternalValue>,
+    ) -> Result<ExternalValue, String> {
         self.mutate_with_update_context(|context| {
             if let Some(callback) = context.external_interface.get_callback(name) {
-                callback.call(context, name, args)
+                callback.try_call(context, name, args)
             } else {
-                ExternalValue::Null
+                Ok(ExternalValue::Null)
             }
         })
     }
@@ -2087,6 +3370,7 @@ pub struct PlayerBuilder {
     navigator: Option<Navigator>,
     renderer: Option<Renderer>,
     storage: Option<Storage>,
+    filesystem: Option<Filesystem>,
     ui: Option<Ui>,
     video: Option<Video>,
 
@@ -2099,6 +3383,10 @@ pub struct PlayerBuilder {
     fullscreen: bool,
     letterbox: Letterbox,
     max_execution_duration: Duration,
+    sample_data_latency: Duration,
+    save_states: bool,
+    input_log: Option<InputLog>,
+    debugger: Option<Box<dyn DebuggerConnection>>,
     viewport_width: u32,
     viewport_height: u32,
     viewport_scale_factor: f64,
@@ -2109,8 +3397,10 @@ pub struct PlayerBuilder {
     quality: StageQuality,
     sandbox_type: SandboxType,
     frame_rate: Option<f64>,
+    frame_rate_mode: FrameRateMode,
     external_interface_providers: Vec<Box<dyn ExternalInterfaceProvider>>,
     fs_command_provider: Box<dyn FsCommandProvider>,
+    gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
 }
 
 impl PlayerBuilder {
@@ -2128,6 +3418,7 @@ impl PlayerBuilder {
             navigator: None,
             renderer: None,
             storage: None,
+            filesystem: None,
             ui: None,
             video: None,
 
@@ -2144,6 +3435,10 @@ impl PlayerBuilder {
             } else {
                 15
             }),
+            sample_data_latency: Duration::from_millis(200),
+            save_states: false,
+            input_log: None,
+            debugger: None,
             viewport_width: 550,
             viewport_height: 400,
             viewport_scale_factor: 1.0,
@@ -2154,8 +3449,10 @@ impl PlayerBuilder {
             quality: StageQuality::High,
             sandbox_type: SandboxType::LocalTrusted,
             frame_rate: None,
+            frame_rate_mode: FrameRateMode::Native,
             external_interface_providers: vec![],
             fs_command_provider: Box::new(NullFsCommandProvider),
+            gamepad_button_mapping: HashMap::new(),
         }
     }
 
@@ -2201,6 +3498,15 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the filesystem backend of the player, which AIR's `flash.filesystem` API uses.
+    ///
+    /// Files can't be accessed unless this is set.
+    #[inline]
+    pub fn with_filesystem(mut self, filesystem: impl 'static + FilesystemBackend) -> Self {
+        self.filesystem = Some(Box::new(filesystem));
+        self
+    }
+
     /// Sets the UI backend of the player.
     #[inline]
     pub fn with_ui(mut self, ui: impl 'static + UiBackend) -> Self {
@@ -2244,6 +3550,47 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets how far ahead of playback dynamic sounds request samples through `SampleDataEvent`.
+    ///
+    /// Lower values reduce latency, but make glitches more likely if frames take long to run.
+    #[inline]
+    pub fn with_sample_data_latency(mut self, latency: Duration) -> Self {
+        self.sample_data_latency = latency;
+        self
+    }
+
+    /// Sets whether the player keeps the journal needed to save and restore states.
+    ///
+    /// The journal grows for as long as the movie runs, so this is off by default.
+    #[inline]
+    pub fn with_save_states(mut self, save_states: bool) -> Self {
+        self.save_states = save_states;
+        self
+    }
+
+    /// Records every event the player receives, so that it can be replayed later.
+    ///
+    /// The player runs on a fixed clock while recording, so that the replay goes the same way.
+    #[inline]
+    pub fn with_input_recording(mut self) -> Self {
+        self.input_log = Some(InputLog::record());
+        self
+    }
+
+    /// Replays the events of an earlier recording, instead of taking live input.
+    #[inline]
+    pub fn with_input_replay(mut self, script: InputScript) -> Self {
+        self.input_log = Some(InputLog::replay(script));
+        self
+    }
+
+    /// Attaches a debugger front-end, which can stop scripts at breakpoints.
+    #[inline]
+    pub fn with_debugger(mut self, connection: Box<dyn DebuggerConnection>) -> Self {
+        self.debugger = Some(connection);
+        self
+    }
+
     /// Sets the dimensions of the stage.
     #[inline]
     pub fn with_viewport_dimensions(
@@ -2314,6 +3661,12 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets how frames are scheduled relative to ticks of the host.
+    pub fn with_frame_rate_mode(mut self, frame_rate_mode: FrameRateMode) -> Self {
+        self.frame_rate_mode = frame_rate_mode;
+        self
+    }
+
     /// Adds an External Interface provider for movies to communicate with
     pub fn with_external_interface(mut self, provider: Box<dyn ExternalInterfaceProvider>) -> Self {
         self.external_interface_providers.push(provider);
@@ -2326,6 +3679,12 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets the keys that gamepad buttons press, for movies that don't support gamepads.
+    pub fn with_gamepad_button_mapping(mut self, mapping: HashMap<GamepadButton, KeyCode>) -> Self {
+        self.gamepad_button_mapping = mapping;
+        self
+    }
+
     fn create_gc_root<'gc>(
         gc_context: &'gc gc_arena::Mutation<'gc>,
         player_version: u8,
@@ -2369,6 +3728,9 @@ impl PlayerBuilder {
                     unbound_text_fields: Vec::new(),
                     stream_manager: StreamManager::new(),
                     sockets: Sockets::empty(),
+                    net_connections: NetConnections::empty(),
+                    local_connections: LocalConnections::empty(),
+                    cameras: Cameras::empty(),
                     dynamic_root,
                 },
             ),
@@ -2398,6 +3760,9 @@ impl PlayerBuilder {
         let storage = self
             .storage
             .unwrap_or_else(|| Box::new(storage::MemoryStorageBackend::new()));
+        let filesystem = self
+            .filesystem
+            .unwrap_or_else(|| Box::new(filesystem::NullFilesystemBackend::new()));
         let ui = self
             .ui
             .unwrap_or_else(|| Box::new(ui::NullUiBackend::new()));
@@ -2419,6 +3784,7 @@ impl PlayerBuilder {
                 navigator,
                 renderer,
                 storage,
+                filesystem,
                 ui,
                 video,
 
@@ -2429,13 +3795,17 @@ impl PlayerBuilder {
                 // Timing
                 frame_rate,
                 forced_frame_rate,
+                frame_rate_mode: self.frame_rate_mode,
+                frame_awaiting_present: false,
                 frame_phase: Default::default(),
                 frame_accumulator: 0.0,
                 recent_run_frame_timings: VecDeque::with_capacity(10),
                 start_time: Instant::now(),
+                fixed_time: self.input_log.as_ref().map(|_| Duration::ZERO),
                 time_offset: 0,
                 time_til_next_timer: None,
                 max_execution_duration: self.max_execution_duration,
+                sample_data_latency: self.sample_data_latency,
                 actions_since_timeout_check: 0,
 
                 // Input
@@ -2444,6 +3814,11 @@ impl PlayerBuilder {
                 mouse_position: Point::ZERO,
                 mouse_cursor: MouseCursor::Arrow,
                 mouse_cursor_needs_check: false,
+                mouse_cursors: MouseCursors::new(),
+                gamepad_button_mapping: self.gamepad_button_mapping,
+                touches: TouchTracker::new(),
+                accessibility_tree: None,
+                time_until_accessibility_update: 0.0,
 
                 // Misc. state
                 rng: SmallRng::seed_from_u64(get_current_date_time().timestamp_millis() as u64),
@@ -2453,11 +3828,17 @@ impl PlayerBuilder {
                 player_version,
                 is_playing: self.autoplay,
                 needs_render: true,
+                inspector_highlight: None,
                 self_reference: self_ref.clone(),
                 load_behavior: self.load_behavior,
                 spoofed_url: self.spoofed_url.clone(),
                 compatibility_rules: self.compatibility_rules.clone(),
                 stub_tracker: StubCollection::new(),
+                profiler: Profiler::default(),
+                api_usage: ApiUsage::default(),
+                journal: Journal::new(self.save_states),
+                debugger: Debugger::new(self.debugger),
+                input_log: self.input_log,
                 #[cfg(feature = "egui")]
                 debug_ui: Default::default(),
 
@@ -2541,6 +3922,7 @@ pub struct DragObject<'gc> {
 
 fn run_mouse_pick<'gc>(
     context: &mut UpdateContext<'_, 'gc>,
+    point: Point<Twips>,
     require_button_mode: bool,
 ) -> Option<InteractiveObject<'gc>> {
     context.stage.iter_render_list().rev().find_map(|level| {
@@ -2548,7 +3930,7 @@ fn run_mouse_pick<'gc>(
             if context.is_action_script_3() {
                 let mut res = None;
                 if let Avm2MousePick::Hit(target) =
-                    l.mouse_pick_avm2(context, *context.mouse_position, require_button_mode)
+                    l.mouse_pick_avm2(context, point, require_button_mode)
                 {
                     // Flash Player appears to never target events at the root object
                     if !target.as_displayobject().is_root() {
@@ -2557,7 +3939,7 @@ fn run_mouse_pick<'gc>(
                 }
                 res
             } else {
-                l.mouse_pick_avm1(context, *context.mouse_position, require_button_mode)
+                l.mouse_pick_avm1(context, point, require_button_mode)
             }
         })
     })
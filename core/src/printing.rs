@@ -0,0 +1,167 @@
+//! Pages printed with `PrintJob`, and the PDF documents they are sent to printers as.
+
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use ruffle_render::bitmap::Bitmap;
+use std::io::Write;
+
+/// The number of points, the unit that paper is measured in, in an inch.
+pub const POINTS_PER_INCH: f64 = 72.0;
+
+/// A page to be printed, rasterized to an image that covers the whole paper.
+pub struct PrintPage {
+    /// The rasterized page.
+    pub image: Bitmap,
+
+    /// The width of the paper, in points.
+    pub width: f64,
+
+    /// The height of the paper, in points.
+    pub height: f64,
+}
+
+/// Encodes pages as a PDF document, with each image stretched over its own page.
+pub fn to_pdf(pages: Vec<PrintPage>) -> Vec<u8> {
+    let mut writer = PdfWriter::new();
+    writer.object(b"<< /Type /Catalog /Pages 2 0 R >>");
+
+    // Every page takes up three objects: the page itself, its contents and its image.
+    let kids: Vec<String> = (0..pages.len())
+        .map(|i| format!("{} 0 R", 3 + i * 3))
+        .collect();
+    writer.object(
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        )
+        .as_bytes(),
+    );
+
+    for page in pages {
+        let id = writer.next_id();
+        writer.object(
+            format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+                 /Resources << /XObject << /Page {} 0 R >> >> /Contents {} 0 R >>",
+                page.width,
+                page.height,
+                id + 2,
+                id + 1
+            )
+            .as_bytes(),
+        );
+
+        let contents = format!("q {} 0 0 {} 0 0 cm /Page Do Q", page.width, page.height);
+        writer.stream("", contents.as_bytes());
+
+        let image = page.image.to_rgb();
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(image.data())
+            .expect("Writing to a Vec can't fail");
+        let data = encoder.finish().expect("Writing to a Vec can't fail");
+        writer.stream(
+            &format!(
+                "/Type /XObject /Subtype /Image /Width {} /Height {} \
+                 /ColorSpace /DeviceRGB /BitsPerComponent 8 /Filter /FlateDecode",
+                image.width(),
+                image.height()
+            ),
+            &data,
+        );
+    }
+
+    writer.finish()
+}
+
+/// Writes the objects of a PDF document in order, keeping track of where each one starts.
+struct PdfWriter {
+    output: Vec<u8>,
+    offsets: Vec<usize>,
+}
+
+impl PdfWriter {
+    fn new() -> Self {
+        Self {
+            output: b"%PDF-1.4\n".to_vec(),
+            offsets: vec![],
+        }
+    }
+
+    /// The number of the next object to be written.
+    fn next_id(&self) -> usize {
+        self.offsets.len() + 1
+    }
+
+    fn object(&mut self, dictionary: &[u8]) {
+        self.begin_object();
+        self.output.extend_from_slice(dictionary);
+        self.output.extend_from_slice(b"\nendobj\n");
+    }
+
+    fn stream(&mut self, entries: &str, data: &[u8]) {
+        self.begin_object();
+        let dictionary = if entries.is_empty() {
+            format!("<< /Length {} >>\nstream\n", data.len())
+        } else {
+            format!("<< {} /Length {} >>\nstream\n", entries, data.len())
+        };
+        self.output.extend_from_slice(dictionary.as_bytes());
+        self.output.extend_from_slice(data);
+        self.output.extend_from_slice(b"\nendstream\nendobj\n");
+    }
+
+    fn begin_object(&mut self) {
+        self.offsets.push(self.output.len());
+        let header = format!("{} 0 obj\n", self.offsets.len());
+        self.output.extend_from_slice(header.as_bytes());
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        let xref_offset = self.output.len();
+        let mut xref = format!("xref\n0 {}\n0000000000 65535 f \n", self.offsets.len() + 1);
+        for offset in &self.offsets {
+            xref.push_str(&format!("{offset:010} 00000 n \n"));
+        }
+        xref.push_str(&format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            self.offsets.len() + 1,
+            xref_offset
+        ));
+        self.output.extend_from_slice(xref.as_bytes());
+        self.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_render::bitmap::BitmapFormat;
+
+    #[test]
+    fn pdf_objects_match_cross_references() {
+        let page = || PrintPage {
+            image: Bitmap::new(2, 2, BitmapFormat::Rgba, vec![255; 16]),
+            width: 612.0,
+            height: 792.0,
+        };
+        let pdf = to_pdf(vec![page(), page()]);
+        let text = String::from_utf8_lossy(&pdf);
+
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.contains("/Kids [3 0 R 6 0 R] /Count 2"));
+
+        let xref = text.rfind("xref\n").unwrap();
+        let offsets: Vec<usize> = text[xref..]
+            .lines()
+            .skip(3)
+            .take_while(|line| line.ends_with(" n "))
+            .map(|line| line[..10].parse().unwrap())
+            .collect();
+        assert_eq!(offsets.len(), 8);
+        for (i, offset) in offsets.into_iter().enumerate() {
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj\n", i + 1).as_bytes()));
+        }
+    }
+}
@@ -0,0 +1,219 @@
+//! Opt-in profiling of where the player spends its time
+//!
+//! While enabled, the player records how long each part of a frame takes, along with any
+//! ActionScript calls that take a noticeable amount of time. The result can be exported in
+//! the Chrome trace event format, which can be opened in `chrome://tracing` or Perfetto.
+
+use instant::Instant;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::time::Duration;
+
+/// ActionScript calls that finish quicker than this aren't recorded.
+///
+/// Recording every call would make the profile far too large to be useful, and would
+/// slow the movie down too much to measure anything.
+const CALL_SAMPLE_THRESHOLD: Duration = Duration::from_micros(100);
+
+/// The most events that will be kept, after which the oldest ones are dropped.
+const MAX_EVENTS: usize = 500_000;
+
+/// The part of the player that a profiled span of time was spent in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    /// A whole frame, including everything that happens during it.
+    Frame,
+
+    /// Running frame scripts and the frame lifecycle.
+    Script,
+
+    /// Dispatching `enterFrame` and other broadcast events.
+    Events,
+
+    /// Drawing the stage.
+    Render,
+
+    /// Applying filters to bitmaps.
+    Filters,
+
+    /// Mixing and updating sounds.
+    Audio,
+
+    /// Collecting garbage.
+    Gc,
+
+    /// A single AVM1 function call.
+    Avm1,
+
+    /// A single AVM2 method call.
+    Avm2,
+}
+
+/// A span of time spent in one part of the player.
+#[derive(Clone, Debug, Serialize)]
+pub struct TraceEvent {
+    pub name: Cow<'static, str>,
+
+    #[serde(rename = "cat")]
+    pub category: Category,
+
+    /// The time this span started, in microseconds since profiling was started.
+    #[serde(rename = "ts")]
+    pub start: u64,
+
+    /// How long this span took, in microseconds.
+    #[serde(rename = "dur")]
+    pub duration: u64,
+}
+
+/// Counters describing how much time has been spent collecting garbage.
+///
+/// Unlike trace events, these are always kept up to date, whether or not profiling is enabled.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GcStats {
+    /// The number of incremental collection slices that have run.
+    pub slices: u64,
+
+    /// The total time spent collecting garbage.
+    pub total_time: Duration,
+
+    /// The longest time taken by a single slice.
+    pub longest_slice: Duration,
+}
+
+/// Records timings of the player while profiling is enabled.
+#[derive(Default)]
+pub struct Profiler {
+    /// When profiling was started, if it's enabled.
+    started: Option<Instant>,
+
+    events: Vec<TraceEvent>,
+
+    gc_stats: GcStats,
+}
+
+impl Profiler {
+    pub fn is_enabled(&self) -> bool {
+        self.started.is_some()
+    }
+
+    /// Start recording, discarding anything that was recorded before.
+    pub fn start_profiling(&mut self) {
+        self.started = Some(Instant::now());
+        self.events.clear();
+    }
+
+    /// Stop recording. Anything that was recorded is kept until profiling is started again.
+    pub fn stop_profiling(&mut self) {
+        self.started = None;
+    }
+
+    /// Mark the start of a span of time, if profiling is enabled.
+    ///
+    /// The returned value must be passed to [`Profiler::end`] once the span is over.
+    #[inline]
+    pub fn begin(&self) -> Option<Instant> {
+        self.started.map(|_| Instant::now())
+    }
+
+    /// Record a span of time that was started with [`Profiler::begin`].
+    pub fn end(
+        &mut self,
+        begin: Option<Instant>,
+        category: Category,
+        name: impl Into<Cow<'static, str>>,
+    ) {
+        if let (Some(started), Some(begin)) = (self.started, begin) {
+            self.push(started, begin, begin.elapsed(), category, name.into());
+        }
+    }
+
+    /// Record an ActionScript call that was started with [`Profiler::begin`], if it took
+    /// long enough to be worth recording.
+    ///
+    /// The name is only built for calls that are recorded.
+    pub fn end_call(
+        &mut self,
+        begin: Option<Instant>,
+        category: Category,
+        name: impl FnOnce() -> String,
+    ) {
+        if let (Some(started), Some(begin)) = (self.started, begin) {
+            let duration = begin.elapsed();
+            if duration >= CALL_SAMPLE_THRESHOLD {
+                self.push(started, begin, duration, category, name().into());
+            }
+        }
+    }
+
+    /// Count a slice of garbage collection that took the given amount of time.
+    pub fn record_gc_slice(&mut self, duration: Duration) {
+        self.gc_stats.slices += 1;
+        self.gc_stats.total_time += duration;
+        self.gc_stats.longest_slice = self.gc_stats.longest_slice.max(duration);
+    }
+
+    pub fn gc_stats(&self) -> GcStats {
+        self.gc_stats
+    }
+
+    fn push(
+        &mut self,
+        started: Instant,
+        begin: Instant,
+        duration: Duration,
+        category: Category,
+        name: Cow<'static, str>,
+    ) {
+        if self.events.len() >= MAX_EVENTS {
+            self.events.drain(..MAX_EVENTS / 10);
+        }
+        self.events.push(TraceEvent {
+            name,
+            category,
+            start: begin.saturating_duration_since(started).as_micros() as u64,
+            duration: duration.as_micros() as u64,
+        });
+    }
+
+    /// The spans recorded during the last profiling session.
+    pub fn events(&self) -> &[TraceEvent] {
+        &self.events
+    }
+
+    /// Export everything recorded during the last profiling session in the Chrome trace
+    /// event format.
+    pub fn to_chrome_trace(&self) -> String {
+        #[derive(Serialize)]
+        struct CompleteEvent<'a> {
+            #[serde(flatten)]
+            event: &'a TraceEvent,
+            ph: &'static str,
+            pid: u32,
+            tid: u32,
+        }
+
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Trace<'a> {
+            trace_events: Vec<CompleteEvent<'a>>,
+            display_time_unit: &'static str,
+        }
+
+        let trace = Trace {
+            trace_events: self
+                .events
+                .iter()
+                .map(|event| CompleteEvent {
+                    event,
+                    ph: "X",
+                    pid: 1,
+                    tid: 1,
+                })
+                .collect(),
+            display_time_unit: "ms",
+        };
+        serde_json::to_string(&trace).unwrap_or_default()
+    }
+}
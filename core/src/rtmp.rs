@@ -0,0 +1,1135 @@
+//! The Real-Time Messaging Protocol, used by `NetConnection` to talk to media servers.
+//!
+//! This implements the client side of RTMP without doing any I/O itself: bytes received
+//! from the server are passed to [`RtmpSession::receive`], which returns the messages
+//! they contained, and bytes that should be sent to the server are taken from
+//! [`RtmpSession::take_output`]. The same session is used for RTMPS, which only differs
+//! in the transport being wrapped in TLS.
+//!
+//! Messages are split into chunks of at most the chunk size, which are interleaved on
+//! chunk streams. Commands (such as `connect`, `createStream` and `play`) and their
+//! replies are encoded with AMF0, as are the shared object messages that keep remote
+//! shared objects in sync.
+
+use flash_lso::amf0::read::AMF0Decoder;
+use flash_lso::types::{Element, Value as AmfValue};
+use std::collections::HashMap;
+
+/// The port that RTMP servers listen on by default.
+pub const DEFAULT_PORT: u16 = 1935;
+
+/// The port that RTMPS servers listen on by default.
+pub const DEFAULT_SECURE_PORT: u16 = 443;
+
+const VERSION: u8 = 3;
+const HANDSHAKE_SIZE: usize = 1536;
+const DEFAULT_CHUNK_SIZE: usize = 128;
+const DEFAULT_WINDOW_ACK_SIZE: u32 = 2_500_000;
+
+/// The largest value of a timestamp field that doesn't need an extended timestamp.
+const MAX_TIMESTAMP: u32 = 0xFFFFFF;
+
+/// The chunk stream used for protocol control messages.
+const CONTROL_CHUNK_STREAM: u32 = 2;
+
+/// The chunk stream used for commands sent on the connection (stream 0).
+const COMMAND_CHUNK_STREAM: u32 = 3;
+
+/// The chunk stream used for commands and data sent on media streams.
+const STREAM_CHUNK_STREAM: u32 = 8;
+
+mod message_type {
+    pub const SET_CHUNK_SIZE: u8 = 1;
+    pub const ABORT: u8 = 2;
+    pub const ACKNOWLEDGEMENT: u8 = 3;
+    pub const USER_CONTROL: u8 = 4;
+    pub const WINDOW_ACK_SIZE: u8 = 5;
+    pub const SET_PEER_BANDWIDTH: u8 = 6;
+    pub const AUDIO: u8 = 8;
+    pub const VIDEO: u8 = 9;
+    pub const DATA_AMF3: u8 = 15;
+    pub const SHARED_OBJECT_AMF3: u8 = 16;
+    pub const COMMAND_AMF3: u8 = 17;
+    pub const DATA_AMF0: u8 = 18;
+    pub const SHARED_OBJECT_AMF0: u8 = 19;
+    pub const COMMAND_AMF0: u8 = 20;
+    pub const AGGREGATE: u8 = 22;
+}
+
+mod user_control {
+    pub const STREAM_BEGIN: u16 = 0;
+    pub const SET_BUFFER_LENGTH: u16 = 3;
+    pub const PING_REQUEST: u16 = 6;
+    pub const PING_RESPONSE: u16 = 7;
+}
+
+mod shared_object_event {
+    pub const USE: u8 = 1;
+    pub const RELEASE: u8 = 2;
+    pub const REQUEST_CHANGE: u8 = 3;
+    pub const CHANGE: u8 = 4;
+    pub const SUCCESS: u8 = 5;
+    pub const SEND_MESSAGE: u8 = 6;
+    pub const STATUS: u8 = 7;
+    pub const CLEAR: u8 = 8;
+    pub const REMOVE: u8 = 9;
+    pub const REQUEST_REMOVE: u8 = 10;
+    pub const USE_SUCCESS: u8 = 11;
+}
+
+/// An event in a shared object message, which keeps a remote shared object in sync
+/// between the server and its clients.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SharedObjectEvent {
+    /// The client starts using the shared object.
+    Use,
+    /// The client stops using the shared object.
+    Release,
+    /// The client asks the server to change a property.
+    RequestChange(String, AmfValue),
+    /// The server changed a property, either for another client or by rejecting our change.
+    Change(String, AmfValue),
+    /// The server accepted our change to a property.
+    Success(String),
+    /// A message that calls a handler on every client of the shared object.
+    SendMessage(String, Vec<AmfValue>),
+    /// The server reports an error or status, with a code and a level.
+    Status(String, String),
+    /// The server removed all properties.
+    Clear,
+    /// The server removed a property.
+    Remove(String),
+    /// The client asks the server to remove a property.
+    RequestRemove(String),
+    /// The server accepted our use of the shared object.
+    UseSuccess,
+}
+
+impl SharedObjectEvent {
+    fn read(type_id: u8, data: &[u8], events: &mut Vec<Self>) -> Option<()> {
+        match type_id {
+            shared_object_event::USE => events.push(Self::Use),
+            shared_object_event::RELEASE => events.push(Self::Release),
+            shared_object_event::CHANGE | shared_object_event::REQUEST_CHANGE => {
+                // Several properties can be changed by the same event.
+                let mut data = data;
+                while !data.is_empty() {
+                    let (name, rest) = read_string(data)?;
+                    let (rest, value) = AMF0Decoder::default().parse_single_element(rest).ok()?;
+                    events.push(if type_id == shared_object_event::CHANGE {
+                        Self::Change(name, value)
+                    } else {
+                        Self::RequestChange(name, value)
+                    });
+                    data = rest;
+                }
+            }
+            shared_object_event::SUCCESS => events.push(Self::Success(read_string(data)?.0)),
+            shared_object_event::SEND_MESSAGE => {
+                let mut values = read_amf0_values(data).into_iter();
+                let Some(AmfValue::String(handler)) = values.next() else {
+                    return None;
+                };
+                events.push(Self::SendMessage(handler, values.collect()));
+            }
+            shared_object_event::STATUS => {
+                let mut values = read_amf0_values(data).into_iter();
+                let (Some(AmfValue::String(code)), Some(AmfValue::String(level))) =
+                    (values.next(), values.next())
+                else {
+                    return None;
+                };
+                events.push(Self::Status(code, level));
+            }
+            shared_object_event::CLEAR => events.push(Self::Clear),
+            shared_object_event::REMOVE => events.push(Self::Remove(read_string(data)?.0)),
+            shared_object_event::REQUEST_REMOVE => {
+                events.push(Self::RequestRemove(read_string(data)?.0))
+            }
+            shared_object_event::USE_SUCCESS => events.push(Self::UseSuccess),
+            type_id => tracing::debug!("Ignoring shared object event of type {}", type_id),
+        }
+        Some(())
+    }
+
+    fn write(&self, output: &mut Vec<u8>) {
+        let mut data = Vec::new();
+        let type_id = match self {
+            Self::Use => shared_object_event::USE,
+            Self::Release => shared_object_event::RELEASE,
+            Self::RequestChange(name, value) | Self::Change(name, value) => {
+                write_amf0_string_raw(&mut data, name);
+                write_amf0(&mut data, value);
+                if let Self::Change(..) = self {
+                    shared_object_event::CHANGE
+                } else {
+                    shared_object_event::REQUEST_CHANGE
+                }
+            }
+            Self::Success(name) => {
+                write_amf0_string_raw(&mut data, name);
+                shared_object_event::SUCCESS
+            }
+            Self::SendMessage(handler, arguments) => {
+                write_amf0(&mut data, &AmfValue::String(handler.clone()));
+                for argument in arguments {
+                    write_amf0(&mut data, argument);
+                }
+                shared_object_event::SEND_MESSAGE
+            }
+            Self::Status(code, level) => {
+                write_amf0(&mut data, &AmfValue::String(code.clone()));
+                write_amf0(&mut data, &AmfValue::String(level.clone()));
+                shared_object_event::STATUS
+            }
+            Self::Clear => shared_object_event::CLEAR,
+            Self::Remove(name) => {
+                write_amf0_string_raw(&mut data, name);
+                shared_object_event::REMOVE
+            }
+            Self::RequestRemove(name) => {
+                write_amf0_string_raw(&mut data, name);
+                shared_object_event::REQUEST_REMOVE
+            }
+            Self::UseSuccess => shared_object_event::USE_SUCCESS,
+        };
+        output.push(type_id);
+        output.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        output.extend_from_slice(&data);
+    }
+}
+
+/// The kind of media carried by a media message.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MediaKind {
+    Audio,
+    Video,
+    /// AMF0 script data, such as `onMetaData`.
+    Data,
+}
+
+impl MediaKind {
+    /// The type of the FLV tag that holds this kind of media.
+    pub fn flv_tag_type(self) -> u8 {
+        match self {
+            MediaKind::Audio => 8,
+            MediaKind::Video => 9,
+            MediaKind::Data => 18,
+        }
+    }
+}
+
+/// Something that the server sent us.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RtmpEvent {
+    /// The server called a command, or replied to one of ours with `_result` or `_error`.
+    Command {
+        stream_id: u32,
+        name: String,
+        transaction_id: f64,
+        command_object: AmfValue,
+        arguments: Vec<AmfValue>,
+    },
+
+    /// The server sent a piece of media on a stream. The data is laid out like the body
+    /// of an FLV tag of the same kind.
+    Media {
+        stream_id: u32,
+        kind: MediaKind,
+        timestamp: u32,
+        data: Vec<u8>,
+    },
+
+    /// The server started sending a stream.
+    StreamBegin(u32),
+
+    /// The server sent events for a remote shared object.
+    SharedObject {
+        name: String,
+        version: u32,
+        events: Vec<SharedObjectEvent>,
+    },
+}
+
+/// An error that makes the connection unusable.
+#[derive(Debug, thiserror::Error)]
+pub enum RtmpError {
+    #[error("Server uses unsupported RTMP version {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("Message of type {0} is malformed")]
+    MalformedMessage(u8),
+
+    #[error("Chunk continues unknown message on chunk stream {0}")]
+    UnknownChunkStream(u32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum HandshakeState {
+    /// We've sent C0 and C1, and are waiting for S0 and S1.
+    WaitingForS1,
+    /// We've sent C2, and are waiting for S2.
+    WaitingForS2,
+    Done,
+}
+
+/// The state of a chunk stream that we're receiving messages on.
+#[derive(Clone, Debug, Default)]
+struct ChunkStream {
+    timestamp: u32,
+    timestamp_delta: u32,
+    length: usize,
+    type_id: u8,
+    stream_id: u32,
+    has_extended_timestamp: bool,
+
+    /// The data of the message that's being received, if it's been split into several chunks.
+    payload: Vec<u8>,
+}
+
+/// A complete message, reassembled from chunks.
+#[derive(Clone, Debug, PartialEq)]
+struct Message {
+    type_id: u8,
+    stream_id: u32,
+    timestamp: u32,
+    payload: Vec<u8>,
+}
+
+/// The client side of an RTMP connection.
+pub struct RtmpSession {
+    handshake: HandshakeState,
+
+    /// The C1 packet that we sent, which the server echoes back in S2.
+    c1: Vec<u8>,
+
+    /// Received data that hasn't been processed yet.
+    input: Vec<u8>,
+
+    /// Data to send to the server.
+    output: Vec<u8>,
+
+    /// Messages that are sent once the handshake is done.
+    queued: Vec<(u32, Message)>,
+
+    in_chunk_size: usize,
+    out_chunk_size: usize,
+    in_chunk_streams: HashMap<u32, ChunkStream>,
+
+    window_ack_size: u32,
+    bytes_received: u32,
+    last_acknowledged: u32,
+
+    next_transaction_id: f64,
+}
+
+impl Default for RtmpSession {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RtmpSession {
+    /// Starts a session, queueing the start of the handshake.
+    pub fn new() -> Self {
+        // C0 is the version, and C1 is our time, four zero bytes, then random data.
+        let mut c1 = vec![0; HANDSHAKE_SIZE];
+        let mut seed: u32 = 0x2545_F491;
+        for byte in &mut c1[8..] {
+            // xorshift32, since the random data doesn't need to be secure.
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            *byte = seed as u8;
+        }
+
+        let mut output = Vec::with_capacity(1 + HANDSHAKE_SIZE);
+        output.push(VERSION);
+        output.extend_from_slice(&c1);
+
+        Self {
+            handshake: HandshakeState::WaitingForS1,
+            c1,
+            input: Vec::new(),
+            output,
+            queued: Vec::new(),
+            in_chunk_size: DEFAULT_CHUNK_SIZE,
+            out_chunk_size: DEFAULT_CHUNK_SIZE,
+            in_chunk_streams: HashMap::new(),
+            window_ack_size: DEFAULT_WINDOW_ACK_SIZE,
+            bytes_received: 0,
+            last_acknowledged: 0,
+            // Transaction 1 is reserved for `connect`.
+            next_transaction_id: 2.0,
+        }
+    }
+
+    /// Whether the handshake is done, and messages are being exchanged.
+    pub fn is_ready(&self) -> bool {
+        self.handshake == HandshakeState::Done
+    }
+
+    /// Takes the data that should be sent to the server.
+    pub fn take_output(&mut self) -> Vec<u8> {
+        std::mem::take(&mut self.output)
+    }
+
+    /// Processes data received from the server, returning the events it contained.
+    pub fn receive(&mut self, data: &[u8]) -> Result<Vec<RtmpEvent>, RtmpError> {
+        self.input.extend_from_slice(data);
+
+        let mut events = Vec::new();
+        loop {
+            match self.handshake {
+                HandshakeState::WaitingForS1 => {
+                    if self.input.len() < 1 + HANDSHAKE_SIZE {
+                        break;
+                    }
+                    if self.input[0] != VERSION {
+                        return Err(RtmpError::UnsupportedVersion(self.input[0]));
+                    }
+
+                    // C2 echoes S1, with the second time field set to when we read it.
+                    let s1: Vec<u8> = self.input.drain(..1 + HANDSHAKE_SIZE).skip(1).collect();
+                    self.output.extend_from_slice(&s1[..4]);
+                    self.output.extend_from_slice(&[0; 4]);
+                    self.output.extend_from_slice(&s1[8..]);
+                    self.handshake = HandshakeState::WaitingForS2;
+                }
+                HandshakeState::WaitingForS2 => {
+                    if self.input.len() < HANDSHAKE_SIZE {
+                        break;
+                    }
+                    let s2: Vec<u8> = self.input.drain(..HANDSHAKE_SIZE).collect();
+                    if s2[8..] != self.c1[8..] {
+                        // Some servers don't echo C1 properly, and Flash Player doesn't mind.
+                        tracing::debug!("RTMP server didn't echo our handshake");
+                    }
+                    self.handshake = HandshakeState::Done;
+
+                    for (chunk_stream_id, message) in std::mem::take(&mut self.queued) {
+                        self.write_message(chunk_stream_id, &message);
+                    }
+                }
+                HandshakeState::Done => match self.read_chunk()? {
+                    Some(Some(message)) => self.handle_message(message, &mut events)?,
+                    Some(None) => {}
+                    None => break,
+                },
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Reads a chunk from the input, returning the message that it completes, if any.
+    ///
+    /// Returns `None` if the whole chunk hasn't been received yet.
+    fn read_chunk(&mut self) -> Result<Option<Option<Message>>, RtmpError> {
+        let input = &self.input;
+        let Some(&first) = input.first() else {
+            return Ok(None);
+        };
+
+        let format = first >> 6;
+        let (chunk_stream_id, mut pos) = match first & 0x3F {
+            0 => match input.get(1) {
+                Some(&id) => (64 + u32::from(id), 2),
+                None => return Ok(None),
+            },
+            1 => match input.get(1..3) {
+                Some(id) => (64 + u32::from(id[0]) + (u32::from(id[1]) << 8), 3),
+                None => return Ok(None),
+            },
+            id => (u32::from(id), 1),
+        };
+
+        let header_length = match format {
+            0 => 11,
+            1 => 7,
+            2 => 3,
+            _ => 0,
+        };
+        let Some(header) = input.get(pos..pos + header_length) else {
+            return Ok(None);
+        };
+        pos += header_length;
+
+        let mut stream = match self.in_chunk_streams.get(&chunk_stream_id) {
+            Some(stream) => stream.clone(),
+            None if format == 0 => ChunkStream::default(),
+            None => return Err(RtmpError::UnknownChunkStream(chunk_stream_id)),
+        };
+        let is_new_message = stream.payload.is_empty();
+
+        let mut timestamp_field = None;
+        if format <= 2 {
+            timestamp_field = Some(read_u24(&header[0..3]));
+        }
+        if format <= 1 {
+            stream.length = read_u24(&header[3..6]) as usize;
+            stream.type_id = header[6];
+        }
+        if format == 0 {
+            stream.stream_id = u32::from_le_bytes([header[7], header[8], header[9], header[10]]);
+        }
+
+        let has_extended_timestamp = match timestamp_field {
+            Some(field) => field == MAX_TIMESTAMP,
+            None => stream.has_extended_timestamp,
+        };
+        let mut timestamp_value = timestamp_field.unwrap_or(stream.timestamp_delta);
+        if has_extended_timestamp {
+            let Some(extended) = input.get(pos..pos + 4) else {
+                return Ok(None);
+            };
+            timestamp_value =
+                u32::from_be_bytes([extended[0], extended[1], extended[2], extended[3]]);
+            pos += 4;
+        }
+        stream.has_extended_timestamp = has_extended_timestamp;
+
+        let remaining = stream.length.saturating_sub(stream.payload.len());
+        let chunk_length = remaining.min(self.in_chunk_size);
+        let Some(data) = input.get(pos..pos + chunk_length) else {
+            return Ok(None);
+        };
+        pos += chunk_length;
+
+        // Timestamps only change at the start of a message.
+        if is_new_message {
+            match format {
+                0 => {
+                    stream.timestamp = timestamp_value;
+                    stream.timestamp_delta = 0;
+                }
+                1 | 2 => {
+                    stream.timestamp = stream.timestamp.wrapping_add(timestamp_value);
+                    stream.timestamp_delta = timestamp_value;
+                }
+                _ => stream.timestamp = stream.timestamp.wrapping_add(stream.timestamp_delta),
+            }
+        }
+        stream.payload.extend_from_slice(data);
+
+        let message = if stream.payload.len() >= stream.length {
+            Some(Message {
+                type_id: stream.type_id,
+                stream_id: stream.stream_id,
+                timestamp: stream.timestamp,
+                payload: std::mem::take(&mut stream.payload),
+            })
+        } else {
+            None
+        };
+
+        self.in_chunk_streams.insert(chunk_stream_id, stream);
+        self.input.drain(..pos);
+
+        self.bytes_received = self.bytes_received.wrapping_add(pos as u32);
+        if self.bytes_received.wrapping_sub(self.last_acknowledged) >= self.window_ack_size {
+            self.last_acknowledged = self.bytes_received;
+            self.send_control(
+                message_type::ACKNOWLEDGEMENT,
+                self.bytes_received.to_be_bytes().to_vec(),
+            );
+        }
+
+        Ok(Some(message))
+    }
+
+    fn handle_message(
+        &mut self,
+        message: Message,
+        events: &mut Vec<RtmpEvent>,
+    ) -> Result<(), RtmpError> {
+        let payload = &message.payload;
+        let malformed = || RtmpError::MalformedMessage(message.type_id);
+        match message.type_id {
+            message_type::SET_CHUNK_SIZE => {
+                let size = payload.get(0..4).ok_or_else(malformed)?;
+                let size = u32::from_be_bytes([size[0], size[1], size[2], size[3]]) & 0x7FFF_FFFF;
+                self.in_chunk_size = (size as usize).max(1);
+            }
+            message_type::ABORT => {
+                let id = payload.get(0..4).ok_or_else(malformed)?;
+                let id = u32::from_be_bytes([id[0], id[1], id[2], id[3]]);
+                if let Some(stream) = self.in_chunk_streams.get_mut(&id) {
+                    stream.payload.clear();
+                }
+            }
+            message_type::WINDOW_ACK_SIZE => {
+                let size = payload.get(0..4).ok_or_else(malformed)?;
+                self.window_ack_size = u32::from_be_bytes([size[0], size[1], size[2], size[3]]);
+            }
+            message_type::SET_PEER_BANDWIDTH => {
+                // Like Flash Player, we reply with a window acknowledgement size of the same size.
+                let size = payload.get(0..4).ok_or_else(malformed)?;
+                self.send_control(message_type::WINDOW_ACK_SIZE, size.to_vec());
+            }
+            message_type::USER_CONTROL => {
+                let event = payload.get(0..2).ok_or_else(malformed)?;
+                let data = &payload[2..];
+                match u16::from_be_bytes([event[0], event[1]]) {
+                    user_control::STREAM_BEGIN => {
+                        let id = data.get(0..4).ok_or_else(malformed)?;
+                        events.push(RtmpEvent::StreamBegin(u32::from_be_bytes([
+                            id[0], id[1], id[2], id[3],
+                        ])));
+                    }
+                    user_control::PING_REQUEST => {
+                        let mut response = user_control::PING_RESPONSE.to_be_bytes().to_vec();
+                        response.extend_from_slice(data);
+                        self.send_control(message_type::USER_CONTROL, response);
+                    }
+                    _ => {}
+                }
+            }
+            message_type::ACKNOWLEDGEMENT => {}
+            message_type::AUDIO | message_type::VIDEO | message_type::DATA_AMF0 => {
+                let kind = match message.type_id {
+                    message_type::AUDIO => MediaKind::Audio,
+                    message_type::VIDEO => MediaKind::Video,
+                    _ => MediaKind::Data,
+                };
+                events.push(RtmpEvent::Media {
+                    stream_id: message.stream_id,
+                    kind,
+                    timestamp: message.timestamp,
+                    data: message.payload,
+                });
+            }
+            message_type::DATA_AMF3 => {
+                // AMF3 data messages start with a format byte, then are encoded with AMF0.
+                events.push(RtmpEvent::Media {
+                    stream_id: message.stream_id,
+                    kind: MediaKind::Data,
+                    timestamp: message.timestamp,
+                    data: payload.get(1..).unwrap_or_default().to_vec(),
+                });
+            }
+            message_type::COMMAND_AMF0 | message_type::COMMAND_AMF3 => {
+                let payload = if message.type_id == message_type::COMMAND_AMF3 {
+                    payload.get(1..).unwrap_or_default()
+                } else {
+                    payload
+                };
+                let mut values = read_amf0_values(payload).into_iter();
+                let Some(AmfValue::String(name)) = values.next() else {
+                    return Err(malformed());
+                };
+                let transaction_id = match values.next() {
+                    Some(AmfValue::Number(id)) => id,
+                    _ => 0.0,
+                };
+                let command_object = values.next().unwrap_or(AmfValue::Null);
+                events.push(RtmpEvent::Command {
+                    stream_id: message.stream_id,
+                    name,
+                    transaction_id,
+                    command_object,
+                    arguments: values.collect(),
+                });
+            }
+            message_type::SHARED_OBJECT_AMF0 | message_type::SHARED_OBJECT_AMF3 => {
+                // Like commands, AMF3 shared object messages start with a format byte.
+                let payload = if message.type_id == message_type::SHARED_OBJECT_AMF3 {
+                    payload.get(1..).unwrap_or_default()
+                } else {
+                    payload
+                };
+                let (name, rest) = read_string(payload).ok_or_else(malformed)?;
+                // The version is followed by the persistence flags, which only matter to the server.
+                let header = rest.get(0..12).ok_or_else(malformed)?;
+                let version = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+
+                let mut data = &rest[12..];
+                let mut so_events = Vec::new();
+                while !data.is_empty() {
+                    let header = data.get(0..5).ok_or_else(malformed)?;
+                    let length =
+                        u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+                    let event_data = data.get(5..5 + length).ok_or_else(malformed)?;
+                    SharedObjectEvent::read(header[0], event_data, &mut so_events)
+                        .ok_or_else(malformed)?;
+                    data = &data[5 + length..];
+                }
+                events.push(RtmpEvent::SharedObject {
+                    name,
+                    version,
+                    events: so_events,
+                });
+            }
+            message_type::AGGREGATE => {
+                // Aggregate messages hold several media messages, laid out as FLV tags.
+                let mut data = &payload[..];
+                let mut first_timestamp = None;
+                while data.len() >= 11 {
+                    let kind = match data[0] {
+                        message_type::AUDIO => Some(MediaKind::Audio),
+                        message_type::VIDEO => Some(MediaKind::Video),
+                        message_type::DATA_AMF0 => Some(MediaKind::Data),
+                        _ => None,
+                    };
+                    let length = read_u24(&data[1..4]) as usize;
+                    let timestamp = read_u24(&data[4..7]) | (u32::from(data[7]) << 24);
+                    let body = data.get(11..11 + length).ok_or_else(malformed)?;
+
+                    // The timestamps of the tags are relative to that of the message.
+                    let first_timestamp = *first_timestamp.get_or_insert(timestamp);
+                    if let Some(kind) = kind {
+                        events.push(RtmpEvent::Media {
+                            stream_id: message.stream_id,
+                            kind,
+                            timestamp: message
+                                .timestamp
+                                .wrapping_add(timestamp.wrapping_sub(first_timestamp)),
+                            data: body.to_vec(),
+                        });
+                    }
+
+                    // Skip the size of the previous tag after the body.
+                    data = data.get(11 + length + 4..).unwrap_or_default();
+                }
+            }
+            type_id => tracing::debug!("Ignoring RTMP message of type {}", type_id),
+        }
+        Ok(())
+    }
+
+    /// Returns a new transaction ID, which the server uses to reply to a command.
+    pub fn next_transaction_id(&mut self) -> f64 {
+        let id = self.next_transaction_id;
+        self.next_transaction_id += 1.0;
+        id
+    }
+
+    /// Sends the `connect` command, which must be the first command of a session.
+    ///
+    /// The server replies with a `_result` or `_error` with transaction ID 1.
+    pub fn connect(&mut self, command_object: AmfValue, arguments: &[AmfValue]) {
+        self.send_command(0, "connect", 1.0, &command_object, arguments);
+    }
+
+    /// Sends a command.
+    ///
+    /// Commands that don't expect a reply should use a transaction ID of 0.
+    pub fn send_command(
+        &mut self,
+        stream_id: u32,
+        name: &str,
+        transaction_id: f64,
+        command_object: &AmfValue,
+        arguments: &[AmfValue],
+    ) {
+        let mut payload = Vec::new();
+        write_amf0(&mut payload, &AmfValue::String(name.to_string()));
+        write_amf0(&mut payload, &AmfValue::Number(transaction_id));
+        write_amf0(&mut payload, command_object);
+        for argument in arguments {
+            write_amf0(&mut payload, argument);
+        }
+
+        let chunk_stream_id = if stream_id == 0 {
+            COMMAND_CHUNK_STREAM
+        } else {
+            STREAM_CHUNK_STREAM
+        };
+        self.send_message(
+            chunk_stream_id,
+            Message {
+                type_id: message_type::COMMAND_AMF0,
+                stream_id,
+                timestamp: 0,
+                payload,
+            },
+        );
+    }
+
+    /// Sends a data message, such as the ones sent by `NetStream.send`.
+    pub fn send_data(&mut self, stream_id: u32, values: &[AmfValue]) {
+        let mut payload = Vec::new();
+        for value in values {
+            write_amf0(&mut payload, value);
+        }
+        self.send_message(
+            STREAM_CHUNK_STREAM,
+            Message {
+                type_id: message_type::DATA_AMF0,
+                stream_id,
+                timestamp: 0,
+                payload,
+            },
+        );
+    }
+
+    /// Sends events for a remote shared object.
+    ///
+    /// `version` is the version of the shared object that the events are based on.
+    pub fn send_shared_object(
+        &mut self,
+        name: &str,
+        version: u32,
+        persistent: bool,
+        events: &[SharedObjectEvent],
+    ) {
+        let mut payload = Vec::new();
+        write_amf0_string_raw(&mut payload, name);
+        payload.extend_from_slice(&version.to_be_bytes());
+        let flags: u32 = if persistent { 2 } else { 0 };
+        payload.extend_from_slice(&flags.to_be_bytes());
+        payload.extend_from_slice(&[0; 4]);
+        for event in events {
+            event.write(&mut payload);
+        }
+        self.send_message(
+            COMMAND_CHUNK_STREAM,
+            Message {
+                type_id: message_type::SHARED_OBJECT_AMF0,
+                stream_id: 0,
+                timestamp: 0,
+                payload,
+            },
+        );
+    }
+
+    /// Tells the server how much of a stream we buffer, in milliseconds.
+    pub fn set_buffer_length(&mut self, stream_id: u32, length: u32) {
+        let mut payload = user_control::SET_BUFFER_LENGTH.to_be_bytes().to_vec();
+        payload.extend_from_slice(&stream_id.to_be_bytes());
+        payload.extend_from_slice(&length.to_be_bytes());
+        self.send_control(message_type::USER_CONTROL, payload);
+    }
+
+    fn send_control(&mut self, type_id: u8, payload: Vec<u8>) {
+        self.send_message(
+            CONTROL_CHUNK_STREAM,
+            Message {
+                type_id,
+                stream_id: 0,
+                timestamp: 0,
+                payload,
+            },
+        );
+    }
+
+    fn send_message(&mut self, chunk_stream_id: u32, message: Message) {
+        if self.is_ready() {
+            self.write_message(chunk_stream_id, &message);
+        } else {
+            self.queued.push((chunk_stream_id, message));
+        }
+    }
+
+    /// Splits a message into chunks, and adds them to the output.
+    ///
+    /// The first chunk has a full header, and the others continue it.
+    fn write_message(&mut self, chunk_stream_id: u32, message: &Message) {
+        let has_extended_timestamp = message.timestamp >= MAX_TIMESTAMP;
+        for (i, chunk) in message.payload.chunks(self.out_chunk_size).enumerate() {
+            let format = if i == 0 { 0 } else { 3 };
+            write_basic_header(&mut self.output, format, chunk_stream_id);
+            if format == 0 {
+                write_u24(&mut self.output, message.timestamp.min(MAX_TIMESTAMP));
+                write_u24(&mut self.output, message.payload.len() as u32);
+                self.output.push(message.type_id);
+                self.output
+                    .extend_from_slice(&message.stream_id.to_le_bytes());
+            }
+            if has_extended_timestamp {
+                self.output
+                    .extend_from_slice(&message.timestamp.to_be_bytes());
+            }
+            self.output.extend_from_slice(chunk);
+        }
+
+        if message.payload.is_empty() {
+            write_basic_header(&mut self.output, 0, chunk_stream_id);
+            write_u24(&mut self.output, message.timestamp.min(MAX_TIMESTAMP));
+            write_u24(&mut self.output, 0);
+            self.output.push(message.type_id);
+            self.output
+                .extend_from_slice(&message.stream_id.to_le_bytes());
+            if has_extended_timestamp {
+                self.output
+                    .extend_from_slice(&message.timestamp.to_be_bytes());
+            }
+        }
+    }
+}
+
+fn read_u24(bytes: &[u8]) -> u32 {
+    (u32::from(bytes[0]) << 16) | (u32::from(bytes[1]) << 8) | u32::from(bytes[2])
+}
+
+/// Reads a string that's prefixed with its 16-bit length, returning it and the rest of the data.
+fn read_string(data: &[u8]) -> Option<(String, &[u8])> {
+    let length = data.get(0..2)?;
+    let length = usize::from(u16::from_be_bytes([length[0], length[1]]));
+    let string = data.get(2..2 + length)?;
+    Some((
+        String::from_utf8_lossy(string).into_owned(),
+        &data[2 + length..],
+    ))
+}
+
+fn write_u24(output: &mut Vec<u8>, value: u32) {
+    output.extend_from_slice(&value.to_be_bytes()[1..]);
+}
+
+fn write_basic_header(output: &mut Vec<u8>, format: u8, chunk_stream_id: u32) {
+    match chunk_stream_id {
+        0..=63 => output.push((format << 6) | chunk_stream_id as u8),
+        64..=319 => {
+            output.push(format << 6);
+            output.push((chunk_stream_id - 64) as u8);
+        }
+        _ => {
+            output.push((format << 6) | 1);
+            output.extend_from_slice(&((chunk_stream_id - 64) as u16).to_le_bytes());
+        }
+    }
+}
+
+/// Reads consecutive AMF0 values, stopping at the end of the data or at the first
+/// value that can't be read.
+pub fn read_amf0_values(mut data: &[u8]) -> Vec<AmfValue> {
+    // References refer to earlier values of the same message, so one decoder is used for all.
+    let mut decoder = AMF0Decoder::default();
+    let mut values = Vec::new();
+    while !data.is_empty() {
+        match decoder.parse_single_element(data) {
+            Ok((rest, value)) => {
+                values.push(value);
+                data = rest;
+            }
+            Err(_) => {
+                tracing::warn!("Failed to read AMF0 value in RTMP message");
+                break;
+            }
+        }
+    }
+    values
+}
+
+/// Writes a value with AMF0.
+pub fn write_amf0(output: &mut Vec<u8>, value: &AmfValue) {
+    match value {
+        AmfValue::Number(n) => {
+            output.push(0x00);
+            output.extend_from_slice(&n.to_be_bytes());
+        }
+        AmfValue::Integer(i) => write_amf0(output, &AmfValue::Number(f64::from(*i))),
+        AmfValue::Bool(b) => {
+            output.push(0x01);
+            output.push(u8::from(*b));
+        }
+        AmfValue::String(s) => {
+            if let Ok(length) = u16::try_from(s.len()) {
+                output.push(0x02);
+                output.extend_from_slice(&length.to_be_bytes());
+            } else {
+                output.push(0x0C);
+                output.extend_from_slice(&(s.len() as u32).to_be_bytes());
+            }
+            output.extend_from_slice(s.as_bytes());
+        }
+        AmfValue::Object(elements, _) => {
+            output.push(0x03);
+            write_amf0_properties(output, elements);
+        }
+        AmfValue::Null => output.push(0x05),
+        AmfValue::ECMAArray(dense, elements, _) => {
+            output.push(0x08);
+            output.extend_from_slice(&((dense.len() + elements.len()) as u32).to_be_bytes());
+            for (i, value) in dense.iter().enumerate() {
+                write_amf0_string_raw(output, &i.to_string());
+                write_amf0(output, value);
+            }
+            write_amf0_properties(output, elements);
+        }
+        AmfValue::StrictArray(values) => {
+            output.push(0x0A);
+            output.extend_from_slice(&(values.len() as u32).to_be_bytes());
+            for value in values {
+                write_amf0(output, value);
+            }
+        }
+        AmfValue::Date(time, timezone) => {
+            output.push(0x0B);
+            output.extend_from_slice(&time.to_be_bytes());
+            output.extend_from_slice(&timezone.unwrap_or(0).to_be_bytes());
+        }
+        AmfValue::XML(content, _) => {
+            output.push(0x0F);
+            output.extend_from_slice(&(content.len() as u32).to_be_bytes());
+            output.extend_from_slice(content.as_bytes());
+        }
+        AmfValue::AMF3(value) => write_amf0(output, value),
+        // Everything else only exists in AMF3.
+        _ => output.push(0x06),
+    }
+}
+
+fn write_amf0_string_raw(output: &mut Vec<u8>, s: &str) {
+    let bytes = &s.as_bytes()[..s.len().min(u16::MAX as usize)];
+    output.extend_from_slice(&(bytes.len() as u16).to_be_bytes());
+    output.extend_from_slice(bytes);
+}
+
+fn write_amf0_properties(output: &mut Vec<u8>, elements: &[Element]) {
+    for element in elements {
+        write_amf0_string_raw(output, &element.name);
+        write_amf0(output, &element.value);
+    }
+    // An empty name, then the object end marker.
+    output.extend_from_slice(&[0x00, 0x00, 0x09]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Completes the handshake of a session, as a server that echoes C1 would, returning
+    /// what the session sent after C2.
+    fn handshake(session: &mut RtmpSession) -> Vec<u8> {
+        let c0_c1 = session.take_output();
+        assert_eq!(c0_c1.len(), 1 + HANDSHAKE_SIZE);
+
+        let mut response = vec![VERSION];
+        response.extend_from_slice(&[0x55; HANDSHAKE_SIZE]);
+        response.extend_from_slice(&c0_c1[1..]);
+        assert_eq!(session.receive(&response).unwrap(), vec![]);
+        assert!(session.is_ready());
+
+        // C2 echoes S1, except for the second time field.
+        let mut output = session.take_output();
+        let mut expected = vec![0x55; HANDSHAKE_SIZE];
+        expected[4..8].fill(0);
+        assert_eq!(output[..HANDSHAKE_SIZE], expected);
+        output.split_off(HANDSHAKE_SIZE)
+    }
+
+    #[test]
+    fn commands_are_sent_after_handshake() {
+        let mut session = RtmpSession::new();
+        session.send_command(0, "test", 2.0, &AmfValue::Null, &[]);
+        assert_eq!(session.output.len(), 1 + HANDSHAKE_SIZE);
+
+        let command = handshake(&mut session);
+        assert_eq!(command[0], COMMAND_CHUNK_STREAM as u8);
+        assert_eq!(command[7], message_type::COMMAND_AMF0);
+    }
+
+    #[test]
+    fn long_messages_are_split_into_chunks() {
+        let mut sender = RtmpSession::new();
+        assert!(handshake(&mut sender).is_empty());
+        let argument = AmfValue::String("x".repeat(300));
+        sender.send_command(0, "longCommand", 5.0, &AmfValue::Null, &[argument.clone()]);
+        let chunks = sender.take_output();
+        // A full header, then two continuation headers, as the message is over 256 bytes.
+        assert_eq!(chunks[0], COMMAND_CHUNK_STREAM as u8);
+        assert_eq!(
+            chunks[12 + DEFAULT_CHUNK_SIZE],
+            0xC0 | COMMAND_CHUNK_STREAM as u8
+        );
+
+        // Read the chunks back with a session of our own.
+        let mut receiver = RtmpSession::new();
+        handshake(&mut receiver);
+        let (first, second) = chunks.split_at(100);
+        assert_eq!(receiver.receive(first).unwrap(), vec![]);
+        assert_eq!(
+            receiver.receive(second).unwrap(),
+            vec![RtmpEvent::Command {
+                stream_id: 0,
+                name: "longCommand".to_string(),
+                transaction_id: 5.0,
+                command_object: AmfValue::Null,
+                arguments: vec![argument],
+            }]
+        );
+    }
+
+    #[test]
+    fn set_chunk_size_is_applied() {
+        let mut session = RtmpSession::new();
+        handshake(&mut session);
+
+        let mut data = vec![
+            0x02,
+            0,
+            0,
+            0,
+            0,
+            0,
+            4,
+            message_type::SET_CHUNK_SIZE,
+            0,
+            0,
+            0,
+            0,
+        ];
+        data.extend_from_slice(&4096u32.to_be_bytes());
+        assert_eq!(session.receive(&data).unwrap(), vec![]);
+        assert_eq!(session.in_chunk_size, 4096);
+    }
+
+    #[test]
+    fn shared_object_messages_round_trip() {
+        let events = vec![
+            SharedObjectEvent::Change("score".to_string(), AmfValue::Number(42.0)),
+            SharedObjectEvent::Success("name".to_string()),
+            SharedObjectEvent::SendMessage(
+                "chat".to_string(),
+                vec![AmfValue::String("hi".to_string())],
+            ),
+            SharedObjectEvent::Remove("old".to_string()),
+            SharedObjectEvent::UseSuccess,
+        ];
+        let mut sender = RtmpSession::new();
+        handshake(&mut sender);
+        sender.send_shared_object("lobby", 7, true, &events);
+
+        let mut receiver = RtmpSession::new();
+        handshake(&mut receiver);
+        assert_eq!(
+            receiver.receive(&sender.take_output()).unwrap(),
+            vec![RtmpEvent::SharedObject {
+                name: "lobby".to_string(),
+                version: 7,
+                events,
+            }]
+        );
+    }
+
+    #[test]
+    fn ping_requests_are_answered() {
+        let mut session = RtmpSession::new();
+        handshake(&mut session);
+
+        let mut data = vec![
+            0x02,
+            0,
+            0,
+            0,
+            0,
+            0,
+            6,
+            message_type::USER_CONTROL,
+            0,
+            0,
+            0,
+            0,
+        ];
+        data.extend_from_slice(&user_control::PING_REQUEST.to_be_bytes());
+        data.extend_from_slice(&1234u32.to_be_bytes());
+        assert_eq!(session.receive(&data).unwrap(), vec![]);
+
+        let output = session.take_output();
+        assert_eq!(output[7], message_type::USER_CONTROL);
+        assert_eq!(&output[12..14], &user_control::PING_RESPONSE.to_be_bytes());
+        assert_eq!(&output[14..18], &1234u32.to_be_bytes());
+    }
+}
@@ -0,0 +1,221 @@
+//! Save states, which return a movie to an earlier point
+//!
+//! Everything a movie creates lives in a garbage collected heap, which can't be copied out
+//! and back in again. Instead, while save states are enabled, the player keeps a journal of
+//! everything that influenced the movie since it loaded: the frames it ran, how much time
+//! passed between them, the input it received, and the times that scripts read. A save state
+//! is a copy of that journal. Restoring one replays the journal into a freshly loaded copy of
+//! the same movie, as fast as possible, which brings it back to where it was.
+//!
+//! Anything that comes from outside of the player isn't journaled, such as network responses
+//! and the current date, so movies that depend on them may not come back exactly as they
+//! were. Restoring a state also takes longer the longer the movie had been running.
+
+use crate::events::PlayerEvent;
+use ruffle_render::backend::ViewportDimensions;
+use std::time::Duration;
+
+/// Something that happened to the player, which needs to happen again to restore a state.
+#[derive(Clone, Debug)]
+pub(crate) enum JournalEntry {
+    /// A frame of the movie was run.
+    Frame,
+
+    /// Everything other than frames was updated for this many milliseconds passing.
+    Tick(f64),
+
+    /// The stage was rendered.
+    Render,
+
+    /// The player received an event.
+    Event(PlayerEvent),
+
+    /// A script read the time since the movie started.
+    Time(Duration),
+
+    /// The size of the viewport changed.
+    Viewport(ViewportDimensions),
+}
+
+/// A point in a movie that it can be returned to.
+#[derive(Clone, Debug)]
+pub struct SaveState {
+    /// The seed of the player's random number generator.
+    seed: u64,
+
+    entries: Vec<JournalEntry>,
+
+    /// The number of frames that had run.
+    frames: u32,
+}
+
+impl SaveState {
+    /// The number of frames that had run when this state was saved.
+    pub fn frames(&self) -> u32 {
+        self.frames
+    }
+}
+
+/// A save state that is being replayed.
+struct Replay {
+    entries: Vec<JournalEntry>,
+
+    /// The position of the next entry to replay.
+    position: usize,
+
+    /// The last time that was replayed.
+    time: Duration,
+}
+
+/// Keeps track of everything that happens to the player while save states are enabled.
+#[derive(Default)]
+pub struct Journal {
+    /// Whether save states were enabled when the player was created.
+    enabled: bool,
+
+    /// Whether the root movie has loaded and everything is being journaled.
+    recording: bool,
+
+    seed: u64,
+
+    entries: Vec<JournalEntry>,
+
+    frames: u32,
+
+    /// A state to restore once the root movie loads.
+    pending: Option<SaveState>,
+
+    replay: Option<Replay>,
+}
+
+impl Journal {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.replay.is_some()
+    }
+
+    /// Restore the given state once the root movie loads.
+    pub fn set_pending(&mut self, state: SaveState) {
+        if self.enabled {
+            self.pending = Some(state);
+        }
+    }
+
+    /// Start journaling, now that the root movie has loaded.
+    ///
+    /// Returns the seed that the player's random number generator should use, which is
+    /// taken from the pending state if there is one.
+    pub fn start(&mut self, new_seed: u64) -> Option<u64> {
+        if !self.enabled {
+            return None;
+        }
+
+        self.recording = true;
+        self.entries.clear();
+        self.frames = 0;
+        self.seed = match self.pending.take() {
+            Some(state) => {
+                self.replay = Some(Replay {
+                    entries: state.entries,
+                    position: 0,
+                    time: Duration::ZERO,
+                });
+                state.seed
+            }
+            None => new_seed,
+        };
+        Some(self.seed)
+    }
+
+    pub(crate) fn record(&mut self, entry: JournalEntry) {
+        if self.recording {
+            if let JournalEntry::Frame = entry {
+                self.frames += 1;
+            }
+            self.entries.push(entry);
+        }
+    }
+
+    /// Journal the time that a script read, or replay the time it read before.
+    pub fn time(&mut self, time: Duration) -> Duration {
+        let time = match &mut self.replay {
+            Some(replay) => {
+                if let Some(JournalEntry::Time(replayed)) = replay.entries.get(replay.position) {
+                    replay.time = *replayed;
+                    replay.position += 1;
+                }
+                replay.time
+            }
+            None => time,
+        };
+        self.record(JournalEntry::Time(time));
+        time
+    }
+
+    /// The next entry to replay, other than times, which are replayed as they're read.
+    pub(crate) fn next_replayed(&mut self) -> Option<JournalEntry> {
+        let replay = self.replay.as_mut()?;
+        while let Some(entry) = replay.entries.get(replay.position) {
+            replay.position += 1;
+            if !matches!(entry, JournalEntry::Time(_)) {
+                return Some(entry.clone());
+            }
+        }
+        self.replay = None;
+        None
+    }
+
+    pub fn save(&self) -> Option<SaveState> {
+        self.recording.then(|| SaveState {
+            seed: self.seed,
+            entries: self.entries.clone(),
+            frames: self.frames,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replays_entries_and_times() {
+        let mut journal = Journal::new(true);
+        journal.record(JournalEntry::Frame);
+        assert_eq!(journal.start(1), Some(1));
+        journal.record(JournalEntry::Frame);
+        journal.time(Duration::from_millis(5));
+        journal.record(JournalEntry::Tick(10.0));
+        let state = journal.save().unwrap();
+        assert_eq!(state.frames(), 1);
+
+        let mut journal = Journal::new(true);
+        journal.set_pending(state);
+        assert_eq!(journal.start(2), Some(1));
+        assert!(matches!(journal.next_replayed(), Some(JournalEntry::Frame)));
+        assert_eq!(
+            journal.time(Duration::from_millis(100)),
+            Duration::from_millis(5)
+        );
+        assert!(matches!(
+            journal.next_replayed(),
+            Some(JournalEntry::Tick(_))
+        ));
+        assert!(journal.next_replayed().is_none());
+        assert!(!journal.is_replaying());
+    }
+}
@@ -1,10 +1,16 @@
 use crate::{
-    avm2::{object::SocketObject, Activation, Avm2, EventObject, TObject},
+    avm2::{
+        bytearray::ByteArrayStorage,
+        object::{ByteArrayObject, SocketObject},
+        Activation, Avm2, EventObject, Object, TObject,
+    },
     backend::navigator::NavigatorBackend,
     context::UpdateContext,
+    string::AvmString,
 };
 use gc_arena::Collect;
 use generational_arena::{Arena, Index};
+use quick_xml::{events::Event, Reader};
 use std::{
     cell::RefCell,
     sync::mpsc::{channel, Receiver, Sender},
@@ -12,28 +18,181 @@ use std::{
 };
 
 pub type SocketHandle = Index;
+pub type ServerSocketHandle = Index;
+pub type DatagramSocketHandle = Index;
 
 #[derive(Collect)]
 #[collect(no_drop)]
 struct Socket<'gc> {
     target: SocketObject<'gc>,
     sender: RefCell<Sender<Vec<u8>>>,
+    host: String,
+    port: u16,
+    connected: bool,
 }
 
 impl<'gc> Socket<'gc> {
-    fn new(target: SocketObject<'gc>, sender: Sender<Vec<u8>>) -> Self {
+    fn new(target: SocketObject<'gc>, sender: Sender<Vec<u8>>, host: String, port: u16) -> Self {
         Self {
             target,
             sender: RefCell::new(sender),
+            host,
+            port,
+            connected: false,
         }
     }
 }
 
+/// A socket that listens for connections, used by AIR's `ServerSocket`.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct ServerSocket<'gc> {
+    target: Object<'gc>,
+    /// Never sent to; the backend stops listening when this is dropped.
+    closer: Sender<()>,
+    address: String,
+    port: u16,
+    listening: bool,
+    /// Connections that were accepted before the movie started listening.
+    #[collect(require_static)]
+    pending: Vec<AcceptedConnection>,
+}
+
+/// A UDP socket, used by AIR's `DatagramSocket`.
+#[derive(Collect)]
+#[collect(no_drop)]
+struct DatagramSocket<'gc> {
+    target: Object<'gc>,
+    sender: Sender<Datagram>,
+    address: String,
+    port: u16,
+    /// The address that packets are sent to by default, and the only one they're
+    /// received from, set by `DatagramSocket.connect`.
+    remote: Option<(String, u16)>,
+    receiving: bool,
+    /// Packets that were received before the movie started receiving.
+    #[collect(require_static)]
+    pending: Vec<Datagram>,
+}
+
+/// A connection accepted by a server socket.
+#[derive(Debug)]
+pub struct AcceptedConnection {
+    /// The address of the remote end of the connection.
+    pub host: String,
+    pub port: u16,
+
+    /// Used to give the connection the handle of the socket that was created for it,
+    /// and the receiver of the data that the movie writes to it.
+    ///
+    /// If this is dropped without sending anything, the connection should be closed.
+    pub attach: Sender<(SocketHandle, Receiver<Vec<u8>>)>,
+}
+
+/// A UDP packet, along with the remote address that it's sent to or received from.
+#[derive(Debug)]
+pub struct Datagram {
+    pub data: Vec<u8>,
+    pub address: String,
+    pub port: u16,
+}
+
+/// What a socket is used for, which decides how the backend connects it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SocketKind {
+    /// A raw TCP socket, opened by `Socket` or `XMLSocket`.
+    ///
+    /// These are only allowed to connect if the server's socket policy file allows it.
+    Socket,
+    /// An RTMP connection, opened by `NetConnection`.
+    Rtmp,
+    /// An RTMP connection over TLS, opened by `NetConnection` for `rtmps://` URLs.
+    Rtmps,
+}
+
 #[derive(Debug)]
 pub enum ConnectionState {
     Connected,
     Failed,
     TimedOut,
+    /// The server's socket policy file doesn't allow the connection.
+    SecurityError,
+}
+
+/// The request that Flash Player sends to a server to ask for its socket policy file.
+pub const POLICY_FILE_REQUEST: &[u8] = b"<policy-file-request/>\0";
+
+/// The port that Flash Player first asks for a socket policy file, before trying
+/// the port that is being connected to.
+pub const POLICY_FILE_PORT: u16 = 843;
+
+/// Returns whether a socket policy file allows movies from `domain` to connect to `port`.
+///
+/// Policy files are cross-domain policy files with `allow-access-from` elements that
+/// list the domains and ports that are allowed, such as:
+/// `<allow-access-from domain="*.example.com" to-ports="507,516-523" />`
+pub fn is_allowed_by_policy(policy: &[u8], domain: &str, port: u16) -> bool {
+    // The policy may be terminated by a null byte.
+    let policy = policy.split(|&b| b == 0).next().unwrap_or_default();
+    let mut reader = Reader::from_reader(policy);
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e) | Event::Empty(e)) if e.name().as_ref() == b"allow-access-from" => {
+                let mut domain_matches = false;
+                let mut port_matches = false;
+                for attribute in e.attributes().flatten() {
+                    let Ok(value) = attribute.unescape_value() else {
+                        continue;
+                    };
+                    match attribute.key.as_ref() {
+                        b"domain" => domain_matches = policy_domain_matches(&value, domain),
+                        b"to-ports" => port_matches = policy_ports_match(&value, port),
+                        _ => {}
+                    }
+                }
+                if domain_matches && port_matches {
+                    return true;
+                }
+            }
+            Ok(Event::Eof) | Err(_) => return false,
+            _ => {}
+        }
+        buf.clear();
+    }
+}
+
+/// Whether the `domain` attribute of a policy matches a domain. Wildcards are allowed
+/// in place of the whole domain (`*`) or of its first labels (`*.example.com`).
+fn policy_domain_matches(pattern: &str, domain: &str) -> bool {
+    let pattern = pattern.trim();
+    if pattern == "*" {
+        true
+    } else if let Some(suffix) = pattern.strip_prefix("*.") {
+        domain.eq_ignore_ascii_case(suffix)
+            || domain
+                .to_ascii_lowercase()
+                .ends_with(&format!(".{}", suffix.to_ascii_lowercase()))
+    } else {
+        domain.eq_ignore_ascii_case(pattern)
+    }
+}
+
+/// Whether the `to-ports` attribute of a policy matches a port. It's a comma-separated
+/// list of ports and ranges of ports (`516-523`), or `*` for all ports.
+fn policy_ports_match(pattern: &str, port: u16) -> bool {
+    pattern.split(',').map(str::trim).any(|ports| {
+        if ports == "*" {
+            return true;
+        }
+        match ports.split_once('-') {
+            Some((start, end)) => match (start.trim().parse::<u16>(), end.trim().parse::<u16>()) {
+                (Ok(start), Ok(end)) => (start..=end).contains(&port),
+                _ => false,
+            },
+            None => ports.parse() == Ok(port),
+        }
+    })
 }
 
 #[derive(Debug)]
@@ -41,11 +200,17 @@ pub enum SocketAction {
     Connect(SocketHandle, ConnectionState),
     Data(SocketHandle, Vec<u8>),
     Close(SocketHandle),
+    /// A server socket accepted a connection.
+    Accept(ServerSocketHandle, AcceptedConnection),
+    /// A datagram socket received a packet.
+    Datagram(DatagramSocketHandle, Datagram),
 }
 
 /// Manages the collection of Sockets.
 pub struct Sockets<'gc> {
     sockets: Arena<Socket<'gc>>,
+    server_sockets: Arena<ServerSocket<'gc>>,
+    datagram_sockets: Arena<DatagramSocket<'gc>>,
 
     receiver: Receiver<SocketAction>,
     sender: Sender<SocketAction>,
@@ -56,6 +221,12 @@ unsafe impl<'gc> Collect for Sockets<'gc> {
         for (_, socket) in self.sockets.iter() {
             socket.trace(cc)
         }
+        for (_, socket) in self.server_sockets.iter() {
+            socket.trace(cc)
+        }
+        for (_, socket) in self.datagram_sockets.iter() {
+            socket.trace(cc)
+        }
     }
 }
 
@@ -65,6 +236,8 @@ impl<'gc> Sockets<'gc> {
 
         Self {
             sockets: Arena::new(),
+            server_sockets: Arena::new(),
+            datagram_sockets: Arena::new(),
             receiver,
             sender,
         }
@@ -79,7 +252,7 @@ impl<'gc> Sockets<'gc> {
     ) {
         let (sender, receiver) = channel();
 
-        let socket = Socket::new(target, sender);
+        let socket = Socket::new(target, sender, host.clone(), port);
         let handle = self.sockets.insert(socket);
 
         // NOTE: This call will send SocketAction::Connect to sender with connection status.
@@ -90,6 +263,7 @@ impl<'gc> Sockets<'gc> {
             handle,
             receiver,
             self.sender.clone(),
+            SocketKind::Socket,
         );
 
         if let Some(existing_handle) = target.set_handle(handle) {
@@ -100,7 +274,13 @@ impl<'gc> Sockets<'gc> {
     }
 
     pub fn is_connected(&self, handle: SocketHandle) -> bool {
-        matches!(self.sockets.get(handle), Some(Socket { .. }))
+        matches!(
+            self.sockets.get(handle),
+            Some(Socket {
+                connected: true,
+                ..
+            })
+        )
     }
 
     pub fn send(&mut self, handle: SocketHandle, data: Vec<u8>) {
@@ -115,6 +295,167 @@ impl<'gc> Sockets<'gc> {
         }
     }
 
+    /// Binds a server socket to a local address, returning an error message if the
+    /// backend couldn't bind it.
+    pub fn bind_server(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        target: Object<'gc>,
+        address: String,
+        port: u16,
+    ) -> Result<(), String> {
+        let (closer, receiver) = channel();
+        let handle = self.server_sockets.insert(ServerSocket {
+            target,
+            closer,
+            address: String::new(),
+            port: 0,
+            listening: false,
+            pending: vec![],
+        });
+
+        match backend.bind_server_socket(address, port, handle, receiver, self.sender.clone()) {
+            Ok((address, port)) => {
+                let socket = &mut self.server_sockets[handle];
+                socket.address = address;
+                socket.port = port;
+                Ok(())
+            }
+            Err(e) => {
+                self.server_sockets.remove(handle);
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns the handle of the server socket that `target` has bound, if any.
+    pub fn server_handle(&self, target: Object<'gc>) -> Option<ServerSocketHandle> {
+        self.server_sockets
+            .iter()
+            .find(|(_, socket)| Object::ptr_eq(socket.target, target))
+            .map(|(handle, _)| handle)
+    }
+
+    /// Returns the local address and port that a server socket is bound to.
+    pub fn server_address(&self, handle: ServerSocketHandle) -> Option<(&str, u16)> {
+        self.server_sockets
+            .get(handle)
+            .map(|socket| (socket.address.as_str(), socket.port))
+    }
+
+    pub fn is_listening(&self, handle: ServerSocketHandle) -> bool {
+        matches!(
+            self.server_sockets.get(handle),
+            Some(ServerSocket {
+                listening: true,
+                ..
+            })
+        )
+    }
+
+    /// Starts dispatching the connections that a server socket accepts.
+    pub fn listen(&mut self, handle: ServerSocketHandle) {
+        if let Some(socket) = self.server_sockets.get_mut(handle) {
+            socket.listening = true;
+            // Connections accepted until now are handled on the next update.
+            for connection in socket.pending.drain(..) {
+                let _ = self.sender.send(SocketAction::Accept(handle, connection));
+            }
+        }
+    }
+
+    pub fn close_server(&mut self, handle: ServerSocketHandle) {
+        if let Some(ServerSocket { closer, .. }) = self.server_sockets.remove(handle) {
+            drop(closer); // NOTE: By dropping the closer, the listening task will end.
+        }
+    }
+
+    /// Binds a datagram socket to a local address, returning an error message if the
+    /// backend couldn't bind it.
+    pub fn bind_datagram(
+        &mut self,
+        backend: &mut dyn NavigatorBackend,
+        target: Object<'gc>,
+        address: String,
+        port: u16,
+    ) -> Result<(), String> {
+        let (sender, receiver) = channel();
+        let handle = self.datagram_sockets.insert(DatagramSocket {
+            target,
+            sender,
+            address: String::new(),
+            port: 0,
+            remote: None,
+            receiving: false,
+            pending: vec![],
+        });
+
+        match backend.bind_datagram_socket(address, port, handle, receiver, self.sender.clone()) {
+            Ok((address, port)) => {
+                let socket = &mut self.datagram_sockets[handle];
+                socket.address = address;
+                socket.port = port;
+                Ok(())
+            }
+            Err(e) => {
+                self.datagram_sockets.remove(handle);
+                Err(e)
+            }
+        }
+    }
+
+    /// Returns the handle of the datagram socket that `target` has bound, if any.
+    pub fn datagram_handle(&self, target: Object<'gc>) -> Option<DatagramSocketHandle> {
+        self.datagram_sockets
+            .iter()
+            .find(|(_, socket)| Object::ptr_eq(socket.target, target))
+            .map(|(handle, _)| handle)
+    }
+
+    /// Returns the local address and port that a datagram socket is bound to.
+    pub fn datagram_address(&self, handle: DatagramSocketHandle) -> Option<(&str, u16)> {
+        self.datagram_sockets
+            .get(handle)
+            .map(|socket| (socket.address.as_str(), socket.port))
+    }
+
+    /// Returns the remote address and port that a datagram socket is connected to.
+    pub fn datagram_remote(&self, handle: DatagramSocketHandle) -> Option<(&str, u16)> {
+        self.datagram_sockets
+            .get(handle)
+            .and_then(|socket| socket.remote.as_ref())
+            .map(|(address, port)| (address.as_str(), *port))
+    }
+
+    pub fn connect_datagram(&mut self, handle: DatagramSocketHandle, address: String, port: u16) {
+        if let Some(socket) = self.datagram_sockets.get_mut(handle) {
+            socket.remote = Some((address, port));
+        }
+    }
+
+    pub fn send_datagram(&mut self, handle: DatagramSocketHandle, datagram: Datagram) {
+        if let Some(socket) = self.datagram_sockets.get(handle) {
+            let _ = socket.sender.send(datagram);
+        }
+    }
+
+    /// Starts dispatching the packets that a datagram socket receives.
+    pub fn receive_datagrams(&mut self, handle: DatagramSocketHandle) {
+        if let Some(socket) = self.datagram_sockets.get_mut(handle) {
+            socket.receiving = true;
+            // Packets received until now are handled on the next update.
+            for datagram in socket.pending.drain(..) {
+                let _ = self.sender.send(SocketAction::Datagram(handle, datagram));
+            }
+        }
+    }
+
+    pub fn close_datagram(&mut self, handle: DatagramSocketHandle) {
+        if let Some(DatagramSocket { sender, .. }) = self.datagram_sockets.remove(handle) {
+            drop(sender); // NOTE: By dropping the sender, the receiving task will end.
+        }
+    }
+
     pub fn update_sockets(context: &mut UpdateContext<'_, 'gc>) {
         let mut activation = Activation::from_nothing(context.reborrow());
 
@@ -127,8 +468,11 @@ impl<'gc> Sockets<'gc> {
         for action in actions {
             match action {
                 SocketAction::Connect(handle, ConnectionState::Connected) => {
-                    let target = match activation.context.sockets.sockets.get(handle) {
-                        Some(socket) => socket.target,
+                    let target = match activation.context.sockets.sockets.get_mut(handle) {
+                        Some(socket) => {
+                            socket.connected = true;
+                            socket.target
+                        }
                         // Socket must have been closed before we could send event.
                         None => continue,
                     };
@@ -141,7 +485,7 @@ impl<'gc> Sockets<'gc> {
                     handle,
                     ConnectionState::Failed | ConnectionState::TimedOut,
                 ) => {
-                    let target = match activation.context.sockets.sockets.get(handle) {
+                    let target = match activation.context.sockets.sockets.remove(handle) {
                         Some(socket) => socket.target,
                         // Socket must have been closed before we could send event.
                         None => continue,
@@ -165,6 +509,43 @@ impl<'gc> Sockets<'gc> {
 
                     Avm2::dispatch_event(&mut activation.context, io_error_evt, target.into());
                 }
+                SocketAction::Connect(handle, ConnectionState::SecurityError) => {
+                    let (target, host, port) =
+                        match activation.context.sockets.sockets.remove(handle) {
+                            Some(socket) => (socket.target, socket.host, socket.port),
+                            // Socket must have been closed before we could send event.
+                            None => continue,
+                        };
+
+                    let message = format!(
+                        "Error #2048: Security sandbox violation: {} cannot load data from {}:{}.",
+                        activation.context.swf.url(),
+                        host,
+                        port
+                    );
+                    let message = AvmString::new_utf8(activation.context.gc_context, message);
+                    let security_error_evt = activation
+                        .avm2()
+                        .classes()
+                        .securityerrorevent
+                        .construct(
+                            &mut activation,
+                            &[
+                                "securityError".into(),
+                                false.into(),
+                                false.into(),
+                                message.into(),
+                                2048.into(),
+                            ],
+                        )
+                        .expect("SecurityErrorEvent should be constructed");
+
+                    Avm2::dispatch_event(
+                        &mut activation.context,
+                        security_error_evt,
+                        target.into(),
+                    );
+                }
                 SocketAction::Data(handle, data) => {
                     let target = match activation.context.sockets.sockets.get(handle) {
                         Some(socket) => socket.target,
@@ -195,7 +576,7 @@ impl<'gc> Sockets<'gc> {
                     Avm2::dispatch_event(&mut activation.context, progress_evt, target.into());
                 }
                 SocketAction::Close(handle) => {
-                    let target = match activation.context.sockets.sockets.get(handle) {
+                    let target = match activation.context.sockets.sockets.remove(handle) {
                         Some(socket) => socket.target,
                         // Socket must have been closed before we could send event.
                         None => continue,
@@ -205,7 +586,125 @@ impl<'gc> Sockets<'gc> {
                         EventObject::bare_default_event(&mut activation.context, "close");
                     Avm2::dispatch_event(&mut activation.context, close_evt, target.into());
                 }
+                SocketAction::Accept(handle, connection) => {
+                    let target = match activation.context.sockets.server_sockets.get_mut(handle) {
+                        Some(server) if server.listening => server.target,
+                        Some(server) => {
+                            server.pending.push(connection);
+                            continue;
+                        }
+                        // Server socket must have been closed before we could accept the connection.
+                        None => continue,
+                    };
+
+                    let socket = activation
+                        .avm2()
+                        .classes()
+                        .socket
+                        .construct(&mut activation, &[])
+                        .expect("Socket should be constructed");
+                    let socket_object =
+                        socket.as_socket().expect("Socket should be a SocketObject");
+
+                    let (sender, receiver) = channel();
+                    let mut accepted =
+                        Socket::new(socket_object, sender, connection.host, connection.port);
+                    accepted.connected = true;
+                    let socket_handle = activation.context.sockets.sockets.insert(accepted);
+                    socket_object.set_handle(socket_handle);
+
+                    if connection.attach.send((socket_handle, receiver)).is_err() {
+                        // The connection was closed before we could accept it.
+                        activation.context.sockets.sockets.remove(socket_handle);
+                        continue;
+                    }
+
+                    let connect_evt = activation
+                        .avm2()
+                        .classes()
+                        .serversocketconnectevent
+                        .construct(
+                            &mut activation,
+                            &["connect".into(), false.into(), false.into(), socket.into()],
+                        )
+                        .expect("ServerSocketConnectEvent should be constructed");
+
+                    Avm2::dispatch_event(&mut activation.context, connect_evt, target);
+                }
+                SocketAction::Datagram(handle, datagram) => {
+                    let (target, address, port) =
+                        match activation.context.sockets.datagram_sockets.get_mut(handle) {
+                            Some(socket) => {
+                                // Connected sockets only receive packets from the remote address.
+                                if let Some((address, port)) = &socket.remote {
+                                    if *address != datagram.address || *port != datagram.port {
+                                        continue;
+                                    }
+                                }
+                                if !socket.receiving {
+                                    socket.pending.push(datagram);
+                                    continue;
+                                }
+                                (socket.target, socket.address.clone(), socket.port)
+                            }
+                            // Socket must have been closed before we could send event.
+                            None => continue,
+                        };
+
+                    let data = ByteArrayObject::from_storage(
+                        &mut activation,
+                        ByteArrayStorage::from_vec(datagram.data),
+                    )
+                    .expect("ByteArray should be constructed");
+                    let src_address =
+                        AvmString::new_utf8(activation.context.gc_context, datagram.address);
+                    let dst_address = AvmString::new_utf8(activation.context.gc_context, address);
+
+                    let data_evt = activation
+                        .avm2()
+                        .classes()
+                        .datagramsocketdataevent
+                        .construct(
+                            &mut activation,
+                            &[
+                                "data".into(),
+                                false.into(),
+                                false.into(),
+                                src_address.into(),
+                                datagram.port.into(),
+                                dst_address.into(),
+                                port.into(),
+                                data.into(),
+                            ],
+                        )
+                        .expect("DatagramSocketDataEvent should be constructed");
+
+                    Avm2::dispatch_event(&mut activation.context, data_evt, target);
+                }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &[u8] = br#"<?xml version="1.0"?>
+<cross-domain-policy>
+    <allow-access-from domain="*.example.com" to-ports="507,516-523" />
+</cross-domain-policy>"#;
+
+    #[test]
+    fn policy_allows_listed_ports() {
+        assert!(is_allowed_by_policy(POLICY, "www.example.com", 507));
+        assert!(is_allowed_by_policy(POLICY, "example.com", 520));
+        assert!(!is_allowed_by_policy(POLICY, "www.example.com", 524));
+    }
+
+    #[test]
+    fn policy_denies_other_domains() {
+        assert!(!is_allowed_by_policy(POLICY, "example.org", 507));
+        assert!(!is_allowed_by_policy(POLICY, "notexample.com", 507));
+    }
+}
@@ -5,27 +5,31 @@ use crate::avm1::{
     ExecutionReason as Avm1ExecutionReason, FlvValueAvm1Ext, ScriptObject as Avm1ScriptObject,
     TObject as Avm1TObject, Value as Avm1Value,
 };
-use crate::avm2::object::TObject as Avm2TObject;
+use crate::avm2::object::{TObject as Avm2TObject, TextureObject};
 use crate::avm2::{
     Activation as Avm2Activation, Avm2, Error as Avm2Error, EventObject as Avm2EventObject,
     FlvValueAvm2Ext, Object as Avm2Object,
 };
+use crate::backend::audio::decoders::FlvAudioDecoder;
+use crate::backend::audio::SoundInstanceHandle;
 use crate::backend::navigator::Request;
 use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject, Video};
 use crate::loader::Error;
+use crate::mp4;
 use crate::string::AvmString;
 use crate::vminterface::AvmObject;
 use flv_rs::{
-    AudioData as FlvAudioData, Error as FlvError, FlvReader, Header as FlvHeader,
-    ScriptData as FlvScriptData, Tag as FlvTag, TagData as FlvTagData, Value as FlvValue,
-    VideoData as FlvVideoData, VideoPacket as FlvVideoPacket,
+    Error as FlvError, FlvReader, Header as FlvHeader, ScriptData as FlvScriptData, Tag as FlvTag,
+    TagData as FlvTagData, Value as FlvValue, VideoData as FlvVideoData,
+    VideoPacket as FlvVideoPacket,
 };
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_render::bitmap::BitmapInfo;
 use ruffle_video::frame::EncodedFrame;
 use ruffle_video::VideoStreamHandle;
 use std::cmp::max;
-use std::io::Seek;
+use std::io::{Seek, SeekFrom};
 use std::sync::{Arc, Mutex};
 use swf::{VideoCodec, VideoDeblocking};
 
@@ -109,8 +113,8 @@ impl<'gc> StreamManager<'gc> {
 ///
 ///  * `LoadManager` fills individual `NetStream` buffers with data (or, in the
 ///    future, empties them out for media upload)
-///  * `StreamManager` processes media data in the `NetStream` buffer (in the
-///    future, sending it to the audio backend or `SoundManager`)
+///  * `StreamManager` processes media data in the `NetStream` buffer, sending
+///    decoded audio to the audio backend
 ///  * `Video` display objects linked to this `NetStream` display the latest
 ///    decoded frame.
 ///
@@ -146,7 +150,7 @@ pub enum NetStreamType {
     },
 }
 
-#[derive(Clone, Debug, Collect)]
+#[derive(Debug, Collect)]
 #[collect(no_drop)]
 pub struct NetStreamData<'gc> {
     /// All data currently loaded in the stream.
@@ -189,6 +193,83 @@ pub struct NetStreamData<'gc> {
 
     /// The AVM2 client object, which corresponds to `NetStream.client`.
     avm2_client: Option<Avm2Object<'gc>>,
+
+    /// Any Stage3D `VideoTexture`s attached to this stream.
+    ///
+    /// Each decoded frame is copied into these textures, after which they
+    /// receive a `textureReady` event.
+    video_textures: Vec<TextureObject<'gc>>,
+
+    /// Any `Video`s displaying this stream.
+    ///
+    /// These are invalidated whenever a new frame is decoded, so that they
+    /// get redrawn.
+    videos: Vec<Video<'gc>>,
+
+    /// The AVM2 `NetConnection` this stream was created with.
+    ///
+    /// If it's connected to an RTMP server, the stream is played from there
+    /// instead of being downloaded.
+    net_connection: Option<Avm2Object<'gc>>,
+
+    /// Whether more data is expected to be added to the buffer, by an RTMP
+    /// server or by `appendBytes`.
+    ///
+    /// Running out of data doesn't end the stream while this is set.
+    expects_more_data: bool,
+
+    /// Whether the stream time should jump to the timestamp of the next tag.
+    ///
+    /// This is set when data is replaced by `appendBytes` after a reset, which
+    /// can continue from any point of the media.
+    rebase_time: bool,
+
+    /// The dynamic sound that the decoded audio of the stream is played
+    /// through, started when the first audio tag is reached.
+    #[collect(require_static)]
+    sound_instance: Option<SoundInstanceHandle>,
+
+    /// The decoder for the audio of the stream.
+    #[collect(require_static)]
+    audio_decoder: FlvAudioDecoder,
+}
+
+/// The number of 44.1kHz sample frames of stream audio that can be queued.
+const STREAM_SOUND_CAPACITY: usize = 44100;
+
+/// The header of the FLV that media from RTMP servers and HDS fragments is
+/// written into, which is followed by the size of the (nonexistent) previous tag.
+const LIVE_FLV_HEADER: &[u8] = &[
+    0x46, 0x4C, 0x56, 0x01, 0x05, 0x00, 0x00, 0x00, 0x09, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// The size of the (nonexistent) tag before the first one, which is placed
+/// before tags that are appended without a header.
+const NO_PREVIOUS_TAG_SIZE: &[u8] = &[0x00, 0x00, 0x00, 0x00];
+
+/// Encodes an FLV tag, followed by its size, for appending to a live stream.
+pub fn flv_tag(tag_type: u8, timestamp: u32, data: &[u8]) -> Vec<u8> {
+    let mut tag = Vec::with_capacity(11 + data.len() + 4);
+    tag.push(tag_type);
+    tag.extend_from_slice(&(data.len() as u32).to_be_bytes()[1..]);
+    tag.extend_from_slice(&timestamp.to_be_bytes()[1..]);
+    tag.push((timestamp >> 24) as u8);
+    tag.extend_from_slice(&[0, 0, 0]);
+    tag.extend_from_slice(data);
+    tag.extend_from_slice(&(11 + data.len() as u32).to_be_bytes());
+    tag
+}
+
+/// An action passed to `NetStream.appendBytesAction`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AppendBytesAction {
+    /// No more data will be appended, so the stream ends once it's all played.
+    EndSequence,
+    /// The buffer is discarded, and the next data starts with a new FLV header.
+    ResetBegin,
+    /// The buffer is discarded, and the next data starts with a tag of the
+    /// current FLV.
+    ResetSeek,
 }
 
 impl<'gc> NetStream<'gc> {
@@ -204,10 +285,84 @@ impl<'gc> NetStream<'gc> {
                 last_decoded_bitmap: None,
                 avm_object,
                 avm2_client: None,
+                video_textures: Vec::new(),
+                videos: Vec::new(),
+                net_connection: None,
+                expects_more_data: false,
+                rebase_time: false,
+                sound_instance: None,
+                audio_decoder: FlvAudioDecoder::default(),
             },
         ))
     }
 
+    pub fn set_net_connection(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        net_connection: Option<Avm2Object<'gc>>,
+    ) {
+        self.0.write(gc_context).net_connection = net_connection;
+    }
+
+    pub fn net_connection(self) -> Option<Avm2Object<'gc>> {
+        self.0.read().net_connection
+    }
+
+    /// The AVM2 `NetStream` object of this stream, if it was created by AVM2.
+    pub fn avm2_object(self) -> Option<Avm2Object<'gc>> {
+        match self.0.read().avm_object {
+            Some(AvmObject::Avm2(object)) => Some(object),
+            _ => None,
+        }
+    }
+
+    /// Prepares the stream to receive media from an RTMP server or HDS fragments,
+    /// discarding anything that was buffered before.
+    pub fn start_live(self, gc_context: MutationContext<'gc, '_>) {
+        self.reset_buffer(gc_context, LIVE_FLV_HEADER, true);
+    }
+
+    /// Appends data to the buffer, such as FLV tags received from an RTMP
+    /// server or bytes passed to `appendBytes`.
+    pub fn append_data(self, data: &[u8]) {
+        self.0.read().buffer.lock().unwrap().extend_from_slice(data);
+    }
+
+    /// Controls how data appended by `appendBytes` is played.
+    pub fn append_bytes_action(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        action: AppendBytesAction,
+    ) {
+        match action {
+            AppendBytesAction::EndSequence => {
+                self.0.write(gc_context).expects_more_data = false;
+            }
+            AppendBytesAction::ResetBegin => self.reset_buffer(gc_context, &[], true),
+            AppendBytesAction::ResetSeek => {
+                self.reset_buffer(gc_context, NO_PREVIOUS_TAG_SIZE, false)
+            }
+        }
+    }
+
+    /// Replaces the buffer with `data`, which the stream continues playing from.
+    ///
+    /// If `new_header` is set, the data is expected to start with a new header.
+    /// Otherwise, it continues the current FLV.
+    fn reset_buffer(self, gc_context: MutationContext<'gc, '_>, data: &[u8], new_header: bool) {
+        let mut write = self.0.write(gc_context);
+        *write.buffer.lock().unwrap() = data.to_vec();
+        write.offset = 0;
+        write.preload_offset = 0;
+        if new_header {
+            write.stream_type = None;
+            write.audio_decoder = FlvAudioDecoder::default();
+        }
+        write.stream_time = 0.0;
+        write.expects_more_data = true;
+        write.rebase_time = true;
+    }
+
     pub fn set_client(self, gc_context: MutationContext<'gc, '_>, new_client: Avm2Object<'gc>) {
         self.0.write(gc_context).avm2_client = Some(new_client);
     }
@@ -220,13 +375,64 @@ impl<'gc> NetStream<'gc> {
         self.0.write(gc_context).avm_object = Some(avm_object);
     }
 
-    pub fn load_buffer(self, context: &mut UpdateContext<'_, 'gc>, data: &mut Vec<u8>) {
+    pub fn attach_video_texture(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        texture: TextureObject<'gc>,
+    ) {
+        let mut write = self.0.write(gc_context);
+        if !write
+            .video_textures
+            .iter()
+            .any(|t| Avm2Object::ptr_eq(*t, texture))
+        {
+            write.video_textures.push(texture);
+        }
+    }
+
+    pub fn attach_video(self, gc_context: MutationContext<'gc, '_>, video: Video<'gc>) {
+        let mut write = self.0.write(gc_context);
+        if !write
+            .videos
+            .iter()
+            .any(|v| DisplayObject::ptr_eq((*v).into(), video.into()))
+        {
+            write.videos.push(video);
+        }
+    }
+
+    pub fn detach_video(self, gc_context: MutationContext<'gc, '_>, video: Video<'gc>) {
         self.0
-            .write(context.gc_context)
-            .buffer
-            .lock()
-            .unwrap()
-            .append(data);
+            .write(gc_context)
+            .videos
+            .retain(|v| !DisplayObject::ptr_eq((*v).into(), video.into()));
+    }
+
+    pub fn detach_video_texture(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        texture: TextureObject<'gc>,
+    ) {
+        self.0
+            .write(gc_context)
+            .video_textures
+            .retain(|t| !Avm2Object::ptr_eq(*t, texture));
+    }
+
+    pub fn load_buffer(self, context: &mut UpdateContext<'_, 'gc>, data: &mut Vec<u8>) {
+        {
+            let write = self.0.write(context.gc_context);
+            let mut buffer = write.buffer.lock().unwrap();
+            buffer.append(data);
+
+            // MP4 files are played as the equivalent FLV.
+            if mp4::is_mp4(&buffer) {
+                match mp4::remux_to_flv(&buffer) {
+                    Ok(flv) => *buffer = flv,
+                    Err(e) => tracing::error!("MP4 parsing failed: {}", e),
+                }
+            }
+        }
 
         // NOTE: The onMetaData event triggers before this event in Flash due to its streaming behavior.
         self.trigger_status_event(
@@ -250,9 +456,20 @@ impl<'gc> NetStream<'gc> {
     /// Start playing media from this NetStream.
     ///
     /// If `name` is specified, this will also trigger streaming download of
-    /// the given resource. Otherwise, the stream will play whatever data is
-    /// available in the buffer.
+    /// the given resource. Otherwise, the stream enters Data Generation Mode,
+    /// where it plays whatever data is passed to `appendBytes`.
     pub fn play(self, context: &mut UpdateContext<'_, 'gc>, name: Option<AvmString<'gc>>) {
+        let rtmp_connection = self
+            .net_connection()
+            .and_then(|net_connection| context.net_connections.handle(net_connection));
+        if let Some(handle) = rtmp_connection {
+            // The server reports when the stream starts playing.
+            if let Some(name) = name {
+                context.net_connections.play(handle, self, name.to_string());
+            }
+            return;
+        }
+
         if let Some(name) = name {
             let request = Request::get(name.to_string());
             let future = context
@@ -260,6 +477,8 @@ impl<'gc> NetStream<'gc> {
                 .load_netstream(context.player.clone(), self, request);
 
             context.navigator.spawn_future(future);
+        } else {
+            self.reset_buffer(context.gc_context, &[], true);
         }
 
         StreamManager::ensure_playing(context, self);
@@ -332,20 +551,22 @@ impl<'gc> NetStream<'gc> {
             }
         }
 
-        let end_time = write.stream_time + dt;
+        let mut end_time = write.stream_time + dt;
         let mut end_of_video = false;
         let mut error = false;
+        let mut decoded_frame = false;
 
         //At this point we should know our stream type.
         if matches!(write.stream_type, Some(NetStreamType::Flv { .. })) {
             let mut reader = FlvReader::from_parts(&buffer, write.offset);
 
             loop {
+                let tag_start = reader.stream_position().expect("valid position");
                 let tag = FlvTag::parse(&mut reader);
                 if let Err(e) = tag {
                     if matches!(e, FlvError::EndOfData) {
                         //TODO: Check expected total length for streaming / progressive download
-                        end_of_video = true;
+                        end_of_video = !write.expects_more_data;
                     } else {
                         //Corrupt tag or out of data
                         tracing::error!("FLV tag parsing failed: {}", e);
@@ -356,10 +577,18 @@ impl<'gc> NetStream<'gc> {
                 }
 
                 let tag = tag.expect("valid tag");
+                if write.rebase_time {
+                    write.stream_time = tag.timestamp as f64;
+                    end_time = write.stream_time + dt;
+                    write.rebase_time = false;
+                }
+
                 if tag.timestamp as f64 >= end_time {
-                    //All tags processed
-                    if let Err(e) = FlvTag::skip_back(&mut reader) {
-                        tracing::error!("FLV skip back failed: {}", e);
+                    //All tags processed. The next tag may not be followed by
+                    //its size yet, so return to its start directly rather
+                    //than skipping back from its end.
+                    if let Err(e) = reader.seek(SeekFrom::Start(tag_start)) {
+                        tracing::error!("FLV seek failed: {}", e);
                     }
 
                     break;
@@ -370,8 +599,15 @@ impl<'gc> NetStream<'gc> {
                     >= write.preload_offset;
 
                 match tag.data {
-                    FlvTagData::Audio(FlvAudioData { .. }) => {
-                        tracing::warn!("Stub: Stream audio processing");
+                    FlvTagData::Audio(audio) => {
+                        let samples = write.audio_decoder.decode(audio);
+                        if !samples.is_empty() && write.sound_instance.is_none() {
+                            write.sound_instance =
+                                context.audio.start_dynamic_sound(STREAM_SOUND_CAPACITY);
+                        }
+                        if let Some(instance) = write.sound_instance {
+                            context.audio.append_dynamic_sound(instance, &samples);
+                        }
                     }
                     FlvTagData::Video(FlvVideoData { codec_id, data, .. }) => {
                         let (video_handle, frame_id) = match write.stream_type {
@@ -381,6 +617,8 @@ impl<'gc> NetStream<'gc> {
                             _ => unreachable!(),
                         };
                         let codec = VideoCodec::from_u8(codec_id as u8);
+                        let is_avc_sequence_header =
+                            matches!(data, FlvVideoPacket::AvcSequenceHeader(_));
 
                         match (video_handle, codec, data) {
                             (maybe_video_handle, Some(codec), FlvVideoPacket::Data(mut data))
@@ -392,16 +630,30 @@ impl<'gc> NetStream<'gc> {
                                     vadjust: _,
                                     mut data,
                                 },
+                            )
+                            | (
+                                maybe_video_handle,
+                                Some(codec),
+                                FlvVideoPacket::AvcSequenceHeader(mut data),
+                            )
+                            | (
+                                maybe_video_handle,
+                                Some(codec),
+                                FlvVideoPacket::AvcNalu {
+                                    composition_time_offset: _,
+                                    mut data,
+                                },
                             ) => {
                                 //Some movies don't actually have metadata, so let's register a
                                 //dummy stream just in case. All the actual data in the registration
-                                //is lies, of course.
+                                //is lies, of course. A size of zero lets decoders that crop to the
+                                //stream size (like VP6) use the encoded size of the frames instead.
                                 let video_handle = match maybe_video_handle {
                                     Some(stream) => stream,
                                     None => {
                                         match context.video.register_video_stream(
                                             1,
-                                            (8, 8),
+                                            (0, 0),
                                             codec,
                                             VideoDeblocking::UseVideoPacketValue,
                                         ) {
@@ -435,10 +687,19 @@ impl<'gc> NetStream<'gc> {
                                     let offset = data.as_ptr() as usize - buffer.as_ptr() as usize;
                                     let len = data.len();
                                     data = &buffer[offset - 1..offset + len];
+                                } else if codec == VideoCodec::H264 {
+                                    // The H.264 decoder needs the AVC packet
+                                    // type and composition time that precede
+                                    // the data.
+                                    let offset = data.as_ptr() as usize - buffer.as_ptr() as usize;
+                                    let len = data.len();
+                                    data = &buffer[offset - 4..offset + len];
                                 }
 
                                 // NOTE: Currently, no implementation of the decoder backend actually requires
-                                if tag_needs_preloading {
+                                // The AVC decoder configuration is always passed on, in case it changed since
+                                // it was preloaded.
+                                if tag_needs_preloading || is_avc_sequence_header {
                                     let encoded_frame = EncodedFrame {
                                         codec,
                                         data, //TODO: ScreenVideo's decoder wants the FLV header bytes
@@ -457,42 +718,40 @@ impl<'gc> NetStream<'gc> {
                                     }
                                 }
 
-                                let encoded_frame = EncodedFrame {
-                                    codec,
-                                    data, //TODO: ScreenVideo's decoder wants the FLV header bytes
-                                    frame_id,
-                                };
+                                // The AVC decoder configuration doesn't contain a picture.
+                                if !is_avc_sequence_header {
+                                    let encoded_frame = EncodedFrame {
+                                        codec,
+                                        data, //TODO: ScreenVideo's decoder wants the FLV header bytes
+                                        frame_id,
+                                    };
 
-                                match context.video.decode_video_stream_frame(
-                                    video_handle,
-                                    encoded_frame,
-                                    context.renderer,
-                                ) {
-                                    Ok(bitmap_info) => {
-                                        let (_, position) = reader.into_parts();
-                                        write.last_decoded_bitmap = Some(bitmap_info);
-                                        reader = FlvReader::from_parts(&buffer, position);
-                                    }
-                                    Err(e) => {
-                                        tracing::error!(
-                                            "Decoding video frame {} failed: {}",
-                                            frame_id,
-                                            e
-                                        );
+                                    match context.video.decode_video_stream_frame(
+                                        video_handle,
+                                        encoded_frame,
+                                        context.renderer,
+                                    ) {
+                                        Ok(bitmap_info) => {
+                                            let (_, position) = reader.into_parts();
+                                            write.last_decoded_bitmap = Some(bitmap_info);
+                                            decoded_frame = true;
+                                            reader = FlvReader::from_parts(&buffer, position);
+                                        }
+                                        Err(e) => {
+                                            tracing::error!(
+                                                "Decoding video frame {} failed: {}",
+                                                frame_id,
+                                                e
+                                            );
+                                        }
                                     }
                                 }
                             }
                             (_, _, FlvVideoPacket::CommandFrame(_command)) => {
                                 tracing::warn!("Stub: FLV command frame processing")
                             }
-                            (_, _, FlvVideoPacket::AvcSequenceHeader(_data)) => {
-                                tracing::warn!("Stub: FLV AVC/H.264 Sequence Header processing")
-                            }
-                            (_, _, FlvVideoPacket::AvcNalu { .. }) => {
-                                tracing::warn!("Stub: FLV AVC/H.264 NALU processing")
-                            }
                             (_, _, FlvVideoPacket::AvcEndOfSequence) => {
-                                tracing::warn!("Stub: FLV AVC/H.264 End of Sequence processing")
+                                // The last picture stays on screen.
                             }
                             (_, None, _) => {
                                 tracing::error!(
@@ -625,6 +884,24 @@ impl<'gc> NetStream<'gc> {
         write.stream_time = end_time;
         drop(write);
 
+        if decoded_frame {
+            let videos = self.0.read().videos.clone();
+            if !videos.is_empty() {
+                *context.needs_render = true;
+            }
+            for video in videos {
+                video.invalidate_cached_bitmap(context.gc_context);
+            }
+            self.update_video_textures(context);
+        }
+
+        if end_of_video || error {
+            let sound_instance = self.0.write(context.gc_context).sound_instance.take();
+            if let Some(instance) = sound_instance {
+                context.audio.end_dynamic_sound(instance);
+            }
+        }
+
         if end_of_video {
             self.trigger_status_event(
                 context,
@@ -651,6 +928,26 @@ impl<'gc> NetStream<'gc> {
         self.0.read().last_decoded_bitmap.clone()
     }
 
+    /// Copy the last decoded frame into all attached `VideoTexture`s.
+    fn update_video_textures(self, context: &mut UpdateContext<'_, 'gc>) {
+        let read = self.0.read();
+        let Some(frame) = read.last_decoded_bitmap.clone() else {
+            return;
+        };
+        let textures = read.video_textures.clone();
+        drop(read);
+
+        for texture in textures {
+            if let Err(e) = texture.copy_video_frame(&frame) {
+                tracing::error!("Failed to copy video frame into VideoTexture: {}", e);
+                continue;
+            }
+
+            let texture_ready = Avm2EventObject::bare_default_event(context, "textureReady");
+            Avm2::dispatch_event(context, texture_ready, texture.into());
+        }
+    }
+
     /// Trigger a status event on the stream.
     pub fn trigger_status_event(
         self,
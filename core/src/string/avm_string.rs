@@ -1,8 +1,10 @@
 use std::borrow::Cow;
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 
+use fnv::FnvHasher;
 use gc_arena::{Collect, Gc, MutationContext};
-use ruffle_wstr::{wstr_impl_traits, WStr, WString};
+use ruffle_wstr::{utils, wstr_impl_traits, WStr, WString};
 
 use crate::string::{AvmAtom, AvmStringRepr};
 
@@ -70,6 +72,16 @@ impl<'gc> AvmString<'gc> {
         }
     }
 
+    /// Hashes this string as if it was lowercase, so that strings differing only in case
+    /// have the same hash. This is cached for owned strings, so that the interned names
+    /// used by AVM1 code are only hashed once.
+    pub fn hash_ignore_case(&self) -> u64 {
+        match &self.source {
+            Source::Owned(s) => s.hash_ignore_case(swf_hash_string_ignore_case),
+            Source::Static(s) => swf_hash_string_ignore_case(s),
+        }
+    }
+
     pub fn concat(
         gc_context: MutationContext<'gc, '_>,
         left: AvmString<'gc>,
@@ -174,3 +186,12 @@ impl<'gc> PartialEq<AvmAtom<'gc>> for AvmString<'gc> {
 impl<'gc> Eq for AvmString<'gc> {}
 
 wstr_impl_traits!(impl['gc] manual_eq for AvmString<'gc>);
+
+/// Hashes a string as if it was lowercase, using the SWF lowercasing rules.
+fn swf_hash_string_ignore_case(s: &WStr) -> u64 {
+    let mut state = FnvHasher::default();
+    s.iter()
+        .for_each(|c| utils::swf_to_lowercase(c).hash(&mut state));
+    state.write_u8(0xff);
+    state.finish()
+}
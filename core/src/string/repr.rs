@@ -15,6 +15,8 @@ pub struct AvmStringRepr {
     meta: wptr::WStrMetadata,
     // We abuse the 'is_wide' bit for interning.
     capacity: Cell<wptr::WStrMetadata>,
+    // The case-insensitive hash of the string, computed on first use.
+    hash_ignore_case: Cell<Option<u64>>,
 }
 
 impl AvmStringRepr {
@@ -25,6 +27,7 @@ impl AvmStringRepr {
             ptr,
             meta,
             capacity,
+            hash_ignore_case: Cell::new(None),
         }
     }
 
@@ -43,6 +46,18 @@ impl AvmStringRepr {
         let new_cap = wptr::WStrMetadata::new32(cap.len32(), true);
         self.capacity.set(new_cap);
     }
+
+    /// Returns the case-insensitive hash of this string, using `compute` on first call.
+    ///
+    /// The string is immutable, so the hash is only ever computed once.
+    pub fn hash_ignore_case(&self, compute: impl FnOnce(&WStr) -> u64) -> u64 {
+        if let Some(hash) = self.hash_ignore_case.get() {
+            return hash;
+        }
+        let hash = compute(self.as_wstr());
+        self.hash_ignore_case.set(Some(hash));
+        hash
+    }
 }
 
 impl Drop for AvmStringRepr {
@@ -1,6 +1,7 @@
-use fnv::FnvHashSet;
+use fnv::{FnvHashMap, FnvHashSet};
+use serde::Serialize;
 use std::borrow::Cow;
-use std::collections::hash_set::Iter;
+use std::collections::hash_map::Keys;
 use std::fmt::{Debug, Display, Formatter};
 
 #[cfg(feature = "known_stubs")]
@@ -113,7 +114,8 @@ impl Display for Stub {
 
 #[derive(Debug, Default)]
 pub struct StubCollection {
-    inner: FnvHashSet<Stub>,
+    /// Every stub that has been encountered, along with how many times.
+    inner: FnvHashMap<Stub, u32>,
 }
 
 impl StubCollection {
@@ -122,13 +124,55 @@ impl StubCollection {
     }
 
     pub fn encounter(&mut self, stub: &Stub) {
-        if !self.inner.contains(stub) {
+        if let Some(count) = self.inner.get_mut(stub) {
+            *count += 1;
+        } else {
             tracing::warn!("Encountered stub: {stub}");
-            self.inner.insert(stub.clone());
+            self.inner.insert(stub.clone(), 1);
         }
     }
 
-    pub fn iter(&self) -> Iter<Stub> {
-        self.inner.iter()
+    pub fn iter(&self) -> Keys<Stub, u32> {
+        self.inner.keys()
+    }
+
+    /// Each stub that has been encountered, along with how many times.
+    pub fn counts(&self) -> impl Iterator<Item = (&Stub, u32)> {
+        self.inner.iter().map(|(stub, count)| (stub, *count))
+    }
+}
+
+/// A summary of the parts of a movie that Ruffle doesn't fully support yet, for showing to users.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompatibilityReport {
+    pub swf_version: u8,
+    pub is_action_script_3: bool,
+
+    /// The stubs that the movie has run into, most often hit first.
+    pub stubs: Vec<StubHit>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StubHit {
+    pub name: String,
+    pub count: u32,
+}
+
+impl CompatibilityReport {
+    pub fn new(swf_version: u8, is_action_script_3: bool, stubs: &StubCollection) -> Self {
+        let mut stubs: Vec<_> = stubs
+            .counts()
+            .map(|(stub, count)| StubHit {
+                name: stub.to_string(),
+                count,
+            })
+            .collect();
+        stubs.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+        Self {
+            swf_version,
+            is_action_script_3,
+            stubs,
+        }
     }
 }
@@ -0,0 +1,364 @@
+//! The touch points on a touchscreen, and the gestures that are recognized from them.
+
+use crate::events::TouchPhase;
+use instant::Instant;
+use std::f64::consts::PI;
+use std::time::Duration;
+
+/// How far a point can move, in stage pixels, and still count as a tap.
+const TAP_DISTANCE: f64 = 10.0;
+
+/// How long a tap can last.
+const TAP_DURATION: Duration = Duration::from_millis(500);
+
+/// How far a point has to move, in stage pixels, to count as a swipe.
+const SWIPE_DISTANCE: f64 = 40.0;
+
+/// How long a swipe can last.
+const SWIPE_DURATION: Duration = Duration::from_millis(500);
+
+/// The progress of a gesture, as reported by AS3's `GesturePhase`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GesturePhase {
+    Begin,
+    Update,
+    End,
+
+    /// The gesture happened all at once, like a swipe.
+    All,
+}
+
+impl GesturePhase {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GesturePhase::Begin => "begin",
+            GesturePhase::Update => "update",
+            GesturePhase::End => "end",
+            GesturePhase::All => "all",
+        }
+    }
+}
+
+/// A gesture recognized from the touch points.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Gesture {
+    /// Two fingers moved, which zooms, rotates and pans.
+    ///
+    /// The changes are relative to the previous `Transform` of the gesture.
+    Transform {
+        phase: GesturePhase,
+        x: f64,
+        y: f64,
+        scale: f64,
+        /// In degrees, clockwise.
+        rotation: f64,
+        offset_x: f64,
+        offset_y: f64,
+    },
+
+    /// A finger moved quickly in a direction. The offsets are -1, 0 or 1.
+    Swipe {
+        x: f64,
+        y: f64,
+        offset_x: f64,
+        offset_y: f64,
+    },
+
+    /// Two fingers touched and left without moving.
+    TwoFingerTap { x: f64, y: f64 },
+}
+
+/// What a touch point did, as reported by `TouchTracker::handle`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TouchResult {
+    /// Whether this is the point that the frontend reports as the mouse.
+    pub is_primary: bool,
+
+    /// Whether the point left without moving, which AS3 reports as `touchTap`.
+    pub is_tap: bool,
+
+    pub gestures: Vec<Gesture>,
+}
+
+struct TrackedPoint {
+    id: i32,
+    start: (f64, f64),
+    position: (f64, f64),
+    start_time: Instant,
+
+    /// Whether no other point touched while this one did, as swipes only use one finger.
+    alone: bool,
+}
+
+impl TrackedPoint {
+    fn distance_moved(&self) -> f64 {
+        distance(self.start, self.position)
+    }
+}
+
+/// The state of a two finger transform.
+struct Transform {
+    started: bool,
+    center: (f64, f64),
+    distance: f64,
+    angle: f64,
+}
+
+impl Transform {
+    fn new(a: (f64, f64), b: (f64, f64)) -> Self {
+        Self {
+            started: false,
+            center: center(a, b),
+            distance: distance(a, b),
+            angle: angle(a, b),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TouchTracker {
+    /// The points that are touching, in the order that they touched.
+    points: Vec<TrackedPoint>,
+
+    /// The point that's reported as the mouse, which is the one that touched while
+    /// no others did.
+    primary: Option<i32>,
+
+    /// The transform of the first two points.
+    transform: Option<Transform>,
+
+    /// Where and when two points touched, if they may become a two finger tap.
+    two_finger_tap: Option<((f64, f64), Instant)>,
+}
+
+impl TouchTracker {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Handles a touch point, with its position in stage pixels.
+    pub fn handle(&mut self, phase: TouchPhase, id: i32, x: f64, y: f64) -> TouchResult {
+        let position = (x, y);
+        let now = Instant::now();
+        let mut gestures = vec![];
+        let mut is_tap = false;
+
+        if phase == TouchPhase::Begin {
+            if self.points.is_empty() {
+                self.primary = Some(id);
+            }
+            let alone = self.points.is_empty();
+            for point in &mut self.points {
+                point.alone = false;
+            }
+            self.points.push(TrackedPoint {
+                id,
+                start: position,
+                position,
+                start_time: now,
+                alone,
+            });
+            match self.points.len() {
+                2 => {
+                    let (a, b) = (self.points[0].position, self.points[1].position);
+                    self.transform = Some(Transform::new(a, b));
+                    self.two_finger_tap = Some((center(a, b), now));
+                }
+                _ => self.two_finger_tap = None,
+            }
+        }
+
+        let is_primary = self.primary == Some(id);
+        let Some(index) = self.points.iter().position(|point| point.id == id) else {
+            return TouchResult {
+                is_primary,
+                is_tap,
+                gestures,
+            };
+        };
+
+        if phase == TouchPhase::Move {
+            let point = &mut self.points[index];
+            point.position = position;
+            if point.distance_moved() > TAP_DISTANCE {
+                self.two_finger_tap = None;
+            }
+
+            if let (Some(transform), true) = (&mut self.transform, index < 2) {
+                let (a, b) = (self.points[0].position, self.points[1].position);
+                let new = Transform::new(a, b);
+                let scale = if transform.distance > 0.0 {
+                    new.distance / transform.distance
+                } else {
+                    1.0
+                };
+                let mut rotation = (new.angle - transform.angle).to_degrees();
+                if rotation > 180.0 {
+                    rotation -= 360.0;
+                } else if rotation <= -180.0 {
+                    rotation += 360.0;
+                }
+                gestures.push(Gesture::Transform {
+                    phase: if transform.started {
+                        GesturePhase::Update
+                    } else {
+                        GesturePhase::Begin
+                    },
+                    x: new.center.0,
+                    y: new.center.1,
+                    scale,
+                    rotation,
+                    offset_x: new.center.0 - transform.center.0,
+                    offset_y: new.center.1 - transform.center.1,
+                });
+                *transform = Transform {
+                    started: true,
+                    ..new
+                };
+            }
+        }
+
+        if phase == TouchPhase::End {
+            let point = self.points.remove(index);
+            let elapsed = now.duration_since(point.start_time);
+            is_tap = point.distance_moved() <= TAP_DISTANCE && elapsed <= TAP_DURATION;
+
+            if index < 2 {
+                if let Some(transform) = self.transform.take() {
+                    if transform.started {
+                        gestures.push(Gesture::Transform {
+                            phase: GesturePhase::End,
+                            x: transform.center.0,
+                            y: transform.center.1,
+                            scale: 1.0,
+                            rotation: 0.0,
+                            offset_x: 0.0,
+                            offset_y: 0.0,
+                        });
+                    }
+                }
+            }
+
+            if self.points.is_empty() {
+                if let Some(((x, y), time)) = self.two_finger_tap.take() {
+                    if now.duration_since(time) <= TAP_DURATION {
+                        gestures.push(Gesture::TwoFingerTap { x, y });
+                    }
+                }
+            }
+
+            let (dx, dy) = (
+                point.position.0 - point.start.0,
+                point.position.1 - point.start.1,
+            );
+            if point.alone && elapsed <= SWIPE_DURATION && point.distance_moved() >= SWIPE_DISTANCE
+            {
+                let (offset_x, offset_y) = if dx.abs() >= dy.abs() {
+                    (dx.signum(), 0.0)
+                } else {
+                    (0.0, dy.signum())
+                };
+                gestures.push(Gesture::Swipe {
+                    x: point.position.0,
+                    y: point.position.1,
+                    offset_x,
+                    offset_y,
+                });
+            }
+
+            if is_primary {
+                self.primary = None;
+            }
+        }
+
+        TouchResult {
+            is_primary,
+            is_tap,
+            gestures,
+        }
+    }
+}
+
+fn distance(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.0 - a.0).hypot(b.1 - a.1)
+}
+
+fn center(a: (f64, f64), b: (f64, f64)) -> (f64, f64) {
+    ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0)
+}
+
+fn angle(a: (f64, f64), b: (f64, f64)) -> f64 {
+    (b.1 - a.1).atan2(b.0 - a.0).rem_euclid(2.0 * PI)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swipe() {
+        let mut tracker = TouchTracker::new();
+        assert!(
+            tracker
+                .handle(TouchPhase::Begin, 1, 100.0, 100.0)
+                .is_primary
+        );
+        tracker.handle(TouchPhase::Move, 1, 150.0, 110.0);
+        let result = tracker.handle(TouchPhase::End, 1, 150.0, 110.0);
+        assert!(!result.is_tap);
+        assert_eq!(
+            result.gestures,
+            vec![Gesture::Swipe {
+                x: 150.0,
+                y: 110.0,
+                offset_x: 1.0,
+                offset_y: 0.0
+            }]
+        );
+    }
+
+    #[test]
+    fn zoom_and_rotate() {
+        let mut tracker = TouchTracker::new();
+        tracker.handle(TouchPhase::Begin, 1, 0.0, 0.0);
+        assert!(!tracker.handle(TouchPhase::Begin, 2, 100.0, 0.0).is_primary);
+
+        let result = tracker.handle(TouchPhase::Move, 2, 0.0, 200.0);
+        let Gesture::Transform {
+            phase,
+            scale,
+            rotation,
+            ..
+        } = result.gestures[0]
+        else {
+            panic!("Expected a transform");
+        };
+        assert_eq!(phase, GesturePhase::Begin);
+        assert!((scale - 2.0).abs() < 1e-9);
+        assert!((rotation - 90.0).abs() < 1e-9);
+
+        let result = tracker.handle(TouchPhase::End, 1, 0.0, 0.0);
+        assert!(matches!(
+            result.gestures[..],
+            [Gesture::Transform {
+                phase: GesturePhase::End,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn two_finger_tap() {
+        let mut tracker = TouchTracker::new();
+        tracker.handle(TouchPhase::Begin, 1, 0.0, 0.0);
+        tracker.handle(TouchPhase::Begin, 2, 20.0, 0.0);
+        assert!(tracker
+            .handle(TouchPhase::End, 1, 0.0, 0.0)
+            .gestures
+            .is_empty());
+        assert_eq!(
+            tracker.handle(TouchPhase::End, 2, 20.0, 0.0).gestures,
+            vec![Gesture::TwoFingerTap { x: 10.0, y: 0.0 }]
+        );
+    }
+}
@@ -17,6 +17,11 @@ file-menu-exit = Exit
 controls-menu = Controls
 controls-menu-suspend = Suspend
 controls-menu-resume = Resume
+controls-menu-screenshot = Take Screenshot
+controls-menu-start-recording = Start Recording
+controls-menu-stop-recording = Stop Recording
+controls-menu-save-state = Save State { $slot }
+controls-menu-load-state = Load State { $slot }
 
 help-menu = Help
 help-menu-join-discord = Join Discord
@@ -30,4 +35,7 @@ debug-menu-open-stage = View Stage Info
 debug-menu-open-movie = View Movie
 debug-menu-open-movie-list = Show Known Movies
 debug-menu-search-display-objects = Search Display Objects...
+debug-menu-memory-profile = Memory Profile
+debug-menu-profiler = Profiler
+debug-menu-console = Console
 
@@ -15,6 +15,8 @@ tcp-connections = TCP Connections
 tcp-connections-allow = Allow
 tcp-connections-ask = Ask
 tcp-connections-deny = Deny
+socket-policy = Socket Policy Files
+socket-policy-check = Check
 
 open-url-mode = Open URL Mode
 open-url-mode-allow = Allow
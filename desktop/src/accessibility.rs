@@ -0,0 +1,121 @@
+//! Exposes the accessibility tree of the movie to screen readers, through AccessKit.
+
+use crate::custom_event::RuffleEvent;
+use accesskit::{NodeBuilder, NodeClassSet, NodeId, Rect, Role, Tree, TreeUpdate};
+use accesskit_winit::{ActionRequestEvent, Adapter};
+use ruffle_core::accessibility::{AccessibilityNode, AccessibilityRole};
+use std::num::NonZeroU128;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use winit::event::WindowEvent;
+use winit::event_loop::EventLoopProxy;
+use winit::window::Window;
+
+/// The node that stands in for the movie until the player exposes its tree.
+const PLACEHOLDER_ID: NodeId = match NonZeroU128::new(1) {
+    Some(id) => NodeId(id),
+    None => unreachable!(),
+};
+
+impl From<ActionRequestEvent> for RuffleEvent {
+    fn from(event: ActionRequestEvent) -> Self {
+        RuffleEvent::AccessibilityAction(event)
+    }
+}
+
+pub struct AccessibilityAdapter {
+    adapter: Adapter,
+
+    /// Whether assistive technology asked for the tree, which AccessKit only does once
+    /// a screen reader starts reading the window.
+    active: Arc<AtomicBool>,
+}
+
+impl AccessibilityAdapter {
+    /// Creates the adapter for a window, which must not have been made visible yet.
+    pub fn new(window: &Window, event_loop: EventLoopProxy<RuffleEvent>) -> Self {
+        let active = Arc::new(AtomicBool::new(false));
+        let source_active = active.clone();
+        let adapter = Adapter::new(
+            window,
+            move || {
+                source_active.store(true, Ordering::Relaxed);
+                let node = NodeBuilder::new(Role::Window).build(&mut NodeClassSet::new());
+                TreeUpdate {
+                    nodes: vec![(PLACEHOLDER_ID, node)],
+                    tree: Some(Tree::new(PLACEHOLDER_ID)),
+                    focus: None,
+                }
+            },
+            event_loop,
+        );
+        Self { adapter, active }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Passes a window event to AccessKit, which keeps track of the window's focus and bounds.
+    pub fn on_event(&self, window: &Window, event: &WindowEvent) -> bool {
+        self.adapter.on_event(window, event)
+    }
+
+    /// Replaces the tree with the player's, offset vertically by `y_offset` window pixels.
+    pub fn update(&self, tree: &AccessibilityNode, y_offset: f64) {
+        let mut classes = NodeClassSet::new();
+        let mut nodes = vec![];
+        let root = add_node(tree, y_offset, &mut classes, &mut nodes);
+        self.adapter.update(TreeUpdate {
+            nodes,
+            tree: Some(Tree::new(root)),
+            focus: None,
+        });
+    }
+}
+
+fn add_node(
+    node: &AccessibilityNode,
+    y_offset: f64,
+    classes: &mut NodeClassSet,
+    nodes: &mut Vec<(NodeId, accesskit::Node)>,
+) -> NodeId {
+    let role = match node.role {
+        AccessibilityRole::Window => Role::Window,
+        AccessibilityRole::Group => Role::Group,
+        AccessibilityRole::Button => Role::Button,
+        AccessibilityRole::StaticText => Role::StaticText,
+        AccessibilityRole::TextInput => Role::TextInput,
+    };
+    let mut builder = NodeBuilder::new(role);
+    if !node.name.is_empty() {
+        builder.set_name(node.name.clone());
+    }
+    if !node.description.is_empty() {
+        builder.set_description(node.description.clone());
+    }
+    if !node.shortcut.is_empty() {
+        builder.set_keyboard_shortcut(node.shortcut.clone());
+    }
+    if let Some(value) = &node.value {
+        builder.set_value(value.clone());
+    }
+    let (x0, y0, x1, y1) = node.bounds;
+    builder.set_bounds(Rect {
+        x0,
+        y0: y0 + y_offset,
+        x1,
+        y1: y1 + y_offset,
+    });
+    let children = node
+        .children
+        .iter()
+        .map(|child| add_node(child, y_offset, classes, nodes))
+        .collect();
+    builder.set_children(children);
+
+    // Stage objects are never at address 1, so their IDs don't clash with the placeholder.
+    let id = NodeId(NonZeroU128::new(node.id.into()).unwrap_or(PLACEHOLDER_ID.0));
+    nodes.push((id, builder.build(classes)));
+    id
+}
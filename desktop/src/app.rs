@@ -1,20 +1,35 @@
+use crate::accessibility::AccessibilityAdapter;
+use crate::capture::Capture;
 use crate::cli::Opt;
+use crate::control::{self, ControlRequest};
 use crate::custom_event::RuffleEvent;
-use crate::gui::{GuiController, MENU_HEIGHT};
+use crate::gui::{GuiController, MovieView, MENU_HEIGHT};
 use crate::player::{PlayerController, PlayerOptions};
 use crate::util::{
     get_screen_size, parse_url, pick_file, plot_stats_in_tracy, winit_key_to_char,
-    winit_to_ruffle_key_code, winit_to_ruffle_text_control,
+    winit_to_ruffle_key_code, winit_to_ruffle_key_location, winit_to_ruffle_text_control,
 };
 use anyhow::{Context, Error};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use image::ImageOutputFormat;
+use rfd::{MessageButtons, MessageDialog, MessageLevel};
+use ruffle_core::events::{TouchPhase as RuffleTouchPhase, TouchPoint};
+use ruffle_core::headless::InputScript;
 use ruffle_core::{PlayerEvent, StageDisplayState};
 use ruffle_render::backend::ViewportDimensions;
+use ruffle_render_wgpu::backend::WgpuRenderBackend;
+use serde_json::{json, Value};
 use std::cell::RefCell;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::time::{Duration, Instant};
 use url::Url;
 use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Size};
-use winit::event::{ElementState, KeyboardInput, ModifiersState, VirtualKeyCode, WindowEvent};
+use winit::event::{
+    ElementState, KeyboardInput, ModifiersState, TouchPhase, VirtualKeyCode, WindowEvent,
+};
 use winit::event_loop::{ControlFlow, EventLoop, EventLoopBuilder};
 use winit::window::{Fullscreen, Icon, Window, WindowBuilder};
 
@@ -24,6 +39,8 @@ pub struct App {
     event_loop: Option<EventLoop<RuffleEvent>>,
     gui: Rc<RefCell<GuiController>>,
     player: PlayerController,
+    accessibility: Rc<AccessibilityAdapter>,
+    capture: Capture,
     min_window_size: LogicalSize<u32>,
     max_window_size: PhysicalSize<u32>,
 }
@@ -47,6 +64,11 @@ impl App {
             .with_min_inner_size(min_window_size)
             .with_max_inner_size(max_window_size)
             .build(&event_loop)?;
+        window.set_ime_allowed(true);
+        let accessibility = Rc::new(AccessibilityAdapter::new(
+            &window,
+            event_loop.create_proxy(),
+        ));
         let window = Rc::new(window);
 
         let mut gui = GuiController::new(window.clone(), &event_loop, &opt)?;
@@ -54,21 +76,32 @@ impl App {
         let mut player = PlayerController::new(
             event_loop.create_proxy(),
             window.clone(),
+            accessibility.clone(),
             gui.descriptors().clone(),
         );
 
         if let Some(movie_url) = movie_url {
             gui.create_movie(&mut player, PlayerOptions::from(&opt), movie_url);
-        } else {
+        } else if opt.control.is_none() {
             gui.show_open_dialog();
         }
 
+        if let Some(channel) = opt.control {
+            if let Err(e) = control::start(channel, event_loop.create_proxy()) {
+                tracing::error!("Couldn't start the control channel: {e}");
+            }
+        }
+
+        let capture = Capture::new(opt.capture_dir.clone(), opt.ffmpeg.clone());
+
         Ok(Self {
             opt,
             window,
             event_loop: Some(event_loop),
             gui: Rc::new(RefCell::new(gui)),
             player,
+            accessibility,
+            capture,
             min_window_size,
             max_window_size,
         })
@@ -87,6 +120,9 @@ impl App {
         let mut minimized = false;
         let mut modifiers = ModifiersState::empty();
         let mut fullscreen_down = false;
+        let mut ime_composing = false;
+        let mut hovered_files: Vec<PathBuf> = Vec::new();
+        let mut dropped_files: Vec<PathBuf> = Vec::new();
 
         if self.opt.movie_url.is_none() {
             // No SWF provided on command line; show window with dummy movie immediately.
@@ -103,6 +139,8 @@ impl App {
                     if let Some(mut player) = self.player.get() {
                         player.flush_shared_objects();
                     }
+                    // Dropping the player saves anything it was recording.
+                    self.player.destroy();
                     crate::shutdown();
                     return;
                 }
@@ -115,6 +153,7 @@ impl App {
                     let dt = new_time.duration_since(time).as_micros();
                     if dt > 0 {
                         time = new_time;
+                        self.player.poll_gamepads();
                         if let Some(mut player) = self.player.get() {
                             player.tick(dt as f64 / 1000.0);
                             next_frame_time = Some(new_time + player.time_til_next_frame());
@@ -132,6 +171,15 @@ impl App {
                         if let Some(mut player) = self.player.get() {
                             // Even if the movie is paused, user interaction with debug tools can change the render output
                             player.render();
+                            // In vsync mode, the next frame is due as soon as this one has been presented.
+                            next_frame_time = Some(Instant::now() + player.time_til_next_frame());
+                            if let Some(renderer) = player
+                                .renderer()
+                                .downcast_ref::<WgpuRenderBackend<MovieView>>()
+                            {
+                                self.capture
+                                    .on_frame_rendered(renderer.target(), renderer.descriptors());
+                            }
                             self.gui.borrow_mut().render(Some(player));
                         } else {
                             self.gui.borrow_mut().render(None);
@@ -141,6 +189,7 @@ impl App {
                 }
 
                 winit::event::Event::WindowEvent { event, .. } => {
+                    self.accessibility.on_event(&self.window, &event);
                     if self.gui.borrow_mut().handle_event(&event) {
                         // Event consumed by GUI.
                         return;
@@ -185,13 +234,36 @@ impl App {
                             self.player.handle_event(event);
                             check_redraw = true;
                         }
+                        WindowEvent::HoveredFile(file) => {
+                            dropped_files.clear();
+                            hovered_files.push(file);
+                        }
+                        WindowEvent::HoveredFileCancelled => {
+                            hovered_files.clear();
+                        }
                         WindowEvent::DroppedFile(file) => {
-                            if let Ok(url) = parse_url(&file) {
-                                self.gui.borrow_mut().create_movie(
-                                    &mut self.player,
-                                    PlayerOptions::from(&self.opt),
-                                    url,
-                                );
+                            // Each of the dropped files is reported separately, after all of
+                            // them were hovered, so the whole drop is handled on the first one.
+                            if dropped_files.contains(&file) {
+                                return;
+                            }
+                            dropped_files = std::mem::take(&mut hovered_files);
+                            if !dropped_files.contains(&file) {
+                                dropped_files = vec![file.clone()];
+                            }
+
+                            let paths = dropped_files
+                                .iter()
+                                .map(|path| path.to_string_lossy().into_owned())
+                                .collect();
+                            let accepted = self
+                                .player
+                                .get()
+                                .map_or(false, |mut player| player.drop_files(paths));
+                            if accepted {
+                                check_redraw = true;
+                            } else {
+                                self.open_dropped_movie(&file);
                             }
                         }
                         WindowEvent::MouseInput { button, state, .. } => {
@@ -246,6 +318,24 @@ impl App {
                                 }
                             }
                         }
+                        WindowEvent::Touch(touch) => {
+                            // The platform already reports the first touch point as the mouse.
+                            let phase = match touch.phase {
+                                TouchPhase::Started => RuffleTouchPhase::Begin,
+                                TouchPhase::Moved => RuffleTouchPhase::Move,
+                                TouchPhase::Ended | TouchPhase::Cancelled => RuffleTouchPhase::End,
+                            };
+                            let point = TouchPoint {
+                                id: touch.id as i32,
+                                x: touch.location.x,
+                                y: touch.location.y - height_offset,
+                                pressure: touch.force.map(|force| force.normalized()),
+                                size: None,
+                            };
+                            self.player
+                                .handle_event(PlayerEvent::Touch { phase, point });
+                            check_redraw = true;
+                        }
                         WindowEvent::CursorLeft { .. } => {
                             if let Some(mut player) = self.player.get() {
                                 player.set_mouse_in_stage(false);
@@ -303,11 +393,13 @@ impl App {
                             if let Some(key) = input.virtual_keycode {
                                 let key_code = winit_to_ruffle_key_code(key);
                                 let key_char = winit_key_to_char(key, modifiers.shift());
+                                let key_location = winit_to_ruffle_key_location(key);
                                 match input.state {
                                     ElementState::Pressed => {
                                         self.player.handle_event(PlayerEvent::KeyDown {
                                             key_code,
                                             key_char,
+                                            key_location,
                                         });
                                         if let Some(control_code) =
                                             winit_to_ruffle_text_control(key, modifiers)
@@ -321,6 +413,7 @@ impl App {
                                         self.player.handle_event(PlayerEvent::KeyUp {
                                             key_code,
                                             key_char,
+                                            key_location,
                                         });
                                     }
                                 };
@@ -332,6 +425,48 @@ impl App {
                             self.player.handle_event(event);
                             check_redraw = true;
                         }
+                        WindowEvent::Ime(ime) => {
+                            use winit::event::Ime;
+                            match ime {
+                                Ime::Preedit(text, cursor) => {
+                                    if !text.is_empty() && !ime_composing {
+                                        self.player.handle_event(PlayerEvent::ImeCompositionStart);
+                                        ime_composing = true;
+                                    }
+                                    if ime_composing {
+                                        self.player.handle_event(
+                                            PlayerEvent::ImeCompositionUpdate { text, cursor },
+                                        );
+                                    }
+                                }
+                                Ime::Commit(text) => {
+                                    if !ime_composing {
+                                        self.player.handle_event(PlayerEvent::ImeCompositionStart);
+                                    }
+                                    self.player
+                                        .handle_event(PlayerEvent::ImeCompositionEnd { text });
+                                    ime_composing = false;
+                                }
+                                Ime::Disabled if ime_composing => {
+                                    self.player.handle_event(PlayerEvent::ImeCompositionEnd {
+                                        text: String::new(),
+                                    });
+                                    ime_composing = false;
+                                }
+                                _ => (),
+                            }
+
+                            // Keep the candidate window next to the caret.
+                            if let Some(mut player) = self.player.get() {
+                                if let Some((x, y)) = player.ime_cursor_position() {
+                                    self.window.set_ime_position(PhysicalPosition::new(
+                                        x,
+                                        y + height_offset,
+                                    ));
+                                }
+                            }
+                            check_redraw = true;
+                        }
                         _ => (),
                     }
                 }
@@ -436,6 +571,39 @@ impl App {
                     self.player.destroy();
                 }
 
+                winit::event::Event::UserEvent(RuffleEvent::RestoreState(state)) => {
+                    if let Some(mut player) = self.player.get() {
+                        player.restore_state(*state);
+                    }
+                }
+
+                winit::event::Event::UserEvent(RuffleEvent::ControlRequest(request)) => {
+                    self.handle_control_request(*request);
+                    check_redraw = true;
+                }
+
+                winit::event::Event::UserEvent(RuffleEvent::TakeScreenshot) => {
+                    self.capture.request_screenshot();
+                    self.window.request_redraw();
+                }
+
+                winit::event::Event::UserEvent(RuffleEvent::ToggleRecording) => {
+                    if self.capture.is_recording() {
+                        self.capture.stop_recording();
+                    } else if let Err(e) = self.capture.start_recording(self.player.audio_tap()) {
+                        tracing::error!("Couldn't start recording: {e:#}");
+                        MessageDialog::new()
+                            .set_level(MessageLevel::Error)
+                            .set_title("Ruffle - Recording failed")
+                            .set_description(&format!("{e:#}"))
+                            .set_buttons(MessageButtons::Ok)
+                            .show();
+                    }
+                    self.gui
+                        .borrow_mut()
+                        .set_recording(self.capture.is_recording());
+                }
+
                 winit::event::Event::UserEvent(RuffleEvent::ExitRequested) => {
                     *control_flow = ControlFlow::Exit;
                     return;
@@ -467,4 +635,120 @@ impl App {
             };
         });
     }
+
+    /// Handles a request from the control channel and responds to it.
+    fn handle_control_request(&mut self, request: ControlRequest) {
+        let result = self.run_control_request(&request);
+        request.respond(result);
+    }
+
+    fn run_control_request(&mut self, request: &ControlRequest) -> Result<Value, String> {
+        match request.method.as_str() {
+            "load" => {
+                let url = request.string_param("url")?;
+                let url = parse_url(Path::new(url)).map_err(|e| e.to_string())?;
+                self.gui.borrow_mut().create_movie(
+                    &mut self.player,
+                    PlayerOptions::from(&self.opt),
+                    url,
+                );
+                return Ok(Value::Null);
+            }
+            "close" => {
+                self.player.destroy();
+                return Ok(Value::Null);
+            }
+            _ => {}
+        }
+
+        let mut player = self.player.get().ok_or("No movie is open")?;
+        match request.method.as_str() {
+            "pause" => player.set_is_playing(false),
+            "resume" => player.set_is_playing(true),
+            "evaluate" => {
+                let expression = request.string_param("expression")?;
+                return player.console_evaluate(None, expression).map(Value::from);
+            }
+            "setVariable" => {
+                let name = request.string_param("name")?;
+                let value = control::console_literal(&request.params["value"])?;
+                player.console_evaluate(None, &format!("{name} = {value}"))?;
+            }
+            "sendInput" => {
+                let event = request.string_param("event")?;
+                let script = InputScript::parse(&format!("1 {event}")).map_err(|e| e.message)?;
+                let event = script.events()[0].event.clone();
+                player.handle_event(event);
+            }
+            "screenshot" => {
+                let renderer = player
+                    .renderer_mut()
+                    .downcast_mut::<WgpuRenderBackend<MovieView>>()
+                    .expect("Renderer must be correct type");
+                let image = renderer.target().capture(renderer.descriptors());
+                let mut png = Vec::new();
+                image
+                    .write_to(&mut Cursor::new(&mut png), ImageOutputFormat::Png)
+                    .map_err(|e| e.to_string())?;
+                return Ok(json!({
+                    "png": STANDARD.encode(png),
+                    "width": image.width(),
+                    "height": image.height(),
+                }));
+            }
+            "metadata" => {
+                let swf = player.swf();
+                return Ok(json!({
+                    "url": swf.url(),
+                    "version": swf.version(),
+                    "width": swf.width().to_pixels(),
+                    "height": swf.height().to_pixels(),
+                    "numFrames": swf.num_frames(),
+                    "isActionScript3": swf.is_action_script_3(),
+                    "frameRate": player.frame_rate(),
+                    "currentFrame": player.current_frame(),
+                    "isPlaying": player.is_playing(),
+                }));
+            }
+            method => return Err(format!("Unknown method \"{method}\"")),
+        }
+        Ok(Value::Null)
+    }
+
+    /// Opens a movie that was dropped onto the window, if the current movie didn't accept it.
+    ///
+    /// Only Flash files are opened, and a movie that's already playing is only replaced
+    /// once the user confirms it.
+    fn open_dropped_movie(&mut self, file: &Path) {
+        let is_movie = file
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map_or(false, |extension| {
+                extension.eq_ignore_ascii_case("swf") || extension.eq_ignore_ascii_case("spl")
+            });
+        if !is_movie {
+            return;
+        }
+
+        if self.player.get().is_some() {
+            let name = file.file_name().unwrap_or_default().to_string_lossy();
+            let confirmed = MessageDialog::new()
+                .set_level(MessageLevel::Info)
+                .set_title("Ruffle - Open movie")
+                .set_description(&format!("Close the current movie and open {name}?"))
+                .set_buttons(MessageButtons::YesNo)
+                .show();
+            if !confirmed {
+                return;
+            }
+        }
+
+        if let Ok(url) = parse_url(file) {
+            self.gui.borrow_mut().create_movie(
+                &mut self.player,
+                PlayerOptions::from(&self.opt),
+                url,
+            );
+        }
+    }
 }
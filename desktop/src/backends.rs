@@ -1,11 +1,17 @@
 mod audio;
+#[cfg(feature = "camera")]
+mod camera;
+mod cookies;
 mod external_interface;
+mod filesystem;
+mod local_connection;
 mod navigator;
 mod storage;
 mod ui;
 
-pub use audio::CpalAudioBackend;
+pub use audio::{AudioTap, CpalAudioBackend};
 pub use external_interface::DesktopExternalInterfaceProvider;
+pub use filesystem::DiskFilesystemBackend;
 pub use navigator::ExternalNavigatorBackend;
 pub use storage::DiskStorageBackend;
 pub use ui::DesktopUiBackend;
@@ -1,10 +1,12 @@
 use anyhow::{anyhow, Context, Error};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use rfd::{MessageButtons, MessageDialog, MessageLevel};
 use ruffle_core::backend::audio::{
-    swf, AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
-    SoundTransform,
+    swf, AudioBackend, AudioMixer, DecodeError, MicrophoneState, RegisterError, SoundHandle,
+    SoundInstanceHandle, SoundTransform,
 };
 use ruffle_core::impl_audio_mixer_backend;
+use std::sync::{Arc, Mutex};
 
 pub struct CpalAudioBackend {
     #[allow(dead_code)]
@@ -13,6 +15,66 @@ pub struct CpalAudioBackend {
     config: cpal::StreamConfig,
     stream: cpal::Stream,
     mixer: AudioMixer,
+
+    /// A copy of the mixed output, for recordings.
+    tap: AudioTap,
+
+    /// The capture of a microphone, if any.
+    microphone: Option<Microphone>,
+
+    /// Whether the user has allowed or denied access to microphones.
+    microphone_allowed: Option<bool>,
+}
+
+/// A copy of the audio that's sent to the output device, taken while a recording is running.
+#[derive(Clone)]
+pub struct AudioTap {
+    /// The interleaved samples mixed since they were last taken, or `None` when not recording.
+    samples: Arc<Mutex<Option<Vec<f32>>>>,
+    pub channels: u16,
+    pub sample_rate: u32,
+}
+
+impl AudioTap {
+    pub fn start(&self) {
+        *self.samples.lock().expect("Audio tap lock poisoned") = Some(Vec::new());
+    }
+
+    pub fn stop(&self) {
+        *self.samples.lock().expect("Audio tap lock poisoned") = None;
+    }
+
+    /// Takes the samples that were mixed since the last call.
+    pub fn take(&self) -> Vec<f32> {
+        self.samples
+            .lock()
+            .expect("Audio tap lock poisoned")
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
+    fn push(&self, samples: impl Iterator<Item = f32>) {
+        if let Some(buffer) = self
+            .samples
+            .lock()
+            .expect("Audio tap lock poisoned")
+            .as_mut()
+        {
+            buffer.extend(samples);
+        }
+    }
+}
+
+/// Audio being captured from an input device.
+struct Microphone {
+    /// Capturing stops when this is dropped.
+    #[allow(dead_code)]
+    stream: cpal::Stream,
+    sample_rate: u32,
+
+    /// The mono samples captured since they were last taken.
+    samples: Arc<Mutex<Vec<f32>>>,
 }
 
 impl CpalAudioBackend {
@@ -30,22 +92,34 @@ impl CpalAudioBackend {
         let sample_format = config.sample_format();
         let config = cpal::StreamConfig::from(config);
         let mixer = AudioMixer::new(config.channels as u8, config.sample_rate.0);
+        let tap = AudioTap {
+            samples: Arc::new(Mutex::new(None)),
+            channels: config.channels,
+            sample_rate: config.sample_rate.0,
+        };
 
         // Start the audio stream.
         let stream = {
             let mixer = mixer.proxy();
+            let tap = tap.clone();
             let error_handler = move |err| tracing::error!("Audio stream error: {}", err);
 
             match sample_format {
                 cpal::SampleFormat::F32 => device.build_output_stream(
                     &config,
-                    move |buffer, _| mixer.mix::<f32>(buffer),
+                    move |buffer: &mut [f32], _| {
+                        mixer.mix::<f32>(buffer);
+                        tap.push(buffer.iter().copied());
+                    },
                     error_handler,
                     None,
                 ),
                 cpal::SampleFormat::I16 => device.build_output_stream(
                     &config,
-                    move |buffer, _| mixer.mix::<i16>(buffer),
+                    move |buffer: &mut [i16], _| {
+                        mixer.mix::<i16>(buffer);
+                        tap.push(buffer.iter().map(|s| f32::from(*s) / 32768.0));
+                    },
                     error_handler,
                     None,
                 ),
@@ -56,6 +130,11 @@ impl CpalAudioBackend {
                         // we fill the buffer as if it was `&[i16]`, and then rotate
                         // the sample values to make 32768 the equilibrium.
                         mixer.mix::<i16>(bytemuck::cast_slice_mut(buffer));
+                        tap.push(
+                            bytemuck::cast_slice::<u16, i16>(buffer)
+                                .iter()
+                                .map(|s| f32::from(*s) / 32768.0),
+                        );
                         for s in buffer.iter_mut() {
                             *s = (*s).wrapping_add(32768);
                         }
@@ -74,6 +153,81 @@ impl CpalAudioBackend {
             config,
             stream,
             mixer,
+            tap,
+            microphone: None,
+            microphone_allowed: None,
+        })
+    }
+
+    /// A tap on the audio that this backend plays.
+    pub fn tap(&self) -> AudioTap {
+        self.tap.clone()
+    }
+
+    /// Starts capturing audio from the input device at `index`.
+    fn capture_microphone(index: usize) -> Result<Microphone, Error> {
+        let device = cpal::default_host()
+            .input_devices()?
+            .nth(index)
+            .ok_or_else(|| anyhow!("No input device {index}"))?;
+        let config = device
+            .default_input_config()
+            .context("Failed to get default input config")?;
+        let sample_format = config.sample_format();
+        let config = cpal::StreamConfig::from(config);
+        let channels = usize::from(config.channels.max(1));
+
+        let samples = Arc::new(Mutex::new(Vec::new()));
+        // Downmix captured frames to mono, dropping them if nobody takes them for a second.
+        let max_samples = config.sample_rate.0 as usize;
+        let capture = {
+            let samples = Arc::clone(&samples);
+            move |frames: &mut dyn Iterator<Item = f32>| {
+                let mut samples = samples.lock().expect("Microphone samples lock poisoned");
+                let mut frames = frames.peekable();
+                while frames.peek().is_some() {
+                    let sum: f32 = frames.by_ref().take(channels).sum();
+                    if samples.len() < max_samples {
+                        samples.push(sum / channels as f32);
+                    }
+                }
+            }
+        };
+        let error_handler = move |err| tracing::error!("Microphone stream error: {}", err);
+
+        let stream = match sample_format {
+            cpal::SampleFormat::F32 => device.build_input_stream(
+                &config,
+                move |buffer: &[f32], _| capture(&mut buffer.iter().copied()),
+                error_handler,
+                None,
+            ),
+            cpal::SampleFormat::I16 => device.build_input_stream(
+                &config,
+                move |buffer: &[i16], _| {
+                    capture(&mut buffer.iter().map(|s| f32::from(*s) / 32768.0))
+                },
+                error_handler,
+                None,
+            ),
+            cpal::SampleFormat::U16 => device.build_input_stream(
+                &config,
+                move |buffer: &[u16], _| {
+                    capture(&mut buffer.iter().map(|s| (f32::from(*s) - 32768.0) / 32768.0))
+                },
+                error_handler,
+                None,
+            ),
+            _ => anyhow::bail!("Unsupported sample format {sample_format:?}"),
+        }?;
+        stream
+            .play()
+            .context("Couldn't start the microphone stream")?;
+
+        Ok(Microphone {
+            stream,
+            sample_rate: config.sample_rate.0,
+            samples,
         })
     }
 }
@@ -88,4 +242,65 @@ impl AudioBackend for CpalAudioBackend {
     fn pause(&mut self) {
         self.stream.pause().expect("Error trying to pause CPAL audio stream. This feature may not be supported by your audio device.");
     }
+
+    fn microphone_names(&self) -> Vec<String> {
+        match cpal::default_host().input_devices() {
+            Ok(devices) => devices
+                .map(|device| device.name().unwrap_or_default())
+                .collect(),
+            Err(e) => {
+                tracing::error!("Couldn't list input devices: {}", e);
+                Vec::new()
+            }
+        }
+    }
+
+    fn start_microphone(&mut self, index: usize) {
+        self.microphone = None;
+
+        // Like Flash Player's privacy dialog, ask once before any microphone is used.
+        let allowed = *self.microphone_allowed.get_or_insert_with(|| {
+            MessageDialog::new()
+                .set_title("Allow microphone access?")
+                .set_level(MessageLevel::Info)
+                .set_description("The SWF file wants to access your microphone.")
+                .set_buttons(MessageButtons::YesNo)
+                .show()
+        });
+        if !allowed {
+            return;
+        }
+
+        match Self::capture_microphone(index) {
+            Ok(microphone) => self.microphone = Some(microphone),
+            Err(e) => tracing::error!("Couldn't capture from microphone: {}", e),
+        }
+    }
+
+    fn stop_microphone(&mut self) {
+        self.microphone = None;
+    }
+
+    fn microphone_state(&self) -> MicrophoneState {
+        match (&self.microphone, self.microphone_allowed) {
+            (_, Some(false)) => MicrophoneState::Denied,
+            (Some(_), _) => MicrophoneState::Allowed,
+            (None, _) => MicrophoneState::Unavailable,
+        }
+    }
+
+    fn take_microphone_samples(&mut self) -> (u32, Vec<f32>) {
+        match &self.microphone {
+            Some(microphone) => (
+                microphone.sample_rate,
+                std::mem::take(
+                    &mut *microphone
+                        .samples
+                        .lock()
+                        .expect("Microphone samples lock poisoned"),
+                ),
+            ),
+            None => (44100, Vec::new()),
+        }
+    }
 }
@@ -0,0 +1,112 @@
+use anyhow::{Context, Error};
+use nokhwa::pixel_format::RgbAFormat;
+use nokhwa::utils::{
+    ApiBackend, CameraFormat, CameraIndex, FrameFormat, RequestedFormat, RequestedFormatType,
+    Resolution,
+};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+/// Returns the names of the video input devices.
+pub fn camera_names() -> Vec<String> {
+    match nokhwa::query(ApiBackend::Auto) {
+        Ok(cameras) => cameras.iter().map(|info| info.human_name()).collect(),
+        Err(e) => {
+            tracing::error!("Couldn't list cameras: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Video being captured from an input device on a background thread.
+pub struct Webcam {
+    /// The newest captured frame that hasn't been taken yet.
+    frame: Arc<Mutex<Option<Bitmap>>>,
+
+    /// Tells the capture thread to stop.
+    stop: Arc<AtomicBool>,
+}
+
+impl Webcam {
+    /// Starts capturing video from the input device at `index`, in the mode
+    /// closest to the given one.
+    pub fn start(index: usize, width: u32, height: u32, fps: f64) -> Result<Self, Error> {
+        let format =
+            RequestedFormat::new::<RgbAFormat>(RequestedFormatType::Closest(CameraFormat::new(
+                Resolution::new(width, height),
+                FrameFormat::MJPEG,
+                fps.round() as u32,
+            )));
+
+        let frame = Arc::new(Mutex::new(None));
+        let stop = Arc::new(AtomicBool::new(false));
+        // Cameras can't be sent between threads on every platform,
+        // so the capture thread opens it and reports back whether that worked.
+        let (opened_sender, opened) = mpsc::channel();
+        {
+            let frame = Arc::clone(&frame);
+            let stop = Arc::clone(&stop);
+            thread::spawn(move || {
+                let camera = nokhwa::Camera::new(CameraIndex::Index(index as u32), format)
+                    .context("Couldn't open the camera")
+                    .and_then(|mut camera| {
+                        camera
+                            .open_stream()
+                            .context("Couldn't start the camera stream")?;
+                        Ok(camera)
+                    });
+                let mut camera = match camera {
+                    Ok(camera) => {
+                        let _ = opened_sender.send(Ok(()));
+                        camera
+                    }
+                    Err(e) => {
+                        let _ = opened_sender.send(Err(e));
+                        return;
+                    }
+                };
+
+                while !stop.load(Ordering::Relaxed) {
+                    let image = match camera
+                        .frame()
+                        .and_then(|buffer| buffer.decode_image::<RgbAFormat>())
+                    {
+                        Ok(image) => image,
+                        Err(e) => {
+                            tracing::error!("Couldn't capture camera frame: {}", e);
+                            break;
+                        }
+                    };
+                    let bitmap = Bitmap::new(
+                        image.width(),
+                        image.height(),
+                        BitmapFormat::Rgba,
+                        image.into_raw(),
+                    );
+                    *frame.lock().expect("Camera frame lock poisoned") = Some(bitmap);
+                }
+                let _ = camera.stop_stream();
+            });
+        }
+
+        opened
+            .recv()
+            .context("The camera thread stopped unexpectedly")??;
+        Ok(Self { frame, stop })
+    }
+
+    pub fn take_frame(&self) -> Option<Bitmap> {
+        self.frame
+            .lock()
+            .expect("Camera frame lock poisoned")
+            .take()
+    }
+}
+
+impl Drop for Webcam {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,163 @@
+//! Session cookies for network requests.
+//!
+//! Cookies are kept in memory for as long as the player runs, and are never written to
+//! disk. Expiry dates are ignored, except that a cookie can be removed by setting it
+//! again with a `Max-Age` of zero.
+
+use url::{Host, Url};
+
+/// A cookie that was set by a server.
+struct Cookie {
+    name: String,
+    value: String,
+
+    /// The host, or the domain including its subdomains, that this cookie is sent to.
+    domain: String,
+
+    /// Whether this cookie is only sent to `domain` itself, and not its subdomains.
+    host_only: bool,
+
+    /// The path prefix of the URLs that this cookie is sent to.
+    path: String,
+
+    /// Whether this cookie is only sent over secure connections.
+    secure: bool,
+}
+
+impl Cookie {
+    fn matches(&self, url: &Url) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        let host = host.to_ascii_lowercase();
+        let domain_matches = if self.host_only {
+            host == self.domain
+        } else {
+            is_in_domain(&host, &self.domain)
+        };
+        domain_matches && path_matches(url.path(), &self.path) && (!self.secure || is_secure(url))
+    }
+}
+
+/// The cookies that servers have set during this session.
+#[derive(Default)]
+pub struct CookieJar {
+    cookies: Vec<Cookie>,
+}
+
+impl CookieJar {
+    /// The value of the `Cookie` header to send with a request to the given URL, if any.
+    pub fn cookie_header(&self, url: &Url) -> Option<String> {
+        let cookies: Vec<_> = self
+            .cookies
+            .iter()
+            .filter(|cookie| cookie.matches(url))
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect();
+        (!cookies.is_empty()).then(|| cookies.join("; "))
+    }
+
+    /// Store the cookie of a `Set-Cookie` header, received in response to a request to the given URL.
+    ///
+    /// Cookies for a domain that the URL doesn't belong to are ignored.
+    pub fn set_cookie(&mut self, url: &Url, header: &str) {
+        let Some(host) = url.host_str() else {
+            return;
+        };
+        let host = host.to_ascii_lowercase();
+        let is_ip_address = matches!(url.host(), Some(Host::Ipv4(_) | Host::Ipv6(_)));
+
+        let mut attributes = header.split(';');
+        let Some((name, value)) = attributes.next().and_then(|pair| pair.split_once('=')) else {
+            return;
+        };
+        let name = name.trim();
+        if name.is_empty() {
+            return;
+        }
+
+        let mut cookie = Cookie {
+            name: name.to_string(),
+            value: value.trim().to_string(),
+            domain: host.clone(),
+            host_only: true,
+            path: default_path(url),
+            secure: false,
+        };
+        let mut removed = false;
+        for attribute in attributes {
+            let (key, value) = attribute.split_once('=').unwrap_or((attribute, ""));
+            let value = value.trim();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "domain" if !value.is_empty() => {
+                    let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                    // Cookies for IP addresses can only ever be sent to that address.
+                    if is_ip_address {
+                        if domain != host {
+                            return;
+                        }
+                        continue;
+                    }
+                    if !is_in_domain(&host, &domain) {
+                        return;
+                    }
+                    // A cookie can't be shared by everything under a public suffix, such as
+                    // "com" or "github.io", only kept for the host that set it.
+                    if is_public_suffix(&domain) {
+                        if domain != host {
+                            return;
+                        }
+                        continue;
+                    }
+                    cookie.domain = domain;
+                    cookie.host_only = false;
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "secure" => cookie.secure = true,
+                "max-age" => removed = value.parse::<i64>().map_or(false, |age| age <= 0),
+                _ => {}
+            }
+        }
+
+        self.cookies.retain(|existing| {
+            existing.name != cookie.name
+                || existing.domain != cookie.domain
+                || existing.path != cookie.path
+        });
+        if !removed {
+            self.cookies.push(cookie);
+        }
+    }
+}
+
+/// Whether the host is the domain itself or one of its subdomains.
+fn is_in_domain(host: &str, domain: &str) -> bool {
+    host == domain
+        || host
+            .strip_suffix(domain)
+            .map_or(false, |prefix| prefix.ends_with('.'))
+}
+
+/// Whether the domain is one under which anyone can register names, according to the
+/// Public Suffix List. Unlisted top-level domains count as public suffixes too.
+fn is_public_suffix(domain: &str) -> bool {
+    psl::suffix_str(domain) == Some(domain)
+}
+
+fn is_secure(url: &Url) -> bool {
+    matches!(url.scheme(), "https" | "wss")
+}
+
+/// The path that a cookie applies to when it doesn't specify one: the directory of the URL.
+fn default_path(url: &Url) -> String {
+    match url.path().rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(index) => url.path()[..index].to_string(),
+    }
+}
+
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || (request_path.starts_with(cookie_path)
+            && (cookie_path.ends_with('/') || request_path[cookie_path.len()..].starts_with('/')))
+}
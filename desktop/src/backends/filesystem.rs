@@ -0,0 +1,193 @@
+use crate::backends::ui::to_date_time;
+use ruffle_core::backend::filesystem::{FileInfo, FilesystemBackend, SpecialDirectory};
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+/// Gives AIR movies access to the files inside of a sandbox directory.
+///
+/// The special directories of `File` are subdirectories of the sandbox, except for
+/// `File.applicationDirectory`, which is the directory of the movie and can only be read.
+pub struct DiskFilesystemBackend {
+    root: PathBuf,
+    application_directory: Option<PathBuf>,
+
+    /// Files and directories outside of the sandbox that the user allowed the movie to read.
+    granted: Vec<PathBuf>,
+}
+
+impl DiskFilesystemBackend {
+    pub fn new(root: PathBuf, application_directory: Option<PathBuf>) -> io::Result<Self> {
+        fs::create_dir_all(&root)?;
+        let root = root.canonicalize()?;
+        for directory in ["app-storage", "cache", "desktop", "documents"] {
+            fs::create_dir_all(root.join(directory))?;
+        }
+        let application_directory = application_directory.and_then(|dir| dir.canonicalize().ok());
+        Ok(Self {
+            root,
+            application_directory,
+            granted: Vec::new(),
+        })
+    }
+
+    /// Resolves a path, following any symlinks, and makes sure that it lies inside the sandbox.
+    ///
+    /// The application directory and granted paths are only allowed if `write` is false.
+    fn sandboxed_path(&self, path: &str, write: bool) -> io::Result<PathBuf> {
+        let mut resolved = PathBuf::new();
+        for component in Path::new(path).components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    resolved.pop();
+                }
+                component => resolved.push(component),
+            }
+        }
+
+        // Canonicalize the part of the path that exists, so that symlinks can't escape the sandbox.
+        let mut existing = resolved.as_path();
+        let mut missing = Vec::new();
+        let resolved = loop {
+            match existing.canonicalize() {
+                Ok(path) => {
+                    break missing
+                        .iter()
+                        .rev()
+                        .fold(path, |path, name| path.join(name))
+                }
+                Err(_) => match (existing.parent(), existing.file_name()) {
+                    (Some(parent), Some(name)) => {
+                        missing.push(name.to_owned());
+                        existing = parent;
+                    }
+                    _ => return Err(io::ErrorKind::PermissionDenied.into()),
+                },
+            }
+        };
+
+        let readable = !write
+            && self
+                .application_directory
+                .iter()
+                .chain(&self.granted)
+                .any(|dir| resolved.starts_with(dir));
+        if resolved.starts_with(&self.root) || readable {
+            Ok(resolved)
+        } else {
+            tracing::warn!("Denied access to {} outside of the AIR sandbox", path);
+            Err(io::ErrorKind::PermissionDenied.into())
+        }
+    }
+}
+
+/// Copies a file, or a directory and everything inside of it.
+///
+/// Symlinks inside of a copied directory are skipped, as they may point outside of the
+/// sandbox or back up to the directory being copied.
+fn copy_recursive(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            if entry.file_type()?.is_symlink() {
+                tracing::warn!("Skipped copying symlink {}", entry.path().display());
+                continue;
+            }
+            copy_recursive(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        fs::copy(from, to).map(|_| ())
+    }
+}
+
+impl FilesystemBackend for DiskFilesystemBackend {
+    fn special_directory(&self, directory: SpecialDirectory) -> Option<String> {
+        let path = match directory {
+            SpecialDirectory::Application => self.application_directory.clone()?,
+            SpecialDirectory::ApplicationStorage => self.root.join("app-storage"),
+            SpecialDirectory::Cache => self.root.join("cache"),
+            SpecialDirectory::Desktop => self.root.join("desktop"),
+            SpecialDirectory::Documents => self.root.join("documents"),
+            SpecialDirectory::User => self.root.clone(),
+        };
+        Some(path.to_string_lossy().into_owned())
+    }
+
+    fn separator(&self) -> &str {
+        if cfg!(windows) {
+            "\\"
+        } else {
+            "/"
+        }
+    }
+
+    fn info(&self, path: &str) -> io::Result<FileInfo> {
+        let metadata = fs::metadata(self.sandboxed_path(path, false)?)?;
+        Ok(FileInfo {
+            is_directory: metadata.is_dir(),
+            size: metadata.len(),
+            creation_date: to_date_time(metadata.created()),
+            modification_date: to_date_time(metadata.modified()),
+        })
+    }
+
+    fn read(&self, path: &str) -> io::Result<Vec<u8>> {
+        fs::read(self.sandboxed_path(path, false)?)
+    }
+
+    fn write(&mut self, path: &str, data: &[u8]) -> io::Result<()> {
+        fs::write(self.sandboxed_path(path, true)?, data)
+    }
+
+    fn create_directory(&mut self, path: &str) -> io::Result<()> {
+        fs::create_dir_all(self.sandboxed_path(path, true)?)
+    }
+
+    fn delete(&mut self, path: &str, recursive: bool) -> io::Result<()> {
+        let path = self.sandboxed_path(path, true)?;
+        if path == self.root {
+            return Err(io::ErrorKind::PermissionDenied.into());
+        }
+        match (path.is_dir(), recursive) {
+            (true, true) => fs::remove_dir_all(path),
+            (true, false) => fs::remove_dir(path),
+            (false, _) => fs::remove_file(path),
+        }
+    }
+
+    fn list_directory(&self, path: &str) -> io::Result<Vec<String>> {
+        fs::read_dir(self.sandboxed_path(path, false)?)?
+            .map(|entry| Ok(entry?.path().to_string_lossy().into_owned()))
+            .collect()
+    }
+
+    fn copy(&mut self, from: &str, to: &str) -> io::Result<()> {
+        // Both paths are canonical, so this also catches copies through symlinks. Copying
+        // a directory into itself would never finish.
+        let from = self.sandboxed_path(from, false)?;
+        let to = self.sandboxed_path(to, true)?;
+        if to.starts_with(&from) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Cannot copy a file or directory into itself",
+            ));
+        }
+        copy_recursive(&from, &to)
+    }
+
+    fn rename(&mut self, from: &str, to: &str) -> io::Result<()> {
+        fs::rename(
+            self.sandboxed_path(from, true)?,
+            self.sandboxed_path(to, true)?,
+        )
+    }
+
+    fn grant_read_access(&mut self, path: &str) {
+        if let Ok(path) = Path::new(path).canonicalize() {
+            self.granted.push(path);
+        }
+    }
+}
@@ -0,0 +1,197 @@
+//! `LocalConnection` messages between players running on this machine.
+//!
+//! Each connection name that's being listened to is registered as a file in a directory
+//! that only the current user can access. The file holds the port of a TCP listener on the
+//! loopback interface, and a token that senders have to present. Messages are sent by
+//! connecting to that port, writing the token and then the message, and closing the
+//! connection. Anything sent without the right token is ignored.
+
+use crate::util::{
+    constant_time_eq, create_private_dir, generate_token, read_private_file, write_private_file,
+};
+use ruffle_core::local_connection::LocalConnectionMessage;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::Sender;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tracing::warn;
+
+/// How long to wait for the listening player to accept a message.
+const SEND_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long to wait for a sender to finish writing its message.
+const RECEIVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The largest message that can be received. Flash limits messages to 40 KB.
+const MAX_MESSAGE_SIZE: u64 = 40 * 1024;
+
+/// The length of the token that senders write before their message.
+const TOKEN_LENGTH: usize = 32;
+
+/// Returns the path of the file that registers a connection name.
+///
+/// The registrations are kept in the user's runtime directory if there is one, and in
+/// Ruffle's data directory otherwise.
+fn registration_path(name: &str) -> Option<PathBuf> {
+    let dir = dirs::runtime_dir()
+        .or_else(dirs::data_local_dir)?
+        .join("ruffle")
+        .join("localconnection");
+    if let Err(e) = create_private_dir(&dir) {
+        warn!("Can't use {} for LocalConnections: {}", dir.display(), e);
+        return None;
+    }
+    // Connection names can contain characters that aren't allowed in file names.
+    let file_name: String = name.bytes().map(|b| format!("{b:02x}")).collect();
+    Some(dir.join(file_name))
+}
+
+/// Connects to the player that's listening with a connection name, if there is one.
+/// Returns the connection along with the token to send messages with.
+fn connect(name: &str) -> Option<(TcpStream, String)> {
+    let path = registration_path(name)?;
+    let registration = read_private_file(&path).ok()?;
+    let (port, token) = registration.trim().split_once(' ')?;
+    let port: u16 = port.parse().ok()?;
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+    match TcpStream::connect_timeout(&address, SEND_TIMEOUT) {
+        Ok(stream) => Some((stream, token.to_string())),
+        Err(_) => {
+            // The player that registered the name must have exited without closing it.
+            let _ = fs::remove_file(path);
+            None
+        }
+    }
+}
+
+/// Sends a message to the player that's listening with a connection name.
+///
+/// Returns `false` if no player is listening with that name.
+pub fn send(name: &str, data: &[u8]) -> bool {
+    let Some((mut stream, token)) = connect(name) else {
+        return false;
+    };
+    if let Err(e) = stream
+        .write_all(token.as_bytes())
+        .and_then(|_| stream.write_all(data))
+    {
+        warn!("Failed to send LocalConnection message: {}", e);
+        return false;
+    }
+    true
+}
+
+/// A connection name that this player is listening with.
+pub struct LocalConnectionListener {
+    name: String,
+    port: u16,
+
+    /// Tells the listening thread to stop.
+    stop: Arc<AtomicBool>,
+}
+
+impl LocalConnectionListener {
+    /// Starts listening for messages sent to a connection name, passing them to `sender`.
+    ///
+    /// Returns `None` if another player is already listening with that name.
+    pub fn listen(name: &str, sender: Sender<LocalConnectionMessage>) -> Option<Self> {
+        if connect(name).is_some() {
+            return None;
+        }
+
+        let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, 0)) {
+            Ok(listener) => listener,
+            Err(e) => {
+                warn!("Failed to listen for LocalConnection messages: {}", e);
+                return None;
+            }
+        };
+        let port = listener.local_addr().ok()?.port();
+        let token = generate_token();
+        let path = registration_path(name)?;
+        if let Err(e) = write_private_file(&path, format!("{port} {token}").as_bytes()) {
+            warn!("Failed to register LocalConnection {}: {}", name, e);
+            return None;
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        {
+            let name = name.to_string();
+            let token: Arc<str> = token.into();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    match stream {
+                        Ok(stream) => {
+                            // A slow sender mustn't hold up the messages of others.
+                            let name = name.clone();
+                            let token = token.clone();
+                            let sender = sender.clone();
+                            thread::spawn(move || receive(stream, name, &token, sender));
+                        }
+                        Err(e) => warn!("Failed to accept LocalConnection message: {}", e),
+                    }
+                }
+            });
+        }
+
+        Some(Self {
+            name: name.to_string(),
+            port,
+            stop,
+        })
+    }
+}
+
+/// Reads a message from a sender, and passes it on to the player if it came with the token.
+fn receive(stream: TcpStream, name: String, token: &str, sender: Sender<LocalConnectionMessage>) {
+    let mut data = Vec::new();
+    let received = stream
+        .set_read_timeout(Some(RECEIVE_TIMEOUT))
+        .and_then(|_| {
+            stream
+                .take(TOKEN_LENGTH as u64 + MAX_MESSAGE_SIZE + 1)
+                .read_to_end(&mut data)
+        });
+    if let Err(e) = received {
+        warn!("Failed to receive LocalConnection message: {}", e);
+        return;
+    }
+    // Other players check whether the name is taken by connecting without sending anything.
+    if data.is_empty() {
+        return;
+    }
+    let given_token = data
+        .get(..TOKEN_LENGTH)
+        .and_then(|t| std::str::from_utf8(t).ok());
+    if !given_token.map_or(false, |given| constant_time_eq(given, token)) {
+        warn!("Ignoring LocalConnection message without the right token");
+        return;
+    }
+    let data = data.split_off(TOKEN_LENGTH);
+    if data.len() as u64 > MAX_MESSAGE_SIZE {
+        warn!("Ignoring LocalConnection message larger than 40 KB");
+        return;
+    }
+    // If the player was closed, the message is dropped with it.
+    let _ = sender.send(LocalConnectionMessage { name, data });
+}
+
+impl Drop for LocalConnectionListener {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(path) = registration_path(&self.name) {
+            let _ = fs::remove_file(path);
+        }
+        // Wake the listening thread up, so that it notices it should stop.
+        let _ = TcpStream::connect((Ipv4Addr::LOCALHOST, self.port));
+    }
+}
@@ -1,29 +1,43 @@
 //! Navigator backend for web
 
+use crate::backends::cookies::CookieJar;
+use crate::backends::local_connection::{self, LocalConnectionListener};
 use crate::custom_event::RuffleEvent;
 use async_io::Timer;
-use async_net::TcpStream;
+use async_net::{TcpListener, TcpStream, UdpSocket};
 use futures::future::select;
-use futures::{AsyncReadExt, AsyncWriteExt};
+use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
 use futures_lite::FutureExt;
+use isahc::http::header::{COOKIE, LOCATION, SET_COOKIE};
 use isahc::http::{HeaderName, HeaderValue};
 use isahc::{
-    config::RedirectPolicy, prelude::*, AsyncReadResponseExt, HttpClient, Request as IsahcRequest,
+    config::RedirectPolicy, prelude::*, AsyncBody, AsyncReadResponseExt, HttpClient,
+    Request as IsahcRequest, Response,
 };
 use rfd::{AsyncMessageDialog, MessageButtons, MessageDialog, MessageLevel};
 use ruffle_core::backend::navigator::{
-    async_return, create_fetch_error, create_specific_fetch_error, ErrorResponse, NavigationMethod,
-    NavigatorBackend, OpenURLMode, OwnedFuture, Request, SocketMode, SuccessResponse,
+    async_return, create_fetch_error, create_specific_fetch_error, ErrorResponse, InterfaceAddress,
+    JavascriptUrlMode, NavigationMethod, NavigationPolicy, NavigatorBackend, NetworkInterface,
+    OpenURLMode, OwnedFuture, Request, ResponseBody, SocketMode, StreamingResponse,
+    SuccessResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
-use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
-use std::collections::HashSet;
+use ruffle_core::local_connection::LocalConnectionMessage;
+use ruffle_core::socket::{
+    is_allowed_by_policy, AcceptedConnection, ConnectionState, Datagram, DatagramSocketHandle,
+    ServerSocketHandle, SocketAction, SocketHandle, SocketKind, POLICY_FILE_PORT,
+    POLICY_FILE_REQUEST,
+};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
 use std::io;
 use std::io::ErrorKind;
+use std::pin::Pin;
 use std::rc::Rc;
 use std::str::FromStr;
-use std::sync::mpsc::{Receiver, Sender, TryRecvError};
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
 use std::time::Duration;
 use tracing::warn;
 use url::{ParseError, Url};
@@ -44,13 +58,26 @@ pub struct ExternalNavigatorBackend {
     // Client to use for network requests
     client: Option<Rc<HttpClient>>,
 
+    /// The cookies that servers have set for requests that manage cookies.
+    cookies: Rc<RefCell<CookieJar>>,
+
+    /// How many redirects a request may follow before it fails.
+    max_redirects: u32,
+
     socket_allowed: HashSet<String>,
 
     socket_mode: SocketMode,
 
+    /// Whether to ask servers for their socket policy file before connecting to them.
+    check_socket_policy: bool,
+
     upgrade_to_https: bool,
 
-    open_url_mode: OpenURLMode,
+    /// Decides which URLs the movie may open in the browser.
+    navigation_policy: NavigationPolicy,
+
+    /// The `LocalConnection` names that this player is listening with.
+    local_connections: HashMap<String, LocalConnectionListener>,
 }
 
 impl ExternalNavigatorBackend {
@@ -61,15 +88,18 @@ impl ExternalNavigatorBackend {
         channel: Sender<OwnedFuture<(), Error>>,
         event_loop: EventLoopProxy<RuffleEvent>,
         proxy: Option<Url>,
+        max_redirects: u32,
         upgrade_to_https: bool,
-        open_url_mode: OpenURLMode,
+        navigation_policy: NavigationPolicy,
         socket_allowed: HashSet<String>,
         socket_mode: SocketMode,
+        check_socket_policy: bool,
     ) -> Self {
         let proxy = proxy.and_then(|url| url.as_str().parse().ok());
+        // Redirects are followed by `fetch` itself, so that cookies are stored along the way.
         let builder = HttpClient::builder()
             .proxy(proxy)
-            .redirect_policy(RedirectPolicy::Follow);
+            .redirect_policy(RedirectPolicy::None);
 
         let client = builder.build().ok().map(Rc::new);
 
@@ -83,12 +113,334 @@ impl ExternalNavigatorBackend {
             channel,
             event_loop,
             client,
+            cookies: Default::default(),
+            max_redirects,
             base_url,
             upgrade_to_https,
-            open_url_mode,
+            navigation_policy,
             socket_allowed,
             socket_mode,
+            check_socket_policy,
+            local_connections: HashMap::new(),
+        }
+    }
+}
+
+/// How long to wait for a server to send its socket policy file.
+const SOCKET_POLICY_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// The largest socket policy file that will be accepted, in bytes.
+///
+/// Flash Player gives up on policy files larger than 20 KB, which also keeps a server
+/// from making us buffer an endless response.
+const MAX_SOCKET_POLICY_SIZE: usize = 20 * 1024;
+
+/// Requests the socket policy file of a server, first from the policy file port,
+/// then from the port that is being connected to.
+async fn fetch_socket_policy(host: &str, port: u16) -> Option<Vec<u8>> {
+    for policy_port in [POLICY_FILE_PORT, port] {
+        if let Some(policy) = request_socket_policy(host, policy_port).await {
+            return Some(policy);
+        }
+    }
+    None
+}
+
+async fn request_socket_policy(host: &str, port: u16) -> Option<Vec<u8>> {
+    let request = async {
+        let mut stream = TcpStream::connect((host, port)).await.ok()?;
+        stream.write_all(POLICY_FILE_REQUEST).await.ok()?;
+
+        // The policy is terminated by a null byte, or by the server closing the connection.
+        let mut policy = Vec::new();
+        let mut buffer = [0; 4096];
+        loop {
+            let read = stream.read(&mut buffer).await.ok()?;
+            if read == 0 {
+                break;
+            }
+            policy.extend_from_slice(&buffer[..read]);
+            if let Some(end) = policy.iter().position(|&b| b == 0) {
+                policy.truncate(end);
+                break;
+            }
+            if policy.len() > MAX_SOCKET_POLICY_SIZE {
+                break;
+            }
+        }
+        if policy.len() > MAX_SOCKET_POLICY_SIZE {
+            warn!("Socket policy from {host}:{port} is too large, ignoring it");
+            return None;
+        }
+        Some(policy)
+    };
+
+    request
+        .or(async {
+            Timer::after(SOCKET_POLICY_TIMEOUT).await;
+            None
+        })
+        .await
+}
+
+/// Passes data between a connected TCP stream and the AVM side, until either end closes
+/// the connection.
+async fn run_socket(
+    stream: impl AsyncRead + AsyncWrite + Unpin,
+    handle: SocketHandle,
+    receiver: Receiver<Vec<u8>>,
+    sender: Sender<SocketAction>,
+) {
+    //NOTE: We clone the sender here as we cant share it between async tasks.
+    let sender2 = sender.clone();
+    let (mut read, mut write) = stream.split();
+
+    let read = std::pin::pin!(async move {
+        loop {
+            let mut buffer = [0; 4096];
+
+            match read.read(&mut buffer).await {
+                Err(e) if e.kind() == ErrorKind::TimedOut => {} // try again later.
+                Err(_) | Ok(0) => {
+                    sender
+                        .send(SocketAction::Close(handle))
+                        .expect("working channel send");
+                    drop(read);
+                    break;
+                }
+                Ok(read) => {
+                    let buffer = buffer.into_iter().take(read).collect::<Vec<_>>();
+
+                    sender
+                        .send(SocketAction::Data(handle, buffer))
+                        .expect("working channel send");
+                }
+            };
+        }
+    });
+
+    let write = std::pin::pin!(async move {
+        let mut pending_write = vec![];
+
+        loop {
+            loop {
+                match receiver.try_recv() {
+                    Ok(val) => {
+                        pending_write.extend(val);
+                    }
+                    Err(TryRecvError::Disconnected) => {
+                        //NOTE: Channel sender has been dropped.
+                        //      This means we have to close the connection.
+                        drop(write);
+                        return;
+                    }
+                    Err(_) => break,
+                }
+            }
+
+            if !pending_write.is_empty() {
+                match write.write(&pending_write).await {
+                    Err(e) if e.kind() == ErrorKind::TimedOut => {} // try again later.
+                    Err(_) => {
+                        sender2
+                            .send(SocketAction::Close(handle))
+                            .expect("working channel send");
+                        drop(write);
+                        return;
+                    }
+                    Ok(written) => {
+                        let _ = pending_write.drain(..written);
+                    }
+                }
+            } else {
+                //NOTE: We wait here as if the buffer is empty the syscall (at least on linux),
+                //      will return immediately, and because of that we get stuck in a infinite loop
+                //      as we never yield to the executor.
+                Timer::after(Duration::from_millis(10)).await;
+            }
+        }
+    });
+
+    //NOTE: If one future exits, this will take the other one down too.
+    select(read, write).await;
+}
+
+/// Send an HTTP request, following its redirects and managing its cookies if it asks for that.
+///
+/// Returns the final URL, whether the request was redirected, and the successful response.
+async fn send_request(
+    client: Option<Rc<HttpClient>>,
+    cookies: Rc<RefCell<CookieJar>>,
+    max_redirects: u32,
+    upgrade_to_https: bool,
+    request: Request,
+    processed_url: Url,
+) -> Result<(String, bool, Response<AsyncBody>), ErrorResponse> {
+    let client = client.ok_or_else(|| ErrorResponse {
+        url: processed_url.to_string(),
+        error: Error::FetchError("Network unavailable".to_string()),
+    })?;
+
+    let mut url = processed_url;
+    let mut method = request.method();
+    let (mut body_data, _) = request.body().clone().unwrap_or_default();
+    let mut redirects = 0;
+    // The headers set by the movie, which may hold credentials, are only sent to the
+    // origin that the movie requested.
+    let mut send_headers = true;
+    let response = loop {
+        let mut isahc_request = match method {
+            NavigationMethod::Get => IsahcRequest::get(url.to_string()),
+            NavigationMethod::Post => IsahcRequest::post(url.to_string()),
+        };
+        if let Some(headers) = isahc_request.headers_mut() {
+            let movie_headers = request.headers().iter().filter(|_| send_headers);
+            for (name, val) in movie_headers {
+                headers.insert(
+                    HeaderName::from_str(name).map_err(|e| ErrorResponse {
+                        url: url.to_string(),
+                        error: Error::FetchError(e.to_string()),
+                    })?,
+                    HeaderValue::from_str(val).map_err(|e| ErrorResponse {
+                        url: url.to_string(),
+                        error: Error::FetchError(e.to_string()),
+                    })?,
+                );
+            }
+            if request.manage_cookies() {
+                if let Some(cookie) = cookies.borrow().cookie_header(&url) {
+                    headers.insert(
+                        COOKIE,
+                        HeaderValue::from_str(&cookie).map_err(|e| ErrorResponse {
+                            url: url.to_string(),
+                            error: Error::FetchError(e.to_string()),
+                        })?,
+                    );
+                }
+            }
+        }
+
+        let body = isahc_request
+            .body(body_data.clone())
+            .map_err(|e| ErrorResponse {
+                url: url.to_string(),
+                error: Error::FetchError(e.to_string()),
+            })?;
+
+        let response = client.send_async(body).await.map_err(|e| ErrorResponse {
+            url: url.to_string(),
+            error: Error::FetchError(e.to_string()),
+        })?;
+
+        if request.manage_cookies() {
+            let mut cookies = cookies.borrow_mut();
+            for header in response.headers().get_all(SET_COOKIE) {
+                if let Ok(header) = header.to_str() {
+                    cookies.set_cookie(&url, header);
+                }
+            }
         }
+
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .and_then(|location| url.join(location).ok());
+        match location {
+            Some(location) if request.follow_redirects() && response.status().is_redirection() => {
+                if redirects >= max_redirects {
+                    return Err(ErrorResponse {
+                        url: url.to_string(),
+                        error: Error::FetchError(format!(
+                            "Too many redirects, the limit is {max_redirects}"
+                        )),
+                    });
+                }
+                redirects += 1;
+
+                // Redirects are held to the same rules as the URL that the movie requested,
+                // and can't lead to local files or other schemes.
+                if !matches!(location.scheme(), "http" | "https") {
+                    return Err(ErrorResponse {
+                        url: url.to_string(),
+                        error: Error::FetchError(format!(
+                            "Refusing to follow a redirect to {location}"
+                        )),
+                    });
+                }
+                let location = upgrade_url(location, upgrade_to_https);
+
+                // Like browsers, only 307 and 308 redirects repeat a POST request.
+                if !matches!(response.status().as_u16(), 307 | 308) {
+                    method = NavigationMethod::Get;
+                    body_data = Vec::new();
+                }
+                // Neither the movie's headers nor the body it posted are sent to other origins.
+                if location.origin() != url.origin() {
+                    send_headers = false;
+                    body_data = Vec::new();
+                }
+                url = location;
+            }
+            _ => break response,
+        }
+    };
+
+    let url = url.to_string();
+    let status = response.status().as_u16();
+    let redirected = redirects > 0;
+    if !response.status().is_success() {
+        let error = Error::HttpNotOk(
+            format!("HTTP status is not ok, got {}", response.status()),
+            status,
+            redirected,
+        );
+        return Err(ErrorResponse { url, error });
+    }
+
+    Ok((url, redirected, response))
+}
+
+/// Upgrades an `http` URL to `https`, if the player is set to do so.
+fn upgrade_url(mut url: Url, upgrade_to_https: bool) -> Url {
+    if upgrade_to_https && url.scheme() == "http" && url.set_scheme("https").is_err() {
+        tracing::error!("Url::set_scheme failed on: {}", url);
+    }
+    url
+}
+
+/// The headers of a response, as (header_name, header_value) pairs.
+fn response_headers(response: &Response<AsyncBody>) -> Vec<(String, String)> {
+    response
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.to_string(),
+                String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            )
+        })
+        .collect()
+}
+
+/// The body of a response that is still being received from the network.
+struct NetworkResponseBody(AsyncBody);
+
+impl ResponseBody for NetworkResponseBody {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>> {
+        Box::pin(async move {
+            let mut chunk = vec![0; 64 * 1024];
+            let read = self
+                .0
+                .read(&mut chunk)
+                .await
+                .map_err(|e| Error::FetchError(e.to_string()))?;
+            if read == 0 {
+                return Ok(None);
+            }
+            chunk.truncate(read);
+            Ok(Some(chunk))
+        })
     }
 }
 
@@ -132,28 +484,40 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         };
 
         if modified_url.scheme() == "javascript" {
-            tracing::warn!(
-                "SWF tried to run a script on desktop, but javascript calls are not allowed"
-            );
+            if self.navigation_policy.javascript == JavascriptUrlMode::Deny {
+                tracing::warn!("SWF tried to run a script, but javascript: URLs are denied");
+            } else {
+                tracing::warn!(
+                    "SWF tried to run a script on desktop, but javascript calls are not allowed"
+                );
+            }
             return;
         }
 
-        if self.open_url_mode == OpenURLMode::Confirm {
-            let message = format!("The SWF file wants to open the website {}", modified_url);
-            // TODO: Add a checkbox with a GUI toolkit
-            let confirm = MessageDialog::new()
-                .set_title("Open website?")
-                .set_level(MessageLevel::Info)
-                .set_description(&message)
-                .set_buttons(MessageButtons::OkCancel)
-                .show();
-            if !confirm {
-                tracing::info!("SWF tried to open a website, but the user declined the request");
+        match self.navigation_policy.check(&modified_url) {
+            OpenURLMode::Allow => {}
+            OpenURLMode::Confirm => {
+                let message = format!("The SWF file wants to open the website {}", modified_url);
+                // TODO: Add a checkbox with a GUI toolkit
+                let confirm = MessageDialog::new()
+                    .set_title("Open website?")
+                    .set_level(MessageLevel::Info)
+                    .set_description(&message)
+                    .set_buttons(MessageButtons::OkCancel)
+                    .show();
+                if !confirm {
+                    tracing::info!(
+                        "SWF tried to open a website, but the user declined the request"
+                    );
+                    return;
+                }
+            }
+            OpenURLMode::Deny => {
+                tracing::warn!(
+                    "SWF tried to open the website {modified_url}, but it's not allowed"
+                );
                 return;
             }
-        } else if self.open_url_mode == OpenURLMode::Deny {
-            tracing::warn!("SWF tried to open a website, but opening a website is not allowed");
-            return;
         }
 
         // If the user confirmed or if in Allow mode, open the website
@@ -230,78 +594,78 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                     body,
                     status: 0,
                     redirected: false,
+                    headers: Vec::new(),
                 })
             }),
-            _ => Box::pin(async move {
-                let client = client.ok_or_else(|| ErrorResponse {
-                    url: processed_url.to_string(),
-                    error: Error::FetchError("Network unavailable".to_string()),
-                })?;
-
-                let mut isahc_request = match request.method() {
-                    NavigationMethod::Get => IsahcRequest::get(processed_url.to_string()),
-                    NavigationMethod::Post => IsahcRequest::post(processed_url.to_string()),
-                };
-                if let Some(headers) = isahc_request.headers_mut() {
-                    for (name, val) in request.headers().iter() {
-                        headers.insert(
-                            HeaderName::from_str(name).map_err(|e| ErrorResponse {
-                                url: processed_url.to_string(),
-                                error: Error::FetchError(e.to_string()),
-                            })?,
-                            HeaderValue::from_str(val).map_err(|e| ErrorResponse {
-                                url: processed_url.to_string(),
-                                error: Error::FetchError(e.to_string()),
-                            })?,
-                        );
-                    }
-                }
+            _ => {
+                let response = send_request(
+                    client,
+                    self.cookies.clone(),
+                    self.max_redirects,
+                    self.upgrade_to_https,
+                    request,
+                    processed_url,
+                );
 
-                let (body_data, _) = request.body().clone().unwrap_or_default();
-                let body = isahc_request.body(body_data).map_err(|e| ErrorResponse {
-                    url: processed_url.to_string(),
-                    error: Error::FetchError(e.to_string()),
-                })?;
-
-                let mut response = client.send_async(body).await.map_err(|e| ErrorResponse {
-                    url: processed_url.to_string(),
-                    error: Error::FetchError(e.to_string()),
-                })?;
-
-                let url = if let Some(uri) = response.effective_uri() {
-                    uri.to_string()
-                } else {
-                    processed_url.into()
-                };
+                Box::pin(async move {
+                    let (url, redirected, mut response) = response.await?;
+                    let status = response.status().as_u16();
+                    let headers = response_headers(&response);
+
+                    let mut body = vec![];
+                    response
+                        .copy_to(&mut body)
+                        .await
+                        .map_err(|e| ErrorResponse {
+                            url: url.clone(),
+                            error: Error::FetchError(e.to_string()),
+                        })?;
 
-                let status = response.status().as_u16();
-                let redirected = response.effective_uri().is_some();
-                if !response.status().is_success() {
-                    let error = Error::HttpNotOk(
-                        format!("HTTP status is not ok, got {}", response.status()),
+                    Ok(SuccessResponse {
+                        url,
+                        body,
                         status,
                         redirected,
-                    );
-                    return Err(ErrorResponse { url, error });
-                }
+                        headers,
+                    })
+                })
+            }
+        }
+    }
 
-                let mut body = vec![];
-                response
-                    .copy_to(&mut body)
-                    .await
-                    .map_err(|e| ErrorResponse {
-                        url: url.clone(),
-                        error: Error::FetchError(e.to_string()),
-                    })?;
+    fn fetch_stream(&self, request: Request) -> OwnedFuture<StreamingResponse, ErrorResponse> {
+        let processed_url = match self.resolve_url(request.url()) {
+            Ok(url) => url,
+            Err(e) => {
+                return async_return(create_fetch_error(request.url(), e));
+            }
+        };
 
-                Ok(SuccessResponse {
-                    url,
-                    body,
-                    status,
-                    redirected,
-                })
-            }),
+        // Local files are read all at once.
+        if processed_url.scheme() == "file" {
+            let fetch = self.fetch(request);
+            return Box::pin(async move { Ok(fetch.await?.into()) });
         }
+
+        let response = send_request(
+            self.client.clone(),
+            self.cookies.clone(),
+            self.max_redirects,
+            self.upgrade_to_https,
+            request,
+            processed_url,
+        );
+        Box::pin(async move {
+            let (url, redirected, response) = response.await?;
+            Ok(StreamingResponse {
+                url,
+                status: response.status().as_u16(),
+                redirected,
+                headers: response_headers(&response),
+                expected_length: response.body().len(),
+                body: Box::new(NetworkResponseBody(response.into_body())),
+            })
+        })
     }
 
     fn resolve_url(&self, url: &str) -> Result<Url, ParseError> {
@@ -321,11 +685,12 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         }
     }
 
-    fn pre_process_url(&self, mut url: Url) -> Url {
-        if self.upgrade_to_https && url.scheme() == "http" && url.set_scheme("https").is_err() {
-            tracing::error!("Url::set_scheme failed on: {}", url);
-        }
-        url
+    fn javascript_url_mode(&self) -> JavascriptUrlMode {
+        self.navigation_policy.javascript
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        upgrade_url(url, self.upgrade_to_https)
     }
 
     fn connect_socket(
@@ -336,10 +701,15 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         handle: SocketHandle,
         receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
+        kind: SocketKind,
     ) {
         let addr = format!("{}:{}", host, port);
         let is_allowed = self.socket_allowed.contains(&addr);
         let socket_mode = self.socket_mode;
+        // Flash Player doesn't ask media servers for socket policy files.
+        let check_socket_policy =
+            self.check_socket_policy && !is_allowed && kind == SocketKind::Socket;
+        let domain = self.base_url.host_str().unwrap_or("localhost").to_owned();
 
         let future = Box::pin(async move {
             match (is_allowed, socket_mode) {
@@ -372,9 +742,30 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                 }
             }
 
+            if check_socket_policy {
+                let is_allowed = match fetch_socket_policy(&host, port).await {
+                    Some(policy) => is_allowed_by_policy(&policy, &domain, port),
+                    None => false,
+                };
+
+                if !is_allowed {
+                    warn!(
+                        "Connection to {}:{} is not allowed by its socket policy file",
+                        host, port
+                    );
+                    sender
+                        .send(SocketAction::Connect(
+                            handle,
+                            ConnectionState::SecurityError,
+                        ))
+                        .expect("working channel send");
+                    return Ok(());
+                }
+            }
+
             let host2 = host.clone();
 
-            let stream = match TcpStream::connect((host, port))
+            let stream = match TcpStream::connect((host.clone(), port))
                 .or(async {
                     Timer::after(timeout).await;
                     Result::<TcpStream, io::Error>::Err(io::Error::new(ErrorKind::TimedOut, ""))
@@ -388,13 +779,7 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                         .expect("working channel send");
                     return Ok(());
                 }
-                Ok(stream) => {
-                    sender
-                        .send(SocketAction::Connect(handle, ConnectionState::Connected))
-                        .expect("working channel send");
-
-                    stream
-                }
+                Ok(stream) => stream,
                 Err(err) => {
                     warn!("Failed to connect to {}:{}, error: {}", host2, port, err);
                     sender
@@ -404,83 +789,251 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                 }
             };
 
-            let sender = sender;
-            //NOTE: We clone the sender here as we cant share it between async tasks.
-            let sender2 = sender.clone();
-            let (mut read, mut write) = stream.split();
+            if kind == SocketKind::Rtmps {
+                let stream = match async_native_tls::connect(host.as_str(), stream).await {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        warn!(
+                            "TLS handshake with {}:{} failed, error: {}",
+                            host, port, err
+                        );
+                        sender
+                            .send(SocketAction::Connect(handle, ConnectionState::Failed))
+                            .expect("working channel send");
+                        return Ok(());
+                    }
+                };
+                sender
+                    .send(SocketAction::Connect(handle, ConnectionState::Connected))
+                    .expect("working channel send");
+                run_socket(stream, handle, receiver, sender).await;
+            } else {
+                sender
+                    .send(SocketAction::Connect(handle, ConnectionState::Connected))
+                    .expect("working channel send");
+                run_socket(stream, handle, receiver, sender).await;
+            }
 
-            let read = std::pin::pin!(async move {
-                loop {
-                    let mut buffer = [0; 4096];
-
-                    match read.read(&mut buffer).await {
-                        Err(e) if e.kind() == ErrorKind::TimedOut => {} // try again later.
-                        Err(_) | Ok(0) => {
-                            sender
-                                .send(SocketAction::Close(handle))
-                                .expect("working channel send");
-                            drop(read);
-                            break;
-                        }
-                        Ok(read) => {
-                            let buffer = buffer.into_iter().take(read).collect::<Vec<_>>();
+            Ok(())
+        });
 
-                            sender
-                                .send(SocketAction::Data(handle, buffer))
-                                .expect("working channel send");
+        self.spawn_future(future);
+    }
+
+    fn bind_server_socket(
+        &mut self,
+        address: String,
+        port: u16,
+        handle: ServerSocketHandle,
+        receiver: Receiver<()>,
+        sender: Sender<SocketAction>,
+    ) -> Result<(String, u16), String> {
+        let listener =
+            std::net::TcpListener::bind((address.as_str(), port)).map_err(|e| e.to_string())?;
+        let local_addr = listener.local_addr().map_err(|e| e.to_string())?;
+        let listener = TcpListener::try_from(listener).map_err(|e| e.to_string())?;
+
+        //NOTE: Accepted connections are handled by their own tasks, which we spawn ourselves.
+        let tasks = self.channel.clone();
+        let event_loop = self.event_loop.clone();
+
+        let future = Box::pin(async move {
+            loop {
+                let accepted = listener
+                    .accept()
+                    .or(async {
+                        // Stop waiting every so often to check if the server socket was closed.
+                        Timer::after(Duration::from_millis(10)).await;
+                        Err(io::Error::new(ErrorKind::TimedOut, ""))
+                    })
+                    .await;
+
+                if let Err(TryRecvError::Disconnected) = receiver.try_recv() {
+                    //NOTE: Channel sender has been dropped.
+                    //      This means the server socket was closed.
+                    return Ok(());
+                }
+
+                let (stream, remote_addr) = match accepted {
+                    Ok(accepted) => accepted,
+                    Err(e) if e.kind() == ErrorKind::TimedOut => continue,
+                    Err(e) => {
+                        warn!("Failed to accept a connection: {}", e);
+                        continue;
+                    }
+                };
+
+                let (attach, attached) = channel();
+                sender
+                    .send(SocketAction::Accept(
+                        handle,
+                        AcceptedConnection {
+                            host: remote_addr.ip().to_string(),
+                            port: remote_addr.port(),
+                            attach,
+                        },
+                    ))
+                    .expect("working channel send");
+
+                let sender = sender.clone();
+                let connection: OwnedFuture<(), Error> = Box::pin(async move {
+                    // Wait for the movie to create a socket for this connection.
+                    let (socket_handle, receiver) = loop {
+                        match attached.try_recv() {
+                            Ok(attached) => break attached,
+                            Err(TryRecvError::Empty) => {
+                                Timer::after(Duration::from_millis(10)).await;
+                            }
+                            Err(TryRecvError::Disconnected) => return Ok(()),
                         }
                     };
+
+                    run_socket(stream, socket_handle, receiver, sender).await;
+                    Ok(())
+                });
+                tasks.send(connection).expect("working channel send");
+                if event_loop.send_event(RuffleEvent::TaskPoll).is_err() {
+                    return Ok(());
                 }
-            });
+            }
+        });
 
-            let write = std::pin::pin!(async move {
-                let mut pending_write = vec![];
+        self.spawn_future(future);
+
+        Ok((local_addr.ip().to_string(), local_addr.port()))
+    }
 
+    fn bind_datagram_socket(
+        &mut self,
+        address: String,
+        port: u16,
+        handle: DatagramSocketHandle,
+        receiver: Receiver<Datagram>,
+        sender: Sender<SocketAction>,
+    ) -> Result<(String, u16), String> {
+        let socket =
+            std::net::UdpSocket::bind((address.as_str(), port)).map_err(|e| e.to_string())?;
+        let local_addr = socket.local_addr().map_err(|e| e.to_string())?;
+        let socket = UdpSocket::try_from(socket).map_err(|e| e.to_string())?;
+
+        let future = Box::pin(async move {
+            let mut buffer = vec![0; 65536];
+
+            loop {
                 loop {
-                    loop {
-                        match receiver.try_recv() {
-                            Ok(val) => {
-                                pending_write.extend(val);
-                            }
-                            Err(TryRecvError::Disconnected) => {
-                                //NOTE: Channel sender has been dropped.
-                                //      This means we have to close the connection.
-                                drop(write);
-                                return;
+                    match receiver.try_recv() {
+                        Ok(datagram) => {
+                            let target = (datagram.address.as_str(), datagram.port);
+                            if let Err(e) = socket.send_to(&datagram.data, target).await {
+                                warn!(
+                                    "Failed to send a datagram to {}:{}: {}",
+                                    datagram.address, datagram.port, e
+                                );
                             }
-                            Err(_) => break,
                         }
-                    }
-
-                    if !pending_write.is_empty() {
-                        match write.write(&pending_write).await {
-                            Err(e) if e.kind() == ErrorKind::TimedOut => {} // try again later.
-                            Err(_) => {
-                                sender2
-                                    .send(SocketAction::Close(handle))
-                                    .expect("working channel send");
-                                drop(write);
-                                return;
-                            }
-                            Ok(written) => {
-                                let _ = pending_write.drain(..written);
-                            }
+                        Err(TryRecvError::Disconnected) => {
+                            //NOTE: Channel sender has been dropped.
+                            //      This means we have to close the socket.
+                            return Ok(());
                         }
-                    } else {
-                        //NOTE: We wait here as if the buffer is empty the syscall (at least on linux),
-                        //      will return immediately, and because of that we get stuck in a infinite loop
-                        //      as we never yield to the executor.
-                        Timer::after(Duration::from_millis(10)).await;
+                        Err(TryRecvError::Empty) => break,
                     }
                 }
-            });
 
-            //NOTE: If one future exits, this will take the other one down too.
-            select(read, write).await;
+                let received = socket
+                    .recv_from(&mut buffer)
+                    .or(async {
+                        // Stop waiting every so often to send pending datagrams.
+                        Timer::after(Duration::from_millis(10)).await;
+                        Err(io::Error::new(ErrorKind::TimedOut, ""))
+                    })
+                    .await;
 
-            Ok(())
+                match received {
+                    Ok((length, remote_addr)) => {
+                        sender
+                            .send(SocketAction::Datagram(
+                                handle,
+                                Datagram {
+                                    data: buffer[..length].to_vec(),
+                                    address: remote_addr.ip().to_string(),
+                                    port: remote_addr.port(),
+                                },
+                            ))
+                            .expect("working channel send");
+                    }
+                    Err(e) if e.kind() == ErrorKind::TimedOut => {}
+                    Err(e) => warn!("Failed to receive a datagram: {}", e),
+                }
+            }
         });
 
         self.spawn_future(future);
+
+        Ok((local_addr.ip().to_string(), local_addr.port()))
+    }
+
+    fn network_interfaces(&self) -> Vec<NetworkInterface> {
+        let addrs = match if_addrs::get_if_addrs() {
+            Ok(addrs) => addrs,
+            Err(e) => {
+                warn!("Failed to list network interfaces: {}", e);
+                return vec![];
+            }
+        };
+
+        let mut interfaces: Vec<NetworkInterface> = vec![];
+        for addr in addrs {
+            let address = match &addr.addr {
+                if_addrs::IfAddr::V4(v4) => InterfaceAddress {
+                    address: v4.ip.to_string(),
+                    broadcast: v4.broadcast.map(|b| b.to_string()).unwrap_or_default(),
+                    ip_version: "IPv4",
+                    prefix_length: u32::from(v4.netmask).count_ones() as i32,
+                },
+                if_addrs::IfAddr::V6(v6) => InterfaceAddress {
+                    address: v6.ip.to_string(),
+                    broadcast: String::new(),
+                    ip_version: "IPv6",
+                    prefix_length: u128::from(v6.netmask).count_ones() as i32,
+                },
+            };
+
+            // Each address of an interface is listed separately.
+            match interfaces.iter_mut().find(|i| i.name == addr.name) {
+                Some(interface) => interface.addresses.push(address),
+                None => interfaces.push(NetworkInterface {
+                    name: addr.name.clone(),
+                    display_name: addr.name,
+                    active: true,
+                    hardware_address: String::new(),
+                    mtu: -1,
+                    addresses: vec![address],
+                }),
+            }
+        }
+        interfaces
+    }
+
+    fn listen_local_connection(
+        &mut self,
+        name: &str,
+        sender: Sender<LocalConnectionMessage>,
+    ) -> bool {
+        match LocalConnectionListener::listen(name, sender) {
+            Some(listener) => {
+                self.local_connections.insert(name.to_string(), listener);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn close_local_connection(&mut self, name: &str) {
+        self.local_connections.remove(name);
+    }
+
+    fn send_local_connection(&mut self, name: &str, data: Vec<u8>) -> bool {
+        local_connection::send(name, &data)
     }
 }
@@ -1,39 +1,103 @@
+#[cfg(feature = "camera")]
+use super::camera::{camera_names, Webcam};
+use crate::accessibility::AccessibilityAdapter;
+use crate::gui::MENU_HEIGHT;
 use anyhow::{Context, Error};
-use arboard::Clipboard;
-use rfd::{MessageButtons, MessageDialog, MessageLevel};
+use arboard::{Clipboard, ImageData};
+use chrono::{DateTime, TimeZone, Utc};
+use fontdb::{Database, Family, Query, Style, Weight};
+use rfd::{AsyncFileDialog, FileHandle, MessageButtons, MessageDialog, MessageLevel};
+use ruffle_core::accessibility::AccessibilityNode;
+#[cfg(feature = "camera")]
+use ruffle_core::backend::ui::CameraState;
 use ruffle_core::backend::ui::{
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    CustomMouseCursor, DialogResultFuture, FileFilter, FontDefinition, FullscreenError,
+    LanguageIdentifier, MouseCursor, SelectedFile, UiBackend, US_ENGLISH,
 };
+use ruffle_core::printing::{self, PrintPage};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat};
+use std::borrow::Cow;
+use std::cell::OnceCell;
+use std::path::Path;
+use std::process::Command;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use sys_locale::get_locale;
 use tracing::error;
 use winit::window::{Fullscreen, Window};
 
 pub struct DesktopUiBackend {
     window: Rc<Window>,
+    accessibility: Rc<AccessibilityAdapter>,
     cursor_visible: bool,
     clipboard: Clipboard,
     language: LanguageIdentifier,
     preferred_cursor: MouseCursor,
+
+    /// The custom cursor set with `Mouse.cursor`, which the GUI draws in place of the
+    /// system cursor, as winit can't set cursor images.
+    custom_cursor: Option<CustomMouseCursor>,
+
+    /// Incremented whenever `custom_cursor` changes, so that the GUI knows when to
+    /// upload it again.
+    custom_cursor_generation: u64,
+
+    /// The fonts installed on the system, loaded on first use.
+    font_database: OnceCell<Database>,
+
+    /// Whether a file dialog is open, as only one can be at a time.
+    file_dialog_open: Arc<AtomicBool>,
+
+    /// The capture of a camera, if any.
+    #[cfg(feature = "camera")]
+    webcam: Option<Webcam>,
+
+    /// Whether the user has allowed or denied access to cameras.
+    #[cfg(feature = "camera")]
+    camera_allowed: Option<bool>,
 }
 
 impl DesktopUiBackend {
-    pub fn new(window: Rc<Window>) -> Result<Self, Error> {
+    pub fn new(window: Rc<Window>, accessibility: Rc<AccessibilityAdapter>) -> Result<Self, Error> {
         let preferred_language = get_locale();
         let language = preferred_language
             .and_then(|l| l.parse().ok())
             .unwrap_or_else(|| US_ENGLISH.clone());
         Ok(Self {
             window,
+            accessibility,
             cursor_visible: true,
             clipboard: Clipboard::new().context("Couldn't get platform clipboard")?,
             language,
             preferred_cursor: MouseCursor::Arrow,
+            custom_cursor: None,
+            custom_cursor_generation: 0,
+            font_database: OnceCell::new(),
+            file_dialog_open: Arc::new(AtomicBool::new(false)),
+            #[cfg(feature = "camera")]
+            webcam: None,
+            #[cfg(feature = "camera")]
+            camera_allowed: None,
         })
     }
 
+    fn font_database(&self) -> &Database {
+        self.font_database.get_or_init(|| {
+            let mut font_database = Database::new();
+            font_database.load_system_fonts();
+            font_database
+        })
+    }
+
+    /// Marks a file dialog as open, returning `false` if one already was.
+    fn open_file_dialog(&self) -> bool {
+        !self.file_dialog_open.swap(true, Ordering::Relaxed)
+    }
+
     pub fn cursor(&self) -> egui::CursorIcon {
-        if self.cursor_visible {
+        if self.cursor_visible && self.custom_cursor.is_none() {
             match self.preferred_cursor {
                 MouseCursor::Arrow => egui::CursorIcon::Default,
                 MouseCursor::Hand => egui::CursorIcon::PointingHand,
@@ -44,6 +108,64 @@ impl DesktopUiBackend {
             egui::CursorIcon::None
         }
     }
+
+    /// The custom cursor to draw, along with how many times it has changed.
+    pub fn custom_cursor(&self) -> Option<(u64, &CustomMouseCursor)> {
+        if self.cursor_visible {
+            self.custom_cursor
+                .as_ref()
+                .map(|cursor| (self.custom_cursor_generation, cursor))
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) fn to_date_time(time: std::io::Result<SystemTime>) -> Option<DateTime<Utc>> {
+    let millis = time.ok()?.duration_since(UNIX_EPOCH).ok()?.as_millis();
+    Utc.timestamp_millis_opt(millis as i64).single()
+}
+
+/// Reads a file that the user picked in a file dialog.
+/// Sends a document to the system's default printer.
+fn print_file(path: &Path) -> std::io::Result<()> {
+    let status = if cfg!(windows) {
+        // Windows prints through the application associated with the file.
+        let command = format!(
+            "Start-Process -Verb Print -FilePath '{}'",
+            path.display().to_string().replace('\'', "''")
+        );
+        Command::new("powershell")
+            .args(["-NoProfile", "-Command", &command])
+            .status()?
+    } else {
+        // Linux and macOS both print through CUPS.
+        Command::new("lpr").arg(path).status()?
+    };
+    if !status.success() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("printing exited with {status}"),
+        ));
+    }
+    Ok(())
+}
+
+fn read_selected_file(file: FileHandle) -> Option<SelectedFile> {
+    let path = file.path();
+    let read = std::fs::read(path).and_then(|contents| Ok((contents, std::fs::metadata(path)?)));
+    match read {
+        Ok((contents, metadata)) => Some(SelectedFile {
+            name: file.file_name(),
+            creation_date: to_date_time(metadata.created()),
+            modification_date: to_date_time(metadata.modified()),
+            contents,
+        }),
+        Err(e) => {
+            error!("Couldn't read {}: {}", path.display(), e);
+            None
+        }
+    }
 }
 
 const DOWNLOAD_FAILED_MESSAGE: &str = "Ruffle failed to open or download this file.";
@@ -61,6 +183,11 @@ impl UiBackend for DesktopUiBackend {
         self.preferred_cursor = cursor;
     }
 
+    fn set_custom_mouse_cursor(&mut self, cursor: Option<&CustomMouseCursor>) {
+        self.custom_cursor = cursor.cloned();
+        self.custom_cursor_generation = self.custom_cursor_generation.wrapping_add(1);
+    }
+
     fn clipboard_content(&mut self) -> String {
         self.clipboard.get_text().unwrap_or_default()
     }
@@ -71,6 +198,27 @@ impl UiBackend for DesktopUiBackend {
         }
     }
 
+    fn clipboard_image(&mut self) -> Option<Bitmap> {
+        let image = self.clipboard.get_image().ok()?;
+        Some(Bitmap::new(
+            image.width as u32,
+            image.height as u32,
+            BitmapFormat::Rgba,
+            image.bytes.into_owned(),
+        ))
+    }
+
+    fn set_clipboard_image(&mut self, image: Bitmap) {
+        let image = ImageData {
+            width: image.width() as usize,
+            height: image.height() as usize,
+            bytes: Cow::Borrowed(image.data()),
+        };
+        if let Err(e) = self.clipboard.set_image(image) {
+            error!("Couldn't set clipboard image: {:?}", e);
+        }
+    }
+
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
         self.window.set_fullscreen(if is_full {
             Some(Fullscreen::Borderless(None))
@@ -80,6 +228,11 @@ impl UiBackend for DesktopUiBackend {
         Ok(())
     }
 
+    fn screen_size(&self) -> Option<(u32, u32)> {
+        let size = self.window.current_monitor()?.size();
+        Some((size.width, size.height))
+    }
+
     fn display_root_movie_download_failed_message(&self) {
         let dialog = MessageDialog::new()
             .set_level(MessageLevel::Warning)
@@ -104,4 +257,178 @@ impl UiBackend for DesktopUiBackend {
     fn language(&self) -> &LanguageIdentifier {
         &self.language
     }
+
+    fn load_device_font(
+        &self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<FontDefinition> {
+        let font_database = self.font_database();
+        let query = Query {
+            families: &[Family::Name(name)],
+            weight: if is_bold {
+                Weight::BOLD
+            } else {
+                Weight::NORMAL
+            },
+            style: if is_italic {
+                Style::Italic
+            } else {
+                Style::Normal
+            },
+            ..Query::default()
+        };
+        let id = font_database.query(&query)?;
+        font_database.with_face_data(id, |data, index| FontDefinition {
+            name: name.to_string(),
+            is_bold,
+            is_italic,
+            data: data.to_vec(),
+            index,
+        })
+    }
+
+    fn is_accessibility_active(&self) -> bool {
+        self.accessibility.is_active()
+    }
+
+    fn update_accessibility_tree(&mut self, tree: AccessibilityNode) {
+        // The movie is drawn below the menu bar, unless it's fullscreen.
+        let y_offset = if self.window.fullscreen().is_some() {
+            0.0
+        } else {
+            MENU_HEIGHT as f64 * self.window.scale_factor()
+        };
+        self.accessibility.update(&tree, y_offset);
+    }
+
+    fn device_font_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .font_database()
+            .faces()
+            .filter_map(|face| face.families.first().map(|(name, _)| name.clone()))
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    #[cfg(feature = "camera")]
+    fn camera_names(&self) -> Vec<String> {
+        camera_names()
+    }
+
+    #[cfg(feature = "camera")]
+    fn start_camera(&mut self, index: usize, width: u32, height: u32, fps: f64) {
+        self.webcam = None;
+
+        // Like Flash Player's privacy dialog, ask once before any camera is used.
+        let allowed = *self.camera_allowed.get_or_insert_with(|| {
+            MessageDialog::new()
+                .set_title("Allow camera access?")
+                .set_level(MessageLevel::Info)
+                .set_description("The SWF file wants to access your camera.")
+                .set_buttons(MessageButtons::YesNo)
+                .show()
+        });
+        if !allowed {
+            return;
+        }
+
+        match Webcam::start(index, width, height, fps) {
+            Ok(webcam) => self.webcam = Some(webcam),
+            Err(e) => error!("Couldn't capture from camera: {}", e),
+        }
+    }
+
+    #[cfg(feature = "camera")]
+    fn stop_camera(&mut self) {
+        self.webcam = None;
+    }
+
+    #[cfg(feature = "camera")]
+    fn camera_state(&self) -> CameraState {
+        match (&self.webcam, self.camera_allowed) {
+            (_, Some(false)) => CameraState::Denied,
+            (Some(_), _) => CameraState::Allowed,
+            (None, _) => CameraState::Unavailable,
+        }
+    }
+
+    #[cfg(feature = "camera")]
+    fn take_camera_frame(&mut self) -> Option<Bitmap> {
+        self.webcam.as_ref().and_then(Webcam::take_frame)
+    }
+
+    fn can_print(&self) -> bool {
+        true
+    }
+
+    fn print(&mut self, pages: Vec<PrintPage>) {
+        // Encoding the pages at printer resolution is slow, so it's kept off the main thread.
+        std::thread::spawn(move || {
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |time| time.as_millis());
+            let path = std::env::temp_dir().join(format!("ruffle-print-{timestamp}.pdf"));
+            if let Err(e) = std::fs::write(&path, printing::to_pdf(pages)) {
+                error!("Couldn't write {}: {}", path.display(), e);
+                return;
+            }
+            if let Err(e) = print_file(&path) {
+                error!("Couldn't print {}: {}", path.display(), e);
+            }
+        });
+    }
+
+    fn display_file_open_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> Option<DialogResultFuture<Vec<SelectedFile>>> {
+        if !self.open_file_dialog() {
+            return None;
+        }
+
+        let mut dialog = AsyncFileDialog::new();
+        for filter in &filters {
+            let extensions: Vec<&str> = filter.extensions.iter().map(String::as_str).collect();
+            dialog = dialog.add_filter(&filter.description, &extensions);
+        }
+
+        let file_dialog_open = self.file_dialog_open.clone();
+        Some(Box::pin(async move {
+            let files = if multiple {
+                dialog.pick_files().await.unwrap_or_default()
+            } else {
+                dialog.pick_file().await.into_iter().collect()
+            };
+            file_dialog_open.store(false, Ordering::Relaxed);
+            files.into_iter().filter_map(read_selected_file).collect()
+        }))
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        file_name: String,
+        data: Vec<u8>,
+    ) -> Option<DialogResultFuture<Option<String>>> {
+        if !self.open_file_dialog() {
+            return None;
+        }
+
+        let dialog = AsyncFileDialog::new().set_file_name(&file_name);
+        let file_dialog_open = self.file_dialog_open.clone();
+        Some(Box::pin(async move {
+            let file = dialog.save_file().await;
+            file_dialog_open.store(false, Ordering::Relaxed);
+            let file = file?;
+            if let Err(e) = std::fs::write(file.path(), data) {
+                error!("Couldn't write {}: {}", file.path().display(), e);
+                return None;
+            }
+            Some(file.file_name())
+        }))
+    }
 }
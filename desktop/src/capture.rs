@@ -0,0 +1,356 @@
+//! Screenshots and video recordings of the movie.
+//!
+//! Videos are encoded by `ffmpeg`, which is sent every rendered frame over a pipe. The audio that
+//! plays meanwhile is written to a WAV file, and the two are combined once the recording stops.
+
+use crate::backends::AudioTap;
+use crate::gui::MovieView;
+use anyhow::{anyhow, Context, Error};
+use chrono::Local;
+use image::RgbaImage;
+use ruffle_render_wgpu::descriptors::Descriptors;
+use std::fs::File;
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::thread::{self, JoinHandle};
+
+/// How many frames can wait for `ffmpeg` before new ones are dropped.
+const QUEUED_FRAMES: usize = 8;
+
+pub struct Capture {
+    /// Where screenshots and recordings are saved.
+    directory: PathBuf,
+
+    /// The `ffmpeg` executable to encode videos with.
+    ffmpeg: PathBuf,
+
+    screenshot_requested: bool,
+    recording: Option<Recording>,
+}
+
+impl Capture {
+    pub fn new(directory: Option<PathBuf>, ffmpeg: PathBuf) -> Self {
+        let directory = directory.unwrap_or_else(|| {
+            dirs::picture_dir()
+                .or_else(dirs::home_dir)
+                .unwrap_or_default()
+                .join("Ruffle")
+        });
+        Self {
+            directory,
+            ffmpeg,
+            screenshot_requested: false,
+            recording: None,
+        }
+    }
+
+    /// Save the next frame that's rendered as a PNG.
+    pub fn request_screenshot(&mut self) {
+        self.screenshot_requested = true;
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Start recording a video of the frames that are rendered and of `audio`, if given.
+    pub fn start_recording(&mut self, audio: Option<AudioTap>) -> Result<(), Error> {
+        if self.recording.is_some() {
+            return Ok(());
+        }
+        let path = self.new_path("mp4")?;
+        self.recording = Some(Recording::new(&self.ffmpeg, path, audio)?);
+        Ok(())
+    }
+
+    /// Stop recording, and finish the video in the background.
+    pub fn stop_recording(&mut self) {
+        if let Some(recording) = self.recording.take() {
+            thread::spawn(move || match recording.finish() {
+                Ok(path) => tracing::info!("Saved recording to {}", path.display()),
+                Err(e) => tracing::error!("Couldn't save recording: {e:#}"),
+            });
+        }
+    }
+
+    /// Capture the frame that was just rendered to `movie_view`, if it's wanted.
+    pub fn on_frame_rendered(&mut self, movie_view: &MovieView, descriptors: &Descriptors) {
+        if !self.screenshot_requested && self.recording.is_none() {
+            return;
+        }
+        let image = movie_view.capture(descriptors);
+
+        if std::mem::take(&mut self.screenshot_requested) {
+            match self.save_screenshot(&image) {
+                Ok(path) => tracing::info!("Saved screenshot to {}", path.display()),
+                Err(e) => tracing::error!("Couldn't save screenshot: {e:#}"),
+            }
+        }
+
+        if let Some(recording) = &mut self.recording {
+            if let Err(e) = recording.push_frame(image) {
+                tracing::error!("Recording stopped: {e:#}");
+                self.stop_recording();
+            }
+        }
+    }
+
+    fn save_screenshot(&self, image: &RgbaImage) -> Result<PathBuf, Error> {
+        let path = self.new_path("png")?;
+        image.save(&path)?;
+        Ok(path)
+    }
+
+    /// A path in the capture directory that's named after the current time.
+    fn new_path(&self, extension: &str) -> Result<PathBuf, Error> {
+        std::fs::create_dir_all(&self.directory)
+            .with_context(|| format!("Couldn't create {}", self.directory.display()))?;
+        let name = Local::now().format("Ruffle %Y-%m-%d %H-%M-%S");
+        Ok(self.directory.join(format!("{name}.{extension}")))
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        // Finish the video on this thread, as the process is about to exit.
+        if let Some(recording) = self.recording.take() {
+            if let Err(e) = recording.finish() {
+                tracing::error!("Couldn't save recording: {e:#}");
+            }
+        }
+    }
+}
+
+/// A video that's being recorded.
+struct Recording {
+    ffmpeg: PathBuf,
+
+    /// Where the finished video is saved.
+    path: PathBuf,
+
+    /// The video without audio, which `ffmpeg` encodes as it goes.
+    video_path: PathBuf,
+
+    /// Sends frames to the thread that writes them to `ffmpeg`, once it has started.
+    encoder: Option<Encoder>,
+
+    audio: Option<(AudioTap, WavWriter)>,
+}
+
+/// A running `ffmpeg` that encodes frames of a fixed size.
+struct Encoder {
+    width: u32,
+    height: u32,
+    frames: SyncSender<RgbaImage>,
+    writer: JoinHandle<io::Result<()>>,
+    process: Child,
+}
+
+impl Recording {
+    fn new(ffmpeg: &Path, path: PathBuf, audio: Option<AudioTap>) -> Result<Self, Error> {
+        // Check for ffmpeg now rather than on the first frame, so that the user hears about it.
+        Command::new(ffmpeg)
+            .arg("-version")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .with_context(|| format!("Couldn't run {}", ffmpeg.display()))?;
+
+        let audio = match audio {
+            Some(tap) => {
+                let writer =
+                    WavWriter::create(&path.with_extension("wav"), tap.channels, tap.sample_rate)?;
+                tap.start();
+                Some((tap, writer))
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            ffmpeg: ffmpeg.to_owned(),
+            video_path: path.with_extension("video.mkv"),
+            path,
+            encoder: None,
+            audio,
+        })
+    }
+
+    fn push_frame(&mut self, image: RgbaImage) -> Result<(), Error> {
+        if let Some((tap, writer)) = &mut self.audio {
+            writer.write(&tap.take())?;
+        }
+
+        if self.encoder.is_none() {
+            self.encoder = Some(Encoder::start(
+                &self.ffmpeg,
+                &self.video_path,
+                image.width(),
+                image.height(),
+            )?);
+        }
+        let encoder = self.encoder.as_mut().expect("Encoder was just started");
+        // The video keeps the size it started with, even if the window is resized.
+        let image = if image.dimensions() != (encoder.width, encoder.height) {
+            image::imageops::resize(
+                &image,
+                encoder.width,
+                encoder.height,
+                image::imageops::FilterType::Triangle,
+            )
+        } else {
+            image
+        };
+        match encoder.frames.try_send(image) {
+            Ok(()) | Err(TrySendError::Full(_)) => Ok(()),
+            Err(TrySendError::Disconnected(_)) => Err(anyhow!("ffmpeg stopped unexpectedly")),
+        }
+    }
+
+    /// Wait for the video to be encoded, then add the audio to it.
+    fn finish(mut self) -> Result<PathBuf, Error> {
+        let audio = match self.audio.take() {
+            Some((tap, mut writer)) => {
+                writer.write(&tap.take())?;
+                tap.stop();
+                writer.finish()?;
+                Some(self.path.with_extension("wav"))
+            }
+            None => None,
+        };
+        let encoder = self
+            .encoder
+            .take()
+            .ok_or_else(|| anyhow!("No frames were recorded"))?;
+        encoder.finish()?;
+
+        let mut command = Command::new(&self.ffmpeg);
+        command
+            .args(["-loglevel", "error", "-y", "-i"])
+            .arg(&self.video_path);
+        if let Some(audio) = &audio {
+            command
+                .arg("-i")
+                .arg(audio)
+                .args(["-c:a", "aac", "-shortest"]);
+        }
+        let status = command
+            .args(["-c:v", "copy"])
+            .arg(&self.path)
+            .status()
+            .context("Couldn't run ffmpeg")?;
+        if !status.success() {
+            return Err(anyhow!("ffmpeg failed with {status}"));
+        }
+
+        let _ = std::fs::remove_file(&self.video_path);
+        if let Some(audio) = audio {
+            let _ = std::fs::remove_file(audio);
+        }
+        Ok(self.path)
+    }
+}
+
+impl Encoder {
+    fn start(ffmpeg: &Path, path: &Path, width: u32, height: u32) -> Result<Self, Error> {
+        let mut process = Command::new(ffmpeg)
+            .args(["-loglevel", "error", "-y"])
+            // Frames aren't rendered at a steady rate, so time them as they arrive.
+            .args(["-use_wallclock_as_timestamps", "1"])
+            .args(["-f", "rawvideo", "-pix_fmt", "rgba"])
+            .args(["-s", &format!("{width}x{height}")])
+            .args(["-i", "-"])
+            .args([
+                "-c:v", "libx264", "-preset", "veryfast", "-pix_fmt", "yuv420p",
+            ])
+            // libx264 needs even dimensions.
+            .args(["-vf", "pad=ceil(iw/2)*2:ceil(ih/2)*2"])
+            .arg(path)
+            .stdin(Stdio::piped())
+            .spawn()
+            .context("Couldn't start ffmpeg")?;
+        let stdin = process.stdin.take().expect("ffmpeg stdin must be piped");
+
+        // Writing frames can block while ffmpeg catches up, which mustn't hold up the movie.
+        let (frames, receiver) = mpsc::sync_channel(QUEUED_FRAMES);
+        let writer = thread::spawn(move || write_frames(stdin, receiver));
+
+        Ok(Self {
+            width,
+            height,
+            frames,
+            writer,
+            process,
+        })
+    }
+
+    fn finish(mut self) -> Result<(), Error> {
+        drop(self.frames);
+        self.writer
+            .join()
+            .map_err(|_| anyhow!("Frame writer panicked"))?
+            .context("Couldn't send frames to ffmpeg")?;
+        let status = self.process.wait()?;
+        if !status.success() {
+            return Err(anyhow!("ffmpeg failed with {status}"));
+        }
+        Ok(())
+    }
+}
+
+fn write_frames(mut stdin: ChildStdin, frames: mpsc::Receiver<RgbaImage>) -> io::Result<()> {
+    for frame in frames {
+        stdin.write_all(frame.as_raw())?;
+    }
+    // Closing stdin tells ffmpeg that the video is over.
+    Ok(())
+}
+
+/// Writes 32-bit float samples to a WAV file as they arrive.
+struct WavWriter {
+    file: BufWriter<File>,
+    data_size: u32,
+}
+
+impl WavWriter {
+    fn create(path: &Path, channels: u16, sample_rate: u32) -> Result<Self, Error> {
+        let file =
+            File::create(path).with_context(|| format!("Couldn't create {}", path.display()))?;
+        let mut file = BufWriter::new(file);
+        let block_align = channels * 4;
+
+        // The sizes are filled in by `finish`.
+        file.write_all(b"RIFF")?;
+        file.write_all(&0u32.to_le_bytes())?;
+        file.write_all(b"WAVEfmt ")?;
+        file.write_all(&16u32.to_le_bytes())?;
+        // IEEE float
+        file.write_all(&3u16.to_le_bytes())?;
+        file.write_all(&channels.to_le_bytes())?;
+        file.write_all(&sample_rate.to_le_bytes())?;
+        file.write_all(&(sample_rate * u32::from(block_align)).to_le_bytes())?;
+        file.write_all(&block_align.to_le_bytes())?;
+        file.write_all(&32u16.to_le_bytes())?;
+        file.write_all(b"data")?;
+        file.write_all(&0u32.to_le_bytes())?;
+
+        Ok(Self { file, data_size: 0 })
+    }
+
+    fn write(&mut self, samples: &[f32]) -> io::Result<()> {
+        for sample in samples {
+            self.file.write_all(&sample.to_le_bytes())?;
+        }
+        self.data_size += samples.len() as u32 * 4;
+        Ok(())
+    }
+
+    fn finish(mut self) -> io::Result<()> {
+        self.file.seek(SeekFrom::Start(4))?;
+        self.file.write_all(&(36 + self.data_size).to_le_bytes())?;
+        self.file.seek(SeekFrom::Start(40))?;
+        self.file.write_all(&self.data_size.to_le_bytes())?;
+        self.file.flush()
+    }
+}
@@ -1,12 +1,14 @@
+use crate::control::ControlChannel;
 use crate::RUFFLE_VERSION;
-use anyhow::Error;
-use clap::Parser;
-use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
-use ruffle_core::config::Letterbox;
+use anyhow::{anyhow, Error};
+use clap::{Parser, ValueEnum};
+use ruffle_core::backend::navigator::{JavascriptUrlMode, OpenURLMode, SocketMode};
+use ruffle_core::config::{FrameRateMode, Letterbox};
+use ruffle_core::events::{GamepadButton, KeyCode};
 use ruffle_core::{LoadBehavior, StageAlign, StageScaleMode};
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use url::Url;
 
 #[derive(Parser, Debug)]
@@ -56,6 +58,11 @@ pub struct Opt {
     #[clap(long, short, default_value = "high")]
     pub quality: StageQuality,
 
+    /// Blur filters larger than this many pixels are applied at a reduced resolution, which is
+    /// faster but less accurate. By default, every blur is applied at full resolution.
+    #[clap(long, value_name = "PIXELS")]
+    pub blur_downsample_threshold: Option<f32>,
+
     /// The alignment of the stage.
     #[clap(long, short)]
     pub align: Option<StageAlign>,
@@ -85,6 +92,10 @@ pub struct Opt {
     #[clap(long)]
     pub proxy: Option<Url>,
 
+    /// How many redirects a network request may follow before it fails.
+    #[clap(long, default_value = "20")]
+    pub max_redirects: u32,
+
     /// Add an endpoint (`[host]:[port]`) to the socket whitelist.
     #[clap(long = "socket-allow", number_of_values = 1, action = clap::ArgAction::Append)]
     pub socket_allow: Vec<String>,
@@ -93,6 +104,12 @@ pub struct Opt {
     #[clap(long = "tcp-connections", default_value = "ask")]
     pub tcp_connections: SocketMode,
 
+    /// Request a socket policy file from servers before connecting to them, and refuse
+    /// connections that it doesn't allow, as Flash Player does in web browsers.
+    /// Endpoints in the socket whitelist are always allowed.
+    #[clap(long, action)]
+    pub check_socket_policy: bool,
+
     /// Replace all embedded HTTP URLs with HTTPS.
     #[clap(long, action)]
     pub upgrade_to_https: bool,
@@ -104,6 +121,30 @@ pub struct Opt {
     #[clap(long, action)]
     pub timedemo: bool,
 
+    /// Run the movie headlessly for this many frames, as fast as possible, and then exit.
+    /// Scripts only see time pass as frames run, so runs with the same input are repeatable.
+    #[clap(long)]
+    pub headless_frames: Option<u32>,
+
+    /// A script of input events to send while running headlessly.
+    /// Each line is a frame number followed by an event, such as "10 mouse_down 320 240 left".
+    #[clap(long, requires = "headless_frames")]
+    pub input_script: Option<PathBuf>,
+
+    /// Frames to capture while running headlessly, as a comma separated list.
+    /// The hash of each captured frame is printed, and the frame is saved to `--capture-dir` if given.
+    #[clap(long, value_delimiter = ',', requires = "headless_frames")]
+    pub capture_frames: Vec<u32>,
+
+    /// Directory to save frames captured while running headlessly to.
+    /// Screenshots and recordings are saved here too, or to a "Ruffle" folder in the user's pictures if it isn't given.
+    #[clap(long)]
+    pub capture_dir: Option<PathBuf>,
+
+    /// The ffmpeg executable to encode recordings with.
+    #[clap(long, default_value = "ffmpeg")]
+    pub ffmpeg: PathBuf,
+
     #[clap(long, default_value = "streaming")]
     pub load_behavior: LoadBehavior,
 
@@ -123,21 +164,102 @@ pub struct Opt {
     #[clap(long)]
     pub frame_rate: Option<f64>,
 
+    /// How often frames of the movie are run.
+    /// "native" runs them at the movie's frame rate, catching up if the player falls behind,
+    /// "vsync" runs one frame per refresh of the display, and "unlimited" runs them as fast as possible.
+    #[clap(long, default_value = "native")]
+    pub frame_rate_mode: FrameRateMode,
+
+    /// How far ahead, in milliseconds, sounds generated with SampleDataEvent are buffered.
+    /// Lower values reduce latency, but may cause glitches in slow movies.
+    #[clap(long, default_value = "200")]
+    pub sample_data_latency: u64,
+
+    /// Keep the journal needed to save and restore states of the movie from the Controls menu.
+    /// States are restored by replaying the movie from the start, which takes longer the longer it had run.
+    #[clap(long, action)]
+    pub save_states: bool,
+
+    /// Record all input the movie receives to the given file, so that it can be replayed with --replay.
+    /// The movie runs on a fixed clock while recording, one frame's worth of time per frame.
+    #[clap(long, value_name = "PATH")]
+    pub record: Option<PathBuf>,
+
+    /// Replay input that was recorded with --record, ignoring live input until the recording ends.
+    #[clap(long, value_name = "PATH", conflicts_with = "record")]
+    pub replay: Option<PathBuf>,
+
+    /// Listen on this local port for debuggers that speak the Debug Adapter Protocol, such as VS Code.
+    /// A movie stops at its breakpoints until the debugger resumes it.
+    /// The attach or launch request must include the token that is written to "debugger-token" in the data directory.
+    #[clap(long, value_name = "PORT")]
+    pub debug_port: Option<u16>,
+
+    /// Accept JSON-RPC requests to control the player, one per line, from "stdio" or from connections to a local port.
+    /// Other programs can use this to load movies, pause them, send input and take screenshots.
+    /// Each request must include the token that is written to "control-token" in the data directory.
+    #[clap(long, value_name = "stdio|PORT")]
+    pub control: Option<ControlChannel>,
+
     /// The handling mode of links opening a new website.
     #[clap(long, default_value = "allow")]
     pub open_url_mode: OpenURLMode,
 
+    /// Always allow links to this domain (and its subdomains), or to this scheme if it ends
+    /// with a colon, such as "mailto:".
+    #[clap(long = "open-url-allow", number_of_values = 1, action = clap::ArgAction::Append)]
+    pub open_url_allow: Vec<String>,
+
+    /// Never allow links to this domain (and its subdomains), or to this scheme if it ends
+    /// with a colon. This takes precedence over --open-url-allow.
+    #[clap(long = "open-url-deny", number_of_values = 1, action = clap::ArgAction::Append)]
+    pub open_url_deny: Vec<String>,
+
+    /// The handling mode of javascript: URLs, which can't run outside of a browser.
+    /// With "external-interface", calls such as "javascript:showScore(10)" are made through
+    /// the External Interface instead.
+    #[clap(long, default_value = "navigate")]
+    pub javascript_urls: JavascriptUrlMode,
+
     /// Provide a dummy (completely empty) External Interface to the movie.
     /// This may break some movies that expect an External Interface to be functional,
     /// but may fix others that always require an External Interface.
     #[clap(long)]
     pub dummy_external_interface: bool,
+
+    /// Give AIR movies access to files through `flash.filesystem.File` and `FileStream`,
+    /// confined to the given directory. The directory of the movie itself is readable as
+    /// `File.applicationDirectory`. Without this option, AIR movies can't access any files.
+    #[clap(long, value_name = "DIRECTORY")]
+    pub air_sandbox: Option<PathBuf>,
+
+    /// Press a key when a gamepad button is pressed, for movies that don't support gamepads.
+    /// The button is named after its position, such as "south" or "d-pad-up",
+    /// and the key is given as a Flash key code, such as "32" for space.
+    ///
+    /// Example: --gamepad-button south=32 --gamepad-button d-pad-left=37
+    #[clap(long, value_name = "BUTTON=KEY", value_parser(parse_gamepad_button))]
+    pub gamepad_button: Vec<(GamepadButton, KeyCode)>,
 }
 
 fn parse_movie_file_or_url(path: &str) -> Result<Url, Error> {
     crate::util::parse_url(Path::new(path))
 }
 
+fn parse_gamepad_button(mapping: &str) -> Result<(GamepadButton, KeyCode), Error> {
+    let (button, key) = mapping
+        .split_once('=')
+        .ok_or_else(|| anyhow!("Expected BUTTON=KEY"))?;
+    let button = GamepadButton::from_str(button, true).map_err(|e| anyhow!(e))?;
+    let key = key
+        .parse::<u8>()
+        .ok()
+        .and_then(KeyCode::from_u8)
+        .filter(|key| *key != KeyCode::Unknown)
+        .ok_or_else(|| anyhow!("Unknown key code \"{key}\""))?;
+    Ok((button, key))
+}
+
 impl Opt {
     #[cfg(feature = "render_trace")]
     pub fn trace_path(&self) -> Option<&Path> {
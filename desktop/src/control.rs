@@ -0,0 +1,240 @@
+//! A control channel that lets other programs, such as game launchers, drive the player.
+//!
+//! Requests and responses are JSON-RPC 2.0 messages, one per line, sent either over standard
+//! input and output or over TCP connections to a local port. Requests are handled on the
+//! main thread, between frames, in the order they arrive.
+//!
+//! Every request must carry the token of this launch of the player in a `token` member.
+//! The token is written to `control-token` in Ruffle's data directory, which only the
+//! current user can read. A request with a missing or wrong token, or a line that isn't
+//! a JSON object, closes the connection.
+//!
+//! The supported methods are:
+//!
+//! * `load` with `{ "url": ... }`, a path or URL, to open a movie
+//! * `close` to close the movie
+//! * `pause` and `resume`
+//! * `evaluate` with `{ "expression": ... }` to run an expression in the ActionScript console
+//! * `setVariable` with `{ "name": ..., "value": ... }`, a shorthand for an assignment
+//! * `screenshot` to get the last rendered frame as a base64-encoded PNG
+//! * `sendInput` with `{ "event": ... }`, an event written as in input scripts,
+//!   such as `"mouse_down 320 240 left"`
+//! * `metadata` to describe the movie and the state of the player
+
+use crate::custom_event::RuffleEvent;
+use crate::util::{constant_time_eq, generate_token, write_private_file};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use winit::event_loop::EventLoopProxy;
+
+/// Where control requests come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlChannel {
+    Stdio,
+    Port(u16),
+}
+
+impl FromStr for ControlChannel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "stdio" {
+            return Ok(Self::Stdio);
+        }
+        s.parse()
+            .map(Self::Port)
+            .map_err(|_| format!("Expected \"stdio\" or a port number, not \"{s}\""))
+    }
+}
+
+/// Writes responses back to whoever sent the requests.
+#[derive(Clone)]
+struct Responder(Arc<Mutex<dyn Write + Send>>);
+
+impl Responder {
+    fn send(&self, message: &Value) {
+        let Ok(mut writer) = self.0.lock() else {
+            return;
+        };
+        if writeln!(writer, "{message}")
+            .and_then(|_| writer.flush())
+            .is_err()
+        {
+            tracing::warn!("Couldn't send control response");
+        }
+    }
+}
+
+/// A request that's waiting to be handled by the main thread.
+pub struct ControlRequest {
+    pub method: String,
+    pub params: Value,
+    id: Value,
+    responder: Responder,
+}
+
+impl ControlRequest {
+    pub fn respond(self, result: Result<Value, String>) {
+        // Notifications don't expect responses.
+        if self.id.is_null() {
+            return;
+        }
+        let response = match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": self.id, "result": result }),
+            Err(message) => json!({
+                "jsonrpc": "2.0",
+                "id": self.id,
+                "error": { "code": -32000, "message": message },
+            }),
+        };
+        self.responder.send(&response);
+    }
+
+    /// A string parameter that the method needs.
+    pub fn string_param(&self, name: &str) -> Result<&str, String> {
+        self.params[name]
+            .as_str()
+            .ok_or_else(|| format!("Missing string parameter \"{name}\""))
+    }
+}
+
+/// Start listening for requests, which are sent to the event loop as they arrive.
+pub fn start(channel: ControlChannel, event_loop: EventLoopProxy<RuffleEvent>) -> io::Result<()> {
+    let token: Arc<str> = generate_token().into();
+    let token_path = write_token(&token)?;
+    tracing::info!("Wrote the control token to {}", token_path.display());
+
+    match channel {
+        ControlChannel::Stdio => {
+            let responder = Responder(Arc::new(Mutex::new(io::stdout())));
+            thread::spawn(move || serve(io::stdin().lock(), responder, event_loop, &token));
+        }
+        ControlChannel::Port(port) => {
+            let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+            tracing::info!("Listening for control connections on port {port}");
+            thread::spawn(move || {
+                for stream in listener.incoming() {
+                    let result = stream.and_then(|stream| {
+                        let reader = BufReader::new(stream.try_clone()?);
+                        let responder = Responder(Arc::new(Mutex::new(stream)));
+                        let event_loop = event_loop.clone();
+                        let token = token.clone();
+                        thread::spawn(move || serve(reader, responder, event_loop, &token));
+                        Ok(())
+                    });
+                    if let Err(e) = result {
+                        tracing::warn!("Control connection failed: {e}");
+                    }
+                }
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Write the token to a file that only the current user can read, returning its path.
+fn write_token(token: &str) -> io::Result<PathBuf> {
+    let dir = dirs::data_local_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No data_local dir"))?
+        .join("ruffle");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("control-token");
+    write_private_file(&path, token.as_bytes())?;
+    Ok(path)
+}
+
+/// Read requests until the other end closes the connection or the event loop is gone.
+///
+/// The connection is also closed by anything that isn't a request with the right token,
+/// such as an HTTP request that a web page sent to the port.
+fn serve(
+    reader: impl BufRead,
+    responder: Responder,
+    event_loop: EventLoopProxy<RuffleEvent>,
+    token: &str,
+) {
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut message = match serde_json::from_str(&line) {
+            Ok(Value::Object(message)) => message,
+            Ok(_) => {
+                responder.send(&json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": { "code": -32600, "message": "Expected a JSON object" },
+                }));
+                break;
+            }
+            Err(e) => {
+                responder.send(&json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": { "code": -32700, "message": e.to_string() },
+                }));
+                break;
+            }
+        };
+        let id = message.remove("id").unwrap_or_default();
+        let has_token = message
+            .get("token")
+            .and_then(Value::as_str)
+            .map_or(false, |given| constant_time_eq(given, token));
+        if !has_token {
+            tracing::warn!("Closing a control connection that sent a wrong token");
+            responder.send(&json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32001, "message": "Missing or wrong token" },
+            }));
+            break;
+        }
+
+        let request = ControlRequest {
+            method: message
+                .get("method")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            params: message.remove("params").unwrap_or_default(),
+            id,
+            responder: responder.clone(),
+        };
+        if event_loop
+            .send_event(RuffleEvent::ControlRequest(Box::new(request)))
+            .is_err()
+        {
+            break;
+        }
+    }
+}
+
+/// Write a JSON value as a literal in the language of the ActionScript console.
+pub fn console_literal(value: &Value) -> Result<String, String> {
+    match value {
+        Value::Null => Ok("null".to_string()),
+        Value::Bool(value) => Ok(value.to_string()),
+        Value::Number(value) => Ok(value.to_string()),
+        Value::String(value) => Ok(format!(
+            "\"{}\"",
+            value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n")
+                .replace('\r', "\\r")
+                .replace('\t', "\\t")
+        )),
+        _ => Err("Only strings, numbers, booleans and null can be set".to_string()),
+    }
+}
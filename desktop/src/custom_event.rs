@@ -1,5 +1,6 @@
 //! Custom event type for desktop ruffle
 
+use crate::control::ControlRequest;
 use crate::player::PlayerOptions;
 
 /// User-defined events.
@@ -19,9 +20,24 @@ pub enum RuffleEvent {
     /// The user requested to close the current SWF.
     CloseFile,
 
+    /// The user requested to restore a save state into the newly opened SWF.
+    RestoreState(Box<ruffle_core::save_state::SaveState>),
+
+    /// The user requested a screenshot of the next frame.
+    TakeScreenshot,
+
+    /// The user requested to start or stop recording a video.
+    ToggleRecording,
+
     /// The user requested to exit Ruffle.
     ExitRequested,
 
     /// The user selected an item in the right-click context menu.
     ContextMenuItemClicked(usize),
+
+    /// Assistive technology asked for an action on an accessible object.
+    AccessibilityAction(accesskit_winit::ActionRequestEvent),
+
+    /// Another program sent a request over the control channel.
+    ControlRequest(Box<ControlRequest>),
 }
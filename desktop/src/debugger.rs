@@ -0,0 +1,466 @@
+//! A Debug Adapter Protocol server, which lets editors such as VS Code debug the movie's
+//! ActionScript.
+//!
+//! Clients connect over TCP and send `attach` or `launch` requests for the movie that's
+//! already running. Only one client is served at a time.
+//!
+//! The `attach` or `launch` request must carry the token of this launch of the player in
+//! a `token` argument. The token is written to `debugger-token` in Ruffle's data directory,
+//! which only the current user can read. Until a client presents it, the only other request
+//! it may send is `initialize`, and anything else closes the connection.
+
+use crate::util::{constant_time_eq, generate_token, write_private_file};
+use ruffle_core::debugger::{
+    DebugCommand, DebugEvent, DebuggerConnection, Scope, StackFrame, StopReason,
+};
+use serde_json::{json, Value};
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// DAP clients expect threads, but movies only ever run on the one.
+const THREAD_ID: u64 = 1;
+
+/// The longest header line that a client may send.
+const MAX_HEADER_LENGTH: u64 = 1024;
+
+/// The largest message that a client may send. Anything larger closes the connection.
+const MAX_MESSAGE_LENGTH: usize = 4 * 1024 * 1024;
+
+/// What the movie looked like when it last stopped.
+#[derive(Default)]
+struct Snapshot {
+    stack: Vec<StackFrame>,
+    scopes: Vec<Scope>,
+}
+
+/// Listens for debugger clients for as long as the application runs.
+pub struct DebugServer {
+    commands: Arc<Mutex<Receiver<DebugCommand>>>,
+    events: Sender<DebugEvent>,
+}
+
+impl DebugServer {
+    pub fn start(port: u16) -> io::Result<Self> {
+        let token: Arc<str> = generate_token().into();
+        let token_path = write_token(&token)?;
+        tracing::info!("Wrote the debugger token to {}", token_path.display());
+
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+        tracing::info!("Listening for debuggers on port {port}");
+
+        let (command_sender, commands) = channel();
+        let (events, event_receiver) = channel();
+        let event_receiver = Arc::new(Mutex::new(event_receiver));
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let result = stream.and_then(|stream| {
+                    Client::new(
+                        stream,
+                        command_sender.clone(),
+                        event_receiver.clone(),
+                        token.clone(),
+                    )?
+                    .serve()
+                });
+                if let Err(e) = result {
+                    tracing::warn!("Debugger connection failed: {e}");
+                }
+                // Whatever the client left behind shouldn't keep the movie stopped.
+                if command_sender.send(DebugCommand::Disconnect).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self {
+            commands: Arc::new(Mutex::new(commands)),
+            events,
+        })
+    }
+
+    /// A connection for a newly created player.
+    pub fn connection(&self) -> Box<dyn DebuggerConnection> {
+        Box::new(DapConnection {
+            commands: self.commands.clone(),
+            events: self.events.clone(),
+        })
+    }
+}
+
+/// Write the token to a file that only the current user can read, returning its path.
+fn write_token(token: &str) -> io::Result<PathBuf> {
+    let dir = dirs::data_local_dir()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No data_local dir"))?
+        .join("ruffle");
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("debugger-token");
+    write_private_file(&path, token.as_bytes())?;
+    Ok(path)
+}
+
+struct DapConnection {
+    commands: Arc<Mutex<Receiver<DebugCommand>>>,
+    events: Sender<DebugEvent>,
+}
+
+impl DebuggerConnection for DapConnection {
+    fn poll(&mut self) -> Option<DebugCommand> {
+        self.commands.lock().ok()?.try_recv().ok()
+    }
+
+    fn wait(&mut self) -> Option<DebugCommand> {
+        self.commands.lock().ok()?.recv().ok()
+    }
+
+    fn send(&mut self, event: DebugEvent) {
+        let _ = self.events.send(event);
+    }
+}
+
+/// Writes messages to the client, from both the thread reading its requests and the one
+/// forwarding the player's events.
+#[derive(Clone)]
+struct Writer {
+    stream: Arc<Mutex<TcpStream>>,
+    seq: Arc<AtomicU64>,
+}
+
+impl Writer {
+    fn send(&self, mut message: Value) -> io::Result<()> {
+        message["seq"] = self.seq.fetch_add(1, Ordering::Relaxed).into();
+        let body = message.to_string();
+        let mut stream = self.stream.lock().expect("Stream lock must be available");
+        write!(stream, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+        stream.flush()
+    }
+
+    fn event(&self, event: &str, body: Value) -> io::Result<()> {
+        self.send(json!({ "type": "event", "event": event, "body": body }))
+    }
+}
+
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: Writer,
+    commands: Sender<DebugCommand>,
+    events: Arc<Mutex<Receiver<DebugEvent>>>,
+    snapshot: Arc<Mutex<Snapshot>>,
+    connected: Arc<AtomicBool>,
+    token: Arc<str>,
+
+    /// The thread forwarding the player's events, started once the client has presented
+    /// the token.
+    forwarder: Option<thread::JoinHandle<()>>,
+}
+
+impl Client {
+    fn new(
+        stream: TcpStream,
+        commands: Sender<DebugCommand>,
+        events: Arc<Mutex<Receiver<DebugEvent>>>,
+        token: Arc<str>,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: Writer {
+                stream: Arc::new(Mutex::new(stream)),
+                seq: Arc::new(AtomicU64::new(1)),
+            },
+            commands,
+            events,
+            snapshot: Default::default(),
+            connected: Arc::new(AtomicBool::new(true)),
+            token,
+            forwarder: None,
+        })
+    }
+
+    fn serve(mut self) -> io::Result<()> {
+        let result = self.read_requests();
+        self.connected.store(false, Ordering::Relaxed);
+        if let Some(forwarder) = self.forwarder.take() {
+            let _ = forwarder.join();
+        }
+        result
+    }
+
+    /// Send the player's events to the client until it disconnects.
+    fn forward_events(&self) -> thread::JoinHandle<()> {
+        let writer = self.writer.clone();
+        let events = self.events.clone();
+        let snapshot = self.snapshot.clone();
+        let connected = self.connected.clone();
+        thread::spawn(move || {
+            let events = events.lock().expect("Event lock must be available");
+            while connected.load(Ordering::Relaxed) {
+                let event = match events.recv_timeout(Duration::from_millis(100)) {
+                    Ok(event) => event,
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Err(RecvTimeoutError::Disconnected) => break,
+                };
+                let result = match event {
+                    DebugEvent::Stopped {
+                        reason,
+                        stack,
+                        scopes,
+                    } => {
+                        *snapshot.lock().expect("Snapshot lock must be available") =
+                            Snapshot { stack, scopes };
+                        let reason = match reason {
+                            StopReason::Breakpoint => "breakpoint",
+                            StopReason::Step => "step",
+                            StopReason::Pause => "pause",
+                        };
+                        writer.event(
+                            "stopped",
+                            json!({
+                                "reason": reason,
+                                "threadId": THREAD_ID,
+                                "allThreadsStopped": true,
+                            }),
+                        )
+                    }
+                    DebugEvent::Continued => writer.event(
+                        "continued",
+                        json!({ "threadId": THREAD_ID, "allThreadsContinued": true }),
+                    ),
+                };
+                if result.is_err() {
+                    break;
+                }
+            }
+        })
+    }
+
+    fn read_requests(&mut self) -> io::Result<()> {
+        while let Some(request) = self.read_message()? {
+            let command = request["command"].as_str().unwrap_or_default().to_string();
+            let arguments = &request["arguments"];
+            if self.forwarder.is_none() && command != "initialize" {
+                let has_token = matches!(command.as_str(), "attach" | "launch")
+                    && arguments["token"]
+                        .as_str()
+                        .map_or(false, |given| constant_time_eq(given, &self.token));
+                if !has_token {
+                    tracing::warn!("Closing a debugger connection that sent a wrong token");
+                    self.writer.send(json!({
+                        "type": "response",
+                        "request_seq": request["seq"].clone(),
+                        "command": command,
+                        "success": false,
+                        "message": "Missing or wrong token",
+                    }))?;
+                    break;
+                }
+                self.forwarder = Some(self.forward_events());
+            }
+            let body = self.handle_request(&command, arguments);
+            let mut response = match body {
+                Ok(body) => json!({ "success": true, "body": body }),
+                Err(message) => json!({ "success": false, "message": message }),
+            };
+            response["type"] = "response".into();
+            response["request_seq"] = request["seq"].clone();
+            response["command"] = command.as_str().into();
+            self.writer.send(response)?;
+
+            match command.as_str() {
+                "initialize" => self.writer.event("initialized", json!({}))?,
+                "disconnect" => break,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the next message, or `None` if the client closed the connection.
+    fn read_message(&mut self) -> io::Result<Option<Value>> {
+        let mut content_length = None;
+        loop {
+            let mut line = String::new();
+            let read = (&mut self.reader)
+                .take(MAX_HEADER_LENGTH)
+                .read_line(&mut line)?;
+            if read == 0 {
+                return Ok(None);
+            }
+            if !line.ends_with('\n') && read as u64 == MAX_HEADER_LENGTH {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Header line is too long",
+                ));
+            }
+            let line = line.trim();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(length) = line.strip_prefix("Content-Length:") {
+                content_length = length.trim().parse().ok();
+            }
+        }
+
+        let content_length = content_length.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidData, "Missing Content-Length header")
+        })?;
+        if content_length > MAX_MESSAGE_LENGTH {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Message of {content_length} bytes is too large"),
+            ));
+        }
+        let mut body = vec![0; content_length];
+        self.reader.read_exact(&mut body)?;
+        serde_json::from_slice(&body)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn send_command(&self, command: DebugCommand) {
+        let _ = self.commands.send(command);
+    }
+
+    fn handle_request(&self, command: &str, arguments: &Value) -> Result<Value, String> {
+        match command {
+            "initialize" => Ok(json!({
+                "supportsConfigurationDoneRequest": true,
+                "supportsFunctionBreakpoints": true,
+            })),
+            "launch" | "attach" | "configurationDone" => Ok(json!({})),
+            "setBreakpoints" => {
+                let file = arguments["source"]["path"]
+                    .as_str()
+                    .or_else(|| arguments["source"]["name"].as_str())
+                    .ok_or("Breakpoints need a source")?
+                    .to_string();
+                let lines: Vec<u32> = arguments["breakpoints"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|breakpoint| breakpoint["line"].as_u64())
+                    .map(|line| line as u32)
+                    .collect();
+                let breakpoints: Vec<_> = lines
+                    .iter()
+                    .map(|line| json!({ "verified": true, "line": line }))
+                    .collect();
+                self.send_command(DebugCommand::SetBreakpoints { file, lines });
+                Ok(json!({ "breakpoints": breakpoints }))
+            }
+            "setFunctionBreakpoints" => {
+                let names: Vec<String> = arguments["breakpoints"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|breakpoint| breakpoint["name"].as_str())
+                    .map(str::to_string)
+                    .collect();
+                let breakpoints: Vec<_> =
+                    names.iter().map(|_| json!({ "verified": true })).collect();
+                self.send_command(DebugCommand::SetFunctionBreakpoints(names));
+                Ok(json!({ "breakpoints": breakpoints }))
+            }
+            "threads" => Ok(json!({ "threads": [{ "id": THREAD_ID, "name": "Main" }] })),
+            "stackTrace" => {
+                let snapshot = self
+                    .snapshot
+                    .lock()
+                    .expect("Snapshot lock must be available");
+                let frames: Vec<_> = snapshot
+                    .stack
+                    .iter()
+                    .enumerate()
+                    .map(|(id, frame)| {
+                        let mut value = json!({
+                            "id": id,
+                            "name": frame.function,
+                            "line": frame.line.unwrap_or_default(),
+                            "column": 0,
+                        });
+                        if let Some(file) = &frame.file {
+                            value["source"] = json!({ "name": file, "path": file });
+                        }
+                        value
+                    })
+                    .collect();
+                Ok(json!({ "stackFrames": frames, "totalFrames": frames.len() }))
+            }
+            "scopes" => {
+                // Only the variables of the innermost frame are known.
+                if arguments["frameId"].as_u64() != Some(0) {
+                    return Ok(json!({ "scopes": [] }));
+                }
+                let snapshot = self
+                    .snapshot
+                    .lock()
+                    .expect("Snapshot lock must be available");
+                let scopes: Vec<_> = snapshot
+                    .scopes
+                    .iter()
+                    .enumerate()
+                    .map(|(index, scope)| {
+                        json!({
+                            "name": scope.name,
+                            "variablesReference": index + 1,
+                            "expensive": false,
+                        })
+                    })
+                    .collect();
+                Ok(json!({ "scopes": scopes }))
+            }
+            "variables" => {
+                let snapshot = self
+                    .snapshot
+                    .lock()
+                    .expect("Snapshot lock must be available");
+                let index = arguments["variablesReference"].as_u64().unwrap_or_default();
+                let variables: Vec<_> = (index as usize)
+                    .checked_sub(1)
+                    .and_then(|index| snapshot.scopes.get(index))
+                    .map(|scope| scope.variables.as_slice())
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|variable| {
+                        json!({
+                            "name": variable.name,
+                            "value": variable.value,
+                            "variablesReference": 0,
+                        })
+                    })
+                    .collect();
+                Ok(json!({ "variables": variables }))
+            }
+            "continue" => {
+                self.send_command(DebugCommand::Continue);
+                Ok(json!({ "allThreadsContinued": true }))
+            }
+            "next" => {
+                self.send_command(DebugCommand::StepOver);
+                Ok(json!({}))
+            }
+            "stepIn" => {
+                self.send_command(DebugCommand::StepIn);
+                Ok(json!({}))
+            }
+            "stepOut" => {
+                self.send_command(DebugCommand::StepOut);
+                Ok(json!({}))
+            }
+            "pause" => {
+                self.send_command(DebugCommand::Pause);
+                Ok(json!({}))
+            }
+            "disconnect" => {
+                self.send_command(DebugCommand::Disconnect);
+                Ok(json!({}))
+            }
+            _ => Err(format!("Unsupported request \"{command}\"")),
+        }
+    }
+}
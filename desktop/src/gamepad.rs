@@ -0,0 +1,115 @@
+//! Gamepad input through gilrs.
+
+use gilrs::{Axis, Button, EventType, Gilrs};
+use ruffle_core::events::{GamepadAxis, GamepadButton};
+use ruffle_core::PlayerEvent;
+
+pub struct GamepadInput {
+    /// `None` if gamepads aren't supported on this system.
+    gilrs: Option<Gilrs>,
+}
+
+impl GamepadInput {
+    pub fn new() -> Self {
+        let gilrs = match Gilrs::new() {
+            Ok(gilrs) => Some(gilrs),
+            Err(e) => {
+                tracing::warn!("Gamepads are unavailable: {}", e);
+                None
+            }
+        };
+        Self { gilrs }
+    }
+
+    /// Events announcing the gamepads that are already connected, for a new player.
+    pub fn connected_gamepads(&self) -> Vec<PlayerEvent> {
+        let Some(gilrs) = &self.gilrs else {
+            return vec![];
+        };
+        gilrs
+            .gamepads()
+            .map(|(id, gamepad)| PlayerEvent::GamepadConnected {
+                id: usize::from(id) as u32,
+                name: gamepad.name().to_string(),
+            })
+            .collect()
+    }
+
+    /// Takes the events of the gamepads since the last call.
+    pub fn poll(&mut self) -> Vec<PlayerEvent> {
+        let Some(gilrs) = &mut self.gilrs else {
+            return vec![];
+        };
+
+        let mut events = vec![];
+        while let Some(event) = gilrs.next_event() {
+            let id = usize::from(event.id) as u32;
+            let event = match event.event {
+                EventType::Connected => PlayerEvent::GamepadConnected {
+                    id,
+                    name: gilrs.gamepad(event.id).name().to_string(),
+                },
+                EventType::Disconnected => PlayerEvent::GamepadDisconnected { id },
+                EventType::ButtonPressed(button, _) => match gilrs_to_ruffle_button(button) {
+                    Some(button) => PlayerEvent::GamepadButtonDown { id, button },
+                    None => continue,
+                },
+                EventType::ButtonReleased(button, _) => match gilrs_to_ruffle_button(button) {
+                    Some(button) => PlayerEvent::GamepadButtonUp { id, button },
+                    None => continue,
+                },
+                EventType::AxisChanged(axis, value, _) => match axis {
+                    Axis::LeftStickX => PlayerEvent::GamepadAxisChanged {
+                        id,
+                        axis: GamepadAxis::LeftStickX,
+                        value: value.into(),
+                    },
+                    // gilrs reports up as positive, but Flash reports down as positive.
+                    Axis::LeftStickY => PlayerEvent::GamepadAxisChanged {
+                        id,
+                        axis: GamepadAxis::LeftStickY,
+                        value: (-value).into(),
+                    },
+                    Axis::RightStickX => PlayerEvent::GamepadAxisChanged {
+                        id,
+                        axis: GamepadAxis::RightStickX,
+                        value: value.into(),
+                    },
+                    Axis::RightStickY => PlayerEvent::GamepadAxisChanged {
+                        id,
+                        axis: GamepadAxis::RightStickY,
+                        value: (-value).into(),
+                    },
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            events.push(event);
+        }
+        events
+    }
+}
+
+/// Convert a gilrs `Button` into a Ruffle `GamepadButton`.
+/// Return `None` if there is no matching button in the standard layout.
+fn gilrs_to_ruffle_button(button: Button) -> Option<GamepadButton> {
+    Some(match button {
+        Button::South => GamepadButton::South,
+        Button::East => GamepadButton::East,
+        Button::West => GamepadButton::West,
+        Button::North => GamepadButton::North,
+        Button::LeftTrigger => GamepadButton::LeftShoulder,
+        Button::RightTrigger => GamepadButton::RightShoulder,
+        Button::LeftTrigger2 => GamepadButton::LeftTrigger,
+        Button::RightTrigger2 => GamepadButton::RightTrigger,
+        Button::Select => GamepadButton::Select,
+        Button::Start => GamepadButton::Start,
+        Button::LeftThumb => GamepadButton::LeftStick,
+        Button::RightThumb => GamepadButton::RightStick,
+        Button::DPadUp => GamepadButton::DPadUp,
+        Button::DPadDown => GamepadButton::DPadDown,
+        Button::DPadLeft => GamepadButton::DPadLeft,
+        Button::DPadRight => GamepadButton::DPadRight,
+        _ => return None,
+    })
+}
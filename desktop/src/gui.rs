@@ -17,6 +17,7 @@ use fluent_templates::{static_loader, Loader};
 use rfd::FileDialog;
 use ruffle_core::backend::ui::US_ENGLISH;
 use ruffle_core::debug_ui::Message as DebugMessage;
+use ruffle_core::save_state::SaveState;
 use ruffle_core::Player;
 use std::collections::HashMap;
 use std::fs;
@@ -40,7 +41,6 @@ pub fn text<'a>(locale: &LanguageIdentifier, id: &'a str) -> Cow<'a, str> {
     })
 }
 
-#[allow(dead_code)]
 pub fn text_with_args<'a, T: AsRef<str>>(
     locale: &LanguageIdentifier,
     id: &'a str,
@@ -55,6 +55,9 @@ pub fn text_with_args<'a, T: AsRef<str>>(
         })
 }
 
+/// The number of save states that can be kept for a movie at once.
+const SAVE_STATE_SLOTS: usize = 4;
+
 /// Size of the top menu bar in pixels.
 /// This is the offset at which the movie will be shown,
 /// and added to the window size if trying to match a movie.
@@ -71,6 +74,12 @@ pub struct RuffleGui {
     default_player_options: PlayerOptions,
     currently_opened: Option<(Url, PlayerOptions)>,
     was_suspended_before_debug: bool,
+
+    /// States saved from the currently opened movie.
+    save_states: [Option<SaveState>; SAVE_STATE_SLOTS],
+
+    /// Whether a video is being recorded.
+    is_recording: bool,
 }
 
 impl RuffleGui {
@@ -104,6 +113,8 @@ impl RuffleGui {
             locale,
             default_player_options,
             currently_opened: None,
+            save_states: Default::default(),
+            is_recording: false,
         }
     }
 
@@ -165,6 +176,10 @@ impl RuffleGui {
 
     /// Notifies the GUI that a new player was created.
     fn on_player_created(&mut self, opt: PlayerOptions, movie_url: Url) {
+        // Save states can only be restored into the movie they were saved from.
+        if self.currently_opened.as_ref().map(|(url, _)| url) != Some(&movie_url) {
+            self.save_states = Default::default();
+        }
         self.currently_opened = Some((movie_url.clone(), opt.clone()));
 
         // Update dialog state to reflect the newly-opened movie's options.
@@ -203,6 +218,16 @@ impl RuffleGui {
                     player.set_is_playing(!player.is_playing());
                 }
             }
+            if player.is_some() && ui.ctx().input_mut(|input| {
+                input.consume_shortcut(&KeyboardShortcut::new(Modifiers::NONE, Key::F12))
+            }) {
+                let _ = self.event_loop.send_event(RuffleEvent::TakeScreenshot);
+            }
+            if (player.is_some() || self.is_recording) && ui.ctx().input_mut(|input| {
+                input.consume_shortcut(&KeyboardShortcut::new(Modifiers::SHIFT, Key::F12))
+            }) {
+                let _ = self.event_loop.send_event(RuffleEvent::ToggleRecording);
+            }
 
             menu::bar(ui, |ui| {
                 menu::menu_button(ui, text(&self.locale, "file-menu"), |ui| {
@@ -254,6 +279,43 @@ impl RuffleGui {
                                 player.set_is_playing(!player.is_playing());
                             }
                         }
+
+                        ui.separator();
+                        let screenshot_shortcut = KeyboardShortcut::new(Modifiers::NONE, Key::F12);
+                        if Button::new(text(&self.locale, "controls-menu-screenshot")).shortcut_text(ui.ctx().format_shortcut(&screenshot_shortcut)).ui(ui).clicked() {
+                            ui.close_menu();
+                            let _ = self.event_loop.send_event(RuffleEvent::TakeScreenshot);
+                        }
+                        let recording_shortcut = KeyboardShortcut::new(Modifiers::SHIFT, Key::F12);
+                        if Button::new(text(&self.locale, if self.is_recording { "controls-menu-stop-recording" } else { "controls-menu-start-recording" })).shortcut_text(ui.ctx().format_shortcut(&recording_shortcut)).ui(ui).clicked() {
+                            ui.close_menu();
+                            let _ = self.event_loop.send_event(RuffleEvent::ToggleRecording);
+                        }
+
+                        let save_states_enabled = player.as_ref().map(|p| p.save_states_enabled()).unwrap_or_default();
+                        if save_states_enabled {
+                            ui.separator();
+                            for (slot, save_state) in self.save_states.iter_mut().enumerate() {
+                                let args = HashMap::from([("slot", FluentValue::from(slot + 1))]);
+                                if Button::new(text_with_args(&self.locale, "controls-menu-save-state", &args)).ui(ui).clicked() {
+                                    ui.close_menu();
+                                    if let Some(player) = &player {
+                                        *save_state = player.save_state();
+                                    }
+                                }
+                            }
+                            ui.separator();
+                            let mut load_slot = None;
+                            for (slot, save_state) in self.save_states.iter().enumerate() {
+                                let args = HashMap::from([("slot", FluentValue::from(slot + 1))]);
+                                if ui.add_enabled(save_state.is_some(), Button::new(text_with_args(&self.locale, "controls-menu-load-state", &args))).clicked() {
+                                    load_slot = Some(slot);
+                                }
+                            }
+                            if let Some(slot) = load_slot {
+                                self.load_state(ui, slot);
+                            }
+                        }
                     });
                 });
                 menu::menu_button(ui, text(&self.locale, "debug-menu"), |ui| {
@@ -282,6 +344,24 @@ impl RuffleGui {
                                 player.debug_ui().queue_message(DebugMessage::SearchForDisplayObject);
                             }
                         }
+                        if Button::new(text(&self.locale, "debug-menu-memory-profile")).ui(ui).clicked() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowMemoryProfile);
+                            }
+                        }
+                        if Button::new(text(&self.locale, "debug-menu-profiler")).ui(ui).clicked() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowProfiler);
+                            }
+                        }
+                        if Button::new(text(&self.locale, "debug-menu-console")).ui(ui).clicked() {
+                            ui.close_menu();
+                            if let Some(player) = &mut player {
+                                player.debug_ui().queue_message(DebugMessage::ShowConsole(None));
+                            }
+                        }
                     });
                 });
                 menu::menu_button(ui, text(&self.locale, "help-menu"), |ui| {
@@ -408,9 +488,11 @@ impl RuffleGui {
                         ui.separator();
                     }
                     let clicked = if item.checked {
-                        Checkbox::new(&mut true, &item.caption).ui(ui).clicked()
+                        ui.add_enabled(item.enabled, Checkbox::new(&mut true, &item.caption))
+                            .clicked()
                     } else {
-                        Button::new(&item.caption).ui(ui).clicked()
+                        ui.add_enabled(item.enabled, Button::new(&item.caption))
+                            .clicked()
                     };
                     if clicked {
                         let _ = self
@@ -460,6 +542,23 @@ impl RuffleGui {
         ui.close_menu();
     }
 
+    /// Restores a save state by reopening the movie and replaying the state into it.
+    fn load_state(&mut self, ui: &mut egui::Ui, slot: usize) {
+        if let (Some(state), Some((movie_url, opts))) = (
+            self.save_states[slot].clone(),
+            self.currently_opened.clone(),
+        ) {
+            let _ = self.event_loop.send_event(RuffleEvent::CloseFile);
+            let _ = self
+                .event_loop
+                .send_event(RuffleEvent::OpenURL(movie_url, opts.into()));
+            let _ = self
+                .event_loop
+                .send_event(RuffleEvent::RestoreState(Box::new(state)));
+        }
+        ui.close_menu();
+    }
+
     fn open_dialog(&mut self, egui_ctx: &egui::Context) {
         if self.is_open_dialog_visible {
             let keep_open = self.open_dialog.show(egui_ctx);
@@ -7,6 +7,7 @@ use crate::player::{PlayerController, PlayerOptions};
 use anyhow::anyhow;
 use egui::Context;
 use fontdb::{Database, Family, Query, Source};
+use ruffle_core::config::FrameRateMode;
 use ruffle_core::Player;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
 use ruffle_render_wgpu::descriptors::Descriptors;
@@ -32,7 +33,10 @@ pub struct GuiController {
     repaint_after: Duration,
     surface: wgpu::Surface,
     surface_format: wgpu::TextureFormat,
+    present_mode: wgpu::PresentMode,
     movie_view_renderer: Arc<MovieViewRenderer>,
+    /// The texture of the custom cursor that the movie set, and the generation of it.
+    custom_cursor: Option<(u64, egui::TextureHandle)>,
     // Note that `window.get_inner_size` can change at any point on x11, even between two lines of code.
     // Use this instead.
     size: PhysicalSize<u32>,
@@ -70,6 +74,11 @@ impl GuiController {
             .first()
             .cloned()
             .expect("At least one format should be supported");
+        // Without vsync, presenting a frame doesn't wait for the display to refresh.
+        let present_mode = match opt.frame_rate_mode {
+            FrameRateMode::Unlimited => wgpu::PresentMode::AutoNoVsync,
+            FrameRateMode::Native | FrameRateMode::Vsync => wgpu::PresentMode::Fifo,
+        };
         let size = window.inner_size();
         surface.configure(
             &device,
@@ -78,12 +87,13 @@ impl GuiController {
                 format: surface_format,
                 width: size.width,
                 height: size.height,
-                present_mode: Default::default(),
+                present_mode,
                 alpha_mode: Default::default(),
                 view_formats: Default::default(),
             },
         );
-        let descriptors = Descriptors::new(instance, adapter, device, queue);
+        let mut descriptors = Descriptors::new(instance, adapter, device, queue);
+        descriptors.set_blur_downsample_threshold(opt.blur_downsample_threshold);
         let egui_ctx = Context::default();
         if let Some(Theme::Light) = window.theme() {
             egui_ctx.set_visuals(egui::Visuals::light());
@@ -116,7 +126,9 @@ impl GuiController {
             repaint_after: Duration::ZERO,
             surface,
             surface_format,
+            present_mode,
             movie_view_renderer,
+            custom_cursor: None,
             size,
         })
     }
@@ -136,7 +148,7 @@ impl GuiController {
                         format: self.surface_format,
                         width: size.width,
                         height: size.height,
-                        present_mode: Default::default(),
+                        present_mode: self.present_mode,
                         alpha_mode: Default::default(),
                         view_formats: Default::default(),
                     },
@@ -201,6 +213,9 @@ impl GuiController {
                     0.0
                 },
             );
+            if let Some(player) = player.as_deref() {
+                draw_custom_cursor(context, &mut self.custom_cursor, player);
+            }
         });
         self.repaint_after = full_output.repaint_after;
 
@@ -307,6 +322,10 @@ impl GuiController {
         Instant::now().duration_since(self.last_update) >= self.repaint_after
     }
 
+    pub fn set_recording(&mut self, is_recording: bool) {
+        self.gui.is_recording = is_recording;
+    }
+
     pub fn show_open_dialog(&mut self) {
         self.gui.open_file_advanced()
     }
@@ -383,3 +402,58 @@ fn load_system_fonts(locale: LanguageIdentifier) -> anyhow::Result<egui::FontDef
 
     Ok(fd)
 }
+
+/// Draws the custom cursor that the movie set with `Mouse.cursor` at the pointer,
+/// as winit can only show the system's cursors.
+fn draw_custom_cursor(
+    context: &Context,
+    texture: &mut Option<(u64, egui::TextureHandle)>,
+    player: &Player,
+) {
+    let ui = player
+        .ui()
+        .downcast_ref::<DesktopUiBackend>()
+        .expect("UI Backend should be DesktopUiBackend");
+    let Some((generation, cursor)) = ui.custom_cursor() else {
+        *texture = None;
+        return;
+    };
+    let Some(pointer) = context.pointer_hover_pos() else {
+        return;
+    };
+    if context.wants_pointer_input() {
+        return;
+    }
+
+    if texture.as_ref().map(|(g, _)| *g) != Some(generation) {
+        let image = egui::ColorImage::from_rgba_unmultiplied(
+            [
+                cursor.image.width() as usize,
+                cursor.image.height() as usize,
+            ],
+            cursor.image.data(),
+        );
+        let handle = context.load_texture("custom_cursor", image, egui::TextureOptions::NEAREST);
+        *texture = Some((generation, handle));
+    }
+    let Some((_, handle)) = texture else {
+        return;
+    };
+
+    // The cursor is drawn at its size in physical pixels, like a system cursor would be.
+    let scale = 1.0 / context.pixels_per_point();
+    let size = handle.size_vec2() * scale;
+    let hotspot = egui::vec2(cursor.hotspot_x as f32, cursor.hotspot_y as f32) * scale;
+    let rect = egui::Rect::from_min_size(pointer - hotspot, size);
+    context
+        .layer_painter(egui::LayerId::new(
+            egui::Order::Tooltip,
+            egui::Id::new("custom_cursor"),
+        ))
+        .image(
+            handle.id(),
+            rect,
+            egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+            egui::Color32::WHITE,
+        );
+}
@@ -181,6 +181,13 @@ impl OpenDialog {
                     });
                 ui.end_row();
 
+                ui.label(text(&self.locale, "socket-policy"));
+                ui.checkbox(
+                    &mut self.options.check_socket_policy,
+                    text(&self.locale, "socket-policy-check"),
+                );
+                ui.end_row();
+
                 // TODO: This should probably be a global setting somewhere, not per load
                 ui.label(text(&self.locale, "open-url-mode"));
                 ComboBox::from_id_source("open-file-advanced-options-open-url-mode")
@@ -0,0 +1,91 @@
+use crate::cli::Opt;
+use crate::time_demo::load_movie;
+use anyhow::{anyhow, Context, Error};
+use ruffle_core::headless::{hash_pixels, HeadlessRunner, InputScript};
+use ruffle_core::PlayerBuilder;
+use ruffle_render_wgpu::backend::WgpuRenderBackend;
+use ruffle_render_wgpu::target::TextureTarget;
+use std::fs::{create_dir_all, read_to_string};
+use std::time::Instant;
+
+pub fn run_headless(mut opt: Opt) -> Result<(), Error> {
+    let movie_url = opt
+        .movie_url
+        .take()
+        .ok_or_else(|| anyhow!("Input file necessary for headless runs"))?;
+    let movie = load_movie(&movie_url, &opt).context("Couldn't load movie")?;
+    let num_frames = opt.headless_frames.unwrap_or_default();
+
+    let script = match &opt.input_script {
+        Some(path) => {
+            let source = read_to_string(path)
+                .with_context(|| format!("Couldn't read input script {}", path.display()))?;
+            InputScript::parse(&source).context("Couldn't parse input script")?
+        }
+        None => InputScript::default(),
+    };
+
+    if let Some(capture_dir) = &opt.capture_dir {
+        create_dir_all(capture_dir).context("Couldn't create capture directory")?;
+    }
+
+    let viewport_width = movie.width().to_pixels() as u32;
+    let viewport_height = movie.height().to_pixels() as u32;
+
+    let renderer = WgpuRenderBackend::for_offscreen(
+        (viewport_width, viewport_height),
+        opt.graphics.into(),
+        opt.power.into(),
+        opt.trace_path(),
+    )
+    .map_err(|e| anyhow!(e.to_string()))
+    .context("Couldn't create wgpu rendering backend")?;
+
+    let mut builder = PlayerBuilder::new();
+
+    if cfg!(feature = "software_video") {
+        builder = builder.with_video(ruffle_video_software::backend::SoftwareVideoBackend::new());
+    }
+
+    let player = builder
+        .with_renderer(renderer)
+        .with_movie(movie)
+        .with_viewport_dimensions(viewport_width, viewport_height, 1.0)
+        .with_frame_rate(opt.frame_rate)
+        .with_autoplay(true)
+        .build();
+
+    let mut player_lock = player.lock().expect("Cannot reenter");
+    let mut runner = HeadlessRunner::new(&mut player_lock, script);
+
+    let start = Instant::now();
+    while runner.frame() < num_frames {
+        runner.run_frame(&mut player_lock);
+
+        let frame = runner.frame();
+        if !opt.capture_frames.contains(&frame) {
+            continue;
+        }
+
+        let image = player_lock
+            .renderer_mut()
+            .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
+            .and_then(|renderer| renderer.capture_frame())
+            .ok_or_else(|| anyhow!("Unable to capture frame {frame}"))?;
+        println!("{frame}: {:016x}", hash_pixels(image.as_raw()));
+
+        if let Some(capture_dir) = &opt.capture_dir {
+            let path = capture_dir.join(format!("{frame}.png"));
+            image
+                .save(&path)
+                .with_context(|| format!("Couldn't save frame {frame} to {}", path.display()))?;
+        }
+    }
+
+    eprintln!(
+        "Ran {num_frames} frames in {}s.",
+        start.elapsed().as_secs_f32()
+    );
+
+    Ok(())
+}
@@ -6,12 +6,18 @@
 // See https://docs.microsoft.com/en-us/cpp/build/reference/subsystem?view=msvc-160 for details.
 #![windows_subsystem = "windows"]
 
+mod accessibility;
 mod app;
 mod backends;
+mod capture;
 mod cli;
+mod control;
 mod custom_event;
+mod debugger;
 mod executor;
+mod gamepad;
 mod gui;
+mod headless;
 mod player;
 mod task;
 mod time_demo;
@@ -21,9 +27,11 @@ use anyhow::Error;
 use app::App;
 use clap::Parser;
 use cli::Opt;
+use control::ControlChannel;
 use ruffle_core::StaticCallstack;
 use std::cell::RefCell;
 use std::panic::PanicInfo;
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::util::SubscriberInitExt;
 use url::Url;
 
@@ -49,7 +57,7 @@ static RUFFLE_VERSION: &str = concat!(
     ")"
 );
 
-fn init() {
+fn attach_console() {
     // When linked with the windows subsystem windows won't automatically attach
     // to the console of the parent process, so we do it explicitly. This fails
     // silently if the parent has no console.
@@ -58,15 +66,24 @@ fn init() {
         use winapi::um::wincon::{AttachConsole, ATTACH_PARENT_PROCESS};
         AttachConsole(ATTACH_PARENT_PROCESS);
     }
+}
 
+fn init(opt: &Opt) {
     let prev_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
         prev_hook(info);
         panic_hook(info);
     }));
 
+    // Standard output carries the responses of the control channel, if it's used.
+    let writer = if opt.control == Some(ControlChannel::Stdio) {
+        BoxMakeWriter::new(std::io::stderr)
+    } else {
+        BoxMakeWriter::new(std::io::stdout)
+    };
     let subscriber = tracing_subscriber::fmt::Subscriber::builder()
         .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
+        .with_writer(writer)
         .finish();
 
     #[cfg(feature = "tracy")]
@@ -152,10 +169,13 @@ fn shutdown() {
 }
 
 fn main() -> Result<(), Error> {
-    init();
+    attach_console();
     let opt = Opt::parse();
+    init(&opt);
     let result = if opt.timedemo {
         time_demo::run_timedemo(opt)
+    } else if opt.headless_frames.is_some() {
+        headless::run_headless(opt)
     } else {
         App::new(opt).map(|app| app.run())
     };
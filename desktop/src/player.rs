@@ -1,22 +1,31 @@
+use crate::accessibility::AccessibilityAdapter;
 use crate::backends::{
-    CpalAudioBackend, DesktopExternalInterfaceProvider, DesktopUiBackend, DiskStorageBackend,
-    ExternalNavigatorBackend,
+    AudioTap, CpalAudioBackend, DesktopExternalInterfaceProvider, DesktopUiBackend,
+    DiskFilesystemBackend, DiskStorageBackend, ExternalNavigatorBackend,
 };
 use crate::cli::Opt;
 use crate::custom_event::RuffleEvent;
+use crate::debugger::DebugServer;
 use crate::executor::WinitAsyncExecutor;
+use crate::gamepad::GamepadInput;
 use crate::gui::MovieView;
 use crate::{CALLSTACK, RENDER_INFO, SWF_INFO};
-use anyhow::anyhow;
+use anyhow::{anyhow, Context};
 use ruffle_core::backend::audio::AudioBackend;
-use ruffle_core::backend::navigator::{OpenURLMode, SocketMode};
-use ruffle_core::config::Letterbox;
+use ruffle_core::backend::navigator::{
+    JavascriptUrlMode, NavigationPolicy, OpenURLMode, SocketMode,
+};
+use ruffle_core::config::{FrameRateMode, Letterbox};
+use ruffle_core::debugger::DebuggerConnection;
+use ruffle_core::events::{GamepadButton, KeyCode};
+use ruffle_core::headless::InputScript;
 use ruffle_core::{LoadBehavior, Player, PlayerBuilder, PlayerEvent, StageAlign, StageScaleMode};
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::backend::WgpuRenderBackend;
 use ruffle_render_wgpu::descriptors::Descriptors;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Duration;
@@ -38,8 +47,10 @@ pub struct PlayerOptions {
     pub volume: f32,
     pub force_scale: bool,
     pub proxy: Option<Url>,
+    pub max_redirects: u32,
     pub socket_allowed: HashSet<String>,
     pub tcp_connections: SocketMode,
+    pub check_socket_policy: bool,
     pub upgrade_to_https: bool,
     pub fullscreen: bool,
     pub load_behavior: LoadBehavior,
@@ -47,8 +58,19 @@ pub struct PlayerOptions {
     pub spoof_url: Option<Url>,
     pub player_version: u8,
     pub frame_rate: Option<f64>,
+    pub frame_rate_mode: FrameRateMode,
+    pub sample_data_latency: u64,
+    pub save_states: bool,
+    pub record: Option<PathBuf>,
+    pub replay: Option<PathBuf>,
+    pub debug_port: Option<u16>,
     pub open_url_mode: OpenURLMode,
+    pub open_url_allow: Vec<String>,
+    pub open_url_deny: Vec<String>,
+    pub javascript_urls: JavascriptUrlMode,
     pub dummy_external_interface: bool,
+    pub air_sandbox: Option<PathBuf>,
+    pub gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
 }
 
 impl From<&Opt> for PlayerOptions {
@@ -64,6 +86,7 @@ impl From<&Opt> for PlayerOptions {
             volume: value.volume,
             force_scale: value.force_scale,
             proxy: value.proxy.clone(),
+            max_redirects: value.max_redirects,
             upgrade_to_https: value.upgrade_to_https,
             fullscreen: value.fullscreen,
             load_behavior: value.load_behavior,
@@ -71,10 +94,22 @@ impl From<&Opt> for PlayerOptions {
             spoof_url: value.spoof_url.clone(),
             player_version: value.player_version.unwrap_or(32),
             frame_rate: value.frame_rate,
+            frame_rate_mode: value.frame_rate_mode,
+            sample_data_latency: value.sample_data_latency,
+            save_states: value.save_states,
+            record: value.record.clone(),
+            replay: value.replay.clone(),
+            debug_port: value.debug_port,
             open_url_mode: value.open_url_mode,
+            open_url_allow: value.open_url_allow.clone(),
+            open_url_deny: value.open_url_deny.clone(),
+            javascript_urls: value.javascript_urls,
             dummy_external_interface: value.dummy_external_interface,
+            air_sandbox: value.air_sandbox.clone(),
+            gamepad_button_mapping: HashMap::from_iter(value.gamepad_button.iter().cloned()),
             socket_allowed: HashSet::from_iter(value.socket_allow.iter().cloned()),
             tcp_connections: value.tcp_connections,
+            check_socket_policy: value.check_socket_policy,
         }
     }
 }
@@ -84,22 +119,33 @@ impl From<&Opt> for PlayerOptions {
 struct ActivePlayer {
     player: Arc<Mutex<Player>>,
     executor: Arc<Mutex<WinitAsyncExecutor>>,
+
+    /// Where the input recording is written once the player closes.
+    record_path: Option<PathBuf>,
+
+    /// A copy of the audio that's played, if there's an audio device.
+    audio_tap: Option<AudioTap>,
 }
 
 impl ActivePlayer {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         opt: &PlayerOptions,
         event_loop: EventLoopProxy<RuffleEvent>,
         movie_url: &Url,
         window: Rc<Window>,
+        accessibility: Rc<AccessibilityAdapter>,
         descriptors: Arc<Descriptors>,
         movie_view: MovieView,
+        debugger: Option<Box<dyn DebuggerConnection>>,
     ) -> Self {
         let mut builder = PlayerBuilder::new();
+        let mut audio_tap = None;
 
         match CpalAudioBackend::new() {
             Ok(mut audio) => {
                 audio.set_volume(opt.volume);
+                audio_tap = Some(audio.tap());
                 builder = builder.with_audio(audio);
             }
             Err(e) => {
@@ -113,10 +159,17 @@ impl ActivePlayer {
             channel,
             event_loop.clone(),
             opt.proxy.clone(),
+            opt.max_redirects,
             opt.upgrade_to_https,
-            opt.open_url_mode,
+            NavigationPolicy {
+                open_url_mode: opt.open_url_mode,
+                allow: opt.open_url_allow.clone(),
+                deny: opt.open_url_deny.clone(),
+                javascript: opt.javascript_urls,
+            },
             opt.socket_allowed.clone(),
             opt.tcp_connections,
+            opt.check_socket_policy,
         );
 
         if cfg!(feature = "software_video") {
@@ -134,6 +187,17 @@ impl ActivePlayer {
                 builder.with_external_interface(Box::<DesktopExternalInterfaceProvider>::default());
         }
 
+        if let Some(sandbox) = &opt.air_sandbox {
+            let application_directory = movie_url
+                .to_file_path()
+                .ok()
+                .and_then(|path| path.parent().map(|path| path.to_path_buf()));
+            match DiskFilesystemBackend::new(sandbox.clone(), application_directory) {
+                Ok(filesystem) => builder = builder.with_filesystem(filesystem),
+                Err(e) => tracing::error!("Unable to create AIR sandbox directory: {}", e),
+            }
+        }
+
         let max_execution_duration = if opt.max_execution_duration == f64::INFINITY {
             Duration::MAX
         } else {
@@ -144,7 +208,10 @@ impl ActivePlayer {
             .with_navigator(navigator)
             .with_renderer(renderer)
             .with_storage(DiskStorageBackend::new().expect("Couldn't create storage backend"))
-            .with_ui(DesktopUiBackend::new(window.clone()).expect("Couldn't create ui backend"))
+            .with_ui(
+                DesktopUiBackend::new(window.clone(), accessibility)
+                    .expect("Couldn't create ui backend"),
+            )
             .with_autoplay(true)
             .with_letterbox(opt.letterbox)
             .with_max_execution_duration(max_execution_duration)
@@ -155,7 +222,25 @@ impl ActivePlayer {
             .with_load_behavior(opt.load_behavior)
             .with_spoofed_url(opt.spoof_url.clone().map(|url| url.to_string()))
             .with_player_version(Some(opt.player_version))
-            .with_frame_rate(opt.frame_rate);
+            .with_frame_rate(opt.frame_rate)
+            .with_frame_rate_mode(opt.frame_rate_mode)
+            .with_gamepad_button_mapping(opt.gamepad_button_mapping.clone())
+            .with_sample_data_latency(Duration::from_millis(opt.sample_data_latency))
+            .with_save_states(opt.save_states);
+
+        if opt.record.is_some() {
+            builder = builder.with_input_recording();
+        } else if let Some(path) = &opt.replay {
+            match load_input_script(path) {
+                Ok(script) => builder = builder.with_input_replay(script),
+                Err(e) => tracing::error!("Unable to load input recording: {:#}", e),
+            }
+        }
+
+        if let Some(debugger) = debugger {
+            builder = builder.with_debugger(debugger);
+        }
+
         let player = builder.build();
 
         let name = movie_url
@@ -184,42 +269,103 @@ impl ActivePlayer {
             );
         }
 
-        Self { player, executor }
+        Self {
+            player,
+            executor,
+            record_path: opt.record.clone(),
+            audio_tap,
+        }
+    }
+}
+
+impl Drop for ActivePlayer {
+    fn drop(&mut self) {
+        let (Some(path), Ok(player)) = (&self.record_path, self.player.lock()) else {
+            return;
+        };
+        if let Some(recording) = player.input_recording() {
+            if let Err(e) = std::fs::write(path, recording.to_string()) {
+                tracing::error!(
+                    "Unable to save input recording to {}: {}",
+                    path.display(),
+                    e
+                );
+            }
+        }
     }
 }
 
+fn load_input_script(path: &Path) -> Result<InputScript, anyhow::Error> {
+    let source = std::fs::read_to_string(path)
+        .with_context(|| format!("Couldn't read {}", path.display()))?;
+    Ok(InputScript::parse(&source)?)
+}
+
 /// Owner of a Ruffle Player (via ActivePlayer),
 /// responsible for either creating, destroying or communicating with that player.
 pub struct PlayerController {
     player: Option<ActivePlayer>,
     event_loop: EventLoopProxy<RuffleEvent>,
     window: Rc<Window>,
+    accessibility: Rc<AccessibilityAdapter>,
     descriptors: Arc<Descriptors>,
+    gamepads: GamepadInput,
+
+    /// Started by the first player that's created with a debug port.
+    debug_server: Option<DebugServer>,
 }
 
 impl PlayerController {
     pub fn new(
         event_loop: EventLoopProxy<RuffleEvent>,
         window: Rc<Window>,
+        accessibility: Rc<AccessibilityAdapter>,
         descriptors: Arc<Descriptors>,
     ) -> Self {
         Self {
             player: None,
             event_loop,
             window,
+            accessibility,
             descriptors,
+            gamepads: GamepadInput::new(),
+            debug_server: None,
         }
     }
 
     pub fn create(&mut self, opt: &PlayerOptions, movie_url: &Url, movie_view: MovieView) {
+        if let (Some(port), None) = (opt.debug_port, &self.debug_server) {
+            match DebugServer::start(port) {
+                Ok(server) => self.debug_server = Some(server),
+                Err(e) => tracing::error!("Unable to listen for debuggers on port {port}: {e}"),
+            }
+        }
+
         self.player = Some(ActivePlayer::new(
             opt,
             self.event_loop.clone(),
             movie_url,
             self.window.clone(),
+            self.accessibility.clone(),
             self.descriptors.clone(),
             movie_view,
+            self.debug_server.as_ref().map(DebugServer::connection),
         ));
+        if let Some(mut player) = self.get() {
+            for event in self.gamepads.connected_gamepads() {
+                player.handle_event(event);
+            }
+        }
+    }
+
+    /// Passes the events of the gamepads since the last call to the player.
+    pub fn poll_gamepads(&mut self) {
+        let events = self.gamepads.poll();
+        if let Some(mut player) = self.get() {
+            for event in events {
+                player.handle_event(event);
+            }
+        }
     }
 
     pub fn destroy(&mut self) {
@@ -239,6 +385,10 @@ impl PlayerController {
         }
     }
 
+    pub fn audio_tap(&self) -> Option<AudioTap> {
+        self.player.as_ref()?.audio_tap.clone()
+    }
+
     pub fn handle_event(&self, event: PlayerEvent) {
         if let Some(mut player) = self.get() {
             if player.is_playing() {
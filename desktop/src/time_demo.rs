@@ -9,7 +9,7 @@ use std::io::Read;
 use std::time::Instant;
 use url::Url;
 
-fn load_movie(url: &Url, opt: &Opt) -> Result<SwfMovie, Error> {
+pub fn load_movie(url: &Url, opt: &Opt) -> Result<SwfMovie, Error> {
     let mut movie = if url.scheme() == "file" {
         SwfMovie::from_path(
             url.to_file_path()
@@ -1,7 +1,10 @@
 use crate::custom_event::RuffleEvent;
 use anyhow::{anyhow, Error};
+use rand::Rng;
 use rfd::FileDialog;
-use ruffle_core::events::{KeyCode, TextControlCode};
+use ruffle_core::events::{KeyCode, KeyLocation, TextControlCode};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
 use url::Url;
 use winit::dpi::PhysicalSize;
@@ -124,6 +127,8 @@ pub fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> KeyCode {
         VirtualKeyCode::NumpadSubtract => KeyCode::NumpadMinus,
         VirtualKeyCode::NumpadDecimal => KeyCode::NumpadPeriod,
         VirtualKeyCode::NumpadDivide => KeyCode::NumpadSlash,
+        VirtualKeyCode::NumpadEnter => KeyCode::Return,
+        VirtualKeyCode::Numlock => KeyCode::NumLock,
         VirtualKeyCode::PageUp => KeyCode::PgUp,
         VirtualKeyCode::PageDown => KeyCode::PgDown,
         VirtualKeyCode::End => KeyCode::End,
@@ -152,6 +157,39 @@ pub fn winit_to_ruffle_key_code(key_code: VirtualKeyCode) -> KeyCode {
     }
 }
 
+/// Convert a winit `VirtualKeyCode` into the location of the key, as reported by
+/// `KeyboardEvent.keyLocation`.
+pub fn winit_to_ruffle_key_location(key_code: VirtualKeyCode) -> KeyLocation {
+    match key_code {
+        VirtualKeyCode::LShift
+        | VirtualKeyCode::LControl
+        | VirtualKeyCode::LAlt
+        | VirtualKeyCode::LWin => KeyLocation::Left,
+        VirtualKeyCode::RShift
+        | VirtualKeyCode::RControl
+        | VirtualKeyCode::RAlt
+        | VirtualKeyCode::RWin => KeyLocation::Right,
+        VirtualKeyCode::Numpad0
+        | VirtualKeyCode::Numpad1
+        | VirtualKeyCode::Numpad2
+        | VirtualKeyCode::Numpad3
+        | VirtualKeyCode::Numpad4
+        | VirtualKeyCode::Numpad5
+        | VirtualKeyCode::Numpad6
+        | VirtualKeyCode::Numpad7
+        | VirtualKeyCode::Numpad8
+        | VirtualKeyCode::Numpad9
+        | VirtualKeyCode::NumpadMultiply
+        | VirtualKeyCode::NumpadAdd
+        | VirtualKeyCode::NumpadSubtract
+        | VirtualKeyCode::NumpadDecimal
+        | VirtualKeyCode::NumpadDivide
+        | VirtualKeyCode::NumpadEnter
+        | VirtualKeyCode::Numlock => KeyLocation::NumPad,
+        _ => KeyLocation::Standard,
+    }
+}
+
 /// Return a character for the given key code and shift state.
 pub fn winit_key_to_char(key_code: VirtualKeyCode, is_shift_down: bool) -> Option<char> {
     // We need to know the character that a keypress outputs for both key down and key up events,
@@ -310,6 +348,101 @@ pub fn parse_url(path: &Path) -> Result<Url, Error> {
     }
 }
 
+/// Generates a random token, which local connections have to present to be trusted.
+pub fn generate_token() -> String {
+    let mut rng = rand::thread_rng();
+    (0..16)
+        .map(|_| format!("{:02x}", rng.gen::<u8>()))
+        .collect()
+}
+
+/// Compares two strings in a time that doesn't depend on where they differ.
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// Creates a directory that only the current user can access, or checks that the
+/// existing directory is one.
+///
+/// Fails if the path is a symlink, or if the directory belongs to another user or
+/// can be accessed by other users.
+pub fn create_private_dir(path: &Path) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    if let Err(e) = builder.create(path) {
+        if e.kind() != io::ErrorKind::AlreadyExists {
+            return Err(e);
+        }
+    }
+
+    // Unlike `metadata`, this doesn't follow symlinks, so a symlink isn't a directory.
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!("{} isn't a directory", path.display()),
+        ));
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        // SAFETY: `getuid` has no preconditions and can't fail.
+        let uid = unsafe { libc::getuid() };
+        if metadata.uid() != uid || metadata.mode() & 0o077 != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("{} is accessible by other users", path.display()),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Writes a file that only the current user can read, replacing any existing file.
+///
+/// The file is created anew, as the permissions of an existing file wouldn't change.
+/// A symlink in its place is replaced rather than followed.
+pub fn write_private_file(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Err(e) = fs::remove_file(path) {
+        if e.kind() != io::ErrorKind::NotFound {
+            return Err(e);
+        }
+    }
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(contents)
+}
+
+/// Reads a file written by `write_private_file`, refusing to follow a symlink.
+pub fn read_private_file(path: &Path) -> io::Result<String> {
+    let mut options = OpenOptions::new();
+    options.read(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_NOFOLLOW);
+    }
+    let mut contents = String::new();
+    options.open(path)?.read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
 fn actually_pick_file(dir: Option<PathBuf>) -> Option<PathBuf> {
     let mut dialog = FileDialog::new()
         .add_filter("Flash Files", &["swf", "spl"])
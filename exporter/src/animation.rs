@@ -0,0 +1,123 @@
+use anyhow::{anyhow, Context, Result};
+use clap::ValueEnum;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, RgbaImage};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+#[derive(ValueEnum, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// A PNG image per frame
+    Png,
+    /// An animated PNG
+    Apng,
+    /// An animated GIF
+    Gif,
+    /// An H.264 video, encoded by ffmpeg
+    Mp4,
+}
+
+impl OutputFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Png | OutputFormat::Apng => "png",
+            OutputFormat::Gif => "gif",
+            OutputFormat::Mp4 => "mp4",
+        }
+    }
+
+    /// Whether every frame goes into one file.
+    pub fn is_animation(self) -> bool {
+        self != OutputFormat::Png
+    }
+}
+
+/// The frames captured from a movie.
+pub struct Animation {
+    pub frames: Vec<RgbaImage>,
+    pub frame_rate: f64,
+}
+
+impl Animation {
+    /// Encode the frames as one file, in a format that isn't `OutputFormat::Png`.
+    pub fn save(&self, format: OutputFormat, path: &Path, ffmpeg: &Path) -> Result<()> {
+        match format {
+            OutputFormat::Png => Err(anyhow!("PNG sequences are saved frame by frame")),
+            OutputFormat::Apng => self.save_apng(path),
+            OutputFormat::Gif => self.save_gif(path),
+            OutputFormat::Mp4 => self.save_mp4(path, ffmpeg),
+        }
+    }
+
+    fn dimensions(&self) -> Result<(u32, u32)> {
+        self.frames
+            .first()
+            .map(|frame| frame.dimensions())
+            .ok_or_else(|| anyhow!("No frames were captured"))
+    }
+
+    fn save_apng(&self, path: &Path) -> Result<()> {
+        let (width, height) = self.dimensions()?;
+        let mut encoder = png::Encoder::new(BufWriter::new(File::create(path)?), width, height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.set_animated(self.frames.len() as u32, 0)?;
+        // The delay is a fraction of a second, so keep two decimal places of the frame rate.
+        let frame_rate = (self.frame_rate * 100.0)
+            .round()
+            .clamp(1.0, u16::MAX.into()) as u16;
+        encoder.set_frame_delay(100, frame_rate)?;
+
+        let mut writer = encoder.write_header()?;
+        for frame in &self.frames {
+            writer.write_image_data(frame.as_raw())?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn save_gif(&self, path: &Path) -> Result<()> {
+        let mut encoder = GifEncoder::new(BufWriter::new(File::create(path)?));
+        encoder.set_repeat(Repeat::Infinite)?;
+        // GIFs store delays in hundredths of a second, which the encoder rounds to.
+        let frame_rate = (self.frame_rate * 100.0).round().max(1.0) as u32;
+        let delay = Delay::from_numer_denom_ms(100_000, frame_rate);
+        encoder.encode_frames(
+            self.frames
+                .iter()
+                .map(|frame| Frame::from_parts(frame.clone(), 0, 0, delay)),
+        )?;
+        Ok(())
+    }
+
+    fn save_mp4(&self, path: &Path, ffmpeg: &Path) -> Result<()> {
+        let (width, height) = self.dimensions()?;
+        let mut process = Command::new(ffmpeg)
+            .args(["-loglevel", "error", "-y"])
+            .args(["-f", "rawvideo", "-pix_fmt", "rgba"])
+            .args(["-s", &format!("{width}x{height}")])
+            .args(["-framerate", &self.frame_rate.to_string()])
+            .args(["-i", "-"])
+            .args(["-c:v", "libx264", "-pix_fmt", "yuv420p"])
+            // libx264 needs even dimensions.
+            .args(["-vf", "pad=ceil(iw/2)*2:ceil(ih/2)*2"])
+            .arg(path)
+            .stdin(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Couldn't run {}", ffmpeg.display()))?;
+
+        let mut stdin = process.stdin.take().expect("ffmpeg stdin must be piped");
+        for frame in &self.frames {
+            stdin.write_all(frame.as_raw())?;
+        }
+        drop(stdin);
+
+        let status = process.wait()?;
+        if !status.success() {
+            return Err(anyhow!("ffmpeg failed with {status}"));
+        }
+        Ok(())
+    }
+}
@@ -1,9 +1,12 @@
+mod animation;
+mod wait;
+
+use animation::{Animation, OutputFormat};
 use anyhow::{anyhow, Result};
 use clap::Parser;
-use image::RgbaImage;
 use indicatif::{ProgressBar, ProgressStyle};
 use rayon::prelude::*;
-use ruffle_core::limits::ExecutionLimit;
+use ruffle_core::headless::{hash_pixels, HeadlessRunner, InputScript};
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::PlayerBuilder;
 use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
@@ -13,9 +16,10 @@ use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::wgpu;
 use std::fs::create_dir_all;
 use std::io::{self, Write};
-use std::panic::catch_unwind;
+use std::panic::{catch_unwind, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use wait::{wait_for, CallLog, WaitCondition};
 use walkdir::{DirEntry, WalkDir};
 
 #[derive(Parser, Debug, Copy, Clone)]
@@ -42,7 +46,7 @@ struct Opt {
 
     /// The file or directory (if multiple frames/files) to store the capture in.
     /// The default value will either be:
-    /// - If given one swf and one frame, the name of the swf + ".png"
+    /// - If given one swf and one frame, or an animation format, the name of the swf + the format's extension
     /// - If given one swf and multiple frames, the name of the swf as a directory
     /// - If given multiple swfs, this field is required.
     #[clap(name = "output")]
@@ -56,6 +60,34 @@ struct Opt {
     #[clap(long = "skipframes", default_value = "0")]
     skipframes: u32,
 
+    /// Skip frames until the main timeline reaches the frame with this label, before skipping `--skipframes` more
+    #[clap(long)]
+    start_label: Option<String>,
+
+    /// Wait for the movie to settle before skipping `--skipframes` and capturing frames:
+    /// "label:NAME" waits for the main timeline to reach a frame label,
+    /// and "call:NAME" waits for the movie to call a function through ExternalInterface.
+    /// Can be given more than once, to wait for whichever happens first.
+    #[clap(long)]
+    wait_for: Vec<WaitCondition>,
+
+    /// The most seconds of movie time to wait for `--wait-for`, after which frames are captured anyway
+    #[clap(long, default_value = "10")]
+    wait_timeout: f64,
+
+    /// How to save the captured frames: as separate images, or as one animation
+    #[clap(long, value_enum, default_value = "png")]
+    format: OutputFormat,
+
+    /// Render frames with a transparent background instead of the movie's background color.
+    /// MP4 videos can't be transparent.
+    #[clap(long, action)]
+    transparent: bool,
+
+    /// The ffmpeg executable to encode MP4 videos with
+    #[clap(long, default_value = "ffmpeg")]
+    ffmpeg: PathBuf,
+
     /// Don't show a progress bar
     #[clap(short, long, action)]
     silent: bool,
@@ -81,24 +113,32 @@ struct Opt {
     /// Skip unsupported movie types (currently AVM 2)
     #[clap(long, action)]
     skip_unsupported: bool,
+
+    /// A script of input events to send to each movie.
+    /// Each line is a frame number followed by an event, such as "10 mouse_down 320 240 left".
+    #[clap(long)]
+    input_script: Option<PathBuf>,
+
+    /// Print a hash of each captured frame instead of saving it as an image
+    #[clap(long, action)]
+    hash: bool,
 }
 
-/// Captures a screenshot. The resulting image uses straight alpha
+/// Captures the frames of a movie. The resulting images use straight alpha
 fn take_screenshot(
     descriptors: Arc<Descriptors>,
     swf_path: &Path,
-    frames: u32,
-    skipframes: u32,
+    opt: &Opt,
     progress: &Option<ProgressBar>,
-    size: SizeOpt,
-    skip_unsupported: bool,
-) -> Result<Vec<RgbaImage>> {
+    script: &InputScript,
+) -> Result<Animation> {
     let movie = SwfMovie::from_path(swf_path, None).map_err(|e| anyhow!(e.to_string()))?;
 
-    if movie.is_action_script_3() && skip_unsupported {
+    if movie.is_action_script_3() && opt.skip_unsupported {
         return Err(anyhow!("Skipping unsupported movie"));
     }
 
+    let size = opt.size;
     let width = size
         .width
         .map(f64::from)
@@ -110,19 +150,63 @@ fn take_screenshot(
         .map(f64::from)
         .unwrap_or_else(|| movie.height().to_pixels());
     let height = (height * size.scale).round() as u32;
+    let num_frames = movie.num_frames();
 
     let target = TextureTarget::new(&descriptors.device, (width, height))
         .map_err(|e| anyhow!(e.to_string()))?;
-    let player = PlayerBuilder::new()
+    let mut builder = PlayerBuilder::new()
         .with_renderer(
             WgpuRenderBackend::new(descriptors, target).map_err(|e| anyhow!(e.to_string()))?,
         )
         .with_movie(movie)
-        .with_viewport_dimensions(width, height, size.scale)
-        .build();
+        .with_viewport_dimensions(width, height, size.scale);
+    // Only offer ExternalInterface to movies that are expected to use it.
+    let calls = CallLog::default();
+    if opt
+        .wait_for
+        .iter()
+        .any(|condition| matches!(condition, WaitCondition::Call(_)))
+    {
+        builder = builder.with_external_interface(Box::new(calls.clone()));
+    }
+    let player = builder.build();
+    if opt.transparent {
+        player.lock().unwrap().set_window_mode("transparent");
+    }
+
+    let mut runner = HeadlessRunner::new(&mut player.lock().unwrap(), script.clone());
+    let mut run_frame = |i: u32| {
+        catch_unwind(AssertUnwindSafe(|| {
+            runner.run_frame(&mut player.lock().unwrap())
+        }))
+        .map_err(|_| anyhow!("Unable to run frame {} of {:?}", i, swf_path))
+    };
+
+    // The frame that a wait ends on has already run, so it doesn't run again.
+    let mut frame_has_run = false;
+    if let Some(label) = &opt.start_label {
+        // Any frame of the main timeline is reached within one pass through it.
+        let conditions = [WaitCondition::Label(label.clone())];
+        let max_frames = u32::from(num_frames) + 1;
+        if !wait_for(&conditions, max_frames, &calls, &player, &mut run_frame)? {
+            return Err(anyhow!(
+                "The main timeline of {:?} never reached the label {:?}",
+                swf_path,
+                label
+            ));
+        }
+        frame_has_run = true;
+    }
+    if !opt.wait_for.is_empty() {
+        let frame_rate = player.lock().unwrap().frame_rate();
+        let max_frames = (opt.wait_timeout * frame_rate).ceil() as u32;
+        // Timing out isn't an error, as some movies never signal that they're ready.
+        let met = wait_for(&opt.wait_for, max_frames, &calls, &player, &mut run_frame)?;
+        frame_has_run |= met || max_frames > 0;
+    }
 
-    let mut result = Vec::new();
-    let totalframes = frames + skipframes;
+    let mut frames = Vec::new();
+    let totalframes = opt.frames + opt.skipframes;
 
     for i in 0..totalframes {
         if let Some(progress) = &progress {
@@ -133,12 +217,11 @@ fn take_screenshot(
             ));
         }
 
-        player.lock().unwrap().preload(&mut ExecutionLimit::none());
-
-        player.lock().unwrap().run_frame();
-        if i >= skipframes {
+        if !std::mem::take(&mut frame_has_run) {
+            run_frame(i)?;
+        }
+        if i >= opt.skipframes {
             match catch_unwind(|| {
-                player.lock().unwrap().render();
                 let mut player = player.lock().unwrap();
                 let renderer = player
                     .renderer_mut()
@@ -146,7 +229,7 @@ fn take_screenshot(
                     .unwrap();
                 renderer.capture_frame()
             }) {
-                Ok(Some(image)) => result.push(image),
+                Ok(Some(image)) => frames.push(image),
                 Ok(None) => return Err(anyhow!("Unable to capture frame {} of {:?}", i, swf_path)),
                 Err(e) => {
                     return Err(anyhow!(
@@ -163,7 +246,9 @@ fn take_screenshot(
             progress.inc(1);
         }
     }
-    Ok(result)
+
+    let frame_rate = player.lock().unwrap().frame_rate();
+    Ok(Animation { frames, frame_rate })
 }
 
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
@@ -196,17 +281,21 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
+fn capture_single_swf(
+    descriptors: Arc<Descriptors>,
+    opt: &Opt,
+    script: &InputScript,
+) -> Result<()> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         result.set_file_name(opt.swf.file_stem().unwrap());
-        if opt.frames == 1 {
-            result.set_extension("png");
+        if opt.frames == 1 || opt.format.is_animation() {
+            result.set_extension(opt.format.extension());
         }
         result
     });
 
-    if opt.frames > 1 {
+    if opt.frames > 1 && !opt.format.is_animation() {
         let _ = create_dir_all(&output);
     }
 
@@ -224,21 +313,26 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         None
     };
 
-    let frames = take_screenshot(
-        descriptors,
-        &opt.swf,
-        opt.frames,
-        opt.skipframes,
-        &progress,
-        opt.size,
-        opt.skip_unsupported,
-    )?;
+    let animation = take_screenshot(descriptors, &opt.swf, opt, &progress, script)?;
+    let frames = &animation.frames;
 
     if let Some(progress) = &progress {
         progress.set_message(opt.swf.file_stem().unwrap().to_string_lossy().into_owned());
     }
 
-    if frames.len() == 1 {
+    if opt.hash {
+        for (frame, image) in frames.iter().enumerate() {
+            println!("{frame}: {:016x}", hash_pixels(image.as_raw()));
+        }
+        if let Some(progress) = progress {
+            progress.finish_and_clear();
+        }
+        return Ok(());
+    }
+
+    if opt.format.is_animation() {
+        animation.save(opt.format, &output, &opt.ffmpeg)?;
+    } else if frames.len() == 1 {
         let image = frames.get(0).unwrap();
         if opt.output_path == Some(PathBuf::from("-")) {
             let mut bytes: Vec<u8> = Vec::new();
@@ -262,7 +356,14 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
         }
     }
 
-    let message = if frames.len() == 1 {
+    let message = if opt.format.is_animation() {
+        Some(format!(
+            "Saved {} frames of {} to {}",
+            frames.len(),
+            opt.swf.to_string_lossy(),
+            output.to_string_lossy()
+        ))
+    } else if frames.len() == 1 {
         if !opt.silent {
             Some(format!(
                 "Saved first frame of {} to {}",
@@ -293,8 +394,12 @@ fn capture_single_swf(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
 }
 
 #[allow(clippy::branches_sharing_code)]
-fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()> {
-    let output = opt.output_path.clone().unwrap();
+fn capture_multiple_swfs(
+    descriptors: Arc<Descriptors>,
+    opt: &Opt,
+    script: &InputScript,
+) -> Result<()> {
+    let output = opt.output_path.clone().unwrap_or_default();
     let files = find_files(&opt.swf, !opt.silent);
 
     let progress = if !opt.silent {
@@ -321,29 +426,36 @@ fn capture_multiple_swfs(descriptors: Arc<Descriptors>, opt: &Opt) -> Result<()>
                     .into_owned(),
             );
         }
-        if let Ok(frames) = take_screenshot(
-            descriptors.clone(),
-            file.path(),
-            opt.frames,
-            opt.skipframes,
-            &progress,
-            opt.size,
-            opt.skip_unsupported,
-        ) {
+        if let Ok(animation) =
+            take_screenshot(descriptors.clone(), file.path(), opt, &progress, script)
+        {
+            let frames = &animation.frames;
             let mut relative_path = file
                 .path()
                 .strip_prefix(&opt.swf)
                 .unwrap_or_else(|_| file.path())
                 .to_path_buf();
 
-            if frames.len() == 1 {
+            if opt.hash {
+                for (frame, image) in frames.iter().enumerate() {
+                    println!(
+                        "{} {frame}: {:016x}",
+                        relative_path.to_string_lossy(),
+                        hash_pixels(image.as_raw())
+                    );
+                }
+            } else if opt.format.is_animation() || frames.len() == 1 {
                 let mut destination: PathBuf = (&output).into();
-                relative_path.set_extension("png");
+                relative_path.set_extension(opt.format.extension());
                 destination.push(relative_path);
                 if let Some(parent) = destination.parent() {
                     let _ = create_dir_all(parent);
                 }
-                frames.get(0).unwrap().save(&destination)?;
+                if opt.format.is_animation() {
+                    animation.save(opt.format, &destination, &opt.ffmpeg)?;
+                } else {
+                    frames.get(0).unwrap().save(&destination)?;
+                }
             } else {
                 let mut parent: PathBuf = (&output).into();
                 relative_path.set_extension("");
@@ -416,10 +528,15 @@ fn main() -> Result<()> {
 
     let descriptors = Arc::new(Descriptors::new(instance, adapter, device, queue));
 
+    let script = match &opt.input_script {
+        Some(path) => InputScript::parse(&std::fs::read_to_string(path)?)?,
+        None => InputScript::default(),
+    };
+
     if opt.swf.is_file() {
-        capture_single_swf(descriptors, &opt)?;
-    } else if opt.output_path.is_some() {
-        capture_multiple_swfs(descriptors, &opt)?;
+        capture_single_swf(descriptors, &opt, &script)?;
+    } else if opt.output_path.is_some() || opt.hash {
+        capture_multiple_swfs(descriptors, &opt, &script)?;
     } else {
         return Err(anyhow!(
             "Output directory is required when exporting multiple files."
@@ -0,0 +1,93 @@
+use anyhow::Result;
+use ruffle_core::context::UpdateContext;
+use ruffle_core::external::{ExternalInterfaceMethod, ExternalInterfaceProvider, Value};
+use ruffle_core::Player;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+/// Something that a movie does once it has settled, such as after a loader or an intro.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WaitCondition {
+    /// The main timeline reaches the frame with this label.
+    Label(String),
+
+    /// The movie calls this function through `ExternalInterface.call`.
+    Call(String),
+}
+
+impl FromStr for WaitCondition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("label", label)) => Ok(Self::Label(label.to_string())),
+            Some(("call", name)) => Ok(Self::Call(name.to_string())),
+            _ => Err(format!(
+                "Expected \"label:NAME\" or \"call:NAME\", not \"{s}\""
+            )),
+        }
+    }
+}
+
+/// Records the functions that a movie calls through ExternalInterface, which all return undefined.
+#[derive(Clone, Default)]
+pub struct CallLog(Rc<RefCell<HashSet<String>>>);
+
+impl CallLog {
+    fn contains(&self, name: &str) -> bool {
+        self.0.borrow().contains(name)
+    }
+}
+
+impl ExternalInterfaceProvider for CallLog {
+    fn get_method(&self, name: &str) -> Option<Box<dyn ExternalInterfaceMethod>> {
+        Some(Box::new(LoggedCall {
+            log: self.clone(),
+            name: name.to_string(),
+        }))
+    }
+
+    fn on_callback_available(&self, _name: &str) {}
+}
+
+struct LoggedCall {
+    log: CallLog,
+    name: String,
+}
+
+impl ExternalInterfaceMethod for LoggedCall {
+    fn call(&self, _context: &mut UpdateContext<'_, '_>, _args: &[Value]) -> Result<Value, String> {
+        self.log.0.borrow_mut().insert(self.name.clone());
+        Ok(Value::Undefined)
+    }
+}
+
+/// Run frames until any of the conditions is met, for at most `max_frames` frames.
+///
+/// Returns whether a condition was met.
+pub fn wait_for(
+    conditions: &[WaitCondition],
+    max_frames: u32,
+    calls: &CallLog,
+    player: &Mutex<Player>,
+    mut run_frame: impl FnMut(u32) -> Result<()>,
+) -> Result<bool> {
+    for i in 0..max_frames {
+        run_frame(i)?;
+        let mut player = player.lock().unwrap();
+        let met = conditions.iter().any(|condition| match condition {
+            WaitCondition::Label(label) => {
+                let label_frame = player.frame_label_number(label);
+                label_frame.is_some() && label_frame == player.current_frame()
+            }
+            WaitCondition::Call(name) => calls.contains(name),
+        });
+        if met {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
@@ -380,6 +380,7 @@ impl WebCanvasRenderBackend {
             RenderBlendMode::Builtin(BlendMode::Overlay) => "overlay",
             RenderBlendMode::Builtin(BlendMode::HardLight) => "hard-light",
             RenderBlendMode::Shader(_) => "source-over", // Canvas does not support shaders
+            RenderBlendMode::AlphaMask => "source-over", // Requires intermediate buffer.
         };
         self.context
             .set_global_composite_operation(mode)
@@ -524,6 +525,18 @@ impl RenderBackend for WebCanvasRenderBackend {
         Err(Error::Unimplemented("run_pixelbender_shader".into()))
     }
 
+    fn run_pixelbender_shader_to_floats(
+        &mut self,
+        _handle: ruffle_render::pixel_bender::PixelBenderShaderHandle,
+        _arguments: &[ruffle_render::pixel_bender::PixelBenderShaderArgument],
+        _width: u32,
+        _height: u32,
+    ) -> Result<Vec<f32>, Error> {
+        Err(Error::Unimplemented(
+            "run_pixelbender_shader_to_floats".into(),
+        ))
+    }
+
     fn create_empty_texture(&mut self, width: u32, height: u32) -> Result<BitmapHandle, Error> {
         let bitmap_data = BitmapData::empty(width, height).map_err(Error::JavascriptError)?;
         Ok(BitmapHandle(Arc::new(bitmap_data)))
@@ -802,6 +815,10 @@ impl CommandHandler for WebCanvasRenderBackend {
     }
 
     fn blend(&mut self, commands: CommandList, blend: RenderBlendMode) {
+        if let RenderBlendMode::AlphaMask = blend {
+            // Without an intermediate buffer, an alpha mask would just be drawn on top.
+            return;
+        }
         self.push_blend_mode(blend);
         commands.execute(self);
         self.pop_blend_mode();
@@ -980,12 +997,7 @@ fn swf_shape_to_canvas_commands(
                     line_cap: line_cap.to_string(),
                     line_join: line_join.to_string(),
                     miter_limit: miter_limit as f64 / 20.0,
-                    scale_mode: match (style.allow_scale_x(), style.allow_scale_y()) {
-                        (false, false) => LineScaleMode::None,
-                        (true, false) => LineScaleMode::Horizontal,
-                        (false, true) => LineScaleMode::Vertical,
-                        (true, true) => LineScaleMode::Both,
-                    },
+                    scale_mode: LineScaleMode::for_style(style),
                 });
             }
         }
@@ -250,6 +250,9 @@ pub struct ShaderConfig<'a> {
     pub shader_type: ShaderType,
     pub vertex_attributes: &'a [Option<VertexAttributeFormat>; 8],
     pub sampler_overrides: &'a [Option<SamplerOverride>; 8],
+    /// Samplers bound to textures which can only be sampled with clamping
+    /// (`RectangleTexture` and `VideoTexture`).
+    pub clamped_samplers: &'a [bool; 8],
     pub version: AgalVersion,
 }
 
@@ -264,6 +267,7 @@ impl<'a> NagaBuilder<'a> {
         mut agal: &[u8],
         vertex_attributes: &[Option<VertexAttributeFormat>; MAX_VERTEX_ATTRIBUTES],
         sampler_overrides: &[Option<SamplerOverride>; 8],
+        clamped_samplers: &[bool; 8],
     ) -> Result<Module> {
         let data = &mut agal;
 
@@ -277,7 +281,8 @@ impl<'a> NagaBuilder<'a> {
 
         let version = match version {
             1 => AgalVersion::Agal1,
-            2 => AgalVersion::Agal2,
+            // AGAL 3 only adds instanced drawing, which doesn't change how we translate programs.
+            2 | 3 => AgalVersion::Agal2,
             _ => return Err(Error::InvalidVersion(version)),
         };
 
@@ -295,6 +300,7 @@ impl<'a> NagaBuilder<'a> {
             shader_type,
             vertex_attributes,
             sampler_overrides,
+            clamped_samplers,
             version,
         });
 
@@ -1103,6 +1109,7 @@ impl<'a> NagaBuilder<'a> {
 
                 let mut filter = sampler_field.filter;
                 let mut wrapping = sampler_field.wrapping;
+                let mut mipmap = sampler_field.mipmap;
 
                 // See https://github.com/openfl/openfl/issues/1332
 
@@ -1119,6 +1126,11 @@ impl<'a> NagaBuilder<'a> {
                 {
                     filter = sampler_override.filter;
                     wrapping = sampler_override.wrapping;
+                    mipmap = sampler_override.mipmap;
+                }
+
+                if self.shader_config.clamped_samplers[texture_id as usize] {
+                    wrapping = Wrapping::Clamp;
                 }
 
                 let sampler_binding = match (filter, wrapping) {
@@ -1178,7 +1190,11 @@ impl<'a> NagaBuilder<'a> {
                     array_index: None,
                     offset: None,
                     // FIXME - get this from 'LOD_bias' in the sampler field
-                    level: naga::SampleLevel::Auto,
+                    level: if mipmap == Mipmap::Disable {
+                        naga::SampleLevel::Zero
+                    } else {
+                        naga::SampleLevel::Auto
+                    },
                     depth_ref: None,
                     gather: None,
                 });
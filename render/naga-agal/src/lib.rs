@@ -2,6 +2,7 @@ use naga::Module;
 
 mod builder;
 mod types;
+mod validate;
 mod varying;
 
 use builder::NagaBuilder;
@@ -29,6 +30,8 @@ pub enum Error {
     ReadError(std::io::Error),
     InvalidOpcode(u32),
     InvalidVersion(u32),
+    InvalidRegisterType(u8),
+    InvalidSampler(u64),
 }
 
 impl From<std::io::Error> for Error {
@@ -37,13 +40,23 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ShaderType {
     Vertex,
     Fragment,
 }
 
+impl std::fmt::Display for ShaderType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShaderType::Vertex => write!(f, "vertex"),
+            ShaderType::Fragment => write!(f, "fragment"),
+        }
+    }
+}
+
 pub use types::{Filter, Mipmap, SamplerOverride, Wrapping};
+pub use validate::{validate_agal, ValidationError};
 
 /**
  * Compiles an Adobe AGAL shader to a Naga Module.
@@ -94,6 +107,7 @@ pub fn agal_to_naga(
     agal: &[u8],
     vertex_attributes: &[Option<VertexAttributeFormat>; MAX_VERTEX_ATTRIBUTES],
     sampler_overrides: &[Option<SamplerOverride>; MAX_TEXTURES],
+    clamped_samplers: &[bool; MAX_TEXTURES],
 ) -> Result<Module, Error> {
-    NagaBuilder::process_agal(agal, vertex_attributes, sampler_overrides)
+    NagaBuilder::process_agal(agal, vertex_attributes, sampler_overrides, clamped_samplers)
 }
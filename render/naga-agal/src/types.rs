@@ -2,7 +2,7 @@ use crate::Error;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 
-#[derive(num_derive::FromPrimitive, Debug)]
+#[derive(num_derive::FromPrimitive, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Opcode {
     Mov = 0x00,
     Add = 0x01,
@@ -57,6 +57,12 @@ pub enum RegisterType {
     FragmentRegister = 6,
 }
 
+impl RegisterType {
+    pub fn parse(val: u8) -> Result<RegisterType, Error> {
+        RegisterType::from_u8(val).ok_or(Error::InvalidRegisterType(val))
+    }
+}
+
 #[derive(Debug, FromPrimitive, Clone)]
 pub enum DirectMode {
     Direct = 0,
@@ -74,7 +80,7 @@ impl DestField {
     pub fn parse(val: u32) -> Result<DestField, Error> {
         let reg_num = (val & 0xFFFF) as u16;
         let write_mask = Mask::from_bits(((val >> 16) & 0xF) as u8).unwrap();
-        let reg_type = RegisterType::from_u16(((val >> 24) & 0xF) as u16).unwrap();
+        let reg_type = RegisterType::parse(((val >> 24) & 0xF) as u8)?;
         Ok(DestField {
             register_type: reg_type,
             write_mask,
@@ -111,8 +117,8 @@ impl SourceField {
         let reg_num = (val & 0xFFFF) as u16;
         let indirect_offset = ((val >> 16) & 0xFF) as u8;
         let swizzle = ((val >> 24) & 0xFF) as u8;
-        let register_type = RegisterType::from_u16(((val >> 32) & 0xF) as u16).unwrap();
-        let index_type = RegisterType::from_u16(((val >> 40) & 0xF) as u16).unwrap();
+        let register_type = RegisterType::parse(((val >> 32) & 0xF) as u8)?;
+        let index_type = RegisterType::parse(((val >> 40) & 0xF) as u8)?;
         let index_select = ((val >> 48) & 0x3) as u8;
         let direct_mode = DirectMode::from_u16(((val >> 63) & 0x1) as u16).unwrap();
         Ok(SourceField {
@@ -192,12 +198,12 @@ impl SamplerField {
     pub fn parse(val: u64) -> Result<SamplerField, Error> {
         let reg_num = (val & 0xFFFF) as u16;
         let load_bias = ((val >> 16) & 0xFF) as i8;
-        let reg_type = RegisterType::from_u64((val >> 32) & 0xF).unwrap();
-        let dimension = Dimension::from_u64((val >> 44) & 0xF).unwrap();
-        let special = Special::parse(((val >> 48) & 0xF) as u8).unwrap();
-        let wrapping = Wrapping::from_u64((val >> 52) & 0xF).unwrap();
-        let mipmap = Mipmap::from_u64((val >> 56) & 0xF).unwrap();
-        let filter = Filter::from_u64((val >> 60) & 0xF).unwrap();
+        let reg_type = RegisterType::parse(((val >> 32) & 0xF) as u8)?;
+        let dimension = Dimension::from_u64((val >> 44) & 0xF).ok_or(Error::InvalidSampler(val))?;
+        let special = Special::parse(((val >> 48) & 0xF) as u8)?;
+        let wrapping = Wrapping::from_u64((val >> 52) & 0xF).ok_or(Error::InvalidSampler(val))?;
+        let mipmap = Mipmap::from_u64((val >> 56) & 0xF).ok_or(Error::InvalidSampler(val))?;
+        let filter = Filter::from_u64((val >> 60) & 0xF).ok_or(Error::InvalidSampler(val))?;
 
         Ok(SamplerField {
             filter,
@@ -0,0 +1,358 @@
+//! Validation of AGAL programs.
+//!
+//! Flash validates AGAL bytecode when `Program3D.upload` is called, and throws a descriptive
+//! error if something is wrong with it. This performs the same checks, so that bad programs
+//! can be rejected up front instead of failing during translation.
+
+use crate::types::{DestField, DirectMode, Opcode, RegisterType, SamplerField, SourceField};
+use crate::ShaderType;
+use num_traits::FromPrimitive;
+use std::fmt;
+
+const HEADER_SIZE: usize = 7;
+const TOKEN_SIZE: usize = 24;
+
+/// A reason for an AGAL program to be rejected.
+///
+/// The messages match the ones used by Flash Player, minus the `Error #xxxx: ` prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    ProgramTooSmall(ShaderType),
+    BadMagic(ShaderType),
+    BadVersion(ShaderType, u32),
+    BadShaderTypeId(ShaderType),
+    ShaderTypeMismatch(ShaderType),
+    InvalidOpcode {
+        shader_type: ShaderType,
+        opcode: u32,
+        token: usize,
+    },
+    OpcodeNotInVersion {
+        shader_type: ShaderType,
+        opcode: u32,
+        token: usize,
+    },
+    FragmentOnlyOpcode {
+        shader_type: ShaderType,
+        opcode: u32,
+        token: usize,
+    },
+    UnbalancedCondition {
+        shader_type: ShaderType,
+        token: usize,
+    },
+    RegisterOutOfBounds {
+        shader_type: ShaderType,
+        token: usize,
+    },
+    BadRegisterType {
+        shader_type: ShaderType,
+        token: usize,
+    },
+    BadSamplerUsage {
+        shader_type: ShaderType,
+        token: usize,
+    },
+}
+
+impl ValidationError {
+    /// The error code that Flash Player uses for this error.
+    pub fn code(&self) -> u32 {
+        match self {
+            ValidationError::ProgramTooSmall(_) => 3615,
+            ValidationError::BadMagic(_) => 3616,
+            ValidationError::BadVersion(_, _) => 3617,
+            ValidationError::BadShaderTypeId(_) => 3618,
+            ValidationError::ShaderTypeMismatch(_) => 3619,
+            ValidationError::InvalidOpcode { .. } => 3620,
+            ValidationError::OpcodeNotInVersion { .. } => 3621,
+            ValidationError::FragmentOnlyOpcode { .. } => 3622,
+            ValidationError::UnbalancedCondition { .. } => 3623,
+            ValidationError::RegisterOutOfBounds { .. } => 3625,
+            ValidationError::BadRegisterType { .. } => 3626,
+            ValidationError::BadSamplerUsage { .. } => 3627,
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AGAL validation failed: ")?;
+        match self {
+            ValidationError::ProgramTooSmall(shader_type) => write!(
+                f,
+                "Program size below minimum length for {shader_type} program."
+            ),
+            ValidationError::BadMagic(shader_type) => write!(
+                f,
+                "Not an AGAL program. Wrong magic byte for {shader_type} program."
+            ),
+            ValidationError::BadVersion(shader_type, version) => write!(
+                f,
+                "Bad AGAL version for {shader_type} program. Current version is {version}."
+            ),
+            ValidationError::BadShaderTypeId(shader_type) => write!(
+                f,
+                "Bad AGAL program type identifier for {shader_type} program."
+            ),
+            ValidationError::ShaderTypeMismatch(shader_type) => write!(
+                f,
+                "Shader type must be either fragment or vertex for {shader_type} program."
+            ),
+            ValidationError::InvalidOpcode {
+                shader_type,
+                opcode,
+                token,
+            } => write!(
+                f,
+                "Invalid opcode, value out of range: {opcode} at token {token} of {shader_type} program."
+            ),
+            ValidationError::OpcodeNotInVersion {
+                shader_type,
+                opcode,
+                token,
+            } => write!(
+                f,
+                "Invalid opcode, {opcode} is not implemented in this version at token {token} of {shader_type} program."
+            ),
+            ValidationError::FragmentOnlyOpcode {
+                shader_type,
+                opcode,
+                token,
+            } => write!(
+                f,
+                "Opcode {opcode} only allowed in fragment programs at token {token} of {shader_type} program."
+            ),
+            ValidationError::UnbalancedCondition { shader_type, token } => write!(
+                f,
+                "Open condition did not have a matching close condition at token {token} of {shader_type} program."
+            ),
+            ValidationError::RegisterOutOfBounds { shader_type, token } => write!(
+                f,
+                "Register index out of bounds at token {token} of {shader_type} program."
+            ),
+            ValidationError::BadRegisterType { shader_type, token } => write!(
+                f,
+                "Bad register type at token {token} of {shader_type} program."
+            ),
+            ValidationError::BadSamplerUsage { shader_type, token } => write!(
+                f,
+                "Sampler registers are only allowed as the second source of a tex opcode at token {token} of {shader_type} program."
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// The number of registers of each type available to a program.
+fn register_count(register_type: &RegisterType, shader_type: ShaderType, version: u32) -> u16 {
+    // The counts in AGAL 1, and in AGAL 2 and later.
+    let (agal1, agal2) = match (shader_type, register_type) {
+        (ShaderType::Vertex, RegisterType::Attribute) => (8, 8),
+        (ShaderType::Vertex, RegisterType::Constant) => (128, 250),
+        (ShaderType::Vertex, RegisterType::Output) => (1, 1),
+        (ShaderType::Vertex, RegisterType::Sampler | RegisterType::FragmentRegister) => (0, 0),
+        (ShaderType::Fragment, RegisterType::Attribute) => (0, 0),
+        (ShaderType::Fragment, RegisterType::Constant) => (28, 64),
+        (ShaderType::Fragment, RegisterType::Output) => (1, 4),
+        (ShaderType::Fragment, RegisterType::Sampler) => (8, 16),
+        (ShaderType::Fragment, RegisterType::FragmentRegister) => (1, 1),
+        (_, RegisterType::Temporary) => (8, 26),
+        (_, RegisterType::Varying) => (8, 10),
+    };
+    if version >= 2 {
+        agal2
+    } else {
+        agal1
+    }
+}
+
+/// Checks that `agal` is a well formed AGAL program of the given type.
+///
+/// This checks the header, that every opcode exists (in the program's version) and is allowed in
+/// this type of program, that every register that's accessed exists, that samplers are only used
+/// by `tex`, and that conditional blocks are balanced.
+pub fn validate_agal(agal: &[u8], shader_type: ShaderType) -> Result<(), ValidationError> {
+    if agal.len() < HEADER_SIZE {
+        return Err(ValidationError::ProgramTooSmall(shader_type));
+    }
+    if agal[0] != 0xa0 {
+        return Err(ValidationError::BadMagic(shader_type));
+    }
+    let version = u32::from_le_bytes([agal[1], agal[2], agal[3], agal[4]]);
+    if !(1..=3).contains(&version) {
+        return Err(ValidationError::BadVersion(shader_type, version));
+    }
+    if agal[5] != 0xa1 {
+        return Err(ValidationError::BadShaderTypeId(shader_type));
+    }
+    let expected_type = match shader_type {
+        ShaderType::Vertex => 0,
+        ShaderType::Fragment => 1,
+    };
+    if agal[6] != expected_type {
+        return Err(ValidationError::ShaderTypeMismatch(shader_type));
+    }
+
+    let tokens = &agal[HEADER_SIZE..];
+    if tokens.len() % TOKEN_SIZE != 0 {
+        return Err(ValidationError::ProgramTooSmall(shader_type));
+    }
+
+    let mut condition_depth = 0usize;
+
+    for (token, bytes) in tokens.chunks_exact(TOKEN_SIZE).enumerate() {
+        let raw_opcode = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+        let bad_register_type = ValidationError::BadRegisterType { shader_type, token };
+
+        let opcode = Opcode::from_u32(raw_opcode).ok_or(ValidationError::InvalidOpcode {
+            shader_type,
+            opcode: raw_opcode,
+            token,
+        })?;
+        let (needs_version_2, fragment_only) = match opcode {
+            Opcode::Ddx | Opcode::Ddy => (true, true),
+            Opcode::Ife | Opcode::Ine | Opcode::Ifg | Opcode::Ifl | Opcode::Els | Opcode::Eif => {
+                (true, false)
+            }
+            Opcode::Kil | Opcode::Tex => (false, true),
+            _ => (false, false),
+        };
+        if needs_version_2 && version < 2 {
+            return Err(ValidationError::OpcodeNotInVersion {
+                shader_type,
+                opcode: raw_opcode,
+                token,
+            });
+        }
+        if fragment_only && shader_type != ShaderType::Fragment {
+            return Err(ValidationError::FragmentOnlyOpcode {
+                shader_type,
+                opcode: raw_opcode,
+                token,
+            });
+        }
+
+        match opcode {
+            Opcode::Ife | Opcode::Ine | Opcode::Ifg | Opcode::Ifl => condition_depth += 1,
+            Opcode::Els if condition_depth == 0 => {
+                return Err(ValidationError::UnbalancedCondition { shader_type, token })
+            }
+            Opcode::Eif => {
+                condition_depth = condition_depth
+                    .checked_sub(1)
+                    .ok_or(ValidationError::UnbalancedCondition { shader_type, token })?;
+            }
+            _ => {}
+        }
+
+        let check_register =
+            |register_type: &RegisterType, index: u16| -> Result<(), ValidationError> {
+                if index >= register_count(register_type, shader_type, version) {
+                    return Err(ValidationError::RegisterOutOfBounds { shader_type, token });
+                }
+                Ok(())
+            };
+        let check_source = |source: u64| -> Result<(), ValidationError> {
+            let source = SourceField::parse(source).map_err(|_| bad_register_type.clone())?;
+            if source.register_type == RegisterType::Sampler {
+                return Err(ValidationError::BadSamplerUsage { shader_type, token });
+            }
+            match source.direct_mode {
+                DirectMode::Direct => check_register(&source.register_type, source.reg_num),
+                DirectMode::Indirect => {
+                    // The index register provides the index, and the register number is just an offset.
+                    check_register(&source.index_type, source.reg_num)?;
+                    if !matches!(
+                        source.register_type,
+                        RegisterType::Constant | RegisterType::Attribute
+                    ) {
+                        return Err(bad_register_type.clone());
+                    }
+                    Ok(())
+                }
+            }
+        };
+
+        // Conditionals and `kil` don't write to their destination.
+        let has_dest = !matches!(
+            opcode,
+            Opcode::Ife
+                | Opcode::Ine
+                | Opcode::Ifg
+                | Opcode::Ifl
+                | Opcode::Els
+                | Opcode::Eif
+                | Opcode::Kil
+        );
+        if has_dest {
+            let dest = DestField::parse(u32::from_le_bytes(bytes[4..8].try_into().unwrap()))
+                .map_err(|_| bad_register_type.clone())?;
+            let writable = match dest.register_type {
+                RegisterType::Temporary | RegisterType::Output => true,
+                RegisterType::Varying => shader_type == ShaderType::Vertex,
+                RegisterType::FragmentRegister => shader_type == ShaderType::Fragment,
+                _ => false,
+            };
+            if !writable {
+                return Err(bad_register_type.clone());
+            }
+            check_register(&dest.register_type, dest.reg_num)?;
+        }
+
+        // Branch terminators don't read anything.
+        if !matches!(opcode, Opcode::Els | Opcode::Eif) {
+            check_source(u64::from_le_bytes(bytes[8..16].try_into().unwrap()))?;
+        }
+
+        let source2 = u64::from_le_bytes(bytes[16..24].try_into().unwrap());
+        if opcode == Opcode::Tex {
+            let sampler = SamplerField::parse(source2)
+                .map_err(|_| ValidationError::BadSamplerUsage { shader_type, token })?;
+            if sampler.reg_type != RegisterType::Sampler {
+                return Err(ValidationError::BadSamplerUsage { shader_type, token });
+            }
+            check_register(&sampler.reg_type, sampler.reg_num)?;
+        } else if uses_second_source(opcode) {
+            check_source(source2)?;
+        }
+    }
+
+    if condition_depth != 0 {
+        return Err(ValidationError::UnbalancedCondition {
+            shader_type,
+            token: tokens.len() / TOKEN_SIZE,
+        });
+    }
+
+    Ok(())
+}
+
+/// Whether the given (non-`tex`) opcode reads from its second source operand.
+fn uses_second_source(opcode: Opcode) -> bool {
+    matches!(
+        opcode,
+        Opcode::Add
+            | Opcode::Sub
+            | Opcode::Mul
+            | Opcode::Div
+            | Opcode::Min
+            | Opcode::Max
+            | Opcode::Pow
+            | Opcode::Crs
+            | Opcode::Dp3
+            | Opcode::Dp4
+            | Opcode::M33
+            | Opcode::M44
+            | Opcode::M34
+            | Opcode::Ife
+            | Opcode::Ine
+            | Opcode::Ifg
+            | Opcode::Ifl
+            | Opcode::Sge
+            | Opcode::Slt
+            | Opcode::Seq
+            | Opcode::Sne
+    )
+}
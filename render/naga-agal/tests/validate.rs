@@ -0,0 +1,79 @@
+use naga_agal::{validate_agal, ShaderType};
+
+const OPCODE_IFE: u32 = 0x1c;
+const OPCODE_KIL: u32 = 0x27;
+
+fn program(version: u32, shader_type: u8, tokens: &[[u32; 6]]) -> Vec<u8> {
+    let mut data = vec![0xa0];
+    data.extend_from_slice(&version.to_le_bytes());
+    data.push(0xa1);
+    data.push(shader_type);
+    for token in tokens {
+        for value in token {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    data
+}
+
+fn error_code(agal: &[u8], shader_type: ShaderType) -> Result<(), u32> {
+    validate_agal(agal, shader_type).map_err(|e| e.code())
+}
+
+/// `mov op, va0`
+const MOV_OUTPUT: [u32; 6] = [0x00, 0x030F_0000, 0, 0, 0, 0];
+
+#[test]
+fn accepts_simple_program() {
+    assert_eq!(
+        error_code(&program(1, 0, &[MOV_OUTPUT]), ShaderType::Vertex),
+        Ok(())
+    );
+}
+
+#[test]
+fn rejects_bad_header() {
+    assert_eq!(error_code(&[0xa0], ShaderType::Vertex), Err(3615));
+    assert_eq!(
+        error_code(&program(4, 0, &[]), ShaderType::Vertex),
+        Err(3617)
+    );
+    assert_eq!(
+        error_code(&program(1, 0, &[]), ShaderType::Fragment),
+        Err(3619)
+    );
+}
+
+#[test]
+fn rejects_bad_tokens() {
+    // Unknown opcode
+    let token = [0xFF, 0x030F_0000, 0, 0, 0, 0];
+    assert_eq!(
+        error_code(&program(1, 0, &[token]), ShaderType::Vertex),
+        Err(3620)
+    );
+    // `kil` in a vertex program
+    let token = [OPCODE_KIL, 0, 0, 2, 0, 0];
+    assert_eq!(
+        error_code(&program(1, 0, &[token]), ShaderType::Vertex),
+        Err(3622)
+    );
+    // `mov op, va8`
+    let token = [0x00, 0x030F_0000, 8, 0, 0, 0];
+    assert_eq!(
+        error_code(&program(1, 0, &[token]), ShaderType::Vertex),
+        Err(3625)
+    );
+    // `mov op, <register type 7>`, which would previously panic while parsing
+    let token = [0x00, 0x030F_0000, 0, 7, 0, 0];
+    assert_eq!(
+        error_code(&program(1, 0, &[token]), ShaderType::Vertex),
+        Err(3626)
+    );
+    // An `ife` without a matching `eif`
+    let token = [OPCODE_IFE, 0, 0, 2, 0, 2];
+    assert_eq!(
+        error_code(&program(2, 0, &[token, MOV_OUTPUT]), ShaderType::Vertex),
+        Err(3623)
+    );
+}
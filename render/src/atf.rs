@@ -0,0 +1,513 @@
+//! Parsing and decoding of ATF (Adobe Texture Format) files.
+//!
+//! ATF files are uploaded with `Texture.uploadCompressedTextureFromByteArray`. A "raw compressed"
+//! ATF file contains the same texture block-compressed in several GPU formats (DXT, PVRTC, ETC1
+//! and, since version 3, ETC2), so that Flash can pick whichever the GPU supports. We can't rely on
+//! any of these being available through wgpu, so we decode one of them to RGBA on the CPU instead.
+//!
+//! Only raw compressed files can be decoded so far. The other formats wrap the blocks in LZMA
+//! or JPEG-XR compression, and PVRTC blocks are never decoded, as DXT or ETC1 blocks are always
+//! stored alongside them.
+
+use crate::bitmap::{Bitmap, BitmapFormat};
+use thiserror::Error;
+
+/// The header of the old (pre-version 1) file format: signature and 24-bit length.
+const OLD_HEADER_SIZE: usize = 6;
+
+/// The header of the new file format: signature, reserved bytes, version and 32-bit length.
+const NEW_HEADER_SIZE: usize = 12;
+
+/// Marks a file as using the new header layout.
+const NEW_FORMAT_MARKER: u8 = 0xff;
+
+const CUBE_MAP_FLAG: u8 = 0x80;
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AtfError {
+    #[error("ATF signature not found")]
+    InvalidSignature,
+
+    #[error("ATF data is truncated")]
+    Truncated,
+
+    #[error("Unknown ATF format {0}")]
+    UnknownFormat(u8),
+
+    #[error("ATF format {0:?} is not supported")]
+    UnsupportedFormat(AtfFormat),
+
+    #[error("ATF texture does not contain DXT or ETC1 data")]
+    NoDecodableData,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtfFormat {
+    Rgb888,
+    Rgba8888,
+    Compressed,
+    RawCompressed,
+    CompressedAlpha,
+    RawCompressedAlpha,
+    CompressedLossy,
+    CompressedLossyAlpha,
+}
+
+impl AtfFormat {
+    fn from_u8(value: u8) -> Option<Self> {
+        Some(match value {
+            0 => AtfFormat::Rgb888,
+            1 => AtfFormat::Rgba8888,
+            2 => AtfFormat::Compressed,
+            3 => AtfFormat::RawCompressed,
+            4 => AtfFormat::CompressedAlpha,
+            5 => AtfFormat::RawCompressedAlpha,
+            12 => AtfFormat::CompressedLossy,
+            13 => AtfFormat::CompressedLossyAlpha,
+            _ => return None,
+        })
+    }
+
+    /// Whether the blocks are stored as they are, without LZMA or JPEG-XR compression.
+    /// These are the only files that can be decoded.
+    pub fn is_raw(self) -> bool {
+        matches!(
+            self,
+            AtfFormat::RawCompressed | AtfFormat::RawCompressedAlpha
+        )
+    }
+
+    pub fn has_alpha(self) -> bool {
+        matches!(
+            self,
+            AtfFormat::Rgba8888
+                | AtfFormat::CompressedAlpha
+                | AtfFormat::RawCompressedAlpha
+                | AtfFormat::CompressedLossyAlpha
+        )
+    }
+}
+
+/// The GPU formats stored for each mip level of a raw compressed texture, in file order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockFormat {
+    Dxt,
+    Pvrtc,
+    Etc1,
+    Etc2,
+}
+
+const BLOCK_FORMATS: [BlockFormat; 4] = [
+    BlockFormat::Dxt,
+    BlockFormat::Pvrtc,
+    BlockFormat::Etc1,
+    BlockFormat::Etc2,
+];
+
+#[derive(Debug)]
+pub struct AtfTexture<'a> {
+    pub format: AtfFormat,
+    pub width: u32,
+    pub height: u32,
+    pub cube_map: bool,
+    pub num_mip_levels: u32,
+    version: u8,
+    data: &'a [u8],
+}
+
+impl<'a> AtfTexture<'a> {
+    pub fn parse(data: &'a [u8]) -> Result<Self, AtfError> {
+        if data.len() < OLD_HEADER_SIZE || &data[0..3] != b"ATF" {
+            return Err(AtfError::InvalidSignature);
+        }
+
+        let (version, length, header_size) = if data.get(6) == Some(&NEW_FORMAT_MARKER) {
+            if data.len() < NEW_HEADER_SIZE {
+                return Err(AtfError::Truncated);
+            }
+            let length = u32::from_be_bytes([data[8], data[9], data[10], data[11]]);
+            (data[7], length as usize, NEW_HEADER_SIZE)
+        } else {
+            let length = u32::from_be_bytes([0, data[3], data[4], data[5]]);
+            (0, length as usize, OLD_HEADER_SIZE)
+        };
+
+        // The length doesn't include the header itself.
+        let data = data
+            .get(header_size..header_size + length)
+            .ok_or(AtfError::Truncated)?;
+        let [format, width_log2, height_log2, num_mip_levels, ..] = *data else {
+            return Err(AtfError::Truncated);
+        };
+
+        Ok(AtfTexture {
+            format: AtfFormat::from_u8(format & !CUBE_MAP_FLAG)
+                .ok_or(AtfError::UnknownFormat(format & !CUBE_MAP_FLAG))?,
+            width: 1 << width_log2.min(12),
+            height: 1 << height_log2.min(12),
+            cube_map: format & CUBE_MAP_FLAG != 0,
+            num_mip_levels: num_mip_levels as u32,
+            version,
+            data: &data[4..],
+        })
+    }
+
+    /// Decodes the top mip level of every face (one for a 2D texture, six for a cube map).
+    pub fn decode(&self) -> Result<Vec<Bitmap>, AtfError> {
+        if !self.format.is_raw() {
+            return Err(AtfError::UnsupportedFormat(self.format));
+        }
+
+        // ETC2 blocks were added in version 3.
+        let block_formats = if self.version < 3 {
+            &BLOCK_FORMATS[..3]
+        } else {
+            &BLOCK_FORMATS[..]
+        };
+
+        let mut data = self.data;
+        let num_faces = if self.cube_map { 6 } else { 1 };
+        let mut faces = Vec::with_capacity(num_faces);
+        for _ in 0..num_faces {
+            let mut top_level = None;
+            for level in 0..self.num_mip_levels {
+                for &block_format in block_formats {
+                    let block = self.read_block(&mut data)?;
+                    if level == 0 && !block.is_empty() {
+                        match (block_format, &top_level) {
+                            // DXT is preferred when available, as it's the most common.
+                            (BlockFormat::Dxt, _) | (BlockFormat::Etc1, None) => {
+                                top_level = Some((block_format, block))
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            let bitmap = match top_level {
+                Some((BlockFormat::Dxt, block)) => self.decode_dxt(block)?,
+                Some((BlockFormat::Etc1, block)) => self.decode_etc1(block)?,
+                _ => return Err(AtfError::NoDecodableData),
+            };
+            faces.push(bitmap);
+        }
+
+        Ok(faces)
+    }
+
+    fn read_block(&self, data: &mut &'a [u8]) -> Result<&'a [u8], AtfError> {
+        let bytes: &'a [u8] = *data;
+        let (length, rest) = if self.version == 0 {
+            let [a, b, c, ..] = *bytes else {
+                return Err(AtfError::Truncated);
+            };
+            (u32::from_be_bytes([0, a, b, c]) as usize, &bytes[3..])
+        } else {
+            let [a, b, c, d, ..] = *bytes else {
+                return Err(AtfError::Truncated);
+            };
+            (u32::from_be_bytes([a, b, c, d]) as usize, &bytes[4..])
+        };
+        let block = rest.get(..length).ok_or(AtfError::Truncated)?;
+        *data = &rest[length..];
+        Ok(block)
+    }
+
+    fn blocks_wide(&self) -> u32 {
+        (self.width + 3) / 4
+    }
+
+    fn blocks_high(&self) -> u32 {
+        (self.height + 3) / 4
+    }
+
+    fn decode_dxt(&self, data: &[u8]) -> Result<Bitmap, AtfError> {
+        let block_size = if self.format.has_alpha() { 16 } else { 8 };
+        let num_blocks = (self.blocks_wide() * self.blocks_high()) as usize;
+        if data.len() < num_blocks * block_size {
+            return Err(AtfError::Truncated);
+        }
+
+        let mut pixels = vec![0; (self.width * self.height * 4) as usize];
+        for (i, block) in data.chunks_exact(block_size).take(num_blocks).enumerate() {
+            let decoded = if self.format.has_alpha() {
+                decode_dxt5_block(block.try_into().expect("block is 16 bytes"))
+            } else {
+                decode_dxt1_block(block.try_into().expect("block is 8 bytes"))
+            };
+            self.write_block(&mut pixels, i as u32, &decoded);
+        }
+
+        Ok(Bitmap::new(
+            self.width,
+            self.height,
+            BitmapFormat::Rgba,
+            pixels,
+        ))
+    }
+
+    fn decode_etc1(&self, data: &[u8]) -> Result<Bitmap, AtfError> {
+        let num_blocks = (self.blocks_wide() * self.blocks_high()) as usize;
+        // With alpha, the color image is followed by a second image holding the alpha channel.
+        let num_images = if self.format.has_alpha() { 2 } else { 1 };
+        if data.len() < num_blocks * 8 * num_images {
+            return Err(AtfError::Truncated);
+        }
+
+        let mut pixels = vec![0; (self.width * self.height * 4) as usize];
+        let (color, alpha) = data.split_at(num_blocks * 8);
+        for (i, block) in color.chunks_exact(8).take(num_blocks).enumerate() {
+            let mut decoded = decode_etc1_block(block.try_into().expect("block is 8 bytes"));
+            if self.format.has_alpha() {
+                let alpha_block = &alpha[i * 8..i * 8 + 8];
+                let alpha = decode_etc1_block(alpha_block.try_into().expect("block is 8 bytes"));
+                for (pixel, alpha) in decoded.iter_mut().zip(alpha) {
+                    pixel[3] = alpha[0];
+                }
+            }
+            self.write_block(&mut pixels, i as u32, &decoded);
+        }
+
+        Ok(Bitmap::new(
+            self.width,
+            self.height,
+            BitmapFormat::Rgba,
+            pixels,
+        ))
+    }
+
+    /// Writes a decoded 4x4 block into the output image, clipping it to the image size.
+    fn write_block(&self, pixels: &mut [u8], block_index: u32, block: &[[u8; 4]; 16]) {
+        let block_x = (block_index % self.blocks_wide()) * 4;
+        let block_y = (block_index / self.blocks_wide()) * 4;
+        for (i, pixel) in block.iter().enumerate() {
+            let x = block_x + i as u32 % 4;
+            let y = block_y + i as u32 / 4;
+            if x < self.width && y < self.height {
+                let offset = ((y * self.width + x) * 4) as usize;
+                pixels[offset..offset + 4].copy_from_slice(pixel);
+            }
+        }
+    }
+}
+
+fn rgb565(color: u16) -> [u8; 4] {
+    let r = ((color >> 11) & 0x1f) as u8;
+    let g = ((color >> 5) & 0x3f) as u8;
+    let b = (color & 0x1f) as u8;
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+        255,
+    ]
+}
+
+fn mix(a: [u8; 4], b: [u8; 4], a_weight: u16, b_weight: u16) -> [u8; 4] {
+    let total = a_weight + b_weight;
+    std::array::from_fn(|i| ((a[i] as u16 * a_weight + b[i] as u16 * b_weight) / total) as u8)
+}
+
+/// Decodes the color part of a DXT block, returning pixels in row-major order.
+fn decode_dxt_colors(block: &[u8; 8], allow_transparent: bool) -> [[u8; 4]; 16] {
+    let c0 = u16::from_le_bytes([block[0], block[1]]);
+    let c1 = u16::from_le_bytes([block[2], block[3]]);
+    let indices = u32::from_le_bytes([block[4], block[5], block[6], block[7]]);
+
+    let color0 = rgb565(c0);
+    let color1 = rgb565(c1);
+    let palette = if c0 > c1 || !allow_transparent {
+        [
+            color0,
+            color1,
+            mix(color0, color1, 2, 1),
+            mix(color0, color1, 1, 2),
+        ]
+    } else {
+        [color0, color1, mix(color0, color1, 1, 1), [0, 0, 0, 0]]
+    };
+
+    std::array::from_fn(|i| palette[((indices >> (i * 2)) & 3) as usize])
+}
+
+fn decode_dxt1_block(block: &[u8; 8]) -> [[u8; 4]; 16] {
+    decode_dxt_colors(block, true)
+}
+
+fn decode_dxt5_block(block: &[u8; 16]) -> [[u8; 4]; 16] {
+    let a0 = block[0] as u16;
+    let a1 = block[1] as u16;
+    let alpha_indices = u64::from_le_bytes([
+        block[2], block[3], block[4], block[5], block[6], block[7], 0, 0,
+    ]);
+    let alpha_palette: [u8; 8] = if a0 > a1 {
+        std::array::from_fn(|i| match i {
+            0 => a0 as u8,
+            1 => a1 as u8,
+            _ => (((8 - i as u16) * a0 + (i as u16 - 1) * a1) / 7) as u8,
+        })
+    } else {
+        std::array::from_fn(|i| match i {
+            0 => a0 as u8,
+            1 => a1 as u8,
+            6 => 0,
+            7 => 255,
+            _ => (((6 - i as u16) * a0 + (i as u16 - 1) * a1) / 5) as u8,
+        })
+    };
+
+    let color_block = block[8..16].try_into().expect("block is 16 bytes");
+    let mut pixels = decode_dxt_colors(color_block, false);
+    for (i, pixel) in pixels.iter_mut().enumerate() {
+        pixel[3] = alpha_palette[((alpha_indices >> (i * 3)) & 7) as usize];
+    }
+    pixels
+}
+
+const ETC1_MODIFIERS: [[i16; 4]; 8] = [
+    [2, 8, -2, -8],
+    [5, 17, -5, -17],
+    [9, 29, -9, -29],
+    [13, 42, -13, -42],
+    [18, 60, -18, -60],
+    [24, 80, -24, -80],
+    [33, 106, -33, -106],
+    [47, 183, -47, -183],
+];
+
+fn decode_etc1_block(block: &[u8; 8]) -> [[u8; 4]; 16] {
+    let high = u32::from_be_bytes([block[0], block[1], block[2], block[3]]);
+    let low = u32::from_be_bytes([block[4], block[5], block[6], block[7]]);
+    let differential = high & 2 != 0;
+    let flipped = high & 1 != 0;
+
+    let base_colors: [[i16; 3]; 2] = if differential {
+        let expand5 = |c: u32| ((c << 3) | (c >> 2)) as i16;
+        // The second color is stored as a signed 3-bit delta from the first.
+        let delta = |c: u32| (((c & 7) as i16) << 13) >> 13;
+        let r = (high >> 27) & 0x1f;
+        let g = (high >> 19) & 0x1f;
+        let b = (high >> 11) & 0x1f;
+        let r2 = (r as i16 + delta(high >> 24)) as u32 & 0x1f;
+        let g2 = (g as i16 + delta(high >> 16)) as u32 & 0x1f;
+        let b2 = (b as i16 + delta(high >> 8)) as u32 & 0x1f;
+        [
+            [expand5(r), expand5(g), expand5(b)],
+            [expand5(r2), expand5(g2), expand5(b2)],
+        ]
+    } else {
+        let expand4 = |c: u32| ((c & 0xf) * 17) as i16;
+        [
+            [
+                expand4(high >> 28),
+                expand4(high >> 20),
+                expand4(high >> 12),
+            ],
+            [expand4(high >> 24), expand4(high >> 16), expand4(high >> 8)],
+        ]
+    };
+    let tables = [(high >> 5) & 7, (high >> 2) & 7];
+
+    std::array::from_fn(|i| {
+        let (x, y) = (i % 4, i / 4);
+        let sub_block = if flipped { y / 2 } else { x / 2 };
+        // Pixel indices are stored column-major.
+        let bit = x * 4 + y;
+        let index = ((low >> (bit + 16)) & 1) << 1 | ((low >> bit) & 1);
+        let modifier = ETC1_MODIFIERS[tables[sub_block] as usize][index as usize];
+        let [r, g, b] = base_colors[sub_block].map(|c| (c + modifier).clamp(0, 255) as u8);
+        [r, g, b, 255]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn atf(version: u8, format: u8, size_log2: u8, blocks: &[&[u8]]) -> Vec<u8> {
+        let mut body = vec![format, size_log2, size_log2, 1];
+        for block in blocks {
+            body.extend_from_slice(&(block.len() as u32).to_be_bytes());
+            body.extend_from_slice(block);
+        }
+
+        let mut data = b"ATF".to_vec();
+        data.extend_from_slice(&[0, 0, 0, NEW_FORMAT_MARKER, version]);
+        data.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        data.extend_from_slice(&body);
+        data
+    }
+
+    #[test]
+    fn parses_header() {
+        let data = atf(3, 0x80 | 3, 3, &[]);
+        let texture = AtfTexture::parse(&data).unwrap();
+        assert_eq!(texture.format, AtfFormat::RawCompressed);
+        assert_eq!((texture.width, texture.height), (8, 8));
+        assert!(texture.cube_map);
+
+        assert_eq!(
+            AtfTexture::parse(b"PNG\0\0\0\0").unwrap_err(),
+            AtfError::InvalidSignature
+        );
+    }
+
+    #[test]
+    fn decodes_dxt1() {
+        // A single block of pure red, using only color0.
+        let block = [0x00, 0xf8, 0x00, 0x00, 0, 0, 0, 0];
+        let data = atf(2, 3, 2, &[&block, &[], &[]]);
+        let faces = AtfTexture::parse(&data).unwrap().decode().unwrap();
+        assert_eq!(faces.len(), 1);
+        assert!(faces[0].data().chunks(4).all(|p| p == [255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn decodes_dxt5_alpha() {
+        // Alpha interpolates between 255 and 0; every pixel uses index 1 (alpha 0).
+        let mut block = [0; 16];
+        block[0] = 255;
+        block[1] = 0;
+        block[2..8].copy_from_slice(&[0b0100_1001, 0b1001_0010, 0b0010_0100, 0x49, 0x92, 0x24]);
+        block[8..10].copy_from_slice(&0xffffu16.to_le_bytes());
+        let data = atf(2, 5, 2, &[&block, &[], &[]]);
+        let faces = AtfTexture::parse(&data).unwrap().decode().unwrap();
+        assert!(faces[0].data().chunks(4).all(|p| p == [255, 255, 255, 0]));
+    }
+
+    #[test]
+    fn decodes_mipmapped_file() {
+        // An 8x8 red texture with all four mip levels, in the layout that png2atf writes.
+        let data = include_bytes!("../tests/fixtures/red_8x8_mipmapped.atf");
+        let texture = AtfTexture::parse(data).unwrap();
+        assert_eq!(texture.format, AtfFormat::RawCompressed);
+        assert_eq!((texture.width, texture.height), (8, 8));
+        assert_eq!(texture.num_mip_levels, 4);
+        assert!(!texture.cube_map);
+
+        let faces = texture.decode().unwrap();
+        assert_eq!(faces.len(), 1);
+        assert_eq!((faces[0].width(), faces[0].height()), (8, 8));
+        assert!(faces[0].data().chunks(4).all(|p| p == [255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn rejects_lzma_compressed() {
+        let data = atf(3, 2, 3, &[]);
+        let texture = AtfTexture::parse(&data).unwrap();
+        assert_eq!(
+            texture.decode().unwrap_err(),
+            AtfError::UnsupportedFormat(AtfFormat::Compressed)
+        );
+    }
+
+    #[test]
+    fn decodes_etc1_when_dxt_missing() {
+        // Individual mode, both base colors 0x8 (136), table 0, all indices 0 (+2).
+        let block = [0x88, 0x88, 0x88, 0x00, 0, 0, 0, 0];
+        let data = atf(2, 3, 2, &[&[], &[], &block]);
+        let faces = AtfTexture::parse(&data).unwrap().decode().unwrap();
+        assert!(faces[0].data().chunks(4).all(|p| p == [138, 138, 138, 255]));
+    }
+}
@@ -1,6 +1,6 @@
 pub mod null;
 
-use crate::bitmap::{Bitmap, BitmapHandle, BitmapSource, PixelRegion, SyncHandle};
+use crate::bitmap::{Bitmap, BitmapHandle, BitmapInfo, BitmapSource, PixelRegion, SyncHandle};
 use crate::commands::CommandList;
 use crate::error::Error;
 use crate::filters::Filter;
@@ -111,12 +111,30 @@ pub trait RenderBackend: Downcast {
         arguments: &[PixelBenderShaderArgument],
         target: BitmapHandle,
     ) -> Result<Box<dyn SyncHandle>, Error>;
+
+    /// Runs a Pixel Bender shader over a `width` by `height` area, returning the
+    /// unclamped output as tightly packed RGBA floats (4 per pixel, row by row).
+    ///
+    /// This is used when a `ShaderJob` targets a `ByteArray` or `Vector.<Number>`.
+    fn run_pixelbender_shader_to_floats(
+        &mut self,
+        handle: PixelBenderShaderHandle,
+        arguments: &[PixelBenderShaderArgument],
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<f32>, Error>;
 }
 impl_downcast!(RenderBackend);
 
-pub trait IndexBuffer: Downcast {}
+pub trait IndexBuffer: Downcast {
+    /// The number of 16-bit indices that this buffer was created with.
+    fn num_indices(&self) -> u32;
+}
 impl_downcast!(IndexBuffer);
-pub trait VertexBuffer: Downcast {}
+pub trait VertexBuffer: Downcast {
+    /// The number of vertices that this buffer was created with.
+    fn num_vertices(&self) -> u32;
+}
 impl_downcast!(VertexBuffer);
 
 pub trait ShaderModule: Downcast {}
@@ -220,6 +238,10 @@ pub trait Context3D: Downcast {
     // as part of stage rendering
     fn should_render(&self) -> bool;
 
+    /// Submits any pending work, and returns the current back buffer (if one has been configured).
+    /// This is used to implement `Context3D.drawToBitmapData`.
+    fn back_buffer(&mut self) -> Option<BitmapInfo>;
+
     // Get a 'disposed' handle - this is what we store in all IndexBuffer3D
     // objects after dispose() has been called.
     fn disposed_index_buffer_handle(&self) -> Rc<dyn IndexBuffer>;
@@ -230,11 +252,15 @@ pub trait Context3D: Downcast {
 
     fn create_index_buffer(&mut self, usage: BufferUsage, num_indices: u32)
         -> Box<dyn IndexBuffer>;
+    /// Creates a vertex buffer. If `instances_per_element` is non-zero, then the buffer holds
+    /// per-instance data (see `Context3D.createVertexBufferForInstances`), with each element
+    /// being used for that many instances.
     fn create_vertex_buffer(
         &mut self,
         usage: BufferUsage,
         num_vertices: u32,
         data_32_per_vertex: u8,
+        instances_per_element: u32,
     ) -> Rc<dyn VertexBuffer>;
 
     fn create_texture(
@@ -363,6 +389,27 @@ impl Context3DTextureFilter {
         }
     }
 }
+
+#[derive(Copy, Clone, Debug)]
+pub enum Context3DMipFilter {
+    MipLinear,
+    MipNearest,
+    MipNone,
+}
+
+impl Context3DMipFilter {
+    pub fn from_wstr(s: &WStr) -> Option<Self> {
+        if s == b"miplinear" {
+            Some(Context3DMipFilter::MipLinear)
+        } else if s == b"mipnearest" {
+            Some(Context3DMipFilter::MipNearest)
+        } else if s == b"mipnone" {
+            Some(Context3DMipFilter::MipNone)
+        } else {
+            None
+        }
+    }
+}
 pub enum Context3DCommand<'a> {
     Clear {
         red: f64,
@@ -406,6 +453,7 @@ pub enum Context3DCommand<'a> {
         index_buffer: &'a dyn IndexBuffer,
         first_index: usize,
         num_triangles: isize,
+        num_instances: u32,
     },
 
     SetVertexBufferAt {
@@ -436,10 +484,21 @@ pub enum Context3DCommand<'a> {
         dest: Rc<dyn Texture>,
         layer: u32,
     },
+    /// Copies a renderer bitmap (such as a decoded video frame) into a texture.
+    ///
+    /// Unlike `CopyBitmapToTexture`, this is performed on the GPU, and so will
+    /// observe any later updates made to `source` before the commands are submitted.
+    CopyBitmapHandleToTexture {
+        source: BitmapHandle,
+        dest: Rc<dyn Texture>,
+    },
     SetTextureAt {
         sampler: u32,
         texture: Option<Rc<dyn Texture>>,
         cube: bool,
+        /// Rectangle and video textures must always be sampled with clamping,
+        /// regardless of the wrap mode requested by the shader.
+        clamp: bool,
     },
     SetColorMask {
         red: bool,
@@ -459,6 +518,7 @@ pub enum Context3DCommand<'a> {
         sampler: u32,
         wrap: Context3DWrapMode,
         filter: Context3DTextureFilter,
+        mip_filter: Context3DMipFilter,
     },
     SetScissorRectangle {
         rect: Option<Rectangle<Twips>>,
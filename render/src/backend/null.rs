@@ -117,6 +117,16 @@ impl RenderBackend for NullRenderer {
         Err(Error::Unimplemented("Pixel bender shader".into()))
     }
 
+    fn run_pixelbender_shader_to_floats(
+        &mut self,
+        _shader: PixelBenderShaderHandle,
+        _arguments: &[PixelBenderShaderArgument],
+        _width: u32,
+        _height: u32,
+    ) -> Result<Vec<f32>, Error> {
+        Err(Error::Unimplemented("Pixel bender shader".into()))
+    }
+
     fn compile_pixelbender_shader(
         &mut self,
         _shader: PixelBenderShader,
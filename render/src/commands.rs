@@ -32,6 +32,11 @@ pub trait CommandHandler {
 pub enum RenderBlendMode {
     Builtin(BlendMode),
     Shader(PixelBenderShaderHandle),
+
+    /// Multiplies everything in the enclosing layer by the alpha of the blended commands,
+    /// clearing it wherever nothing was drawn.
+    /// This is used for masks that are applied through their alpha channel.
+    AlphaMask,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -1,10 +1,15 @@
+pub mod software;
+
 use crate::{
     bitmap::BitmapHandle,
     pixel_bender::{PixelBenderShaderArgument, PixelBenderShaderHandle},
 };
 use downcast_rs::{impl_downcast, Downcast};
 use std::fmt::Debug;
-use swf::Color;
+use swf::{Color, GradientRecord};
+
+/// The number of entries in a gradient filter lookup table.
+pub const GRADIENT_FILTER_LUT_SIZE: usize = 256;
 
 #[derive(Debug, Clone)]
 pub enum Filter {
@@ -72,6 +77,27 @@ impl Filter {
     }
 }
 
+impl PartialEq for Filter {
+    /// Compares two filters, for the purposes of deciding if a cached result can be reused.
+    ///
+    /// Shader filters, and displacement map filters with a map bitmap, are never considered
+    /// equal, as their inputs can't be cheaply compared.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Filter::BevelFilter(a), Filter::BevelFilter(b)) => a == b,
+            (Filter::BlurFilter(a), Filter::BlurFilter(b)) => a == b,
+            (Filter::ColorMatrixFilter(a), Filter::ColorMatrixFilter(b)) => a == b,
+            (Filter::ConvolutionFilter(a), Filter::ConvolutionFilter(b)) => a == b,
+            (Filter::DisplacementMapFilter(a), Filter::DisplacementMapFilter(b)) => a == b,
+            (Filter::DropShadowFilter(a), Filter::DropShadowFilter(b)) => a == b,
+            (Filter::GlowFilter(a), Filter::GlowFilter(b)) => a == b,
+            (Filter::GradientBevelFilter(a), Filter::GradientBevelFilter(b)) => a == b,
+            (Filter::GradientGlowFilter(a), Filter::GradientGlowFilter(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
 impl From<&swf::Filter> for Filter {
     fn from(value: &swf::Filter) -> Self {
         match value {
@@ -105,6 +131,51 @@ impl Default for Filter {
     }
 }
 
+/// Builds the lookup table used by `GradientBevelFilter` and `GradientGlowFilter`.
+///
+/// Each entry is a straight (non-premultiplied) RGBA color, sampled at the
+/// ratio corresponding to its index. Ratios outside of the first and last
+/// records are clamped to the nearest record, matching Flash.
+pub fn gradient_filter_lut(records: &[GradientRecord]) -> [u8; GRADIENT_FILTER_LUT_SIZE * 4] {
+    let mut colors = [0; GRADIENT_FILTER_LUT_SIZE * 4];
+    if records.is_empty() {
+        return colors;
+    }
+
+    for t in 0..GRADIENT_FILTER_LUT_SIZE {
+        let color = match records
+            .iter()
+            .position(|record| usize::from(record.ratio) >= t)
+        {
+            // Before (or exactly on) the first record.
+            Some(0) => records[0].color,
+            Some(next) => {
+                let last_record = &records[next - 1];
+                let next_record = &records[next];
+                let a = (t as f32 - f32::from(last_record.ratio))
+                    / (f32::from(next_record.ratio) - f32::from(last_record.ratio));
+                let lerp = |from: u8, to: u8| {
+                    (f32::from(from) + (f32::from(to) - f32::from(from)) * a).round() as u8
+                };
+                Color {
+                    r: lerp(last_record.color.r, next_record.color.r),
+                    g: lerp(last_record.color.g, next_record.color.g),
+                    b: lerp(last_record.color.b, next_record.color.b),
+                    a: lerp(last_record.color.a, next_record.color.a),
+                }
+            }
+            // After the last record.
+            None => records[records.len() - 1].color,
+        };
+        colors[t * 4] = color.r;
+        colors[t * 4 + 1] = color.g;
+        colors[t * 4 + 2] = color.b;
+        colors[t * 4 + 3] = color.a;
+    }
+
+    colors
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum DisplacementMapFilterComponent {
     Alpha,
@@ -135,6 +206,23 @@ pub struct DisplacementMapFilter {
     pub viewscale_y: f32,
 }
 
+impl PartialEq for DisplacementMapFilter {
+    fn eq(&self, other: &Self) -> bool {
+        // The pixels of the same map bitmap may have changed since it was last used.
+        self.map_bitmap.is_none()
+            && other.map_bitmap.is_none()
+            && self.color == other.color
+            && self.component_x == other.component_x
+            && self.component_y == other.component_y
+            && self.map_point == other.map_point
+            && self.mode == other.mode
+            && self.scale_x == other.scale_x
+            && self.scale_y == other.scale_y
+            && self.viewscale_x == other.viewscale_x
+            && self.viewscale_y == other.viewscale_y
+    }
+}
+
 impl DisplacementMapFilter {
     pub fn scale(&mut self, x: f32, y: f32) {
         self.viewscale_x *= x;
@@ -158,3 +246,28 @@ impl Default for DisplacementMapFilter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gradient_filter_lut_interpolates_and_clamps() {
+        let records = [
+            GradientRecord {
+                ratio: 64,
+                color: Color::from_rgba(0x00000000),
+            },
+            GradientRecord {
+                ratio: 192,
+                color: Color::from_rgba(0xFFFF8000),
+            },
+        ];
+        let lut = gradient_filter_lut(&records);
+        assert_eq!(&lut[0..4], &[0, 0, 0, 0]);
+        assert_eq!(&lut[64 * 4..64 * 4 + 4], &[0, 0, 0, 0]);
+        assert_eq!(&lut[128 * 4..128 * 4 + 4], &[128, 64, 0, 128]);
+        assert_eq!(&lut[192 * 4..192 * 4 + 4], &[255, 128, 0, 255]);
+        assert_eq!(&lut[255 * 4..255 * 4 + 4], &[255, 128, 0, 255]);
+    }
+}
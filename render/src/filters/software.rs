@@ -0,0 +1,586 @@
+//! CPU implementations of filters.
+//!
+//! These are used when a render backend can't apply a filter itself,
+//! and operate on premultiplied RGBA pixels.
+
+use crate::filters::gradient_filter_lut;
+use swf::{
+    BevelFilter, BlurFilter, Color, ColorMatrixFilter, ConvolutionFilter, DropShadowFilter,
+    GlowFilter, GradientFilter, GradientRecord,
+};
+
+/// A premultiplied RGBA image that software filters read from and write to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilterImage {
+    pub width: u32,
+    pub height: u32,
+    /// Premultiplied RGBA pixels, row by row.
+    pub pixels: Vec<[u8; 4]>,
+}
+
+impl FilterImage {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![[0; 4]; width as usize * height as usize],
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, x: u32, y: u32) -> [u8; 4] {
+        self.pixels[(y * self.width + x) as usize]
+    }
+
+    #[inline]
+    pub fn set(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        self.pixels[(y * self.width + x) as usize] = color;
+    }
+
+    /// Returns the pixel at the given position, or `None` if it's out of bounds.
+    #[inline]
+    pub fn get_checked(&self, x: i32, y: i32) -> Option<[u8; 4]> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            None
+        } else {
+            Some(self.get(x as u32, y as u32))
+        }
+    }
+
+    /// Returns the pixel at the given position, clamped to the edges of the image.
+    #[inline]
+    pub fn get_clamped(&self, x: i32, y: i32) -> [u8; 4] {
+        let x = x.clamp(0, self.width as i32 - 1) as u32;
+        let y = y.clamp(0, self.height as i32 - 1) as u32;
+        self.get(x, y)
+    }
+
+    /// Returns every channel of this image as its own plane of floating point values.
+    fn planes(&self) -> [Vec<f32>; 4] {
+        std::array::from_fn(|channel| {
+            self.pixels
+                .iter()
+                .map(|pixel| f32::from(pixel[channel]))
+                .collect()
+        })
+    }
+
+    /// Returns the alpha channel of this image, normalized to 0-1.
+    fn alpha_plane(&self) -> Vec<f32> {
+        self.pixels
+            .iter()
+            .map(|pixel| f32::from(pixel[3]) / 255.0)
+            .collect()
+    }
+
+    /// Returns the pixel at the given position with channels normalized to 0-1.
+    #[inline]
+    fn get_unit(&self, x: u32, y: u32) -> [f32; 4] {
+        self.get(x, y).map(|c| f32::from(c) / 255.0)
+    }
+}
+
+/// Converts a premultiplied color to an unmultiplied one, with floating point channels.
+#[inline]
+fn unmultiply(color: [u8; 4]) -> [f32; 4] {
+    let a = f32::from(color[3]);
+    if a == 0.0 {
+        [0.0; 4]
+    } else {
+        [
+            f32::from(color[0]) * 255.0 / a,
+            f32::from(color[1]) * 255.0 / a,
+            f32::from(color[2]) * 255.0 / a,
+            a,
+        ]
+    }
+}
+
+/// Converts an unmultiplied color with floating point channels to a premultiplied one.
+#[inline]
+fn premultiply(color: [f32; 4]) -> [u8; 4] {
+    let a = color[3].clamp(0.0, 255.0);
+    let channel = |c: f32| (c.clamp(0.0, 255.0) * a / 255.0).round() as u8;
+    [
+        channel(color[0]),
+        channel(color[1]),
+        channel(color[2]),
+        a.round() as u8,
+    ]
+}
+
+/// Applies a `ConvolutionFilter` to `source`, producing an image of the same size.
+pub fn apply_convolution(filter: &ConvolutionFilter, source: &FilterImage) -> FilterImage {
+    let mut dest = FilterImage::new(source.width, source.height);
+    if source.width == 0 || source.height == 0 {
+        return dest;
+    }
+
+    let cols = i32::from(filter.num_matrix_cols);
+    let rows = i32::from(filter.num_matrix_rows);
+    let matrix: Vec<f32> = filter.matrix.iter().map(|v| v.to_f32()).collect();
+    let divisor = match filter.divisor.to_f32() {
+        // Flash treats a divisor of 0 as 1.
+        d if d == 0.0 => 1.0,
+        d => d,
+    };
+    let bias = filter.bias.to_f32();
+    let Color { r, g, b, a } = filter.default_color;
+    let default_color = [f32::from(r), f32::from(g), f32::from(b), f32::from(a)];
+    let preserve_alpha = filter.is_preserve_alpha();
+
+    for y in 0..source.height as i32 {
+        for x in 0..source.width as i32 {
+            let mut sum = [0.0f32; 4];
+            for my in 0..rows {
+                for mx in 0..cols {
+                    let weight = matrix
+                        .get((my * cols + mx) as usize)
+                        .copied()
+                        .unwrap_or_default();
+                    if weight == 0.0 {
+                        continue;
+                    }
+                    let sample_x = x + mx - cols / 2;
+                    let sample_y = y + my - rows / 2;
+                    let sample = if filter.is_clamped() {
+                        unmultiply(source.get_clamped(sample_x, sample_y))
+                    } else {
+                        source
+                            .get_checked(sample_x, sample_y)
+                            .map(unmultiply)
+                            .unwrap_or(default_color)
+                    };
+                    for (total, value) in sum.iter_mut().zip(sample) {
+                        *total += value * weight;
+                    }
+                }
+            }
+
+            let mut color = sum.map(|total| total / divisor + bias);
+            if preserve_alpha {
+                color[3] = f32::from(source.get(x as u32, y as u32)[3]);
+            }
+            dest.set(x as u32, y as u32, premultiply(color));
+        }
+    }
+
+    dest
+}
+
+/// Converts a color with channels normalized to 0-1 back to bytes.
+#[inline]
+fn from_unit(color: [f32; 4]) -> [u8; 4] {
+    color.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+/// Converts a straight alpha `Color` to a premultiplied one, with channels normalized to 0-1.
+fn premultiplied_unit(color: Color) -> [f32; 4] {
+    let a = f32::from(color.a) / 255.0;
+    [
+        f32::from(color.r) / 255.0 * a,
+        f32::from(color.g) / 255.0 * a,
+        f32::from(color.b) / 255.0 * a,
+        a,
+    ]
+}
+
+/// Performs a single box blur pass over one channel, in one direction.
+///
+/// `size` is the full (potentially fractional) width of the kernel. Pixels that are only
+/// partially covered by the kernel contribute proportionally, and anything outside of the image
+/// is treated as transparent.
+fn blur_pass(values: &[f32], width: u32, height: u32, size: f32, horizontal: bool) -> Vec<f32> {
+    let half = size / 2.0;
+    let reach = (half - 0.5).ceil().max(0.0) as i32;
+    let weights: Vec<f32> = (-reach..=reach)
+        .map(|k| {
+            let k = k as f32;
+            let coverage = (k + 0.5).min(half) - (k - 0.5).max(-half);
+            coverage.clamp(0.0, 1.0) / size
+        })
+        .collect();
+
+    let (width, height) = (width as i32, height as i32);
+    let mut result = vec![0.0; values.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let mut total = 0.0;
+            for (k, weight) in (-reach..=reach).zip(&weights) {
+                let (sample_x, sample_y) = if horizontal { (x + k, y) } else { (x, y + k) };
+                if sample_x >= 0 && sample_y >= 0 && sample_x < width && sample_y < height {
+                    total += values[(sample_y * width + sample_x) as usize] * weight;
+                }
+            }
+            result[(y * width + x) as usize] = total;
+        }
+    }
+    result
+}
+
+/// Blurs a single channel of an image, using every pass of `filter`.
+fn blur_plane(mut values: Vec<f32>, width: u32, height: u32, filter: &BlurFilter) -> Vec<f32> {
+    for _ in 0..filter.num_passes() {
+        for (size, horizontal) in [(filter.blur_x, true), (filter.blur_y, false)] {
+            // A width of 1 or less is a noop (it'd just sample itself and nothing else)
+            let size = size.to_f32().min(255.0);
+            if size > 1.0 {
+                values = blur_pass(&values, width, height, size, horizontal);
+            }
+        }
+    }
+    values
+}
+
+/// Samples a plane at the nearest pixel to the given position, or 0 if it's out of bounds.
+#[inline]
+fn sample_plane(values: &[f32], width: u32, height: u32, x: f32, y: f32) -> f32 {
+    let (x, y) = (x.round() as i32, y.round() as i32);
+    if x < 0 || y < 0 || x >= width as i32 || y >= height as i32 {
+        0.0
+    } else {
+        values[(y as u32 * width + x as u32) as usize]
+    }
+}
+
+/// Combines the source with a (premultiplied) glow, the same way that every glow-like filter does.
+fn composite_glow(
+    dest: [f32; 4],
+    glow: [f32; 4],
+    inner: bool,
+    outer: bool,
+    knockout: bool,
+    composite_source: bool,
+) -> [f32; 4] {
+    let mix = |f: &dyn Fn(f32, f32) -> f32| -> [f32; 4] {
+        [
+            f(dest[0], glow[0]),
+            f(dest[1], glow[1]),
+            f(dest[2], glow[2]),
+            f(dest[3], glow[3]),
+        ]
+    };
+    match (inner, outer, knockout, composite_source) {
+        (true, true, true, _) => glow,
+        (true, true, false, _) => mix(&|d, g| d - d * glow[3] + g),
+        (true, false, false, true) => mix(&|d, g| g * dest[3] + d * (1.0 - glow[3])),
+        (true, false, _, _) => glow.map(|g| g * dest[3]),
+        (false, _, true, _) => glow.map(|g| g * (1.0 - dest[3])),
+        (false, _, false, true) => mix(&|d, g| g * (1.0 - dest[3]) + d),
+        (false, _, false, false) => glow,
+    }
+}
+
+/// Applies a glow to every pixel of `source`, where `glow` calculates the color of the glow
+/// from the blurred alpha value at the given pixel.
+#[allow(clippy::too_many_arguments)]
+fn apply_glow_like(
+    source: &FilterImage,
+    blur: &BlurFilter,
+    offset: (f32, f32),
+    inner: bool,
+    outer: bool,
+    knockout: bool,
+    composite_source: bool,
+    glow: impl Fn(f32) -> [f32; 4],
+) -> FilterImage {
+    let (width, height) = (source.width, source.height);
+    let blurred = blur_plane(source.alpha_plane(), width, height, blur);
+    let mut dest = FilterImage::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            let alpha = sample_plane(
+                &blurred,
+                width,
+                height,
+                x as f32 + offset.0,
+                y as f32 + offset.1,
+            );
+            let color = composite_glow(
+                source.get_unit(x, y),
+                glow(alpha),
+                inner,
+                outer,
+                knockout,
+                composite_source,
+            );
+            dest.set(x, y, from_unit(color));
+        }
+    }
+    dest
+}
+
+/// Applies a bevel to every pixel of `source`, where `bevel` calculates the color of the bevel
+/// from the blurred alpha values at the highlight and shadow sides of the given pixel.
+#[allow(clippy::too_many_arguments)]
+fn apply_bevel_like(
+    source: &FilterImage,
+    blur: &BlurFilter,
+    offset: (f32, f32),
+    inner: bool,
+    outer: bool,
+    knockout: bool,
+    composite_source: bool,
+    bevel: impl Fn(f32, f32) -> [f32; 4],
+) -> FilterImage {
+    let (width, height) = (source.width, source.height);
+    let blurred = blur_plane(source.alpha_plane(), width, height, blur);
+    let mut dest = FilterImage::new(width, height);
+    for y in 0..height {
+        for x in 0..width {
+            let (x_f, y_f) = (x as f32, y as f32);
+            let highlight = sample_plane(&blurred, width, height, x_f + offset.0, y_f + offset.1);
+            let shadow = sample_plane(&blurred, width, height, x_f - offset.0, y_f - offset.1);
+            let color = composite_glow(
+                source.get_unit(x, y),
+                bevel(highlight, shadow),
+                inner,
+                outer,
+                knockout,
+                composite_source,
+            );
+            dest.set(x, y, from_unit(color));
+        }
+    }
+    dest
+}
+
+/// Returns a function that looks up the (premultiplied) color of a gradient for a ratio between 0 and 1.
+fn gradient_lookup(records: &[GradientRecord]) -> impl Fn(f32) -> [f32; 4] {
+    let lut = gradient_filter_lut(records);
+    move |ratio: f32| {
+        let index = (ratio.clamp(0.0, 1.0) * 255.0).round() as usize * 4;
+        let a = f32::from(lut[index + 3]) / 255.0;
+        [
+            f32::from(lut[index]) / 255.0 * a,
+            f32::from(lut[index + 1]) / 255.0 * a,
+            f32::from(lut[index + 2]) / 255.0 * a,
+            a,
+        ]
+    }
+}
+
+/// Returns how far a filter's blur should be offset, given its angle and distance.
+fn angle_offset(angle: swf::Fixed16, distance: swf::Fixed16) -> (f32, f32) {
+    let (angle, distance) = (angle.to_f32(), distance.to_f32());
+    (angle.cos() * distance, angle.sin() * distance)
+}
+
+/// Applies a `BlurFilter` to `source`, producing an image of the same size.
+pub fn apply_blur(filter: &BlurFilter, source: &FilterImage) -> FilterImage {
+    let (width, height) = (source.width, source.height);
+    let planes = source
+        .planes()
+        .map(|plane| blur_plane(plane, width, height, filter));
+    let mut dest = FilterImage::new(width, height);
+    for (i, pixel) in dest.pixels.iter_mut().enumerate() {
+        // Flash truncates the result of blurs, rather than rounding it.
+        *pixel = std::array::from_fn(|c| planes[c][i].clamp(0.0, 255.0) as u8);
+    }
+    dest
+}
+
+/// Applies a `ColorMatrixFilter` to `source`, producing an image of the same size.
+pub fn apply_color_matrix(filter: &ColorMatrixFilter, source: &FilterImage) -> FilterImage {
+    let m = &filter.matrix;
+    let mut dest = FilterImage::new(source.width, source.height);
+    for (dest, &pixel) in dest.pixels.iter_mut().zip(&source.pixels) {
+        let [r, g, b, a] = unmultiply(pixel);
+        let color: [f32; 4] = std::array::from_fn(|row| {
+            let row = &m[row * 5..row * 5 + 5];
+            row[0] * r + row[1] * g + row[2] * b + row[3] * a + row[4]
+        });
+        *dest = premultiply(color);
+    }
+    dest
+}
+
+/// Applies a `GlowFilter` to `source`, producing an image of the same size.
+pub fn apply_glow(filter: &GlowFilter, source: &FilterImage) -> FilterImage {
+    glow_with_offset(filter, source, (0.0, 0.0))
+}
+
+fn glow_with_offset(filter: &GlowFilter, source: &FilterImage, offset: (f32, f32)) -> FilterImage {
+    let inner = filter.is_inner();
+    let strength = filter.strength.to_f32();
+    let color = filter.color;
+    let [r, g, b] = [color.r, color.g, color.b].map(|c| f32::from(c) / 255.0);
+    apply_glow_like(
+        source,
+        &filter.inner_blur_filter(),
+        offset,
+        inner,
+        !inner,
+        filter.is_knockout(),
+        filter.composite_source(),
+        |blur| {
+            let blur = if inner { 1.0 - blur } else { blur };
+            let alpha = f32::from(color.a) / 255.0 * (blur * strength).clamp(0.0, 1.0);
+            [r * alpha, g * alpha, b * alpha, alpha]
+        },
+    )
+}
+
+/// Applies a `DropShadowFilter` to `source`, producing an image of the same size.
+pub fn apply_drop_shadow(filter: &DropShadowFilter, source: &FilterImage) -> FilterImage {
+    let (x, y) = angle_offset(filter.angle, filter.distance);
+    glow_with_offset(&filter.inner_glow_filter(), source, (-x, -y))
+}
+
+/// Applies a `BevelFilter` to `source`, producing an image of the same size.
+pub fn apply_bevel(filter: &BevelFilter, source: &FilterImage) -> FilterImage {
+    let strength = filter.strength.to_f32();
+    let highlight_color = premultiplied_unit(filter.highlight_color);
+    let shadow_color = premultiplied_unit(filter.shadow_color);
+    let on_top = filter.is_on_top();
+    apply_bevel_like(
+        source,
+        &filter.inner_blur_filter(),
+        angle_offset(filter.angle, filter.distance),
+        on_top || filter.is_inner(),
+        on_top || !filter.is_inner(),
+        filter.is_knockout(),
+        true,
+        |highlight, shadow| {
+            let highlight_alpha = ((highlight - shadow) * strength).clamp(0.0, 1.0);
+            let shadow_alpha = ((shadow - highlight) * strength).clamp(0.0, 1.0);
+            std::array::from_fn(|c| {
+                highlight_color[c] * highlight_alpha + shadow_color[c] * shadow_alpha
+            })
+        },
+    )
+}
+
+/// Applies a `GradientGlowFilter` to `source`, producing an image of the same size.
+pub fn apply_gradient_glow(filter: &GradientFilter, source: &FilterImage) -> FilterImage {
+    let strength = filter.strength.to_f32();
+    let gradient = gradient_lookup(&filter.colors);
+    let on_top = filter.is_on_top();
+    let (x, y) = angle_offset(filter.angle, filter.distance);
+    apply_glow_like(
+        source,
+        &filter.inner_blur_filter(),
+        (-x, -y),
+        on_top || filter.is_inner(),
+        on_top || !filter.is_inner(),
+        filter.is_knockout(),
+        filter.composite_source(),
+        |blur| gradient(blur * strength),
+    )
+}
+
+/// Applies a `GradientBevelFilter` to `source`, producing an image of the same size.
+pub fn apply_gradient_bevel(filter: &GradientFilter, source: &FilterImage) -> FilterImage {
+    let strength = filter.strength.to_f32();
+    let gradient = gradient_lookup(&filter.colors);
+    let on_top = filter.is_on_top();
+    apply_bevel_like(
+        source,
+        &filter.inner_blur_filter(),
+        angle_offset(filter.angle, filter.distance),
+        on_top || filter.is_inner(),
+        on_top || !filter.is_inner(),
+        filter.is_knockout(),
+        true,
+        // The gradient runs from the shadow (ratio 0) through the middle (ratio 128) to the highlight (ratio 255).
+        |highlight, shadow| gradient(0.5 + (highlight - shadow) * strength * 0.5),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::{BlurFilterFlags, ConvolutionFilterFlags, Fixed16, Fixed8, GlowFilterFlags};
+
+    fn filter(
+        matrix: &[f32],
+        cols: u8,
+        rows: u8,
+        flags: ConvolutionFilterFlags,
+    ) -> ConvolutionFilter {
+        ConvolutionFilter {
+            num_matrix_rows: rows,
+            num_matrix_cols: cols,
+            matrix: matrix.iter().map(|v| Fixed16::from_f32(*v)).collect(),
+            divisor: Fixed16::ONE,
+            bias: Fixed16::ZERO,
+            default_color: Color::from_rgba(0),
+            flags,
+        }
+    }
+
+    #[test]
+    fn identity_convolution() {
+        let mut source = FilterImage::new(3, 3);
+        source.set(1, 1, [255, 0, 0, 255]);
+        source.set(0, 2, [0, 128, 0, 128]);
+        let result = apply_convolution(
+            &filter(
+                &[0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0],
+                3,
+                3,
+                ConvolutionFilterFlags::empty(),
+            ),
+            &source,
+        );
+        assert_eq!(result, source);
+    }
+
+    #[test]
+    fn clamped_convolution_samples_edges() {
+        let mut source = FilterImage::new(2, 1);
+        source.set(0, 0, [255, 255, 255, 255]);
+        source.set(1, 0, [0, 0, 0, 255]);
+        // Shift everything one pixel to the left.
+        let result = apply_convolution(
+            &filter(&[0.0, 0.0, 1.0], 3, 1, ConvolutionFilterFlags::CLAMP),
+            &source,
+        );
+        assert_eq!(result.get(0, 0), [0, 0, 0, 255]);
+        assert_eq!(result.get(1, 0), [0, 0, 0, 255]);
+
+        let result = apply_convolution(
+            &filter(&[0.0, 0.0, 1.0], 3, 1, ConvolutionFilterFlags::empty()),
+            &source,
+        );
+        assert_eq!(result.get(1, 0), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn blur_spreads_evenly() {
+        let mut source = FilterImage::new(5, 1);
+        source.set(2, 0, [255, 255, 255, 255]);
+        let result = apply_blur(
+            &BlurFilter {
+                blur_x: Fixed16::from_f32(3.0),
+                blur_y: Fixed16::ZERO,
+                flags: BlurFilterFlags::from_passes(1),
+            },
+            &source,
+        );
+        assert_eq!(result.get(0, 0), [0, 0, 0, 0]);
+        assert_eq!(result.get(1, 0), [85, 85, 85, 85]);
+        assert_eq!(result.get(2, 0), [85, 85, 85, 85]);
+        assert_eq!(result.get(3, 0), [85, 85, 85, 85]);
+        assert_eq!(result.get(4, 0), [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn knockout_outer_glow_only_shows_glow() {
+        let mut source = FilterImage::new(3, 1);
+        source.set(1, 0, [255, 255, 255, 255]);
+        let result = apply_glow(
+            &GlowFilter {
+                color: Color::from_rgba(0xFFFF0000),
+                blur_x: Fixed16::from_f32(3.0),
+                blur_y: Fixed16::ZERO,
+                strength: Fixed8::from_f32(3.0),
+                flags: GlowFilterFlags::KNOCKOUT | GlowFilterFlags::from_passes(1),
+            },
+            &source,
+        );
+        assert_eq!(result.get(0, 0), [255, 0, 0, 255]);
+        assert_eq!(result.get(1, 0), [0, 0, 0, 0]);
+        assert_eq!(result.get(2, 0), [255, 0, 0, 255]);
+    }
+}
@@ -3,7 +3,7 @@ use enum_map::Enum;
 use smallvec::SmallVec;
 use swf::{CharacterId, FillStyle, LineStyle, Rectangle, Shape, ShapeRecord, Twips};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum FillRule {
     EvenOdd,
     NonZero,
@@ -78,7 +78,7 @@ pub fn calculate_shape_bounds(shape_records: &[swf::ShapeRecord]) -> swf::Rectan
 /// `DrawPath` represents a solid fill or a stroke.
 /// Fills are always closed paths, while strokes may be open or closed.
 /// Closed paths will have the first point equal to the last point.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum DrawPath<'a> {
     Stroke {
         style: &'a LineStyle,
@@ -94,7 +94,7 @@ pub enum DrawPath<'a> {
 
 /// `DistilledShape` represents a ready-to-be-consumed collection of paths (both fills and strokes)
 /// that has been converted down from another source (such as SWF's `swf::Shape` format).
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct DistilledShape<'a> {
     pub paths: Vec<DrawPath<'a>>,
     pub shape_bounds: Rectangle<Twips>,
@@ -102,6 +102,86 @@ pub struct DistilledShape<'a> {
     pub id: CharacterId,
 }
 
+impl<'a> DistilledShape<'a> {
+    /// Whether any fill or stroke of this shape is filled with a bitmap.
+    /// The IDs of such bitmaps are only meaningful to the `BitmapSource` of the shape.
+    pub fn has_bitmap_fills(&self) -> bool {
+        self.paths.iter().any(|path| {
+            let style = match path {
+                DrawPath::Fill { style, .. } => *style,
+                DrawPath::Stroke { style, .. } => style.fill_style(),
+            };
+            matches!(style, FillStyle::Bitmap { .. })
+        })
+    }
+
+    /// Whether any stroke of this shape doesn't scale with the shape in both directions.
+    /// The width of such strokes depends on the transform the shape is drawn with.
+    pub fn has_non_scaling_strokes(&self) -> bool {
+        self.paths.iter().any(|path| match path {
+            DrawPath::Stroke { style, .. } => {
+                LineScaleMode::for_style(style) != LineScaleMode::Both
+            }
+            DrawPath::Fill { .. } => false,
+        })
+    }
+
+    /// Calls `f` with a copy of this shape, where the strokes that don't scale with the shape in
+    /// both directions are resized to be drawn with the correct width under `matrix`.
+    pub fn with_scaled_strokes<R>(
+        self,
+        matrix: &Matrix,
+        f: impl FnOnce(DistilledShape<'_>) -> R,
+    ) -> R {
+        let mut line_scales = LineScales::new(matrix);
+        let scaled_styles: Vec<Option<LineStyle>> = self
+            .paths
+            .iter()
+            .map(|path| match path {
+                DrawPath::Stroke { style, .. } => {
+                    let scale_mode = LineScaleMode::for_style(style);
+                    (scale_mode != LineScaleMode::Both).then(|| {
+                        let width =
+                            line_scales.local_width(style.width().to_pixels() as f32, scale_mode);
+                        (*style)
+                            .clone()
+                            .with_width(Twips::from_pixels(width.into()))
+                    })
+                }
+                DrawPath::Fill { .. } => None,
+            })
+            .collect();
+
+        let paths = self
+            .paths
+            .into_iter()
+            .zip(&scaled_styles)
+            .map(|(path, scaled_style)| match (path, scaled_style) {
+                (
+                    DrawPath::Stroke {
+                        commands,
+                        is_closed,
+                        ..
+                    },
+                    Some(style),
+                ) => DrawPath::Stroke {
+                    style,
+                    commands,
+                    is_closed,
+                },
+                (path, _) => path,
+            })
+            .collect();
+
+        f(DistilledShape {
+            paths,
+            shape_bounds: self.shape_bounds,
+            edge_bounds: self.edge_bounds,
+            id: self.id,
+        })
+    }
+}
+
 impl<'a> From<&'a swf::Shape> for DistilledShape<'a> {
     fn from(shape: &'a Shape) -> Self {
         Self {
@@ -115,7 +195,7 @@ impl<'a> From<&'a swf::Shape> for DistilledShape<'a> {
 
 /// `DrawCommands` trace the outline of a path.
 /// Fills follow the even-odd fill rule, with opposite winding for holes.
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum DrawCommand {
     MoveTo(swf::Point<Twips>),
     LineTo(swf::Point<Twips>),
@@ -1139,6 +1219,18 @@ pub enum LineScaleMode {
     Both,
 }
 
+impl LineScaleMode {
+    /// The scale mode of strokes drawn with the given line style.
+    pub fn for_style(style: &LineStyle) -> Self {
+        match (style.allow_scale_x(), style.allow_scale_y()) {
+            (false, false) => LineScaleMode::None,
+            (true, false) => LineScaleMode::Horizontal,
+            (false, true) => LineScaleMode::Vertical,
+            (true, true) => LineScaleMode::Both,
+        }
+    }
+}
+
 /// Helper type for calculating line widths for a transformed shape.
 pub struct LineScales<'a> {
     matrix: &'a Matrix,
@@ -1171,6 +1263,24 @@ impl<'a> LineScales<'a> {
         // This usually occurs in "hairline" strokes (exported with width of 1 twip).
         scaled_width.max(1.0)
     }
+
+    /// Returns the width a line needs in its shape's own coordinate space, to be drawn with
+    /// its final width after transformation.
+    pub fn local_width(&mut self, width: f32, scale_mode: LineScaleMode) -> f32 {
+        let scaled_width = self.transform_width(width, scale_mode);
+        let scales = self.scales.expect("Calculated by transform_width");
+        if scales[LineScaleMode::Both as usize] > 0.0 {
+            scaled_width / scales[LineScaleMode::Both as usize]
+        } else {
+            scaled_width
+        }
+    }
+
+    /// Returns a key that's equal for matrices that give lines the same widths, ignoring
+    /// differences that are too small to be visible.
+    pub fn cache_key(matrix: &Matrix) -> [i32; 4] {
+        [matrix.a, matrix.b, matrix.c, matrix.d].map(|value| (value * 256.0).round() as i32)
+    }
 }
 
 #[cfg(test)]
@@ -1317,4 +1427,14 @@ mod tests {
             1,
         );
     }
+
+    #[test]
+    fn non_scaling_line_width() {
+        let matrix = Matrix::scale(2.0, 2.0);
+        let mut line_scales = LineScales::new(&matrix);
+        assert_eq!(line_scales.local_width(3.0, LineScaleMode::Both), 3.0);
+        assert_eq!(line_scales.local_width(3.0, LineScaleMode::None), 1.5);
+        // Lines are always at least a pixel wide after transformation.
+        assert_eq!(line_scales.local_width(0.0, LineScaleMode::None), 0.5);
+    }
 }
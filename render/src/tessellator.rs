@@ -1,5 +1,5 @@
 use crate::bitmap::BitmapSource;
-use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, GradientType};
+use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, GradientType, LineScaleMode};
 use lyon::path::Path;
 use lyon::tessellation::{
     self,
@@ -45,16 +45,32 @@ impl ShapeTessellator {
                     style,
                     commands,
                     winding_rule: _,
-                } => (*style, ruffle_path_to_lyon_path(commands, true), false),
+                } => (
+                    *style,
+                    ruffle_path_to_lyon_path(commands, true, None),
+                    false,
+                ),
                 DrawPath::Stroke {
                     style,
                     commands,
                     is_closed,
-                } => (
-                    style.fill_style(),
-                    ruffle_path_to_lyon_path(commands, *is_closed),
-                    true,
-                ),
+                } => {
+                    // Pixel hinted strokes are snapped to the pixel grid, so that they're drawn
+                    // sharply. Odd widths are centered on pixels, rather than between them.
+                    let pixel_offset = style.is_pixel_hinted().then(|| {
+                        let width = style.width().to_pixels().max(1.0).round() as i32;
+                        if width % 2 == 1 {
+                            0.5
+                        } else {
+                            0.0
+                        }
+                    });
+                    (
+                        style.fill_style(),
+                        ruffle_path_to_lyon_path(commands, *is_closed, pixel_offset),
+                        true,
+                    )
+                }
             };
 
             let (draw, color, needs_flush) = match fill_style {
@@ -123,10 +139,12 @@ impl ShapeTessellator {
                 // * Strokes followed by fills, because strokes need to be omitted
                 //   when using this shape as a mask.
                 self.flush_draw(DrawType::Color);
-            } else if !self.is_stroke && next_is_stroke {
+            }
+            if next_is_stroke && self.mask_index_count.is_none() {
                 // Bake solid color fills followed by strokes into a single draw call, and adjust
                 // the index count to omit the strokes when rendering this shape as a mask.
-                debug_assert!(self.mask_index_count.is_none());
+                // Gradient and bitmap strokes get a draw call of their own, which is omitted
+                // from masks entirely.
                 self.mask_index_count = Some(self.lyon_mesh.indices.len() as u32);
             }
             self.is_stroke = next_is_stroke;
@@ -140,8 +158,15 @@ impl ShapeTessellator {
                     &mut buffers_builder,
                 ),
                 DrawPath::Stroke { style, .. } => {
-                    // TODO(Herschel): 0 width indicates "hairline".
-                    let width = (style.width().to_pixels() as f32).max(1.0);
+                    let width = if LineScaleMode::for_style(style) == LineScaleMode::Both {
+                        // Flash draws all strokes with a minimum width of 1 pixel. The transform
+                        // isn't known yet, so approximate this in the shape's coordinate space.
+                        (style.width().to_pixels() as f32).max(1.0)
+                    } else {
+                        // Strokes that don't scale with their shape are expected to have been
+                        // resized for their transform by `DistilledShape::with_scaled_strokes`.
+                        style.width().to_pixels() as f32
+                    };
                     let mut stroke_options = StrokeOptions::default()
                         .with_line_width(width)
                         .with_start_cap(match style.start_cap() {
@@ -159,14 +184,11 @@ impl ShapeTessellator {
                         swf::LineJoinStyle::Round => tessellation::LineJoin::Round,
                         swf::LineJoinStyle::Bevel => tessellation::LineJoin::Bevel,
                         swf::LineJoinStyle::Miter(limit) => {
-                            // Avoid lyon assert with small miter limits.
-                            let limit = limit.to_f32();
-                            if limit >= StrokeOptions::MINIMUM_MITER_LIMIT {
-                                stroke_options = stroke_options.with_miter_limit(limit);
-                                tessellation::LineJoin::MiterClip
-                            } else {
-                                tessellation::LineJoin::Bevel
-                            }
+                            // Flash clamps the miter limit to at least 1, which also avoids
+                            // a lyon assert with small miter limits.
+                            let limit = limit.to_f32().max(StrokeOptions::MINIMUM_MITER_LIMIT);
+                            stroke_options = stroke_options.with_miter_limit(limit);
+                            tessellation::LineJoin::MiterClip
                         }
                     };
                     stroke_options = stroke_options.with_line_join(line_join);
@@ -180,7 +202,7 @@ impl ShapeTessellator {
             match result {
                 Ok(_) => {
                     if needs_flush {
-                        // Non-solid color fills are isolated draw calls; flush immediately.
+                        // Non-solid color fills and strokes are isolated draw calls; flush immediately.
                         self.flush_draw(draw);
                     }
                 }
@@ -201,6 +223,7 @@ impl ShapeTessellator {
     fn flush_draw(&mut self, draw: DrawType) {
         if self.lyon_mesh.vertices.is_empty() || self.lyon_mesh.indices.len() < 3 {
             // Ignore degenerate fills
+            self.mask_index_count = None;
             return;
         }
         let draw_mesh = std::mem::replace(&mut self.lyon_mesh, VertexBuffers::new());
@@ -326,10 +349,24 @@ fn swf_bitmap_to_gl_matrix(
     [[a, d, 0.0], [b, e, 0.0], [c, f, 1.0]]
 }
 
-fn ruffle_path_to_lyon_path(commands: &[DrawCommand], is_closed: bool) -> Path {
-    fn point(point: swf::Point<swf::Twips>) -> lyon::math::Point {
-        lyon::math::Point::new(point.x.to_pixels() as f32, point.y.to_pixels() as f32)
-    }
+/// Converts drawing commands to a lyon path.
+///
+/// If `pixel_offset` is set, points are snapped to whole pixels plus that offset.
+fn ruffle_path_to_lyon_path(
+    commands: &[DrawCommand],
+    is_closed: bool,
+    pixel_offset: Option<f32>,
+) -> Path {
+    let point = |point: swf::Point<swf::Twips>| {
+        let x = point.x.to_pixels() as f32;
+        let y = point.y.to_pixels() as f32;
+        match pixel_offset {
+            Some(offset) => {
+                lyon::math::Point::new((x - offset).round() + offset, (y - offset).round() + offset)
+            }
+            None => lyon::math::Point::new(x, y),
+        }
+    };
 
     let mut builder = Path::builder();
     let mut cursor = Some(swf::Point::ZERO);
@@ -1140,6 +1140,18 @@ impl RenderBackend for WebGlRenderBackend {
         Err(BitmapError::Unimplemented("run_pixelbender_shader".into()))
     }
 
+    fn run_pixelbender_shader_to_floats(
+        &mut self,
+        _handle: ruffle_render::pixel_bender::PixelBenderShaderHandle,
+        _arguments: &[ruffle_render::pixel_bender::PixelBenderShaderArgument],
+        _width: u32,
+        _height: u32,
+    ) -> Result<Vec<f32>, BitmapError> {
+        Err(BitmapError::Unimplemented(
+            "run_pixelbender_shader_to_floats".into(),
+        ))
+    }
+
     fn create_empty_texture(
         &mut self,
         width: u32,
@@ -1508,6 +1520,10 @@ impl CommandHandler for WebGlRenderBackend {
     }
 
     fn blend(&mut self, commands: CommandList, blend: RenderBlendMode) {
+        if let RenderBlendMode::AlphaMask = blend {
+            // Without an intermediate buffer, an alpha mask would just be drawn on top.
+            return;
+        }
         self.push_blend_mode(blend);
         commands.execute(self);
         self.pop_blend_mode();
@@ -0,0 +1,86 @@
+#import filter
+
+/// Flash allows up to 15x15 matrices, packed into vec4s.
+const MAX_MATRIX_SIZE: i32 = 225;
+
+struct Filter {
+    matrix: array<vec4<f32>, 57>,
+    default_color: vec4<f32>,
+    bias: f32,
+    divisor: f32,
+    matrix_x: i32,
+    matrix_y: i32,
+    source_bounds: vec4<i32>,
+    preserve_alpha: u32,
+    clamp_edges: u32,
+    _padding1: u32,
+    _padding2: u32,
+}
+
+@group(0) @binding(0) var texture: texture_2d<f32>;
+@group(0) @binding(1) var texture_sampler: sampler;
+@group(0) @binding(2) var<uniform> filter_args: Filter;
+
+@vertex
+fn main_vertex(in: filter::VertexInput) -> filter::VertexOutput {
+    return filter::main_vertex(in);
+}
+
+fn unmultiply(color: vec4<f32>) -> vec4<f32> {
+    if (color.a == 0.0) {
+        return vec4<f32>(0.0);
+    }
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
+
+fn weight(index: i32) -> f32 {
+    let packed = filter_args.matrix[index / 4];
+    let component = index % 4;
+    if (component == 0) {
+        return packed.x;
+    } else if (component == 1) {
+        return packed.y;
+    } else if (component == 2) {
+        return packed.z;
+    }
+    return packed.w;
+}
+
+@fragment
+fn main_fragment(in: filter::VertexOutput) -> @location(0) vec4<f32> {
+    let bounds = filter_args.source_bounds;
+    let size = vec2<f32>(textureDimensions(texture));
+    let center = vec2<i32>(floor(in.uv * size));
+    let clamp_edges = filter_args.clamp_edges > 0u;
+
+    var sum = vec4<f32>(0.0);
+    for (var y = 0; y < filter_args.matrix_y; y++) {
+        for (var x = 0; x < filter_args.matrix_x; x++) {
+            let index = y * filter_args.matrix_x + x;
+            if (index >= MAX_MATRIX_SIZE) {
+                break;
+            }
+            let w = weight(index);
+            if (w == 0.0) {
+                continue;
+            }
+            var coord = center + vec2<i32>(x - filter_args.matrix_x / 2, y - filter_args.matrix_y / 2);
+            var texel: vec4<f32>;
+            if (clamp_edges) {
+                coord = clamp(coord, bounds.xy, bounds.zw - vec2<i32>(1, 1));
+                texel = unmultiply(textureLoad(texture, coord, 0));
+            } else if (any(coord < bounds.xy) || any(coord >= bounds.zw)) {
+                texel = filter_args.default_color;
+            } else {
+                texel = unmultiply(textureLoad(texture, coord, 0));
+            }
+            sum += texel * w;
+        }
+    }
+
+    var color = saturate(sum / filter_args.divisor + vec4<f32>(filter_args.bias / 255.0));
+    if (filter_args.preserve_alpha > 0u) {
+        color.a = textureLoad(texture, clamp(center, bounds.xy, bounds.zw - vec2<i32>(1, 1)), 0).a;
+    }
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
@@ -8,11 +8,12 @@ use crate::surface::{LayerRef, Surface};
 use crate::target::{MaybeOwnedBuffer, TextureTarget};
 use crate::target::{RenderTargetFrame, TextureBufferInfo};
 use crate::uniform_buffer::{BufferStorage, UniformBuffer};
-use crate::utils::{run_copy_pipeline, BufferDimensions};
+use crate::utils::{capture_image, run_copy_pipeline, BufferDimensions};
 use crate::{
     as_texture, format_list, get_backend_names, ColorAdjustments, Descriptors, Error,
     QueueSyncHandle, RenderTarget, SwapChainTarget, Texture, Transforms,
 };
+use fnv::FnvHashMap;
 use image::imageops::FilterType;
 use ruffle_render::backend::{BitmapCacheEntry, Context3D};
 use ruffle_render::backend::{RenderBackend, ShapeHandle, ViewportDimensions};
@@ -30,9 +31,11 @@ use ruffle_render::shape_utils::DistilledShape;
 use ruffle_render::tessellator::ShapeTessellator;
 use std::borrow::Cow;
 use std::cell::Cell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use std::mem;
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use swf::{Color, Rectangle};
 use tracing::instrument;
 use wgpu::SubmissionIndex;
@@ -41,6 +44,10 @@ use wgpu::SubmissionIndex;
 /// before it's automatically allocated a buffer on each write.
 const TEXTURE_READS_BEFORE_PROMOTION: u8 = 5;
 
+/// How many shapes may be in the tessellation cache before entries of shapes
+/// that are no longer used are removed.
+const SHAPE_CACHE_PRUNE_THRESHOLD: usize = 256;
+
 pub struct WgpuRenderBackend<T: RenderTarget> {
     pub(crate) descriptors: Arc<Descriptors>,
     uniform_buffers_storage: BufferStorage<Transforms>,
@@ -49,6 +56,9 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     surface: Surface,
     meshes: Vec<Mesh>,
     shape_tessellator: ShapeTessellator,
+    /// Meshes of registered shapes, keyed by a hash of their paths, so that identical shapes
+    /// (such as dynamic drawings that are cleared and redrawn every frame) are only tessellated once.
+    shape_cache: FnvHashMap<u64, Weak<Mesh>>,
     // This is currently unused - we just store it to report in
     // `get_viewport_dimensions`
     viewport_scale_factor: f64,
@@ -207,6 +217,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             surface,
             meshes: Vec::new(),
             shape_tessellator: ShapeTessellator::new(),
+            shape_cache: FnvHashMap::default(),
             viewport_scale_factor: 1.0,
             texture_pool: TexturePool::new(),
             offscreen_texture_pool: TexturePool::new(),
@@ -475,8 +486,28 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         shape: DistilledShape,
         bitmap_source: &dyn BitmapSource,
     ) -> ShapeHandle {
-        let mesh = self.register_shape_internal(shape, bitmap_source);
-        ShapeHandle(Arc::new(mesh))
+        // Bitmap IDs are only meaningful to this shape's `bitmap_source`, so such shapes can't be shared.
+        let key = (!shape.has_bitmap_fills()).then(|| {
+            let mut hasher = DefaultHasher::new();
+            shape.hash(&mut hasher);
+            hasher.finish()
+        });
+
+        if let Some(mesh) = key
+            .and_then(|key| self.shape_cache.get(&key))
+            .and_then(Weak::upgrade)
+        {
+            return ShapeHandle(mesh);
+        }
+
+        let mesh = Arc::new(self.register_shape_internal(shape, bitmap_source));
+        if let Some(key) = key {
+            if self.shape_cache.len() >= SHAPE_CACHE_PRUNE_THRESHOLD {
+                self.shape_cache.retain(|_, mesh| mesh.strong_count() > 0);
+            }
+            self.shape_cache.insert(key, Arc::downgrade(&mesh));
+        }
+        ShapeHandle(mesh)
     }
 
     #[instrument(level = "debug", skip_all)]
@@ -818,7 +849,10 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 | Filter::ColorMatrixFilter(_)
                 | Filter::ShaderFilter(_)
                 | Filter::BevelFilter(_)
+                | Filter::GradientGlowFilter(_)
+                | Filter::GradientBevelFilter(_)
                 | Filter::DisplacementMapFilter(_)
+                | Filter::ConvolutionFilter(_)
         )
     }
 
@@ -866,40 +900,132 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                     label: label.as_deref(),
                 });
 
-        let applied_filter = self.descriptors.filters.apply(
-            &self.descriptors,
-            &mut draw_encoder,
-            &mut self.offscreen_texture_pool,
-            FilterSource {
+        // Filters such as glows may draw outside of the source area. Flash keeps the output
+        // aligned with the source, so any spill is placed relative to `dest_point` and clipped.
+        let source_rect = Rectangle {
+            x_min: 0,
+            y_min: 0,
+            x_max: source_size.0 as i32,
+            y_max: source_size.1 as i32,
+        };
+        let filter_rect = self
+            .descriptors
+            .filters
+            .calculate_dest_rect(&filter, source_rect);
+        let padded_texture = if filter_rect != source_rect {
+            // Everything outside of the source area must be transparent, so we copy
+            // the source into a fresh (zeroed) texture that's big enough for the result.
+            let texture = self
+                .descriptors
+                .device
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: create_debug_label!("Padded filter source").as_deref(),
+                    size: wgpu::Extent3d {
+                        width: filter_rect.width() as u32,
+                        height: filter_rect.height() as u32,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: source_texture.texture.format(),
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING
+                        | wgpu::TextureUsages::COPY_DST
+                        | wgpu::TextureUsages::COPY_SRC,
+                    view_formats: &[],
+                });
+            draw_encoder.copy_texture_to_texture(
+                wgpu::ImageCopyTexture {
+                    texture: &source_texture.texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x: source_point.0,
+                        y: source_point.1,
+                        z: 0,
+                    },
+                    aspect: Default::default(),
+                },
+                wgpu::ImageCopyTexture {
+                    texture: &texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x: (-filter_rect.x_min) as u32,
+                        y: (-filter_rect.y_min) as u32,
+                        z: 0,
+                    },
+                    aspect: Default::default(),
+                },
+                wgpu::Extent3d {
+                    width: source_size.0,
+                    height: source_size.1,
+                    depth_or_array_layers: 1,
+                },
+            );
+            Some(texture)
+        } else {
+            None
+        };
+        let filter_source = match &padded_texture {
+            Some(texture) => FilterSource::for_entire_texture(texture),
+            None => FilterSource {
                 texture: &source_texture.texture,
                 point: source_point,
                 size: source_size,
             },
+        };
+
+        let applied_filter = self.descriptors.filters.apply(
+            &self.descriptors,
+            &mut draw_encoder,
+            &mut self.offscreen_texture_pool,
+            filter_source,
             filter,
         );
-        draw_encoder.copy_texture_to_texture(
-            wgpu::ImageCopyTexture {
-                texture: applied_filter.color_texture(),
-                mip_level: 0,
-                origin: wgpu::Origin3d { x: 0, y: 0, z: 0 },
-                aspect: Default::default(),
-            },
-            wgpu::ImageCopyTexture {
-                texture: &dest_texture.texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d {
-                    x: dest_point.0,
-                    y: dest_point.1,
-                    z: 0,
+
+        // Work out where the result lands, clipping anything that falls outside of the destination
+        let dest_x = dest_point.0 as i32 + filter_rect.x_min;
+        let dest_y = dest_point.1 as i32 + filter_rect.y_min;
+        let copy_x = (-dest_x).max(0) as u32;
+        let copy_y = (-dest_y).max(0) as u32;
+        let dest_x = dest_x.max(0) as u32;
+        let dest_y = dest_y.max(0) as u32;
+        let copy_width = applied_filter
+            .width()
+            .saturating_sub(copy_x)
+            .min(dest_texture.texture.width().saturating_sub(dest_x));
+        let copy_height = applied_filter
+            .height()
+            .saturating_sub(copy_y)
+            .min(dest_texture.texture.height().saturating_sub(dest_y));
+        if copy_width > 0 && copy_height > 0 {
+            draw_encoder.copy_texture_to_texture(
+                wgpu::ImageCopyTexture {
+                    texture: applied_filter.color_texture(),
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x: copy_x,
+                        y: copy_y,
+                        z: 0,
+                    },
+                    aspect: Default::default(),
                 },
-                aspect: Default::default(),
-            },
-            wgpu::Extent3d {
-                width: (applied_filter.width()).min(dest_texture.texture.width() - dest_point.0),
-                height: (applied_filter.height()).min(dest_texture.texture.height() - dest_point.1),
-                depth_or_array_layers: 1,
-            },
-        );
+                wgpu::ImageCopyTexture {
+                    texture: &dest_texture.texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d {
+                        x: dest_x,
+                        y: dest_y,
+                        z: 0,
+                    },
+                    aspect: Default::default(),
+                },
+                wgpu::Extent3d {
+                    width: copy_width,
+                    height: copy_height,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
         let index = target.submit(
             &self.descriptors.device,
             &self.descriptors.queue,
@@ -993,6 +1119,126 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         ))
     }
 
+    fn run_pixelbender_shader_to_floats(
+        &mut self,
+        shader: PixelBenderShaderHandle,
+        arguments: &[PixelBenderShaderArgument],
+        width: u32,
+        height: u32,
+    ) -> Result<Vec<f32>, BitmapError> {
+        if width == 0 || height == 0 {
+            return Err(BitmapError::InvalidSize);
+        }
+
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+
+        let texture = self
+            .descriptors
+            .device
+            .create_texture(&wgpu::TextureDescriptor {
+                label: create_debug_label!("PixelBender float target").as_deref(),
+                size: extent,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba32Float,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[wgpu::TextureFormat::Rgba32Float],
+            });
+        let view = texture.create_view(&Default::default());
+
+        let bytes_per_pixel = 4 * std::mem::size_of::<f32>();
+        let unpadded_bytes_per_row = width as usize * bytes_per_pixel;
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as usize;
+        let dimensions = BufferDimensions {
+            width: width as usize,
+            height: height as usize,
+            unpadded_bytes_per_row,
+            padded_bytes_per_row: (unpadded_bytes_per_row
+                + (align - unpadded_bytes_per_row % align) % align)
+                as u32,
+        };
+
+        let buffer = self
+            .descriptors
+            .device
+            .create_buffer(&wgpu::BufferDescriptor {
+                label: create_debug_label!("PixelBender float readback buffer").as_deref(),
+                size: dimensions.size(),
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                mapped_at_creation: false,
+            });
+
+        let mut render_command_encoder =
+            self.descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: create_debug_label!("Render command encoder").as_deref(),
+                });
+
+        run_pixelbender_shader_impl(
+            &self.descriptors,
+            shader,
+            ShaderMode::ShaderJob,
+            arguments,
+            &texture,
+            &mut render_command_encoder,
+            Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: true,
+                },
+            }),
+            1,
+            &FilterSource::for_entire_texture(&texture),
+        )?;
+
+        render_command_encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: Default::default(),
+                aspect: Default::default(),
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(dimensions.padded_bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            extent,
+        );
+
+        let index = self
+            .descriptors
+            .queue
+            .submit(Some(render_command_encoder.finish()));
+
+        Ok(capture_image(
+            &self.descriptors.device,
+            &buffer,
+            &dimensions,
+            Some(index),
+            |bytes, padded_bytes_per_row| {
+                let mut floats = Vec::with_capacity(width as usize * height as usize * 4);
+                for row in bytes.chunks(padded_bytes_per_row as usize) {
+                    floats.extend_from_slice(bytemuck::cast_slice(
+                        &row[..dimensions.unpadded_bytes_per_row],
+                    ));
+                }
+                floats
+            },
+        ))
+    }
+
     fn create_empty_texture(
         &mut self,
         width: u32,
@@ -14,6 +14,7 @@ pub enum ComplexBlend {
     Erase,      // Can't be trivial, requires layer tracking
     Overlay,    // Can't be trivial, big math expression
     HardLight,  // Can't be trivial, big math expression
+    AlphaMask,  // Can't be trivial, affects the parent outside of the blended area
 }
 
 #[derive(Debug, Clone)]
@@ -58,6 +59,7 @@ impl BlendType {
                 BlendType::Complex(ComplexBlend::HardLight)
             }
             RenderBlendMode::Shader(shader) => BlendType::Shader(shader),
+            RenderBlendMode::AlphaMask => BlendType::Complex(ComplexBlend::AlphaMask),
         }
     }
 
@@ -1,8 +1,8 @@
 use naga::valid::{Capabilities, ValidationFlags, Validator};
-use naga_agal::{Filter, SamplerOverride, Wrapping};
+use naga_agal::{Filter, Mipmap, SamplerOverride, Wrapping};
 use ruffle_render::backend::{
-    Context3DTextureFilter, Context3DTriangleFace, Context3DVertexBufferFormat, Context3DWrapMode,
-    Texture,
+    Context3DMipFilter, Context3DTextureFilter, Context3DTriangleFace, Context3DVertexBufferFormat,
+    Context3DWrapMode, Texture,
 };
 
 use wgpu::{
@@ -93,30 +93,45 @@ pub struct BoundTextureData {
     pub id: Rc<dyn Texture>,
     pub view: Rc<TextureView>,
     pub cube: bool,
+    /// Whether this texture must always be sampled with clamping.
+    pub clamp: bool,
 }
 
 impl Hash for BoundTextureData {
     fn hash<H: Hasher>(&self, state: &mut H) {
         // We can't hash 'view', but we can hash the pointer of the 'Rc<dyn Texture>',
         // which is unique to the TextureView
-        let BoundTextureData { id, cube, view: _ } = self;
+        let BoundTextureData {
+            id,
+            cube,
+            clamp,
+            view: _,
+        } = self;
         (Rc::as_ptr(id) as *const ()).hash(state);
         cube.hash(state);
+        clamp.hash(state);
     }
 }
 
 impl PartialEq for BoundTextureData {
     fn eq(&self, other: &Self) -> bool {
-        let BoundTextureData { id, cube, view: _ } = self;
+        let BoundTextureData {
+            id,
+            cube,
+            clamp,
+            view: _,
+        } = self;
         let BoundTextureData {
             id: other_id,
             cube: other_cube,
+            clamp: other_clamp,
             view: _,
         } = other;
         std::ptr::eq(
             Rc::as_ptr(id) as *const (),
             Rc::as_ptr(other_id) as *const (),
         ) && cube == other_cube
+            && clamp == other_clamp
     }
 }
 impl Eq for BoundTextureData {}
@@ -441,10 +456,22 @@ impl CurrentPipeline {
                     data_bytes_per_vertex);
                 }
 
+                let step_mode = match data.buffer.instances_per_element {
+                    0 => wgpu::VertexStepMode::Vertex,
+                    1 => wgpu::VertexStepMode::Instance,
+                    instances_per_element => {
+                        // wgpu can only advance instance data once per instance.
+                        tracing::warn!(
+                            "Vertex buffer with {instances_per_element} instances per element is unsupported, using 1"
+                        );
+                        wgpu::VertexStepMode::Instance
+                    }
+                };
+
                 let attrs = &data.attrs;
                 wgpu::VertexBufferLayout {
                     array_stride: data_bytes_per_vertex,
-                    step_mode: wgpu::VertexStepMode::Vertex,
+                    step_mode,
                     attributes: attrs,
                 }
             })
@@ -512,6 +539,7 @@ impl CurrentPipeline {
         sampler: usize,
         wrap: ruffle_render::backend::Context3DWrapMode,
         filter: ruffle_render::backend::Context3DTextureFilter,
+        mip_filter: Context3DMipFilter,
     ) {
         let sampler_override = SamplerOverride {
             wrapping: match wrap {
@@ -523,10 +551,18 @@ impl CurrentPipeline {
             filter: match filter {
                 Context3DTextureFilter::Linear => Filter::Linear,
                 Context3DTextureFilter::Nearest => Filter::Nearest,
-                _ => unimplemented!(),
+                // FIXME - implement anisotropic filtering. For now, this is
+                // the closest approximation that we support.
+                Context3DTextureFilter::Anisotropic2X
+                | Context3DTextureFilter::Anisotropic4X
+                | Context3DTextureFilter::Anisotropic8X
+                | Context3DTextureFilter::Anisotropic16X => Filter::Linear,
+            },
+            mipmap: match mip_filter {
+                Context3DMipFilter::MipNone => Mipmap::Disable,
+                Context3DMipFilter::MipNearest => Mipmap::Nearest,
+                Context3DMipFilter::MipLinear => Mipmap::Linear,
             },
-            // FIXME - implement this
-            mipmap: naga_agal::Mipmap::Disable,
         };
         if self.sampler_override[sampler] != Some(sampler_override) {
             self.dirty.set(true);
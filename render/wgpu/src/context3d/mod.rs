@@ -2,7 +2,7 @@ use ruffle_render::backend::{
     Context3D, Context3DBlendFactor, Context3DCommand, Context3DCompareMode,
     Context3DTextureFormat, Context3DVertexBufferFormat, IndexBuffer, ProgramType, VertexBuffer,
 };
-use ruffle_render::bitmap::{BitmapFormat, BitmapHandle};
+use ruffle_render::bitmap::{BitmapFormat, BitmapHandle, BitmapInfo};
 use ruffle_render::error::Error;
 use std::borrow::Cow;
 use std::cell::Cell;
@@ -17,7 +17,7 @@ use wgpu::{CommandEncoder, Extent3d, RenderPass};
 
 use crate::context3d::current_pipeline::{BoundTextureData, AGAL_FLOATS_PER_REGISTER};
 use crate::descriptors::Descriptors;
-use crate::Texture;
+use crate::{as_texture, Texture};
 
 use std::num::NonZeroU64;
 use std::rc::Rc;
@@ -198,6 +198,40 @@ impl WgpuContext3D {
         )
     }
 
+    /// Converts an `antiAlias` value from ActionScript into a sample count that we can render with.
+    /// Flash treats `antiAlias` as a quality hint, so we round down to the closest sample count
+    /// that's supported by the device (for both the color format, and our depth format if needed).
+    fn supported_sample_count(
+        &self,
+        anti_alias: u32,
+        format: wgpu::TextureFormat,
+        depth_and_stencil: bool,
+    ) -> u32 {
+        if anti_alias <= 1 {
+            return 1;
+        }
+        let color_features = self
+            .descriptors
+            .adapter
+            .get_texture_format_features(format)
+            .flags;
+        let depth_features = self
+            .descriptors
+            .adapter
+            .get_texture_format_features(wgpu::TextureFormat::Depth24PlusStencil8)
+            .flags;
+
+        // Round down to a power of two, then keep halving until we find something supported.
+        let mut sample_count = 1 << (u32::BITS - 1 - anti_alias.leading_zeros());
+        while sample_count > 1
+            && (!color_features.sample_count_supported(sample_count)
+                || (depth_and_stencil && !depth_features.sample_count_supported(sample_count)))
+        {
+            sample_count /= 2;
+        }
+        sample_count
+    }
+
     // This restores rendering to our normal buffer. It can be triggered explicitly
     // from ActionScript via Context3D.setRenderToBackBuffer(), or automatically
     // when calling Context3D.present()
@@ -235,6 +269,44 @@ impl WgpuContext3D {
         self.clear_color = None;
     }
 
+    /// Submits all buffered commands, including a `clear()` that hasn't been
+    /// followed by a draw call yet, so that the render target can be read from.
+    fn flush(&mut self) {
+        let mut flush_command_encoder =
+            self.descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: create_debug_label!("Flush command encoder").as_deref(),
+                });
+
+        if let (Some(clear_color), Some(view)) =
+            (self.clear_color.take(), &self.current_texture_view)
+        {
+            begin_render_pass(
+                &mut flush_command_encoder,
+                Some(clear_color),
+                view,
+                self.current_texture_resolve_view.as_deref(),
+                self.current_depth_texture_view.as_deref(),
+            );
+        }
+
+        self.buffer_staging_belt.finish();
+        let new_encoder =
+            self.descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: create_debug_label!("Buffer command encoder").as_deref(),
+                });
+        let finished_buffer_command_encoder =
+            std::mem::replace(&mut self.buffer_command_encoder, new_encoder);
+        self.descriptors.queue.submit([
+            finished_buffer_command_encoder.finish(),
+            flush_command_encoder.finish(),
+        ]);
+        self.buffer_staging_belt.recall();
+    }
+
     fn make_render_pass<'a>(
         &'a mut self,
         command_encoder: &'a mut CommandEncoder,
@@ -243,49 +315,13 @@ impl WgpuContext3D {
         // will use a clear color of None. This ensures that by itself,
         // re-creating the render pass has no effect on the output
         let clear_color = self.clear_color.take();
-        let color_load = match clear_color {
-            Some(clear) if clear.mask & COLOR_MASK != 0 => wgpu::LoadOp::Clear(clear.rgb),
-            _ => wgpu::LoadOp::Load,
-        };
-
-        let depth_load = match clear_color {
-            Some(clear) if clear.mask & DEPTH_MASK != 0 => wgpu::LoadOp::Clear(clear.depth),
-            _ => wgpu::LoadOp::Load,
-        };
-
-        let stencil_load = match clear_color {
-            Some(clear) if clear.mask & STENCIL_MASK != 0 => wgpu::LoadOp::Clear(clear.stencil),
-            _ => wgpu::LoadOp::Load,
-        };
-
-        let depth_stencil_attachment = if let Some(depth_view) = &self.current_depth_texture_view {
-            Some(wgpu::RenderPassDepthStencilAttachment {
-                view: depth_view,
-                depth_ops: Some(wgpu::Operations {
-                    load: depth_load,
-                    store: true,
-                }),
-                stencil_ops: Some(wgpu::Operations {
-                    load: stencil_load,
-                    store: true,
-                }),
-            })
-        } else {
-            None
-        };
-
-        let mut pass = command_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Context3D render pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: self.current_texture_view.as_ref().unwrap(),
-                resolve_target: self.current_texture_resolve_view.as_deref(),
-                ops: wgpu::Operations {
-                    load: color_load,
-                    store: true,
-                },
-            })],
-            depth_stencil_attachment,
-        });
+        let mut pass = begin_render_pass(
+            command_encoder,
+            clear_color,
+            self.current_texture_view.as_ref().unwrap(),
+            self.current_texture_resolve_view.as_deref(),
+            self.current_depth_texture_view.as_deref(),
+        );
         pass.set_bind_group(0, self.bind_group.as_ref().unwrap(), &[]);
         pass.set_pipeline(
             self.compiled_pipeline
@@ -339,20 +375,33 @@ pub struct IndexBufferWrapper {
     /// A cpu-side copy of the buffer data. This is used to allow us to
     /// perform unaligned writes to the GPU buffer, which is required by ActionScript.
     pub data: Vec<u8>,
+    pub num_indices: u32,
 }
 
 #[derive(Debug)]
 pub struct VertexBufferWrapper {
     pub buffer: wgpu::Buffer,
+    pub num_vertices: u32,
     pub data_32_per_vertex: u8,
+    /// If non-zero, this buffer holds per-instance data rather than per-vertex data.
+    pub instances_per_element: u32,
 }
 
 pub struct TextureWrapper {
     texture: wgpu::Texture,
 }
 
-impl IndexBuffer for IndexBufferWrapper {}
-impl VertexBuffer for VertexBufferWrapper {}
+impl IndexBuffer for IndexBufferWrapper {
+    fn num_indices(&self) -> u32 {
+        self.num_indices
+    }
+}
+
+impl VertexBuffer for VertexBufferWrapper {
+    fn num_vertices(&self) -> u32 {
+        self.num_vertices
+    }
+}
 impl ruffle_render::backend::Texture for TextureWrapper {}
 
 // Context3D.setVertexBufferAt supports up to 8 vertex buffer attributes
@@ -375,6 +424,16 @@ impl Context3D for WgpuContext3D {
         self.current_texture_view.is_some()
     }
 
+    fn back_buffer(&mut self) -> Option<BitmapInfo> {
+        let size = self.back_buffer_size?;
+        self.flush();
+        Some(BitmapInfo {
+            handle: self.back_buffer_raw_texture_handle.clone(),
+            width: size.width as u16,
+            height: size.height as u16,
+        })
+    }
+
     fn create_index_buffer(
         &mut self,
         _ruffle_usage: ruffle_render::backend::BufferUsage,
@@ -390,6 +449,7 @@ impl Context3D for WgpuContext3D {
         Box::new(IndexBufferWrapper {
             buffer,
             data: vec![0; size as usize],
+            num_indices,
         })
     }
 
@@ -398,6 +458,7 @@ impl Context3D for WgpuContext3D {
         _usage: ruffle_render::backend::BufferUsage,
         num_vertices: u32,
         data_32_per_vertex: u8,
+        instances_per_element: u32,
     ) -> Rc<dyn VertexBuffer> {
         let buffer = self.descriptors.device.create_buffer(&BufferDescriptor {
             label: None,
@@ -408,7 +469,9 @@ impl Context3D for WgpuContext3D {
         });
         Rc::new(VertexBufferWrapper {
             buffer,
+            num_vertices,
             data_32_per_vertex,
+            instances_per_element,
         })
     }
 
@@ -525,12 +588,10 @@ impl Context3D for WgpuContext3D {
                 wants_best_resolution: _,
                 wants_best_resolution_on_browser_zoom: _,
             } => {
-                let mut sample_count = anti_alias;
-                if sample_count == 0 {
-                    sample_count = 1;
-                }
-                let texture_label = create_debug_label!("Render target texture");
                 let format = wgpu::TextureFormat::Rgba8Unorm;
+                let sample_count =
+                    self.supported_sample_count(anti_alias, format, depth_and_stencil);
+                let texture_label = create_debug_label!("Render target texture");
 
                 let make_it = || {
                     // TODO - see if we can deduplicate this with the code in `CommandTarget`
@@ -686,16 +747,20 @@ impl Context3D for WgpuContext3D {
 
                 // Unfortunately, ActionScript works with 2-byte indices, while wgpu requires
                 // copy offsets and sizes to have 4-byte alignment. To support this, we need
-                // to keep a copy of the data on the CPU side. We round *down* the offset to
-                // the closest multiple of 4 bytes, and round *up* the length to the closest
-                // multiple of 4 bytes. We then perform a copy from our CPU-side buffer, which
-                // which uses the existing data (at the beiginning or end) to fill out the copy
-                // to the required length and offset. Without this, we would lose data in the CPU
-                // buffer whenever we performed a copy with an unalignd offset or length.
+                // to keep a copy of the data on the CPU side. We round *down* the start of the
+                // updated range to the closest multiple of 4 bytes, and round *up* the end of it.
+                // We then perform a copy from our CPU-side buffer, which uses the existing data
+                // (at the beginning or end) to fill out the copy to the required length and offset.
+                // Without this, we would lose data in the CPU buffer whenever we performed a copy
+                // with an unaligned offset or length.
+                //
+                // Only the updated range is written, so streaming a few indices into a large
+                // buffer doesn't require re-uploading the whole thing.
                 let offset_bytes = start_offset * std::mem::size_of::<u16>();
                 let rounded_down_offset =
                     offset_bytes - (offset_bytes % COPY_BUFFER_ALIGNMENT as usize);
-                let rounded_up_length = align_copy_buffer_size(data.len());
+                let rounded_up_end = align_copy_buffer_size(offset_bytes + data.len());
+                let rounded_up_length = rounded_up_end - rounded_down_offset;
 
                 buffer.data[offset_bytes..(offset_bytes + data.len())].copy_from_slice(&data);
                 self.buffer_staging_belt
@@ -706,10 +771,7 @@ impl Context3D for WgpuContext3D {
                         NonZeroU64::new(rounded_up_length as u64).unwrap(),
                         &self.descriptors.device,
                     )
-                    .copy_from_slice(
-                        &buffer.data
-                            [rounded_down_offset..(rounded_down_offset + rounded_up_length)],
-                    );
+                    .copy_from_slice(&buffer.data[rounded_down_offset..rounded_up_end]);
             }
 
             Context3DCommand::UploadToVertexBuffer {
@@ -747,12 +809,12 @@ impl Context3D for WgpuContext3D {
                 anti_alias,
                 surface_selector: _,
             } => {
-                let mut sample_count = anti_alias;
-                if sample_count == 0 {
-                    sample_count = 1;
-                }
-
                 let texture_wrapper = texture.as_any().downcast_ref::<TextureWrapper>().unwrap();
+                let sample_count = self.supported_sample_count(
+                    anti_alias,
+                    texture_wrapper.texture.format(),
+                    enable_depth_and_stencil,
+                );
                 self.current_texture_size = Some(Extent3d {
                     width: texture_wrapper.texture.width(),
                     height: texture_wrapper.texture.height(),
@@ -818,6 +880,7 @@ impl Context3D for WgpuContext3D {
                 index_buffer,
                 first_index,
                 num_triangles,
+                num_instances,
             } => {
                 let index_buffer: &IndexBufferWrapper = index_buffer
                     .as_any()
@@ -864,7 +927,7 @@ impl Context3D for WgpuContext3D {
 
                 render_pass
                     .set_index_buffer(index_buffer.buffer.slice(..), wgpu::IndexFormat::Uint16);
-                render_pass.draw_indexed(indices, 0, 0..1);
+                render_pass.draw_indexed(indices, 0, 0..num_instances);
 
                 // A `RenderPass` needs to hold references to several fields in `self`, so we can't
                 // easily re-use it across multiple `DrawTriangles` calls.
@@ -1031,10 +1094,27 @@ impl Context3D for WgpuContext3D {
                     },
                 );
             }
+            Context3DCommand::CopyBitmapHandleToTexture { source, dest } => {
+                let source = &as_texture(&source).texture;
+                let dest = dest.as_any().downcast_ref::<TextureWrapper>().unwrap();
+
+                // The bitmap may be a different size than the texture (e.g. a video
+                // that changed resolution), so only copy the overlapping region.
+                self.buffer_command_encoder.copy_texture_to_texture(
+                    source.as_image_copy(),
+                    dest.texture.as_image_copy(),
+                    wgpu::Extent3d {
+                        width: source.width().min(dest.texture.width()),
+                        height: source.height().min(dest.texture.height()),
+                        depth_or_array_layers: 1,
+                    },
+                );
+            }
             Context3DCommand::SetTextureAt {
                 sampler,
                 texture,
                 cube,
+                clamp,
             } => {
                 let bound_texture = if let Some(texture) = texture {
                     let texture_wrapper =
@@ -1050,6 +1130,7 @@ impl Context3D for WgpuContext3D {
                         id: texture.clone(),
                         view: Rc::new(texture_wrapper.texture.create_view(&view)),
                         cube,
+                        clamp,
                     })
                 } else {
                     None
@@ -1154,9 +1235,14 @@ impl Context3D for WgpuContext3D {
                 sampler,
                 wrap,
                 filter,
+                mip_filter,
             } => {
-                self.current_pipeline
-                    .update_sampler_state_at(sampler as usize, wrap, filter);
+                self.current_pipeline.update_sampler_state_at(
+                    sampler as usize,
+                    wrap,
+                    filter,
+                    mip_filter,
+                );
             }
             Context3DCommand::SetScissorRectangle { rect } => {
                 self.scissor_rectangle = rect;
@@ -1173,6 +1259,56 @@ pub struct ClearColor {
     mask: u32,
 }
 
+/// Begins a render pass on the given views, applying any pending `clear()`.
+fn begin_render_pass<'a>(
+    command_encoder: &'a mut CommandEncoder,
+    clear_color: Option<ClearColor>,
+    view: &'a TextureView,
+    resolve_target: Option<&'a TextureView>,
+    depth_view: Option<&'a TextureView>,
+) -> RenderPass<'a> {
+    let color_load = match clear_color {
+        Some(clear) if clear.mask & COLOR_MASK != 0 => wgpu::LoadOp::Clear(clear.rgb),
+        _ => wgpu::LoadOp::Load,
+    };
+
+    let depth_load = match clear_color {
+        Some(clear) if clear.mask & DEPTH_MASK != 0 => wgpu::LoadOp::Clear(clear.depth),
+        _ => wgpu::LoadOp::Load,
+    };
+
+    let stencil_load = match clear_color {
+        Some(clear) if clear.mask & STENCIL_MASK != 0 => wgpu::LoadOp::Clear(clear.stencil),
+        _ => wgpu::LoadOp::Load,
+    };
+
+    let depth_stencil_attachment =
+        depth_view.map(|depth_view| wgpu::RenderPassDepthStencilAttachment {
+            view: depth_view,
+            depth_ops: Some(wgpu::Operations {
+                load: depth_load,
+                store: true,
+            }),
+            stencil_ops: Some(wgpu::Operations {
+                load: stencil_load,
+                store: true,
+            }),
+        });
+
+    command_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Context3D render pass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view,
+            resolve_target,
+            ops: wgpu::Operations {
+                load: color_load,
+                store: true,
+            },
+        })],
+        depth_stencil_attachment,
+    })
+}
+
 fn convert_texture_format(input: Context3DTextureFormat) -> Result<wgpu::TextureFormat, Error> {
     match input {
         // All of these formats are unsupported by wgpu to various degrees:
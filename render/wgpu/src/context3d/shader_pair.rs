@@ -55,10 +55,17 @@ impl ShaderPairAgal {
         RefMut::map(compiled, |compiled| {
             // TODO: Figure out a way to avoid the clone when we have a cache hit
             compiled.get_or_insert_mut(data.clone(), || {
+                let clamped_samplers = std::array::from_fn(|i| {
+                    data.bound_textures[i]
+                        .as_ref()
+                        .map_or(false, |texture| texture.clamp)
+                });
+
                 let vertex_naga_module = naga_agal::agal_to_naga(
                     &self.vertex_bytecode,
                     &data.vertex_attributes,
                     &data.sampler_overrides,
+                    &clamped_samplers,
                 )
                 .unwrap();
                 let vertex_module =
@@ -73,6 +80,7 @@ impl ShaderPairAgal {
                     &self.fragment_bytecode,
                     &data.vertex_attributes,
                     &data.sampler_overrides,
+                    &clamped_samplers,
                 )
                 .unwrap();
                 let fragment_module =
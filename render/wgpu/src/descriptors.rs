@@ -80,6 +80,12 @@ impl Descriptors {
         }
     }
 
+    /// Sets the blur size (in pixels) above which blur-based filters are performed at a reduced
+    /// resolution, trading a little accuracy for speed. `None` always blurs at full resolution.
+    pub fn set_blur_downsample_threshold(&mut self, threshold: Option<f32>) {
+        self.filters.blur.set_downsample_threshold(threshold);
+    }
+
     pub fn copy_srgb_pipeline(
         &self,
         format: wgpu::TextureFormat,
@@ -18,6 +18,9 @@ const PASS_SCALES: [f32; 15] = [
     1.0, 2.1, 2.7, 3.1, 3.5, 3.8, 4.0, 4.2, 4.4, 4.6, 5.0, 6.0, 6.0, 7.0, 7.0,
 ];
 
+/// The most we'll ever shrink a texture by before blurring it.
+const MAX_DOWNSAMPLE_SCALE: u32 = 4;
+
 /// This is a 1:1 match of of `struct Filter` in `blur.wgsl`. See that, and the usage below, for more info.
 /// Since WebGL requires 16 byte struct size (alignment), some of these fields (namely m2 and last_weight)
 /// are passed in precomputed, even though they are trivial to get (addition/multiplication by constant).
@@ -34,10 +37,24 @@ struct BlurUniform {
     last_weight: f32,
 }
 
+impl BlurUniform {
+    /// A "blur" that samples exactly one (bilinearly filtered) point, used to resize a texture.
+    const PASSTHROUGH: BlurUniform = BlurUniform {
+        direction: [0.0, 0.0],
+        full_size: 1.0,
+        m: 0.0,
+        m2: 0.0,
+        first_weight: 0.0,
+        last_offset: 0.0,
+        last_weight: 1.0,
+    };
+}
+
 pub struct BlurFilter {
     bind_group_layout: wgpu::BindGroupLayout,
     pipeline_layout: wgpu::PipelineLayout,
     pipelines: SampleCountMap<OnceLock<wgpu::RenderPipeline>>,
+    downsample_threshold: Option<f32>,
 }
 
 impl BlurFilter {
@@ -102,6 +119,7 @@ impl BlurFilter {
             pipelines: Default::default(),
             pipeline_layout,
             bind_group_layout,
+            downsample_threshold: None,
         }
     }
 
@@ -170,13 +188,21 @@ impl BlurFilter {
         let sample_count = source.texture.sample_count();
         let format = source.texture.format();
         let pipeline = self.pipeline(descriptors, sample_count);
+        let vertices = source.vertices(&descriptors.device);
+        let source_view = source.texture.create_view(&Default::default());
+
+        let scale = self.downsample_scale(filter, source.size);
+        let blur_size = (
+            (source.size.0 + scale - 1) / scale,
+            (source.size.1 + scale - 1) / scale,
+        );
 
         let mut flip = CommandTarget::new(
             descriptors,
             texture_pool,
             wgpu::Extent3d {
-                width: source.size.0,
-                height: source.size.1,
+                width: blur_size.0,
+                height: blur_size.1,
                 depth_or_array_layers: 1,
             },
             format,
@@ -185,6 +211,79 @@ impl BlurFilter {
             draw_encoder,
         );
         let mut flop = CommandTarget::new(
+            descriptors,
+            texture_pool,
+            wgpu::Extent3d {
+                width: blur_size.0,
+                height: blur_size.1,
+                depth_or_array_layers: 1,
+            },
+            format,
+            sample_count,
+            RenderTargetMode::FreshWithColor(wgpu::Color::TRANSPARENT),
+            draw_encoder,
+        );
+
+        if scale == 1 {
+            let blurred = self.blur_passes(
+                descriptors,
+                draw_encoder,
+                pipeline,
+                filter,
+                1.0,
+                (&source_view, vertices.slice(..)),
+                (source.texture.width(), source.texture.height()),
+                &mut flip,
+                &mut flop,
+            );
+            // If nothing happened, don't return an empty unused texture
+            return blurred.then_some(flip);
+        }
+
+        // Large blurs are performed on a downsampled copy of the source, and then scaled back up.
+        // The result is slightly softer than a full resolution blur, but it's hard to tell apart
+        // when the blur is this large - and it's significantly cheaper.
+        let mut downsampled = CommandTarget::new(
+            descriptors,
+            texture_pool,
+            wgpu::Extent3d {
+                width: blur_size.0,
+                height: blur_size.1,
+                depth_or_array_layers: 1,
+            },
+            format,
+            sample_count,
+            RenderTargetMode::FreshWithColor(wgpu::Color::TRANSPARENT),
+            draw_encoder,
+        );
+        self.render(
+            descriptors,
+            draw_encoder,
+            pipeline,
+            &mut downsampled,
+            &source_view,
+            vertices.slice(..),
+            BlurUniform::PASSTHROUGH,
+        );
+        let blurred = self.blur_passes(
+            descriptors,
+            draw_encoder,
+            pipeline,
+            filter,
+            scale as f32,
+            (
+                downsampled.color_view(),
+                descriptors.quad.filter_vertices.slice(..),
+            ),
+            (blur_size.0, blur_size.1),
+            &mut flip,
+            &mut flop,
+        );
+        if !blurred {
+            return None;
+        }
+
+        let mut target = CommandTarget::new(
             descriptors,
             texture_pool,
             wgpu::Extent3d {
@@ -197,10 +296,63 @@ impl BlurFilter {
             RenderTargetMode::FreshWithColor(wgpu::Color::TRANSPARENT),
             draw_encoder,
         );
+        self.render(
+            descriptors,
+            draw_encoder,
+            pipeline,
+            &mut target,
+            flip.color_view(),
+            descriptors.quad.filter_vertices.slice(..),
+            BlurUniform::PASSTHROUGH,
+        );
+        Some(target)
+    }
 
-        let vertices = source.vertices(&descriptors.device);
+    /// Sets the blur size (in pixels) above which a blur will be performed at a reduced resolution.
+    /// `None` disables downsampling entirely, and every blur is performed at full resolution.
+    pub fn set_downsample_threshold(&mut self, threshold: Option<f32>) {
+        self.downsample_threshold = threshold;
+    }
 
-        let source_view = source.texture.create_view(&Default::default());
+    /// Calculates how much the source should be shrunk by before blurring it.
+    /// This is always a power of two, and is 1 if no downsampling should take place.
+    fn downsample_scale(&self, filter: &BlurFilterArgs, size: (u32, u32)) -> u32 {
+        let Some(threshold) = self.downsample_threshold else {
+            return 1;
+        };
+        let strength = filter
+            .blur_x
+            .to_f32()
+            .max(filter.blur_y.to_f32())
+            .min(255.0);
+        let mut scale = 1;
+        while scale < MAX_DOWNSAMPLE_SCALE
+            && strength / scale as f32 > threshold
+            && size.0 >= scale * 2
+            && size.1 >= scale * 2
+        {
+            scale *= 2;
+        }
+        scale
+    }
+
+    /// Performs every blur pass of the filter, ping-ponging between `flip` and `flop`.
+    /// The end result will be in `flip`. Returns `false` if no passes were performed.
+    ///
+    /// `scale` is how much the input has been shrunk by, and the blur sizes are divided by it.
+    #[allow(clippy::too_many_arguments)]
+    fn blur_passes(
+        &self,
+        descriptors: &Descriptors,
+        draw_encoder: &mut CommandEncoder,
+        pipeline: &RenderPipeline,
+        filter: &BlurFilterArgs,
+        scale: f32,
+        (input_view, input_vertices): (&TextureView, BufferSlice),
+        (input_width, input_height): (u32, u32),
+        flip: &mut CommandTarget,
+        flop: &mut CommandTarget,
+    ) -> bool {
         let mut first = true;
         for _ in 0..(filter.num_passes() as usize) {
             for i in 0..2 {
@@ -210,20 +362,20 @@ impl BlurFilter {
                 } else {
                     filter.blur_y.to_f32()
                 };
-                // Full width of the kernel (left edge to right edge)
-                let full_size = strength.min(255.0);
-                if full_size <= 1.0 {
+                if strength.min(255.0) <= 1.0 {
                     // A width of 1 or less is a noop (it'd just sample itself and nothing else)
                     continue;
                 }
+                // Full width of the kernel (left edge to right edge)
+                let full_size = (strength.min(255.0) / scale).max(1.0);
 
                 let (previous_view, previous_vertices, previous_width, previous_height) = if first {
                     first = false;
                     (
-                        &source_view,
-                        vertices.slice(..),
-                        source.texture.width() as f32,
-                        source.texture.height() as f32,
+                        input_view,
+                        input_vertices,
+                        input_width as f32,
+                        input_height as f32,
                     )
                 } else {
                     (
@@ -274,37 +426,53 @@ impl BlurFilter {
                     last_weight,
                 };
 
-                if descriptors.limits.max_push_constant_size > 0 {
-                    self.render_with_push_constants(
-                        descriptors,
-                        draw_encoder,
-                        pipeline,
-                        &mut flop,
-                        previous_view,
-                        previous_vertices,
-                        uniform,
-                    );
-                } else {
-                    self.render_with_uniform_buffers(
-                        descriptors,
-                        draw_encoder,
-                        pipeline,
-                        &mut flop,
-                        previous_view,
-                        previous_vertices,
-                        uniform,
-                    );
-                }
+                self.render(
+                    descriptors,
+                    draw_encoder,
+                    pipeline,
+                    flop,
+                    previous_view,
+                    previous_vertices,
+                    uniform,
+                );
 
-                std::mem::swap(&mut flip, &mut flop);
+                std::mem::swap(flip, flop);
             }
         }
+        !first
+    }
 
-        if first {
-            // Nothing happened, don't return an empty unused texture
-            None
+    #[allow(clippy::too_many_arguments)]
+    fn render(
+        &self,
+        descriptors: &Descriptors,
+        draw_encoder: &mut CommandEncoder,
+        pipeline: &RenderPipeline,
+        destination: &mut CommandTarget,
+        source: &TextureView,
+        vertices: BufferSlice,
+        uniform: BlurUniform,
+    ) {
+        if descriptors.limits.max_push_constant_size > 0 {
+            self.render_with_push_constants(
+                descriptors,
+                draw_encoder,
+                pipeline,
+                destination,
+                source,
+                vertices,
+                uniform,
+            );
         } else {
-            Some(flip)
+            self.render_with_uniform_buffers(
+                descriptors,
+                draw_encoder,
+                pipeline,
+                destination,
+                source,
+                vertices,
+                uniform,
+            );
         }
     }
 
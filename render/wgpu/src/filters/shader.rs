@@ -12,6 +12,7 @@ use crate::{
 };
 
 use super::FilterSource;
+use swf::Rectangle;
 
 /// All of the data is stored in the `ShaderFilterArgs`
 #[derive(Default)]
@@ -22,6 +23,20 @@ impl ShaderFilter {
         Self
     }
 
+    pub fn calculate_dest_rect(
+        &self,
+        filter: &ShaderFilterArgs,
+        source_rect: Rectangle<i32>,
+    ) -> Rectangle<i32> {
+        // The extensions grow the area the shader is run over, letting it draw outside of the source.
+        Rectangle {
+            x_min: source_rect.x_min - filter.left_extension.max(0),
+            x_max: source_rect.x_max + filter.right_extension.max(0),
+            y_min: source_rect.y_min - filter.top_extension.max(0),
+            y_max: source_rect.y_max + filter.bottom_extension.max(0),
+        }
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn apply<'a>(
         &self,
@@ -30,7 +45,7 @@ impl ShaderFilter {
         draw_encoder: &mut wgpu::CommandEncoder,
         source: &FilterSource<'a>,
         mut filter: ShaderFilterArgs<'a>,
-    ) -> CommandTarget {
+    ) -> Option<CommandTarget> {
         let sample_count = source.texture.sample_count();
         let format = source.texture.format();
 
@@ -56,7 +71,7 @@ impl ShaderFilter {
             }
         }
 
-        run_pixelbender_shader_impl(
+        if let Err(e) = run_pixelbender_shader_impl(
             descriptors,
             filter.shader,
             ShaderMode::Filter,
@@ -66,8 +81,11 @@ impl ShaderFilter {
             target.color_attachments(),
             target.sample_count(),
             source,
-        )
-        .expect("Failed to run pixelbender shader");
-        target
+        ) {
+            // Flash silently skips shaders that it can't run, so we shouldn't bring down the player
+            tracing::warn!("Failed to run pixelbender shader filter: {e:?}");
+            return None;
+        }
+        Some(target)
     }
 }
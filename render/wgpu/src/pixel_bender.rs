@@ -33,6 +33,7 @@ pub struct PixelBenderWgpuShader {
     bind_group_layout: wgpu::BindGroupLayout,
     pipeline_layout: PipelineLayout,
     pipelines: SampleCountMap<OnceLock<RenderPipeline>>,
+    float_pipeline: OnceLock<RenderPipeline>,
     vertex_shader: wgpu::ShaderModule,
     fragment_shader: wgpu::ShaderModule,
     shader: PixelBenderShader,
@@ -48,44 +49,68 @@ impl PixelBenderWgpuShader {
     /// Gets a `RenderPipeline` for the specified sample count
     fn get_pipeline(&self, descriptors: &Descriptors, samples: u32) -> &wgpu::RenderPipeline {
         self.pipelines.get_or_init(samples, || {
-            descriptors
-                .device
-                .create_render_pipeline(&RenderPipelineDescriptor {
-                    label: create_debug_label!("PixelBender shader pipeline").as_deref(),
-                    layout: Some(&self.pipeline_layout),
-                    vertex: VertexState {
-                        module: &self.vertex_shader,
-                        entry_point: naga_pixelbender::VERTEX_SHADER_ENTRYPOINT,
-                        buffers: &VERTEX_BUFFERS_DESCRIPTION_FILTERS,
-                    },
-                    fragment: Some(wgpu::FragmentState {
-                        module: &self.fragment_shader,
-                        entry_point: naga_pixelbender::FRAGMENT_SHADER_ENTRYPOINT,
-                        targets: &[Some(ColorTargetState {
-                            format: TextureFormat::Rgba8Unorm,
-                            // FIXME - what should this be?
-                            blend: Some(wgpu::BlendState {
-                                color: BlendComponent::OVER,
-                                alpha: BlendComponent::OVER,
-                            }),
-                            write_mask: ColorWrites::all(),
-                        })],
-                    }),
-                    primitive: wgpu::PrimitiveState {
-                        front_face: FrontFace::Ccw,
-                        cull_mode: None,
-                        ..Default::default()
-                    },
-                    depth_stencil: None,
-                    multisample: wgpu::MultisampleState {
-                        count: samples,
-                        mask: !0,
-                        alpha_to_coverage_enabled: false,
-                    },
-                    multiview: Default::default(),
-                })
+            self.create_pipeline(
+                descriptors,
+                TextureFormat::Rgba8Unorm,
+                // FIXME - what should this be?
+                Some(wgpu::BlendState {
+                    color: BlendComponent::OVER,
+                    alpha: BlendComponent::OVER,
+                }),
+                samples,
+            )
+        })
+    }
+
+    /// Gets a `RenderPipeline` that writes unclamped floats to a `Rgba32Float` target.
+    /// This is used when a `ShaderJob` targets a `ByteArray` or `Vector.<Number>`.
+    fn get_float_pipeline(&self, descriptors: &Descriptors) -> &wgpu::RenderPipeline {
+        self.float_pipeline.get_or_init(|| {
+            // Float targets aren't blendable, so the shader output is written as-is.
+            self.create_pipeline(descriptors, TextureFormat::Rgba32Float, None, 1)
         })
     }
+
+    fn create_pipeline(
+        &self,
+        descriptors: &Descriptors,
+        format: TextureFormat,
+        blend: Option<wgpu::BlendState>,
+        samples: u32,
+    ) -> wgpu::RenderPipeline {
+        descriptors
+            .device
+            .create_render_pipeline(&RenderPipelineDescriptor {
+                label: create_debug_label!("PixelBender shader pipeline").as_deref(),
+                layout: Some(&self.pipeline_layout),
+                vertex: VertexState {
+                    module: &self.vertex_shader,
+                    entry_point: naga_pixelbender::VERTEX_SHADER_ENTRYPOINT,
+                    buffers: &VERTEX_BUFFERS_DESCRIPTION_FILTERS,
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &self.fragment_shader,
+                    entry_point: naga_pixelbender::FRAGMENT_SHADER_ENTRYPOINT,
+                    targets: &[Some(ColorTargetState {
+                        format,
+                        blend,
+                        write_mask: ColorWrites::all(),
+                    })],
+                }),
+                primitive: wgpu::PrimitiveState {
+                    front_face: FrontFace::Ccw,
+                    cull_mode: None,
+                    ..Default::default()
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState {
+                    count: samples,
+                    mask: !0,
+                    alpha_to_coverage_enabled: false,
+                },
+                multiview: Default::default(),
+            })
+    }
 }
 
 impl PixelBenderShaderImpl for PixelBenderWgpuShader {
@@ -240,6 +265,7 @@ impl PixelBenderWgpuShader {
             bind_group_layout,
             pipeline_layout,
             pipelines: Default::default(),
+            float_pipeline: OnceLock::new(),
             shader,
             vertex_shader,
             fragment_shader,
@@ -552,7 +578,11 @@ pub(super) fn run_pixelbender_shader_impl(
 
     let vertices = source.vertices(&descriptors.device);
 
-    let pipeline = compiled_shader.get_pipeline(descriptors, sample_count);
+    let pipeline = if target.format() == TextureFormat::Rgba32Float {
+        compiled_shader.get_float_pipeline(descriptors)
+    } else {
+        compiled_shader.get_pipeline(descriptors, sample_count)
+    };
 
     let mut render_pass = render_command_encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
         label: Some("PixelBender render pass"),
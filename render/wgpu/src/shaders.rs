@@ -25,7 +25,10 @@ pub struct Shaders {
     pub blur_filter: wgpu::ShaderModule,
     pub glow_filter: wgpu::ShaderModule,
     pub bevel_filter: wgpu::ShaderModule,
+    pub gradient_glow_filter: wgpu::ShaderModule,
+    pub gradient_bevel_filter: wgpu::ShaderModule,
     pub displacement_map_filter: wgpu::ShaderModule,
+    pub convolution_filter: wgpu::ShaderModule,
 }
 
 impl Shaders {
@@ -104,6 +107,20 @@ impl Shaders {
             "filter/bevel.wgsl",
             include_str!("../shaders/filter/bevel.wgsl"),
         );
+        let gradient_glow_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/gradient_glow.wgsl",
+            include_str!("../shaders/filter/gradient_glow.wgsl"),
+        );
+        let gradient_bevel_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/gradient_bevel.wgsl",
+            include_str!("../shaders/filter/gradient_bevel.wgsl"),
+        );
         let displacement_map_filter = make_shader(
             device,
             &mut composer,
@@ -111,6 +128,13 @@ impl Shaders {
             "filter/displacement_map.wgsl",
             include_str!("../shaders/filter/displacement_map.wgsl"),
         );
+        let convolution_filter = make_shader(
+            device,
+            &mut composer,
+            &shader_defs,
+            "filter/convolution.wgsl",
+            include_str!("../shaders/filter/convolution.wgsl"),
+        );
         let gradient_shader = make_shader(
             device,
             &mut composer,
@@ -129,6 +153,7 @@ impl Shaders {
             ComplexBlend::Erase => make_shader(device, &mut composer, &shader_defs, "blend/erase.wgsl", include_str!("../shaders/blend/erase.wgsl")),
             ComplexBlend::Overlay => make_shader(device, &mut composer, &shader_defs, "blend/overlay.wgsl", include_str!("../shaders/blend/overlay.wgsl")),
             ComplexBlend::HardLight => make_shader(device, &mut composer, &shader_defs, "blend/hardlight.wgsl", include_str!("../shaders/blend/hardlight.wgsl")),
+            ComplexBlend::AlphaMask => make_shader(device, &mut composer, &shader_defs, "blend/alpha_mask.wgsl", include_str!("../shaders/blend/alpha_mask.wgsl")),
         };
 
         Self {
@@ -143,7 +168,10 @@ impl Shaders {
             blur_filter,
             glow_filter,
             bevel_filter,
+            gradient_glow_filter,
+            gradient_bevel_filter,
             displacement_map_filter,
+            convolution_filter,
         }
     }
 }
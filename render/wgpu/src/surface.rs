@@ -116,7 +116,7 @@ impl Surface {
         color_buffers: &'frame mut UniformBuffer<'global, ColorAdjustments>,
         uniform_encoder: &'frame mut wgpu::CommandEncoder,
         draw_encoder: &'frame mut wgpu::CommandEncoder,
-        nearest_layer: LayerRef<'frame>,
+        nearest_layer: LayerRef,
         texture_pool: &mut TexturePool,
     ) -> CommandTarget {
         let target = CommandTarget::new(
@@ -143,7 +143,7 @@ impl Surface {
             target.width(),
             target.height(),
             match nearest_layer {
-                LayerRef::Current => LayerRef::Parent(&target),
+                LayerRef::Current => LayerRef::Parent,
                 layer => layer,
             },
             texture_pool,
@@ -223,22 +223,18 @@ impl Surface {
                     .expect("Failed to run PixelBender blend mode");
                 }
                 Chunk::Blend(texture, ChunkBlendMode::Complex(blend_mode), needs_depth) => {
-                    let parent = match blend_mode {
-                        ComplexBlend::Alpha | ComplexBlend::Erase => {
-                            match nearest_layer {
-                                LayerRef::None => {
-                                    // An Alpha or Erase with no Layer above it should be ignored
-                                    continue;
-                                }
-                                LayerRef::Current => &target,
-                                LayerRef::Parent(layer) => layer,
-                            }
-                        }
-                        _ => &target,
-                    };
+                    if matches!(blend_mode, ComplexBlend::Alpha | ComplexBlend::Erase)
+                        && nearest_layer == LayerRef::None
+                    {
+                        // An Alpha or Erase with no Layer above it should be ignored
+                        continue;
+                    }
 
+                    // Every blend group is rendered in isolation, so Alpha and Erase apply to
+                    // what has been drawn into this target so far - the layer itself, or a group
+                    // inside of it that will later be composited onto the layer.
                     let parent_blend_buffer =
-                        parent.update_blend_buffer(descriptors, texture_pool, draw_encoder);
+                        target.update_blend_buffer(descriptors, texture_pool, draw_encoder);
 
                     let blend_bind_group =
                         descriptors
@@ -3,7 +3,6 @@ use crate::blend::TrivialBlend;
 use crate::blend::{BlendType, ComplexBlend};
 use crate::buffer_pool::TexturePool;
 use crate::mesh::{as_mesh, DrawType, Mesh};
-use crate::surface::target::CommandTarget;
 use crate::surface::Surface;
 use crate::{
     as_texture, ColorAdjustments, Descriptors, MaskState, Pipelines, PushConstants, Transforms,
@@ -455,11 +454,20 @@ pub enum DrawCommand {
     PopMask,
 }
 
-#[derive(Copy, Clone)]
-pub enum LayerRef<'a> {
+/// The nearest `BlendMode.LAYER` group that encloses the commands being drawn.
+///
+/// `Alpha` and `Erase` blends only have an effect inside such a group, where they are
+/// applied to the contents of the offscreen target that they're drawn into.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LayerRef {
+    /// There is no enclosing layer.
     None,
+
+    /// The target being drawn into is itself the layer.
     Current,
-    Parent(&'a CommandTarget),
+
+    /// The target being drawn into is an isolated group inside of a layer.
+    Parent,
 }
 
 /// Replaces every blend with a RenderBitmap, with the subcommands rendered out to a temporary texture
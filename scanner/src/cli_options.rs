@@ -35,6 +35,14 @@ pub struct ScanOpt {
     /// Filenames to ignore
     #[clap(short = 'i', long = "ignore", action = clap::ArgAction::Append)]
     pub ignore: Vec<String>,
+
+    /// How many frames of each SWF to run
+    #[clap(short = 'f', long = "frames", default_value = "1")]
+    pub frames: u32,
+
+    /// Also store the content facts of each SWF in this file, as JSON Lines
+    #[clap(long = "facts")]
+    pub facts_path: Option<PathBuf>,
 }
 
 #[derive(Parser, Debug)]
@@ -49,4 +57,12 @@ pub struct ExecuteReportOpt {
     /// The single SWF file to parse and run
     #[clap(name = "file")]
     pub input_path: PathBuf,
+
+    /// How many frames of the SWF to run
+    #[clap(short = 'f', long = "frames", default_value = "1")]
+    pub frames: u32,
+
+    /// Also store the content facts of the SWF in this file, as JSON
+    #[clap(long = "facts")]
+    pub facts_path: Option<PathBuf>,
 }
@@ -1,6 +1,7 @@
 //! Child/executor process impls
 
 use crate::cli_options::ExecuteReportOpt;
+use crate::facts::{count_tags, ContentFacts};
 use crate::file_results::{AvmType, FileResults, Step};
 use crate::logging::{ScanLogBackend, ThreadLocalScanLogger, LOCAL_LOGGER};
 use ruffle_core::backend::navigator::{NullExecutor, NullNavigatorBackend};
@@ -9,12 +10,17 @@ use ruffle_core::swf::{decompress_swf, parse_swf};
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::PlayerBuilder;
 use sha2::{Digest, Sha256};
-use std::io::{stdout, Write};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{stdout, BufWriter, Write};
 use std::panic::catch_unwind;
 use std::path::Path;
 use std::time::{Duration, Instant};
 
-fn execute_swf(file: &Path) {
+/// The built-in APIs and stubs that running a SWF hit, by name.
+type ExecutionTally = (BTreeMap<String, u32>, BTreeMap<String, u32>);
+
+fn execute_swf(file: &Path, frames: u32) -> ExecutionTally {
     let base_path = file.parent().unwrap();
     let executor = NullExecutor::new();
     let movie = SwfMovie::from_path(file, None).unwrap();
@@ -26,11 +32,27 @@ fn execute_swf(file: &Path) {
         .with_movie(movie)
         .build();
 
-    player.lock().unwrap().preload(&mut ExecutionLimit::none());
+    let mut player = player.lock().unwrap();
+    player.set_api_usage_tracking(true);
+    player.preload(&mut ExecutionLimit::none());
 
-    player.lock().unwrap().run_frame();
-    player.lock().unwrap().update_timers(frame_time);
+    for _ in 0..frames {
+        player.run_frame();
+        player.update_timers(frame_time);
+    }
     //executor.poll_all().unwrap();
+
+    let apis = player
+        .api_usage()
+        .iter()
+        .map(|(name, count)| (name.to_string(), count))
+        .collect();
+    let stubs = player
+        .encountered_stubs()
+        .counts()
+        .map(|(stub, count)| (stub.to_string(), count))
+        .collect();
+    (apis, stubs)
 }
 
 fn checkpoint<W: Write>(
@@ -56,19 +78,38 @@ fn checkpoint<W: Write>(
 pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), std::io::Error> {
     ThreadLocalScanLogger::init();
 
-    let start = Instant::now();
-    let file_path = execute_report_opt.input_path;
-    let name = file_path
+    let name = execute_report_opt
+        .input_path
         .file_name()
         .expect("Valid file name in input path")
         .to_string_lossy()
         .into_owned();
+    let mut facts = ContentFacts::new(&name);
+
+    let result = execute_report(&execute_report_opt, &name, &mut facts);
+
+    // The facts are written even if the report stopped early, as far as they got.
+    if let Some(facts_path) = &execute_report_opt.facts_path {
+        let file = BufWriter::new(File::create(facts_path)?);
+        serde_json::to_writer(file, &facts)?;
+    }
+
+    result
+}
+
+fn execute_report(
+    execute_report_opt: &ExecuteReportOpt,
+    name: &str,
+    facts: &mut ContentFacts,
+) -> Result<(), std::io::Error> {
+    let start = Instant::now();
+    let file_path = &execute_report_opt.input_path;
 
     LOCAL_LOGGER.with(|log_buffer| {
         log_buffer.borrow_mut().truncate(0);
     });
 
-    let mut file_result = FileResults::new(&name);
+    let mut file_result = FileResults::new(name);
 
     let stdout = stdout();
     let mut writer = csv::Writer::from_writer(stdout.lock());
@@ -76,7 +117,7 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
 
     file_result.progress = Step::Read;
 
-    let data = match std::fs::read(&file_path) {
+    let data = match std::fs::read(file_path) {
         Ok(data) => data,
         Err(e) => {
             file_result.error = Some(format!("File error: {e}"));
@@ -92,6 +133,11 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
     hash.update(&data[..]);
 
     file_result.hash = hash.finalize().to_vec();
+    facts.hash = file_result
+        .hash
+        .iter()
+        .map(|b| format!("{b:02X}"))
+        .collect();
     checkpoint(&mut file_result, &start, &mut writer)?;
 
     file_result.progress = Step::Decompress;
@@ -106,6 +152,8 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
         }
     };
 
+    count_tags(&swf_buf.data, swf_buf.header.version(), &mut facts.tags);
+
     checkpoint(&mut file_result, &start, &mut writer)?;
     file_result.progress = Step::Parse;
 
@@ -128,6 +176,8 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
                     true => AvmType::Avm2,
                     false => AvmType::Avm1,
                 });
+                facts.version = file_result.version;
+                facts.vm_type = file_result.vm_type.clone();
             }
             Err(e) => {
                 file_result.error = Some(format!("Parse error: {e}"));
@@ -149,9 +199,15 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
     checkpoint(&mut file_result, &start, &mut writer)?;
     file_result.progress = Step::Execute;
 
-    //Run one frame of the movie in Ruffle.
-    if let Err(e) = catch_unwind(|| execute_swf(&file_path)) {
-        match e.downcast::<String>() {
+    //Run some frames of the movie in Ruffle.
+    let frames = execute_report_opt.frames;
+    match catch_unwind(|| execute_swf(file_path, frames)) {
+        Ok((apis, stubs)) => {
+            facts.frames_executed = frames;
+            facts.apis = apis;
+            facts.stubs = stubs;
+        }
+        Err(e) => match e.downcast::<String>() {
             Ok(e) => {
                 file_result.error = Some(format!("PANIC: {e}"));
                 checkpoint(&mut file_result, &start, &mut writer)?;
@@ -160,7 +216,7 @@ pub fn execute_report_main(execute_report_opt: ExecuteReportOpt) -> Result<(), s
                 file_result.error = Some("PANIC".to_string());
                 checkpoint(&mut file_result, &start, &mut writer)?;
             }
-        }
+        },
     }
 
     let errors = LOCAL_LOGGER.with(|log_buffer| {
@@ -0,0 +1,74 @@
+//! Content facts type.
+//!
+//! The `ContentFacts` type in this module describes what a SWF contains and
+//! which parts of Ruffle running it touched, for building compatibility
+//! dashboards out of a scan.
+
+use crate::file_results::AvmType;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use swf::read::Reader;
+use swf::TagCode;
+
+/// The facts gathered about a single SWF.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentFacts {
+    /// The file name scanned (including path).
+    pub name: String,
+
+    /// The SHA256 hash of the SWF file, as capital hex.
+    pub hash: String,
+
+    /// The file format version of this SWF.
+    pub version: Option<u8>,
+
+    /// The AVM type of the movie.
+    pub vm_type: Option<AvmType>,
+
+    /// How many frames of the movie were run.
+    pub frames_executed: u32,
+
+    /// How many of each tag the SWF contains, including tags inside sprites.
+    pub tags: BTreeMap<String, u32>,
+
+    /// How many times each built-in ActionScript API was called.
+    pub apis: BTreeMap<String, u32>,
+
+    /// How many times each stubbed or unimplemented API was hit.
+    pub stubs: BTreeMap<String, u32>,
+}
+
+impl ContentFacts {
+    pub fn new(name: &str) -> Self {
+        ContentFacts {
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Count the tags in a decompressed SWF tag stream, descending into sprites.
+///
+/// Counting stops at the end of the stream or at the first truncated tag.
+pub fn count_tags(data: &[u8], version: u8, tags: &mut BTreeMap<String, u32>) {
+    let mut reader = Reader::new(data, version);
+    while let Ok((tag_code, length)) = reader.read_tag_code_and_length() {
+        let input = *reader.get_mut();
+        let Some(tag_data) = input.get(..length) else {
+            break;
+        };
+        *reader.get_mut() = &input[length..];
+
+        *tags.entry(TagCode::format(tag_code)).or_default() += 1;
+        if tag_code == TagCode::End as u16 {
+            break;
+        }
+        if tag_code == TagCode::DefineSprite as u16 {
+            // Skip the sprite's ID and frame count.
+            if let Some(sprite_tags) = tag_data.get(4..) {
+                count_tags(sprite_tags, version, tags);
+            }
+        }
+    }
+}
@@ -7,6 +7,7 @@ use clap::Parser;
 mod analyze;
 mod cli_options;
 mod execute;
+mod facts;
 mod file_results;
 mod logging;
 mod scan;
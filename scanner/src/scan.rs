@@ -2,6 +2,7 @@
 
 use crate::analyze::analyze;
 use crate::cli_options::ScanOpt;
+use crate::facts::ContentFacts;
 use crate::file_results::FileResults;
 use crate::ser_bridge::SerBridge;
 use indicatif::{ProgressBar, ProgressStyle};
@@ -14,6 +15,8 @@ use walkdir::{DirEntry, WalkDir};
 
 use std::env;
 use std::ffi::OsStr;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 use std::process::Command;
 use std::time::Instant;
 
@@ -38,13 +41,28 @@ pub fn find_files(root: &Path, ignore: &[String]) -> Vec<DirEntry> {
     results
 }
 
-pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) -> FileResults {
+/// Scan a single file in a child process.
+///
+/// If `facts_path` is given, the child also stores the content facts of the
+/// file there, which are read back and returned.
+pub fn scan_file<P: AsRef<OsStr>>(
+    exec_path: P,
+    file: &DirEntry,
+    name: &str,
+    frames: u32,
+    facts_path: Option<&Path>,
+) -> (FileResults, Option<ContentFacts>) {
     let start = Instant::now();
     let mut file_results = FileResults::new(name);
 
-    let subproc = Command::new(exec_path)
+    let mut command = Command::new(exec_path);
+    command
         .args(["execute-report", &file.path().to_string_lossy()])
-        .output();
+        .args(["--frames", &frames.to_string()]);
+    if let Some(facts_path) = facts_path {
+        command.arg("--facts").arg(facts_path);
+    }
+    let subproc = command.output();
     match subproc {
         Ok(output) => {
             let mut reader = csv::Reader::from_reader(&output.stdout[..]);
@@ -107,7 +125,18 @@ pub fn scan_file<P: AsRef<OsStr>>(exec_path: P, file: &DirEntry, name: &str) ->
 
     file_results.testing_time = start.elapsed().as_millis();
 
-    file_results
+    let facts = facts_path.map(|facts_path| {
+        // A child that crashed early may not have stored anything.
+        let mut facts = std::fs::read(facts_path)
+            .ok()
+            .and_then(|data| serde_json::from_slice::<ContentFacts>(&data).ok())
+            .unwrap_or_default();
+        let _ = std::fs::remove_file(facts_path);
+        facts.name = name.to_string();
+        facts
+    });
+
+    (file_results, facts)
 }
 
 /// The main scanner process.
@@ -117,6 +146,12 @@ pub fn scan_main(opt: ScanOpt) -> Result<(), std::io::Error> {
     let binary_path = env::current_exe()?;
     let to_scan = find_files(&opt.input_path, &opt.ignore);
     let mut writer = csv::Writer::from_path(opt.output_path.clone())?;
+    let mut facts_writer = match &opt.facts_path {
+        Some(facts_path) => Some(BufWriter::new(File::create(facts_path)?)),
+        None => None,
+    };
+    let facts_dir = env::temp_dir();
+    let collect_facts = opt.facts_path.is_some();
 
     let progress = ProgressBar::new(to_scan.len() as u64);
     progress.set_style(
@@ -129,13 +164,26 @@ pub fn scan_main(opt: ScanOpt) -> Result<(), std::io::Error> {
 
     let result_iter = to_scan
         .into_par_iter()
-        .map(move |file| {
+        .enumerate()
+        .map(move |(index, file)| {
             let name = file
                 .path()
                 .strip_prefix(&opt.input_path)
                 .unwrap_or_else(|_| file.path())
                 .to_slash_lossy();
-            let result = scan_file(&binary_path, &file, &name);
+            let facts_path = collect_facts.then(|| {
+                facts_dir.join(format!(
+                    "ruffle_scanner_{}_{index}.json",
+                    std::process::id()
+                ))
+            });
+            let result = scan_file(
+                &binary_path,
+                &file,
+                &name,
+                opt.frames,
+                facts_path.as_deref(),
+            );
 
             progress.inc(1);
             progress.set_message(name.into_owned());
@@ -143,11 +191,20 @@ pub fn scan_main(opt: ScanOpt) -> Result<(), std::io::Error> {
             result
         })
         .ser_bridge()
-        .map(|result| {
+        .map(|(result, facts)| {
             if let Err(e) = writer.serialize(result.clone()) {
                 eprintln!("{e}");
             };
 
+            if let (Some(facts_writer), Some(facts)) = (&mut facts_writer, facts) {
+                let line = serde_json::to_writer(&mut *facts_writer, &facts)
+                    .map_err(std::io::Error::from)
+                    .and_then(|()| writeln!(facts_writer));
+                if let Err(e) = line {
+                    eprintln!("{e}");
+                }
+            }
+
             result
         });
 
@@ -23,6 +23,11 @@ impl GradientFilter {
         self.flags.contains(GradientFilterFlags::KNOCKOUT)
     }
 
+    #[inline]
+    pub fn composite_source(&self) -> bool {
+        self.flags.contains(GradientFilterFlags::COMPOSITE_SOURCE)
+    }
+
     #[inline]
     pub fn is_on_top(&self) -> bool {
         self.flags.contains(GradientFilterFlags::ON_TOP)
@@ -0,0 +1,22 @@
+// Compiled for SWF 7.
+// Names from the constant pool are interned, while the ones built with `+` aren't.
+var o = {};
+o.Foo = 1;
+trace(o.foo);
+var n = "F" + "OO";
+trace(o[n]);
+o[n] = 2;
+trace(o.Foo);
+o.bar = "b";
+o["B" + "AR"] = "B";
+trace(o.bar);
+trace("// for (var k in o)");
+for (var k in o) {
+	trace(k);
+}
+delete o["f" + "OO"];
+trace(o.Foo);
+trace("// for (var k in o)");
+for (var k in o) {
+	trace(k);
+}
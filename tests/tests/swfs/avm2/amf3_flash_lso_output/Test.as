@@ -0,0 +1,70 @@
+package {
+	import flash.display.Sprite;
+	import flash.utils.ByteArray;
+
+	// The AMF3 data written by `ByteArray.writeObject` must stay the same as what the
+	// flash_lso crate used to write, and data written by flash_lso must read back the same.
+	public class Test extends Sprite {
+		public function Test() {
+			write("int", 5);
+			write("negative int", -1);
+			write("int outside of the AMF3 range", 268435456);
+			write("Number", 1.5);
+			write("String", "hello");
+			write("Array with a repeated string", ["a", "a"]);
+			write("Object", {a: 1});
+			var vector:Vector.<int> = new Vector.<int>();
+			vector.push(1);
+			vector.push(2);
+			write("Vector.<int>", vector);
+			var bytes:ByteArray = new ByteArray();
+			bytes.writeByte(1);
+			bytes.writeByte(2);
+			write("ByteArray", bytes);
+
+			var value:* = read("int", "04 05");
+			trace(value + " " + (value is int));
+			value = read("negative int", "04 ff ff ff ff");
+			trace(value + " " + (value is int));
+			value = read("Number", "05 3f f8 00 00 00 00 00 00");
+			trace(value);
+			value = read("String", "06 0b 68 65 6c 6c 6f");
+			trace(value);
+			value = read("Array with a repeated string", "09 05 01 06 03 61 06 00");
+			trace(value.length + " " + value);
+			value = read("Object", "0a 0b 01 03 61 04 01 01");
+			trace(value.a);
+			value = read("Vector.<int>", "0d 05 00 00 00 00 01 00 00 00 02");
+			trace(value.length + " " + value + " " + (value is Vector.<int>));
+			value = read("ByteArray", "0c 05 01 02");
+			trace(value.length + " " + value[0] + " " + value[1]);
+		}
+
+		private function write(name:String, value:*):void {
+			var bytes:ByteArray = new ByteArray();
+			bytes.writeObject(value);
+			trace("write " + name + ": " + toHex(bytes));
+		}
+
+		private function read(name:String, hex:String):* {
+			var bytes:ByteArray = new ByteArray();
+			var parts:Array = hex.split(" ");
+			for (var i:int = 0; i < parts.length; i++) {
+				bytes.writeByte(parseInt(parts[i], 16));
+			}
+			bytes.position = 0;
+			var value:* = bytes.readObject();
+			trace("read " + name + ": " + bytes.bytesAvailable + " bytes left");
+			return value;
+		}
+
+		private function toHex(bytes:ByteArray):String {
+			var parts:Array = [];
+			for (var i:int = 0; i < bytes.length; i++) {
+				var byte:String = bytes[i].toString(16);
+				parts.push(byte.length == 1 ? "0" + byte : byte);
+			}
+			return parts.join(" ");
+		}
+	}
+}
@@ -0,0 +1,53 @@
+package {
+	import flash.display.Sprite;
+	import flash.utils.ByteArray;
+
+	public class Test extends Sprite {
+		public function Test() {
+			// A Vector.<int> that claims to have 0x0FFFFFFF elements, but has none.
+			var hugeVector:ByteArray = new ByteArray();
+			hugeVector.writeByte(0x0D);
+			hugeVector.writeUnsignedInt(0xFFFFFFFF);
+			hugeVector.writeByte(0);
+			read("huge Vector.<int>", hugeVector);
+
+			// A Vector.<Number> with two elements, but only one of them present.
+			var shortVector:ByteArray = new ByteArray();
+			shortVector.writeByte(0x0F);
+			shortVector.writeByte((2 << 1) | 1);
+			shortVector.writeByte(0);
+			shortVector.writeDouble(1.5);
+			read("truncated Vector.<Number>", shortVector);
+
+			read("arrays nested 100 deep", nestedArrays(100));
+			read("arrays nested 10000 deep", nestedArrays(10000));
+		}
+
+		// Arrays that each contain the next one as their only element, ending in null.
+		private function nestedArrays(depth:int):ByteArray {
+			var bytes:ByteArray = new ByteArray();
+			for (var i:int = 0; i < depth; i++) {
+				bytes.writeByte(0x09);
+				bytes.writeByte((1 << 1) | 1);
+				bytes.writeByte(0x01);
+			}
+			bytes.writeByte(0x01);
+			return bytes;
+		}
+
+		private function read(name:String, bytes:ByteArray):void {
+			bytes.position = 0;
+			try {
+				var value:* = bytes.readObject();
+				var depth:int = 0;
+				while (value is Array) {
+					value = value[0];
+					depth++;
+				}
+				trace(name + ": read, depth " + depth);
+			} catch (e:Error) {
+				trace(name + ": " + Object(e).constructor + " " + e.errorID);
+			}
+		}
+	}
+}
@@ -0,0 +1,86 @@
+package {
+	import flash.display.Sprite;
+	import flash.net.registerClassAlias;
+	import flash.utils.ByteArray;
+	import flash.utils.Dictionary;
+
+	public class Test extends Sprite {
+		public function Test() {
+			registerClassAlias("Point3", Point3);
+
+			var point:Point3 = roundtrip(new Point3(1, 2, 3)) as Point3;
+			trace("Point3: " + point);
+
+			var key:Object = {name: "key"};
+			var dictionary:Dictionary = new Dictionary();
+			dictionary[key] = "object value";
+			dictionary["string"] = 5;
+			var readDictionary:Dictionary = roundtrip(dictionary) as Dictionary;
+			// The order of the entries isn't defined, so they're sorted first.
+			var entries:Array = [];
+			for (var k:* in readDictionary) {
+				if (k is String) {
+					entries.push("Dictionary string key: " + k + " = " + readDictionary[k]);
+				} else {
+					entries.push("Dictionary object key: " + k.name + " = " + readDictionary[k]);
+				}
+			}
+			entries.sort();
+			for each (var entry:String in entries) {
+				trace(entry);
+			}
+			trace("Dictionary entries: " + entries.length);
+
+			var nested:Object = {points: [new Point3(4, 5, 6), new Point3(7, 8, 9)]};
+			nested.self = nested;
+			var readNested:Object = roundtrip(nested);
+			trace("Nested points: " + readNested.points);
+			trace("Self reference kept: " + (readNested.self === readNested));
+
+			var shared:Point3 = new Point3(0, 0, 0);
+			var pair:Array = roundtrip([shared, shared]) as Array;
+			trace("Shared reference kept: " + (pair[0] === pair[1]));
+		}
+
+		private function roundtrip(value:Object):Object {
+			var bytes:ByteArray = new ByteArray();
+			bytes.writeObject(value);
+			bytes.position = 0;
+			var result:Object = bytes.readObject();
+			trace("Bytes left: " + bytes.bytesAvailable);
+			return result;
+		}
+	}
+}
+
+import flash.utils.IDataInput;
+import flash.utils.IDataOutput;
+import flash.utils.IExternalizable;
+
+class Point3 implements IExternalizable {
+	public var x:int;
+	public var y:int;
+	public var z:int;
+
+	public function Point3(x:int = 0, y:int = 0, z:int = 0) {
+		this.x = x;
+		this.y = y;
+		this.z = z;
+	}
+
+	public function writeExternal(output:IDataOutput):void {
+		output.writeByte(x);
+		output.writeShort(y);
+		output.writeInt(z);
+	}
+
+	public function readExternal(input:IDataInput):void {
+		x = input.readByte();
+		y = input.readShort();
+		z = input.readInt();
+	}
+
+	public function toString():String {
+		return "(" + x + ", " + y + ", " + z + ")";
+	}
+}
@@ -0,0 +1,62 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.filters.BitmapFilter;
+	import flash.filters.BlurFilter;
+	import flash.filters.ColorMatrixFilter;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	public class Test extends MovieClip {
+		// Moves red into the green channel, so filtered pixels are easy to tell apart.
+		static const RED_TO_GREEN:Array = [
+			0, 0, 0, 0, 0,
+			1, 0, 0, 0, 0,
+			0, 0, 0, 0, 0,
+			0, 0, 0, 1, 0
+		];
+
+		public function Test() {
+			var bitmap:BitmapData = new BitmapData(50, 50, true, 0xFFFF0000);
+
+			trace("// generateFilterRect");
+			testFilterRect(bitmap, new Rectangle(10, 20, 30, 40), new ColorMatrixFilter(RED_TO_GREEN));
+			testFilterRect(bitmap, new Rectangle(-10, -10, 500, 500), new ColorMatrixFilter(RED_TO_GREEN));
+			testFilterRect(bitmap, new Rectangle(10, 20, 30, 40), new BlurFilter(0, 0, 1));
+			trace("");
+
+			trace("// sourceRect partially outside of the source");
+			testApplyFilter(new Rectangle(-5, -5, 10, 10), new Point(2, 2), [
+				new Point(2, 2), new Point(6, 6), new Point(7, 7), new Point(9, 9)
+			]);
+
+			trace("// negative destPoint");
+			testApplyFilter(new Rectangle(0, 0, 10, 10), new Point(-3, -4), [
+				new Point(0, 0), new Point(6, 5), new Point(7, 5), new Point(6, 6)
+			]);
+
+			trace("// sourceRect larger than the destination");
+			testApplyFilter(new Rectangle(0, 0, 100, 100), new Point(8, 0), [
+				new Point(7, 0), new Point(8, 0), new Point(9, 9)
+			]);
+
+			trace("// destPoint outside of the destination");
+			testApplyFilter(new Rectangle(0, 0, 10, 10), new Point(10, 10), [
+				new Point(9, 9)
+			]);
+		}
+
+		private function testFilterRect(bitmap:BitmapData, sourceRect:Rectangle, filter:BitmapFilter):void {
+			trace(sourceRect + " -> " + bitmap.generateFilterRect(sourceRect, filter));
+		}
+
+		private function testApplyFilter(sourceRect:Rectangle, destPoint:Point, points:Array):void {
+			var source:BitmapData = new BitmapData(10, 10, true, 0xFFFF0000);
+			var dest:BitmapData = new BitmapData(10, 10, true, 0xFF0000FF);
+			dest.applyFilter(source, sourceRect, destPoint, new ColorMatrixFilter(RED_TO_GREEN));
+			for each (var point:Point in points) {
+				trace(point + ": " + dest.getPixel32(point.x, point.y).toString(16));
+			}
+		}
+	}
+}
@@ -0,0 +1,48 @@
+package {
+	import flash.desktop.Clipboard;
+	import flash.desktop.ClipboardFormats;
+	import flash.display.Sprite;
+	import flash.events.Event;
+
+	public class Test extends Sprite {
+		public function Test() {
+			try {
+				Clipboard.generalClipboard.getData(ClipboardFormats.TEXT_FORMAT);
+				trace("getData outside paste: no error");
+			} catch (e:SecurityError) {
+				trace("getData outside paste: " + e.errorID);
+			}
+
+			try {
+				Clipboard.generalClipboard.formats;
+				trace("formats outside paste: no error");
+			} catch (e:SecurityError) {
+				trace("formats outside paste: " + e.errorID);
+			}
+
+			trace("setData outside paste: " + Clipboard.generalClipboard.setData(ClipboardFormats.TEXT_FORMAT, "Ruffle"));
+
+			var own:Clipboard = new Clipboard();
+			own.setData(ClipboardFormats.TEXT_FORMAT, "Own");
+			trace("own clipboard: " + own.getData(ClipboardFormats.TEXT_FORMAT));
+
+			var self:Test = this;
+			stage.addEventListener(Event.PASTE, function(e:Event):void {
+				trace("paste: bubbles " + e.bubbles + ", cancelable " + e.cancelable + ", target " + e.target);
+				trace("formats in paste: " + Clipboard.generalClipboard.formats.length);
+				trace("getData in paste: " + (Clipboard.generalClipboard.getData(ClipboardFormats.TEXT_FORMAT) is String));
+				self.addEventListener(Event.ENTER_FRAME, afterPaste);
+			});
+		}
+
+		private function afterPaste(e:Event):void {
+			removeEventListener(Event.ENTER_FRAME, afterPaste);
+			try {
+				Clipboard.generalClipboard.getData(ClipboardFormats.TEXT_FORMAT);
+				trace("getData after paste: no error");
+			} catch (e:SecurityError) {
+				trace("getData after paste: " + e.errorID);
+			}
+		}
+	}
+}
@@ -0,0 +1,25 @@
+package {
+	import flash.display.Loader;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.net.URLRequest;
+	import flash.system.ApplicationDomain;
+	import flash.system.LoaderContext;
+
+	public class Test extends Sprite {
+		public function Test() {
+			trace("Parent: " + SharedDef.NAME);
+
+			var domain:ApplicationDomain = new ApplicationDomain(ApplicationDomain.currentDomain);
+			var loader:Loader = new Loader();
+			loader.contentLoaderInfo.addEventListener(Event.INIT, function(e:Event):void {
+				var definition:Object = domain.getDefinition("SharedDef");
+				trace("Child domain getDefinition: " + definition.NAME);
+				trace("Same class as parent: " + (definition === SharedDef));
+				trace("Child domain hasDefinition(OnlyInChild): " + domain.hasDefinition("OnlyInChild"));
+				trace("Parent domain hasDefinition(OnlyInChild): " + ApplicationDomain.currentDomain.hasDefinition("OnlyInChild"));
+			});
+			loader.load(new URLRequest("child/child.swf"), new LoaderContext(false, domain));
+		}
+	}
+}
@@ -0,0 +1,10 @@
+package {
+	import flash.display.Sprite;
+
+	public class Child extends Sprite {
+		public function Child() {
+			trace("Child code: " + SharedDef.NAME);
+			new OnlyInChild();
+		}
+	}
+}
@@ -0,0 +1,4 @@
+package {
+	public class OnlyInChild {
+	}
+}
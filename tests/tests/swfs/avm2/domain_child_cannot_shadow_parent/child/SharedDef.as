@@ -0,0 +1,5 @@
+package {
+	public class SharedDef {
+		public static const NAME:String = "SharedDef from child";
+	}
+}
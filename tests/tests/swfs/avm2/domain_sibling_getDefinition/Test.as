@@ -0,0 +1,48 @@
+package {
+	import flash.display.Loader;
+	import flash.display.Sprite;
+	import flash.events.Event;
+	import flash.net.URLRequest;
+	import flash.system.ApplicationDomain;
+	import flash.system.LoaderContext;
+
+	public class Test extends Sprite {
+		private var domainA:ApplicationDomain = new ApplicationDomain(ApplicationDomain.currentDomain);
+		private var domainB:ApplicationDomain = new ApplicationDomain(ApplicationDomain.currentDomain);
+
+		public function Test() {
+			var loaderA:Loader = new Loader();
+			loaderA.contentLoaderInfo.addEventListener(Event.INIT, function(e:Event):void {
+				var loaderB:Loader = new Loader();
+				loaderB.contentLoaderInfo.addEventListener(Event.INIT, function(e:Event):void {
+					checkDomains();
+				});
+				loaderB.load(new URLRequest("b/b.swf"), new LoaderContext(false, domainB));
+			});
+			loaderA.load(new URLRequest("a/a.swf"), new LoaderContext(false, domainA));
+		}
+
+		private function checkDomains():void {
+			trace("A hasDefinition(OnlyInA): " + domainA.hasDefinition("OnlyInA"));
+			trace("B hasDefinition(OnlyInA): " + domainB.hasDefinition("OnlyInA"));
+			trace("A hasDefinition(OnlyInB): " + domainA.hasDefinition("OnlyInB"));
+			trace("B hasDefinition(OnlyInB): " + domainB.hasDefinition("OnlyInB"));
+			trace("Parent hasDefinition(OnlyInA): " + ApplicationDomain.currentDomain.hasDefinition("OnlyInA"));
+
+			trace("A getDefinition(OnlyInA): " + domainA.getDefinition("OnlyInA"));
+			trace("B getDefinition(OnlyInB): " + domainB.getDefinition("OnlyInB"));
+			try {
+				domainB.getDefinition("OnlyInA");
+				trace("B getDefinition(OnlyInA): no error");
+			} catch (e:ReferenceError) {
+				trace("B getDefinition(OnlyInA): " + e);
+			}
+			try {
+				domainA.getDefinition("OnlyInB");
+				trace("A getDefinition(OnlyInB): no error");
+			} catch (e:ReferenceError) {
+				trace("A getDefinition(OnlyInB): " + e);
+			}
+		}
+	}
+}
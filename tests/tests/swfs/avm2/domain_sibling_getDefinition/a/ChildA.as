@@ -0,0 +1,9 @@
+package {
+	import flash.display.Sprite;
+
+	public class ChildA extends Sprite {
+		public function ChildA() {
+			new OnlyInA();
+		}
+	}
+}
@@ -0,0 +1,9 @@
+package {
+	import flash.display.Sprite;
+
+	public class ChildB extends Sprite {
+		public function ChildB() {
+			new OnlyInB();
+		}
+	}
+}
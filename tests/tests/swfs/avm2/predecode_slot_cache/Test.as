@@ -0,0 +1,64 @@
+package {
+	import flash.display.Sprite;
+
+	// `readX` and `writeX` are called often enough to be pre-decoded, after which
+	// their property accesses remember the slot they found on the last object.
+	// Objects of other classes keep `x` in a different slot, in a getter, in a
+	// constant, or as a dynamic property, so the remembered slot must not be used
+	// for them.
+	public class Test extends Sprite {
+		public function Test() {
+			var objects:Array = [new First(), new Second(), new WithGetter(), new WithConst(), {x: "dynamic"}];
+			var results:Array = [];
+			for (var i:int = 0; i < 30; i++) {
+				var object:Object = objects[i % objects.length];
+				try {
+					writeX(object, i + 0.5);
+					results.push(readX(object));
+				} catch (e:Error) {
+					results.push(e.errorID);
+				}
+			}
+			trace(results.slice(0, 5).join(","));
+			trace(results.slice(25).join(","));
+
+			var second:Second = new Second();
+			writeX(second, 7.9);
+			trace("Second: a=" + second.a + " x=" + second.x + " b=" + second.b);
+		}
+
+		private function readX(object:Object):* {
+			return object.x;
+		}
+
+		private function writeX(object:Object, value:Number):void {
+			object.x = value;
+		}
+	}
+}
+
+class First {
+	public var x:int = 1;
+}
+
+class Second {
+	public var a:String = "a";
+	public var x:int = 2;
+	public var b:String = "b";
+}
+
+class WithGetter {
+	private var stored:Number = 3;
+
+	public function get x():Number {
+		return stored * 10;
+	}
+
+	public function set x(value:Number):void {
+		stored = value;
+	}
+}
+
+class WithConst {
+	public const x:String = "const";
+}
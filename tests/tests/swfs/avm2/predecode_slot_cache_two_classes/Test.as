@@ -0,0 +1,59 @@
+package {
+	import flash.display.Sprite;
+
+	// The property accesses in `bump` are pre-decoded after a few calls, and remember
+	// the slot of `x` on the last object they were used on. `First` and `Second` keep `x`
+	// in different slots, so switching between them must replace the remembered slot
+	// instead of reading or writing the wrong one.
+	public class Test extends Sprite {
+		public function Test() {
+			var first:First = new First();
+			var second:Second = new Second();
+			var third:Third = new Third();
+
+			// Only `First`, so the remembered slot keeps being used.
+			for (var i:int = 0; i < 20; i++) {
+				bump(first);
+			}
+			trace("after First only: first.x=" + first.x);
+
+			// Only `Second`, so the slot is replaced once and then used again.
+			for (i = 0; i < 20; i++) {
+				bump(second);
+			}
+			trace("after Second only: second.x=" + second.x + " a=" + second.a + " b=" + second.b);
+
+			// Both classes, so the slot is replaced on every call.
+			for (i = 0; i < 20; i++) {
+				bump(first);
+				bump(second);
+			}
+			trace("after both: first.x=" + first.x + " second.x=" + second.x + " a=" + second.a + " b=" + second.b);
+
+			// A subclass of `First` has its own vtable, with `x` in the same slot.
+			for (i = 0; i < 10; i++) {
+				bump(third);
+				bump(first);
+			}
+			trace("after Third: third.x=" + third.x + " y=" + third.y + " first.x=" + first.x);
+		}
+
+		private function bump(object:Object):void {
+			object.x = object.x + 1;
+		}
+	}
+}
+
+class First {
+	public var x:int = 0;
+}
+
+class Second {
+	public var a:String = "a";
+	public var b:String = "b";
+	public var x:Number = 0.5;
+}
+
+class Third extends First {
+	public var y:String = "y";
+}
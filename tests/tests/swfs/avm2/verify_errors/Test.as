@@ -0,0 +1,60 @@
+package {
+	import flash.display.Sprite;
+
+	// The bodies of the `bad*` methods are patched in test.swf after compiling,
+	// so that each one fails verification:
+	//
+	// * badOpcode: `returnvoid` is replaced with the undefined opcode 0xFF.
+	// * badFallOff: `returnvoid` is replaced with `nop`.
+	// * badRegister: `getlocal0` is replaced with `getlocal3`, past local_count.
+	// * badCpool: the string index of `pushstring` is replaced with 127.
+	// * badBranch: the `jump` over the loop body targets the middle of an instruction.
+	//
+	// Each method is verified the first time it's called, and the call throws.
+	public class Test extends Sprite {
+		public function Test() {
+			check("badOpcode", badOpcode);
+			check("badFallOff", badFallOff);
+			check("badRegister", badRegister);
+			check("badCpool", badCpool);
+			check("badBranch", badBranch);
+
+			// A method that failed verification keeps failing.
+			check("badOpcode again", badOpcode);
+			check("good", good);
+		}
+
+		private function check(name:String, method:Function):void {
+			try {
+				trace(name + ": returned " + method());
+			} catch (e:VerifyError) {
+				trace(name + ": VerifyError " + e.errorID);
+			}
+		}
+
+		private function badOpcode():void {
+		}
+
+		private function badFallOff():void {
+		}
+
+		private function badRegister():void {
+		}
+
+		private function badCpool():String {
+			return "verify_errors_cpool_marker";
+		}
+
+		private function badBranch():int {
+			var total:int = 0;
+			for (var i:int = 0; i < 3; i++) {
+				total += i;
+			}
+			return total;
+		}
+
+		private function good():String {
+			return "ok";
+		}
+	}
+}
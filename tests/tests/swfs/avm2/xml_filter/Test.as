@@ -0,0 +1,32 @@
+package {
+  import flash.display.Sprite;
+  public class Test extends Sprite { }
+}
+
+XML.prettyPrinting = false;
+
+var items: XML = <items><item id="1" type="a">one</item><item id="2" type="b">two</item><item id="3" type="a">three</item></items>;
+
+var type_a: XMLList = items.item.(@type == "a");
+trace("type_a.length(): " + type_a.length());
+trace("type_a: " + type_a.toXMLString());
+trace('items.item.(@id == "2"): ' + items.item.(@id == "2"));
+trace('items.item.(@id == "4").length(): ' + items.item.(@id == "4").length());
+trace('items.item.(@id == 3).toXMLString(): ' + items.item.(@id == 3).toXMLString());
+
+var people: XML = <people><person><name>Bob</name><age>30</age></person><person><name>Alice</name><age>25</age></person></people>;
+trace("people.person.(age > 26).name: " + people.person.(age > 26).name);
+trace('people.person.(name == "Alice").age: ' + people.person.(name == "Alice").age);
+trace("people.person.(age < 100).length(): " + people.person.(age < 100).length());
+
+var root: XML = <root a="1"><child/></root>;
+trace('root.(@a == "1").length(): ' + root.(@a == "1").length());
+trace('root.(@a == "2").length(): ' + root.(@a == "2").length());
+trace('root.(@a == "1").toXMLString(): ' + root.(@a == "1").toXMLString());
+
+try {
+  var obj: * = {};
+  obj.(a == 1);
+} catch (e: TypeError) {
+  trace("filter on Object: " + e.errorID);
+}
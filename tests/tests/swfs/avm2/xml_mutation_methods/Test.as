@@ -0,0 +1,59 @@
+package {
+  import flash.display.Sprite;
+  public class Test extends Sprite { }
+}
+
+XML.prettyPrinting = false;
+
+var xml: XML = <a><b/><c/></a>;
+trace("before: " + xml.toXMLString());
+trace("appendChild returns this: " + (xml.appendChild(<d/>) === xml));
+trace("appendChild: " + xml.toXMLString());
+xml.prependChild(<z/>);
+trace("prependChild: " + xml.toXMLString());
+xml.insertChildAfter(xml.b[0], <b2/>);
+trace("insertChildAfter: " + xml.toXMLString());
+xml.insertChildBefore(xml.c[0], <c0/>);
+trace("insertChildBefore: " + xml.toXMLString());
+xml.insertChildAfter(null, <first/>);
+trace("insertChildAfter(null): " + xml.toXMLString());
+xml.insertChildBefore(null, <last/>);
+trace("insertChildBefore(null): " + xml.toXMLString());
+trace("insertChildAfter(not a child): " + xml.insertChildAfter(<b/>, <x/>));
+trace("insertChildBefore(not a child): " + xml.insertChildBefore(<b/>, <x/>));
+trace("after: " + xml.toXMLString());
+
+var text: XML = <t/>;
+text.appendChild("hello");
+trace("appendChild(String): " + text.toXMLString());
+text.appendChild(<list><i>1</i><i>2</i></list>.i);
+trace("appendChild(XMLList): " + text.toXMLString());
+
+var parent: XML = <p><old>1</old><old>2</old></p>;
+parent.setChildren(<new/>);
+trace("setChildren(XML): " + parent.toXMLString());
+parent.setChildren("text");
+trace("setChildren(String): " + parent.toXMLString());
+
+var replaced: XML = <r><b>1</b><c/><b>2</b></r>;
+replaced.replace("b", <x/>);
+trace('replace("b", XML): ' + replaced.toXMLString());
+replaced.replace("c", "text");
+trace('replace("c", String): ' + replaced.toXMLString());
+replaced.replace(0, <first/>);
+trace("replace(0, XML): " + replaced.toXMLString());
+replaced.replace("missing", <y/>);
+trace('replace("missing", XML): ' + replaced.toXMLString());
+
+var cyclic: XML = <outer><inner/></outer>;
+try {
+  cyclic.inner[0].appendChild(cyclic);
+} catch (e: TypeError) {
+  trace("appendChild(ancestor): " + e.errorID);
+}
+try {
+  cyclic.appendChild(cyclic);
+} catch (e: TypeError) {
+  trace("appendChild(self): " + e.errorID);
+}
+trace("cyclic: " + cyclic.toXMLString());
@@ -0,0 +1,48 @@
+package {
+  import flash.display.Sprite;
+  public class Test extends Sprite { }
+}
+
+XML.prettyPrinting = false;
+
+var foo = new Namespace("http://foo");
+var d = new Namespace("http://default");
+
+var xml: XML = <root xmlns="http://default" xmlns:foo="http://foo"><foo:child foo:attr="1">x</foo:child><plain/></root>;
+trace("xml.toXMLString(): " + xml.toXMLString());
+trace("xml.namespace().uri: " + xml.namespace().uri);
+trace("xml.namespace().prefix: " + xml.namespace().prefix);
+trace('xml.namespace("foo").uri: ' + xml.namespace("foo").uri);
+trace('xml.namespace("bar"): ' + xml.namespace("bar"));
+trace("xml.inScopeNamespaces().length: " + xml.inScopeNamespaces().length);
+trace("xml.namespaceDeclarations().length: " + xml.namespaceDeclarations().length);
+
+var child: XML = xml.foo::child[0];
+trace("child.name().uri: " + child.name().uri);
+trace("child.localName(): " + child.localName());
+trace("child.namespace().prefix: " + child.namespace().prefix);
+trace("child.inScopeNamespaces().length: " + child.inScopeNamespaces().length);
+trace("child.namespaceDeclarations().length: " + child.namespaceDeclarations().length);
+trace("child.@foo::attr: " + child.@foo::attr);
+trace("child.@attr.length(): " + child.@attr.length());
+
+trace("xml.child.length(): " + xml.child.length());
+trace("xml.plain.length(): " + xml.plain.length());
+trace("xml.d::plain.length(): " + xml.d::plain.length());
+
+xml.foo::added = "v";
+trace("after xml.foo::added = \"v\": " + xml.toXMLString());
+
+var el: XML = <el/>;
+el.addNamespace(new Namespace("p", "http://p"));
+trace("addNamespace: " + el.toXMLString());
+el.setNamespace(new Namespace("q", "http://q"));
+trace("setNamespace: " + el.toXMLString());
+trace("el.name().uri: " + el.name().uri);
+el.removeNamespace(new Namespace("p", "http://p"));
+trace("removeNamespace: " + el.toXMLString());
+el.setLocalName("renamed");
+trace("setLocalName: " + el.toXMLString());
+el.setName("plain");
+trace("setName: " + el.toXMLString());
+trace("el.name().uri: " + el.name().uri);
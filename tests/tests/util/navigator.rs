@@ -3,11 +3,16 @@ use async_io::Timer;
 use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::navigator::{
     fetch_path, resolve_url_with_relative_base_path, ErrorResponse, NavigationMethod,
-    NavigatorBackend, NullExecutor, NullSpawner, OwnedFuture, Request, SuccessResponse,
+    NavigatorBackend, NetworkInterface, NullExecutor, NullSpawner, OwnedFuture, Request,
+    SuccessResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
-use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
+use ruffle_core::local_connection::LocalConnectionMessage;
+use ruffle_core::socket::{
+    ConnectionState, Datagram, DatagramSocketHandle, ServerSocketHandle, SocketAction,
+    SocketHandle, SocketKind,
+};
 use ruffle_socket_format::SocketEvent;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::{Receiver, Sender, TryRecvError};
@@ -111,6 +116,7 @@ impl NavigatorBackend for TestNavigatorBackend {
         handle: SocketHandle,
         receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
+        _kind: SocketKind,
     ) {
         if let Some(log) = &self.log {
             log.avm_trace("Navigator::connect_socket");
@@ -170,4 +176,44 @@ impl NavigatorBackend for TestNavigatorBackend {
             }));
         }
     }
+
+    fn bind_server_socket(
+        &mut self,
+        _address: String,
+        _port: u16,
+        _handle: ServerSocketHandle,
+        _receiver: Receiver<()>,
+        _sender: Sender<SocketAction>,
+    ) -> Result<(String, u16), String> {
+        Err("Server sockets are not supported".to_string())
+    }
+
+    fn bind_datagram_socket(
+        &mut self,
+        _address: String,
+        _port: u16,
+        _handle: DatagramSocketHandle,
+        _receiver: Receiver<Datagram>,
+        _sender: Sender<SocketAction>,
+    ) -> Result<(String, u16), String> {
+        Err("Datagram sockets are not supported".to_string())
+    }
+
+    fn network_interfaces(&self) -> Vec<NetworkInterface> {
+        vec![]
+    }
+
+    fn listen_local_connection(
+        &mut self,
+        _name: &str,
+        _sender: Sender<LocalConnectionMessage>,
+    ) -> bool {
+        true
+    }
+
+    fn close_local_connection(&mut self, _name: &str) {}
+
+    fn send_local_connection(&mut self, _name: &str, _data: Vec<u8>) -> bool {
+        false
+    }
 }
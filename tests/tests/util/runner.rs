@@ -9,7 +9,7 @@ use ruffle_core::backend::audio::{
 use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::navigator::NullExecutor;
 use ruffle_core::events::MouseButton as RuffleMouseButton;
-use ruffle_core::events::{KeyCode, TextControlCode as RuffleTextControlCode};
+use ruffle_core::events::{KeyCode, KeyLocation, TextControlCode as RuffleTextControlCode};
 use ruffle_core::impl_audio_mixer_backend;
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
@@ -211,6 +211,7 @@ pub fn run_swf(
                 AutomatedEvent::KeyDown { key_code } => PlayerEvent::KeyDown {
                     key_code: KeyCode::from_u8(*key_code).expect("Invalid keycode in test"),
                     key_char: None,
+                    key_location: KeyLocation::Standard,
                 },
                 AutomatedEvent::TextInput { codepoint } => PlayerEvent::TextInput {
                     codepoint: *codepoint,
@@ -10,6 +10,9 @@ pub mod vp6;
 #[cfg(feature = "screenvideo")]
 pub mod screen;
 
+#[cfg(feature = "h264")]
+pub mod h264;
+
 /// Trait for video decoders.
 /// This should be implemented for each video codec.
 pub trait VideoDecoder {
@@ -0,0 +1,179 @@
+use crate::decoder::VideoDecoder;
+use openh264::decoder::Decoder;
+use ruffle_render::bitmap::BitmapFormat;
+use ruffle_video::error::Error;
+use ruffle_video::frame::{DecodedFrame, EncodedFrame, FrameDependency};
+
+#[derive(thiserror::Error, Debug)]
+pub enum H264Error {
+    #[error("Decoder error: {0}")]
+    DecoderError(#[from] openh264::Error),
+
+    #[error("AVC packet is too short")]
+    ShortPacket,
+
+    #[error("Invalid AVC decoder configuration record")]
+    InvalidConfiguration,
+
+    #[error("Picture data arrived before the AVC decoder configuration record")]
+    MissingConfiguration,
+
+    #[error("Unknown AVC packet type {0}")]
+    UnknownPacketType(u8),
+
+    #[error("No picture has been decoded yet")]
+    NoPicture,
+}
+
+impl From<H264Error> for Error {
+    fn from(error: H264Error) -> Self {
+        Error::DecoderError(Box::new(error))
+    }
+}
+
+/// The start code that precedes each NAL unit in an Annex B bitstream,
+/// which is the only format OpenH264 accepts.
+const START_CODE: &[u8] = &[0, 0, 0, 1];
+
+/// The NAL unit type of a slice of an IDR (instantaneous decoder refresh) picture.
+const NAL_UNIT_TYPE_IDR: u8 = 5;
+
+/// Splits length-prefixed NAL units, as stored in FLV and MP4 files.
+fn nal_units(mut data: &[u8], length_size: usize) -> impl Iterator<Item = &[u8]> {
+    std::iter::from_fn(move || {
+        let length = data
+            .get(..length_size)?
+            .iter()
+            .fold(0usize, |length, byte| (length << 8) | *byte as usize);
+        let unit = data.get(length_size..length_size + length)?;
+        data = &data[length_size + length..];
+        Some(unit)
+    })
+}
+
+/// H.264 video decoder.
+///
+/// Every frame is an FLV `AVCVIDEOPACKET`: a packet type, a composition time
+/// offset, and then either the decoder configuration record or NAL units.
+pub struct H264Decoder {
+    decoder: Decoder,
+
+    /// The number of bytes used for the length of each NAL unit.
+    length_size: Option<usize>,
+
+    /// The SPS and PPS NAL units from the configuration record, in Annex B
+    /// format, which are waiting to be sent along with the next picture.
+    parameter_sets: Vec<u8>,
+
+    last_frame: Option<DecodedFrame>,
+}
+
+impl H264Decoder {
+    pub fn new() -> Result<Self, Error> {
+        Ok(Self {
+            decoder: Decoder::new().map_err(H264Error::from)?,
+            length_size: None,
+            parameter_sets: Vec::new(),
+            last_frame: None,
+        })
+    }
+
+    /// Reads an `AVCDecoderConfigurationRecord`.
+    fn configure(&mut self, record: &[u8]) -> Result<(), H264Error> {
+        let length_size = (record.get(4).ok_or(H264Error::InvalidConfiguration)? & 0b11) + 1;
+
+        let mut parameter_sets = Vec::new();
+        let mut data = &record[5..];
+        // The SPS count is in the low 5 bits, and the PPS count is a whole byte.
+        for count_mask in [0b1_1111, 0xFF] {
+            let (&count, rest) = data.split_first().ok_or(H264Error::InvalidConfiguration)?;
+            data = rest;
+            for _ in 0..(count & count_mask) {
+                let length = data
+                    .get(..2)
+                    .map(|length| u16::from_be_bytes([length[0], length[1]]) as usize)
+                    .ok_or(H264Error::InvalidConfiguration)?;
+                let unit = data
+                    .get(2..2 + length)
+                    .ok_or(H264Error::InvalidConfiguration)?;
+                parameter_sets.extend_from_slice(START_CODE);
+                parameter_sets.extend_from_slice(unit);
+                data = &data[2 + length..];
+            }
+        }
+
+        self.length_size = Some(length_size as usize);
+        self.parameter_sets = parameter_sets;
+        Ok(())
+    }
+}
+
+impl VideoDecoder for H264Decoder {
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+        let (&packet_type, data) = encoded_frame
+            .data
+            .split_first()
+            .ok_or(H264Error::ShortPacket)?;
+        let data = data.get(3..).ok_or(H264Error::ShortPacket)?;
+
+        match packet_type {
+            0 => {
+                self.configure(data)?;
+                Ok(FrameDependency::None)
+            }
+            1 => {
+                let length_size = self.length_size.ok_or(H264Error::MissingConfiguration)?;
+                let is_keyframe = nal_units(data, length_size)
+                    .any(|unit| unit.first().map(|b| b & 0b1_1111) == Some(NAL_UNIT_TYPE_IDR));
+                Ok(if is_keyframe {
+                    FrameDependency::None
+                } else {
+                    FrameDependency::Past
+                })
+            }
+            2 => Ok(FrameDependency::Past),
+            other => Err(H264Error::UnknownPacketType(other).into()),
+        }
+    }
+
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+        let (&packet_type, data) = encoded_frame
+            .data
+            .split_first()
+            .ok_or(H264Error::ShortPacket)?;
+        let data = data.get(3..).ok_or(H264Error::ShortPacket)?;
+
+        match packet_type {
+            0 => self.configure(data)?,
+            1 => {
+                let length_size = self.length_size.ok_or(H264Error::MissingConfiguration)?;
+
+                let mut bitstream = std::mem::take(&mut self.parameter_sets);
+                for unit in nal_units(data, length_size) {
+                    bitstream.extend_from_slice(START_CODE);
+                    bitstream.extend_from_slice(unit);
+                }
+
+                // The decoder may hold pictures back for reordering, in which
+                // case we keep showing the previous one.
+                if let Some(yuv) = self.decoder.decode(&bitstream).map_err(H264Error::from)? {
+                    let (width, height) = yuv.dimension_rgb();
+                    let mut rgba = vec![0; width * height * 4];
+                    yuv.write_rgba8(&mut rgba);
+                    self.last_frame = Some(DecodedFrame::new(
+                        width as u32,
+                        height as u32,
+                        BitmapFormat::Rgba,
+                        rgba,
+                    ));
+                }
+            }
+            2 => {}
+            other => return Err(H264Error::UnknownPacketType(other).into()),
+        }
+
+        self.last_frame
+            .clone()
+            .ok_or_else(|| H264Error::NoPicture.into())
+    }
+}
@@ -94,6 +94,24 @@ fn crop(data: &[u8], mut width: usize, to_size: (u16, u16)) -> Vec<u8> {
     data
 }
 
+/// Extends a plane with fully transparent (zeroed) pixels on the right and
+/// bottom edges, so it covers `to_size`.
+fn pad(data: Vec<u8>, width: usize, to_size: (u16, u16)) -> Vec<u8> {
+    let (new_width, new_height) = (to_size.0 as usize, to_size.1 as usize);
+    if width >= new_width && data.len() >= new_width * new_height {
+        return data;
+    }
+
+    let mut padded = vec![0; new_width * new_height];
+    if width > 0 {
+        for (row, source) in data.chunks_exact(width).take(new_height).enumerate() {
+            let len = width.min(new_width);
+            padded[row * new_width..row * new_width + len].copy_from_slice(&source[..len]);
+        }
+    }
+    padded
+}
+
 impl VideoDecoder for Vp6Decoder {
     fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
         // Luckily the very first bit of the encoded frames is exactly this flag,
@@ -200,7 +218,13 @@ impl VideoDecoder for Vp6Decoder {
         // Cropping the encoded frame (containing whole macroblocks) to the
         // size requested by the the bounds attribute.
 
-        let &bounds = &self.bounds;
+        // Streams that were registered without a size, such as FLVs without
+        // metadata, are shown at their encoded size.
+        let bounds = if self.bounds == (0, 0) {
+            (width as u16, height as u16)
+        } else {
+            self.bounds
+        };
 
         if width < bounds.0 as usize || height < bounds.1 as usize {
             log::warn!("A VP6 video frame is smaller than the bounds of the stream it belongs in. This is not supported.");
@@ -225,7 +249,10 @@ impl VideoDecoder for Vp6Decoder {
 
             let alpha_offset = frame.get_offset(3);
             let alpha = &yuv[alpha_offset..alpha_offset + alpha_width * alpha_height];
+            // It may also be smaller than the frame, in which case the rest of
+            // the frame is transparent, so each row still lines up with the Y channel.
             let a = crop(alpha, alpha_width, bounds);
+            let a = pad(a, alpha_width.min(bounds.0 as usize), bounds);
 
             let mut data = y.to_vec();
             data.extend(u);
@@ -0,0 +1,31 @@
+/**
+ * A stub that a movie has run into: a part of Flash that Ruffle doesn't
+ * fully support yet.
+ */
+export interface StubHit {
+    /**
+     * A description of the stub, such as
+     * `AVM2 flash.display.Stage.quality setter`.
+     */
+    readonly name: string;
+
+    /**
+     * How many times the movie has run into the stub.
+     */
+    readonly count: number;
+}
+
+/**
+ * The parts of a movie that Ruffle doesn't fully support yet, which may
+ * explain why it doesn't work as expected.
+ */
+export interface CompatibilityReport {
+    readonly swfVersion: number;
+
+    readonly isActionScript3: boolean;
+
+    /**
+     * The stubs that the movie has run into, most often hit first.
+     */
+    readonly stubs: StubHit[];
+}
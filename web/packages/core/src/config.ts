@@ -2,9 +2,11 @@ import type { BaseLoadOptions } from "./load-options";
 import {
     AutoPlay,
     ContextMenu,
+    FrameRateMode,
     Letterbox,
     LogLevel,
     OpenURLMode,
+    JavascriptUrlMode,
     NetworkingAccessMode,
     UnmuteOverlay,
     WindowMode,
@@ -36,11 +38,18 @@ export const DEFAULT_CONFIG: Required<BaseLoadOptions> = {
     scale: "showAll",
     forceScale: false,
     frameRate: null,
+    frameRateMode: FrameRateMode.Native,
+    saveStates: false,
     wmode: WindowMode.Opaque,
     publicPath: null,
     polyfills: true,
     playerVersion: null,
     preferredRenderer: null,
     openUrlMode: OpenURLMode.Allow,
+    openUrlAllow: [],
+    openUrlDeny: [],
+    javascriptUrlMode: JavascriptUrlMode.Navigate,
     allowNetworking: NetworkingAccessMode.All,
+    socketProxy: [],
+    gamepadButtonMapping: {},
 };
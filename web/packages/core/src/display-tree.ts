@@ -0,0 +1,64 @@
+/**
+ * A display object in the display list of a movie, and everything below it.
+ */
+export interface DisplayNode {
+    /**
+     * The type of display object, such as `MovieClip` or `Bitmap`.
+     */
+    readonly kind: string;
+
+    /**
+     * The instance name of the display object, which may be empty.
+     */
+    readonly name: string;
+
+    readonly depth: number;
+
+    readonly x: number;
+
+    readonly y: number;
+
+    /**
+     * The rotation in degrees.
+     */
+    readonly rotation: number;
+
+    /**
+     * The horizontal scale as a percentage.
+     */
+    readonly scaleX: number;
+
+    /**
+     * The vertical scale as a percentage.
+     */
+    readonly scaleY: number;
+
+    readonly alpha: number;
+
+    readonly visible: boolean;
+
+    /**
+     * The bounds on the stage in pixels, as `[xMin, yMin, xMax, yMax]`.
+     */
+    readonly bounds: [number, number, number, number];
+
+    /**
+     * The names of the filters applied to the display object.
+     */
+    readonly filters: string[];
+
+    readonly children: DisplayNode[];
+}
+
+/**
+ * A property of a display object that can be changed with
+ * `setDisplayProperty`.
+ */
+export type DisplayProperty =
+    | "x"
+    | "y"
+    | "rotation"
+    | "scaleX"
+    | "scaleY"
+    | "alpha"
+    | "visible";
@@ -16,3 +16,5 @@ export * from "./config";
 export * from "./load-options";
 export * from "./build-info";
 export * from "./swf-utils";
+export * from "./display-tree";
+export * from "./compatibility-report";
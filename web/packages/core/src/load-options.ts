@@ -52,6 +52,28 @@ export const enum Letterbox {
     On = "on",
 }
 
+/**
+ * Controls how often frames of the movie are run.
+ */
+export const enum FrameRateMode {
+    /**
+     * Frames are run at the movie's frame rate. If the browser falls behind,
+     * several frames are run at once to catch up, keeping the movie in time with its music.
+     */
+    Native = "native",
+
+    /**
+     * Exactly one frame is run every time the browser draws a new frame,
+     * which is usually the refresh rate of the display.
+     */
+    Vsync = "vsync",
+
+    /**
+     * Frames are run as often as the browser allows.
+     */
+    Unlimited = "unlimited",
+}
+
 /**
  * When the player is muted, this controls whether or not Ruffle will show a
  * "click to unmute" overlay on top of the movie.
@@ -215,6 +237,29 @@ export const enum OpenURLMode {
     Deny = "deny",
 }
 
+/**
+ * The handling mode of `javascript:` URLs that a movie tries to open.
+ */
+export const enum JavascriptUrlMode {
+    /**
+     * Open the URL like any other, which runs the script on the page,
+     * as long as `allowScriptAccess` is enabled.
+     */
+    Navigate = "navigate",
+
+    /**
+     * Ignore all `javascript:` URLs.
+     */
+    Deny = "deny",
+
+    /**
+     * Turn URLs such as `javascript:showScore(10)` into calls of the
+     * `showScore` function through `ExternalInterface`, instead of running
+     * arbitrary scripts.
+     */
+    ExternalInterface = "externalInterface",
+}
+
 /**
  * The networking API access mode of the Ruffle player.
  */
@@ -248,6 +293,30 @@ export const enum NetworkingAccessMode {
     None = "none",
 }
 
+/**
+ * A WebSocket proxy that relays the data of a TCP socket connection.
+ *
+ * Browsers can't open TCP connections, so the connections of `Socket` and
+ * `XMLSocket` are made through a WebSocket instead. The proxy is expected to
+ * connect to the given host and port, and relay binary messages both ways.
+ */
+export interface SocketProxy {
+    /**
+     * The host that the movie connects to.
+     */
+    host: string;
+
+    /**
+     * The port that the movie connects to.
+     */
+    port: number;
+
+    /**
+     * The URL of the WebSocket proxy, such as `wss://example.com/proxy`.
+     */
+    proxyUrl: string;
+}
+
 /**
  * Any options used for loading a movie.
  */
@@ -454,6 +523,23 @@ export interface BaseLoadOptions {
      */
     frameRate?: number | null;
 
+    /**
+     * Controls how often frames of the movie are run, relative to the display.
+     *
+     * @default FrameRateMode.Native
+     */
+    frameRateMode?: FrameRateMode;
+
+    /**
+     * Whether to keep the journal needed to save and restore states of the movie.
+     *
+     * States are restored by replaying the movie from the start, so restoring takes
+     * longer the longer the movie had been running.
+     *
+     * @default false
+     */
+    saveStates?: boolean;
+
     /**
      * The window mode of the Ruffle player.
      *
@@ -512,12 +598,58 @@ export interface BaseLoadOptions {
      */
     openUrlMode?: OpenURLMode;
 
+    /**
+     * Links that can always be opened, regardless of `openUrlMode`.
+     *
+     * Each entry is either a domain, such as `example.com`, which also
+     * covers its subdomains, or a scheme followed by a colon, such as
+     * `mailto:`.
+     *
+     * @default []
+     */
+    openUrlAllow?: string[];
+
+    /**
+     * Links that can never be opened, written like the entries of
+     * `openUrlAllow`. These take precedence over `openUrlAllow`.
+     *
+     * @default []
+     */
+    openUrlDeny?: string[];
+
+    /**
+     * The handling mode of `javascript:` URLs.
+     *
+     * @default JavascriptUrlMode.Navigate
+     */
+    javascriptUrlMode?: JavascriptUrlMode;
+
     /**
      * Which flash networking APIs may be accessed.
      *
      * @default NetworkingAccessMode.All
      */
     allowNetworking?: NetworkingAccessMode;
+
+    /**
+     * The WebSocket proxies to use for the socket connections of movies.
+     *
+     * A movie can only connect to hosts and ports that have a proxy.
+     *
+     * @default []
+     */
+    socketProxy?: SocketProxy[];
+
+    /**
+     * The keys that gamepad buttons press, for movies that don't support gamepads.
+     *
+     * Buttons are named after their position in the standard layout, such as
+     * "south" or "d-pad-up", and keys are given as Flash key codes.
+     *
+     * @example { "south": 32, "d-pad-left": 37 }
+     * @default {}
+     */
+    gamepadButtonMapping?: Record<string, number>;
 }
 
 /**
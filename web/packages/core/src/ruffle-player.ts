@@ -1,4 +1,4 @@
-import type { Ruffle } from "../dist/ruffle_web";
+import type { Ruffle, SaveState } from "../dist/ruffle_web";
 import { loadRuffle } from "./load-ruffle";
 import { ruffleShadowTemplate } from "./shadow-template";
 import { lookupElement } from "./register-element";
@@ -11,6 +11,8 @@ import {
     WindowMode,
 } from "./load-options";
 import type { MovieMetadata } from "./movie-metadata";
+import type { DisplayNode, DisplayProperty } from "./display-tree";
+import type { CompatibilityReport } from "./compatibility-report";
 import { swfFileName } from "./swf-utils";
 import { buildInfo } from "./build-info";
 import { text, textAsParagraphs } from "./i18n";
@@ -133,6 +135,7 @@ export class RufflePlayer extends HTMLElement {
     private readonly splashScreen: HTMLElement;
     private readonly virtualKeyboard: HTMLInputElement;
     private readonly saveManager: HTMLDivElement;
+    private readonly compatibilityReportModal: HTMLDivElement;
 
     private readonly contextMenuOverlay: HTMLElement;
     // Firefox has a read-only "contextMenu" property,
@@ -154,6 +157,11 @@ export class RufflePlayer extends HTMLElement {
     private swfUrl?: URL;
     private instance: Ruffle | null;
     private lastActivePlayingState: boolean;
+    private memoryProfiling = false;
+    private profiling = false;
+
+    // A save state to restore into the next movie that's loaded.
+    private pendingSaveState: SaveState | null = null;
 
     private _metadata: MovieMetadata | null;
     private _readyState: ReadyState;
@@ -243,21 +251,11 @@ export class RufflePlayer extends HTMLElement {
         this.saveManager = <HTMLDivElement>(
             this.shadow.getElementById("save-manager")!
         );
-        this.saveManager.addEventListener("click", () =>
-            this.saveManager.classList.add("hidden"),
+        this.addModalListeners(this.saveManager);
+        this.compatibilityReportModal = <HTMLDivElement>(
+            this.shadow.getElementById("compatibility-report")!
         );
-        const modalArea = this.saveManager.querySelector("#modal-area");
-        if (modalArea) {
-            modalArea.addEventListener("click", (event) =>
-                event.stopPropagation(),
-            );
-        }
-        const closeSaveManager = this.saveManager.querySelector("#close-modal");
-        if (closeSaveManager) {
-            closeSaveManager.addEventListener("click", () =>
-                this.saveManager.classList.add("hidden"),
-            );
-        }
+        this.addModalListeners(this.compatibilityReportModal);
         const backupSaves = <HTMLElement>(
             this.saveManager.querySelector("#backup-saves")
         );
@@ -757,6 +755,11 @@ export class RufflePlayer extends HTMLElement {
 
             await this.ensureFreshInstance();
 
+            if (this.pendingSaveState) {
+                this.instance!.restore_state(this.pendingSaveState);
+                this.pendingSaveState = null;
+            }
+
             if ("url" in options) {
                 console.log(`Loading SWF file ${options.url}`);
                 this.swfUrl = new URL(options.url, document.baseURI);
@@ -1143,6 +1146,82 @@ export class RufflePlayer extends HTMLElement {
         this.saveManager.classList.remove("hidden");
     }
 
+    /**
+     * Makes a modal close when its close button or its surroundings are
+     * clicked.
+     *
+     * @param modal The element that contains the modal area.
+     */
+    private addModalListeners(modal: HTMLDivElement): void {
+        modal.addEventListener("click", () => modal.classList.add("hidden"));
+        const modalArea = modal.querySelector(".modal-area");
+        if (modalArea) {
+            modalArea.addEventListener("click", (event) =>
+                event.stopPropagation(),
+            );
+        }
+        const closeModal = modal.querySelector(".close-modal");
+        if (closeModal) {
+            closeModal.addEventListener("click", () =>
+                modal.classList.add("hidden"),
+            );
+        }
+    }
+
+    /**
+     * Lists the parts of the movie that Ruffle doesn't fully support yet.
+     *
+     * Each stub is counted every time the movie runs into it, so the ones
+     * that are hit most often are the likeliest causes of problems.
+     *
+     * @returns The report, or null if there is no movie loaded.
+     */
+    compatibilityReport(): CompatibilityReport | null {
+        if (this.instance) {
+            return this.instance.compatibility_report() ?? null;
+        }
+        return null;
+    }
+
+    /**
+     * Opens a modal that shows the compatibility report.
+     */
+    private openCompatibilityReport(): void {
+        const report = this.compatibilityReport();
+        const summary = this.compatibilityReportModal.querySelector(
+            "#compatibility-summary",
+        );
+        const stubTable = this.compatibilityReportModal.querySelector(
+            "#compatibility-stubs",
+        );
+        if (!report || !summary || !stubTable) {
+            return;
+        }
+        summary.textContent = text(
+            report.stubs.length > 0
+                ? "compatibility-report-stubs"
+                : "compatibility-report-no-stubs",
+            {
+                avm: report.isActionScript3 ? "3" : "1/2",
+                version: report.swfVersion,
+            },
+        );
+        stubTable.textContent = "";
+        for (const stub of report.stubs) {
+            const row = document.createElement("TR");
+            const nameCol = document.createElement("TD");
+            nameCol.textContent = stub.name;
+            const countCol = document.createElement("TD");
+            countCol.textContent = text("compatibility-report-count", {
+                count: stub.count,
+            });
+            row.appendChild(nameCol);
+            row.appendChild(countCol);
+            stubTable.appendChild(row);
+        }
+        this.compatibilityReportModal.classList.remove("hidden");
+    }
+
     /**
      * Fetches the loaded SWF and downloads it.
      */
@@ -1164,6 +1243,211 @@ export class RufflePlayer extends HTMLElement {
             console.error("SWF download failed");
         }
     }
+    /**
+     * Starts or stops tracking object allocations for memory profiles.
+     *
+     * Only ActionScript 3 objects constructed while tracking is enabled are counted.
+     *
+     * @param enabled Whether to track object allocations.
+     */
+    setMemoryProfiling(enabled: boolean): void {
+        if (this.instance) {
+            this.instance.set_memory_profiling(enabled);
+            this.memoryProfiling = enabled;
+        }
+    }
+
+    /**
+     * Counts the tracked objects that are still alive, by class.
+     *
+     * @returns The memory profile as CSV, with the columns `class`, `count` and `size`,
+     * or null if there is no movie loaded.
+     */
+    memoryProfile(): string | null {
+        if (this.instance) {
+            return this.instance.memory_profile();
+        }
+        return null;
+    }
+
+    /**
+     * Downloads the current memory profile as a CSV file.
+     */
+    downloadMemoryProfile(): void {
+        const profile = this.memoryProfile();
+        if (profile !== null) {
+            this.saveFile(
+                new Blob([profile], { type: "text/csv" }),
+                "memory_profile.csv",
+            );
+        }
+    }
+
+    /**
+     * Saves the current state of the movie.
+     *
+     * This requires the `saveStates` option to be enabled.
+     *
+     * @returns A state that can be passed to `restoreState`, or null if
+     * save states aren't enabled or the movie hasn't loaded yet.
+     */
+    saveState(): SaveState | null {
+        if (this.instance) {
+            return this.instance.save_state() ?? null;
+        }
+        return null;
+    }
+
+    /**
+     * Restores a state that was saved from the same movie.
+     *
+     * The movie is reloaded and the state is replayed into it, which may
+     * take a while if the movie had been running for a long time.
+     *
+     * @param state A state returned by `saveState`.
+     */
+    async restoreState(state: SaveState): Promise<void> {
+        if (this.loadedConfig) {
+            this.pendingSaveState = state;
+            await this.load(this.loadedConfig);
+        }
+    }
+
+    /**
+     * Starts or stops recording frame timings and slow ActionScript calls.
+     *
+     * Starting a new profile discards the previous one.
+     *
+     * @param enabled Whether to record timings.
+     */
+    setProfiling(enabled: boolean): void {
+        if (this.instance) {
+            this.instance.set_profiling(enabled);
+            this.profiling = enabled;
+        }
+    }
+
+    /**
+     * Exports the timings recorded by the last profile.
+     *
+     * @returns The profile in the Chrome trace event format, which can be opened in
+     * chrome://tracing or Perfetto, or null if there is no movie loaded.
+     */
+    profileTrace(): string | null {
+        if (this.instance) {
+            return this.instance.profile_trace();
+        }
+        return null;
+    }
+
+    /**
+     * Downloads the timings recorded by the last profile as a Chrome trace file.
+     */
+    downloadProfileTrace(): void {
+        const trace = this.profileTrace();
+        if (trace !== null) {
+            this.saveFile(
+                new Blob([trace], { type: "application/json" }),
+                "ruffle_profile.json",
+            );
+        }
+    }
+
+    /**
+     * Takes a snapshot of the display list, starting from the stage.
+     *
+     * Display objects are found by their path from the stage: the index of
+     * each one among its parent's children.
+     *
+     * @returns The stage and everything below it, or null if there is no
+     * movie loaded.
+     */
+    displayTree(): DisplayNode | null {
+        if (this.instance) {
+            return this.instance.display_tree() ?? null;
+        }
+        return null;
+    }
+
+    /**
+     * Changes a property of a display object.
+     *
+     * @param path The path from the stage to the display object.
+     * @param property The property to change.
+     * @param value The new value, which is 0 or 1 for `visible`.
+     * @returns Whether the display object was found.
+     */
+    setDisplayProperty(
+        path: number[],
+        property: DisplayProperty,
+        value: number,
+    ): boolean {
+        if (this.instance) {
+            return this.instance.set_display_property(
+                new Uint32Array(path),
+                property,
+                value,
+            );
+        }
+        return false;
+    }
+
+    /**
+     * Outlines a display object on the stage.
+     *
+     * @param path The path from the stage to the display object, or null to
+     * remove the outline.
+     */
+    highlightDisplayObject(path: number[] | null): void {
+        if (this.instance) {
+            this.instance.highlight_display_object(
+                path ? new Uint32Array(path) : undefined,
+            );
+        }
+    }
+
+    /**
+     * Evaluates an ActionScript expression in the running movie.
+     *
+     * The console understands literals, names, member access, calls and
+     * assignments, such as `this.score = 100` or `_root.gotoAndStop(5)`.
+     *
+     * @param expression The expression to evaluate.
+     * @param path The path from the stage to the display object to evaluate
+     * the expression on, or null for the root movie.
+     * @returns The result of the expression, as text.
+     * @throws An error with a message if the expression couldn't be evaluated.
+     */
+    evaluate(expression: string, path: number[] | null = null): string {
+        if (!this.instance) {
+            throw new Error("No movie is loaded");
+        }
+        return this.instance.evaluate(
+            expression,
+            path ? new Uint32Array(path) : undefined,
+        );
+    }
+
+    /**
+     * Lists the ways that a partially typed expression could continue.
+     *
+     * @param expression The expression, ending with a partially typed name.
+     * @param path The path from the stage to the display object to complete
+     * the expression on, or null for the root movie.
+     * @returns Each possible expression with the name finished.
+     */
+    complete(expression: string, path: number[] | null = null): string[] {
+        if (this.instance) {
+            return (
+                this.instance.complete(
+                    expression,
+                    path ? new Uint32Array(path) : undefined,
+                ) ?? []
+            );
+        }
+        return [];
+    }
+
     private virtualKeyboardInput() {
         const input = this.virtualKeyboard;
         const string = input.value;
@@ -1255,6 +1539,44 @@ export class RufflePlayer extends HTMLElement {
             });
         }
 
+        if (isExtension && this.instance) {
+            if (this.profiling) {
+                items.push({
+                    text: text("context-menu-stop-profiling"),
+                    onClick: () => {
+                        this.setProfiling(false);
+                        this.downloadProfileTrace();
+                    },
+                });
+            } else {
+                items.push({
+                    text: text("context-menu-start-profiling"),
+                    onClick: () => this.setProfiling(true),
+                });
+            }
+            if (this.memoryProfiling) {
+                items.push({
+                    text: text("context-menu-download-memory-profile"),
+                    onClick: this.downloadMemoryProfile.bind(this),
+                });
+                items.push({
+                    text: text("context-menu-stop-memory-profile"),
+                    onClick: () => this.setMemoryProfiling(false),
+                });
+            } else {
+                items.push({
+                    text: text("context-menu-start-memory-profile"),
+                    onClick: () => this.setMemoryProfiling(true),
+                });
+            }
+        }
+
+        if (this.instance) {
+            items.push({
+                text: text("context-menu-compatibility-report"),
+                onClick: this.openCompatibilityReport.bind(this),
+            });
+        }
         if (window.isSecureContext) {
             items.push({
                 text: text("context-menu-copy-debug-info"),
@@ -1344,7 +1666,11 @@ export class RufflePlayer extends HTMLElement {
     }
 
     private showContextMenu(event: MouseEvent | PointerEvent): void {
-        if (this.panicked || !this.saveManager.classList.contains("hidden")) {
+        if (
+            this.panicked ||
+            !this.saveManager.classList.contains("hidden") ||
+            !this.compatibilityReportModal.classList.contains("hidden")
+        ) {
             return;
         }
 
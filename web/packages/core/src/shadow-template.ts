@@ -291,11 +291,12 @@ ruffleShadowTemplate.innerHTML = `
             height: 1px;
         }
 
-        #save-manager {
+        #save-manager,
+        #compatibility-report {
             height: inherit;
             user-select: text;
         }
-        #modal-area {
+        .modal-area {
             position: sticky;
             background: white;
             width: fit-content;
@@ -325,7 +326,7 @@ ruffleShadowTemplate.innerHTML = `
             color: white;
         }
 
-        #close-modal {
+        .close-modal {
             position: absolute;
             top: 5px;
             right: 10px;
@@ -361,6 +362,31 @@ ruffleShadowTemplate.innerHTML = `
         #local-saves tr:nth-child(even) {
             background-color: #f2f2f2;
         }
+
+        #compatibility-summary {
+            max-width: 480px;
+            padding-right: 16px;
+            padding-bottom: 8px;
+            border-bottom: 2px solid #888;
+        }
+
+        #compatibility-stubs {
+            border-collapse: collapse;
+            overflow-y: auto;
+            display: block;
+            height: calc(100% - 60px);
+        }
+
+        #compatibility-stubs td {
+            border-bottom: 1px solid #bbb;
+            height: 24px;
+            padding-right: 1em;
+            word-break: break-all;
+        }
+
+        #compatibility-stubs tr:nth-child(even) {
+            background-color: #f2f2f2;
+        }
     </style>
     <style id="dynamic_styles"></style>
 
@@ -379,8 +405,8 @@ ruffleShadowTemplate.innerHTML = `
     </div>
 
     <div id="save-manager" class="hidden">
-        <div id="modal-area">
-            <span id="close-modal">&times;</span>
+        <div class="modal-area">
+            <span class="close-modal">&times;</span>
             <div class="general-save-options">
                 <span class="save-option" id="backup-saves"></span>
             </div>
@@ -388,6 +414,14 @@ ruffleShadowTemplate.innerHTML = `
         </div>
     </div>
 
+    <div id="compatibility-report" class="hidden">
+        <div class="modal-area">
+            <span class="close-modal">&times;</span>
+            <p id="compatibility-summary"></p>
+            <table id="compatibility-stubs"></table>
+        </div>
+    </div>
+
     <div id="context-menu-overlay" class="hidden">
         <ul id="context-menu"></ul>
     </div>
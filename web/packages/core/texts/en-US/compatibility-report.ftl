@@ -0,0 +1,9 @@
+compatibility-report-stubs =
+    This content (SWF version {$version}, ActionScript {$avm}) has used these features,
+    which Ruffle doesn't fully support yet. They may be why it doesn't work as expected.
+compatibility-report-no-stubs = This content (SWF version {$version}, ActionScript {$avm}) hasn't used any features that Ruffle is known not to support.
+compatibility-report-count =
+    { $count ->
+        [one] Used once
+        *[other] Used {$count} times
+    }
\ No newline at end of file
@@ -1,5 +1,11 @@
 context-menu-download-swf = Download .swf
+context-menu-compatibility-report = Compatibility report
 context-menu-copy-debug-info = Copy debug info
+context-menu-start-profiling = Start profiling
+context-menu-stop-profiling = Stop profiling and download trace
+context-menu-start-memory-profile = Start memory profile
+context-menu-download-memory-profile = Download memory profile
+context-menu-stop-memory-profile = Stop memory profile
 context-menu-open-save-manager = Open Save Manager
 context-menu-about-ruffle =
     { $flavor ->
@@ -0,0 +1 @@
+chrome.devtools.panels.create("Ruffle", "images/icon32.png", "inspector.html");
@@ -0,0 +1,191 @@
+import type { DisplayNode, DisplayProperty } from "ruffle-core";
+
+// Finds the first Ruffle player in the inspected page. Polyfilled elements
+// may be registered under other names, so look for the player's methods.
+const FIND_PLAYER = `Array.from(document.querySelectorAll("*")).find(
+    (element) => typeof element.displayTree === "function"
+)`;
+
+let tree: HTMLUListElement;
+let search: HTMLInputElement;
+let properties: HTMLFormElement;
+let message: HTMLDivElement;
+
+let root: DisplayNode | null = null;
+let selectedPath: number[] | null = null;
+
+/**
+ * Runs a method of the player in the inspected page.
+ *
+ * @param method The name of the method.
+ * @param args The arguments, which must be serializable as JSON.
+ * @returns The result of the method, or null if there's no player.
+ */
+function callPlayer<T>(method: string, ...args: unknown[]): Promise<T | null> {
+    const call = `(() => {
+        const player = ${FIND_PLAYER};
+        return player ? player.${method}(...${JSON.stringify(args)}) : null;
+    })()`;
+    return new Promise((resolve, reject) => {
+        chrome.devtools.inspectedWindow.eval(
+            call,
+            (result: T | null, exception) => {
+                if (exception?.isError || exception?.isException) {
+                    reject(new Error(exception.value || exception.description));
+                } else {
+                    resolve(result);
+                }
+            },
+        );
+    });
+}
+
+function nodeLabel(node: DisplayNode): string {
+    return node.name ? `${node.kind} "${node.name}"` : node.kind;
+}
+
+function findNode(path: number[]): DisplayNode | null {
+    let node = root;
+    for (const index of path) {
+        node = node?.children[index] ?? null;
+    }
+    return node;
+}
+
+/**
+ * Whether this node or any below it matches the search.
+ *
+ * @param node The node to check.
+ * @param query The lowercase search text.
+ * @returns Whether the node should be shown.
+ */
+function matches(node: DisplayNode, query: string): boolean {
+    return (
+        nodeLabel(node).toLowerCase().includes(query) ||
+        node.children.some((child) => matches(child, query))
+    );
+}
+
+function buildTree(
+    node: DisplayNode,
+    path: number[],
+    query: string,
+): HTMLLIElement {
+    const item = document.createElement("li");
+    const label = document.createElement("div");
+    label.className = "node";
+    label.textContent = nodeLabel(node);
+    label.classList.toggle("invisible", !node.visible);
+    label.classList.toggle(
+        "match",
+        query !== "" && nodeLabel(node).toLowerCase().includes(query),
+    );
+    label.classList.toggle(
+        "selected",
+        selectedPath !== null && selectedPath.join() === path.join(),
+    );
+    label.addEventListener("mouseenter", () =>
+        callPlayer("highlightDisplayObject", path),
+    );
+    label.addEventListener("click", () => {
+        selectedPath = path;
+        render();
+    });
+    item.appendChild(label);
+
+    const children = node.children
+        .map((child, index) => ({ child, path: [...path, index] }))
+        .filter(({ child }) => query === "" || matches(child, query));
+    if (children.length > 0) {
+        const list = document.createElement("ul");
+        for (const { child, path } of children) {
+            list.appendChild(buildTree(child, path, query));
+        }
+        item.appendChild(list);
+    }
+    return item;
+}
+
+function showProperties(node: DisplayNode | null) {
+    properties.hidden = node === null;
+    if (node === null) {
+        return;
+    }
+    document.getElementById("selected-name")!.textContent = nodeLabel(node);
+    for (const input of properties.querySelectorAll<HTMLInputElement>(
+        "input[data-property]",
+    )) {
+        const property = input.dataset["property"] as DisplayProperty;
+        if (input.type === "checkbox") {
+            input.checked = node.visible;
+        } else if (document.activeElement !== input) {
+            input.value = String(node[property]);
+        }
+    }
+    const [xMin, yMin, xMax, yMax] = node.bounds;
+    document.getElementById("bounds")!.textContent =
+        `Bounds: (${xMin}, ${yMin}) to (${xMax}, ${yMax})`;
+    document.getElementById("filters")!.textContent =
+        node.filters.length > 0
+            ? `Filters: ${node.filters.join(", ")}`
+            : "No filters";
+}
+
+function render() {
+    tree.textContent = "";
+    if (root) {
+        tree.appendChild(buildTree(root, [], search.value.toLowerCase()));
+    }
+    showProperties(selectedPath ? findNode(selectedPath) : null);
+}
+
+async function refresh() {
+    try {
+        root = await callPlayer<DisplayNode>("displayTree");
+        message.textContent = root
+            ? ""
+            : "No Ruffle player with a movie was found.";
+    } catch (e) {
+        root = null;
+        message.textContent = String(e);
+    }
+    if (selectedPath && !findNode(selectedPath)) {
+        selectedPath = null;
+    }
+    render();
+}
+
+window.addEventListener("DOMContentLoaded", () => {
+    tree = document.getElementById("tree") as HTMLUListElement;
+    search = document.getElementById("search") as HTMLInputElement;
+    properties = document.getElementById("properties") as HTMLFormElement;
+    message = document.getElementById("message") as HTMLDivElement;
+
+    search.addEventListener("input", render);
+    document.getElementById("refresh")!.addEventListener("click", refresh);
+    tree.addEventListener("mouseleave", () =>
+        callPlayer("highlightDisplayObject", null),
+    );
+    properties.addEventListener("submit", (event) => event.preventDefault());
+    properties.addEventListener("change", async (event) => {
+        const input = event.target as HTMLInputElement;
+        const property = input.dataset["property"] as DisplayProperty;
+        const value =
+            input.type === "checkbox"
+                ? Number(input.checked)
+                : input.valueAsNumber;
+        if (selectedPath && !isNaN(value)) {
+            await callPlayer(
+                "setDisplayProperty",
+                selectedPath,
+                property,
+                value,
+            );
+            await refresh();
+        }
+    });
+
+    refresh();
+    // Keep the panel in step with the movie, which changes on its own.
+    setInterval(refresh, 1000);
+});
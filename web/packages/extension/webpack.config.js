@@ -98,6 +98,8 @@ export default function (env, _argv) {
             background: "./src/background.ts",
             player: "./src/player.ts",
             pluginPolyfill: "./src/plugin-polyfill.ts",
+            devtools: "./src/devtools.ts",
+            inspector: "./src/inspector.ts",
         },
         output: {
             path: url.fileURLToPath(new URL("assets/dist/", import.meta.url)),
@@ -1,6 +1,6 @@
 use ruffle_core::backend::audio::{
-    swf, AudioBackend, AudioMixer, AudioMixerProxy, DecodeError, RegisterError, SoundHandle,
-    SoundInstanceHandle, SoundTransform,
+    swf, AudioBackend, AudioMixer, AudioMixerProxy, DecodeError, MicrophoneState, RegisterError,
+    SoundHandle, SoundInstanceHandle, SoundTransform,
 };
 use ruffle_core::impl_audio_mixer_backend;
 use ruffle_web_common::JsResult;
@@ -12,7 +12,11 @@ use tracing_subscriber::layer::Layered;
 use tracing_subscriber::Registry;
 use tracing_wasm::WASMLayer;
 use wasm_bindgen::{closure::Closure, prelude::*, JsCast};
-use web_sys::AudioContext;
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    AudioContext, AudioProcessingEvent, MediaStream, MediaStreamAudioSourceNode,
+    MediaStreamConstraints, ScriptProcessorNode,
+};
 
 #[allow(dead_code)]
 pub struct WebAudioBackend {
@@ -26,6 +30,8 @@ pub struct WebAudioBackend {
     /// For how many seconds were we able to continuously fill the next buffer "at a sufficiently early time".
     probation_elapsed: Rc<Cell<f32>>,
     log_subscriber: Arc<Layered<WASMLayer, Registry>>,
+    /// The capture of the microphone, if any.
+    microphone: Option<Rc<Microphone>>,
 }
 
 impl WebAudioBackend {
@@ -59,6 +65,7 @@ impl WebAudioBackend {
             time: Rc::new(Cell::new(0.0)),
             probation_elapsed: Rc::new(Cell::new(0.0)),
             log_subscriber,
+            microphone: None,
         };
 
         // Create and start the audio buffers.
@@ -94,6 +101,160 @@ impl AudioBackend for WebAudioBackend {
             f64::from(self.buffer_size.get()) / f64::from(self.context.sample_rate()),
         ))
     }
+
+    fn microphone_names(&self) -> Vec<String> {
+        // Listing devices is asynchronous and needs permission first,
+        // so we only offer the browser's default microphone.
+        let has_media_devices = web_sys::window()
+            .and_then(|window| window.navigator().media_devices().ok())
+            .is_some();
+        if has_media_devices {
+            vec!["Default".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn start_microphone(&mut self, _index: usize) {
+        match Microphone::start(&self.context) {
+            Ok(microphone) => self.microphone = Some(microphone),
+            Err(e) => {
+                tracing::error!("Couldn't capture from microphone: {:?}", e);
+                self.microphone = None;
+            }
+        }
+    }
+
+    fn stop_microphone(&mut self) {
+        self.microphone = None;
+    }
+
+    fn microphone_state(&self) -> MicrophoneState {
+        self.microphone
+            .as_ref()
+            .map_or(MicrophoneState::Unavailable, |microphone| {
+                microphone.state.get()
+            })
+    }
+
+    fn take_microphone_samples(&mut self) -> (u32, Vec<f32>) {
+        let samples = self
+            .microphone
+            .as_ref()
+            .map(|microphone| microphone.samples.take())
+            .unwrap_or_default();
+        (self.context.sample_rate() as u32, samples)
+    }
+}
+
+/// Audio being captured from the microphone through `getUserMedia`.
+struct Microphone {
+    context: AudioContext,
+    state: Cell<MicrophoneState>,
+    /// The mono samples captured since they were last taken.
+    samples: RefCell<Vec<f32>>,
+    /// The stream and the nodes that capture it, once the user has granted access.
+    capture: RefCell<Option<(MediaStream, MediaStreamAudioSourceNode, ScriptProcessorNode)>>,
+    on_audio_process: RefCell<Option<Closure<dyn FnMut(AudioProcessingEvent)>>>,
+}
+
+impl Microphone {
+    /// The number of samples captured in each `audioprocess` event.
+    const BUFFER_SIZE: u32 = 4096;
+
+    /// Asks the user for access to the microphone, and starts capturing once it's granted.
+    fn start(context: &AudioContext) -> Result<Rc<Self>, JsValue> {
+        let window = web_sys::window().ok_or("No window")?;
+        let promise = window
+            .navigator()
+            .media_devices()?
+            .get_user_media_with_constraints(MediaStreamConstraints::new().audio(&true.into()))?;
+
+        let microphone = Rc::new(Self {
+            context: context.clone(),
+            state: Cell::new(MicrophoneState::Pending),
+            samples: RefCell::new(Vec::new()),
+            capture: RefCell::new(None),
+            on_audio_process: RefCell::new(None),
+        });
+
+        // Hold a weak reference, so that stopping the capture while the
+        // browser's permission prompt is open doesn't leave it running.
+        let weak = Rc::downgrade(&microphone);
+        spawn_local(async move {
+            let result = JsFuture::from(promise).await;
+            let Some(microphone) = weak.upgrade() else {
+                if let Ok(stream) = result {
+                    stop_tracks(&stream.unchecked_into());
+                }
+                return;
+            };
+            match result {
+                Ok(stream) => {
+                    if let Err(e) = microphone.capture(stream.unchecked_into()) {
+                        tracing::error!("Couldn't capture from microphone: {:?}", e);
+                        microphone.state.set(MicrophoneState::Unavailable);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Microphone access was denied: {:?}", e);
+                    microphone.state.set(MicrophoneState::Denied);
+                }
+            }
+        });
+
+        Ok(microphone)
+    }
+
+    fn capture(self: &Rc<Self>, stream: MediaStream) -> Result<(), JsValue> {
+        let source = self.context.create_media_stream_source(&stream)?;
+        let processor = self
+            .context
+            .create_script_processor_with_buffer_size_and_number_of_input_channels_and_number_of_output_channels(
+                Self::BUFFER_SIZE,
+                1,
+                1,
+            )?;
+
+        let weak = Rc::downgrade(self);
+        let on_audio_process = Closure::new(move |event: AudioProcessingEvent| {
+            if let (Some(microphone), Ok(buffer)) = (weak.upgrade(), event.input_buffer()) {
+                if let Ok(data) = buffer.get_channel_data(0) {
+                    microphone.samples.borrow_mut().extend_from_slice(&data);
+                }
+            }
+        });
+        processor.set_onaudioprocess(Some(on_audio_process.as_ref().unchecked_ref()));
+
+        // The processor only runs while it's connected to the output. It outputs silence.
+        source.connect_with_audio_node(&processor)?;
+        processor.connect_with_audio_node(&self.context.destination())?;
+
+        *self.on_audio_process.borrow_mut() = Some(on_audio_process);
+        *self.capture.borrow_mut() = Some((stream, source, processor));
+        self.state.set(MicrophoneState::Allowed);
+        Ok(())
+    }
+}
+
+impl Drop for Microphone {
+    fn drop(&mut self) {
+        if let Some((stream, source, processor)) = self.capture.take() {
+            processor.set_onaudioprocess(None);
+            let _ = source.disconnect();
+            let _ = processor.disconnect();
+            stop_tracks(&stream);
+        }
+    }
+}
+
+/// Stops every track of a `MediaStream`, which releases the device.
+pub(crate) fn stop_tracks(stream: &MediaStream) {
+    for track in stream.get_tracks().iter() {
+        if let Ok(track) = track.dyn_into::<web_sys::MediaStreamTrack>() {
+            track.stop();
+        }
+    }
 }
 
 impl Drop for WebAudioBackend {
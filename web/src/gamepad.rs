@@ -0,0 +1,112 @@
+//! Gamepad input through the Gamepad API, which has to be polled.
+
+use js_sys::Array;
+use ruffle_core::events::{GamepadAxis, GamepadButton};
+use ruffle_core::PlayerEvent;
+use std::collections::HashMap;
+use wasm_bindgen::JsCast;
+use web_sys::Gamepad;
+
+/// The buttons of the standard gamepad layout, in the order of `Gamepad.buttons`.
+const BUTTONS: [GamepadButton; 16] = [
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::West,
+    GamepadButton::North,
+    GamepadButton::LeftShoulder,
+    GamepadButton::RightShoulder,
+    GamepadButton::LeftTrigger,
+    GamepadButton::RightTrigger,
+    GamepadButton::Select,
+    GamepadButton::Start,
+    GamepadButton::LeftStick,
+    GamepadButton::RightStick,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+];
+
+/// The axes of the standard gamepad layout, in the order of `Gamepad.axes`.
+const AXES: [GamepadAxis; 4] = [
+    GamepadAxis::LeftStickX,
+    GamepadAxis::LeftStickY,
+    GamepadAxis::RightStickX,
+    GamepadAxis::RightStickY,
+];
+
+/// What a gamepad looked like when it was last polled.
+#[derive(Default)]
+struct GamepadState {
+    buttons: [bool; BUTTONS.len()],
+    axes: [f64; AXES.len()],
+}
+
+#[derive(Default)]
+pub struct GamepadInput {
+    /// The connected gamepads, by their `Gamepad.index`.
+    gamepads: HashMap<u32, GamepadState>,
+}
+
+impl GamepadInput {
+    /// Returns the events of the gamepads since the last call.
+    pub fn poll(&mut self) -> Vec<PlayerEvent> {
+        let gamepads = web_sys::window()
+            .and_then(|window| window.navigator().get_gamepads().ok())
+            .unwrap_or_else(Array::new);
+
+        let mut events = vec![];
+        let mut connected = HashMap::new();
+        for gamepad in gamepads.iter() {
+            let Ok(gamepad) = gamepad.dyn_into::<Gamepad>() else {
+                continue;
+            };
+            if !gamepad.connected() {
+                continue;
+            }
+
+            let id = gamepad.index();
+            let previous = self.gamepads.remove(&id).unwrap_or_else(|| {
+                events.push(PlayerEvent::GamepadConnected {
+                    id,
+                    name: gamepad.id(),
+                });
+                GamepadState::default()
+            });
+
+            let mut state = GamepadState::default();
+            for (i, button) in gamepad.buttons().iter().take(BUTTONS.len()).enumerate() {
+                let pressed = button
+                    .dyn_into::<web_sys::GamepadButton>()
+                    .map_or(false, |button| button.pressed());
+                state.buttons[i] = pressed;
+                if pressed != previous.buttons[i] {
+                    let button = BUTTONS[i];
+                    events.push(if pressed {
+                        PlayerEvent::GamepadButtonDown { id, button }
+                    } else {
+                        PlayerEvent::GamepadButtonUp { id, button }
+                    });
+                }
+            }
+            for (i, value) in gamepad.axes().iter().take(AXES.len()).enumerate() {
+                let value = value.as_f64().unwrap_or_default();
+                state.axes[i] = value;
+                if value != previous.axes[i] {
+                    events.push(PlayerEvent::GamepadAxisChanged {
+                        id,
+                        axis: AXES[i],
+                        value,
+                    });
+                }
+            }
+            connected.insert(id, state);
+        }
+
+        for &id in self.gamepads.keys() {
+            events.push(PlayerEvent::GamepadDisconnected { id });
+        }
+        self.gamepads = connected;
+        events
+    }
+}
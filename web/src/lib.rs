@@ -2,6 +2,8 @@
 
 //! Ruffle web frontend.
 mod audio;
+mod gamepad;
+mod local_connection;
 mod log_adapter;
 mod navigator;
 mod storage;
@@ -9,15 +11,19 @@ mod ui;
 
 use generational_arena::{Arena, Index};
 use js_sys::{Array, Error as JsError, Function, Object, Promise, Uint8Array};
-use ruffle_core::backend::navigator::OpenURLMode;
+use ruffle_core::backend::navigator::{JavascriptUrlMode, NavigationPolicy, OpenURLMode};
 use ruffle_core::compatibility_rules::CompatibilityRules;
-use ruffle_core::config::{Letterbox, NetworkingAccessMode};
+use ruffle_core::config::{FrameRateMode, Letterbox, NetworkingAccessMode};
 use ruffle_core::context::UpdateContext;
-use ruffle_core::events::{KeyCode, MouseButton, MouseWheelDelta, TextControlCode};
+use ruffle_core::events::{
+    GamepadButton, KeyCode, KeyLocation, MouseButton, MouseWheelDelta, TextControlCode, TouchPhase,
+    TouchPoint,
+};
 use ruffle_core::external::{
     ExternalInterfaceMethod, ExternalInterfaceProvider, FsCommandProvider, Value as ExternalValue,
     Value,
 };
+use ruffle_core::inspector::DisplayProperty;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{
     Color, Player, PlayerBuilder, PlayerEvent, SandboxType, StageAlign, StageScaleMode,
@@ -27,7 +33,7 @@ use ruffle_render::quality::StageQuality;
 use ruffle_video_software::backend::SoftwareVideoBackend;
 use ruffle_web_common::JsResult;
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::Once;
@@ -40,8 +46,8 @@ use tracing_wasm::{WASMLayer, WASMLayerConfigBuilder};
 use url::Url;
 use wasm_bindgen::{prelude::*, JsCast, JsValue};
 use web_sys::{
-    AddEventListenerOptions, ClipboardEvent, Element, Event, EventTarget, HtmlCanvasElement,
-    HtmlElement, KeyboardEvent, PointerEvent, WheelEvent, Window,
+    AddEventListenerOptions, ClipboardEvent, CompositionEvent, Element, Event, EventTarget,
+    HtmlCanvasElement, HtmlElement, KeyboardEvent, PointerEvent, WheelEvent, Window,
 };
 
 static RUFFLE_GLOBAL_PANIC: Once = Once::new();
@@ -81,8 +87,10 @@ struct RuffleInstance {
     key_down_callback: Option<Closure<dyn FnMut(KeyboardEvent)>>,
     key_up_callback: Option<Closure<dyn FnMut(KeyboardEvent)>>,
     paste_callback: Option<Closure<dyn FnMut(ClipboardEvent)>>,
+    composition_callback: Option<Closure<dyn FnMut(CompositionEvent)>>,
     unload_callback: Option<Closure<dyn FnMut(Event)>>,
     has_focus: bool,
+    gamepads: gamepad::GamepadInput,
     trace_observer: Rc<RefCell<JsValue>>,
     log_subscriber: Arc<Layered<WASMLayer, Registry>>,
 }
@@ -271,6 +279,10 @@ struct Config {
 
     frame_rate: Option<f64>,
 
+    frame_rate_mode: FrameRateMode,
+
+    save_states: bool,
+
     wmode: Option<String>,
 
     #[serde(deserialize_with = "deserialize_log_level")]
@@ -285,7 +297,17 @@ struct Config {
 
     open_url_mode: OpenURLMode,
 
+    open_url_allow: Vec<String>,
+
+    open_url_deny: Vec<String>,
+
+    javascript_url_mode: JavascriptUrlMode,
+
     allow_networking: NetworkingAccessMode,
+
+    socket_proxy: Vec<navigator::SocketProxy>,
+
+    gamepad_button_mapping: HashMap<GamepadButton, u8>,
 }
 
 /// Metadata about the playing SWF file to be passed back to JavaScript.
@@ -310,6 +332,10 @@ struct MovieMetadata {
 #[derive(Clone, Copy)]
 pub struct Ruffle(Index);
 
+/// A saved state of a movie, which is opaque to JS.
+#[wasm_bindgen]
+pub struct SaveState(ruffle_core::save_state::SaveState);
+
 #[wasm_bindgen]
 impl Ruffle {
     #[allow(clippy::new_ret_no_self)]
@@ -419,6 +445,82 @@ impl Ruffle {
             .unwrap_or(JsValue::NULL)
     }
 
+    pub fn set_memory_profiling(&mut self, profiling: bool) {
+        let _ = self.with_core_mut(|core| core.set_memory_profiling(profiling));
+    }
+
+    pub fn memory_profile(&mut self) -> JsValue {
+        self.with_core_mut(|core| JsValue::from_str(&core.memory_profile().to_csv()))
+            .unwrap_or(JsValue::NULL)
+    }
+
+    pub fn save_state(&self) -> Option<SaveState> {
+        self.with_core(|core| core.save_state())
+            .ok()
+            .flatten()
+            .map(SaveState)
+    }
+
+    pub fn restore_state(&mut self, state: &SaveState) {
+        let _ = self.with_core_mut(|core| core.restore_state(state.0.clone()));
+    }
+
+    pub fn set_profiling(&mut self, profiling: bool) {
+        let _ = self.with_core_mut(|core| core.set_profiling(profiling));
+    }
+
+    pub fn profile_trace(&self) -> JsValue {
+        self.with_core(|core| JsValue::from_str(&core.profiler().to_chrome_trace()))
+            .unwrap_or(JsValue::NULL)
+    }
+
+    pub fn compatibility_report(&self) -> JsValue {
+        self.with_core(|core| {
+            serde_wasm_bindgen::to_value(&core.compatibility_report()).unwrap_or(JsValue::UNDEFINED)
+        })
+        .unwrap_or(JsValue::UNDEFINED)
+    }
+
+    pub fn display_tree(&mut self) -> JsValue {
+        self.with_core_mut(|core| {
+            let tree = core.inspect_display_tree();
+            serde_wasm_bindgen::to_value(&tree).unwrap_or(JsValue::UNDEFINED)
+        })
+        .unwrap_or(JsValue::UNDEFINED)
+    }
+
+    pub fn set_display_property(
+        &mut self,
+        path: Vec<usize>,
+        property: JsValue,
+        value: f64,
+    ) -> bool {
+        let Ok(property) = serde_wasm_bindgen::from_value::<DisplayProperty>(property) else {
+            return false;
+        };
+        self.with_core_mut(|core| core.set_display_property(&path, property, value))
+            .unwrap_or_default()
+    }
+
+    pub fn highlight_display_object(&mut self, path: Option<Vec<usize>>) {
+        let _ = self.with_core_mut(|core| core.set_inspector_highlight(path));
+    }
+
+    pub fn evaluate(&mut self, input: &str, path: Option<Vec<usize>>) -> Result<String, JsValue> {
+        self.with_core_mut(|core| core.console_evaluate(path.as_deref(), input))
+            .map_err(|e| e.to_string())
+            .and_then(|result| result)
+            .map_err(|e| JsValue::from_str(&e))
+    }
+
+    pub fn complete(&mut self, input: &str, path: Option<Vec<usize>>) -> JsValue {
+        self.with_core_mut(|core| {
+            let completions = core.console_complete(path.as_deref(), input);
+            serde_wasm_bindgen::to_value(&completions).unwrap_or(JsValue::UNDEFINED)
+        })
+        .unwrap_or(JsValue::UNDEFINED)
+    }
+
     // after the context menu is closed, remember to call `clear_custom_menu_items`!
     pub fn prepare_context_menu(&mut self) -> JsValue {
         self.with_core_mut(|core| {
@@ -446,8 +548,16 @@ impl Ruffle {
         // Instance is dropped at this point.
     }
 
+    /// Calls a callback added with `ExternalInterface.addCallback`.
+    ///
+    /// If the callback throws and `ExternalInterface.marshallExceptions` is enabled,
+    /// the error is thrown in JavaScript.
     #[allow(clippy::boxed_local)] // for js_bind
-    pub fn call_exposed_callback(&self, name: &str, args: Box<[JsValue]>) -> JsValue {
+    pub fn call_exposed_callback(
+        &self,
+        name: &str,
+        args: Box<[JsValue]>,
+    ) -> Result<JsValue, JsValue> {
         let args: Vec<ExternalValue> = args.iter().map(js_to_external_value).collect();
 
         // Re-entrant callbacks need to return through the hole that was punched through for them
@@ -457,13 +567,18 @@ impl Ruffle {
         if let Some(context) = CURRENT_CONTEXT.with(|v| *v.borrow()) {
             unsafe {
                 if let Some(callback) = (*context).external_interface.get_callback(name) {
-                    return external_to_js_value(callback.call(&mut *context, name, args));
+                    return callback
+                        .try_call(&mut *context, name, args)
+                        .map(external_to_js_value)
+                        .map_err(|message| js_sys::Error::new(&message).into());
                 }
             }
         }
 
-        self.with_core_mut(|core| external_to_js_value(core.call_internal_interface(name, args)))
-            .unwrap_or(JsValue::UNDEFINED)
+        self.with_core_mut(|core| core.try_call_internal_interface(name, args))
+            .unwrap_or(Ok(ExternalValue::Undefined))
+            .map(external_to_js_value)
+            .map_err(|message| js_sys::Error::new(&message).into())
     }
 
     pub fn set_trace_observer(&self, observer: JsValue) {
@@ -538,7 +653,13 @@ impl Ruffle {
             config.upgrade_to_https,
             config.base_url,
             log_subscriber.clone(),
-            config.open_url_mode,
+            NavigationPolicy {
+                open_url_mode: config.open_url_mode,
+                allow: config.open_url_allow,
+                deny: config.open_url_deny,
+                javascript: config.javascript_url_mode,
+            },
+            config.socket_proxy,
         ));
 
         match window.local_storage() {
@@ -599,6 +720,15 @@ impl Ruffle {
                 config.force_scale,
             )
             .with_frame_rate(config.frame_rate)
+            .with_frame_rate_mode(config.frame_rate_mode)
+            .with_save_states(config.save_states)
+            .with_gamepad_button_mapping(
+                config
+                    .gamepad_button_mapping
+                    .into_iter()
+                    .filter_map(|(button, key)| Some((button, KeyCode::from_u8(key)?)))
+                    .collect(),
+            )
             // FIXME - should this be configurable?
             .with_sandbox_type(SandboxType::Remote)
             .build();
@@ -634,10 +764,12 @@ impl Ruffle {
             mouse_wheel_callback: None,
             key_down_callback: None,
             key_up_callback: None,
+            composition_callback: None,
             paste_callback: None,
             unload_callback: None,
             timestamp: None,
             has_focus: false,
+            gamepads: Default::default(),
             trace_observer,
             log_subscriber,
         };
@@ -660,6 +792,9 @@ impl Ruffle {
             // Create mouse move handler.
             let mouse_move_callback = Closure::new(move |js_event: PointerEvent| {
                 let _ = ruffle.with_instance(move |instance| {
+                    if !instance.handle_touch(&js_event, TouchPhase::Move) {
+                        return;
+                    }
                     let event = PlayerEvent::MouseMove {
                         x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
                         y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
@@ -724,6 +859,10 @@ impl Ruffle {
                             .unchecked_ref::<Element>()
                             .set_pointer_capture(js_event.pointer_id());
                     }
+                    if !instance.handle_touch(&js_event, TouchPhase::Begin) {
+                        js_event.prevent_default();
+                        return;
+                    }
                     let device_pixel_ratio = instance.device_pixel_ratio;
                     let event = PlayerEvent::MouseDown {
                         x: f64::from(js_event.offset_x()) * device_pixel_ratio,
@@ -795,6 +934,9 @@ impl Ruffle {
                             .unchecked_ref::<Element>()
                             .release_pointer_capture(js_event.pointer_id());
                     }
+                    if !instance.handle_touch(&js_event, TouchPhase::End) {
+                        return;
+                    }
                     let event = PlayerEvent::MouseUp {
                         x: f64::from(js_event.offset_x()) * instance.device_pixel_ratio,
                         y: f64::from(js_event.offset_y()) * instance.device_pixel_ratio,
@@ -852,13 +994,24 @@ impl Ruffle {
             // Create keydown event handler.
             let key_down_callback = Closure::new(move |js_event: KeyboardEvent| {
                 let _ = ruffle.with_instance(|instance| {
-                    if instance.has_focus {
+                    // Keys pressed while composing text belong to the input method.
+                    if instance.has_focus && !js_event.is_composing() {
                         let mut paste_event = false;
                         let _ = instance.with_core_mut(|core| {
-                            let key_code = web_to_ruffle_key_code(&js_event.code());
+                            let key_code =
+                                web_to_ruffle_key_code(&js_event.key(), &js_event.code());
                             let key_char = web_key_to_codepoint(&js_event.key());
+                            let key_location = web_to_ruffle_key_location(js_event.location());
                             let is_ctrl_cmd = js_event.ctrl_key() || js_event.meta_key();
-                            core.handle_event(PlayerEvent::KeyDown { key_code, key_char });
+                            core.set_lock_state(
+                                js_event.get_modifier_state("CapsLock"),
+                                js_event.get_modifier_state("NumLock"),
+                            );
+                            core.handle_event(PlayerEvent::KeyDown {
+                                key_code,
+                                key_char,
+                                key_location,
+                            });
 
                             if let Some(control_code) = web_to_ruffle_text_control(
                                 &js_event.key(),
@@ -924,9 +1077,19 @@ impl Ruffle {
                 let _ = ruffle.with_instance(|instance| {
                     if instance.has_focus {
                         let _ = instance.with_core_mut(|core| {
-                            let key_code = web_to_ruffle_key_code(&js_event.code());
+                            let key_code =
+                                web_to_ruffle_key_code(&js_event.key(), &js_event.code());
                             let key_char = web_key_to_codepoint(&js_event.key());
-                            core.handle_event(PlayerEvent::KeyUp { key_code, key_char });
+                            let key_location = web_to_ruffle_key_location(js_event.location());
+                            core.set_lock_state(
+                                js_event.get_modifier_state("CapsLock"),
+                                js_event.get_modifier_state("NumLock"),
+                            );
+                            core.handle_event(PlayerEvent::KeyUp {
+                                key_code,
+                                key_char,
+                                key_location,
+                            });
                         });
                         js_event.prevent_default();
                     }
@@ -938,6 +1101,43 @@ impl Ruffle {
                 .warn_on_error();
             instance.key_up_callback = Some(key_up_callback);
 
+            // Create input method composition event handler.
+            let composition_callback = Closure::new(move |js_event: CompositionEvent| {
+                let _ = ruffle.with_instance(|instance| {
+                    if instance.has_focus {
+                        let _ = instance.with_core_mut(|core| {
+                            let text = js_event.data().unwrap_or_default();
+                            match js_event.type_().as_str() {
+                                "compositionstart" => {
+                                    core.handle_event(PlayerEvent::ImeCompositionStart)
+                                }
+                                "compositionupdate" => {
+                                    let cursor = Some((text.len(), text.len()));
+                                    core.handle_event(PlayerEvent::ImeCompositionUpdate {
+                                        text,
+                                        cursor,
+                                    })
+                                }
+                                "compositionend" => {
+                                    core.handle_event(PlayerEvent::ImeCompositionEnd { text })
+                                }
+                                _ => (),
+                            }
+                        });
+                    }
+                });
+            });
+
+            for event in ["compositionstart", "compositionupdate", "compositionend"] {
+                window
+                    .add_event_listener_with_callback(
+                        event,
+                        composition_callback.as_ref().unchecked_ref(),
+                    )
+                    .warn_on_error();
+            }
+            instance.composition_callback = Some(composition_callback);
+
             let unload_callback = Closure::new(move |_| {
                 let _ = ruffle.with_core_mut(|core| {
                     core.flush_shared_objects();
@@ -1097,7 +1297,10 @@ impl Ruffle {
     fn tick(&mut self, timestamp: f64) {
         let mut dt = 0.0;
         let mut new_dimensions = None;
+        let mut gamepad_events = vec![];
         let _ = self.with_instance_mut(|instance| {
+            gamepad_events = instance.gamepads.poll();
+
             // Check for canvas resize.
             let canvas_width = instance.canvas.client_width();
             let canvas_height = instance.canvas.client_height();
@@ -1160,6 +1363,9 @@ impl Ruffle {
                 });
             }
 
+            for event in gamepad_events {
+                core.handle_event(event);
+            }
             core.tick(dt);
 
             // Render if the core signals a new frame, or if we resized.
@@ -1224,6 +1430,32 @@ impl RuffleInstance {
         }
         ret
     }
+
+    /// Reports a pointer event from a touchscreen as a touch point.
+    ///
+    /// Returns whether the event should also be reported as a mouse event,
+    /// which is the case for all pointers other than secondary touch points.
+    fn handle_touch(&self, js_event: &PointerEvent, phase: TouchPhase) -> bool {
+        if js_event.pointer_type() != "touch" {
+            return true;
+        }
+
+        let device_pixel_ratio = self.device_pixel_ratio;
+        let point = TouchPoint {
+            id: js_event.pointer_id(),
+            x: f64::from(js_event.offset_x()) * device_pixel_ratio,
+            y: f64::from(js_event.offset_y()) * device_pixel_ratio,
+            pressure: Some(f64::from(js_event.pressure())),
+            size: Some((
+                js_event.width() * device_pixel_ratio,
+                js_event.height() * device_pixel_ratio,
+            )),
+        };
+        let _ = self.with_core_mut(|core| {
+            core.handle_event(PlayerEvent::Touch { phase, point });
+        });
+        js_event.is_primary()
+    }
 }
 
 impl Drop for RuffleInstance {
@@ -1326,6 +1558,16 @@ impl Drop for RuffleInstance {
                 )
                 .warn_on_error();
         }
+        if let Some(composition_callback) = self.composition_callback.take() {
+            for event in ["compositionstart", "compositionupdate", "compositionend"] {
+                self.window
+                    .remove_event_listener_with_callback(
+                        event,
+                        composition_callback.as_ref().unchecked_ref(),
+                    )
+                    .warn_on_error();
+            }
+        }
         if let Some(unload_callback) = self.unload_callback.take() {
             self.window
                 .remove_event_listener_with_callback(
@@ -1364,7 +1606,11 @@ struct JavascriptMethod {
 }
 
 impl ExternalInterfaceMethod for JavascriptMethod {
-    fn call(&self, context: &mut UpdateContext<'_, '_>, args: &[ExternalValue]) -> ExternalValue {
+    fn call(
+        &self,
+        context: &mut UpdateContext<'_, '_>,
+        args: &[ExternalValue],
+    ) -> Result<ExternalValue, String> {
         let old_context = CURRENT_CONTEXT.with(|v| {
             v.replace(Some(unsafe {
                 std::mem::transmute::<&mut UpdateContext, &mut UpdateContext<'static, 'static>>(
@@ -1377,13 +1623,12 @@ impl ExternalInterfaceMethod for JavascriptMethod {
             for arg in args {
                 args_array.push(&external_to_js_value(arg.to_owned()));
             }
-            if let Ok(result) = function.apply(&self.this, &args_array) {
-                js_to_external_value(&result)
-            } else {
-                ExternalValue::Undefined
+            match function.apply(&self.this, &args_array) {
+                Ok(result) => Ok(js_to_external_value(&result)),
+                Err(error) => Err(js_error_message(&error)),
             }
         } else {
-            ExternalValue::Undefined
+            Ok(ExternalValue::Undefined)
         };
         CURRENT_CONTEXT.with(|v| v.replace(old_context));
         result
@@ -1450,6 +1695,17 @@ impl FsCommandProvider for JavascriptInterface {
     }
 }
 
+/// Describes an exception thrown by JavaScript, for when it's rethrown in ActionScript.
+fn js_error_message(error: &JsValue) -> String {
+    if let Some(error) = error.dyn_ref::<js_sys::Error>() {
+        String::from(error.message())
+    } else if let Some(message) = error.as_string() {
+        message
+    } else {
+        format!("{error:?}")
+    }
+}
+
 fn js_to_external_value(js: &JsValue) -> ExternalValue {
     if let Some(value) = js.as_f64() {
         ExternalValue::Number(value)
@@ -1684,10 +1940,20 @@ fn parse_movie_parameters(input: &JsValue) -> Vec<(String, String)> {
     params
 }
 
-/// Convert a web `KeyboardEvent.code` value into a Ruffle `KeyCode`.
+/// Convert a web `KeyboardEvent.key` and `KeyboardEvent.code` pair into a Ruffle `KeyCode`.
 /// Return `KeyCode::Unknown` if there is no matching Flash key code.
-fn web_to_ruffle_key_code(key_code: &str) -> KeyCode {
-    match key_code {
+///
+/// Like in Flash, letter keys are identified by the letter that they type in the user's
+/// keyboard layout, and other keys by their position.
+fn web_to_ruffle_key_code(key: &str, code: &str) -> KeyCode {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c.is_ascii_alphabetic() {
+            return KeyCode::from_u8(c.to_ascii_uppercase() as u8).unwrap_or(KeyCode::Unknown);
+        }
+    }
+
+    match code {
         "Backspace" => KeyCode::Backspace,
         "Tab" => KeyCode::Tab,
         "Enter" => KeyCode::Return,
@@ -1695,6 +1961,7 @@ fn web_to_ruffle_key_code(key_code: &str) -> KeyCode {
         "ControlLeft" | "ControlRight" => KeyCode::Control,
         "AltLeft" | "AltRight" => KeyCode::Alt,
         "CapsLock" => KeyCode::CapsLock,
+        "NumLock" => KeyCode::NumLock,
         "Escape" => KeyCode::Escape,
         "Space" => KeyCode::Space,
         "Digit0" => KeyCode::Key0,
@@ -1788,6 +2055,16 @@ fn web_to_ruffle_key_code(key_code: &str) -> KeyCode {
     }
 }
 
+/// Convert a web `KeyboardEvent.location` value into a Ruffle `KeyLocation`.
+fn web_to_ruffle_key_location(location: u32) -> KeyLocation {
+    match location {
+        KeyboardEvent::DOM_KEY_LOCATION_LEFT => KeyLocation::Left,
+        KeyboardEvent::DOM_KEY_LOCATION_RIGHT => KeyLocation::Right,
+        KeyboardEvent::DOM_KEY_LOCATION_NUMPAD => KeyLocation::NumPad,
+        _ => KeyLocation::Standard,
+    }
+}
+
 /// Convert a web `KeyboardEvent.key` value into a character codepoint.
 /// Return `None` if they input was not a printable character.
 fn web_key_to_codepoint(key: &str) -> Option<char> {
@@ -0,0 +1,131 @@
+//! `LocalConnection` messages between players on pages of the same origin.
+//!
+//! Players share a `BroadcastChannel`, on which they announce the connection names that
+//! they listen with, so that each player knows which names are taken by the others.
+
+use js_sys::{Array, Uint8Array};
+use ruffle_core::local_connection::LocalConnectionMessage;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use std::sync::mpsc::Sender;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::{BroadcastChannel, MessageEvent};
+
+const CHANNEL_NAME: &str = "ruffle-localconnection";
+
+/// Asks the other players to announce the names that they listen with.
+const QUERY: &str = "query";
+/// A player started listening with a name.
+const CONNECT: &str = "connect";
+/// A player stopped listening with a name.
+const CLOSE: &str = "close";
+/// A message sent to the player that listens with a name.
+const MESSAGE: &str = "message";
+
+#[derive(Default)]
+struct State {
+    /// The names that this player listens with.
+    listening: HashMap<String, Sender<LocalConnectionMessage>>,
+
+    /// The names that other players listen with.
+    remote: HashSet<String>,
+}
+
+pub struct LocalConnectionChannel {
+    channel: BroadcastChannel,
+    state: Rc<RefCell<State>>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+}
+
+impl LocalConnectionChannel {
+    pub fn new() -> Result<Self, JsValue> {
+        let channel = BroadcastChannel::new(CHANNEL_NAME)?;
+        let state = Rc::new(RefCell::new(State::default()));
+
+        let on_message = {
+            let channel = channel.clone();
+            let state = state.clone();
+            Closure::new(move |event: MessageEvent| {
+                let event = Array::from(&event.data());
+                let kind = event.get(0).as_string().unwrap_or_default();
+                let name = event.get(1).as_string().unwrap_or_default();
+                let mut state = state.borrow_mut();
+                match kind.as_str() {
+                    QUERY => {
+                        for name in state.listening.keys() {
+                            post(&channel, CONNECT, name, None);
+                        }
+                    }
+                    CONNECT => {
+                        state.remote.insert(name);
+                    }
+                    CLOSE => {
+                        state.remote.remove(&name);
+                    }
+                    MESSAGE => {
+                        if let Some(sender) = state.listening.get(&name) {
+                            let data = Uint8Array::new(&event.get(2)).to_vec();
+                            let _ = sender.send(LocalConnectionMessage { name, data });
+                        }
+                    }
+                    _ => {}
+                }
+            })
+        };
+        channel.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        post(&channel, QUERY, "", None);
+
+        Ok(Self {
+            channel,
+            state,
+            _on_message: on_message,
+        })
+    }
+
+    /// Starts listening with a name, returning `false` if another player already is.
+    pub fn listen(&self, name: &str, sender: Sender<LocalConnectionMessage>) -> bool {
+        let mut state = self.state.borrow_mut();
+        if state.remote.contains(name) {
+            return false;
+        }
+        state.listening.insert(name.to_string(), sender);
+        post(&self.channel, CONNECT, name, None);
+        true
+    }
+
+    pub fn close(&self, name: &str) {
+        if self.state.borrow_mut().listening.remove(name).is_some() {
+            post(&self.channel, CLOSE, name, None);
+        }
+    }
+
+    /// Sends a message to the player that listens with a name, returning `false` if there's none.
+    pub fn send(&self, name: &str, data: &[u8]) -> bool {
+        if !self.state.borrow().remote.contains(name) {
+            return false;
+        }
+        post(&self.channel, MESSAGE, name, Some(data));
+        true
+    }
+}
+
+impl Drop for LocalConnectionChannel {
+    fn drop(&mut self) {
+        for name in self.state.borrow().listening.keys() {
+            post(&self.channel, CLOSE, name, None);
+        }
+        self.channel.close();
+    }
+}
+
+fn post(channel: &BroadcastChannel, kind: &str, name: &str, data: Option<&[u8]>) {
+    let message = Array::of2(&kind.into(), &name.into());
+    if let Some(data) = data {
+        message.push(&Uint8Array::from(data));
+    }
+    if let Err(e) = channel.post_message(&message) {
+        tracing::error!("Failed to post LocalConnection message: {:?}", e);
+    }
+}
@@ -1,34 +1,61 @@
 //! Navigator backend for web
-use js_sys::{Array, ArrayBuffer, Uint8Array};
+use crate::local_connection::LocalConnectionChannel;
+use js_sys::{Array, ArrayBuffer, Promise, Reflect, Uint8Array};
 use ruffle_core::backend::navigator::{
-    async_return, create_fetch_error, create_specific_fetch_error, ErrorResponse, NavigationMethod,
-    NavigatorBackend, OpenURLMode, OwnedFuture, Request, SuccessResponse,
+    async_return, create_fetch_error, create_specific_fetch_error, ErrorResponse,
+    JavascriptUrlMode, NavigationMethod, NavigationPolicy, NavigatorBackend, NetworkInterface,
+    OpenURLMode, OwnedFuture, Request, ResponseBody, StreamingResponse, SuccessResponse,
 };
 use ruffle_core::config::NetworkingAccessMode;
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
-use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
-use std::sync::mpsc::{Receiver, Sender};
+use ruffle_core::local_connection::LocalConnectionMessage;
+use ruffle_core::socket::{
+    ConnectionState, Datagram, DatagramSocketHandle, ServerSocketHandle, SocketAction,
+    SocketHandle, SocketKind,
+};
+use serde::Deserialize;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::mpsc::{Receiver, Sender, TryRecvError};
 use std::sync::Arc;
 use std::time::Duration;
 use tracing_subscriber::layer::Layered;
 use tracing_subscriber::Registry;
 use tracing_wasm::WASMLayer;
 use url::{ParseError, Url};
+use wasm_bindgen::closure::Closure;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::{spawn_local, JsFuture};
 use web_sys::{
-    window, Blob, BlobPropertyBag, HtmlFormElement, HtmlInputElement, Request as WebRequest,
-    RequestInit, Response as WebResponse,
+    window, BinaryType, Blob, BlobPropertyBag, HtmlFormElement, HtmlInputElement, MessageEvent,
+    ReadableStreamDefaultReader, Request as WebRequest, RequestCredentials, RequestInit,
+    RequestRedirect, Response as WebResponse, WebSocket,
 };
 
+/// A WebSocket proxy that relays the data of socket connections to a host and port.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SocketProxy {
+    host: String,
+    port: u16,
+    proxy_url: String,
+}
+
+/// How often the connection of a socket is checked for data to send.
+const SOCKET_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 pub struct WebNavigatorBackend {
     log_subscriber: Arc<Layered<WASMLayer, Registry>>,
     allow_script_access: bool,
     allow_networking: NetworkingAccessMode,
     upgrade_to_https: bool,
     base_url: Option<Url>,
-    open_url_mode: OpenURLMode,
+    navigation_policy: NavigationPolicy,
+    socket_proxies: Vec<SocketProxy>,
+
+    /// Connects `LocalConnection`s to players on other pages, if the browser supports it.
+    local_connections: Option<LocalConnectionChannel>,
 }
 
 impl WebNavigatorBackend {
@@ -38,7 +65,8 @@ impl WebNavigatorBackend {
         upgrade_to_https: bool,
         base_url: Option<String>,
         log_subscriber: Arc<Layered<WASMLayer, Registry>>,
-        open_url_mode: OpenURLMode,
+        navigation_policy: NavigationPolicy,
+        socket_proxies: Vec<SocketProxy>,
     ) -> Self {
         let window = web_sys::window().expect("window()");
 
@@ -73,15 +101,155 @@ impl WebNavigatorBackend {
             tracing::error!("Could not get base URL for base directory inference.");
         }
 
+        let local_connections = LocalConnectionChannel::new()
+            .map_err(|e| tracing::warn!("LocalConnection is limited to this player: {:?}", e))
+            .ok();
+
         Self {
             allow_script_access,
             allow_networking,
             upgrade_to_https,
             base_url,
             log_subscriber,
-            open_url_mode,
+            navigation_policy,
+            socket_proxies,
+            local_connections,
+        }
+    }
+}
+
+/// Waits for the given duration.
+async fn sleep(duration: Duration) {
+    let promise = Promise::new(&mut |resolve, _reject| {
+        if let Some(window) = window() {
+            let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+                &resolve,
+                duration.as_millis() as i32,
+            );
+        }
+    });
+    let _ = JsFuture::from(promise).await;
+}
+
+/// Send a request with the Fetch API, and return the response once it has been
+/// received successfully.
+async fn send_request(request: Request, url: Url) -> Result<WebResponse, ErrorResponse> {
+    let mut init = RequestInit::new();
+
+    init.method(&request.method().to_string());
+
+    // The browser manages the cookie jar, we can only choose not to use it.
+    if !request.manage_cookies() {
+        init.credentials(RequestCredentials::Omit);
+    }
+
+    // Browsers don't expose the response of a redirect, so a request that
+    // doesn't follow redirects fails with an opaque response instead.
+    if !request.follow_redirects() {
+        init.redirect(RequestRedirect::Manual);
+    }
+
+    if let Some((data, mime)) = request.body() {
+        let blob = Blob::new_with_buffer_source_sequence_and_options(
+            &Array::from_iter([Uint8Array::from(data.as_slice()).buffer()]),
+            BlobPropertyBag::new().type_(mime),
+        )
+        .map_err(|_| ErrorResponse {
+            url: url.to_string(),
+            error: Error::FetchError("Got JS error".to_string()),
+        })?
+        .dyn_into()
+        .map_err(|_| ErrorResponse {
+            url: url.to_string(),
+            error: Error::FetchError("Got JS error".to_string()),
+        })?;
+
+        init.body(Some(&blob));
+    }
+
+    let web_request =
+        WebRequest::new_with_str_and_init(url.as_str(), &init).map_err(|_| ErrorResponse {
+            url: url.to_string(),
+            error: Error::FetchError(format!("Unable to create request for {url}")),
+        })?;
+
+    let headers = web_request.headers();
+
+    for (header_name, header_val) in request.headers() {
+        headers
+            .set(header_name, header_val)
+            .map_err(|_| ErrorResponse {
+                url: url.to_string(),
+                error: Error::FetchError("Got JS error".to_string()),
+            })?;
+    }
+
+    let window = web_sys::window().expect("window()");
+    let fetchval = JsFuture::from(window.fetch_with_request(&web_request))
+        .await
+        .map_err(|_| ErrorResponse {
+            url: url.to_string(),
+            error: Error::FetchError("Got JS error".to_string()),
+        })?;
+
+    let response: WebResponse = fetchval.dyn_into().map_err(|_| ErrorResponse {
+        url: url.to_string(),
+        error: Error::FetchError("Fetch result wasn't a WebResponse".to_string()),
+    })?;
+    let url = response.url();
+    let status = response.status();
+    let redirected = response.redirected();
+    if !response.ok() {
+        let error = Error::HttpNotOk(
+            format!("HTTP status is not ok, got {}", response.status_text()),
+            status,
+            redirected,
+        );
+        return Err(ErrorResponse { url, error });
+    }
+
+    Ok(response)
+}
+
+/// The headers of a response, as (header_name, header_value) pairs.
+fn response_headers(response: &WebResponse) -> Vec<(String, String)> {
+    let mut headers = Vec::new();
+    if let Ok(Some(entries)) = js_sys::try_iter(&response.headers()) {
+        for entry in entries.flatten() {
+            let entry = Array::from(&entry);
+            if let (Some(name), Some(value)) = (entry.get(0).as_string(), entry.get(1).as_string())
+            {
+                headers.push((name, value));
+            }
         }
     }
+    headers
+}
+
+/// The body of a response that is still being received, read from its `ReadableStream`.
+///
+/// A response without a body has no reader.
+struct StreamResponseBody(Option<ReadableStreamDefaultReader>);
+
+impl ResponseBody for StreamResponseBody {
+    fn next_chunk(&mut self) -> Pin<Box<dyn Future<Output = Result<Option<Vec<u8>>, Error>> + '_>> {
+        Box::pin(async move {
+            let Some(reader) = &self.0 else {
+                return Ok(None);
+            };
+            let result = JsFuture::from(reader.read())
+                .await
+                .map_err(|_| Error::FetchError("Got JS error".to_string()))?;
+            let done = Reflect::get(&result, &"done".into())
+                .map_err(|_| Error::FetchError("Got JS error".to_string()))?;
+            if done.is_truthy() {
+                return Ok(None);
+            }
+            let value = Reflect::get(&result, &"value".into())
+                .map_err(|_| Error::FetchError("Got JS error".to_string()))?;
+            Ok(Some(Uint8Array::new(&value).to_vec()))
+        })
+    }
 }
 
 impl NavigatorBackend for WebNavigatorBackend {
@@ -143,22 +311,31 @@ impl NavigatorBackend for WebNavigatorBackend {
 
         let window = window().expect("window()");
 
-        if url.scheme() != "javascript" {
-            if self.open_url_mode == OpenURLMode::Confirm {
-                let message = format!("The SWF file wants to open the website {}", &url);
-                // TODO: Add a checkbox with a GUI toolkit
-                let confirm = window
-                    .confirm_with_message(&message)
-                    .expect("confirm_with_message()");
-                if !confirm {
-                    tracing::info!(
-                        "SWF tried to open a website, but the user declined the request"
-                    );
+        if url.scheme() == "javascript" {
+            if self.navigation_policy.javascript == JavascriptUrlMode::Deny {
+                tracing::warn!("SWF tried to run a script, but javascript: URLs are denied");
+                return;
+            }
+        } else {
+            match self.navigation_policy.check(&url) {
+                OpenURLMode::Allow => {}
+                OpenURLMode::Confirm => {
+                    let message = format!("The SWF file wants to open the website {}", &url);
+                    // TODO: Add a checkbox with a GUI toolkit
+                    let confirm = window
+                        .confirm_with_message(&message)
+                        .expect("confirm_with_message()");
+                    if !confirm {
+                        tracing::info!(
+                            "SWF tried to open a website, but the user declined the request"
+                        );
+                        return;
+                    }
+                }
+                OpenURLMode::Deny => {
+                    tracing::warn!("SWF tried to open the website {url}, but it's not allowed");
                     return;
                 }
-            } else if self.open_url_mode == OpenURLMode::Deny {
-                tracing::warn!("SWF tried to open a website, but opening a website is not allowed");
-                return;
             }
             // If the user confirmed or if in `Allow` mode, open the website.
         }
@@ -231,73 +408,11 @@ impl NavigatorBackend for WebNavigatorBackend {
         };
 
         Box::pin(async move {
-            let mut init = RequestInit::new();
-
-            init.method(&request.method().to_string());
-
-            if let Some((data, mime)) = request.body() {
-                let blob = Blob::new_with_buffer_source_sequence_and_options(
-                    &Array::from_iter([Uint8Array::from(data.as_slice()).buffer()]),
-                    BlobPropertyBag::new().type_(mime),
-                )
-                .map_err(|_| ErrorResponse {
-                    url: url.to_string(),
-                    error: Error::FetchError("Got JS error".to_string()),
-                })?
-                .dyn_into()
-                .map_err(|_| ErrorResponse {
-                    url: url.to_string(),
-                    error: Error::FetchError("Got JS error".to_string()),
-                })?;
-
-                init.body(Some(&blob));
-            }
-
-            let web_request = match WebRequest::new_with_str_and_init(url.as_str(), &init) {
-                Ok(web_request) => web_request,
-                Err(_) => {
-                    return create_specific_fetch_error(
-                        "Unable to create request for",
-                        url.as_str(),
-                        "",
-                    )
-                }
-            };
-
-            let headers = web_request.headers();
-
-            for (header_name, header_val) in request.headers() {
-                headers
-                    .set(header_name, header_val)
-                    .map_err(|_| ErrorResponse {
-                        url: url.to_string(),
-                        error: Error::FetchError("Got JS error".to_string()),
-                    })?;
-            }
-
-            let window = web_sys::window().expect("window()");
-            let fetchval = JsFuture::from(window.fetch_with_request(&web_request))
-                .await
-                .map_err(|_| ErrorResponse {
-                    url: url.to_string(),
-                    error: Error::FetchError("Got JS error".to_string()),
-                })?;
-
-            let response: WebResponse = fetchval.dyn_into().map_err(|_| ErrorResponse {
-                url: url.to_string(),
-                error: Error::FetchError("Fetch result wasn't a WebResponse".to_string()),
-            })?;
+            let response = send_request(request, url).await?;
             let url = response.url();
             let status = response.status();
             let redirected = response.redirected();
-            if !response.ok() {
-                let error = Error::HttpNotOk(
-                    format!("HTTP status is not ok, got {}", response.status_text()),
-                    status,
-                    redirected,
-                );
-                return Err(ErrorResponse { url, error });
-            }
+            let headers = response_headers(&response);
 
             let body: ArrayBuffer = JsFuture::from(response.array_buffer().map_err(|_| {
                 ErrorResponse {
@@ -324,6 +439,41 @@ impl NavigatorBackend for WebNavigatorBackend {
                 body,
                 status,
                 redirected,
+                headers,
+            })
+        })
+    }
+
+    fn fetch_stream(&self, request: Request) -> OwnedFuture<StreamingResponse, ErrorResponse> {
+        let url = match self.resolve_url(request.url()) {
+            Ok(url) if url.scheme() != "file" => url,
+            // Let `fetch` report why this URL can't be fetched.
+            _ => {
+                let fetch = self.fetch(request);
+                return Box::pin(async move { Ok(fetch.await?.into()) });
+            }
+        };
+
+        Box::pin(async move {
+            let response = send_request(request, url).await?;
+            let expected_length = response
+                .headers()
+                .get("Content-Length")
+                .ok()
+                .flatten()
+                .and_then(|length| length.parse().ok());
+            let reader = response.body().map(|body| {
+                body.get_reader()
+                    .unchecked_into::<ReadableStreamDefaultReader>()
+            });
+
+            Ok(StreamingResponse {
+                url: response.url(),
+                status: response.status(),
+                redirected: response.redirected(),
+                headers: response_headers(&response),
+                expected_length,
+                body: Box::new(StreamResponseBody(reader)),
             })
         })
     }
@@ -352,6 +502,10 @@ impl NavigatorBackend for WebNavigatorBackend {
         })
     }
 
+    fn javascript_url_mode(&self) -> JavascriptUrlMode {
+        self.navigation_policy.javascript
+    }
+
     fn pre_process_url(&self, mut url: Url) -> Url {
         if self.upgrade_to_https && url.scheme() == "http" && url.set_scheme("https").is_err() {
             tracing::error!("Url::set_scheme failed on: {}", url);
@@ -361,16 +515,171 @@ impl NavigatorBackend for WebNavigatorBackend {
 
     fn connect_socket(
         &mut self,
-        _host: String,
-        _port: u16,
-        _timeout: Duration,
+        host: String,
+        port: u16,
+        timeout: Duration,
         handle: SocketHandle,
-        _receiver: Receiver<Vec<u8>>,
+        receiver: Receiver<Vec<u8>>,
         sender: Sender<SocketAction>,
+        // RTMPS is relayed like any other connection, with the proxy handling TLS.
+        _kind: SocketKind,
     ) {
-        // FIXME: Add way to call out to JS code.
-        sender
-            .send(SocketAction::Connect(handle, ConnectionState::Failed))
-            .expect("working channel send");
+        // Browsers can't open TCP connections, so they're relayed by a WebSocket proxy.
+        let Some(proxy) = self
+            .socket_proxies
+            .iter()
+            .find(|proxy| proxy.host == host && proxy.port == port)
+        else {
+            tracing::warn!("Missing WebSocket proxy for host {}, port {}", host, port);
+            sender
+                .send(SocketAction::Connect(handle, ConnectionState::Failed))
+                .expect("working channel send");
+            return;
+        };
+
+        let ws = match WebSocket::new(&proxy.proxy_url) {
+            Ok(ws) => ws,
+            Err(e) => {
+                tracing::error!("Failed to create WebSocket, reason {:?}", e);
+                sender
+                    .send(SocketAction::Connect(handle, ConnectionState::Failed))
+                    .expect("working channel send");
+                return;
+            }
+        };
+        ws.set_binary_type(BinaryType::Arraybuffer);
+
+        let on_open = {
+            let sender = sender.clone();
+            Closure::<dyn FnMut()>::new(move || {
+                let _ = sender.send(SocketAction::Connect(handle, ConnectionState::Connected));
+            })
+        };
+        let on_message = {
+            let sender = sender.clone();
+            Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+                if let Ok(buffer) = event.data().dyn_into::<ArrayBuffer>() {
+                    let data = Uint8Array::new(&buffer).to_vec();
+                    let _ = sender.send(SocketAction::Data(handle, data));
+                }
+            })
+        };
+        ws.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        ws.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+
+        self.spawn_future(Box::pin(async move {
+            let mut was_open = false;
+            let mut waited = Duration::ZERO;
+            let mut pending_write = vec![];
+
+            loop {
+                let mut is_closed_by_movie = false;
+                loop {
+                    match receiver.try_recv() {
+                        Ok(data) => pending_write.extend(data),
+                        Err(TryRecvError::Disconnected) => {
+                            //NOTE: Channel sender has been dropped.
+                            //      This means we have to close the connection.
+                            is_closed_by_movie = true;
+                            break;
+                        }
+                        Err(TryRecvError::Empty) => break,
+                    }
+                }
+                if is_closed_by_movie {
+                    let _ = ws.close();
+                    break;
+                }
+
+                match ws.ready_state() {
+                    WebSocket::CONNECTING if waited >= timeout => {
+                        let _ =
+                            sender.send(SocketAction::Connect(handle, ConnectionState::TimedOut));
+                        let _ = ws.close();
+                        break;
+                    }
+                    WebSocket::OPEN => {
+                        was_open = true;
+                        if !pending_write.is_empty() {
+                            if let Err(e) = ws.send_with_u8_array(&pending_write) {
+                                tracing::error!("Failed to send data to WebSocket, reason {:?}", e);
+                            }
+                            pending_write.clear();
+                        }
+                    }
+                    WebSocket::CLOSING | WebSocket::CLOSED => {
+                        // The close event has already been fired by now, so any data that was
+                        // received has been sent to the player.
+                        let action = if was_open {
+                            SocketAction::Close(handle)
+                        } else {
+                            SocketAction::Connect(handle, ConnectionState::Failed)
+                        };
+                        let _ = sender.send(action);
+                        break;
+                    }
+                    _ => {}
+                }
+
+                sleep(SOCKET_POLL_INTERVAL).await;
+                waited += SOCKET_POLL_INTERVAL;
+            }
+
+            // The callbacks are dropped now, so they must not be called anymore.
+            ws.set_onopen(None);
+            ws.set_onmessage(None);
+            drop((on_open, on_message));
+            Ok(())
+        }));
+    }
+
+    fn bind_server_socket(
+        &mut self,
+        _address: String,
+        _port: u16,
+        _handle: ServerSocketHandle,
+        _receiver: Receiver<()>,
+        _sender: Sender<SocketAction>,
+    ) -> Result<(String, u16), String> {
+        Err("Server sockets are not supported in browsers".to_string())
+    }
+
+    fn bind_datagram_socket(
+        &mut self,
+        _address: String,
+        _port: u16,
+        _handle: DatagramSocketHandle,
+        _receiver: Receiver<Datagram>,
+        _sender: Sender<SocketAction>,
+    ) -> Result<(String, u16), String> {
+        Err("Datagram sockets are not supported in browsers".to_string())
+    }
+
+    fn network_interfaces(&self) -> Vec<NetworkInterface> {
+        vec![]
+    }
+
+    fn listen_local_connection(
+        &mut self,
+        name: &str,
+        sender: Sender<LocalConnectionMessage>,
+    ) -> bool {
+        match &self.local_connections {
+            Some(local_connections) => local_connections.listen(name, sender),
+            None => true,
+        }
+    }
+
+    fn close_local_connection(&mut self, name: &str) {
+        if let Some(local_connections) = &self.local_connections {
+            local_connections.close(name);
+        }
+    }
+
+    fn send_local_connection(&mut self, name: &str, data: Vec<u8>) -> bool {
+        match &self.local_connections {
+            Some(local_connections) => local_connections.send(name, &data),
+            None => false,
+        }
     }
 }
@@ -1,11 +1,130 @@
 use super::JavascriptPlayer;
+use crate::audio::stop_tracks;
+use chrono::{TimeZone, Utc};
+use js_sys::{Array, Function, Promise, Reflect, Uint8Array};
+use ruffle_core::accessibility::{AccessibilityNode, AccessibilityRole};
 use ruffle_core::backend::ui::{
-    FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+    CameraState, CustomMouseCursor, DialogResultFuture, FileFilter, FontDefinition,
+    FullscreenError, LanguageIdentifier, MouseCursor, SelectedFile, UiBackend, US_ENGLISH,
 };
+use ruffle_core::printing::{self, PrintPage};
+use ruffle_render::bitmap::{Bitmap, BitmapFormat};
 use ruffle_web_common::JsResult;
 use std::borrow::Cow;
-use wasm_bindgen::JsCast;
-use web_sys::{HtmlCanvasElement, HtmlDocument, HtmlTextAreaElement};
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use wasm_bindgen::{Clamped, JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    Blob, BlobPropertyBag, CanvasRenderingContext2d, Document, Element, File, HtmlAnchorElement,
+    HtmlCanvasElement, HtmlDocument, HtmlInputElement, HtmlTextAreaElement, HtmlVideoElement,
+    ImageData, MediaStream, MediaStreamConstraints, Url,
+};
+
+/// A font installed on the system, as reported by the Local Font Access API.
+struct LocalFont {
+    /// The JS `FontData` object, used to load the font's contents.
+    font_data: JsValue,
+    postscript_name: String,
+    is_bold: bool,
+    is_italic: bool,
+}
+
+/// The fonts installed on the system.
+///
+/// The Local Font Access API is asynchronous, so fonts are unavailable until they've been
+/// loaded. The contents of each font are only loaded once it's first requested.
+#[derive(Default)]
+struct LocalFonts {
+    /// Every installed font, by family name.
+    fonts: HashMap<String, Vec<LocalFont>>,
+
+    /// The contents of the fonts that have been loaded, by PostScript name.
+    data: HashMap<String, Vec<u8>>,
+
+    /// The PostScript names of the fonts that have been requested.
+    requested: HashSet<String>,
+}
+
+impl LocalFonts {
+    /// Starts querying the fonts installed on the system.
+    ///
+    /// This only succeeds in browsers that support `window.queryLocalFonts()`, once the user
+    /// has granted permission to use local fonts.
+    fn query(local_fonts: Rc<RefCell<Self>>) {
+        let window = web_sys::window().expect("window()");
+        let Some(query) = Reflect::get(&window, &"queryLocalFonts".into())
+            .ok()
+            .and_then(|query| query.dyn_into::<Function>().ok())
+        else {
+            return;
+        };
+        let Some(promise) = query
+            .call0(&window)
+            .ok()
+            .and_then(|promise| promise.dyn_into::<Promise>().ok())
+        else {
+            return;
+        };
+
+        spawn_local(async move {
+            let fonts = match JsFuture::from(promise).await {
+                Ok(fonts) => fonts,
+                Err(e) => {
+                    tracing::info!("Couldn't query local fonts: {:?}", e);
+                    return;
+                }
+            };
+
+            let mut local_fonts = local_fonts.borrow_mut();
+            for font_data in Array::from(&fonts).iter() {
+                let property = |name: &str| {
+                    Reflect::get(&font_data, &name.into())
+                        .ok()
+                        .and_then(|value| value.as_string())
+                        .unwrap_or_default()
+                };
+                let family = property("family");
+                let style = property("style");
+                let postscript_name = property("postscriptName");
+                local_fonts
+                    .fonts
+                    .entry(family)
+                    .or_default()
+                    .push(LocalFont {
+                        font_data,
+                        postscript_name,
+                        is_bold: style.contains("Bold"),
+                        is_italic: style.contains("Italic") || style.contains("Oblique"),
+                    });
+            }
+        });
+    }
+
+    /// Starts loading the contents of a font.
+    fn load(local_fonts: Rc<RefCell<Self>>, postscript_name: String, font_data: JsValue) {
+        spawn_local(async move {
+            let data = async {
+                let blob = Reflect::get(&font_data, &"blob".into())?
+                    .dyn_into::<Function>()?
+                    .call0(&font_data)?
+                    .dyn_into::<Promise>()?;
+                let blob: Blob = JsFuture::from(blob).await?.dyn_into()?;
+                let buffer = JsFuture::from(blob.array_buffer()).await?;
+                Ok::<_, JsValue>(Uint8Array::new(&buffer).to_vec())
+            }
+            .await;
+
+            match data {
+                Ok(data) => {
+                    local_fonts.borrow_mut().data.insert(postscript_name, data);
+                }
+                Err(e) => tracing::warn!("Couldn't load local font {}: {:?}", postscript_name, e),
+            }
+        });
+    }
+}
 
 /// An implementation of `UiBackend` utilizing `web_sys` bindings to input APIs.
 pub struct WebUiBackend {
@@ -13,8 +132,15 @@ pub struct WebUiBackend {
     canvas: HtmlCanvasElement,
     cursor_visible: bool,
     cursor: MouseCursor,
+    /// The CSS value of the custom cursor set with `Mouse.cursor`, if there is one.
+    custom_cursor: Option<String>,
     language: LanguageIdentifier,
-    clipboard_content: String,
+    clipboard_content: Rc<RefCell<String>>,
+    local_fonts: Rc<RefCell<LocalFonts>>,
+    webcam: Option<Rc<Webcam>>,
+
+    /// The invisible elements over the canvas that describe the movie to screen readers.
+    accessibility_overlay: Option<Element>,
 }
 
 impl WebUiBackend {
@@ -24,26 +150,74 @@ impl WebUiBackend {
         let language = preferred_language
             .and_then(|l| l.parse().ok())
             .unwrap_or_else(|| US_ENGLISH.clone());
+        let local_fonts = Rc::new(RefCell::new(LocalFonts::default()));
+        LocalFonts::query(local_fonts.clone());
         Self {
             js_player,
             canvas: canvas.clone(),
             cursor_visible: true,
             cursor: MouseCursor::Arrow,
+            custom_cursor: None,
             language,
-            clipboard_content: "".into(),
+            clipboard_content: Default::default(),
+            local_fonts,
+            webcam: None,
+            accessibility_overlay: None,
+        }
+    }
+
+    /// Copies text with `document.execCommand("copy")`, for when the async Clipboard API
+    /// isn't available, as it's only provided in secure contexts (HTTPS).
+    fn copy_with_exec_command(&self, content: &str) {
+        if let Some(element) = self.canvas.parent_element() {
+            let window = web_sys::window().expect("window()");
+            let document: HtmlDocument = window
+                .document()
+                .expect("document()")
+                .dyn_into()
+                .expect("document() didn't give us a document");
+            let textarea: HtmlTextAreaElement = document
+                .create_element("textarea")
+                .expect("create_element() must succeed")
+                .dyn_into()
+                .expect("create_element(\"textarea\") didn't give us a textarea");
+
+            let editing_text = self.js_player.is_virtual_keyboard_focused();
+            textarea.set_value(content);
+            let _ = element.append_child(&textarea);
+            textarea.select();
+
+            match document.exec_command("copy") {
+                Ok(success) => {
+                    if !success {
+                        tracing::warn!(
+                            "Couldn't set clipboard contents: The browser rejected the call"
+                        );
+                    }
+                }
+                Err(e) => tracing::error!("Couldn't set clipboard contents: {:?}", e),
+            }
+
+            let _ = element.remove_child(&textarea);
+            if editing_text {
+                // Return focus to the text area
+                self.js_player.open_virtual_keyboard();
+            }
         }
     }
 
     fn update_mouse_cursor(&self) {
-        let cursor = if self.cursor_visible {
+        let cursor = if !self.cursor_visible {
+            "none"
+        } else if let Some(custom_cursor) = &self.custom_cursor {
+            custom_cursor.as_str()
+        } else {
             match self.cursor {
                 MouseCursor::Arrow => "auto",
                 MouseCursor::Hand => "pointer",
                 MouseCursor::IBeam => "text",
                 MouseCursor::Grab => "grab",
             }
-        } else {
-            "none"
         };
         self.canvas
             .style()
@@ -52,6 +226,76 @@ impl WebUiBackend {
     }
 }
 
+/// Creates an element with ARIA attributes for an accessible object, along with its children.
+fn accessibility_element(
+    document: &Document,
+    node: &AccessibilityNode,
+    device_pixel_ratio: f64,
+) -> Result<Element, JsValue> {
+    let element = document.create_element("div")?;
+    let role = match node.role {
+        AccessibilityRole::Window => "application",
+        AccessibilityRole::Group => "group",
+        AccessibilityRole::Button => "button",
+        AccessibilityRole::StaticText => "note",
+        AccessibilityRole::TextInput => "textbox",
+    };
+    element.set_attribute("role", role)?;
+    if !node.name.is_empty() {
+        element.set_attribute("aria-label", &node.name)?;
+    }
+    if !node.description.is_empty() {
+        element.set_attribute("aria-description", &node.description)?;
+    }
+    if !node.shortcut.is_empty() {
+        element.set_attribute("aria-keyshortcuts", &node.shortcut)?;
+    }
+    if let Some(value) = &node.value {
+        element.set_attribute("aria-readonly", "true")?;
+        element.set_text_content(Some(value));
+    }
+
+    // The element covers the object, so that screen readers can point out where it is.
+    let (x_min, y_min, x_max, y_max) = node.bounds;
+    element.set_attribute(
+        "style",
+        &format!(
+            "position: absolute; left: {}px; top: {}px; width: {}px; height: {}px; color: transparent; overflow: hidden;",
+            x_min / device_pixel_ratio,
+            y_min / device_pixel_ratio,
+            (x_max - x_min) / device_pixel_ratio,
+            (y_max - y_min) / device_pixel_ratio,
+        ),
+    )?;
+
+    for child in &node.children {
+        let child = accessibility_element(document, child, device_pixel_ratio)?;
+        element.append_child(&child)?;
+    }
+    Ok(element)
+}
+
+/// Encodes the image of a custom cursor as a data URL that can be used in CSS.
+fn custom_cursor_url(cursor: &CustomMouseCursor) -> Result<String, JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("No document")?;
+    let canvas: HtmlCanvasElement = document.create_element("canvas")?.dyn_into()?;
+    canvas.set_width(cursor.image.width());
+    canvas.set_height(cursor.image.height());
+    let context: CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or("No 2D canvas context")?
+        .dyn_into()?;
+    let image_data = ImageData::new_with_u8_clamped_array_and_sh(
+        Clamped(cursor.image.data()),
+        cursor.image.width(),
+        cursor.image.height(),
+    )?;
+    context.put_image_data(&image_data, 0.0, 0.0)?;
+    canvas.to_data_url()
+}
+
 impl UiBackend for WebUiBackend {
     fn mouse_visible(&self) -> bool {
         self.cursor_visible
@@ -67,50 +311,45 @@ impl UiBackend for WebUiBackend {
         self.update_mouse_cursor();
     }
 
-    fn clipboard_content(&mut self) -> String {
-        // On web, clipboard content is not directly accessible due to security restrictions,
-        // but pasting from the clipboard is supported via the JS `paste` event
-        self.clipboard_content.to_owned()
+    fn set_custom_mouse_cursor(&mut self, cursor: Option<&CustomMouseCursor>) {
+        self.custom_cursor = cursor.and_then(|cursor| match custom_cursor_url(cursor) {
+            Ok(url) => Some(format!(
+                "url(\"{url}\") {} {}, auto",
+                cursor.hotspot_x, cursor.hotspot_y
+            )),
+            Err(e) => {
+                tracing::error!("Couldn't create custom cursor: {:?}", e);
+                None
+            }
+        });
+        self.update_mouse_cursor();
     }
 
-    fn set_clipboard_content(&mut self, content: String) {
-        self.clipboard_content = content.to_owned();
-        // We use `document.execCommand("copy")` as `navigator.clipboard.writeText("string")`
-        // is available only in secure contexts (HTTPS).
-        if let Some(element) = self.canvas.parent_element() {
-            let window = web_sys::window().expect("window()");
-            let document: HtmlDocument = window
-                .document()
-                .expect("document()")
-                .dyn_into()
-                .expect("document() didn't give us a document");
-            let textarea: HtmlTextAreaElement = document
-                .create_element("textarea")
-                .expect("create_element() must succeed")
-                .dyn_into()
-                .expect("create_element(\"textarea\") didn't give us a textarea");
-
-            let editing_text = self.js_player.is_virtual_keyboard_focused();
-            textarea.set_value(&content);
-            let _ = element.append_child(&textarea);
-            textarea.select();
-
-            match document.exec_command("copy") {
-                Ok(success) => {
-                    if !success {
-                        tracing::warn!(
-                            "Couldn't set clipboard contents: The browser rejected the call"
-                        );
+    fn clipboard_content(&mut self) -> String {
+        // On web, the clipboard can only be read asynchronously, so we return the content
+        // that we last knew of (such as from the JS `paste` event), and refresh it in the background.
+        if let Some(clipboard) = secure_clipboard() {
+            let clipboard_content = self.clipboard_content.clone();
+            spawn_local(async move {
+                if let Ok(text) = JsFuture::from(clipboard.read_text()).await {
+                    if let Some(text) = text.as_string() {
+                        *clipboard_content.borrow_mut() = text;
                     }
                 }
-                Err(e) => tracing::error!("Couldn't set clipboard contents: {:?}", e),
-            }
+            });
+        }
+        self.clipboard_content.borrow().clone()
+    }
 
-            let _ = element.remove_child(&textarea);
-            if editing_text {
-                // Return focus to the text area
-                self.js_player.open_virtual_keyboard();
-            }
+    fn set_clipboard_content(&mut self, content: String) {
+        *self.clipboard_content.borrow_mut() = content.clone();
+        match secure_clipboard() {
+            Some(clipboard) => spawn_local(async move {
+                if let Err(e) = JsFuture::from(clipboard.write_text(&content)).await {
+                    tracing::error!("Couldn't set clipboard contents: {:?}", e);
+                }
+            }),
+            None => self.copy_with_exec_command(&content),
         }
     }
 
@@ -124,6 +363,15 @@ impl UiBackend for WebUiBackend {
         }
     }
 
+    fn screen_size(&self) -> Option<(u32, u32)> {
+        let window = web_sys::window()?;
+        let screen = window.screen().ok()?;
+        let device_pixel_ratio = window.device_pixel_ratio();
+        let width = screen.width().ok()? as f64 * device_pixel_ratio;
+        let height = screen.height().ok()? as f64 * device_pixel_ratio;
+        Some((width.round() as u32, height.round() as u32))
+    }
+
     fn display_root_movie_download_failed_message(&self) {
         self.js_player.display_root_movie_download_failed_message()
     }
@@ -139,4 +387,370 @@ impl UiBackend for WebUiBackend {
     fn language(&self) -> &LanguageIdentifier {
         &self.language
     }
+
+    fn load_device_font(
+        &self,
+        name: &str,
+        is_bold: bool,
+        is_italic: bool,
+    ) -> Option<FontDefinition> {
+        let mut local_fonts = self.local_fonts.borrow_mut();
+        let font = local_fonts.fonts.get(name)?.iter().min_by_key(|font| {
+            u8::from(font.is_bold != is_bold) + u8::from(font.is_italic != is_italic)
+        })?;
+
+        if let Some(data) = local_fonts.data.get(&font.postscript_name) {
+            return Some(FontDefinition {
+                name: name.to_string(),
+                is_bold: font.is_bold,
+                is_italic: font.is_italic,
+                data: data.clone(),
+                index: 0,
+            });
+        }
+
+        let postscript_name = font.postscript_name.clone();
+        let font_data = font.font_data.clone();
+        if local_fonts.requested.insert(postscript_name.clone()) {
+            LocalFonts::load(self.local_fonts.clone(), postscript_name, font_data);
+        }
+        None
+    }
+
+    fn device_font_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.local_fonts.borrow().fonts.keys().cloned().collect();
+        names.sort_unstable();
+        names
+    }
+
+    fn is_accessibility_active(&self) -> bool {
+        // Browsers don't tell whether a screen reader is running.
+        true
+    }
+
+    fn update_accessibility_tree(&mut self, tree: AccessibilityNode) {
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+            return;
+        };
+        let device_pixel_ratio = web_sys::window()
+            .map(|window| window.device_pixel_ratio())
+            .unwrap_or(1.0);
+        let element = match accessibility_element(&document, &tree, device_pixel_ratio) {
+            Ok(element) => element,
+            Err(e) => {
+                tracing::error!("Couldn't create accessibility elements: {:?}", e);
+                return;
+            }
+        };
+
+        // The overlay doesn't take any input, which goes to the canvas below it.
+        element
+            .set_attribute(
+                "style",
+                "position: absolute; left: 0; top: 0; width: 100%; height: 100%; pointer-events: none; overflow: hidden; color: transparent;",
+            )
+            .warn_on_error();
+        if let Some(old) = self.accessibility_overlay.take() {
+            old.replace_with_with_node_1(&element).warn_on_error();
+        } else if let Some(parent) = self.canvas.parent_element() {
+            parent.append_child(&element).warn_on_error();
+        }
+        self.accessibility_overlay = Some(element);
+    }
+
+    fn max_touch_points(&self) -> u32 {
+        web_sys::window()
+            .map(|window| window.navigator().max_touch_points().max(0) as u32)
+            .unwrap_or(0)
+    }
+
+    fn camera_names(&self) -> Vec<String> {
+        // Listing devices is asynchronous and needs permission first,
+        // so we only offer the browser's default camera.
+        let has_media_devices = web_sys::window()
+            .and_then(|window| window.navigator().media_devices().ok())
+            .is_some();
+        if has_media_devices {
+            vec!["Default".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn start_camera(&mut self, _index: usize, width: u32, height: u32, fps: f64) {
+        match Webcam::start(width, height, fps) {
+            Ok(webcam) => self.webcam = Some(webcam),
+            Err(e) => {
+                tracing::error!("Couldn't capture from camera: {:?}", e);
+                self.webcam = None;
+            }
+        }
+    }
+
+    fn stop_camera(&mut self) {
+        self.webcam = None;
+    }
+
+    fn camera_state(&self) -> CameraState {
+        self.webcam
+            .as_ref()
+            .map_or(CameraState::Unavailable, |webcam| webcam.state.get())
+    }
+
+    fn take_camera_frame(&mut self) -> Option<Bitmap> {
+        self.webcam.as_ref().and_then(|webcam| webcam.take_frame())
+    }
+
+    fn can_print(&self) -> bool {
+        true
+    }
+
+    fn print(&mut self, pages: Vec<PrintPage>) {
+        // Browsers can't print without showing a page, so the pages are downloaded as a PDF
+        // for the user to print themselves.
+        if let Err(e) = download_file("print.pdf", &printing::to_pdf(pages)) {
+            tracing::error!("Couldn't download print job: {:?}", e);
+        }
+    }
+
+    fn display_file_open_dialog(
+        &mut self,
+        filters: Vec<FileFilter>,
+        multiple: bool,
+    ) -> Option<DialogResultFuture<Vec<SelectedFile>>> {
+        let accept: Vec<String> = filters
+            .iter()
+            .flat_map(|filter| filter.extensions.iter())
+            .map(|extension| format!(".{extension}"))
+            .collect();
+        match open_file_picker(&accept.join(","), multiple) {
+            Ok(dialog) => Some(dialog),
+            Err(e) => {
+                tracing::error!("Couldn't show file picker: {:?}", e);
+                None
+            }
+        }
+    }
+
+    fn display_file_save_dialog(
+        &mut self,
+        file_name: String,
+        data: Vec<u8>,
+    ) -> Option<DialogResultFuture<Option<String>>> {
+        // Browsers save downloads without asking where, so the file is saved straight away.
+        if let Err(e) = download_file(&file_name, &data) {
+            tracing::error!("Couldn't save file {}: {:?}", file_name, e);
+            return None;
+        }
+        Some(Box::pin(async move { Some(file_name) }))
+    }
+}
+
+/// Returns the async Clipboard API, which browsers only provide in secure contexts.
+fn secure_clipboard() -> Option<web_sys::Clipboard> {
+    let window = web_sys::window()?;
+    if !window.is_secure_context() {
+        return None;
+    }
+    window.navigator().clipboard()
+}
+
+/// Shows the browser's file picker, through a file input element.
+fn open_file_picker(
+    accept: &str,
+    multiple: bool,
+) -> Result<DialogResultFuture<Vec<SelectedFile>>, JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("No document")?;
+    let input: HtmlInputElement = document.create_element("input")?.dyn_into()?;
+    input.set_type("file");
+    input.set_accept(accept);
+    input.set_multiple(multiple);
+
+    // The picker fires `change` once files are picked, or `cancel` if it's closed without any.
+    let closed = Promise::new(&mut |resolve, _reject| {
+        input.set_onchange(Some(&resolve));
+        let _ = input.add_event_listener_with_callback("cancel", &resolve);
+    });
+    input.click();
+
+    Ok(Box::pin(async move {
+        let _ = JsFuture::from(closed).await;
+        let mut selected = Vec::new();
+        let Some(files) = input.files() else {
+            return selected;
+        };
+        for file in (0..files.length()).filter_map(|i| files.get(i)) {
+            match read_file(&file).await {
+                Ok(contents) => selected.push(SelectedFile {
+                    name: file.name(),
+                    creation_date: None,
+                    modification_date: Utc
+                        .timestamp_millis_opt(file.last_modified() as i64)
+                        .single(),
+                    contents,
+                }),
+                Err(e) => tracing::error!("Couldn't read file {}: {:?}", file.name(), e),
+            }
+        }
+        selected
+    }))
+}
+
+async fn read_file(file: &File) -> Result<Vec<u8>, JsValue> {
+    let buffer = JsFuture::from(file.array_buffer()).await?;
+    Ok(Uint8Array::new(&buffer).to_vec())
+}
+
+/// Makes the browser download data as a file, through a link to it.
+fn download_file(file_name: &str, data: &[u8]) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("No document")?;
+    let blob = Blob::new_with_u8_array_sequence_and_options(
+        &Array::of1(&Uint8Array::from(data)),
+        BlobPropertyBag::new().type_("application/octet-stream"),
+    )?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+    let link: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    link.set_href(&url);
+    link.set_download(file_name);
+    link.click();
+    Url::revoke_object_url(&url)
+}
+
+/// Video being captured from the camera through `getUserMedia`.
+struct Webcam {
+    state: Cell<CameraState>,
+    /// The element that plays the stream, once the user has granted access.
+    video: RefCell<Option<(MediaStream, HtmlVideoElement)>>,
+    /// The canvas that frames are copied out of the video through.
+    canvas: HtmlCanvasElement,
+    context: CanvasRenderingContext2d,
+    /// How many milliseconds apart frames are taken.
+    frame_interval: f64,
+    last_frame_time: Cell<f64>,
+}
+
+impl Webcam {
+    /// Asks the user for access to the camera, and starts capturing once it's granted.
+    fn start(width: u32, height: u32, fps: f64) -> Result<Rc<Self>, JsValue> {
+        let window = web_sys::window().ok_or("No window")?;
+        let document = window.document().ok_or("No document")?;
+
+        let ideal = |value: f64| -> Result<JsValue, JsValue> {
+            let constraint = js_sys::Object::new();
+            Reflect::set(&constraint, &"ideal".into(), &value.into())?;
+            Ok(constraint.into())
+        };
+        let video_constraints = js_sys::Object::new();
+        Reflect::set(&video_constraints, &"width".into(), &ideal(width.into())?)?;
+        Reflect::set(&video_constraints, &"height".into(), &ideal(height.into())?)?;
+        Reflect::set(&video_constraints, &"frameRate".into(), &ideal(fps)?)?;
+        let promise = window
+            .navigator()
+            .media_devices()?
+            .get_user_media_with_constraints(
+                MediaStreamConstraints::new().video(&video_constraints),
+            )?;
+
+        let canvas: HtmlCanvasElement = document.create_element("canvas")?.dyn_into()?;
+        let context: CanvasRenderingContext2d = canvas
+            .get_context("2d")?
+            .ok_or("No 2D canvas context")?
+            .dyn_into()?;
+
+        let webcam = Rc::new(Self {
+            state: Cell::new(CameraState::Pending),
+            video: RefCell::new(None),
+            canvas,
+            context,
+            frame_interval: 1000.0 / fps.max(1.0),
+            last_frame_time: Cell::new(f64::NEG_INFINITY),
+        });
+
+        // Hold a weak reference, so that stopping the capture while the
+        // browser's permission prompt is open doesn't leave it running.
+        let weak = Rc::downgrade(&webcam);
+        spawn_local(async move {
+            let result = JsFuture::from(promise).await;
+            let Some(webcam) = weak.upgrade() else {
+                if let Ok(stream) = result {
+                    stop_tracks(&stream.unchecked_into());
+                }
+                return;
+            };
+            match result {
+                Ok(stream) => {
+                    if let Err(e) = webcam.capture(document, stream.unchecked_into()) {
+                        tracing::error!("Couldn't capture from camera: {:?}", e);
+                        webcam.state.set(CameraState::Unavailable);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("Camera access was denied: {:?}", e);
+                    webcam.state.set(CameraState::Denied);
+                }
+            }
+        });
+
+        Ok(webcam)
+    }
+
+    fn capture(&self, document: web_sys::Document, stream: MediaStream) -> Result<(), JsValue> {
+        let video: HtmlVideoElement = document.create_element("video")?.dyn_into()?;
+        video.set_muted(true);
+        video.set_src_object(Some(&stream));
+        let _ = video.play()?;
+
+        *self.video.borrow_mut() = Some((stream, video));
+        self.state.set(CameraState::Allowed);
+        Ok(())
+    }
+
+    /// Copies the current picture of the video, if it's time for a new frame.
+    fn take_frame(&self) -> Option<Bitmap> {
+        let now = js_sys::Date::now();
+        if now - self.last_frame_time.get() < self.frame_interval {
+            return None;
+        }
+
+        let video = self.video.borrow();
+        let (_, video) = video.as_ref()?;
+        let (width, height) = (video.video_width(), video.video_height());
+        // Nothing has been decoded until the video knows its size.
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        if self.canvas.width() != width || self.canvas.height() != height {
+            self.canvas.set_width(width);
+            self.canvas.set_height(height);
+        }
+        self.context
+            .draw_image_with_html_video_element(video, 0.0, 0.0)
+            .ok()?;
+        let image_data = self
+            .context
+            .get_image_data(0.0, 0.0, width.into(), height.into())
+            .ok()?;
+
+        self.last_frame_time.set(now);
+        Some(Bitmap::new(
+            width,
+            height,
+            BitmapFormat::Rgba,
+            image_data.data().0,
+        ))
+    }
+}
+
+impl Drop for Webcam {
+    fn drop(&mut self) {
+        if let Some((stream, video)) = self.video.take() {
+            video.set_src_object(None);
+            stop_tracks(&stream);
+        }
+    }
 }